@@ -176,6 +176,7 @@ fn make_event_envelope() -> Envelope {
     Envelope::Event {
         ref_id: "run-bench-001".into(),
         event: make_agent_event(),
+        seq: None,
     }
 }
 
@@ -136,7 +136,9 @@ fn medium_config() -> BackplaneConfig {
         bind_address: Some("127.0.0.1".into()),
         port: Some(8080),
         policy_profiles: vec!["default".into(), "strict".into()],
+        policy_presets: Vec::new(),
         backends,
+        rbac: None,
     }
 }
 
@@ -161,7 +163,9 @@ fn large_config() -> BackplaneConfig {
         bind_address: Some("0.0.0.0".into()),
         port: Some(9090),
         policy_profiles: (0..20).map(|i| format!("profile_{i}")).collect(),
+        policy_presets: Vec::new(),
         backends,
+        rbac: None,
     }
 }
 
@@ -557,6 +557,7 @@ fn build_jsonl_stream(event_count: usize) -> Vec<u8> {
                 },
                 ext: None,
             },
+            seq: None,
         };
         lines.push(JsonlCodec::encode(&ev).unwrap());
     }
@@ -92,6 +92,7 @@ fn bench_parse_stream(c: &mut Criterion) {
                     },
                     ext: None,
                 },
+                seq: None,
             };
             jsonl.push_str(&JsonlCodec::encode(&env).unwrap());
         }
@@ -128,6 +129,7 @@ fn bench_parse_mixed_stream(c: &mut Criterion) {
                     },
                     ext: None,
                 },
+                seq: None,
             };
             jsonl.push_str(&JsonlCodec::encode(&env).unwrap());
         }
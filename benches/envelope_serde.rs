@@ -45,6 +45,7 @@ fn event_envelope() -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -144,6 +145,7 @@ fn bench_decode_stream(c: &mut Criterion) {
                     },
                     ext: None,
                 },
+                seq: None,
             };
             jsonl.push_str(&JsonlCodec::encode(&env).unwrap());
         }
@@ -176,6 +178,7 @@ fn bench_encode_to_writer(c: &mut Criterion) {
                     },
                     ext: None,
                 },
+                seq: None,
             })
             .collect();
 
@@ -50,6 +50,7 @@ fn event_envelope(i: usize) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
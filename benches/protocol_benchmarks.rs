@@ -50,6 +50,7 @@ fn event_envelope(i: usize) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -66,6 +67,7 @@ fn tool_event_envelope(i: usize) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
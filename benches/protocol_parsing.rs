@@ -46,6 +46,7 @@ fn event_envelope_delta(text: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -62,6 +63,7 @@ fn event_envelope_tool_call(i: usize) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -6,15 +6,20 @@
 pub mod health;
 pub mod metadata;
 pub mod metrics;
+pub mod models;
 pub mod registry;
 pub mod selection;
+pub mod vendor;
 
 pub use health::{BackendHealth, HealthStatus};
 pub use metadata::{BackendMetadata, RateLimit};
 pub use metrics::BackendMetrics;
+pub use models::ModelInfo;
 pub use registry::BackendRegistry;
 pub use selection::{SelectionStrategy, select_backend};
+pub use vendor::{VendorConfig, VendorConfigError, VendorNamespace};
 
+use abp_core::ir::{IrConversation, IrToolChoice, IrToolDefinition};
 use abp_core::{
     AgentEvent, CapabilityManifest, CapabilityRequirement, CapabilityRequirements, ExecutionMode,
     WorkOrder,
@@ -41,6 +46,24 @@ pub trait Backend: Send + Sync {
         work_order: WorkOrder,
         events_tx: mpsc::Sender<AgentEvent>,
     ) -> Result<abp_core::Receipt>;
+
+    /// Models this backend can serve right now.
+    ///
+    /// Backends that discover models at runtime (e.g. whatever is pulled
+    /// into a local Ollama) override this. The default advertises nothing,
+    /// which callers treat as "no restriction".
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        Ok(Vec::new())
+    }
+
+    /// The `config.vendor` namespace this backend reads, if any.
+    ///
+    /// The runtime validates that namespace before dispatch and removes
+    /// every other backend's namespace from the work order it hands over.
+    /// The default reads none, and sees `config.vendor` unchanged.
+    fn vendor_namespace(&self) -> Option<VendorNamespace> {
+        None
+    }
 }
 
 /// Extended backend trait with lifecycle hooks.
@@ -109,6 +132,117 @@ pub fn extract_execution_mode(work_order: &WorkOrder) -> ExecutionMode {
     ExecutionMode::default()
 }
 
+/// Extracts the deterministic sampling seed from a work order's vendor config.
+///
+/// Checks `config.vendor["abp"]["seed"]` first, then `config.vendor["abp.seed"]`.
+/// Backends that advertise [`Capability::SeedDeterminism`](abp_core::Capability::SeedDeterminism)
+/// should forward this seed to their model so repeated runs can be replayed.
+#[must_use]
+pub fn extract_seed(work_order: &WorkOrder) -> Option<u64> {
+    let vendor = &work_order.config.vendor;
+    vendor
+        .get("abp")
+        .and_then(|v| v.get("seed"))
+        .or_else(|| vendor.get("abp.seed"))
+        .and_then(serde_json::Value::as_u64)
+}
+
+/// Extracts the tools offered to the model from a work order's vendor config.
+///
+/// Checks `config.vendor["abp"]["tools"]` first, then `config.vendor["abp.tools"]`.
+/// Returns an empty list when no (valid) tool definitions are present.
+#[must_use]
+pub fn extract_tools(work_order: &WorkOrder) -> Vec<IrToolDefinition> {
+    abp_vendor_value(work_order, "tools")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Extracts the tool-choice policy from a work order's vendor config.
+///
+/// Checks `config.vendor["abp"]["tool_choice"]` first, then
+/// `config.vendor["abp.tool_choice"]`.
+#[must_use]
+pub fn extract_tool_choice(work_order: &WorkOrder) -> Option<IrToolChoice> {
+    abp_vendor_value(work_order, "tool_choice").and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Extracts the conversation history from a work order's vendor config.
+///
+/// Checks `config.vendor["abp"]["conversation"]` first, then
+/// `config.vendor["abp.conversation"]`. Returns `None` when no (valid)
+/// conversation is attached; backends then fall back to the task text.
+#[must_use]
+pub fn extract_conversation(work_order: &WorkOrder) -> Option<IrConversation> {
+    abp_vendor_value(work_order, "conversation")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Extracts the language the model must answer in from a work order's
+/// vendor config, as a normalized BCP 47 tag (see
+/// [`normalize_language_tag`]).
+///
+/// Reads `config.response_language`. When that is unset, falls back to
+/// `response_language`, then its `locale` alias, each under
+/// `config.vendor["abp"]` first and then as a flat `abp.`-prefixed key, as
+/// set by `--param abp.response_language=...`.
+/// Returns `None` when no (valid) language is requested.
+#[must_use]
+pub fn extract_response_language(work_order: &WorkOrder) -> Option<String> {
+    match &work_order.config.response_language {
+        Some(tag) => normalize_language_tag(tag),
+        None => ["response_language", "locale"]
+            .into_iter()
+            .find_map(|key| abp_vendor_value(work_order, key))
+            .and_then(serde_json::Value::as_str)
+            .and_then(normalize_language_tag),
+    }
+}
+
+/// Normalizes a BCP 47 language tag such as `pt_br` or `ZH-hant-tw` to its
+/// canonical casing (`pt-BR`, `zh-Hant-TW`).
+///
+/// Underscores are accepted as separators. Returns `None` unless the tag
+/// starts with a 2–3 letter language subtag and every subtag is 1–8
+/// ASCII alphanumerics.
+#[must_use]
+pub fn normalize_language_tag(tag: &str) -> Option<String> {
+    let subtags: Vec<&str> = tag.trim().split(['-', '_']).collect();
+    let language = subtags[0];
+    if !(2..=3).contains(&language.len()) || !language.bytes().all(|b| b.is_ascii_alphabetic()) {
+        return None;
+    }
+    if subtags
+        .iter()
+        .any(|s| s.is_empty() || s.len() > 8 || !s.bytes().all(|b| b.is_ascii_alphanumeric()))
+    {
+        return None;
+    }
+    let mut normalized = language.to_ascii_lowercase();
+    for subtag in &subtags[1..] {
+        normalized.push('-');
+        match subtag.len() {
+            // Script, e.g. `Hant`.
+            4 if subtag.bytes().all(|b| b.is_ascii_alphabetic()) => {
+                normalized.push_str(&subtag[..1].to_ascii_uppercase());
+                normalized.push_str(&subtag[1..].to_ascii_lowercase());
+            }
+            // Region, e.g. `BR`.
+            2 => normalized.push_str(&subtag.to_ascii_uppercase()),
+            _ => normalized.push_str(&subtag.to_ascii_lowercase()),
+        }
+    }
+    Some(normalized)
+}
+
+fn abp_vendor_value<'a>(work_order: &'a WorkOrder, key: &str) -> Option<&'a serde_json::Value> {
+    let vendor = &work_order.config.vendor;
+    vendor
+        .get("abp")
+        .and_then(|v| v.get(key))
+        .or_else(|| vendor.get(&format!("abp.{key}")))
+}
+
 /// Validates that a work order is compatible with passthrough execution mode.
 pub fn validate_passthrough_compatibility(_work_order: &WorkOrder) -> Result<()> {
     Ok(())
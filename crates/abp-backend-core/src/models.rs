@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Models a backend advertises through [`Backend::list_models`](crate::Backend::list_models).
+
+use abp_core::{Capability, CapabilityManifest, SupportLevel};
+use serde::{Deserialize, Serialize};
+
+/// A model a backend can serve right now.
+///
+/// # Examples
+///
+/// ```
+/// use abp_backend_core::ModelInfo;
+/// use abp_core::{Capability, SupportLevel};
+///
+/// let model = ModelInfo::new("llama3.1:8b")
+///     .display_name("Llama 3.1 8B")
+///     .max_context_tokens(131_072)
+///     .capability(Capability::ToolUse, SupportLevel::Native);
+/// assert!(model.matches("llama3.1:8b"));
+/// assert!(!model.matches("llama3.1"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelInfo {
+    /// Model identifier as the backend expects it in a work order.
+    pub id: String,
+    /// Human-readable name, if the backend reports one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// Maximum context window in tokens, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_context_tokens: Option<u64>,
+    /// Maximum output tokens per response, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+    /// Capabilities that differ from the backend-wide manifest for this model.
+    #[serde(default, skip_serializing_if = "CapabilityManifest::is_empty")]
+    pub capabilities: CapabilityManifest,
+}
+
+impl ModelInfo {
+    /// A model with only its identifier known.
+    #[must_use]
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the human-readable name.
+    #[must_use]
+    pub fn display_name(mut self, name: impl Into<String>) -> Self {
+        self.display_name = Some(name.into());
+        self
+    }
+
+    /// Set the context window.
+    #[must_use]
+    pub fn max_context_tokens(mut self, tokens: u64) -> Self {
+        self.max_context_tokens = Some(tokens);
+        self
+    }
+
+    /// Set the output token limit.
+    #[must_use]
+    pub fn max_output_tokens(mut self, tokens: u64) -> Self {
+        self.max_output_tokens = Some(tokens);
+        self
+    }
+
+    /// Declare how this model supports `capability`.
+    #[must_use]
+    pub fn capability(mut self, capability: Capability, level: SupportLevel) -> Self {
+        self.capabilities.insert(capability, level);
+        self
+    }
+
+    /// Whether a work order naming `model` asks for this model.
+    #[must_use]
+    pub fn matches(&self, model: &str) -> bool {
+        self.id == model
+    }
+
+    /// The backend-wide manifest `base` with this model's capabilities
+    /// applied on top.
+    #[must_use]
+    pub fn apply_to(&self, base: &CapabilityManifest) -> CapabilityManifest {
+        let mut manifest = base.clone();
+        manifest.extend(self.capabilities.clone());
+        manifest
+    }
+}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Backend-scoped vendor configuration.
+//!
+//! `config.vendor` travels with a work order to whichever backend runs it.
+//! Knobs meant for one provider live under that provider's namespace, either
+//! nested (`vendor["openai"]["temperature"]`) or as a dotted key
+//! (`vendor["openai.temperature"]`); nested values win when both are set.
+//!
+//! Each SDK crate describes its namespace with a
+//! [`VendorConfig`] type, and its backends
+//! advertise it through
+//! [`Backend::vendor_namespace`](crate::Backend::vendor_namespace). Before
+//! dispatch the runtime validates that namespace and
+//! [scopes](crate::vendor::VendorNamespace::scope) the work order, so a
+//! backend never sees another provider's knobs.
+
+use abp_core::WorkOrder;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// The `config.vendor` namespaces owned by backends.
+///
+/// Other keys (`abp`, legacy flat keys) are shared by every backend.
+pub const BACKEND_NAMESPACES: [&str; 6] =
+    ["openai", "claude", "gemini", "codex", "kimi", "copilot"];
+
+/// A backend's typed view of its `config.vendor` namespace.
+///
+/// Implementations are usually `#[serde(deny_unknown_fields)]` structs, so
+/// misspelled or foreign knobs are rejected rather than silently dropped.
+pub trait VendorConfig: DeserializeOwned {
+    /// The `config.vendor` key this backend owns, e.g. `"openai"`.
+    const NAMESPACE: &'static str;
+
+    /// Checks beyond the field types, such as value ranges.
+    ///
+    /// # Errors
+    ///
+    /// A message describing the first invalid value.
+    fn check(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Parse and check this namespace from a work order.
+    ///
+    /// Returns `Ok(None)` when the work order sets nothing under it.
+    ///
+    /// # Errors
+    ///
+    /// [`VendorConfigError`] when the namespace does not match the schema.
+    fn from_work_order(work_order: &WorkOrder) -> Result<Option<Self>, VendorConfigError> {
+        let Some(value) = namespace(work_order, Self::NAMESPACE) else {
+            return Ok(None);
+        };
+        let config: Self = serde_json::from_value(value)
+            .map_err(|e| VendorConfigError::new(Self::NAMESPACE, e.to_string()))?;
+        config
+            .check()
+            .map_err(|message| VendorConfigError::new(Self::NAMESPACE, message))?;
+        Ok(Some(config))
+    }
+
+    /// This namespace as lowering sees it: parsed from the work order, or
+    /// the defaults when it is unset or invalid.
+    ///
+    /// The runtime rejects invalid namespaces before dispatch, so lowering
+    /// can treat them as empty.
+    #[must_use]
+    fn from_work_order_or_default(work_order: &WorkOrder) -> Self
+    where
+        Self: Default,
+    {
+        Self::from_work_order(work_order)
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+    }
+}
+
+/// The merged contents of `config.vendor[namespace]` and its dotted keys.
+///
+/// Returns `None` when neither form is present. A nested value that is not
+/// an object is returned as-is so validation can reject it.
+#[must_use]
+pub fn namespace(work_order: &WorkOrder, namespace: &str) -> Option<Value> {
+    let vendor = &work_order.config.vendor;
+    let nested = vendor.get(namespace);
+    if let Some(value) = nested.filter(|v| !v.is_object()) {
+        return Some(value.clone());
+    }
+    let prefix = format!("{namespace}.");
+    let mut merged: Map<String, Value> = vendor
+        .iter()
+        .filter_map(|(k, v)| Some((k.strip_prefix(&prefix)?.to_string(), v.clone())))
+        .collect();
+    if let Some(Value::Object(nested)) = nested {
+        merged.extend(nested.clone());
+    }
+    (nested.is_some() || !merged.is_empty()).then_some(Value::Object(merged))
+}
+
+/// A backend's vendor namespace and the schema that validates it.
+#[derive(Debug, Clone, Copy)]
+pub struct VendorNamespace {
+    name: &'static str,
+    validate: fn(&WorkOrder) -> Result<(), VendorConfigError>,
+}
+
+impl VendorNamespace {
+    /// The namespace described by `T`.
+    #[must_use]
+    pub fn of<T: VendorConfig>() -> Self {
+        Self {
+            name: T::NAMESPACE,
+            validate: |work_order| T::from_work_order(work_order).map(|_| ()),
+        }
+    }
+
+    /// The `config.vendor` key this namespace owns.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Check the work order's settings under this namespace.
+    ///
+    /// # Errors
+    ///
+    /// [`VendorConfigError`] when the namespace does not match the schema.
+    pub fn validate(&self, work_order: &WorkOrder) -> Result<(), VendorConfigError> {
+        (self.validate)(work_order)
+    }
+
+    /// Remove every other backend's namespace from the work order.
+    ///
+    /// Shared keys and this namespace are kept.
+    pub fn scope(&self, work_order: &mut WorkOrder) {
+        work_order
+            .config
+            .vendor
+            .retain(|key, _| owner(key).is_none_or(|ns| ns == self.name));
+    }
+}
+
+/// Check that an optional numeric knob lies within `range`.
+///
+/// For use in [`VendorConfig::check`] implementations.
+///
+/// # Errors
+///
+/// A message naming `field` when the value is out of range.
+pub fn check_range(
+    field: &str,
+    value: Option<f64>,
+    range: RangeInclusive<f64>,
+) -> Result<(), String> {
+    match value {
+        Some(v) if !range.contains(&v) => Err(format!(
+            "{field} must be between {} and {}, got {v}",
+            range.start(),
+            range.end()
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The backend namespace `key` belongs to, if any.
+fn owner(key: &str) -> Option<&'static str> {
+    let head = key.split_once('.').map_or(key, |(head, _)| head);
+    BACKEND_NAMESPACES.into_iter().find(|ns| *ns == head)
+}
+
+/// A vendor namespace that does not match its backend's schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VendorConfigError {
+    /// The namespace that failed validation.
+    pub namespace: String,
+    /// What is wrong with it.
+    pub message: String,
+}
+
+impl VendorConfigError {
+    /// Create an error for `namespace`.
+    pub fn new(namespace: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for VendorConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid vendor.{} config: {}",
+            self.namespace, self.message
+        )
+    }
+}
+
+impl std::error::Error for VendorConfigError {}
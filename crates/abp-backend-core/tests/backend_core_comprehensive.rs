@@ -11,9 +11,11 @@ use abp_backend_core::health::{BackendHealth, HealthStatus};
 use abp_backend_core::metadata::{BackendMetadata, RateLimit};
 use abp_backend_core::registry::BackendRegistry;
 use abp_backend_core::{
-    Backend, ensure_capability_requirements, extract_execution_mode,
-    validate_passthrough_compatibility,
+    Backend, ensure_capability_requirements, extract_conversation, extract_execution_mode,
+    extract_response_language, extract_seed, extract_tool_choice, extract_tools,
+    normalize_language_tag, validate_passthrough_compatibility,
 };
+use abp_core::ir::{IrConversation, IrMessage, IrRole, IrToolChoice, IrToolDefinition};
 use abp_core::{
     AgentEvent, AgentEventKind, BackendIdentity, Capability, CapabilityManifest,
     CapabilityRequirement, CapabilityRequirements, ExecutionMode, MinSupport, ReceiptBuilder,
@@ -1074,3 +1076,177 @@ fn reexport_rate_limit() {
 fn reexport_backend_registry() {
     let _: abp_backend_core::BackendRegistry = BackendRegistry::new();
 }
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 14. extract_seed
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn extract_seed_absent_by_default() {
+    assert_eq!(extract_seed(&make_work_order()), None);
+}
+
+#[test]
+fn extract_seed_nested_and_flat() {
+    let mut vendor = BTreeMap::new();
+    vendor.insert("abp".into(), serde_json::json!({"seed": 42}));
+    assert_eq!(extract_seed(&make_work_order_with_vendor(vendor)), Some(42));
+
+    let mut vendor = BTreeMap::new();
+    vendor.insert("abp.seed".into(), serde_json::json!(7));
+    assert_eq!(extract_seed(&make_work_order_with_vendor(vendor)), Some(7));
+}
+
+#[test]
+fn extract_seed_ignores_non_integer_values() {
+    let mut vendor = BTreeMap::new();
+    vendor.insert("abp".into(), serde_json::json!({"seed": "42"}));
+    assert_eq!(extract_seed(&make_work_order_with_vendor(vendor)), None);
+}
+
+#[test]
+fn extract_seed_from_builder() {
+    let wo = WorkOrderBuilder::new("task").seed(99).build();
+    assert_eq!(extract_seed(&wo), Some(99));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 15. extract_tools / extract_tool_choice
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn extract_tools_absent_by_default() {
+    let wo = make_work_order();
+    assert!(extract_tools(&wo).is_empty());
+    assert_eq!(extract_tool_choice(&wo), None);
+}
+
+#[test]
+fn extract_tools_from_builder() {
+    let tool = IrToolDefinition {
+        name: "read_file".into(),
+        description: "Read a file".into(),
+        parameters: serde_json::json!({"type": "object"}),
+    };
+    let wo = WorkOrderBuilder::new("task")
+        .seed(1)
+        .tools(vec![tool.clone()])
+        .tool_choice(IrToolChoice::Tool {
+            name: "read_file".into(),
+        })
+        .build();
+    assert_eq!(extract_tools(&wo), vec![tool]);
+    assert_eq!(
+        extract_tool_choice(&wo),
+        Some(IrToolChoice::Tool {
+            name: "read_file".into()
+        })
+    );
+    assert_eq!(extract_seed(&wo), Some(1));
+}
+
+#[test]
+fn extract_tool_choice_flat_key() {
+    let mut vendor = BTreeMap::new();
+    vendor.insert(
+        "abp.tool_choice".into(),
+        serde_json::json!({"type": "required"}),
+    );
+    assert_eq!(
+        extract_tool_choice(&make_work_order_with_vendor(vendor)),
+        Some(IrToolChoice::Required)
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 16. extract_conversation
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn extract_conversation_absent_by_default() {
+    assert_eq!(extract_conversation(&make_work_order()), None);
+}
+
+#[test]
+fn extract_conversation_from_builder() {
+    let conv = IrConversation::new()
+        .push(IrMessage::text(IrRole::System, "be terse"))
+        .push(IrMessage::text(IrRole::User, "hi"))
+        .push(IrMessage::text(IrRole::Assistant, "hello"))
+        .push(IrMessage::text(IrRole::User, "again"));
+    let wo = WorkOrderBuilder::new("again")
+        .seed(3)
+        .conversation(conv.clone())
+        .build();
+    assert_eq!(extract_conversation(&wo), Some(conv));
+    assert_eq!(extract_seed(&wo), Some(3));
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 17. extract_response_language
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn extract_response_language_absent_by_default() {
+    assert_eq!(extract_response_language(&make_work_order()), None);
+}
+
+#[test]
+fn extract_response_language_normalizes_the_tag() {
+    let wo = WorkOrderBuilder::new("task")
+        .response_language("pt_br")
+        .build();
+    assert_eq!(extract_response_language(&wo).as_deref(), Some("pt-BR"));
+
+    let mut vendor = BTreeMap::new();
+    vendor.insert("abp.locale".into(), serde_json::json!("ZH-hant-tw"));
+    assert_eq!(
+        extract_response_language(&make_work_order_with_vendor(vendor)).as_deref(),
+        Some("zh-Hant-TW")
+    );
+}
+
+#[test]
+fn extract_response_language_prefers_response_language_over_locale() {
+    let mut vendor = BTreeMap::new();
+    vendor.insert(
+        "abp".into(),
+        serde_json::json!({"locale": "de", "response_language": "fr"}),
+    );
+    assert_eq!(
+        extract_response_language(&make_work_order_with_vendor(vendor)).as_deref(),
+        Some("fr")
+    );
+}
+
+#[test]
+fn extract_response_language_prefers_the_config_field() {
+    let mut vendor = BTreeMap::new();
+    vendor.insert("abp.response_language".into(), serde_json::json!("fr"));
+    let mut wo = make_work_order_with_vendor(vendor);
+    wo.config.response_language = Some("en_gb".into());
+    assert_eq!(extract_response_language(&wo).as_deref(), Some("en-GB"));
+
+    let config: abp_core::RuntimeConfig =
+        serde_json::from_value(serde_json::json!({"vendor": {}, "env": {}, "locale": "ja"}))
+            .unwrap();
+    assert_eq!(config.response_language.as_deref(), Some("ja"));
+}
+
+#[test]
+fn normalize_language_tag_rejects_malformed_tags() {
+    for tag in [
+        "",
+        "e",
+        "english please",
+        "en--US",
+        "1x",
+        "en-toolongsubtag",
+    ] {
+        assert_eq!(normalize_language_tag(tag), None, "{tag:?}");
+    }
+    assert_eq!(
+        normalize_language_tag(" es-419 ").as_deref(),
+        Some("es-419")
+    );
+}
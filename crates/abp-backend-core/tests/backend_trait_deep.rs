@@ -1327,3 +1327,27 @@ fn ensure_capabilities_restricted_does_not_satisfy_native() {
     };
     assert!(ensure_capability_requirements(&reqs, &caps).is_err());
 }
+
+#[tokio::test]
+async fn list_models_defaults_to_nothing_advertised() {
+    assert!(FailingBackend.list_models().await.unwrap().is_empty());
+}
+
+#[test]
+fn model_info_capabilities_override_backend_manifest() {
+    let mut base = CapabilityManifest::default();
+    base.insert(Capability::Vision, SupportLevel::Unsupported);
+    base.insert(Capability::Streaming, SupportLevel::Native);
+    let model = abp_backend_core::ModelInfo::new("llava")
+        .max_context_tokens(4096)
+        .capability(Capability::Vision, SupportLevel::Native);
+    let manifest = model.apply_to(&base);
+    assert!(matches!(
+        manifest.get(&Capability::Vision),
+        Some(SupportLevel::Native)
+    ));
+    assert_eq!(manifest.len(), 2);
+    let json = serde_json::to_value(&model).unwrap();
+    assert_eq!(json["max_context_tokens"], 4096);
+    assert!(json.get("display_name").is_none());
+}
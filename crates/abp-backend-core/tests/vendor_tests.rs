@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for backend-scoped `config.vendor` namespaces.
+
+use abp_backend_core::vendor::{self, check_range};
+use abp_backend_core::{VendorConfig, VendorConfigError, VendorNamespace};
+use abp_core::{WorkOrder, WorkOrderBuilder};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+struct Acme {
+    model: Option<String>,
+    temperature: Option<f64>,
+}
+
+impl VendorConfig for Acme {
+    const NAMESPACE: &'static str = "openai";
+
+    fn check(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0..=2.0)
+    }
+}
+
+fn work_order(vendor: Value) -> WorkOrder {
+    let mut wo = WorkOrderBuilder::new("task").build();
+    wo.config.vendor = serde_json::from_value(vendor).unwrap();
+    wo
+}
+
+#[test]
+fn nested_and_dotted_keys_merge_with_nested_winning() {
+    let wo = work_order(json!({
+        "openai": {"temperature": 0.5},
+        "openai.temperature": 1.5,
+        "openai.model": "gpt-4o",
+        "claude.max_tokens": 10,
+    }));
+    assert_eq!(
+        vendor::namespace(&wo, "openai"),
+        Some(json!({"temperature": 0.5, "model": "gpt-4o"}))
+    );
+    assert_eq!(vendor::namespace(&wo, "gemini"), None);
+    assert_eq!(
+        Acme::from_work_order(&wo).unwrap(),
+        Some(Acme {
+            model: Some("gpt-4o".into()),
+            temperature: Some(0.5),
+        })
+    );
+}
+
+#[test]
+fn unknown_keys_and_out_of_range_values_are_rejected() {
+    let ns = VendorNamespace::of::<Acme>();
+    assert_eq!(ns.name(), "openai");
+    assert!(ns.validate(&work_order(json!({}))).is_ok());
+    assert!(
+        ns.validate(&work_order(json!({"claude": {"x": 1}})))
+            .is_ok()
+    );
+
+    let err = ns
+        .validate(&work_order(json!({"openai.top_k": 40})))
+        .unwrap_err();
+    assert_eq!(err.namespace, "openai");
+    assert!(err.message.contains("top_k"), "{err}");
+
+    let err = ns
+        .validate(&work_order(json!({"openai": {"temperature": 3.0}})))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        VendorConfigError::new("openai", "temperature must be between 0 and 2, got 3")
+    );
+    assert_eq!(
+        err.to_string(),
+        "invalid vendor.openai config: temperature must be between 0 and 2, got 3"
+    );
+
+    assert!(ns.validate(&work_order(json!({"openai": 7}))).is_err());
+}
+
+#[test]
+fn scope_drops_other_backends_namespaces() {
+    let mut wo = work_order(json!({
+        "abp": {"mode": "mapped"},
+        "abp.max_tokens": 100,
+        "temperature": 0.2,
+        "openai": {"model": "gpt-4o"},
+        "openai.seed": 1,
+        "claude": {"max_tokens": 10},
+        "gemini.topP": 0.9,
+        "kimi.use_search": true,
+    }));
+    VendorNamespace::of::<Acme>().scope(&mut wo);
+    let keys: Vec<_> = wo.config.vendor.keys().map(String::as_str).collect();
+    assert_eq!(
+        keys,
+        [
+            "abp",
+            "abp.max_tokens",
+            "openai",
+            "openai.seed",
+            "temperature"
+        ]
+    );
+}
+
+#[test]
+fn lowering_falls_back_to_defaults() {
+    let invalid = work_order(json!({"openai": {"temperature": "hot"}}));
+    assert_eq!(Acme::from_work_order_or_default(&invalid), Acme::default());
+    assert_eq!(
+        Acme::from_work_order_or_default(&work_order(json!({}))),
+        Acme::default()
+    );
+}
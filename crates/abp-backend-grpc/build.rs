@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+fn main() {
+    // Use the vendored protoc so the build does not depend on a system install.
+    let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+    // SAFETY: build scripts are single-threaded.
+    #[allow(unsafe_code)]
+    unsafe {
+        std::env::set_var("PROTOC", protoc);
+    }
+    println!("cargo:rerun-if-changed=proto/abp/v0/sidecar.proto");
+    tonic_prost_build::compile_protos("proto/abp/v0/sidecar.proto").expect("compile sidecar.proto");
+}
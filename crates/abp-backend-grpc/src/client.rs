@@ -0,0 +1,173 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Host side of the gRPC sidecar transport.
+
+use std::time::Duration;
+
+use abp_core::{AgentEvent, Receipt, WorkOrder};
+use abp_host::SidecarHello;
+use abp_protocol::{Envelope, ProtocolError};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::Streaming;
+use tonic::transport::Endpoint;
+use tracing::{debug, warn};
+
+use crate::proto::sidecar_client::SidecarClient;
+use crate::{GrpcError, convert, proto};
+
+/// A session with a gRPC sidecar that has completed its `hello` handshake.
+///
+/// Each session carries a single run, mirroring a JSONL sidecar process.
+#[derive(Debug)]
+pub struct GrpcSidecarClient {
+    /// Parsed hello handshake data.
+    pub hello: SidecarHello,
+    features_advertised: bool,
+    outbound: mpsc::Sender<proto::Envelope>,
+    inbound: Streaming<proto::Envelope>,
+}
+
+/// Handle to an in-progress run on a gRPC sidecar.
+#[derive(Debug)]
+pub struct GrpcRun {
+    /// Stream of events for the run.
+    pub events: ReceiverStream<AgentEvent>,
+
+    /// Final receipt for the run.
+    pub receipt: oneshot::Receiver<Result<Receipt, GrpcError>>,
+}
+
+impl GrpcSidecarClient {
+    /// Connect to the sidecar at `endpoint` (e.g. `http://127.0.0.1:50051`),
+    /// open a session, and wait for its `hello`.
+    ///
+    /// `timeout` bounds both the connection and the wait for `hello`.
+    ///
+    /// # Errors
+    ///
+    /// [`GrpcError::Transport`] when the sidecar is unreachable,
+    /// [`GrpcError::Timeout`] when no hello arrives in time, and
+    /// [`GrpcError::Protocol`] when the first message is not a hello or its contract
+    /// version is incompatible.
+    pub async fn connect(endpoint: &str, timeout: Duration) -> Result<Self, GrpcError> {
+        let channel = Endpoint::from_shared(endpoint.to_string())?
+            .connect_timeout(timeout)
+            .connect()
+            .await?;
+        let (outbound, rx) = mpsc::channel(16);
+        let mut inbound = SidecarClient::new(channel)
+            .session(ReceiverStream::new(rx))
+            .await?
+            .into_inner();
+
+        let first = tokio::time::timeout(timeout, inbound.message())
+            .await
+            .map_err(|_| GrpcError::Timeout { duration: timeout })??
+            .ok_or(GrpcError::Closed)?;
+
+        let (contract_version, backend, capabilities, advertised) = match convert::decode(first)? {
+            Envelope::Hello {
+                contract_version,
+                backend,
+                capabilities,
+                features,
+                ..
+            } => (contract_version, backend, capabilities, features),
+            other => {
+                return Err(GrpcError::Protocol(ProtocolError::UnexpectedMessage {
+                    expected: "hello".into(),
+                    got: format!("{other:?}"),
+                }));
+            }
+        };
+
+        // Feature negotiation only bridges minor versions; a differing major
+        // or malformed version is rejected either way.
+        if let Some(err) = abp_core::compat::check_sidecar(&contract_version).to_error() {
+            return Err(GrpcError::Protocol(err.into()));
+        }
+
+        debug!(target: "abp.sidecar.grpc", "sidecar hello: backend={} endpoint={endpoint}", backend.id);
+
+        Ok(Self {
+            hello: SidecarHello {
+                contract_version,
+                backend,
+                capabilities,
+            },
+            features_advertised: advertised.is_some(),
+            outbound,
+            inbound,
+        })
+    }
+
+    /// Whether the sidecar sent a feature-negotiation block in its hello.
+    #[must_use]
+    pub fn features_advertised(&self) -> bool {
+        self.features_advertised
+    }
+
+    /// Compare the sidecar's contract version with ours.
+    #[must_use]
+    pub fn compat(&self) -> abp_core::compat::CompatReport {
+        abp_core::compat::check_sidecar(&self.hello.contract_version)
+    }
+
+    /// Send a `run` and stream its events until `final` or `fatal`.
+    ///
+    /// Events for other run ids are dropped with a warning.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the work order cannot be encoded or the session has closed.
+    pub async fn run(self, run_id: String, work_order: WorkOrder) -> Result<GrpcRun, GrpcError> {
+        let run = convert::encode(&Envelope::Run {
+            id: run_id.clone(),
+            work_order,
+        })?;
+        self.outbound
+            .send(run)
+            .await
+            .map_err(|_| GrpcError::Closed)?;
+
+        let (events_tx, events_rx) = mpsc::channel(256);
+        let (receipt_tx, receipt_rx) = oneshot::channel();
+        let Self {
+            outbound,
+            mut inbound,
+            ..
+        } = self;
+
+        tokio::spawn(async move {
+            let result = loop {
+                let frame = match inbound.message().await {
+                    Ok(Some(frame)) => frame,
+                    Ok(None) => break Err(GrpcError::Closed),
+                    Err(status) => break Err(status.into()),
+                };
+                match convert::decode(frame) {
+                    Ok(Envelope::Event { ref_id, event, .. }) if ref_id == run_id => {
+                        let _ = events_tx.send(event).await;
+                    }
+                    Ok(Envelope::Final { ref_id, receipt }) if ref_id == run_id => {
+                        break Ok(receipt);
+                    }
+                    Ok(Envelope::Fatal { error, .. }) => break Err(GrpcError::Fatal(error)),
+                    Ok(other) => {
+                        warn!(target: "abp.sidecar.grpc", "dropping unexpected frame: {other:?}");
+                    }
+                    Err(e) => break Err(e),
+                }
+            };
+            // Hold the request stream open until the run ends; closing it
+            // early reads as a cancellation to some servers.
+            drop(outbound);
+            let _ = receipt_tx.send(result);
+        });
+
+        Ok(GrpcRun {
+            events: ReceiverStream::new(events_rx),
+            receipt: receipt_rx,
+        })
+    }
+}
@@ -0,0 +1,251 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Conversions between ABP [`Envelope`]s and their protobuf form.
+//!
+//! Contract types travel as canonical JSON in the `json` field of each
+//! message, so a receipt hashes the same whichever transport carried it.
+//! The typed fields alongside are filled in on encode and ignored on decode.
+
+use abp_core::{AgentEvent, Receipt, WorkOrder};
+use abp_protocol::Envelope;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::GrpcError;
+use crate::proto;
+use crate::proto::envelope::Frame;
+
+/// Encode an envelope for the wire.
+///
+/// # Errors
+///
+/// Fails only if a contract type cannot be serialized to JSON.
+pub fn encode(envelope: &Envelope) -> Result<proto::Envelope, GrpcError> {
+    let frame = match envelope {
+        Envelope::Hello {
+            contract_version,
+            backend,
+            ..
+        } => Frame::Hello(proto::Hello {
+            json: to_json("hello", envelope)?,
+            contract_version: contract_version.clone(),
+            backend_id: backend.id.clone(),
+        }),
+        Envelope::Run { id, work_order } => Frame::Run(proto::Run {
+            id: id.clone(),
+            work_order: Some(encode_work_order(work_order)?),
+        }),
+        Envelope::Event { ref_id, event, seq } => Frame::Event(proto::Event {
+            ref_id: ref_id.clone(),
+            event: Some(encode_event(event)?),
+            seq: *seq,
+        }),
+        Envelope::Final { ref_id, receipt } => Frame::Final(proto::Final {
+            ref_id: ref_id.clone(),
+            receipt: Some(encode_receipt(receipt)?),
+        }),
+        Envelope::Fatal {
+            ref_id,
+            error,
+            error_code,
+        } => Frame::Fatal(proto::Fatal {
+            ref_id: ref_id.clone(),
+            error: error.clone(),
+            error_code: error_code.map(|c| c.as_str().to_string()),
+        }),
+    };
+    Ok(proto::Envelope { frame: Some(frame) })
+}
+
+/// Decode an envelope received from the wire.
+///
+/// An unrecognized `error_code` on a fatal frame is dropped rather than
+/// rejected, so newer sidecars can report codes this host does not know.
+///
+/// # Errors
+///
+/// [`GrpcError::Missing`] when a frame or payload is absent and
+/// [`GrpcError::Payload`] when a `json` field is not a valid contract value.
+pub fn decode(envelope: proto::Envelope) -> Result<Envelope, GrpcError> {
+    match envelope.frame.ok_or(GrpcError::Missing("frame"))? {
+        Frame::Hello(hello) => {
+            let env: Envelope = from_json("hello", &hello.json)?;
+            match env {
+                Envelope::Hello { .. } => Ok(env),
+                _ => Err(GrpcError::Missing("hello")),
+            }
+        }
+        Frame::Run(run) => Ok(Envelope::Run {
+            id: run.id,
+            work_order: from_json(
+                "work_order",
+                &run.work_order.ok_or(GrpcError::Missing("work_order"))?.json,
+            )?,
+        }),
+        Frame::Event(event) => Ok(Envelope::Event {
+            ref_id: event.ref_id,
+            event: from_json(
+                "event",
+                &event.event.ok_or(GrpcError::Missing("event"))?.json,
+            )?,
+            seq: event.seq,
+        }),
+        Frame::Final(fin) => Ok(Envelope::Final {
+            ref_id: fin.ref_id,
+            receipt: from_json(
+                "receipt",
+                &fin.receipt.ok_or(GrpcError::Missing("receipt"))?.json,
+            )?,
+        }),
+        Frame::Fatal(fatal) => Ok(Envelope::Fatal {
+            ref_id: fatal.ref_id,
+            error: fatal.error,
+            error_code: fatal
+                .error_code
+                .and_then(|c| serde_json::from_value(serde_json::Value::String(c)).ok()),
+        }),
+    }
+}
+
+fn encode_work_order(wo: &WorkOrder) -> Result<proto::WorkOrder, GrpcError> {
+    Ok(proto::WorkOrder {
+        json: to_json("work_order", wo)?,
+        id: wo.id.to_string(),
+        task: wo.task.clone(),
+        model: wo.config.model.clone().unwrap_or_default(),
+    })
+}
+
+fn encode_event(event: &AgentEvent) -> Result<proto::AgentEvent, GrpcError> {
+    let json = to_json("event", event)?;
+    let kind = serde_json::from_str::<serde_json::Value>(&json)
+        .ok()
+        .and_then(|v| v.get("type").and_then(|t| t.as_str()).map(str::to_string))
+        .unwrap_or_default();
+    Ok(proto::AgentEvent {
+        json,
+        ts: event.ts.to_rfc3339(),
+        kind,
+    })
+}
+
+fn encode_receipt(receipt: &Receipt) -> Result<proto::Receipt, GrpcError> {
+    let outcome = serde_json::to_value(&receipt.outcome)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default();
+    Ok(proto::Receipt {
+        json: to_json("receipt", receipt)?,
+        run_id: receipt.meta.run_id.to_string(),
+        outcome,
+        receipt_sha256: receipt.receipt_sha256.clone().unwrap_or_default(),
+    })
+}
+
+fn to_json<T: Serialize>(field: &'static str, value: &T) -> Result<String, GrpcError> {
+    serde_json::to_string(value).map_err(|source| GrpcError::Payload { field, source })
+}
+
+fn from_json<T: DeserializeOwned>(field: &'static str, json: &str) -> Result<T, GrpcError> {
+    serde_json::from_str(json).map_err(|source| GrpcError::Payload { field, source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::{AgentEventKind, BackendIdentity, CapabilityManifest, WorkOrderBuilder};
+    use abp_error::ErrorCode;
+
+    fn roundtrip(env: &Envelope) -> Envelope {
+        decode(encode(env).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn contract_types_survive_the_wire() {
+        let hello = Envelope::hello(
+            BackendIdentity {
+                id: "go-sidecar".into(),
+                backend_version: Some("1.2".into()),
+                adapter_version: None,
+            },
+            CapabilityManifest::new(),
+        );
+        let Envelope::Hello { backend, .. } = roundtrip(&hello) else {
+            panic!("expected hello");
+        };
+        assert_eq!(backend.id, "go-sidecar");
+
+        let wo = WorkOrderBuilder::new("fix it").model("gpt-4o").build();
+        let frame = encode(&Envelope::Run {
+            id: "r1".into(),
+            work_order: wo.clone(),
+        })
+        .unwrap();
+        let Some(Frame::Run(run)) = &frame.frame else {
+            panic!("expected run frame");
+        };
+        assert_eq!(run.work_order.as_ref().unwrap().model, "gpt-4o");
+        let Envelope::Run { work_order, .. } = decode(frame).unwrap() else {
+            panic!("expected run");
+        };
+        assert_eq!(work_order.id, wo.id);
+
+        let event = Envelope::Event {
+            ref_id: "r1".into(),
+            event: AgentEvent {
+                ts: chrono::Utc::now(),
+                kind: AgentEventKind::AssistantDelta { text: "hi".into() },
+                ext: None,
+            },
+            seq: Some(3),
+        };
+        let frame = encode(&event).unwrap();
+        let Some(Frame::Event(ev)) = &frame.frame else {
+            panic!("expected event frame");
+        };
+        assert_eq!(ev.event.as_ref().unwrap().kind, "assistant_delta");
+        let Envelope::Event { seq, .. } = decode(frame).unwrap() else {
+            panic!("expected event");
+        };
+        assert_eq!(seq, Some(3));
+    }
+
+    #[test]
+    fn fatal_codes_are_optional_and_lenient() {
+        let fatal = Envelope::Fatal {
+            ref_id: None,
+            error: "boom".into(),
+            error_code: Some(ErrorCode::BackendTimeout),
+        };
+        let Envelope::Fatal { error_code, .. } = roundtrip(&fatal) else {
+            panic!("expected fatal");
+        };
+        assert_eq!(error_code, Some(ErrorCode::BackendTimeout));
+
+        let env = proto::Envelope {
+            frame: Some(Frame::Fatal(proto::Fatal {
+                ref_id: Some("r1".into()),
+                error: "boom".into(),
+                error_code: Some("from_the_future".into()),
+            })),
+        };
+        let Envelope::Fatal { error_code, .. } = decode(env).unwrap() else {
+            panic!("expected fatal");
+        };
+        assert_eq!(error_code, None);
+    }
+
+    #[test]
+    fn missing_payloads_are_rejected() {
+        let env = proto::Envelope {
+            frame: Some(Frame::Final(proto::Final {
+                ref_id: "r1".into(),
+                receipt: None,
+            })),
+        };
+        assert!(matches!(decode(env), Err(GrpcError::Missing("receipt"))));
+        assert!(matches!(
+            decode(proto::Envelope { frame: None }),
+            Err(GrpcError::Missing("frame"))
+        ));
+    }
+}
@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Errors from the gRPC sidecar transport.
+
+use std::time::Duration;
+
+use abp_error::{AbpError, ErrorCode};
+use abp_protocol::ProtocolError;
+use thiserror::Error;
+
+/// Errors from connecting to or talking with a gRPC sidecar.
+#[derive(Debug, Error)]
+pub enum GrpcError {
+    /// The endpoint is invalid or the connection could not be established.
+    #[error("failed to connect to sidecar: {0}")]
+    Transport(#[from] tonic::transport::Error),
+
+    /// The sidecar ended the session with a gRPC status.
+    #[error("sidecar session failed: {0}")]
+    Status(Box<tonic::Status>),
+
+    /// A `json` payload could not be encoded or decoded.
+    #[error("invalid {field} payload: {source}")]
+    Payload {
+        /// Which payload was invalid.
+        field: &'static str,
+        /// The underlying JSON error.
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// A frame or one of its required fields was absent.
+    #[error("sidecar frame is missing {0}")]
+    Missing(&'static str),
+
+    /// The sidecar sent an unexpected or out-of-order message.
+    #[error("protocol error: {0}")]
+    Protocol(#[from] ProtocolError),
+
+    /// The sidecar reported a fatal error via a `fatal` frame.
+    #[error("sidecar fatal error: {0}")]
+    Fatal(String),
+
+    /// The sidecar closed the session before completing the run.
+    #[error("sidecar closed the session unexpectedly")]
+    Closed,
+
+    /// The sidecar did not answer in time.
+    #[error("sidecar timed out after {duration:?}")]
+    Timeout {
+        /// How long we waited before timing out.
+        duration: Duration,
+    },
+}
+
+impl From<tonic::Status> for GrpcError {
+    fn from(status: tonic::Status) -> Self {
+        Self::Status(Box::new(status))
+    }
+}
+
+impl GrpcError {
+    /// The classified error code: a failed or dropped session is
+    /// [`BackendCrashed`](ErrorCode::BackendCrashed).
+    #[must_use]
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Transport(_) | Self::Status(_) | Self::Fatal(_) | Self::Closed => {
+                ErrorCode::BackendCrashed
+            }
+            Self::Timeout { .. } => ErrorCode::BackendTimeout,
+            Self::Payload { .. } | Self::Missing(_) => ErrorCode::ProtocolInvalidEnvelope,
+            Self::Protocol(e) => e.error_code().unwrap_or(ErrorCode::ProtocolInvalidEnvelope),
+        }
+    }
+}
+
+impl From<GrpcError> for AbpError {
+    fn from(err: GrpcError) -> Self {
+        AbpError::new(err.error_code(), err.to_string()).with_source(err)
+    }
+}
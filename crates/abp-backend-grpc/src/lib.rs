@@ -0,0 +1,111 @@
+#![doc = include_str!("../README.md")]
+#![deny(unsafe_code)]
+#![warn(missing_docs)]
+//! gRPC transport for sidecar backends.
+
+pub mod client;
+pub mod convert;
+pub mod error;
+
+/// Generated protobuf messages and gRPC stubs for `abp.v0`.
+///
+/// Sidecars written in Rust can implement [`proto::sidecar_server::Sidecar`]
+/// directly; other languages generate their own stubs from
+/// `proto/abp/v0/sidecar.proto`.
+#[allow(missing_docs, clippy::all, clippy::pedantic)]
+pub mod proto {
+    tonic::include_proto!("abp.v0");
+}
+
+pub use client::{GrpcRun, GrpcSidecarClient};
+pub use error::GrpcError;
+
+use std::time::Duration;
+
+use abp_backend_core::{Backend, ensure_capability_requirements};
+use abp_core::{AgentEvent, BackendIdentity, CapabilityManifest, Receipt, WorkOrder};
+use abp_error::AbpError;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tracing::debug;
+use uuid::Uuid;
+
+/// Default bound on connecting to a sidecar and receiving its `hello`.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Sidecar backend reached over gRPC instead of stdio.
+///
+/// The sidecar runs a server implementing the `abp.v0.Sidecar` service at
+/// `endpoint`; each run opens one session on it.
+#[derive(Debug, Clone)]
+pub struct GrpcBackend {
+    /// Server URI, e.g. `http://127.0.0.1:50051`.
+    pub endpoint: String,
+    /// Bound on connecting and receiving the `hello`.
+    pub connect_timeout: Duration,
+}
+
+impl GrpcBackend {
+    /// Creates a gRPC backend for the sidecar at `endpoint`.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+        }
+    }
+
+    /// Sets the connect-and-hello timeout.
+    #[must_use]
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+}
+
+#[async_trait]
+impl Backend for GrpcBackend {
+    fn identity(&self) -> BackendIdentity {
+        BackendIdentity {
+            id: "grpc".to_string(),
+            backend_version: None,
+            adapter_version: Some("0.1".to_string()),
+        }
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        CapabilityManifest::default()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> Result<Receipt> {
+        let client = GrpcSidecarClient::connect(&self.endpoint, self.connect_timeout)
+            .await
+            .map_err(|e| {
+                anyhow::Error::new(AbpError::from(e))
+                    .context(format!("connect to grpc sidecar at {}", self.endpoint))
+            })?;
+
+        ensure_capability_requirements(&work_order.requirements, &client.hello.capabilities)
+            .context("capability requirements not satisfied")?;
+
+        debug!(target: "abp.sidecar.grpc", "connected to sidecar backend={}", client.hello.backend.id);
+
+        let mut run = client
+            .run(run_id.to_string(), work_order)
+            .await
+            .context("start run")?;
+
+        while let Some(ev) = run.events.next().await {
+            let _ = events_tx.send(ev).await;
+        }
+
+        let receipt = run.receipt.await.context("receive receipt")??;
+        Ok(receipt)
+    }
+}
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! `GrpcBackend` against an in-process gRPC sidecar.
+
+use std::pin::Pin;
+use std::time::Duration;
+
+use abp_backend_core::Backend;
+use abp_backend_grpc::proto::sidecar_server::{Sidecar, SidecarServer};
+use abp_backend_grpc::{GrpcBackend, convert, proto};
+use abp_core::{
+    AgentEvent, AgentEventKind, BackendIdentity, Capability, CapabilityManifest,
+    CapabilityRequirement, CapabilityRequirements, MinSupport, Outcome, SupportLevel,
+    WorkOrderBuilder,
+};
+use abp_protocol::Envelope;
+use abp_receipt::ReceiptBuilder;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status, Streaming};
+use uuid::Uuid;
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Ok,
+    Fatal,
+}
+
+struct MockSidecar {
+    mode: Mode,
+}
+
+type Frames = Pin<Box<dyn Stream<Item = Result<proto::Envelope, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl Sidecar for MockSidecar {
+    type SessionStream = Frames;
+
+    async fn session(
+        &self,
+        request: Request<Streaming<proto::Envelope>>,
+    ) -> Result<Response<Self::SessionStream>, Status> {
+        let mut inbound = request.into_inner();
+        let (tx, rx) = mpsc::channel(16);
+        let mode = self.mode;
+        tokio::spawn(async move {
+            let mut caps = CapabilityManifest::new();
+            caps.insert(Capability::Streaming, SupportLevel::Native);
+            let hello = Envelope::hello(
+                BackendIdentity {
+                    id: "mock-grpc".into(),
+                    backend_version: None,
+                    adapter_version: None,
+                },
+                caps,
+            );
+            let _ = tx.send(Ok(convert::encode(&hello).unwrap())).await;
+
+            let Some(Ok(frame)) = inbound.next().await else {
+                return;
+            };
+            let Ok(Envelope::Run { id, work_order }) = convert::decode(frame) else {
+                return;
+            };
+            let reply = match mode {
+                Mode::Ok => {
+                    for text in ["hello ", "from grpc"] {
+                        let event = Envelope::Event {
+                            ref_id: id.clone(),
+                            event: AgentEvent {
+                                ts: chrono::Utc::now(),
+                                kind: AgentEventKind::AssistantDelta { text: text.into() },
+                                ext: None,
+                            },
+                            seq: None,
+                        };
+                        let _ = tx.send(Ok(convert::encode(&event).unwrap())).await;
+                    }
+                    let receipt = ReceiptBuilder::new("mock-grpc")
+                        .work_order_id(work_order.id)
+                        .outcome(Outcome::Complete)
+                        .with_hash()
+                        .unwrap();
+                    Envelope::Final {
+                        ref_id: id,
+                        receipt,
+                    }
+                }
+                Mode::Fatal => Envelope::Fatal {
+                    ref_id: Some(id),
+                    error: "model exploded".into(),
+                    error_code: None,
+                },
+            };
+            let _ = tx.send(Ok(convert::encode(&reply).unwrap())).await;
+        });
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+async fn serve(mode: Mode) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(SidecarServer::new(MockSidecar { mode }))
+            .serve_with_incoming(TcpListenerStream::new(listener)),
+    );
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn runs_stream_events_and_return_the_sidecar_receipt() {
+    let backend = GrpcBackend::new(serve(Mode::Ok).await);
+    let wo = WorkOrderBuilder::new("say hello").build();
+    let (tx, mut rx) = mpsc::channel(16);
+
+    let receipt = backend.run(Uuid::new_v4(), wo.clone(), tx).await.unwrap();
+
+    let mut text = String::new();
+    while let Ok(ev) = rx.try_recv() {
+        if let AgentEventKind::AssistantDelta { text: t } = ev.kind {
+            text.push_str(&t);
+        }
+    }
+    assert_eq!(text, "hello from grpc");
+    assert_eq!(receipt.meta.work_order_id, wo.id);
+    assert_eq!(receipt.outcome, Outcome::Complete);
+    assert!(abp_receipt::verify_hash(&receipt));
+}
+
+#[tokio::test]
+async fn fatal_frames_fail_the_run() {
+    let backend = GrpcBackend::new(serve(Mode::Fatal).await);
+    let (tx, _rx) = mpsc::channel(16);
+    let err = backend
+        .run(Uuid::new_v4(), WorkOrderBuilder::new("x").build(), tx)
+        .await
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("model exploded"), "{err:#}");
+}
+
+#[tokio::test]
+async fn unmet_requirements_are_rejected_after_hello() {
+    let backend = GrpcBackend::new(serve(Mode::Ok).await);
+    let wo = WorkOrderBuilder::new("x")
+        .requirements(CapabilityRequirements {
+            required: vec![CapabilityRequirement {
+                capability: Capability::ToolBash,
+                min_support: MinSupport::Native,
+            }],
+        })
+        .build();
+    let (tx, _rx) = mpsc::channel(16);
+    let err = backend.run(Uuid::new_v4(), wo, tx).await.unwrap_err();
+    assert!(
+        format!("{err:#}").contains("capability requirements"),
+        "{err:#}"
+    );
+}
+
+#[tokio::test]
+async fn unreachable_endpoints_fail_fast() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let backend = GrpcBackend::new(endpoint).with_connect_timeout(Duration::from_secs(2));
+    let (tx, _rx) = mpsc::channel(16);
+    let err = backend
+        .run(Uuid::new_v4(), WorkOrderBuilder::new("x").build(), tx)
+        .await
+        .unwrap_err();
+    assert!(
+        format!("{err:#}").contains("connect to grpc sidecar"),
+        "{err:#}"
+    );
+}
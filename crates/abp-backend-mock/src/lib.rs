@@ -5,7 +5,9 @@
 
 pub mod scenarios;
 
-use abp_backend_core::{Backend, ensure_capability_requirements, extract_execution_mode};
+use abp_backend_core::{
+    Backend, ensure_capability_requirements, extract_execution_mode, extract_seed,
+};
 use abp_core::{
     AgentEvent, AgentEventKind, BackendIdentity, CONTRACT_VERSION, CapabilityManifest, Outcome,
     Receipt, RunMetadata, UsageNormalized, VerificationReport, WorkOrder,
@@ -97,6 +99,10 @@ impl Backend for MockBackend {
             .as_millis() as u64;
 
         let mode = extract_execution_mode(&work_order);
+        let mut usage_raw = json!({"note": "mock"});
+        if let Some(seed) = extract_seed(&work_order) {
+            usage_raw["seed"] = json!(seed);
+        }
 
         let receipt = Receipt {
             meta: RunMetadata {
@@ -110,7 +116,7 @@ impl Backend for MockBackend {
             backend: self.identity(),
             capabilities: self.capabilities(),
             mode,
-            usage_raw: json!({"note": "mock"}),
+            usage_raw,
             usage: UsageNormalized {
                 input_tokens: Some(0),
                 output_tokens: Some(0),
@@ -123,6 +129,7 @@ impl Backend for MockBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: Outcome::Complete,
             receipt_sha256: None,
@@ -4,7 +4,9 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
 
-use abp_backend_core::{Backend, ensure_capability_requirements, extract_execution_mode};
+use abp_backend_core::{
+    Backend, ModelInfo, VendorNamespace, ensure_capability_requirements, extract_execution_mode,
+};
 use abp_core::{
     AgentEvent, AgentEventKind, BackendIdentity, CONTRACT_VERSION, CapabilityManifest, Outcome,
     Receipt, RunMetadata, UsageNormalized, VerificationReport, WorkOrder,
@@ -227,6 +229,15 @@ impl EventSequenceBuilder {
         self
     }
 
+    /// Emit a `Progress` event.
+    pub fn progress(mut self, percent: Option<f64>, message: impl Into<String>) -> Self {
+        self.push(AgentEventKind::Progress {
+            percent,
+            message: message.into(),
+        });
+        self
+    }
+
     /// Emit a `Warning` event.
     pub fn warning(mut self, message: impl Into<String>) -> Self {
         self.push(AgentEventKind::Warning {
@@ -792,6 +803,14 @@ impl<B: Backend + Send + Sync> Backend for MockBackendRecorder<B> {
 
         res
     }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        self.inner.list_models().await
+    }
+
+    fn vendor_namespace(&self) -> Option<VendorNamespace> {
+        self.inner.vendor_namespace()
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -853,6 +872,7 @@ fn build_receipt(
             git_diff: None,
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         },
         outcome: outcome.cloned().unwrap_or(Outcome::Complete),
         receipt_sha256: None,
@@ -42,8 +42,13 @@ fn event_kinds(events: &[AgentEvent]) -> Vec<String> {
             AgentEventKind::CommandExecuted { command, .. } => {
                 format!("CommandExecuted({command})")
             }
+            AgentEventKind::Progress { message, .. } => format!("Progress({message})"),
             AgentEventKind::Warning { message } => format!("Warning({message})"),
             AgentEventKind::Error { message, .. } => format!("Error({message})"),
+            AgentEventKind::RunSummary { outcome, .. } => format!("RunSummary({outcome:?})"),
+            AgentEventKind::ArtifactChunk { name, seq, .. } => {
+                format!("ArtifactChunk({name},{seq})")
+            }
         })
         .collect()
 }
@@ -179,6 +184,17 @@ async fn builder_warning_event() {
     assert!(kinds.contains(&"Warning(deprecated API)".to_string()));
 }
 
+#[tokio::test]
+async fn builder_progress_event() {
+    let scenario = EventSequenceBuilder::new()
+        .progress(Some(50.0), "halfway")
+        .build();
+    let b = ScenarioMockBackend::new(scenario);
+    let (_, events) = run_backend(&b, "progress").await.unwrap();
+    let kinds = event_kinds(&events);
+    assert!(kinds.contains(&"Progress(halfway)".to_string()));
+}
+
 #[tokio::test]
 async fn builder_error_event_does_not_fail_run() {
     let scenario = EventSequenceBuilder::new()
@@ -3,8 +3,9 @@
 #![warn(missing_docs)]
 //! Sidecar backend implementation for JSONL protocol adapters.
 
-use abp_backend_core::{Backend, ensure_capability_requirements};
+use abp_backend_core::{Backend, VendorNamespace, ensure_capability_requirements};
 use abp_core::{AgentEvent, BackendIdentity, CapabilityManifest, Receipt, WorkOrder};
+use abp_error::AbpError;
 use abp_host::{HostError, SidecarClient, SidecarSpec};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
@@ -20,12 +21,26 @@ use uuid::Uuid;
 pub struct SidecarBackend {
     /// Specification describing how to spawn the sidecar process.
     pub spec: SidecarSpec,
+    vendor_namespace: Option<VendorNamespace>,
 }
 
 impl SidecarBackend {
     /// Creates a new sidecar backend from the given spec.
     pub fn new(spec: SidecarSpec) -> Self {
-        Self { spec }
+        Self {
+            spec,
+            vendor_namespace: None,
+        }
+    }
+
+    /// Scope the sidecar to a `config.vendor` namespace (builder pattern).
+    ///
+    /// The runtime validates that namespace before dispatch, and the
+    /// sidecar receives no other backend's namespace.
+    #[must_use]
+    pub fn with_vendor_namespace(mut self, namespace: VendorNamespace) -> Self {
+        self.vendor_namespace = Some(namespace);
+        self
     }
 }
 
@@ -43,6 +58,10 @@ impl Backend for SidecarBackend {
         CapabilityManifest::default()
     }
 
+    fn vendor_namespace(&self) -> Option<VendorNamespace> {
+        self.vendor_namespace
+    }
+
     async fn run(
         &self,
         run_id: Uuid,
@@ -51,7 +70,7 @@ impl Backend for SidecarBackend {
     ) -> Result<Receipt> {
         let client = SidecarClient::spawn(self.spec.clone())
             .await
-            .context("spawn sidecar")?;
+            .map_err(|e| anyhow::Error::new(AbpError::from(e)).context("spawn sidecar"))?;
 
         ensure_capability_requirements(&work_order.requirements, &client.hello.capabilities)
             .context("capability requirements not satisfied")?;
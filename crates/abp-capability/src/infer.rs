@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Capability requirements inferred from what a request contains.
+//!
+//! Callers rarely list the capabilities a request needs, but the request
+//! itself tells: a message with an image needs vision, offered tools need
+//! tool use, a `response_format` needs structured output. [`RequestContent`]
+//! reads these from a work order built by a shim — images in the IR
+//! conversation (`config.vendor["abp"]["conversation"]`), tool definitions
+//! (`config.vendor["abp"]["tools"]`), and an OpenAI-style
+//! `config.vendor["response_format"]` — and [`infer_requirements`] adds the
+//! matching requirements to `work_order.requirements`, so projection and
+//! pre-flight checks judge backends on what the request actually uses:
+//!
+//! | Request content                    | Required capability                     |
+//! |------------------------------------|-----------------------------------------|
+//! | image blocks                       | [`Capability::Vision`]                  |
+//! | tool definitions                   | [`Capability::ToolUse`]                 |
+//! | `response_format` `json_schema`    | [`Capability::StructuredOutputJsonSchema`] |
+//! | `response_format` `json_object`    | [`Capability::JsonMode`]                |
+//!
+//! Inferred requirements accept emulated support. A capability the caller
+//! already requires, or offers as a rung of a capability ladder, is left as
+//! the caller declared it.
+
+use abp_core::ir::{IrContentBlock, IrConversation};
+use abp_core::{Capability, CapabilityLadder, CapabilityRequirement, MinSupport, WorkOrder};
+use serde_json::Value;
+
+/// The parts of a request that call for a capability.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestContent {
+    /// The conversation contains images.
+    pub images: bool,
+    /// Tools are offered to the model.
+    pub tools: bool,
+    /// Output must match a JSON schema.
+    pub json_schema: bool,
+    /// Output must be a JSON object.
+    pub json_object: bool,
+}
+
+impl RequestContent {
+    /// Inspect the content a shim recorded on `work_order`.
+    #[must_use]
+    pub fn from_work_order(work_order: &WorkOrder) -> Self {
+        let vendor = &work_order.config.vendor;
+        let abp = vendor.get("abp");
+        let images = abp
+            .and_then(|abp| abp.get("conversation"))
+            .and_then(|v| serde_json::from_value::<IrConversation>(v.clone()).ok())
+            .is_some_and(|conv| {
+                conv.messages
+                    .iter()
+                    .any(|m| m.content.iter().any(has_image))
+            });
+        let tools = abp
+            .and_then(|abp| abp.get("tools"))
+            .and_then(Value::as_array)
+            .is_some_and(|tools| !tools.is_empty());
+        let format = vendor
+            .get("response_format")
+            .and_then(|f| f.get("type"))
+            .and_then(Value::as_str);
+        Self {
+            images,
+            tools,
+            json_schema: format == Some("json_schema"),
+            json_object: format == Some("json_object"),
+        }
+    }
+
+    /// The capabilities this content needs, in table order.
+    #[must_use]
+    pub fn capabilities(&self) -> Vec<Capability> {
+        [
+            (self.images, Capability::Vision),
+            (self.tools, Capability::ToolUse),
+            (self.json_schema, Capability::StructuredOutputJsonSchema),
+            (self.json_object, Capability::JsonMode),
+        ]
+        .into_iter()
+        .filter_map(|(needed, cap)| needed.then_some(cap))
+        .collect()
+    }
+
+    /// Add the requirements this content needs to `work_order`.
+    ///
+    /// Returns the capabilities that were added; those already required or
+    /// covered by a capability ladder are skipped.
+    pub fn apply(&self, work_order: &mut WorkOrder) -> Vec<Capability> {
+        let laddered: Vec<Capability> = ladders(work_order)
+            .into_iter()
+            .flat_map(|l| l.rungs.into_iter().map(|r| r.capability))
+            .collect();
+        let mut added = Vec::new();
+        for capability in self.capabilities() {
+            let declared = work_order
+                .requirements
+                .required
+                .iter()
+                .any(|r| r.capability == capability);
+            if declared || laddered.contains(&capability) {
+                continue;
+            }
+            work_order
+                .requirements
+                .required
+                .push(CapabilityRequirement {
+                    capability: capability.clone(),
+                    min_support: MinSupport::Emulated,
+                });
+            added.push(capability);
+        }
+        added
+    }
+}
+
+/// Add the requirements implied by `work_order`'s content to it.
+///
+/// Shorthand for [`RequestContent::from_work_order`] followed by
+/// [`RequestContent::apply`]; returns the capabilities that were added.
+pub fn infer_requirements(work_order: &mut WorkOrder) -> Vec<Capability> {
+    RequestContent::from_work_order(work_order).apply(work_order)
+}
+
+fn has_image(block: &IrContentBlock) -> bool {
+    match block {
+        IrContentBlock::Image { .. } => true,
+        IrContentBlock::ToolResult { content, .. } => content.iter().any(has_image),
+        _ => false,
+    }
+}
+
+fn ladders(work_order: &WorkOrder) -> Vec<CapabilityLadder> {
+    work_order
+        .config
+        .vendor
+        .get("abp")
+        .and_then(|abp| abp.get("capability_ladders"))
+        .and_then(Value::as_array)
+        .map(|ladders| {
+            ladders
+                .iter()
+                .filter_map(|l| serde_json::from_value(l.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
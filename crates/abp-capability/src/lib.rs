@@ -29,9 +29,22 @@
 //! When a capability is not natively supported, the [`emulation`] module and
 //! [`EmulationStrategy`] describe how ABP can bridge the gap — client-side
 //! polyfill, server fallback, or best-effort approximation.
+//!
+//! ## Per-model manifests
+//!
+//! The [`models`] module derives model-specific manifests from registry
+//! metadata (vision, JSON mode, tool calling, context limits) so a backend
+//! serving many models negotiates against the model actually requested.
+//!
+//! ## Inferred requirements
+//!
+//! The [`infer`] module derives requirements from a request's content —
+//! images, tools, `response_format` — so shims need not enumerate them.
 
 pub mod compare;
 pub mod emulation;
+pub mod infer;
+pub mod models;
 pub mod negotiate;
 pub mod preflight;
 pub mod registry;
@@ -432,7 +445,8 @@ pub fn default_emulation_strategy(cap: &Capability) -> EmulationStrategy {
         | Capability::ToolAskUser
         | Capability::HooksPreToolUse
         | Capability::HooksPostToolUse
-        | Capability::Checkpointing => EmulationStrategy::ClientSide,
+        | Capability::Checkpointing
+        | Capability::ResponseLanguage => EmulationStrategy::ClientSide,
 
         // Server can provide degraded version
         Capability::FunctionCalling
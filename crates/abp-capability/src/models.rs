@@ -0,0 +1,574 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Per-model capability inference.
+//!
+//! A single backend frequently serves many models with very different
+//! feature sets (e.g. `gpt-4o` accepts images while `gpt-3.5-turbo` does
+//! not). [`ModelProfile`] captures the model registry metadata that matters
+//! for negotiation, and [`ModelCatalog`] derives a model-specific
+//! [`CapabilityManifest`] by overlaying that metadata on top of the
+//! backend-level manifest.
+
+use crate::{NegotiationResult, negotiate_capabilities};
+use abp_core::{Capability, CapabilityManifest, SupportLevel as CoreSupportLevel};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+// ---------------------------------------------------------------------------
+// ModelProfile
+// ---------------------------------------------------------------------------
+
+/// Registry metadata describing what a single model can do.
+///
+/// # Examples
+///
+/// ```
+/// use abp_capability::models::ModelProfile;
+/// use abp_core::{Capability, SupportLevel};
+///
+/// let profile = ModelProfile::new("gpt-4o")
+///     .vision(true)
+///     .tool_calling(true)
+///     .max_context_tokens(128_000);
+/// let manifest = profile.infer_manifest();
+/// assert!(matches!(manifest.get(&Capability::Vision), Some(SupportLevel::Native)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelProfile {
+    /// Model identifier (e.g. `"gpt-4o"`, `"claude-3-5-sonnet"`).
+    pub model: String,
+    /// Accepts image inputs. `None` when the metadata does not say.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vision: Option<bool>,
+    /// Supports a JSON-only response mode. `None` when the metadata does not say.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_mode: Option<bool>,
+    /// Supports schema-constrained structured output. `None` when the metadata does not say.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub structured_output: Option<bool>,
+    /// Supports tool / function calling. `None` when the metadata does not say.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calling: Option<bool>,
+    /// Supports token streaming. `None` when the metadata does not say.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub streaming: Option<bool>,
+    /// Supports extended thinking / reasoning output. `None` when the metadata does not say.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extended_thinking: Option<bool>,
+    /// Maximum context window in tokens, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_context_tokens: Option<u64>,
+    /// Maximum output tokens per response, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+}
+
+impl ModelProfile {
+    /// Create a profile that says nothing about any feature.
+    #[must_use]
+    pub fn new(model: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            vision: None,
+            json_mode: None,
+            structured_output: None,
+            tool_calling: None,
+            streaming: None,
+            extended_thinking: None,
+            max_context_tokens: None,
+            max_output_tokens: None,
+        }
+    }
+
+    /// Set image-input support.
+    #[must_use]
+    pub fn vision(mut self, enabled: bool) -> Self {
+        self.vision = Some(enabled);
+        self
+    }
+
+    /// Set JSON mode support.
+    #[must_use]
+    pub fn json_mode(mut self, enabled: bool) -> Self {
+        self.json_mode = Some(enabled);
+        self
+    }
+
+    /// Set structured-output (JSON Schema) support.
+    #[must_use]
+    pub fn structured_output(mut self, enabled: bool) -> Self {
+        self.structured_output = Some(enabled);
+        self
+    }
+
+    /// Set tool-calling support.
+    #[must_use]
+    pub fn tool_calling(mut self, enabled: bool) -> Self {
+        self.tool_calling = Some(enabled);
+        self
+    }
+
+    /// Set streaming support.
+    #[must_use]
+    pub fn streaming(mut self, enabled: bool) -> Self {
+        self.streaming = Some(enabled);
+        self
+    }
+
+    /// Set extended-thinking support.
+    #[must_use]
+    pub fn extended_thinking(mut self, enabled: bool) -> Self {
+        self.extended_thinking = Some(enabled);
+        self
+    }
+
+    /// Set the maximum context window in tokens.
+    #[must_use]
+    pub fn max_context_tokens(mut self, tokens: u64) -> Self {
+        self.max_context_tokens = Some(tokens);
+        self
+    }
+
+    /// Set the maximum output tokens per response.
+    #[must_use]
+    pub fn max_output_tokens(mut self, tokens: u64) -> Self {
+        self.max_output_tokens = Some(tokens);
+        self
+    }
+
+    /// Derive the capability entries implied by this profile.
+    ///
+    /// Only capabilities the metadata speaks to are included; everything
+    /// else is left for the backend-level manifest to decide.
+    #[must_use]
+    pub fn infer_manifest(&self) -> CapabilityManifest {
+        let level = |enabled: bool| {
+            if enabled {
+                CoreSupportLevel::Native
+            } else {
+                CoreSupportLevel::Unsupported
+            }
+        };
+
+        let flags = [
+            (Capability::Vision, self.vision),
+            (Capability::ImageInput, self.vision),
+            (Capability::JsonMode, self.json_mode),
+            (
+                Capability::StructuredOutputJsonSchema,
+                self.structured_output,
+            ),
+            (Capability::ToolUse, self.tool_calling),
+            (Capability::FunctionCalling, self.tool_calling),
+            (Capability::Streaming, self.streaming),
+            (Capability::ExtendedThinking, self.extended_thinking),
+        ];
+        let mut manifest: CapabilityManifest = flags
+            .into_iter()
+            .filter_map(|(cap, enabled)| enabled.map(|e| (cap, level(e))))
+            .collect();
+        if self.max_output_tokens.is_some() {
+            manifest.insert(Capability::MaxTokens, CoreSupportLevel::Native);
+        }
+        manifest
+    }
+
+    /// Overlay this profile on a backend-level manifest.
+    ///
+    /// The profile can only take capabilities away: one the model lacks is
+    /// downgraded to `Unsupported` unless the backend advertises it as
+    /// `Emulated` or `Restricted`, in which case the backend's answer wins —
+    /// emulation does not depend on the model. A capability the model has
+    /// keeps whatever level the backend advertises, and capabilities the
+    /// profile does not mention are left untouched.
+    #[must_use]
+    pub fn apply_to(&self, base: &CapabilityManifest) -> CapabilityManifest {
+        let mut manifest = base.clone();
+        for (cap, inferred) in self.infer_manifest() {
+            if !matches!(inferred, CoreSupportLevel::Unsupported) {
+                continue;
+            }
+            match manifest.get(&cap) {
+                None | Some(CoreSupportLevel::Emulated | CoreSupportLevel::Restricted { .. }) => {}
+                Some(_) => {
+                    manifest.insert(cap, inferred);
+                }
+            }
+        }
+        manifest
+    }
+}
+
+// ---------------------------------------------------------------------------
+// ModelCatalog
+// ---------------------------------------------------------------------------
+
+/// Lookup table of [`ModelProfile`]s keyed by model identifier.
+///
+/// Lookups first try an exact match and then fall back to the longest
+/// registered prefix, so dated snapshots such as `gpt-4o-2024-08-06`
+/// resolve to the `gpt-4o` profile.
+///
+/// # Examples
+///
+/// ```
+/// use abp_capability::models::ModelCatalog;
+/// use abp_core::{Capability, SupportLevel};
+///
+/// let catalog = ModelCatalog::with_defaults();
+/// let profile = catalog.lookup("gpt-4o-2024-08-06").unwrap();
+/// assert_eq!(profile.model, "gpt-4o");
+/// assert_eq!(catalog.context_limit("gpt-4o"), Some(128_000));
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModelCatalog {
+    profiles: BTreeMap<String, ModelProfile>,
+}
+
+impl ModelCatalog {
+    /// Create an empty catalog.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a catalog pre-populated with well-known models.
+    #[must_use]
+    pub fn with_defaults() -> Self {
+        let mut catalog = Self::new();
+        for profile in default_profiles() {
+            catalog.register(profile);
+        }
+        catalog
+    }
+
+    /// Register (or replace) a model profile.
+    pub fn register(&mut self, profile: ModelProfile) {
+        self.profiles.insert(profile.model.clone(), profile);
+    }
+
+    /// Remove a model profile. Returns `true` if it existed.
+    pub fn unregister(&mut self, model: &str) -> bool {
+        self.profiles.remove(model).is_some()
+    }
+
+    /// Number of registered profiles.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.profiles.len()
+    }
+
+    /// Returns `true` if no profiles are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+
+    /// Return all registered model identifiers.
+    #[must_use]
+    pub fn models(&self) -> Vec<&str> {
+        self.profiles.keys().map(String::as_str).collect()
+    }
+
+    /// Look up a profile by exact model id, falling back to the longest
+    /// registered prefix.
+    #[must_use]
+    pub fn lookup(&self, model: &str) -> Option<&ModelProfile> {
+        if let Some(profile) = self.profiles.get(model) {
+            return Some(profile);
+        }
+        self.profiles
+            .iter()
+            .filter(|(id, _)| model.starts_with(id.as_str()))
+            .max_by_key(|(id, _)| id.len())
+            .map(|(_, profile)| profile)
+    }
+
+    /// Derive the manifest for `model` served by a backend advertising `base`.
+    ///
+    /// Returns `base` unchanged when the model is unknown.
+    #[must_use]
+    pub fn manifest_for(&self, base: &CapabilityManifest, model: &str) -> CapabilityManifest {
+        match self.lookup(model) {
+            Some(profile) => profile.apply_to(base),
+            None => base.clone(),
+        }
+    }
+
+    /// Maximum context window for `model`, if known.
+    #[must_use]
+    pub fn context_limit(&self, model: &str) -> Option<u64> {
+        self.lookup(model).and_then(|p| p.max_context_tokens)
+    }
+
+    /// Negotiate `required` against the model-specific manifest.
+    #[must_use]
+    pub fn negotiate_for_model(
+        &self,
+        base: &CapabilityManifest,
+        model: &str,
+        required: &[Capability],
+    ) -> NegotiationResult {
+        negotiate_capabilities(required, &self.manifest_for(base, model))
+    }
+}
+
+fn default_profiles() -> Vec<ModelProfile> {
+    // The built-in profiles describe each model fully: anything not switched
+    // on below is a feature the model lacks.
+    let known = |id: &str| {
+        ModelProfile::new(id)
+            .vision(false)
+            .json_mode(false)
+            .structured_output(false)
+            .tool_calling(false)
+            .streaming(false)
+            .extended_thinking(false)
+    };
+    let chat = |id: &str| known(id).streaming(true).tool_calling(true).json_mode(true);
+    vec![
+        // OpenAI
+        chat("gpt-4o")
+            .vision(true)
+            .structured_output(true)
+            .max_context_tokens(128_000)
+            .max_output_tokens(16_384),
+        chat("gpt-4o-mini")
+            .vision(true)
+            .structured_output(true)
+            .max_context_tokens(128_000)
+            .max_output_tokens(16_384),
+        chat("gpt-4-turbo")
+            .vision(true)
+            .max_context_tokens(128_000)
+            .max_output_tokens(4_096),
+        chat("gpt-3.5-turbo")
+            .max_context_tokens(16_385)
+            .max_output_tokens(4_096),
+        chat("o1")
+            .vision(true)
+            .structured_output(true)
+            .extended_thinking(true)
+            .max_context_tokens(200_000)
+            .max_output_tokens(100_000),
+        chat("o3-mini")
+            .structured_output(true)
+            .extended_thinking(true)
+            .max_context_tokens(200_000)
+            .max_output_tokens(100_000),
+        // Anthropic
+        known("claude-3-5-sonnet")
+            .streaming(true)
+            .tool_calling(true)
+            .vision(true)
+            .max_context_tokens(200_000)
+            .max_output_tokens(8_192),
+        known("claude-3-5-haiku")
+            .streaming(true)
+            .tool_calling(true)
+            .max_context_tokens(200_000)
+            .max_output_tokens(8_192),
+        known("claude-3-7-sonnet")
+            .streaming(true)
+            .tool_calling(true)
+            .vision(true)
+            .extended_thinking(true)
+            .max_context_tokens(200_000)
+            .max_output_tokens(64_000),
+        known("claude-3-opus")
+            .streaming(true)
+            .tool_calling(true)
+            .vision(true)
+            .max_context_tokens(200_000)
+            .max_output_tokens(4_096),
+        // Google
+        chat("gemini-1.5-pro")
+            .vision(true)
+            .structured_output(true)
+            .max_context_tokens(2_097_152)
+            .max_output_tokens(8_192),
+        chat("gemini-1.5-flash")
+            .vision(true)
+            .structured_output(true)
+            .max_context_tokens(1_048_576)
+            .max_output_tokens(8_192),
+        chat("gemini-2.0-flash")
+            .vision(true)
+            .structured_output(true)
+            .max_context_tokens(1_048_576)
+            .max_output_tokens(8_192),
+        // Moonshot
+        chat("moonshot-v1-8k").max_context_tokens(8_192),
+        chat("moonshot-v1-32k").max_context_tokens(32_768),
+        chat("moonshot-v1-128k").max_context_tokens(131_072),
+    ]
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_from(entries: &[(Capability, CoreSupportLevel)]) -> CapabilityManifest {
+        entries.iter().cloned().collect()
+    }
+
+    #[test]
+    fn new_profile_says_nothing() {
+        let m = ModelProfile::new("m").infer_manifest();
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn infer_manifest_maps_flags() {
+        let p = ModelProfile::new("m")
+            .vision(true)
+            .tool_calling(true)
+            .json_mode(false)
+            .max_output_tokens(1024);
+        let m = p.infer_manifest();
+        assert_eq!(m[&Capability::Vision], CoreSupportLevel::Native);
+        assert_eq!(m[&Capability::ImageInput], CoreSupportLevel::Native);
+        assert_eq!(m[&Capability::ToolUse], CoreSupportLevel::Native);
+        assert_eq!(m[&Capability::FunctionCalling], CoreSupportLevel::Native);
+        assert_eq!(m[&Capability::JsonMode], CoreSupportLevel::Unsupported);
+        assert_eq!(m[&Capability::MaxTokens], CoreSupportLevel::Native);
+        assert!(!m.contains_key(&Capability::Streaming));
+    }
+
+    #[test]
+    fn apply_to_downgrades_native_backend_caps() {
+        let base = manifest_from(&[
+            (Capability::Vision, CoreSupportLevel::Native),
+            (Capability::Temperature, CoreSupportLevel::Native),
+        ]);
+        let m = ModelProfile::new("text-only").vision(false).apply_to(&base);
+        assert_eq!(m[&Capability::Vision], CoreSupportLevel::Unsupported);
+        // Capabilities the profile does not speak to are untouched.
+        assert_eq!(m[&Capability::Temperature], CoreSupportLevel::Native);
+    }
+
+    #[test]
+    fn apply_to_keeps_backend_emulation() {
+        let base = manifest_from(&[(
+            Capability::StructuredOutputJsonSchema,
+            CoreSupportLevel::Emulated,
+        )]);
+        let m = ModelProfile::new("m")
+            .structured_output(false)
+            .apply_to(&base);
+        assert_eq!(
+            m[&Capability::StructuredOutputJsonSchema],
+            CoreSupportLevel::Emulated
+        );
+    }
+
+    #[test]
+    fn apply_to_never_upgrades_backend_caps() {
+        let base = manifest_from(&[
+            (Capability::Vision, CoreSupportLevel::Emulated),
+            (Capability::ToolUse, CoreSupportLevel::Unsupported),
+        ]);
+        let m = ModelProfile::new("m")
+            .vision(true)
+            .tool_calling(true)
+            .streaming(true)
+            .apply_to(&base);
+        assert_eq!(m, base);
+    }
+
+    #[test]
+    fn apply_to_leaves_unmentioned_caps_alone() {
+        let base = manifest_from(&[
+            (Capability::Vision, CoreSupportLevel::Native),
+            (Capability::Streaming, CoreSupportLevel::Native),
+            (Capability::ToolUse, CoreSupportLevel::Native),
+            (Capability::JsonMode, CoreSupportLevel::Native),
+        ]);
+        let m = ModelProfile::new("vision-only")
+            .vision(true)
+            .apply_to(&base);
+        assert_eq!(m, base);
+    }
+
+    #[test]
+    fn catalog_exact_and_prefix_lookup() {
+        let catalog = ModelCatalog::with_defaults();
+        assert_eq!(catalog.lookup("gpt-4o").unwrap().model, "gpt-4o");
+        assert_eq!(catalog.lookup("gpt-4o-mini").unwrap().model, "gpt-4o-mini");
+        assert_eq!(
+            catalog.lookup("gpt-4o-mini-2024-07-18").unwrap().model,
+            "gpt-4o-mini"
+        );
+        assert_eq!(
+            catalog.lookup("claude-3-5-sonnet-20241022").unwrap().model,
+            "claude-3-5-sonnet"
+        );
+        assert!(catalog.lookup("unknown-model").is_none());
+    }
+
+    #[test]
+    fn catalog_manifest_for_unknown_model_is_base() {
+        let catalog = ModelCatalog::with_defaults();
+        let base = manifest_from(&[(Capability::Vision, CoreSupportLevel::Native)]);
+        assert_eq!(catalog.manifest_for(&base, "mystery"), base);
+    }
+
+    #[test]
+    fn catalog_distinguishes_models_on_same_backend() {
+        let catalog = ModelCatalog::with_defaults();
+        let base = crate::openai_gpt4o_manifest();
+        let gpt4o = catalog.manifest_for(&base, "gpt-4o");
+        let gpt35 = catalog.manifest_for(&base, "gpt-3.5-turbo");
+        assert_eq!(gpt4o[&Capability::Vision], CoreSupportLevel::Native);
+        assert_eq!(gpt35[&Capability::Vision], CoreSupportLevel::Unsupported);
+    }
+
+    #[test]
+    fn catalog_context_limit() {
+        let catalog = ModelCatalog::with_defaults();
+        assert_eq!(catalog.context_limit("claude-3-5-haiku"), Some(200_000));
+        assert_eq!(catalog.context_limit("nope"), None);
+    }
+
+    #[test]
+    fn catalog_negotiate_for_model() {
+        let catalog = ModelCatalog::with_defaults();
+        let base = crate::openai_gpt4o_manifest();
+        let ok = catalog.negotiate_for_model(&base, "gpt-4o", &[Capability::Vision]);
+        assert!(ok.is_viable());
+        let bad = catalog.negotiate_for_model(&base, "gpt-3.5-turbo", &[Capability::Vision]);
+        assert!(!bad.is_viable());
+    }
+
+    #[test]
+    fn catalog_register_and_unregister() {
+        let mut catalog = ModelCatalog::new();
+        assert!(catalog.is_empty());
+        catalog.register(ModelProfile::new("local-llm").streaming(true));
+        assert_eq!(catalog.len(), 1);
+        assert_eq!(catalog.models(), vec!["local-llm"]);
+        assert!(catalog.unregister("local-llm"));
+        assert!(!catalog.unregister("local-llm"));
+    }
+
+    #[test]
+    fn profile_serde_roundtrip() {
+        let p = ModelProfile::new("gpt-4o")
+            .vision(true)
+            .max_context_tokens(128_000);
+        let json = serde_json::to_string(&p).unwrap();
+        let back: ModelProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(p, back);
+    }
+
+    #[test]
+    fn profile_deserializes_with_missing_flags() {
+        let p: ModelProfile = serde_json::from_str(r#"{"model":"x","vision":true}"#).unwrap();
+        assert_eq!(p.vision, Some(true));
+        assert_eq!(p.tool_calling, None);
+        assert_eq!(p.max_context_tokens, None);
+    }
+}
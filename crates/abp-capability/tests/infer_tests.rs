@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for capability requirements inferred from request content.
+
+use abp_capability::infer::{RequestContent, infer_requirements};
+use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole, IrToolDefinition};
+use abp_core::{
+    Capability, CapabilityLadder, CapabilityRequirement, CapabilityRequirements, MinSupport,
+    WorkOrder, WorkOrderBuilder,
+};
+use serde_json::json;
+
+fn with_image() -> IrConversation {
+    IrConversation {
+        messages: vec![IrMessage::new(
+            IrRole::User,
+            vec![
+                IrContentBlock::Text {
+                    text: "what is this?".into(),
+                },
+                IrContentBlock::Image {
+                    media_type: "image/png".into(),
+                    data: "iVBORw0KGgo=".into(),
+                },
+            ],
+        )],
+    }
+}
+
+fn tool() -> IrToolDefinition {
+    IrToolDefinition {
+        name: "lookup".into(),
+        description: "Look something up".into(),
+        parameters: json!({"type": "object"}),
+    }
+}
+
+/// Required capabilities, each with whether it must be native.
+fn required(wo: &WorkOrder) -> Vec<(Capability, bool)> {
+    wo.requirements
+        .required
+        .iter()
+        .map(|r| {
+            (
+                r.capability.clone(),
+                matches!(r.min_support, MinSupport::Native),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn plain_text_requests_need_nothing() {
+    let mut wo = WorkOrderBuilder::new("hello")
+        .conversation(IrConversation {
+            messages: vec![IrMessage::text(IrRole::User, "hello")],
+        })
+        .build();
+    assert_eq!(
+        RequestContent::from_work_order(&wo),
+        RequestContent::default()
+    );
+    assert!(infer_requirements(&mut wo).is_empty());
+    assert!(wo.requirements.required.is_empty());
+}
+
+#[test]
+fn images_tools_and_response_format_become_requirements() {
+    let mut wo = WorkOrderBuilder::new("describe")
+        .conversation(with_image())
+        .tools(vec![tool()])
+        .build();
+    wo.config.vendor.insert(
+        "response_format".into(),
+        json!({"type": "json_schema", "json_schema": {"name": "x", "schema": {}}}),
+    );
+
+    let added = infer_requirements(&mut wo);
+    assert_eq!(
+        added,
+        [
+            Capability::Vision,
+            Capability::ToolUse,
+            Capability::StructuredOutputJsonSchema
+        ]
+    );
+    assert!(required(&wo).iter().all(|(_, native)| !native));
+
+    // Inferring again adds nothing.
+    assert!(infer_requirements(&mut wo).is_empty());
+    assert_eq!(wo.requirements.required.len(), 3);
+}
+
+#[test]
+fn json_object_format_needs_json_mode() {
+    let mut wo = WorkOrderBuilder::new("json").build();
+    wo.config
+        .vendor
+        .insert("response_format".into(), json!({"type": "json_object"}));
+    assert_eq!(infer_requirements(&mut wo), [Capability::JsonMode]);
+
+    let mut text = WorkOrderBuilder::new("text").build();
+    text.config
+        .vendor
+        .insert("response_format".into(), json!({"type": "text"}));
+    assert!(infer_requirements(&mut text).is_empty());
+}
+
+#[test]
+fn images_inside_tool_results_count() {
+    let conv = IrConversation {
+        messages: vec![IrMessage::new(
+            IrRole::User,
+            vec![IrContentBlock::ToolResult {
+                tool_use_id: "t1".into(),
+                content: vec![IrContentBlock::Image {
+                    media_type: "image/jpeg".into(),
+                    data: "/9j/".into(),
+                }],
+                is_error: false,
+            }],
+        )],
+    };
+    let wo = WorkOrderBuilder::new("screenshot")
+        .conversation(conv)
+        .build();
+    assert!(RequestContent::from_work_order(&wo).images);
+}
+
+#[test]
+fn caller_declarations_and_ladders_win() {
+    let mut wo = WorkOrderBuilder::new("describe")
+        .conversation(with_image())
+        .tools(vec![tool()])
+        .requirements(CapabilityRequirements {
+            required: vec![CapabilityRequirement {
+                capability: Capability::Vision,
+                min_support: MinSupport::Native,
+            }],
+        })
+        .capability_ladder(
+            CapabilityLadder::new()
+                .rung(Capability::ToolUse, MinSupport::Native)
+                .rung(Capability::FunctionCalling, MinSupport::Emulated),
+        )
+        .build();
+
+    assert!(infer_requirements(&mut wo).is_empty());
+    assert_eq!(required(&wo), [(Capability::Vision, true)]);
+}
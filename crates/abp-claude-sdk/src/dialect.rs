@@ -1,14 +1,18 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 //! Anthropic Claude dialect: config, request/response types, and mapping logic.
 
+use abp_core::ir::{IrToolChoice, IrToolDefinition};
 use abp_core::{
     AgentEvent, AgentEventKind, Capability, CapabilityManifest, SupportLevel, WorkOrder,
 };
+use abp_sidecar_sdk::VendorConfig;
 use chrono::Utc;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use crate::vendor::ClaudeVendorConfig;
+
 /// Version string for this dialect adapter.
 pub const DIALECT_VERSION: &str = "claude/v0.1";
 
@@ -207,6 +211,12 @@ pub struct ClaudeRequest {
     /// Extended thinking configuration.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub thinking: Option<ThinkingConfig>,
+    /// Tools the model may call.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<ClaudeToolDef>,
+    /// How the model should choose among `tools`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ClaudeToolChoice>,
 }
 
 /// A single message in the Claude conversation format.
@@ -503,35 +513,98 @@ pub fn map_stop_reason(reason: ClaudeStopReason) -> &'static str {
 
 /// Map an ABP [`WorkOrder`] to a [`ClaudeRequest`].
 ///
-/// Uses the work order task as the initial user message and applies
-/// config defaults where the work order does not specify overrides.
+/// Sends the attached conversation history when there is one (see
+/// [`extract_conversation`](abp_backend_core::extract_conversation)); its
+/// system message takes precedence over the configured system prompt and
+/// context snippets follow as a trailing user message. Otherwise uses the
+/// work order task as the initial user message. Knobs under
+/// `config.vendor.claude` (see [`ClaudeVendorConfig`]) override the config
+/// defaults; other backends' namespaces are ignored.
 pub fn map_work_order(wo: &WorkOrder, config: &ClaudeConfig) -> ClaudeRequest {
+    let vendor = ClaudeVendorConfig::from_work_order_or_default(wo);
     let model = wo
         .config
         .model
         .as_deref()
+        .or(vendor.model.as_deref())
         .unwrap_or(&config.model)
         .to_string();
 
-    let system = config.system_prompt.clone();
+    let mut system = config.system_prompt.clone();
 
-    let mut user_content = wo.task.clone();
+    let mut snippets = String::new();
     for snippet in &wo.context.snippets {
-        user_content.push_str(&format!(
+        snippets.push_str(&format!(
             "\n\n--- {} ---\n{}",
             snippet.name, snippet.content
         ));
     }
+    let user_message = |content: String| ClaudeMessage {
+        role: "user".into(),
+        content,
+    };
+
+    let messages = match abp_backend_core::extract_conversation(wo) {
+        Some(conv) if !conv.is_empty() => {
+            if let Some(prompt) = crate::lowering::extract_system_prompt(&conv) {
+                system = Some(prompt);
+            }
+            let mut messages = crate::lowering::from_ir(&conv);
+            if !snippets.is_empty() {
+                messages.push(user_message(snippets.trim_start().to_string()));
+            }
+            messages
+        }
+        _ => vec![user_message(format!("{}{snippets}", wo.task))],
+    };
+
+    // Anthropic has no "none" tool choice that keeps tools advertised, so a
+    // `None` policy withholds the tools instead.
+    let tool_choice = abp_backend_core::extract_tool_choice(wo);
+    let tools = if tool_choice == Some(IrToolChoice::None) {
+        Vec::new()
+    } else {
+        abp_backend_core::extract_tools(wo)
+            .iter()
+            .map(tool_def_from_ir)
+            .collect()
+    };
+    let tool_choice = tool_choice
+        .filter(|_| !tools.is_empty())
+        .and_then(|choice| tool_choice_from_ir(&choice));
 
     ClaudeRequest {
         model,
-        max_tokens: config.max_tokens,
+        max_tokens: vendor.max_tokens.unwrap_or(config.max_tokens),
         system,
-        messages: vec![ClaudeMessage {
-            role: "user".into(),
-            content: user_content,
-        }],
+        messages,
         thinking: config.thinking.clone(),
+        tools,
+        tool_choice,
+    }
+}
+
+/// Convert an IR tool definition to the Anthropic tool format.
+#[must_use]
+pub fn tool_def_from_ir(def: &IrToolDefinition) -> ClaudeToolDef {
+    ClaudeToolDef {
+        name: def.name.clone(),
+        description: def.description.clone(),
+        input_schema: def.parameters.clone(),
+    }
+}
+
+/// Convert an IR tool-choice policy to the Anthropic form.
+///
+/// [`IrToolChoice::Required`] maps to `any`; [`IrToolChoice::None`] has no
+/// Anthropic equivalent and yields `None`.
+#[must_use]
+pub fn tool_choice_from_ir(choice: &IrToolChoice) -> Option<ClaudeToolChoice> {
+    match choice {
+        IrToolChoice::Auto => Some(ClaudeToolChoice::Auto {}),
+        IrToolChoice::Required => Some(ClaudeToolChoice::Any {}),
+        IrToolChoice::Tool { name } => Some(ClaudeToolChoice::Tool { name: name.clone() }),
+        IrToolChoice::None => None,
     }
 }
 
@@ -752,6 +825,7 @@ pub fn verify_passthrough_fidelity(events: &[ClaudeStreamEvent]) -> bool {
 mod tests {
     use super::*;
     use abp_core::WorkOrderBuilder;
+    use abp_core::ir::{IrConversation, IrMessage, IrRole};
 
     #[test]
     fn default_config_has_sensible_values() {
@@ -772,6 +846,57 @@ mod tests {
         assert!(req.messages[0].content.contains("Refactor auth module"));
     }
 
+    #[test]
+    fn map_work_order_sends_conversation_history() {
+        let conv = IrConversation::new()
+            .push(IrMessage::text(IrRole::System, "Be terse"))
+            .push(IrMessage::text(IrRole::User, "What is 2+2?"))
+            .push(IrMessage::text(IrRole::Assistant, "4"))
+            .push(IrMessage::text(IrRole::User, "And times 3?"));
+        let wo = WorkOrderBuilder::new("And times 3?")
+            .conversation(conv)
+            .build();
+        let req = map_work_order(&wo, &ClaudeConfig::default());
+
+        assert_eq!(req.system.as_deref(), Some("Be terse"));
+        let roles: Vec<_> = req.messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user", "assistant", "user"]);
+        assert_eq!(req.messages[1].content, "4");
+    }
+
+    #[test]
+    fn map_work_order_advertises_work_order_tools() {
+        let wo = WorkOrderBuilder::new("Weather?")
+            .tools(vec![IrToolDefinition {
+                name: "get_weather".into(),
+                description: "Current weather".into(),
+                parameters: serde_json::json!({"type": "object"}),
+            }])
+            .tool_choice(IrToolChoice::Required)
+            .build();
+        let req = map_work_order(&wo, &ClaudeConfig::default());
+
+        assert_eq!(req.tools.len(), 1);
+        assert_eq!(req.tools[0].input_schema["type"], "object");
+        assert_eq!(req.tool_choice, Some(ClaudeToolChoice::Any {}));
+    }
+
+    #[test]
+    fn map_work_order_withholds_tools_for_none_choice() {
+        let wo = WorkOrderBuilder::new("Weather?")
+            .tools(vec![IrToolDefinition {
+                name: "get_weather".into(),
+                description: String::new(),
+                parameters: serde_json::json!({"type": "object"}),
+            }])
+            .tool_choice(IrToolChoice::None)
+            .build();
+        let req = map_work_order(&wo, &ClaudeConfig::default());
+
+        assert!(req.tools.is_empty());
+        assert!(req.tool_choice.is_none());
+    }
+
     #[test]
     fn map_work_order_respects_model_override() {
         let wo = WorkOrderBuilder::new("task")
@@ -4,8 +4,19 @@
 //! `to_ir` converts a slice of `ClaudeMessage`s (plus optional system
 //! prompt) into an `IrConversation`, and `from_ir` converts an
 //! `IrConversation` back into Claude messages.
-
-use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole};
+//!
+//! Prompt-caching breakpoints
+//! ([`IrMessage::cache_breakpoints`](abp_core::ir::IrMessage::cache_breakpoints))
+//! become a `cache_control` object on the content blocks they mark, and a
+//! `cache_control` on a Claude content block marks the same IR block: the
+//! last block through
+//! [`IrMessage::with_cache_control`](abp_core::ir::IrMessage::with_cache_control),
+//! any other through
+//! [`IrMessage::with_block_cache_control`](abp_core::ir::IrMessage::with_block_cache_control).
+
+use abp_core::ir::{
+    CACHE_CONTROL_KEY, IrCacheControl, IrContentBlock, IrConversation, IrMessage, IrRole,
+};
 
 use crate::dialect::{ClaudeContentBlock, ClaudeImageSource, ClaudeMessage};
 
@@ -80,7 +91,17 @@ fn message_to_ir(msg: &ClaudeMessage) -> IrMessage {
     // Try parsing content as a JSON array of ClaudeContentBlock
     if let Ok(blocks) = serde_json::from_str::<Vec<ClaudeContentBlock>>(&msg.content) {
         let ir_blocks: Vec<IrContentBlock> = blocks.iter().map(block_to_ir).collect();
-        return IrMessage::new(role, ir_blocks);
+        let last = ir_blocks.len().saturating_sub(1);
+        return cache_breakpoints(&msg.content).into_iter().fold(
+            IrMessage::new(role, ir_blocks),
+            |message, (index, cache_control)| {
+                if index == last {
+                    message.with_cache_control(cache_control)
+                } else {
+                    message.with_block_cache_control(index, cache_control)
+                }
+            },
+        );
     }
 
     // Plain text content
@@ -139,8 +160,18 @@ fn message_from_ir(msg: &IrMessage) -> ClaudeMessage {
         )
     });
 
-    if has_structured {
+    let breakpoints = msg.cache_breakpoints();
+    if has_structured || !breakpoints.is_empty() {
         let blocks: Vec<ClaudeContentBlock> = msg.content.iter().map(block_from_ir).collect();
+        let mut blocks = serde_json::to_value(&blocks).unwrap_or_default();
+        for (index, cache_control) in breakpoints {
+            if let Some(block) = blocks.get_mut(index).and_then(|b| b.as_object_mut()) {
+                block.insert(
+                    CACHE_CONTROL_KEY.into(),
+                    serde_json::to_value(cache_control).unwrap_or_default(),
+                );
+            }
+        }
         let content = serde_json::to_string(&blocks).unwrap_or_default();
         ClaudeMessage {
             role: role.to_string(),
@@ -155,6 +186,20 @@ fn message_from_ir(msg: &IrMessage) -> ClaudeMessage {
     }
 }
 
+/// The `cache_control` set on each block of a JSON array of Claude content
+/// blocks, by block index.
+fn cache_breakpoints(content: &str) -> Vec<(usize, IrCacheControl)> {
+    let blocks: Vec<serde_json::Value> = serde_json::from_str(content).unwrap_or_default();
+    blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, b)| {
+            let cache_control = serde_json::from_value(b.get(CACHE_CONTROL_KEY)?.clone()).ok()?;
+            Some((index, cache_control))
+        })
+        .collect()
+}
+
 fn block_from_ir(block: &IrContentBlock) -> ClaudeContentBlock {
     match block {
         IrContentBlock::Text { text } => ClaudeContentBlock::Text { text: text.clone() },
@@ -572,4 +617,66 @@ mod tests {
             other => panic!("expected ToolResult, got {other:?}"),
         }
     }
+
+    #[test]
+    fn cache_control_roundtrip() {
+        let conv = IrConversation::from_messages(vec![
+            IrMessage::text(IrRole::User, "big document")
+                .with_cache_control(IrCacheControl::ephemeral()),
+            IrMessage::text(IrRole::User, "question"),
+        ]);
+        let msgs = from_ir(&conv);
+        let blocks: serde_json::Value = serde_json::from_str(&msgs[0].content).unwrap();
+        assert_eq!(
+            blocks,
+            json!([{"type": "text", "text": "big document", "cache_control": {"type": "ephemeral"}}])
+        );
+        assert_eq!(msgs[1].content, "question");
+
+        let back = to_ir(&msgs, None);
+        assert_eq!(
+            back.messages[0].cache_control(),
+            Some(IrCacheControl::ephemeral())
+        );
+        assert_eq!(back.messages[0].text_content(), "big document");
+        assert_eq!(back.messages[1].cache_control(), None);
+    }
+
+    #[test]
+    fn block_cache_control_roundtrip() {
+        let conv = IrConversation::from_messages(vec![
+            IrMessage::new(
+                IrRole::User,
+                vec![
+                    IrContentBlock::Text {
+                        text: "instructions".into(),
+                    },
+                    IrContentBlock::Text {
+                        text: "document".into(),
+                    },
+                    IrContentBlock::Text {
+                        text: "question".into(),
+                    },
+                ],
+            )
+            .with_block_cache_control(0, IrCacheControl::ephemeral())
+            .with_block_cache_control(1, IrCacheControl::ephemeral()),
+        ]);
+        let msgs = from_ir(&conv);
+        let blocks: serde_json::Value = serde_json::from_str(&msgs[0].content).unwrap();
+        assert_eq!(blocks[0]["cache_control"], json!({"type": "ephemeral"}));
+        assert_eq!(blocks[1]["cache_control"], json!({"type": "ephemeral"}));
+        assert!(blocks[2].get("cache_control").is_none());
+
+        let back = to_ir(&msgs, None);
+        assert_eq!(back.messages[0].cache_control(), None);
+        assert_eq!(
+            back.messages[0].cache_breakpoints(),
+            [
+                (0, IrCacheControl::ephemeral()),
+                (1, IrCacheControl::ephemeral())
+            ]
+            .into()
+        );
+    }
 }
@@ -224,7 +224,8 @@ impl From<MessagesRequest> for WorkOrder {
     /// Convert an Anthropic Messages API request into an ABP [`WorkOrder`].
     ///
     /// The user message text becomes the work order task. Tools and metadata
-    /// are stored in `config.vendor` for downstream adapters.
+    /// are stored in `config.vendor` for downstream adapters. System blocks
+    /// are flattened to text, dropping their `cache_control`.
     fn from(req: MessagesRequest) -> Self {
         let task = extract_task(&req.messages);
 
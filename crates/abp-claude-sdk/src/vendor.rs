@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Schema for the `config.vendor.claude` namespace.
+
+use abp_sidecar_sdk::VendorConfig;
+use abp_sidecar_sdk::vendor::check_range;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Claude knobs accepted under `config.vendor.claude`.
+///
+/// Covers the request parameters used by lowering and the client settings
+/// read by the Claude sidecar host, including the camelCase spellings the
+/// host accepts. Unknown keys are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ClaudeVendorConfig {
+    /// Model override, used when the work order does not set one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Maximum output tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature (0–1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Nucleus sampling probability (0–1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Top-k sampling cutoff.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    /// Run through the persistent client instead of one-shot queries.
+    #[serde(default, alias = "clientMode", skip_serializing_if = "Option::is_none")]
+    pub client_mode: Option<bool>,
+    /// Keep the client alive between runs.
+    #[serde(
+        default,
+        alias = "clientPersist",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub client_persist: Option<bool>,
+    /// Key identifying a persisted client session.
+    #[serde(
+        default,
+        alias = "clientSessionKey",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub client_session_key: Option<String>,
+    /// Client request timeout in milliseconds.
+    #[serde(
+        default,
+        alias = "clientTimeoutMs",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub client_timeout_ms: Option<u64>,
+    /// Retries after a failed query.
+    #[serde(
+        default,
+        alias = "retryCount",
+        alias = "retries",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retry_count: Option<u32>,
+    /// Delay between retries in milliseconds.
+    #[serde(
+        default,
+        alias = "retryDelayMs",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retry_delay_ms: Option<u64>,
+    /// Raw Claude Agent SDK option overrides, passed through by the host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl VendorConfig for ClaudeVendorConfig {
+    const NAMESPACE: &'static str = "claude";
+
+    fn check(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0..=1.0)?;
+        check_range("top_p", self.top_p, 0.0..=1.0)?;
+        if self.max_tokens == Some(0) {
+            return Err("max_tokens must be at least 1".into());
+        }
+        Ok(())
+    }
+}
@@ -624,6 +624,8 @@ fn claude_request_serde_roundtrip() {
             content: "Hello".into(),
         }],
         thinking: None,
+        tools: vec![],
+        tool_choice: None,
     };
     let json = serde_json::to_string(&req).unwrap();
     let parsed: ClaudeRequest = serde_json::from_str(&json).unwrap();
@@ -640,6 +642,8 @@ fn claude_request_with_thinking() {
         system: None,
         messages: vec![],
         thinking: Some(ThinkingConfig::new(10000)),
+        tools: vec![],
+        tool_choice: None,
     };
     let json = serde_json::to_string(&req).unwrap();
     assert!(json.contains("thinking"));
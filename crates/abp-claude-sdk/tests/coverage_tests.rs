@@ -53,6 +53,8 @@ fn claude_request_serde_roundtrip() {
             content: "Hello".into(),
         }],
         thinking: None,
+        tools: vec![],
+        tool_choice: None,
     };
     let json = serde_json::to_string(&req).unwrap();
     let parsed: ClaudeRequest = serde_json::from_str(&json).unwrap();
@@ -73,6 +75,8 @@ fn claude_request_with_thinking_config_roundtrip() {
             content: "Think carefully".into(),
         }],
         thinking: Some(ThinkingConfig::new(8192)),
+        tools: vec![],
+        tool_choice: None,
     };
     let json = serde_json::to_string(&req).unwrap();
     assert!(json.contains("thinking"));
@@ -89,6 +93,8 @@ fn claude_request_omits_thinking_when_none() {
         system: None,
         messages: vec![],
         thinking: None,
+        tools: vec![],
+        tool_choice: None,
     };
     let json = serde_json::to_string(&req).unwrap();
     assert!(!json.contains("thinking"));
@@ -26,6 +26,12 @@ pub struct Cli {
     /// Falls back to `backplane.toml` in the current directory if present.
     #[arg(long, global = true)]
     pub config: Option<PathBuf>,
+
+    /// Caller identity checked against the config's `[rbac]` roles.
+    ///
+    /// Falls back to the `ABP_IDENTITY` environment variable.
+    #[arg(long, global = true)]
+    pub identity: Option<String>,
 }
 
 /// Available CLI subcommands.
@@ -115,6 +121,11 @@ pub enum Commands {
         #[arg(long)]
         policy: Option<PathBuf>,
 
+        /// Named policy preset to apply on top of the policy (repeatable),
+        /// e.g. `read_only`, `docs_only`, `tests_only`, `no_network`, `full_sandbox`.
+        #[arg(long = "policy-preset")]
+        policy_presets: Vec<String>,
+
         /// Write the receipt to this file path.
         #[arg(long)]
         output: Option<PathBuf>,
@@ -203,7 +214,7 @@ pub enum Commands {
     },
 
     /// Receipt inspection and comparison.
-    #[command(name = "receipt")]
+    #[command(name = "receipt", alias = "receipts")]
     ReceiptCmd {
         /// The receipt action to perform.
         #[command(subcommand)]
@@ -216,6 +227,16 @@ pub enum Commands {
         #[arg(long)]
         json: bool,
     },
+
+    /// Show which backend the projection matrix would pick for a work order.
+    Project {
+        /// Path to the WorkOrder JSON file.
+        file: PathBuf,
+
+        /// Print the decision as JSON.
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// Actions for the `config` subcommand.
@@ -253,9 +274,21 @@ pub enum ConfigAction {
 /// Actions for the `receipt` subcommand.
 #[derive(Subcommand, Debug)]
 pub enum ReceiptAction {
+    /// Show a receipt as a timeline with usage, changes, and outcome.
+    Show {
+        /// Path to the receipt JSON file.
+        #[arg()]
+        file: PathBuf,
+        /// Print the rendered view as JSON.
+        #[arg(long, conflicts_with = "markdown")]
+        json: bool,
+        /// Render as Markdown, e.g. for a pull request comment.
+        #[arg(long)]
+        markdown: bool,
+    },
     /// Verify a receipt file's hash integrity.
     Verify {
-        /// Path to the receipt JSON file.
+        /// Path to the receipt JSON file, or a `.jsonl` archive of receipts.
         #[arg()]
         file: PathBuf,
     },
@@ -268,6 +301,42 @@ pub enum ReceiptAction {
         #[arg()]
         file2: PathBuf,
     },
+    /// Export receipt traces as a Chrome-tracing / Perfetto timeline.
+    Timeline {
+        /// Receipt JSON files or `.jsonl` archives of receipts.
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// Write the timeline here instead of stdout.
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Post a receipt to GitHub as a commit status or check run.
+    ///
+    /// Authenticates with the `GITHUB_TOKEN` environment variable.
+    Github {
+        /// Path to the receipt JSON file.
+        #[arg()]
+        file: PathBuf,
+        /// Repository as `owner/name`.
+        #[arg(long)]
+        repo: String,
+        /// Commit the run was for.
+        #[arg(long)]
+        sha: String,
+        /// Create a check run with the receipt summary and diff instead of
+        /// a commit status.
+        #[arg(long)]
+        check_run: bool,
+        /// Status context / check run name.
+        #[arg(long, default_value = "agent-backplane")]
+        name: String,
+        /// Link shown on the status or check run.
+        #[arg(long)]
+        details_url: Option<String>,
+        /// GitHub API base URL (for GitHub Enterprise Server).
+        #[arg(long, default_value = "https://api.github.com")]
+        api_url: String,
+    },
 }
 
 /// Schema kind argument for the `schema` subcommand.
@@ -7,7 +7,7 @@
 use abp_core::{Receipt, WorkOrder, receipt_hash};
 use anyhow::{Context, Result};
 use schemars::schema_for;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Schema types that can be printed by the `schema` subcommand.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -111,6 +111,46 @@ pub fn verify_receipt_file(path: &Path) -> Result<(Receipt, bool)> {
     inspect_receipt_file(path)
 }
 
+/// Verify every receipt in a JSON-lines archive.
+///
+/// The archive is memory-mapped and each trace is hashed event by event, so
+/// archives larger than memory can be checked.
+pub fn verify_receipt_archive(path: &Path) -> Result<abp_receipt::reader::ArchiveReport> {
+    let reader = abp_receipt::reader::ReceiptArchiveReader::open(path)
+        .with_context(|| format!("open receipt archive '{}'", path.display()))?;
+    reader
+        .verify_all()
+        .with_context(|| format!("verify receipt archive '{}'", path.display()))
+}
+
+/// Render receipts as a Chrome-tracing / Perfetto timeline (JSON).
+///
+/// Each path is a receipt JSON file or, with a `.jsonl` extension, an
+/// archive holding one receipt per line.
+pub fn receipt_timeline(paths: &[PathBuf]) -> Result<String> {
+    let mut receipts = Vec::new();
+    for path in paths {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("read receipt file '{}'", path.display()))?;
+        if path.extension().is_some_and(|e| e == "jsonl") {
+            for (i, line) in content.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let receipt: Receipt = serde_json::from_str(line).with_context(|| {
+                    format!("parse receipt on line {} of '{}'", i + 1, path.display())
+                })?;
+                receipts.push(receipt);
+            }
+        } else {
+            let receipt: Receipt = serde_json::from_str(&content)
+                .with_context(|| format!("parse receipt from '{}'", path.display()))?;
+            receipts.push(receipt);
+        }
+    }
+    Ok(abp_receipt::timeline::to_chrome_trace_json(&receipts)?)
+}
+
 /// Load and validate a configuration file.
 ///
 /// Returns a list of human-readable diagnostic messages (errors and warnings).
@@ -140,6 +180,9 @@ pub fn config_check(path: Option<&Path>) -> Result<Vec<String>> {
 }
 
 /// Diff two receipt files, returning a human-readable summary of differences.
+///
+/// One `field: old -> new` line per change reported by
+/// [`abp_receipt::diff_receipts`].
 pub fn receipt_diff(path1: &Path, path2: &Path) -> Result<String> {
     let content1 = std::fs::read_to_string(path1)
         .with_context(|| format!("read receipt file '{}'", path1.display()))?;
@@ -151,53 +194,12 @@ pub fn receipt_diff(path1: &Path, path2: &Path) -> Result<String> {
     let r2: Receipt = serde_json::from_str(&content2)
         .with_context(|| format!("parse receipt from '{}'", path2.display()))?;
 
-    let mut diffs = Vec::new();
-
-    if r1.outcome != r2.outcome {
-        diffs.push(format!("outcome: {:?} -> {:?}", r1.outcome, r2.outcome));
-    }
-    if r1.backend.id != r2.backend.id {
-        diffs.push(format!("backend: {} -> {}", r1.backend.id, r2.backend.id));
-    }
-    if r1.meta.run_id != r2.meta.run_id {
-        diffs.push(format!("run_id: {} -> {}", r1.meta.run_id, r2.meta.run_id));
-    }
-    if r1.meta.contract_version != r2.meta.contract_version {
-        diffs.push(format!(
-            "contract_version: {} -> {}",
-            r1.meta.contract_version, r2.meta.contract_version
-        ));
-    }
-    if r1.meta.duration_ms != r2.meta.duration_ms {
-        diffs.push(format!(
-            "duration_ms: {} -> {}",
-            r1.meta.duration_ms, r2.meta.duration_ms
-        ));
-    }
-    if r1.mode != r2.mode {
-        diffs.push(format!("mode: {:?} -> {:?}", r1.mode, r2.mode));
-    }
-    if r1.trace.len() != r2.trace.len() {
-        diffs.push(format!(
-            "trace_events: {} -> {}",
-            r1.trace.len(),
-            r2.trace.len()
-        ));
-    }
-    if r1.artifacts.len() != r2.artifacts.len() {
-        diffs.push(format!(
-            "artifacts: {} -> {}",
-            r1.artifacts.len(),
-            r2.artifacts.len()
-        ));
-    }
-    if r1.receipt_sha256 != r2.receipt_sha256 {
-        diffs.push(format!(
-            "receipt_sha256: {} -> {}",
-            r1.receipt_sha256.as_deref().unwrap_or("<none>"),
-            r2.receipt_sha256.as_deref().unwrap_or("<none>"),
-        ));
-    }
+    let diff = abp_receipt::diff_receipts(&r1, &r2);
+    let diffs: Vec<String> = diff
+        .changes
+        .iter()
+        .map(|c| format!("{}: {} -> {}", c.field, c.old, c.new))
+        .collect();
 
     if diffs.is_empty() {
         Ok("no differences".to_string())
@@ -148,6 +148,9 @@ fn event_kind_tag(kind: &AgentEventKind) -> &'static str {
         AgentEventKind::ToolResult { .. } => "tool_result",
         AgentEventKind::FileChanged { .. } => "file_changed",
         AgentEventKind::CommandExecuted { .. } => "command_executed",
+        AgentEventKind::Progress { .. } => "progress",
+        AgentEventKind::RunSummary { .. } => "run_summary",
+        AgentEventKind::ArtifactChunk { .. } => "artifact_chunk",
         AgentEventKind::Warning { .. } => "warning",
         AgentEventKind::Error { .. } => "error",
     }
@@ -178,8 +181,27 @@ fn event_brief(kind: &AgentEventKind) -> String {
             Some(code) => format!("{} => {code}", truncate(command, 40)),
             None => truncate(command, 40),
         },
+        AgentEventKind::Progress { percent, message } => match percent {
+            Some(pct) => format!("{pct:.0}% {}", truncate(message, 50)),
+            None => truncate(message, 60),
+        },
         AgentEventKind::Warning { message } => truncate(message, 60),
         AgentEventKind::Error { message, .. } => truncate(message, 60),
+        AgentEventKind::ArtifactChunk {
+            name, seq, last, ..
+        } => format!("{name} #{seq}{}", if *last { " (last)" } else { "" }),
+        AgentEventKind::RunSummary {
+            outcome,
+            duration_ms,
+            tool_calls,
+            files_changed,
+            ..
+        } => format!(
+            "{} in {duration_ms}ms, {} tool calls, {} files changed",
+            outcome_str(outcome),
+            tool_calls.values().sum::<u64>(),
+            files_changed.len()
+        ),
     }
 }
 
@@ -132,6 +132,30 @@ fn build_runtime(config: &abp_config::BackplaneConfig) -> Runtime {
                 spec.args = args.clone();
                 rt.register_backend(name, abp_integrations::SidecarBackend::new(spec));
             }
+            abp_config::BackendEntry::Grpc {
+                endpoint,
+                timeout_secs,
+            } => {
+                let mut backend = abp_integrations::GrpcBackend::new(endpoint);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                rt.register_backend(name, backend);
+            }
+            abp_config::BackendEntry::WebSocket { url, timeout_secs } => {
+                let mut backend = abp_integrations::WsBackend::new(url);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                rt.register_backend(name, backend);
+            }
+            abp_config::BackendEntry::Ipc { path, timeout_secs } => {
+                let mut backend = abp_integrations::IpcBackend::new(path);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                rt.register_backend(name, backend);
+            }
         }
     }
     rt
@@ -7,6 +7,8 @@ pub mod commands;
 pub mod config;
 pub mod format;
 pub mod health;
+pub mod project;
+pub mod receipt_view;
 pub mod schema;
 pub mod status;
 pub mod translate;
@@ -6,6 +6,8 @@ use abp_cli::cli::{
 };
 use abp_cli::commands::{self, SchemaKind};
 use abp_cli::health as health_cmd;
+use abp_cli::project as project_cmd;
+use abp_cli::receipt_view::ReceiptView;
 use abp_cli::schema as schema_cmd;
 use abp_cli::status as status_cmd;
 use abp_cli::translate as translate_cmd;
@@ -20,6 +22,11 @@ use abp_host::SidecarSpec;
 use abp_integrations::SidecarBackend;
 use abp_kimi_sdk as kimi_sdk;
 use abp_runtime::Runtime;
+use abp_runtime::github::{GitHubReportKind, GitHubReporter};
+use abp_runtime::journal::{ReceiptJournal, RecoveredRun};
+use abp_runtime::rbac::Permission;
+use abp_runtime::store::ReceiptStore;
+use abp_workspace::janitor::WorkspaceJanitor;
 use anyhow::{Context, Result};
 use clap::Parser;
 use serde_json::{Map as JsonMap, Value as JsonValue};
@@ -62,6 +69,13 @@ async fn main() {
         }
     }
 
+    let identity = cli
+        .identity
+        .clone()
+        .or_else(|| std::env::var("ABP_IDENTITY").ok())
+        .filter(|id| !id.trim().is_empty());
+    let identity = identity.as_deref();
+
     let result = match cli.command {
         Commands::Backends {
             capabilities,
@@ -72,12 +86,14 @@ async fn main() {
             cmd_validate(file.as_deref(), config_file.as_deref())
         }
         Commands::Schema { kind, output } => cmd_schema(kind, output),
-        Commands::Inspect { file } => cmd_inspect(&file),
+        Commands::Inspect { file } => authorize(&config, identity, Permission::ReadReceipts, &file)
+            .and_then(|()| cmd_inspect(&file)),
         Commands::Translate { from, to, file } => cmd_translate(&from, &to, file),
         Commands::Health { json } => cmd_health(&config, json),
         Commands::ConfigCmd { action } => cmd_config(action, config_path),
-        Commands::ReceiptCmd { action } => cmd_receipt(action),
+        Commands::ReceiptCmd { action } => cmd_receipt(action, &config, identity).await,
         Commands::Status { json } => cmd_status(&config, json),
+        Commands::Project { file, json } => cmd_project(&file, json, config_path.as_deref()),
         Commands::Run {
             backend,
             task,
@@ -94,6 +110,7 @@ async fn main() {
             out,
             json,
             policy,
+            policy_presets,
             output,
             events,
             stream,
@@ -117,6 +134,7 @@ async fn main() {
                 out,
                 json,
                 policy,
+                policy_presets,
                 output,
                 events,
                 stream,
@@ -124,6 +142,7 @@ async fn main() {
                 retry,
                 fallback,
                 &config,
+                identity,
             )
             .await
         }
@@ -155,6 +174,30 @@ async fn cmd_backends(
                 spec.args = args.clone();
                 rt.register_backend(name, SidecarBackend::new(spec));
             }
+            abp_config::BackendEntry::Grpc {
+                endpoint,
+                timeout_secs,
+            } => {
+                let mut backend = abp_integrations::GrpcBackend::new(endpoint);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                rt.register_backend(name, backend);
+            }
+            abp_config::BackendEntry::WebSocket { url, timeout_secs } => {
+                let mut backend = abp_integrations::WsBackend::new(url);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                rt.register_backend(name, backend);
+            }
+            abp_config::BackendEntry::Ipc { path, timeout_secs } => {
+                let mut backend = abp_integrations::IpcBackend::new(path);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                rt.register_backend(name, backend);
+            }
         }
     }
 
@@ -383,9 +426,48 @@ fn cmd_config(action: ConfigAction, global_config_path: Option<PathBuf>) -> Resu
     }
 }
 
-fn cmd_receipt(action: ReceiptAction) -> Result<()> {
+async fn cmd_receipt(
+    action: ReceiptAction,
+    config: &abp_config::BackplaneConfig,
+    identity: Option<&str>,
+) -> Result<()> {
     match action {
+        ReceiptAction::Show {
+            file,
+            json,
+            markdown,
+        } => {
+            authorize(config, identity, Permission::ReadReceipts, &file)?;
+            let (receipt, valid) = commands::inspect_receipt_file(&file)?;
+            let hash_valid = receipt.receipt_sha256.as_ref().map(|_| valid);
+            let view = ReceiptView::new(&receipt, hash_valid);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&view)?);
+            } else if markdown {
+                print!("{}", view.render_markdown());
+            } else {
+                print!("{}", view.render_text());
+            }
+            Ok(())
+        }
         ReceiptAction::Verify { file } => {
+            authorize(config, identity, Permission::ReadReceipts, &file)?;
+            if file.extension().is_some_and(|e| e == "jsonl") {
+                let report = commands::verify_receipt_archive(&file)?;
+                println!("receipts: {}", report.receipts);
+                if report.is_valid() {
+                    println!("hash: VALID");
+                } else {
+                    let lines: Vec<String> = report
+                        .invalid_lines
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect();
+                    println!("hash: INVALID (lines {})", lines.join(", "));
+                    std::process::exit(EXIT_RUNTIME_ERROR);
+                }
+                return Ok(());
+            }
             let (receipt, valid) = commands::verify_receipt_file(&file)?;
             println!(
                 "sha256: {}",
@@ -400,13 +482,68 @@ fn cmd_receipt(action: ReceiptAction) -> Result<()> {
             Ok(())
         }
         ReceiptAction::Diff { file1, file2 } => {
+            authorize(config, identity, Permission::ReadReceipts, &file1)?;
+            authorize(config, identity, Permission::ReadReceipts, &file2)?;
             let diff = commands::receipt_diff(&file1, &file2)?;
             println!("{diff}");
             Ok(())
         }
+        ReceiptAction::Timeline { files, out } => {
+            for file in &files {
+                authorize(config, identity, Permission::ReadReceipts, file)?;
+            }
+            let timeline = commands::receipt_timeline(&files)?;
+            match out {
+                Some(path) => std::fs::write(&path, timeline)
+                    .with_context(|| format!("write timeline '{}'", path.display()))?,
+                None => println!("{timeline}"),
+            }
+            Ok(())
+        }
+        ReceiptAction::Github {
+            file,
+            repo,
+            sha,
+            check_run,
+            name,
+            details_url,
+            api_url,
+        } => {
+            authorize(config, identity, Permission::ReadReceipts, &file)?;
+            let token = std::env::var("GITHUB_TOKEN")
+                .context("GITHUB_TOKEN must be set to post to GitHub")?;
+            let (receipt, _) = commands::verify_receipt_file(&file)?;
+            let kind = if check_run {
+                GitHubReportKind::CheckRun
+            } else {
+                GitHubReportKind::CommitStatus
+            };
+            let mut reporter = GitHubReporter::new(&repo, token)
+                .with_api_base(api_url)
+                .with_kind(kind)
+                .with_name(name);
+            if let Some(url) = details_url {
+                reporter = reporter.with_details_url(url);
+            }
+            reporter.report(&receipt, &sha).await?;
+            println!("reported {:?} to {repo}@{sha}", receipt.outcome);
+            Ok(())
+        }
     }
 }
 
+/// Fail unless the config's RBAC roles grant `identity` `permission` on `file`.
+fn authorize(
+    config: &abp_config::BackplaneConfig,
+    identity: Option<&str>,
+    permission: Permission,
+    file: &std::path::Path,
+) -> Result<()> {
+    let resource = file.display().to_string();
+    abp_runtime::rbac::check(config.rbac.as_ref(), identity, permission, &resource)?;
+    Ok(())
+}
+
 fn cmd_status(config: &abp_config::BackplaneConfig, json: bool) -> Result<()> {
     let info = status_cmd::gather_status(config)?;
     if json {
@@ -416,6 +553,25 @@ fn cmd_status(config: &abp_config::BackplaneConfig, json: bool) -> Result<()> {
     }
 }
 
+fn cmd_project(
+    file: &std::path::Path,
+    json: bool,
+    config_path: Option<&std::path::Path>,
+) -> Result<()> {
+    let content =
+        std::fs::read_to_string(file).with_context(|| format!("read {}", file.display()))?;
+    let wo: WorkOrder = serde_json::from_str(&content)
+        .with_context(|| format!("parse work order {}", file.display()))?;
+    let (rt, unprojected) = project_cmd::build_runtime(config_path)?;
+    let report = project_cmd::explain(&rt, unprojected, &wo)?;
+    if json {
+        project_cmd::print_report_json(&report)
+    } else {
+        project_cmd::print_report(&report);
+        Ok(())
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn cmd_run(
     backend: Option<String>,
@@ -433,6 +589,7 @@ async fn cmd_run(
     out: Option<PathBuf>,
     json: bool,
     policy_path: Option<PathBuf>,
+    policy_presets: Vec<String>,
     output: Option<PathBuf>,
     events_path: Option<PathBuf>,
     _stream: bool,
@@ -440,6 +597,7 @@ async fn cmd_run(
     retry: u32,
     fallback: Option<String>,
     config: &abp_config::BackplaneConfig,
+    identity: Option<&str>,
 ) -> Result<()> {
     // Resolve backend: --backend flag > config default_backend > "mock".
     let backend = normalize_backend_name(
@@ -447,7 +605,44 @@ async fn cmd_run(
             .or_else(|| config.default_backend.clone())
             .unwrap_or_else(|| "mock".to_string()),
     );
-    let mut rt = Runtime::with_default_backends();
+    let mut rt = Runtime::with_default_backends().with_run_summary(true);
+    if let Some(rbac) = &config.rbac {
+        rt = rt.with_rbac(rbac.clone());
+    }
+    rt.authorize(identity, Permission::Submit, &format!("backend:{backend}"))?;
+
+    // With a receipts_dir configured, journal runs so a crash between backend
+    // completion and persistence still leaves a receipt. Runs an earlier
+    // process left behind are finalized (or marked interrupted) first.
+    if let Some(ref dir) = config.receipts_dir {
+        let dir = PathBuf::from(dir);
+        let journal = ReceiptJournal::new(dir.join(".journal")).with_store(ReceiptStore::new(&dir));
+        for recovered in journal.recover().context("recover receipt journal")? {
+            if !json {
+                let state = match recovered {
+                    RecoveredRun::Finalized(_) => "finalized",
+                    RecoveredRun::Interrupted(_) => "interrupted",
+                };
+                eprintln!(
+                    "recovered {state} run {} into {}",
+                    recovered.receipt().meta.run_id,
+                    dir.display()
+                );
+            }
+        }
+        rt = rt.with_journal(journal);
+    }
+
+    // Reclaim staged workspaces left behind by crashed runs.
+    match WorkspaceJanitor::default().sweep() {
+        Ok(report) if !report.removed.is_empty() && !json => eprintln!(
+            "removed {} orphaned staging dir(s), reclaimed {} bytes",
+            report.removed.len(),
+            report.bytes_reclaimed
+        ),
+        Ok(_) => {}
+        Err(e) => tracing::warn!("staging cleanup failed: {e:#}"),
+    }
 
     // Register built-in sidecars.
     if backend == "sidecar:node" {
@@ -538,6 +733,30 @@ async fn cmd_run(
                 spec.args = args.clone();
                 rt.register_backend(name, SidecarBackend::new(spec));
             }
+            abp_config::BackendEntry::Grpc {
+                endpoint,
+                timeout_secs,
+            } => {
+                let mut backend = abp_integrations::GrpcBackend::new(endpoint);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                rt.register_backend(name, backend);
+            }
+            abp_config::BackendEntry::WebSocket { url, timeout_secs } => {
+                let mut backend = abp_integrations::WsBackend::new(url);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                rt.register_backend(name, backend);
+            }
+            abp_config::BackendEntry::Ipc { path, timeout_secs } => {
+                let mut backend = abp_integrations::IpcBackend::new(path);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                rt.register_backend(name, backend);
+            }
         }
     }
 
@@ -583,8 +802,25 @@ async fn cmd_run(
         default_policy()
     };
 
+    // Presets from config come first, then `--policy-preset` flags;
+    // the runtime composes them into the policy before the run starts.
+    let presets: Vec<String> = config
+        .policy_presets
+        .iter()
+        .chain(&policy_presets)
+        .cloned()
+        .collect();
+    if !presets.is_empty() {
+        abp_policy::presets::resolve(&presets)?;
+        insert_vendor_path(
+            &mut vendor,
+            &format!("abp.{}", abp_policy::presets::PRESETS_VENDOR_KEY),
+            JsonValue::from(presets),
+        );
+    }
+
     let work_order_id = Uuid::new_v4();
-    let wo = WorkOrder {
+    let mut wo = WorkOrder {
         id: work_order_id,
         task,
         lane: lane.into(),
@@ -603,9 +839,17 @@ async fn cmd_run(
             env,
             max_budget_usd,
             max_turns,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
 
+    // Record who ran the CLI so shared receipt stores can tell runs apart.
+    abp_core::submitter::Submitter::from_env()
+        .client("abp-cli", Some(env!("CARGO_PKG_VERSION")))
+        .attach(&mut wo);
+
     // Run with retry and fallback support.
     let backends_to_try: Vec<String> = {
         let mut v = vec![backend.clone()];
@@ -863,8 +1107,30 @@ fn print_event(ev: &abp_core::AgentEvent) {
             eprintln!("[bash] {:?} => {:?}", command, exit_code);
         }
 
+        Progress { percent, message } => match percent {
+            Some(pct) => eprintln!("[progress] {pct:.0}% {message}"),
+            None => eprintln!("[progress] {message}"),
+        },
         Warning { message } => eprintln!("[warn] {message}"),
         Error { message, .. } => eprintln!("[error] {message}"),
+        ArtifactChunk {
+            name, seq, last, ..
+        } => eprintln!(
+            "[artifact] {name} chunk {seq}{}",
+            if *last { " (last)" } else { "" }
+        ),
+        RunSummary {
+            outcome,
+            duration_ms,
+            tool_calls,
+            files_changed,
+            policy_denials,
+            ..
+        } => eprintln!(
+            "[summary] {outcome:?} in {duration_ms}ms: {} tool calls, {} files changed, {policy_denials} policy denials",
+            tool_calls.values().sum::<u64>(),
+            files_changed.len()
+        ),
     }
 }
 
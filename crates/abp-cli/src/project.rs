@@ -0,0 +1,130 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Project subcommand implementation.
+//!
+//! Shows which backend the projection matrix would pick for a work order,
+//! with the score breakdown, required emulations, and fallback chain, so
+//! routing decisions can be debugged without running anything.
+
+use std::path::Path;
+
+use abp_core::WorkOrder;
+use abp_runtime::registry::DEFAULT_PRIORITY;
+use abp_runtime::{ProjectionMatrix, ProjectionResult, ProjectionScore, Runtime};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// A projection decision together with the backends it could not consider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectionReport {
+    /// The projection matrix's decision.
+    #[serde(flatten)]
+    pub projection: ProjectionResult,
+    /// Registered backends left out because they have no known dialect.
+    pub unprojected: Vec<String>,
+}
+
+/// Build a runtime with the default backends plus those declared at
+/// `config_path`, and project every backend whose dialect is known.
+///
+/// Backends declared with a `dialect` are projected as declared; the rest
+/// are projected under the dialect their name implies, at the default
+/// priority. Backends with neither are reported as unprojected.
+///
+/// # Errors
+///
+/// Returns an error if the config file cannot be loaded.
+pub fn build_runtime(config_path: Option<&Path>) -> Result<(Runtime, Vec<String>)> {
+    let mut rt = Runtime::with_default_backends();
+    if let Some(path) = config_path {
+        rt.register_backends_from_config(path)
+            .with_context(|| format!("load backends from {}", path.display()))?;
+    }
+
+    let mut unprojected = Vec::new();
+    for name in rt.backend_names() {
+        if rt
+            .projection()
+            .as_ref()
+            .is_some_and(|pm| pm.backend_entry(&name).is_some())
+        {
+            continue;
+        }
+        let Some(dialect) = abp_runtime::infer_dialect_from_backend(&name) else {
+            unprojected.push(name);
+            continue;
+        };
+        let capabilities = rt
+            .backend(&name)
+            .map(|b| b.capabilities())
+            .unwrap_or_default();
+        if rt.projection_mut().is_none() {
+            rt = rt.with_projection(ProjectionMatrix::with_defaults());
+        }
+        if let Some(pm) = rt.projection_mut() {
+            pm.register_backend(&name, capabilities, dialect, DEFAULT_PRIORITY);
+        }
+    }
+    Ok((rt, unprojected))
+}
+
+/// Project `work_order` onto the runtime's backends.
+///
+/// # Errors
+///
+/// Returns an error if no projected backend can serve the work order.
+pub fn explain(
+    rt: &Runtime,
+    unprojected: Vec<String>,
+    work_order: &WorkOrder,
+) -> Result<ProjectionReport> {
+    let projection = rt
+        .select_backend(work_order)
+        .context("project work order")?;
+    Ok(ProjectionReport {
+        projection,
+        unprojected,
+    })
+}
+
+fn format_score(score: &ProjectionScore) -> String {
+    format!(
+        "total={:.3}  capability={:.3}  fidelity={:.3}  priority={:.3}",
+        score.total, score.capability_coverage, score.mapping_fidelity, score.priority
+    )
+}
+
+/// Print a projection report in human-readable form.
+pub fn print_report(report: &ProjectionReport) {
+    let p = &report.projection;
+    println!("selected: {}", p.selected_backend);
+    println!("  {}", format_score(&p.fidelity_score));
+    if p.required_emulations.is_empty() {
+        println!("emulations: none");
+    } else {
+        println!("emulations:");
+        for e in &p.required_emulations {
+            println!("  {:?} via {}", e.capability, e.strategy);
+        }
+    }
+    if p.fallback_chain.is_empty() {
+        println!("fallbacks: none");
+    } else {
+        println!("fallbacks:");
+        for (i, f) in p.fallback_chain.iter().enumerate() {
+            println!("  {}. {}  {}", i + 1, f.backend_id, format_score(&f.score));
+        }
+    }
+    for name in &report.unprojected {
+        println!("not projected (no dialect): {name}");
+    }
+}
+
+/// Print a projection report as pretty JSON.
+///
+/// # Errors
+///
+/// Returns an error if serialization fails.
+pub fn print_report_json(report: &ProjectionReport) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(report)?);
+    Ok(())
+}
@@ -0,0 +1,411 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Receipt show subcommand implementation.
+//!
+//! Renders a receipt as a readable timeline of what the run did — messages,
+//! tool calls, commands, file changes — followed by token usage, the
+//! workspace diff, and the outcome. Output is plain text, Markdown (for PR
+//! comments and issue reports), or JSON.
+
+use abp_core::{AgentEvent, AgentEventKind, Receipt, UsageNormalized};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+
+/// Longest text shown for a single timeline entry.
+const PREVIEW_CHARS: usize = 120;
+
+/// One step of a run, as shown in the timeline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    /// Milliseconds since the run started.
+    pub offset_ms: i64,
+    /// Event kind tag, e.g. `tool_call`. Consecutive `assistant_delta`
+    /// events are merged into one entry.
+    pub kind: String,
+    /// One-line description.
+    pub summary: String,
+    /// Whether the step reported a failure.
+    pub is_error: bool,
+}
+
+/// Size of the workspace diff.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffStat {
+    /// Files touched.
+    pub files: usize,
+    /// Lines added.
+    pub insertions: usize,
+    /// Lines removed.
+    pub deletions: usize,
+}
+
+impl DiffStat {
+    /// Count files and changed lines in a unified diff.
+    #[must_use]
+    pub fn from_unified_diff(diff: &str) -> Self {
+        let mut stat = Self::default();
+        for line in diff.lines() {
+            if line.starts_with("diff --git ") {
+                stat.files += 1;
+            } else if line.starts_with('+') && !line.starts_with("+++") {
+                stat.insertions += 1;
+            } else if line.starts_with('-') && !line.starts_with("---") {
+                stat.deletions += 1;
+            }
+        }
+        stat
+    }
+}
+
+/// Everything `abp receipt show` displays about a receipt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptView {
+    /// Run identifier.
+    pub run_id: String,
+    /// Backend that executed the run.
+    pub backend: String,
+    /// Final outcome, as serialized in the receipt.
+    pub outcome: String,
+    /// When the run started.
+    pub started_at: DateTime<Utc>,
+    /// Run duration in milliseconds.
+    pub duration_ms: u64,
+    /// Whether the stored hash matches the receipt; `None` if unhashed.
+    pub hash_valid: Option<bool>,
+    /// Normalized token usage.
+    pub usage: UsageNormalized,
+    /// What the run did, in order.
+    pub timeline: Vec<TimelineEntry>,
+    /// Number of tool calls made.
+    pub tool_calls: usize,
+    /// Paths reported as changed, in first-seen order.
+    pub files_changed: Vec<String>,
+    /// Size of the workspace diff, if one was captured.
+    pub diff_stat: Option<DiffStat>,
+    /// The workspace diff, if one was captured.
+    pub git_diff: Option<String>,
+}
+
+impl ReceiptView {
+    /// Build the view of `receipt`. `hash_valid` is the result of checking
+    /// its stored hash, if it has one.
+    #[must_use]
+    pub fn new(receipt: &Receipt, hash_valid: Option<bool>) -> Self {
+        let started_at = receipt.meta.started_at;
+        let mut timeline: Vec<TimelineEntry> = Vec::new();
+        let mut streamed = String::new();
+        let mut files_changed: Vec<String> = Vec::new();
+        let mut tool_calls = 0;
+
+        for event in &receipt.trace {
+            let offset_ms = (event.ts - started_at).num_milliseconds();
+            if let AgentEventKind::AssistantDelta { text } = &event.kind {
+                if let Some(last) = timeline.last_mut()
+                    && last.kind == "assistant_delta"
+                {
+                    streamed.push_str(text);
+                    last.summary = preview(&streamed);
+                    continue;
+                }
+                streamed = text.clone();
+            }
+            match &event.kind {
+                AgentEventKind::ToolCall { .. } => tool_calls += 1,
+                AgentEventKind::FileChanged { path, .. } if !files_changed.contains(path) => {
+                    files_changed.push(path.clone());
+                }
+                _ => {}
+            }
+            timeline.push(entry(event, offset_ms));
+        }
+
+        let git_diff = receipt
+            .verification
+            .git_diff
+            .clone()
+            .filter(|d| !d.trim().is_empty());
+
+        Self {
+            run_id: receipt.meta.run_id.to_string(),
+            backend: receipt.backend.id.clone(),
+            outcome: serde_json::to_value(&receipt.outcome)
+                .ok()
+                .and_then(|v| v.as_str().map(String::from))
+                .unwrap_or_default(),
+            started_at,
+            duration_ms: receipt.meta.duration_ms,
+            hash_valid,
+            usage: receipt.usage.clone(),
+            timeline,
+            tool_calls,
+            files_changed,
+            diff_stat: git_diff.as_deref().map(DiffStat::from_unified_diff),
+            git_diff,
+        }
+    }
+
+    /// Render as plain text for a terminal.
+    #[must_use]
+    pub fn render_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "run:      {}", self.run_id);
+        let _ = writeln!(out, "backend:  {}", self.backend);
+        let _ = writeln!(out, "outcome:  {}", self.outcome);
+        let _ = writeln!(out, "started:  {}", self.started_at.to_rfc3339());
+        let _ = writeln!(out, "duration: {}", format_ms(self.duration_ms as i64));
+        let _ = writeln!(out, "hash:     {}", hash_label(self.hash_valid));
+
+        let _ = writeln!(out, "\ntimeline:");
+        if self.timeline.is_empty() {
+            let _ = writeln!(out, "  (no events)");
+        }
+        for e in &self.timeline {
+            let marker = if e.is_error { "!" } else { " " };
+            let _ = writeln!(
+                out,
+                "{marker} {:>9}  {:<17}  {}",
+                format!("+{}", format_ms(e.offset_ms)),
+                e.kind,
+                e.summary
+            );
+        }
+
+        let _ = writeln!(out, "\nusage:");
+        for (label, value) in usage_rows(&self.usage) {
+            let _ = writeln!(out, "  {label:<14} {value}");
+        }
+
+        let _ = writeln!(out, "\nchanges:");
+        let _ = writeln!(out, "  tool calls:    {}", self.tool_calls);
+        let _ = writeln!(out, "  files changed: {}", self.files_changed.len());
+        for path in &self.files_changed {
+            let _ = writeln!(out, "    {path}");
+        }
+        if let Some(stat) = self.diff_stat {
+            let _ = writeln!(out, "  diff:          {}", format_stat(stat));
+        }
+        out
+    }
+
+    /// Render as Markdown, e.g. for a pull request comment.
+    #[must_use]
+    pub fn render_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "## Run `{}`\n", self.run_id);
+        let _ = writeln!(out, "| | |\n|---|---|");
+        let _ = writeln!(out, "| Backend | `{}` |", self.backend);
+        let _ = writeln!(out, "| Outcome | **{}** |", self.outcome);
+        let _ = writeln!(out, "| Started | {} |", self.started_at.to_rfc3339());
+        let _ = writeln!(out, "| Duration | {} |", format_ms(self.duration_ms as i64));
+        let _ = writeln!(out, "| Hash | {} |", hash_label(self.hash_valid));
+        let _ = writeln!(out, "| Tool calls | {} |", self.tool_calls);
+
+        let _ = writeln!(out, "\n### Timeline\n");
+        if self.timeline.is_empty() {
+            let _ = writeln!(out, "_No events._");
+        } else {
+            let _ = writeln!(out, "| Time | Event | Details |\n|---:|---|---|");
+            for e in &self.timeline {
+                let kind = if e.is_error {
+                    format!("**{}**", e.kind)
+                } else {
+                    e.kind.clone()
+                };
+                let _ = writeln!(
+                    out,
+                    "| +{} | {kind} | {} |",
+                    format_ms(e.offset_ms),
+                    escape_cell(&e.summary)
+                );
+            }
+        }
+
+        let rows = usage_rows(&self.usage);
+        if !rows.is_empty() {
+            let _ = writeln!(out, "\n### Usage\n\n| | |\n|---|---:|");
+            for (label, value) in rows {
+                let _ = writeln!(out, "| {label} | {value} |");
+            }
+        }
+
+        if !self.files_changed.is_empty() || self.git_diff.is_some() {
+            let _ = writeln!(out, "\n### Changes\n");
+            for path in &self.files_changed {
+                let _ = writeln!(out, "- `{path}`");
+            }
+            if let (Some(stat), Some(diff)) = (self.diff_stat, &self.git_diff) {
+                let _ = writeln!(
+                    out,
+                    "\n<details><summary>Diff ({})</summary>\n\n```diff\n{}\n```\n\n</details>",
+                    format_stat(stat),
+                    diff.trim_end()
+                );
+            }
+        }
+        out
+    }
+}
+
+fn entry(event: &AgentEvent, offset_ms: i64) -> TimelineEntry {
+    let (kind, summary, is_error) = match &event.kind {
+        AgentEventKind::RunStarted { message } => ("run_started", message.clone(), false),
+        AgentEventKind::RunCompleted { message } => ("run_completed", message.clone(), false),
+        AgentEventKind::AssistantDelta { text } => ("assistant_delta", text.clone(), false),
+        AgentEventKind::AssistantMessage { text } => ("assistant_message", text.clone(), false),
+        AgentEventKind::ToolCall {
+            tool_name, input, ..
+        } => ("tool_call", format!("{tool_name} {input}"), false),
+        AgentEventKind::ToolResult {
+            tool_name,
+            output,
+            is_error,
+            ..
+        } => {
+            let output = match output {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            ("tool_result", format!("{tool_name} → {output}"), *is_error)
+        }
+        AgentEventKind::FileChanged { path, summary } => {
+            ("file_changed", format!("{path}: {summary}"), false)
+        }
+        AgentEventKind::CommandExecuted {
+            command, exit_code, ..
+        } => {
+            let status = exit_code.map_or_else(|| "?".to_string(), |c| c.to_string());
+            (
+                "command_executed",
+                format!("{command} (exit {status})"),
+                exit_code.is_some_and(|c| c != 0),
+            )
+        }
+        AgentEventKind::Progress { percent, message } => {
+            let summary = match percent {
+                Some(p) => format!("{p:.0}% {message}"),
+                None => message.clone(),
+            };
+            ("progress", summary, false)
+        }
+        AgentEventKind::Warning { message } => ("warning", message.clone(), false),
+        AgentEventKind::Error {
+            message,
+            error_code,
+        } => {
+            let summary = match error_code {
+                Some(code) => format!("[{}] {message}", code.as_str()),
+                None => message.clone(),
+            };
+            ("error", summary, true)
+        }
+        AgentEventKind::ArtifactChunk { name, seq, .. } => {
+            ("artifact_chunk", format!("{name} chunk {seq}"), false)
+        }
+        AgentEventKind::RunSummary {
+            outcome,
+            duration_ms,
+            files_changed,
+            ..
+        } => (
+            "run_summary",
+            format!(
+                "{outcome:?} in {duration_ms}ms, {} files changed",
+                files_changed.len()
+            ),
+            false,
+        ),
+    };
+    TimelineEntry {
+        offset_ms,
+        kind: kind.to_string(),
+        summary: preview(&summary),
+        is_error,
+    }
+}
+
+/// First line-free [`PREVIEW_CHARS`] characters of `text`.
+fn preview(text: &str) -> String {
+    let flat: String = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .take(PREVIEW_CHARS + 1)
+        .collect();
+    if flat.chars().count() > PREVIEW_CHARS {
+        let cut: String = flat.chars().take(PREVIEW_CHARS).collect();
+        format!("{cut}…")
+    } else {
+        flat
+    }
+}
+
+fn usage_rows(usage: &UsageNormalized) -> Vec<(&'static str, String)> {
+    let tokens = [
+        ("input tokens", usage.input_tokens),
+        ("output tokens", usage.output_tokens),
+        ("cache read", usage.cache_read_tokens),
+        ("cache write", usage.cache_write_tokens),
+        ("request units", usage.request_units),
+    ];
+    let mut rows: Vec<_> = tokens
+        .into_iter()
+        .filter_map(|(label, v)| v.map(|v| (label, v.to_string())))
+        .collect();
+    if let Some(cost) = usage.estimated_cost_usd {
+        rows.push(("cost (est.)", format!("${cost:.4}")));
+    }
+    rows
+}
+
+fn format_ms(ms: i64) -> String {
+    if ms.abs() < 1000 {
+        format!("{ms}ms")
+    } else {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    }
+}
+
+fn format_stat(stat: DiffStat) -> String {
+    format!(
+        "{} file(s), +{} -{}",
+        stat.files, stat.insertions, stat.deletions
+    )
+}
+
+fn hash_label(valid: Option<bool>) -> &'static str {
+    match valid {
+        Some(true) => "valid",
+        Some(false) => "INVALID",
+        None => "none",
+    }
+}
+
+fn escape_cell(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_stat_counts_files_and_lines() {
+        let diff = "diff --git a/x b/x\n--- a/x\n+++ b/x\n@@ -1 +1,2 @@\n-old\n+new\n+more\n";
+        assert_eq!(
+            DiffStat::from_unified_diff(diff),
+            DiffStat {
+                files: 1,
+                insertions: 2,
+                deletions: 1
+            }
+        );
+    }
+
+    #[test]
+    fn preview_flattens_and_truncates() {
+        assert_eq!(preview("a\n  b"), "a b");
+        let long = "é".repeat(PREVIEW_CHARS + 5);
+        assert_eq!(preview(&long).chars().count(), PREVIEW_CHARS + 1);
+    }
+}
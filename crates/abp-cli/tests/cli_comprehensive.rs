@@ -336,6 +336,27 @@ fn parse_run_policy_option() {
     }
 }
 
+#[test]
+fn parse_run_policy_presets() {
+    let cli = Cli::try_parse_from([
+        "abp",
+        "run",
+        "--task",
+        "x",
+        "--policy-preset",
+        "read_only",
+        "--policy-preset",
+        "no_network",
+    ])
+    .unwrap();
+    match cli.command {
+        Commands::Run { policy_presets, .. } => {
+            assert_eq!(policy_presets, ["read_only", "no_network"]);
+        }
+        _ => panic!("expected Run"),
+    }
+}
+
 #[test]
 fn parse_validate_subcommand() {
     let cli = Cli::try_parse_from(["abp", "validate", "wo.json"]).unwrap();
@@ -553,6 +553,57 @@ fn invalid_param_format_fails() {
         .failure();
 }
 
+// ── Additional: policy presets ──────────────────────────────────────
+
+#[test]
+fn policy_preset_flag_is_accepted() {
+    let tmp = tempfile::tempdir().expect("create temp dir");
+    let receipt = tmp.path().join("receipt.json");
+    abp()
+        .args([
+            "run",
+            "--backend",
+            "mock",
+            "--task",
+            "preset test",
+            "--policy-preset",
+            "read_only",
+            "--policy-preset",
+            "no-network",
+            "--root",
+            tmp.path().to_str().unwrap(),
+            "--workspace-mode",
+            "pass-through",
+            "--out",
+            receipt.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    assert!(receipt.exists());
+}
+
+#[test]
+fn unknown_policy_preset_fails() {
+    let tmp = tempfile::tempdir().expect("create temp dir");
+    abp()
+        .args([
+            "run",
+            "--backend",
+            "mock",
+            "--task",
+            "bad preset",
+            "--policy-preset",
+            "lockdown",
+            "--root",
+            tmp.path().to_str().unwrap(),
+            "--workspace-mode",
+            "pass-through",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("lockdown"));
+}
+
 // ── Additional: subcommand help ─────────────────────────────────────
 
 #[test]
@@ -1006,6 +1057,23 @@ fn receipt_verify_tampered_hash_fails() {
         .stdout(predicate::str::contains("INVALID"));
 }
 
+#[test]
+fn receipts_alias_verifies_hash() {
+    let tmp = tempfile::tempdir().expect("create temp dir");
+    let receipt = abp_core::ReceiptBuilder::new("mock")
+        .outcome(abp_core::Outcome::Complete)
+        .with_hash()
+        .unwrap();
+    let path = tmp.path().join("receipt.json");
+    std::fs::write(&path, serde_json::to_string_pretty(&receipt).unwrap()).unwrap();
+
+    abp()
+        .args(["receipts", "verify", path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("VALID"));
+}
+
 // ── 26. Receipt diff subcommand ─────────────────────────────────────
 
 #[test]
@@ -1062,6 +1130,59 @@ fn receipt_diff_different_shows_changes() {
         .stdout(predicate::str::contains("backend"));
 }
 
+#[test]
+fn receipt_timeline_writes_chrome_trace() {
+    let tmp = tempfile::tempdir().expect("create temp dir");
+    let receipt = abp_core::ReceiptBuilder::new("mock")
+        .outcome(abp_core::Outcome::Complete)
+        .with_hash()
+        .unwrap();
+    let path = tmp.path().join("receipt.json");
+    let out = tmp.path().join("trace.json");
+    std::fs::write(&path, serde_json::to_string_pretty(&receipt).unwrap()).unwrap();
+
+    abp()
+        .args([
+            "receipt",
+            "timeline",
+            path.to_str().unwrap(),
+            "--out",
+            out.to_str().unwrap(),
+        ])
+        .assert()
+        .success();
+    let trace: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+    let events = trace["traceEvents"].as_array().unwrap();
+    assert!(events.iter().any(|e| e["ph"] == "X" && e["cat"] == "run"));
+}
+
+#[test]
+fn receipt_github_requires_a_token() {
+    let tmp = tempfile::tempdir().expect("create temp dir");
+    let receipt = abp_core::ReceiptBuilder::new("mock")
+        .outcome(abp_core::Outcome::Complete)
+        .with_hash()
+        .unwrap();
+    let path = tmp.path().join("receipt.json");
+    std::fs::write(&path, serde_json::to_string_pretty(&receipt).unwrap()).unwrap();
+
+    abp()
+        .env_remove("GITHUB_TOKEN")
+        .args([
+            "receipt",
+            "github",
+            path.to_str().unwrap(),
+            "--repo",
+            "octo/widgets",
+            "--sha",
+            "6dcb09b5b57875f334f61aebed695e2e4193db5e",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("GITHUB_TOKEN"));
+}
+
 // ── 27. Events file output ──────────────────────────────────────────
 
 #[test]
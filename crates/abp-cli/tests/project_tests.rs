@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the `abp project` subcommand.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+fn abp() -> Command {
+    #[allow(deprecated)]
+    Command::cargo_bin("abp").expect("binary `abp` should be built")
+}
+
+const CONFIG: &str = r#"
+[backends.fast]
+type = "mock"
+dialect = "open_ai"
+priority = 90
+
+[backends.slow]
+type = "mock"
+dialect = "claude"
+priority = 10
+"#;
+
+fn fixture() -> (tempfile::TempDir, String, String) {
+    let tmp = tempfile::tempdir().expect("create temp dir");
+    let cfg = tmp.path().join("backplane.toml");
+    std::fs::write(&cfg, CONFIG).unwrap();
+    let wo = abp_core::WorkOrderBuilder::new("route me").build();
+    let wo_path = tmp.path().join("wo.json");
+    std::fs::write(&wo_path, serde_json::to_string(&wo).unwrap()).unwrap();
+    let cfg = cfg.to_str().unwrap().to_string();
+    let wo_path = wo_path.to_str().unwrap().to_string();
+    (tmp, cfg, wo_path)
+}
+
+#[test]
+fn project_json_reports_selection_and_fallbacks() {
+    let (_tmp, cfg, wo) = fixture();
+    let out = abp()
+        .args(["--config", &cfg, "project", &wo, "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let report: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(report["selected_backend"], "fast");
+    assert_eq!(report["fallback_chain"][0]["backend_id"], "slow");
+    assert!(report["fidelity_score"]["total"].is_number());
+    assert!(
+        report["unprojected"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|n| n == "mock")
+    );
+}
+
+#[test]
+fn project_prints_human_readable_decision() {
+    let (_tmp, cfg, wo) = fixture();
+    abp()
+        .args(["--config", &cfg, "project", &wo])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("selected: fast"))
+        .stdout(predicate::str::contains("1. slow"))
+        .stdout(predicate::str::contains("not projected (no dialect): mock"));
+}
+
+#[test]
+fn project_rejects_invalid_work_order() {
+    let tmp = tempfile::tempdir().expect("create temp dir");
+    let wo = tmp.path().join("wo.json");
+    std::fs::write(&wo, "{}").unwrap();
+    abp()
+        .args(["project", wo.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("parse work order"));
+}
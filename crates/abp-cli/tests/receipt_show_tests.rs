@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for `abp receipt show` and `abp receipt diff`.
+
+use abp_core::{
+    AgentEvent, AgentEventKind, Outcome, ReceiptBuilder, UsageNormalized, VerificationReport,
+};
+use assert_cmd::Command;
+use chrono::{Duration, Utc};
+use predicates::prelude::*;
+use std::path::{Path, PathBuf};
+
+fn abp() -> Command {
+    #[allow(deprecated)]
+    Command::cargo_bin("abp").expect("binary `abp` should be built")
+}
+
+const DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1,2 @@\n-fn old() {}\n+fn new() {}\n+fn extra() {}\n";
+
+fn write_receipt(dir: &Path) -> PathBuf {
+    let start = Utc::now();
+    let at = |ms: i64, kind: AgentEventKind| AgentEvent {
+        ts: start + Duration::milliseconds(ms),
+        kind,
+        ext: None,
+    };
+    let mut receipt = ReceiptBuilder::new("mock")
+        .outcome(Outcome::Complete)
+        .usage(UsageNormalized {
+            input_tokens: Some(1200),
+            output_tokens: Some(340),
+            ..Default::default()
+        })
+        .verification(VerificationReport {
+            git_diff: Some(DIFF.into()),
+            ..Default::default()
+        })
+        .add_trace_event(at(
+            0,
+            AgentEventKind::AssistantDelta {
+                text: "hello ".into(),
+            },
+        ))
+        .add_trace_event(at(
+            5,
+            AgentEventKind::AssistantDelta {
+                text: "world".into(),
+            },
+        ))
+        .add_trace_event(at(
+            120,
+            AgentEventKind::ToolCall {
+                tool_name: "read_file".into(),
+                tool_use_id: Some("t1".into()),
+                parent_tool_use_id: None,
+                input: serde_json::json!({"path": "src/lib.rs"}),
+            },
+        ))
+        .add_trace_event(at(
+            1500,
+            AgentEventKind::CommandExecuted {
+                command: "cargo test".into(),
+                exit_code: Some(101),
+                output_preview: None,
+            },
+        ))
+        .add_trace_event(at(
+            1600,
+            AgentEventKind::FileChanged {
+                path: "src/lib.rs".into(),
+                summary: "renamed old to new".into(),
+            },
+        ))
+        .build();
+    receipt.meta.started_at = start;
+    let receipt = receipt.with_hash().unwrap();
+    let path = dir.join("receipt.json");
+    std::fs::write(&path, serde_json::to_string_pretty(&receipt).unwrap()).unwrap();
+    path
+}
+
+#[test]
+fn show_renders_text_timeline() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = write_receipt(tmp.path());
+    abp()
+        .args(["receipt", "show", path.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("outcome:  complete"))
+        .stdout(predicate::str::contains("hash:     valid"))
+        .stdout(predicate::str::contains("hello world"))
+        .stdout(predicate::str::contains(
+            "read_file {\"path\":\"src/lib.rs\"}",
+        ))
+        .stdout(predicate::str::contains("! "))
+        .stdout(predicate::str::contains("cargo test (exit 101)"))
+        .stdout(predicate::str::contains("output tokens  340"))
+        .stdout(predicate::str::contains("1 file(s), +2 -1"));
+}
+
+#[test]
+fn show_renders_markdown() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = write_receipt(tmp.path());
+    abp()
+        .args(["receipt", "show", path.to_str().unwrap(), "--markdown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| Outcome | **complete** |"))
+        .stdout(predicate::str::contains("| +1.5s | **command_executed** |"))
+        .stdout(predicate::str::contains("- `src/lib.rs`"))
+        .stdout(predicate::str::contains("```diff\ndiff --git"));
+}
+
+#[test]
+fn show_renders_json() {
+    let tmp = tempfile::tempdir().unwrap();
+    let path = write_receipt(tmp.path());
+    let out = abp()
+        .args(["receipt", "show", path.to_str().unwrap(), "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let view: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(view["hash_valid"], true);
+    assert_eq!(view["tool_calls"], 1);
+    assert_eq!(view["timeline"].as_array().unwrap().len(), 4);
+    assert_eq!(view["timeline"][0]["summary"], "hello world");
+    assert_eq!(view["timeline"][2]["offset_ms"], 1500);
+    assert_eq!(view["files_changed"], serde_json::json!(["src/lib.rs"]));
+    assert_eq!(view["diff_stat"]["insertions"], 2);
+    assert_eq!(view["usage"]["input_tokens"], 1200);
+}
+
+#[test]
+fn show_rejects_json_with_markdown() {
+    abp()
+        .args(["receipt", "show", "r.json", "--json", "--markdown"])
+        .assert()
+        .failure();
+}
+
+#[test]
+fn diff_lists_changed_fields() {
+    let tmp = tempfile::tempdir().unwrap();
+    let a = ReceiptBuilder::new("mock")
+        .outcome(Outcome::Complete)
+        .build();
+    let mut b = a.clone();
+    b.outcome = Outcome::Failed;
+    let pa = tmp.path().join("a.json");
+    let pb = tmp.path().join("b.json");
+    std::fs::write(&pa, serde_json::to_string(&a).unwrap()).unwrap();
+    std::fs::write(&pb, serde_json::to_string(&b).unwrap()).unwrap();
+    abp()
+        .args([
+            "receipt",
+            "diff",
+            pa.to_str().unwrap(),
+            pb.to_str().unwrap(),
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("outcome: Complete -> Failed\n"));
+}
@@ -5,11 +5,14 @@
 use abp_core::{
     AgentEvent, AgentEventKind, Capability, CapabilityManifest, SupportLevel, WorkOrder,
 };
+use abp_sidecar_sdk::VendorConfig;
 use chrono::Utc;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use crate::vendor::CodexVendorConfig;
+
 /// Version string for this dialect adapter.
 pub const DIALECT_VERSION: &str = "codex/v0.1";
 
@@ -553,13 +556,16 @@ pub enum CodexStreamDelta {
 
 /// Map an ABP [`WorkOrder`] to a [`CodexRequest`].
 ///
-/// Uses the work order task as the initial user message and applies
-/// config defaults where the work order does not specify overrides.
+/// Uses the work order task as the initial user message. Knobs under
+/// `config.vendor.codex` (see [`CodexVendorConfig`]) override the config
+/// defaults; other backends' namespaces are ignored.
 pub fn map_work_order(wo: &WorkOrder, config: &CodexConfig) -> CodexRequest {
+    let vendor = CodexVendorConfig::from_work_order_or_default(wo);
     let model = wo
         .config
         .model
         .as_deref()
+        .or(vendor.model.as_deref())
         .unwrap_or(&config.model)
         .to_string();
 
@@ -577,8 +583,8 @@ pub fn map_work_order(wo: &WorkOrder, config: &CodexConfig) -> CodexRequest {
             role: "user".into(),
             content: user_content,
         }],
-        max_output_tokens: config.max_output_tokens,
-        temperature: config.temperature,
+        max_output_tokens: vendor.max_output_tokens.or(config.max_output_tokens),
+        temperature: vendor.temperature.or(config.temperature),
         tools: Vec::new(),
         text: None,
     }
@@ -5,6 +5,9 @@
 //! [`IrConversation`], and [`from_ir`] converts an [`IrConversation`] back
 //! into Codex response items.  [`input_to_ir`] converts [`CodexInputItem`]s
 //! into an [`IrConversation`] for the request path.
+//!
+//! Cache breakpoints ([`IrMessage::cache_control`](abp_core::ir::IrMessage::cache_control)) are dropped: the
+//! Responses API caches prompt prefixes automatically.
 
 use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole, IrUsage};
 
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Schema for the `config.vendor.codex` namespace.
+
+use std::collections::BTreeMap;
+
+use abp_sidecar_sdk::VendorConfig;
+use abp_sidecar_sdk::vendor::check_range;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Codex knobs accepted under `config.vendor.codex`.
+///
+/// Covers the request parameters used by lowering, the metadata written by
+/// [`convert`](crate::convert), and the client and thread settings read by
+/// the Codex sidecar host, including its camelCase spellings. Unknown keys
+/// are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CodexVendorConfig {
+    /// Model override, used when the work order does not set one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Sampling temperature (0–2).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Nucleus sampling probability (0–1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Maximum output tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    /// Whether to stream the response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    /// System instructions carried over from a Codex request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// Source dialect tag written by request conversion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dialect: Option<String>,
+    /// API key passed to the Codex client.
+    #[serde(default, alias = "apiKey", skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// API base URL passed to the Codex client.
+    #[serde(default, alias = "baseUrl", skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Raw Codex client configuration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<Map<String, Value>>,
+    /// Extra environment variables for the Codex process.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<BTreeMap<String, String>>,
+    /// Path to a specific Codex executable.
+    #[serde(
+        default,
+        alias = "codexPathOverride",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub codex_path_override: Option<String>,
+    /// Thread sandbox mode.
+    #[serde(
+        default,
+        alias = "sandboxMode",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sandbox_mode: Option<String>,
+    /// Skip Codex's check that the workspace is a git repository.
+    #[serde(
+        default,
+        alias = "skipGitRepoCheck",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub skip_git_repo_check: Option<bool>,
+    /// Web search mode.
+    #[serde(
+        default,
+        alias = "webSearchMode",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub web_search_mode: Option<String>,
+    /// Whether web search is enabled.
+    #[serde(
+        default,
+        alias = "webSearchEnabled",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub web_search_enabled: Option<bool>,
+    /// Tool approval policy.
+    #[serde(
+        default,
+        alias = "approvalPolicy",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub approval_policy: Option<String>,
+    /// Directories the thread may access besides the workspace.
+    #[serde(
+        default,
+        alias = "additionalDirectories",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub additional_directories: Option<Vec<String>>,
+    /// JSON schema the final response must match.
+    #[serde(
+        default,
+        alias = "outputSchema",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub output_schema: Option<Map<String, Value>>,
+    /// Reasoning effort for the turn.
+    #[serde(
+        default,
+        alias = "modelReasoningEffort",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub model_reasoning_effort: Option<String>,
+    /// Existing thread to continue.
+    #[serde(default, alias = "threadId", skip_serializing_if = "Option::is_none")]
+    pub thread_id: Option<String>,
+    /// Resume `thread_id` instead of starting a new thread.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume: Option<bool>,
+    /// Turn timeout in milliseconds.
+    #[serde(default, alias = "timeoutMs", skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// Retries after a failed turn.
+    #[serde(
+        default,
+        alias = "retryCount",
+        alias = "retries",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retry_count: Option<u32>,
+}
+
+impl VendorConfig for CodexVendorConfig {
+    const NAMESPACE: &'static str = "codex";
+
+    fn check(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0..=2.0)?;
+        check_range("top_p", self.top_p, 0.0..=1.0)?;
+        if self.resume == Some(true) && self.thread_id.is_none() {
+            return Err("resume requires thread_id".into());
+        }
+        Ok(())
+    }
+}
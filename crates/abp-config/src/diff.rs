@@ -128,6 +128,29 @@ pub fn diff(old: &BackplaneConfig, new: &BackplaneConfig) -> ConfigDiff {
         ));
     }
 
+    // Policy presets
+    if old.policy_presets != new.policy_presets {
+        let old_val = format!("{:?}", old.policy_presets);
+        let new_val = format!("{:?}", new.policy_presets);
+        changes.push(ConfigChange::Modified(
+            "policy_presets".into(),
+            old_val,
+            new_val,
+        ));
+    }
+
+    // RBAC
+    if old.rbac != new.rbac {
+        let fmt_rbac = |r: &Option<crate::RbacConfig>| {
+            serde_json::to_string(r).unwrap_or_else(|_| format!("{r:?}"))
+        };
+        changes.push(ConfigChange::Modified(
+            "rbac".into(),
+            fmt_rbac(&old.rbac),
+            fmt_rbac(&new.rbac),
+        ));
+    }
+
     // Backends
     let all_keys: BTreeSet<&String> = old.backends.keys().chain(new.backends.keys()).collect();
     for key in all_keys {
@@ -191,6 +214,21 @@ fn format_backend(entry: &BackendEntry) -> String {
             s.push(')');
             s
         }
+        BackendEntry::Grpc {
+            endpoint,
+            timeout_secs,
+        } => match timeout_secs {
+            Some(t) => format!("grpc(endpoint={endpoint:?}, timeout={t}s)"),
+            None => format!("grpc(endpoint={endpoint:?})"),
+        },
+        BackendEntry::WebSocket { url, timeout_secs } => match timeout_secs {
+            Some(t) => format!("websocket(url={url:?}, timeout={t}s)"),
+            None => format!("websocket(url={url:?})"),
+        },
+        BackendEntry::Ipc { path, timeout_secs } => match timeout_secs {
+            Some(t) => format!("ipc(path={path:?}, timeout={t}s)"),
+            None => format!("ipc(path={path:?})"),
+        },
     }
 }
 
@@ -213,7 +251,9 @@ mod tests {
             bind_address: None,
             port: None,
             policy_profiles: Vec::new(),
+            policy_presets: Vec::new(),
             backends: BTreeMap::new(),
+            rbac: None,
         }
     }
 
@@ -126,7 +126,8 @@ impl fmt::Display for AnalysisResult {
 ///
 /// Default classification rules:
 /// - `log_level`, `receipts_dir`, `workspace_dir` → [`Impact::Safe`]
-/// - `default_backend`, `policy_profiles` → [`Impact::RestartRequired`]
+/// - `default_backend`, `policy_profiles`, `policy_presets` →
+///   [`Impact::RestartRequired`]
 /// - `bind_address`, `port` → [`Impact::Breaking`]
 /// - Backend additions → [`Impact::Safe`]
 /// - Backend modifications → [`Impact::RestartRequired`]
@@ -197,7 +198,7 @@ impl ConfigDiffAnalyzer {
     fn default_classify(&self, field: &str, change: &ConfigChange) -> Impact {
         match field {
             "log_level" | "receipts_dir" | "workspace_dir" => Impact::Safe,
-            "default_backend" | "policy_profiles" => Impact::RestartRequired,
+            "default_backend" | "policy_profiles" | "policy_presets" => Impact::RestartRequired,
             "bind_address" | "port" => Impact::Breaking,
             f if f.starts_with("backends.") => match change {
                 ConfigChange::Added(..) => Impact::Safe,
@@ -241,7 +242,9 @@ mod tests {
             bind_address: None,
             port: None,
             policy_profiles: Vec::new(),
+            policy_presets: Vec::new(),
             backends: BTreeMap::new(),
+            rbac: None,
         }
     }
 
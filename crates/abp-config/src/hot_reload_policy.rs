@@ -197,7 +197,9 @@ mod tests {
             bind_address: None,
             port: None,
             policy_profiles: Vec::new(),
+            policy_presets: Vec::new(),
             backends: BTreeMap::new(),
+            rbac: None,
         }
     }
 
@@ -96,7 +96,7 @@ pub fn validate_config(config: &BackplaneConfig) -> ValidationResult {
             errors.push("backend name must not be empty".into());
         }
 
-        match backend {
+        let timeout_secs = match backend {
             BackendEntry::Sidecar {
                 command,
                 timeout_secs,
@@ -107,18 +107,44 @@ pub fn validate_config(config: &BackplaneConfig) -> ValidationResult {
                         "backend '{name}': sidecar command must not be empty"
                     ));
                 }
-                if let Some(t) = timeout_secs {
-                    if *t == 0 || *t > crate::MAX_TIMEOUT_SECS {
-                        errors.push(format!(
-                            "backend '{name}': timeout {t}s out of range (1..{})",
-                            crate::MAX_TIMEOUT_SECS
-                        ));
-                    } else if *t > crate::LARGE_TIMEOUT_THRESHOLD {
-                        warnings.push(format!("backend '{name}': large timeout ({t}s)"));
-                    }
+                timeout_secs
+            }
+            BackendEntry::Grpc {
+                endpoint,
+                timeout_secs,
+            } => {
+                if !crate::is_grpc_endpoint(endpoint) {
+                    errors.push(format!(
+                        "backend '{name}': grpc endpoint must be an http:// or https:// URI"
+                    ));
+                }
+                timeout_secs
+            }
+            BackendEntry::WebSocket { url, timeout_secs } => {
+                if !crate::is_ws_url(url) {
+                    errors.push(format!(
+                        "backend '{name}': websocket url must be a ws:// URL"
+                    ));
                 }
+                timeout_secs
+            }
+            BackendEntry::Ipc { path, timeout_secs } => {
+                if path.trim().is_empty() {
+                    errors.push(format!("backend '{name}': ipc path must not be empty"));
+                }
+                timeout_secs
+            }
+            BackendEntry::Mock {} => continue,
+        };
+        if let Some(t) = timeout_secs {
+            if *t == 0 || *t > crate::MAX_TIMEOUT_SECS {
+                errors.push(format!(
+                    "backend '{name}': timeout {t}s out of range (1..{})",
+                    crate::MAX_TIMEOUT_SECS
+                ));
+            } else if *t > crate::LARGE_TIMEOUT_THRESHOLD {
+                warnings.push(format!("backend '{name}': large timeout ({t}s)"));
             }
-            BackendEntry::Mock {} => {}
         }
     }
 
@@ -150,7 +176,9 @@ mod tests {
             bind_address: None,
             port: None,
             policy_profiles: Vec::new(),
+            policy_presets: Vec::new(),
             backends: BTreeMap::from([("mock".into(), BackendEntry::Mock {})]),
+            rbac: None,
         }
     }
 
@@ -15,12 +15,15 @@ pub mod env;
 pub mod hot_reload_policy;
 pub mod hot_validate;
 pub mod migrate;
+pub mod rbac;
 pub mod schema;
 pub mod store;
 pub mod transaction;
 pub mod validate;
 pub mod watcher;
 
+pub use rbac::{Permission, RbacConfig};
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -147,9 +150,19 @@ pub struct BackplaneConfig {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub policy_profiles: Vec<String>,
 
+    /// Named policy presets (`read_only`, `docs_only`, `tests_only`,
+    /// `no_network`, `full_sandbox`) applied to every run, composed with the
+    /// run's own policy.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub policy_presets: Vec<String>,
+
     /// Named backend definitions.
     #[serde(default)]
     pub backends: BTreeMap<String, BackendEntry>,
+
+    /// Role-based access control; `None` lets every caller do everything.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rbac: Option<RbacConfig>,
 }
 
 impl Default for BackplaneConfig {
@@ -162,7 +175,9 @@ impl Default for BackplaneConfig {
             bind_address: None,
             port: None,
             policy_profiles: Vec::new(),
+            policy_presets: Vec::new(),
             backends: BTreeMap::new(),
+            rbac: None,
         }
     }
 }
@@ -186,6 +201,34 @@ pub enum BackendEntry {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         timeout_secs: Option<u64>,
     },
+    /// A sidecar reached over gRPC instead of stdio.
+    #[serde(rename = "grpc")]
+    Grpc {
+        /// Server URI, e.g. `http://127.0.0.1:50051`.
+        endpoint: String,
+        /// Optional connect-and-handshake timeout in seconds (1–86 400).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+    },
+    /// A backend on another host, reached over a WebSocket.
+    #[serde(rename = "websocket")]
+    WebSocket {
+        /// Remote URL, e.g. `ws://agents.internal:7070/abp`.
+        url: String,
+        /// Optional connect-and-handshake timeout in seconds (1–86 400).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+    },
+    /// A long-lived sidecar daemon reached over a Unix domain socket or, on
+    /// Windows, a named pipe.
+    #[serde(rename = "ipc")]
+    Ipc {
+        /// Socket path, or pipe name such as `\\.\pipe\abp-sidecar`.
+        path: String,
+        /// Optional connect-and-handshake timeout in seconds (1–86 400).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_secs: Option<u64>,
+    },
 }
 
 // ---------------------------------------------------------------------------
@@ -338,13 +381,20 @@ pub fn validate_config(config: &BackplaneConfig) -> Result<Vec<ConfigWarning>, C
         }
     }
 
+    // Validate policy preset names.
+    for name in &config.policy_presets {
+        if let Err(e) = name.parse::<abp_policy::presets::PolicyPreset>() {
+            errors.push(e.to_string());
+        }
+    }
+
     // Validate each backend entry.
     for (name, backend) in &config.backends {
         if name.is_empty() {
             errors.push("backend name must not be empty".into());
         }
 
-        match backend {
+        let timeout_secs = match backend {
             BackendEntry::Sidecar {
                 command,
                 timeout_secs,
@@ -355,23 +405,54 @@ pub fn validate_config(config: &BackplaneConfig) -> Result<Vec<ConfigWarning>, C
                         "backend '{name}': sidecar command must not be empty"
                     ));
                 }
-                if let Some(t) = timeout_secs {
-                    if *t == 0 || *t > MAX_TIMEOUT_SECS {
-                        errors.push(format!(
-                            "backend '{name}': timeout {t}s out of range (1..{MAX_TIMEOUT_SECS})"
-                        ));
-                    } else if *t > LARGE_TIMEOUT_THRESHOLD {
-                        warnings.push(ConfigWarning::LargeTimeout {
-                            backend: name.clone(),
-                            secs: *t,
-                        });
-                    }
+                timeout_secs
+            }
+            BackendEntry::Grpc {
+                endpoint,
+                timeout_secs,
+            } => {
+                if !is_grpc_endpoint(endpoint) {
+                    errors.push(format!(
+                        "backend '{name}': grpc endpoint must be an http:// or https:// URI"
+                    ));
+                }
+                timeout_secs
+            }
+            BackendEntry::WebSocket { url, timeout_secs } => {
+                if !is_ws_url(url) {
+                    errors.push(format!(
+                        "backend '{name}': websocket url must be a ws:// URL"
+                    ));
                 }
+                timeout_secs
+            }
+            BackendEntry::Ipc { path, timeout_secs } => {
+                if path.trim().is_empty() {
+                    errors.push(format!("backend '{name}': ipc path must not be empty"));
+                }
+                timeout_secs
+            }
+            BackendEntry::Mock {} => continue,
+        };
+        if let Some(t) = timeout_secs {
+            if *t == 0 || *t > MAX_TIMEOUT_SECS {
+                errors.push(format!(
+                    "backend '{name}': timeout {t}s out of range (1..{MAX_TIMEOUT_SECS})"
+                ));
+            } else if *t > LARGE_TIMEOUT_THRESHOLD {
+                warnings.push(ConfigWarning::LargeTimeout {
+                    backend: name.clone(),
+                    secs: *t,
+                });
             }
-            BackendEntry::Mock {} => {}
         }
     }
 
+    // Validate RBAC role bindings.
+    if let Some(ref rbac) = config.rbac {
+        errors.extend(rbac.validate());
+    }
+
     // Advisory: missing optional fields.
     if config.default_backend.is_none() {
         warnings.push(ConfigWarning::MissingOptionalField {
@@ -408,6 +489,11 @@ pub fn merge_configs(base: BackplaneConfig, overlay: BackplaneConfig) -> Backpla
     } else {
         overlay.policy_profiles
     };
+    let policy_presets = if overlay.policy_presets.is_empty() {
+        base.policy_presets
+    } else {
+        overlay.policy_presets
+    };
     BackplaneConfig {
         default_backend: overlay.default_backend.or(base.default_backend),
         workspace_dir: overlay.workspace_dir.or(base.workspace_dir),
@@ -416,7 +502,9 @@ pub fn merge_configs(base: BackplaneConfig, overlay: BackplaneConfig) -> Backpla
         bind_address: overlay.bind_address.or(base.bind_address),
         port: overlay.port.or(base.port),
         policy_profiles,
+        policy_presets,
         backends,
+        rbac: overlay.rbac.or(base.rbac),
     }
 }
 
@@ -472,6 +560,15 @@ pub fn generate_default_toml() -> String {
         "# Paths to policy profile files loaded at startup.",
         "# policy_profiles = [\"policies/default.toml\"]",
         "",
+        "# Named policy presets applied to every run.",
+        "# policy_presets = [\"no_network\"]",
+        "",
+        "# Role-based access control (omit to allow every caller everything).",
+        "# [rbac.roles]",
+        "# operator = [\"submit\", \"cancel\", \"read_receipts\"]",
+        "# [rbac.bindings]",
+        "# \"alice@example.com\" = [\"operator\"]",
+        "",
         "# Backend definitions:",
         "# [backends.mock]",
         "# type = \"mock\"",
@@ -650,6 +747,18 @@ impl ConfigBuilder {
         self
     }
 
+    /// Add a named policy preset.
+    pub fn policy_preset(mut self, name: impl Into<String>) -> Self {
+        self.config.policy_presets.push(name.into());
+        self
+    }
+
+    /// Set the role-based access control section.
+    pub fn rbac(mut self, rbac: RbacConfig) -> Self {
+        self.config.rbac = Some(rbac);
+        self
+    }
+
     /// Add a backend entry.
     pub fn backend(mut self, name: impl Into<String>, entry: BackendEntry) -> Self {
         self.config.backends.insert(name.into(), entry);
@@ -687,6 +796,21 @@ pub(crate) fn is_valid_hostname(s: &str) -> bool {
     })
 }
 
+/// Check whether `s` is an `http://` or `https://` URI with a host, as gRPC
+/// endpoints must be.
+pub(crate) fn is_grpc_endpoint(s: &str) -> bool {
+    s.strip_prefix("http://")
+        .or_else(|| s.strip_prefix("https://"))
+        .is_some_and(|rest| !rest.trim().is_empty() && !rest.starts_with('/'))
+}
+
+/// Check whether `s` is a `ws://` URL with a host. TLS (`wss://`) is
+/// expected to be terminated by a proxy in front of the remote.
+pub(crate) fn is_ws_url(s: &str) -> bool {
+    s.strip_prefix("ws://")
+        .is_some_and(|rest| !rest.trim().is_empty() && !rest.starts_with('/'))
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -1710,4 +1834,152 @@ mod tests {
             validate_config(&cfg).unwrap_or_else(|_| panic!("level '{level}' should be valid"));
         }
     }
+
+    // -- 68. Policy presets must name a known preset --------------------------
+
+    #[test]
+    fn unknown_policy_presets_fail_validation() {
+        let cfg = BackplaneConfig::builder()
+            .policy_preset("read_only")
+            .policy_preset("no-network")
+            .build();
+        validate_config(&cfg).unwrap();
+
+        let cfg = BackplaneConfig::builder().policy_preset("lockdown").build();
+        match validate_config(&cfg).unwrap_err() {
+            ConfigError::ValidationError { reasons } => {
+                assert!(
+                    reasons.iter().any(|r| r.contains("lockdown")),
+                    "{reasons:?}"
+                );
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    // -- 69. gRPC backends parse and validate their endpoint -----------------
+
+    #[test]
+    fn grpc_backend_parses_and_validates_endpoint() {
+        let cfg = parse_toml(
+            r#"
+            [backends.go]
+            type = "grpc"
+            endpoint = "http://127.0.0.1:50051"
+            timeout_secs = 5
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            cfg.backends["go"],
+            BackendEntry::Grpc {
+                endpoint: "http://127.0.0.1:50051".into(),
+                timeout_secs: Some(5),
+            }
+        );
+        validate_config(&cfg).unwrap();
+
+        for endpoint in ["127.0.0.1:50051", "http://", "unix:///tmp/abp.sock"] {
+            let mut cfg = BackplaneConfig::default();
+            cfg.backends.insert(
+                "go".into(),
+                BackendEntry::Grpc {
+                    endpoint: endpoint.into(),
+                    timeout_secs: None,
+                },
+            );
+            match validate_config(&cfg).unwrap_err() {
+                ConfigError::ValidationError { reasons } => {
+                    assert!(
+                        reasons.iter().any(|r| r.contains("grpc endpoint")),
+                        "{endpoint}: {reasons:?}"
+                    );
+                }
+                other => panic!("unexpected error: {other}"),
+            }
+        }
+    }
+
+    // -- 70. WebSocket backends parse and validate their URL -----------------
+
+    #[test]
+    fn websocket_backend_parses_and_validates_url() {
+        let cfg = parse_toml(
+            r#"
+            [backends.remote]
+            type = "websocket"
+            url = "ws://agents.internal:7070/abp"
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            cfg.backends["remote"],
+            BackendEntry::WebSocket {
+                url: "ws://agents.internal:7070/abp".into(),
+                timeout_secs: None,
+            }
+        );
+        validate_config(&cfg).unwrap();
+
+        for url in ["agents.internal:7070", "ws://", "http://agents.internal"] {
+            let mut cfg = BackplaneConfig::default();
+            cfg.backends.insert(
+                "remote".into(),
+                BackendEntry::WebSocket {
+                    url: url.into(),
+                    timeout_secs: None,
+                },
+            );
+            match validate_config(&cfg).unwrap_err() {
+                ConfigError::ValidationError { reasons } => {
+                    assert!(
+                        reasons.iter().any(|r| r.contains("websocket url")),
+                        "{url}: {reasons:?}"
+                    );
+                }
+                other => panic!("unexpected error: {other}"),
+            }
+        }
+    }
+
+    // -- 71. IPC backends parse and require a path ---------------------------
+
+    #[test]
+    fn ipc_backend_parses_and_requires_path() {
+        let cfg = parse_toml(
+            r#"
+            [backends.daemon]
+            type = "ipc"
+            path = "/run/abp/claude.sock"
+            timeout_secs = 5
+        "#,
+        )
+        .unwrap();
+        assert_eq!(
+            cfg.backends["daemon"],
+            BackendEntry::Ipc {
+                path: "/run/abp/claude.sock".into(),
+                timeout_secs: Some(5),
+            }
+        );
+        validate_config(&cfg).unwrap();
+
+        let mut cfg = BackplaneConfig::default();
+        cfg.backends.insert(
+            "daemon".into(),
+            BackendEntry::Ipc {
+                path: " ".into(),
+                timeout_secs: None,
+            },
+        );
+        match validate_config(&cfg).unwrap_err() {
+            ConfigError::ValidationError { reasons } => {
+                assert!(
+                    reasons.iter().any(|r| r.contains("ipc path")),
+                    "{reasons:?}"
+                );
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
 }
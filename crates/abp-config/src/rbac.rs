@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Role-based access control settings.
+//!
+//! An [`RbacConfig`] names roles, lists the [`Permission`]s each role grants,
+//! and binds caller identities to roles:
+//!
+//! ```toml
+//! [rbac]
+//! anonymous_roles = []
+//!
+//! [rbac.roles]
+//! operator = ["submit", "cancel", "read_receipts"]
+//! auditor = ["read_receipts"]
+//! admin = ["submit", "cancel", "read_receipts"]
+//!
+//! [rbac.bindings]
+//! "alice@example.com" = ["admin"]
+//! "ci-bot" = ["operator"]
+//! ```
+//!
+//! When no `[rbac]` section is present, every caller may do everything.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+// ---------------------------------------------------------------------------
+// Permission
+// ---------------------------------------------------------------------------
+
+/// A runtime operation that can be granted to a role.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize, JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// Submit work orders for execution.
+    Submit,
+    /// Cancel and delete runs.
+    Cancel,
+    /// List runs and read their status and receipts.
+    ReadReceipts,
+}
+
+impl Permission {
+    /// Every permission, in declaration order.
+    pub const ALL: [Permission; 3] = [
+        Permission::Submit,
+        Permission::Cancel,
+        Permission::ReadReceipts,
+    ];
+
+    /// The `snake_case` name used in configuration files.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Permission::Submit => "submit",
+            Permission::Cancel => "cancel",
+            Permission::ReadReceipts => "read_receipts",
+        }
+    }
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// RbacConfig
+// ---------------------------------------------------------------------------
+
+/// Role definitions and identity bindings (the `[rbac]` config section).
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+pub struct RbacConfig {
+    /// Role name → permissions the role grants.
+    #[serde(default)]
+    pub roles: BTreeMap<String, BTreeSet<Permission>>,
+
+    /// Caller identity → roles held by that caller.
+    #[serde(default)]
+    pub bindings: BTreeMap<String, Vec<String>>,
+
+    /// Roles granted to callers that present no identity.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub anonymous_roles: Vec<String>,
+}
+
+impl RbacConfig {
+    /// Roles held by `identity`, or the anonymous roles for `None`.
+    ///
+    /// Unknown identities hold no roles.
+    pub fn roles_for(&self, identity: Option<&str>) -> &[String] {
+        match identity {
+            Some(id) => self.bindings.get(id).map(Vec::as_slice).unwrap_or(&[]),
+            None => &self.anonymous_roles,
+        }
+    }
+
+    /// Union of the permissions granted by every role `identity` holds.
+    pub fn permissions_for(&self, identity: Option<&str>) -> BTreeSet<Permission> {
+        self.roles_for(identity)
+            .iter()
+            .filter_map(|role| self.roles.get(role))
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// Whether `identity` may perform `permission`.
+    pub fn allows(&self, identity: Option<&str>, permission: Permission) -> bool {
+        self.roles_for(identity)
+            .iter()
+            .filter_map(|role| self.roles.get(role))
+            .any(|perms| perms.contains(&permission))
+    }
+
+    /// Problems with the section: bindings to roles that are not defined.
+    pub fn validate(&self) -> Vec<String> {
+        let bound = self
+            .bindings
+            .iter()
+            .flat_map(|(id, roles)| roles.iter().map(move |r| (format!("identity '{id}'"), r)));
+        let anonymous = self
+            .anonymous_roles
+            .iter()
+            .map(|r| ("anonymous_roles".to_string(), r));
+        bound
+            .chain(anonymous)
+            .filter(|(_, role)| !self.roles.contains_key(*role))
+            .map(|(who, role)| format!("rbac: {who} is bound to undefined role '{role}'"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> RbacConfig {
+        toml::from_str(
+            r#"
+            anonymous_roles = ["viewer"]
+
+            [roles]
+            viewer = ["read_receipts"]
+            operator = ["submit", "cancel", "read_receipts"]
+
+            [bindings]
+            alice = ["operator"]
+            bob = ["viewer", "operator"]
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn bound_identity_gets_role_permissions() {
+        let rbac = sample();
+        assert!(rbac.allows(Some("alice"), Permission::Submit));
+        assert!(rbac.allows(Some("alice"), Permission::Cancel));
+        assert!(rbac.allows(Some("alice"), Permission::ReadReceipts));
+    }
+
+    #[test]
+    fn permissions_are_unioned_across_roles() {
+        let perms = sample().permissions_for(Some("bob"));
+        assert_eq!(
+            perms.into_iter().collect::<Vec<_>>(),
+            [
+                Permission::Submit,
+                Permission::Cancel,
+                Permission::ReadReceipts
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_and_anonymous_callers() {
+        let rbac = sample();
+        assert!(!rbac.allows(Some("mallory"), Permission::ReadReceipts));
+        assert!(rbac.allows(None, Permission::ReadReceipts));
+        assert!(!rbac.allows(None, Permission::Submit));
+    }
+
+    #[test]
+    fn undefined_roles_are_reported() {
+        let mut rbac = sample();
+        rbac.bindings.insert("carol".into(), vec!["root".into()]);
+        rbac.anonymous_roles.push("guest".into());
+        assert_eq!(
+            rbac.validate(),
+            [
+                "rbac: identity 'carol' is bound to undefined role 'root'",
+                "rbac: anonymous_roles is bound to undefined role 'guest'",
+            ]
+        );
+        assert!(sample().validate().is_empty());
+    }
+
+    #[test]
+    fn permission_names_match_serde() {
+        for p in Permission::ALL {
+            assert_eq!(serde_json::to_value(p).unwrap(), p.as_str());
+        }
+    }
+}
@@ -131,6 +131,14 @@ impl ConfigSchema {
                 constraint: None,
                 description: "Paths to policy profile files loaded at startup",
             },
+            FieldSchema {
+                name: "policy_presets",
+                field_type: FieldType::StringArray,
+                required: false,
+                default: Some("[]"),
+                constraint: Some("read_only, docs_only, tests_only, no_network, full_sandbox"),
+                description: "Named policy presets applied to every run",
+            },
             FieldSchema {
                 name: "backends",
                 field_type: FieldType::BackendMap,
@@ -218,7 +226,7 @@ pub fn validate_against_schema(config: &BackplaneConfig) -> Vec<ValidationIssue>
             });
         }
 
-        match backend {
+        let timeout_secs = match backend {
             BackendEntry::Sidecar {
                 command,
                 timeout_secs,
@@ -230,23 +238,56 @@ pub fn validate_against_schema(config: &BackplaneConfig) -> Vec<ValidationIssue>
                         message: format!("backends.{name}.command: must not be empty"),
                     });
                 }
-                if let Some(t) = timeout_secs {
-                    if *t == 0 || *t > MAX_TIMEOUT_SECS {
-                        issues.push(ValidationIssue {
-                            severity: Severity::Error,
-                            message: format!(
-                                "backends.{name}.timeout_secs: {t} out of range (1..{MAX_TIMEOUT_SECS})"
-                            ),
-                        });
-                    } else if *t > LARGE_TIMEOUT_THRESHOLD {
-                        issues.push(ValidationIssue {
-                            severity: Severity::Warning,
-                            message: format!("backends.{name}.timeout_secs: large timeout ({t}s)"),
-                        });
-                    }
+                timeout_secs
+            }
+            BackendEntry::Grpc {
+                endpoint,
+                timeout_secs,
+            } => {
+                if !crate::is_grpc_endpoint(endpoint) {
+                    issues.push(ValidationIssue {
+                        severity: Severity::Error,
+                        message: format!(
+                            "backends.{name}.endpoint: must be an http:// or https:// URI"
+                        ),
+                    });
+                }
+                timeout_secs
+            }
+            BackendEntry::WebSocket { url, timeout_secs } => {
+                if !crate::is_ws_url(url) {
+                    issues.push(ValidationIssue {
+                        severity: Severity::Error,
+                        message: format!("backends.{name}.url: must be a ws:// URL"),
+                    });
                 }
+                timeout_secs
+            }
+            BackendEntry::Ipc { path, timeout_secs } => {
+                if path.trim().is_empty() {
+                    issues.push(ValidationIssue {
+                        severity: Severity::Error,
+                        message: format!("backends.{name}.path: must not be empty"),
+                    });
+                }
+                timeout_secs
+            }
+            BackendEntry::Mock {} => continue,
+        };
+        if let Some(t) = timeout_secs {
+            if *t == 0 || *t > MAX_TIMEOUT_SECS {
+                issues.push(ValidationIssue {
+                    severity: Severity::Error,
+                    message: format!(
+                        "backends.{name}.timeout_secs: {t} out of range (1..{MAX_TIMEOUT_SECS})"
+                    ),
+                });
+            } else if *t > LARGE_TIMEOUT_THRESHOLD {
+                issues.push(ValidationIssue {
+                    severity: Severity::Warning,
+                    message: format!("backends.{name}.timeout_secs: large timeout ({t}s)"),
+                });
             }
-            BackendEntry::Mock {} => {}
         }
     }
 
@@ -295,7 +336,9 @@ mod tests {
             bind_address: None,
             port: None,
             policy_profiles: Vec::new(),
+            policy_presets: Vec::new(),
             backends: BTreeMap::from([("mock".into(), BackendEntry::Mock {})]),
+            rbac: None,
         }
     }
 
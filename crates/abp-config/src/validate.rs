@@ -105,7 +105,7 @@ impl ConfigValidator {
                 errors.push("backend name must not be empty".into());
             }
 
-            match backend {
+            let timeout_secs = match backend {
                 BackendEntry::Sidecar {
                     command,
                     timeout_secs,
@@ -116,20 +116,46 @@ impl ConfigValidator {
                             "backend '{name}': sidecar command must not be empty"
                         ));
                     }
-                    if let Some(t) = timeout_secs {
-                        if *t == 0 || *t > MAX_TIMEOUT_SECS {
-                            errors.push(format!(
-                                "backend '{name}': timeout {t}s out of range (1..{MAX_TIMEOUT_SECS})"
-                            ));
-                        } else if *t > LARGE_TIMEOUT_THRESHOLD {
-                            issues.push(ValidationIssue {
-                                severity: Severity::Warning,
-                                message: format!("backend '{name}' has a large timeout ({t}s)"),
-                            });
-                        }
+                    timeout_secs
+                }
+                BackendEntry::Grpc {
+                    endpoint,
+                    timeout_secs,
+                } => {
+                    if !crate::is_grpc_endpoint(endpoint) {
+                        errors.push(format!(
+                            "backend '{name}': grpc endpoint must be an http:// or https:// URI"
+                        ));
+                    }
+                    timeout_secs
+                }
+                BackendEntry::WebSocket { url, timeout_secs } => {
+                    if !crate::is_ws_url(url) {
+                        errors.push(format!(
+                            "backend '{name}': websocket url must be a ws:// URL"
+                        ));
                     }
+                    timeout_secs
+                }
+                BackendEntry::Ipc { path, timeout_secs } => {
+                    if path.trim().is_empty() {
+                        errors.push(format!("backend '{name}': ipc path must not be empty"));
+                    }
+                    timeout_secs
+                }
+                BackendEntry::Mock {} => continue,
+            };
+            if let Some(t) = timeout_secs {
+                if *t == 0 || *t > MAX_TIMEOUT_SECS {
+                    errors.push(format!(
+                        "backend '{name}': timeout {t}s out of range (1..{MAX_TIMEOUT_SECS})"
+                    ));
+                } else if *t > LARGE_TIMEOUT_THRESHOLD {
+                    issues.push(ValidationIssue {
+                        severity: Severity::Warning,
+                        message: format!("backend '{name}' has a large timeout ({t}s)"),
+                    });
                 }
-                BackendEntry::Mock {} => {}
             }
         }
 
@@ -227,6 +253,15 @@ pub fn diff_configs(a: &BackplaneConfig, b: &BackplaneConfig) -> Vec<ConfigDiff>
         });
     }
 
+    // Policy presets
+    if a.policy_presets != b.policy_presets {
+        diffs.push(ConfigDiff {
+            path: "policy_presets".into(),
+            old_value: format!("{:?}", a.policy_presets),
+            new_value: format!("{:?}", b.policy_presets),
+        });
+    }
+
     // Backends: compare keyed entries.
     let all_keys: BTreeSet<&String> = a.backends.keys().chain(b.backends.keys()).collect();
     for key in all_keys {
@@ -315,6 +350,21 @@ fn format_backend_entry(entry: &BackendEntry) -> String {
             s.push(')');
             s
         }
+        BackendEntry::Grpc {
+            endpoint,
+            timeout_secs,
+        } => match timeout_secs {
+            Some(t) => format!("grpc(endpoint={endpoint:?}, timeout={t}s)"),
+            None => format!("grpc(endpoint={endpoint:?})"),
+        },
+        BackendEntry::WebSocket { url, timeout_secs } => match timeout_secs {
+            Some(t) => format!("websocket(url={url:?}, timeout={t}s)"),
+            None => format!("websocket(url={url:?})"),
+        },
+        BackendEntry::Ipc { path, timeout_secs } => match timeout_secs {
+            Some(t) => format!("ipc(path={path:?}, timeout={t}s)"),
+            None => format!("ipc(path={path:?})"),
+        },
     }
 }
 
@@ -454,7 +504,7 @@ impl ConfigValidator {
                 });
             }
 
-            match backend {
+            let timeout_secs = match backend {
                 BackendEntry::Sidecar {
                     command,
                     timeout_secs,
@@ -467,25 +517,57 @@ impl ConfigValidator {
                             severity: IssueSeverity::Error,
                         });
                     }
-                    if let Some(t) = timeout_secs {
-                        if *t == 0 || *t > MAX_TIMEOUT_SECS {
-                            errors.push(ConfigIssue {
-                                field: format!("backends.{name}.timeout_secs"),
-                                message: format!(
-                                    "timeout {t}s out of range (1..{MAX_TIMEOUT_SECS})"
-                                ),
-                                severity: IssueSeverity::Error,
-                            });
-                        } else if *t > LARGE_TIMEOUT_THRESHOLD {
-                            warnings.push(ConfigIssue {
-                                field: format!("backends.{name}.timeout_secs"),
-                                message: format!("large timeout ({t}s); consider reducing"),
-                                severity: IssueSeverity::Warning,
-                            });
-                        }
+                    timeout_secs
+                }
+                BackendEntry::Grpc {
+                    endpoint,
+                    timeout_secs,
+                } => {
+                    if !crate::is_grpc_endpoint(endpoint) {
+                        errors.push(ConfigIssue {
+                            field: format!("backends.{name}.endpoint"),
+                            message: "grpc endpoint must be an http:// or https:// URI".into(),
+                            severity: IssueSeverity::Error,
+                        });
+                    }
+                    timeout_secs
+                }
+                BackendEntry::WebSocket { url, timeout_secs } => {
+                    if !crate::is_ws_url(url) {
+                        errors.push(ConfigIssue {
+                            field: format!("backends.{name}.url"),
+                            message: "websocket url must be a ws:// URL".into(),
+                            severity: IssueSeverity::Error,
+                        });
                     }
+                    timeout_secs
+                }
+                BackendEntry::Ipc { path, timeout_secs } => {
+                    if path.trim().is_empty() {
+                        errors.push(ConfigIssue {
+                            field: format!("backends.{name}.path"),
+                            message: "ipc path must not be empty".into(),
+                            severity: IssueSeverity::Error,
+                        });
+                    }
+                    timeout_secs
+                }
+                BackendEntry::Mock {} => continue,
+            };
+            if let Some(t) = timeout_secs {
+                if *t == 0 || *t > MAX_TIMEOUT_SECS {
+                    errors.push(ConfigIssue {
+                        field: format!("backends.{name}.timeout_secs"),
+                        message: format!("timeout {t}s out of range (1..{MAX_TIMEOUT_SECS})"),
+                        severity: IssueSeverity::Error,
+                    });
+                } else if *t > LARGE_TIMEOUT_THRESHOLD {
+                    warnings.push(ConfigIssue {
+                        field: format!("backends.{name}.timeout_secs"),
+                        message: format!("large timeout ({t}s); consider reducing"),
+                        severity: IssueSeverity::Warning,
+                    });
                 }
-                BackendEntry::Mock {} => {}
             }
         }
 
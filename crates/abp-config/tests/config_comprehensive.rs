@@ -834,10 +834,12 @@ fn toml_roundtrip() {
         bind_address: Some("127.0.0.1".into()),
         port: Some(9090),
         policy_profiles: vec!["p.toml".into()],
+        policy_presets: Vec::new(),
         backends: BTreeMap::from([
             ("m".into(), BackendEntry::Mock {}),
             ("s".into(), sidecar_entry("node", Some(120))),
         ]),
+        rbac: None,
     };
     let ser = toml::to_string(&cfg).unwrap();
     let de: BackplaneConfig = toml::from_str(&ser).unwrap();
@@ -854,7 +856,9 @@ fn json_roundtrip() {
         bind_address: Some("::1".into()),
         port: Some(443),
         policy_profiles: vec!["pol.toml".into()],
+        policy_presets: Vec::new(),
         backends: BTreeMap::from([("m".into(), BackendEntry::Mock {})]),
+        rbac: None,
     };
     let json = serde_json::to_string(&cfg).unwrap();
     let de: BackplaneConfig = serde_json::from_str(&json).unwrap();
@@ -1927,7 +1931,9 @@ fn check_no_errors_for_valid_full_config() {
         bind_address: Some("127.0.0.1".into()),
         port: Some(8080),
         policy_profiles: vec![],
+        policy_presets: Vec::new(),
         backends: BTreeMap::new(),
+        rbac: None,
     };
     cfg.backends.insert("mock".into(), BackendEntry::Mock {});
     let result = ConfigValidator::check(&cfg);
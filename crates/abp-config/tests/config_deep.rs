@@ -1168,6 +1168,7 @@ fn roundtrip_full_config() {
         bind_address: Some("127.0.0.1".into()),
         port: Some(8080),
         policy_profiles: vec!["a.toml".into(), "b.toml".into()],
+        policy_presets: Vec::new(),
         backends: BTreeMap::from([
             ("mock".into(), BackendEntry::Mock {}),
             (
@@ -1179,6 +1180,7 @@ fn roundtrip_full_config() {
                 },
             ),
         ]),
+        rbac: None,
     };
     let s = toml::to_string(&cfg).unwrap();
     let de: BackplaneConfig = toml::from_str(&s).unwrap();
@@ -57,6 +57,7 @@ fn fully_valid_config() -> BackplaneConfig {
         bind_address: None,
         port: None,
         policy_profiles: Vec::new(),
+        policy_presets: Vec::new(),
         backends: BTreeMap::from([
             ("mock".into(), BackendEntry::Mock {}),
             (
@@ -68,6 +69,7 @@ fn fully_valid_config() -> BackplaneConfig {
                 },
             ),
         ]),
+        rbac: None,
     }
 }
 
@@ -1083,6 +1085,7 @@ fn toml_roundtrip_with_all_scalar_fields() {
         bind_address: Some("0.0.0.0".into()),
         port: Some(8443),
         policy_profiles: vec!["policy1.toml".into(), "policy2.toml".into()],
+        policy_presets: Vec::new(),
         backends: BTreeMap::from([
             ("mock".into(), BackendEntry::Mock {}),
             (
@@ -1094,6 +1097,7 @@ fn toml_roundtrip_with_all_scalar_fields() {
                 },
             ),
         ]),
+        rbac: None,
     };
     let serialized = toml::to_string(&cfg).unwrap();
     let deserialized: BackplaneConfig = toml::from_str(&serialized).unwrap();
@@ -27,7 +27,9 @@ fn base_config() -> BackplaneConfig {
         bind_address: None,
         port: None,
         policy_profiles: Vec::new(),
+        policy_presets: Vec::new(),
         backends: BTreeMap::new(),
+        rbac: None,
     }
 }
 
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Errors raised while loading golden transcripts.
+
+use std::path::PathBuf;
+
+/// A golden transcript could not be loaded.
+#[derive(Debug, thiserror::Error)]
+pub enum ConformanceError {
+    /// The transcript file could not be read.
+    #[error("failed to read transcript {path}")]
+    Io {
+        /// File that was read.
+        path: PathBuf,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// A line is not a valid case.
+    #[error("invalid case on line {line}")]
+    Parse {
+        /// 1-based line number.
+        line: usize,
+        /// Underlying JSON error.
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// Two cases share a name.
+    #[error("duplicate case name '{name}' on line {line}")]
+    DuplicateCase {
+        /// The repeated name.
+        name: String,
+        /// 1-based line number of the second occurrence.
+        line: usize,
+    },
+}
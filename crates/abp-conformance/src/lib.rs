@@ -0,0 +1,18 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+#![doc = include_str!("../README.md")]
+#![deny(unsafe_code)]
+#![warn(missing_docs)]
+
+//! Golden-transcript conformance harness for Agent Backplane backends.
+
+mod error;
+pub mod matching;
+mod runner;
+mod transcript;
+
+pub use error::ConformanceError;
+pub use runner::{
+    CORE_LABEL, CapabilityTally, CaseResult, CaseStatus, ConformanceReport, ConformanceRunner,
+    DEFAULT_TIMEOUT,
+};
+pub use transcript::{CaseRequest, GoldenCase, Transcript};
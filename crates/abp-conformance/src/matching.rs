@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! JSON pattern matching used for expected events and receipt fields.
+
+use serde_json::Value;
+
+/// Whether `actual` matches `pattern`.
+///
+/// Objects match when every key of the pattern is present in `actual` and
+/// matches recursively; extra keys are ignored. Arrays match element-wise
+/// and must have the same length. Other values must be equal.
+#[must_use]
+pub fn matches(pattern: &Value, actual: &Value) -> bool {
+    mismatch(pattern, actual, "$").is_none()
+}
+
+/// The first point at which `actual` fails to match `pattern`, as a
+/// JSON-path-like location and a description.
+#[must_use]
+pub fn mismatch(pattern: &Value, actual: &Value, path: &str) -> Option<String> {
+    match (pattern, actual) {
+        (Value::Object(want), Value::Object(got)) => want.iter().find_map(|(key, w)| {
+            let at = format!("{path}.{key}");
+            match got.get(key) {
+                Some(g) => mismatch(w, g, &at),
+                None => Some(format!("{at}: missing, expected {w}")),
+            }
+        }),
+        (Value::Array(want), Value::Array(got)) => {
+            if want.len() != got.len() {
+                return Some(format!(
+                    "{path}: expected {} elements, got {}",
+                    want.len(),
+                    got.len()
+                ));
+            }
+            want.iter()
+                .zip(got)
+                .enumerate()
+                .find_map(|(i, (w, g))| mismatch(w, g, &format!("{path}[{i}]")))
+        }
+        _ if pattern == actual => None,
+        _ => Some(format!("{path}: expected {pattern}, got {actual}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn objects_match_on_listed_keys_only() {
+        let actual = json!({"type": "assistant_message", "text": "hi", "ts": "now"});
+        assert!(matches(&json!({"type": "assistant_message"}), &actual));
+        assert!(!matches(&json!({"type": "run_started"}), &actual));
+    }
+
+    #[test]
+    fn mismatch_names_the_path() {
+        let actual = json!({"backend": {"id": "mock"}, "trace": [1, 2]});
+        assert_eq!(
+            mismatch(&json!({"backend": {"id": "other"}}), &actual, "$").unwrap(),
+            "$.backend.id: expected \"other\", got \"mock\""
+        );
+        assert!(
+            mismatch(&json!({"trace": [1]}), &actual, "$")
+                .unwrap()
+                .starts_with("$.trace: expected 1 elements")
+        );
+        assert!(
+            mismatch(&json!({"usage": {}}), &actual, "$")
+                .unwrap()
+                .contains("missing")
+        );
+    }
+}
@@ -0,0 +1,319 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Running golden cases against a backend and reporting the results.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use abp_backend_core::Backend;
+use abp_core::{AgentEvent, CONTRACT_VERSION, Capability, Receipt, SupportLevel};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::matching::{matches, mismatch};
+use crate::transcript::{GoldenCase, Transcript};
+
+/// Label under which cases that name no capability are tallied.
+pub const CORE_LABEL: &str = "core";
+
+/// How long a single case may run before it fails.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Outcome of one case.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CaseStatus {
+    /// Every expectation held.
+    Passed,
+    /// At least one expectation did not hold.
+    Failed {
+        /// What went wrong, one entry per broken expectation.
+        reasons: Vec<String>,
+    },
+    /// The backend does not advertise a capability the case exercises.
+    Skipped {
+        /// Capabilities the backend lacks.
+        missing: Vec<Capability>,
+    },
+}
+
+/// Result of one [`GoldenCase`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaseResult {
+    /// Case name.
+    pub name: String,
+    /// Capabilities the case exercises.
+    pub capabilities: Vec<Capability>,
+    /// What happened.
+    pub status: CaseStatus,
+}
+
+/// Pass/fail/skip counts for one capability.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapabilityTally {
+    /// Cases that passed.
+    pub passed: usize,
+    /// Cases that failed.
+    pub failed: usize,
+    /// Cases that were skipped.
+    pub skipped: usize,
+}
+
+/// Results of a transcript run against one backend.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConformanceReport {
+    /// Identity id of the backend under test.
+    pub backend: String,
+    /// Per-case results in transcript order.
+    pub cases: Vec<CaseResult>,
+}
+
+impl ConformanceReport {
+    /// Whether no case failed. Skipped cases do not count against the backend.
+    #[must_use]
+    pub fn is_conformant(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// The cases that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &CaseResult> {
+        self.cases
+            .iter()
+            .filter(|c| matches!(c.status, CaseStatus::Failed { .. }))
+    }
+
+    /// Counts per capability, keyed by its snake_case name; cases naming no
+    /// capability are tallied under [`CORE_LABEL`].
+    #[must_use]
+    pub fn by_capability(&self) -> BTreeMap<String, CapabilityTally> {
+        let mut tallies: BTreeMap<String, CapabilityTally> = BTreeMap::new();
+        for case in &self.cases {
+            let labels: Vec<String> = if case.capabilities.is_empty() {
+                vec![CORE_LABEL.to_string()]
+            } else {
+                case.capabilities.iter().map(capability_label).collect()
+            };
+            for label in labels {
+                let tally = tallies.entry(label).or_default();
+                match case.status {
+                    CaseStatus::Passed => tally.passed += 1,
+                    CaseStatus::Failed { .. } => tally.failed += 1,
+                    CaseStatus::Skipped { .. } => tally.skipped += 1,
+                }
+            }
+        }
+        tallies
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "conformance of backend '{}':", self.backend)?;
+        for (label, tally) in self.by_capability() {
+            writeln!(
+                f,
+                "  {label}: {} passed, {} failed, {} skipped",
+                tally.passed, tally.failed, tally.skipped
+            )?;
+        }
+        for case in self.failures() {
+            if let CaseStatus::Failed { reasons } = &case.status {
+                writeln!(f, "  FAILED {}:", case.name)?;
+                for reason in reasons {
+                    writeln!(f, "    - {reason}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn capability_label(capability: &Capability) -> String {
+    serde_json::to_value(capability)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{capability:?}"))
+}
+
+/// Runs golden transcripts against [`Backend`] implementations.
+///
+/// # Examples
+///
+/// ```no_run
+/// use abp_conformance::{ConformanceRunner, Transcript};
+///
+/// # async fn demo(backend: &dyn abp_backend_core::Backend) {
+/// let suite = Transcript::load("goldens/mock.jsonl").unwrap();
+/// let report = ConformanceRunner::new().run(backend, &suite).await;
+/// assert!(report.is_conformant(), "{report}");
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConformanceRunner {
+    timeout: Duration,
+}
+
+impl Default for ConformanceRunner {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl ConformanceRunner {
+    /// A runner with the [`DEFAULT_TIMEOUT`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how long a single case may run.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Run every case of `transcript` against `backend`, in order.
+    pub async fn run(&self, backend: &dyn Backend, transcript: &Transcript) -> ConformanceReport {
+        let mut cases = Vec::with_capacity(transcript.len());
+        for case in &transcript.cases {
+            cases.push(self.run_case(backend, case).await);
+        }
+        ConformanceReport {
+            backend: backend.identity().id,
+            cases,
+        }
+    }
+
+    /// Run one case against `backend`.
+    pub async fn run_case(&self, backend: &dyn Backend, case: &GoldenCase) -> CaseResult {
+        let advertised = backend.capabilities();
+        let missing: Vec<Capability> = case
+            .capabilities
+            .iter()
+            .filter(|c| matches!(advertised.get(*c), None | Some(SupportLevel::Unsupported)))
+            .cloned()
+            .collect();
+        let status = if missing.is_empty() {
+            let reasons = self.check(backend, case).await;
+            if reasons.is_empty() {
+                CaseStatus::Passed
+            } else {
+                CaseStatus::Failed { reasons }
+            }
+        } else {
+            CaseStatus::Skipped { missing }
+        };
+        CaseResult {
+            name: case.name.clone(),
+            capabilities: case.capabilities.clone(),
+            status,
+        }
+    }
+
+    async fn check(&self, backend: &dyn Backend, case: &GoldenCase) -> Vec<String> {
+        let work_order = case.request.to_work_order();
+        let work_order_id = work_order.id;
+        let run_id = Uuid::new_v4();
+
+        let (tx, mut rx) = mpsc::channel::<AgentEvent>(256);
+        let collect = async {
+            let mut events = Vec::new();
+            while let Some(ev) = rx.recv().await {
+                events.push(ev);
+            }
+            events
+        };
+        let run = async { tokio::join!(backend.run(run_id, work_order, tx), collect) };
+        let Ok((result, events)) = tokio::time::timeout(self.timeout, run).await else {
+            return vec![format!("run did not finish within {:?}", self.timeout)];
+        };
+
+        let receipt = match (result, &case.expected_error) {
+            (Err(e), Some(needle)) => {
+                let message = format!("{e:#}");
+                return if message.contains(needle.as_str()) {
+                    Vec::new()
+                } else {
+                    vec![format!("error '{message}' does not contain '{needle}'")]
+                };
+            }
+            (Err(e), None) => return vec![format!("run failed: {e:#}")],
+            (Ok(_), Some(needle)) => {
+                return vec![format!(
+                    "run succeeded; expected an error containing '{needle}'"
+                )];
+            }
+            (Ok(receipt), None) => receipt,
+        };
+
+        let mut reasons = contract_violations(&receipt, run_id, work_order_id);
+        reasons.extend(event_violations(&case.expected_events, &events));
+        if let Some(pattern) = &case.expected_receipt {
+            match serde_json::to_value(&receipt) {
+                Ok(actual) => reasons.extend(mismatch(pattern, &actual, "receipt")),
+                Err(e) => reasons.push(format!("receipt does not serialize: {e}")),
+            }
+        }
+        reasons
+    }
+}
+
+/// Receipt fields every backend must get right regardless of the case.
+fn contract_violations(receipt: &Receipt, run_id: Uuid, work_order_id: Uuid) -> Vec<String> {
+    let mut reasons = Vec::new();
+    if receipt.meta.run_id != run_id {
+        reasons.push(format!(
+            "receipt run_id {} does not echo {run_id}",
+            receipt.meta.run_id
+        ));
+    }
+    if receipt.meta.work_order_id != work_order_id {
+        reasons.push(format!(
+            "receipt work_order_id {} does not echo {work_order_id}",
+            receipt.meta.work_order_id
+        ));
+    }
+    if receipt.meta.contract_version != CONTRACT_VERSION {
+        reasons.push(format!(
+            "receipt contract_version '{}' is not '{CONTRACT_VERSION}'",
+            receipt.meta.contract_version
+        ));
+    }
+    if let Some(hash) = &receipt.receipt_sha256 {
+        match abp_core::receipt_hash(receipt) {
+            Ok(expected) if &expected == hash => {}
+            Ok(expected) => reasons.push(format!(
+                "receipt_sha256 {hash} does not match computed {expected}"
+            )),
+            Err(e) => reasons.push(format!("receipt cannot be hashed: {e}")),
+        }
+    }
+    reasons
+}
+
+/// Check that `patterns` match `events` in order, allowing other events in
+/// between.
+fn event_violations(patterns: &[Value], events: &[AgentEvent]) -> Vec<String> {
+    let actual: Vec<Value> = events
+        .iter()
+        .map(|e| serde_json::to_value(e).unwrap_or(Value::Null))
+        .collect();
+    let mut next = 0;
+    for (i, pattern) in patterns.iter().enumerate() {
+        match actual[next..].iter().position(|a| matches(pattern, a)) {
+            Some(offset) => next += offset + 1,
+            None => {
+                return vec![format!(
+                    "expected event #{i} {pattern} not streamed after event #{next} ({} events streamed)",
+                    actual.len()
+                )];
+            }
+        }
+    }
+    Vec::new()
+}
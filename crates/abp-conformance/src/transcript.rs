@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Golden transcripts: cases loaded from JSON-lines files.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use abp_core::{Capability, CapabilityRequirements, WorkOrder, WorkOrderBuilder, WorkspaceMode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::ConformanceError;
+
+/// The request half of a case, turned into a [`WorkOrder`] per run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaseRequest {
+    /// Task text of the work order.
+    pub task: String,
+    /// Model to request, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Capability requirements of the work order.
+    #[serde(default)]
+    pub requirements: CapabilityRequirements,
+    /// Entries merged into `config.vendor`, e.g. `{"abp": {"mode": "passthrough"}}`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vendor: BTreeMap<String, Value>,
+}
+
+impl CaseRequest {
+    /// A fresh work order for this request, run in pass-through mode on the
+    /// current directory.
+    #[must_use]
+    pub fn to_work_order(&self) -> WorkOrder {
+        let mut builder = WorkOrderBuilder::new(&self.task)
+            .workspace_mode(WorkspaceMode::PassThrough)
+            .requirements(self.requirements.clone());
+        if let Some(model) = &self.model {
+            builder = builder.model(model);
+        }
+        let mut work_order = builder.build();
+        work_order.config.vendor.extend(self.vendor.clone());
+        work_order
+    }
+}
+
+/// One golden case: request, expected events, and expected receipt fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoldenCase {
+    /// Unique name within the transcript.
+    pub name: String,
+    /// Capabilities the case exercises. The case is skipped on backends that
+    /// do not advertise all of them; an empty list counts as core contract.
+    #[serde(default)]
+    pub capabilities: Vec<Capability>,
+    /// The request to run.
+    pub request: CaseRequest,
+    /// Patterns the streamed events must match, in order. Other events may
+    /// appear in between.
+    #[serde(default)]
+    pub expected_events: Vec<Value>,
+    /// Pattern the serialized receipt must match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_receipt: Option<Value>,
+    /// Substring the run's error must contain; the run must fail when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_error: Option<String>,
+}
+
+/// An ordered set of [`GoldenCase`]s.
+///
+/// # Examples
+///
+/// ```
+/// use abp_conformance::Transcript;
+///
+/// let suite = Transcript::parse(
+///     r#"{"name": "hello", "request": {"task": "hi"}, "expected_receipt": {"outcome": "complete"}}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(suite.cases[0].name, "hello");
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Transcript {
+    /// Cases in file order.
+    pub cases: Vec<GoldenCase>,
+}
+
+impl Transcript {
+    /// Parse JSON-lines text, one case per non-blank line.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConformanceError::Parse`] for a malformed line and
+    /// [`ConformanceError::DuplicateCase`] for a repeated case name.
+    pub fn parse(text: &str) -> Result<Self, ConformanceError> {
+        let mut cases = Vec::new();
+        let mut names = BTreeSet::new();
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            if raw.trim().is_empty() {
+                continue;
+            }
+            let case: GoldenCase = serde_json::from_str(raw)
+                .map_err(|source| ConformanceError::Parse { line, source })?;
+            if !names.insert(case.name.clone()) {
+                return Err(ConformanceError::DuplicateCase {
+                    name: case.name,
+                    line,
+                });
+            }
+            cases.push(case);
+        }
+        Ok(Self { cases })
+    }
+
+    /// Read and parse a JSON-lines file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConformanceError::Io`] if the file cannot be read, or any
+    /// error of [`parse`](Self::parse).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ConformanceError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|source| ConformanceError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&text)
+    }
+
+    /// Number of cases.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cases.len()
+    }
+
+    /// Whether the transcript has no cases.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+}
@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Runs the golden transcripts against the mock backend and checks that the
+//! harness reports non-conforming backends.
+
+use abp_backend_core::Backend;
+use abp_backend_mock::MockBackend;
+use abp_conformance::{
+    CORE_LABEL, CapabilityTally, CaseStatus, ConformanceError, ConformanceRunner, Transcript,
+};
+use abp_core::{AgentEvent, BackendIdentity, Capability, CapabilityManifest, Receipt, WorkOrder};
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn mock_suite() -> Transcript {
+    Transcript::load(concat!(env!("CARGO_MANIFEST_DIR"), "/goldens/mock.jsonl")).unwrap()
+}
+
+#[tokio::test]
+async fn mock_backend_conforms_to_its_goldens() {
+    let report = ConformanceRunner::new()
+        .run(&MockBackend, &mock_suite())
+        .await;
+    assert!(report.is_conformant(), "{report}");
+    assert_eq!(report.backend, "mock");
+
+    let tallies = report.by_capability();
+    assert_eq!(
+        tallies["streaming"],
+        CapabilityTally {
+            passed: 1,
+            failed: 0,
+            skipped: 0
+        }
+    );
+    assert_eq!(tallies[CORE_LABEL].passed, 5);
+    // The mock does not advertise vision, so the vision case is skipped.
+    assert_eq!(tallies["vision"].skipped, 1);
+    let skipped = report.cases.iter().find(|c| c.name == "streams_images");
+    assert_eq!(
+        skipped.unwrap().status,
+        CaseStatus::Skipped {
+            missing: vec![Capability::Vision]
+        }
+    );
+}
+
+/// Mock backend that forgets to echo the run id and streams nothing.
+struct Sloppy;
+
+#[async_trait]
+impl Backend for Sloppy {
+    fn identity(&self) -> BackendIdentity {
+        BackendIdentity {
+            id: "sloppy".into(),
+            backend_version: None,
+            adapter_version: None,
+        }
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        MockBackend.capabilities()
+    }
+
+    async fn run(
+        &self,
+        _run_id: Uuid,
+        work_order: WorkOrder,
+        _events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        let (tx, _rx) = mpsc::channel(16);
+        MockBackend.run(Uuid::nil(), work_order, tx).await
+    }
+}
+
+#[tokio::test]
+async fn contract_and_golden_violations_are_reported() {
+    let report = ConformanceRunner::new().run(&Sloppy, &mock_suite()).await;
+    assert!(!report.is_conformant());
+
+    let streaming = report
+        .cases
+        .iter()
+        .find(|c| c.name == "streams_a_complete_run")
+        .unwrap();
+    let CaseStatus::Failed { reasons } = &streaming.status else {
+        panic!("expected failure, got {:?}", streaming.status);
+    };
+    assert!(reasons.iter().any(|r| r.contains("does not echo")));
+    assert!(reasons.iter().any(|r| r.contains("expected event #0")));
+
+    // Every run breaks the run-id contract except the one expected to fail.
+    assert_eq!(report.failures().count(), 6);
+    assert_eq!(report.by_capability()[CORE_LABEL].passed, 1);
+    assert!(report.to_string().contains("FAILED streams_a_complete_run"));
+}
+
+/// Backend that never answers.
+struct Hung;
+
+#[async_trait]
+impl Backend for Hung {
+    fn identity(&self) -> BackendIdentity {
+        Sloppy.identity()
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        CapabilityManifest::new()
+    }
+
+    async fn run(
+        &self,
+        _run_id: Uuid,
+        _work_order: WorkOrder,
+        _events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        std::future::pending().await
+    }
+}
+
+#[tokio::test]
+async fn hung_backend_times_out() {
+    let suite = Transcript::parse(r#"{"name": "t", "request": {"task": "wait"}}"#).unwrap();
+    let report = ConformanceRunner::new()
+        .timeout(Duration::from_millis(50))
+        .run(&Hung, &suite)
+        .await;
+    let CaseStatus::Failed { reasons } = &report.cases[0].status else {
+        panic!("expected failure");
+    };
+    assert!(reasons[0].contains("did not finish"));
+}
+
+#[test]
+fn malformed_and_duplicate_lines_are_rejected() {
+    let err = Transcript::parse("\n{\"name\": \"a\"}\n").unwrap_err();
+    assert!(matches!(err, ConformanceError::Parse { line: 2, .. }));
+
+    let line = r#"{"name": "a", "request": {"task": "t"}}"#;
+    let err = Transcript::parse(&format!("{line}\n{line}")).unwrap_err();
+    assert!(matches!(
+        err,
+        ConformanceError::DuplicateCase { line: 2, .. }
+    ));
+
+    let dir = tempfile::tempdir().unwrap();
+    let err = Transcript::load(dir.path().join("missing.jsonl")).unwrap_err();
+    assert!(matches!(err, ConformanceError::Io { .. }));
+}
@@ -4,11 +4,14 @@
 use abp_core::{
     AgentEvent, AgentEventKind, Capability, CapabilityManifest, SupportLevel, WorkOrder,
 };
+use abp_sidecar_sdk::VendorConfig;
 use chrono::Utc;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use crate::vendor::CopilotVendorConfig;
+
 /// Version string for this dialect adapter.
 pub const DIALECT_VERSION: &str = "copilot/v0.1";
 
@@ -398,12 +401,16 @@ pub enum CopilotStreamEvent {
 /// Map an ABP [`WorkOrder`] to a [`CopilotRequest`].
 ///
 /// Populates references from the work order's context files and snippets.
-/// Uses the work order task as the initial user message.
+/// Uses the work order task as the initial user message. Knobs under
+/// `config.vendor.copilot` (see [`CopilotVendorConfig`]) override the config
+/// defaults; other backends' namespaces are ignored.
 pub fn map_work_order(wo: &WorkOrder, config: &CopilotConfig) -> CopilotRequest {
+    let vendor = CopilotVendorConfig::from_work_order_or_default(wo);
     let model = wo
         .config
         .model
         .as_deref()
+        .or(vendor.model.as_deref())
         .unwrap_or(&config.model)
         .to_string();
 
@@ -434,7 +441,11 @@ pub fn map_work_order(wo: &WorkOrder, config: &CopilotConfig) -> CopilotRequest
 
     let mut messages = Vec::new();
 
-    if let Some(system_prompt) = &config.system_prompt {
+    if let Some(system_prompt) = vendor
+        .system_message
+        .as_ref()
+        .or(config.system_prompt.as_ref())
+    {
         messages.push(CopilotMessage {
             role: "system".into(),
             content: system_prompt.clone(),
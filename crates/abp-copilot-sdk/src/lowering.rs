@@ -4,6 +4,9 @@
 //! [`to_ir`] converts a slice of [`CopilotMessage`]s into an
 //! [`IrConversation`], and [`from_ir`] converts an [`IrConversation`] back
 //! into Copilot messages.
+//!
+//! Cache breakpoints ([`IrMessage::cache_control`](abp_core::ir::IrMessage::cache_control)) are dropped; Copilot
+//! exposes no prompt-caching controls.
 
 use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole};
 use std::collections::BTreeMap;
@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Schema for the `config.vendor.copilot` namespace.
+
+use abp_sidecar_sdk::VendorConfig;
+use abp_sidecar_sdk::vendor::check_range;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Copilot knobs accepted under `config.vendor.copilot`.
+///
+/// Covers the settings used by lowering, the metadata written by
+/// [`convert`](crate::convert), and the session settings read by the
+/// Copilot sidecar host, including its camelCase spellings. Unknown keys
+/// are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CopilotVendorConfig {
+    /// Model override, used when the work order does not set one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Sampling temperature (0–2).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Nucleus sampling probability (0–1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Whether to stream the response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    /// Source dialect tag written by request conversion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dialect: Option<String>,
+    /// Copilot intent carried over from a Copilot request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intent: Option<String>,
+    /// Copilot references carried over from a Copilot request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub references: Option<Vec<Value>>,
+    /// System message, replacing the configured system prompt.
+    #[serde(
+        default,
+        alias = "systemMessage",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub system_message: Option<String>,
+    /// Reasoning effort forwarded to the host.
+    #[serde(
+        default,
+        alias = "reasoningEffort",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub reasoning_effort: Option<String>,
+    /// GitHub token for the Copilot session.
+    #[serde(default, alias = "apiToken", skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Session timeout in milliseconds.
+    #[serde(default, alias = "timeoutMs", skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// Attempts before giving up on a failed session.
+    #[serde(
+        default,
+        alias = "retryAttempts",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retry_attempts: Option<u32>,
+    /// Base delay between retries in milliseconds.
+    #[serde(
+        default,
+        alias = "retryBaseDelayMs",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retry_base_delay_ms: Option<u64>,
+    /// Tools the session may use.
+    #[serde(
+        default,
+        alias = "availableTools",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub available_tools: Option<Vec<String>>,
+    /// Tools the session must not use.
+    #[serde(
+        default,
+        alias = "excludedTools",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub excluded_tools: Option<Vec<String>>,
+    /// MCP servers to attach to the session.
+    #[serde(default, alias = "mcpServers", skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<Value>,
+    /// Existing session to continue.
+    #[serde(default, alias = "sessionId", skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+}
+
+impl VendorConfig for CopilotVendorConfig {
+    const NAMESPACE: &'static str = "copilot";
+
+    fn check(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0..=2.0)?;
+        check_range("top_p", self.top_p, 0.0..=1.0)
+    }
+}
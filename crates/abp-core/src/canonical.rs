@@ -0,0 +1,256 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Canonical JSON serialization for hashing and signatures.
+//!
+//! The canonical form follows the JSON Canonicalization Scheme
+//! ([RFC 8785](https://www.rfc-editor.org/rfc/rfc8785)) so that hashes of
+//! [`WorkOrder`](crate::WorkOrder)s, [`Receipt`](crate::Receipt)s, and
+//! protocol envelopes are stable across serde versions, serializer
+//! settings (e.g. `preserve_order`), and implementation languages:
+//!
+//! - object keys are sorted by their UTF-16 code units;
+//! - no insignificant whitespace is emitted;
+//! - floating-point numbers use the ECMAScript `Number.prototype.toString`
+//!   format (`1.0` → `1`, `1e21` → `1e+21`, `1e-7` → `1e-7`);
+//! - strings escape only `"`, `\`, and control characters, using the short
+//!   forms `\b \f \n \r \t` where available and lowercase `\u00xx` otherwise.
+//!
+//! One deliberate deviation from RFC 8785: integers that fit in `i64`/`u64`
+//! are written exactly rather than being rounded through an IEEE-754 double.
+
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+use crate::ContractError;
+
+/// Serialize `value` to its canonical JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::canonical::to_canonical_string;
+///
+/// let json = to_canonical_string(&serde_json::json!({"b": 1.0, "a": [true, null]})).unwrap();
+/// assert_eq!(json, r#"{"a":[true,null],"b":1}"#);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`ContractError::Json`] if the value cannot be serialized.
+pub fn to_canonical_string<T: Serialize + ?Sized>(value: &T) -> Result<String, ContractError> {
+    let v = serde_json::to_value(value)?;
+    Ok(value_to_canonical_string(&v))
+}
+
+/// Serialize `value` to canonical JSON bytes (UTF-8).
+///
+/// # Errors
+///
+/// Returns [`ContractError::Json`] if the value cannot be serialized.
+pub fn to_canonical_bytes<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, ContractError> {
+    to_canonical_string(value).map(String::into_bytes)
+}
+
+/// Hex-encoded SHA-256 digest of the canonical form of `value`.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::canonical::canonical_sha256;
+///
+/// let a = canonical_sha256(&serde_json::json!({"x": 1, "y": 2})).unwrap();
+/// let b = canonical_sha256(&serde_json::json!({"y": 2.0, "x": 1})).unwrap();
+/// assert_eq!(a, b);
+/// ```
+///
+/// # Errors
+///
+/// Returns [`ContractError::Json`] if the value cannot be serialized.
+pub fn canonical_sha256<T: Serialize + ?Sized>(value: &T) -> Result<String, ContractError> {
+    to_canonical_string(value).map(|s| crate::sha256_hex(s.as_bytes()))
+}
+
+/// Render an already-built [`Value`] in canonical form.
+#[must_use]
+pub fn value_to_canonical_string(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(out, n),
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => write_object(out, map),
+    }
+}
+
+fn write_object(out: &mut String, map: &Map<String, Value>) {
+    let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+    entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+    out.push('{');
+    for (i, (key, value)) in entries.into_iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_string(out, key);
+        out.push(':');
+        write_value(out, value);
+    }
+    out.push('}');
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < '\u{20}' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_number(out: &mut String, n: &Number) {
+    if let Some(i) = n.as_i64() {
+        out.push_str(&i.to_string());
+    } else if let Some(u) = n.as_u64() {
+        out.push_str(&u.to_string());
+    } else if let Some(f) = n.as_f64() {
+        out.push_str(&format_f64(f));
+    } else {
+        // Unreachable without `arbitrary_precision`; fall back to serde_json.
+        out.push_str(&n.to_string());
+    }
+}
+
+/// Format a finite `f64` the way ECMAScript's `Number.prototype.toString` does.
+fn format_f64(f: f64) -> String {
+    if f == 0.0 {
+        // Both +0 and -0 serialize as "0".
+        return "0".to_string();
+    }
+    if !f.is_finite() {
+        // serde_json never produces these, but stay total.
+        return "null".to_string();
+    }
+
+    // Rust's `{:e}` yields the shortest round-tripping digits, e.g. "1.25e-7".
+    let sci = format!("{:e}", f.abs());
+    let (mantissa, exp) = sci
+        .split_once('e')
+        .expect("LowerExp always has an exponent");
+    let exp: i32 = exp.parse().expect("LowerExp exponent is an integer");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    let n = exp + 1;
+
+    let body = if k <= n && n <= 21 {
+        format!("{digits}{}", "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        let (int, frac) = digits.split_at(n as usize);
+        format!("{int}.{frac}")
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{digits}", "0".repeat((-n) as usize))
+    } else {
+        let e = n - 1;
+        let sign = if e < 0 { '-' } else { '+' };
+        let (first, rest) = digits.split_at(1);
+        if rest.is_empty() {
+            format!("{first}e{sign}{}", e.abs())
+        } else {
+            format!("{first}.{rest}e{sign}{}", e.abs())
+        }
+    };
+
+    if f < 0.0 { format!("-{body}") } else { body }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn canon(v: Value) -> String {
+        value_to_canonical_string(&v)
+    }
+
+    #[test]
+    fn keys_sorted_recursively_without_whitespace() {
+        let v = json!({"z": {"b": 1, "a": 2}, "a": [ {"y": 1, "x": 2} ]});
+        assert_eq!(canon(v), r#"{"a":[{"x":2,"y":1}],"z":{"a":2,"b":1}}"#);
+    }
+
+    #[test]
+    fn keys_sorted_by_utf16_code_units() {
+        // U+1F600 (surrogate pair D83D..) sorts before U+FF61 in UTF-16,
+        // although its UTF-8 encoding sorts after.
+        let v = json!({"\u{ff61}": 1, "\u{1f600}": 2});
+        assert_eq!(canon(v), "{\"\u{1f600}\":2,\"\u{ff61}\":1}");
+    }
+
+    #[test]
+    fn floats_use_ecmascript_format() {
+        let cases = [
+            (1.0, "1"),
+            (-1.5, "-1.5"),
+            (0.1, "0.1"),
+            (100.0, "100"),
+            (1e20, "100000000000000000000"),
+            (1e21, "1e+21"),
+            (1.5e300, "1.5e+300"),
+            (0.000001, "0.000001"),
+            (1e-7, "1e-7"),
+            (-2.5e-8, "-2.5e-8"),
+            (123.456, "123.456"),
+            (-0.0, "0"),
+        ];
+        for (f, expected) in cases {
+            assert_eq!(format_f64(f), expected, "formatting {f}");
+        }
+    }
+
+    #[test]
+    fn integers_are_exact() {
+        assert_eq!(canon(json!(u64::MAX)), u64::MAX.to_string());
+        assert_eq!(canon(json!(i64::MIN)), i64::MIN.to_string());
+    }
+
+    #[test]
+    fn strings_escape_only_required_characters() {
+        let v = json!("a\"b\\c\n\t\u{1}\u{7f}é/");
+        assert_eq!(canon(v), "\"a\\\"b\\\\c\\n\\t\\u0001\u{7f}é/\"");
+    }
+
+    #[test]
+    fn matches_serde_json_for_integer_only_values() {
+        let v = json!({"b": [1, 2, {"d": null, "c": "x"}], "a": true});
+        assert_eq!(canon(v.clone()), serde_json::to_string(&v).unwrap());
+    }
+
+    #[test]
+    fn hash_is_independent_of_float_spelling() {
+        let a = canonical_sha256(&json!({"cost": 2.0})).unwrap();
+        let b = canonical_sha256(&json!({"cost": 2})).unwrap();
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Injectable time source.
+//!
+//! Timing-dependent code (budgets, retries, heartbeats, receipt timestamps)
+//! reads time through a [`Clock`](crate::clock::Clock) instead of calling
+//! `Utc::now()` or `Instant::now()` directly.
+//! [`SystemClock`](crate::clock::SystemClock) is the real clock;
+//! [`ManualClock`](crate::clock::ManualClock) only moves when a test advances
+//! it, so timeouts and durations can be exercised without sleeping.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+
+/// A source of wall-clock and monotonic time.
+pub trait Clock: Send + Sync + fmt::Debug {
+    /// Current wall-clock time, used for timestamps.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Current monotonic time, used for measuring durations.
+    fn instant(&self) -> Instant;
+
+    /// Account for a wait of `duration` and return how long the caller must
+    /// still sleep for real.
+    ///
+    /// The system clock returns `duration` unchanged. A manual clock advances
+    /// itself instead and returns zero, so waits in tests complete at once.
+    fn sleep_for(&self, duration: Duration) -> Duration {
+        duration
+    }
+}
+
+/// A clock shared between components.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The system clock, shared.
+#[must_use]
+pub fn system_clock() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// The real clock, backed by [`Utc::now`] and [`Instant::now`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when told to.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::clock::{Clock, ManualClock};
+/// use std::time::Duration;
+///
+/// let clock = ManualClock::default();
+/// let (t0, i0) = (clock.now(), clock.instant());
+/// clock.advance(Duration::from_secs(5));
+/// assert_eq!((clock.now() - t0).num_seconds(), 5);
+/// assert_eq!(clock.instant() - i0, Duration::from_secs(5));
+///
+/// // Sleeping advances the clock instead of blocking.
+/// assert_eq!(clock.sleep_for(Duration::from_secs(1)), Duration::ZERO);
+/// assert_eq!(clock.elapsed(), Duration::from_secs(6));
+/// ```
+#[derive(Debug)]
+pub struct ManualClock {
+    start: DateTime<Utc>,
+    base: Instant,
+    elapsed: Mutex<Duration>,
+}
+
+impl ManualClock {
+    /// Create a clock whose wall time starts at `start`.
+    #[must_use]
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            base: Instant::now(),
+            elapsed: Mutex::new(Duration::ZERO),
+        }
+    }
+
+    /// Move the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().expect("clock mutex poisoned") += duration;
+    }
+
+    /// Total time the clock has been advanced since creation.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.lock().expect("clock mutex poisoned")
+    }
+}
+
+impl Default for ManualClock {
+    /// A clock starting at the Unix epoch.
+    fn default() -> Self {
+        Self::new(DateTime::UNIX_EPOCH)
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        chrono::Duration::from_std(self.elapsed())
+            .ok()
+            .and_then(|d| self.start.checked_add_signed(d))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+
+    fn instant(&self) -> Instant {
+        self.base + self.elapsed()
+    }
+
+    fn sleep_for(&self, duration: Duration) -> Duration {
+        self.advance(duration);
+        Duration::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_clock_moves_forward() {
+        let clock = SystemClock;
+        let i0 = clock.instant();
+        assert!(clock.instant() >= i0);
+        assert_eq!(
+            clock.sleep_for(Duration::from_millis(3)),
+            Duration::from_millis(3)
+        );
+    }
+
+    #[test]
+    fn manual_clock_is_frozen_until_advanced() {
+        let clock = ManualClock::default();
+        assert_eq!(clock.now(), DateTime::UNIX_EPOCH);
+        assert_eq!(clock.instant(), clock.instant());
+        clock.advance(Duration::from_millis(1500));
+        assert_eq!(clock.now().timestamp_millis(), 1500);
+    }
+
+    #[test]
+    fn shared_clock_is_object_safe() {
+        let manual = Arc::new(ManualClock::default());
+        let shared: SharedClock = manual.clone();
+        manual.advance(Duration::from_secs(1));
+        assert_eq!(shared.now().timestamp(), 1);
+    }
+}
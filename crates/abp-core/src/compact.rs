@@ -0,0 +1,240 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Trace compaction: semantic summarization of receipts for storage.
+//!
+//! Streaming backends emit one [`AssistantDelta`](crate::AgentEventKind::AssistantDelta)
+//! per token and the runtime adds idle heartbeats, so a long run's trace is
+//! mostly noise once it is archived. [`Receipt::compact`](crate::Receipt::compact)
+//! rewrites the trace under a
+//! [`CompactionPolicy`](crate::compact::CompactionPolicy): runs of deltas
+//! become one [`AssistantMessage`](crate::AgentEventKind::AssistantMessage)
+//! (or are dropped when the backend already sent the full message), and
+//! heartbeats or all progress events can be dropped.
+//!
+//! Compaction is lossy. The compacted receipt records a
+//! [`CompactionRecord`](crate::compact::CompactionRecord) under
+//! `usage_raw["compaction"]` with the policy applied and the hash of the
+//! original receipt, so an auditor can tell a compacted receipt from a full
+//! one and match it to the original if that was kept elsewhere.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{AgentEvent, AgentEventKind, ContractError, Receipt};
+
+/// Key under `receipt.usage_raw` holding the [`CompactionRecord`].
+pub const COMPACTION_KEY: &str = "compaction";
+
+/// Extension key marking runtime idle heartbeats (`Progress` events the
+/// runtime synthesizes while a backend is silent).
+pub const HEARTBEAT_EXT_KEY: &str = "abp.idle_ms";
+
+/// What [`Receipt::compact`] may discard.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::compact::CompactionPolicy;
+///
+/// let policy = CompactionPolicy::default();
+/// assert!(policy.merge_deltas);
+/// assert!(policy.drop_heartbeats);
+/// assert!(!policy.drop_progress);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactionPolicy {
+    /// Replace each run of consecutive assistant deltas with one assistant
+    /// message. Deltas carrying `ext` metadata are kept as they are.
+    pub merge_deltas: bool,
+    /// Drop runtime idle heartbeats.
+    pub drop_heartbeats: bool,
+    /// Drop every `Progress` event, heartbeats included.
+    pub drop_progress: bool,
+}
+
+impl Default for CompactionPolicy {
+    fn default() -> Self {
+        Self {
+            merge_deltas: true,
+            drop_heartbeats: true,
+            drop_progress: false,
+        }
+    }
+}
+
+impl CompactionPolicy {
+    /// The most aggressive policy: merge deltas and drop all progress events.
+    #[must_use]
+    pub fn aggressive() -> Self {
+        Self {
+            merge_deltas: true,
+            drop_heartbeats: true,
+            drop_progress: true,
+        }
+    }
+
+    fn drops(&self, event: &AgentEvent) -> bool {
+        if !matches!(event.kind, AgentEventKind::Progress { .. }) {
+            return false;
+        }
+        self.drop_progress
+            || (self.drop_heartbeats
+                && event
+                    .ext
+                    .as_ref()
+                    .is_some_and(|ext| ext.contains_key(HEARTBEAT_EXT_KEY)))
+    }
+}
+
+/// How a receipt was compacted, stored under `usage_raw["compaction"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactionRecord {
+    /// `receipt_sha256` of the receipt before its first compaction.
+    pub original_sha256: Option<String>,
+    /// Policy that was applied.
+    pub policy: CompactionPolicy,
+    /// Trace length before compaction.
+    pub events_before: usize,
+    /// Trace length after compaction.
+    pub events_after: usize,
+}
+
+impl CompactionRecord {
+    /// Read the compaction record of a receipt, if it was compacted.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        let value = receipt.usage_raw.get(COMPACTION_KEY)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+/// Rewrite `trace` according to `policy`.
+#[must_use]
+pub fn compact_trace(trace: Vec<AgentEvent>, policy: &CompactionPolicy) -> Vec<AgentEvent> {
+    let mut out: Vec<AgentEvent> = Vec::with_capacity(trace.len());
+    // Start index in `out` of the delta run being merged, if any.
+    let mut run_start: Option<usize> = None;
+
+    for event in trace {
+        if policy.drops(&event) {
+            continue;
+        }
+        let mergeable = policy.merge_deltas
+            && event.ext.is_none()
+            && matches!(event.kind, AgentEventKind::AssistantDelta { .. });
+        if mergeable {
+            match run_start {
+                Some(start) => {
+                    if let (
+                        AgentEventKind::AssistantDelta { text },
+                        AgentEventKind::AssistantDelta { text: more },
+                    ) = (&mut out[start].kind, &event.kind)
+                    {
+                        text.push_str(more);
+                    }
+                }
+                None => {
+                    run_start = Some(out.len());
+                    out.push(event);
+                }
+            }
+            continue;
+        }
+        if let Some(start) = run_start.take() {
+            finish_run(&mut out, start, Some(&event));
+        }
+        out.push(event);
+    }
+    if let Some(start) = run_start {
+        finish_run(&mut out, start, None);
+    }
+    out
+}
+
+/// Turn the merged delta at `out[start]` into an assistant message, or drop
+/// it if `next` is the same message in full.
+fn finish_run(out: &mut Vec<AgentEvent>, start: usize, next: Option<&AgentEvent>) {
+    let merged = out.remove(start);
+    let AgentEventKind::AssistantDelta { text } = merged.kind else {
+        out.insert(start, merged);
+        return;
+    };
+    if let Some(AgentEvent {
+        kind: AgentEventKind::AssistantMessage { text: full },
+        ..
+    }) = next
+        && *full == text
+    {
+        return;
+    }
+    out.insert(
+        start,
+        AgentEvent {
+            ts: merged.ts,
+            kind: AgentEventKind::AssistantMessage { text },
+            ext: None,
+        },
+    );
+}
+
+impl Receipt {
+    /// Compact the trace under `policy` for cheaper storage.
+    ///
+    /// Records a [`CompactionRecord`] under `usage_raw["compaction"]` that
+    /// keeps the original `receipt_sha256`. If the receipt was hashed, the
+    /// compacted receipt is re-hashed so it still verifies on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use abp_core::compact::{CompactionPolicy, CompactionRecord};
+    /// use abp_core::{AgentEvent, AgentEventKind, ReceiptBuilder};
+    ///
+    /// let delta = |t: &str| AgentEvent {
+    ///     ts: chrono::Utc::now(),
+    ///     kind: AgentEventKind::AssistantDelta { text: t.into() },
+    ///     ext: None,
+    /// };
+    /// let receipt = ReceiptBuilder::new("mock")
+    ///     .add_trace_event(delta("Hel"))
+    ///     .add_trace_event(delta("lo"))
+    ///     .build()
+    ///     .with_hash()
+    ///     .unwrap();
+    /// let original = receipt.receipt_sha256.clone();
+    ///
+    /// let compacted = receipt.compact(&CompactionPolicy::default()).unwrap();
+    /// assert_eq!(compacted.trace.len(), 1);
+    /// let record = CompactionRecord::from_receipt(&compacted).unwrap();
+    /// assert_eq!(record.original_sha256, original);
+    /// assert_ne!(compacted.receipt_sha256, original);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ContractError::Json`] if the receipt cannot be re-hashed.
+    pub fn compact(mut self, policy: &CompactionPolicy) -> Result<Self, ContractError> {
+        let previous = CompactionRecord::from_receipt(&self);
+        let events_before = self.trace.len();
+        self.trace = compact_trace(std::mem::take(&mut self.trace), policy);
+        let record = CompactionRecord {
+            original_sha256: previous.as_ref().map_or_else(
+                || self.receipt_sha256.clone(),
+                |p| p.original_sha256.clone(),
+            ),
+            policy: *policy,
+            events_before: previous.map_or(events_before, |p| p.events_before),
+            events_after: self.trace.len(),
+        };
+
+        if !self.usage_raw.is_object() {
+            self.usage_raw = serde_json::json!({ "original": self.usage_raw.take() });
+        }
+        if let Some(map) = self.usage_raw.as_object_mut() {
+            map.insert(COMPACTION_KEY.to_string(), serde_json::to_value(&record)?);
+        }
+
+        if self.receipt_sha256.is_some() {
+            self = self.with_hash()?;
+        }
+        Ok(self)
+    }
+}
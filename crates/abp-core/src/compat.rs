@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Contract compatibility checks between a host and a sidecar.
+//!
+//! Implements the policy from `docs/versioning.md` for
+//! [`CONTRACT_VERSION`](crate::CONTRACT_VERSION) strings of the form
+//! `abp/v{major}.{minor}`:
+//!
+//! - same major, same minor — identical contracts;
+//! - same major, different minor — compatible, the difference is additive
+//!   (optional fields, enum variants, envelope types, capability keys);
+//! - different major — breaking, the peers cannot talk.
+//!
+//! [`check`](crate::compat::check) returns a
+//! [`CompatReport`](crate::compat::CompatReport) whose message says which side is
+//! behind and what to upgrade, instead of a bare "version mismatch".
+
+use std::fmt;
+
+use abp_error::{AbpError, ErrorCode};
+use serde::{Deserialize, Serialize};
+
+/// How two contract versions relate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatLevel {
+    /// Both sides speak exactly the same contract.
+    Identical,
+    /// The sidecar is a newer minor version; it may send additions the host
+    /// does not know about, which the host ignores.
+    SidecarNewerMinor,
+    /// The sidecar is an older minor version; it will not use additions the
+    /// host knows about, and optional fields take their defaults.
+    SidecarOlderMinor,
+    /// Major versions differ — breaking changes separate the two contracts.
+    MajorMismatch,
+    /// One of the version strings is not of the form `abp/v{major}.{minor}`.
+    Malformed,
+}
+
+impl CompatLevel {
+    /// Whether the two sides can interoperate at this level.
+    #[must_use]
+    pub fn is_compatible(self) -> bool {
+        matches!(
+            self,
+            Self::Identical | Self::SidecarNewerMinor | Self::SidecarOlderMinor
+        )
+    }
+}
+
+/// Result of comparing a host and a sidecar contract version.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::compat::{self, CompatLevel};
+///
+/// let report = compat::check("abp/v0.1", "abp/v0.3");
+/// assert_eq!(report.level, CompatLevel::SidecarNewerMinor);
+/// assert!(report.is_compatible());
+///
+/// let report = compat::check("abp/v0.1", "abp/v1.0");
+/// assert!(!report.is_compatible());
+/// assert!(report.to_string().contains("upgrade the host"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompatReport {
+    /// Contract version of the host (control plane).
+    pub host: String,
+    /// Contract version reported by the sidecar.
+    pub sidecar: String,
+    /// How the two versions relate.
+    pub level: CompatLevel,
+    /// What the difference means in practice.
+    pub detail: String,
+    /// What to do about it, when action is needed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+}
+
+impl CompatReport {
+    /// Whether the two sides can interoperate.
+    #[must_use]
+    pub fn is_compatible(&self) -> bool {
+        self.level.is_compatible()
+    }
+
+    /// Convert an incompatible report into a classified error carrying the
+    /// versions and remediation as context. Returns `None` when compatible.
+    #[must_use]
+    pub fn to_error(&self) -> Option<AbpError> {
+        if self.is_compatible() {
+            return None;
+        }
+        let mut err = AbpError::new(ErrorCode::ProtocolVersionMismatch, self.to_string())
+            .with_context("host_version", &self.host)
+            .with_context("sidecar_version", &self.sidecar);
+        if let Some(fix) = &self.remediation {
+            err = err.with_context("remediation", fix);
+        }
+        Some(err)
+    }
+}
+
+impl fmt::Display for CompatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_compatible() {
+            f.write_str("incompatible contract version: ")?;
+        }
+        f.write_str(&self.detail)?;
+        if let Some(fix) = &self.remediation {
+            write!(f, "; {fix}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a contract version of the form `abp/v{major}.{minor}` into
+/// `(major, minor)`, or `None` when it is malformed.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::compat::parse_version;
+///
+/// assert_eq!(parse_version("abp/v0.1"), Some((0, 1)));
+/// assert_eq!(parse_version("abp/0.1"), None);
+/// ```
+#[must_use]
+pub fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let rest = version.strip_prefix("abp/v")?;
+    let (major, minor) = rest.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Compare a host contract version with the version a sidecar reported.
+#[must_use]
+pub fn check(host: &str, sidecar: &str) -> CompatReport {
+    let (level, detail, remediation) = match (parse_version(host), parse_version(sidecar)) {
+        (None, _) => (
+            CompatLevel::Malformed,
+            format!("host contract version \"{host}\" is not of the form abp/v<major>.<minor>"),
+            Some("rebuild the host from a release with a valid CONTRACT_VERSION".to_string()),
+        ),
+        (_, None) => (
+            CompatLevel::Malformed,
+            format!(
+                "sidecar reported contract version \"{sidecar}\", \
+                 which is not of the form abp/v<major>.<minor>"
+            ),
+            Some(format!(
+                "fix the sidecar's hello envelope to send \"contract_version\": \"{host}\""
+            )),
+        ),
+        (Some((hmaj, _)), Some((smaj, _))) if hmaj != smaj => {
+            let fix = if smaj > hmaj {
+                format!(
+                    "upgrade the host to an abp/v{smaj}.x release, or pin the sidecar to one \
+                     speaking abp/v{hmaj}.x"
+                )
+            } else {
+                format!("upgrade the sidecar to a release speaking abp/v{hmaj}.x")
+            };
+            (
+                CompatLevel::MajorMismatch,
+                format!(
+                    "sidecar speaks {sidecar} but host speaks {host}; major versions differ, \
+                     so the wire formats contain breaking changes"
+                ),
+                Some(fix),
+            )
+        }
+        (Some((_, hmin)), Some((_, smin))) if smin > hmin => (
+            CompatLevel::SidecarNewerMinor,
+            format!(
+                "sidecar {sidecar} is newer than host {host}; additive changes only, \
+                 unknown fields and variants from the sidecar are ignored"
+            ),
+            Some(format!(
+                "upgrade the host to {sidecar} to use everything the sidecar offers"
+            )),
+        ),
+        (Some((_, hmin)), Some((_, smin))) if smin < hmin => (
+            CompatLevel::SidecarOlderMinor,
+            format!(
+                "sidecar {sidecar} is older than host {host}; additive changes only, \
+                 newer optional fields take their defaults"
+            ),
+            None,
+        ),
+        _ => (
+            CompatLevel::Identical,
+            format!("host and sidecar both speak {host}"),
+            None,
+        ),
+    };
+
+    CompatReport {
+        host: host.to_string(),
+        sidecar: sidecar.to_string(),
+        level,
+        detail,
+        remediation,
+    }
+}
+
+/// Compare a sidecar's contract version against this build's
+/// [`CONTRACT_VERSION`](crate::CONTRACT_VERSION).
+#[must_use]
+pub fn check_sidecar(sidecar: &str) -> CompatReport {
+    check(crate::CONTRACT_VERSION, sidecar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_versions() {
+        let r = check("abp/v0.1", "abp/v0.1");
+        assert_eq!(r.level, CompatLevel::Identical);
+        assert!(r.is_compatible());
+        assert!(r.remediation.is_none());
+        assert!(r.to_error().is_none());
+    }
+
+    #[test]
+    fn newer_minor_sidecar_is_compatible_with_hint() {
+        let r = check("abp/v0.1", "abp/v0.4");
+        assert_eq!(r.level, CompatLevel::SidecarNewerMinor);
+        assert!(r.is_compatible());
+        assert!(
+            r.remediation
+                .unwrap()
+                .contains("upgrade the host to abp/v0.4")
+        );
+    }
+
+    #[test]
+    fn older_minor_sidecar_is_compatible() {
+        let r = check("abp/v0.3", "abp/v0.1");
+        assert_eq!(r.level, CompatLevel::SidecarOlderMinor);
+        assert!(r.is_compatible());
+        assert!(r.remediation.is_none());
+    }
+
+    #[test]
+    fn older_major_sidecar_must_upgrade() {
+        let r = check("abp/v1.0", "abp/v0.9");
+        assert_eq!(r.level, CompatLevel::MajorMismatch);
+        let msg = r.to_string();
+        assert!(msg.starts_with("incompatible contract version"));
+        assert!(msg.contains("upgrade the sidecar to a release speaking abp/v1.x"));
+    }
+
+    #[test]
+    fn newer_major_sidecar_suggests_host_upgrade() {
+        let r = check("abp/v0.1", "abp/v2.0");
+        assert_eq!(r.level, CompatLevel::MajorMismatch);
+        assert!(
+            r.to_string()
+                .contains("upgrade the host to an abp/v2.x release")
+        );
+    }
+
+    #[test]
+    fn malformed_sidecar_version() {
+        let r = check("abp/v0.1", "v0.1");
+        assert_eq!(r.level, CompatLevel::Malformed);
+        assert!(!r.is_compatible());
+        assert!(r.to_string().contains("\"contract_version\": \"abp/v0.1\""));
+    }
+
+    #[test]
+    fn malformed_host_version() {
+        let r = check("garbage", "abp/v0.1");
+        assert_eq!(r.level, CompatLevel::Malformed);
+        assert!(r.detail.contains("host contract version"));
+    }
+
+    #[test]
+    fn incompatible_report_converts_to_error() {
+        let err = check("abp/v0.1", "abp/v3.0").to_error().unwrap();
+        assert_eq!(err.code, ErrorCode::ProtocolVersionMismatch);
+        assert_eq!(err.context["sidecar_version"], "abp/v3.0");
+        assert!(err.context.contains_key("remediation"));
+    }
+
+    #[test]
+    fn check_sidecar_uses_contract_version() {
+        assert_eq!(
+            check_sidecar(crate::CONTRACT_VERSION).level,
+            CompatLevel::Identical
+        );
+    }
+}
@@ -212,6 +212,9 @@ fn event_kind_name(kind: &AgentEventKind) -> String {
         AgentEventKind::ToolResult { .. } => "tool_result".into(),
         AgentEventKind::FileChanged { .. } => "file_changed".into(),
         AgentEventKind::CommandExecuted { .. } => "command_executed".into(),
+        AgentEventKind::Progress { .. } => "progress".into(),
+        AgentEventKind::RunSummary { .. } => "run_summary".into(),
+        AgentEventKind::ArtifactChunk { .. } => "artifact_chunk".into(),
         AgentEventKind::Warning { .. } => "warning".into(),
         AgentEventKind::Error { .. } => "error".into(),
     }
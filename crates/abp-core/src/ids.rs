@@ -0,0 +1,277 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Injectable identifier generation.
+//!
+//! Components that mint identifiers (run ids, work order ids) draw them from an
+//! [`IdGenerator`](crate::ids::IdGenerator) instead of calling
+//! `Uuid::new_v4()` directly, so tests and replays can make them predictable.
+//!
+//! - [`UuidV4Generator`](crate::ids::UuidV4Generator) — random ids; the
+//!   default.
+//! - [`UlidGenerator`](crate::ids::UlidGenerator) — ULID layout (48-bit
+//!   millisecond timestamp, 80 random bits), so ids sort by creation time.
+//!   They are carried in a [`Uuid`] and keep that order in hyphenated form;
+//!   [`to_ulid_string`](crate::ids::to_ulid_string) renders the canonical
+//!   26-character ULID text.
+//! - [`SeededIdGenerator`](crate::ids::SeededIdGenerator) and
+//!   [`SequentialIdGenerator`](crate::ids::SequentialIdGenerator) —
+//!   deterministic sequences for golden tests and replay.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crate::clock::{SharedClock, system_clock};
+
+/// A source of unique identifiers.
+pub trait IdGenerator: Send + Sync + fmt::Debug {
+    /// Produce the next identifier.
+    fn next_id(&self) -> Uuid;
+}
+
+/// An identifier generator shared between components.
+pub type SharedIdGenerator = Arc<dyn IdGenerator>;
+
+/// The default generator (random v4 UUIDs), shared.
+#[must_use]
+pub fn default_id_generator() -> SharedIdGenerator {
+    Arc::new(UuidV4Generator)
+}
+
+/// Random version-4 UUIDs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UuidV4Generator;
+
+impl IdGenerator for UuidV4Generator {
+    fn next_id(&self) -> Uuid {
+        Uuid::new_v4()
+    }
+}
+
+const ULID_RANDOM_BITS: u32 = 80;
+const ULID_RANDOM_MASK: u128 = (1 << ULID_RANDOM_BITS) - 1;
+
+/// Time-sortable ids in the ULID layout.
+///
+/// Ids minted within the same millisecond increment the random part, so the
+/// sequence is strictly increasing even under a frozen clock.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::clock::ManualClock;
+/// use abp_core::ids::{IdGenerator, UlidGenerator, ulid_timestamp_ms};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// let clock = Arc::new(ManualClock::default());
+/// let ids = UlidGenerator::with_clock(clock.clone());
+/// let a = ids.next_id();
+/// clock.advance(Duration::from_millis(5));
+/// let b = ids.next_id();
+/// assert!(a < b);
+/// assert!(a.to_string() < b.to_string());
+/// assert_eq!(ulid_timestamp_ms(b), 5);
+/// ```
+#[derive(Debug)]
+pub struct UlidGenerator {
+    clock: SharedClock,
+    last: Mutex<u128>,
+}
+
+impl UlidGenerator {
+    /// Create a generator timestamped by the system clock.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_clock(system_clock())
+    }
+
+    /// Create a generator timestamped by `clock`.
+    #[must_use]
+    pub fn with_clock(clock: SharedClock) -> Self {
+        Self {
+            clock,
+            last: Mutex::new(0),
+        }
+    }
+}
+
+impl Default for UlidGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdGenerator for UlidGenerator {
+    fn next_id(&self) -> Uuid {
+        let ms = u128::from(self.clock.now().timestamp_millis().max(0) as u64);
+        let mut last = self.last.lock().expect("ulid mutex poisoned");
+        let fresh = (ms << ULID_RANDOM_BITS) | (Uuid::new_v4().as_u128() & ULID_RANDOM_MASK);
+        // A clock that has not moved past the previous id continues its
+        // sequence instead of risking an out-of-order id.
+        let next = if (*last >> ULID_RANDOM_BITS) >= ms {
+            last.wrapping_add(1)
+        } else {
+            fresh
+        };
+        *last = next;
+        Uuid::from_u128(next)
+    }
+}
+
+/// Millisecond timestamp encoded in a ULID-layout id.
+#[must_use]
+pub fn ulid_timestamp_ms(id: Uuid) -> u64 {
+    (id.as_u128() >> ULID_RANDOM_BITS) as u64
+}
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Render `id` as a 26-character Crockford base32 ULID string.
+#[must_use]
+pub fn to_ulid_string(id: Uuid) -> String {
+    let value = id.as_u128();
+    (0..26)
+        .map(|i| {
+            let shift = 125 - 5 * i;
+            CROCKFORD[((value >> shift) & 0x1f) as usize] as char
+        })
+        .collect()
+}
+
+/// Parse a 26-character ULID string (case-insensitive; `I`, `L` and `O` are
+/// read as `1`, `1` and `0`).
+///
+/// Returns `None` for strings of the wrong length, with invalid characters,
+/// or encoding more than 128 bits.
+#[must_use]
+pub fn parse_ulid(s: &str) -> Option<Uuid> {
+    if s.len() != 26 {
+        return None;
+    }
+    let mut value: u128 = 0;
+    for (i, c) in s.chars().enumerate() {
+        let c = match c.to_ascii_uppercase() {
+            'I' | 'L' => '1',
+            'O' => '0',
+            c => c,
+        };
+        let digit = CROCKFORD.iter().position(|&b| b as char == c)? as u128;
+        if i == 0 && digit > 7 {
+            return None;
+        }
+        value = (value << 5) | digit;
+    }
+    Some(Uuid::from_u128(value))
+}
+
+/// Deterministic v4-shaped ids derived from a seed.
+///
+/// Two generators with the same seed produce the same sequence, so seeded
+/// runs get the same run ids on every replay.
+#[derive(Debug)]
+pub struct SeededIdGenerator {
+    state: Mutex<u64>,
+}
+
+impl SeededIdGenerator {
+    /// Create a generator whose sequence is determined by `seed`.
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Mutex::new(seed),
+        }
+    }
+}
+
+/// SplitMix64 step: advances `state` and returns the next output.
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+impl IdGenerator for SeededIdGenerator {
+    fn next_id(&self) -> Uuid {
+        let mut state = self.state.lock().expect("id seed mutex poisoned");
+        let hi = splitmix64(&mut state);
+        let lo = splitmix64(&mut state);
+        let bytes = ((u128::from(hi) << 64) | u128::from(lo)).to_be_bytes();
+        uuid::Builder::from_random_bytes(bytes).into_uuid()
+    }
+}
+
+/// Ids `00000000-0000-0000-0000-000000000001`, `…0002`, and so on.
+///
+/// Useful in tests where an id should be readable at a glance.
+#[derive(Debug, Default)]
+pub struct SequentialIdGenerator {
+    next: AtomicU64,
+}
+
+impl SequentialIdGenerator {
+    /// Create a generator whose first id is `1`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IdGenerator for SequentialIdGenerator {
+    fn next_id(&self) -> Uuid {
+        Uuid::from_u128(u128::from(self.next.fetch_add(1, Ordering::Relaxed) + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+
+    #[test]
+    fn ulid_is_monotonic_under_frozen_clock() {
+        let ids = UlidGenerator::with_clock(Arc::new(ManualClock::default()));
+        let a = ids.next_id();
+        let b = ids.next_id();
+        assert!(a < b);
+        assert_eq!(ulid_timestamp_ms(a), 0);
+        assert_eq!(ulid_timestamp_ms(b), 0);
+    }
+
+    #[test]
+    fn ulid_string_round_trips() {
+        let id = UlidGenerator::new().next_id();
+        let text = to_ulid_string(id);
+        assert_eq!(text.len(), 26);
+        assert_eq!(parse_ulid(&text), Some(id));
+        assert_eq!(parse_ulid(&text.to_lowercase()), Some(id));
+        assert_eq!(to_ulid_string(Uuid::max()), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+        assert_eq!(parse_ulid("8ZZZZZZZZZZZZZZZZZZZZZZZZZ"), None);
+        assert_eq!(parse_ulid("short"), None);
+        assert_eq!(parse_ulid("0000000000000000000000000U"), None);
+    }
+
+    #[test]
+    fn seeded_ids_repeat_per_seed() {
+        let a = SeededIdGenerator::new(42);
+        let b = SeededIdGenerator::new(42);
+        let first = a.next_id();
+        assert_eq!(first, b.next_id());
+        assert_eq!(a.next_id(), b.next_id());
+        assert_ne!(first, SeededIdGenerator::new(43).next_id());
+        assert_eq!(first.get_version_num(), 4);
+    }
+
+    #[test]
+    fn sequential_ids_count_from_one() {
+        let ids = SequentialIdGenerator::new();
+        assert_eq!(
+            ids.next_id().to_string(),
+            "00000000-0000-0000-0000-000000000001"
+        );
+        assert_eq!(ids.next_id().as_u128(), 2);
+    }
+}
@@ -136,6 +136,105 @@ impl IrMessage {
     }
 }
 
+// ── Prompt caching ──────────────────────────────────────────────────────
+
+/// Metadata key under which an [`IrMessage`] carries its
+/// [`IrCacheControl`] breakpoint.
+pub const CACHE_CONTROL_KEY: &str = "cache_control";
+
+/// Metadata key under which an [`IrMessage`] carries [`IrCacheControl`]
+/// breakpoints on individual content blocks, keyed by block index.
+pub const CACHE_BREAKPOINTS_KEY: &str = "cache_breakpoints";
+
+/// A prompt-caching breakpoint.
+///
+/// Everything up to and including the annotated content is a cacheable
+/// prefix. Serializes like Anthropic's `cache_control` object.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IrCacheControl {
+    /// A short-lived cache entry.
+    Ephemeral {
+        /// Requested lifetime such as `"5m"` or `"1h"`; the provider's
+        /// default when unset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ttl: Option<String>,
+    },
+}
+
+impl IrCacheControl {
+    /// An ephemeral breakpoint with the provider's default lifetime.
+    #[must_use]
+    pub fn ephemeral() -> Self {
+        Self::Ephemeral { ttl: None }
+    }
+}
+
+impl IrMessage {
+    /// Mark this message's last content block as a cache breakpoint.
+    ///
+    /// Content blocks carry no metadata of their own, so the annotation is
+    /// stored under [`CACHE_CONTROL_KEY`] in the message metadata; dialects
+    /// that support explicit breakpoints attach it to the last block.
+    #[must_use]
+    pub fn with_cache_control(mut self, cache_control: IrCacheControl) -> Self {
+        if let Ok(value) = serde_json::to_value(cache_control) {
+            self.metadata.insert(CACHE_CONTROL_KEY.into(), value);
+        }
+        self
+    }
+
+    /// The cache breakpoint on this message's last content block, if any.
+    #[must_use]
+    pub fn cache_control(&self) -> Option<IrCacheControl> {
+        self.metadata
+            .get(CACHE_CONTROL_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// Mark content block `index` as a cache breakpoint.
+    ///
+    /// For breakpoints inside a message; the annotation is stored under
+    /// [`CACHE_BREAKPOINTS_KEY`] in the message metadata.
+    #[must_use]
+    pub fn with_block_cache_control(mut self, index: usize, cache_control: IrCacheControl) -> Self {
+        if let Ok(value) = serde_json::to_value(cache_control) {
+            let entry = self
+                .metadata
+                .entry(CACHE_BREAKPOINTS_KEY.into())
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+            if let Some(map) = entry.as_object_mut() {
+                map.insert(index.to_string(), value);
+            }
+        }
+        self
+    }
+
+    /// Every cache breakpoint on this message, by content block index.
+    ///
+    /// A breakpoint set with [`with_cache_control`](Self::with_cache_control)
+    /// is reported on the last block.
+    #[must_use]
+    pub fn cache_breakpoints(&self) -> BTreeMap<usize, IrCacheControl> {
+        let mut breakpoints: BTreeMap<usize, IrCacheControl> = self
+            .metadata
+            .get(CACHE_BREAKPOINTS_KEY)
+            .and_then(serde_json::Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(index, v)| {
+                Some((index.parse().ok()?, serde_json::from_value(v.clone()).ok()?))
+            })
+            .collect();
+        if let (Some(cache_control), Some(last)) =
+            (self.cache_control(), self.content.len().checked_sub(1))
+        {
+            breakpoints.insert(last, cache_control);
+        }
+        breakpoints
+    }
+}
+
 // ── Tool definitions ────────────────────────────────────────────────────
 
 /// A canonical tool definition for cross-dialect normalization.
@@ -151,6 +250,26 @@ pub struct IrToolDefinition {
     pub parameters: serde_json::Value,
 }
 
+/// How the model may use the tools offered to it.
+///
+/// Normalizes OpenAI `tool_choice`, Claude `tool_choice`, and Gemini
+/// `functionCallingConfig`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IrToolChoice {
+    /// The model decides whether to call a tool.
+    Auto,
+    /// The model must not call any tool.
+    None,
+    /// The model must call at least one tool.
+    Required,
+    /// The model must call the named tool.
+    Tool {
+        /// Name of the required tool.
+        name: String,
+    },
+}
+
 // ── Conversation ────────────────────────────────────────────────────────
 
 /// An ordered sequence of [`IrMessage`]s with helper accessors.
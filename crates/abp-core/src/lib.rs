@@ -32,14 +32,24 @@
 //!
 //! `receipt_hash()` sets `receipt_sha256` to `null` before hashing so the
 //! stored hash is never self-referential.  Prefer [`Receipt::with_hash`]
-//! over calling `receipt_hash()` directly.
+//! over calling `receipt_hash()` directly.  Hash inputs are rendered with
+//! the [`canonical`] serializer (RFC 8785 style), so hashes are stable across
+//! serde versions and implementation languages.
 #![deny(unsafe_code)]
 #![warn(missing_docs)]
 
 /// Event aggregation and analytics.
 pub mod aggregate;
+/// Canonical JSON serialization for hashing and signatures.
+pub mod canonical;
 /// Receipt chain verification and integrity checking.
 pub mod chain;
+/// Injectable time source for timestamps and durations.
+pub mod clock;
+/// Trace compaction: semantic summarization of receipts for storage.
+pub mod compact;
+/// Contract-version compatibility checks between host and sidecar.
+pub mod compat;
 /// Configuration validation and defaults.
 pub mod config;
 /// Comprehensive error catalog for the Agent Backplane.
@@ -48,12 +58,20 @@ pub mod error;
 pub mod ext;
 /// Event filtering for agent event streams.
 pub mod filter;
+/// Injectable identifier generation, including time-sortable ULIDs.
+pub mod ids;
 /// Intermediate Representation for cross-dialect message normalization.
 pub mod ir;
+/// Merkle digests of workspace contents for input provenance.
+pub mod merkle;
 /// Advanced capability negotiation.
 pub mod negotiate;
 /// Event stream combinator utilities.
 pub mod stream;
+/// Work order provenance: who and what submitted a run.
+pub mod submitter;
+/// Typed envelopes (JSON, text, binary, table) for tool results.
+pub mod tool_result;
 /// Receipt validation utilities.
 pub mod validate;
 /// Comprehensive receipt and chain verification.
@@ -189,6 +207,102 @@ pub struct RuntimeConfig {
 
     /// Hard cap on turns/iterations (best-effort).
     pub max_turns: Option<u32>,
+
+    /// Language every answer must be in, as a BCP 47 tag such as `fr` or
+    /// `pt-BR` (accepted as `locale` too).
+    #[serde(default, alias = "locale", skip_serializing_if = "Option::is_none")]
+    pub response_language: Option<String>,
+
+    /// How strictly the run must preserve the request's semantics when a
+    /// capability is emulated or a dialect mapping is lossy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fidelity_policy: Option<FidelityPolicy>,
+
+    /// Who and what submitted the work order; the runtime copies it into
+    /// the receipt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submitter: Option<submitter::Submitter>,
+}
+
+/// How strictly a run must preserve the semantics of the submitted request.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::FidelityPolicy;
+///
+/// assert_eq!(FidelityPolicy::default(), FidelityPolicy::Warn);
+/// assert_eq!(FidelityPolicy::parse("STRICT"), Some(FidelityPolicy::Strict));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FidelityPolicy {
+    /// Reject the run if any mapping step is lossy or any capability is emulated.
+    Strict,
+    /// Allow degradation but log it and record it in the receipt.
+    #[default]
+    Warn,
+    /// Allow degradation silently (still recorded in the receipt).
+    Permissive,
+}
+
+impl FidelityPolicy {
+    /// Parse a policy name (case-insensitive).
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "strict" => Some(Self::Strict),
+            "warn" => Some(Self::Warn),
+            "permissive" => Some(Self::Permissive),
+            _ => None,
+        }
+    }
+
+    /// Read the policy a work order asks for.
+    ///
+    /// Reads `config.fidelity_policy`. When that is unset, falls back to
+    /// `config.vendor["abp"]["fidelity_policy"]` and then the flat
+    /// `config.vendor["abp.fidelity_policy"]` key, as set by
+    /// `--param abp.fidelity_policy=...`. A missing value means the default
+    /// ([`FidelityPolicy::Warn`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigInvalid`](abp_error::ErrorCode::ConfigInvalid)
+    /// error when the vendor value is not a recognised policy name, so a
+    /// typo cannot quietly downgrade a strict run.
+    pub fn from_work_order(work_order: &WorkOrder) -> Result<Self, abp_error::AbpError> {
+        if let Some(policy) = work_order.config.fidelity_policy {
+            return Ok(policy);
+        }
+        let vendor = &work_order.config.vendor;
+        let Some(value) = vendor
+            .get("abp")
+            .and_then(|abp| abp.get("fidelity_policy"))
+            .or_else(|| vendor.get("abp.fidelity_policy"))
+        else {
+            return Ok(Self::default());
+        };
+        value.as_str().and_then(Self::parse).ok_or_else(|| {
+            abp_error::AbpError::new(
+                abp_error::ErrorCode::ConfigInvalid,
+                format!(
+                    "unrecognised fidelity policy {value}; expected \"strict\", \"warn\" or \"permissive\""
+                ),
+            )
+            .with_context("fidelity_policy", value)
+        })
+    }
+}
+
+impl std::fmt::Display for FidelityPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Strict => f.write_str("strict"),
+            Self::Warn => f.write_str("warn"),
+            Self::Permissive => f.write_str("permissive"),
+        }
+    }
 }
 
 /// Security policy: tool allow/deny lists, path restrictions, network rules.
@@ -234,6 +348,47 @@ pub struct CapabilityRequirement {
     pub min_support: MinSupport,
 }
 
+/// Ordered fallbacks for one capability need, best rung first.
+///
+/// A structured-output need might degrade from a native JSON schema, to
+/// native JSON mode, to prompt-emulated schema output. The runtime picks the
+/// first rung the backend satisfies and requires that rung for the run.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::{Capability, CapabilityLadder, MinSupport};
+///
+/// let ladder = CapabilityLadder::new()
+///     .rung(Capability::StructuredOutputJsonSchema, MinSupport::Native)
+///     .rung(Capability::JsonMode, MinSupport::Native)
+///     .rung(Capability::StructuredOutputJsonSchema, MinSupport::Emulated);
+/// assert_eq!(ladder.rungs.len(), 3);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct CapabilityLadder {
+    /// Acceptable requirements, most preferred first.
+    pub rungs: Vec<CapabilityRequirement>,
+}
+
+impl CapabilityLadder {
+    /// An empty ladder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a rung below the existing ones.
+    #[must_use]
+    pub fn rung(mut self, capability: Capability, min_support: MinSupport) -> Self {
+        self.rungs.push(CapabilityRequirement {
+            capability,
+            min_support,
+        });
+        self
+    }
+}
+
 /// Minimum acceptable [`SupportLevel`] threshold.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -356,6 +511,9 @@ pub enum Capability {
     Embeddings,
     /// Image generation (e.g. DALL-E).
     ImageGeneration,
+    /// Answer in a requested language (`config.response_language`) without a
+    /// prompt instruction.
+    ResponseLanguage,
 }
 
 /// How well a backend supports a given [`Capability`].
@@ -552,6 +710,47 @@ pub struct UsageNormalized {
     pub estimated_cost_usd: Option<f64>,
 }
 
+impl UsageNormalized {
+    /// Fill missing cache counters from a provider's raw usage object.
+    ///
+    /// Recognizes the normalized `cache_read_tokens` / `cache_write_tokens`
+    /// keys and the vendor spellings: Anthropic `cache_read_input_tokens`
+    /// and `cache_creation_input_tokens`, OpenAI
+    /// `prompt_tokens_details.cached_tokens` (or `input_tokens_details` on
+    /// the Responses API), Gemini `cachedContentTokenCount`, and Moonshot
+    /// `cached_tokens`. The object may be nested under a `usage` key.
+    /// Counters that are already set are left alone.
+    pub fn fill_cache_from_raw(&mut self, raw: &serde_json::Value) {
+        let raw = raw.get("usage").filter(|u| u.is_object()).unwrap_or(raw);
+        let number = |path: &[&str]| {
+            path.iter()
+                .try_fold(raw, |v, key| v.get(key))
+                .and_then(serde_json::Value::as_u64)
+        };
+        if self.cache_read_tokens.is_none() {
+            self.cache_read_tokens = [
+                &["cache_read_tokens"][..],
+                &["cache_read_input_tokens"],
+                &["prompt_tokens_details", "cached_tokens"],
+                &["input_tokens_details", "cached_tokens"],
+                &["cachedContentTokenCount"],
+                &["cached_content_token_count"],
+                &["cached_tokens"],
+            ]
+            .into_iter()
+            .find_map(number);
+        }
+        if self.cache_write_tokens.is_none() {
+            self.cache_write_tokens = [
+                &["cache_write_tokens"][..],
+                &["cache_creation_input_tokens"],
+            ]
+            .into_iter()
+            .find_map(number);
+        }
+    }
+}
+
 /// High-level result status of a run.
 ///
 /// # Examples
@@ -591,6 +790,10 @@ pub struct VerificationReport {
     pub git_status: Option<String>,
     /// Whether the harness (if any) reported success.
     pub harness_ok: bool,
+    /// Merkle digest of the staged input workspace, recorded before the
+    /// backend ran. Absent for pass-through workspaces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_digest: Option<merkle::WorkspaceDigest>,
 }
 
 /// A timestamped event emitted by an agent during a run.
@@ -699,6 +902,42 @@ pub enum AgentEventKind {
         output_preview: Option<String>,
     },
 
+    /// Progress of a long-running step.
+    ///
+    /// The runtime also synthesizes these as idle heartbeats while a backend
+    /// is silent; those carry `ext["abp.idle_ms"]` and no `percent`.
+    Progress {
+        /// Estimated completion in `0.0..=100.0`, if known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        percent: Option<f64>,
+        /// Human-readable status.
+        message: String,
+    },
+
+    /// One chunk of an artifact streamed by the backend.
+    ///
+    /// Lets a backend hand over artifacts too large for a single event
+    /// (generated images, datasets). The runtime assembles the chunks of each
+    /// artifact in `seq` order, hashes the result, and registers it in the
+    /// receipt; chunks themselves reach neither the caller nor the trace.
+    ArtifactChunk {
+        /// Artifact name, a relative path such as `"images/cat.png"`.
+        name: String,
+        /// Zero-based position of the chunk; `0` (re)starts the artifact.
+        seq: u64,
+        /// Base64-encoded chunk bytes.
+        data: String,
+        /// Whether this chunk completes the artifact.
+        #[serde(default)]
+        last: bool,
+        /// Media type of the artifact, if known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        media_type: Option<String>,
+        /// Hex SHA-256 of the whole artifact, checked on the last chunk.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sha256: Option<String>,
+    },
+
     /// A non-fatal warning emitted during the run.
     Warning {
         /// Warning message text.
@@ -713,6 +952,29 @@ pub enum AgentEventKind {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         error_code: Option<abp_error::ErrorCode>,
     },
+
+    /// Roll-up of a finished run, synthesized by the runtime as the last
+    /// event of the stream.
+    ///
+    /// It is derived from the final receipt, so it is not part of the
+    /// receipt's own trace.
+    RunSummary {
+        /// Outcome the receipt records.
+        outcome: Outcome,
+        /// Wall-clock duration of the run in milliseconds.
+        duration_ms: u64,
+        /// Token usage and cost, as in the receipt.
+        usage: UsageNormalized,
+        /// Number of tool calls, keyed by tool name.
+        #[serde(default)]
+        tool_calls: BTreeMap<String, u64>,
+        /// Distinct paths reported by `file_changed` events, sorted.
+        #[serde(default)]
+        files_changed: Vec<String>,
+        /// Number of operations a policy rule denied.
+        #[serde(default)]
+        policy_denials: u64,
+    },
 }
 
 /// Errors from contract-level operations (serialization, hashing).
@@ -725,9 +987,8 @@ pub enum ContractError {
 
 /// Produce a deterministic JSON string for hashing.
 ///
-/// This is not a full JCS implementation, but it is stable for our types:
-/// - keys are sorted (serde_json Map is a BTreeMap by default)
-/// - numbers are serialized consistently by serde_json
+/// Shorthand for [`canonical::to_canonical_string`]: keys are sorted, no
+/// whitespace is emitted, and floats use the RFC 8785 number format.
 ///
 /// # Examples
 ///
@@ -742,8 +1003,7 @@ pub enum ContractError {
 ///
 /// Returns [`ContractError::Json`] if the value cannot be serialized.
 pub fn canonical_json<T: Serialize>(value: &T) -> Result<String, ContractError> {
-    let v = serde_json::to_value(value)?;
-    Ok(serde_json::to_string(&v)?)
+    canonical::to_canonical_string(value)
 }
 
 /// Compute the hex-encoded SHA-256 digest of `bytes`.
@@ -819,7 +1079,7 @@ pub fn receipt_hash(receipt: &Receipt) -> Result<String, ContractError> {
     if let serde_json::Value::Object(map) = &mut v {
         map.insert("receipt_sha256".to_string(), serde_json::Value::Null);
     }
-    let json = serde_json::to_string(&v)?;
+    let json = canonical::value_to_canonical_string(&v);
     Ok(sha256_hex(json.as_bytes()))
 }
 
@@ -843,6 +1103,7 @@ pub fn receipt_hash(receipt: &Receipt) -> Result<String, ContractError> {
 /// ```
 #[derive(Debug)]
 pub struct WorkOrderBuilder {
+    id: Option<Uuid>,
     task: String,
     lane: ExecutionLane,
     root: String,
@@ -860,6 +1121,7 @@ impl WorkOrderBuilder {
     #[must_use]
     pub fn new(task: impl Into<String>) -> Self {
         Self {
+            id: None,
             task: task.into(),
             lane: ExecutionLane::PatchFirst,
             root: ".".into(),
@@ -873,6 +1135,15 @@ impl WorkOrderBuilder {
         }
     }
 
+    /// Use `id` instead of a random work order id.
+    ///
+    /// Pair with an [`ids::IdGenerator`] to give golden tests stable ids.
+    #[must_use]
+    pub fn id(mut self, id: Uuid) -> Self {
+        self.id = Some(id);
+        self
+    }
+
     /// Set the execution lane.
     #[must_use]
     pub fn lane(mut self, lane: ExecutionLane) -> Self {
@@ -945,12 +1216,128 @@ impl WorkOrderBuilder {
         self.config.max_turns = Some(turns);
         self
     }
+    /// Set the deterministic sampling seed (`config.vendor["abp"]["seed"]`).
+    ///
+    /// Backends that support seeding forward it to the model, and the runtime
+    /// records it in the receipt so the run can be replayed.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.abp_vendor()["seed"] = serde_json::json!(seed);
+        self
+    }
+
+    /// Set the tools offered to the model (`config.vendor["abp"]["tools"]`).
+    ///
+    /// Backends that support function calling surface these schemas to the
+    /// model in their own dialect.
+    #[must_use]
+    pub fn tools(mut self, tools: Vec<ir::IrToolDefinition>) -> Self {
+        self.abp_vendor()["tools"] = serde_json::to_value(tools).unwrap_or_default();
+        self
+    }
+
+    /// Set how the model may use the offered tools
+    /// (`config.vendor["abp"]["tool_choice"]`).
+    #[must_use]
+    pub fn tool_choice(mut self, choice: ir::IrToolChoice) -> Self {
+        self.abp_vendor()["tool_choice"] = serde_json::to_value(choice).unwrap_or_default();
+        self
+    }
+
+    /// Attach the full conversation history (`config.vendor["abp"]["conversation"]`).
+    ///
+    /// The task stays the one-line summary used for logs and receipts;
+    /// backends that speak a chat dialect send this history to the model
+    /// instead of the task alone, so earlier turns are not lost.
+    #[must_use]
+    pub fn conversation(mut self, conversation: ir::IrConversation) -> Self {
+        self.abp_vendor()["conversation"] = serde_json::to_value(conversation).unwrap_or_default();
+        self
+    }
+
+    /// Require answers in a language, given as a BCP 47 tag such as `fr` or
+    /// `pt-BR` (`config.response_language`).
+    ///
+    /// Backends without native support for
+    /// [`Capability::ResponseLanguage`] get the requirement as a system
+    /// prompt instruction from the runtime, which records the emulation in
+    /// the receipt.
+    #[must_use]
+    pub fn response_language(mut self, tag: impl Into<String>) -> Self {
+        self.config.response_language = Some(tag.into());
+        self
+    }
+
+    /// Set how strictly the run must preserve the request's semantics
+    /// (`config.fidelity_policy`).
+    ///
+    /// Under [`FidelityPolicy::Strict`] the runtime rejects the run before
+    /// the backend starts if any capability would be emulated or any
+    /// dialect mapping is lossy.
+    #[must_use]
+    pub fn fidelity_policy(mut self, policy: FidelityPolicy) -> Self {
+        self.config.fidelity_policy = Some(policy);
+        self
+    }
+
+    /// Record who and what submitted the work order (`config.submitter`).
+    ///
+    /// The runtime copies it into the receipt.
+    #[must_use]
+    pub fn submitter(mut self, submitter: submitter::Submitter) -> Self {
+        self.config.submitter = Some(submitter);
+        self
+    }
+
+    /// Add a capability fallback ladder
+    /// (`config.vendor["abp"]["capability_ladders"]`).
+    ///
+    /// Unlike a fixed [`requirement`](Self::requirements), a ladder lets the
+    /// runtime settle for the best rung the chosen backend supports.
+    #[must_use]
+    pub fn capability_ladder(mut self, ladder: CapabilityLadder) -> Self {
+        let ladders = &mut self.abp_vendor()["capability_ladders"];
+        if !ladders.is_array() {
+            *ladders = serde_json::json!([]);
+        }
+        if let (Some(list), Ok(value)) = (ladders.as_array_mut(), serde_json::to_value(ladder)) {
+            list.push(value);
+        }
+        self
+    }
+
+    /// Override a runtime feature flag for this work order
+    /// (`config.vendor["abp"]["features"]`).
+    ///
+    /// Only flags the runtime declares can be overridden; others are ignored.
+    #[must_use]
+    pub fn feature(mut self, name: impl Into<String>, enabled: bool) -> Self {
+        let features = &mut self.abp_vendor()["features"];
+        if !features.is_object() {
+            *features = serde_json::json!({});
+        }
+        features[name.into()] = serde_json::json!(enabled);
+        self
+    }
+
+    /// The `config.vendor["abp"]` object, created if missing.
+    fn abp_vendor(&mut self) -> &mut serde_json::Value {
+        let abp = self
+            .config
+            .vendor
+            .entry("abp".to_string())
+            .or_insert_with(|| serde_json::json!({}));
+        if !abp.is_object() {
+            *abp = serde_json::json!({});
+        }
+        abp
+    }
 
     /// Consume the builder and produce a [`WorkOrder`].
     #[must_use]
     pub fn build(self) -> WorkOrder {
         WorkOrder {
-            id: Uuid::new_v4(),
+            id: self.id.unwrap_or_else(Uuid::new_v4),
             task: self.task,
             lane: self.lane,
             workspace: WorkspaceSpec {
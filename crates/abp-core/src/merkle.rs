@@ -0,0 +1,182 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Merkle digests of workspace contents for input provenance.
+//!
+//! A [`WorkspaceDigest`](crate::merkle::WorkspaceDigest) records the SHA-256
+//! of every file in a staged workspace plus a Merkle root over them. Stored in
+//! a receipt's verification block, it lets consumers prove exactly which
+//! input state produced a diff, and detect files changed between staging and
+//! execution.
+//!
+//! The tree is built as follows, with all hashes hex-encoded SHA-256:
+//!
+//! - a leaf is `H(0x00 || path || 0x00 || file_hash)`, where `path` is the
+//!   `/`-separated relative path and leaves are ordered by path;
+//! - an inner node is `H(0x01 || left || right)`; an odd node at the end of a
+//!   level is carried up unchanged;
+//! - the root of an empty workspace is `H("")`.
+//!
+//! The `0x00`/`0x01` prefixes keep leaves and inner nodes from colliding.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Per-file hashes of a workspace and the Merkle root over them.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::merkle::WorkspaceDigest;
+/// use std::collections::BTreeMap;
+///
+/// let mut files = BTreeMap::new();
+/// files.insert("src/lib.rs".to_string(), abp_core::sha256_hex(b"fn main() {}"));
+/// let digest = WorkspaceDigest::from_files(files);
+/// assert!(digest.verify());
+/// assert_eq!(digest.root.len(), 64);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct WorkspaceDigest {
+    /// Hex-encoded Merkle root over all files.
+    pub root: String,
+    /// Hex-encoded SHA-256 of each file's contents, keyed by `/`-separated
+    /// path relative to the workspace root.
+    pub files: BTreeMap<String, String>,
+}
+
+impl WorkspaceDigest {
+    /// Build a digest from per-file content hashes.
+    #[must_use]
+    pub fn from_files(files: BTreeMap<String, String>) -> Self {
+        Self {
+            root: merkle_root(&files),
+            files,
+        }
+    }
+
+    /// Whether `root` matches the Merkle root recomputed from `files`.
+    #[must_use]
+    pub fn verify(&self) -> bool {
+        merkle_root(&self.files) == self.root
+    }
+
+    /// Paths that were added, removed, or modified between `self` and
+    /// `other`, sorted.
+    #[must_use]
+    pub fn changed_paths(&self, other: &Self) -> Vec<String> {
+        let mut changed: Vec<String> = self
+            .files
+            .iter()
+            .filter(|(path, hash)| other.files.get(*path) != Some(*hash))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            other
+                .files
+                .keys()
+                .filter(|path| !self.files.contains_key(*path))
+                .cloned(),
+        );
+        changed.sort();
+        changed
+    }
+}
+
+/// Compute the Merkle root over `files` (path → hex content hash).
+#[must_use]
+pub fn merkle_root(files: &BTreeMap<String, String>) -> String {
+    let mut level: Vec<[u8; 32]> = files
+        .iter()
+        .map(|(path, hash)| {
+            let mut h = Sha256::new();
+            h.update([0x00]);
+            h.update(path.as_bytes());
+            h.update([0x00]);
+            h.update(hash.as_bytes());
+            h.finalize().into()
+        })
+        .collect();
+
+    if level.is_empty() {
+        return crate::sha256_hex(b"");
+    }
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut h = Sha256::new();
+                    h.update([0x01]);
+                    h.update(left);
+                    h.update(right);
+                    h.finalize().into()
+                }
+                [odd] => *odd,
+                _ => unreachable!("chunks(2) yields one or two nodes"),
+            })
+            .collect();
+    }
+
+    level[0].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(p, c)| ((*p).to_string(), crate::sha256_hex(c.as_bytes())))
+            .collect()
+    }
+
+    #[test]
+    fn empty_workspace_has_fixed_root() {
+        let d = WorkspaceDigest::from_files(BTreeMap::new());
+        assert_eq!(
+            d.root,
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert!(d.verify());
+    }
+
+    #[test]
+    fn root_changes_with_content_and_path() {
+        let a = WorkspaceDigest::from_files(files(&[("a.txt", "one"), ("b.txt", "two")]));
+        let b = WorkspaceDigest::from_files(files(&[("a.txt", "one"), ("b.txt", "TWO")]));
+        let c = WorkspaceDigest::from_files(files(&[("a.txt", "one"), ("c.txt", "two")]));
+        assert_ne!(a.root, b.root);
+        assert_ne!(a.root, c.root);
+    }
+
+    #[test]
+    fn odd_leaf_count_is_deterministic() {
+        let f = files(&[("a", "1"), ("b", "2"), ("c", "3")]);
+        assert_eq!(merkle_root(&f), merkle_root(&f.clone()));
+        assert_ne!(
+            merkle_root(&f),
+            merkle_root(&files(&[("a", "1"), ("b", "2")]))
+        );
+    }
+
+    #[test]
+    fn tampered_file_list_fails_verification() {
+        let mut d = WorkspaceDigest::from_files(files(&[("a", "1"), ("b", "2")]));
+        d.files.insert("b".into(), crate::sha256_hex(b"evil"));
+        assert!(!d.verify());
+    }
+
+    #[test]
+    fn changed_paths_reports_add_remove_modify() {
+        let before =
+            WorkspaceDigest::from_files(files(&[("keep", "k"), ("mod", "1"), ("rm", "x")]));
+        let after =
+            WorkspaceDigest::from_files(files(&[("keep", "k"), ("mod", "2"), ("new", "n")]));
+        assert_eq!(before.changed_paths(&after), vec!["mod", "new", "rm"]);
+        assert!(before.changed_paths(&before).is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Work order provenance: who and what submitted a run.
+//!
+//! In a shared deployment many people, agents, and tools submit work to the
+//! same runtime. A [`Submitter`](crate::submitter::Submitter) names the user,
+//! host, client library, the calling application's git commit, and the tenant
+//! and API key the run is billed to. It rides
+//! on the work order as `config.submitter` (see
+//! [`WorkOrderBuilder::submitter`](crate::WorkOrderBuilder::submitter)) and
+//! the runtime copies it to `usage_raw["submitter"]`, so every receipt answers
+//! which tool or human kicked off the run.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{Receipt, WorkOrder};
+
+/// Flat work-order vendor key carrying the [`Submitter`], read when
+/// `config.submitter` and `config.vendor["abp"]["submitter"]` are absent.
+pub const SUBMITTER_VENDOR_KEY: &str = "abp.submitter";
+
+/// Key under `receipt.usage_raw` holding the [`Submitter`].
+pub const SUBMITTER_KEY: &str = "submitter";
+
+/// Environment variable read by [`Submitter::from_env`] for the calling
+/// application's git commit.
+pub const GIT_COMMIT_ENV: &str = "ABP_GIT_COMMIT";
+
+/// Client library or tool that built the work order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ClientInfo {
+    /// Name, e.g. `"abp-cli"` or `"abp-shim-openai"`.
+    pub name: String,
+    /// Version of the client, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Who and what submitted a work order.
+///
+/// Every field is optional; callers fill in what they know.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::WorkOrderBuilder;
+/// use abp_core::submitter::Submitter;
+///
+/// let submitter = Submitter::new()
+///     .user("alice")
+///     .client("review-bot", Some("1.4.0"))
+///     .git_commit("9fceb02");
+/// let wo = WorkOrderBuilder::new("Review the PR")
+///     .submitter(submitter.clone())
+///     .build();
+/// assert_eq!(Submitter::from_work_order(&wo), Some(submitter));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Submitter {
+    /// User account or agent name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Host the work order was submitted from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Client library or tool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client: Option<ClientInfo>,
+    /// Git commit of the calling application.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_commit: Option<String>,
+    /// Tenant billed for the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+    /// API key the run was submitted with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<String>,
+}
+
+impl Submitter {
+    /// An empty submitter.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The current process's user and host, plus the git commit in
+    /// `ABP_GIT_COMMIT`.
+    ///
+    /// Reads `USER` (or `USERNAME`) and `HOSTNAME` (or `COMPUTERNAME`);
+    /// unset or empty variables leave the field empty.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let var = |names: &[&str]| {
+            names
+                .iter()
+                .find_map(|n| std::env::var(n).ok().filter(|v| !v.is_empty()))
+        };
+        Self {
+            user: var(&["USER", "USERNAME"]),
+            host: var(&["HOSTNAME", "COMPUTERNAME"]),
+            client: None,
+            git_commit: var(&[GIT_COMMIT_ENV]),
+            tenant: None,
+            api_key_id: None,
+        }
+    }
+
+    /// Set the user.
+    #[must_use]
+    pub fn user(mut self, user: impl Into<String>) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    /// Set the host.
+    #[must_use]
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.host = Some(host.into());
+        self
+    }
+
+    /// Set the client library or tool.
+    #[must_use]
+    pub fn client(mut self, name: impl Into<String>, version: Option<&str>) -> Self {
+        self.client = Some(ClientInfo {
+            name: name.into(),
+            version: version.map(str::to_string),
+        });
+        self
+    }
+
+    /// Set the calling application's git commit.
+    #[must_use]
+    pub fn git_commit(mut self, commit: impl Into<String>) -> Self {
+        self.git_commit = Some(commit.into());
+        self
+    }
+
+    /// Set the tenant billed for the run.
+    #[must_use]
+    pub fn tenant(mut self, tenant: impl Into<String>) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    /// Set the API key the run was submitted with.
+    #[must_use]
+    pub fn api_key_id(mut self, key_id: impl Into<String>) -> Self {
+        self.api_key_id = Some(key_id.into());
+        self
+    }
+
+    /// Whether no field is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Read the submitter a work order carries, if any.
+    ///
+    /// Reads `config.submitter`. When that is unset, falls back to
+    /// `config.vendor["abp"]["submitter"]` and then the flat
+    /// [`SUBMITTER_VENDOR_KEY`], as set by `--param abp.submitter=...`.
+    #[must_use]
+    pub fn from_work_order(work_order: &WorkOrder) -> Option<Self> {
+        if let Some(submitter) = &work_order.config.submitter {
+            return Some(submitter.clone());
+        }
+        let vendor = &work_order.config.vendor;
+        let value = vendor
+            .get("abp")
+            .and_then(|abp| abp.get(SUBMITTER_VENDOR_KEY.trim_start_matches("abp.")))
+            .or_else(|| vendor.get(SUBMITTER_VENDOR_KEY))?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// Read the submitter the runtime stored in a receipt, if any.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        let value = receipt.usage_raw.get(SUBMITTER_KEY)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// Stamp this submitter onto a work order, replacing any earlier one.
+    pub fn attach(&self, work_order: &mut WorkOrder) {
+        let vendor = &mut work_order.config.vendor;
+        vendor.remove(SUBMITTER_VENDOR_KEY);
+        if let Some(abp) = vendor.get_mut("abp").and_then(|v| v.as_object_mut()) {
+            abp.remove(SUBMITTER_VENDOR_KEY.trim_start_matches("abp."));
+        }
+        work_order.config.submitter = Some(self.clone());
+    }
+}
@@ -0,0 +1,292 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Typed envelopes for tool results.
+//!
+//! [`AgentEventKind::ToolResult`](crate::AgentEventKind::ToolResult) carries its output as an untyped
+//! [`serde_json::Value`]. A
+//! [`ToolResultFormat`](crate::tool_result::ToolResultFormat) says what that value is —
+//! JSON, text, a binary blob, or a table — plus its media type, encoding,
+//! schema reference, and whether the tool cut it short. The envelope rides in
+//! the event's `ext["abp.tool_result"]`, and in IR under the message metadata
+//! key `"abp.tool_results"` (keyed by tool use id, see
+//! [`IrMessage::tool_result_format`](crate::ir::IrMessage::tool_result_format)), so renderers and dialect lowering can
+//! treat tables and blobs differently from plain text.
+//!
+//! Results without an envelope fall back to
+//! [`ToolResultFormat::infer`](crate::tool_result::ToolResultFormat::infer).
+//!
+//! # Examples
+//!
+//! ```
+//! use abp_core::tool_result::{ToolResultFormat, ToolResultKind};
+//! use abp_core::{AgentEvent, AgentEventKind};
+//! use serde_json::json;
+//!
+//! let mut event = AgentEvent {
+//!     ts: chrono::Utc::now(),
+//!     kind: AgentEventKind::ToolResult {
+//!         tool_name: "Query".into(),
+//!         tool_use_id: Some("tu-1".into()),
+//!         output: json!([{"id": 1, "name": "a"}, {"id": 2, "name": "b"}]),
+//!         is_error: false,
+//!     },
+//!     ext: None,
+//! };
+//! ToolResultFormat::new(ToolResultKind::Table)
+//!     .truncated(true)
+//!     .attach(&mut event);
+//!
+//! let format = ToolResultFormat::of_event(&event).unwrap();
+//! assert_eq!(format.kind, ToolResultKind::Table);
+//! assert!(format.truncated);
+//! ```
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::ir::{IrContentBlock, IrMessage};
+use crate::{AgentEvent, AgentEventKind};
+
+/// Event `ext` key carrying the [`ToolResultFormat`].
+pub const TOOL_RESULT_FORMAT_KEY: &str = "abp.tool_result";
+
+/// IR message metadata key mapping tool use ids to their [`ToolResultFormat`].
+pub const IR_TOOL_RESULTS_KEY: &str = "abp.tool_results";
+
+/// What a tool result's output holds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolResultKind {
+    /// Structured JSON.
+    #[default]
+    Json,
+    /// Human-readable text; the output is a JSON string.
+    Text,
+    /// Opaque bytes; the output is a JSON string in the stated encoding.
+    Binary,
+    /// Rows of records; the output is an array of objects, or an object
+    /// with `columns` and `rows` arrays.
+    Table,
+}
+
+/// How a [`ToolResultKind::Binary`] or [`ToolResultKind::Text`] payload is
+/// encoded inside its JSON string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolResultEncoding {
+    /// The string is the content itself.
+    #[default]
+    Utf8,
+    /// The string is base64-encoded bytes.
+    Base64,
+}
+
+/// Type envelope for one tool result.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ToolResultFormat {
+    /// What the output holds.
+    pub kind: ToolResultKind,
+    /// MIME type of the content, e.g. `"image/png"` or `"text/csv"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    /// Encoding of string payloads.
+    #[serde(default)]
+    pub encoding: ToolResultEncoding,
+    /// URI or name of a schema the output conforms to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    /// Whether the tool cut the output short.
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+impl ToolResultFormat {
+    /// An envelope of the given kind with default encoding and no extras.
+    #[must_use]
+    pub fn new(kind: ToolResultKind) -> Self {
+        Self {
+            kind,
+            ..Self::default()
+        }
+    }
+
+    /// Base64-encoded bytes of `media_type`.
+    #[must_use]
+    pub fn binary(media_type: impl Into<String>) -> Self {
+        Self::new(ToolResultKind::Binary)
+            .media_type(media_type)
+            .encoding(ToolResultEncoding::Base64)
+    }
+
+    /// Set the media type.
+    #[must_use]
+    pub fn media_type(mut self, media_type: impl Into<String>) -> Self {
+        self.media_type = Some(media_type.into());
+        self
+    }
+
+    /// Set the encoding.
+    #[must_use]
+    pub fn encoding(mut self, encoding: ToolResultEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Set the schema reference.
+    #[must_use]
+    pub fn schema(mut self, schema: impl Into<String>) -> Self {
+        self.schema = Some(schema.into());
+        self
+    }
+
+    /// Mark the output as truncated.
+    #[must_use]
+    pub fn truncated(mut self, truncated: bool) -> Self {
+        self.truncated = truncated;
+        self
+    }
+
+    /// Best guess for an output that came without an envelope: strings are
+    /// text, non-empty arrays of objects are tables, anything else is JSON.
+    #[must_use]
+    pub fn infer(output: &Value) -> Self {
+        let kind = match output {
+            Value::String(_) => ToolResultKind::Text,
+            Value::Array(rows) if !rows.is_empty() && rows.iter().all(Value::is_object) => {
+                ToolResultKind::Table
+            }
+            _ => ToolResultKind::Json,
+        };
+        Self::new(kind)
+    }
+
+    /// The envelope stored on `event`, or one inferred from its output.
+    ///
+    /// Returns `None` for events that are not tool results.
+    #[must_use]
+    pub fn of_event(event: &AgentEvent) -> Option<Self> {
+        let AgentEventKind::ToolResult { output, .. } = &event.kind else {
+            return None;
+        };
+        let stored = event
+            .ext
+            .as_ref()
+            .and_then(|ext| ext.get(TOOL_RESULT_FORMAT_KEY))
+            .and_then(|v| serde_json::from_value(v.clone()).ok());
+        Some(stored.unwrap_or_else(|| Self::infer(output)))
+    }
+
+    /// Store this envelope in `event.ext`.
+    pub fn attach(&self, event: &mut AgentEvent) {
+        event.ext.get_or_insert_with(Default::default).insert(
+            TOOL_RESULT_FORMAT_KEY.to_string(),
+            serde_json::to_value(self).expect("tool result format serialises to JSON"),
+        );
+    }
+
+    /// `output` as text for consumers that only take strings.
+    ///
+    /// Text is returned as is, binary payloads become a short placeholder,
+    /// and JSON and tables are serialised compactly.
+    #[must_use]
+    pub fn render_text(&self, output: &Value) -> String {
+        match (self.kind, output) {
+            (ToolResultKind::Text, Value::String(s)) => s.clone(),
+            (ToolResultKind::Binary, Value::String(s)) => format!(
+                "[binary {}, {} bytes]",
+                self.media_type
+                    .as_deref()
+                    .unwrap_or("application/octet-stream"),
+                self.decoded_len(s)
+            ),
+            (_, other) => other.to_string(),
+        }
+    }
+
+    /// IR content blocks for `output`.
+    ///
+    /// Base64 images become [`IrContentBlock::Image`]; everything else is a
+    /// single [`IrContentBlock::Text`] from [`render_text`](Self::render_text).
+    #[must_use]
+    pub fn to_ir_content(&self, output: &Value) -> Vec<IrContentBlock> {
+        if let (
+            ToolResultKind::Binary,
+            ToolResultEncoding::Base64,
+            Some(media),
+            Value::String(data),
+        ) = (self.kind, self.encoding, &self.media_type, output)
+            && media.starts_with("image/")
+        {
+            return vec![IrContentBlock::Image {
+                media_type: media.clone(),
+                data: data.clone(),
+            }];
+        }
+        vec![IrContentBlock::Text {
+            text: self.render_text(output),
+        }]
+    }
+
+    fn decoded_len(&self, s: &str) -> usize {
+        match self.encoding {
+            ToolResultEncoding::Utf8 => s.len(),
+            ToolResultEncoding::Base64 => {
+                let data = s.trim_end_matches('=').len();
+                data * 3 / 4
+            }
+        }
+    }
+}
+
+impl IrMessage {
+    /// The [`ToolResultFormat`] recorded for `tool_use_id`, if any.
+    #[must_use]
+    pub fn tool_result_format(&self, tool_use_id: &str) -> Option<ToolResultFormat> {
+        self.metadata
+            .get(IR_TOOL_RESULTS_KEY)?
+            .get(tool_use_id)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// Record the [`ToolResultFormat`] of the result for `tool_use_id`.
+    pub fn set_tool_result_format(&mut self, tool_use_id: &str, format: &ToolResultFormat) {
+        let entry = self
+            .metadata
+            .entry(IR_TOOL_RESULTS_KEY.to_string())
+            .or_insert_with(|| Value::Object(Default::default()));
+        if !entry.is_object() {
+            *entry = Value::Object(Default::default());
+        }
+        entry[tool_use_id] =
+            serde_json::to_value(format).expect("tool result format serialises to JSON");
+    }
+
+    /// A [`Tool`](crate::ir::IrRole::Tool) message holding the result in
+    /// `event`, with its format recorded in the metadata.
+    ///
+    /// Returns `None` for events that are not tool results.
+    #[must_use]
+    pub fn from_tool_result(event: &AgentEvent) -> Option<Self> {
+        let AgentEventKind::ToolResult {
+            tool_name,
+            tool_use_id,
+            output,
+            is_error,
+        } = &event.kind
+        else {
+            return None;
+        };
+        let format = ToolResultFormat::of_event(event)?;
+        let id = tool_use_id.clone().unwrap_or_else(|| tool_name.clone());
+        let mut msg = Self::new(
+            crate::ir::IrRole::Tool,
+            vec![IrContentBlock::ToolResult {
+                tool_use_id: id.clone(),
+                content: format.to_ir_content(output),
+                is_error: *is_error,
+            }],
+        );
+        msg.set_tool_result_format(&id, &format);
+        Some(msg)
+    }
+}
@@ -88,6 +88,9 @@ fn wo_full_build() {
         env,
         max_budget_usd: Some(1.5),
         max_turns: Some(20),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
 
     let wo = WorkOrderBuilder::new("full task")
@@ -139,6 +142,27 @@ fn wo_max_turns() {
     assert_eq!(wo.config.max_turns, Some(42));
 }
 
+#[test]
+fn wo_seed_merges_into_abp_vendor_config() {
+    let mut config = abp_core::RuntimeConfig::default();
+    config
+        .vendor
+        .insert("abp".into(), serde_json::json!({"mode": "passthrough"}));
+    let wo = WorkOrderBuilder::new("task").config(config).seed(7).build();
+    assert_eq!(
+        wo.config.vendor["abp"],
+        serde_json::json!({"mode": "passthrough", "seed": 7})
+    );
+}
+
+#[test]
+fn wo_explicit_id_overrides_random_id() {
+    use abp_core::ids::{IdGenerator, SequentialIdGenerator};
+    let ids = SequentialIdGenerator::new();
+    let wo = WorkOrderBuilder::new("task").id(ids.next_id()).build();
+    assert_eq!(wo.id.as_u128(), 1);
+}
+
 #[test]
 fn wo_budget_limits() {
     let wo = WorkOrderBuilder::new("task").max_budget_usd(9.99).build();
@@ -645,6 +669,7 @@ fn receipt_verification_report() {
         git_diff: Some("diff --git a/foo b/foo".into()),
         git_status: Some("M foo".into()),
         harness_ok: true,
+        input_digest: None,
     };
 
     let receipt = ReceiptBuilder::new("mock")
@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for trace compaction.
+
+use std::collections::BTreeMap;
+
+use abp_core::compact::{
+    COMPACTION_KEY, CompactionPolicy, CompactionRecord, HEARTBEAT_EXT_KEY, compact_trace,
+};
+use abp_core::{AgentEvent, AgentEventKind, Receipt, ReceiptBuilder, receipt_hash};
+use chrono::Utc;
+
+fn event(kind: AgentEventKind) -> AgentEvent {
+    AgentEvent {
+        ts: Utc::now(),
+        kind,
+        ext: None,
+    }
+}
+
+fn delta(text: &str) -> AgentEvent {
+    event(AgentEventKind::AssistantDelta { text: text.into() })
+}
+
+fn message(text: &str) -> AgentEvent {
+    event(AgentEventKind::AssistantMessage { text: text.into() })
+}
+
+fn progress(message: &str) -> AgentEvent {
+    event(AgentEventKind::Progress {
+        percent: Some(50.0),
+        message: message.into(),
+    })
+}
+
+fn heartbeat() -> AgentEvent {
+    let mut ext = BTreeMap::new();
+    ext.insert(HEARTBEAT_EXT_KEY.to_string(), serde_json::json!(5000));
+    AgentEvent {
+        ext: Some(ext),
+        ..event(AgentEventKind::Progress {
+            percent: None,
+            message: "waiting for backend (5s without events)".into(),
+        })
+    }
+}
+
+fn tool_call() -> AgentEvent {
+    event(AgentEventKind::ToolCall {
+        tool_name: "read_file".into(),
+        tool_use_id: Some("t1".into()),
+        parent_tool_use_id: None,
+        input: serde_json::json!({"path": "a.rs"}),
+    })
+}
+
+fn texts(trace: &[AgentEvent]) -> Vec<String> {
+    trace
+        .iter()
+        .map(|e| match &e.kind {
+            AgentEventKind::AssistantDelta { text } => format!("delta:{text}"),
+            AgentEventKind::AssistantMessage { text } => format!("message:{text}"),
+            AgentEventKind::Progress { message, .. } => format!("progress:{message}"),
+            AgentEventKind::ToolCall { tool_name, .. } => format!("tool:{tool_name}"),
+            other => format!("{other:?}"),
+        })
+        .collect()
+}
+
+fn receipt(trace: Vec<AgentEvent>) -> Receipt {
+    let mut builder = ReceiptBuilder::new("mock");
+    for ev in trace {
+        builder = builder.add_trace_event(ev);
+    }
+    builder.build()
+}
+
+#[test]
+fn delta_runs_become_messages() {
+    let trace = vec![delta("a"), delta("b"), tool_call(), delta("c"), delta("d")];
+    let out = compact_trace(trace, &CompactionPolicy::default());
+    assert_eq!(
+        texts(&out),
+        ["message:ab", "tool:read_file", "message:cd"].map(String::from)
+    );
+}
+
+#[test]
+fn deltas_repeated_by_a_full_message_are_dropped() {
+    let trace = vec![delta("Hel"), delta("lo"), message("Hello")];
+    let out = compact_trace(trace, &CompactionPolicy::default());
+    assert_eq!(texts(&out), ["message:Hello"].map(String::from));
+}
+
+#[test]
+fn heartbeats_are_dropped_but_progress_kept_by_default() {
+    let trace = vec![delta("a"), heartbeat(), delta("b"), progress("indexing")];
+    let out = compact_trace(trace, &CompactionPolicy::default());
+    assert_eq!(
+        texts(&out),
+        ["message:ab", "progress:indexing"].map(String::from)
+    );
+}
+
+#[test]
+fn aggressive_policy_drops_all_progress() {
+    let trace = vec![progress("indexing"), heartbeat(), tool_call()];
+    let out = compact_trace(trace, &CompactionPolicy::aggressive());
+    assert_eq!(texts(&out), ["tool:read_file"].map(String::from));
+}
+
+#[test]
+fn deltas_with_ext_are_kept() {
+    let mut raw = delta("b");
+    raw.ext = Some(BTreeMap::from([(
+        "raw_message".to_string(),
+        serde_json::json!({"delta": "b"}),
+    )]));
+    let trace = vec![delta("a"), raw, delta("c")];
+    let out = compact_trace(trace, &CompactionPolicy::default());
+    assert_eq!(
+        texts(&out),
+        ["message:a", "delta:b", "message:c"].map(String::from)
+    );
+}
+
+#[test]
+fn disabled_policy_changes_nothing() {
+    let policy = CompactionPolicy {
+        merge_deltas: false,
+        drop_heartbeats: false,
+        drop_progress: false,
+    };
+    let trace = vec![delta("a"), heartbeat(), delta("b")];
+    let out = compact_trace(trace.clone(), &policy);
+    assert_eq!(texts(&out), texts(&trace));
+}
+
+#[test]
+fn compact_records_policy_and_original_hash() {
+    let original = receipt(vec![delta("a"), delta("b"), heartbeat()])
+        .with_hash()
+        .unwrap();
+    let original_hash = original.receipt_sha256.clone();
+
+    let compacted = original.compact(&CompactionPolicy::default()).unwrap();
+    let record = CompactionRecord::from_receipt(&compacted).unwrap();
+    assert_eq!(record.original_sha256, original_hash);
+    assert_eq!(record.policy, CompactionPolicy::default());
+    assert_eq!(record.events_before, 3);
+    assert_eq!(record.events_after, 1);
+
+    // The compacted receipt carries its own valid hash.
+    assert_ne!(compacted.receipt_sha256, original_hash);
+    assert_eq!(
+        compacted.receipt_sha256.as_deref(),
+        Some(receipt_hash(&compacted).unwrap().as_str())
+    );
+}
+
+#[test]
+fn unhashed_receipt_stays_unhashed() {
+    let compacted = receipt(vec![delta("a")])
+        .compact(&CompactionPolicy::default())
+        .unwrap();
+    assert!(compacted.receipt_sha256.is_none());
+    assert!(compacted.usage_raw.get(COMPACTION_KEY).is_some());
+}
+
+#[test]
+fn recompaction_keeps_first_original_hash() {
+    let original = receipt(vec![delta("a"), heartbeat(), progress("x")])
+        .with_hash()
+        .unwrap();
+    let original_hash = original.receipt_sha256.clone();
+
+    let once = original.compact(&CompactionPolicy::default()).unwrap();
+    let twice = once.compact(&CompactionPolicy::aggressive()).unwrap();
+    let record = CompactionRecord::from_receipt(&twice).unwrap();
+    assert_eq!(record.original_sha256, original_hash);
+    assert_eq!(record.policy, CompactionPolicy::aggressive());
+    assert_eq!(record.events_before, 3);
+    assert_eq!(record.events_after, 1);
+}
+
+#[test]
+fn non_object_usage_raw_is_preserved() {
+    let mut r = receipt(vec![delta("a")]);
+    r.usage_raw = serde_json::json!("opaque");
+    let compacted = r.compact(&CompactionPolicy::default()).unwrap();
+    assert_eq!(compacted.usage_raw["original"], "opaque");
+    assert!(compacted.usage_raw.get(COMPACTION_KEY).is_some());
+}
@@ -893,6 +893,32 @@ fn usage_normalized_serde_roundtrip() {
     assert_eq!(u.output_tokens, u2.output_tokens);
 }
 
+#[test]
+fn usage_normalized_fills_cache_from_raw() {
+    let mut u = UsageNormalized::default();
+    u.fill_cache_from_raw(&serde_json::json!({
+        "input_tokens": 10,
+        "cache_read_input_tokens": 80,
+        "cache_creation_input_tokens": 20,
+    }));
+    assert_eq!(u.cache_read_tokens, Some(80));
+    assert_eq!(u.cache_write_tokens, Some(20));
+
+    let mut u = UsageNormalized::default();
+    u.fill_cache_from_raw(&serde_json::json!({
+        "usage": {"prompt_tokens_details": {"cached_tokens": 64}},
+    }));
+    assert_eq!(u.cache_read_tokens, Some(64));
+    assert_eq!(u.cache_write_tokens, None);
+
+    let mut u = UsageNormalized {
+        cache_read_tokens: Some(1),
+        ..Default::default()
+    };
+    u.fill_cache_from_raw(&serde_json::json!({"cachedContentTokenCount": 9}));
+    assert_eq!(u.cache_read_tokens, Some(1));
+}
+
 #[test]
 fn usage_normalized_clone_debug() {
     let u = UsageNormalized::default();
@@ -939,6 +965,7 @@ fn verification_report_serde_roundtrip() {
         git_diff: Some("diff".into()),
         git_status: Some("M src/lib.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let v2 = roundtrip(&v);
     assert_eq!(v.git_diff, v2.git_diff);
@@ -77,6 +77,9 @@ fn sample_work_order() -> WorkOrder {
             env: BTreeMap::new(),
             max_budget_usd: Some(1.0),
             max_turns: Some(10),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -301,6 +304,7 @@ fn receipt_builder_all_methods() {
             git_diff: Some("diff".into()),
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         })
         .build();
 
@@ -924,6 +928,7 @@ fn serde_roundtrip_verification_report() {
         git_diff: Some("diff --git a/b".into()),
         git_status: Some("M src/lib.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let json = serde_json::to_string(&v).unwrap();
     let back: VerificationReport = serde_json::from_str(&json).unwrap();
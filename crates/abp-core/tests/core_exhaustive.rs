@@ -751,6 +751,7 @@ fn receipt_builder_verification() {
         git_diff: Some("diff".into()),
         git_status: Some("M file.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let r = ReceiptBuilder::new("x").verification(v).build();
     assert_eq!(r.verification.git_diff.as_deref(), Some("diff"));
@@ -928,6 +929,7 @@ fn verification_report_construction() {
         git_diff: Some("diff --git".into()),
         git_status: Some("M src/lib.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let json = serde_json::to_string(&vr).unwrap();
     let back: VerificationReport = serde_json::from_str(&json).unwrap();
@@ -1341,6 +1343,7 @@ fn receipt_full_construction_and_hash() {
             git_diff: Some("diff".into()),
             git_status: Some("M f.rs".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .add_trace_event(ev1)
         .add_trace_event(ev2)
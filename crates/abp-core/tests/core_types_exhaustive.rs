@@ -963,6 +963,9 @@ fn work_order_builder_config() {
         env: BTreeMap::new(),
         max_budget_usd: Some(10.0),
         max_turns: Some(20),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let wo = WorkOrderBuilder::new("t").config(config).build();
     assert_eq!(wo.config.model.as_deref(), Some("claude-3"));
@@ -1116,6 +1119,7 @@ fn receipt_builder_verification() {
         git_diff: Some("diff content".into()),
         git_status: Some("M src/lib.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let r = ReceiptBuilder::new("mock").verification(v).build();
     assert!(r.verification.harness_ok);
@@ -1408,6 +1412,7 @@ fn verification_report_roundtrip() {
         git_diff: Some("---\n+++".into()),
         git_status: Some("M file.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let json = serde_json::to_string(&v).unwrap();
     let back: VerificationReport = serde_json::from_str(&json).unwrap();
@@ -1471,6 +1476,9 @@ fn runtime_config_roundtrip() {
         env,
         max_budget_usd: Some(5.0),
         max_turns: Some(10),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json = serde_json::to_string(&c).unwrap();
     let back: RuntimeConfig = serde_json::from_str(&json).unwrap();
@@ -108,6 +108,9 @@ fn full_work_order() -> WorkOrder {
             env: BTreeMap::from([("RUST_LOG".into(), "debug".into())]),
             max_budget_usd: Some(5.0),
             max_turns: Some(20),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -200,6 +203,7 @@ fn full_receipt() -> Receipt {
             git_diff: Some("+added line\n-removed line".into()),
             git_status: Some("M src/lib.rs\nA src/new.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
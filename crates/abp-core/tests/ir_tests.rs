@@ -385,6 +385,57 @@ fn conversation_serde_roundtrip() {
     assert_eq!(conv, back);
 }
 
+// ═══════════════════════════════════════════════════════════════════════
+// Prompt caching
+// ═══════════════════════════════════════════════════════════════════════
+
+#[test]
+fn cache_control_lives_in_message_metadata() {
+    let plain = IrMessage::text(IrRole::User, "long context");
+    assert_eq!(plain.cache_control(), None);
+
+    let cached = plain.with_cache_control(IrCacheControl::ephemeral());
+    assert_eq!(cached.cache_control(), Some(IrCacheControl::ephemeral()));
+    assert_eq!(
+        cached.metadata[CACHE_CONTROL_KEY],
+        json!({"type": "ephemeral"})
+    );
+
+    let hour = IrCacheControl::Ephemeral {
+        ttl: Some("1h".into()),
+    };
+    let s = serde_json::to_string(&cached.with_cache_control(hour.clone())).unwrap();
+    let back: IrMessage = serde_json::from_str(&s).unwrap();
+    assert_eq!(back.cache_control(), Some(hour));
+}
+
+#[test]
+fn block_cache_breakpoints_are_keyed_by_index() {
+    let msg = IrMessage::new(
+        IrRole::User,
+        vec![
+            IrContentBlock::Text { text: "a".into() },
+            IrContentBlock::Text { text: "b".into() },
+        ],
+    );
+    assert!(msg.cache_breakpoints().is_empty());
+
+    let msg = msg
+        .with_block_cache_control(0, IrCacheControl::ephemeral())
+        .with_cache_control(IrCacheControl::ephemeral());
+    assert_eq!(
+        msg.metadata[CACHE_BREAKPOINTS_KEY],
+        json!({"0": {"type": "ephemeral"}})
+    );
+    assert_eq!(
+        msg.cache_breakpoints().into_iter().collect::<Vec<_>>(),
+        [
+            (0, IrCacheControl::ephemeral()),
+            (1, IrCacheControl::ephemeral())
+        ]
+    );
+}
+
 // ═══════════════════════════════════════════════════════════════════════
 // IrToolDefinition
 // ═══════════════════════════════════════════════════════════════════════
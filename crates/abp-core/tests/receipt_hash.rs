@@ -68,3 +68,12 @@ fn receipt_hash_matches_stored_hash() {
 
     assert_eq!(receipt.receipt_sha256.as_deref(), Some(recomputed.as_str()));
 }
+
+#[test]
+fn receipt_hash_ignores_float_spelling_in_usage_raw() {
+    let mut a = abp_core::ReceiptBuilder::new("mock").build();
+    a.usage_raw = serde_json::json!({"cost_usd": 2.0});
+    let mut b = a.clone();
+    b.usage_raw = serde_json::json!({"cost_usd": 2});
+    assert_eq!(receipt_hash(&a).unwrap(), receipt_hash(&b).unwrap());
+}
@@ -107,6 +107,9 @@ fn roundtrip_work_order() {
             },
             max_budget_usd: Some(1.5),
             max_turns: Some(10),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     roundtrip_json(&wo);
@@ -152,6 +155,9 @@ fn roundtrip_runtime_config() {
         env: BTreeMap::new(),
         max_budget_usd: Some(5.0),
         max_turns: Some(20),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     roundtrip_json(&rc);
 }
@@ -247,6 +253,7 @@ fn roundtrip_verification_report() {
         git_diff: Some("diff --git a/f b/f\n".into()),
         git_status: Some("M f\n".into()),
         harness_ok: true,
+        input_digest: None,
     };
     roundtrip_json(&vr);
 }
@@ -328,6 +335,7 @@ fn roundtrip_receipt_full() {
             git_diff: Some("diff".into()),
             git_status: None,
             harness_ok: false,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -545,6 +553,9 @@ fn optional_fields_absent() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     roundtrip_json(&rc);
 
@@ -578,6 +589,9 @@ fn optional_fields_present() {
         },
         max_budget_usd: Some(10.0),
         max_turns: Some(100),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     roundtrip_json(&rc);
 }
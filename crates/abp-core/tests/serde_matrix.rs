@@ -117,6 +117,9 @@ fn sample_work_order_full() -> WorkOrder {
             env,
             max_budget_usd: Some(1.5),
             max_turns: Some(20),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -171,6 +174,7 @@ fn sample_receipt_full() -> Receipt {
             git_diff: Some("+line".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -841,6 +845,9 @@ fn runtime_config_full_roundtrip() {
         },
         max_budget_usd: Some(5.0),
         max_turns: Some(50),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     assert_roundtrip(&cfg);
     assert_pretty_compact_equal(&cfg);
@@ -906,6 +913,7 @@ fn verification_report_full_roundtrip() {
         git_diff: Some("+new line\n-old line".into()),
         git_status: Some("M src/lib.rs\nA tests/new.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     assert_roundtrip(&vr);
     assert_pretty_compact_equal(&vr);
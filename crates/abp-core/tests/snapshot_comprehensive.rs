@@ -112,6 +112,9 @@ fn snapshot_full_work_order_all_fields() {
             ]),
             max_budget_usd: Some(5.0),
             max_turns: Some(25),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     assert_json_snapshot!("comprehensive_full_work_order", wo);
@@ -195,6 +198,7 @@ fn snapshot_full_receipt_with_hash() {
             git_diff: Some("+pub fn authorize() -> Result<Token> {\n+    // PKCE flow\n+}".into()),
             git_status: Some("M src/auth.rs\nA src/oauth2.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -594,6 +598,7 @@ fn snapshot_receipt_failed_outcome() {
             git_diff: None,
             git_status: None,
             harness_ok: false,
+            input_digest: None,
         })
         .build();
     let value = serde_json::to_value(&receipt).unwrap();
@@ -77,6 +77,9 @@ fn sample_work_order() -> WorkOrder {
             env: BTreeMap::new(),
             max_budget_usd: Some(1.0),
             max_turns: Some(10),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -123,6 +126,7 @@ fn sample_receipt() -> Receipt {
             git_diff: Some("+line".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for work order submitter provenance.
+
+use abp_core::submitter::{SUBMITTER_KEY, SUBMITTER_VENDOR_KEY, Submitter};
+use abp_core::{ReceiptBuilder, WorkOrderBuilder};
+
+fn full() -> Submitter {
+    Submitter::new()
+        .user("alice")
+        .host("build-07")
+        .client("review-bot", Some("1.4.0"))
+        .git_commit("9fceb02")
+}
+
+#[test]
+fn builder_stamps_submitter_on_work_order() {
+    let wo = WorkOrderBuilder::new("t").submitter(full()).build();
+    assert_eq!(Submitter::from_work_order(&wo), Some(full()));
+    assert_eq!(wo.config.submitter, Some(full()));
+    assert!(wo.config.vendor.is_empty());
+}
+
+#[test]
+fn nested_vendor_key_is_read_as_fallback() {
+    let mut wo = WorkOrderBuilder::new("t").build();
+    wo.config.vendor.insert(
+        "abp".to_string(),
+        serde_json::json!({"submitter": {"user": "alice"}}),
+    );
+    assert_eq!(
+        Submitter::from_work_order(&wo),
+        Some(Submitter::new().user("alice"))
+    );
+
+    Submitter::new().user("bob").attach(&mut wo);
+    assert_eq!(wo.config.vendor["abp"], serde_json::json!({}));
+    assert_eq!(wo.config.submitter, Some(Submitter::new().user("bob")));
+}
+
+#[test]
+fn flat_vendor_key_is_read_as_fallback() {
+    let mut wo = WorkOrderBuilder::new("t").build();
+    wo.config.vendor.insert(
+        SUBMITTER_VENDOR_KEY.to_string(),
+        serde_json::json!({"user": "alice"}),
+    );
+    assert_eq!(
+        Submitter::from_work_order(&wo),
+        Some(Submitter::new().user("alice"))
+    );
+
+    Submitter::new().user("bob").attach(&mut wo);
+    assert!(!wo.config.vendor.contains_key(SUBMITTER_VENDOR_KEY));
+    assert_eq!(
+        Submitter::from_work_order(&wo),
+        Some(Submitter::new().user("bob"))
+    );
+}
+
+#[test]
+fn submitter_round_trips_through_json() {
+    let wo = WorkOrderBuilder::new("t").submitter(full()).build();
+    let json = serde_json::to_value(&wo).unwrap();
+    assert_eq!(json["config"]["submitter"]["user"], "alice");
+    let back: abp_core::WorkOrder = serde_json::from_value(json).unwrap();
+    assert_eq!(Submitter::from_work_order(&back), Some(full()));
+}
+
+#[test]
+fn unset_fields_are_omitted() {
+    let value = serde_json::to_value(Submitter::new().user("ci")).unwrap();
+    assert_eq!(value, serde_json::json!({"user": "ci"}));
+    assert!(Submitter::new().is_empty());
+    assert!(!full().is_empty());
+}
+
+#[test]
+fn attach_replaces_earlier_submitter() {
+    let mut wo = WorkOrderBuilder::new("t").submitter(full()).build();
+    Submitter::new().user("bob").attach(&mut wo);
+    assert_eq!(
+        Submitter::from_work_order(&wo).unwrap().user.as_deref(),
+        Some("bob")
+    );
+}
+
+#[test]
+fn receipt_without_submitter_reads_none() {
+    let mut receipt = ReceiptBuilder::new("mock").build();
+    assert!(Submitter::from_receipt(&receipt).is_none());
+    receipt.usage_raw = serde_json::json!({ SUBMITTER_KEY: {"user": "alice"} });
+    assert_eq!(
+        Submitter::from_receipt(&receipt),
+        Some(Submitter::new().user("alice"))
+    );
+}
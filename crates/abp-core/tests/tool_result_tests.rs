@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for typed tool-result envelopes.
+
+use abp_core::ir::{IrContentBlock, IrMessage, IrRole};
+use abp_core::tool_result::{
+    IR_TOOL_RESULTS_KEY, TOOL_RESULT_FORMAT_KEY, ToolResultEncoding, ToolResultFormat,
+    ToolResultKind,
+};
+use abp_core::{AgentEvent, AgentEventKind};
+use serde_json::{Value, json};
+
+fn result(output: Value) -> AgentEvent {
+    AgentEvent {
+        ts: chrono::Utc::now(),
+        kind: AgentEventKind::ToolResult {
+            tool_name: "Read".into(),
+            tool_use_id: Some("tu-1".into()),
+            output,
+            is_error: false,
+        },
+        ext: None,
+    }
+}
+
+#[test]
+fn untagged_results_are_inferred_from_their_output() {
+    let kind = |v: Value| ToolResultFormat::of_event(&result(v)).unwrap().kind;
+    assert_eq!(kind(json!("hello")), ToolResultKind::Text);
+    assert_eq!(kind(json!([{"a": 1}, {"a": 2}])), ToolResultKind::Table);
+    assert_eq!(kind(json!([1, 2])), ToolResultKind::Json);
+    assert_eq!(kind(json!([])), ToolResultKind::Json);
+    assert_eq!(kind(json!({"ok": true})), ToolResultKind::Json);
+
+    let call = AgentEvent {
+        ts: chrono::Utc::now(),
+        kind: AgentEventKind::Warning {
+            message: "w".into(),
+        },
+        ext: None,
+    };
+    assert_eq!(ToolResultFormat::of_event(&call), None);
+}
+
+#[test]
+fn attached_envelope_wins_and_serialises_compactly() {
+    let mut event = result(json!("iVBORw0KGgo="));
+    let format = ToolResultFormat::binary("image/png").schema("urn:shot");
+    format.attach(&mut event);
+
+    assert_eq!(ToolResultFormat::of_event(&event), Some(format));
+    assert_eq!(
+        event.ext.as_ref().unwrap()[TOOL_RESULT_FORMAT_KEY],
+        json!({
+            "kind": "binary",
+            "media_type": "image/png",
+            "encoding": "base64",
+            "schema": "urn:shot",
+            "truncated": false,
+        })
+    );
+
+    let wire = serde_json::to_string(&event).unwrap();
+    let back: AgentEvent = serde_json::from_str(&wire).unwrap();
+    assert_eq!(
+        ToolResultFormat::of_event(&back).unwrap().encoding,
+        ToolResultEncoding::Base64
+    );
+}
+
+#[test]
+fn rendering_depends_on_the_kind() {
+    let text = ToolResultFormat::new(ToolResultKind::Text);
+    assert_eq!(text.render_text(&json!("a\nb")), "a\nb");
+
+    let table = ToolResultFormat::new(ToolResultKind::Table);
+    assert_eq!(table.render_text(&json!([{"a": 1}])), r#"[{"a":1}]"#);
+
+    let blob = ToolResultFormat::binary("application/pdf");
+    assert_eq!(
+        blob.render_text(&json!("AAAAAA==")),
+        "[binary application/pdf, 4 bytes]"
+    );
+    assert_eq!(
+        blob.to_ir_content(&json!("AAAAAA==")),
+        vec![IrContentBlock::Text {
+            text: "[binary application/pdf, 4 bytes]".into()
+        }]
+    );
+}
+
+#[test]
+fn ir_message_carries_the_envelope_per_tool_use_id() {
+    let mut event = result(json!("iVBORw0KGgo="));
+    ToolResultFormat::binary("image/png")
+        .truncated(true)
+        .attach(&mut event);
+
+    let msg = IrMessage::from_tool_result(&event).unwrap();
+    assert_eq!(msg.role, IrRole::Tool);
+    let IrContentBlock::ToolResult { content, .. } = &msg.content[0] else {
+        panic!("expected a tool result block");
+    };
+    assert_eq!(
+        content[0],
+        IrContentBlock::Image {
+            media_type: "image/png".into(),
+            data: "iVBORw0KGgo=".into(),
+        }
+    );
+    assert!(msg.tool_result_format("tu-1").unwrap().truncated);
+    assert_eq!(msg.tool_result_format("tu-2"), None);
+
+    let mut other = IrMessage::text(IrRole::Tool, "x");
+    other
+        .metadata
+        .insert(IR_TOOL_RESULTS_KEY.into(), json!("junk"));
+    other.set_tool_result_format("tu-9", &ToolResultFormat::default());
+    assert_eq!(
+        other.tool_result_format("tu-9"),
+        Some(ToolResultFormat::new(ToolResultKind::Json))
+    );
+}
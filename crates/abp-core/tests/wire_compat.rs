@@ -685,6 +685,7 @@ fn receipt_hash_vector_10_with_verification() {
         git_diff: Some("+line".into()),
         git_status: Some("M file.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let empty_hash = receipt_hash(&deterministic_receipt("mock", Outcome::Complete)).unwrap();
     let ver_hash = receipt_hash(&r).unwrap();
@@ -747,6 +748,9 @@ fn work_order_json_roundtrip_stable() {
             env: BTreeMap::new(),
             max_budget_usd: Some(1.0),
             max_turns: Some(10),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
 
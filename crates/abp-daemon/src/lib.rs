@@ -37,7 +37,7 @@ use axum::{
     Json, Router,
     extract::ws::{Message, WebSocket, WebSocketUpgrade},
     extract::{Path as AxPath, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::sse::{Event as SseEvent, Sse},
     response::{IntoResponse, Response},
     routing::{get, post},
@@ -53,7 +53,7 @@ use thiserror::Error;
 use tokio::fs;
 use tokio::sync::RwLock;
 use tokio_stream::StreamExt;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
 // ---------------------------------------------------------------------------
@@ -426,6 +426,36 @@ impl IntoResponse for ApiError {
     }
 }
 
+/// Request header naming the caller, checked against the runtime's RBAC
+/// roles. Deployments with RBAC enabled should set it from an authenticating
+/// proxy rather than trust clients to send it.
+pub const IDENTITY_HEADER: &str = "x-abp-identity";
+
+/// The caller identity from [`IDENTITY_HEADER`], if present and non-empty.
+pub fn caller_identity(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(IDENTITY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Ask the runtime whether the caller may perform `permission`; `403` if not.
+fn authorize(
+    state: &AppState,
+    headers: &HeaderMap,
+    permission: abp_runtime::rbac::Permission,
+    resource: &str,
+) -> Result<Option<String>, ApiError> {
+    let identity = caller_identity(headers);
+    state
+        .runtime
+        .authorize(identity.as_deref(), permission, resource)
+        .map_err(|e| ApiError::new(StatusCode::FORBIDDEN, e.to_string()))?;
+    Ok(identity)
+}
+
 /// Build the Axum router with all daemon routes under the `/api/v1` prefix.
 ///
 /// Legacy (un-prefixed) routes from [`build_app`] are also included for
@@ -437,6 +467,7 @@ pub fn build_versioned_app(state: Arc<AppState>) -> Router {
         .route("/health", get(cmd_health))
         .route("/status/{run_id}", get(cmd_get_run))
         .route("/validate", post(cmd_validate_v1))
+        .route("/errors", get(cmd_errors))
         .with_state(state.clone());
 
     build_app(state).nest("/api/v1", v1)
@@ -451,6 +482,7 @@ pub fn build_app(state: Arc<AppState>) -> Router {
         .route("/backends", get(cmd_backends))
         .route("/capabilities", get(cmd_capabilities))
         .route("/config", get(cmd_config))
+        .route("/errors", get(cmd_errors))
         .route("/validate", post(cmd_validate))
         .route("/schema/{schema_type}", get(cmd_schema))
         .route("/run", post(cmd_run))
@@ -550,8 +582,26 @@ async fn cmd_capabilities(
 
 async fn cmd_run(
     State(state): State<Arc<AppState>>,
-    Json(req): Json<RunRequest>,
+    headers: HeaderMap,
+    Json(mut req): Json<RunRequest>,
 ) -> Result<Json<RunResponse>, ApiError> {
+    let identity = authorize(
+        &state,
+        &headers,
+        abp_runtime::rbac::Permission::Submit,
+        &format!("backend:{}", req.backend),
+    )?;
+
+    // Bill the run to the authenticated caller, not to whatever API key the
+    // request body names.
+    if let Some(identity) = identity {
+        req.work_order
+            .config
+            .submitter
+            .get_or_insert_with(Default::default)
+            .api_key_id = Some(identity);
+    }
+
     // Validate the work order before processing.
     if let Err(errors) = validation::RequestValidator::validate_work_order(&req.work_order) {
         return Err(ApiError::new(StatusCode::BAD_REQUEST, errors.join("; ")));
@@ -630,7 +680,16 @@ async fn cmd_run(
     }))
 }
 
-async fn cmd_list_runs(State(state): State<Arc<AppState>>) -> Json<Vec<Uuid>> {
+async fn cmd_list_runs(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<Uuid>>, ApiError> {
+    authorize(
+        &state,
+        &headers,
+        abp_runtime::rbac::Permission::ReadReceipts,
+        "runs",
+    )?;
     // Merge tracker runs with legacy receipt-only runs for backward compat.
     let mut ids: Vec<Uuid> = state.receipts.read().await.keys().cloned().collect();
     for (id, _) in state.run_tracker.list_runs().await {
@@ -639,13 +698,21 @@ async fn cmd_list_runs(State(state): State<Arc<AppState>>) -> Json<Vec<Uuid>> {
         }
     }
     ids.sort_unstable();
-    Json(ids)
+    Ok(Json(ids))
 }
 
 async fn cmd_get_run(
     AxPath(run_id): AxPath<Uuid>,
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
 ) -> Result<Json<serde_json::Value>, ApiError> {
+    // A completed status carries the receipt, so this is a receipt read.
+    authorize(
+        &state,
+        &headers,
+        abp_runtime::rbac::Permission::ReadReceipts,
+        &format!("run:{run_id}"),
+    )?;
     // Prefer tracker status when available.
     if let Some(status) = state.run_tracker.get_run_status(run_id).await {
         return Ok(Json(json!({
@@ -669,7 +736,14 @@ async fn cmd_get_run(
 async fn cmd_delete_run(
     AxPath(run_id): AxPath<Uuid>,
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
 ) -> Result<Json<serde_json::Value>, ApiError> {
+    authorize(
+        &state,
+        &headers,
+        abp_runtime::rbac::Permission::Cancel,
+        &format!("run:{run_id}"),
+    )?;
     match state.run_tracker.remove_run(run_id).await {
         Ok(_status) => {
             // Also remove from receipts cache if present.
@@ -691,7 +765,14 @@ async fn cmd_delete_run(
 async fn cmd_get_run_receipt(
     AxPath(run_id): AxPath<Uuid>,
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
 ) -> Result<Json<Receipt>, ApiError> {
+    authorize(
+        &state,
+        &headers,
+        abp_runtime::rbac::Permission::ReadReceipts,
+        &format!("run:{run_id}"),
+    )?;
     // Check tracker first.
     if let Some(status) = state.run_tracker.get_run_status(run_id).await {
         if let RunStatus::Completed { receipt } = status {
@@ -712,13 +793,31 @@ async fn cmd_get_run_receipt(
 async fn cmd_cancel_run(
     AxPath(run_id): AxPath<Uuid>,
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
 ) -> Result<Json<serde_json::Value>, ApiError> {
+    let identity = authorize(
+        &state,
+        &headers,
+        abp_runtime::rbac::Permission::Cancel,
+        &format!("run:{run_id}"),
+    )?;
     state
         .run_tracker
         .cancel_run(run_id)
         .await
         .map_err(|e| ApiError::new(StatusCode::CONFLICT, e.to_string()))?;
     info!(run_id = %run_id, "run cancelled");
+    if let Some(log) = state.runtime.audit_log()
+        && let Err(e) = log.record(
+            identity.as_deref(),
+            abp_runtime::audit::AuditAction::RunCancelled {
+                run_id,
+                reason: Some("cancelled via daemon API".into()),
+            },
+        )
+    {
+        warn!(run_id = %run_id, error = %e, "failed to append audit log entry");
+    }
     Ok(Json(json!({ "run_id": run_id, "status": "cancelled" })))
 }
 
@@ -730,6 +829,11 @@ async fn cmd_config(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     }))
 }
 
+/// The full error code catalogue, so clients need not copy code strings.
+async fn cmd_errors() -> Json<Vec<abp_error_taxonomy::catalog::ErrorCodeEntry>> {
+    Json(abp_error_taxonomy::catalog::catalog())
+}
+
 async fn cmd_validate(
     State(state): State<Arc<AppState>>,
     Json(req): Json<RunRequest>,
@@ -826,7 +930,14 @@ async fn handle_ws(mut socket: WebSocket) {
 async fn cmd_list_receipts(
     Query(q): Query<ReceiptListQuery>,
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
 ) -> Result<Json<Vec<Uuid>>, ApiError> {
+    authorize(
+        &state,
+        &headers,
+        abp_runtime::rbac::Permission::ReadReceipts,
+        "receipts",
+    )?;
     let mut out = state
         .receipts
         .read()
@@ -851,7 +962,14 @@ async fn cmd_list_receipts(
 async fn cmd_get_receipt(
     AxPath(run_id): AxPath<Uuid>,
     State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
 ) -> Result<Json<Receipt>, ApiError> {
+    authorize(
+        &state,
+        &headers,
+        abp_runtime::rbac::Permission::ReadReceipts,
+        &format!("run:{run_id}"),
+    )?;
     if let Some(receipt) = state.receipts.read().await.get(&run_id).cloned() {
         return Ok(Json(receipt));
     }
@@ -156,9 +156,37 @@ fn build_runtime(host_root: &Path, config: &abp_config::BackplaneConfig) -> Resu
                 spec.args = args.clone();
                 runtime.register_backend(name, SidecarBackend::new(spec));
             }
+            abp_config::BackendEntry::Grpc {
+                endpoint,
+                timeout_secs,
+            } => {
+                let mut backend = abp_integrations::GrpcBackend::new(endpoint);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                runtime.register_backend(name, backend);
+            }
+            abp_config::BackendEntry::WebSocket { url, timeout_secs } => {
+                let mut backend = abp_integrations::WsBackend::new(url);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                runtime.register_backend(name, backend);
+            }
+            abp_config::BackendEntry::Ipc { path, timeout_secs } => {
+                let mut backend = abp_integrations::IpcBackend::new(path);
+                if let Some(secs) = timeout_secs {
+                    backend = backend.with_connect_timeout(std::time::Duration::from_secs(*secs));
+                }
+                runtime.register_backend(name, backend);
+            }
         }
     }
 
+    if let Some(rbac) = &config.rbac {
+        runtime = runtime.with_rbac(rbac.clone());
+    }
+
     Ok(runtime)
 }
 
@@ -368,3 +368,27 @@ async fn metrics_content_type_is_json() {
         "expected application/json, got: {ct}"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Error code catalogue
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn errors_endpoint_serves_the_full_catalogue() {
+    let tmp = tempfile::tempdir().unwrap();
+    let app = abp_daemon::build_versioned_app(test_state(tmp.path()));
+
+    for uri in ["/errors", "/api/v1/errors"] {
+        let (status, json) = get_json(app.clone(), uri).await;
+        assert_eq!(status, StatusCode::OK);
+        let entries = json.as_array().expect("catalogue is an array");
+        assert_eq!(entries.len(), abp_error_taxonomy::ErrorCode::ALL.len());
+        let not_found = entries
+            .iter()
+            .find(|e| e["code"] == "backend_not_found")
+            .unwrap();
+        assert_eq!(not_found["category"], "backend");
+        assert_eq!(not_found["http_status"], 404);
+        assert_eq!(not_found["retryable"], false);
+    }
+}
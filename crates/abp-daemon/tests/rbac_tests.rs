@@ -0,0 +1,238 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Role-based access control on the daemon HTTP API.
+
+use abp_core::submitter::Submitter;
+use abp_core::{WorkOrder, WorkOrderBuilder, WorkspaceMode};
+use abp_daemon::{AppState, IDENTITY_HEADER, RunRequest, RunTracker, build_app};
+use abp_integrations::MockBackend;
+use abp_runtime::Runtime;
+use abp_runtime::audit::{AuditAction, AuditLog};
+use abp_runtime::rbac::{Permission, RbacConfig};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+fn rbac() -> RbacConfig {
+    use Permission::*;
+    RbacConfig {
+        roles: [
+            ("operator".into(), [Submit, Cancel, ReadReceipts].into()),
+            ("auditor".into(), [ReadReceipts].into()),
+        ]
+        .into(),
+        bindings: [
+            ("alice".into(), vec!["operator".into()]),
+            ("bob".into(), vec!["auditor".into()]),
+        ]
+        .into(),
+        anonymous_roles: vec![],
+    }
+}
+
+fn state(dir: &std::path::Path) -> Arc<AppState> {
+    let mut runtime = Runtime::new()
+        .with_rbac(rbac())
+        .with_audit_log(AuditLog::in_memory());
+    runtime.register_backend("mock", MockBackend);
+    Arc::new(AppState {
+        runtime: Arc::new(runtime),
+        receipts: Arc::new(RwLock::new(HashMap::new())),
+        receipts_dir: dir.to_path_buf(),
+        run_tracker: RunTracker::new(),
+    })
+}
+
+fn work_order() -> WorkOrder {
+    WorkOrderBuilder::new("rbac task")
+        .root(".")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build()
+}
+
+fn run_request(identity: Option<&str>) -> Request<Body> {
+    let body = serde_json::to_vec(&RunRequest {
+        backend: "mock".into(),
+        work_order: work_order(),
+    })
+    .unwrap();
+    let mut req = Request::builder()
+        .method("POST")
+        .uri("/run")
+        .header("content-type", "application/json");
+    if let Some(id) = identity {
+        req = req.header(IDENTITY_HEADER, id);
+    }
+    req.body(Body::from(body)).unwrap()
+}
+
+fn get(uri: &str, identity: &str) -> Request<Body> {
+    Request::builder()
+        .uri(uri)
+        .header(IDENTITY_HEADER, identity)
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn submit_requires_the_submit_permission() {
+    let tmp = tempfile::tempdir().unwrap();
+    let state = state(tmp.path());
+    let app = build_app(state.clone());
+
+    let denied = app.clone().oneshot(run_request(Some("bob"))).await.unwrap();
+    assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+    let anonymous = app.clone().oneshot(run_request(None)).await.unwrap();
+    assert_eq!(anonymous.status(), StatusCode::FORBIDDEN);
+    let allowed = app.oneshot(run_request(Some("alice"))).await.unwrap();
+    assert_eq!(allowed.status(), StatusCode::OK);
+    assert_eq!(state.receipts.read().await.len(), 1);
+}
+
+#[tokio::test]
+async fn runs_are_billed_to_the_authenticated_caller() {
+    let tmp = tempfile::tempdir().unwrap();
+    let app = build_app(state(tmp.path()));
+    let mut wo = work_order();
+    wo.config.submitter = Some(Submitter::new().tenant("acme").api_key_id("someone-else"));
+    let body = serde_json::to_vec(&RunRequest {
+        backend: "mock".into(),
+        work_order: wo,
+    })
+    .unwrap();
+    let req = Request::builder()
+        .method("POST")
+        .uri("/run")
+        .header("content-type", "application/json")
+        .header(IDENTITY_HEADER, "alice")
+        .body(Body::from(body))
+        .unwrap();
+
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let run: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(run["receipt"]["usage_raw"]["api_key_id"], "alice");
+    assert_eq!(run["receipt"]["usage_raw"]["tenant"], "acme");
+}
+
+#[tokio::test]
+async fn receipts_are_readable_by_auditors_only() {
+    let tmp = tempfile::tempdir().unwrap();
+    let app = build_app(state(tmp.path()));
+
+    let listed = app.clone().oneshot(get("/receipts", "bob")).await.unwrap();
+    assert_eq!(listed.status(), StatusCode::OK);
+    let missing = app
+        .clone()
+        .oneshot(get(&format!("/receipts/{}", Uuid::new_v4()), "bob"))
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    let stranger = app.oneshot(get("/receipts", "mallory")).await.unwrap();
+    assert_eq!(stranger.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn runs_and_their_status_need_read_receipts() {
+    let tmp = tempfile::tempdir().unwrap();
+    let state = state(tmp.path());
+    let app = build_app(state.clone());
+    app.clone()
+        .oneshot(run_request(Some("alice")))
+        .await
+        .unwrap();
+    let run_id = *state.receipts.read().await.keys().next().unwrap();
+
+    let listed = app.clone().oneshot(get("/runs", "bob")).await.unwrap();
+    assert_eq!(listed.status(), StatusCode::OK);
+    let status = app
+        .clone()
+        .oneshot(get(&format!("/runs/{run_id}"), "bob"))
+        .await
+        .unwrap();
+    assert_eq!(status.status(), StatusCode::OK);
+
+    let listed = app.clone().oneshot(get("/runs", "mallory")).await.unwrap();
+    assert_eq!(listed.status(), StatusCode::FORBIDDEN);
+    let status = app
+        .oneshot(get(&format!("/runs/{run_id}"), "mallory"))
+        .await
+        .unwrap();
+    assert_eq!(status.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn delete_needs_the_cancel_permission() {
+    let tmp = tempfile::tempdir().unwrap();
+    let state = state(tmp.path());
+    let app = build_app(state.clone());
+    app.clone()
+        .oneshot(run_request(Some("alice")))
+        .await
+        .unwrap();
+    let run_id = *state.receipts.read().await.keys().next().unwrap();
+    let delete = |identity: &str| {
+        Request::builder()
+            .method("DELETE")
+            .uri(format!("/runs/{run_id}"))
+            .header(IDENTITY_HEADER, identity)
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let denied = app.clone().oneshot(delete("bob")).await.unwrap();
+    assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+    assert!(state.receipts.read().await.contains_key(&run_id));
+    let allowed = app.oneshot(delete("alice")).await.unwrap();
+    assert_eq!(allowed.status(), StatusCode::OK);
+    assert!(!state.receipts.read().await.contains_key(&run_id));
+}
+
+#[tokio::test]
+async fn cancel_is_checked_before_the_run_is_looked_up() {
+    let tmp = tempfile::tempdir().unwrap();
+    let app = build_app(state(tmp.path()));
+    let req = Request::builder()
+        .method("POST")
+        .uri(format!("/runs/{}/cancel", Uuid::new_v4()))
+        .header(IDENTITY_HEADER, "bob")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn decisions_are_audited() {
+    let tmp = tempfile::tempdir().unwrap();
+    let state = state(tmp.path());
+    let app = build_app(state.clone());
+    app.clone().oneshot(run_request(Some("bob"))).await.unwrap();
+    app.oneshot(run_request(Some("alice"))).await.unwrap();
+
+    let entries = state.runtime.audit_log().unwrap().entries();
+    let checks: Vec<_> = entries
+        .iter()
+        .filter_map(|e| match &e.action {
+            AuditAction::AccessChecked {
+                permission,
+                resource,
+                allowed,
+            } => Some((e.actor.as_deref(), *permission, resource.as_str(), *allowed)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        checks,
+        [
+            (Some("bob"), Permission::Submit, "backend:mock", false),
+            (Some("alice"), Permission::Submit, "backend:mock", true),
+        ]
+    );
+}
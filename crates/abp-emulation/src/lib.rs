@@ -9,8 +9,10 @@
 #![warn(missing_docs)]
 
 pub mod function_calling;
+pub mod response_language;
 pub mod strategies;
 pub mod streaming_emulator;
+pub mod structured_output;
 pub mod tool_emulator;
 pub mod vision_emulator;
 
@@ -96,6 +98,18 @@ pub fn emulate_stop_sequences() -> EmulationStrategy {
     }
 }
 
+/// Pre-configured strategy: ask for the requested response language in the
+/// system prompt.
+///
+/// [`ResponseLanguageEmulator`](response_language::ResponseLanguageEmulator)
+/// produces the instruction for a specific language.
+#[must_use]
+pub fn emulate_response_language() -> EmulationStrategy {
+    EmulationStrategy::SystemPromptInjection {
+        prompt: "Always respond in the requested language.".into(),
+    }
+}
+
 // ── Fidelity ────────────────────────────────────────────────────────────
 
 /// How a capability is fulfilled — natively by the backend or via emulation.
@@ -167,6 +181,7 @@ impl EmulationConfig {
 /// - `ExtendedThinking` → system-prompt injection
 /// - `StructuredOutputJsonSchema` → post-processing
 /// - `CodeExecution` → disabled
+/// - `ResponseLanguage` → system-prompt injection
 /// - Everything else → disabled with a generic reason
 #[must_use]
 pub fn default_strategy(capability: &Capability) -> EmulationStrategy {
@@ -182,6 +197,7 @@ pub fn default_strategy(capability: &Capability) -> EmulationStrategy {
         },
         Capability::ImageInput => emulate_image_input(),
         Capability::StopSequences => emulate_stop_sequences(),
+        Capability::ResponseLanguage => emulate_response_language(),
         other => EmulationStrategy::Disabled {
             reason: format!("No emulation available for {other:?}"),
         },
@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Response-language emulation for backends that cannot be told natively
+//! which language to answer in.
+//!
+//! [`ResponseLanguageEmulator`] turns a BCP 47 tag into a system prompt
+//! instruction. It is applied for every backend that does not declare
+//! native [`Capability::ResponseLanguage`](abp_core::Capability::ResponseLanguage)
+//! support, so the language can be set once for a deployment regardless of
+//! which provider ends up serving the run.
+
+use abp_core::ir::IrConversation;
+use serde::{Deserialize, Serialize};
+
+use crate::{EmulationStrategy, inject_system_prompt};
+
+/// English names of common primary language subtags, for readable prompts.
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("ar", "Arabic"),
+    ("cs", "Czech"),
+    ("da", "Danish"),
+    ("de", "German"),
+    ("el", "Greek"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fi", "Finnish"),
+    ("fr", "French"),
+    ("he", "Hebrew"),
+    ("hi", "Hindi"),
+    ("id", "Indonesian"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("nl", "Dutch"),
+    ("no", "Norwegian"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ru", "Russian"),
+    ("sv", "Swedish"),
+    ("th", "Thai"),
+    ("tr", "Turkish"),
+    ("uk", "Ukrainian"),
+    ("vi", "Vietnamese"),
+    ("zh", "Chinese"),
+];
+
+/// Emulates `ResponseLanguage` by instructing the model through the system
+/// prompt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseLanguageEmulator {
+    tag: String,
+}
+
+impl ResponseLanguageEmulator {
+    /// Create an emulator for the BCP 47 `tag`, e.g. `fr-CA`.
+    ///
+    /// The tag is used as given; normalize it first (see
+    /// `abp_backend_core::normalize_language_tag`).
+    #[must_use]
+    pub fn new(tag: impl Into<String>) -> Self {
+        Self { tag: tag.into() }
+    }
+
+    /// The requested language tag.
+    #[must_use]
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// The language's English name and tag, e.g. `French (fr-CA)`; languages
+    /// without a known name are described by their tag.
+    #[must_use]
+    pub fn language(&self) -> String {
+        let primary = self.tag.split('-').next().unwrap_or_default();
+        match LANGUAGE_NAMES.iter().find(|(code, _)| *code == primary) {
+            Some((_, name)) => format!("{name} ({})", self.tag),
+            None => format!("the language with BCP 47 tag `{}`", self.tag),
+        }
+    }
+
+    /// Instructions asking the model to answer in the language.
+    #[must_use]
+    pub fn instructions(&self) -> String {
+        format!(
+            "Always respond in {}, whatever language the request or any quoted \
+             material is written in. Keep code, identifiers, and quoted text unchanged.",
+            self.language()
+        )
+    }
+
+    /// The strategy to record in the emulation report.
+    #[must_use]
+    pub fn strategy(&self) -> EmulationStrategy {
+        EmulationStrategy::SystemPromptInjection {
+            prompt: self.instructions(),
+        }
+    }
+
+    /// Inject the instructions into a conversation's system prompt.
+    pub fn inject(&self, conv: &mut IrConversation) {
+        inject_system_prompt(conv, &self.instructions());
+    }
+
+    /// Append the instructions to a plain-text task.
+    #[must_use]
+    pub fn instruct_task(&self, task: &str) -> String {
+        format!("{task}\n\n{}", self.instructions())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::ir::{IrMessage, IrRole};
+
+    #[test]
+    fn known_languages_are_named() {
+        assert_eq!(
+            ResponseLanguageEmulator::new("fr-CA").language(),
+            "French (fr-CA)"
+        );
+        assert_eq!(
+            ResponseLanguageEmulator::new("haw").language(),
+            "the language with BCP 47 tag `haw`"
+        );
+    }
+
+    #[test]
+    fn inject_extends_or_creates_the_system_prompt() {
+        let emu = ResponseLanguageEmulator::new("de");
+        let mut conv = IrConversation::new()
+            .push(IrMessage::text(IrRole::System, "Be brief."))
+            .push(IrMessage::text(IrRole::User, "Hello"));
+        emu.inject(&mut conv);
+        assert_eq!(conv.messages.len(), 2);
+        let system = conv.messages[0].text_content();
+        assert!(system.starts_with("Be brief."));
+        assert!(system.contains("German (de)"));
+
+        let mut conv = IrConversation::new().push(IrMessage::text(IrRole::User, "Hello"));
+        emu.inject(&mut conv);
+        assert_eq!(conv.messages[0].role, IrRole::System);
+        assert_eq!(conv.messages[0].text_content(), emu.instructions());
+    }
+
+    #[test]
+    fn strategy_carries_the_instructions() {
+        let emu = ResponseLanguageEmulator::new("ja");
+        assert_eq!(
+            emu.strategy(),
+            EmulationStrategy::SystemPromptInjection {
+                prompt: emu.instructions()
+            }
+        );
+        assert!(
+            emu.instruct_task("Fix the bug")
+                .starts_with("Fix the bug\n\n")
+        );
+    }
+}
@@ -0,0 +1,352 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Structured-output emulation for backends without JSON schema support.
+//!
+//! [`StructuredOutputEmulator`] asks for schema-conforming JSON through the
+//! prompt, then recovers the JSON value from whatever the model produced:
+//! Markdown fences and surrounding prose are dropped and trailing commas
+//! removed before the value is validated against the schema. Every repair
+//! and violation is kept in the [`StructuredOutputResult`] so it can be
+//! reported as a fidelity note rather than silently papered over.
+
+use abp_core::ir::IrConversation;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::inject_system_prompt;
+
+/// Outcome of post-processing a model response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredOutputResult {
+    /// The recovered JSON value, if any could be parsed.
+    pub value: Option<Value>,
+    /// Repairs applied to the raw text before it parsed.
+    pub repairs: Vec<String>,
+    /// Schema violations of the recovered value (or why none was found).
+    pub violations: Vec<String>,
+}
+
+impl StructuredOutputResult {
+    /// Returns `true` if a value was recovered and it matches the schema.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.value.is_some() && self.violations.is_empty()
+    }
+
+    /// Notes describing how far the result is from native structured output.
+    #[must_use]
+    pub fn fidelity_notes(&self) -> Vec<String> {
+        let mut notes = vec![
+            "JSON schema requested through prompt instructions; not enforced by the backend"
+                .to_string(),
+        ];
+        notes.extend(
+            self.repairs
+                .iter()
+                .map(|r| format!("model output repaired: {r}")),
+        );
+        notes.extend(
+            self.violations
+                .iter()
+                .map(|v| format!("output does not match the schema: {v}")),
+        );
+        notes
+    }
+}
+
+/// Emulates `StructuredOutputJsonSchema` by prompt injection plus
+/// parse-and-repair of the response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredOutputEmulator {
+    name: String,
+    schema: Value,
+}
+
+impl StructuredOutputEmulator {
+    /// Create an emulator for `schema`, reported under `name`.
+    #[must_use]
+    pub fn new(name: impl Into<String>, schema: Value) -> Self {
+        Self {
+            name: name.into(),
+            schema,
+        }
+    }
+
+    /// Build from an OpenAI-style `response_format` value.
+    ///
+    /// `json_schema` formats use their embedded schema; `json_object` only
+    /// requires an object. Returns `None` for `text` and anything else.
+    #[must_use]
+    pub fn from_response_format(format: &Value) -> Option<Self> {
+        match format.get("type").and_then(Value::as_str)? {
+            "json_object" => Some(Self::new("json_object", json!({"type": "object"}))),
+            "json_schema" => {
+                let spec = format.get("json_schema")?;
+                let name = spec
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or("json_schema");
+                Some(Self::new(name, spec.get("schema")?.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Name of the schema.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The JSON Schema the output must match.
+    #[must_use]
+    pub fn schema(&self) -> &Value {
+        &self.schema
+    }
+
+    // ── Prompt injection ───────────────────────────────────────────────
+
+    /// Instructions asking the model for JSON matching the schema.
+    #[must_use]
+    pub fn instructions(&self) -> String {
+        let schema =
+            serde_json::to_string_pretty(&self.schema).unwrap_or_else(|_| self.schema.to_string());
+        format!(
+            "Respond with a single JSON value that matches the JSON Schema `{}` below. \
+             Do not wrap it in Markdown or add any text before or after it.\n\n{schema}",
+            self.name
+        )
+    }
+
+    /// Inject the instructions into a conversation's system prompt.
+    pub fn inject(&self, conv: &mut IrConversation) {
+        inject_system_prompt(conv, &self.instructions());
+    }
+
+    /// Append the instructions to a plain-text task.
+    #[must_use]
+    pub fn instruct_task(&self, task: &str) -> String {
+        format!("{task}\n\n{}", self.instructions())
+    }
+
+    // ── Response processing ────────────────────────────────────────────
+
+    /// Recover the JSON value from `text` and validate it against the schema.
+    #[must_use]
+    pub fn process(&self, text: &str) -> StructuredOutputResult {
+        let (value, repairs) = match recover_json(text) {
+            Ok(found) => found,
+            Err(repairs) => {
+                return StructuredOutputResult {
+                    value: None,
+                    repairs,
+                    violations: vec!["no JSON value found in the output".into()],
+                };
+            }
+        };
+        let violations = match jsonschema::validator_for(&self.schema) {
+            Ok(validator) => validator
+                .iter_errors(&value)
+                .map(|e| {
+                    let path = e.instance_path.to_string();
+                    if path.is_empty() {
+                        e.to_string()
+                    } else {
+                        format!("{path}: {e}")
+                    }
+                })
+                .collect(),
+            Err(e) => vec![format!("schema `{}` does not compile: {e}", self.name)],
+        };
+        StructuredOutputResult {
+            value: Some(value),
+            repairs,
+            violations,
+        }
+    }
+}
+
+/// Parse `text` as JSON, repairing the usual model mistakes on the way.
+///
+/// On failure returns the repairs that were attempted.
+fn recover_json(text: &str) -> Result<(Value, Vec<String>), Vec<String>> {
+    let mut repairs = Vec::new();
+    let mut candidate = text.trim();
+
+    if let Some(inner) = fenced_block(candidate) {
+        candidate = inner.trim();
+        repairs.push("removed Markdown code fence".to_string());
+    }
+    if let Ok(value) = serde_json::from_str(candidate) {
+        return Ok((value, repairs));
+    }
+
+    if let Some(start) = candidate.find(['{', '['])
+        && let Some(end) = closing_bracket(candidate, start)
+        && (start, end + 1) != (0, candidate.len())
+    {
+        candidate = &candidate[start..=end];
+        repairs.push("removed text around the JSON value".to_string());
+        if let Ok(value) = serde_json::from_str(candidate) {
+            return Ok((value, repairs));
+        }
+    }
+
+    let without_commas = strip_trailing_commas(candidate);
+    if without_commas != candidate
+        && let Ok(value) = serde_json::from_str(&without_commas)
+    {
+        repairs.push("removed trailing commas".to_string());
+        return Ok((value, repairs));
+    }
+    Err(repairs)
+}
+
+/// The body of the first ```` ``` ```` block in `text`, if there is one.
+fn fenced_block(text: &str) -> Option<&str> {
+    let start = text.find("```")?;
+    let after = &text[start + 3..];
+    // Skip the language tag on the opening line.
+    let body = &after[after.find('\n')? + 1..];
+    Some(&body[..body.find("```")?])
+}
+
+/// Index of the bracket closing the one at `start`, ignoring brackets in
+/// string literals.
+fn closing_bracket(text: &str, start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text[start..].char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Drop commas that directly precede a closing bracket, outside strings.
+fn strip_trailing_commas(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' && text[i + 1..].trim_start().starts_with(['}', ']']) {
+            continue;
+        }
+        out.push(c);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::ir::{IrMessage, IrRole};
+
+    fn colour() -> StructuredOutputEmulator {
+        StructuredOutputEmulator::new(
+            "colour",
+            json!({
+                "type": "object",
+                "properties": {"name": {"type": "string"}},
+                "required": ["name"]
+            }),
+        )
+    }
+
+    #[test]
+    fn response_format_types() {
+        let schema = json!({
+            "type": "json_schema",
+            "json_schema": {"name": "colour", "schema": {"type": "object"}}
+        });
+        let emu = StructuredOutputEmulator::from_response_format(&schema).unwrap();
+        assert_eq!(emu.name(), "colour");
+
+        let object = json!({"type": "json_object"});
+        let emu = StructuredOutputEmulator::from_response_format(&object).unwrap();
+        assert_eq!(emu.schema(), &json!({"type": "object"}));
+
+        assert!(StructuredOutputEmulator::from_response_format(&json!({"type": "text"})).is_none());
+    }
+
+    #[test]
+    fn inject_adds_schema_to_system_prompt() {
+        let mut conv = IrConversation::new()
+            .push(IrMessage::text(IrRole::System, "Be brief."))
+            .push(IrMessage::text(IrRole::User, "Name a colour"));
+        colour().inject(&mut conv);
+        assert_eq!(conv.messages.len(), 2);
+        let system = conv.messages[0].text_content();
+        assert!(system.starts_with("Be brief."));
+        assert!(system.contains("`colour`"));
+        assert!(system.contains("\"required\""));
+    }
+
+    #[test]
+    fn clean_output_needs_no_repair() {
+        let result = colour().process(r#"{"name": "red"}"#);
+        assert!(result.is_valid());
+        assert!(result.repairs.is_empty());
+        assert_eq!(result.fidelity_notes().len(), 1);
+    }
+
+    #[test]
+    fn fenced_output_with_prose_and_trailing_comma_is_repaired() {
+        let text =
+            "Sure! Here it is:\n```json\n{\"name\": \"red\", \"tags\": [\"warm\",],}\n```\nEnjoy.";
+        let result = colour().process(text);
+        assert!(result.is_valid(), "{result:?}");
+        assert_eq!(result.value.unwrap()["tags"], json!(["warm"]));
+        assert_eq!(
+            result.repairs,
+            ["removed Markdown code fence", "removed trailing commas"]
+        );
+    }
+
+    #[test]
+    fn surrounding_prose_is_dropped() {
+        let result = colour().process(r#"The answer is {"name": "a } in a string"} as requested."#);
+        assert!(result.is_valid());
+        assert_eq!(result.value.unwrap()["name"], "a } in a string");
+        assert_eq!(result.repairs, ["removed text around the JSON value"]);
+    }
+
+    #[test]
+    fn violations_become_fidelity_notes() {
+        let result = colour().process(r#"{"colour": "red"}"#);
+        assert!(!result.is_valid());
+        let notes = result.fidelity_notes();
+        assert!(notes[1].contains("\"name\" is a required property"));
+
+        let result = colour().process("I don't know.");
+        assert!(result.value.is_none());
+        assert!(result.fidelity_notes()[1].contains("no JSON value found"));
+    }
+}
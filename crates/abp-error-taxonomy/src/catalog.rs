@@ -0,0 +1,72 @@
+//! Machine-readable catalogue of every [`ErrorCode`].
+//!
+//! Non-Rust clients build their error handling against this table instead
+//! of copying code strings by hand. The daemon serves it at `GET /errors`,
+//! and a generated copy is checked in at `contracts/error_codes.json`
+//! (regenerate with `cargo xtask error-codes`).
+//!
+//! # Examples
+//!
+//! ```
+//! use abp_error_taxonomy::ErrorCode;
+//! use abp_error_taxonomy::catalog::catalog;
+//!
+//! let entries = catalog();
+//! assert_eq!(entries.len(), ErrorCode::ALL.len());
+//! let timeout = entries.iter().find(|e| e.code == "backend_timeout").unwrap();
+//! assert!(timeout.retryable);
+//! assert_eq!(timeout.http_status, 504);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::ErrorCode;
+use crate::docs::error_code_doc;
+
+/// One row of the catalogue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorCodeEntry {
+    /// Stable wire string, e.g. `"backend_timeout"`.
+    pub code: String,
+    /// Category wire string, e.g. `"backend"`.
+    pub category: String,
+    /// Short human-readable message.
+    pub message: String,
+    /// Longer explanation of when the code is raised.
+    pub description: String,
+    /// Whether retrying the operation may succeed.
+    pub retryable: bool,
+    /// HTTP status a server answers with for this code.
+    pub http_status: u16,
+}
+
+impl ErrorCodeEntry {
+    /// The catalogue row for `code`.
+    pub fn new(code: ErrorCode) -> Self {
+        Self {
+            code: code.as_str().to_string(),
+            category: code.category().to_string(),
+            message: code.message().to_string(),
+            description: error_code_doc(&code).description,
+            retryable: code.is_retryable(),
+            http_status: code.http_status(),
+        }
+    }
+}
+
+/// Every error code, in declaration order.
+pub fn catalog() -> Vec<ErrorCodeEntry> {
+    ErrorCode::ALL
+        .iter()
+        .copied()
+        .map(ErrorCodeEntry::new)
+        .collect()
+}
+
+/// The catalogue as pretty-printed JSON, as checked in under `contracts/`.
+pub fn catalog_json() -> String {
+    let mut json =
+        serde_json::to_string_pretty(&catalog()).expect("catalogue entries serialise to JSON");
+    json.push('\n');
+    json
+}
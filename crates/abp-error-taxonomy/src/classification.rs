@@ -296,6 +296,7 @@ impl ErrorClassifier {
                 ErrorSeverity::Retriable,
                 ClassificationCategory::ServerError,
             ),
+            ErrorCode::QuotaExceeded => (ErrorSeverity::Fatal, ClassificationCategory::RateLimit),
 
             // Stream
             ErrorCode::StreamClosed => (
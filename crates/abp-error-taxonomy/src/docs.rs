@@ -412,6 +412,17 @@ pub fn error_code_doc(code: &ErrorCode) -> ErrorCodeDoc {
                 .into(),
             see_also: vec![ErrorCode::BackendUnavailable, ErrorCode::RateLimitExceeded],
         },
+        ErrorCode::QuotaExceeded => ErrorCodeDoc {
+            code: *code,
+            description: "A tenant or API key has used up its token or spend \
+                          quota for the current daily or monthly window. \
+                          Requests are rejected before dispatch until the \
+                          window resets or the quota is raised."
+                .into(),
+            example: r#"AbpError::new(ErrorCode::QuotaExceeded, "tenant acme exceeded monthly token quota")"#
+                .into(),
+            see_also: vec![ErrorCode::RateLimitExceeded],
+        },
 
         // -- Stream ---------------------------------------------------------
         ErrorCode::StreamClosed => ErrorCodeDoc {
@@ -538,6 +549,7 @@ mod tests {
         ErrorCode::ConfigInvalid,
         ErrorCode::RateLimitExceeded,
         ErrorCode::CircuitBreakerOpen,
+        ErrorCode::QuotaExceeded,
         ErrorCode::StreamClosed,
         ErrorCode::ReceiptStoreFailed,
         ErrorCode::ValidationFailed,
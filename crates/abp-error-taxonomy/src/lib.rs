@@ -11,6 +11,7 @@ pub use classification::{
     RecoverySuggestion,
 };
 
+pub mod catalog;
 pub mod context;
 pub mod docs;
 pub mod mapping;
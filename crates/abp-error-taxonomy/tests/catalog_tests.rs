@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the machine-readable error code catalogue.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use abp_error_taxonomy::ErrorCode;
+use abp_error_taxonomy::catalog::{ErrorCodeEntry, catalog, catalog_json};
+
+#[test]
+fn checked_in_catalogue_is_up_to_date() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../contracts/error_codes.json");
+    let on_disk = std::fs::read_to_string(&path).expect("contracts/error_codes.json exists");
+    assert_eq!(
+        on_disk,
+        catalog_json(),
+        "contracts/error_codes.json is stale; run `cargo xtask error-codes`"
+    );
+}
+
+#[test]
+fn catalogue_lists_every_code_once_with_its_wire_strings() {
+    let entries = catalog();
+    let codes: HashSet<_> = entries.iter().map(|e| e.code.as_str()).collect();
+    assert_eq!(codes.len(), ErrorCode::ALL.len());
+
+    for (entry, code) in entries.iter().zip(ErrorCode::ALL) {
+        assert_eq!(
+            serde_json::to_value(code).unwrap(),
+            serde_json::json!(entry.code)
+        );
+        assert_eq!(
+            serde_json::to_value(code.category()).unwrap(),
+            serde_json::json!(entry.category)
+        );
+        assert!(!entry.description.is_empty(), "{}", entry.code);
+    }
+}
+
+#[test]
+fn entries_round_trip_through_json() {
+    let entries = catalog();
+    let back: Vec<ErrorCodeEntry> = serde_json::from_str(&catalog_json()).unwrap();
+    assert_eq!(back, entries);
+}
@@ -98,7 +98,8 @@ pub fn categorize(code: ErrorCode) -> RecoveryCategory {
         | ErrorCode::WorkspaceStagingFailed
         | ErrorCode::ExecutionToolFailed
         | ErrorCode::ExecutionWorkspaceError
-        | ErrorCode::ReceiptStoreFailed => RecoveryCategory::ResourceExhausted,
+        | ErrorCode::ReceiptStoreFailed
+        | ErrorCode::QuotaExceeded => RecoveryCategory::ResourceExhausted,
 
         ErrorCode::RateLimitExceeded | ErrorCode::CircuitBreakerOpen => RecoveryCategory::RateLimit,
 
@@ -314,6 +314,11 @@ fn diagnostic_template(code: ErrorCode) -> (&'static str, &'static str, &'static
             "Wait for the circuit breaker to transition to half-open; check backend health",
             &[ErrorCode::BackendUnavailable, ErrorCode::RateLimitExceeded],
         ),
+        ErrorCode::QuotaExceeded => (
+            "The tenant or API key used up its token or spend quota for the current day or month",
+            "Wait for the quota window to reset or raise the quota for this tenant",
+            &[ErrorCode::RateLimitExceeded],
+        ),
 
         // -- Stream --
         ErrorCode::StreamClosed => (
@@ -500,6 +505,7 @@ mod tests {
             ErrorCode::ConfigInvalid,
             ErrorCode::RateLimitExceeded,
             ErrorCode::CircuitBreakerOpen,
+            ErrorCode::QuotaExceeded,
             ErrorCode::StreamClosed,
             ErrorCode::ValidationFailed,
             ErrorCode::SidecarSpawnFailed,
@@ -277,6 +277,8 @@ pub enum ErrorCode {
     RateLimitExceeded,
     /// Circuit breaker is open for the target backend.
     CircuitBreakerOpen,
+    /// A tenant or API key has used up its daily or monthly quota.
+    QuotaExceeded,
 
     // -- Stream --
     /// Event stream closed prematurely (all receivers dropped).
@@ -306,6 +308,57 @@ pub enum ErrorCode {
 }
 
 impl ErrorCode {
+    /// Every code, in declaration order.
+    ///
+    /// Lets tooling publish the whole taxonomy without hand-copying names.
+    pub const ALL: &'static [ErrorCode] = &[
+        Self::ProtocolInvalidEnvelope,
+        Self::ProtocolHandshakeFailed,
+        Self::ProtocolMissingRefId,
+        Self::ProtocolUnexpectedMessage,
+        Self::ProtocolVersionMismatch,
+        Self::MappingUnsupportedCapability,
+        Self::MappingDialectMismatch,
+        Self::MappingLossyConversion,
+        Self::MappingUnmappableTool,
+        Self::BackendNotFound,
+        Self::BackendUnavailable,
+        Self::BackendTimeout,
+        Self::BackendRateLimited,
+        Self::BackendAuthFailed,
+        Self::BackendModelNotFound,
+        Self::BackendCrashed,
+        Self::ExecutionToolFailed,
+        Self::ExecutionWorkspaceError,
+        Self::ExecutionPermissionDenied,
+        Self::ContractVersionMismatch,
+        Self::ContractSchemaViolation,
+        Self::ContractInvalidReceipt,
+        Self::CapabilityUnsupported,
+        Self::CapabilityEmulationFailed,
+        Self::PolicyDenied,
+        Self::PolicyInvalid,
+        Self::WorkspaceInitFailed,
+        Self::WorkspaceStagingFailed,
+        Self::IrLoweringFailed,
+        Self::IrInvalid,
+        Self::ReceiptHashMismatch,
+        Self::ReceiptChainBroken,
+        Self::DialectUnknown,
+        Self::DialectMappingFailed,
+        Self::ConfigInvalid,
+        Self::RateLimitExceeded,
+        Self::CircuitBreakerOpen,
+        Self::QuotaExceeded,
+        Self::StreamClosed,
+        Self::ReceiptStoreFailed,
+        Self::ValidationFailed,
+        Self::SidecarSpawnFailed,
+        Self::BackendContentFiltered,
+        Self::BackendContextLength,
+        Self::Internal,
+    ];
+
     /// Returns the broad [`ErrorCategory`] this code belongs to.
     pub fn category(&self) -> ErrorCategory {
         match self {
@@ -352,7 +405,9 @@ impl ErrorCode {
 
             Self::ConfigInvalid => ErrorCategory::Config,
 
-            Self::RateLimitExceeded | Self::CircuitBreakerOpen => ErrorCategory::RateLimit,
+            Self::RateLimitExceeded | Self::CircuitBreakerOpen | Self::QuotaExceeded => {
+                ErrorCategory::RateLimit
+            }
 
             Self::StreamClosed => ErrorCategory::Stream,
 
@@ -409,6 +464,7 @@ impl ErrorCode {
             Self::ConfigInvalid => "config_invalid",
             Self::RateLimitExceeded => "rate_limit_exceeded",
             Self::CircuitBreakerOpen => "circuit_breaker_open",
+            Self::QuotaExceeded => "quota_exceeded",
             Self::StreamClosed => "stream_closed",
             Self::ReceiptStoreFailed => "receipt_store_failed",
             Self::ValidationFailed => "validation_failed",
@@ -461,6 +517,7 @@ impl ErrorCode {
             Self::ConfigInvalid => "configuration file or value is invalid",
             Self::RateLimitExceeded => "rate limiter blocked the request",
             Self::CircuitBreakerOpen => "circuit breaker is open for the backend",
+            Self::QuotaExceeded => "usage quota for the current window is exhausted",
             Self::StreamClosed => "event stream closed prematurely",
             Self::ReceiptStoreFailed => "receipt persistence failed",
             Self::ValidationFailed => "request validation failed",
@@ -485,6 +542,64 @@ impl ErrorCode {
                 | Self::StreamClosed
         )
     }
+
+    /// HTTP status a server should answer with when a request fails with
+    /// this code.
+    ///
+    /// Client mistakes map to `4xx`; failures of a backend or sidecar behind
+    /// ABP map to `502`/`503`/`504`; everything else is `500`.
+    pub fn http_status(&self) -> u16 {
+        match self {
+            Self::ContractVersionMismatch
+            | Self::ContractSchemaViolation
+            | Self::PolicyInvalid
+            | Self::IrInvalid
+            | Self::DialectUnknown
+            | Self::ValidationFailed
+            | Self::BackendContextLength => 400,
+
+            Self::ExecutionPermissionDenied | Self::PolicyDenied => 403,
+
+            Self::BackendNotFound | Self::BackendModelNotFound => 404,
+
+            Self::MappingUnsupportedCapability
+            | Self::MappingDialectMismatch
+            | Self::MappingLossyConversion
+            | Self::MappingUnmappableTool
+            | Self::ContractInvalidReceipt
+            | Self::CapabilityUnsupported
+            | Self::ReceiptHashMismatch
+            | Self::ReceiptChainBroken
+            | Self::DialectMappingFailed
+            | Self::BackendContentFiltered => 422,
+
+            Self::BackendRateLimited | Self::RateLimitExceeded | Self::QuotaExceeded => 429,
+
+            Self::ProtocolInvalidEnvelope
+            | Self::ProtocolHandshakeFailed
+            | Self::ProtocolMissingRefId
+            | Self::ProtocolUnexpectedMessage
+            | Self::ProtocolVersionMismatch
+            | Self::BackendAuthFailed
+            | Self::BackendCrashed
+            | Self::SidecarSpawnFailed => 502,
+
+            Self::BackendUnavailable | Self::CircuitBreakerOpen => 503,
+
+            Self::BackendTimeout => 504,
+
+            Self::ExecutionToolFailed
+            | Self::ExecutionWorkspaceError
+            | Self::CapabilityEmulationFailed
+            | Self::WorkspaceInitFailed
+            | Self::WorkspaceStagingFailed
+            | Self::IrLoweringFailed
+            | Self::ConfigInvalid
+            | Self::StreamClosed
+            | Self::ReceiptStoreFailed
+            | Self::Internal => 500,
+        }
+    }
 }
 
 impl fmt::Display for ErrorCode {
@@ -1169,6 +1284,7 @@ mod tests {
         // RateLimit
         ErrorCode::RateLimitExceeded,
         ErrorCode::CircuitBreakerOpen,
+        ErrorCode::QuotaExceeded,
         // Stream
         ErrorCode::StreamClosed,
         // Validation
@@ -1567,6 +1683,27 @@ mod tests {
 
     // -- Unique string representations ----------------------------------
 
+    #[test]
+    fn public_code_list_matches_test_list() {
+        let public: HashSet<_> = ErrorCode::ALL.iter().collect();
+        let local: HashSet<_> = ALL_CODES.iter().collect();
+        assert_eq!(public, local);
+        assert_eq!(ErrorCode::ALL.len(), ALL_CODES.len());
+    }
+
+    #[test]
+    fn http_status_agrees_with_retryability() {
+        for &code in ErrorCode::ALL {
+            let status = code.http_status();
+            assert!((400..600).contains(&status), "{code:?}");
+            if code.is_retryable() {
+                assert!(status == 429 || status >= 500, "{code:?} -> {status}");
+            }
+        }
+        assert_eq!(ErrorCode::BackendNotFound.http_status(), 404);
+        assert_eq!(ErrorCode::PolicyDenied.http_status(), 403);
+    }
+
     #[test]
     fn all_codes_have_unique_as_str() {
         let mut seen = HashSet::new();
@@ -1589,7 +1726,7 @@ mod tests {
     #[test]
     fn error_code_count() {
         // Ensure we don't silently drop a variant from ALL_CODES.
-        assert_eq!(ALL_CODES.len(), 44);
+        assert_eq!(ALL_CODES.len(), 45);
     }
 
     #[test]
@@ -194,6 +194,7 @@ impl ErrorClassifier {
             | ErrorCode::SidecarSpawnFailed
             | ErrorCode::BackendContentFiltered
             | ErrorCode::BackendContextLength
+            | ErrorCode::QuotaExceeded
             | ErrorCode::Internal => ErrorClassification::Permanent,
         }
     }
@@ -4,10 +4,13 @@
 use abp_core::{
     AgentEvent, AgentEventKind, Capability, CapabilityManifest, SupportLevel, WorkOrder,
 };
+use abp_sidecar_sdk::VendorConfig;
 use chrono::Utc;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::vendor::GeminiVendorConfig;
+
 /// Version string for this dialect adapter.
 pub const DIALECT_VERSION: &str = "gemini/v0.1";
 
@@ -497,28 +500,58 @@ pub struct GeminiStreamChunk {
 
 /// Map an ABP [`WorkOrder`] to a [`GeminiRequest`].
 ///
-/// Uses the work order task as the initial user message and applies
-/// config defaults where the work order does not specify overrides.
+/// Sends the attached conversation history when there is one (see
+/// [`extract_conversation`](abp_backend_core::extract_conversation)), with
+/// its system message as the system instruction and context snippets as a
+/// trailing user turn. Otherwise uses the work order task as the initial
+/// user message. Knobs under `config.vendor.gemini` (see
+/// [`GeminiVendorConfig`]) override the config defaults; other backends'
+/// namespaces are ignored.
 pub fn map_work_order(wo: &WorkOrder, config: &GeminiConfig) -> GeminiRequest {
+    let vendor = GeminiVendorConfig::from_work_order_or_default(wo);
     let model = wo
         .config
         .model
         .as_deref()
+        .or(vendor.model.as_deref())
         .unwrap_or(&config.model)
         .to_string();
 
-    let mut user_text = wo.task.clone();
+    let mut snippets = String::new();
     for snippet in &wo.context.snippets {
-        user_text.push_str(&format!(
+        snippets.push_str(&format!(
             "\n\n--- {} ---\n{}",
             snippet.name, snippet.content
         ));
     }
+    let user_content = |text: String| GeminiContent {
+        role: "user".into(),
+        parts: vec![GeminiPart::Text(text)],
+    };
+
+    let (contents, system_instruction) = match abp_backend_core::extract_conversation(wo) {
+        Some(conv) if !conv.is_empty() => {
+            let mut contents = crate::lowering::from_ir(&conv);
+            if !snippets.is_empty() {
+                contents.push(user_content(snippets.trim_start().to_string()));
+            }
+            (contents, crate::lowering::extract_system_instruction(&conv))
+        }
+        _ => (vec![user_content(format!("{}{snippets}", wo.task))], None),
+    };
 
-    let generation_config = if config.max_output_tokens.is_some() || config.temperature.is_some() {
+    let max_output_tokens = vendor.max_output_tokens.or(config.max_output_tokens);
+    let temperature = vendor.temperature.or(config.temperature);
+    let generation_config = if max_output_tokens.is_some()
+        || temperature.is_some()
+        || vendor.top_p.is_some()
+        || vendor.top_k.is_some()
+    {
         Some(GeminiGenerationConfig {
-            max_output_tokens: config.max_output_tokens,
-            temperature: config.temperature,
+            max_output_tokens,
+            temperature,
+            top_p: vendor.top_p,
+            top_k: vendor.top_k,
             ..Default::default()
         })
     } else {
@@ -527,11 +560,8 @@ pub fn map_work_order(wo: &WorkOrder, config: &GeminiConfig) -> GeminiRequest {
 
     GeminiRequest {
         model,
-        contents: vec![GeminiContent {
-            role: "user".into(),
-            parts: vec![GeminiPart::Text(user_text)],
-        }],
-        system_instruction: None,
+        contents,
+        system_instruction,
         generation_config,
         safety_settings: None,
         tools: None,
@@ -680,6 +710,7 @@ pub type PromptFeedback = GeminiPromptFeedback;
 mod tests {
     use super::*;
     use abp_core::WorkOrderBuilder;
+    use abp_core::ir::{IrConversation, IrMessage, IrRole};
 
     #[test]
     fn default_config_has_sensible_values() {
@@ -689,6 +720,23 @@ mod tests {
         assert!(cfg.max_output_tokens.unwrap_or(0) > 0);
     }
 
+    #[test]
+    fn map_work_order_sends_conversation_history() {
+        let conv = IrConversation::new()
+            .push(IrMessage::text(IrRole::System, "Be terse"))
+            .push(IrMessage::text(IrRole::User, "What is 2+2?"))
+            .push(IrMessage::text(IrRole::Assistant, "4"))
+            .push(IrMessage::text(IrRole::User, "And times 3?"));
+        let wo = WorkOrderBuilder::new("And times 3?")
+            .conversation(conv)
+            .build();
+        let req = map_work_order(&wo, &GeminiConfig::default());
+
+        assert!(req.system_instruction.is_some());
+        let roles: Vec<_> = req.contents.iter().map(|c| c.role.as_str()).collect();
+        assert_eq!(roles, ["user", "model", "user"]);
+    }
+
     #[test]
     fn map_work_order_uses_task_as_user_content() {
         let wo = WorkOrderBuilder::new("Migrate to async").build();
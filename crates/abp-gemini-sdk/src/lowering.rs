@@ -3,6 +3,9 @@
 //!
 //! `to_ir` converts a slice of `GeminiContent`s into an `IrConversation`,
 //! and `from_ir` converts an `IrConversation` back into Gemini contents.
+//!
+//! Cache breakpoints ([`IrMessage::cache_control`](abp_core::ir::IrMessage::cache_control)) are dropped; Gemini
+//! caches context through separately created cached-content resources.
 
 use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole};
 
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Schema for the `config.vendor.gemini` namespace.
+
+use abp_sidecar_sdk::VendorConfig;
+use abp_sidecar_sdk::vendor::check_range;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Gemini knobs accepted under `config.vendor.gemini`.
+///
+/// Covers the generation parameters used by lowering and the settings read
+/// by the Gemini sidecar host, which spells them in camelCase. Unknown keys
+/// are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GeminiVendorConfig {
+    /// Model override, used when the work order does not set one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Sampling temperature (0–2).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Nucleus sampling probability (0–1).
+    #[serde(default, alias = "topP", skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Top-k sampling cutoff.
+    #[serde(default, alias = "topK", skip_serializing_if = "Option::is_none")]
+    pub top_k: Option<u32>,
+    /// Maximum output tokens.
+    #[serde(
+        default,
+        alias = "maxOutputTokens",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_output_tokens: Option<u32>,
+    /// Reasoning effort hint forwarded to the host.
+    #[serde(
+        default,
+        alias = "reasoningEffort",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub reasoning_effort: Option<String>,
+    /// Thinking mode setting forwarded to the host as-is.
+    #[serde(
+        default,
+        alias = "thinkingMode",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub thinking_mode: Option<serde_json::Value>,
+    /// Route requests through Vertex AI instead of the Gemini API.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vertex: Option<bool>,
+}
+
+impl VendorConfig for GeminiVendorConfig {
+    const NAMESPACE: &'static str = "gemini";
+
+    fn check(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0..=2.0)?;
+        check_range("top_p", self.top_p, 0.0..=1.0)
+    }
+}
@@ -66,6 +66,24 @@ fn work_order_falls_back_to_config_model() {
     assert_eq!(req.model, cfg.model);
 }
 
+#[test]
+fn gemini_vendor_namespace_sets_generation_config() {
+    let mut wo = WorkOrderBuilder::new("task").build();
+    wo.config.vendor = serde_json::from_value(serde_json::json!({
+        "gemini": {"topP": 0.8, "top_k": 20},
+        "gemini.maxOutputTokens": 1024,
+        "openai": {"temperature": 1.5},
+    }))
+    .unwrap();
+    let req = map_work_order(&wo, &GeminiConfig::default());
+
+    let generation = req.generation_config.unwrap();
+    assert_eq!(generation.top_p, Some(0.8));
+    assert_eq!(generation.top_k, Some(20));
+    assert_eq!(generation.max_output_tokens, Some(1024));
+    assert_eq!(generation.temperature, GeminiConfig::default().temperature);
+}
+
 #[test]
 fn context_snippets_are_included_in_user_message() {
     let ctx = ContextPacket {
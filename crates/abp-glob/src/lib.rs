@@ -34,10 +34,17 @@ impl MatchDecision {
 /// Exclude patterns take precedence: a path matching an exclude glob is denied
 /// even if it also matches an include glob. Empty pattern lists are treated as
 /// "no constraint" (all paths pass).
+///
+/// As in `.gitignore`, an exclude pattern starting with `!` re-includes paths
+/// an earlier exclude pattern matched, and the last matching pattern wins:
+/// `["**", "!docs/**"]` excludes everything outside `docs/`. Write `\!` for
+/// a pattern that starts with a literal `!`.
 #[derive(Debug, Clone)]
 pub struct IncludeExcludeGlobs {
     include: Option<GlobSet>,
     exclude: Option<GlobSet>,
+    /// Per exclude pattern, whether it re-includes (`!`); empty when none do.
+    reinclude: Vec<bool>,
 }
 
 impl IncludeExcludeGlobs {
@@ -62,20 +69,35 @@ impl IncludeExcludeGlobs {
     ///
     /// Returns an error if any glob pattern is syntactically invalid.
     pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let reinclude: Vec<bool> = exclude.iter().map(|p| p.starts_with('!')).collect();
+        let stripped: Vec<String> = exclude
+            .iter()
+            .map(|p| p.strip_prefix('!').unwrap_or(p).to_string())
+            .collect();
         Ok(Self {
             include: build_globset(include)?,
-            exclude: build_globset(exclude)?,
+            exclude: build_globset(&stripped)?,
+            reinclude: if reinclude.contains(&true) {
+                reinclude
+            } else {
+                Vec::new()
+            },
         })
     }
 
     /// Evaluate a [`Path`] against the compiled glob rules.
     #[must_use]
     pub fn decide_path(&self, candidate: &Path) -> MatchDecision {
-        if self
-            .exclude
-            .as_ref()
-            .is_some_and(|set| set.is_match(candidate))
-        {
+        if self.exclude.as_ref().is_some_and(|set| {
+            if self.reinclude.is_empty() {
+                set.is_match(candidate)
+            } else {
+                set.matches(candidate)
+                    .into_iter()
+                    .max()
+                    .is_some_and(|last| !self.reinclude[last])
+            }
+        }) {
             return MatchDecision::DeniedByExclude;
         }
         if self
@@ -164,6 +186,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn negated_excludes_reinclude_and_the_last_match_wins() {
+        let rules = IncludeExcludeGlobs::new(
+            &Vec::new(),
+            &patterns(&["**", "!docs/**", "docs/private/**"]),
+        )
+        .expect("compile rules");
+        assert_eq!(rules.decide_str("docs/guide.md"), MatchDecision::Allowed);
+        assert_eq!(
+            rules.decide_str("src/lib.rs"),
+            MatchDecision::DeniedByExclude
+        );
+        assert_eq!(
+            rules.decide_str("docs/private/keys.md"),
+            MatchDecision::DeniedByExclude
+        );
+        let literal = IncludeExcludeGlobs::new(&Vec::new(), &patterns(&["\\!literal"]))
+            .expect("compile escaped rule");
+        assert_eq!(
+            literal.decide_str("!literal"),
+            MatchDecision::DeniedByExclude
+        );
+    }
+
     #[test]
     fn invalid_pattern_returns_error() {
         let err = IncludeExcludeGlobs::new(&patterns(&["["]), &Vec::new())
@@ -16,7 +16,12 @@ pub mod registry;
 pub mod retry;
 pub mod shutdown_coordinator;
 
-use abp_core::{AgentEvent, BackendIdentity, CapabilityManifest, Receipt, WorkOrder};
+use abp_core::{
+    AgentEvent, AgentEventKind, BackendIdentity, CapabilityManifest, Receipt, WorkOrder,
+};
+use abp_protocol::dedup::{DedupStats, EventDeduplicator, Ingest};
+use abp_protocol::features::{ProtocolFeatures, negotiate_features};
+use abp_protocol::secure::{self, ChannelKey, ChannelRole, Opener, SecureHandshake};
 use abp_protocol::{Envelope, JsonlCodec, ProtocolError};
 use futures::Stream;
 use serde::{Deserialize, Serialize};
@@ -24,7 +29,7 @@ use std::collections::BTreeMap;
 use std::process::Stdio;
 use thiserror::Error;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::{Child, ChildStdin, Command};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
 use tokio::sync::{mpsc, oneshot};
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::{debug, warn};
@@ -72,9 +77,18 @@ pub struct SidecarHello {
 pub struct SidecarClient {
     child: Child,
     stdin: ChildStdin,
-    stdout: BufReader<tokio::process::ChildStdout>,
+    stdout: BufReader<ChildStdout>,
+    /// Encrypts lines to the sidecar on a secure channel.
+    sealer: Option<secure::Sealer>,
+    /// Decrypts lines from the sidecar on a secure channel.
+    opener: Option<Opener>,
     /// Handshake data received from the sidecar's initial `hello` message.
     pub hello: SidecarHello,
+    /// Protocol features agreed with the sidecar during the handshake.
+    pub features: ProtocolFeatures,
+    /// Whether the sidecar sent a feature-negotiation block (older sidecars
+    /// do not, and are held to the baseline feature set).
+    features_advertised: bool,
 }
 
 /// An in-progress sidecar run: provides an event stream, a receipt future, and a wait handle.
@@ -141,11 +155,57 @@ pub enum HostError {
     },
 }
 
+impl HostError {
+    /// The classified error code: a failed or vanished process is
+    /// [`BackendCrashed`](abp_error::ErrorCode::BackendCrashed).
+    #[must_use]
+    pub fn error_code(&self) -> abp_error::ErrorCode {
+        use abp_error::ErrorCode;
+        match self {
+            Self::Spawn(_)
+            | Self::Stdout(_)
+            | Self::Stdin(_)
+            | Self::Fatal(_)
+            | Self::Exited { .. }
+            | Self::SidecarCrashed { .. } => ErrorCode::BackendCrashed,
+            Self::Timeout { .. } => ErrorCode::BackendTimeout,
+            Self::Protocol(e) => e.error_code().unwrap_or(ErrorCode::ProtocolInvalidEnvelope),
+            Self::Violation(_) => ErrorCode::ProtocolInvalidEnvelope,
+        }
+    }
+}
+
+impl From<HostError> for abp_error::AbpError {
+    fn from(err: HostError) -> Self {
+        abp_error::AbpError::new(err.error_code(), err.to_string()).with_source(err)
+    }
+}
+
 impl SidecarClient {
     /// Spawn a sidecar process and perform the `hello` handshake.
     ///
     /// The sidecar MUST emit a `hello` envelope as its first stdout line.
+    /// A sidecar whose contract version differs in major version, or is
+    /// malformed, is rejected with a
+    /// [`ProtocolVersionMismatch`](abp_error::ErrorCode::ProtocolVersionMismatch)
+    /// error.
     pub async fn spawn(spec: SidecarSpec) -> Result<Self, HostError> {
+        Self::spawn_with_key(spec, None).await
+    }
+
+    /// Spawn a sidecar over an encrypted, authenticated channel.
+    ///
+    /// The hex-encoded `key` is passed to the sidecar in
+    /// [`CHANNEL_KEY_ENV`](secure::CHANNEL_KEY_ENV); a wrapper command that
+    /// crosses a trust boundary (`ssh`, `docker exec`, …) must forward it or
+    /// provision the same key on the far side. Both ends exchange
+    /// [`SecureHandshake`] lines first, and every line after that, starting
+    /// with `hello`, is sealed.
+    pub async fn spawn_secure(spec: SidecarSpec, key: ChannelKey) -> Result<Self, HostError> {
+        Self::spawn_with_key(spec, Some(key)).await
+    }
+
+    async fn spawn_with_key(spec: SidecarSpec, key: Option<ChannelKey>) -> Result<Self, HostError> {
         let mut cmd = Command::new(&spec.command);
         cmd.args(&spec.args)
             .stdin(Stdio::piped())
@@ -159,10 +219,13 @@ impl SidecarClient {
         for (k, v) in &spec.env {
             cmd.env(k, v);
         }
+        if let Some(key) = &key {
+            cmd.env(secure::CHANNEL_KEY_ENV, key.to_hex());
+        }
 
         let mut child = cmd.spawn().map_err(HostError::Spawn)?;
 
-        let stdin = child
+        let mut stdin = child
             .stdin
             .take()
             .ok_or_else(|| HostError::Violation("sidecar stdin unavailable".into()))?;
@@ -194,27 +257,49 @@ impl SidecarClient {
 
         let mut stdout = BufReader::new(stdout);
 
+        // On a secure channel, exchange handshake lines before anything else.
+        let (sealer, mut opener) = match key {
+            Some(key) => {
+                let handshake =
+                    SecureHandshake::new(&key, ChannelRole::Host).map_err(ProtocolError::from)?;
+                stdin
+                    .write_all(handshake.line().as_bytes())
+                    .await
+                    .map_err(HostError::Stdin)?;
+                stdin.flush().await.map_err(HostError::Stdin)?;
+                let Some(peer) = read_line(&mut stdout, &mut None).await? else {
+                    let status = child.wait().await.ok();
+                    return Err(HostError::Exited {
+                        code: status.and_then(|s| s.code()),
+                    });
+                };
+                let (sealer, opener) = handshake
+                    .complete(&peer)
+                    .map_err(ProtocolError::from)?
+                    .split();
+                debug!(target: "abp.sidecar", "secure channel established");
+                (Some(sealer), Some(opener))
+            }
+            None => (None, None),
+        };
+
         // Expect hello as the first line.
-        let mut line = String::new();
-        let n = stdout
-            .read_line(&mut line)
-            .await
-            .map_err(HostError::Stdout)?;
-        if n == 0 {
+        let Some(line) = read_line(&mut stdout, &mut opener).await? else {
             let status = child.wait().await.ok();
             return Err(HostError::Exited {
                 code: status.and_then(|s| s.code()),
             });
-        }
+        };
 
-        let env = JsonlCodec::decode(line.trim_end())?;
-        let (contract_version, backend, capabilities) = match env {
+        let env = JsonlCodec::decode(&line)?;
+        let (contract_version, backend, capabilities, advertised) = match env {
             Envelope::Hello {
                 contract_version,
                 backend,
                 capabilities,
+                features,
                 ..
-            } => (contract_version, backend, capabilities),
+            } => (contract_version, backend, capabilities, features),
             other => {
                 return Err(HostError::Protocol(ProtocolError::UnexpectedMessage {
                     expected: "hello".into(),
@@ -225,18 +310,125 @@ impl SidecarClient {
 
         debug!(target: "abp.sidecar", "sidecar hello: backend={}", backend.id);
 
+        // Feature negotiation only bridges minor versions; a differing major
+        // or malformed version is rejected either way.
+        if let Some(err) = abp_core::compat::check_sidecar(&contract_version).to_error() {
+            return Err(HostError::Protocol(err.into()));
+        }
+
+        let features = negotiate_features(&ProtocolFeatures::current(), advertised.as_ref());
+        debug!(
+            target: "abp.sidecar",
+            "negotiated features: heartbeat={} tool_channel={} compression={:?} event_kinds={}",
+            features.heartbeat,
+            features.tool_channel,
+            features.preferred_compression(),
+            features.event_kinds.len()
+        );
+
         Ok(Self {
             child,
             stdin,
             stdout,
+            sealer,
+            opener,
             hello: SidecarHello {
                 contract_version,
                 backend,
                 capabilities,
             },
+            features,
+            features_advertised: advertised.is_some(),
         })
     }
 
+    /// Whether the channel to the sidecar is encrypted.
+    #[must_use]
+    pub fn is_secure(&self) -> bool {
+        self.sealer.is_some()
+    }
+
+    /// Whether the sidecar sent a feature-negotiation block in its hello.
+    #[must_use]
+    pub fn features_advertised(&self) -> bool {
+        self.features_advertised
+    }
+
+    /// Compare the sidecar's contract version with ours.
+    ///
+    /// `spawn` has already rejected incompatible versions; the report tells
+    /// a compatible sidecar's minor-version drift apart from an exact match.
+    #[must_use]
+    pub fn compat(&self) -> abp_core::compat::CompatReport {
+        abp_core::compat::check_sidecar(&self.hello.contract_version)
+    }
+
+    /// Send a `ping` and wait up to `timeout` for the matching `pong`,
+    /// returning the round-trip time.
+    ///
+    /// Meant for idle sidecars between the handshake and [`run`](Self::run)
+    /// when [`ProtocolFeatures::heartbeat`] was agreed. Other lines received
+    /// while waiting are dropped.
+    ///
+    /// # Errors
+    ///
+    /// [`HostError::Timeout`] when no pong arrives in time and
+    /// [`HostError::Exited`] when the sidecar closes stdout.
+    pub async fn ping(
+        &mut self,
+        seq: u64,
+        timeout: std::time::Duration,
+    ) -> Result<std::time::Duration, HostError> {
+        let started = std::time::Instant::now();
+        let mut line = serde_json::json!({"t": "ping", "seq": seq}).to_string();
+        line.push('\n');
+        if let Some(sealer) = &mut self.sealer {
+            line = sealer.seal(&line).map_err(ProtocolError::from)?;
+        }
+        self.stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(HostError::Stdin)?;
+        self.stdin.flush().await.map_err(HostError::Stdin)?;
+
+        let pong = async {
+            loop {
+                let Some(reply) = read_line(&mut self.stdout, &mut self.opener).await? else {
+                    let status = self.child.wait().await.ok();
+                    return Err(HostError::Exited {
+                        code: status.and_then(|s| s.code()),
+                    });
+                };
+                let value: serde_json::Value = serde_json::from_str(&reply).unwrap_or_default();
+                if value["t"] == "pong" && value["seq"] == seq {
+                    return Ok(());
+                }
+                debug!(target: "abp.sidecar", "ignoring line while waiting for pong {seq}");
+            }
+        };
+        tokio::time::timeout(timeout, pong)
+            .await
+            .map_err(|_| HostError::Timeout { duration: timeout })??;
+        Ok(started.elapsed())
+    }
+
+    /// The sidecar's exit status if the process has exited, without waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HostError::Exited`] if the process status cannot be read.
+    pub fn try_wait(&mut self) -> Result<Option<std::process::ExitStatus>, HostError> {
+        self.child.try_wait().map_err(|e| HostError::Exited {
+            code: e.raw_os_error(),
+        })
+    }
+
+    /// Kill the sidecar process and return its exit code, if any.
+    pub async fn kill(mut self) -> Option<i32> {
+        let _ = self.child.kill().await;
+        self.child.wait().await.ok().and_then(|s| s.code())
+    }
+
     /// Send a work order and begin streaming events from the sidecar.
     ///
     /// Consumes `self` because a single client handles exactly one run.
@@ -248,12 +440,31 @@ impl SidecarClient {
         let (ev_tx, ev_rx) = mpsc::channel::<AgentEvent>(256);
         let (receipt_tx, receipt_rx) = oneshot::channel::<Result<Receipt, HostError>>();
 
+        // Tell feature-aware sidecars which subset was agreed, via
+        // `config.vendor["abp"]["protocol_features"]`.
+        let mut work_order = work_order;
+        if self.features_advertised
+            && let Ok(agreed) = serde_json::to_value(&self.features)
+        {
+            let abp = work_order
+                .config
+                .vendor
+                .entry("abp".to_string())
+                .or_insert_with(|| serde_json::json!({}));
+            if let Some(obj) = abp.as_object_mut() {
+                obj.insert("protocol_features".to_string(), agreed);
+            }
+        }
+
         // Send Run request.
         let msg = Envelope::Run {
             id: run_id.clone(),
             work_order,
         };
-        let line = JsonlCodec::encode(&msg)?;
+        let mut line = JsonlCodec::encode(&msg)?;
+        if let Some(sealer) = &mut self.sealer {
+            line = sealer.seal(&line).map_err(ProtocolError::from)?;
+        }
         self.stdin
             .write_all(line.as_bytes())
             .await
@@ -261,37 +472,52 @@ impl SidecarClient {
         self.stdin.flush().await.map_err(HostError::Stdin)?;
 
         let mut stdout = self.stdout;
+        let mut opener = self.opener;
         let mut child = self.child;
 
         let wait = tokio::spawn(async move {
-            let mut buf = String::new();
+            let mut dedup = EventDeduplicator::new();
             loop {
-                buf.clear();
-                let n = stdout
-                    .read_line(&mut buf)
-                    .await
-                    .map_err(HostError::Stdout)?;
-                if n == 0 {
-                    // Child closed stdout; treat as exit.
-                    let status = child.wait().await.map_err(|e| HostError::Exited {
-                        code: e.raw_os_error(),
-                    })?;
-                    return Err(HostError::Exited {
-                        code: status.code(),
-                    });
-                }
-
-                let line = buf.trim_end();
+                let line = match read_line(&mut stdout, &mut opener).await {
+                    Ok(Some(line)) => line,
+                    Ok(None) => {
+                        // Child closed stdout; treat as exit.
+                        let status = child.wait().await.map_err(|e| HostError::Exited {
+                            code: e.raw_os_error(),
+                        })?;
+                        return Err(HostError::Exited {
+                            code: status.code(),
+                        });
+                    }
+                    Err(HostError::Protocol(e)) => {
+                        let _ = receipt_tx.send(Err(HostError::Protocol(e)));
+                        break;
+                    }
+                    Err(e) => return Err(e),
+                };
                 if line.is_empty() {
                     continue;
                 }
 
-                match JsonlCodec::decode(line) {
-                    Ok(Envelope::Event { ref_id, event }) => {
+                match JsonlCodec::decode(&line) {
+                    Ok(Envelope::Event { ref_id, event, seq }) => {
                         if ref_id != run_id {
                             warn!(target: "abp.sidecar", "dropping event for other run_id={ref_id}");
                             continue;
                         }
+                        match dedup.ingest(&ref_id, seq) {
+                            Ingest::Accept => {}
+                            Ingest::Duplicate => {
+                                debug!(target: "abp.sidecar", "dropping duplicate event seq={seq:?}");
+                                continue;
+                            }
+                            Ingest::Gap { expected, got } => {
+                                warn!(target: "abp.sidecar", "event gap: expected seq={expected}, got seq={got}");
+                                if ev_tx.send(gap_warning(expected, got)).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
                         if ev_tx.send(event).await.is_err() {
                             // Receiver dropped; stop.
                             break;
@@ -302,6 +528,7 @@ impl SidecarClient {
                             warn!(target: "abp.sidecar", "dropping final for other run_id={ref_id}");
                             continue;
                         }
+                        let receipt = record_event_delivery(receipt, dedup.stats());
                         let _ = receipt_tx.send(Ok(receipt));
                         break;
                     }
@@ -346,6 +573,73 @@ impl SidecarClient {
     }
 }
 
+/// Read the next line from the sidecar without its line ending, opening it
+/// on a secure channel. Returns `None` once stdout closes.
+async fn read_line(
+    stdout: &mut BufReader<ChildStdout>,
+    opener: &mut Option<Opener>,
+) -> Result<Option<String>, HostError> {
+    let mut buf = String::new();
+    let n = stdout
+        .read_line(&mut buf)
+        .await
+        .map_err(HostError::Stdout)?;
+    if n == 0 {
+        return Ok(None);
+    }
+    let line = buf.trim_end();
+    match opener {
+        Some(opener) if !line.is_empty() => {
+            Ok(Some(opener.open(line).map_err(ProtocolError::from)?))
+        }
+        _ => Ok(Some(line.to_string())),
+    }
+}
+
+/// Guidance event emitted in place of events lost in transport.
+fn gap_warning(expected: u64, got: u64) -> AgentEvent {
+    AgentEvent {
+        ts: chrono::Utc::now(),
+        kind: AgentEventKind::Warning {
+            message: format!(
+                "sidecar event stream gap: expected seq {expected}, got seq {got}; \
+                 {} event(s) may have been lost in transport",
+                got - expected
+            ),
+        },
+        ext: None,
+    }
+}
+
+/// Record de-duplication counters in `usage_raw["event_delivery"]` when
+/// delivery was not clean, re-hashing the receipt if it was hashed.
+fn record_event_delivery(mut receipt: Receipt, stats: DedupStats) -> Receipt {
+    if stats.is_clean() {
+        return receipt;
+    }
+    let Ok(value) = serde_json::to_value(stats) else {
+        return receipt;
+    };
+    match receipt.usage_raw.as_object_mut() {
+        Some(obj) => {
+            obj.insert("event_delivery".to_string(), value);
+        }
+        None => {
+            receipt.usage_raw = serde_json::json!({
+                "original": receipt.usage_raw,
+                "event_delivery": value,
+            });
+        }
+    }
+    if receipt.receipt_sha256.is_some() {
+        match receipt.clone().with_hash() {
+            Ok(hashed) => receipt = hashed,
+            Err(e) => warn!(target: "abp.sidecar", "failed to re-hash receipt: {e}"),
+        }
+    }
+    receipt
+}
+
 /// Type-erased stream of [`AgentEvent`]s.
 // Convenience: accept a stream of events as a trait object.
 pub type EventStream = dyn Stream<Item = AgentEvent> + Send + Unpin;
@@ -484,37 +484,25 @@ async fn conformance_malformed_json_midstream() {
 }
 
 // ---------------------------------------------------------------------------
-// 13. Hello with wrong contract version is accepted (no host-side validation)
+// 13. Hello with an incompatible contract version is rejected
 // ---------------------------------------------------------------------------
 
 #[tokio::test]
 async fn conformance_wrong_contract_version() {
     let py = require_python!();
-    let client = SidecarClient::spawn(mock_spec_with_mode(&py, "wrong_version"))
+    let err = SidecarClient::spawn(mock_spec_with_mode(&py, "wrong_version"))
         .await
-        .expect("spawn should succeed");
+        .expect_err("spawn should reject an incompatible contract version");
 
+    assert!(matches!(err, HostError::Protocol(_)), "got {err:?}");
     assert_eq!(
-        client.hello.contract_version, "abp/v999.0",
-        "host should preserve the sidecar's reported contract version"
+        err.error_code(),
+        abp_error::ErrorCode::ProtocolVersionMismatch
+    );
+    assert!(
+        err.to_string().contains("abp/v999.0"),
+        "error should name the sidecar's contract version: {err}"
     );
-
-    // The run should still succeed — version check is the caller's concern.
-    let run_id = Uuid::new_v4().to_string();
-    let sidecar_run = client
-        .run(run_id.clone(), test_work_order())
-        .await
-        .expect("run should succeed");
-
-    let _events: Vec<_> = sidecar_run.events.collect().await;
-    let receipt = sidecar_run
-        .receipt
-        .await
-        .expect("receipt channel should not be dropped")
-        .expect("receipt should be Ok");
-    assert!(matches!(receipt.outcome, abp_core::Outcome::Complete));
-
-    sidecar_run.wait.await.unwrap().unwrap();
 }
 
 // ---------------------------------------------------------------------------
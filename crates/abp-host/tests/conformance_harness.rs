@@ -88,6 +88,7 @@ fn validate_hello(hello: &Envelope) -> Vec<ConformanceResult> {
             backend,
             capabilities: _,
             mode: _,
+            ..
         } => {
             if backend.id.is_empty() {
                 r.push(ConformanceResult::fail(
@@ -143,7 +144,7 @@ fn validate_event_stream(events: &[Envelope]) -> Vec<ConformanceResult> {
 
     for (i, env) in events.iter().enumerate() {
         match env {
-            Envelope::Event { ref_id, event } => {
+            Envelope::Event { ref_id, event, .. } => {
                 ref_ids.push(ref_id.as_str());
                 agent_events.push(event);
             }
@@ -411,6 +412,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind, offset_ms: i64) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -462,6 +464,7 @@ fn test_validate_hello_empty_backend() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     assert_has_failure(&validate_hello(&hello), "hello_has_backend");
 }
@@ -477,6 +480,7 @@ fn test_validate_hello_bad_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     assert_has_failure(&validate_hello(&hello), "hello_version_format_valid");
 }
@@ -588,6 +592,7 @@ fn test_validate_events_duplicate_timestamps() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Event {
             ref_id: "r".into(),
@@ -598,6 +603,7 @@ fn test_validate_events_duplicate_timestamps() {
                 },
                 ext: None,
             },
+            seq: None,
         },
     ];
     assert_has_failure(
@@ -86,6 +86,7 @@ fn hello_with_wrong_contract_version_round_trips() {
         },
         capabilities: test_capabilities(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim_end()).unwrap();
@@ -111,6 +112,7 @@ fn hello_with_empty_contract_version_round_trips() {
         },
         capabilities: test_capabilities(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim_end()).unwrap();
@@ -135,6 +137,7 @@ fn hello_version_mismatch_is_detectable() {
         },
         capabilities: test_capabilities(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim_end()).unwrap();
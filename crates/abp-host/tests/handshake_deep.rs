@@ -105,6 +105,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -346,6 +347,7 @@ fn invalid_hello_wrong_version_format_validator() {
         backend: test_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let v = EnvelopeValidator::new();
     let result = v.validate(&hello);
@@ -363,6 +365,7 @@ fn invalid_hello_empty_version_validator() {
         backend: test_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let v = EnvelopeValidator::new();
     let result = v.validate(&hello);
@@ -384,6 +387,7 @@ fn invalid_hello_empty_backend_id_validator() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let v = EnvelopeValidator::new();
     let result = v.validate(&hello);
@@ -587,7 +591,7 @@ fn event_assistant_delta_roundtrip() {
     );
     let decoded = roundtrip(&env);
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             assert!(
                 matches!(event.kind, AgentEventKind::AssistantDelta { text } if text == "Hello ")
@@ -870,6 +874,7 @@ fn event_empty_ref_id_validator_error() {
             kind: AgentEventKind::AssistantDelta { text: "x".into() },
             ext: None,
         },
+        seq: None,
     };
     let result = v.validate(&env);
     assert!(!result.valid);
@@ -889,6 +894,7 @@ fn event_with_ext_passthrough_data() {
             kind: AgentEventKind::AssistantMessage { text: "msg".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -95,6 +95,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -1609,6 +1610,7 @@ fn encode_decode_hello_envelope() {
         backend: test_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim_end()).unwrap();
@@ -1653,7 +1655,7 @@ fn encode_decode_event_envelope() {
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim_end()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-001");
             match event.kind {
                 AgentEventKind::AssistantMessage { text } => {
@@ -1783,6 +1785,7 @@ fn encoded_line_contains_tag_field() {
         backend: test_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     assert!(encoded.contains(r#""t":"hello""#));
@@ -1840,6 +1843,7 @@ fn hello_with_capabilities_roundtrip() {
         backend: test_backend(),
         capabilities: caps.clone(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
 
     let encoded = JsonlCodec::encode(&env).unwrap();
@@ -1871,6 +1875,7 @@ fn hello_empty_capabilities_is_valid() {
         backend: test_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim_end()).unwrap();
@@ -1889,6 +1894,7 @@ fn hello_passthrough_mode() {
         backend: test_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Passthrough,
+        features: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim_end()).unwrap();
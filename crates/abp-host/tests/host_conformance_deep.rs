@@ -87,6 +87,7 @@ fn make_event(ref_id: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -661,6 +662,7 @@ fn validate_hello_empty_contract_version() {
         backend: test_backend(),
         capabilities: test_capabilities(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validator.validate(&hello);
     assert!(!result.valid, "empty contract_version should fail");
@@ -677,6 +679,7 @@ fn validate_hello_invalid_version_format() {
         backend: test_backend(),
         capabilities: test_capabilities(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validator.validate(&hello);
     assert!(!result.valid, "invalid version format should fail");
@@ -700,6 +703,7 @@ fn validate_hello_empty_backend_id() {
         },
         capabilities: test_capabilities(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validator.validate(&hello);
     assert!(!result.valid, "empty backend.id should fail");
@@ -717,6 +721,7 @@ fn validate_hello_missing_optional_versions_warns() {
         },
         capabilities: test_capabilities(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validator.validate(&hello);
     assert!(
@@ -763,6 +768,7 @@ fn validate_event_empty_ref_id() {
             },
             ext: None,
         },
+        seq: None,
     };
     let result = validator.validate(&event);
     assert!(!result.valid, "empty event ref_id should fail");
@@ -868,7 +874,7 @@ fn roundtrip_event_run_started() {
     let line = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             assert!(matches!(event.kind, AgentEventKind::RunStarted { .. }));
         }
@@ -887,6 +893,7 @@ fn roundtrip_event_assistant_delta() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -911,6 +918,7 @@ fn roundtrip_event_file_changed() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -940,6 +948,7 @@ fn roundtrip_event_tool_call() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -1023,6 +1032,7 @@ fn roundtrip_event_with_ext_field() {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -1045,6 +1055,7 @@ fn roundtrip_event_without_ext_field() {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&event).unwrap();
     // ext: None should be skipped in serialization (skip_serializing_if)
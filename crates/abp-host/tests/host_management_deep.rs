@@ -133,6 +133,7 @@ fn make_event_json(ref_id: &str, kind: AgentEventKind) -> String {
     let env = Envelope::Event {
         ref_id: ref_id.into(),
         event: test_agent_event(kind),
+        seq: None,
     };
     JsonlCodec::encode(&env).unwrap()
 }
@@ -386,6 +387,7 @@ fn jsonl_encode_event_has_tag_t() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains(r#""t":"event""#));
@@ -867,10 +869,12 @@ fn event_stream_tool_call_and_result() {
     let env1 = Envelope::Event {
         ref_id: ref_id.into(),
         event: tool_call,
+        seq: None,
     };
     let env2 = Envelope::Event {
         ref_id: ref_id.into(),
         event: tool_result,
+        seq: None,
     };
 
     let line1 = JsonlCodec::encode(&env1).unwrap();
@@ -900,6 +904,7 @@ fn event_with_ext_field_roundtrips() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
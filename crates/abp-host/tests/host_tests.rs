@@ -200,6 +200,114 @@ async fn spawn_receives_hello() {
     assert_eq!(client.hello.backend.backend_version.as_deref(), Some("0.1"));
 }
 
+#[tokio::test]
+async fn spawn_without_feature_block_uses_baseline() {
+    let Some(py) = python_cmd() else {
+        eprintln!("SKIP: python not found");
+        return;
+    };
+
+    let mut spec = SidecarSpec::new(&py);
+    spec.args = vec![mock_script_path()];
+
+    let client = SidecarClient::spawn(spec).await.unwrap();
+    assert_eq!(
+        client.features,
+        abp_protocol::features::ProtocolFeatures::baseline()
+    );
+}
+
+#[tokio::test]
+async fn spawn_negotiates_advertised_features() {
+    let Some(py) = python_cmd() else {
+        eprintln!("SKIP: python not found");
+        return;
+    };
+
+    let mut spec = SidecarSpec::new(&py);
+    spec.args = vec![mock_script_path(), "features".into()];
+
+    let client = SidecarClient::spawn(spec).await.unwrap();
+    let agreed = client.features.clone();
+    assert!(agreed.heartbeat);
+    assert!(!agreed.tool_channel);
+    assert_eq!(
+        agreed.preferred_compression(),
+        abp_protocol::compress::CompressionAlgorithm::Gzip
+    );
+    assert!(agreed.supports_event_kind("assistant_message"));
+    assert!(!agreed.supports_event_kind("reasoning_delta"));
+    assert!(!agreed.supports_event_kind("tool_call"));
+
+    // The agreed subset is forwarded to the sidecar with the run.
+    let run = client
+        .run(Uuid::new_v4().to_string(), test_work_order())
+        .await
+        .unwrap();
+    let events: Vec<_> = run.events.collect().await;
+    let abp_core::AgentEventKind::RunStarted { message } = &events[0].kind else {
+        panic!("expected run_started, got {:?}", events[0].kind);
+    };
+    let echoed: abp_protocol::features::ProtocolFeatures = serde_json::from_str(message).unwrap();
+    assert_eq!(echoed, agreed);
+}
+
+#[tokio::test]
+async fn run_drops_retried_events_and_warns_on_gap() {
+    let Some(py) = python_cmd() else {
+        eprintln!("SKIP: python not found");
+        return;
+    };
+
+    let mut spec = SidecarSpec::new(&py);
+    spec.args = vec![mock_script_path(), "retried_events".into()];
+
+    let client = SidecarClient::spawn(spec).await.unwrap();
+    let run = client
+        .run(Uuid::new_v4().to_string(), test_work_order())
+        .await
+        .unwrap();
+    let events: Vec<_> = run.events.collect().await;
+    let texts: Vec<String> = events
+        .iter()
+        .map(|e| match &e.kind {
+            abp_core::AgentEventKind::AssistantDelta { text } => text.clone(),
+            abp_core::AgentEventKind::Warning { message } => format!("warning: {message}"),
+            other => panic!("unexpected event {other:?}"),
+        })
+        .collect();
+    assert_eq!(texts.len(), 4);
+    assert_eq!(texts[0], "chunk 0");
+    assert_eq!(texts[1], "chunk 1");
+    assert!(texts[2].starts_with("warning: sidecar event stream gap: expected seq 2, got seq 3"));
+    assert_eq!(texts[3], "chunk 3");
+
+    let receipt = run.receipt.await.unwrap().unwrap();
+    let delivery = &receipt.usage_raw["event_delivery"];
+    assert_eq!(delivery["duplicates"], 2);
+    assert_eq!(delivery["gaps"], 1);
+    assert_eq!(delivery["missing"], 1);
+}
+
+#[tokio::test]
+async fn spawn_rejects_incompatible_contract_version() {
+    let Some(py) = python_cmd() else {
+        eprintln!("SKIP: python not found");
+        return;
+    };
+
+    let mut spec = SidecarSpec::new(&py);
+    spec.args = vec![mock_script_path(), "wrong_version".into()];
+
+    let err = SidecarClient::spawn(spec).await.unwrap_err();
+    assert!(matches!(err, HostError::Protocol(_)), "got {err:?}");
+    assert_eq!(
+        err.error_code(),
+        abp_error::ErrorCode::ProtocolVersionMismatch
+    );
+    assert!(err.to_string().contains("abp/v999.0"), "got {err}");
+}
+
 #[tokio::test]
 async fn run_receives_events_and_receipt() {
     let py = match python_cmd() {
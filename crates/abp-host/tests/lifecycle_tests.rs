@@ -167,16 +167,17 @@ async fn handshake_missing_hello_fails_with_protocol_error() {
 // ---------------------------------------------------------------------------
 
 #[tokio::test]
-async fn handshake_wrong_contract_version_still_accepted() {
+async fn handshake_wrong_contract_version_rejected() {
     let py = require_python!();
-    let client = SidecarClient::spawn(mock_spec_with_mode(&py, "wrong_version"))
+    let err = SidecarClient::spawn(mock_spec_with_mode(&py, "wrong_version"))
         .await
-        .expect("spawn should succeed even with wrong version");
+        .expect_err("spawn should reject an incompatible contract version");
 
-    // The client stores whatever version the sidecar reported; version
-    // negotiation/rejection is a higher-level concern.
-    assert_eq!(client.hello.contract_version, "abp/v999.0");
-    assert_eq!(client.hello.backend.id, "mock-test");
+    assert!(matches!(err, HostError::Protocol(_)), "got {err:?}");
+    assert_eq!(
+        err.error_code(),
+        abp_error::ErrorCode::ProtocolVersionMismatch
+    );
 }
 
 // ---------------------------------------------------------------------------
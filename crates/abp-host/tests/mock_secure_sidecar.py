@@ -0,0 +1,127 @@
+"""Mock sidecar speaking the secure (encrypted) JSONL channel.
+
+Reads the channel key from ABP_SIDECAR_CHANNEL_KEY, performs the
+abp-secure/v1 handshake, then runs hello -> run -> event -> final with every
+line sealed. Requires the `cryptography` package.
+
+Modes:
+  default     - secure handshake, then a normal run
+  plaintext   - skip encryption after the handshake (host must reject)
+  wrong_key   - derive keys from a different channel key (host must reject)
+"""
+import base64
+import datetime
+import json
+import os
+import sys
+
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric.x25519 import (
+    X25519PrivateKey,
+    X25519PublicKey,
+)
+from cryptography.hazmat.primitives.ciphers.aead import ChaCha20Poly1305
+from cryptography.hazmat.primitives.kdf.hkdf import HKDF
+
+VERSION = "abp-secure/v1"
+mode = sys.argv[1] if len(sys.argv) > 1 else "default"
+
+key = bytes.fromhex(os.environ["ABP_SIDECAR_CHANNEL_KEY"])
+if mode == "wrong_key":
+    key = bytes(32)
+
+private = X25519PrivateKey.generate()
+public = private.public_key().public_bytes(
+    serialization.Encoding.Raw, serialization.PublicFormat.Raw
+)
+print(
+    json.dumps({"secure": VERSION, "public_key": base64.b64encode(public).decode()}),
+    flush=True,
+)
+
+peer = json.loads(sys.stdin.readline())
+host_public = base64.b64decode(peer["public_key"])
+shared = private.exchange(X25519PublicKey.from_public_bytes(host_public))
+
+
+def derive(label):
+    info = label + host_public + public
+    return ChaCha20Poly1305(
+        HKDF(algorithm=hashes.SHA256(), length=32, salt=key, info=info).derive(shared)
+    )
+
+
+to_host = derive(b"abp sidecar->host")
+to_sidecar = derive(b"abp host->sidecar")
+counters = {"send": 0, "recv": 0}
+
+
+def nonce(direction):
+    n = counters[direction]
+    counters[direction] += 1
+    return bytes(4) + n.to_bytes(8, "big")
+
+
+def emit(obj):
+    line = json.dumps(obj)
+    if mode == "plaintext":
+        print(line, flush=True)
+        return
+    sealed = to_host.encrypt(nonce("send"), line.encode(), VERSION.encode())
+    print(base64.b64encode(sealed).decode(), flush=True)
+
+
+def receive():
+    frame = base64.b64decode(sys.stdin.readline().strip())
+    return json.loads(to_sidecar.decrypt(nonce("recv"), frame, VERSION.encode()))
+
+
+emit(
+    {
+        "t": "hello",
+        "contract_version": "abp/v0.1",
+        "backend": {"id": "mock-secure", "backend_version": "0.1", "adapter_version": "0.1"},
+        "capabilities": {},
+        "mode": "mapped",
+    }
+)
+run = receive()
+ref_id = run["id"]
+emit(
+    {
+        "t": "event",
+        "ref_id": ref_id,
+        "event": {
+            "ts": "2024-01-01T00:00:00Z",
+            "type": "assistant_message",
+            "text": run["work_order"]["task"],
+        },
+    }
+)
+now = datetime.datetime.now(datetime.timezone.utc).isoformat()
+emit(
+    {
+        "t": "final",
+        "ref_id": ref_id,
+        "receipt": {
+            "meta": {
+                "run_id": ref_id,
+                "work_order_id": run["work_order"]["id"],
+                "contract_version": "abp/v0.1",
+                "started_at": now,
+                "finished_at": now,
+                "duration_ms": 0,
+            },
+            "backend": {"id": "mock-secure", "backend_version": "0.1", "adapter_version": "0.1"},
+            "capabilities": {},
+            "mode": "mapped",
+            "usage_raw": {},
+            "usage": {},
+            "trace": [],
+            "artifacts": [],
+            "verification": {"harness_ok": True},
+            "outcome": "complete",
+            "receipt_sha256": None,
+        },
+    }
+)
@@ -9,10 +9,12 @@ Modes:
   multi_event_kinds- hello → run → events of varied kinds → final
   slow             - hello → run → events with delays → final
   bad_json_midstream - hello → run → event → malformed line
-  wrong_version    - hello with wrong contract version → run → final
+  wrong_version    - hello with an incompatible contract version (host rejects it)
   no_hello         - sends an event envelope as first line (no hello)
   fatal            - hello → run → event → fatal
   hang             - hello → run → event → sleep forever
+  features         - hello with a feature block → run → echo agreed features → final
+  retried_events   - hello → run → sequenced events with re-sends and a gap → final
 """
 import sys
 import json
@@ -38,9 +40,12 @@ def make_hello(version="abp/v0.1"):
 
 
 def read_run():
+    return read_run_envelope()["id"]
+
+
+def read_run_envelope():
     line = sys.stdin.readline()
-    run = json.loads(line)
-    return run["id"]
+    return json.loads(line)
 
 
 def make_event(ref_id, event_type, **kwargs):
@@ -135,6 +140,32 @@ elif mode == "wrong_version":
     emit(make_event(ref_id, "run_started", message="wrong version"))
     emit(make_final(ref_id))
 
+elif mode == "features":
+    # Advertise a feature-negotiation block and echo back what the host agreed.
+    hello = make_hello()
+    hello["features"] = {
+        "event_kinds": ["run_started", "assistant_message", "reasoning_delta"],
+        "compression": ["gzip"],
+        "heartbeat": True,
+        "tool_channel": False,
+    }
+    emit(hello)
+    run = read_run_envelope()
+    ref_id = run["id"]
+    agreed = run["work_order"]["config"]["vendor"].get("abp", {}).get("protocol_features")
+    emit(make_event(ref_id, "run_started", message=json.dumps(agreed, sort_keys=True)))
+    emit(make_final(ref_id))
+
+elif mode == "retried_events":
+    # At-least-once delivery: seq 0 and 1 are re-sent, seq 2 is lost.
+    emit(make_hello())
+    ref_id = read_run()
+    for seq in [0, 1, 0, 1, 3]:
+        ev = make_event(ref_id, "assistant_delta", text=f"chunk {seq}")
+        ev["seq"] = seq
+        emit(ev)
+    emit(make_final(ref_id))
+
 elif mode == "no_hello":
     # Send a non-hello envelope as the very first line.
     emit(make_event("fake", "run_started", message="no hello"))
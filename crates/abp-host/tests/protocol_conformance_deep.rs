@@ -288,12 +288,12 @@ async fn handshake_completes_with_correct_version() {
 
 #[tokio::test]
 #[ignore = "requires python"]
-async fn handshake_wrong_version_is_preserved() {
+async fn handshake_wrong_version_is_rejected() {
     let py = require_python!();
-    let client = SidecarClient::spawn(spec_mode(&py, "wrong_version"))
+    let err = SidecarClient::spawn(spec_mode(&py, "wrong_version"))
         .await
-        .expect("spawn should succeed");
-    assert_eq!(client.hello.contract_version, "abp/v999.0");
+        .unwrap_err();
+    assert!(err.to_string().contains("abp/v999.0"), "got {err}");
 }
 
 #[tokio::test]
@@ -1041,6 +1041,7 @@ fn validate_hello_empty_backend_id() {
         },
         capabilities: CapabilityManifest::new(),
         mode: abp_core::ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&hello);
     assert!(!result.valid);
@@ -1062,6 +1063,7 @@ fn validate_hello_invalid_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: abp_core::ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&hello);
     assert!(!result.valid);
@@ -1097,6 +1099,7 @@ fn validate_sequence_happy_path() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: "run-1".into(),
@@ -1149,6 +1152,7 @@ fn validate_sequence_ref_id_mismatch() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: "run-1".into(),
@@ -106,6 +106,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -199,6 +200,7 @@ fn hello_wrong_contract_version_fails_validation() {
         backend: test_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let v = EnvelopeValidator::new();
     let result = v.validate(&hello);
@@ -221,6 +223,7 @@ fn hello_unparseable_version_fails_validation() {
         backend: test_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let v = EnvelopeValidator::new();
     let result = v.validate(&hello);
@@ -503,6 +506,7 @@ fn event_with_null_ext_field_round_trips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -930,6 +934,7 @@ fn event_with_ext_field_round_trips() {
                 ("cost".into(), serde_json::json!(0.001)),
             ])),
         },
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -107,6 +107,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -244,7 +245,7 @@ fn envelope_event_roundtrip() {
     assert!(encoded.contains(r#""t":"event""#));
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-42");
             assert!(matches!(event.kind, AgentEventKind::RunStarted { .. }));
         }
@@ -332,6 +333,7 @@ fn validate_hello_with_empty_backend_id() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&hello);
@@ -349,6 +351,7 @@ fn validate_hello_with_invalid_version() {
         backend: test_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&hello);
@@ -372,6 +375,7 @@ fn validate_hello_warns_on_missing_optional_fields() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&hello);
@@ -622,6 +626,7 @@ fn large_payload_event_round_trips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&event_env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -652,11 +657,12 @@ fn unicode_in_envelope_fields() {
             },
             ext: None,
         },
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&event_env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "実行-42");
             if let AgentEventKind::AssistantMessage { text } = &event.kind {
                 assert!(text.contains("こんにちは"));
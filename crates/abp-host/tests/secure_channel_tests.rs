@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! End-to-end tests for the encrypted sidecar channel.
+
+use abp_core::{AgentEventKind, Outcome, WorkOrderBuilder};
+use abp_host::{HostError, SidecarClient, SidecarSpec};
+use abp_protocol::secure::ChannelKey;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// `python3` with the `cryptography` package, if available.
+fn python_with_cryptography() -> Option<String> {
+    ["python3", "python"].into_iter().find_map(|cmd| {
+        std::process::Command::new(cmd)
+            .args(["-c", "import cryptography"])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .ok()
+            .filter(|s| s.success())
+            .map(|_| cmd.to_string())
+    })
+}
+
+macro_rules! require_python {
+    () => {
+        match python_with_cryptography() {
+            Some(cmd) => cmd,
+            None => {
+                eprintln!("SKIP: python with the cryptography package not found");
+                return;
+            }
+        }
+    };
+}
+
+fn spec(python: &str, mode: &str) -> SidecarSpec {
+    let script = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("mock_secure_sidecar.py");
+    let mut spec = SidecarSpec::new(python);
+    spec.args = vec![script.to_string_lossy().into_owned(), mode.into()];
+    spec
+}
+
+#[tokio::test]
+async fn secure_run_round_trips() {
+    let python = require_python!();
+    let key = ChannelKey::generate().unwrap();
+    let client = SidecarClient::spawn_secure(spec(&python, "default"), key)
+        .await
+        .unwrap();
+    assert!(client.is_secure());
+    assert_eq!(client.hello.backend.id, "mock-secure");
+
+    let wo = WorkOrderBuilder::new("secret task").build();
+    let run = client.run(Uuid::new_v4().to_string(), wo).await.unwrap();
+    let events: Vec<_> = run.events.collect().await;
+    assert!(matches!(
+        &events[0].kind,
+        AgentEventKind::AssistantMessage { text } if text == "secret task"
+    ));
+    let receipt = run.receipt.await.unwrap().unwrap();
+    assert_eq!(receipt.outcome, Outcome::Complete);
+}
+
+#[tokio::test]
+async fn plaintext_after_handshake_is_rejected() {
+    let python = require_python!();
+    let key = ChannelKey::generate().unwrap();
+    let err = SidecarClient::spawn_secure(spec(&python, "plaintext"), key)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, HostError::Protocol(_)), "{err}");
+    assert!(err.to_string().contains("secure frame rejected"), "{err}");
+}
+
+#[tokio::test]
+async fn wrong_key_is_rejected() {
+    let python = require_python!();
+    let key = ChannelKey::generate().unwrap();
+    let err = SidecarClient::spawn_secure(spec(&python, "wrong_key"), key)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("authentication failed"), "{err}");
+}
+
+#[tokio::test]
+async fn plain_spawn_is_not_secure() {
+    let python = require_python!();
+    let mut spec = SidecarSpec::new(python);
+    spec.args = vec![
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("mock_sidecar.py")
+            .to_string_lossy()
+            .into_owned(),
+    ];
+    let client = SidecarClient::spawn(spec).await.unwrap();
+    assert!(!client.is_secure());
+}
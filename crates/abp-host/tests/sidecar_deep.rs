@@ -119,6 +119,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -525,7 +526,7 @@ fn event_envelope_roundtrip() {
     assert!(line.contains(r#""t":"event""#));
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             assert!(matches!(
                 event.kind,
@@ -260,11 +260,11 @@ async fn hello_with_extra_fields_tolerated() {
 }
 
 #[tokio::test]
-async fn hello_wrong_version_still_parses() {
+async fn hello_wrong_version_is_rejected() {
     let py = require_python!();
     let spec = mock_spec_with_mode(&py, "wrong_version");
-    let client = SidecarClient::spawn(spec).await.unwrap();
-    assert_eq!(client.hello.contract_version, "abp/v999.0");
+    let err = SidecarClient::spawn(spec).await.unwrap_err();
+    assert!(err.to_string().contains("abp/v999.0"), "got {err}");
 }
 
 #[test]
@@ -274,6 +274,7 @@ fn hello_envelope_codec_roundtrip() {
         backend: test_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -287,6 +288,7 @@ fn hello_envelope_has_correct_tag() {
         backend: test_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let v: serde_json::Value = serde_json::from_str(&json).unwrap();
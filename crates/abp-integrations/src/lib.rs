@@ -13,13 +13,19 @@ pub mod metrics;
 pub mod pool;
 pub mod projection;
 pub mod selector;
+pub mod supervisor;
 
+pub use abp_backend_core::vendor;
 pub use abp_backend_core::{
-    Backend, ensure_capability_requirements, extract_execution_mode,
-    validate_passthrough_compatibility,
+    Backend, ModelInfo, VendorConfig, VendorNamespace, ensure_capability_requirements,
+    extract_conversation, extract_execution_mode, extract_response_language, extract_seed,
+    extract_tool_choice, extract_tools, validate_passthrough_compatibility,
 };
+pub use abp_backend_grpc::GrpcBackend;
 pub use abp_backend_mock::MockBackend;
 pub use abp_backend_sidecar::SidecarBackend;
+pub use abp_transport_ipc::IpcBackend;
+pub use abp_transport_ws::WsBackend;
 pub use selector::{
     BackendHealth, BackendSelector, CandidateEvaluation, DialectMatch, FallbackStrategy,
     SelectionCriteria, SelectionError, SelectionReport,
@@ -0,0 +1,531 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Supervised sidecar processes.
+//!
+//! A sidecar serves one run per process, so a plain
+//! [`SidecarBackend`](crate::SidecarBackend) pays for a spawn and `hello`
+//! handshake on every run and only learns that the sidecar is broken when a
+//! run fails. [`Supervisor`] keeps one handshaken sidecar warm instead:
+//!
+//! - it spawns the sidecar and performs the handshake ahead of time;
+//! - while the sidecar is idle it checks that the process is still running
+//!   and, when the sidecar agreed to heartbeats, pings it every
+//!   [`HeartbeatConfig::interval`]; a sidecar that misses
+//!   [`HeartbeatConfig::max_missed`] pongs in a row is killed;
+//! - a sidecar that exits, stalls, or fails its handshake is restarted with
+//!   the exponential backoff of [`RetryConfig`], and after `max_retries`
+//!   consecutive failures the supervisor gives up; a sidecar speaking an
+//!   incompatible contract version is given up on at once;
+//! - each run takes the warm sidecar and a replacement is started at once.
+//!
+//! Failures surface as [`AbpError`]s with
+//! [`ErrorCode::BackendCrashed`] (or
+//! [`ErrorCode::ProtocolVersionMismatch`]) carrying the sidecar command,
+//! last exit code, and restart count as context.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use abp_backend_core::{Backend, ensure_capability_requirements};
+use abp_core::{AgentEvent, BackendIdentity, CapabilityManifest, Receipt, WorkOrder};
+use abp_error::{AbpError, ErrorCode};
+use abp_host::retry::{RetryConfig, compute_delay};
+use abp_host::{HostError, SidecarClient, SidecarHello, SidecarSpec};
+use abp_protocol::heartbeat::{HeartbeatConfig, HeartbeatMonitor, HeartbeatState};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Notify, mpsc};
+use tokio::task::JoinHandle;
+use tokio_stream::StreamExt;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Tuning for a [`Supervisor`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorConfig {
+    /// Liveness checks for the idle sidecar.
+    pub heartbeat: HeartbeatConfig,
+    /// Backoff between restarts. `max_retries` consecutive failures stop
+    /// the supervisor; `overall_timeout` bounds how long a run waits for a
+    /// ready sidecar.
+    pub restart: RetryConfig,
+    /// How long spawning the sidecar and its `hello` may take, in
+    /// milliseconds.
+    pub handshake_timeout_ms: u64,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            heartbeat: HeartbeatConfig::default(),
+            restart: RetryConfig {
+                max_retries: 5,
+                ..RetryConfig::default()
+            },
+            handshake_timeout_ms: 30_000,
+        }
+    }
+}
+
+impl SupervisorConfig {
+    /// How long spawning the sidecar and its `hello` may take.
+    #[must_use]
+    pub fn handshake_timeout(&self) -> Duration {
+        Duration::from_millis(self.handshake_timeout_ms)
+    }
+}
+
+/// What a [`Supervisor`] is doing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum SupervisorState {
+    /// Spawning a sidecar and waiting for its `hello`.
+    Starting,
+    /// A handshaken sidecar is idle and waiting for a run.
+    Ready,
+    /// Waiting out the backoff before the next start.
+    Restarting {
+        /// Consecutive failures so far.
+        attempt: u32,
+        /// Backoff before the next start, in milliseconds.
+        delay_ms: u64,
+    },
+    /// Gave up after too many consecutive failures, or at once on a
+    /// failure a restart cannot fix.
+    Failed {
+        /// The last failure.
+        reason: String,
+        /// How runs checked out from the supervisor fail.
+        #[serde(default = "crashed_code")]
+        code: ErrorCode,
+    },
+    /// Shut down.
+    Stopped,
+}
+
+fn crashed_code() -> ErrorCode {
+    ErrorCode::BackendCrashed
+}
+
+/// Point-in-time view of a [`Supervisor`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupervisorStatus {
+    /// Current state.
+    pub state: SupervisorState,
+    /// Sidecars started to replace one that crashed, stalled, or failed
+    /// its handshake.
+    pub restarts: u32,
+    /// Exit code of the last sidecar that exited on its own.
+    pub last_exit_code: Option<i32>,
+    /// The last failure.
+    pub last_error: Option<String>,
+    /// Heartbeat state of the idle sidecar.
+    pub heartbeat: HeartbeatState,
+    /// The `hello` of the most recently started sidecar.
+    pub hello: Option<SidecarHello>,
+}
+
+struct Shared {
+    name: String,
+    spec: SidecarSpec,
+    config: SupervisorConfig,
+    /// The warm sidecar, taken by runs.
+    slot: tokio::sync::Mutex<Option<SidecarClient>>,
+    status: Mutex<SupervisorStatus>,
+    /// Signalled when a sidecar becomes ready or the supervisor stops.
+    ready: Notify,
+    /// Signalled when a run takes the warm sidecar.
+    taken: Notify,
+}
+
+impl Shared {
+    fn update(&self, f: impl FnOnce(&mut SupervisorStatus)) {
+        f(&mut self.status.lock().expect("supervisor status lock poisoned"));
+    }
+
+    fn status(&self) -> SupervisorStatus {
+        self.status
+            .lock()
+            .expect("supervisor status lock poisoned")
+            .clone()
+    }
+
+    /// A `BackendCrashed` error with the sidecar's exit context.
+    fn crashed(&self, message: impl Into<String>) -> AbpError {
+        self.error(ErrorCode::BackendCrashed, message)
+    }
+
+    fn error(&self, code: ErrorCode, message: impl Into<String>) -> AbpError {
+        let status = self.status();
+        AbpError::new(code, format!("sidecar '{}' {}", self.name, message.into()))
+            .with_context("backend", &self.name)
+            .with_context("command", &self.spec.command)
+            .with_context("exit_code", status.last_exit_code)
+            .with_context("restarts", status.restarts)
+    }
+}
+
+/// Keeps a warm, health-checked sidecar process and restarts it when it
+/// dies; see the [module docs](self).
+///
+/// Register it with the runtime like any other [`Backend`]. Dropping the
+/// supervisor stops supervision; [`shutdown`](Self::shutdown) also kills
+/// the idle sidecar.
+pub struct Supervisor {
+    shared: Arc<Shared>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl std::fmt::Debug for Supervisor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Supervisor")
+            .field("name", &self.shared.name)
+            .field("command", &self.shared.spec.command)
+            .field("state", &self.shared.status().state)
+            .finish()
+    }
+}
+
+impl Supervisor {
+    /// Start supervising the sidecar `spec` under `name`.
+    ///
+    /// Must be called within a Tokio runtime; the first sidecar is spawned
+    /// in the background.
+    #[must_use]
+    pub fn start(name: impl Into<String>, spec: SidecarSpec, config: SupervisorConfig) -> Self {
+        let shared = Arc::new(Shared {
+            name: name.into(),
+            spec,
+            config,
+            slot: tokio::sync::Mutex::new(None),
+            status: Mutex::new(SupervisorStatus {
+                state: SupervisorState::Starting,
+                restarts: 0,
+                last_exit_code: None,
+                last_error: None,
+                heartbeat: HeartbeatState::Idle,
+                hello: None,
+            }),
+            ready: Notify::new(),
+            taken: Notify::new(),
+        });
+        let task = tokio::spawn(supervise(Arc::clone(&shared)));
+        Self {
+            shared,
+            task: Some(task),
+        }
+    }
+
+    /// The name the sidecar is supervised under.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.shared.name
+    }
+
+    /// Current state, restart count, and last failure.
+    #[must_use]
+    pub fn status(&self) -> SupervisorStatus {
+        self.shared.status()
+    }
+
+    /// Take the warm sidecar, waiting for one to become ready.
+    ///
+    /// A replacement is started as soon as the sidecar is taken.
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::BackendCrashed`] once the supervisor has given up or
+    /// stopped, and [`ErrorCode::BackendUnavailable`] when no sidecar is
+    /// ready within the restart policy's `overall_timeout`.
+    pub async fn checkout(&self) -> Result<SidecarClient, AbpError> {
+        let wait = self.shared.config.restart.overall_timeout;
+        let take = async {
+            loop {
+                let ready = self.shared.ready.notified();
+                tokio::pin!(ready);
+                ready.as_mut().enable();
+                if let Some(client) = self.shared.slot.lock().await.take() {
+                    self.shared.taken.notify_one();
+                    return Ok(client);
+                }
+                match self.shared.status().state {
+                    SupervisorState::Failed { reason, code } => {
+                        return Err(self.shared.error(code, format!("is down: {reason}")));
+                    }
+                    SupervisorState::Stopped => {
+                        return Err(self.shared.crashed("supervisor is stopped"));
+                    }
+                    _ => ready.await,
+                }
+            }
+        };
+        tokio::time::timeout(wait, take).await.unwrap_or_else(|_| {
+            Err(self.shared.error(
+                ErrorCode::BackendUnavailable,
+                format!("not ready within {wait:?}"),
+            ))
+        })
+    }
+
+    /// Stop supervising and kill the idle sidecar.
+    pub async fn shutdown(mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+            let _ = task.await;
+        }
+        if let Some(client) = self.shared.slot.lock().await.take() {
+            client.kill().await;
+        }
+        self.shared.update(|s| s.state = SupervisorState::Stopped);
+        self.shared.ready.notify_waiters();
+    }
+
+    /// Turn a failure of a checked-out sidecar into an error, recording
+    /// its exit code.
+    fn run_failed(&self, err: HostError) -> anyhow::Error {
+        let code = match &err {
+            HostError::Exited { code } => *code,
+            HostError::SidecarCrashed { exit_code, .. } => *exit_code,
+            _ => None,
+        };
+        if code.is_some() {
+            self.shared.update(|s| s.last_exit_code = code);
+        }
+        let message = match code {
+            Some(code) => format!("exited with code {code} during the run"),
+            None => format!("failed during the run: {err}"),
+        };
+        anyhow::Error::new(self.shared.crashed(message).with_source(err))
+    }
+}
+
+impl Drop for Supervisor {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for Supervisor {
+    fn identity(&self) -> BackendIdentity {
+        match self.shared.status().hello {
+            Some(hello) => hello.backend,
+            None => BackendIdentity {
+                id: "sidecar".to_string(),
+                backend_version: None,
+                adapter_version: Some("0.1".to_string()),
+            },
+        }
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        self.shared
+            .status()
+            .hello
+            .map(|h| h.capabilities)
+            .unwrap_or_default()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> Result<Receipt> {
+        let client = self.checkout().await?;
+
+        ensure_capability_requirements(&work_order.requirements, &client.hello.capabilities)
+            .context("capability requirements not satisfied")?;
+
+        let mut run = client
+            .run(run_id.to_string(), work_order)
+            .await
+            .map_err(|e| self.run_failed(e))?;
+        while let Some(ev) = run.events.next().await {
+            let _ = events_tx.send(ev).await;
+        }
+
+        match run.receipt.await {
+            Ok(Ok(receipt)) => {
+                let _ = run.wait.await;
+                Ok(receipt)
+            }
+            Ok(Err(e)) => Err(self.run_failed(e)),
+            // The reader stopped without a receipt: the process went away.
+            Err(_) => match run.wait.await {
+                Ok(Err(e)) => Err(self.run_failed(e)),
+                _ => Err(self.run_failed(HostError::Exited { code: None })),
+            },
+        }
+    }
+}
+
+/// Why a supervised sidecar has to be replaced.
+struct Failure {
+    reason: String,
+    exit_code: Option<i32>,
+    /// Anything but `BackendCrashed` fails again on restart, so the
+    /// supervisor gives up at once.
+    code: ErrorCode,
+}
+
+/// The supervision loop: start a sidecar, watch it while idle, and replace
+/// it when it is taken or dies.
+async fn supervise(shared: Arc<Shared>) {
+    let config = &shared.config;
+    let mut failures = 0u32;
+    loop {
+        shared.update(|s| {
+            s.state = SupervisorState::Starting;
+            s.heartbeat = HeartbeatState::Idle;
+        });
+        let failure = match start(&shared).await {
+            Ok(client) => {
+                debug!(target: "abp.supervisor", "sidecar '{}' ready", shared.name);
+                *shared.slot.lock().await = Some(client);
+                shared.update(|s| s.state = SupervisorState::Ready);
+                shared.ready.notify_waiters();
+                match watch(&shared, &mut failures).await {
+                    Some(failure) => failure,
+                    // Taken by a run: start a replacement straight away.
+                    None => continue,
+                }
+            }
+            Err(failure) => failure,
+        };
+
+        failures += 1;
+        warn!(
+            target: "abp.supervisor",
+            "sidecar '{}' failed ({} in a row): {}", shared.name, failures, failure.reason
+        );
+        shared.update(|s| {
+            s.last_error = Some(failure.reason.clone());
+            if failure.exit_code.is_some() {
+                s.last_exit_code = failure.exit_code;
+            }
+        });
+        if failures > config.restart.max_retries || failure.code != ErrorCode::BackendCrashed {
+            shared.update(|s| {
+                s.state = SupervisorState::Failed {
+                    reason: failure.reason,
+                    code: failure.code,
+                }
+            });
+            shared.ready.notify_waiters();
+            return;
+        }
+        let delay = compute_delay(&config.restart, failures - 1);
+        shared.update(|s| {
+            s.restarts += 1;
+            s.state = SupervisorState::Restarting {
+                attempt: failures,
+                delay_ms: delay.as_millis() as u64,
+            };
+        });
+        tokio::time::sleep(delay).await;
+    }
+}
+
+/// Spawn the sidecar and complete its handshake.
+async fn start(shared: &Shared) -> Result<SidecarClient, Failure> {
+    let timeout = shared.config.handshake_timeout();
+    let started = tokio::time::timeout(timeout, SidecarClient::spawn(shared.spec.clone())).await;
+    match started {
+        Ok(Ok(client)) => {
+            let hello = client.hello.clone();
+            shared.update(|s| s.hello = Some(hello));
+            Ok(client)
+        }
+        Ok(Err(e)) => Err(Failure {
+            exit_code: match &e {
+                HostError::Exited { code } => *code,
+                _ => None,
+            },
+            reason: format!("handshake failed: {e}"),
+            code: match e.error_code() {
+                ErrorCode::ProtocolVersionMismatch => ErrorCode::ProtocolVersionMismatch,
+                _ => ErrorCode::BackendCrashed,
+            },
+        }),
+        Err(_) => Err(Failure {
+            reason: format!("no hello within {timeout:?}"),
+            exit_code: None,
+            code: ErrorCode::BackendCrashed,
+        }),
+    }
+}
+
+/// Watch the idle sidecar until a run takes it (`None`) or it has to be
+/// replaced. Each passed liveness check resets `failures`.
+async fn watch(shared: &Shared, failures: &mut u32) -> Option<Failure> {
+    let config = &shared.config.heartbeat;
+    let mut monitor = HeartbeatMonitor::new(config.clone());
+    loop {
+        tokio::select! {
+            () = shared.taken.notified() => {}
+            () = tokio::time::sleep(config.interval()) => {}
+        }
+        let mut slot = shared.slot.lock().await;
+        let client = slot.as_mut()?;
+
+        let failure = match client.try_wait() {
+            Ok(Some(status)) => Some(Failure {
+                reason: match status.code() {
+                    Some(code) => format!("exited with code {code} while idle"),
+                    None => format!("exited while idle ({status})"),
+                },
+                exit_code: status.code(),
+                code: ErrorCode::BackendCrashed,
+            }),
+            Ok(None) if client.features.heartbeat => {
+                let ping = monitor.next_ping();
+                match client.ping(ping.seq, config.timeout()).await {
+                    Ok(_) => {
+                        monitor.record_pong(ping.seq);
+                        None
+                    }
+                    Err(HostError::Timeout { .. }) => {
+                        monitor.record_miss();
+                        monitor.is_stalled().then(|| Failure {
+                            reason: format!("stalled: {} heartbeats missed", config.max_missed()),
+                            exit_code: None,
+                            code: ErrorCode::BackendCrashed,
+                        })
+                    }
+                    Err(e) => Some(Failure {
+                        exit_code: match &e {
+                            HostError::Exited { code } => *code,
+                            _ => None,
+                        },
+                        reason: format!("heartbeat failed: {e}"),
+                        code: ErrorCode::BackendCrashed,
+                    }),
+                }
+            }
+            Ok(None) => None,
+            Err(e) => Some(Failure {
+                reason: e.to_string(),
+                exit_code: None,
+                code: ErrorCode::BackendCrashed,
+            }),
+        };
+        shared.update(|s| s.heartbeat = monitor.state().clone());
+
+        match failure {
+            Some(failure) => {
+                if let Some(client) = slot.take() {
+                    drop(slot);
+                    client.kill().await;
+                }
+                return Some(failure);
+            }
+            None if !matches!(monitor.state(), HeartbeatState::Degraded { .. }) => {
+                *failures = 0;
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,112 @@
+"""Mock sidecar for the supervisor tests.
+
+Modes:
+  ok           - hello (with heartbeat) → answers pings → run → event → final
+  no_pong      - hello (with heartbeat) → ignores pings → run → final
+  crash        - hello → exit 3
+  crash_once   - like crash on the first start (marker file argv[2]), then ok
+  crash_in_run - hello → run → exit 4
+  wrong_major  - hello with contract version abp/v999.0 → serve
+"""
+import datetime
+import json
+import os
+import sys
+
+mode = sys.argv[1] if len(sys.argv) > 1 else "ok"
+
+
+def emit(obj):
+    print(json.dumps(obj), flush=True)
+
+
+def now_ts():
+    return datetime.datetime.now(datetime.timezone.utc).isoformat()
+
+
+def hello(version="abp/v0.1"):
+    return {
+        "t": "hello",
+        "contract_version": version,
+        "backend": {
+            "id": "mock-supervised",
+            "backend_version": "0.1",
+            "adapter_version": "0.1",
+        },
+        "capabilities": {},
+        "mode": "mapped",
+        "features": {"heartbeat": True},
+    }
+
+
+def final(ref_id):
+    now = now_ts()
+    receipt = {
+        "meta": {
+            "run_id": ref_id,
+            "work_order_id": "00000000-0000-0000-0000-000000000000",
+            "contract_version": "abp/v0.1",
+            "started_at": now,
+            "finished_at": now,
+            "duration_ms": 0,
+        },
+        "backend": {
+            "id": "mock-supervised",
+            "backend_version": "0.1",
+            "adapter_version": "0.1",
+        },
+        "capabilities": {},
+        "mode": "mapped",
+        "usage_raw": {},
+        "usage": {"input_tokens": 0, "output_tokens": 0},
+        "trace": [],
+        "artifacts": [],
+        "verification": {"harness_ok": True},
+        "outcome": "complete",
+        "receipt_sha256": None,
+    }
+    return {"t": "final", "ref_id": ref_id, "receipt": receipt}
+
+
+def serve(answer_pings=True):
+    for line in sys.stdin:
+        msg = json.loads(line)
+        if msg.get("t") == "ping":
+            if answer_pings:
+                emit({"t": "pong", "seq": msg["seq"]})
+        elif msg.get("t") == "run":
+            ref_id = msg["id"]
+            event = {"ts": now_ts(), "type": "run_started", "message": "supervised"}
+            emit({"t": "event", "ref_id": ref_id, "event": event})
+            emit(final(ref_id))
+            return
+
+
+if mode == "ok":
+    emit(hello())
+    serve()
+
+elif mode == "no_pong":
+    emit(hello())
+    serve(answer_pings=False)
+
+elif mode == "crash":
+    emit(hello())
+    sys.exit(3)
+
+elif mode == "crash_once":
+    marker = sys.argv[2]
+    emit(hello())
+    if not os.path.exists(marker):
+        open(marker, "w").close()
+        sys.exit(3)
+    serve()
+
+elif mode == "crash_in_run":
+    emit(hello())
+    sys.stdin.readline()
+    sys.exit(4)
+
+elif mode == "wrong_major":
+    emit(hello("abp/v999.0"))
+    serve()
@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the sidecar supervisor against a mock Python sidecar.
+
+use std::time::Duration;
+
+use abp_core::WorkOrderBuilder;
+use abp_error::{AbpError, ErrorCode};
+use abp_host::SidecarSpec;
+use abp_host::retry::RetryConfig;
+use abp_integrations::Backend;
+use abp_integrations::supervisor::{
+    Supervisor, SupervisorConfig, SupervisorState, SupervisorStatus,
+};
+use abp_protocol::heartbeat::{HeartbeatConfig, HeartbeatState};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+fn python_cmd() -> Option<String> {
+    for cmd in &["python3", "python"] {
+        if std::process::Command::new(cmd)
+            .arg("--version")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok()
+        {
+            return Some(cmd.to_string());
+        }
+    }
+    None
+}
+
+fn spec(py: &str, args: &[&str]) -> SidecarSpec {
+    let script = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("supervisor_sidecar.py");
+    let mut spec = SidecarSpec::new(py);
+    spec.args = std::iter::once(script.to_string_lossy().into_owned())
+        .chain(args.iter().map(|a| a.to_string()))
+        .collect();
+    spec
+}
+
+fn config() -> SupervisorConfig {
+    SupervisorConfig {
+        heartbeat: HeartbeatConfig::new(Duration::from_millis(50), Duration::from_millis(200), 2),
+        restart: RetryConfig {
+            max_retries: 2,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_millis(50),
+            overall_timeout: Duration::from_secs(10),
+            jitter_factor: 0.0,
+        },
+        handshake_timeout_ms: 10_000,
+    }
+}
+
+/// Poll the supervisor until `done` holds, or fail after ten seconds.
+async fn wait_for(sup: &Supervisor, done: impl Fn(&SupervisorStatus) -> bool) -> SupervisorStatus {
+    for _ in 0..200 {
+        let status = sup.status();
+        if done(&status) {
+            return status;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!(
+        "supervisor never reached the expected state: {:?}",
+        sup.status()
+    );
+}
+
+async fn run(sup: &Supervisor) -> anyhow::Result<abp_core::Receipt> {
+    let (tx, mut rx) = mpsc::channel(16);
+    let receipt = sup
+        .run(Uuid::new_v4(), WorkOrderBuilder::new("t").build(), tx)
+        .await;
+    while rx.try_recv().is_ok() {}
+    receipt
+}
+
+#[tokio::test]
+async fn runs_on_a_warm_sidecar_and_replaces_it() {
+    let Some(py) = python_cmd() else { return };
+    let sup = Supervisor::start("mock", spec(&py, &["ok"]), config());
+
+    let status = wait_for(&sup, |s| s.heartbeat == HeartbeatState::Alive).await;
+    assert_eq!(status.state, SupervisorState::Ready);
+    assert_eq!(sup.identity().id, "mock-supervised");
+
+    for _ in 0..2 {
+        let receipt = run(&sup).await.unwrap();
+        assert_eq!(receipt.backend.id, "mock-supervised");
+    }
+    assert_eq!(sup.status().restarts, 0);
+    sup.shutdown().await;
+}
+
+#[tokio::test]
+async fn crashed_sidecars_are_restarted() {
+    let Some(py) = python_cmd() else { return };
+    let marker = std::env::temp_dir().join(format!("abp-supervisor-{}", Uuid::new_v4()));
+    let sup = Supervisor::start(
+        "mock",
+        spec(&py, &["crash_once", &marker.to_string_lossy()]),
+        config(),
+    );
+
+    let status = wait_for(&sup, |s| {
+        s.restarts == 1 && s.heartbeat == HeartbeatState::Alive
+    })
+    .await;
+    assert_eq!(status.last_exit_code, Some(3));
+    assert!(run(&sup).await.is_ok());
+    sup.shutdown().await;
+    let _ = std::fs::remove_file(marker);
+}
+
+#[tokio::test]
+async fn gives_up_after_max_retries() {
+    let Some(py) = python_cmd() else { return };
+    let sup = Supervisor::start("mock", spec(&py, &["crash"]), config());
+
+    let status = wait_for(&sup, |s| matches!(s.state, SupervisorState::Failed { .. })).await;
+    assert_eq!(status.restarts, 2);
+
+    let err = sup.checkout().await.unwrap_err();
+    assert_eq!(err.code, ErrorCode::BackendCrashed);
+    assert_eq!(err.context["exit_code"], 3);
+    assert_eq!(err.context["restarts"], 2);
+    assert_eq!(err.context["command"], py.as_str());
+
+    let err = run(&sup).await.unwrap_err();
+    let err = err.downcast_ref::<AbpError>().expect("an AbpError");
+    assert_eq!(err.code, ErrorCode::BackendCrashed);
+}
+
+#[tokio::test]
+async fn gives_up_at_once_on_an_incompatible_contract_version() {
+    let Some(py) = python_cmd() else { return };
+    let sup = Supervisor::start("mock", spec(&py, &["wrong_major"]), config());
+
+    let status = wait_for(&sup, |s| matches!(s.state, SupervisorState::Failed { .. })).await;
+    assert_eq!(status.restarts, 0);
+
+    let err = run(&sup).await.unwrap_err();
+    let err = err.downcast_ref::<AbpError>().expect("an AbpError");
+    assert_eq!(err.code, ErrorCode::ProtocolVersionMismatch);
+    assert!(err.message.contains("abp/v999.0"), "{}", err.message);
+}
+
+#[tokio::test]
+async fn stalled_sidecars_are_replaced() {
+    let Some(py) = python_cmd() else { return };
+    let mut config = config();
+    config.heartbeat =
+        HeartbeatConfig::new(Duration::from_millis(20), Duration::from_millis(20), 2);
+    let sup = Supervisor::start("mock", spec(&py, &["no_pong"]), config);
+
+    let status = wait_for(&sup, |s| s.restarts >= 1).await;
+    let error = status.last_error.unwrap();
+    assert!(error.contains("stalled"), "{error}");
+    sup.shutdown().await;
+}
+
+#[tokio::test]
+async fn crashes_during_a_run_carry_the_exit_code() {
+    let Some(py) = python_cmd() else { return };
+    let sup = Supervisor::start("mock", spec(&py, &["crash_in_run"]), config());
+
+    let err = run(&sup).await.unwrap_err();
+    let err = err.downcast_ref::<AbpError>().expect("an AbpError");
+    assert_eq!(err.code, ErrorCode::BackendCrashed);
+    assert_eq!(err.context["exit_code"], 4);
+    assert!(
+        err.message.contains("exited with code 4"),
+        "{}",
+        err.message
+    );
+    sup.shutdown().await;
+}
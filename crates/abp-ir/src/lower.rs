@@ -7,6 +7,7 @@
 //! network calls — just data reshaping.
 
 use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole, IrToolDefinition};
+use abp_core::tool_result::ToolResultKind;
 use abp_sdk_types::Dialect;
 
 // ── Role mapping ───────────────────────────────────────────────────────
@@ -222,8 +223,8 @@ fn lower_claude_content_block(block: &IrContentBlock) -> serde_json::Value {
             let inner: Vec<serde_json::Value> = content
                 .iter()
                 .map(|c| match c {
-                    IrContentBlock::Text { text } => {
-                        serde_json::json!({"type": "text", "text": text})
+                    IrContentBlock::Text { .. } | IrContentBlock::Image { .. } => {
+                        lower_claude_content_block(c)
                     }
                     _ => serde_json::json!({"type": "unknown"}),
                 })
@@ -268,8 +269,11 @@ pub fn lower_to_gemini(conv: &IrConversation, tools: &[IrToolDefinition]) -> ser
         .filter(|m| m.role != IrRole::System)
         .map(|m| {
             let role = ir_role_to_dialect(m.role, Dialect::Gemini);
-            let parts: Vec<serde_json::Value> =
-                m.content.iter().filter_map(lower_gemini_part).collect();
+            let parts: Vec<serde_json::Value> = m
+                .content
+                .iter()
+                .filter_map(|b| lower_gemini_part(m, b))
+                .collect();
             serde_json::json!({"role": role, "parts": parts})
         })
         .collect();
@@ -295,7 +299,11 @@ pub fn lower_to_gemini(conv: &IrConversation, tools: &[IrToolDefinition]) -> ser
 }
 
 /// Lower a single IR content block to a Gemini `part`, if representable.
-fn lower_gemini_part(block: &IrContentBlock) -> Option<serde_json::Value> {
+///
+/// Tool results recorded as JSON or tables in `msg` (see
+/// [`IrMessage::tool_result_format`]) are sent as structured responses
+/// rather than strings.
+fn lower_gemini_part(msg: &IrMessage, block: &IrContentBlock) -> Option<serde_json::Value> {
     match block {
         IrContentBlock::Text { text } => Some(serde_json::json!({"text": text})),
         IrContentBlock::Image { media_type, data } => {
@@ -317,10 +325,15 @@ fn lower_gemini_part(block: &IrContentBlock) -> Option<serde_json::Value> {
                 })
                 .collect::<Vec<_>>()
                 .join("");
+            let structured = msg
+                .tool_result_format(tool_use_id)
+                .filter(|f| matches!(f.kind, ToolResultKind::Json | ToolResultKind::Table))
+                .and_then(|_| serde_json::from_str::<serde_json::Value>(&text).ok());
+            let result = structured.unwrap_or_else(|| serde_json::json!(text));
             Some(serde_json::json!({
                 "functionResponse": {
                     "name": tool_use_id,
-                    "response": {"result": text},
+                    "response": {"result": result},
                 }
             }))
         }
@@ -607,4 +620,59 @@ mod tests {
         assert_eq!(lower_to_codex(&conv, &tools), openai);
         assert_eq!(lower_to_copilot(&conv, &tools), openai);
     }
+
+    // ── Typed tool results ─────────────────────────────────────────────
+
+    fn tool_result_conv(
+        output: serde_json::Value,
+        format: abp_core::tool_result::ToolResultFormat,
+    ) -> IrConversation {
+        let mut event = abp_core::AgentEvent {
+            ts: chrono::Utc::now(),
+            kind: abp_core::AgentEventKind::ToolResult {
+                tool_name: "query".into(),
+                tool_use_id: Some("tu_1".into()),
+                output,
+                is_error: false,
+            },
+            ext: None,
+        };
+        format.attach(&mut event);
+        IrConversation::new().push(IrMessage::from_tool_result(&event).unwrap())
+    }
+
+    #[test]
+    fn gemini_receives_table_results_as_structured_json() {
+        use abp_core::tool_result::{ToolResultFormat, ToolResultKind};
+        let rows = serde_json::json!([{"id": 1}, {"id": 2}]);
+        let conv = tool_result_conv(rows.clone(), ToolResultFormat::new(ToolResultKind::Table));
+        let lowered = lower_to_gemini(&conv, &[]);
+        assert_eq!(
+            lowered["contents"][0]["parts"][0]["functionResponse"]["response"]["result"],
+            rows
+        );
+
+        let conv = tool_result_conv(
+            serde_json::json!("[1, 2]"),
+            ToolResultFormat::new(ToolResultKind::Text),
+        );
+        let lowered = lower_to_gemini(&conv, &[]);
+        assert_eq!(
+            lowered["contents"][0]["parts"][0]["functionResponse"]["response"]["result"],
+            "[1, 2]"
+        );
+    }
+
+    #[test]
+    fn claude_receives_binary_image_results_as_image_blocks() {
+        use abp_core::tool_result::ToolResultFormat;
+        let conv = tool_result_conv(
+            serde_json::json!("iVBORw0KGgo="),
+            ToolResultFormat::binary("image/png"),
+        );
+        let lowered = lower_to_claude(&conv, &[]);
+        let inner = &lowered["messages"][0]["content"][0]["content"][0];
+        assert_eq!(inner["type"], "image");
+        assert_eq!(inner["source"]["media_type"], "image/png");
+    }
 }
@@ -1661,6 +1661,7 @@ fn claude_tool_result_unknown_nested_block() {
                     media_type: "image/png".into(),
                     data: "abc".into(),
                 },
+                IrContentBlock::Thinking { text: "hmm".into() },
             ],
             is_error: false,
         }],
@@ -1670,7 +1671,9 @@ fn claude_tool_result_unknown_nested_block() {
         .as_array()
         .unwrap();
     assert_eq!(inner[0]["type"], "text");
-    assert_eq!(inner[1]["type"], "unknown");
+    assert_eq!(inner[1]["type"], "image");
+    assert_eq!(inner[1]["source"]["data"], "abc");
+    assert_eq!(inner[2]["type"], "unknown");
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -8,11 +8,14 @@
 use abp_core::{
     AgentEvent, AgentEventKind, Capability, CapabilityManifest, SupportLevel, WorkOrder,
 };
+use abp_sidecar_sdk::VendorConfig;
 use chrono::Utc;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use crate::vendor::KimiVendorConfig;
+
 /// Version string for this dialect adapter.
 pub const DIALECT_VERSION: &str = "kimi/v0.1";
 
@@ -491,14 +494,17 @@ pub struct KimiChunkFunctionCall {
 
 /// Map an ABP [`WorkOrder`] to a [`KimiRequest`].
 ///
-/// Uses the work order task as the initial user message and applies
-/// config defaults where the work order does not specify overrides.
-/// Tool definitions from the work order are translated to Kimi format.
+/// Uses the work order task as the initial user message. Knobs under
+/// `config.vendor.kimi` (see [`KimiVendorConfig`]) override the config
+/// defaults; other backends' namespaces are ignored. Tool definitions from
+/// the work order are translated to Kimi format.
 pub fn map_work_order(wo: &WorkOrder, config: &KimiConfig) -> KimiRequest {
+    let vendor = KimiVendorConfig::from_work_order_or_default(wo);
     let model = wo
         .config
         .model
         .as_deref()
+        .or(vendor.model.as_deref())
         .unwrap_or(&config.model)
         .to_string();
 
@@ -520,16 +526,16 @@ pub fn map_work_order(wo: &WorkOrder, config: &KimiConfig) -> KimiRequest {
         tool_calls: None,
     });
 
-    // Enable search if config says so
-    let use_search = config
+    // Enable search if the vendor namespace or config says so
+    let use_search = vendor.use_search.or(config
         .use_k1_reasoning
-        .and_then(|v| if v { Some(true) } else { None });
+        .and_then(|v| if v { Some(true) } else { None }));
 
     KimiRequest {
         model,
         messages,
-        max_tokens: config.max_tokens,
-        temperature: config.temperature,
+        max_tokens: vendor.max_tokens.or(config.max_tokens),
+        temperature: vendor.temperature.or(config.temperature),
         stream: None,
         tools: None,
         use_search,
@@ -18,9 +18,13 @@ pub mod lowering;
 /// Model listing types for the Moonshot Kimi Models API.
 pub mod models;
 pub mod types;
+/// Schema for the `config.vendor.kimi` namespace, checked before dispatch.
+pub mod vendor;
 
 use abp_runtime::Runtime;
-use abp_sidecar_sdk::{register_sidecar_backend, sidecar_script as resolve_sidecar_script};
+use abp_sidecar_sdk::{
+    VendorNamespace, resolve_sidecar_backend, sidecar_script as resolve_sidecar_script,
+};
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 
@@ -49,16 +53,22 @@ pub fn register_backend(
     host_root: &Path,
     command_override: Option<&str>,
 ) -> Result<bool> {
-    register_sidecar_backend(
-        runtime,
-        backend_name,
+    let backend = resolve_sidecar_backend(
         host_root,
         HOST_SCRIPT_RELATIVE,
         command_override,
         DEFAULT_NODE_COMMAND,
         "Kimi",
     )
-    .with_context(|| format!("resolve Kimi command for {backend_name}"))
+    .with_context(|| format!("resolve Kimi command for {backend_name}"))?;
+    let Some(backend) = backend else {
+        return Ok(false);
+    };
+    runtime.register_backend(
+        backend_name,
+        backend.with_vendor_namespace(VendorNamespace::of::<vendor::KimiVendorConfig>()),
+    );
+    Ok(true)
 }
 
 /// Resolve the host script path for a given runtime root.
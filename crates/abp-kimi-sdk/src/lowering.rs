@@ -3,6 +3,9 @@
 //!
 //! [`to_ir`] converts a slice of [`KimiMessage`]s into an [`IrConversation`],
 //! and [`from_ir`] converts an [`IrConversation`] back into Kimi messages.
+//!
+//! Cache breakpoints ([`IrMessage::cache_control`](abp_core::ir::IrMessage::cache_control)) are dropped, as Moonshot
+//! applies context caching to repeated prefixes on its own.
 
 use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole, IrUsage};
 
@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Schema for the `config.vendor.kimi` namespace.
+
+use abp_sidecar_sdk::VendorConfig;
+use abp_sidecar_sdk::vendor::check_range;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Kimi knobs accepted under `config.vendor.kimi`.
+///
+/// Covers the request parameters used by lowering, the search and plugin
+/// settings written by request conversion, and the settings read by the
+/// Kimi sidecar host, including its camelCase spellings. Unknown keys are
+/// rejected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct KimiVendorConfig {
+    /// Model override, used when the work order does not set one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Sampling temperature (0–1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Nucleus sampling probability (0–1).
+    #[serde(default, alias = "topP", skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Maximum output tokens.
+    #[serde(
+        default,
+        alias = "maxTokens",
+        alias = "token_limit",
+        alias = "tokenLimit",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_tokens: Option<u32>,
+    /// Whether to stream the response.
+    #[serde(default, alias = "streaming", skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    /// Source dialect tag written by request conversion.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dialect: Option<String>,
+    /// Enable Kimi's built-in web search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_search: Option<bool>,
+    /// Options for the built-in web search.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_options: Option<Value>,
+    /// Uploaded files to use as reference context.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ref_file_ids: Option<Vec<String>>,
+    /// Plugins to enable by id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin_ids: Option<Vec<String>>,
+    /// Inline plugin definitions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugins: Option<Vec<Value>>,
+    /// Thinking mode forwarded to the host.
+    #[serde(
+        default,
+        alias = "thinkingMode",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub thinking_mode: Option<String>,
+    /// Reasoning effort forwarded to the host.
+    #[serde(
+        default,
+        alias = "reasoningEffort",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub reasoning_effort: Option<String>,
+    /// Agent mode forwarded to the host.
+    #[serde(default, alias = "agentMode", skip_serializing_if = "Option::is_none")]
+    pub agent_mode: Option<String>,
+    /// Run the agent as a swarm.
+    #[serde(default, alias = "agentSwarm", skip_serializing_if = "Option::is_none")]
+    pub agent_swarm: Option<bool>,
+    /// Approve all tool calls without asking.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yolo: Option<bool>,
+    /// Request timeout in milliseconds.
+    #[serde(default, alias = "timeoutMs", skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// Attempts before giving up on a failed request.
+    #[serde(
+        default,
+        alias = "retryAttempts",
+        alias = "retries",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retry_attempts: Option<u32>,
+    /// Base delay between retries in milliseconds.
+    #[serde(
+        default,
+        alias = "retryBaseDelayMs",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retry_base_delay_ms: Option<u64>,
+    /// API key for the Moonshot API.
+    #[serde(default, alias = "apiKey", skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// API base URL.
+    #[serde(default, alias = "baseUrl", skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+}
+
+impl VendorConfig for KimiVendorConfig {
+    const NAMESPACE: &'static str = "kimi";
+
+    fn check(&self) -> Result<(), String> {
+        check_range("temperature", self.temperature, 0.0..=1.0)?;
+        check_range("top_p", self.top_p, 0.0..=1.0)
+    }
+}
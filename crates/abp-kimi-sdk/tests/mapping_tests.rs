@@ -68,6 +68,21 @@ fn work_order_falls_back_to_config_model() {
     assert_eq!(req.model, cfg.model);
 }
 
+#[test]
+fn kimi_vendor_namespace_enables_search() {
+    let mut wo = WorkOrderBuilder::new("task").build();
+    wo.config.vendor = serde_json::from_value(serde_json::json!({
+        "kimi.use_search": true,
+        "kimi.max_tokens": 256,
+        "openai": {"max_tokens": 9999},
+    }))
+    .unwrap();
+    let req = map_work_order(&wo, &KimiConfig::default());
+
+    assert_eq!(req.use_search, Some(true));
+    assert_eq!(req.max_tokens, Some(256));
+}
+
 #[test]
 fn context_snippets_are_included_in_user_message() {
     let ctx = ContextPacket {
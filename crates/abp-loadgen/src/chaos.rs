@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! A backend that simulates provider latency, tool traffic, and failures.
+
+use std::time::Duration;
+
+use abp_backend_core::{Backend, extract_seed};
+use abp_core::{
+    AgentEvent, AgentEventKind, BackendIdentity, CapabilityManifest, Outcome, Receipt,
+    VerificationReport, WorkOrder,
+};
+use abp_receipt::ReceiptBuilder;
+use anyhow::{Result, bail};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::mix::WorkloadKind;
+use crate::rng::SplitMix64;
+
+/// How a [`ChaosBackend`] misbehaves.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ChaosConfig {
+    /// Fixed delay before the first event, like a provider's time to first
+    /// token.
+    pub latency: Duration,
+    /// Up to this much extra delay, drawn uniformly per run.
+    pub jitter: Duration,
+    /// Fraction of runs, in `[0, 1]`, that fail without a receipt.
+    pub failure_rate: f64,
+    /// Extra delay per KiB of context, so long-context work is slower.
+    pub per_context_kib: Duration,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            latency: Duration::from_millis(5),
+            jitter: Duration::from_millis(20),
+            failure_rate: 0.0,
+            per_context_kib: Duration::from_micros(100),
+        }
+    }
+}
+
+/// Backend that shapes its output by the work order's [`WorkloadKind`].
+///
+/// * Chat runs stream a handful of text deltas and a final message.
+/// * Tool-heavy runs make 5–15 tool calls, each followed by its result.
+/// * Long-context runs are delayed in proportion to their context size.
+///
+/// Delays are slept, not spun, so thousands of runs can be in flight at
+/// once. Which runs fail and how long each takes are drawn from the work
+/// order's seed when it has one, so a seeded load run is repeatable.
+///
+/// Receipts report a clean workspace, so the runtime does not probe git
+/// for every run.
+#[derive(Debug, Clone, Default)]
+pub struct ChaosBackend {
+    config: ChaosConfig,
+}
+
+impl ChaosBackend {
+    /// A backend that misbehaves as `config` says.
+    #[must_use]
+    pub fn new(config: ChaosConfig) -> Self {
+        Self { config }
+    }
+
+    /// The configuration in use.
+    #[must_use]
+    pub fn config(&self) -> &ChaosConfig {
+        &self.config
+    }
+
+    fn delay(&self, rng: &mut SplitMix64, work_order: &WorkOrder) -> Duration {
+        let context_bytes: usize = work_order
+            .context
+            .snippets
+            .iter()
+            .map(|s| s.content.len())
+            .sum();
+        let kib = u32::try_from(context_bytes / 1024).unwrap_or(u32::MAX);
+        self.config.latency
+            + self.config.jitter.mul_f64(rng.unit())
+            + self.config.per_context_kib.saturating_mul(kib)
+    }
+}
+
+#[async_trait]
+impl Backend for ChaosBackend {
+    fn identity(&self) -> BackendIdentity {
+        BackendIdentity {
+            id: "chaos".to_string(),
+            backend_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            adapter_version: None,
+        }
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        use abp_core::{Capability as C, SupportLevel as S};
+        let mut m = CapabilityManifest::default();
+        m.insert(C::Streaming, S::Native);
+        m.insert(C::ToolRead, S::Native);
+        m.insert(C::ToolBash, S::Native);
+        m
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> Result<Receipt> {
+        let started = Utc::now();
+        let seed = extract_seed(&work_order).unwrap_or_else(|| run_id.as_u64_pair().0);
+        let mut rng = SplitMix64::new(seed);
+        let kind = WorkloadKind::of(&work_order).unwrap_or(WorkloadKind::Chat);
+
+        emit(
+            &events_tx,
+            AgentEventKind::RunStarted {
+                message: format!("chaos {kind} run"),
+            },
+        )
+        .await;
+        tokio::time::sleep(self.delay(&mut rng, &work_order)).await;
+
+        if rng.unit() < self.config.failure_rate {
+            emit(
+                &events_tx,
+                AgentEventKind::Error {
+                    message: "injected failure".into(),
+                    error_code: None,
+                },
+            )
+            .await;
+            bail!("chaos backend injected a failure");
+        }
+
+        let mut output_tokens = 0u64;
+        match kind {
+            WorkloadKind::Chat | WorkloadKind::LongContext => {
+                for i in 0..4 + rng.below(8) {
+                    emit(
+                        &events_tx,
+                        AgentEventKind::AssistantDelta {
+                            text: format!("chunk {i} "),
+                        },
+                    )
+                    .await;
+                    output_tokens += 8;
+                }
+            }
+            WorkloadKind::ToolHeavy => {
+                for i in 0..5 + rng.below(11) {
+                    let tool_use_id = format!("call-{i}");
+                    emit(
+                        &events_tx,
+                        AgentEventKind::ToolCall {
+                            tool_name: "bash".into(),
+                            tool_use_id: Some(tool_use_id.clone()),
+                            parent_tool_use_id: None,
+                            input: serde_json::json!({ "command": format!("step {i}") }),
+                        },
+                    )
+                    .await;
+                    emit(
+                        &events_tx,
+                        AgentEventKind::ToolResult {
+                            tool_name: "bash".into(),
+                            tool_use_id: Some(tool_use_id),
+                            output: serde_json::json!("ok"),
+                            is_error: false,
+                        },
+                    )
+                    .await;
+                    output_tokens += 24;
+                }
+            }
+        }
+        emit(
+            &events_tx,
+            AgentEventKind::AssistantMessage {
+                text: "done".into(),
+            },
+        )
+        .await;
+        emit(
+            &events_tx,
+            AgentEventKind::RunCompleted {
+                message: "chaos run complete".into(),
+            },
+        )
+        .await;
+
+        let input_tokens = (work_order.task.len()
+            + work_order
+                .context
+                .snippets
+                .iter()
+                .map(|s| s.content.len())
+                .sum::<usize>()) as u64
+            / 4;
+        Ok(ReceiptBuilder::new("chaos")
+            .backend_version(env!("CARGO_PKG_VERSION"))
+            .capabilities(self.capabilities())
+            .run_id(run_id)
+            .work_order_id(work_order.id)
+            .started_at(started)
+            .finished_at(Utc::now())
+            .outcome(Outcome::Complete)
+            .usage_tokens(input_tokens, output_tokens)
+            .verification(VerificationReport {
+                git_diff: Some(String::new()),
+                git_status: Some(String::new()),
+                harness_ok: true,
+                input_digest: None,
+            })
+            .build())
+    }
+}
+
+async fn emit(events_tx: &mpsc::Sender<AgentEvent>, kind: AgentEventKind) {
+    let _ = events_tx
+        .send(AgentEvent {
+            ts: Utc::now(),
+            kind,
+            ext: None,
+        })
+        .await;
+}
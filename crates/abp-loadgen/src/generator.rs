@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Open-loop load generation against a runtime.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use abp_core::{Outcome, WorkOrder};
+use abp_runtime::Runtime;
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+use tokio_stream::StreamExt;
+
+use crate::mix::{WorkloadKind, WorkloadMix};
+use crate::report::{KindReport, LatencySummary, LoadReport, MemorySampler};
+use crate::rng::SplitMix64;
+
+/// What traffic to generate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoadProfile {
+    /// Work orders submitted per second.
+    pub rate: f64,
+    /// How long to keep submitting.
+    pub duration: Duration,
+    /// Cap on runs in flight. Arrivals beyond it wait, and the wait counts
+    /// toward their latency.
+    pub max_in_flight: usize,
+    /// Relative share of each kind of work order.
+    pub mix: WorkloadMix,
+    /// Seed for the workload sequence; the same seed submits the same work
+    /// orders in the same order.
+    pub seed: u64,
+}
+
+impl Default for LoadProfile {
+    fn default() -> Self {
+        Self {
+            rate: 50.0,
+            duration: Duration::from_secs(10),
+            max_in_flight: 256,
+            mix: WorkloadMix::default(),
+            seed: 0,
+        }
+    }
+}
+
+impl LoadProfile {
+    /// Number of work orders the profile submits.
+    #[must_use]
+    pub fn total_runs(&self) -> u64 {
+        (self.rate * self.duration.as_secs_f64()).floor() as u64
+    }
+}
+
+struct Sample {
+    kind: WorkloadKind,
+    latency_us: u64,
+    events: u64,
+    ok: bool,
+}
+
+/// Drives a [`Runtime`] with the traffic described by a [`LoadProfile`].
+///
+/// Arrivals are open-loop: work order `i` is scheduled at `i / rate`
+/// seconds whether or not earlier runs have finished, and its latency is
+/// measured from that scheduled time to its receipt. A slow runtime
+/// therefore shows up as growing latency instead of a quietly reduced
+/// arrival rate.
+#[derive(Debug, Clone, Default)]
+pub struct LoadGenerator {
+    profile: LoadProfile,
+}
+
+impl LoadGenerator {
+    /// A generator for `profile`.
+    #[must_use]
+    pub fn new(profile: LoadProfile) -> Self {
+        Self { profile }
+    }
+
+    /// The profile in use.
+    #[must_use]
+    pub fn profile(&self) -> &LoadProfile {
+        &self.profile
+    }
+
+    /// The work orders a run would submit, in order.
+    pub fn work_orders(&self) -> impl Iterator<Item = (WorkloadKind, WorkOrder)> + '_ {
+        let mut rng = SplitMix64::new(self.profile.seed);
+        (0..self.profile.total_runs()).map(move |_| {
+            let kind = self.profile.mix.pick(&mut rng);
+            (kind, kind.work_order(rng.next_u64()))
+        })
+    }
+
+    /// Submit the profile's traffic to `backend` on `runtime` and wait for
+    /// every run to finish.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the profile is invalid or `backend` is not
+    /// registered. Failures of individual runs are counted, not returned.
+    pub async fn run(&self, runtime: Arc<Runtime>, backend: &str) -> Result<LoadReport> {
+        let p = &self.profile;
+        if !(p.rate.is_finite() && p.rate > 0.0) {
+            bail!("rate must be a positive number, got {}", p.rate);
+        }
+        if p.max_in_flight == 0 {
+            bail!("max_in_flight must be at least 1");
+        }
+        if p.mix.total() == 0 {
+            bail!("workload mix has no positive weights");
+        }
+        if runtime.backend(backend).is_none() {
+            bail!("unknown backend '{backend}'");
+        }
+
+        let sampler = MemorySampler::start();
+        let limit = Arc::new(Semaphore::new(p.max_in_flight));
+        let interval = Duration::from_secs_f64(1.0 / p.rate);
+        let mut tasks = JoinSet::new();
+        let mut samples = Vec::new();
+        let start = Instant::now();
+
+        for (i, (kind, work_order)) in self.work_orders().enumerate() {
+            let scheduled = start + interval.mul_f64(i as f64);
+            tokio::time::sleep_until(scheduled).await;
+            let permit = Arc::clone(&limit).acquire_owned().await?;
+            let runtime = Arc::clone(&runtime);
+            let backend = backend.to_string();
+            tasks.spawn(async move {
+                let _permit = permit;
+                run_one(&runtime, &backend, kind, work_order, scheduled).await
+            });
+            while let Some(done) = tasks.try_join_next() {
+                samples.push(done?);
+            }
+        }
+        while let Some(done) = tasks.join_next().await {
+            samples.push(done?);
+        }
+        let elapsed = start.elapsed();
+
+        Ok(summarize(
+            &samples,
+            elapsed,
+            sampler.map(MemorySampler::finish),
+        ))
+    }
+}
+
+async fn run_one(
+    runtime: &Runtime,
+    backend: &str,
+    kind: WorkloadKind,
+    work_order: WorkOrder,
+    scheduled: Instant,
+) -> Sample {
+    let mut events = 0;
+    let ok = match runtime.run_streaming(backend, work_order).await {
+        Ok(mut handle) => {
+            while handle.events.next().await.is_some() {
+                events += 1;
+            }
+            matches!(handle.receipt.await, Ok(Ok(r)) if r.outcome == Outcome::Complete)
+        }
+        Err(_) => false,
+    };
+    Sample {
+        kind,
+        latency_us: u64::try_from(scheduled.elapsed().as_micros()).unwrap_or(u64::MAX),
+        events,
+        ok,
+    }
+}
+
+fn summarize(
+    samples: &[Sample],
+    elapsed: Duration,
+    memory: Option<crate::report::MemoryUsage>,
+) -> LoadReport {
+    let mut all: Vec<u64> = samples.iter().map(|s| s.latency_us).collect();
+    let mut by_kind = BTreeMap::new();
+    for kind in WorkloadKind::ALL {
+        let mut latencies = Vec::new();
+        let mut k = KindReport::default();
+        for s in samples.iter().filter(|s| s.kind == kind) {
+            k.submitted += 1;
+            if s.ok {
+                k.succeeded += 1;
+            } else {
+                k.failed += 1;
+            }
+            latencies.push(s.latency_us);
+        }
+        if k.submitted > 0 {
+            k.latency = LatencySummary::from_samples(&mut latencies);
+            by_kind.insert(kind, k);
+        }
+    }
+    let succeeded = samples.iter().filter(|s| s.ok).count() as u64;
+    let secs = elapsed.as_secs_f64();
+    LoadReport {
+        submitted: samples.len() as u64,
+        succeeded,
+        failed: samples.len() as u64 - succeeded,
+        events: samples.iter().map(|s| s.events).sum(),
+        elapsed_ms: u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+        throughput_per_sec: if secs > 0.0 {
+            samples.len() as f64 / secs
+        } else {
+            0.0
+        },
+        latency: LatencySummary::from_samples(&mut all),
+        by_kind,
+        memory,
+    }
+}
@@ -0,0 +1,15 @@
+#![doc = include_str!("../README.md")]
+#![deny(unsafe_code)]
+#![warn(missing_docs)]
+//! Synthetic load generation for the Agent Backplane runtime.
+
+pub mod chaos;
+pub mod generator;
+pub mod mix;
+pub mod report;
+mod rng;
+
+pub use chaos::{ChaosBackend, ChaosConfig};
+pub use generator::{LoadGenerator, LoadProfile};
+pub use mix::{WorkloadKind, WorkloadMix};
+pub use report::{KindReport, LatencySummary, LoadReport, MemoryUsage};
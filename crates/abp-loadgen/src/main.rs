@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+#![deny(unsafe_code)]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use abp_integrations::MockBackend;
+use abp_loadgen::{ChaosBackend, ChaosConfig, LoadGenerator, LoadProfile, WorkloadMix};
+use abp_runtime::Runtime;
+use anyhow::Result;
+use clap::{Parser, ValueEnum};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum BackendChoice {
+    /// Simulated latency, tool traffic, and failures.
+    Chaos,
+    /// The fixed four-event mock backend.
+    Mock,
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "abp-loadgen",
+    version,
+    about = "Drive the Agent Backplane runtime with synthetic work orders"
+)]
+struct Args {
+    /// Work orders submitted per second.
+    #[arg(long, default_value_t = 50.0)]
+    rate: f64,
+
+    /// Seconds to keep submitting.
+    #[arg(long, default_value_t = 10.0)]
+    duration: f64,
+
+    /// Workload weights, e.g. `chat=6,tool_heavy=3,long_context=1`.
+    #[arg(long, default_value_t = WorkloadMix::default())]
+    mix: WorkloadMix,
+
+    /// Seed for the workload sequence and chaos draws.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Maximum runs in flight.
+    #[arg(long, default_value_t = 256)]
+    max_in_flight: usize,
+
+    /// Backend to drive.
+    #[arg(long, value_enum, default_value_t = BackendChoice::Chaos)]
+    backend: BackendChoice,
+
+    /// Chaos backend: fixed delay per run, in milliseconds.
+    #[arg(long, default_value_t = 5)]
+    latency_ms: u64,
+
+    /// Chaos backend: maximum random extra delay, in milliseconds.
+    #[arg(long, default_value_t = 20)]
+    jitter_ms: u64,
+
+    /// Chaos backend: fraction of runs that fail, in `[0, 1]`.
+    #[arg(long, default_value_t = 0.0)]
+    failure_rate: f64,
+
+    /// Print the report as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let mut runtime = Runtime::new();
+    runtime.register_backend("mock", MockBackend);
+    runtime.register_backend(
+        "chaos",
+        ChaosBackend::new(ChaosConfig {
+            latency: Duration::from_millis(args.latency_ms),
+            jitter: Duration::from_millis(args.jitter_ms),
+            failure_rate: args.failure_rate,
+            ..ChaosConfig::default()
+        }),
+    );
+    let backend = match args.backend {
+        BackendChoice::Chaos => "chaos",
+        BackendChoice::Mock => "mock",
+    };
+
+    let profile = LoadProfile {
+        rate: args.rate,
+        duration: Duration::try_from_secs_f64(args.duration)?,
+        max_in_flight: args.max_in_flight,
+        mix: args.mix,
+        seed: args.seed,
+    };
+    let report = LoadGenerator::new(profile)
+        .run(Arc::new(runtime), backend)
+        .await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print!("{report}");
+    }
+    Ok(())
+}
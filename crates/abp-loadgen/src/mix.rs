@@ -0,0 +1,260 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Workload kinds and weighted mixes of them.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use abp_core::{ContextPacket, ContextSnippet, WorkOrder, WorkOrderBuilder, WorkspaceMode};
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::rng::SplitMix64;
+
+/// Vendor key under `config.vendor["abp"]` naming a work order's workload.
+pub const WORKLOAD_KEY: &str = "workload";
+
+/// Size of the context attached to a long-context work order.
+pub const LONG_CONTEXT_BYTES: usize = 64 * 1024;
+
+const SNIPPET_BYTES: usize = 8 * 1024;
+
+const WORDS: [&str; 16] = [
+    "runtime",
+    "receipt",
+    "backend",
+    "policy",
+    "stream",
+    "event",
+    "workspace",
+    "sidecar",
+    "dialect",
+    "capability",
+    "projection",
+    "budget",
+    "trace",
+    "mapper",
+    "envelope",
+    "contract",
+];
+
+/// The shape of a synthetic work order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkloadKind {
+    /// A short question answered with streamed text.
+    Chat,
+    /// An agentic task that makes many tool calls.
+    ToolHeavy,
+    /// A task carrying a large context packet.
+    LongContext,
+}
+
+impl WorkloadKind {
+    /// Every kind, in declaration order.
+    pub const ALL: [Self; 3] = [Self::Chat, Self::ToolHeavy, Self::LongContext];
+
+    /// The `snake_case` name used in mixes and reports.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Chat => "chat",
+            Self::ToolHeavy => "tool_heavy",
+            Self::LongContext => "long_context",
+        }
+    }
+
+    /// The kind recorded on `work_order` by [`work_order`](Self::work_order).
+    #[must_use]
+    pub fn of(work_order: &WorkOrder) -> Option<Self> {
+        work_order
+            .config
+            .vendor
+            .get("abp")
+            .and_then(|v| v.get(WORKLOAD_KEY))
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// Synthesize a work order of this kind.
+    ///
+    /// The same `seed` always yields the same task and context. Work orders
+    /// pass the workspace through, so no files are staged per run.
+    #[must_use]
+    pub fn work_order(self, seed: u64) -> WorkOrder {
+        let mut rng = SplitMix64::new(seed);
+        let topic = WORDS[rng.below(WORDS.len() as u64) as usize];
+        let builder = match self {
+            Self::Chat => WorkOrderBuilder::new(format!("Explain how the {topic} works")),
+            Self::ToolHeavy => WorkOrderBuilder::new(format!(
+                "Find every use of the {topic} API, run the tests, and fix failures"
+            ))
+            .max_turns(20),
+            Self::LongContext => {
+                WorkOrderBuilder::new(format!("Summarize the {topic} notes below"))
+                    .context(long_context(&mut rng))
+            }
+        };
+        let mut wo = builder
+            .root(".")
+            .workspace_mode(WorkspaceMode::PassThrough)
+            .seed(seed)
+            .build();
+        if let Some(abp) = wo
+            .config
+            .vendor
+            .get_mut("abp")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            abp.insert(WORKLOAD_KEY.into(), serde_json::json!(self));
+        }
+        wo
+    }
+}
+
+fn long_context(rng: &mut SplitMix64) -> ContextPacket {
+    let snippets = (0..LONG_CONTEXT_BYTES / SNIPPET_BYTES)
+        .map(|i| {
+            let mut content = String::with_capacity(SNIPPET_BYTES + 16);
+            while content.len() < SNIPPET_BYTES {
+                content.push_str(WORDS[rng.below(WORDS.len() as u64) as usize]);
+                content.push(' ');
+            }
+            content.truncate(SNIPPET_BYTES);
+            ContextSnippet {
+                name: format!("notes-{i}.md"),
+                content,
+            }
+        })
+        .collect();
+    ContextPacket {
+        files: Vec::new(),
+        snippets,
+    }
+}
+
+impl fmt::Display for WorkloadKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for WorkloadKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.trim().replace('-', "_").to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|k| k.as_str() == name)
+            .ok_or_else(|| {
+                let known: Vec<_> = Self::ALL.iter().map(|k| k.as_str()).collect();
+                anyhow!(
+                    "unknown workload '{s}' (expected one of: {})",
+                    known.join(", ")
+                )
+            })
+    }
+}
+
+/// Relative weights of each [`WorkloadKind`] in the generated traffic.
+///
+/// Parses from and displays as `chat=6,tool_heavy=3,long_context=1`.
+/// Kinds left out get weight 0.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkloadMix {
+    weights: BTreeMap<WorkloadKind, u32>,
+}
+
+impl WorkloadMix {
+    /// A mix of only `kind`.
+    #[must_use]
+    pub fn only(kind: WorkloadKind) -> Self {
+        Self {
+            weights: BTreeMap::from([(kind, 1)]),
+        }
+    }
+
+    /// Set the weight of `kind`.
+    #[must_use]
+    pub fn with(mut self, kind: WorkloadKind, weight: u32) -> Self {
+        if weight == 0 {
+            self.weights.remove(&kind);
+        } else {
+            self.weights.insert(kind, weight);
+        }
+        self
+    }
+
+    /// The weight of `kind`.
+    #[must_use]
+    pub fn weight(&self, kind: WorkloadKind) -> u32 {
+        self.weights.get(&kind).copied().unwrap_or(0)
+    }
+
+    /// Sum of all weights.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.weights.values().map(|&w| u64::from(w)).sum()
+    }
+
+    /// Pick a kind with probability proportional to its weight.
+    pub(crate) fn pick(&self, rng: &mut SplitMix64) -> WorkloadKind {
+        let mut roll = rng.below(self.total());
+        for (&kind, &w) in &self.weights {
+            if roll < u64::from(w) {
+                return kind;
+            }
+            roll -= u64::from(w);
+        }
+        WorkloadKind::Chat
+    }
+}
+
+impl Default for WorkloadMix {
+    fn default() -> Self {
+        Self {
+            weights: BTreeMap::from([
+                (WorkloadKind::Chat, 6),
+                (WorkloadKind::ToolHeavy, 3),
+                (WorkloadKind::LongContext, 1),
+            ]),
+        }
+    }
+}
+
+impl fmt::Display for WorkloadMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<_> = self
+            .weights
+            .iter()
+            .map(|(k, w)| format!("{k}={w}"))
+            .collect();
+        f.write_str(&parts.join(","))
+    }
+}
+
+impl FromStr for WorkloadMix {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut weights = BTreeMap::new();
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (kind, weight) = match part.split_once('=') {
+                Some((k, w)) => (
+                    k.parse::<WorkloadKind>()?,
+                    w.trim()
+                        .parse::<u32>()
+                        .map_err(|_| anyhow!("invalid weight in '{part}'"))?,
+                ),
+                None => (part.parse::<WorkloadKind>()?, 1),
+            };
+            if weight > 0 {
+                weights.insert(kind, weight);
+            }
+        }
+        if weights.is_empty() {
+            bail!("workload mix '{s}' has no positive weights");
+        }
+        Ok(Self { weights })
+    }
+}
@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Load run results: throughput, latency percentiles, and memory.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::mix::WorkloadKind;
+
+/// Latency distribution of a set of runs, in microseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    /// Number of samples.
+    pub count: u64,
+    /// Fastest run.
+    pub min_us: u64,
+    /// Arithmetic mean.
+    pub mean_us: u64,
+    /// Median.
+    pub p50_us: u64,
+    /// 90th percentile.
+    pub p90_us: u64,
+    /// 99th percentile.
+    pub p99_us: u64,
+    /// Slowest run.
+    pub max_us: u64,
+}
+
+impl LatencySummary {
+    /// Summarize `samples`, which are sorted in place.
+    ///
+    /// Percentiles use the nearest-rank method. An empty slice gives an
+    /// all-zero summary.
+    #[must_use]
+    pub fn from_samples(samples: &mut [u64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+        let n = samples.len();
+        let rank = |p: usize| samples[(n * p).div_ceil(100).clamp(1, n) - 1];
+        Self {
+            count: n as u64,
+            min_us: samples[0],
+            mean_us: (samples.iter().map(|&s| u128::from(s)).sum::<u128>() / n as u128) as u64,
+            p50_us: rank(50),
+            p90_us: rank(90),
+            p99_us: rank(99),
+            max_us: samples[n - 1],
+        }
+    }
+}
+
+/// Results for one [`WorkloadKind`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KindReport {
+    /// Work orders of this kind submitted.
+    pub submitted: u64,
+    /// Runs that produced a complete receipt.
+    pub succeeded: u64,
+    /// Runs that errored or produced a non-complete receipt.
+    pub failed: u64,
+    /// Latency of every finished run, successful or not.
+    pub latency: LatencySummary,
+}
+
+/// Resident set size of this process over a load run, in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryUsage {
+    /// Before the first work order was submitted.
+    pub start_bytes: u64,
+    /// Highest sample seen during the run.
+    pub peak_bytes: u64,
+    /// After the last run finished.
+    pub end_bytes: u64,
+}
+
+/// Everything measured by one [`LoadGenerator::run`](crate::LoadGenerator::run).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoadReport {
+    /// Work orders submitted.
+    pub submitted: u64,
+    /// Runs that produced a complete receipt.
+    pub succeeded: u64,
+    /// Runs that errored or produced a non-complete receipt.
+    pub failed: u64,
+    /// Agent events received across all runs.
+    pub events: u64,
+    /// Wall time from the first submission to the last receipt.
+    pub elapsed_ms: u64,
+    /// Finished runs per second of wall time.
+    pub throughput_per_sec: f64,
+    /// Latency of every finished run, from its scheduled start to its
+    /// receipt.
+    pub latency: LatencySummary,
+    /// Per-kind breakdown.
+    pub by_kind: BTreeMap<WorkloadKind, KindReport>,
+    /// Memory use, where the platform reports it (Linux only).
+    pub memory: Option<MemoryUsage>,
+}
+
+fn ms(us: u64) -> f64 {
+    us as f64 / 1000.0
+}
+
+fn write_latency(f: &mut fmt::Formatter<'_>, l: &LatencySummary) -> fmt::Result {
+    write!(
+        f,
+        "p50={:.1}ms p90={:.1}ms p99={:.1}ms max={:.1}ms",
+        ms(l.p50_us),
+        ms(l.p90_us),
+        ms(l.p99_us),
+        ms(l.max_us)
+    )
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "runs:       {} submitted, {} succeeded, {} failed",
+            self.submitted, self.succeeded, self.failed
+        )?;
+        writeln!(
+            f,
+            "throughput: {:.1} runs/s over {:.2}s ({} events)",
+            self.throughput_per_sec,
+            self.elapsed_ms as f64 / 1000.0,
+            self.events
+        )?;
+        write!(f, "latency:    ")?;
+        write_latency(f, &self.latency)?;
+        writeln!(f)?;
+        for (kind, k) in &self.by_kind {
+            write!(
+                f,
+                "  {:<13} {:>6} runs {:>4} failed  ",
+                kind.as_str(),
+                k.submitted,
+                k.failed
+            )?;
+            write_latency(f, &k.latency)?;
+            writeln!(f)?;
+        }
+        if let Some(m) = &self.memory {
+            let mib = |b: u64| b as f64 / (1024.0 * 1024.0);
+            writeln!(
+                f,
+                "memory:     start={:.1}MiB peak={:.1}MiB end={:.1}MiB",
+                mib(m.start_bytes),
+                mib(m.peak_bytes),
+                mib(m.end_bytes)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Current resident set size of this process, where the platform reports
+/// it.
+#[must_use]
+pub fn resident_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmRSS:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Samples [`resident_bytes`] in the background, keeping the peak.
+pub(crate) struct MemorySampler {
+    start: u64,
+    peak: Arc<AtomicU64>,
+    task: JoinHandle<()>,
+}
+
+impl MemorySampler {
+    const INTERVAL: Duration = Duration::from_millis(50);
+
+    /// Start sampling, or `None` if memory cannot be read here.
+    pub(crate) fn start() -> Option<Self> {
+        let start = resident_bytes()?;
+        let peak = Arc::new(AtomicU64::new(start));
+        let task = tokio::spawn({
+            let peak = Arc::clone(&peak);
+            async move {
+                loop {
+                    tokio::time::sleep(Self::INTERVAL).await;
+                    if let Some(now) = resident_bytes() {
+                        peak.fetch_max(now, Ordering::Relaxed);
+                    }
+                }
+            }
+        });
+        Some(Self { start, peak, task })
+    }
+
+    pub(crate) fn finish(self) -> MemoryUsage {
+        self.task.abort();
+        let end = resident_bytes().unwrap_or(self.start);
+        MemoryUsage {
+            start_bytes: self.start,
+            peak_bytes: self.peak.load(Ordering::Relaxed).max(end),
+            end_bytes: end,
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for workload synthesis and load runs.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use abp_core::WorkspaceMode;
+use abp_loadgen::mix::LONG_CONTEXT_BYTES;
+use abp_loadgen::{
+    ChaosBackend, ChaosConfig, LatencySummary, LoadGenerator, LoadProfile, WorkloadKind,
+    WorkloadMix,
+};
+use abp_runtime::Runtime;
+
+fn quick_chaos(failure_rate: f64) -> ChaosBackend {
+    ChaosBackend::new(ChaosConfig {
+        latency: Duration::from_millis(1),
+        jitter: Duration::from_millis(2),
+        failure_rate,
+        ..ChaosConfig::default()
+    })
+}
+
+fn profile(mix: WorkloadMix) -> LoadProfile {
+    LoadProfile {
+        rate: 200.0,
+        duration: Duration::from_millis(300),
+        mix,
+        seed: 7,
+        ..LoadProfile::default()
+    }
+}
+
+#[test]
+fn mix_parses_and_displays() {
+    let mix: WorkloadMix = "chat=6, tool-heavy=3,long_context=0".parse().unwrap();
+    assert_eq!(mix.weight(WorkloadKind::Chat), 6);
+    assert_eq!(mix.weight(WorkloadKind::ToolHeavy), 3);
+    assert_eq!(mix.weight(WorkloadKind::LongContext), 0);
+    assert_eq!(mix.to_string(), "chat=6,tool_heavy=3");
+    assert_eq!(
+        WorkloadMix::default().to_string(),
+        "chat=6,tool_heavy=3,long_context=1"
+    );
+
+    assert!("chat=0".parse::<WorkloadMix>().is_err());
+    assert!("video=1".parse::<WorkloadMix>().is_err());
+    assert!("chat=many".parse::<WorkloadMix>().is_err());
+}
+
+#[test]
+fn work_orders_are_seeded_and_tagged() {
+    let a = WorkloadKind::LongContext.work_order(42);
+    let b = WorkloadKind::LongContext.work_order(42);
+    assert_eq!(a.task, b.task);
+    assert_eq!(a.context.snippets[3].content, b.context.snippets[3].content);
+    assert_eq!(WorkloadKind::of(&a), Some(WorkloadKind::LongContext));
+    assert_eq!(abp_backend_core::extract_seed(&a), Some(42));
+    assert!(matches!(a.workspace.mode, WorkspaceMode::PassThrough));
+    let bytes: usize = a.context.snippets.iter().map(|s| s.content.len()).sum();
+    assert_eq!(bytes, LONG_CONTEXT_BYTES);
+
+    let chat = WorkloadKind::Chat.work_order(42);
+    assert!(chat.context.snippets.is_empty());
+    assert_eq!(WorkloadKind::of(&chat), Some(WorkloadKind::Chat));
+}
+
+#[test]
+fn generator_sequence_follows_seed_and_mix() {
+    let gen_a = LoadGenerator::new(profile(WorkloadMix::default()));
+    let gen_b = LoadGenerator::new(profile(WorkloadMix::default()));
+    let a: Vec<_> = gen_a.work_orders().map(|(k, wo)| (k, wo.task)).collect();
+    let b: Vec<_> = gen_b.work_orders().map(|(k, wo)| (k, wo.task)).collect();
+    assert_eq!(a.len(), 60);
+    assert_eq!(a, b);
+    assert!(a.iter().any(|(k, _)| *k == WorkloadKind::Chat));
+
+    let only = LoadGenerator::new(profile(WorkloadMix::only(WorkloadKind::ToolHeavy)));
+    assert!(
+        only.work_orders()
+            .all(|(k, _)| k == WorkloadKind::ToolHeavy)
+    );
+}
+
+#[test]
+fn latency_summary_uses_nearest_rank() {
+    let mut samples: Vec<u64> = (1..=100).rev().collect();
+    let s = LatencySummary::from_samples(&mut samples);
+    assert_eq!(s.count, 100);
+    assert_eq!((s.min_us, s.max_us), (1, 100));
+    assert_eq!((s.p50_us, s.p90_us, s.p99_us), (50, 90, 99));
+    assert_eq!(s.mean_us, 50);
+    assert_eq!(
+        LatencySummary::from_samples(&mut []),
+        LatencySummary::default()
+    );
+}
+
+#[tokio::test]
+async fn load_run_reports_every_submission() {
+    let mut rt = Runtime::new();
+    rt.register_backend("chaos", quick_chaos(0.0));
+    let report = LoadGenerator::new(profile(WorkloadMix::default()))
+        .run(Arc::new(rt), "chaos")
+        .await
+        .unwrap();
+
+    assert_eq!(report.submitted, 60);
+    assert_eq!(report.succeeded, 60);
+    assert_eq!(report.failed, 0);
+    assert_eq!(report.latency.count, 60);
+    assert!(report.latency.p50_us >= 1_000);
+    assert!(report.throughput_per_sec > 0.0);
+    let per_kind: u64 = report.by_kind.values().map(|k| k.submitted).sum();
+    assert_eq!(per_kind, 60);
+    // Every run emits at least start, message, and completion.
+    assert!(report.events >= 3 * 60);
+    if cfg!(target_os = "linux") {
+        let mem = report.memory.unwrap();
+        assert!(mem.peak_bytes >= mem.start_bytes);
+    }
+
+    let text = report.to_string();
+    assert!(text.contains("60 submitted, 60 succeeded, 0 failed"));
+    let json = serde_json::to_value(&report).unwrap();
+    assert!(json["by_kind"]["chat"]["latency"]["p99_us"].is_u64());
+}
+
+#[tokio::test]
+async fn injected_failures_are_counted() {
+    let mut rt = Runtime::new();
+    rt.register_backend("chaos", quick_chaos(1.0));
+    let report = LoadGenerator::new(profile(WorkloadMix::only(WorkloadKind::Chat)))
+        .run(Arc::new(rt), "chaos")
+        .await
+        .unwrap();
+    assert_eq!(report.submitted, 60);
+    assert_eq!(report.failed, 60);
+    assert_eq!(report.by_kind[&WorkloadKind::Chat].failed, 60);
+}
+
+#[tokio::test]
+async fn invalid_runs_are_rejected() {
+    let rt = Arc::new(Runtime::new());
+    let err = LoadGenerator::new(LoadProfile::default())
+        .run(Arc::clone(&rt), "chaos")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("unknown backend"));
+
+    let zero_rate = LoadProfile {
+        rate: 0.0,
+        ..LoadProfile::default()
+    };
+    assert!(
+        LoadGenerator::new(zero_rate)
+            .run(rt, "chaos")
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Composition of two mappers through an intermediate dialect.
+
+use abp_core::AgentEvent;
+use abp_dialect::Dialect;
+use serde_json::Value;
+
+use crate::{DialectRequest, DialectResponse, Mapper, MappingError};
+
+/// Runs two mappers back to back, e.g. Claude → OpenAI → Kimi.
+///
+/// Requests and responses go through both mappers; events are produced by
+/// the second, since they are emitted in the final target's format.
+///
+/// # Examples
+///
+/// ```
+/// use abp_mapper::{ChainMapper, ClaudeToOpenAiMapper, DialectRequest, Mapper, OpenAiCompatMapper};
+/// use abp_dialect::Dialect;
+/// use serde_json::json;
+///
+/// let to_kimi = OpenAiCompatMapper::new(Dialect::OpenAi, Dialect::Kimi).unwrap();
+/// let mapper = ChainMapper::new(ClaudeToOpenAiMapper, to_kimi).unwrap();
+/// assert_eq!(mapper.source_dialect(), Dialect::Claude);
+/// assert_eq!(mapper.target_dialect(), Dialect::Kimi);
+///
+/// let req = DialectRequest {
+///     dialect: Dialect::Claude,
+///     body: json!({
+///         "model": "moonshot-v1-8k",
+///         "max_tokens": 128,
+///         "system": "Be brief.",
+///         "messages": [{"role": "user", "content": "Hello"}]
+///     }),
+/// };
+/// let result = mapper.map_request(&req).unwrap();
+/// assert_eq!(result["messages"][0]["role"], "system");
+/// ```
+pub struct ChainMapper {
+    first: Box<dyn Mapper>,
+    second: Box<dyn Mapper>,
+}
+
+impl ChainMapper {
+    /// Chain `first` into `second`, or `None` if `first` does not target
+    /// the dialect `second` reads.
+    #[must_use]
+    pub fn new(first: impl Mapper + 'static, second: impl Mapper + 'static) -> Option<Self> {
+        (first.target_dialect() == second.source_dialect()).then(|| Self {
+            first: Box::new(first),
+            second: Box::new(second),
+        })
+    }
+
+    /// The dialect requests pass through between the two mappers.
+    #[must_use]
+    pub fn via(&self) -> Dialect {
+        self.first.target_dialect()
+    }
+}
+
+impl Mapper for ChainMapper {
+    fn map_request(&self, from: &DialectRequest) -> Result<Value, MappingError> {
+        let body = self.first.map_request(from)?;
+        self.second.map_request(&DialectRequest {
+            dialect: self.via(),
+            body,
+        })
+    }
+
+    fn map_response(&self, from: &Value) -> Result<DialectResponse, MappingError> {
+        let intermediate = self.first.map_response(from)?;
+        self.second.map_response(&intermediate.body)
+    }
+
+    fn map_event(&self, from: &AgentEvent) -> Result<Value, MappingError> {
+        self.second.map_event(from)
+    }
+
+    fn source_dialect(&self) -> Dialect {
+        self.first.source_dialect()
+    }
+
+    fn target_dialect(&self) -> Dialect {
+        self.second.target_dialect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GeminiToOpenAiMapper, OpenAiCompatMapper, OpenAiToClaudeMapper};
+    use serde_json::json;
+
+    #[test]
+    fn mismatched_mappers_do_not_chain() {
+        assert!(ChainMapper::new(GeminiToOpenAiMapper, OpenAiToClaudeMapper).is_some());
+        assert!(ChainMapper::new(OpenAiToClaudeMapper, GeminiToOpenAiMapper).is_none());
+    }
+
+    #[test]
+    fn kimi_to_claude_goes_through_openai() {
+        let from_kimi = OpenAiCompatMapper::new(Dialect::Kimi, Dialect::OpenAi).unwrap();
+        let mapper = ChainMapper::new(from_kimi, OpenAiToClaudeMapper).unwrap();
+        assert_eq!(mapper.via(), Dialect::OpenAi);
+
+        let req = DialectRequest {
+            dialect: Dialect::Kimi,
+            body: json!({
+                "model": "claude-sonnet-4-20250514",
+                "messages": [
+                    {"role": "system", "content": "Be brief."},
+                    {"role": "user", "content": "Hello"}
+                ],
+                "max_tokens": 64,
+                "use_search": false
+            }),
+        };
+        let out = mapper.map_request(&req).unwrap();
+        assert_eq!(out["system"], "Be brief.");
+        assert_eq!(out["messages"][0]["role"], "user");
+        assert_eq!(
+            mapper.map_response(&json!({})).unwrap().dialect,
+            Dialect::Claude
+        );
+    }
+}
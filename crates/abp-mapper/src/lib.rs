@@ -14,7 +14,9 @@
 //!
 //! * **JSON-level** — the [`Mapper`] trait operates on raw `serde_json::Value`
 //!   payloads.  Implementations like [`OpenAiToClaudeMapper`] convert one
-//!   vendor's JSON schema into another's.
+//!   vendor's JSON schema into another's. [`OpenAiCompatMapper`] covers
+//!   the OpenAI-compatible dialects (Kimi, Copilot), and [`ChainMapper`]
+//!   composes two mappers through an intermediate dialect.
 //!
 //! * **IR-level** — the [`IrMapper`] trait translates via the intermediate
 //!   representation defined in `abp-ir`.  Use [`default_ir_mapper`] to obtain
@@ -53,6 +55,7 @@
 //! assert_eq!(mapped, req.body);
 //! ```
 
+mod chain;
 mod claude_to_openai;
 mod error;
 mod factory;
@@ -70,6 +73,7 @@ mod ir_openai_copilot;
 mod ir_openai_gemini;
 mod ir_openai_kimi;
 mod map_error;
+mod openai_compat;
 mod openai_to_claude;
 mod openai_to_gemini;
 
@@ -90,6 +94,7 @@ pub mod validate_ir;
 /// Validation pipeline for mapping correctness.
 pub mod validation;
 
+pub use chain::ChainMapper;
 pub use claude_to_openai::ClaudeToOpenAiMapper;
 pub use error::MappingError;
 pub use factory::{default_ir_mapper, supported_ir_pairs};
@@ -107,6 +112,7 @@ pub use ir_openai_copilot::OpenAiCopilotIrMapper;
 pub use ir_openai_gemini::OpenAiGeminiIrMapper;
 pub use ir_openai_kimi::OpenAiKimiIrMapper;
 pub use map_error::MapError;
+pub use openai_compat::OpenAiCompatMapper;
 pub use openai_to_claude::OpenAiToClaudeMapper;
 pub use openai_to_gemini::OpenAiToGeminiMapper;
 
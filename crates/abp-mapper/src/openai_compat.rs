@@ -0,0 +1,366 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Maps between OpenAI chat-completions and its compatible dialects
+//! (Kimi, Copilot).
+
+use abp_core::{AgentEvent, AgentEventKind};
+use abp_dialect::Dialect;
+use serde_json::{Map, Value, json};
+
+use crate::{DialectRequest, DialectResponse, Mapper, MappingError};
+
+/// Request fields every OpenAI-compatible dialect accepts.
+const SHARED_FIELDS: &[&str] = &[
+    "model",
+    "messages",
+    "stream",
+    "temperature",
+    "top_p",
+    "max_tokens",
+    "stop",
+    "tools",
+    "tool_choice",
+];
+
+/// Maps requests between OpenAI and a dialect that follows the OpenAI
+/// chat-completions surface.
+///
+/// Messages, tools and sampling parameters pass through unchanged; only the
+/// fields the dialects disagree on are touched.
+///
+/// # Mapping summary
+///
+/// | Source | Target | Notes |
+/// |---|---|---|
+/// | OpenAI `max_completion_tokens` | `max_tokens` | Renamed when `max_tokens` is absent |
+/// | OpenAI `image_url` content parts | — | Rejected: neither Kimi nor Copilot takes image input |
+/// | OpenAI-only fields (`n`, `logprobs`, ...) | — | Dropped |
+/// | Kimi `use_search: true` | — | Rejected: OpenAI has no built-in web search |
+/// | Copilot `references` | `messages[0]{role:system}` | Rendered into a system message |
+/// | Copilot `copilot_metadata`, `turn_history` | — | Dropped |
+///
+/// # Examples
+///
+/// ```
+/// use abp_mapper::{Mapper, OpenAiCompatMapper, DialectRequest};
+/// use abp_dialect::Dialect;
+/// use serde_json::json;
+///
+/// let mapper = OpenAiCompatMapper::new(Dialect::OpenAi, Dialect::Kimi).unwrap();
+/// let req = DialectRequest {
+///     dialect: Dialect::OpenAi,
+///     body: json!({
+///         "model": "moonshot-v1-8k",
+///         "messages": [{"role": "user", "content": "Hello"}],
+///         "max_completion_tokens": 256,
+///         "logprobs": true
+///     }),
+/// };
+/// let result = mapper.map_request(&req).unwrap();
+/// assert_eq!(result["max_tokens"], 256);
+/// assert!(result.get("logprobs").is_none());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct OpenAiCompatMapper {
+    source: Dialect,
+    target: Dialect,
+}
+
+impl OpenAiCompatMapper {
+    /// A mapper from `source` to `target`, or `None` unless one side is
+    /// OpenAI and the other Kimi or Copilot.
+    #[must_use]
+    pub fn new(source: Dialect, target: Dialect) -> Option<Self> {
+        let compat = |d| matches!(d, Dialect::Kimi | Dialect::Copilot);
+        let supported = (source == Dialect::OpenAi && compat(target))
+            || (compat(source) && target == Dialect::OpenAi);
+        supported.then_some(Self { source, target })
+    }
+
+    fn map_into_compat(
+        &self,
+        obj: &Map<String, Value>,
+    ) -> Result<Map<String, Value>, MappingError> {
+        if let Some(Value::Array(messages)) = obj.get("messages") {
+            let has_image = messages.iter().any(|m| {
+                m.get("content")
+                    .and_then(Value::as_array)
+                    .is_some_and(|parts| {
+                        parts
+                            .iter()
+                            .any(|p| p.get("type").and_then(Value::as_str) == Some("image_url"))
+                    })
+            });
+            if has_image {
+                return Err(self.unsupported("image_input"));
+            }
+        }
+
+        let mut result = keep_shared(obj);
+        if !result.contains_key("max_tokens")
+            && let Some(max) = obj.get("max_completion_tokens")
+        {
+            result.insert("max_tokens".into(), max.clone());
+        }
+        Ok(result)
+    }
+
+    fn map_out_of_compat(
+        &self,
+        obj: &Map<String, Value>,
+    ) -> Result<Map<String, Value>, MappingError> {
+        if obj.get("use_search").and_then(Value::as_bool) == Some(true) {
+            return Err(self.unsupported("web_search"));
+        }
+
+        let mut result = keep_shared(obj);
+        if let Some(Value::Array(refs)) = obj.get("references")
+            && !refs.is_empty()
+        {
+            let context = refs
+                .iter()
+                .map(render_reference)
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            let system = json!({
+                "role": "system",
+                "content": format!("Referenced context:\n\n{context}"),
+            });
+            if let Some(Value::Array(messages)) = result.get_mut("messages") {
+                messages.insert(0, system);
+            } else {
+                result.insert("messages".into(), json!([system]));
+            }
+        }
+        Ok(result)
+    }
+
+    fn unsupported(&self, capability: &str) -> MappingError {
+        MappingError::UnsupportedCapability {
+            capability: capability.into(),
+            source_dialect: self.source,
+            target_dialect: self.target,
+        }
+    }
+}
+
+impl Mapper for OpenAiCompatMapper {
+    fn map_request(&self, from: &DialectRequest) -> Result<Value, MappingError> {
+        if from.dialect != self.source {
+            return Err(MappingError::UnmappableRequest {
+                reason: format!(
+                    "OpenAiCompatMapper expects {} dialect, got {}",
+                    self.source, from.dialect
+                ),
+            });
+        }
+
+        let obj = from
+            .body
+            .as_object()
+            .ok_or_else(|| MappingError::UnmappableRequest {
+                reason: "request body must be a JSON object".into(),
+            })?;
+
+        let result = if self.source == Dialect::OpenAi {
+            self.map_into_compat(obj)?
+        } else {
+            self.map_out_of_compat(obj)?
+        };
+        Ok(Value::Object(result))
+    }
+
+    fn map_response(&self, from: &Value) -> Result<DialectResponse, MappingError> {
+        // All three dialects return chat.completion objects.
+        Ok(DialectResponse {
+            dialect: self.target,
+            body: from.clone(),
+        })
+    }
+
+    fn map_event(&self, from: &AgentEvent) -> Result<Value, MappingError> {
+        match &from.kind {
+            AgentEventKind::AssistantDelta { text } => Ok(json!({
+                "object": "chat.completion.chunk",
+                "choices": [{
+                    "index": 0,
+                    "delta": {"content": text}
+                }]
+            })),
+            AgentEventKind::AssistantMessage { text } => Ok(json!({
+                "object": "chat.completion",
+                "choices": [{
+                    "index": 0,
+                    "message": {"role": "assistant", "content": text},
+                    "finish_reason": "stop"
+                }]
+            })),
+            AgentEventKind::ToolCall {
+                tool_name,
+                tool_use_id,
+                input,
+                ..
+            } => {
+                let args = serde_json::to_string(input).unwrap_or_else(|_| "{}".into());
+                Ok(json!({
+                    "object": "chat.completion.chunk",
+                    "choices": [{
+                        "index": 0,
+                        "delta": {
+                            "tool_calls": [{
+                                "id": tool_use_id.as_deref().unwrap_or(""),
+                                "type": "function",
+                                "function": {
+                                    "name": tool_name,
+                                    "arguments": args
+                                }
+                            }]
+                        }
+                    }]
+                }))
+            }
+            AgentEventKind::ToolResult {
+                tool_use_id,
+                output,
+                ..
+            } => {
+                let content = match output {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                Ok(json!({
+                    "role": "tool",
+                    "tool_call_id": tool_use_id.as_deref().unwrap_or(""),
+                    "content": content
+                }))
+            }
+            _ => serde_json::to_value(from).map_err(|e| MappingError::UnmappableRequest {
+                reason: format!("failed to serialize event: {e}"),
+            }),
+        }
+    }
+
+    fn source_dialect(&self) -> Dialect {
+        self.source
+    }
+
+    fn target_dialect(&self) -> Dialect {
+        self.target
+    }
+}
+
+/// Copies the fields every OpenAI-compatible dialect understands.
+fn keep_shared(obj: &Map<String, Value>) -> Map<String, Value> {
+    obj.iter()
+        .filter(|(k, _)| SHARED_FIELDS.contains(&k.as_str()))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+/// Renders one Copilot reference as plain text.
+fn render_reference(reference: &Value) -> String {
+    let kind = reference
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("reference");
+    let id = reference.get("id").and_then(Value::as_str).unwrap_or("");
+    let data = match reference.get("data") {
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+        None => String::new(),
+    };
+    format!("[{kind} {id}]\n{data}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn request(dialect: Dialect, body: Value) -> DialectRequest {
+        DialectRequest { dialect, body }
+    }
+
+    #[test]
+    fn only_openai_compatible_pairs_are_supported() {
+        assert!(OpenAiCompatMapper::new(Dialect::OpenAi, Dialect::Kimi).is_some());
+        assert!(OpenAiCompatMapper::new(Dialect::Copilot, Dialect::OpenAi).is_some());
+        assert!(OpenAiCompatMapper::new(Dialect::Kimi, Dialect::Copilot).is_none());
+        assert!(OpenAiCompatMapper::new(Dialect::OpenAi, Dialect::Claude).is_none());
+    }
+
+    #[test]
+    fn openai_to_kimi_rejects_images() {
+        let mapper = OpenAiCompatMapper::new(Dialect::OpenAi, Dialect::Kimi).unwrap();
+        let req = request(
+            Dialect::OpenAi,
+            json!({
+                "model": "gpt-4o",
+                "messages": [{"role": "user", "content": [
+                    {"type": "text", "text": "what is this?"},
+                    {"type": "image_url", "image_url": {"url": "data:image/png;base64,AA=="}}
+                ]}]
+            }),
+        );
+        let err = mapper.map_request(&req).unwrap_err();
+        assert!(matches!(
+            err,
+            MappingError::UnsupportedCapability { ref capability, .. } if capability == "image_input"
+        ));
+    }
+
+    #[test]
+    fn kimi_search_has_no_openai_equivalent() {
+        let mapper = OpenAiCompatMapper::new(Dialect::Kimi, Dialect::OpenAi).unwrap();
+        let body = json!({"model": "moonshot-v1-8k", "messages": [], "use_search": true});
+        assert!(mapper.map_request(&request(Dialect::Kimi, body)).is_err());
+
+        let body = json!({"model": "moonshot-v1-8k", "messages": [], "use_search": false});
+        let out = mapper.map_request(&request(Dialect::Kimi, body)).unwrap();
+        assert!(out.get("use_search").is_none());
+    }
+
+    #[test]
+    fn copilot_references_become_a_system_message() {
+        let mapper = OpenAiCompatMapper::new(Dialect::Copilot, Dialect::OpenAi).unwrap();
+        let body = json!({
+            "model": "gpt-4o",
+            "messages": [{"role": "user", "content": "explain"}],
+            "references": [{"type": "file", "id": "src/main.rs", "data": "fn main() {}"}],
+            "copilot_metadata": {"session": "s1"}
+        });
+        let out = mapper
+            .map_request(&request(Dialect::Copilot, body))
+            .unwrap();
+        let messages = out["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["role"], "system");
+        let context = messages[0]["content"].as_str().unwrap();
+        assert!(context.contains("[file src/main.rs]\nfn main() {}"));
+        assert!(out.get("references").is_none());
+        assert!(out.get("copilot_metadata").is_none());
+    }
+
+    #[test]
+    fn wrong_source_dialect_is_rejected() {
+        let mapper = OpenAiCompatMapper::new(Dialect::OpenAi, Dialect::Copilot).unwrap();
+        let req = request(Dialect::Kimi, json!({"model": "m", "messages": []}));
+        assert!(mapper.map_request(&req).is_err());
+    }
+
+    #[test]
+    fn events_use_chat_completion_chunks() {
+        let mapper = OpenAiCompatMapper::new(Dialect::OpenAi, Dialect::Kimi).unwrap();
+        let ev = AgentEvent {
+            ts: Utc::now(),
+            kind: AgentEventKind::AssistantDelta { text: "hi".into() },
+            ext: None,
+        };
+        let out = mapper.map_event(&ev).unwrap();
+        assert_eq!(out["choices"][0]["delta"]["content"], "hi");
+        assert_eq!(
+            mapper.map_response(&json!({})).unwrap().dialect,
+            Dialect::Kimi
+        );
+    }
+}
@@ -0,0 +1,410 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Expose MCP tools to backends as work-order tool definitions.
+
+use std::collections::BTreeMap;
+
+use abp_core::ir::IrToolDefinition;
+use abp_core::{AgentEventKind, Capability, CapabilityManifest, SupportLevel, WorkOrder};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::protocol::{CallToolResult, McpTool};
+use crate::transport::{SseTransport, StdioTransport};
+use crate::{McpClient, McpError};
+
+/// Key under `config.vendor["abp"]` carrying server configurations for
+/// backends with native MCP support.
+pub const MCP_SERVERS_VENDOR_KEY: &str = "mcp_servers";
+
+/// Prefix of every bridged tool name.
+pub const TOOL_PREFIX: &str = "mcp__";
+
+/// Name a bridged tool is offered under: `mcp__<server>__<tool>`.
+#[must_use]
+pub fn qualified_name(server: &str, tool: &str) -> String {
+    format!("{TOOL_PREFIX}{server}__{tool}")
+}
+
+/// How to reach an MCP server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "transport", rename_all = "snake_case")]
+pub enum McpServerConfig {
+    /// A child process speaking newline-delimited JSON.
+    Stdio {
+        /// Program to run.
+        command: String,
+        /// Arguments.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+        /// Extra environment variables.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        env: BTreeMap<String, String>,
+    },
+    /// An HTTP server with an SSE event stream.
+    Sse {
+        /// URL of the event stream.
+        url: String,
+    },
+}
+
+impl McpServerConfig {
+    /// A stdio server run as `command args...`.
+    #[must_use]
+    pub fn stdio<I, S>(command: impl Into<String>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Stdio {
+            command: command.into(),
+            args: args.into_iter().map(Into::into).collect(),
+            env: BTreeMap::new(),
+        }
+    }
+
+    /// An SSE server whose event stream is at `url`.
+    #[must_use]
+    pub fn sse(url: impl Into<String>) -> Self {
+        Self::Sse { url: url.into() }
+    }
+
+    /// Open a transport to the server and run the handshake.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`McpError`] if the server cannot be reached or initialised.
+    pub async fn connect(&self) -> Result<McpClient, McpError> {
+        match self {
+            Self::Stdio { command, args, env } => {
+                McpClient::connect(StdioTransport::spawn(command, args, env)?).await
+            }
+            Self::Sse { url } => McpClient::connect(SseTransport::connect(url).await?).await,
+        }
+    }
+}
+
+/// Read the server configurations a work order passes to a native backend.
+#[must_use]
+pub fn extract_servers(work_order: &WorkOrder) -> BTreeMap<String, McpServerConfig> {
+    work_order
+        .config
+        .vendor
+        .get("abp")
+        .and_then(|abp| abp.get(MCP_SERVERS_VENDOR_KEY))
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// How [`McpBridge::inject`] offered the tools to a backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeMode {
+    /// Server configurations were passed through; the backend connects itself.
+    Native,
+    /// Tools were added as function tools; calls go through
+    /// [`McpBridge::execute`].
+    Emulated,
+}
+
+#[derive(Debug)]
+struct Server {
+    config: Option<McpServerConfig>,
+    client: McpClient,
+    tools: Vec<McpTool>,
+}
+
+/// A set of named MCP servers whose tools are offered to backends.
+///
+/// # Examples
+///
+/// ```no_run
+/// use abp_core::{CapabilityManifest, WorkOrderBuilder};
+/// use abp_mcp::{BridgeMode, McpBridge, McpServerConfig};
+///
+/// # async fn demo() -> Result<(), abp_mcp::McpError> {
+/// let mut bridge = McpBridge::new();
+/// bridge
+///     .connect("files", McpServerConfig::stdio("mcp-server-filesystem", ["/srv"]))
+///     .await?;
+///
+/// let mut wo = WorkOrderBuilder::new("List /srv").build();
+/// let mode = bridge.inject(&mut wo, &CapabilityManifest::new());
+/// assert_eq!(mode, BridgeMode::Emulated);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct McpBridge {
+    servers: BTreeMap<String, Server>,
+}
+
+impl McpBridge {
+    /// A bridge with no servers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect to a server, discover its tools, and add it as `name`,
+    /// replacing any server of that name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`McpError`] if the server cannot be reached or its tools
+    /// cannot be listed.
+    pub async fn connect(
+        &mut self,
+        name: impl Into<String>,
+        config: McpServerConfig,
+    ) -> Result<(), McpError> {
+        let client = config.connect().await?;
+        self.insert(name.into(), Some(config), client).await
+    }
+
+    /// Add an already connected client as `name` and discover its tools.
+    ///
+    /// Without a configuration the server cannot be handed to a native
+    /// backend, so [`inject`](Self::inject) always emulates it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`McpError`] if the tools cannot be listed.
+    pub async fn add_client(
+        &mut self,
+        name: impl Into<String>,
+        client: McpClient,
+    ) -> Result<(), McpError> {
+        self.insert(name.into(), None, client).await
+    }
+
+    async fn insert(
+        &mut self,
+        name: String,
+        config: Option<McpServerConfig>,
+        client: McpClient,
+    ) -> Result<(), McpError> {
+        let tools = client.list_tools().await?;
+        tracing::debug!(server = %name, tools = tools.len(), "MCP tools discovered");
+        self.servers.insert(
+            name,
+            Server {
+                config,
+                client,
+                tools,
+            },
+        );
+        Ok(())
+    }
+
+    /// Re-list every server's tools.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`McpError`]; servers listed before it keep their
+    /// new tools.
+    pub async fn refresh(&mut self) -> Result<(), McpError> {
+        for server in self.servers.values_mut() {
+            server.tools = server.client.list_tools().await?;
+        }
+        Ok(())
+    }
+
+    /// Names of the connected servers, sorted.
+    pub fn server_names(&self) -> impl Iterator<Item = &str> {
+        self.servers.keys().map(String::as_str)
+    }
+
+    /// Every bridged tool as an IR definition, by server then tool order.
+    #[must_use]
+    pub fn tool_definitions(&self) -> Vec<IrToolDefinition> {
+        self.servers
+            .iter()
+            .flat_map(|(server, s)| {
+                s.tools
+                    .iter()
+                    .map(|tool| tool.to_definition(qualified_name(server, &tool.name)))
+            })
+            .collect()
+    }
+
+    /// Whether `tool_name` is one of the bridged tools.
+    #[must_use]
+    pub fn is_bridged(&self, tool_name: &str) -> bool {
+        self.resolve(tool_name).is_some()
+    }
+
+    /// How [`inject`](Self::inject) would offer the tools to a backend with
+    /// `capabilities`.
+    ///
+    /// Native only when the backend declares [`Capability::McpClient`] as
+    /// native and every server has a configuration to pass on.
+    #[must_use]
+    pub fn mode_for(&self, capabilities: &CapabilityManifest) -> BridgeMode {
+        let native = matches!(
+            capabilities.get(&Capability::McpClient),
+            Some(SupportLevel::Native)
+        );
+        if native && self.servers.values().all(|s| s.config.is_some()) {
+            BridgeMode::Native
+        } else {
+            BridgeMode::Emulated
+        }
+    }
+
+    /// Offer the bridged tools on `work_order` to a backend with
+    /// `capabilities`.
+    ///
+    /// In [`BridgeMode::Native`] the server configurations go under
+    /// `config.vendor["abp"]["mcp_servers"]`. In [`BridgeMode::Emulated`] the
+    /// tool definitions are appended to `config.vendor["abp"]["tools"]`,
+    /// skipping names the work order already offers.
+    pub fn inject(
+        &self,
+        work_order: &mut WorkOrder,
+        capabilities: &CapabilityManifest,
+    ) -> BridgeMode {
+        let mode = self.mode_for(capabilities);
+        let abp = work_order
+            .config
+            .vendor
+            .entry("abp".to_string())
+            .or_insert_with(|| json!({}));
+        if !abp.is_object() {
+            *abp = json!({});
+        }
+        match mode {
+            BridgeMode::Native => {
+                let servers: BTreeMap<&str, &McpServerConfig> = self
+                    .servers
+                    .iter()
+                    .filter_map(|(name, s)| Some((name.as_str(), s.config.as_ref()?)))
+                    .collect();
+                abp[MCP_SERVERS_VENDOR_KEY] = serde_json::to_value(servers).unwrap_or_default();
+            }
+            BridgeMode::Emulated => {
+                let mut tools: Vec<IrToolDefinition> = abp
+                    .get("tools")
+                    .and_then(|v| serde_json::from_value(v.clone()).ok())
+                    .unwrap_or_default();
+                for def in self.tool_definitions() {
+                    if !tools.iter().any(|t| t.name == def.name) {
+                        tools.push(def);
+                    }
+                }
+                abp["tools"] = serde_json::to_value(tools).unwrap_or_default();
+            }
+        }
+        mode
+    }
+
+    /// Call a bridged tool by its qualified name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::UnknownTool`] if no server offers the tool, or the
+    /// server's error if the call fails.
+    pub async fn call(
+        &self,
+        tool_name: &str,
+        arguments: Value,
+    ) -> Result<CallToolResult, McpError> {
+        let (server, tool) = self
+            .resolve(tool_name)
+            .ok_or_else(|| McpError::UnknownTool(tool_name.to_string()))?;
+        server.client.call_tool(&tool.name, arguments).await
+    }
+
+    /// Answer an emulated tool call.
+    ///
+    /// Returns `None` when `call` is not a [`AgentEventKind::ToolCall`] for a
+    /// bridged tool. Otherwise returns the matching
+    /// [`AgentEventKind::ToolResult`]; transport failures become error
+    /// results so the model sees them.
+    pub async fn execute(&self, call: &AgentEventKind) -> Option<AgentEventKind> {
+        let AgentEventKind::ToolCall {
+            tool_name,
+            tool_use_id,
+            input,
+            ..
+        } = call
+        else {
+            return None;
+        };
+        if !self.is_bridged(tool_name) {
+            return None;
+        }
+        let (output, is_error) = match self.call(tool_name, input.clone()).await {
+            Ok(result) => (
+                json!({"content": result.content, "text": result.text()}),
+                result.is_error,
+            ),
+            Err(e) => (json!({"error": e.to_string()}), true),
+        };
+        Some(AgentEventKind::ToolResult {
+            tool_name: tool_name.clone(),
+            tool_use_id: tool_use_id.clone(),
+            output,
+            is_error,
+        })
+    }
+
+    /// Close every server connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`McpError`]; every server is still closed.
+    pub async fn close(&self) -> Result<(), McpError> {
+        let mut first = Ok(());
+        for server in self.servers.values() {
+            let result = server.client.close().await;
+            if first.is_ok() {
+                first = result;
+            }
+        }
+        first
+    }
+
+    fn resolve(&self, tool_name: &str) -> Option<(&Server, &McpTool)> {
+        let rest = tool_name.strip_prefix(TOOL_PREFIX)?;
+        // Server names may contain "__", so try every split point.
+        rest.match_indices("__").find_map(|(at, _)| {
+            let server = self.servers.get(&rest[..at])?;
+            let tool = server.tools.iter().find(|t| t.name == rest[at + 2..])?;
+            Some((server, tool))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn server_configs_round_trip_through_a_work_order() {
+        let mut wo = abp_core::WorkOrderBuilder::new("t").build();
+        let servers = BTreeMap::from([
+            (
+                "files".to_string(),
+                McpServerConfig::stdio("fs-server", ["/srv"]),
+            ),
+            (
+                "web".to_string(),
+                McpServerConfig::sse("http://localhost:9000/sse"),
+            ),
+        ]);
+        wo.config.vendor.insert(
+            "abp".into(),
+            json!({ MCP_SERVERS_VENDOR_KEY: serde_json::to_value(&servers).unwrap() }),
+        );
+        assert_eq!(extract_servers(&wo), servers);
+        assert_eq!(
+            serde_json::to_value(&servers["web"]).unwrap(),
+            json!({"transport": "sse", "url": "http://localhost:9000/sse"})
+        );
+    }
+
+    #[test]
+    fn qualified_names_use_the_claude_convention() {
+        assert_eq!(qualified_name("files", "read"), "mcp__files__read");
+    }
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! JSON-RPC client for a single MCP server.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use tokio::sync::Mutex;
+
+use crate::McpError;
+use crate::protocol::{
+    self, CallToolResult, METHOD_NOT_FOUND, McpTool, PROTOCOL_VERSION, ServerInfo,
+};
+use crate::transport::Transport;
+
+/// How long a request may wait for its response by default.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A connected, initialised MCP server.
+///
+/// Requests are serialised: each one waits for its own response before the
+/// next is sent. Server notifications received meanwhile are dropped and
+/// server requests other than `ping` are answered with "method not found".
+pub struct McpClient {
+    transport: Box<dyn Transport>,
+    exchange: Mutex<()>,
+    next_id: AtomicU64,
+    timeout: Duration,
+    server: ServerInfo,
+}
+
+impl std::fmt::Debug for McpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("McpClient")
+            .field("server", &self.server)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl McpClient {
+    /// Run the `initialize` handshake over `transport`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`McpError`] if the server does not complete the handshake.
+    pub async fn connect(transport: impl Transport + 'static) -> Result<Self, McpError> {
+        Self::connect_with_timeout(transport, DEFAULT_REQUEST_TIMEOUT).await
+    }
+
+    /// Like [`connect`](Self::connect), with a per-request `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`McpError`] if the server does not complete the handshake.
+    pub async fn connect_with_timeout(
+        transport: impl Transport + 'static,
+        timeout: Duration,
+    ) -> Result<Self, McpError> {
+        let mut client = Self {
+            transport: Box::new(transport),
+            exchange: Mutex::new(()),
+            next_id: AtomicU64::new(1),
+            timeout,
+            server: ServerInfo {
+                name: String::new(),
+                version: String::new(),
+                protocol_version: String::new(),
+            },
+        };
+        let result = client
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {"name": "abp-mcp", "version": env!("CARGO_PKG_VERSION")},
+                }),
+            )
+            .await?;
+        let info = result.get("serverInfo");
+        let field = |v: Option<&Value>| v.and_then(Value::as_str).unwrap_or_default().to_string();
+        client.server = ServerInfo {
+            name: field(info.and_then(|i| i.get("name"))),
+            version: field(info.and_then(|i| i.get("version"))),
+            protocol_version: field(result.get("protocolVersion")),
+        };
+        client
+            .transport
+            .send(protocol::notification("notifications/initialized"))
+            .await?;
+        tracing::debug!(server = %client.server.name, "MCP server initialised");
+        Ok(client)
+    }
+
+    /// What the server reported during the handshake.
+    #[must_use]
+    pub fn server(&self) -> &ServerInfo {
+        &self.server
+    }
+
+    /// Every tool the server offers, following pagination cursors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`McpError`] if a page cannot be fetched or parsed.
+    pub async fn list_tools(&self) -> Result<Vec<McpTool>, McpError> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(c) => json!({"cursor": c}),
+                None => json!({}),
+            };
+            let mut page = self.request("tools/list", params).await?;
+            let batch: Vec<McpTool> = serde_json::from_value(page["tools"].take())
+                .map_err(|e| McpError::Malformed(format!("tools/list: {e}")))?;
+            tools.extend(batch);
+            cursor = page
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            if cursor.is_none() {
+                return Ok(tools);
+            }
+        }
+    }
+
+    /// Call tool `name` with `arguments`.
+    ///
+    /// A tool that fails reports it through
+    /// [`CallToolResult::is_error`], not through this method's error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`McpError`] if the request itself fails.
+    pub async fn call_tool(
+        &self,
+        name: &str,
+        arguments: Value,
+    ) -> Result<CallToolResult, McpError> {
+        let result = self
+            .request("tools/call", json!({"name": name, "arguments": arguments}))
+            .await?;
+        serde_json::from_value(result).map_err(|e| McpError::Malformed(format!("tools/call: {e}")))
+    }
+
+    /// Shut the transport down.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`McpError`] if the transport could not be closed.
+    pub async fn close(&self) -> Result<(), McpError> {
+        self.transport.close().await
+    }
+
+    /// Send `method` and wait for the matching response.
+    async fn request(&self, method: &str, params: Value) -> Result<Value, McpError> {
+        let _exchange = self.exchange.lock().await;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.transport
+            .send(protocol::request(id, method, params))
+            .await?;
+        let wait = async {
+            loop {
+                let message = self.transport.receive().await?;
+                if message.get("method").is_some() {
+                    self.answer_server(&message).await?;
+                    continue;
+                }
+                if message.get("id").and_then(Value::as_u64) == Some(id) {
+                    return protocol::into_result(message);
+                }
+                tracing::debug!(%message, "dropping unexpected MCP message");
+            }
+        };
+        tokio::time::timeout(self.timeout, wait)
+            .await
+            .map_err(|_| McpError::Timeout {
+                method: method.to_string(),
+            })?
+    }
+
+    /// Reply to a server-initiated request; notifications need no reply.
+    async fn answer_server(&self, message: &Value) -> Result<(), McpError> {
+        let Some(id) = message.get("id") else {
+            return Ok(());
+        };
+        let reply = if message["method"] == "ping" {
+            json!({"jsonrpc": "2.0", "id": id, "result": {}})
+        } else {
+            json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {"code": METHOD_NOT_FOUND, "message": "method not supported by client"},
+            })
+        };
+        self.transport.send(reply).await
+    }
+}
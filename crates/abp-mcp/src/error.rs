@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Errors raised while talking to MCP servers.
+
+/// An MCP exchange failed.
+#[derive(Debug, thiserror::Error)]
+pub enum McpError {
+    /// The server process could not be started.
+    #[error("failed to spawn MCP server '{command}'")]
+    Spawn {
+        /// Program that was run.
+        command: String,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// Reading from or writing to the server failed.
+    #[error("MCP transport I/O failed")]
+    Io(#[from] std::io::Error),
+
+    /// An HTTP request to the server failed.
+    #[error("MCP HTTP request failed")]
+    Http(#[from] reqwest::Error),
+
+    /// The server sent something that is not valid JSON-RPC.
+    #[error("malformed MCP message: {0}")]
+    Malformed(String),
+
+    /// The server answered a request with a JSON-RPC error.
+    #[error("MCP server error {code}: {message}")]
+    Rpc {
+        /// JSON-RPC error code.
+        code: i64,
+        /// Error message from the server.
+        message: String,
+    },
+
+    /// The server closed the connection.
+    #[error("MCP server closed the connection")]
+    Closed,
+
+    /// The server did not answer within the request timeout.
+    #[error("MCP request '{method}' timed out")]
+    Timeout {
+        /// Method that was requested.
+        method: String,
+    },
+
+    /// No connected server offers the tool.
+    #[error("unknown MCP tool '{0}'")]
+    UnknownTool(String),
+}
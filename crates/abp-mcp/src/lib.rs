@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+#![doc = include_str!("../README.md")]
+#![deny(unsafe_code)]
+#![warn(missing_docs)]
+
+//! Model Context Protocol client and tool bridge for Agent Backplane.
+
+mod bridge;
+mod client;
+mod error;
+pub mod protocol;
+pub mod transport;
+
+pub use bridge::{
+    BridgeMode, MCP_SERVERS_VENDOR_KEY, McpBridge, McpServerConfig, TOOL_PREFIX, extract_servers,
+    qualified_name,
+};
+pub use client::{DEFAULT_REQUEST_TIMEOUT, McpClient};
+pub use error::McpError;
+pub use protocol::{CallToolResult, McpTool, PROTOCOL_VERSION, ServerInfo};
+pub use transport::{SseTransport, StdioTransport, Transport};
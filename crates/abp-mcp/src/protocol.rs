@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! MCP wire types.
+//!
+//! Only the parts of the protocol the client uses are modelled: the
+//! `initialize` handshake, `tools/list`, and `tools/call`. Everything else is
+//! kept as raw JSON.
+
+use abp_core::ir::IrToolDefinition;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::McpError;
+
+/// MCP protocol revision the client announces.
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// JSON-RPC error code for methods the client does not implement.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Build a JSON-RPC request.
+#[must_use]
+pub fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params})
+}
+
+/// Build a JSON-RPC notification.
+#[must_use]
+pub fn notification(method: &str) -> Value {
+    json!({"jsonrpc": "2.0", "method": method})
+}
+
+/// Turn a JSON-RPC response into its `result`.
+///
+/// # Errors
+///
+/// Returns [`McpError::Rpc`] for error responses and
+/// [`McpError::Malformed`] when neither field is present.
+pub fn into_result(mut response: Value) -> Result<Value, McpError> {
+    if let Some(error) = response.get("error") {
+        return Err(McpError::Rpc {
+            code: error.get("code").and_then(Value::as_i64).unwrap_or(0),
+            message: error
+                .get("message")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+    response
+        .get_mut("result")
+        .map(Value::take)
+        .ok_or_else(|| McpError::Malformed(format!("response without result: {response}")))
+}
+
+/// What the server reported about itself during `initialize`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerInfo {
+    /// Server name.
+    pub name: String,
+    /// Server version.
+    #[serde(default)]
+    pub version: String,
+    /// Protocol revision the server agreed to.
+    #[serde(default)]
+    pub protocol_version: String,
+}
+
+/// A tool offered by an MCP server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpTool {
+    /// Tool name, unique within its server.
+    pub name: String,
+    /// What the tool does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// JSON Schema of the tool's arguments.
+    #[serde(rename = "inputSchema", default = "empty_schema")]
+    pub input_schema: Value,
+}
+
+fn empty_schema() -> Value {
+    json!({"type": "object"})
+}
+
+impl McpTool {
+    /// This tool as an IR definition offered under `name`.
+    #[must_use]
+    pub fn to_definition(&self, name: impl Into<String>) -> IrToolDefinition {
+        IrToolDefinition {
+            name: name.into(),
+            description: self.description.clone().unwrap_or_default(),
+            parameters: self.input_schema.clone(),
+        }
+    }
+}
+
+/// Outcome of a `tools/call` request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CallToolResult {
+    /// Content blocks (`text`, `image`, `resource`, ...) as sent.
+    #[serde(default)]
+    pub content: Vec<Value>,
+    /// Whether the tool reported a failure.
+    #[serde(rename = "isError", default)]
+    pub is_error: bool,
+}
+
+impl CallToolResult {
+    /// The text blocks of the result, joined by newlines.
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter(|block| block.get("type").and_then(Value::as_str) == Some("text"))
+            .filter_map(|block| block.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_responses_become_rpc_errors() {
+        let err = into_result(json!({
+            "jsonrpc": "2.0", "id": 1,
+            "error": {"code": -32602, "message": "bad params"}
+        }))
+        .unwrap_err();
+        assert!(matches!(err, McpError::Rpc { code: -32602, .. }));
+    }
+
+    #[test]
+    fn text_joins_only_text_blocks() {
+        let result: CallToolResult = serde_json::from_value(json!({
+            "content": [
+                {"type": "text", "text": "a"},
+                {"type": "image", "data": "...", "mimeType": "image/png"},
+                {"type": "text", "text": "b"}
+            ]
+        }))
+        .unwrap();
+        assert_eq!(result.text(), "a\nb");
+        assert!(!result.is_error);
+    }
+
+    #[test]
+    fn tool_without_schema_takes_any_object() {
+        let tool: McpTool = serde_json::from_value(json!({"name": "ping"})).unwrap();
+        let def = tool.to_definition("mcp__s__ping");
+        assert_eq!(def.parameters, json!({"type": "object"}));
+        assert_eq!(def.description, "");
+    }
+}
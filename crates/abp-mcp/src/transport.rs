@@ -0,0 +1,309 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Message transports between the client and an MCP server.
+//!
+//! A [`Transport`] moves whole JSON-RPC messages;
+//! request/response matching is left to [`McpClient`](crate::McpClient).
+
+use std::collections::BTreeMap;
+use std::process::Stdio;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::{Mutex, mpsc};
+use tokio::task::JoinHandle;
+
+use crate::McpError;
+
+/// A bidirectional channel of JSON-RPC messages.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Send one message to the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`McpError`] if the message could not be delivered.
+    async fn send(&self, message: Value) -> Result<(), McpError>;
+
+    /// Wait for the next message from the server.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::Closed`] once the server has gone away.
+    async fn receive(&self) -> Result<Value, McpError>;
+
+    /// Shut the connection down.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`McpError`] if the server could not be stopped cleanly.
+    async fn close(&self) -> Result<(), McpError> {
+        Ok(())
+    }
+}
+
+// ── stdio ───────────────────────────────────────────────────────────────
+
+/// Newline-delimited JSON over a child process's stdin and stdout.
+///
+/// The child is killed when the transport is dropped. Its stderr is
+/// inherited, since MCP servers log there.
+#[derive(Debug)]
+pub struct StdioTransport {
+    child: Mutex<Child>,
+    stdin: Mutex<ChildStdin>,
+    stdout: Mutex<Lines<BufReader<ChildStdout>>>,
+}
+
+impl StdioTransport {
+    /// Spawn `command` with `args` and extra environment variables `env`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`McpError::Spawn`] if the process could not be started.
+    pub fn spawn<I, S>(
+        command: &str,
+        args: I,
+        env: &BTreeMap<String, String>,
+    ) -> Result<Self, McpError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<std::ffi::OsStr>,
+    {
+        let mut child = Command::new(command)
+            .args(args)
+            .envs(env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|source| McpError::Spawn {
+                command: command.to_string(),
+                source,
+            })?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        Ok(Self {
+            child: Mutex::new(child),
+            stdin: Mutex::new(stdin),
+            stdout: Mutex::new(BufReader::new(stdout).lines()),
+        })
+    }
+}
+
+#[async_trait]
+impl Transport for StdioTransport {
+    async fn send(&self, message: Value) -> Result<(), McpError> {
+        let mut line = message.to_string();
+        line.push('\n');
+        let mut stdin = self.stdin.lock().await;
+        stdin.write_all(line.as_bytes()).await?;
+        stdin.flush().await?;
+        Ok(())
+    }
+
+    async fn receive(&self) -> Result<Value, McpError> {
+        let mut stdout = self.stdout.lock().await;
+        loop {
+            let line = stdout.next_line().await?.ok_or(McpError::Closed)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            return serde_json::from_str(&line)
+                .map_err(|e| McpError::Malformed(format!("{e}: {line}")));
+        }
+    }
+
+    async fn close(&self) -> Result<(), McpError> {
+        let mut child = self.child.lock().await;
+        if child.try_wait()?.is_none() {
+            child.kill().await?;
+        }
+        Ok(())
+    }
+}
+
+// ── SSE ─────────────────────────────────────────────────────────────────
+
+/// One Server-Sent Event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SseEvent {
+    event: String,
+    data: String,
+}
+
+/// Incremental parser for a `text/event-stream` body.
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    /// Feed a chunk and return the events it completed.
+    ///
+    /// Works on bytes so a UTF-8 sequence or CRLF split across chunks is
+    /// decoded only once its event is complete.
+    fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend(chunk.iter().filter(|b| **b != b'\r'));
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.windows(2).position(|w| w == b"\n\n") {
+            let block: Vec<u8> = self.buffer.drain(..end + 2).collect();
+            let block = String::from_utf8_lossy(&block);
+            let mut event = String::from("message");
+            let mut data = Vec::new();
+            for line in block.lines() {
+                if let Some(value) = line.strip_prefix("event:") {
+                    event = value.trim().to_string();
+                } else if let Some(value) = line.strip_prefix("data:") {
+                    data.push(value.strip_prefix(' ').unwrap_or(value));
+                }
+            }
+            if !data.is_empty() {
+                events.push(SseEvent {
+                    event,
+                    data: data.join("\n"),
+                });
+            }
+        }
+        events
+    }
+}
+
+/// MCP's HTTP+SSE transport.
+///
+/// Server messages arrive as `message` events on a long-lived GET stream;
+/// client messages are POSTed to the URL announced by the stream's first
+/// `endpoint` event.
+#[derive(Debug)]
+pub struct SseTransport {
+    http: reqwest::Client,
+    endpoint: reqwest::Url,
+    events: Mutex<mpsc::Receiver<SseEvent>>,
+    reader: JoinHandle<()>,
+}
+
+impl SseTransport {
+    /// Open the event stream at `url` and wait for the POST endpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`McpError`] if the stream cannot be opened or closes
+    /// before announcing an endpoint.
+    pub async fn connect(url: &str) -> Result<Self, McpError> {
+        let http = reqwest::Client::new();
+        let url = reqwest::Url::parse(url).map_err(|e| McpError::Malformed(e.to_string()))?;
+        let response = http
+            .get(url.clone())
+            .header(reqwest::header::ACCEPT, "text/event-stream")
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let (tx, mut rx) = mpsc::channel(64);
+        let reader = tokio::spawn(async move {
+            let mut body = response.bytes_stream();
+            let mut parser = SseParser::default();
+            while let Some(Ok(chunk)) = body.next().await {
+                for event in parser.push(&chunk) {
+                    if tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+
+        let endpoint = loop {
+            let Some(event) = rx.recv().await else {
+                reader.abort();
+                return Err(McpError::Closed);
+            };
+            if event.event == "endpoint" {
+                break url
+                    .join(event.data.trim())
+                    .map_err(|e| McpError::Malformed(e.to_string()))?;
+            }
+        };
+        Ok(Self {
+            http,
+            endpoint,
+            events: Mutex::new(rx),
+            reader,
+        })
+    }
+
+    /// URL client messages are POSTed to.
+    #[must_use]
+    pub fn endpoint(&self) -> &reqwest::Url {
+        &self.endpoint
+    }
+}
+
+#[async_trait]
+impl Transport for SseTransport {
+    async fn send(&self, message: Value) -> Result<(), McpError> {
+        self.http
+            .post(self.endpoint.clone())
+            .json(&message)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    async fn receive(&self) -> Result<Value, McpError> {
+        let mut events = self.events.lock().await;
+        loop {
+            let event = events.recv().await.ok_or(McpError::Closed)?;
+            if event.event != "message" {
+                continue;
+            }
+            return serde_json::from_str(&event.data)
+                .map_err(|e| McpError::Malformed(format!("{e}: {}", event.data)));
+        }
+    }
+
+    async fn close(&self) -> Result<(), McpError> {
+        self.reader.abort();
+        Ok(())
+    }
+}
+
+impl Drop for SseTransport {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parser_handles_split_chunks_and_multiline_data() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b"event: endpoint\r\ndata: /mes").is_empty());
+        let events = parser.push(b"sages?id=1\r\n\r\ndata: {\"a\":\ndata: 1}\n\n");
+        assert_eq!(
+            events,
+            vec![
+                SseEvent {
+                    event: "endpoint".into(),
+                    data: "/messages?id=1".into(),
+                },
+                SseEvent {
+                    event: "message".into(),
+                    data: "{\"a\":\n1}".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn comments_and_empty_blocks_are_skipped() {
+        let mut parser = SseParser::default();
+        assert!(parser.push(b": keep-alive\n\n").is_empty());
+    }
+}
@@ -0,0 +1,291 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the MCP client, its transports, and the tool bridge.
+
+use std::sync::{Arc, Mutex};
+
+use abp_core::ir::IrToolDefinition;
+use abp_core::{AgentEventKind, Capability, CapabilityManifest, SupportLevel, WorkOrderBuilder};
+use abp_mcp::{
+    BridgeMode, McpBridge, McpClient, McpError, McpServerConfig, SseTransport, Transport,
+    extract_servers,
+};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+
+/// Answers of a small MCP server with an `echo` tool and a `fail` tool split
+/// over two `tools/list` pages.
+fn answer(request: &Value) -> Option<Value> {
+    let id = request.get("id")?.clone();
+    let params = &request["params"];
+    let result = match request["method"].as_str()? {
+        "initialize" => json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": {"tools": {}},
+            "serverInfo": {"name": "test-server", "version": "0.3.0"},
+        }),
+        "tools/list" if params.get("cursor").is_none() => json!({
+            "tools": [{
+                "name": "echo",
+                "description": "Echo the text back",
+                "inputSchema": {"type": "object", "properties": {"text": {"type": "string"}}},
+            }],
+            "nextCursor": "page-2",
+        }),
+        "tools/list" => json!({"tools": [{"name": "fail"}]}),
+        "tools/call" if params["name"] == "echo" => json!({
+            "content": [{"type": "text", "text": params["arguments"]["text"]}],
+        }),
+        "tools/call" => json!({
+            "content": [{"type": "text", "text": "boom"}],
+            "isError": true,
+        }),
+        _ => {
+            return Some(json!({
+                "jsonrpc": "2.0", "id": id,
+                "error": {"code": -32601, "message": "no such method"},
+            }));
+        }
+    };
+    Some(json!({"jsonrpc": "2.0", "id": id, "result": result}))
+}
+
+/// In-process transport that pings the client before every response.
+struct FakeServer {
+    outbox: mpsc::UnboundedSender<Value>,
+    inbox: tokio::sync::Mutex<mpsc::UnboundedReceiver<Value>>,
+    seen: Arc<Mutex<Vec<Value>>>,
+}
+
+impl FakeServer {
+    fn new() -> (Self, Arc<Mutex<Vec<Value>>>) {
+        let (outbox, inbox) = mpsc::unbounded_channel();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let server = Self {
+            outbox,
+            inbox: tokio::sync::Mutex::new(inbox),
+            seen: seen.clone(),
+        };
+        (server, seen)
+    }
+}
+
+#[async_trait]
+impl Transport for FakeServer {
+    async fn send(&self, message: Value) -> Result<(), McpError> {
+        self.seen.lock().unwrap().push(message.clone());
+        if message.get("method").is_some()
+            && let Some(response) = answer(&message)
+        {
+            let _ = self
+                .outbox
+                .send(json!({"jsonrpc": "2.0", "method": "notifications/progress"}));
+            let _ = self
+                .outbox
+                .send(json!({"jsonrpc": "2.0", "id": "srv-1", "method": "ping"}));
+            let _ = self.outbox.send(response);
+        }
+        Ok(())
+    }
+
+    async fn receive(&self) -> Result<Value, McpError> {
+        self.inbox.lock().await.recv().await.ok_or(McpError::Closed)
+    }
+}
+
+#[tokio::test]
+async fn client_handshakes_pages_tools_and_answers_pings() {
+    let (server, seen) = FakeServer::new();
+    let client = McpClient::connect(server).await.unwrap();
+    assert_eq!(client.server().name, "test-server");
+    assert_eq!(client.server().protocol_version, "2024-11-05");
+
+    let tools = client.list_tools().await.unwrap();
+    let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["echo", "fail"]);
+
+    let result = client
+        .call_tool("echo", json!({"text": "hi"}))
+        .await
+        .unwrap();
+    assert_eq!(result.text(), "hi");
+
+    let seen = seen.lock().unwrap();
+    assert!(
+        seen.iter()
+            .any(|m| m["method"] == "notifications/initialized")
+    );
+    assert!(
+        seen.iter()
+            .any(|m| m["id"] == "srv-1" && m["result"] == json!({})),
+        "ping was not answered: {seen:?}"
+    );
+}
+
+#[tokio::test]
+async fn bridge_emulates_mcp_for_backends_without_native_support() {
+    let (server, _) = FakeServer::new();
+    let mut bridge = McpBridge::new();
+    bridge
+        .add_client("kit", McpClient::connect(server).await.unwrap())
+        .await
+        .unwrap();
+
+    let mut wo = WorkOrderBuilder::new("t")
+        .tools(vec![IrToolDefinition {
+            name: "local".into(),
+            description: String::new(),
+            parameters: json!({"type": "object"}),
+        }])
+        .build();
+    // No configuration to hand over, so even a native backend gets emulation.
+    let caps = CapabilityManifest::from([(Capability::McpClient, SupportLevel::Native)]);
+    assert_eq!(bridge.inject(&mut wo, &caps), BridgeMode::Emulated);
+
+    let offered: Vec<IrToolDefinition> =
+        serde_json::from_value(wo.config.vendor["abp"]["tools"].clone()).unwrap();
+    let names: Vec<_> = offered.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["local", "mcp__kit__echo", "mcp__kit__fail"]);
+    assert_eq!(offered[1].description, "Echo the text back");
+
+    let call = AgentEventKind::ToolCall {
+        tool_name: "mcp__kit__echo".into(),
+        tool_use_id: Some("tu-1".into()),
+        parent_tool_use_id: None,
+        input: json!({"text": "via bridge"}),
+    };
+    let Some(AgentEventKind::ToolResult {
+        tool_use_id,
+        output,
+        is_error,
+        ..
+    }) = bridge.execute(&call).await
+    else {
+        panic!("bridged call was not executed");
+    };
+    assert_eq!(tool_use_id.as_deref(), Some("tu-1"));
+    assert_eq!(output["text"], "via bridge");
+    assert!(!is_error);
+
+    let failed = bridge.call("mcp__kit__fail", json!({})).await.unwrap();
+    assert!(failed.is_error);
+
+    let local = AgentEventKind::ToolCall {
+        tool_name: "local".into(),
+        tool_use_id: None,
+        parent_tool_use_id: None,
+        input: json!({}),
+    };
+    assert!(bridge.execute(&local).await.is_none());
+    assert!(matches!(
+        bridge.call("mcp__other__echo", json!({})).await,
+        Err(McpError::UnknownTool(_))
+    ));
+}
+
+#[cfg(unix)]
+const STDIO_SERVER: &str = r#"
+while IFS= read -r line; do
+  id=$(printf '%s' "$line" | sed -n 's/.*"id":\([0-9]*\).*/\1/p')
+  [ -z "$id" ] && continue
+  case "$line" in
+    *'"initialize"'*) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"protocolVersion\":\"2024-11-05\",\"capabilities\":{},\"serverInfo\":{\"name\":\"sh-server\",\"version\":\"1\"}}}" ;;
+    *'"tools/list"'*) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"tools\":[{\"name\":\"date\",\"inputSchema\":{\"type\":\"object\"}}]}}" ;;
+    *'"tools/call"'*) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"content\":[{\"type\":\"text\",\"text\":\"today\"}]}}" ;;
+  esac
+done
+"#;
+
+#[cfg(unix)]
+#[tokio::test]
+async fn stdio_server_is_bridged_natively_when_the_backend_supports_mcp() {
+    let config = McpServerConfig::stdio("sh", ["-c", STDIO_SERVER]);
+    let mut bridge = McpBridge::new();
+    bridge.connect("clock", config.clone()).await.unwrap();
+
+    let result = bridge.call("mcp__clock__date", json!({})).await.unwrap();
+    assert_eq!(result.text(), "today");
+
+    let mut native = WorkOrderBuilder::new("t").build();
+    let caps = CapabilityManifest::from([(Capability::McpClient, SupportLevel::Native)]);
+    assert_eq!(bridge.inject(&mut native, &caps), BridgeMode::Native);
+    assert_eq!(extract_servers(&native)["clock"], config);
+    assert!(native.config.vendor["abp"].get("tools").is_none());
+
+    let mut emulated = WorkOrderBuilder::new("t").build();
+    let caps = CapabilityManifest::from([(Capability::McpClient, SupportLevel::Unsupported)]);
+    assert_eq!(bridge.inject(&mut emulated, &caps), BridgeMode::Emulated);
+    assert_eq!(
+        abp_backend_tools(&emulated),
+        ["mcp__clock__date".to_string()]
+    );
+    bridge.close().await.unwrap();
+}
+
+#[cfg(unix)]
+fn abp_backend_tools(wo: &abp_core::WorkOrder) -> Vec<String> {
+    let tools: Vec<IrToolDefinition> =
+        serde_json::from_value(wo.config.vendor["abp"]["tools"].clone()).unwrap();
+    tools.into_iter().map(|t| t.name).collect()
+}
+
+#[tokio::test]
+async fn sse_transport_posts_to_the_announced_endpoint() {
+    use axum::extract::State;
+    use axum::response::sse::{Event, Sse};
+    use axum::routing::{get, post};
+    use tokio_stream::StreamExt;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    type Stream = Arc<Mutex<Option<mpsc::UnboundedReceiver<Event>>>>;
+    let (events, rx) = mpsc::unbounded_channel::<Event>();
+    let stream: Stream = Arc::new(Mutex::new(Some(rx)));
+
+    let app = axum::Router::new()
+        .route(
+            "/sse",
+            get(
+                |State((_, stream)): State<(mpsc::UnboundedSender<Event>, Stream)>| async move {
+                    let rx = stream.lock().unwrap().take().expect("one stream");
+                    let endpoint = Event::default()
+                        .event("endpoint")
+                        .data("/messages?session=1");
+                    let rest = UnboundedReceiverStream::new(rx);
+                    Sse::new(
+                        tokio_stream::once(endpoint)
+                            .chain(rest)
+                            .map(Ok::<_, std::convert::Infallible>),
+                    )
+                },
+            ),
+        )
+        .route(
+            "/messages",
+            post(
+                |State((events, _)): State<(mpsc::UnboundedSender<Event>, Stream)>,
+                 axum::Json(message): axum::Json<Value>| async move {
+                    if let Some(response) = answer(&message) {
+                        let _ = events
+                            .send(Event::default().event("message").data(response.to_string()));
+                    }
+                    axum::http::StatusCode::ACCEPTED
+                },
+            ),
+        )
+        .with_state((events, stream));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let transport = SseTransport::connect(&format!("http://{addr}/sse"))
+        .await
+        .unwrap();
+    assert_eq!(transport.endpoint().path(), "/messages");
+    let client = McpClient::connect(transport).await.unwrap();
+    assert_eq!(client.server().name, "test-server");
+    let result = client
+        .call_tool("echo", json!({"text": "over sse"}))
+        .await
+        .unwrap();
+    assert_eq!(result.text(), "over sse");
+}
@@ -350,6 +350,11 @@ impl From<ChatCompletionRequest> for WorkOrder {
 
         let mut builder = WorkOrderBuilder::new(task).model(&req.model);
 
+        // Negative seeds have no ABP equivalent and are dropped.
+        if let Some(seed) = req.seed.and_then(|s| u64::try_from(s).ok()) {
+            builder = builder.seed(seed);
+        }
+
         if !snippets.is_empty() {
             builder = builder.context(abp_core::ContextPacket {
                 files: vec![],
@@ -875,6 +880,16 @@ mod tests {
         assert_eq!(wo.config.model.as_deref(), Some("gpt-4-turbo"));
     }
 
+    #[test]
+    fn request_to_work_order_propagates_seed() {
+        let mut req = make_request(vec![Message::User {
+            content: "Hello".into(),
+        }]);
+        req.seed = Some(1234);
+        let wo: WorkOrder = req.into();
+        assert_eq!(wo.config.vendor["abp"]["seed"], serde_json::json!(1234));
+    }
+
     #[test]
     fn request_to_work_order_maps_system_to_snippets() {
         let req = make_request(vec![
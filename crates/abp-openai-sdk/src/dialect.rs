@@ -4,11 +4,13 @@
 use abp_core::{
     AgentEvent, AgentEventKind, Capability, CapabilityManifest, SupportLevel, WorkOrder,
 };
+use abp_integrations::VendorConfig;
 use chrono::Utc;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::response_format::ResponseFormat;
+use crate::vendor::OpenAIVendorConfig;
 
 /// Version string for this dialect adapter.
 pub const DIALECT_VERSION: &str = "openai/v0.1";
@@ -329,36 +331,54 @@ pub struct OpenAIUsage {
 
 /// Map an ABP [`WorkOrder`] to an [`OpenAIRequest`].
 ///
-/// Uses the work order task as the initial user message and applies
-/// config defaults where the work order does not specify overrides.
+/// Sends the attached conversation history when there is one (see
+/// [`extract_conversation`](abp_integrations::extract_conversation)), with
+/// context snippets as a trailing user message; otherwise uses the work
+/// order task as the initial user message. Knobs under
+/// `config.vendor.openai` (see [`OpenAIVendorConfig`]) override the config
+/// defaults; other backends' namespaces are ignored.
 pub fn map_work_order(wo: &WorkOrder, config: &OpenAIConfig) -> OpenAIRequest {
+    let vendor = OpenAIVendorConfig::from_work_order_or_default(wo);
     let model = wo
         .config
         .model
         .as_deref()
+        .or(vendor.model.as_deref())
         .unwrap_or(&config.model)
         .to_string();
 
-    let mut user_content = wo.task.clone();
+    let mut snippets = String::new();
     for snippet in &wo.context.snippets {
-        user_content.push_str(&format!(
+        snippets.push_str(&format!(
             "\n\n--- {} ---\n{}",
             snippet.name, snippet.content
         ));
     }
+    let user_message = |content: String| OpenAIMessage {
+        role: "user".into(),
+        content: Some(content),
+        tool_calls: None,
+        tool_call_id: None,
+    };
+
+    let messages = match abp_integrations::extract_conversation(wo) {
+        Some(conv) if !conv.is_empty() => {
+            let mut messages = crate::lowering::from_ir(&conv);
+            if !snippets.is_empty() {
+                messages.push(user_message(snippets.trim_start().to_string()));
+            }
+            messages
+        }
+        _ => vec![user_message(format!("{}{snippets}", wo.task))],
+    };
 
     OpenAIRequest {
         model,
-        messages: vec![OpenAIMessage {
-            role: "user".into(),
-            content: Some(user_content),
-            tool_calls: None,
-            tool_call_id: None,
-        }],
+        messages,
         tools: None,
         tool_choice: None,
-        temperature: config.temperature,
-        max_tokens: config.max_tokens,
+        temperature: vendor.temperature.or(config.temperature),
+        max_tokens: vendor.max_tokens.or(config.max_tokens),
         response_format: None,
     }
 }
@@ -408,6 +428,7 @@ pub fn map_response(resp: &OpenAIResponse) -> Vec<AgentEvent> {
 mod tests {
     use super::*;
     use abp_core::WorkOrderBuilder;
+    use abp_core::ir::{IrConversation, IrMessage, IrRole};
 
     #[test]
     fn default_config_has_sensible_values() {
@@ -434,6 +455,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn map_work_order_sends_conversation_history() {
+        let conv = IrConversation::new()
+            .push(IrMessage::text(IrRole::System, "Be terse"))
+            .push(IrMessage::text(IrRole::User, "What is 2+2?"))
+            .push(IrMessage::text(IrRole::Assistant, "4"))
+            .push(IrMessage::text(IrRole::User, "And times 3?"));
+        let wo = WorkOrderBuilder::new("And times 3?")
+            .conversation(conv)
+            .build();
+        let req = map_work_order(&wo, &OpenAIConfig::default());
+
+        let roles: Vec<_> = req.messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["system", "user", "assistant", "user"]);
+        assert_eq!(req.messages[2].content.as_deref(), Some("4"));
+    }
+
     #[test]
     fn map_work_order_respects_model_override() {
         let wo = WorkOrderBuilder::new("task").model("gpt-4-turbo").build();
@@ -45,6 +45,13 @@ pub mod models;
 /// OpenAI `response_format` parameter.
 pub mod response_format;
 
+/// OpenAI Responses API types (`POST /v1/responses`).
+///
+/// Models `ResponsesRequest` with its typed input items (messages, function
+/// calls and outputs, reasoning) and `ResponsesResponse` with its output
+/// items and usage.
+pub mod responses;
+
 /// Server-sent event (SSE) streaming chunk types.
 ///
 /// Models `chat.completion.chunk` objects and maps them to ABP's
@@ -57,8 +64,14 @@ pub mod streaming;
 /// translated to a non-OpenAI backend and surfaces typed diagnostics.
 pub mod validation;
 
+/// Schema for the `config.vendor.openai` namespace.
+///
+/// `OpenAIVendorConfig` is validated by the runtime before dispatch and read
+/// by [`dialect::map_work_order`] during lowering.
+pub mod vendor;
+
 use abp_host::SidecarSpec;
-use abp_integrations::SidecarBackend;
+use abp_integrations::{SidecarBackend, VendorNamespace};
 use abp_runtime::Runtime;
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
@@ -102,7 +115,9 @@ pub fn register_backend(
 
     let mut spec = SidecarSpec::new(command);
     spec.args = vec![host_script.to_string_lossy().into_owned()];
-    runtime.register_backend(backend_name, SidecarBackend::new(spec));
+    let backend = SidecarBackend::new(spec)
+        .with_vendor_namespace(VendorNamespace::of::<vendor::OpenAIVendorConfig>());
+    runtime.register_backend(backend_name, backend);
     Ok(true)
 }
 
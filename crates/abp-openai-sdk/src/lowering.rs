@@ -3,10 +3,19 @@
 //!
 //! `to_ir` converts a slice of `OpenAIMessage`s into an `IrConversation`,
 //! and `from_ir` converts an `IrConversation` back into OpenAI messages.
+//! The `responses_*` functions do the same for the Responses API's input
+//! and output items.
+//!
+//! Cache breakpoints ([`IrMessage::cache_control`](abp_core::ir::IrMessage::cache_control)) are dropped: OpenAI
+//! caches long prompt prefixes automatically.
 
-use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole};
+use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole, IrUsage};
 
 use crate::dialect::{OpenAIFunctionCall, OpenAIMessage, OpenAIToolCall};
+use crate::responses::{
+    ReasoningSummary, ResponsesContentPart, ResponsesInput, ResponsesInputItem,
+    ResponsesMessageContent, ResponsesOutputItem, ResponsesRequest, ResponsesUsage,
+};
 
 /// Convert a slice of [`OpenAIMessage`]s into an [`IrConversation`].
 ///
@@ -30,8 +39,221 @@ pub fn from_ir(conv: &IrConversation) -> Vec<OpenAIMessage> {
     conv.messages.iter().map(message_from_ir).collect()
 }
 
+/// Convert a Responses API request into an [`IrConversation`].
+///
+/// `instructions` become a leading system message and a plain-text `input`
+/// a single user message. `developer` messages map to [`IrRole::System`].
+/// Function calls and reasoning items join the assistant message they
+/// follow, as [`IrContentBlock::ToolUse`] and [`IrContentBlock::Thinking`]
+/// blocks; function call outputs become [`IrRole::Tool`] messages.
+#[must_use]
+pub fn responses_to_ir(request: &ResponsesRequest) -> IrConversation {
+    let mut messages = Vec::new();
+    if let Some(instructions) = &request.instructions
+        && !instructions.is_empty()
+    {
+        messages.push(IrMessage::text(IrRole::System, instructions.clone()));
+    }
+    match &request.input {
+        ResponsesInput::Text(text) => messages.push(IrMessage::text(IrRole::User, text.clone())),
+        ResponsesInput::Items(items) => {
+            for item in items {
+                push_input_item(&mut messages, item);
+            }
+        }
+    }
+    IrConversation::from_messages(messages)
+}
+
+/// Convert an [`IrConversation`] into Responses API input items.
+///
+/// Assistant messages expand to their reasoning items, then the message
+/// text, then one `function_call` item per tool use. Tool results become
+/// `function_call_output` items.
+#[must_use]
+pub fn responses_input_from_ir(conv: &IrConversation) -> Vec<ResponsesInputItem> {
+    let mut items = Vec::new();
+    for msg in &conv.messages {
+        match msg.role {
+            IrRole::System | IrRole::User => {
+                items.push(ResponsesInputItem::message(
+                    map_role_from_ir(msg.role),
+                    msg.text_content(),
+                ));
+            }
+            IrRole::Assistant => {
+                let mut calls = Vec::new();
+                for block in &msg.content {
+                    match block {
+                        IrContentBlock::Thinking { text } => {
+                            items.push(ResponsesInputItem::Reasoning {
+                                id: None,
+                                summary: vec![ReasoningSummary::SummaryText { text: text.clone() }],
+                            });
+                        }
+                        IrContentBlock::ToolUse { id, name, input } => {
+                            calls.push(ResponsesInputItem::FunctionCall {
+                                id: None,
+                                call_id: id.clone(),
+                                name: name.clone(),
+                                arguments: serde_json::to_string(input).unwrap_or_default(),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                let text = msg.text_content();
+                if !text.is_empty() {
+                    items.push(ResponsesInputItem::Message {
+                        role: "assistant".into(),
+                        content: ResponsesMessageContent::Parts(vec![
+                            ResponsesContentPart::OutputText {
+                                text,
+                                annotations: Vec::new(),
+                            },
+                        ]),
+                    });
+                }
+                items.extend(calls);
+            }
+            IrRole::Tool => {
+                for block in &msg.content {
+                    if let IrContentBlock::ToolResult {
+                        tool_use_id,
+                        content,
+                        ..
+                    } = block
+                    {
+                        items.push(ResponsesInputItem::function_call_output(
+                            tool_use_id.clone(),
+                            IrMessage::new(IrRole::Tool, content.clone()).text_content(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    items
+}
+
+/// Convert the output items of a Responses API response into one assistant
+/// [`IrMessage`].
+#[must_use]
+pub fn responses_output_to_ir(output: &[ResponsesOutputItem]) -> IrMessage {
+    let mut blocks = Vec::new();
+    for item in output {
+        match item {
+            ResponsesOutputItem::Message { content, .. } => {
+                blocks.extend(content.iter().map(|part| IrContentBlock::Text {
+                    text: part.text().to_string(),
+                }));
+            }
+            ResponsesOutputItem::FunctionCall {
+                call_id,
+                name,
+                arguments,
+                ..
+            } => blocks.push(IrContentBlock::ToolUse {
+                id: call_id.clone(),
+                name: name.clone(),
+                input: parse_arguments(arguments),
+            }),
+            ResponsesOutputItem::Reasoning { summary, .. } => {
+                blocks.push(IrContentBlock::Thinking {
+                    text: summary_text(summary),
+                })
+            }
+        }
+    }
+    IrMessage::new(IrRole::Assistant, blocks)
+}
+
+/// Convert Responses API usage into [`IrUsage`].
+#[must_use]
+pub fn responses_usage_to_ir(usage: &ResponsesUsage) -> IrUsage {
+    IrUsage::from_io(usage.input_tokens, usage.output_tokens)
+}
+
 // ── Helpers ─────────────────────────────────────────────────────────────
 
+fn push_input_item(messages: &mut Vec<IrMessage>, item: &ResponsesInputItem) {
+    match item {
+        ResponsesInputItem::Message { role, content } => {
+            let text = content.text();
+            let role = match role.as_str() {
+                "system" | "developer" => IrRole::System,
+                "assistant" => IrRole::Assistant,
+                _ => IrRole::User,
+            };
+            if role == IrRole::Assistant {
+                if !text.is_empty() {
+                    push_assistant_block(messages, IrContentBlock::Text { text });
+                }
+            } else if text.is_empty() {
+                messages.push(IrMessage::new(role, Vec::new()));
+            } else {
+                messages.push(IrMessage::text(role, text));
+            }
+        }
+        ResponsesInputItem::FunctionCall {
+            call_id,
+            name,
+            arguments,
+            ..
+        } => push_assistant_block(
+            messages,
+            IrContentBlock::ToolUse {
+                id: call_id.clone(),
+                name: name.clone(),
+                input: parse_arguments(arguments),
+            },
+        ),
+        ResponsesInputItem::FunctionCallOutput { call_id, output } => {
+            messages.push(IrMessage::new(
+                IrRole::Tool,
+                vec![IrContentBlock::ToolResult {
+                    tool_use_id: call_id.clone(),
+                    content: vec![IrContentBlock::Text {
+                        text: output.clone(),
+                    }],
+                    is_error: false,
+                }],
+            ));
+        }
+        ResponsesInputItem::Reasoning { summary, .. } => push_assistant_block(
+            messages,
+            IrContentBlock::Thinking {
+                text: summary_text(summary),
+            },
+        ),
+    }
+}
+
+/// Append `block` to the trailing assistant message, starting one if the
+/// conversation does not end with an assistant turn.
+fn push_assistant_block(messages: &mut Vec<IrMessage>, block: IrContentBlock) {
+    if let Some(last) = messages.last_mut()
+        && last.role == IrRole::Assistant
+    {
+        last.content.push(block);
+    } else {
+        messages.push(IrMessage::new(IrRole::Assistant, vec![block]));
+    }
+}
+
+fn summary_text(summary: &[ReasoningSummary]) -> String {
+    summary
+        .iter()
+        .map(|ReasoningSummary::SummaryText { text }| text.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_arguments(arguments: &str) -> serde_json::Value {
+    serde_json::from_str(arguments)
+        .unwrap_or_else(|_| serde_json::Value::String(arguments.to_string()))
+}
+
 fn map_role_to_ir(role: &str) -> IrRole {
     match role {
         "system" => IrRole::System,
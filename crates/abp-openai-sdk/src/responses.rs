@@ -0,0 +1,430 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! OpenAI Responses API types (`POST /v1/responses`).
+//!
+//! The Responses API replaces the chat `messages` array with a list of typed
+//! input items: messages, prior function calls, their outputs, and reasoning
+//! items carried over from an earlier turn. The response is likewise a list
+//! of typed output items. Lowering to and from ABP's IR lives in
+//! [`lowering`](crate::lowering).
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// ---------------------------------------------------------------------------
+// Request types
+// ---------------------------------------------------------------------------
+
+/// A Responses API request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ResponsesRequest {
+    /// Model identifier (e.g. `gpt-4o`, `o3`).
+    pub model: String,
+    /// Input: a bare user prompt or a list of input items.
+    pub input: ResponsesInput,
+    /// System-level instructions placed before the input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    /// Tools available to the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ResponsesTool>>,
+    /// Controls which tool the model should call.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<serde_json::Value>,
+    /// Reasoning configuration for reasoning models.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<ReasoningConfig>,
+    /// Maximum tokens to generate, including reasoning tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    /// Sampling temperature (0.0–2.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Nucleus sampling parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// ID of a previous response to continue from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_response_id: Option<String>,
+    /// Whether the provider should store the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+    /// Whether to stream the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    /// Caller-supplied key/value metadata.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<BTreeMap<String, String>>,
+}
+
+impl ResponsesRequest {
+    /// Create a request for `model` with a plain-text prompt.
+    #[must_use]
+    pub fn new(model: impl Into<String>, input: impl Into<ResponsesInput>) -> Self {
+        Self {
+            model: model.into(),
+            input: input.into(),
+            instructions: None,
+            tools: None,
+            tool_choice: None,
+            reasoning: None,
+            max_output_tokens: None,
+            temperature: None,
+            top_p: None,
+            previous_response_id: None,
+            store: None,
+            stream: None,
+            metadata: None,
+        }
+    }
+}
+
+/// The `input` of a Responses API request.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum ResponsesInput {
+    /// A single user prompt.
+    Text(String),
+    /// A list of input items.
+    Items(Vec<ResponsesInputItem>),
+}
+
+impl From<&str> for ResponsesInput {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+
+impl From<String> for ResponsesInput {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<Vec<ResponsesInputItem>> for ResponsesInput {
+    fn from(items: Vec<ResponsesInputItem>) -> Self {
+        Self::Items(items)
+    }
+}
+
+/// An input item of a Responses API request.
+///
+/// Message items may omit `"type"` on the wire, as the API allows.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(remote = "Self", tag = "type", rename_all = "snake_case")]
+pub enum ResponsesInputItem {
+    /// A conversation message.
+    Message {
+        /// Message role (`user`, `assistant`, `system`, or `developer`).
+        role: String,
+        /// Message content.
+        content: ResponsesMessageContent,
+    },
+    /// A function call the model made in an earlier turn.
+    FunctionCall {
+        /// Item identifier.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        /// Correlation ID matched by the call's output.
+        call_id: String,
+        /// Name of the function.
+        name: String,
+        /// JSON-encoded arguments.
+        arguments: String,
+    },
+    /// The output of a function call, supplied by the caller.
+    FunctionCallOutput {
+        /// Correlation ID of the function call.
+        call_id: String,
+        /// Function output.
+        output: String,
+    },
+    /// A reasoning item carried over from an earlier response.
+    Reasoning {
+        /// Item identifier.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        /// Reasoning summary parts.
+        #[serde(default)]
+        summary: Vec<ReasoningSummary>,
+    },
+}
+
+impl ResponsesInputItem {
+    /// A message item with plain-text content.
+    #[must_use]
+    pub fn message(role: impl Into<String>, text: impl Into<String>) -> Self {
+        Self::Message {
+            role: role.into(),
+            content: ResponsesMessageContent::Text(text.into()),
+        }
+    }
+
+    /// A function call output item.
+    #[must_use]
+    pub fn function_call_output(call_id: impl Into<String>, output: impl Into<String>) -> Self {
+        Self::FunctionCallOutput {
+            call_id: call_id.into(),
+            output: output.into(),
+        }
+    }
+}
+
+impl Serialize for ResponsesInputItem {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Self::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ResponsesInputItem {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        if let Some(obj) = value.as_object_mut()
+            && !obj.contains_key("type")
+            && obj.contains_key("role")
+        {
+            obj.insert("type".into(), "message".into());
+        }
+        Self::deserialize(value).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Content of a message input item.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum ResponsesMessageContent {
+    /// Plain text.
+    Text(String),
+    /// A list of content parts.
+    Parts(Vec<ResponsesContentPart>),
+}
+
+impl ResponsesMessageContent {
+    /// Concatenated text of the content.
+    #[must_use]
+    pub fn text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::Parts(parts) => parts.iter().map(ResponsesContentPart::text).collect(),
+        }
+    }
+}
+
+/// A content part of an input or output message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponsesContentPart {
+    /// Text supplied by the caller.
+    InputText {
+        /// The text.
+        text: String,
+    },
+    /// Text generated by the model.
+    OutputText {
+        /// The text.
+        text: String,
+        /// Citations and other annotations.
+        #[serde(default)]
+        annotations: Vec<serde_json::Value>,
+    },
+    /// A refusal generated by the model.
+    Refusal {
+        /// The refusal message.
+        refusal: String,
+    },
+}
+
+impl ResponsesContentPart {
+    /// Text of the part (the refusal message for refusals).
+    #[must_use]
+    pub fn text(&self) -> &str {
+        match self {
+            Self::InputText { text } | Self::OutputText { text, .. } => text,
+            Self::Refusal { refusal } => refusal,
+        }
+    }
+}
+
+/// A reasoning summary part.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReasoningSummary {
+    /// Summary text.
+    SummaryText {
+        /// The summary text.
+        text: String,
+    },
+}
+
+/// A tool available to the model.
+///
+/// Unlike Chat Completions, function tools are flat: `name` and `parameters`
+/// sit next to `"type": "function"`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponsesTool {
+    /// A function tool.
+    Function {
+        /// Function name.
+        name: String,
+        /// Human-readable description.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        /// JSON Schema of the parameters.
+        #[serde(default)]
+        parameters: serde_json::Value,
+        /// Whether to enforce strict schema adherence.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        strict: Option<bool>,
+    },
+}
+
+/// Reasoning configuration for reasoning models.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ReasoningConfig {
+    /// How much effort the model spends reasoning.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<ReasoningEffort>,
+    /// Reasoning summary detail (`auto`, `concise`, or `detailed`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+/// Reasoning effort levels.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReasoningEffort {
+    /// Minimal reasoning.
+    Minimal,
+    /// Low effort.
+    Low,
+    /// Medium effort (the API default).
+    Medium,
+    /// High effort.
+    High,
+}
+
+// ---------------------------------------------------------------------------
+// Response types
+// ---------------------------------------------------------------------------
+
+/// A Responses API response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ResponsesResponse {
+    /// Unique response identifier (e.g. `resp_...`).
+    pub id: String,
+    /// Object type — always `"response"`.
+    pub object: String,
+    /// Unix timestamp of creation.
+    pub created_at: u64,
+    /// Model that generated the response.
+    pub model: String,
+    /// Status of the response.
+    pub status: ResponseStatus,
+    /// Output items produced by the model.
+    pub output: Vec<ResponsesOutputItem>,
+    /// Token usage statistics.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<ResponsesUsage>,
+    /// Error details when `status` is `failed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ResponseError>,
+}
+
+impl ResponsesResponse {
+    /// Concatenated text of all output messages (the SDKs' `output_text`).
+    #[must_use]
+    pub fn output_text(&self) -> String {
+        self.output
+            .iter()
+            .filter_map(|item| match item {
+                ResponsesOutputItem::Message { content, .. } => Some(content),
+                _ => None,
+            })
+            .flatten()
+            .filter_map(|part| match part {
+                ResponsesContentPart::OutputText { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Status of a response.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseStatus {
+    /// The response finished normally.
+    Completed,
+    /// Generation stopped early (e.g. `max_output_tokens` reached).
+    Incomplete,
+    /// Generation failed; see `error`.
+    Failed,
+    /// Generation is still running.
+    InProgress,
+}
+
+/// An output item of a Responses API response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponsesOutputItem {
+    /// An assistant message.
+    Message {
+        /// Item identifier.
+        id: String,
+        /// Message role — always `"assistant"`.
+        role: String,
+        /// Content parts.
+        content: Vec<ResponsesContentPart>,
+    },
+    /// A function call requested by the model.
+    FunctionCall {
+        /// Item identifier.
+        id: String,
+        /// Correlation ID the caller echoes in `function_call_output`.
+        call_id: String,
+        /// Name of the function.
+        name: String,
+        /// JSON-encoded arguments.
+        arguments: String,
+    },
+    /// The model's reasoning.
+    Reasoning {
+        /// Item identifier.
+        id: String,
+        /// Reasoning summary parts.
+        #[serde(default)]
+        summary: Vec<ReasoningSummary>,
+    },
+}
+
+/// Token usage of a response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ResponsesUsage {
+    /// Tokens consumed by the input.
+    pub input_tokens: u64,
+    /// Tokens generated in the output.
+    pub output_tokens: u64,
+    /// Total tokens (input + output).
+    pub total_tokens: u64,
+    /// Breakdown of output token usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_tokens_details: Option<OutputTokensDetails>,
+}
+
+/// Breakdown of output token usage.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct OutputTokensDetails {
+    /// Tokens spent on reasoning.
+    pub reasoning_tokens: u64,
+}
+
+/// Error details of a failed response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ResponseError {
+    /// Error code (e.g. `server_error`).
+    pub code: String,
+    /// Human-readable message.
+    pub message: String,
+}
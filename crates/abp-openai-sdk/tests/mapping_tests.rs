@@ -68,6 +68,31 @@ fn work_order_falls_back_to_config_model() {
     assert_eq!(req.model, cfg.model);
 }
 
+#[test]
+fn openai_vendor_namespace_overrides_config_and_ignores_others() {
+    let mut wo = WorkOrderBuilder::new("task").build();
+    wo.config.vendor = serde_json::from_value(serde_json::json!({
+        "openai": {"model": "gpt-4o-mini", "temperature": 0.2},
+        "openai.max_tokens": 512,
+        "claude": {"max_tokens": 10, "temperature": 0.9},
+    }))
+    .unwrap();
+    let cfg = OpenAIConfig {
+        max_tokens: Some(8192),
+        temperature: Some(1.0),
+        ..OpenAIConfig::default()
+    };
+    let req = map_work_order(&wo, &cfg);
+
+    assert_eq!(req.model, "gpt-4o-mini");
+    assert_eq!(req.temperature, Some(0.2));
+    assert_eq!(req.max_tokens, Some(512));
+
+    // The work order's own model still wins over the vendor namespace.
+    wo.config.model = Some("gpt-4-turbo".into());
+    assert_eq!(map_work_order(&wo, &cfg).model, "gpt-4-turbo");
+}
+
 #[test]
 fn context_snippets_are_included_in_user_message() {
     let ctx = ContextPacket {
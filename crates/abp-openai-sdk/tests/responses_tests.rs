@@ -0,0 +1,161 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the Responses API types and their IR lowering.
+
+use abp_core::ir::{IrContentBlock, IrRole};
+use abp_openai_sdk::lowering::{
+    responses_input_from_ir, responses_output_to_ir, responses_to_ir, responses_usage_to_ir,
+};
+use abp_openai_sdk::responses::{
+    ReasoningEffort, ResponseStatus, ResponsesInput, ResponsesInputItem, ResponsesRequest,
+    ResponsesResponse, ResponsesTool,
+};
+use serde_json::json;
+
+fn request(body: serde_json::Value) -> ResponsesRequest {
+    serde_json::from_value(body).unwrap()
+}
+
+#[test]
+fn string_input_becomes_user_message_after_instructions() {
+    let req = request(json!({
+        "model": "gpt-4o",
+        "instructions": "Be terse.",
+        "input": "What is 2+2?",
+    }));
+    let conv = responses_to_ir(&req);
+    assert_eq!(conv.messages.len(), 2);
+    assert_eq!(conv.messages[0].role, IrRole::System);
+    assert_eq!(conv.messages[0].text_content(), "Be terse.");
+    assert_eq!(conv.messages[1].role, IrRole::User);
+    assert_eq!(conv.messages[1].text_content(), "What is 2+2?");
+}
+
+#[test]
+fn message_items_may_omit_type() {
+    let req = request(json!({
+        "model": "gpt-4o",
+        "input": [
+            {"role": "developer", "content": "Use tools."},
+            {"type": "message", "role": "user", "content": [
+                {"type": "input_text", "text": "Weather in "},
+                {"type": "input_text", "text": "Paris?"},
+            ]},
+        ],
+    }));
+    let ResponsesInput::Items(items) = &req.input else {
+        panic!("expected items");
+    };
+    assert!(matches!(&items[0], ResponsesInputItem::Message { role, .. } if role == "developer"));
+
+    let conv = responses_to_ir(&req);
+    assert_eq!(conv.messages[0].role, IrRole::System);
+    assert_eq!(conv.messages[1].text_content(), "Weather in Paris?");
+
+    let json = serde_json::to_value(&req).unwrap();
+    assert_eq!(json["input"][0]["type"], "message");
+}
+
+#[test]
+fn reasoning_and_function_calls_join_one_assistant_turn() {
+    let req = request(json!({
+        "model": "o3",
+        "reasoning": {"effort": "high", "summary": "auto"},
+        "input": [
+            {"role": "user", "content": "Weather in Paris?"},
+            {"type": "reasoning", "id": "rs_1", "summary": [
+                {"type": "summary_text", "text": "Need the weather tool."},
+            ]},
+            {"type": "function_call", "id": "fc_1", "call_id": "call_1",
+             "name": "get_weather", "arguments": "{\"city\":\"Paris\"}"},
+            {"type": "function_call_output", "call_id": "call_1", "output": "18C"},
+        ],
+    }));
+    assert_eq!(
+        req.reasoning.as_ref().unwrap().effort,
+        Some(ReasoningEffort::High)
+    );
+
+    let conv = responses_to_ir(&req);
+    assert_eq!(conv.messages.len(), 3);
+    let assistant = &conv.messages[1];
+    assert_eq!(assistant.role, IrRole::Assistant);
+    assert!(matches!(
+        &assistant.content[0],
+        IrContentBlock::Thinking { text } if text == "Need the weather tool."
+    ));
+    assert!(matches!(
+        &assistant.content[1],
+        IrContentBlock::ToolUse { id, name, input }
+            if id == "call_1" && name == "get_weather" && input["city"] == "Paris"
+    ));
+    let tool = &conv.messages[2];
+    assert_eq!(tool.role, IrRole::Tool);
+    assert!(matches!(
+        &tool.content[0],
+        IrContentBlock::ToolResult { tool_use_id, .. } if tool_use_id == "call_1"
+    ));
+}
+
+#[test]
+fn input_items_roundtrip_through_ir() {
+    let req = request(json!({
+        "model": "o3",
+        "input": [
+            {"role": "user", "content": "Weather?"},
+            {"type": "reasoning", "summary": [{"type": "summary_text", "text": "Look it up."}]},
+            {"type": "function_call", "call_id": "call_1", "name": "get_weather",
+             "arguments": "{}"},
+            {"type": "function_call_output", "call_id": "call_1", "output": "18C"},
+        ],
+    }));
+    let items = responses_input_from_ir(&responses_to_ir(&req));
+    assert_eq!(ResponsesInput::Items(items), req.input);
+}
+
+#[test]
+fn function_tools_are_flat() {
+    let req = request(json!({
+        "model": "gpt-4o",
+        "input": "hi",
+        "tools": [{"type": "function", "name": "get_weather",
+                   "parameters": {"type": "object"}, "strict": true}],
+    }));
+    let ResponsesTool::Function { name, strict, .. } = &req.tools.as_ref().unwrap()[0];
+    assert_eq!(name, "get_weather");
+    assert_eq!(*strict, Some(true));
+}
+
+#[test]
+fn response_output_lowers_to_assistant_message() {
+    let resp: ResponsesResponse = serde_json::from_value(json!({
+        "id": "resp_1",
+        "object": "response",
+        "created_at": 1_700_000_000,
+        "model": "o3",
+        "status": "completed",
+        "output": [
+            {"type": "reasoning", "id": "rs_1", "summary": []},
+            {"type": "message", "id": "msg_1", "role": "assistant", "content": [
+                {"type": "output_text", "text": "It is 18C.", "annotations": []},
+            ]},
+            {"type": "function_call", "id": "fc_1", "call_id": "call_2",
+             "name": "log", "arguments": "not json"},
+        ],
+        "usage": {"input_tokens": 10, "output_tokens": 5, "total_tokens": 15,
+                  "output_tokens_details": {"reasoning_tokens": 3}},
+    }))
+    .unwrap();
+    assert_eq!(resp.status, ResponseStatus::Completed);
+    assert_eq!(resp.output_text(), "It is 18C.");
+
+    let msg = responses_output_to_ir(&resp.output);
+    assert_eq!(msg.role, IrRole::Assistant);
+    assert_eq!(msg.text_content(), "It is 18C.");
+    assert!(matches!(
+        &msg.content[2],
+        IrContentBlock::ToolUse { input, .. } if input == "not json"
+    ));
+
+    let usage = responses_usage_to_ir(resp.usage.as_ref().unwrap());
+    assert_eq!(usage.total_tokens, 15);
+}
@@ -0,0 +1,685 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Layered policies with documented precedence and decision explanations.
+//!
+//! A deployment rarely has one policy: an organisation sets defaults, a
+//! tenant or project narrows or widens them, presets are picked per run,
+//! and the work order carries its own rules.
+//! [`LayeredPolicy`](crate::layers::LayeredPolicy) keeps those as separate
+//! [`PolicyLayer`](crate::layers::PolicyLayer)s instead of merging them into
+//! one profile, so every decision can be traced back to the layer and rule
+//! that made it.
+//!
+//! # Precedence
+//!
+//! Layers are ordered by [`LayerKind`](crate::layers::LayerKind), most
+//! general first: `org` < `tenant` < `project` < `preset` < `work_order`.
+//! Layers of the same kind keep the order they were added in. For each
+//! action:
+//!
+//! 1. Every layer is evaluated on its own and returns *allow*, *deny*, or
+//!    *abstain* (no rule in the layer matched).
+//! 2. A deny from an [enforced](crate::layers::PolicyLayer::enforced) layer
+//!    is final; the most general enforced deny is reported.
+//! 3. Otherwise the most specific layer that did not abstain decides.
+//! 4. When every layer abstains the action is allowed, as with an empty
+//!    [`PolicyProfile`](abp_core::PolicyProfile).
+//!
+//! Within a layer the rules mean what they mean to
+//! [`PolicyEngine`](crate::PolicyEngine): deny lists are last-match-wins
+//! with `!pattern` re-inclusions, a non-empty allow list denies what it
+//! does not match, and a matching re-inclusion or allow-list entry is an
+//! explicit allow that lets a project open up what the org default denied.
+//!
+//! Commands (`Command` actions) are checked against the `Bash` tool rule
+//! and the `Bash(<glob>)` entries of `disallowed_tools`, as the built-in
+//! shell tool does; each simple command in a chain is checked on its own.
+//!
+//! # Examples
+//!
+//! ```
+//! use abp_core::PolicyProfile;
+//! use abp_policy::layers::{Action, LayerKind, LayeredPolicy, PolicyLayer};
+//!
+//! let org = PolicyLayer::new(LayerKind::Org, "acme", PolicyProfile {
+//!     deny_write: vec!["**/.git/**".into(), "vendor/**".into()],
+//!     ..PolicyProfile::default()
+//! })
+//! .enforced();
+//! let project = PolicyLayer::new(LayerKind::Project, "widgets", PolicyProfile {
+//!     deny_write: vec!["**".into(), "!src/**".into()],
+//!     ..PolicyProfile::default()
+//! });
+//! let policy = LayeredPolicy::new(vec![project, org]).unwrap();
+//!
+//! let why = policy.explain(&Action::write("src/lib.rs"));
+//! assert!(why.allowed);
+//! assert_eq!(why.decided_by.as_deref(), Some("widgets"));
+//!
+//! let why = policy.explain(&Action::write(".git/config"));
+//! assert!(!why.allowed);
+//! assert_eq!(why.decided_by.as_deref(), Some("acme"));
+//! assert_eq!(why.rule.as_deref(), Some("**/.git/**"));
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use abp_core::{PolicyProfile, WorkOrder};
+use anyhow::{Context, Result};
+use globset::{Glob, GlobMatcher};
+use serde::{Deserialize, Serialize};
+
+use crate::Decision;
+use crate::compose::PolicyDecision;
+use crate::network::NetworkRule;
+use crate::presets::{self, PolicyPreset};
+
+/// Where a layer sits in the precedence order, most general first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LayerKind {
+    /// Organisation-wide defaults.
+    Org,
+    /// Rules for one tenant of a shared deployment.
+    Tenant,
+    /// Rules for one project or repository.
+    Project,
+    /// A named preset picked for the run.
+    Preset,
+    /// The work order's own policy.
+    WorkOrder,
+}
+
+impl LayerKind {
+    /// The snake_case name of the kind.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Org => "org",
+            Self::Tenant => "tenant",
+            Self::Project => "project",
+            Self::Preset => "preset",
+            Self::WorkOrder => "work_order",
+        }
+    }
+}
+
+impl fmt::Display for LayerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One named policy layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyLayer {
+    /// Name shown in explanations (an org, tenant, project, or preset name).
+    pub name: String,
+    /// Where the layer sits in the precedence order.
+    pub kind: LayerKind,
+    /// Whether the layer's denies are final, so more specific layers
+    /// cannot re-allow what it denies.
+    #[serde(default)]
+    pub enforced: bool,
+    /// The layer's rules.
+    pub profile: PolicyProfile,
+}
+
+impl PolicyLayer {
+    /// A layer that more specific layers may override.
+    #[must_use]
+    pub fn new(kind: LayerKind, name: impl Into<String>, profile: PolicyProfile) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            enforced: false,
+            profile,
+        }
+    }
+
+    /// A [`LayerKind::Preset`] layer holding `preset`'s profile.
+    #[must_use]
+    pub fn preset(preset: PolicyPreset) -> Self {
+        Self::new(LayerKind::Preset, preset.name(), preset.profile())
+    }
+
+    /// Make the layer's denies final.
+    #[must_use]
+    pub fn enforced(mut self) -> Self {
+        self.enforced = true;
+        self
+    }
+}
+
+/// Something a policy can be asked about.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Action {
+    /// Using a tool by name.
+    Tool {
+        /// Tool name.
+        name: String,
+    },
+    /// Reading a workspace-relative path.
+    Read {
+        /// Path to read.
+        path: PathBuf,
+    },
+    /// Writing a workspace-relative path.
+    Write {
+        /// Path to write.
+        path: PathBuf,
+    },
+    /// Connecting to a network host.
+    Connect {
+        /// Host name or IP address.
+        host: String,
+        /// Port, when known.
+        port: Option<u16>,
+    },
+    /// Running a shell command.
+    Command {
+        /// The command line.
+        command: String,
+    },
+}
+
+impl Action {
+    /// Using the tool `name`.
+    #[must_use]
+    pub fn tool(name: impl Into<String>) -> Self {
+        Self::Tool { name: name.into() }
+    }
+
+    /// Reading `path`.
+    #[must_use]
+    pub fn read(path: impl Into<PathBuf>) -> Self {
+        Self::Read { path: path.into() }
+    }
+
+    /// Writing `path`.
+    #[must_use]
+    pub fn write(path: impl Into<PathBuf>) -> Self {
+        Self::Write { path: path.into() }
+    }
+
+    /// Connecting to `host` on `port`.
+    #[must_use]
+    pub fn connect(host: impl Into<String>, port: Option<u16>) -> Self {
+        Self::Connect {
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// Running the shell command `command`.
+    #[must_use]
+    pub fn command(command: impl Into<String>) -> Self {
+        Self::Command {
+            command: command.into(),
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tool { name } => write!(f, "tool '{name}'"),
+            Self::Read { path } => write!(f, "read '{}'", path.display()),
+            Self::Write { path } => write!(f, "write '{}'", path.display()),
+            Self::Connect {
+                host,
+                port: Some(port),
+            } => write!(f, "connect '{host}:{port}'"),
+            Self::Connect { host, port: None } => write!(f, "connect '{host}'"),
+            Self::Command { command } => write!(f, "command '{command}'"),
+        }
+    }
+}
+
+/// What one layer said about an action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerVerdict {
+    /// Layer name.
+    pub layer: String,
+    /// Layer kind.
+    pub kind: LayerKind,
+    /// Whether the layer is enforced.
+    pub enforced: bool,
+    /// The layer's decision; [`PolicyDecision::Abstain`] when no rule matched.
+    pub decision: PolicyDecision,
+    /// The rule that produced the decision, as written in the profile.
+    pub rule: Option<String>,
+}
+
+/// Why a [`LayeredPolicy`] allowed or denied an action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Explanation {
+    /// The action that was checked.
+    pub action: Action,
+    /// Whether the action is permitted.
+    pub allowed: bool,
+    /// Name of the deciding layer; `None` when every layer abstained.
+    pub decided_by: Option<String>,
+    /// The deciding rule, when one matched.
+    pub rule: Option<String>,
+    /// Human-readable reason.
+    pub reason: String,
+    /// Every layer's verdict, in precedence order.
+    pub layers: Vec<LayerVerdict>,
+}
+
+impl Explanation {
+    /// The outcome as a plain [`Decision`].
+    #[must_use]
+    pub fn decision(&self) -> Decision {
+        if self.allowed {
+            Decision::allow()
+        } else {
+            Decision::deny(self.reason.clone())
+        }
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome = if self.allowed { "allowed" } else { "denied" };
+        writeln!(f, "{}: {outcome} ({})", self.action, self.reason)?;
+        for v in &self.layers {
+            let marker = if self.decided_by.as_deref() == Some(v.layer.as_str()) {
+                '*'
+            } else {
+                ' '
+            };
+            let enforced = if v.enforced { ", enforced" } else { "" };
+            let verdict = match &v.decision {
+                PolicyDecision::Allow { reason } | PolicyDecision::Deny { reason } => {
+                    reason.as_str()
+                }
+                PolicyDecision::Abstain => "no matching rule",
+            };
+            writeln!(f, "{marker} {} ({}{enforced}): {verdict}", v.layer, v.kind)?;
+        }
+        Ok(())
+    }
+}
+
+/// A stack of compiled [`PolicyLayer`]s evaluated with the precedence
+/// described in the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct LayeredPolicy {
+    layers: Vec<CompiledLayer>,
+}
+
+impl LayeredPolicy {
+    /// Compile `layers`, ordering them by [`LayerKind`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the layer if any glob pattern is invalid.
+    pub fn new(mut layers: Vec<PolicyLayer>) -> Result<Self> {
+        layers.sort_by_key(|l| l.kind);
+        let layers = layers
+            .into_iter()
+            .map(|l| {
+                CompiledLayer::compile(&l)
+                    .with_context(|| format!("compile {} policy layer '{}'", l.kind, l.name))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { layers })
+    }
+
+    /// `base` plus the layers a work order brings: the presets it requests
+    /// (see [`presets::requested`]) and its own policy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a requested preset is unknown or a pattern is
+    /// invalid.
+    pub fn for_work_order(mut base: Vec<PolicyLayer>, work_order: &WorkOrder) -> Result<Self> {
+        base.extend(
+            presets::requested(work_order)?
+                .into_iter()
+                .map(PolicyLayer::preset),
+        );
+        base.push(PolicyLayer::new(
+            LayerKind::WorkOrder,
+            work_order.id.to_string(),
+            work_order.policy.clone(),
+        ));
+        Self::new(base)
+    }
+
+    /// The layers in precedence order.
+    pub fn layers(&self) -> impl Iterator<Item = &PolicyLayer> {
+        self.layers.iter().map(|c| &c.layer)
+    }
+
+    /// Explain how the layers decide `action`.
+    #[must_use]
+    pub fn explain(&self, action: &Action) -> Explanation {
+        let layers: Vec<LayerVerdict> = self
+            .layers
+            .iter()
+            .map(|c| {
+                let (decision, rule) = c.evaluate(action);
+                LayerVerdict {
+                    layer: c.layer.name.clone(),
+                    kind: c.layer.kind,
+                    enforced: c.layer.enforced,
+                    decision,
+                    rule,
+                }
+            })
+            .collect();
+
+        let decisive = layers
+            .iter()
+            .find(|v| v.enforced && v.decision.is_deny())
+            .or_else(|| layers.iter().rev().find(|v| !v.decision.is_abstain()));
+        let (allowed, decided_by, rule, reason) = match decisive {
+            Some(v) => match &v.decision {
+                PolicyDecision::Deny { reason } => (
+                    false,
+                    Some(v.layer.clone()),
+                    v.rule.clone(),
+                    format!("{reason} ({} layer '{}')", v.kind, v.layer),
+                ),
+                PolicyDecision::Allow { reason } => (
+                    true,
+                    Some(v.layer.clone()),
+                    v.rule.clone(),
+                    format!("{reason} ({} layer '{}')", v.kind, v.layer),
+                ),
+                PolicyDecision::Abstain => unreachable!("abstaining layers never decide"),
+            },
+            None => (true, None, None, "no layer has a matching rule".to_string()),
+        };
+        Explanation {
+            action: action.clone(),
+            allowed,
+            decided_by,
+            rule,
+            reason,
+            layers,
+        }
+    }
+
+    /// Check whether `tool_name` is permitted.
+    #[must_use]
+    pub fn can_use_tool(&self, tool_name: &str) -> Decision {
+        self.explain(&Action::tool(tool_name)).decision()
+    }
+
+    /// Check whether reading `rel_path` is permitted.
+    #[must_use]
+    pub fn can_read_path(&self, rel_path: &Path) -> Decision {
+        self.explain(&Action::read(rel_path)).decision()
+    }
+
+    /// Check whether writing `rel_path` is permitted.
+    #[must_use]
+    pub fn can_write_path(&self, rel_path: &Path) -> Decision {
+        self.explain(&Action::write(rel_path)).decision()
+    }
+
+    /// Check whether connecting to `host` on `port` is permitted.
+    #[must_use]
+    pub fn can_connect(&self, host: &str, port: Option<u16>) -> Decision {
+        self.explain(&Action::connect(host, port)).decision()
+    }
+
+    /// Check whether the shell command `command` may run.
+    #[must_use]
+    pub fn can_run_command(&self, command: &str) -> Decision {
+        self.explain(&Action::command(command)).decision()
+    }
+}
+
+/// One glob rule, remembered as written so explanations can quote it.
+#[derive(Debug, Clone)]
+struct GlobRule {
+    pattern: String,
+    matcher: GlobMatcher,
+    reinclude: bool,
+}
+
+impl GlobRule {
+    fn compile(pattern: &str) -> Result<Self> {
+        let reinclude = pattern.starts_with('!');
+        let glob = pattern.strip_prefix('!').unwrap_or(pattern);
+        Ok(Self {
+            pattern: pattern.to_string(),
+            matcher: Glob::new(glob)
+                .with_context(|| format!("invalid glob: {glob}"))?
+                .compile_matcher(),
+            reinclude,
+        })
+    }
+
+    fn compile_all<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Result<Vec<Self>> {
+        patterns.into_iter().map(Self::compile).collect()
+    }
+}
+
+/// The last rule in a last-match-wins list that matches `candidate`.
+fn last_match<'a>(rules: &'a [GlobRule], candidate: &Path) -> Option<&'a GlobRule> {
+    rules.iter().rev().find(|r| r.matcher.is_match(candidate))
+}
+
+type Verdict = (PolicyDecision, Option<String>);
+
+fn allow(reason: String, rule: &str) -> Verdict {
+    (PolicyDecision::Allow { reason }, Some(rule.to_string()))
+}
+
+fn deny(reason: String, rule: Option<&str>) -> Verdict {
+    (PolicyDecision::Deny { reason }, rule.map(str::to_string))
+}
+
+const ABSTAIN: Verdict = (PolicyDecision::Abstain, None);
+
+#[derive(Debug, Clone)]
+struct CompiledLayer {
+    layer: PolicyLayer,
+    allowed_tools: Vec<GlobRule>,
+    disallowed_tools: Vec<GlobRule>,
+    commands: Vec<GlobRule>,
+    deny_read: Vec<GlobRule>,
+    deny_write: Vec<GlobRule>,
+    allow_network: Vec<NetworkRule>,
+    deny_network: Vec<NetworkRule>,
+}
+
+impl CompiledLayer {
+    fn compile(layer: &PolicyLayer) -> Result<Self> {
+        let p = &layer.profile;
+        let (commands, tools): (Vec<&String>, Vec<&String>) = p
+            .disallowed_tools
+            .iter()
+            .partition(|t| command_pattern(t).is_some());
+        Ok(Self {
+            layer: layer.clone(),
+            allowed_tools: GlobRule::compile_all(p.allowed_tools.iter().map(String::as_str))
+                .context("compile allowed_tools")?,
+            disallowed_tools: GlobRule::compile_all(tools.into_iter().map(String::as_str))
+                .context("compile disallowed_tools")?,
+            commands: GlobRule::compile_all(
+                commands.into_iter().filter_map(|c| command_pattern(c)),
+            )
+            .context("compile Bash(...) command rules")?,
+            deny_read: GlobRule::compile_all(p.deny_read.iter().map(String::as_str))
+                .context("compile deny_read")?,
+            deny_write: GlobRule::compile_all(p.deny_write.iter().map(String::as_str))
+                .context("compile deny_write")?,
+            allow_network: p
+                .allow_network
+                .iter()
+                .map(|r| NetworkRule::parse(r))
+                .collect(),
+            deny_network: p
+                .deny_network
+                .iter()
+                .map(|r| NetworkRule::parse(r))
+                .collect(),
+        })
+    }
+
+    fn evaluate(&self, action: &Action) -> Verdict {
+        match action {
+            Action::Tool { name } => self.tool(name),
+            Action::Read { path } => Self::path(&self.deny_read, "read", path),
+            Action::Write { path } => Self::path(&self.deny_write, "write", path),
+            Action::Connect { host, port } => self.connect(host, *port),
+            Action::Command { command } => self.command(command),
+        }
+    }
+
+    fn tool(&self, name: &str) -> Verdict {
+        let excluded = last_match(&self.disallowed_tools, Path::new(name));
+        if let Some(rule) = excluded.filter(|r| !r.reinclude) {
+            return deny(format!("tool '{name}' is disallowed"), Some(&rule.pattern));
+        }
+        if !self.allowed_tools.is_empty() {
+            return match self.allowed_tools.iter().find(|r| r.matcher.is_match(name)) {
+                Some(rule) => allow(format!("tool '{name}' is allowed"), &rule.pattern),
+                None => deny(format!("tool '{name}' not in allowlist"), None),
+            };
+        }
+        match excluded {
+            Some(rule) => allow(format!("tool '{name}' is re-allowed"), &rule.pattern),
+            None => ABSTAIN,
+        }
+    }
+
+    fn path(rules: &[GlobRule], verb: &str, path: &Path) -> Verdict {
+        let shown = path.display();
+        match last_match(rules, path) {
+            Some(rule) if rule.reinclude => {
+                allow(format!("{verb} re-allowed for '{shown}'"), &rule.pattern)
+            }
+            Some(rule) => deny(format!("{verb} denied for '{shown}'"), Some(&rule.pattern)),
+            None => ABSTAIN,
+        }
+    }
+
+    fn connect(&self, host: &str, port: Option<u16>) -> Verdict {
+        let target = match port {
+            Some(p) => format!("{host}:{p}"),
+            None => host.to_string(),
+        };
+        if let Some(rule) = self.deny_network.iter().find(|r| r.matches(host, port)) {
+            return deny(
+                format!("network access to '{target}' denied"),
+                Some(rule.as_str()),
+            );
+        }
+        if self.allow_network.is_empty() {
+            return ABSTAIN;
+        }
+        match self.allow_network.iter().find(|r| r.matches(host, port)) {
+            Some(rule) => allow(
+                format!("network access to '{target}' allowed"),
+                rule.as_str(),
+            ),
+            None => deny(
+                format!("network access to '{target}' not in allow_network"),
+                None,
+            ),
+        }
+    }
+
+    fn command(&self, command: &str) -> Verdict {
+        let shell = self.tool("Bash");
+        if shell.0.is_deny() {
+            return shell;
+        }
+        let mut reallowed = None;
+        for part in std::iter::once(command.trim()).chain(simple_commands(command)) {
+            match last_match(&self.commands, Path::new(part)) {
+                Some(rule) if rule.reinclude => reallowed = reallowed.or(Some(rule)),
+                Some(rule) => {
+                    return deny(
+                        format!("command '{part}' is disallowed"),
+                        Some(&rule.pattern),
+                    );
+                }
+                None => {}
+            }
+        }
+        match reallowed {
+            Some(rule) => allow(format!("command '{command}' is re-allowed"), &rule.pattern),
+            None => shell,
+        }
+    }
+}
+
+/// The command glob in a `Bash(<glob>)` tool rule.
+fn command_pattern(rule: &str) -> Option<&str> {
+    rule.strip_prefix("Bash(")
+        .and_then(|r| r.strip_suffix(')'))
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+}
+
+/// The simple commands `command` chains together.
+fn simple_commands(command: &str) -> impl Iterator<Item = &str> {
+    command
+        .split(['\n', ';', '&', '|'])
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(kind: LayerKind, name: &str, profile: PolicyProfile) -> PolicyLayer {
+        PolicyLayer::new(kind, name, profile)
+    }
+
+    #[test]
+    fn layers_are_ordered_by_kind() {
+        let policy = LayeredPolicy::new(vec![
+            layer(LayerKind::WorkOrder, "wo", PolicyProfile::default()),
+            layer(LayerKind::Org, "org", PolicyProfile::default()),
+            layer(LayerKind::Project, "a", PolicyProfile::default()),
+            layer(LayerKind::Project, "b", PolicyProfile::default()),
+        ])
+        .unwrap();
+        let names: Vec<_> = policy.layers().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, ["org", "a", "b", "wo"]);
+    }
+
+    #[test]
+    fn bash_rules_are_commands_not_tools() {
+        let policy = LayeredPolicy::new(vec![layer(
+            LayerKind::Org,
+            "org",
+            PolicyProfile {
+                disallowed_tools: vec!["Bash(rm *)".into()],
+                ..PolicyProfile::default()
+            },
+        )])
+        .unwrap();
+        assert!(policy.can_use_tool("Bash").allowed);
+        assert!(policy.can_run_command("ls -la").allowed);
+        let why = policy.explain(&Action::command("ls && rm -rf /"));
+        assert!(!why.allowed);
+        assert_eq!(why.rule.as_deref(), Some("rm *"));
+    }
+
+    #[test]
+    fn invalid_globs_name_the_layer() {
+        let err = LayeredPolicy::new(vec![layer(
+            LayerKind::Tenant,
+            "globex",
+            PolicyProfile {
+                deny_read: vec!["[".into()],
+                ..PolicyProfile::default()
+            },
+        )])
+        .unwrap_err();
+        assert!(format!("{err:#}").contains("tenant policy layer 'globex'"));
+    }
+}
@@ -15,6 +15,12 @@ pub mod audit;
 pub mod compose;
 /// Composed policy evaluation over multiple engines.
 pub mod composed;
+/// Layered policies (org, tenant, project, preset, work order) and `explain`.
+pub mod layers;
+/// Network egress rules (host patterns, ports, CIDR blocks).
+pub mod network;
+/// Named policy presets (`read_only`, `docs_only`, …) and their composition.
+pub mod presets;
 /// Rate-limiting policy for agent throughput.
 pub mod rate_limit;
 /// Rule-based access control engine.
@@ -58,13 +64,14 @@ impl Decision {
 
 /// Compiled policy evaluator built from a [`PolicyProfile`].
 ///
-/// Enforces tool allow/deny lists and path-based read/write restrictions
-/// using glob pattern matching. Deny rules always take precedence over allow rules.
+/// Enforces tool allow/deny lists, path-based read/write restrictions, and
+/// network egress rules. Deny rules always take precedence over allow rules.
 #[derive(Debug, Clone)]
 pub struct PolicyEngine {
     tool_rules: IncludeExcludeGlobs,
     deny_read: IncludeExcludeGlobs,
     deny_write: IncludeExcludeGlobs,
+    network: network::NetworkPolicy,
 }
 
 impl PolicyEngine {
@@ -101,6 +108,7 @@ impl PolicyEngine {
                 .context("compile deny_read globs")?,
             deny_write: IncludeExcludeGlobs::new(no_include, &policy.deny_write)
                 .context("compile deny_write globs")?,
+            network: network::NetworkPolicy::new(&policy.allow_network, &policy.deny_network),
         })
     }
 
@@ -155,6 +163,43 @@ impl PolicyEngine {
         }
         Decision::allow()
     }
+
+    /// Check whether connecting to `host` on `port` is permitted by the
+    /// `allow_network` / `deny_network` rules (see [`network`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use abp_core::PolicyProfile;
+    /// use abp_policy::PolicyEngine;
+    ///
+    /// let policy = PolicyProfile {
+    ///     allow_network: vec!["*.github.com:443".into()],
+    ///     deny_network: vec!["gist.github.com".into()],
+    ///     ..PolicyProfile::default()
+    /// };
+    /// let engine = PolicyEngine::new(&policy).unwrap();
+    ///
+    /// assert!(engine.can_connect("api.github.com", Some(443)).allowed);
+    /// assert!(!engine.can_connect("api.github.com", Some(22)).allowed);
+    /// assert!(!engine.can_connect("gist.github.com", Some(443)).allowed);
+    /// ```
+    #[must_use]
+    pub fn can_connect(&self, host: &str, port: Option<u16>) -> Decision {
+        self.network.check(host, port)
+    }
+
+    /// Check whether a request to `url` is permitted by the network rules.
+    #[must_use]
+    pub fn can_fetch_url(&self, url: &str) -> Decision {
+        self.network.check_url(url)
+    }
+
+    /// The compiled network egress rules.
+    #[must_use]
+    pub fn network(&self) -> &network::NetworkPolicy {
+        &self.network
+    }
 }
 
 #[cfg(test)]
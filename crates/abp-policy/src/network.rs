@@ -0,0 +1,346 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Network egress rules.
+//!
+//! `PolicyProfile::allow_network` and `deny_network` hold rule strings of
+//! the form `host[:ports]`:
+//!
+//! | Rule | Matches |
+//! |------|---------|
+//! | `api.example.com` | that host, any port |
+//! | `*.example.com` | any subdomain of `example.com`, any port |
+//! | `example.com:443` | that host on port 443 only |
+//! | `*:8000-8999` | any host on ports 8000 through 8999 |
+//! | `10.0.0.0/8` | any IPv4 address in the block |
+//! | `[::1]:22` | the IPv6 loopback on port 22 |
+//!
+//! Host patterns are case-insensitive globs where `*` matches any run of
+//! characters (dots included) and `?` matches one. A rule with ports never
+//! matches a connection whose port is unknown. Deny rules win over allow
+//! rules, and a non-empty allow list denies every host it does not match.
+//!
+//! Rules are compiled leniently: a string that is not a valid `host:ports`
+//! rule is matched as a plain host pattern, so one odd entry cannot stop a
+//! whole policy from loading.
+
+use std::net::IpAddr;
+
+use crate::Decision;
+
+/// One compiled `host[:ports]` rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkRule {
+    raw: String,
+    host: HostPattern,
+    ports: Option<(u16, u16)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HostPattern {
+    Glob(String),
+    Cidr(IpAddr, u8),
+}
+
+impl NetworkRule {
+    /// Compile a rule string.
+    #[must_use]
+    pub fn parse(rule: &str) -> Self {
+        let (host, ports) = split_ports(rule).unwrap_or((rule, None));
+        let host = parse_cidr(host).unwrap_or_else(|| HostPattern::Glob(host.to_ascii_lowercase()));
+        Self {
+            raw: rule.to_string(),
+            host,
+            ports,
+        }
+    }
+
+    /// The rule as written.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Whether a connection to `host` on `port` falls under this rule.
+    #[must_use]
+    pub fn matches(&self, host: &str, port: Option<u16>) -> bool {
+        if let Some((lo, hi)) = self.ports {
+            match port {
+                Some(p) if (lo..=hi).contains(&p) => {}
+                _ => return false,
+            }
+        }
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        match &self.host {
+            HostPattern::Glob(pattern) => {
+                glob_match(pattern.as_bytes(), host.to_ascii_lowercase().as_bytes())
+            }
+            HostPattern::Cidr(net, bits) => host
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| in_cidr(ip, *net, *bits)),
+        }
+    }
+}
+
+/// Compiled allow and deny rules for outbound connections.
+///
+/// # Examples
+///
+/// ```
+/// use abp_policy::network::NetworkPolicy;
+///
+/// let policy = NetworkPolicy::new(
+///     &["*.example.com:443".into()],
+///     &["admin.example.com".into()],
+/// );
+/// assert!(policy.check("api.example.com", Some(443)).allowed);
+/// assert!(!policy.check("api.example.com", Some(80)).allowed);
+/// assert!(!policy.check("admin.example.com", Some(443)).allowed);
+/// assert!(!policy.check_url("http://api.example.com/v1").allowed);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkPolicy {
+    allow: Vec<NetworkRule>,
+    deny: Vec<NetworkRule>,
+}
+
+impl NetworkPolicy {
+    /// Compile `allow` and `deny` rule lists.
+    #[must_use]
+    pub fn new(allow: &[String], deny: &[String]) -> Self {
+        Self {
+            allow: allow.iter().map(|r| NetworkRule::parse(r)).collect(),
+            deny: deny.iter().map(|r| NetworkRule::parse(r)).collect(),
+        }
+    }
+
+    /// Whether no rule is configured, so every connection is allowed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Check a connection to `host` on `port`.
+    #[must_use]
+    pub fn check(&self, host: &str, port: Option<u16>) -> Decision {
+        let target = match port {
+            Some(p) => format!("{host}:{p}"),
+            None => host.to_string(),
+        };
+        if let Some(rule) = self.deny.iter().find(|r| r.matches(host, port)) {
+            return Decision::deny(format!(
+                "network access to '{target}' denied by rule '{}'",
+                rule.as_str()
+            ));
+        }
+        if !self.allow.is_empty() && !self.allow.iter().any(|r| r.matches(host, port)) {
+            return Decision::deny(format!("network access to '{target}' not in allow_network"));
+        }
+        Decision::allow()
+    }
+
+    /// Check a request to `url`, using the scheme's default port when the
+    /// URL names none.
+    ///
+    /// A URL without a recognisable host is denied unless no rule is
+    /// configured.
+    #[must_use]
+    pub fn check_url(&self, url: &str) -> Decision {
+        match url_target(url) {
+            Some((host, port)) => self.check(&host, port),
+            None if self.is_empty() => Decision::allow(),
+            None => Decision::deny(format!("cannot determine network host of '{url}'")),
+        }
+    }
+}
+
+/// The host and port a URL connects to.
+///
+/// The port falls back to the scheme default (`http`/`ws` 80,
+/// `https`/`wss` 443, `ftp` 21, `ssh` 22) and is `None` for other schemes.
+#[must_use]
+pub fn url_target(url: &str) -> Option<(String, Option<u16>)> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, a)| a);
+    let (host, port) = if let Some(stripped) = authority.strip_prefix('[') {
+        let (host, tail) = stripped.split_once(']')?;
+        (host, tail.strip_prefix(':'))
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    if host.is_empty() {
+        return None;
+    }
+    let port = match port {
+        Some(p) => Some(p.parse().ok()?),
+        None => match scheme.to_ascii_lowercase().as_str() {
+            "http" | "ws" => Some(80),
+            "https" | "wss" => Some(443),
+            "ftp" => Some(21),
+            "ssh" => Some(22),
+            _ => None,
+        },
+    };
+    Some((host.to_ascii_lowercase(), port))
+}
+
+/// Split `host:ports`, returning `None` when the suffix is not a port spec.
+fn split_ports(rule: &str) -> Option<(&str, Option<(u16, u16)>)> {
+    if let Some(stripped) = rule.strip_prefix('[') {
+        let (host, tail) = stripped.split_once(']')?;
+        return match tail.strip_prefix(':') {
+            Some(ports) => Some((host, parse_ports(ports)?)),
+            None if tail.is_empty() => Some((host, None)),
+            None => None,
+        };
+    }
+    let (host, ports) = rule.rsplit_once(':')?;
+    if host.contains(':') {
+        // A bare IPv6 address; ports need the bracketed form.
+        return None;
+    }
+    Some((host, parse_ports(ports)?))
+}
+
+/// `*` (any port), `N`, or `LO-HI`.
+fn parse_ports(spec: &str) -> Option<Option<(u16, u16)>> {
+    if spec == "*" {
+        return Some(None);
+    }
+    let (lo, hi) = spec.split_once('-').unwrap_or((spec, spec));
+    let (lo, hi) = (lo.parse().ok()?, hi.parse().ok()?);
+    (lo <= hi).then_some(Some((lo, hi)))
+}
+
+fn parse_cidr(host: &str) -> Option<HostPattern> {
+    let (addr, bits) = host.split_once('/')?;
+    let addr: IpAddr = addr.parse().ok()?;
+    let bits: u8 = bits.parse().ok()?;
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    (bits <= max).then_some(HostPattern::Cidr(addr, bits))
+}
+
+fn in_cidr(ip: IpAddr, net: IpAddr, bits: u8) -> bool {
+    match (ip, net) {
+        (IpAddr::V4(ip), IpAddr::V4(net)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(bits)).unwrap_or(0);
+            u32::from(ip) & mask == u32::from(net) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(net)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(bits)).unwrap_or(0);
+            u128::from(ip) & mask == u128::from(net) & mask
+        }
+        _ => false,
+    }
+}
+
+/// Glob match where `*` spans any run of bytes and `?` matches one.
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == b'?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == b'*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((sp, st)) = star {
+            p = sp + 1;
+            t = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allow: &[&str], deny: &[&str]) -> NetworkPolicy {
+        let owned = |v: &[&str]| v.iter().map(|s| (*s).to_string()).collect::<Vec<_>>();
+        NetworkPolicy::new(&owned(allow), &owned(deny))
+    }
+
+    #[test]
+    fn wildcard_matches_subdomains_at_any_depth_but_not_the_apex() {
+        let rule = NetworkRule::parse("*.Example.com");
+        assert!(rule.matches("api.example.com", None));
+        assert!(rule.matches("a.b.EXAMPLE.com", Some(1)));
+        assert!(!rule.matches("example.com", None));
+        assert!(!rule.matches("example.com.evil.net", None));
+    }
+
+    #[test]
+    fn port_rules_need_a_matching_known_port() {
+        let rule = NetworkRule::parse("db.internal:5432");
+        assert!(rule.matches("db.internal", Some(5432)));
+        assert!(!rule.matches("db.internal", Some(5433)));
+        assert!(!rule.matches("db.internal", None));
+
+        let range = NetworkRule::parse("*:8000-8999");
+        assert!(range.matches("anything", Some(8080)));
+        assert!(!range.matches("anything", Some(9000)));
+        assert!(NetworkRule::parse("host:*").matches("host", None));
+    }
+
+    #[test]
+    fn cidr_and_ipv6_rules() {
+        let block = NetworkRule::parse("10.0.0.0/8");
+        assert!(block.matches("10.42.0.1", None));
+        assert!(!block.matches("11.0.0.1", None));
+        assert!(!block.matches("ten.example", None));
+
+        let v6 = NetworkRule::parse("[::1]:22");
+        assert!(v6.matches("[::1]", Some(22)));
+        assert!(!v6.matches("::1", Some(23)));
+        assert!(NetworkRule::parse("fe80::/10").matches("fe80::1", Some(80)));
+    }
+
+    #[test]
+    fn malformed_rules_fall_back_to_host_patterns() {
+        let rule = NetworkRule::parse("host:99999");
+        assert!(rule.matches("host:99999", None));
+        assert!(!rule.matches("host", Some(80)));
+        assert!(NetworkRule::parse("").matches("", None));
+    }
+
+    #[test]
+    fn deny_beats_allow_and_allow_list_is_exclusive() {
+        let p = policy(&["*.example.com"], &["admin.example.com"]);
+        assert!(p.check("api.example.com", None).allowed);
+        let denied = p.check("admin.example.com", Some(443));
+        assert_eq!(
+            denied.reason.as_deref(),
+            Some("network access to 'admin.example.com:443' denied by rule 'admin.example.com'")
+        );
+        let outside = p.check("other.org", None);
+        assert!(!outside.allowed);
+        assert!(outside.reason.unwrap().contains("not in allow_network"));
+    }
+
+    #[test]
+    fn urls_resolve_host_and_default_port() {
+        assert_eq!(
+            url_target("https://user:pw@API.example.com/v1?q=1"),
+            Some(("api.example.com".into(), Some(443)))
+        );
+        assert_eq!(
+            url_target("http://[::1]:8080/"),
+            Some(("::1".into(), Some(8080)))
+        );
+        assert_eq!(url_target("custom://host"), Some(("host".into(), None)));
+        assert_eq!(url_target("not a url"), None);
+
+        let p = policy(&[], &["*:80"]);
+        assert!(!p.check_url("http://example.com").allowed);
+        assert!(p.check_url("https://example.com").allowed);
+        assert!(!p.check_url("garbage").allowed);
+        assert!(NetworkPolicy::default().check_url("garbage").allowed);
+    }
+}
@@ -0,0 +1,409 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Named policy presets for common restrictions.
+//!
+//! Hand-written glob sets for "read only" or "docs only" are easy to get
+//! subtly wrong. A [`PolicyPreset`](crate::presets::PolicyPreset) names a
+//! reviewed [`PolicyProfile`] instead:
+//!
+//! | Preset | Effect |
+//! |--------|--------|
+//! | `read_only` | No file writes; write, edit and shell tools disabled |
+//! | `docs_only` | Writes limited to `docs/`, `doc/` and Markdown, reStructuredText or AsciiDoc files; shell disabled |
+//! | `tests_only` | Writes limited to test directories and test files; shell stays available to run them |
+//! | `no_network` | Every host denied; web tools disabled |
+//! | `full_sandbox` | `no_network`, no shell or MCP tools, and credentials hidden and protected |
+//!
+//! Presets are selected by name — from a work order's
+//! `config.vendor["abp"]["policy_presets"]` (a name or a list of names), or
+//! the `policy_presets` list of the backplane configuration — and composed
+//! with the work order's own policy by [`compose`](crate::presets::compose):
+//! every restriction applies, and custom globs come last, so a custom
+//! `deny_write` entry such as `"!src/generated/**"` can re-open a path a
+//! preset closed.
+//!
+//! ```
+//! use abp_core::PolicyProfile;
+//! use abp_policy::PolicyEngine;
+//! use abp_policy::presets::{PolicyPreset, compose};
+//! use std::path::Path;
+//!
+//! let custom = PolicyProfile {
+//!     deny_write: vec!["docs/internal/**".into()],
+//!     ..PolicyProfile::default()
+//! };
+//! let policy = compose(&[PolicyPreset::DocsOnly], &custom);
+//! let engine = PolicyEngine::new(&policy).unwrap();
+//!
+//! assert!(engine.can_write_path(Path::new("docs/guide.md")).allowed);
+//! assert!(engine.can_write_path(Path::new("README.md")).allowed);
+//! assert!(!engine.can_write_path(Path::new("src/lib.rs")).allowed);
+//! assert!(!engine.can_write_path(Path::new("docs/internal/plan.md")).allowed);
+//! assert!(!engine.can_use_tool("Bash").allowed);
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use abp_core::{PolicyProfile, WorkOrder};
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Key under `config.vendor["abp"]` naming the presets a work order runs
+/// under.
+pub const PRESETS_VENDOR_KEY: &str = "policy_presets";
+
+/// Tools that modify files.
+const WRITE_TOOLS: [&str; 4] = ["Write", "Edit", "MultiEdit", "NotebookEdit"];
+
+/// Tools that reach the network.
+const WEB_TOOLS: [&str; 2] = ["WebFetch", "WebSearch"];
+
+/// Credentials and keys no sandboxed run should see.
+const SECRETS: [&str; 9] = [
+    "**/.env",
+    "**/.env.*",
+    "**/*.pem",
+    "**/*.key",
+    "**/id_rsa*",
+    "**/id_ed25519*",
+    "**/.ssh/**",
+    "**/.aws/**",
+    "**/.netrc",
+];
+
+/// A named, reviewed policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyPreset {
+    /// No file writes; write, edit and shell tools disabled.
+    ReadOnly,
+    /// Writes limited to documentation; shell disabled.
+    DocsOnly,
+    /// Writes limited to tests; shell available to run them.
+    TestsOnly,
+    /// Every network host denied; web tools disabled.
+    NoNetwork,
+    /// No network, shell or MCP tools; credentials hidden and protected.
+    FullSandbox,
+}
+
+impl PolicyPreset {
+    /// Every preset, in documentation order.
+    pub const ALL: [Self; 5] = [
+        Self::ReadOnly,
+        Self::DocsOnly,
+        Self::TestsOnly,
+        Self::NoNetwork,
+        Self::FullSandbox,
+    ];
+
+    /// The name the preset is selected by.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::DocsOnly => "docs_only",
+            Self::TestsOnly => "tests_only",
+            Self::NoNetwork => "no_network",
+            Self::FullSandbox => "full_sandbox",
+        }
+    }
+
+    /// One-line summary of what the preset allows.
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "no file writes; write, edit and shell tools disabled",
+            Self::DocsOnly => "writes limited to documentation; shell disabled",
+            Self::TestsOnly => "writes limited to tests; shell available to run them",
+            Self::NoNetwork => "every network host denied; web tools disabled",
+            Self::FullSandbox => "no network, shell or MCP tools; credentials hidden and protected",
+        }
+    }
+
+    /// The policy the preset stands for.
+    #[must_use]
+    pub fn profile(self) -> PolicyProfile {
+        match self {
+            Self::ReadOnly => PolicyProfile {
+                disallowed_tools: strings(WRITE_TOOLS.iter().chain(&["Bash"])),
+                deny_write: strings(&["**"]),
+                ..PolicyProfile::default()
+            },
+            Self::DocsOnly => PolicyProfile {
+                disallowed_tools: strings(&["Bash", "NotebookEdit"]),
+                deny_write: strings(&[
+                    "**",
+                    "!docs/**",
+                    "!doc/**",
+                    "!**/*.md",
+                    "!**/*.mdx",
+                    "!**/*.rst",
+                    "!**/*.adoc",
+                ]),
+                ..PolicyProfile::default()
+            },
+            Self::TestsOnly => PolicyProfile {
+                deny_write: strings(&[
+                    "**",
+                    "!**/tests/**",
+                    "!**/test/**",
+                    "!**/__tests__/**",
+                    "!**/testdata/**",
+                    "!**/fixtures/**",
+                    "!**/*_test.*",
+                    "!**/*_tests.*",
+                    "!**/test_*.*",
+                    "!**/*.test.*",
+                    "!**/*.spec.*",
+                ]),
+                ..PolicyProfile::default()
+            },
+            Self::NoNetwork => PolicyProfile {
+                disallowed_tools: strings(&WEB_TOOLS),
+                deny_network: strings(&["*"]),
+                ..PolicyProfile::default()
+            },
+            Self::FullSandbox => PolicyProfile {
+                disallowed_tools: strings(WEB_TOOLS.iter().chain(&[
+                    "Bash",
+                    "KillBash",
+                    "NotebookEdit",
+                    "mcp__*",
+                ])),
+                deny_read: strings(&SECRETS),
+                deny_write: strings(SECRETS.iter().chain(&["**/.git/**"])),
+                deny_network: strings(&["*"]),
+                ..PolicyProfile::default()
+            },
+        }
+    }
+}
+
+impl fmt::Display for PolicyPreset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for PolicyPreset {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.trim().replace('-', "_").to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|p| p.name() == name)
+            .ok_or_else(|| {
+                let known: Vec<_> = Self::ALL.iter().map(|p| p.name()).collect();
+                anyhow!(
+                    "unknown policy preset '{s}' (expected one of: {})",
+                    known.join(", ")
+                )
+            })
+    }
+}
+
+/// Parse preset names.
+///
+/// # Errors
+///
+/// Returns an error naming the first unknown preset.
+pub fn resolve<S: AsRef<str>>(names: &[S]) -> Result<Vec<PolicyPreset>> {
+    names.iter().map(|n| n.as_ref().parse()).collect()
+}
+
+/// The policy of `presets` combined with `custom`.
+///
+/// Rule lists are concatenated — presets in order, then `custom` — so every
+/// restriction applies and `custom`'s `!` re-inclusions in `deny_read` /
+/// `deny_write` come last and win. Repeated rules keep their last
+/// position.
+#[must_use]
+pub fn compose(presets: &[PolicyPreset], custom: &PolicyProfile) -> PolicyProfile {
+    let profiles: Vec<PolicyProfile> = presets
+        .iter()
+        .map(|p| p.profile())
+        .chain([custom.clone()])
+        .collect();
+    let join = |field: fn(&PolicyProfile) -> &Vec<String>| {
+        keep_last(profiles.iter().flat_map(|p| field(p).iter().cloned()))
+    };
+    PolicyProfile {
+        allowed_tools: join(|p| &p.allowed_tools),
+        disallowed_tools: join(|p| &p.disallowed_tools),
+        deny_read: join(|p| &p.deny_read),
+        deny_write: join(|p| &p.deny_write),
+        allow_network: join(|p| &p.allow_network),
+        deny_network: join(|p| &p.deny_network),
+        require_approval_for: join(|p| &p.require_approval_for),
+    }
+}
+
+/// The presets `work_order` asks for in
+/// `config.vendor["abp"]["policy_presets"]` (or the flat
+/// `"abp.policy_presets"` key).
+///
+/// # Errors
+///
+/// Returns an error if the value is not a name or a list of names, or names
+/// an unknown preset.
+pub fn requested(work_order: &WorkOrder) -> Result<Vec<PolicyPreset>> {
+    let vendor = &work_order.config.vendor;
+    let value = vendor
+        .get("abp")
+        .and_then(|abp| abp.get(PRESETS_VENDOR_KEY))
+        .or_else(|| vendor.get(&format!("abp.{PRESETS_VENDOR_KEY}")));
+    match value {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::String(name)) => Ok(vec![name.parse()?]),
+        Some(Value::Array(names)) => names
+            .iter()
+            .map(|n| match n {
+                Value::String(name) => name.parse(),
+                other => bail!("policy preset names must be strings, got {other}"),
+            })
+            .collect(),
+        Some(other) => bail!("{PRESETS_VENDOR_KEY} must be a name or a list of names, got {other}"),
+    }
+}
+
+/// Fold the presets `work_order` asks for into its policy and drop the
+/// request, so applying twice changes nothing.
+///
+/// Returns the presets applied.
+///
+/// # Errors
+///
+/// Fails like [`requested`].
+pub fn apply(work_order: &mut WorkOrder) -> Result<Vec<PolicyPreset>> {
+    let presets = requested(work_order)?;
+    if presets.is_empty() {
+        return Ok(presets);
+    }
+    work_order.policy = compose(&presets, &work_order.policy);
+    let vendor = &mut work_order.config.vendor;
+    vendor.remove(&format!("abp.{PRESETS_VENDOR_KEY}"));
+    if let Some(abp) = vendor.get_mut("abp").and_then(Value::as_object_mut) {
+        abp.remove(PRESETS_VENDOR_KEY);
+    }
+    Ok(presets)
+}
+
+fn strings<'a>(items: impl IntoIterator<Item = &'a &'a str>) -> Vec<String> {
+    items.into_iter().map(|s| (*s).to_string()).collect()
+}
+
+/// `items` without repeats, each kept at its last position.
+fn keep_last(items: impl DoubleEndedIterator<Item = String>) -> Vec<String> {
+    let mut seen = BTreeSet::new();
+    let mut kept: Vec<String> = items.rev().filter(|s| seen.insert(s.clone())).collect();
+    kept.reverse();
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PolicyEngine;
+    use std::path::Path;
+
+    fn engine(presets: &[PolicyPreset]) -> PolicyEngine {
+        PolicyEngine::new(&compose(presets, &PolicyProfile::default())).unwrap()
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for preset in PolicyPreset::ALL {
+            assert_eq!(preset.name().parse::<PolicyPreset>().unwrap(), preset);
+            let json = serde_json::to_value(preset).unwrap();
+            assert_eq!(json, preset.name());
+        }
+        assert_eq!(
+            "Read-Only".parse::<PolicyPreset>().unwrap(),
+            PolicyPreset::ReadOnly
+        );
+        let err = "yolo".parse::<PolicyPreset>().unwrap_err().to_string();
+        assert!(err.contains("read_only, docs_only"), "{err}");
+    }
+
+    #[test]
+    fn read_only_blocks_every_write() {
+        let e = engine(&[PolicyPreset::ReadOnly]);
+        assert!(!e.can_write_path(Path::new("README.md")).allowed);
+        assert!(!e.can_use_tool("Edit").allowed);
+        assert!(e.can_use_tool("Read").allowed);
+        assert!(e.can_read_path(Path::new("src/lib.rs")).allowed);
+    }
+
+    #[test]
+    fn tests_only_opens_test_paths() {
+        let e = engine(&[PolicyPreset::TestsOnly]);
+        for path in [
+            "tests/cli.rs",
+            "crates/a/tests/x.rs",
+            "src/parser_test.go",
+            "web/app.spec.ts",
+            "pkg/test_util.py",
+        ] {
+            assert!(e.can_write_path(Path::new(path)).allowed, "{path}");
+        }
+        for path in ["src/lib.rs", "Cargo.toml", "latest/x.rs"] {
+            assert!(!e.can_write_path(Path::new(path)).allowed, "{path}");
+        }
+        assert!(e.can_use_tool("Bash").allowed);
+    }
+
+    #[test]
+    fn composed_presets_all_apply() {
+        let e = engine(&[PolicyPreset::DocsOnly, PolicyPreset::NoNetwork]);
+        assert!(!e.can_fetch_url("https://example.com").allowed);
+        assert!(!e.can_write_path(Path::new("src/main.rs")).allowed);
+        assert!(e.can_write_path(Path::new("docs/index.md")).allowed);
+        assert!(!e.can_write_path(Path::new("requirements.txt")).allowed);
+
+        let sandbox = engine(&[PolicyPreset::FullSandbox]);
+        assert!(!sandbox.can_read_path(Path::new("config/.env")).allowed);
+        assert!(!sandbox.can_use_tool("mcp__github__create_issue").allowed);
+        assert!(sandbox.can_write_path(Path::new("src/lib.rs")).allowed);
+        assert!(!sandbox.can_write_path(Path::new(".git/config")).allowed);
+    }
+
+    #[test]
+    fn custom_rules_come_last_and_repeats_collapse() {
+        let custom = PolicyProfile {
+            deny_write: vec!["!src/generated/**".into(), "**".into()],
+            ..PolicyProfile::default()
+        };
+        let policy = compose(&[PolicyPreset::ReadOnly], &custom);
+        assert_eq!(policy.deny_write, ["!src/generated/**", "**"]);
+
+        let custom = PolicyProfile {
+            deny_write: vec!["!src/generated/**".into()],
+            ..PolicyProfile::default()
+        };
+        let e = PolicyEngine::new(&compose(&[PolicyPreset::ReadOnly], &custom)).unwrap();
+        assert!(e.can_write_path(Path::new("src/generated/api.rs")).allowed);
+        assert!(!e.can_write_path(Path::new("src/lib.rs")).allowed);
+    }
+
+    #[test]
+    fn work_orders_select_presets_by_name() {
+        let mut wo = abp_core::WorkOrderBuilder::new("t").build();
+        wo.config.vendor.insert(
+            "abp".into(),
+            serde_json::json!({"policy_presets": ["read_only", "no_network"]}),
+        );
+        let applied = apply(&mut wo).unwrap();
+        assert_eq!(applied, [PolicyPreset::ReadOnly, PolicyPreset::NoNetwork]);
+        assert_eq!(wo.policy.deny_network, ["*"]);
+        assert!(requested(&wo).unwrap().is_empty());
+
+        wo.config
+            .vendor
+            .insert("abp.policy_presets".into(), serde_json::json!("nope"));
+        assert!(apply(&mut wo).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for layered policy precedence and explanations.
+
+use std::path::Path;
+
+use abp_core::{PolicyProfile, WorkOrderBuilder};
+use abp_policy::compose::PolicyDecision;
+use abp_policy::layers::{Action, LayerKind, LayeredPolicy, PolicyLayer};
+use abp_policy::presets::PolicyPreset;
+use serde_json::json;
+
+fn org() -> PolicyLayer {
+    PolicyLayer::new(
+        LayerKind::Org,
+        "acme",
+        PolicyProfile {
+            disallowed_tools: vec!["WebFetch".into()],
+            deny_read: vec!["**/.env".into()],
+            deny_write: vec!["**".into()],
+            deny_network: vec!["*".into()],
+            ..PolicyProfile::default()
+        },
+    )
+}
+
+fn project() -> PolicyLayer {
+    PolicyLayer::new(
+        LayerKind::Project,
+        "widgets",
+        PolicyProfile {
+            disallowed_tools: vec!["!WebFetch".into()],
+            deny_write: vec!["!src/**".into(), "src/generated/**".into()],
+            allow_network: vec!["*.github.com:443".into()],
+            ..PolicyProfile::default()
+        },
+    )
+}
+
+#[test]
+fn the_most_specific_layer_with_an_opinion_decides() {
+    let policy = LayeredPolicy::new(vec![project(), org()]).unwrap();
+
+    let why = policy.explain(&Action::write("src/lib.rs"));
+    assert!(why.allowed);
+    assert_eq!(why.decided_by.as_deref(), Some("widgets"));
+    assert_eq!(why.rule.as_deref(), Some("!src/**"));
+
+    let why = policy.explain(&Action::write("src/generated/api.rs"));
+    assert!(!why.allowed);
+    assert_eq!(why.rule.as_deref(), Some("src/generated/**"));
+
+    // The project has no write rule for README.md, so the org default holds.
+    let why = policy.explain(&Action::write("README.md"));
+    assert!(!why.allowed);
+    assert_eq!(why.decided_by.as_deref(), Some("acme"));
+    assert_eq!(why.layers[1].decision, PolicyDecision::Abstain);
+
+    assert!(policy.can_use_tool("WebFetch").allowed);
+    assert!(policy.can_connect("api.github.com", Some(443)).allowed);
+    assert!(!policy.can_connect("example.com", Some(443)).allowed);
+}
+
+#[test]
+fn enforced_denies_cannot_be_reopened() {
+    let policy = LayeredPolicy::new(vec![org().enforced(), project()]).unwrap();
+
+    let why = policy.explain(&Action::write("src/lib.rs"));
+    assert!(!why.allowed);
+    assert_eq!(why.decided_by.as_deref(), Some("acme"));
+    assert_eq!(why.rule.as_deref(), Some("**"));
+    assert!(!policy.can_use_tool("WebFetch").allowed);
+    assert!(!policy.can_connect("api.github.com", Some(443)).allowed);
+
+    // Enforcement only pins denies; what the org leaves open stays open.
+    assert!(policy.can_read_path(Path::new("src/lib.rs")).allowed);
+}
+
+#[test]
+fn unmatched_actions_are_allowed() {
+    let policy = LayeredPolicy::new(vec![org()]).unwrap();
+    let why = policy.explain(&Action::tool("Read"));
+    assert!(why.allowed);
+    assert_eq!(why.decided_by, None);
+    assert_eq!(why.reason, "no layer has a matching rule");
+
+    let empty = LayeredPolicy::new(Vec::new()).unwrap();
+    assert!(empty.can_write_path(Path::new("anything")).allowed);
+}
+
+#[test]
+fn allowlists_deny_what_they_do_not_name() {
+    let policy = LayeredPolicy::new(vec![
+        PolicyLayer::new(
+            LayerKind::Tenant,
+            "globex",
+            PolicyProfile {
+                allowed_tools: vec!["Read".into(), "Grep".into()],
+                ..PolicyProfile::default()
+            },
+        ),
+        PolicyLayer::new(
+            LayerKind::WorkOrder,
+            "run",
+            PolicyProfile {
+                disallowed_tools: vec!["Grep".into()],
+                ..PolicyProfile::default()
+            },
+        ),
+    ])
+    .unwrap();
+
+    let why = policy.explain(&Action::tool("Write"));
+    assert!(!why.allowed);
+    assert_eq!(why.decided_by.as_deref(), Some("globex"));
+    assert_eq!(why.rule, None);
+    assert!(why.reason.contains("not in allowlist"), "{}", why.reason);
+
+    let why = policy.explain(&Action::tool("Grep"));
+    assert!(!why.allowed);
+    assert_eq!(why.decided_by.as_deref(), Some("run"));
+}
+
+#[test]
+fn commands_respect_the_shell_tool_and_bash_rules() {
+    let policy = LayeredPolicy::new(vec![
+        PolicyLayer::new(
+            LayerKind::Org,
+            "acme",
+            PolicyProfile {
+                disallowed_tools: vec!["Bash(git push*)".into(), "Bash(curl *)".into()],
+                ..PolicyProfile::default()
+            },
+        )
+        .enforced(),
+        PolicyLayer::new(
+            LayerKind::Project,
+            "widgets",
+            PolicyProfile {
+                disallowed_tools: vec!["Bash(!curl *)".into()],
+                ..PolicyProfile::default()
+            },
+        ),
+        PolicyLayer::preset(PolicyPreset::ReadOnly),
+    ])
+    .unwrap();
+
+    // The read-only preset disallows the shell tool outright.
+    let why = policy.explain(&Action::command("cargo test"));
+    assert!(!why.allowed);
+    assert_eq!(why.decided_by.as_deref(), Some("read_only"));
+    assert_eq!(why.rule.as_deref(), Some("Bash"));
+
+    let without_preset = LayeredPolicy::new(policy.layers().take(2).cloned().collect()).unwrap();
+    assert!(without_preset.can_run_command("cargo test").allowed);
+    let why = without_preset.explain(&Action::command("cargo test && git push origin"));
+    assert!(!why.allowed);
+    assert_eq!(why.rule.as_deref(), Some("git push*"));
+    // `curl` is denied by an enforced layer, so the project cannot re-allow it.
+    assert!(!without_preset.can_run_command("curl https://x").allowed);
+}
+
+#[test]
+fn work_orders_bring_their_presets_and_policy() {
+    let mut wo = WorkOrderBuilder::new("t")
+        .policy(PolicyProfile {
+            deny_read: vec!["secrets/**".into()],
+            ..PolicyProfile::default()
+        })
+        .build();
+    wo.config
+        .vendor
+        .insert("abp".into(), json!({"policy_presets": ["no_network"]}));
+
+    let policy = LayeredPolicy::for_work_order(vec![org()], &wo).unwrap();
+    let kinds: Vec<_> = policy.layers().map(|l| l.kind).collect();
+    assert_eq!(
+        kinds,
+        [LayerKind::Org, LayerKind::Preset, LayerKind::WorkOrder]
+    );
+
+    let why = policy.explain(&Action::read("secrets/key.pem"));
+    assert!(!why.allowed);
+    assert_eq!(why.decided_by, Some(wo.id.to_string()));
+
+    let why = policy.explain(&Action::connect("example.com", None));
+    assert!(!why.allowed);
+    assert_eq!(why.decided_by.as_deref(), Some("no_network"));
+}
+
+#[test]
+fn explanations_render_and_serialize() {
+    let policy = LayeredPolicy::new(vec![org(), project()]).unwrap();
+    let why = policy.explain(&Action::write("src/lib.rs"));
+
+    let text = why.to_string();
+    let lines: Vec<_> = text.lines().collect();
+    assert_eq!(
+        lines[0],
+        "write 'src/lib.rs': allowed (write re-allowed for 'src/lib.rs' (project layer 'widgets'))"
+    );
+    assert_eq!(lines[1], "  acme (org): write denied for 'src/lib.rs'");
+    assert_eq!(
+        lines[2],
+        "* widgets (project): write re-allowed for 'src/lib.rs'"
+    );
+
+    let value = serde_json::to_value(&why).unwrap();
+    assert_eq!(
+        value["action"],
+        json!({"type": "write", "path": "src/lib.rs"})
+    );
+    assert_eq!(value["layers"][0]["kind"], "org");
+    assert_eq!(value["layers"][1]["decision"]["type"], "allow");
+}
@@ -17,8 +17,8 @@ use abp_capability::{NegotiationResult, negotiate};
 use abp_core::{Capability, CapabilityManifest, CapabilityRequirements, WorkOrder};
 use abp_dialect::Dialect;
 use abp_mapper::{
-    ClaudeToOpenAiMapper, GeminiToOpenAiMapper, IdentityMapper, Mapper, OpenAiToClaudeMapper,
-    OpenAiToGeminiMapper,
+    ChainMapper, ClaudeToOpenAiMapper, GeminiToOpenAiMapper, IdentityMapper, Mapper,
+    OpenAiCompatMapper, OpenAiToClaudeMapper, OpenAiToGeminiMapper,
 };
 use abp_mapping::MappingRegistry;
 use serde::{Deserialize, Serialize};
@@ -908,6 +908,24 @@ impl ProjectionMatrix {
     /// or [`ProjectionError::NoSuitableBackend`] if no backend can satisfy the
     /// work order's requirements.
     pub fn project(&self, work_order: &WorkOrder) -> Result<ProjectionResult, ProjectionError> {
+        self.project_where(work_order, |_| true)
+    }
+
+    /// Project a work order onto the registered backends for which `usable`
+    /// returns `true`.
+    ///
+    /// Backends left out are neither selected nor listed in the fallback
+    /// chain, e.g. while they are known to be unhealthy.
+    ///
+    /// # Errors
+    ///
+    /// As [`project`](Self::project); [`ProjectionError::NoSuitableBackend`]
+    /// also when `usable` rejects every backend.
+    pub fn project_where(
+        &self,
+        work_order: &WorkOrder,
+        usable: impl Fn(&str) -> bool,
+    ) -> Result<ProjectionResult, ProjectionError> {
         if self.backends.is_empty() {
             return Err(ProjectionError::EmptyMatrix);
         }
@@ -924,7 +942,7 @@ impl ProjectionMatrix {
 
         let mut scored: Vec<(String, ProjectionScore, NegotiationResult)> = Vec::new();
 
-        for entry in self.backends.values() {
+        for entry in self.backends.values().filter(|e| usable(&e.id)) {
             let neg = negotiate(&entry.capabilities, &work_order.requirements);
             let cap_coverage = capability_coverage(&neg, &work_order.requirements);
             let fidelity = self.mapping_fidelity(source_dialect, entry.dialect);
@@ -969,7 +987,7 @@ impl ProjectionMatrix {
             // No fully compatible backend — pick the best partial match.
             if scored.is_empty() {
                 return Err(ProjectionError::NoSuitableBackend {
-                    reason: "no backends scored".into(),
+                    reason: "no usable backends".into(),
                 });
             }
             // If the best backend has zero capability coverage, reject.
@@ -1118,10 +1136,35 @@ fn resolve_mapper_for_pair(source: Dialect, target: Dialect) -> Option<Box<dyn M
         (Dialect::Codex, Dialect::OpenAi) | (Dialect::OpenAi, Dialect::Codex) => {
             Some(Box::new(IdentityMapper))
         }
+        // Kimi and Copilot are OpenAI-compatible; other pairs go via OpenAI.
+        (Dialect::OpenAi, Dialect::Kimi | Dialect::Copilot)
+        | (Dialect::Kimi | Dialect::Copilot, Dialect::OpenAi) => {
+            Some(Box::new(OpenAiCompatMapper::new(source, target)?))
+        }
+        (Dialect::Claude, Dialect::Kimi) => chain(
+            ClaudeToOpenAiMapper,
+            OpenAiCompatMapper::new(Dialect::OpenAi, target)?,
+        ),
+        (Dialect::Gemini, Dialect::Kimi) => chain(
+            GeminiToOpenAiMapper,
+            OpenAiCompatMapper::new(Dialect::OpenAi, target)?,
+        ),
+        (Dialect::Kimi, Dialect::Claude) => chain(
+            OpenAiCompatMapper::new(source, Dialect::OpenAi)?,
+            OpenAiToClaudeMapper,
+        ),
+        (Dialect::Kimi, Dialect::Gemini) => chain(
+            OpenAiCompatMapper::new(source, Dialect::OpenAi)?,
+            OpenAiToGeminiMapper,
+        ),
         _ => None,
     }
 }
 
+fn chain(first: impl Mapper + 'static, second: impl Mapper + 'static) -> Option<Box<dyn Mapper>> {
+    ChainMapper::new(first, second).map(|m| Box::new(m) as Box<dyn Mapper>)
+}
+
 // ── Helpers ─────────────────────────────────────────────────────────────
 
 /// Compute the fraction of required capabilities that are native or emulated.
@@ -2265,6 +2308,27 @@ mod tests {
         assert!(mapper.is_none());
     }
 
+    #[test]
+    fn resolve_mapper_covers_kimi_and_copilot_pairs() {
+        let pm = ProjectionMatrix::with_defaults();
+        for (src, tgt) in [
+            (Dialect::OpenAi, Dialect::Kimi),
+            (Dialect::Kimi, Dialect::OpenAi),
+            (Dialect::Claude, Dialect::Kimi),
+            (Dialect::Kimi, Dialect::Claude),
+            (Dialect::Gemini, Dialect::Kimi),
+            (Dialect::Kimi, Dialect::Gemini),
+            (Dialect::OpenAi, Dialect::Copilot),
+            (Dialect::Copilot, Dialect::OpenAi),
+        ] {
+            let m = pm
+                .resolve_mapper(src, tgt)
+                .unwrap_or_else(|| panic!("no mapper for {src} -> {tgt}"));
+            assert_eq!(m.source_dialect(), src);
+            assert_eq!(m.target_dialect(), tgt);
+        }
+    }
+
     #[test]
     fn resolve_mapper_codex_to_openai_uses_identity() {
         let pm = ProjectionMatrix::with_defaults();
@@ -9,7 +9,7 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 
-use abp_core::ir::IrConversation;
+use abp_core::ir::{IrConversation, IrToolChoice, IrToolDefinition};
 use abp_dialect::Dialect;
 use abp_dialect::features::DialectFeature;
 use abp_mapper::{IrMapper, default_ir_mapper, supported_ir_pairs};
@@ -56,6 +56,389 @@ pub struct CapabilityGap {
     pub description: String,
 }
 
+// ── Translation log ─────────────────────────────────────────────────────
+
+/// Category of a concrete transformation applied during translation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslationStepKind {
+    /// Several system messages were collapsed into fewer.
+    SystemPromptsMerged,
+    /// A whole message disappeared from the conversation.
+    MessageDropped,
+    /// A message was added (e.g. a synthesized turn).
+    MessageAdded,
+    /// A message changed role (e.g. `tool` → `user`).
+    RoleChanged,
+    /// Content blocks of a given kind were removed.
+    ContentDropped,
+    /// A tool invocation was renamed.
+    ToolRenamed,
+    /// Message metadata keys were not carried over.
+    MetadataDropped,
+    /// A request field has no equivalent in the target and was not sent.
+    FieldDropped,
+    /// A request field was sent under a different name or value.
+    FieldRenamed,
+}
+
+/// A single entry in the translation audit trail.
+///
+/// Entries explain why the backend saw a different prompt than the one
+/// submitted; they are recorded in the receipt as `translation_log`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationLogEntry {
+    /// What kind of transformation happened.
+    pub kind: TranslationStepKind,
+    /// Index of the affected message in the source conversation, if the
+    /// change can be attributed to a single message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_index: Option<usize>,
+    /// Path of the affected request field, such as
+    /// `messages[1].content` or `tool_choice`, when the change is confined
+    /// to one field.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// Human-readable description of the change.
+    pub detail: String,
+}
+
+impl TranslationLogEntry {
+    fn new(kind: TranslationStepKind, message_index: Option<usize>, detail: String) -> Self {
+        Self {
+            kind,
+            message_index,
+            field: None,
+            detail,
+        }
+    }
+
+    fn at(mut self, field: String) -> Self {
+        self.field = Some(field);
+        self
+    }
+}
+
+fn block_kind(block: &abp_core::ir::IrContentBlock) -> &'static str {
+    use abp_core::ir::IrContentBlock;
+    match block {
+        IrContentBlock::Text { .. } => "text",
+        IrContentBlock::Image { .. } => "image",
+        IrContentBlock::ToolUse { .. } => "tool_use",
+        IrContentBlock::ToolResult { .. } => "tool_result",
+        IrContentBlock::Thinking { .. } => "thinking",
+    }
+}
+
+fn block_counts<'a>(
+    messages: impl IntoIterator<Item = &'a abp_core::ir::IrMessage>,
+) -> BTreeMap<&'static str, usize> {
+    let mut counts = BTreeMap::new();
+    for msg in messages {
+        for block in &msg.content {
+            *counts.entry(block_kind(block)).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+fn tool_uses(message: &abp_core::ir::IrMessage) -> impl Iterator<Item = (usize, &str)> {
+    message
+        .content
+        .iter()
+        .enumerate()
+        .filter_map(|(j, b)| match b {
+            abp_core::ir::IrContentBlock::ToolUse { name, .. } => Some((j, name.as_str())),
+            _ => None,
+        })
+}
+
+fn tool_names(conversation: &IrConversation) -> Vec<&str> {
+    conversation
+        .messages
+        .iter()
+        .flat_map(|m| &m.content)
+        .filter_map(|b| match b {
+            abp_core::ir::IrContentBlock::ToolUse { name, .. } => Some(name.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Diff a source conversation against its translation and describe every
+/// concrete transformation as a [`TranslationLogEntry`].
+///
+/// Messages are compared index-by-index when both conversations have the
+/// same length, so role changes, dropped metadata and content, and renamed
+/// tool calls are pinned to the message and field they affect; otherwise
+/// differences are reported in aggregate.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::ir::{IrConversation, IrMessage, IrRole};
+/// use abp_projection::translate::{TranslationStepKind, audit_translation};
+///
+/// let source = IrConversation::from_messages(vec![
+///     IrMessage::text(IrRole::System, "Be brief."),
+///     IrMessage::text(IrRole::System, "Use English."),
+///     IrMessage::text(IrRole::User, "Hi"),
+/// ]);
+/// let translated = IrConversation::from_messages(vec![
+///     IrMessage::text(IrRole::System, "Be brief.\nUse English."),
+///     IrMessage::text(IrRole::User, "Hi"),
+/// ]);
+/// let log = audit_translation(&source, &translated);
+/// assert_eq!(log[0].kind, TranslationStepKind::SystemPromptsMerged);
+/// ```
+#[must_use]
+pub fn audit_translation(
+    source: &IrConversation,
+    translated: &IrConversation,
+) -> Vec<TranslationLogEntry> {
+    use abp_core::ir::IrRole;
+
+    let mut log = Vec::new();
+    let src = &source.messages;
+    let dst = &translated.messages;
+
+    // System prompt merges.
+    let src_system = src.iter().filter(|m| m.role == IrRole::System).count();
+    let dst_system = dst.iter().filter(|m| m.role == IrRole::System).count();
+    if src_system > 1 && (1..src_system).contains(&dst_system) {
+        log.push(TranslationLogEntry::new(
+            TranslationStepKind::SystemPromptsMerged,
+            None,
+            format!("{src_system} system messages merged into {dst_system}"),
+        ));
+    }
+    let merged = if dst_system > 0 {
+        src_system.saturating_sub(dst_system)
+    } else {
+        0
+    };
+
+    // Messages dropped or added (beyond system merges).
+    let expected = src.len() - merged;
+    if dst.len() < expected {
+        log.push(TranslationLogEntry::new(
+            TranslationStepKind::MessageDropped,
+            None,
+            format!("{} message(s) dropped", expected - dst.len()),
+        ));
+    } else if dst.len() > expected {
+        log.push(TranslationLogEntry::new(
+            TranslationStepKind::MessageAdded,
+            None,
+            format!("{} message(s) added", dst.len() - expected),
+        ));
+    }
+
+    // Per-message changes when messages line up.
+    if src.len() == dst.len() {
+        for (i, (s, d)) in src.iter().zip(dst).enumerate() {
+            if s.role != d.role {
+                log.push(
+                    TranslationLogEntry::new(
+                        TranslationStepKind::RoleChanged,
+                        Some(i),
+                        format!("role {:?} became {:?}", s.role, d.role),
+                    )
+                    .at(format!("messages[{i}].role")),
+                );
+            }
+            let dropped: Vec<&str> = s
+                .metadata
+                .keys()
+                .filter(|k| !d.metadata.contains_key(*k))
+                .map(String::as_str)
+                .collect();
+            if !dropped.is_empty() {
+                log.push(
+                    TranslationLogEntry::new(
+                        TranslationStepKind::MetadataDropped,
+                        Some(i),
+                        format!("metadata dropped: {}", dropped.join(", ")),
+                    )
+                    .at(format!("messages[{i}].metadata")),
+                );
+            }
+            let kept = block_counts([d]);
+            for (kind, count) in block_counts([s]) {
+                let remaining = kept.get(kind).copied().unwrap_or(0);
+                if remaining < count {
+                    log.push(
+                        TranslationLogEntry::new(
+                            TranslationStepKind::ContentDropped,
+                            Some(i),
+                            format!("{} {kind} block(s) dropped", count - remaining),
+                        )
+                        .at(format!("messages[{i}].content")),
+                    );
+                }
+            }
+            let (src_tools, dst_tools): (Vec<_>, Vec<_>) =
+                (tool_uses(s).collect(), tool_uses(d).collect());
+            if src_tools.len() == dst_tools.len() {
+                for ((_, from), (j, to)) in src_tools.into_iter().zip(dst_tools) {
+                    if from != to {
+                        log.push(
+                            TranslationLogEntry::new(
+                                TranslationStepKind::ToolRenamed,
+                                Some(i),
+                                format!("tool '{from}' renamed to '{to}'"),
+                            )
+                            .at(format!("messages[{i}].content[{j}].name")),
+                        );
+                    }
+                }
+            }
+        }
+    } else {
+        let dst_keys: BTreeSet<&str> = dst
+            .iter()
+            .flat_map(|m| m.metadata.keys().map(String::as_str))
+            .collect();
+        let dropped: BTreeSet<&str> = src
+            .iter()
+            .flat_map(|m| m.metadata.keys().map(String::as_str))
+            .filter(|k| !dst_keys.contains(k))
+            .collect();
+        if !dropped.is_empty() {
+            log.push(TranslationLogEntry::new(
+                TranslationStepKind::MetadataDropped,
+                None,
+                format!(
+                    "metadata dropped: {}",
+                    dropped.into_iter().collect::<Vec<_>>().join(", ")
+                ),
+            ));
+        }
+
+        // Content blocks removed, by kind. System merges legitimately fold
+        // text blocks together, so text is only compared outside system turns.
+        let src_blocks = block_counts(src.iter().filter(|m| m.role != IrRole::System));
+        let dst_blocks = block_counts(dst.iter().filter(|m| m.role != IrRole::System));
+        for (kind, &count) in &src_blocks {
+            let remaining = dst_blocks.get(kind).copied().unwrap_or(0);
+            if remaining < count {
+                log.push(TranslationLogEntry::new(
+                    TranslationStepKind::ContentDropped,
+                    None,
+                    format!("{} {kind} block(s) dropped", count - remaining),
+                ));
+            }
+        }
+
+        // Tool renames (paired by call order).
+        let src_tools = tool_names(source);
+        let dst_tools = tool_names(translated);
+        if src_tools.len() == dst_tools.len() {
+            let renamed: BTreeSet<(&str, &str)> = src_tools
+                .iter()
+                .zip(&dst_tools)
+                .filter(|(a, b)| a != b)
+                .map(|(a, b)| (*a, *b))
+                .collect();
+            for (from, to) in renamed {
+                log.push(TranslationLogEntry::new(
+                    TranslationStepKind::ToolRenamed,
+                    None,
+                    format!("tool '{from}' renamed to '{to}'"),
+                ));
+            }
+        }
+    }
+
+    log
+}
+
+/// Describe how the tools offered to the model and the tool-choice policy
+/// change when a request moves from `from` to `to`.
+///
+/// Tool definitions carry over between every pair of dialects; the
+/// tool-choice vocabularies differ. Claude has no `none` choice and calls
+/// `required` `any`; Gemini expresses `required` as mode `ANY` and a named
+/// tool as mode `ANY` restricted by `allowedFunctionNames`.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::ir::IrToolChoice;
+/// use abp_dialect::Dialect;
+/// use abp_projection::translate::{TranslationStepKind, audit_tools};
+///
+/// let log = audit_tools(Dialect::OpenAi, Dialect::Claude, &[], Some(&IrToolChoice::None));
+/// assert_eq!(log[0].kind, TranslationStepKind::FieldDropped);
+/// assert_eq!(log[0].field.as_deref(), Some("tool_choice"));
+/// ```
+#[must_use]
+pub fn audit_tools(
+    from: Dialect,
+    to: Dialect,
+    tools: &[IrToolDefinition],
+    tool_choice: Option<&IrToolChoice>,
+) -> Vec<TranslationLogEntry> {
+    let mut log = Vec::new();
+    if from == to {
+        return log;
+    }
+    let target = to.label();
+
+    if !abp_dialect::matrix::dialect_features(to)
+        .supports(DialectFeature::ToolUse)
+        .is_available()
+    {
+        for (i, tool) in tools.iter().enumerate() {
+            log.push(
+                TranslationLogEntry::new(
+                    TranslationStepKind::FieldDropped,
+                    None,
+                    format!("tool '{}' dropped: {target} has no tool use", tool.name),
+                )
+                .at(format!("tools[{i}]")),
+            );
+        }
+        if tool_choice.is_some() {
+            log.push(
+                TranslationLogEntry::new(
+                    TranslationStepKind::FieldDropped,
+                    None,
+                    format!("tool_choice dropped: {target} has no tool use"),
+                )
+                .at("tool_choice".into()),
+            );
+        }
+        return log;
+    }
+
+    let entry = match (to, tool_choice) {
+        (Dialect::Claude, Some(IrToolChoice::None)) => Some((
+            TranslationStepKind::FieldDropped,
+            format!("tool_choice 'none' dropped: {target} has no equivalent"),
+        )),
+        (Dialect::Claude, Some(IrToolChoice::Required)) => Some((
+            TranslationStepKind::FieldRenamed,
+            format!("tool_choice 'required' sent as 'any' to {target}"),
+        )),
+        (Dialect::Gemini, Some(IrToolChoice::Required)) => Some((
+            TranslationStepKind::FieldRenamed,
+            format!("tool_choice 'required' sent as mode 'ANY' to {target}"),
+        )),
+        (Dialect::Gemini, Some(IrToolChoice::Tool { name })) => Some((
+            TranslationStepKind::FieldRenamed,
+            format!(
+                "tool_choice tool '{name}' sent as mode 'ANY' with allowedFunctionNames ['{name}'] to {target}"
+            ),
+        )),
+        _ => None,
+    };
+    if let Some((kind, detail)) = entry {
+        log.push(TranslationLogEntry::new(kind, None, detail).at("tool_choice".into()));
+    }
+    log
+}
+
 // ── Translation result ──────────────────────────────────────────────────
 
 /// The outcome of a successful translation, carrying the translated
@@ -72,6 +455,8 @@ pub struct TranslationResult {
     pub mode: TranslationMode,
     /// Detected capability gaps (non-fatal warnings).
     pub gaps: Vec<CapabilityGap>,
+    /// Concrete transformations applied to the conversation.
+    pub log: Vec<TranslationLogEntry>,
 }
 
 // ── Registered translator ───────────────────────────────────────────────
@@ -235,6 +620,7 @@ impl TranslationEngine {
                 to,
                 mode: TranslationMode::Passthrough,
                 gaps: Vec::new(),
+                log: Vec::new(),
             });
         }
 
@@ -253,6 +639,7 @@ impl TranslationEngine {
         })?;
 
         let gaps = self.detect_gaps(from, to, conversation);
+        let log = audit_translation(conversation, &translated);
 
         Ok(TranslationResult {
             conversation: translated,
@@ -260,6 +647,7 @@ impl TranslationEngine {
             to,
             mode: TranslationMode::Mapped,
             gaps,
+            log,
         })
     }
 
@@ -280,6 +668,7 @@ impl TranslationEngine {
                 to,
                 mode: TranslationMode::Passthrough,
                 gaps: Vec::new(),
+                log: Vec::new(),
             });
         }
 
@@ -297,6 +686,7 @@ impl TranslationEngine {
         })?;
 
         let gaps = self.detect_gaps(from, to, conversation);
+        let log = audit_translation(conversation, &translated);
 
         Ok(TranslationResult {
             conversation: translated,
@@ -304,6 +694,7 @@ impl TranslationEngine {
             to,
             mode: TranslationMode::Mapped,
             gaps,
+            log,
         })
     }
 
@@ -1085,4 +1476,165 @@ mod tests {
             assert_eq!(result.mode, TranslationMode::Mapped);
         }
     }
+
+    // ── Translation log ─────────────────────────────────────────────────
+
+    #[test]
+    fn audit_identical_conversations_is_empty() {
+        let conv = tool_conv();
+        assert!(audit_translation(&conv, &conv).is_empty());
+    }
+
+    #[test]
+    fn audit_records_system_merge() {
+        let source = IrConversation::from_messages(vec![
+            IrMessage::text(IrRole::System, "A"),
+            IrMessage::text(IrRole::System, "B"),
+            IrMessage::text(IrRole::User, "Hi"),
+        ]);
+        let translated = IrConversation::from_messages(vec![
+            IrMessage::text(IrRole::System, "A\nB"),
+            IrMessage::text(IrRole::User, "Hi"),
+        ]);
+        let log = audit_translation(&source, &translated);
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].kind, TranslationStepKind::SystemPromptsMerged);
+        assert_eq!(log[0].detail, "2 system messages merged into 1");
+    }
+
+    #[test]
+    fn audit_records_dropped_blocks_and_roles() {
+        let source = thinking_conv();
+        let translated =
+            IrConversation::from_messages(vec![IrMessage::text(IrRole::User, "Answer")]);
+        let log = audit_translation(&source, &translated);
+        let kinds: Vec<_> = log.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TranslationStepKind::RoleChanged,
+                TranslationStepKind::ContentDropped
+            ]
+        );
+        assert_eq!(log[0].message_index, Some(0));
+        assert_eq!(log[0].field.as_deref(), Some("messages[0].role"));
+        assert_eq!(log[1].detail, "1 thinking block(s) dropped");
+        assert_eq!(log[1].field.as_deref(), Some("messages[0].content"));
+    }
+
+    #[test]
+    fn audit_records_tool_rename_and_metadata() {
+        let source = tool_conv();
+        let mut translated = tool_conv();
+        translated.messages[0]
+            .metadata
+            .insert("cache_control".into(), serde_json::json!("x"));
+        let mut source_with_meta = source.clone();
+        source_with_meta.messages[1]
+            .metadata
+            .insert("vendor_id".into(), serde_json::json!(1));
+        if let IrContentBlock::ToolUse { name, .. } = &mut translated.messages[1].content[0] {
+            *name = "web_search".into();
+        }
+        let log = audit_translation(&source_with_meta, &translated);
+        assert!(
+            log.iter()
+                .any(|e| e.kind == TranslationStepKind::ToolRenamed
+                    && e.detail == "tool 'search' renamed to 'web_search'"
+                    && e.field.as_deref() == Some("messages[1].content[0].name"))
+        );
+        let meta = log
+            .iter()
+            .find(|e| e.kind == TranslationStepKind::MetadataDropped)
+            .unwrap();
+        assert_eq!(meta.message_index, Some(1));
+        assert!(meta.detail.contains("vendor_id"));
+    }
+
+    #[test]
+    fn audit_records_dropped_messages() {
+        let source = multi_turn_conv();
+        let mut translated = multi_turn_conv();
+        translated.messages.truncate(2);
+        let log = audit_translation(&source, &translated);
+        assert_eq!(log[0].kind, TranslationStepKind::MessageDropped);
+        assert_eq!(log[0].detail, "2 message(s) dropped");
+    }
+
+    #[test]
+    fn translate_result_carries_log() {
+        let engine = TranslationEngine::with_defaults();
+        let result = engine
+            .translate(Dialect::OpenAi, Dialect::OpenAi, &thinking_conv())
+            .unwrap();
+        assert!(result.log.is_empty());
+        let result = engine
+            .translate(Dialect::Claude, Dialect::OpenAi, &thinking_conv())
+            .unwrap();
+        assert_eq!(
+            result.log,
+            audit_translation(&thinking_conv(), &result.conversation)
+        );
+    }
+
+    #[test]
+    fn audit_tools_records_tool_choice_changes() {
+        let tools = vec![IrToolDefinition {
+            name: "search".into(),
+            description: "Search".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let log = audit_tools(
+            Dialect::OpenAi,
+            Dialect::Claude,
+            &tools,
+            Some(&IrToolChoice::None),
+        );
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].kind, TranslationStepKind::FieldDropped);
+        assert_eq!(log[0].field.as_deref(), Some("tool_choice"));
+
+        let log = audit_tools(
+            Dialect::OpenAi,
+            Dialect::Gemini,
+            &tools,
+            Some(&IrToolChoice::Tool {
+                name: "search".into(),
+            }),
+        );
+        assert_eq!(log[0].kind, TranslationStepKind::FieldRenamed);
+        assert!(log[0].detail.contains("allowedFunctionNames"));
+
+        assert!(
+            audit_tools(
+                Dialect::OpenAi,
+                Dialect::OpenAi,
+                &tools,
+                Some(&IrToolChoice::None)
+            )
+            .is_empty()
+        );
+        assert!(
+            audit_tools(
+                Dialect::Claude,
+                Dialect::OpenAi,
+                &tools,
+                Some(&IrToolChoice::Required)
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn log_entry_serde_omits_missing_index() {
+        let entry = TranslationLogEntry::new(
+            TranslationStepKind::ContentDropped,
+            None,
+            "1 image block(s) dropped".into(),
+        );
+        let json = serde_json::to_value(&entry).unwrap();
+        assert_eq!(json["kind"], "content_dropped");
+        assert!(json.get("message_index").is_none());
+        assert!(json.get("field").is_none());
+    }
 }
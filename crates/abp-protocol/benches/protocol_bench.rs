@@ -23,6 +23,7 @@ fn event_envelope(i: usize) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
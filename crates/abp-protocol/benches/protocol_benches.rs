@@ -36,6 +36,7 @@ fn event_envelope() -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -22,6 +22,7 @@ use abp_core::{
 };
 
 use crate::Envelope;
+use crate::features::ProtocolFeatures;
 
 // ---------------------------------------------------------------------------
 // BuilderError
@@ -95,6 +96,7 @@ impl EnvelopeBuilder {
         EventBuilder {
             ref_id: None,
             event,
+            seq: None,
         }
     }
 
@@ -130,6 +132,7 @@ pub struct HelloBuilder {
     adapter_version: Option<String>,
     capabilities: Option<CapabilityManifest>,
     mode: Option<ExecutionMode>,
+    features: Option<ProtocolFeatures>,
 }
 
 impl HelloBuilder {
@@ -168,6 +171,13 @@ impl HelloBuilder {
         self
     }
 
+    /// Advertise a protocol feature-negotiation block. Omitted by default.
+    #[must_use]
+    pub fn features(mut self, features: ProtocolFeatures) -> Self {
+        self.features = Some(features);
+        self
+    }
+
     /// Build the `Hello` [`Envelope`].
     ///
     /// # Errors
@@ -187,6 +197,7 @@ impl HelloBuilder {
             },
             capabilities: self.capabilities.unwrap_or_default(),
             mode: self.mode.unwrap_or_default(),
+            features: self.features,
         })
     }
 }
@@ -239,6 +250,7 @@ impl RunBuilder {
 pub struct EventBuilder {
     ref_id: Option<String>,
     event: AgentEvent,
+    seq: Option<u64>,
 }
 
 impl EventBuilder {
@@ -249,6 +261,13 @@ impl EventBuilder {
         self
     }
 
+    /// Set the per-run sequence number used for de-duplication.
+    #[must_use]
+    pub fn seq(mut self, seq: u64) -> Self {
+        self.seq = Some(seq);
+        self
+    }
+
     /// Build the `Event` [`Envelope`].
     ///
     /// # Errors
@@ -260,6 +279,7 @@ impl EventBuilder {
         Ok(Envelope::Event {
             ref_id,
             event: self.event,
+            seq: self.seq,
         })
     }
 }
@@ -0,0 +1,239 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Idempotent ingestion of sequenced events.
+//!
+//! Sidecar delivery is at-least-once: after a transport hiccup a sidecar may
+//! re-send event envelopes the host already received. Sidecars that number
+//! their events set `seq` on each `event` envelope; [`EventDeduplicator`]
+//! keys events by `(run_id, seq)`, drops repeats, and reports gaps so the
+//! host can tell the caller that events were lost.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Envelope;
+
+/// Outcome of ingesting a single event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ingest {
+    /// First delivery of this event, or the event carries no `seq`.
+    Accept,
+    /// This `(run_id, seq)` was already ingested; drop the event.
+    Duplicate,
+    /// New event, but it skips past sequence numbers that have not arrived.
+    /// The event itself should still be delivered.
+    Gap {
+        /// The next sequence number that was expected.
+        expected: u64,
+        /// The sequence number that actually arrived.
+        got: u64,
+    },
+}
+
+impl Ingest {
+    /// Whether the event should be passed on to consumers.
+    #[must_use]
+    pub fn should_deliver(self) -> bool {
+        !matches!(self, Self::Duplicate)
+    }
+}
+
+/// Counters describing event delivery quality.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DedupStats {
+    /// Events accepted for delivery (including unsequenced ones).
+    pub accepted: u64,
+    /// Events dropped because their `(run_id, seq)` was already seen.
+    pub duplicates: u64,
+    /// Times an event arrived ahead of the expected sequence number.
+    pub gaps: u64,
+    /// Sequence numbers below the highest seen that never arrived.
+    pub missing: u64,
+    /// Events that carried no `seq` and could not be de-duplicated.
+    pub unsequenced: u64,
+}
+
+impl DedupStats {
+    /// Whether delivery was clean: no duplicates, gaps, or missing events.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.duplicates == 0 && self.gaps == 0 && self.missing == 0
+    }
+}
+
+/// Drops re-delivered events and detects gaps, keyed by `(run_id, seq)`.
+///
+/// # Examples
+///
+/// ```
+/// use abp_protocol::dedup::{EventDeduplicator, Ingest};
+///
+/// let mut dedup = EventDeduplicator::new();
+/// assert_eq!(dedup.ingest("run-1", Some(0)), Ingest::Accept);
+/// assert_eq!(dedup.ingest("run-1", Some(0)), Ingest::Duplicate);
+/// assert_eq!(dedup.ingest("run-1", Some(2)), Ingest::Gap { expected: 1, got: 2 });
+/// assert_eq!(dedup.stats().missing, 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EventDeduplicator {
+    seen: BTreeMap<String, BTreeSet<u64>>,
+    accepted: u64,
+    duplicates: u64,
+    gaps: u64,
+    unsequenced: u64,
+}
+
+impl EventDeduplicator {
+    /// Create an empty de-duplicator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an event for `run_id` with optional sequence number `seq`.
+    pub fn ingest(&mut self, run_id: &str, seq: Option<u64>) -> Ingest {
+        let Some(seq) = seq else {
+            self.accepted += 1;
+            self.unsequenced += 1;
+            return Ingest::Accept;
+        };
+
+        let seen = self.seen.entry(run_id.to_string()).or_default();
+        let expected = seen.last().map_or(0, |max| max + 1);
+        if !seen.insert(seq) {
+            self.duplicates += 1;
+            return Ingest::Duplicate;
+        }
+        self.accepted += 1;
+        if seq > expected {
+            self.gaps += 1;
+            Ingest::Gap { expected, got: seq }
+        } else {
+            Ingest::Accept
+        }
+    }
+
+    /// Record an envelope. Only `event` envelopes are subject to
+    /// de-duplication; every other variant is accepted unchanged.
+    pub fn ingest_envelope(&mut self, envelope: &Envelope) -> Ingest {
+        match envelope {
+            Envelope::Event { ref_id, seq, .. } => self.ingest(ref_id, *seq),
+            _ => Ingest::Accept,
+        }
+    }
+
+    /// Number of sequence numbers for `run_id` below the highest seen that
+    /// have not arrived.
+    #[must_use]
+    pub fn missing(&self, run_id: &str) -> u64 {
+        self.seen.get(run_id).map_or(0, missing_in)
+    }
+
+    /// Counters aggregated across all runs.
+    #[must_use]
+    pub fn stats(&self) -> DedupStats {
+        DedupStats {
+            accepted: self.accepted,
+            duplicates: self.duplicates,
+            gaps: self.gaps,
+            missing: self.seen.values().map(missing_in).sum(),
+            unsequenced: self.unsequenced,
+        }
+    }
+
+    /// Drop the sequence state for a finished run.
+    pub fn forget(&mut self, run_id: &str) {
+        self.seen.remove(run_id);
+    }
+}
+
+fn missing_in(seen: &BTreeSet<u64>) -> u64 {
+    seen.last().map_or(0, |max| max + 1 - seen.len() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_order_events_are_accepted() {
+        let mut d = EventDeduplicator::new();
+        for seq in 0..5 {
+            assert_eq!(d.ingest("r", Some(seq)), Ingest::Accept);
+        }
+        let stats = d.stats();
+        assert_eq!(stats.accepted, 5);
+        assert!(stats.is_clean());
+    }
+
+    #[test]
+    fn retried_events_are_dropped() {
+        let mut d = EventDeduplicator::new();
+        d.ingest("r", Some(0));
+        d.ingest("r", Some(1));
+        assert_eq!(d.ingest("r", Some(0)), Ingest::Duplicate);
+        assert_eq!(d.ingest("r", Some(1)), Ingest::Duplicate);
+        assert!(!Ingest::Duplicate.should_deliver());
+        assert_eq!(d.stats().duplicates, 2);
+        assert_eq!(d.stats().accepted, 2);
+    }
+
+    #[test]
+    fn sequences_are_scoped_per_run() {
+        let mut d = EventDeduplicator::new();
+        assert_eq!(d.ingest("a", Some(0)), Ingest::Accept);
+        assert_eq!(d.ingest("b", Some(0)), Ingest::Accept);
+        assert_eq!(d.stats().duplicates, 0);
+    }
+
+    #[test]
+    fn gap_is_reported_and_late_arrival_fills_it() {
+        let mut d = EventDeduplicator::new();
+        d.ingest("r", Some(0));
+        let gap = d.ingest("r", Some(3));
+        assert_eq!(
+            gap,
+            Ingest::Gap {
+                expected: 1,
+                got: 3
+            }
+        );
+        assert!(gap.should_deliver());
+        assert_eq!(d.missing("r"), 2);
+
+        assert_eq!(d.ingest("r", Some(1)), Ingest::Accept);
+        assert_eq!(d.missing("r"), 1);
+        assert_eq!(d.stats().gaps, 1);
+    }
+
+    #[test]
+    fn first_event_not_zero_is_a_gap() {
+        let mut d = EventDeduplicator::new();
+        assert_eq!(
+            d.ingest("r", Some(2)),
+            Ingest::Gap {
+                expected: 0,
+                got: 2
+            }
+        );
+        assert_eq!(d.missing("r"), 2);
+    }
+
+    #[test]
+    fn unsequenced_events_pass_through() {
+        let mut d = EventDeduplicator::new();
+        assert_eq!(d.ingest("r", None), Ingest::Accept);
+        assert_eq!(d.ingest("r", None), Ingest::Accept);
+        let stats = d.stats();
+        assert_eq!(stats.unsequenced, 2);
+        assert!(stats.is_clean());
+    }
+
+    #[test]
+    fn forget_resets_run_state() {
+        let mut d = EventDeduplicator::new();
+        d.ingest("r", Some(0));
+        d.forget("r");
+        assert_eq!(d.ingest("r", Some(0)), Ingest::Accept);
+    }
+}
@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Feature-flag negotiation for the sidecar handshake.
+//!
+//! A sidecar may attach a [`ProtocolFeatures`] block to its `hello`
+//! envelope. The host intersects it with its own features to obtain the
+//! common subset both peers will use. A sidecar that sends no block is
+//! assumed to speak [`ProtocolFeatures::baseline`].
+//!
+//! Because both sides agree on an explicit subset, a host and sidecar whose
+//! contract versions differ can still interoperate as long as the sidecar
+//! advertises its features — see [`negotiate_features`].
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::compress::CompressionAlgorithm;
+
+/// Event kinds (the `type` tag of `AgentEventKind`) defined by `abp/v0.1`.
+pub const BASELINE_EVENT_KINDS: &[&str] = &[
+    "run_started",
+    "run_completed",
+    "assistant_delta",
+    "assistant_message",
+    "tool_call",
+    "tool_result",
+    "file_changed",
+    "command_executed",
+    "warning",
+    "error",
+];
+
+/// Optional protocol features a peer supports.
+///
+/// # Examples
+///
+/// ```
+/// use abp_protocol::features::ProtocolFeatures;
+///
+/// let ours = ProtocolFeatures::current();
+/// let agreed = ours.intersect(&ProtocolFeatures::baseline());
+/// assert!(!agreed.heartbeat);
+/// assert!(agreed.supports_event_kind("assistant_delta"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ProtocolFeatures {
+    /// Event kinds the peer can emit and understand.
+    #[serde(default)]
+    pub event_kinds: BTreeSet<String>,
+    /// Frame compression algorithms, most preferred first. Empty means
+    /// uncompressed frames only.
+    #[serde(default)]
+    pub compression: Vec<CompressionAlgorithm>,
+    /// Ping/pong heartbeats are understood.
+    #[serde(default)]
+    pub heartbeat: bool,
+    /// Host-executed tool calls over a dedicated tool channel are understood.
+    #[serde(default)]
+    pub tool_channel: bool,
+}
+
+impl ProtocolFeatures {
+    /// Features a sidecar without a negotiation block is assumed to support:
+    /// the `abp/v0.1` event kinds and nothing else.
+    #[must_use]
+    pub fn baseline() -> Self {
+        Self {
+            event_kinds: BASELINE_EVENT_KINDS
+                .iter()
+                .map(|k| (*k).to_string())
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Features supported by this build of the protocol crate.
+    #[must_use]
+    pub fn current() -> Self {
+        Self {
+            compression: vec![CompressionAlgorithm::Zstd, CompressionAlgorithm::Gzip],
+            heartbeat: true,
+            tool_channel: true,
+            ..Self::baseline()
+        }
+    }
+
+    /// Whether `kind` is in the supported event-kind set.
+    #[must_use]
+    pub fn supports_event_kind(&self, kind: &str) -> bool {
+        self.event_kinds.contains(kind)
+    }
+
+    /// The first compression algorithm in this set, or
+    /// [`CompressionAlgorithm::None`] when frames stay uncompressed.
+    #[must_use]
+    pub fn preferred_compression(&self) -> CompressionAlgorithm {
+        self.compression
+            .first()
+            .copied()
+            .unwrap_or(CompressionAlgorithm::None)
+    }
+
+    /// The subset supported by both `self` and `other`.
+    ///
+    /// Compression keeps `self`'s preference order.
+    #[must_use]
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self {
+            event_kinds: self
+                .event_kinds
+                .intersection(&other.event_kinds)
+                .cloned()
+                .collect(),
+            compression: self
+                .compression
+                .iter()
+                .filter(|c| other.compression.contains(c))
+                .copied()
+                .collect(),
+            heartbeat: self.heartbeat && other.heartbeat,
+            tool_channel: self.tool_channel && other.tool_channel,
+        }
+    }
+}
+
+/// Agree on the features a host and sidecar will use.
+///
+/// `theirs` is the block from the sidecar's `hello`; `None` (an older
+/// sidecar) falls back to [`ProtocolFeatures::baseline`].
+///
+/// # Examples
+///
+/// ```
+/// use abp_protocol::features::{ProtocolFeatures, negotiate_features};
+///
+/// let agreed = negotiate_features(&ProtocolFeatures::current(), None);
+/// assert_eq!(agreed, ProtocolFeatures::baseline());
+/// ```
+#[must_use]
+pub fn negotiate_features(
+    ours: &ProtocolFeatures,
+    theirs: Option<&ProtocolFeatures>,
+) -> ProtocolFeatures {
+    match theirs {
+        Some(theirs) => ours.intersect(theirs),
+        None => ours.intersect(&ProtocolFeatures::baseline()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn baseline_matches_event_kind_tags() {
+        let event = abp_core::AgentEvent {
+            ts: chrono::Utc::now(),
+            kind: abp_core::AgentEventKind::AssistantDelta { text: "x".into() },
+            ext: None,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        let tag = json["type"].as_str().unwrap();
+        assert!(ProtocolFeatures::baseline().supports_event_kind(tag));
+        assert_eq!(BASELINE_EVENT_KINDS.len(), 10);
+    }
+
+    #[test]
+    fn current_is_superset_of_baseline() {
+        let current = ProtocolFeatures::current();
+        let baseline = ProtocolFeatures::baseline();
+        assert_eq!(current.intersect(&baseline), baseline);
+    }
+
+    #[test]
+    fn intersect_keeps_our_compression_order() {
+        let ours = ProtocolFeatures::current();
+        let theirs = ProtocolFeatures {
+            compression: vec![CompressionAlgorithm::Gzip, CompressionAlgorithm::Zstd],
+            ..ProtocolFeatures::baseline()
+        };
+        let agreed = ours.intersect(&theirs);
+        assert_eq!(agreed.preferred_compression(), CompressionAlgorithm::Zstd);
+        assert!(!agreed.heartbeat);
+    }
+
+    #[test]
+    fn unknown_event_kinds_are_dropped() {
+        let mut theirs = ProtocolFeatures::current();
+        theirs.event_kinds.insert("reasoning_delta".into());
+        theirs.event_kinds.remove("file_changed");
+        let agreed = negotiate_features(&ProtocolFeatures::current(), Some(&theirs));
+        assert!(!agreed.supports_event_kind("reasoning_delta"));
+        assert!(!agreed.supports_event_kind("file_changed"));
+        assert!(agreed.heartbeat && agreed.tool_channel);
+    }
+
+    #[test]
+    fn missing_block_falls_back_to_baseline() {
+        let agreed = negotiate_features(&ProtocolFeatures::current(), None);
+        assert_eq!(agreed.preferred_compression(), CompressionAlgorithm::None);
+        assert!(!agreed.tool_channel);
+    }
+
+    #[test]
+    fn serde_defaults_for_partial_block() {
+        let f: ProtocolFeatures = serde_json::from_str(r#"{"heartbeat":true}"#).unwrap();
+        assert!(f.heartbeat);
+        assert!(f.event_kinds.is_empty());
+        assert!(f.compression.is_empty());
+    }
+}
@@ -5,6 +5,7 @@
 //! `pong`. [`HeartbeatMonitor`] tracks the round-trip and declares a sidecar
 //! stalled when too many consecutive pings go unanswered.
 
+use abp_core::clock::{SharedClock, system_clock};
 use serde::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 
@@ -157,6 +158,7 @@ pub struct HeartbeatMonitor {
     pending_ping_seq: Option<u64>,
     total_pings: u64,
     total_pongs: u64,
+    clock: SharedClock,
 }
 
 impl HeartbeatMonitor {
@@ -173,9 +175,17 @@ impl HeartbeatMonitor {
             pending_ping_seq: None,
             total_pings: 0,
             total_pongs: 0,
+            clock: system_clock(),
         }
     }
 
+    /// Read time from `clock` instead of the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Current heartbeat state.
     #[must_use]
     pub fn state(&self) -> &HeartbeatState {
@@ -193,13 +203,10 @@ impl HeartbeatMonitor {
         let seq = self.next_seq;
         self.next_seq += 1;
         self.total_pings += 1;
-        self.last_ping_at = Some(Instant::now());
+        self.last_ping_at = Some(self.clock.instant());
         self.pending_ping_seq = Some(seq);
 
-        let timestamp_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as u64;
+        let timestamp_ms = u64::try_from(self.clock.now().timestamp_millis()).unwrap_or_default();
 
         Ping { seq, timestamp_ms }
     }
@@ -209,7 +216,7 @@ impl HeartbeatMonitor {
         self.total_pongs += 1;
         if self.pending_ping_seq == Some(seq) {
             self.consecutive_missed = 0;
-            self.last_pong_at = Some(Instant::now());
+            self.last_pong_at = Some(self.clock.instant());
             self.pending_ping_seq = None;
             self.state = HeartbeatState::Alive;
         }
@@ -263,7 +270,7 @@ impl HeartbeatMonitor {
     /// Time since the last successful pong, if any.
     #[must_use]
     pub fn time_since_last_pong(&self) -> Option<Duration> {
-        self.last_pong_at.map(|t| t.elapsed())
+        self.last_pong_at.map(|t| self.since(t))
     }
 
     /// Returns `true` if the pending ping has timed out.
@@ -273,7 +280,7 @@ impl HeartbeatMonitor {
             return false;
         }
         self.last_ping_at
-            .is_some_and(|t| t.elapsed() >= self.config.timeout())
+            .is_some_and(|t| self.since(t) >= self.config.timeout())
     }
 
     /// Returns `true` if enough time has elapsed since the last ping to send another.
@@ -281,7 +288,7 @@ impl HeartbeatMonitor {
     pub fn should_ping(&self) -> bool {
         match self.last_ping_at {
             None => true,
-            Some(t) => t.elapsed() >= self.config.interval(),
+            Some(t) => self.since(t) >= self.config.interval(),
         }
     }
 
@@ -296,6 +303,10 @@ impl HeartbeatMonitor {
         self.total_pings = 0;
         self.total_pongs = 0;
     }
+
+    fn since(&self, t: Instant) -> Duration {
+        self.clock.instant().saturating_duration_since(t)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -494,4 +505,26 @@ mod tests {
         let mon = HeartbeatMonitor::new(test_config());
         assert!(!mon.is_pending_timeout());
     }
+    #[test]
+    fn timeouts_follow_injected_clock() {
+        let clock = std::sync::Arc::new(abp_core::clock::ManualClock::default());
+        let mut mon = HeartbeatMonitor::new(test_config()).with_clock(clock.clone());
+
+        let ping = mon.next_ping();
+        assert_eq!(ping.timestamp_ms, 0);
+        assert!(!mon.should_ping());
+        assert!(!mon.is_pending_timeout());
+
+        clock.advance(Duration::from_millis(50));
+        assert!(mon.is_pending_timeout());
+        mon.record_miss();
+
+        clock.advance(Duration::from_millis(50));
+        assert!(mon.should_ping());
+        let ping = mon.next_ping();
+        assert_eq!(ping.timestamp_ms, 100);
+        mon.record_pong(ping.seq);
+        clock.advance(Duration::from_millis(7));
+        assert_eq!(mon.time_since_last_pong(), Some(Duration::from_millis(7)));
+    }
 }
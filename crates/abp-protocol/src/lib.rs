@@ -42,9 +42,12 @@ pub mod builder;
 pub mod capability_advertisement;
 pub mod codec;
 pub mod compress;
+pub mod dedup;
+pub mod features;
 pub mod graceful_shutdown;
 pub mod heartbeat;
 pub mod router;
+pub mod secure;
 pub mod stream;
 pub mod validate;
 pub mod version;
@@ -105,6 +108,10 @@ pub enum Envelope {
         /// Execution mode this sidecar will use. Defaults to "mapped" if absent.
         #[serde(default)]
         mode: ExecutionMode,
+        /// Optional protocol features the sidecar supports. Absent for older
+        /// sidecars, which are assumed to speak the baseline feature set.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        features: Option<features::ProtocolFeatures>,
     },
 
     /// Control-plane request to start executing a work order.
@@ -121,6 +128,11 @@ pub enum Envelope {
         ref_id: String,
         /// The agent event payload.
         event: AgentEvent,
+        /// Per-run sequence number, starting at 0. Lets the host drop
+        /// events re-sent after a transport retry and notice lost ones.
+        /// Absent for sidecars that do not number their events.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seq: Option<u64>,
     },
 
     /// Terminal message carrying the execution receipt.
@@ -162,7 +174,19 @@ impl Envelope {
             backend,
             capabilities,
             mode,
+            features: None,
+        }
+    }
+
+    /// Attach a feature-negotiation block to a `Hello` envelope.
+    ///
+    /// Has no effect on other envelope types.
+    #[must_use]
+    pub fn with_features(mut self, protocol_features: features::ProtocolFeatures) -> Self {
+        if let Self::Hello { features, .. } = &mut self {
+            *features = Some(protocol_features);
         }
+        self
     }
 
     /// Create a `Fatal` envelope with an [`ErrorCode`](abp_error::ErrorCode).
@@ -285,6 +309,36 @@ impl JsonlCodec {
         Ok(s)
     }
 
+    /// Serialize an [`Envelope`] to its canonical form (sorted keys, RFC 8785
+    /// number format), newline-terminated.
+    ///
+    /// Use this when an envelope is hashed or signed; the output is stable
+    /// across serializer versions and languages, and still decodes with
+    /// [`JsonlCodec::decode`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use abp_protocol::{Envelope, JsonlCodec};
+    /// let envelope = Envelope::Fatal {
+    ///     ref_id: None,
+    ///     error: "boom".into(),
+    ///     error_code: None,
+    /// };
+    /// let json = JsonlCodec::encode_canonical(&envelope).unwrap();
+    /// assert_eq!(json, "{\"error\":\"boom\",\"ref_id\":null,\"t\":\"fatal\"}\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProtocolError::Json`] if the envelope cannot be serialized.
+    pub fn encode_canonical(msg: &Envelope) -> Result<String, ProtocolError> {
+        let v = serde_json::to_value(msg)?;
+        let mut s = abp_core::canonical::value_to_canonical_string(&v);
+        s.push('\n');
+        Ok(s)
+    }
+
     /// Deserialize a single JSON line into an [`Envelope`].
     ///
     /// # Examples
@@ -376,7 +430,8 @@ impl JsonlCodec {
 
 /// Parse a version string of the form `"abp/vMAJOR.MINOR"` into `(MAJOR, MINOR)`.
 ///
-/// Returns `None` if the string does not match the expected format.
+/// Returns `None` if the string does not match the expected format. Same
+/// parser as [`abp_core::compat::parse_version`].
 ///
 /// # Examples
 ///
@@ -389,18 +444,15 @@ impl JsonlCodec {
 /// ```
 #[must_use]
 pub fn parse_version(version: &str) -> Option<(u32, u32)> {
-    let rest = version.strip_prefix("abp/v")?;
-    let (major_str, minor_str) = rest.split_once('.')?;
-    let major = major_str.parse::<u32>().ok()?;
-    let minor = minor_str.parse::<u32>().ok()?;
-    Some((major, minor))
+    abp_core::compat::parse_version(version)
 }
 
 /// Two versions are compatible when they share the same major component.
 ///
 /// For example `"abp/v0.1"` and `"abp/v0.2"` are compatible, but
 /// `"abp/v1.0"` and `"abp/v0.1"` are not.  Returns `false` if either
-/// string cannot be parsed.
+/// string cannot be parsed. This is [`abp_core::compat::check`] reduced
+/// to a yes or no.
 ///
 /// # Examples
 ///
@@ -412,10 +464,7 @@ pub fn parse_version(version: &str) -> Option<(u32, u32)> {
 /// ```
 #[must_use]
 pub fn is_compatible_version(their_version: &str, our_version: &str) -> bool {
-    match (parse_version(their_version), parse_version(our_version)) {
-        (Some((their_major, _)), Some((our_major, _))) => their_major == our_major,
-        _ => false,
-    }
+    abp_core::compat::check(our_version, their_version).is_compatible()
 }
 
 /// Re-export of the value-based [`sidecar_kit::Frame`] for raw protocol work.
@@ -0,0 +1,445 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Authenticated encryption for the sidecar JSONL channel.
+//!
+//! For sidecars that run across a trust boundary (another user, container,
+//! or host), the control plane and sidecar can wrap every JSONL line in
+//! ChaCha20-Poly1305. Both sides hold a pre-shared [`ChannelKey`]. Each
+//! connection opens with an ephemeral X25519 exchange ([`SecureHandshake`])
+//! whose shared secret is mixed with the key through HKDF-SHA256, so a peer
+//! without the key can neither read nor forge frames, and a leaked key does
+//! not expose earlier sessions.
+//!
+//! After the handshake every line travels as base64 ciphertext. Nonces are
+//! per-direction counters, so a dropped, replayed, or reordered frame fails
+//! to open.
+
+use abp_error::{AbpError, ErrorCode};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ring::aead::{Aad, CHACHA20_POLY1305, LessSafeKey, Nonce, UnboundKey};
+use ring::agreement::{self, EphemeralPrivateKey, UnparsedPublicKey, X25519};
+use ring::hkdf::{HKDF_SHA256, Salt};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Environment variable the host sets on a secure sidecar to hand it the
+/// hex-encoded [`ChannelKey`].
+pub const CHANNEL_KEY_ENV: &str = "ABP_SIDECAR_CHANNEL_KEY";
+
+/// Handshake version string; bumped if the key schedule or framing changes.
+pub const SECURE_VERSION: &str = "abp-secure/v1";
+
+/// Errors from the secure channel.
+#[derive(Debug, thiserror::Error)]
+pub enum SecureError {
+    /// A channel key was not 32 bytes of hex.
+    #[error("invalid channel key: {0}")]
+    InvalidKey(String),
+    /// The peer's handshake line was malformed or used another version.
+    #[error("secure handshake failed: {0}")]
+    Handshake(String),
+    /// A frame could not be decoded or failed authentication.
+    #[error("secure frame rejected: {0}")]
+    Frame(String),
+    /// The system random number generator or key agreement failed.
+    #[error("secure channel crypto failure")]
+    Crypto,
+}
+
+impl From<ring::error::Unspecified> for SecureError {
+    fn from(_: ring::error::Unspecified) -> Self {
+        Self::Crypto
+    }
+}
+
+impl From<SecureError> for crate::ProtocolError {
+    fn from(err: SecureError) -> Self {
+        let code = match err {
+            SecureError::Frame(_) => ErrorCode::ProtocolInvalidEnvelope,
+            _ => ErrorCode::ProtocolHandshakeFailed,
+        };
+        Self::Abp(AbpError::new(code, err.to_string()))
+    }
+}
+
+/// Result alias for secure channel operations.
+pub type Result<T> = std::result::Result<T, SecureError>;
+
+/// A 256-bit key shared out of band between the control plane and a sidecar.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ChannelKey([u8; 32]);
+
+impl ChannelKey {
+    /// Generate a random key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SecureError::Crypto`] if the system RNG fails.
+    pub fn generate() -> Result<Self> {
+        let mut bytes = [0u8; 32];
+        SystemRandom::new().fill(&mut bytes)?;
+        Ok(Self(bytes))
+    }
+
+    /// Wrap raw key bytes.
+    #[must_use]
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Parse a key from 64 hex characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SecureError::InvalidKey`] for anything else.
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        let hex = hex.trim();
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(SecureError::InvalidKey("expected 64 hex characters".into()));
+        }
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)
+                .map_err(|e| SecureError::InvalidKey(e.to_string()))?;
+        }
+        Ok(Self(bytes))
+    }
+
+    /// Read the key from [`CHANNEL_KEY_ENV`], if set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SecureError::InvalidKey`] if the variable is set but is not
+    /// a valid key.
+    pub fn from_env() -> Result<Option<Self>> {
+        match std::env::var(CHANNEL_KEY_ENV) {
+            Ok(hex) => Self::from_hex(&hex).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Encode the key as 64 lowercase hex characters.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+impl fmt::Debug for ChannelKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ChannelKey(<redacted>)")
+    }
+}
+
+/// Which end of the channel a [`SecureHandshake`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelRole {
+    /// The control plane, writing to the sidecar's stdin.
+    Host,
+    /// The sidecar, writing to its stdout.
+    Sidecar,
+}
+
+/// Wire form of a handshake line.
+#[derive(Debug, Serialize, Deserialize)]
+struct HandshakeLine {
+    secure: String,
+    public_key: String,
+}
+
+/// One side of the key exchange that opens a secure channel.
+///
+/// Each side sends [`line`](Self::line) before anything else, reads the
+/// peer's handshake line, and passes it to [`complete`](Self::complete).
+///
+/// # Examples
+///
+/// ```
+/// use abp_protocol::secure::{ChannelKey, ChannelRole, SecureHandshake};
+///
+/// let key = ChannelKey::generate().unwrap();
+/// let host = SecureHandshake::new(&key, ChannelRole::Host).unwrap();
+/// let sidecar = SecureHandshake::new(&key, ChannelRole::Sidecar).unwrap();
+/// let (host_line, sidecar_line) = (host.line(), sidecar.line());
+///
+/// let (mut host_tx, _host_rx) = host.complete(&sidecar_line).unwrap().split();
+/// let (_sidecar_tx, mut sidecar_rx) = sidecar.complete(&host_line).unwrap().split();
+///
+/// let frame = host_tx.seal("{\"t\":\"run\"}\n").unwrap();
+/// assert_eq!(sidecar_rx.open(&frame).unwrap(), "{\"t\":\"run\"}");
+/// ```
+pub struct SecureHandshake {
+    key: ChannelKey,
+    role: ChannelRole,
+    private: EphemeralPrivateKey,
+    public: Vec<u8>,
+}
+
+impl fmt::Debug for SecureHandshake {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecureHandshake")
+            .field("role", &self.role)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SecureHandshake {
+    /// Start a handshake with a fresh ephemeral key pair.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SecureError::Crypto`] if key generation fails.
+    pub fn new(key: &ChannelKey, role: ChannelRole) -> Result<Self> {
+        let private = EphemeralPrivateKey::generate(&X25519, &SystemRandom::new())?;
+        let public = private.compute_public_key()?.as_ref().to_vec();
+        Ok(Self {
+            key: key.clone(),
+            role,
+            private,
+            public,
+        })
+    }
+
+    /// Newline-terminated handshake line to send to the peer.
+    #[must_use]
+    pub fn line(&self) -> String {
+        let line = HandshakeLine {
+            secure: SECURE_VERSION.to_string(),
+            public_key: BASE64.encode(&self.public),
+        };
+        let mut s = serde_json::to_string(&line).unwrap_or_default();
+        s.push('\n');
+        s
+    }
+
+    /// Finish the exchange with the peer's handshake line.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SecureError::Handshake`] if the line is not a handshake for
+    /// [`SECURE_VERSION`], or [`SecureError::Crypto`] if key agreement fails.
+    pub fn complete(self, peer_line: &str) -> Result<SecureChannel> {
+        let peer: HandshakeLine = serde_json::from_str(peer_line.trim())
+            .map_err(|e| SecureError::Handshake(format!("expected handshake line: {e}")))?;
+        if peer.secure != SECURE_VERSION {
+            return Err(SecureError::Handshake(format!(
+                "unsupported version '{}', expected '{SECURE_VERSION}'",
+                peer.secure
+            )));
+        }
+        let peer_public = BASE64
+            .decode(&peer.public_key)
+            .map_err(|e| SecureError::Handshake(format!("bad public key: {e}")))?;
+
+        let (host_public, sidecar_public) = match self.role {
+            ChannelRole::Host => (self.public.as_slice(), peer_public.as_slice()),
+            ChannelRole::Sidecar => (peer_public.as_slice(), self.public.as_slice()),
+        };
+        let salt = Salt::new(HKDF_SHA256, &self.key.0);
+        let (to_sidecar, to_host) = agreement::agree_ephemeral(
+            self.private,
+            &UnparsedPublicKey::new(&X25519, &peer_public),
+            |shared| {
+                let prk = salt.extract(shared);
+                let derive = |label: &[u8]| -> Result<LessSafeKey> {
+                    let info = [label, host_public, sidecar_public];
+                    let okm = prk.expand(&info, &CHACHA20_POLY1305)?;
+                    Ok(LessSafeKey::new(UnboundKey::from(okm)))
+                };
+                Ok::<_, SecureError>((derive(b"abp host->sidecar")?, derive(b"abp sidecar->host")?))
+            },
+        )??;
+
+        let (send, recv) = match self.role {
+            ChannelRole::Host => (to_sidecar, to_host),
+            ChannelRole::Sidecar => (to_host, to_sidecar),
+        };
+        Ok(SecureChannel {
+            sealer: Sealer {
+                key: send,
+                counter: 0,
+            },
+            opener: Opener {
+                key: recv,
+                counter: 0,
+            },
+        })
+    }
+}
+
+/// An established secure channel: one key per direction.
+#[derive(Debug)]
+pub struct SecureChannel {
+    sealer: Sealer,
+    opener: Opener,
+}
+
+impl SecureChannel {
+    /// Split into the sending and receiving halves.
+    #[must_use]
+    pub fn split(self) -> (Sealer, Opener) {
+        (self.sealer, self.opener)
+    }
+}
+
+/// Encrypts outgoing lines.
+pub struct Sealer {
+    key: LessSafeKey,
+    counter: u64,
+}
+
+impl fmt::Debug for Sealer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sealer")
+            .field("counter", &self.counter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Sealer {
+    /// Encrypt one JSONL line (with or without its trailing newline) into a
+    /// newline-terminated frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SecureError::Crypto`] if encryption fails or the nonce
+    /// counter is exhausted.
+    pub fn seal(&mut self, line: &str) -> Result<String> {
+        let nonce = next_nonce(&mut self.counter)?;
+        let mut buf = line.trim_end_matches('\n').as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(nonce, Aad::from(SECURE_VERSION), &mut buf)?;
+        let mut frame = BASE64.encode(&buf);
+        frame.push('\n');
+        Ok(frame)
+    }
+}
+
+/// Decrypts incoming frames.
+pub struct Opener {
+    key: LessSafeKey,
+    counter: u64,
+}
+
+impl fmt::Debug for Opener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Opener")
+            .field("counter", &self.counter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Opener {
+    /// Decrypt one frame back into its JSONL line (without the newline).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SecureError::Frame`] if the frame is not base64, was
+    /// tampered with, or arrived out of order.
+    pub fn open(&mut self, frame: &str) -> Result<String> {
+        let mut buf = BASE64
+            .decode(frame.trim())
+            .map_err(|e| SecureError::Frame(format!("not base64: {e}")))?;
+        let nonce = next_nonce(&mut self.counter)?;
+        let plain = self
+            .key
+            .open_in_place(nonce, Aad::from(SECURE_VERSION), &mut buf)
+            .map_err(|_| SecureError::Frame("authentication failed".into()))?;
+        String::from_utf8(plain.to_vec()).map_err(|e| SecureError::Frame(e.to_string()))
+    }
+}
+
+/// Nonce for the current counter value, advancing the counter.
+fn next_nonce(counter: &mut u64) -> Result<Nonce> {
+    let mut bytes = [0u8; 12];
+    bytes[4..].copy_from_slice(&counter.to_be_bytes());
+    *counter = counter.checked_add(1).ok_or(SecureError::Crypto)?;
+    Ok(Nonce::assume_unique_for_key(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(host_key: &ChannelKey, sidecar_key: &ChannelKey) -> (SecureChannel, SecureChannel) {
+        let host = SecureHandshake::new(host_key, ChannelRole::Host).unwrap();
+        let sidecar = SecureHandshake::new(sidecar_key, ChannelRole::Sidecar).unwrap();
+        let (host_line, sidecar_line) = (host.line(), sidecar.line());
+        (
+            host.complete(&sidecar_line).unwrap(),
+            sidecar.complete(&host_line).unwrap(),
+        )
+    }
+
+    #[test]
+    fn key_hex_round_trips() {
+        let key = ChannelKey::generate().unwrap();
+        assert_eq!(ChannelKey::from_hex(&key.to_hex()).unwrap(), key);
+        assert!(ChannelKey::from_hex("abcd").is_err());
+        assert!(ChannelKey::from_hex(&"zz".repeat(32)).is_err());
+        assert_eq!(format!("{key:?}"), "ChannelKey(<redacted>)");
+    }
+
+    #[test]
+    fn both_directions_round_trip() {
+        let key = ChannelKey::generate().unwrap();
+        let (host, sidecar) = pair(&key, &key);
+        let (mut host_tx, mut host_rx) = host.split();
+        let (mut sidecar_tx, mut sidecar_rx) = sidecar.split();
+
+        let frame = host_tx.seal("{\"t\":\"run\"}\n").unwrap();
+        assert!(frame.ends_with('\n'));
+        assert!(!frame.contains("run"));
+        assert_eq!(sidecar_rx.open(&frame).unwrap(), "{\"t\":\"run\"}");
+
+        let frame = sidecar_tx.seal("{\"t\":\"final\"}").unwrap();
+        assert_eq!(host_rx.open(&frame).unwrap(), "{\"t\":\"final\"}");
+    }
+
+    #[test]
+    fn mismatched_keys_fail_to_open() {
+        let (host, sidecar) = pair(
+            &ChannelKey::generate().unwrap(),
+            &ChannelKey::generate().unwrap(),
+        );
+        let (mut host_tx, _) = host.split();
+        let (_, mut sidecar_rx) = sidecar.split();
+        let frame = host_tx.seal("{}").unwrap();
+        assert!(matches!(
+            sidecar_rx.open(&frame),
+            Err(SecureError::Frame(_))
+        ));
+    }
+
+    #[test]
+    fn replayed_and_tampered_frames_are_rejected() {
+        let key = ChannelKey::generate().unwrap();
+        let (host, sidecar) = pair(&key, &key);
+        let (mut host_tx, _) = host.split();
+        let (_, mut sidecar_rx) = sidecar.split();
+
+        let first = host_tx.seal("{\"n\":1}").unwrap();
+        sidecar_rx.open(&first).unwrap();
+        assert!(sidecar_rx.open(&first).is_err());
+
+        let second = host_tx.seal("{\"n\":2}").unwrap();
+        let mut bytes = BASE64.decode(second.trim()).unwrap();
+        bytes[0] ^= 1;
+        assert!(sidecar_rx.open(&BASE64.encode(bytes)).is_err());
+    }
+
+    #[test]
+    fn non_handshake_line_is_rejected() {
+        let key = ChannelKey::generate().unwrap();
+        let host = SecureHandshake::new(&key, ChannelRole::Host).unwrap();
+        let err = host.complete(r#"{"t":"hello"}"#).unwrap_err();
+        assert!(matches!(err, SecureError::Handshake(_)));
+
+        let host = SecureHandshake::new(&key, ChannelRole::Host).unwrap();
+        let err = host
+            .complete(r#"{"secure":"abp-secure/v0","public_key":""}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("unsupported version"));
+    }
+}
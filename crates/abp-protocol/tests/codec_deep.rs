@@ -98,6 +98,7 @@ fn make_event(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantMessage { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -344,7 +345,7 @@ fn roundtrip_event() {
     let line = JsonlCodec::encode(&original).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             match &event.kind {
                 AgentEventKind::AssistantMessage { text } => assert_eq!(text, "hello world"),
@@ -923,7 +924,7 @@ fn utf8_event_with_unicode() {
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-日本");
             match &event.kind {
                 AgentEventKind::AssistantMessage { text } => {
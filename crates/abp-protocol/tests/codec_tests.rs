@@ -348,3 +348,21 @@ fn streaming_utf8_content() {
         matches!(&decoded[1], Envelope::Fatal { error, ref_id, .. } if error == "中文 العربية" && ref_id.as_deref() == Some("ü-ref"))
     );
 }
+
+// ── Canonical encoding ───────────────────────────────────────────────────
+
+#[test]
+fn canonical_encoding_roundtrips_and_is_stable() {
+    let wo = WorkOrderBuilder::new("canonical").build();
+    let env = Envelope::Run {
+        id: "run-1".into(),
+        work_order: wo,
+    };
+    let a = JsonlCodec::encode_canonical(&env).unwrap();
+    assert!(a.ends_with('\n'));
+    assert!(a.starts_with("{\"id\":\"run-1\",\"t\":\"run\",\"work_order\":{"));
+
+    let decoded = JsonlCodec::decode(a.trim_end()).unwrap();
+    let b = JsonlCodec::encode_canonical(&decoded).unwrap();
+    assert_eq!(a, b);
+}
@@ -130,6 +130,7 @@ fn hello_roundtrip_preserves_all_fields() {
             backend,
             capabilities,
             mode,
+            ..
         } => {
             assert_eq!(contract_version, CONTRACT_VERSION);
             assert_eq!(backend.id, "deep");
@@ -341,6 +342,9 @@ fn run_work_order_with_config() {
         },
         max_budget_usd: Some(1.5),
         max_turns: Some(10),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let env = Envelope::Run {
         id: "cfg".into(),
@@ -385,6 +389,7 @@ fn event_tag_is_event() {
         event: mk_event(AgentEventKind::RunStarted {
             message: "go".into(),
         }),
+        seq: None,
     };
     let v = serde_json::to_value(&env).unwrap();
     assert_eq!(v["t"], "event");
@@ -397,9 +402,10 @@ fn event_run_started_roundtrip() {
         event: mk_event(AgentEventKind::RunStarted {
             message: "starting".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "rs");
             match event.kind {
                 AgentEventKind::RunStarted { message } => assert_eq!(message, "starting"),
@@ -417,6 +423,7 @@ fn event_run_completed_roundtrip() {
         event: mk_event(AgentEventKind::RunCompleted {
             message: "done".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -434,6 +441,7 @@ fn event_assistant_delta_roundtrip() {
         event: mk_event(AgentEventKind::AssistantDelta {
             text: "token".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -451,6 +459,7 @@ fn event_assistant_message_roundtrip() {
         event: mk_event(AgentEventKind::AssistantMessage {
             text: "Hello, world!".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -471,6 +480,7 @@ fn event_tool_call_roundtrip() {
             parent_tool_use_id: None,
             input: serde_json::json!({"cmd": "ls -la"}),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -501,6 +511,7 @@ fn event_tool_call_with_parent_id() {
             parent_tool_use_id: Some("tu-1".into()),
             input: serde_json::json!({"path": "main.rs"}),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -525,6 +536,7 @@ fn event_tool_result_roundtrip() {
             output: serde_json::json!("file.txt\ndir/"),
             is_error: false,
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -554,6 +566,7 @@ fn event_tool_result_error_flag() {
             output: serde_json::json!("permission denied"),
             is_error: true,
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -572,6 +585,7 @@ fn event_file_changed_roundtrip() {
             path: "src/lib.rs".into(),
             summary: "added tests".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -594,6 +608,7 @@ fn event_command_executed_roundtrip() {
             exit_code: Some(0),
             output_preview: Some("42 passed".into()),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -621,6 +636,7 @@ fn event_command_executed_no_exit_code() {
             exit_code: None,
             output_preview: None,
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -645,6 +661,7 @@ fn event_warning_roundtrip() {
         event: mk_event(AgentEventKind::Warning {
             message: "budget low".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -663,6 +680,7 @@ fn event_error_kind_roundtrip() {
             message: "oops".into(),
             error_code: None,
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -687,6 +705,7 @@ fn event_error_kind_with_code() {
             message: "bad".into(),
             error_code: Some(abp_error::ErrorCode::Internal),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -704,6 +723,7 @@ fn event_ref_id_preserved_across_roundtrip() {
     let env = Envelope::Event {
         ref_id: "correlation-42".into(),
         event: mk_event(AgentEventKind::AssistantDelta { text: "x".into() }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { ref_id, .. } => assert_eq!(ref_id, "correlation-42"),
@@ -722,6 +742,7 @@ fn event_with_ext_field() {
             kind: AgentEventKind::AssistantDelta { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -737,6 +758,7 @@ fn event_with_ext_none_omitted() {
     let env = Envelope::Event {
         ref_id: "no-ext".into(),
         event: mk_event(AgentEventKind::AssistantDelta { text: "a".into() }),
+        seq: None,
     };
     let v = serde_json::to_value(&env).unwrap();
     assert!(v["event"].get("raw_message").is_none());
@@ -1018,6 +1040,7 @@ fn each_variant_has_distinct_tag() {
             event: mk_event(AgentEventKind::Warning {
                 message: "w".into(),
             }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r".into(),
@@ -1044,6 +1067,7 @@ fn inner_event_uses_type_not_t() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event: mk_event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+        seq: None,
     };
     let v = serde_json::to_value(&env).unwrap();
     assert_eq!(v["event"]["type"], "assistant_message");
@@ -1064,6 +1088,7 @@ fn ref_id_matches_run_id_in_event() {
     let event_env = Envelope::Event {
         ref_id: run_id.into(),
         event: mk_event(AgentEventKind::AssistantDelta { text: "t".into() }),
+        seq: None,
     };
     let rv = serde_json::to_value(&run_env).unwrap();
     let ev = serde_json::to_value(&event_env).unwrap();
@@ -1307,6 +1332,7 @@ fn large_assistant_message_roundtrip() {
         event: mk_event(AgentEventKind::AssistantMessage {
             text: large_text.clone(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -1328,6 +1354,7 @@ fn large_tool_result_roundtrip() {
             output: big_output.clone(),
             is_error: false,
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -1437,6 +1464,7 @@ fn jsonl_stream_multiple_envelopes() {
             event: mk_event(AgentEventKind::Warning {
                 message: "w".into(),
             }),
+            seq: None,
         },
         Envelope::Fatal {
             ref_id: None,
@@ -1494,12 +1522,14 @@ fn jsonl_stream_full_session_simulation() {
             event: mk_event(AgentEventKind::RunStarted {
                 message: "starting".into(),
             }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
             event: mk_event(AgentEventKind::AssistantDelta {
                 text: "Hello".into(),
             }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
@@ -1509,6 +1539,7 @@ fn jsonl_stream_full_session_simulation() {
                 parent_tool_use_id: None,
                 input: serde_json::json!({"cmd": "echo hi"}),
             }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
@@ -1518,6 +1549,7 @@ fn jsonl_stream_full_session_simulation() {
                 output: serde_json::json!("hi"),
                 is_error: false,
             }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
@@ -1525,12 +1557,14 @@ fn jsonl_stream_full_session_simulation() {
                 path: "out.txt".into(),
                 summary: "created".into(),
             }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
             event: mk_event(AgentEventKind::RunCompleted {
                 message: "done".into(),
             }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: run_id.into(),
@@ -1724,7 +1758,7 @@ fn wire_hello_from_fixed_json() {
 fn wire_event_assistant_delta_from_fixed_json() {
     let json = r#"{"t":"event","ref_id":"r1","event":{"ts":"2025-01-01T00:00:00Z","type":"assistant_delta","text":"hi"}}"#;
     match serde_json::from_str::<Envelope>(json).unwrap() {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "r1");
             match event.kind {
                 AgentEventKind::AssistantDelta { text } => assert_eq!(text, "hi"),
@@ -1764,6 +1798,7 @@ fn all_variants_roundtrip_via_jsonl_codec() {
             event: mk_event(AgentEventKind::AssistantMessage {
                 text: "hello".into(),
             }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r1".into(),
@@ -1828,6 +1863,7 @@ fn all_event_kinds_roundtrip() {
         let env = Envelope::Event {
             ref_id: "r".into(),
             event: mk_event(kind),
+            seq: None,
         };
         let encoded = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(encoded.trim_end()).unwrap();
@@ -1862,6 +1898,7 @@ fn event_with_unicode_text() {
         event: mk_event(AgentEventKind::AssistantMessage {
             text: "こんにちは 🌍 Ñoño café".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -1883,6 +1920,7 @@ fn event_empty_text_roundtrip() {
         event: mk_event(AgentEventKind::AssistantDelta {
             text: String::new(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -1944,6 +1982,7 @@ fn event_tool_call_empty_input() {
             parent_tool_use_id: None,
             input: serde_json::json!({}),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -1966,6 +2005,7 @@ fn event_tool_call_null_input() {
             parent_tool_use_id: None,
             input: serde_json::Value::Null,
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -2094,6 +2134,7 @@ fn event_type_tag_uses_snake_case() {
         let env = Envelope::Event {
             ref_id: "r".into(),
             event: mk_event(kind),
+            seq: None,
         };
         let v = serde_json::to_value(&env).unwrap();
         assert_eq!(
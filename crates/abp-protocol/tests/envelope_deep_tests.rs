@@ -152,6 +152,7 @@ fn construct_event_variant() {
         event: mk_event(AgentEventKind::RunStarted {
             message: "starting".into(),
         }),
+        seq: None,
     };
     assert!(matches!(env, Envelope::Event { .. }));
 }
@@ -227,6 +228,7 @@ fn tag_field_is_t_not_type_for_event() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event: mk_event(AgentEventKind::AssistantDelta { text: "tok".into() }),
+        seq: None,
     };
     let v = serde_json::to_value(&env).unwrap();
     assert_eq!(v["t"], "event");
@@ -269,6 +271,7 @@ fn all_tag_values_are_snake_case() {
             event: mk_event(AgentEventKind::RunStarted {
                 message: "go".into(),
             }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r".into(),
@@ -330,7 +333,7 @@ fn parse_fatal_from_json_string() {
 fn parse_event_assistant_delta_from_json_string() {
     let json = r#"{"t":"event","ref_id":"r1","event":{"ts":"2025-01-01T00:00:00Z","type":"assistant_delta","text":"hello"}}"#;
     let env = JsonlCodec::decode(json).unwrap();
-    if let Envelope::Event { ref_id, event } = env {
+    if let Envelope::Event { ref_id, event, .. } = env {
         assert_eq!(ref_id, "r1");
         if let AgentEventKind::AssistantDelta { text } = event.kind {
             assert_eq!(text, "hello");
@@ -393,9 +396,10 @@ fn roundtrip_event_preserves_tool_call_details() {
             parent_tool_use_id: Some("tu-98".into()),
             input: serde_json::json!({"path": "/etc/hosts"}),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-rt");
             if let AgentEventKind::ToolCall {
                 tool_name,
@@ -577,9 +581,10 @@ fn event_run_started_roundtrip() {
         event: mk_event(AgentEventKind::RunStarted {
             message: "beginning work".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "corr-1");
             if let AgentEventKind::RunStarted { message } = event.kind {
                 assert_eq!(message, "beginning work");
@@ -598,6 +603,7 @@ fn event_run_completed_roundtrip() {
         event: mk_event(AgentEventKind::RunCompleted {
             message: "done".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -614,6 +620,7 @@ fn event_assistant_message_roundtrip() {
         event: mk_event(AgentEventKind::AssistantMessage {
             text: "Here is the fix.".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -637,6 +644,7 @@ fn event_tool_result_with_error_flag() {
             output: serde_json::json!({"stderr": "command not found"}),
             is_error: true,
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -658,6 +666,7 @@ fn event_file_changed_roundtrip() {
             path: "lib/auth.rs".into(),
             summary: "added JWT validation".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -681,6 +690,7 @@ fn event_command_executed_roundtrip() {
             exit_code: Some(1),
             output_preview: Some("test failed".into()),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -708,6 +718,7 @@ fn event_warning_roundtrip() {
         event: mk_event(AgentEventKind::Warning {
             message: "budget running low".into(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -729,6 +740,7 @@ fn event_error_kind_with_code_roundtrip() {
             message: "permission denied".into(),
             error_code: Some(abp_error::ErrorCode::PolicyDenied),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -1050,6 +1062,7 @@ fn ref_id_uuid_format_preserved() {
     let env = Envelope::Event {
         ref_id: uuid_str.clone(),
         event: mk_event(AgentEventKind::AssistantDelta { text: "x".into() }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { ref_id, .. } => {
@@ -1070,6 +1083,7 @@ fn ref_id_correlation_across_event_and_final() {
         event: mk_event(AgentEventKind::AssistantDelta {
             text: "working".into(),
         }),
+        seq: None,
     };
     let final_env = Envelope::Final {
         ref_id: run_id.into(),
@@ -1105,6 +1119,7 @@ fn ref_id_correlation_run_to_events() {
         event: mk_event(AgentEventKind::RunStarted {
             message: "start".into(),
         }),
+        seq: None,
     };
 
     let run_id_decoded = match roundtrip(&run_env) {
@@ -1140,6 +1155,7 @@ fn event_ext_field_roundtrip() {
     let env = Envelope::Event {
         ref_id: "ext-test".into(),
         event,
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -1163,6 +1179,7 @@ fn event_ext_none_omitted_in_json() {
     let env = Envelope::Event {
         ref_id: "ext-none".into(),
         event,
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     // ext should be omitted via skip_serializing_if
@@ -1192,6 +1209,7 @@ fn event_ext_with_nested_objects() {
     let env = Envelope::Event {
         ref_id: "ext-deep".into(),
         event,
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -1214,6 +1232,7 @@ fn large_assistant_message_roundtrip() {
         event: mk_event(AgentEventKind::AssistantMessage {
             text: large_text.clone(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -1239,6 +1258,7 @@ fn large_tool_call_input_roundtrip() {
             parent_tool_use_id: None,
             input: large_input.clone(),
         }),
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -1297,6 +1317,7 @@ fn large_payload_is_single_jsonl_line() {
     let env = Envelope::Event {
         ref_id: "run-sl".into(),
         event: mk_event(AgentEventKind::AssistantMessage { text: large_text }),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     assert_eq!(encoded.matches('\n').count(), 1);
@@ -120,6 +120,7 @@ fn hello_roundtrip() {
         backend,
         capabilities,
         mode,
+        ..
     } = back
     {
         assert_eq!(contract_version, CONTRACT_VERSION);
@@ -204,6 +205,7 @@ fn event_serialize_has_correct_tag() {
         event: test_agent_event(AgentEventKind::RunStarted {
             message: "go".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_value(&env).unwrap();
     assert_eq!(json["t"], "event");
@@ -214,6 +216,7 @@ fn event_ref_id_correlation() {
     let env = Envelope::Event {
         ref_id: "run-42".into(),
         event: test_agent_event(AgentEventKind::AssistantDelta { text: "hi".into() }),
+        seq: None,
     };
     let s = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&s).unwrap();
@@ -234,6 +237,7 @@ fn event_with_tool_call() {
             parent_tool_use_id: None,
             input: serde_json::json!({"cmd": "ls"}),
         }),
+        seq: None,
     };
     let s = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&s).unwrap();
@@ -258,6 +262,7 @@ fn event_with_tool_result() {
             output: serde_json::json!("file list"),
             is_error: false,
         }),
+        seq: None,
     };
     let json = serde_json::to_value(&env).unwrap();
     assert_eq!(json["event"]["type"], "tool_result");
@@ -271,6 +276,7 @@ fn event_with_file_changed() {
             path: "src/main.rs".into(),
             summary: "added function".into(),
         }),
+        seq: None,
     };
     let s = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&s).unwrap();
@@ -293,6 +299,7 @@ fn event_with_warning() {
         event: test_agent_event(AgentEventKind::Warning {
             message: "budget low".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_value(&env).unwrap();
     assert_eq!(json["event"]["type"], "warning");
@@ -307,6 +314,7 @@ fn event_with_error_kind() {
             message: "oops".into(),
             error_code: None,
         }),
+        seq: None,
     };
     let json = serde_json::to_value(&env).unwrap();
     assert_eq!(json["event"]["type"], "error");
@@ -321,6 +329,7 @@ fn event_with_command_executed() {
             exit_code: Some(0),
             output_preview: Some("ok".into()),
         }),
+        seq: None,
     };
     let s = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&s).unwrap();
@@ -556,10 +565,11 @@ fn roundtrip_event() {
         event: test_agent_event(AgentEventKind::AssistantMessage {
             text: "hello world".into(),
         }),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim_end()).unwrap();
-    if let Envelope::Event { ref_id, event } = decoded {
+    if let Envelope::Event { ref_id, event, .. } = decoded {
         assert_eq!(ref_id, "r1");
         if let AgentEventKind::AssistantMessage { text } = event.kind {
             assert_eq!(text, "hello world");
@@ -655,6 +665,7 @@ fn wire_format_hello_from_fixed_json() {
         backend,
         capabilities,
         mode,
+        ..
     } = env
     {
         assert_eq!(contract_version, "abp/v0.1");
@@ -694,7 +705,7 @@ fn wire_format_fatal_null_ref_id() {
 fn wire_format_event_assistant_delta() {
     let json = r#"{"t":"event","ref_id":"r1","event":{"ts":"2025-01-01T00:00:00Z","type":"assistant_delta","text":"hi"}}"#;
     let env: Envelope = serde_json::from_str(json).unwrap();
-    if let Envelope::Event { ref_id, event } = env {
+    if let Envelope::Event { ref_id, event, .. } = env {
         assert_eq!(ref_id, "r1");
         if let AgentEventKind::AssistantDelta { text } = event.kind {
             assert_eq!(text, "hi");
@@ -80,6 +80,9 @@ fn sample_work_order() -> WorkOrder {
             env: BTreeMap::from([("RUST_LOG".into(), "debug".into())]),
             max_budget_usd: Some(1.0),
             max_turns: Some(10),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -126,6 +129,7 @@ fn sample_receipt() -> Receipt {
             git_diff: Some("+line".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -178,6 +182,7 @@ fn golden_envelope_event() {
             },
             ext: None,
         },
+        seq: None,
     };
     assert_json_snapshot!("golden_envelope_event", env);
 }
@@ -242,6 +247,7 @@ fn golden_jsonl_stream() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Event {
             ref_id: "run-001".into(),
@@ -252,6 +258,7 @@ fn golden_jsonl_stream() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Event {
             ref_id: "run-001".into(),
@@ -262,6 +269,7 @@ fn golden_jsonl_stream() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: "run-001".into(),
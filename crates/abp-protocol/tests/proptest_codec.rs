@@ -214,11 +214,15 @@ fn arb_envelope() -> impl Strategy<Value = Envelope> {
                 backend,
                 capabilities,
                 mode,
+                features: None,
             }),
         (arb_nonempty_string(), arb_work_order())
             .prop_map(|(id, work_order)| Envelope::Run { id, work_order }),
-        (arb_nonempty_string(), arb_agent_event())
-            .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event }),
+        (arb_nonempty_string(), arb_agent_event()).prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        }),
         (arb_nonempty_string(), arb_receipt())
             .prop_map(|(ref_id, receipt)| Envelope::Final { ref_id, receipt }),
         (prop::option::of(arb_nonempty_string()), arb_string()).prop_map(|(ref_id, error)| {
@@ -346,11 +346,15 @@ fn arb_envelope() -> impl Strategy<Value = Envelope> {
                 backend,
                 capabilities,
                 mode,
+                features: None,
             }),
         (arb_nonempty_string(), arb_work_order())
             .prop_map(|(id, work_order)| Envelope::Run { id, work_order }),
-        (arb_nonempty_string(), arb_agent_event())
-            .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event }),
+        (arb_nonempty_string(), arb_agent_event()).prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        }),
         (arb_nonempty_string(), arb_receipt())
             .prop_map(|(ref_id, receipt)| Envelope::Final { ref_id, receipt }),
         (prop::option::of(arb_nonempty_string()), arb_string()).prop_map(|(ref_id, error)| {
@@ -416,7 +420,7 @@ proptest! {
     /// `ref_id` on the `Event` variant must survive a round-trip unchanged.
     #[test]
     fn ref_id_preserved_event(ref_id in arb_nonempty_string(), event in arb_agent_event()) {
-        let env = Envelope::Event { ref_id: ref_id.clone(), event };
+        let env = Envelope::Event { ref_id: ref_id.clone(), event, seq: None, };
         let json_str = serde_json::to_string(&env).unwrap();
         let decoded: Envelope = serde_json::from_str(&json_str).unwrap();
 
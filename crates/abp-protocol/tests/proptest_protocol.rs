@@ -346,11 +346,15 @@ fn arb_envelope() -> impl Strategy<Value = Envelope> {
                 backend,
                 capabilities,
                 mode,
+                features: None,
             }),
         (arb_nonempty_string(), arb_work_order())
             .prop_map(|(id, work_order)| Envelope::Run { id, work_order }),
-        (arb_nonempty_string(), arb_agent_event())
-            .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event }),
+        (arb_nonempty_string(), arb_agent_event()).prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        }),
         (arb_nonempty_string(), arb_receipt())
             .prop_map(|(ref_id, receipt)| Envelope::Final { ref_id, receipt }),
         (prop::option::of(arb_nonempty_string()), arb_string()).prop_map(|(ref_id, error)| {
@@ -410,7 +414,7 @@ proptest! {
         receipt in arb_receipt(),
     ) {
         // Event envelope preserves ref_id.
-        let event_env = Envelope::Event { ref_id: ref_id.clone(), event };
+        let event_env = Envelope::Event { ref_id: ref_id.clone(), event, seq: None, };
         let event_json = serde_json::to_string(&event_env).unwrap();
         let event_decoded: Envelope = serde_json::from_str(&event_json).unwrap();
         if let Envelope::Event { ref_id: got, .. } = event_decoded {
@@ -444,10 +448,10 @@ proptest! {
             contract_version: CONTRACT_VERSION.to_string(),
             backend,
             capabilities: caps,
-            mode,
+            mode, features: None,
         };
         let run = Envelope::Run { id: "r1".into(), work_order };
-        let evt = Envelope::Event { ref_id: "r1".into(), event };
+        let evt = Envelope::Event { ref_id: "r1".into(), event, seq: None, };
         let fin = Envelope::Final { ref_id: "r1".into(), receipt };
         let fatal = Envelope::Fatal { ref_id: Some("r1".into()), error: "boom".into(), error_code: None};
 
@@ -551,7 +555,7 @@ proptest! {
     /// Any AgentEvent in an Event envelope roundtrips.
     #[test]
     fn agent_event_in_envelope_roundtrip(ref_id in arb_nonempty_string(), event in arb_agent_event()) {
-        let env = Envelope::Event { ref_id, event };
+        let env = Envelope::Event { ref_id, event, seq: None, };
         let json = serde_json::to_string(&env).unwrap();
         let decoded: Envelope = serde_json::from_str(&json).unwrap();
         let orig_val = serde_json::to_value(&env).unwrap();
@@ -662,7 +666,7 @@ proptest! {
         let ref_id = "run-abc";
         let mut buf = String::new();
         for event in &events {
-            let env = Envelope::Event { ref_id: ref_id.into(), event: event.clone() };
+            let env = Envelope::Event { ref_id: ref_id.into(), event: event.clone(), seq: None, };
             buf.push_str(&JsonlCodec::encode(&env).unwrap());
         }
 
@@ -671,7 +675,7 @@ proptest! {
 
         for (i, line) in lines.iter().enumerate() {
             let decoded = JsonlCodec::decode(line).unwrap();
-            if let Envelope::Event { ref_id: got_ref, event: got_event } = decoded {
+            if let Envelope::Event { ref_id: got_ref, event: got_event, .. } = decoded {
                 prop_assert_eq!(ref_id, &got_ref);
                 let orig_val = serde_json::to_value(&events[i]).unwrap();
                 let dec_val = serde_json::to_value(&got_event).unwrap();
@@ -78,6 +78,7 @@ fn make_event(ref_id: &str) -> Envelope {
     Envelope::Event {
         ref_id: ref_id.into(),
         event: make_agent_event(),
+        seq: None,
     }
 }
 
@@ -109,6 +110,7 @@ fn hello_envelope_construction() {
             backend,
             capabilities,
             mode,
+            ..
         } => {
             assert_eq!(contract_version, CONTRACT_VERSION);
             assert_eq!(backend.id, "test-sidecar");
@@ -163,7 +165,7 @@ fn run_envelope_construction() {
 fn event_envelope_construction() {
     let env = make_event("run-123");
     match &env {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-123");
         }
         _ => panic!("expected Event"),
@@ -1165,6 +1167,7 @@ fn validator_hello_empty_backend_id() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -1188,6 +1191,7 @@ fn validator_hello_invalid_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -1211,6 +1215,7 @@ fn validator_hello_empty_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -1231,6 +1236,7 @@ fn validator_hello_warns_missing_optional_fields() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(result.valid); // warnings only
@@ -1243,6 +1249,7 @@ fn validator_event_empty_ref_id() {
     let env = Envelope::Event {
         ref_id: "".into(),
         event: make_agent_event(),
+        seq: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -1786,6 +1793,7 @@ fn event_assistant_message_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1804,6 +1812,7 @@ fn event_assistant_delta_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1822,6 +1831,7 @@ fn event_run_started_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1840,6 +1850,7 @@ fn event_run_completed_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1861,6 +1872,7 @@ fn event_tool_call_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1882,6 +1894,7 @@ fn event_tool_result_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1901,6 +1914,7 @@ fn event_file_changed_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1921,6 +1935,7 @@ fn event_command_executed_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1939,6 +1954,7 @@ fn event_warning_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1958,6 +1974,7 @@ fn event_error_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -243,6 +243,7 @@ fn empty_ref_id_on_event() {
     let env = Envelope::Event {
         ref_id: String::new(),
         event,
+        seq: None,
     };
 
     let encoded = JsonlCodec::encode(&env).unwrap();
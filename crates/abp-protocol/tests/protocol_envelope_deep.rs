@@ -132,6 +132,7 @@ fn roundtrip_hello_preserves_all_fields() {
         backend,
         capabilities,
         mode,
+        ..
     } = back
     {
         assert_eq!(contract_version, CONTRACT_VERSION);
@@ -168,10 +169,11 @@ fn roundtrip_event_run_started() {
         event: mk_event(AgentEventKind::RunStarted {
             message: "starting".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
-    if let Envelope::Event { ref_id, event } = back {
+    if let Envelope::Event { ref_id, event, .. } = back {
         assert_eq!(ref_id, "rt-ev-1");
         assert!(matches!(event.kind, AgentEventKind::RunStarted { .. }));
     } else {
@@ -186,10 +188,11 @@ fn roundtrip_event_run_completed() {
         event: mk_event(AgentEventKind::RunCompleted {
             message: "done".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
-    if let Envelope::Event { ref_id, event } = back {
+    if let Envelope::Event { ref_id, event, .. } = back {
         assert_eq!(ref_id, "rt-ev-2");
         if let AgentEventKind::RunCompleted { message } = event.kind {
             assert_eq!(message, "done");
@@ -208,6 +211,7 @@ fn roundtrip_event_assistant_delta() {
         event: mk_event(AgentEventKind::AssistantDelta {
             text: "chunk".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -229,6 +233,7 @@ fn roundtrip_event_assistant_message() {
         event: mk_event(AgentEventKind::AssistantMessage {
             text: "full message".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -253,6 +258,7 @@ fn roundtrip_event_tool_call() {
             parent_tool_use_id: None,
             input: serde_json::json!({"pattern": "fn main"}),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -285,6 +291,7 @@ fn roundtrip_event_tool_result() {
             output: serde_json::json!("success"),
             is_error: false,
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -313,6 +320,7 @@ fn roundtrip_event_file_changed() {
             path: "src/lib.rs".into(),
             summary: "added tests".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -337,6 +345,7 @@ fn roundtrip_event_command_executed() {
             exit_code: Some(0),
             output_preview: Some("ok".into()),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -365,6 +374,7 @@ fn roundtrip_event_warning() {
         event: mk_event(AgentEventKind::Warning {
             message: "low budget".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -387,6 +397,7 @@ fn roundtrip_event_error() {
             message: "something bad".into(),
             error_code: None,
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -482,6 +493,7 @@ fn tag_field_is_t_not_type_event() {
         event: mk_event(AgentEventKind::RunStarted {
             message: "go".into(),
         }),
+        seq: None,
     };
     let val = serde_json::to_value(&env).unwrap();
     assert_eq!(val["t"], "event");
@@ -632,6 +644,7 @@ fn event_ref_id_matches_run_id() {
     let event_env = Envelope::Event {
         ref_id: run_id.into(),
         event: mk_event(AgentEventKind::AssistantDelta { text: "hi".into() }),
+        seq: None,
     };
     let val = serde_json::to_value(&event_env).unwrap();
     assert_eq!(val["ref_id"], run_id);
@@ -693,6 +706,7 @@ fn event_payload_contains_agent_event() {
         event: mk_event(AgentEventKind::AssistantDelta {
             text: "delta text".into(),
         }),
+        seq: None,
     };
     let val = serde_json::to_value(&env).unwrap();
     assert_eq!(val["event"]["type"], "assistant_delta");
@@ -819,6 +833,7 @@ fn jsonl_concatenation_is_parseable() {
             event: mk_event(AgentEventKind::RunStarted {
                 message: "go".into(),
             }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r1".into(),
@@ -1032,6 +1047,7 @@ fn large_assistant_message_roundtrips() {
         event: mk_event(AgentEventKind::AssistantMessage {
             text: big_text.clone(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -1060,6 +1076,7 @@ fn large_tool_output_roundtrips() {
             output: big_output.clone(),
             is_error: false,
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -1088,6 +1105,7 @@ fn large_tool_input_roundtrips() {
             parent_tool_use_id: None,
             input: big_input.clone(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -1398,6 +1416,7 @@ fn decode_stream_full_conversation() {
         event: mk_event(AgentEventKind::RunStarted {
             message: "starting".into(),
         }),
+        seq: None,
     })
     .unwrap();
     let ev2 = JsonlCodec::encode(&Envelope::Event {
@@ -1405,6 +1424,7 @@ fn decode_stream_full_conversation() {
         event: mk_event(AgentEventKind::AssistantDelta {
             text: "hello".into(),
         }),
+        seq: None,
     })
     .unwrap();
     let ev3 = JsonlCodec::encode(&Envelope::Event {
@@ -1412,6 +1432,7 @@ fn decode_stream_full_conversation() {
         event: mk_event(AgentEventKind::RunCompleted {
             message: "done".into(),
         }),
+        seq: None,
     })
     .unwrap();
     let fin = JsonlCodec::encode(&Envelope::Final {
@@ -1456,6 +1477,7 @@ fn decode_stream_many_events_same_ref_id() {
             event: mk_event(AgentEventKind::AssistantDelta {
                 text: format!("chunk-{i}"),
             }),
+            seq: None,
         };
         stream.push_str(&JsonlCodec::encode(&env).unwrap());
     }
@@ -1563,6 +1585,7 @@ fn event_with_ext_field_roundtrips() {
             kind: AgentEventKind::AssistantDelta { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -1582,6 +1605,7 @@ fn event_with_error_code_in_agent_event_error() {
             message: "tool failed".into(),
             error_code: Some(abp_error::ErrorCode::ExecutionToolFailed),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -1611,6 +1635,7 @@ fn tool_result_is_error_true_roundtrips() {
             output: serde_json::json!("permission denied"),
             is_error: true,
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -1635,6 +1660,7 @@ fn tool_call_with_parent_tool_use_id() {
             parent_tool_use_id: Some("tu-parent".into()),
             input: serde_json::json!({}),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -1710,6 +1736,7 @@ fn unicode_in_assistant_text_roundtrips() {
         event: mk_event(AgentEventKind::AssistantMessage {
             text: "Привет мир! こんにちは 🌍".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -1751,6 +1778,7 @@ fn command_executed_with_none_fields() {
             exit_code: None,
             output_preview: None,
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json).unwrap();
@@ -95,6 +95,7 @@ fn make_event(ref_id: &str) -> Envelope {
     Envelope::Event {
         ref_id: ref_id.into(),
         event: sample_event(),
+        seq: None,
     }
 }
 
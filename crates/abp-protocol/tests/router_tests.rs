@@ -66,6 +66,7 @@ fn event_envelope(ref_id: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -156,6 +156,7 @@ fn roundtrip_event() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json_str = serde_json::to_string(&env).unwrap();
     let back: Envelope = serde_json::from_str(&json_str).unwrap();
@@ -178,6 +178,7 @@ fn snapshot_envelope_event_assistant_delta() {
             },
             ext: None,
         },
+        seq: None,
     };
     let value = serde_json::to_value(&env).unwrap();
     assert_json_snapshot!("protocol_event_assistant_delta", value);
@@ -199,6 +200,7 @@ fn snapshot_envelope_event_tool_call() {
             },
             ext: None,
         },
+        seq: None,
     };
     let value = serde_json::to_value(&env).unwrap();
     assert_json_snapshot!("protocol_event_tool_call", value);
@@ -219,6 +221,7 @@ fn snapshot_envelope_event_command_executed() {
             },
             ext: None,
         },
+        seq: None,
     };
     let value = serde_json::to_value(&env).unwrap();
     assert_json_snapshot!("protocol_event_command_executed", value);
@@ -238,6 +241,7 @@ fn snapshot_envelope_event_error() {
             },
             ext: None,
         },
+        seq: None,
     };
     let value = serde_json::to_value(&env).unwrap();
     assert_json_snapshot!("protocol_event_error", value);
@@ -303,6 +307,7 @@ fn snapshot_jsonl_batch_three_envelopes() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Fatal {
             ref_id: Some("run-1".into()),
@@ -350,6 +355,7 @@ fn snapshot_envelope_event_tool_result_error() {
             },
             ext: None,
         },
+        seq: None,
     };
     let value = serde_json::to_value(&env).unwrap();
     assert_json_snapshot!("protocol_event_tool_result_error", value);
@@ -125,6 +125,7 @@ fn snapshot_event_envelope_assistant_delta() {
             },
             ext: None,
         },
+        seq: None,
     };
     assert_json_snapshot!("envelope_event_assistant_delta", env);
 }
@@ -143,6 +144,7 @@ fn snapshot_event_envelope_tool_call() {
             },
             ext: None,
         },
+        seq: None,
     };
     assert_json_snapshot!("envelope_event_tool_call", env);
 }
@@ -159,6 +161,7 @@ fn snapshot_event_envelope_file_changed() {
             },
             ext: None,
         },
+        seq: None,
     };
     assert_json_snapshot!("envelope_event_file_changed", env);
 }
@@ -84,6 +84,7 @@ fn event_env(ref_id: &str) -> Envelope {
         event: test_event(AgentEventKind::RunStarted {
             message: "go".into(),
         }),
+        seq: None,
     }
 }
 
@@ -124,6 +125,7 @@ fn hello_empty_contract_version() {
         backend: test_backend(),
         capabilities: test_capabilities(),
         mode: Default::default(),
+        features: None,
     };
     let r = v.validate(&env);
     assert!(!r.valid);
@@ -140,6 +142,7 @@ fn hello_invalid_contract_version() {
         backend: test_backend(),
         capabilities: test_capabilities(),
         mode: Default::default(),
+        features: None,
     };
     let r = v.validate(&env);
     assert!(!r.valid);
@@ -160,6 +163,7 @@ fn hello_empty_backend_id() {
         },
         capabilities: test_capabilities(),
         mode: Default::default(),
+        features: None,
     };
     let r = v.validate(&env);
     assert!(!r.valid);
@@ -180,6 +184,7 @@ fn hello_warns_on_missing_optional_backend_fields() {
         },
         capabilities: test_capabilities(),
         mode: Default::default(),
+        features: None,
     };
     let r = v.validate(&env);
     assert!(r.valid);
@@ -257,6 +262,7 @@ fn event_empty_ref_id() {
         event: test_event(AgentEventKind::RunStarted {
             message: "go".into(),
         }),
+        seq: None,
     };
     let r = v.validate(&env);
     assert!(!r.valid);
@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Billing ledger export in CSV and Parquet.
+//!
+//! A [`BillingRecord`] is the finance-facing projection of a receipt: one
+//! row per run with its tenant, backend, model, token counts, cost,
+//! duration, and outcome. [`billing_ledger`] walks a [`ReceiptStore`] for a
+//! time range; [`export_billing_csv`] and [`export_billing_parquet`] write
+//! the result for downstream pipelines that do not consume raw receipts.
+
+use abp_core::{Outcome, Receipt};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::filter::ReceiptFilter;
+use crate::parquet::{self, Column, ColumnData};
+use crate::{ReceiptStore, Result};
+
+/// Key under `receipt.usage_raw` naming the tenant billed for a run.
+pub const TENANT_KEY: &str = "tenant";
+
+/// Key under `receipt.usage_raw` naming the model that served a run.
+pub const MODEL_KEY: &str = "model";
+
+/// Column names shared by the CSV header and the Parquet schema.
+const COLUMNS: [&str; 13] = [
+    "run_id",
+    "work_order_id",
+    "tenant",
+    "backend",
+    "model",
+    "started_at",
+    "duration_ms",
+    "input_tokens",
+    "output_tokens",
+    "cache_read_tokens",
+    "cache_write_tokens",
+    "cost_usd",
+    "outcome",
+];
+
+/// One billable run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BillingRecord {
+    /// Run identifier.
+    pub run_id: Uuid,
+    /// Work order the run executed.
+    pub work_order_id: Uuid,
+    /// Tenant from `usage_raw["tenant"]`, if recorded.
+    pub tenant: Option<String>,
+    /// Backend identifier.
+    pub backend: String,
+    /// Model from `usage_raw["model"]`, if recorded.
+    pub model: Option<String>,
+    /// When the run started.
+    pub started_at: DateTime<Utc>,
+    /// Wall-clock duration in milliseconds.
+    pub duration_ms: u64,
+    /// Input (prompt) tokens.
+    pub input_tokens: Option<u64>,
+    /// Output (completion) tokens.
+    pub output_tokens: Option<u64>,
+    /// Tokens read from the cache.
+    pub cache_read_tokens: Option<u64>,
+    /// Tokens written to the cache.
+    pub cache_write_tokens: Option<u64>,
+    /// Estimated cost in US dollars.
+    pub cost_usd: Option<f64>,
+    /// Run outcome.
+    pub outcome: Outcome,
+}
+
+impl BillingRecord {
+    /// Project a receipt onto its billing fields.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Self {
+        let raw_str = |key: &str| {
+            receipt
+                .usage_raw
+                .get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string)
+        };
+        Self {
+            run_id: receipt.meta.run_id,
+            work_order_id: receipt.meta.work_order_id,
+            tenant: raw_str(TENANT_KEY),
+            backend: receipt.backend.id.clone(),
+            model: raw_str(MODEL_KEY),
+            started_at: receipt.meta.started_at,
+            duration_ms: receipt.meta.duration_ms,
+            input_tokens: receipt.usage.input_tokens,
+            output_tokens: receipt.usage.output_tokens,
+            cache_read_tokens: receipt.usage.cache_read_tokens,
+            cache_write_tokens: receipt.usage.cache_write_tokens,
+            cost_usd: receipt.usage.estimated_cost_usd,
+            outcome: receipt.outcome.clone(),
+        }
+    }
+}
+
+/// Collect billing records for receipts that started within `[from, to]`,
+/// ordered by start time.
+pub async fn billing_ledger(
+    store: &dyn ReceiptStore,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<BillingRecord>> {
+    let filter = ReceiptFilter {
+        time_range: Some((from, to)),
+        ..ReceiptFilter::default()
+    };
+    let mut records: Vec<_> = store
+        .list(filter)
+        .await?
+        .iter()
+        .map(BillingRecord::from_receipt)
+        .collect();
+    records.sort_by(|a, b| {
+        a.started_at
+            .cmp(&b.started_at)
+            .then(a.run_id.cmp(&b.run_id))
+    });
+    Ok(records)
+}
+
+/// Export billing records as RFC 4180 CSV with a header row.
+///
+/// Columns: `run_id,work_order_id,tenant,backend,model,started_at,duration_ms,input_tokens,output_tokens,cache_read_tokens,cache_write_tokens,cost_usd,outcome`.
+/// Missing values are empty fields.
+#[must_use]
+pub fn export_billing_csv(records: &[BillingRecord]) -> String {
+    let mut buf = COLUMNS.join(",");
+    buf.push('\n');
+    for r in records {
+        let opt = |v: Option<u64>| v.map_or(String::new(), |t| t.to_string());
+        let fields = [
+            r.run_id.to_string(),
+            r.work_order_id.to_string(),
+            csv_field(r.tenant.as_deref().unwrap_or_default()),
+            csv_field(&r.backend),
+            csv_field(r.model.as_deref().unwrap_or_default()),
+            r.started_at.to_rfc3339(),
+            r.duration_ms.to_string(),
+            opt(r.input_tokens),
+            opt(r.output_tokens),
+            opt(r.cache_read_tokens),
+            opt(r.cache_write_tokens),
+            r.cost_usd.map_or(String::new(), |c| c.to_string()),
+            outcome_label(&r.outcome).to_string(),
+        ];
+        buf.push_str(&fields.join(","));
+        buf.push('\n');
+    }
+    buf
+}
+
+/// Export billing records as an uncompressed Parquet file.
+///
+/// Uses the same column names as [`export_billing_csv`]. Ids, tenant,
+/// backend, model, and outcome are UTF-8 strings; `started_at` is a
+/// millisecond timestamp; `cost_usd` is a double; the rest are `INT64`.
+#[must_use]
+pub fn export_billing_parquet(records: &[BillingRecord]) -> Vec<u8> {
+    let strings =
+        |f: fn(&BillingRecord) -> Option<String>| ColumnData::Utf8(records.iter().map(f).collect());
+    let ints = |f: fn(&BillingRecord) -> Option<u64>| {
+        ColumnData::Int64(
+            records
+                .iter()
+                .map(|r| f(r).map(|n| i64::try_from(n).unwrap_or(i64::MAX)))
+                .collect(),
+        )
+    };
+    let data = [
+        strings(|r| Some(r.run_id.to_string())),
+        strings(|r| Some(r.work_order_id.to_string())),
+        strings(|r| r.tenant.clone()),
+        strings(|r| Some(r.backend.clone())),
+        strings(|r| r.model.clone()),
+        ColumnData::TimestampMillis(
+            records
+                .iter()
+                .map(|r| Some(r.started_at.timestamp_millis()))
+                .collect(),
+        ),
+        ints(|r| Some(r.duration_ms)),
+        ints(|r| r.input_tokens),
+        ints(|r| r.output_tokens),
+        ints(|r| r.cache_read_tokens),
+        ints(|r| r.cache_write_tokens),
+        ColumnData::Double(records.iter().map(|r| r.cost_usd).collect()),
+        strings(|r| Some(outcome_label(&r.outcome).to_string())),
+    ];
+    let columns: Vec<_> = COLUMNS
+        .into_iter()
+        .zip(data)
+        .map(|(name, data)| Column { name, data })
+        .collect();
+    parquet::write(
+        &columns,
+        concat!("abp-receipt-store version ", env!("CARGO_PKG_VERSION")),
+    )
+}
+
+/// Wire name of an outcome (`complete`, `partial`, `failed`).
+fn outcome_label(outcome: &Outcome) -> &'static str {
+    match outcome {
+        Outcome::Complete => "complete",
+        Outcome::Partial => "partial",
+        Outcome::Failed => "failed",
+    }
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
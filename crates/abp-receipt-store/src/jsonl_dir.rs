@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Directory of daily JSON-lines files.
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tracing::debug;
+
+use abp_core::Receipt;
+
+use crate::error::StoreError;
+use crate::filter::ReceiptFilter;
+use crate::{ReceiptStore, Result};
+
+/// Receipt store backed by a directory of JSON-lines files, one per UTC day.
+///
+/// Receipts land in `{root}/{YYYY-MM-DD}.jsonl` by their `started_at` date.
+/// Storing appends one line, so the cost of a write does not grow with the
+/// archive; deleting rewrites only the day file that held the receipt. Old
+/// days can be archived or removed by moving files.
+#[derive(Debug)]
+pub struct JsonlDirReceiptStore {
+    root: PathBuf,
+    mu: Mutex<()>,
+}
+
+impl JsonlDirReceiptStore {
+    /// Create (or open) a store rooted at `root`.
+    ///
+    /// The directory is created on the first write if it does not exist.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            mu: Mutex::new(()),
+        }
+    }
+
+    /// Return the store's root directory.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Day file a receipt belongs in.
+    #[must_use]
+    pub fn file_for(&self, receipt: &Receipt) -> PathBuf {
+        self.root.join(format!(
+            "{}.jsonl",
+            receipt.meta.started_at.format("%Y-%m-%d")
+        ))
+    }
+
+    /// Every receipt, oldest day first, in append order within a day.
+    async fn read_all(&self) -> Result<Vec<(PathBuf, Receipt)>> {
+        let root = self.root.clone();
+        tokio::task::spawn_blocking(move || read_dir_sync(&root))
+            .await
+            .map_err(|e| StoreError::Other(e.to_string()))?
+    }
+}
+
+fn day_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let entries = match std::fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("jsonl") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn read_dir_sync(root: &Path) -> Result<Vec<(PathBuf, Receipt)>> {
+    let mut out = Vec::new();
+    for path in day_files(root)? {
+        let content = std::fs::read_to_string(&path)?;
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let receipt: Receipt = serde_json::from_str(line).map_err(|e| {
+                StoreError::Other(format!("{} line {}: {e}", path.display(), i + 1))
+            })?;
+            out.push((path.clone(), receipt));
+        }
+    }
+    Ok(out)
+}
+
+#[async_trait]
+impl ReceiptStore for JsonlDirReceiptStore {
+    async fn store(&self, receipt: &Receipt) -> Result<()> {
+        let _lock = self.mu.lock().await;
+        let id = receipt.meta.run_id;
+        if self
+            .read_all()
+            .await?
+            .iter()
+            .any(|(_, r)| r.meta.run_id == id)
+        {
+            return Err(StoreError::DuplicateId(id.to_string()));
+        }
+        let path = self.file_for(receipt);
+        tokio::fs::create_dir_all(&self.root).await?;
+        let mut line = serde_json::to_string(receipt)?;
+        line.push('\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.sync_data().await?;
+        debug!(id = %id, path = %path.display(), "appended receipt");
+        Ok(())
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<Receipt>> {
+        Ok(self
+            .read_all()
+            .await?
+            .into_iter()
+            .map(|(_, r)| r)
+            .find(|r| r.meta.run_id.to_string() == id))
+    }
+
+    async fn list(&self, filter: ReceiptFilter) -> Result<Vec<Receipt>> {
+        let matched = self
+            .read_all()
+            .await?
+            .into_iter()
+            .map(|(_, r)| r)
+            .filter(|r| filter.matches(r))
+            .collect();
+        Ok(filter.paginate(matched))
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool> {
+        let _lock = self.mu.lock().await;
+        let all = self.read_all().await?;
+        let Some((path, _)) = all.iter().find(|(_, r)| r.meta.run_id.to_string() == id) else {
+            return Ok(false);
+        };
+        let path = path.clone();
+        let mut buf = Vec::new();
+        for (_, r) in all
+            .iter()
+            .filter(|(p, r)| *p == path && r.meta.run_id.to_string() != id)
+        {
+            buf.extend_from_slice(serde_json::to_string(r)?.as_bytes());
+            buf.push(b'\n');
+        }
+        if buf.is_empty() {
+            tokio::fs::remove_file(&path).await?;
+        } else {
+            tokio::fs::write(&path, buf).await?;
+        }
+        debug!(id = %id, path = %path.display(), "deleted receipt");
+        Ok(true)
+    }
+
+    async fn count(&self) -> Result<usize> {
+        Ok(self.read_all().await?.len())
+    }
+}
@@ -5,6 +5,7 @@
 
 //! Async receipt storage, indexing, and chain validation for the Agent Backplane.
 
+mod billing;
 mod chain;
 mod diff;
 mod error;
@@ -12,10 +13,18 @@ mod export;
 mod file;
 mod filter;
 mod index;
+mod jsonl_dir;
 mod memory;
+mod parquet;
 mod retention;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 mod stats;
 
+pub use billing::{
+    BillingRecord, MODEL_KEY, TENANT_KEY, billing_ledger, export_billing_csv,
+    export_billing_parquet,
+};
 pub use chain::{
     ChainValidation, ChainValidationError, validate_chain, validate_chain_with_parents,
 };
@@ -27,8 +36,11 @@ pub use export::{
 pub use file::FileReceiptStore;
 pub use filter::ReceiptFilter;
 pub use index::ReceiptIndex;
+pub use jsonl_dir::JsonlDirReceiptStore;
 pub use memory::InMemoryReceiptStore;
 pub use retention::{ReceiptRetention, RetentionResult};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteReceiptStore;
 pub use stats::ReceiptStats;
 
 // Re-export core types for convenience.
@@ -56,6 +68,15 @@ pub trait ReceiptStore: Send + Sync {
 
     /// Count total receipts in the store.
     async fn count(&self) -> Result<usize>;
+
+    /// Every receipt produced for a work order, in the store's list order.
+    async fn get_by_work_order_id(&self, work_order_id: &str) -> Result<Vec<Receipt>> {
+        self.list(ReceiptFilter {
+            work_order_id: Some(work_order_id.to_string()),
+            ..ReceiptFilter::default()
+        })
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -100,4 +100,8 @@ impl ReceiptStore for InMemoryReceiptStore {
         let guard = self.inner.read().await;
         Ok(guard.map.len())
     }
+
+    async fn get_by_work_order_id(&self, work_order_id: &str) -> Result<Vec<Receipt>> {
+        InMemoryReceiptStore::get_by_work_order_id(self, work_order_id).await
+    }
 }
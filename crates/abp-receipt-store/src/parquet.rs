@@ -0,0 +1,299 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Minimal Apache Parquet writer for flat, nullable tables.
+//!
+//! Writes a single row group with one uncompressed, `PLAIN`-encoded data
+//! page per column. Every column is `OPTIONAL`; definition levels are
+//! RLE-encoded. That is enough for finance tooling (Spark, DuckDB, pandas)
+//! to read ledgers without pulling an Arrow stack into the workspace.
+
+/// Values of one column, one entry per row.
+#[derive(Debug, Clone)]
+pub(crate) enum ColumnData {
+    /// UTF-8 strings (`BYTE_ARRAY` / `UTF8`).
+    Utf8(Vec<Option<String>>),
+    /// Signed 64-bit integers (`INT64`).
+    Int64(Vec<Option<i64>>),
+    /// 64-bit floats (`DOUBLE`).
+    Double(Vec<Option<f64>>),
+    /// Milliseconds since the Unix epoch (`INT64` / `TIMESTAMP_MILLIS`).
+    TimestampMillis(Vec<Option<i64>>),
+}
+
+impl ColumnData {
+    fn len(&self) -> usize {
+        match self {
+            Self::Utf8(v) => v.len(),
+            Self::Int64(v) | Self::TimestampMillis(v) => v.len(),
+            Self::Double(v) => v.len(),
+        }
+    }
+
+    fn physical_type(&self) -> i32 {
+        match self {
+            Self::Utf8(_) => TYPE_BYTE_ARRAY,
+            Self::Int64(_) | Self::TimestampMillis(_) => TYPE_INT64,
+            Self::Double(_) => TYPE_DOUBLE,
+        }
+    }
+
+    fn converted_type(&self) -> Option<i32> {
+        match self {
+            Self::Utf8(_) => Some(CONVERTED_UTF8),
+            Self::TimestampMillis(_) => Some(CONVERTED_TIMESTAMP_MILLIS),
+            Self::Int64(_) | Self::Double(_) => None,
+        }
+    }
+
+    /// Definition levels (1 = present) and `PLAIN`-encoded present values.
+    fn encode(&self) -> (Vec<bool>, Vec<u8>) {
+        let mut defined = Vec::with_capacity(self.len());
+        let mut values = Vec::new();
+        match self {
+            Self::Utf8(v) => {
+                for s in v {
+                    defined.push(s.is_some());
+                    if let Some(s) = s {
+                        values.extend_from_slice(&(s.len() as u32).to_le_bytes());
+                        values.extend_from_slice(s.as_bytes());
+                    }
+                }
+            }
+            Self::Int64(v) | Self::TimestampMillis(v) => {
+                for n in v {
+                    defined.push(n.is_some());
+                    if let Some(n) = n {
+                        values.extend_from_slice(&n.to_le_bytes());
+                    }
+                }
+            }
+            Self::Double(v) => {
+                for n in v {
+                    defined.push(n.is_some());
+                    if let Some(n) = n {
+                        values.extend_from_slice(&n.to_le_bytes());
+                    }
+                }
+            }
+        }
+        (defined, values)
+    }
+}
+
+/// A named column.
+#[derive(Debug, Clone)]
+pub(crate) struct Column {
+    pub(crate) name: &'static str,
+    pub(crate) data: ColumnData,
+}
+
+const MAGIC: &[u8] = b"PAR1";
+
+const TYPE_INT64: i32 = 2;
+const TYPE_DOUBLE: i32 = 5;
+const TYPE_BYTE_ARRAY: i32 = 6;
+const CONVERTED_UTF8: i32 = 0;
+const CONVERTED_TIMESTAMP_MILLIS: i32 = 9;
+const REPETITION_OPTIONAL: i32 = 1;
+const ENCODING_PLAIN: i32 = 0;
+const ENCODING_RLE: i32 = 3;
+const CODEC_UNCOMPRESSED: i32 = 0;
+const PAGE_DATA: i32 = 0;
+
+/// Encode `columns` (all of equal length) as a Parquet file.
+pub(crate) fn write(columns: &[Column], created_by: &str) -> Vec<u8> {
+    let num_rows = columns.first().map_or(0, |c| c.data.len()) as i64;
+    let mut out = MAGIC.to_vec();
+    let mut chunks = Vec::with_capacity(columns.len());
+
+    for column in columns {
+        let (defined, values) = column.data.encode();
+        let levels = encode_levels(&defined);
+        let mut page = Vec::with_capacity(4 + levels.len() + values.len());
+        page.extend_from_slice(&(levels.len() as u32).to_le_bytes());
+        page.extend_from_slice(&levels);
+        page.extend_from_slice(&values);
+
+        let mut header = Compact::new();
+        header.i32(1, PAGE_DATA);
+        header.i32(2, page.len() as i32);
+        header.i32(3, page.len() as i32);
+        header.begin_struct(5);
+        header.i32(1, defined.len() as i32);
+        header.i32(2, ENCODING_PLAIN);
+        header.i32(3, ENCODING_RLE);
+        header.i32(4, ENCODING_RLE);
+        header.end_struct();
+        let header = header.finish();
+
+        let offset = out.len() as i64;
+        let size = (header.len() + page.len()) as i64;
+        out.extend_from_slice(&header);
+        out.extend_from_slice(&page);
+        chunks.push((offset, size, defined.len() as i64));
+    }
+
+    let mut meta = Compact::new();
+    meta.i32(1, 1);
+    meta.list_begin(2, COMPACT_STRUCT, columns.len() + 1);
+    meta.begin_element();
+    meta.binary(4, b"schema");
+    meta.i32(5, columns.len() as i32);
+    meta.end_struct();
+    for column in columns {
+        meta.begin_element();
+        meta.i32(1, column.data.physical_type());
+        meta.i32(3, REPETITION_OPTIONAL);
+        meta.binary(4, column.name.as_bytes());
+        if let Some(converted) = column.data.converted_type() {
+            meta.i32(6, converted);
+        }
+        meta.end_struct();
+    }
+    meta.i64(3, num_rows);
+    meta.list_begin(4, COMPACT_STRUCT, 1);
+    meta.begin_element();
+    meta.list_begin(1, COMPACT_STRUCT, columns.len());
+    for (column, (offset, size, num_values)) in columns.iter().zip(&chunks) {
+        meta.begin_element();
+        meta.i64(2, *offset);
+        meta.begin_struct(3);
+        meta.i32(1, column.data.physical_type());
+        meta.list_begin(2, COMPACT_I32, 2);
+        meta.list_i32(ENCODING_PLAIN);
+        meta.list_i32(ENCODING_RLE);
+        meta.list_begin(3, COMPACT_BINARY, 1);
+        meta.list_binary(column.name.as_bytes());
+        meta.i32(4, CODEC_UNCOMPRESSED);
+        meta.i64(5, *num_values);
+        meta.i64(6, *size);
+        meta.i64(7, *size);
+        meta.i64(9, *offset);
+        meta.end_struct();
+        meta.end_struct();
+    }
+    meta.i64(2, chunks.iter().map(|(_, size, _)| size).sum());
+    meta.i64(3, num_rows);
+    meta.end_struct();
+    meta.binary(6, created_by.as_bytes());
+    let meta = meta.finish();
+
+    out.extend_from_slice(&meta);
+    out.extend_from_slice(&(meta.len() as u32).to_le_bytes());
+    out.extend_from_slice(MAGIC);
+    out
+}
+
+/// RLE-encode bit-width-1 definition levels as runs of equal values.
+fn encode_levels(defined: &[bool]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut iter = defined.iter().peekable();
+    while let Some(&value) = iter.next() {
+        let mut run = 1u64;
+        while iter.next_if(|&&v| v == value).is_some() {
+            run += 1;
+        }
+        write_varint(&mut out, run << 1);
+        out.push(u8::from(value));
+    }
+    out
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+const COMPACT_I32: u8 = 5;
+const COMPACT_I64: u8 = 6;
+const COMPACT_BINARY: u8 = 8;
+const COMPACT_LIST: u8 = 9;
+const COMPACT_STRUCT: u8 = 12;
+
+/// Thrift compact-protocol encoder for the Parquet metadata structs.
+struct Compact {
+    buf: Vec<u8>,
+    last_field: Vec<i16>,
+}
+
+impl Compact {
+    fn new() -> Self {
+        Self {
+            buf: Vec::new(),
+            last_field: vec![0],
+        }
+    }
+
+    fn field(&mut self, id: i16, ty: u8) {
+        let last = self.last_field.last_mut().expect("open struct");
+        let delta = id - *last;
+        if (1..=15).contains(&delta) {
+            self.buf.push(((delta as u8) << 4) | ty);
+        } else {
+            self.buf.push(ty);
+            write_varint(&mut self.buf, zigzag(i64::from(id)));
+        }
+        *last = id;
+    }
+
+    fn i32(&mut self, id: i16, value: i32) {
+        self.field(id, COMPACT_I32);
+        write_varint(&mut self.buf, zigzag(i64::from(value)));
+    }
+
+    fn i64(&mut self, id: i16, value: i64) {
+        self.field(id, COMPACT_I64);
+        write_varint(&mut self.buf, zigzag(value));
+    }
+
+    fn binary(&mut self, id: i16, value: &[u8]) {
+        self.field(id, COMPACT_BINARY);
+        self.list_binary(value);
+    }
+
+    fn begin_struct(&mut self, id: i16) {
+        self.field(id, COMPACT_STRUCT);
+        self.last_field.push(0);
+    }
+
+    fn end_struct(&mut self) {
+        self.buf.push(0);
+        self.last_field.pop();
+    }
+
+    fn list_begin(&mut self, id: i16, elem: u8, len: usize) {
+        self.field(id, COMPACT_LIST);
+        if len < 15 {
+            self.buf.push(((len as u8) << 4) | elem);
+        } else {
+            self.buf.push(0xF0 | elem);
+            write_varint(&mut self.buf, len as u64);
+        }
+    }
+
+    /// Start a struct element of a list; close it with [`end_struct`](Self::end_struct).
+    fn begin_element(&mut self) {
+        self.last_field.push(0);
+    }
+
+    fn list_i32(&mut self, value: i32) {
+        write_varint(&mut self.buf, zigzag(i64::from(value)));
+    }
+
+    fn list_binary(&mut self, value: &[u8]) {
+        write_varint(&mut self.buf, value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        self.buf.push(0);
+        self.buf
+    }
+}
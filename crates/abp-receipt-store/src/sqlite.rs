@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! SQLite-backed receipt store.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use rusqlite::{Connection, OptionalExtension, params};
+use tracing::debug;
+
+use abp_core::Receipt;
+
+use crate::error::StoreError;
+use crate::filter::ReceiptFilter;
+use crate::{ReceiptStore, Result};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS receipts (
+    seq           INTEGER PRIMARY KEY AUTOINCREMENT,
+    run_id        TEXT NOT NULL UNIQUE,
+    work_order_id TEXT NOT NULL,
+    backend       TEXT NOT NULL,
+    outcome       TEXT NOT NULL,
+    started_at    TEXT NOT NULL,
+    receipt       TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS receipts_work_order_id ON receipts (work_order_id);
+CREATE INDEX IF NOT EXISTS receipts_backend ON receipts (backend);
+";
+
+/// Receipt store backed by a single SQLite database file.
+///
+/// Each receipt is one row: its full JSON plus indexed columns for run ID,
+/// work order ID, backend, and outcome. Lookups by run or work order ID and
+/// filters on backend or outcome run in SQLite; the remaining
+/// [`ReceiptFilter`] criteria are applied to the decoded rows. Results come
+/// back in insertion order.
+#[derive(Debug, Clone)]
+pub struct SqliteReceiptStore {
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl SqliteReceiptStore {
+    /// Open (or create) a database at `path` and apply the schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StoreError::Other`] if the database cannot be opened or
+    /// migrated.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(path.as_ref()).map_err(sql_err)?;
+        Self::with_connection(conn)
+    }
+
+    /// An in-memory database, for tests.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StoreError::Other`] if SQLite cannot be initialised.
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory().map_err(sql_err)?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA).map_err(sql_err)?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+        })
+    }
+
+    /// Run `f` against the connection on the blocking thread pool.
+    async fn with_conn<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Connection) -> Result<T> + Send + 'static,
+    {
+        let conn = Arc::clone(&self.conn);
+        tokio::task::spawn_blocking(move || {
+            let guard = conn
+                .lock()
+                .map_err(|_| StoreError::Other("sqlite connection lock poisoned".into()))?;
+            f(&guard)
+        })
+        .await
+        .map_err(|e| StoreError::Other(e.to_string()))?
+    }
+}
+
+fn sql_err(e: rusqlite::Error) -> StoreError {
+    StoreError::Other(format!("sqlite: {e}"))
+}
+
+fn outcome_str(receipt: &Receipt) -> Result<String> {
+    match serde_json::to_value(&receipt.outcome)? {
+        serde_json::Value::String(s) => Ok(s),
+        other => Ok(other.to_string()),
+    }
+}
+
+fn decode_rows(rows: Vec<String>) -> Result<Vec<Receipt>> {
+    rows.iter()
+        .map(|json| serde_json::from_str(json).map_err(StoreError::from))
+        .collect()
+}
+
+#[async_trait]
+impl ReceiptStore for SqliteReceiptStore {
+    async fn store(&self, receipt: &Receipt) -> Result<()> {
+        let id = receipt.meta.run_id.to_string();
+        let work_order_id = receipt.meta.work_order_id.to_string();
+        let backend = receipt.backend.id.clone();
+        let outcome = outcome_str(receipt)?;
+        let started_at = receipt.meta.started_at.to_rfc3339();
+        let json = serde_json::to_string(receipt)?;
+        self.with_conn(move |conn| {
+            let inserted = conn
+                .execute(
+                    "INSERT OR IGNORE INTO receipts
+                     (run_id, work_order_id, backend, outcome, started_at, receipt)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![id, work_order_id, backend, outcome, started_at, json],
+                )
+                .map_err(sql_err)?;
+            if inserted == 0 {
+                return Err(StoreError::DuplicateId(id));
+            }
+            debug!(id = %id, "stored receipt in sqlite");
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<Receipt>> {
+        let id = id.to_string();
+        let json: Option<String> = self
+            .with_conn(move |conn| {
+                conn.query_row(
+                    "SELECT receipt FROM receipts WHERE run_id = ?1",
+                    params![id],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(sql_err)
+            })
+            .await?;
+        json.map(|j| serde_json::from_str(&j).map_err(StoreError::from))
+            .transpose()
+    }
+
+    async fn list(&self, filter: ReceiptFilter) -> Result<Vec<Receipt>> {
+        let outcome = match &filter.outcome {
+            Some(o) => Some(match serde_json::to_value(o)? {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            }),
+            None => None,
+        };
+        let backend = filter.backend.clone();
+        let work_order_id = filter.work_order_id.clone();
+        let rows = self
+            .with_conn(move |conn| {
+                let mut stmt = conn
+                    .prepare(
+                        "SELECT receipt FROM receipts
+                         WHERE (?1 IS NULL OR outcome = ?1)
+                           AND (?2 IS NULL OR backend = ?2)
+                           AND (?3 IS NULL OR work_order_id = ?3)
+                         ORDER BY seq",
+                    )
+                    .map_err(sql_err)?;
+                stmt.query_map(params![outcome, backend, work_order_id], |row| row.get(0))
+                    .map_err(sql_err)?
+                    .collect::<std::result::Result<Vec<String>, _>>()
+                    .map_err(sql_err)
+            })
+            .await?;
+        let matched = decode_rows(rows)?
+            .into_iter()
+            .filter(|r| filter.matches(r))
+            .collect();
+        Ok(filter.paginate(matched))
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool> {
+        let id = id.to_string();
+        self.with_conn(move |conn| {
+            let removed = conn
+                .execute("DELETE FROM receipts WHERE run_id = ?1", params![id])
+                .map_err(sql_err)?;
+            Ok(removed > 0)
+        })
+        .await
+    }
+
+    async fn count(&self) -> Result<usize> {
+        self.with_conn(|conn| {
+            let n: i64 = conn
+                .query_row("SELECT COUNT(*) FROM receipts", [], |row| row.get(0))
+                .map_err(sql_err)?;
+            Ok(usize::try_from(n).unwrap_or_default())
+        })
+        .await
+    }
+
+    async fn get_by_work_order_id(&self, work_order_id: &str) -> Result<Vec<Receipt>> {
+        let work_order_id = work_order_id.to_string();
+        let rows = self
+            .with_conn(move |conn| {
+                let mut stmt = conn
+                    .prepare("SELECT receipt FROM receipts WHERE work_order_id = ?1 ORDER BY seq")
+                    .map_err(sql_err)?;
+                stmt.query_map(params![work_order_id], |row| row.get(0))
+                    .map_err(sql_err)?
+                    .collect::<std::result::Result<Vec<String>, _>>()
+                    .map_err(sql_err)
+            })
+            .await?;
+        decode_rows(rows)
+    }
+}
@@ -7,6 +7,9 @@ use uuid::Uuid;
 
 use abp_core::{Outcome, Receipt};
 
+#[cfg(feature = "sqlite")]
+use crate::SqliteReceiptStore;
+use crate::billing::{BillingRecord, billing_ledger, export_billing_csv, export_billing_parquet};
 use crate::chain::{ChainValidationError, validate_chain};
 use crate::diff::diff_receipts;
 use crate::export::{
@@ -16,7 +19,7 @@ use crate::filter::ReceiptFilter;
 use crate::index::ReceiptIndex;
 use crate::retention::ReceiptRetention;
 use crate::stats::ReceiptStats;
-use crate::{FileReceiptStore, InMemoryReceiptStore, ReceiptStore};
+use crate::{FileReceiptStore, InMemoryReceiptStore, JsonlDirReceiptStore, ReceiptStore};
 
 // ── Helpers ────────────────────────────────────────────────────────
 
@@ -2220,3 +2223,303 @@ async fn file_store_multiple_deletes() {
     let remaining = store.list(ReceiptFilter::default()).await.unwrap();
     assert_eq!(remaining[0].backend.id, "b");
 }
+
+// ── Billing export ─────────────────────────────────────────────────
+
+fn make_billed_receipt(tenant: &str, ts: chrono::DateTime<Utc>) -> Receipt {
+    let mut r = make_receipt_at("openai", Outcome::Complete, ts);
+    r.usage_raw = serde_json::json!({"tenant": tenant, "model": "gpt-4o"});
+    r.usage.input_tokens = Some(120);
+    r.usage.output_tokens = Some(30);
+    r.usage.estimated_cost_usd = Some(0.0045);
+    r.meta.duration_ms = 850;
+    r
+}
+
+#[tokio::test]
+async fn billing_ledger_covers_time_range_in_order() {
+    let store = InMemoryReceiptStore::new();
+    let t = |h| Utc.with_ymd_and_hms(2025, 3, 1, h, 0, 0).unwrap();
+    store
+        .store(&make_billed_receipt("late", t(12)))
+        .await
+        .unwrap();
+    store
+        .store(&make_billed_receipt("early", t(9)))
+        .await
+        .unwrap();
+    store
+        .store(&make_billed_receipt("outside", t(20)))
+        .await
+        .unwrap();
+
+    let ledger = billing_ledger(&store, t(8), t(13)).await.unwrap();
+    let tenants: Vec<_> = ledger.iter().map(|r| r.tenant.as_deref()).collect();
+    assert_eq!(tenants, [Some("early"), Some("late")]);
+    assert_eq!(ledger[0].model.as_deref(), Some("gpt-4o"));
+    assert_eq!(ledger[0].input_tokens, Some(120));
+    assert_eq!(ledger[0].cost_usd, Some(0.0045));
+    assert_eq!(ledger[0].duration_ms, 850);
+}
+
+#[test]
+fn billing_csv_quotes_and_leaves_missing_values_empty() {
+    let ts = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+    let billed = BillingRecord::from_receipt(&make_billed_receipt("acme, inc", ts));
+    let bare = BillingRecord::from_receipt(&make_receipt("mock", Outcome::Failed));
+
+    let csv = export_billing_csv(&[billed.clone(), bare]);
+    let lines: Vec<_> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("run_id,work_order_id,tenant,backend,model,"));
+    assert_eq!(
+        lines[1],
+        format!(
+            "{},{},\"acme, inc\",openai,gpt-4o,2025-03-01T09:00:00+00:00,850,120,30,,,0.0045,complete",
+            billed.run_id,
+            Uuid::nil()
+        )
+    );
+    assert!(lines[2].ends_with(",,,,,,failed"));
+}
+
+#[test]
+fn billing_parquet_has_magic_and_footer() {
+    let ts = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+    let records: Vec<_> = (0..20)
+        .map(|i| BillingRecord::from_receipt(&make_billed_receipt(&format!("t{i}"), ts)))
+        .collect();
+    let bytes = export_billing_parquet(&records);
+
+    assert_eq!(&bytes[..4], b"PAR1");
+    assert_eq!(&bytes[bytes.len() - 4..], b"PAR1");
+    let footer_len =
+        u32::from_le_bytes(bytes[bytes.len() - 8..bytes.len() - 4].try_into().unwrap()) as usize;
+    assert!(footer_len + 12 < bytes.len());
+    let footer = &bytes[bytes.len() - 8 - footer_len..bytes.len() - 8];
+    for column in ["run_id", "tenant", "cost_usd", "outcome"] {
+        assert!(footer.windows(column.len()).any(|w| w == column.as_bytes()));
+    }
+    // Every tenant value is stored in the data pages.
+    assert!(bytes.windows(3).any(|w| w == b"t19"));
+}
+
+#[test]
+fn billing_parquet_reads_back_with_parquet_reader() {
+    use std::io::Write;
+
+    use parquet::basic::{ConvertedType, Type as PhysicalType};
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::{Field, RowAccessor};
+
+    let ts = Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap();
+    let mut records: Vec<_> = (0..20)
+        .map(|i| BillingRecord::from_receipt(&make_billed_receipt(&format!("t{i}"), ts)))
+        .collect();
+    records[3].tenant = None;
+    records[3].cost_usd = None;
+
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(&export_billing_parquet(&records)).unwrap();
+    let reader = SerializedFileReader::new(file).unwrap();
+
+    let meta = reader.metadata().file_metadata();
+    assert_eq!(meta.num_rows(), 20);
+    let schema = meta.schema_descr();
+    let names: Vec<_> = schema.columns().iter().map(|c| c.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "run_id",
+            "work_order_id",
+            "tenant",
+            "backend",
+            "model",
+            "started_at",
+            "duration_ms",
+            "input_tokens",
+            "output_tokens",
+            "cache_read_tokens",
+            "cache_write_tokens",
+            "cost_usd",
+            "outcome",
+        ]
+    );
+    assert_eq!(schema.column(0).converted_type(), ConvertedType::UTF8);
+    assert_eq!(
+        schema.column(5).converted_type(),
+        ConvertedType::TIMESTAMP_MILLIS
+    );
+    assert_eq!(schema.column(6).physical_type(), PhysicalType::INT64);
+    assert_eq!(schema.column(11).physical_type(), PhysicalType::DOUBLE);
+
+    let rows: Vec<_> = reader
+        .get_row_iter(None)
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(rows.len(), 20);
+    for (row, record) in rows.iter().zip(&records) {
+        assert_eq!(row.get_string(0).unwrap(), &record.run_id.to_string());
+        assert_eq!(row.get_string(3).unwrap(), "openai");
+        assert_eq!(row.get_string(4).unwrap(), "gpt-4o");
+        assert_eq!(row.get_timestamp_millis(5).unwrap(), ts.timestamp_millis());
+        assert_eq!(row.get_long(6).unwrap(), 850);
+        assert_eq!(row.get_long(7).unwrap(), 120);
+        assert_eq!(row.get_long(8).unwrap(), 30);
+        assert_eq!(row.get_string(12).unwrap(), "complete");
+    }
+    assert_eq!(rows[19].get_string(2).unwrap(), "t19");
+    assert_eq!(rows[0].get_double(11).unwrap(), 0.0045);
+
+    // Missing values round-trip as nulls.
+    let fields: Vec<_> = rows[3].get_column_iter().map(|(_, f)| f).collect();
+    assert_eq!(fields[2], &Field::Null);
+    assert_eq!(fields[9], &Field::Null);
+    assert_eq!(fields[11], &Field::Null);
+}
+
+// ── Persistent backends ────────────────────────────────────────────
+
+/// Exercise the shared `ReceiptStore` contract against a fresh store.
+async fn check_store_contract(store: &dyn ReceiptStore) {
+    let woid = Uuid::new_v4();
+    let day1 = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+    let day2 = Utc.with_ymd_and_hms(2025, 6, 2, 12, 0, 0).unwrap();
+    let mut a = make_receipt_at("mock", Outcome::Complete, day1);
+    a.meta.work_order_id = woid;
+    let mut b = make_receipt_at("openai", Outcome::Failed, day2);
+    b.meta.work_order_id = woid;
+    let c = make_receipt_at("mock", Outcome::Failed, day2);
+    for r in [&a, &b, &c] {
+        store.store(r).await.unwrap();
+    }
+    assert!(matches!(
+        store.store(&a).await,
+        Err(crate::StoreError::DuplicateId(_))
+    ));
+    assert_eq!(store.count().await.unwrap(), 3);
+
+    let got = store
+        .get(&b.meta.run_id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(got.meta.run_id, b.meta.run_id);
+    assert!(
+        store
+            .get(&Uuid::new_v4().to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    let for_wo = store.get_by_work_order_id(&woid.to_string()).await.unwrap();
+    let ids: Vec<_> = for_wo.iter().map(|r| r.meta.run_id).collect();
+    assert_eq!(ids, [a.meta.run_id, b.meta.run_id]);
+
+    let failed_mock = store
+        .list(ReceiptFilter {
+            outcome: Some(Outcome::Failed),
+            backend: Some("mock".into()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(failed_mock.len(), 1);
+    assert_eq!(failed_mock[0].meta.run_id, c.meta.run_id);
+
+    let page = store
+        .list(ReceiptFilter {
+            offset: Some(1),
+            limit: Some(1),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(page[0].meta.run_id, b.meta.run_id);
+
+    assert!(store.delete(&a.meta.run_id.to_string()).await.unwrap());
+    assert!(!store.delete(&a.meta.run_id.to_string()).await.unwrap());
+    assert_eq!(store.count().await.unwrap(), 2);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_store_contract() {
+    let store = SqliteReceiptStore::open_in_memory().unwrap();
+    check_store_contract(&store).await;
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_store_persists_across_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("receipts.db");
+    let r = make_receipt("mock", Outcome::Complete);
+    SqliteReceiptStore::open(&path)
+        .unwrap()
+        .store(&r)
+        .await
+        .unwrap();
+
+    let reopened = SqliteReceiptStore::open(&path).unwrap();
+    assert_eq!(reopened.count().await.unwrap(), 1);
+    let got = reopened
+        .get(&r.meta.run_id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(got.backend.id, "mock");
+}
+
+#[tokio::test]
+async fn jsonl_dir_store_contract() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = JsonlDirReceiptStore::new(dir.path().join("receipts"));
+    check_store_contract(&store).await;
+}
+
+#[tokio::test]
+async fn jsonl_dir_store_writes_one_file_per_day() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = JsonlDirReceiptStore::new(dir.path());
+    let day = Utc.with_ymd_and_hms(2025, 6, 1, 23, 59, 0).unwrap();
+    let a = make_receipt_at("mock", Outcome::Complete, day);
+    let b = make_receipt_at(
+        "mock",
+        Outcome::Complete,
+        day + chrono::Duration::minutes(2),
+    );
+    store.store(&a).await.unwrap();
+    store.store(&b).await.unwrap();
+
+    assert_eq!(store.file_for(&a), dir.path().join("2025-06-01.jsonl"));
+    assert_eq!(store.file_for(&b), dir.path().join("2025-06-02.jsonl"));
+    assert!(store.file_for(&a).exists() && store.file_for(&b).exists());
+
+    // Deleting the only receipt of a day removes that day's file.
+    store.delete(&b.meta.run_id.to_string()).await.unwrap();
+    assert!(!store.file_for(&b).exists());
+    let reopened = JsonlDirReceiptStore::new(dir.path());
+    assert_eq!(reopened.count().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn trait_get_by_work_order_id_defaults_to_list() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = FileReceiptStore::new(dir.path().join("r.jsonl"));
+    let mut r = make_receipt("mock", Outcome::Complete);
+    r.meta.work_order_id = Uuid::new_v4();
+    store.store(&r).await.unwrap();
+    store
+        .store(&make_receipt("mock", Outcome::Complete))
+        .await
+        .unwrap();
+    let store: &dyn ReceiptStore = &store;
+    let got = store
+        .get_by_work_order_id(&r.meta.work_order_id.to_string())
+        .await
+        .unwrap();
+    assert_eq!(got.len(), 1);
+}
@@ -32,6 +32,7 @@ pub struct ReceiptBuilder {
     adapter_version: Option<String>,
     model: Option<String>,
     dialect: Option<String>,
+    provenance: crate::provenance::Provenance,
     capabilities: CapabilityManifest,
     mode: ExecutionMode,
     outcome: Outcome,
@@ -57,6 +58,7 @@ impl ReceiptBuilder {
             adapter_version: None,
             model: None,
             dialect: None,
+            provenance: crate::provenance::Provenance::default(),
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
             outcome: Outcome::Complete,
@@ -120,6 +122,13 @@ impl ReceiptBuilder {
         self
     }
 
+    /// Record a provider request (stored in `usage_raw.provenance`).
+    #[must_use]
+    pub fn provider_request(mut self, request: crate::provenance::ProviderRequest) -> Self {
+        self.provenance.push(request);
+        self
+    }
+
     /// Set the run start timestamp.
     #[must_use]
     pub fn started_at(mut self, dt: DateTime<Utc>) -> Self {
@@ -252,8 +261,9 @@ impl ReceiptBuilder {
 
         // Merge model/dialect into usage_raw if set.
         let usage_raw = self.build_usage_raw();
+        let provenance = self.provenance;
 
-        Receipt {
+        let mut receipt = Receipt {
             meta: RunMetadata {
                 run_id: self.run_id.unwrap_or_else(Uuid::new_v4),
                 work_order_id: self.work_order_id,
@@ -276,7 +286,11 @@ impl ReceiptBuilder {
             verification: self.verification,
             outcome: self.outcome,
             receipt_sha256: None,
+        };
+        if !provenance.is_empty() {
+            provenance.attach(&mut receipt);
         }
+        receipt
     }
 
     /// Build the receipt and compute its hash.
@@ -62,13 +62,14 @@ impl CanonicalReceipt {
 
 /// Produce deterministic JSON bytes from a canonical receipt.
 ///
+/// Uses [`abp_core::canonical`], so keys are emitted in sorted order.
+///
 /// # Errors
 ///
 /// Returns [`ContractError::Json`] if serialization fails.
 pub fn canonicalize(receipt: &Receipt) -> Result<Vec<u8>, ContractError> {
     let canonical = CanonicalReceipt::from_receipt(receipt);
-    let bytes = serde_json::to_vec(&canonical)?;
-    Ok(bytes)
+    abp_core::canonical::to_canonical_bytes(&canonical)
 }
 
 /// Compute the hex-encoded SHA-256 hash of the canonical form.
@@ -23,19 +23,29 @@ mod diff;
 pub mod enrich;
 /// Receipt export in multiple formats for bulk reporting.
 pub mod export;
+/// Provider request identifiers normalized into a receipt `provenance` block.
+pub mod provenance;
+/// Memory-mapped, streaming reader for large JSON-lines receipt archives.
+pub mod reader;
 /// Serialization in JSON and compact binary formats.
 pub mod serde_formats;
+/// Ed25519 signing and verification of receipt chains.
+pub mod signing;
 /// Standalone receipt statistics computation.
 pub mod stats;
 /// Pluggable receipt storage with an in-memory implementation.
 pub mod store;
 /// Receipt aggregation summaries (success rate, tokens, error distribution).
 pub mod summary;
+/// Receipt traces exported as Chrome-tracing / Perfetto timelines.
+pub mod timeline;
 mod validate;
 /// Receipt verification and batch auditing utilities.
 pub mod verify;
 /// Receipt format versioning and compatibility checking.
 pub mod version;
+/// Verification of receipts written under earlier contract versions.
+pub mod versioned;
 
 pub use builder::ReceiptBuilder;
 pub use chain::{
@@ -43,8 +53,11 @@ pub use chain::{
     ExportedEntry, ReceiptChain, TamperEvidence, TamperKind,
 };
 pub use diff::{FieldDiff, ReceiptDiff, diff_receipts};
+pub use provenance::{Provenance, ProviderRequest};
+pub use signing::{ReceiptSigningKey, ReceiptVerifyingKey, SignedChain, SignedEntry, SigningError};
 pub use validate::{ReceiptValidator, ValidationError};
 pub use verify::{AuditIssue, AuditReport, ReceiptAuditor, VerificationResult, verify_receipt};
+pub use versioned::{VerifierSet, VersionedReceipt, VersionedVerifyError, verify_any};
 
 // Re-export core receipt types so consumers can depend on abp-receipt alone.
 pub use abp_core::{
@@ -59,8 +72,9 @@ use sha2::{Digest, Sha256};
 /// The `receipt_sha256` field is forced to `null` before serialization so
 /// that the output is independent of any previously stored hash.
 ///
-/// Keys are sorted (serde_json uses `BTreeMap` internally) and numbers
-/// are serialized consistently, making the output deterministic.
+/// Rendered with [`abp_core::canonical`]: keys are sorted, no whitespace is
+/// emitted, and numbers use the RFC 8785 format, making the output
+/// deterministic across serializer versions.
 ///
 /// # Errors
 ///
@@ -81,7 +95,7 @@ pub fn canonicalize(receipt: &Receipt) -> Result<String, ContractError> {
     if let serde_json::Value::Object(map) = &mut v {
         map.insert("receipt_sha256".to_string(), serde_json::Value::Null);
     }
-    Ok(serde_json::to_string(&v)?)
+    Ok(abp_core::canonical::value_to_canonical_string(&v))
 }
 
 /// Compute the hex-encoded SHA-256 hash of the canonical receipt form.
@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Provider request provenance — trace outputs back to the provider call.
+//!
+//! Providers tag each response with identifiers that their support teams can
+//! look up: OpenAI's completion `id` and `system_fingerprint`, Anthropic's
+//! message `id` and `request-id` header, Gemini's `responseId`. A
+//! [`Provenance`] block gathers these, normalized to [`ProviderRequest`]s,
+//! under `usage_raw["provenance"]` so they are covered by the receipt hash.
+//!
+//! [`collect`](crate::provenance::collect) finds identifiers in a receipt's
+//! `usage_raw` and in the raw vendor payloads that passthrough events carry in
+//! `ext["raw_message"]`; [`record`](crate::provenance::record) writes the
+//! result back into the receipt.
+
+use abp_core::Receipt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Key under `receipt.usage_raw` holding the [`Provenance`] block.
+pub const PROVENANCE_KEY: &str = "provenance";
+
+/// Identifiers of one provider request, as reported by the provider.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderRequest {
+    /// Provider name, e.g. `"openai"`, `"anthropic"`, `"gemini"`.
+    pub provider: String,
+    /// Transport-level request id (`x-request-id` / `request-id` header).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Provider id of the generated response (`chatcmpl-…`, `msg_…`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_id: Option<String>,
+    /// Backend configuration fingerprint (OpenAI `system_fingerprint`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_fingerprint: Option<String>,
+    /// Model version that actually served the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+impl ProviderRequest {
+    /// A request from `provider` with no identifiers yet.
+    #[must_use]
+    pub fn new(provider: impl Into<String>) -> Self {
+        Self {
+            provider: provider.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the transport-level request id.
+    #[must_use]
+    pub fn request_id(mut self, id: impl Into<String>) -> Self {
+        self.request_id = Some(id.into());
+        self
+    }
+
+    /// Set the provider response id.
+    #[must_use]
+    pub fn response_id(mut self, id: impl Into<String>) -> Self {
+        self.response_id = Some(id.into());
+        self
+    }
+
+    /// Set the system fingerprint.
+    #[must_use]
+    pub fn system_fingerprint(mut self, fp: impl Into<String>) -> Self {
+        self.system_fingerprint = Some(fp.into());
+        self
+    }
+
+    /// Set the serving model.
+    #[must_use]
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    /// Recognize a raw provider response (or stream event) and pull out its
+    /// identifiers.
+    ///
+    /// Returns `None` if `raw` does not look like an OpenAI, Anthropic, or
+    /// Gemini payload or carries no identifier.
+    #[must_use]
+    pub fn from_raw(raw: &Value) -> Option<Self> {
+        let obj = raw.as_object()?;
+        let s = |v: Option<&Value>| v.and_then(Value::as_str).map(str::to_string);
+        let header_id = s(obj.get("request_id"))
+            .or_else(|| s(obj.get("_request_id")))
+            .or_else(|| s(obj.get("x-request-id")))
+            .or_else(|| s(obj.get("request-id")));
+
+        let object = obj.get("object").and_then(Value::as_str).unwrap_or("");
+        let kind = obj.get("type").and_then(Value::as_str).unwrap_or("");
+
+        let req = if object.starts_with("chat.completion") || object == "response" {
+            Self {
+                provider: "openai".into(),
+                request_id: header_id,
+                response_id: s(obj.get("id")),
+                system_fingerprint: s(obj.get("system_fingerprint")),
+                model: s(obj.get("model")),
+            }
+        } else if kind == "message" || kind == "message_start" {
+            let message = if kind == "message_start" {
+                obj.get("message").and_then(Value::as_object)?
+            } else {
+                obj
+            };
+            Self {
+                provider: "anthropic".into(),
+                request_id: header_id,
+                response_id: s(message.get("id")),
+                system_fingerprint: None,
+                model: s(message.get("model")),
+            }
+        } else if obj.contains_key("responseId") || obj.contains_key("modelVersion") {
+            Self {
+                provider: "gemini".into(),
+                request_id: header_id,
+                response_id: s(obj.get("responseId")),
+                system_fingerprint: None,
+                model: s(obj.get("modelVersion")),
+            }
+        } else {
+            return None;
+        };
+
+        req.has_identifier().then_some(req)
+    }
+
+    /// Whether any provider-issued identifier is present.
+    #[must_use]
+    pub fn has_identifier(&self) -> bool {
+        self.request_id.is_some() || self.response_id.is_some() || self.system_fingerprint.is_some()
+    }
+}
+
+/// Normalized provider identifiers for every provider request in a run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Provider requests, in the order they were observed.
+    pub requests: Vec<ProviderRequest>,
+}
+
+impl Provenance {
+    /// Read the provenance block a receipt already carries, if any.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        let block = receipt.usage_raw.get(PROVENANCE_KEY)?;
+        serde_json::from_value(block.clone()).ok()
+    }
+
+    /// Add a request unless an identical one is already recorded.
+    pub fn push(&mut self, request: ProviderRequest) {
+        if !self.requests.contains(&request) {
+            self.requests.push(request);
+        }
+    }
+
+    /// Whether no requests are recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Write this block into `receipt.usage_raw`, replacing any existing one.
+    ///
+    /// A non-object `usage_raw` is preserved under `"original"`.
+    pub fn attach(&self, receipt: &mut Receipt) {
+        if !receipt.usage_raw.is_object() {
+            receipt.usage_raw = serde_json::json!({ "original": receipt.usage_raw.take() });
+        }
+        if let Some(map) = receipt.usage_raw.as_object_mut()
+            && let Ok(value) = serde_json::to_value(self)
+        {
+            map.insert(PROVENANCE_KEY.to_string(), value);
+        }
+    }
+}
+
+/// Gather provider identifiers from everywhere a receipt may carry them.
+///
+/// Sources, in order: an existing `usage_raw["provenance"]` block, the
+/// `usage_raw` object itself (sidecars often forward the provider response
+/// metadata there), and `ext["raw_message"]` of each trace event.
+#[must_use]
+pub fn collect(receipt: &Receipt) -> Provenance {
+    let mut provenance = Provenance::from_receipt(receipt).unwrap_or_default();
+    if let Some(req) = ProviderRequest::from_raw(&receipt.usage_raw) {
+        provenance.push(req);
+    }
+    for event in &receipt.trace {
+        if let Some(req) = event
+            .ext
+            .as_ref()
+            .and_then(|ext| ext.get("raw_message"))
+            .and_then(ProviderRequest::from_raw)
+        {
+            provenance.push(req);
+        }
+    }
+    provenance
+}
+
+/// [`collect`] provenance and attach it to the receipt if any was found.
+///
+/// Returns `true` if the receipt now carries a provenance block.
+pub fn record(receipt: &mut Receipt) -> bool {
+    let provenance = collect(receipt);
+    if provenance.is_empty() {
+        return false;
+    }
+    provenance.attach(receipt);
+    true
+}
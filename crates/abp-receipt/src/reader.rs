@@ -0,0 +1,463 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Zero-copy reading of large JSON-lines receipt archives.
+//!
+//! [`ReceiptArchiveReader`](crate::reader::ReceiptArchiveReader) memory-maps
+//! an archive with one receipt per line and hands out
+//! [`RawReceipt`](crate::reader::RawReceipt) views that borrow from the
+//! mapping. A view can decode just the receipt's metadata
+//! ([`header`](crate::reader::RawReceipt::header)), walk its trace one event
+//! at a time ([`trace`](crate::reader::RawReceipt::trace)), or verify its
+//! hash ([`verify`](crate::reader::RawReceipt::verify)) — none of which
+//! materialize the full trace, so archives far larger than memory can be
+//! audited.
+//!
+//! Streaming verification produces the same digest as
+//! [`compute_hash`](crate::compute_hash): each top-level field is rendered in
+//! canonical form and fed to the hasher in sorted key order, with the trace
+//! rendered event by event.
+
+use std::fs::File;
+use std::path::Path;
+
+use abp_core::{
+    AgentEvent, ArtifactRef, BackendIdentity, CapabilityManifest, ExecutionMode, Outcome, Receipt,
+    RunMetadata, UsageNormalized, VerificationReport,
+};
+use memmap2::Mmap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Errors from reading a receipt archive.
+#[derive(Debug, thiserror::Error)]
+pub enum ReaderError {
+    /// The archive could not be opened or mapped.
+    #[error("failed to read receipt archive: {0}")]
+    Io(#[from] std::io::Error),
+    /// A line is not a well-formed receipt.
+    #[error("line {line}: {message}")]
+    Malformed {
+        /// 1-based line number in the archive.
+        line: usize,
+        /// What was wrong.
+        message: String,
+    },
+}
+
+/// Everything in a receipt except its trace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReceiptHeader {
+    /// Run metadata.
+    pub meta: RunMetadata,
+    /// Backend that produced the receipt.
+    pub backend: BackendIdentity,
+    /// Capabilities the backend advertised.
+    pub capabilities: CapabilityManifest,
+    /// Execution mode.
+    #[serde(default)]
+    pub mode: ExecutionMode,
+    /// Raw vendor usage payload.
+    pub usage_raw: serde_json::Value,
+    /// Normalized usage counters.
+    pub usage: UsageNormalized,
+    /// Artifacts produced by the run.
+    pub artifacts: Vec<ArtifactRef>,
+    /// Verification report.
+    pub verification: VerificationReport,
+    /// Run outcome.
+    pub outcome: Outcome,
+    /// Stored receipt hash.
+    pub receipt_sha256: Option<String>,
+}
+
+/// Outcome of verifying every receipt in an archive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveReport {
+    /// Number of receipts read.
+    pub receipts: usize,
+    /// 1-based line numbers of receipts whose stored hash did not verify.
+    pub invalid_lines: Vec<usize>,
+}
+
+impl ArchiveReport {
+    /// Whether every receipt verified.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        self.invalid_lines.is_empty()
+    }
+}
+
+// ── Archive ────────────────────────────────────────────────────────
+
+/// Memory-mapped JSON-lines receipt archive.
+#[derive(Debug)]
+pub struct ReceiptArchiveReader {
+    map: Option<Mmap>,
+}
+
+impl ReceiptArchiveReader {
+    /// Map the archive at `path`.
+    ///
+    /// The file must not be truncated while the reader is alive.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReaderError::Io`] if the file cannot be opened or mapped.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ReaderError> {
+        let file = File::open(path)?;
+        if file.metadata()?.len() == 0 {
+            return Ok(Self { map: None });
+        }
+        // SAFETY: the mapping is read-only and only ever viewed as bytes; the
+        // caller is told not to truncate the file while the reader is alive.
+        #[allow(unsafe_code)]
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(Self { map: Some(map) })
+    }
+
+    /// The mapped bytes.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.map.as_deref().unwrap_or_default()
+    }
+
+    /// Iterate the receipts in file order without decoding them.
+    pub fn iter(&self) -> impl Iterator<Item = RawReceipt<'_>> {
+        raw_receipts(self.as_bytes())
+    }
+
+    /// Verify every receipt's hash, streaming each trace.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReaderError::Malformed`] at the first line that is not a
+    /// receipt.
+    pub fn verify_all(&self) -> Result<ArchiveReport, ReaderError> {
+        let mut report = ArchiveReport::default();
+        for raw in self.iter() {
+            report.receipts += 1;
+            if !raw.verify()? {
+                report.invalid_lines.push(raw.line());
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Split JSON-lines `bytes` into receipt views, skipping blank lines.
+pub fn raw_receipts(bytes: &[u8]) -> impl Iterator<Item = RawReceipt<'_>> {
+    bytes
+        .split(|b| *b == b'\n')
+        .enumerate()
+        .filter(|(_, line)| !line.trim_ascii().is_empty())
+        .map(|(i, line)| RawReceipt {
+            bytes: line.trim_ascii(),
+            line: i + 1,
+        })
+}
+
+// ── RawReceipt ─────────────────────────────────────────────────────
+
+/// One undecoded receipt borrowed from an archive.
+#[derive(Debug, Clone, Copy)]
+pub struct RawReceipt<'a> {
+    bytes: &'a [u8],
+    line: usize,
+}
+
+impl<'a> RawReceipt<'a> {
+    /// View `bytes` (a single JSON receipt) as a raw receipt.
+    #[must_use]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes: bytes.trim_ascii(),
+            line: 1,
+        }
+    }
+
+    /// 1-based line number in the archive.
+    #[must_use]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The receipt's JSON bytes.
+    #[must_use]
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Decode everything except the trace.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReaderError::Malformed`] if the line is not a receipt.
+    pub fn header(&self) -> Result<ReceiptHeader, ReaderError> {
+        self.decode(self.bytes)
+    }
+
+    /// Decode the whole receipt, trace included.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReaderError::Malformed`] if the line is not a receipt.
+    pub fn receipt(&self) -> Result<Receipt, ReaderError> {
+        self.decode(self.bytes)
+    }
+
+    /// Iterate the trace, decoding one event at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReaderError::Malformed`] if the receipt has no `trace`
+    /// array.
+    pub fn trace(&self) -> Result<TraceIter<'a>, ReaderError> {
+        let fields = self.fields()?;
+        let trace = fields
+            .iter()
+            .find(|(k, _)| *k == "trace")
+            .map(|(_, v)| *v)
+            .ok_or_else(|| self.malformed("missing trace"))?;
+        TraceIter::new(trace, self.line)
+    }
+
+    /// Recompute the receipt hash without materializing the trace.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReaderError::Malformed`] if the line is not a receipt.
+    pub fn compute_hash(&self) -> Result<String, ReaderError> {
+        let h = self.header()?;
+        // Fields before and after `trace`, in canonical (sorted) key order.
+        let before = [
+            ("artifacts", self.canonical(&h.artifacts)?),
+            ("backend", self.canonical(&h.backend)?),
+            ("capabilities", self.canonical(&h.capabilities)?),
+            ("meta", self.canonical(&h.meta)?),
+            ("mode", self.canonical(&h.mode)?),
+            ("outcome", self.canonical(&h.outcome)?),
+            ("receipt_sha256", "null".to_string()),
+        ];
+        let after = [
+            ("usage", self.canonical(&h.usage)?),
+            ("usage_raw", self.canonical(&h.usage_raw)?),
+            ("verification", self.canonical(&h.verification)?),
+        ];
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"{");
+        for (name, value) in &before {
+            hasher.update(format!("\"{name}\":{value},"));
+        }
+        hasher.update(b"\"trace\":[");
+        for (i, event) in self.trace()?.enumerate() {
+            if i > 0 {
+                hasher.update(b",");
+            }
+            hasher.update(self.canonical(&event?)?);
+        }
+        hasher.update(b"]");
+        for (name, value) in &after {
+            hasher.update(format!(",\"{name}\":{value}"));
+        }
+        hasher.update(b"}");
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Whether the stored hash matches the recomputed one.
+    ///
+    /// Like [`verify_hash`](crate::verify_hash), a receipt without a stored
+    /// hash is considered valid.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReaderError::Malformed`] if the line is not a receipt.
+    pub fn verify(&self) -> Result<bool, ReaderError> {
+        match self.header()?.receipt_sha256 {
+            None => Ok(true),
+            Some(stored) => Ok(stored == self.compute_hash()?),
+        }
+    }
+
+    fn fields(&self) -> Result<Vec<(&'a str, &'a [u8])>, ReaderError> {
+        object_fields(self.bytes).map_err(|m| self.malformed(m))
+    }
+
+    fn canonical<T: Serialize>(&self, value: &T) -> Result<String, ReaderError> {
+        abp_core::canonical::to_canonical_string(value).map_err(|e| self.malformed(e.to_string()))
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, ReaderError> {
+        serde_json::from_slice(bytes).map_err(|e| self.malformed(e.to_string()))
+    }
+
+    fn malformed(&self, message: impl Into<String>) -> ReaderError {
+        ReaderError::Malformed {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+}
+
+// ── Trace iteration ────────────────────────────────────────────────
+
+/// Lazily decodes the events of one receipt's trace.
+#[derive(Debug, Clone)]
+pub struct TraceIter<'a> {
+    rest: &'a [u8],
+    line: usize,
+    done: bool,
+}
+
+impl<'a> TraceIter<'a> {
+    fn new(array: &'a [u8], line: usize) -> Result<Self, ReaderError> {
+        let inner = array
+            .strip_prefix(b"[")
+            .and_then(|a| a.strip_suffix(b"]"))
+            .ok_or_else(|| ReaderError::Malformed {
+                line,
+                message: "trace is not an array".into(),
+            })?;
+        Ok(Self {
+            rest: inner,
+            line,
+            done: false,
+        })
+    }
+}
+
+impl Iterator for TraceIter<'_> {
+    type Item = Result<AgentEvent, ReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let rest = self.rest.trim_ascii_start();
+        if rest.is_empty() {
+            self.done = true;
+            return None;
+        }
+        let malformed = |message: String| {
+            Some(Err(ReaderError::Malformed {
+                line: self.line,
+                message,
+            }))
+        };
+        let end = match value_end(rest, 0) {
+            Ok(end) => end,
+            Err(m) => {
+                self.done = true;
+                return malformed(m.to_string());
+            }
+        };
+        let (event, tail) = rest.split_at(end);
+        let tail = tail.trim_ascii_start();
+        self.rest = match tail.split_first() {
+            Some((b',', tail)) => tail,
+            None => tail,
+            Some(_) => {
+                self.done = true;
+                return malformed("expected ',' between trace events".into());
+            }
+        };
+        Some(
+            serde_json::from_slice(event).map_err(|e| ReaderError::Malformed {
+                line: self.line,
+                message: format!("trace event: {e}"),
+            }),
+        )
+    }
+}
+
+// ── JSON scanning ──────────────────────────────────────────────────
+
+/// Split a JSON object into `(key, raw value)` pairs without decoding the
+/// values. Keys are returned as written (escapes are not processed).
+fn object_fields(bytes: &[u8]) -> Result<Vec<(&str, &[u8])>, &'static str> {
+    let mut fields = Vec::new();
+    let mut pos = skip_ws(bytes, 0);
+    if bytes.get(pos) != Some(&b'{') {
+        return Err("expected a JSON object");
+    }
+    pos = skip_ws(bytes, pos + 1);
+    if bytes.get(pos) == Some(&b'}') {
+        return Ok(fields);
+    }
+    loop {
+        if bytes.get(pos) != Some(&b'"') {
+            return Err("expected an object key");
+        }
+        let key_end = value_end(bytes, pos)?;
+        let key = std::str::from_utf8(&bytes[pos + 1..key_end - 1])
+            .map_err(|_| "object key is not UTF-8")?;
+        pos = skip_ws(bytes, key_end);
+        if bytes.get(pos) != Some(&b':') {
+            return Err("expected ':' after object key");
+        }
+        let start = skip_ws(bytes, pos + 1);
+        let end = value_end(bytes, start)?;
+        fields.push((key, &bytes[start..end]));
+        pos = skip_ws(bytes, end);
+        match bytes.get(pos) {
+            Some(b',') => pos = skip_ws(bytes, pos + 1),
+            Some(b'}') => return Ok(fields),
+            _ => return Err("expected ',' or '}' in object"),
+        }
+    }
+}
+
+fn skip_ws(bytes: &[u8], mut pos: usize) -> usize {
+    while bytes.get(pos).is_some_and(u8::is_ascii_whitespace) {
+        pos += 1;
+    }
+    pos
+}
+
+/// Index just past the JSON value starting at `start`.
+fn value_end(bytes: &[u8], start: usize) -> Result<usize, &'static str> {
+    let mut depth = 0usize;
+    let mut pos = start;
+    let mut in_string = false;
+    while let Some(&b) = bytes.get(pos) {
+        pos += 1;
+        if in_string {
+            match b {
+                b'\\' => pos += 1,
+                b'"' => {
+                    in_string = false;
+                    if depth == 0 {
+                        return Ok(pos);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match b {
+            b'"' => in_string = true,
+            b'{' | b'[' => depth += 1,
+            // A closing bracket at depth 0 ends a scalar value.
+            b'}' | b']' | b',' | b':' | b' ' | b'\t' | b'\r' | b'\n' if depth == 0 => {
+                return if pos - 1 > start {
+                    Ok(pos - 1)
+                } else {
+                    Err("expected a JSON value")
+                };
+            }
+            b'}' | b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(pos);
+                }
+            }
+            _ => {}
+        }
+    }
+    if depth == 0 && !in_string && pos > start {
+        Ok(pos)
+    } else {
+        Err("truncated JSON value")
+    }
+}
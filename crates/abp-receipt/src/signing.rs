@@ -0,0 +1,349 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Ed25519 signatures over receipt chains.
+//!
+//! `receipt_sha256` proves a receipt was not altered after hashing, but not
+//! who produced it. [`ReceiptChain::sign`] signs every chain entry — its
+//! sequence number, receipt hash, and the previous entry's hash — with a
+//! [`ReceiptSigningKey`], producing a [`SignedChain`] that can be stored next
+//! to the receipts and checked later against a trusted
+//! [`ReceiptVerifyingKey`]. Because each signature covers the previous hash,
+//! entries cannot be dropped, reordered, or spliced in without detection.
+
+use std::fmt;
+
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{ED25519, Ed25519KeyPair, KeyPair, UnparsedPublicKey};
+use serde::{Deserialize, Serialize};
+
+use crate::chain::{ChainError, ReceiptChain};
+
+/// Domain separator prefixed to every signed message; bumped if the signed
+/// layout changes.
+pub const SIGNATURE_VERSION: &str = "abp-receipt-chain/v1";
+
+/// Errors from signing or verifying a receipt chain.
+#[derive(Debug, thiserror::Error)]
+pub enum SigningError {
+    /// A key was not 32 bytes of hex.
+    #[error("invalid signing key: {0}")]
+    InvalidKey(String),
+    /// The system random number generator or key construction failed.
+    #[error("receipt signing crypto failure")]
+    Crypto,
+    /// A receipt in the chain has no `receipt_sha256` to sign.
+    #[error("receipt at chain index {index} has no hash")]
+    UnhashedReceipt {
+        /// Index of the unhashed receipt.
+        index: usize,
+    },
+    /// The signatures were made with a different key than the trusted one.
+    #[error("chain was signed by key {found}, expected {expected}")]
+    KeyMismatch {
+        /// Hex public key the caller trusts.
+        expected: String,
+        /// Hex public key recorded in the signed chain.
+        found: String,
+    },
+    /// The signed chain and the receipt chain have different lengths.
+    #[error("signed chain has {signed} entries, receipt chain has {chain}")]
+    LengthMismatch {
+        /// Number of signed entries.
+        signed: usize,
+        /// Number of receipts.
+        chain: usize,
+    },
+    /// A signed entry's sequence, hash, or parent hash does not match the
+    /// receipt chain or the previous entry.
+    #[error("signed entry {index} does not match the receipt chain")]
+    EntryMismatch {
+        /// Index of the mismatched entry.
+        index: usize,
+    },
+    /// A signature did not verify.
+    #[error("invalid signature at chain index {index}")]
+    BadSignature {
+        /// Index of the entry with the bad signature.
+        index: usize,
+    },
+    /// The receipt chain itself failed hash verification.
+    #[error(transparent)]
+    Chain(#[from] ChainError),
+}
+
+impl From<ring::error::Unspecified> for SigningError {
+    fn from(_: ring::error::Unspecified) -> Self {
+        Self::Crypto
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    let hex = hex.trim();
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn key_bytes(hex: &str) -> Result<[u8; 32], SigningError> {
+    from_hex(hex)
+        .and_then(|b| <[u8; 32]>::try_from(b).ok())
+        .ok_or_else(|| SigningError::InvalidKey("expected 64 hex characters".into()))
+}
+
+// ── Keys ───────────────────────────────────────────────────────────
+
+/// Private ed25519 key used to sign receipt chains.
+pub struct ReceiptSigningKey {
+    seed: [u8; 32],
+    pair: Ed25519KeyPair,
+}
+
+impl ReceiptSigningKey {
+    /// Generate a random key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SigningError::Crypto`] if the system RNG fails.
+    pub fn generate() -> Result<Self, SigningError> {
+        let mut seed = [0u8; 32];
+        SystemRandom::new().fill(&mut seed)?;
+        Self::from_seed(seed)
+    }
+
+    /// Build a key from its 32-byte seed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SigningError::Crypto`] if the seed is rejected.
+    pub fn from_seed(seed: [u8; 32]) -> Result<Self, SigningError> {
+        let pair = Ed25519KeyPair::from_seed_unchecked(&seed).map_err(|_| SigningError::Crypto)?;
+        Ok(Self { seed, pair })
+    }
+
+    /// Parse a key from its seed as 64 hex characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SigningError::InvalidKey`] for anything else.
+    pub fn from_hex(hex: &str) -> Result<Self, SigningError> {
+        Self::from_seed(key_bytes(hex)?)
+    }
+
+    /// Encode the seed as 64 lowercase hex characters.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.seed)
+    }
+
+    /// The matching public key.
+    #[must_use]
+    pub fn verifying_key(&self) -> ReceiptVerifyingKey {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(self.pair.public_key().as_ref());
+        ReceiptVerifyingKey(bytes)
+    }
+
+    fn sign(&self, message: &[u8]) -> String {
+        to_hex(self.pair.sign(message).as_ref())
+    }
+}
+
+impl fmt::Debug for ReceiptSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReceiptSigningKey")
+            .field("public", &self.verifying_key().to_hex())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Public ed25519 key that verifies a [`SignedChain`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReceiptVerifyingKey([u8; 32]);
+
+impl ReceiptVerifyingKey {
+    /// Wrap raw public key bytes.
+    #[must_use]
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Parse a public key from 64 hex characters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SigningError::InvalidKey`] for anything else.
+    pub fn from_hex(hex: &str) -> Result<Self, SigningError> {
+        key_bytes(hex).map(Self)
+    }
+
+    /// Encode the key as 64 lowercase hex characters.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.0)
+    }
+
+    fn verify(&self, message: &[u8], signature_hex: &str) -> bool {
+        let Some(signature) = from_hex(signature_hex) else {
+            return false;
+        };
+        UnparsedPublicKey::new(&ED25519, self.0)
+            .verify(message, &signature)
+            .is_ok()
+    }
+}
+
+// ── Signed chain ───────────────────────────────────────────────────
+
+/// Signature over one receipt chain entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedEntry {
+    /// Sequence number of the entry in the chain.
+    pub sequence: u64,
+    /// `receipt_sha256` of the entry's receipt.
+    pub receipt_hash: String,
+    /// Hash of the previous receipt (`None` for the first entry).
+    pub parent_hash: Option<String>,
+    /// Hex ed25519 signature over the sequence, hash, and parent hash.
+    pub signature: String,
+}
+
+impl SignedEntry {
+    /// Bytes covered by [`signature`](Self::signature).
+    #[must_use]
+    pub fn signed_message(&self) -> Vec<u8> {
+        format!(
+            "{SIGNATURE_VERSION}\n{}\n{}\n{}",
+            self.sequence,
+            self.receipt_hash,
+            self.parent_hash.as_deref().unwrap_or("")
+        )
+        .into_bytes()
+    }
+}
+
+/// Per-entry signatures for a [`ReceiptChain`], plus the signer's public key.
+///
+/// Stored alongside the chain (e.g. next to an
+/// [`export_chain`](ReceiptChain::export_chain) file) and checked with
+/// [`verify`](Self::verify).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedChain {
+    /// Hex public key of the signer.
+    pub public_key: String,
+    /// One signed entry per receipt, in chain order.
+    pub entries: Vec<SignedEntry>,
+}
+
+impl SignedChain {
+    /// Check signatures and hash linkage of the entries alone, without the
+    /// receipts.
+    ///
+    /// # Errors
+    ///
+    /// - [`SigningError::KeyMismatch`] if the chain was signed by another key.
+    /// - [`SigningError::EntryMismatch`] if an entry's parent hash is not the
+    ///   previous entry's hash or sequence numbers are not increasing.
+    /// - [`SigningError::BadSignature`] if a signature does not verify.
+    pub fn verify_entries(&self, key: &ReceiptVerifyingKey) -> Result<(), SigningError> {
+        if self.public_key != key.to_hex() {
+            return Err(SigningError::KeyMismatch {
+                expected: key.to_hex(),
+                found: self.public_key.clone(),
+            });
+        }
+        let mut prev: Option<&SignedEntry> = None;
+        for (index, entry) in self.entries.iter().enumerate() {
+            let linked = match prev {
+                Some(p) => {
+                    entry.parent_hash.as_deref() == Some(p.receipt_hash.as_str())
+                        && entry.sequence > p.sequence
+                }
+                None => entry.parent_hash.is_none(),
+            };
+            if !linked {
+                return Err(SigningError::EntryMismatch { index });
+            }
+            if !key.verify(&entry.signed_message(), &entry.signature) {
+                return Err(SigningError::BadSignature { index });
+            }
+            prev = Some(entry);
+        }
+        Ok(())
+    }
+
+    /// Verify that `chain` is exactly the chain `key` signed.
+    ///
+    /// Recomputes every receipt hash, then checks each entry against the
+    /// receipt at the same position and verifies its signature.
+    ///
+    /// # Errors
+    ///
+    /// Any [`SigningError`] from [`verify_entries`](Self::verify_entries),
+    /// plus [`SigningError::Chain`] if a receipt hash does not verify,
+    /// [`SigningError::LengthMismatch`] if receipts were added or removed, and
+    /// [`SigningError::EntryMismatch`] if an entry does not describe the
+    /// receipt at its index.
+    pub fn verify(
+        &self,
+        chain: &ReceiptChain,
+        key: &ReceiptVerifyingKey,
+    ) -> Result<(), SigningError> {
+        if self.entries.len() != chain.len() {
+            return Err(SigningError::LengthMismatch {
+                signed: self.entries.len(),
+                chain: chain.len(),
+            });
+        }
+        if !chain.is_empty() {
+            chain.verify()?;
+        }
+        for (index, (entry, receipt)) in self.entries.iter().zip(chain.iter()).enumerate() {
+            if receipt.receipt_sha256.as_deref() != Some(entry.receipt_hash.as_str())
+                || chain.sequence_at(index) != Some(entry.sequence)
+            {
+                return Err(SigningError::EntryMismatch { index });
+            }
+        }
+        self.verify_entries(key)
+    }
+}
+
+impl ReceiptChain {
+    /// Sign every entry of the chain with `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SigningError::UnhashedReceipt`] if a receipt has no
+    /// `receipt_sha256`.
+    pub fn sign(&self, key: &ReceiptSigningKey) -> Result<SignedChain, SigningError> {
+        let mut entries = Vec::with_capacity(self.len());
+        let mut parent_hash: Option<String> = None;
+        for (index, receipt) in self.iter().enumerate() {
+            let receipt_hash = receipt
+                .receipt_sha256
+                .clone()
+                .ok_or(SigningError::UnhashedReceipt { index })?;
+            let mut entry = SignedEntry {
+                sequence: self.sequence_at(index).unwrap_or(index as u64),
+                receipt_hash,
+                parent_hash: parent_hash.take(),
+                signature: String::new(),
+            };
+            entry.signature = key.sign(&entry.signed_message());
+            parent_hash = Some(entry.receipt_hash.clone());
+            entries.push(entry);
+        }
+        Ok(SignedChain {
+            public_key: key.verifying_key().to_hex(),
+            entries,
+        })
+    }
+}
@@ -2257,3 +2257,117 @@ fn receipt_bytes_roundtrip_preserves_all() {
     assert_eq!(r.outcome, r2.outcome);
     assert_eq!(r.backend.backend_version, r2.backend.backend_version);
 }
+
+// ── Provenance tests ───────────────────────────────────────────────
+
+#[test]
+fn provenance_normalizes_openai_completion() {
+    use crate::provenance::ProviderRequest;
+    let raw = serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "model": "gpt-4o-2024-08-06",
+        "system_fingerprint": "fp_1",
+        "x-request-id": "req_1",
+    });
+    assert_eq!(
+        ProviderRequest::from_raw(&raw),
+        Some(
+            ProviderRequest::new("openai")
+                .request_id("req_1")
+                .response_id("chatcmpl-1")
+                .system_fingerprint("fp_1")
+                .model("gpt-4o-2024-08-06")
+        )
+    );
+}
+
+#[test]
+fn provenance_normalizes_anthropic_message_start() {
+    use crate::provenance::ProviderRequest;
+    let raw = serde_json::json!({
+        "type": "message_start",
+        "request_id": "req_011",
+        "message": {"id": "msg_01", "type": "message", "model": "claude-sonnet-4-20250514"},
+    });
+    assert_eq!(
+        ProviderRequest::from_raw(&raw),
+        Some(
+            ProviderRequest::new("anthropic")
+                .request_id("req_011")
+                .response_id("msg_01")
+                .model("claude-sonnet-4-20250514")
+        )
+    );
+}
+
+#[test]
+fn provenance_normalizes_gemini_response() {
+    use crate::provenance::ProviderRequest;
+    let raw = serde_json::json!({"responseId": "r-1", "modelVersion": "gemini-2.0-flash-001"});
+    let req = ProviderRequest::from_raw(&raw).unwrap();
+    assert_eq!(req.provider, "gemini");
+    assert_eq!(req.response_id.as_deref(), Some("r-1"));
+    assert_eq!(req.model.as_deref(), Some("gemini-2.0-flash-001"));
+}
+
+#[test]
+fn provenance_ignores_unrecognized_or_idless_payloads() {
+    use crate::provenance::ProviderRequest;
+    assert!(ProviderRequest::from_raw(&serde_json::json!({"foo": 1})).is_none());
+    assert!(ProviderRequest::from_raw(&serde_json::json!("text")).is_none());
+    assert!(ProviderRequest::from_raw(&serde_json::json!({"object": "chat.completion"})).is_none());
+}
+
+#[test]
+fn provenance_collected_from_trace_and_deduplicated() {
+    use crate::provenance::{self, PROVENANCE_KEY, Provenance, ProviderRequest};
+    let raw = serde_json::json!({"type": "message", "id": "msg_9", "model": "claude"});
+    let event = AgentEvent {
+        ts: Utc::now(),
+        kind: AgentEventKind::AssistantMessage { text: "hi".into() },
+        ext: Some([("raw_message".to_string(), raw)].into_iter().collect()),
+    };
+    let mut r = ReceiptBuilder::new("mock")
+        .add_trace_event(event.clone())
+        .add_trace_event(event)
+        .build();
+    assert!(provenance::record(&mut r));
+    let p = Provenance::from_receipt(&r).unwrap();
+    assert_eq!(
+        p.requests,
+        [ProviderRequest::new("anthropic")
+            .response_id("msg_9")
+            .model("claude")]
+    );
+
+    // Recording again keeps the existing block intact.
+    assert!(provenance::record(&mut r));
+    assert_eq!(Provenance::from_receipt(&r).unwrap(), p);
+    assert!(r.usage_raw[PROVENANCE_KEY].is_object());
+}
+
+#[test]
+fn builder_provider_request_is_hashed() {
+    use crate::provenance::{PROVENANCE_KEY, Provenance, ProviderRequest};
+    let traced = ReceiptBuilder::new("mock")
+        .provider_request(ProviderRequest::new("openai").request_id("req_1"))
+        .build();
+    assert_eq!(
+        Provenance::from_receipt(&traced).unwrap().requests[0]
+            .request_id
+            .as_deref(),
+        Some("req_1")
+    );
+    let mut stripped = traced.clone();
+    stripped
+        .usage_raw
+        .as_object_mut()
+        .unwrap()
+        .remove(PROVENANCE_KEY);
+    assert_ne!(
+        compute_hash(&traced).unwrap(),
+        compute_hash(&stripped).unwrap()
+    );
+    assert!(Provenance::from_receipt(&ReceiptBuilder::new("mock").build()).is_none());
+}
@@ -0,0 +1,444 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Receipt traces as Chrome-tracing / Perfetto timelines.
+//!
+//! [`spans`](crate::timeline::spans) reconstructs where the time of a run
+//! went from the timestamps in its trace: time before a backend event while
+//! no tool is running is spent waiting on the backend, time before a thinking
+//! event is thinking, and each tool call lasts until its matching result.
+//! [`to_chrome_trace`](crate::timeline::to_chrome_trace) renders the spans of
+//! one or more receipts in the Trace Event Format, so the output opens
+//! directly in `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+//!
+//! Every receipt becomes a process. Thread 0 holds the run itself, thread 1
+//! the agent (waiting, thinking and instant events) and threads 2 and up the
+//! tool calls, one per concurrently running call.
+
+use abp_core::{AgentEvent, AgentEventKind, ContractError, Receipt};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+/// Thread holding the run span.
+const RUN_LANE: u32 = 0;
+/// Thread holding waiting, thinking and instant events.
+const AGENT_LANE: u32 = 1;
+/// First thread used for tool calls.
+const FIRST_TOOL_LANE: u32 = 2;
+
+/// What a [`Span`] spent its time on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpanKind {
+    /// The whole run, from `started_at` to `finished_at`.
+    Run,
+    /// Waiting for the backend to produce its next event.
+    Waiting,
+    /// Extended thinking (events with `ext["thinking"] == true`).
+    Thinking,
+    /// A tool call, from the call to its result.
+    Tool,
+}
+
+impl SpanKind {
+    /// Trace Event Format category.
+    #[must_use]
+    pub fn category(self) -> &'static str {
+        match self {
+            Self::Run => "run",
+            Self::Waiting => "backend",
+            Self::Thinking => "thinking",
+            Self::Tool => "tool",
+        }
+    }
+}
+
+/// A stretch of time in a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Span {
+    /// What the time was spent on.
+    pub kind: SpanKind,
+    /// Display name (the tool name for tool spans).
+    pub name: String,
+    /// Start of the span.
+    pub start: DateTime<Utc>,
+    /// End of the span.
+    pub end: DateTime<Utc>,
+    /// Timeline thread the span is drawn on.
+    pub lane: u32,
+    /// Extra details shown when the span is selected.
+    pub args: Value,
+}
+
+impl Span {
+    fn new(
+        kind: SpanKind,
+        name: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        lane: u32,
+    ) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            start,
+            end,
+            lane,
+            args: json!({}),
+        }
+    }
+}
+
+/// A tool call still waiting for its result.
+struct OpenCall<'a> {
+    id: Option<&'a str>,
+    name: &'a str,
+    start: DateTime<Utc>,
+    lane: u32,
+    input: &'a Value,
+}
+
+/// Reconstruct the spans of a receipt's run from its trace.
+///
+/// Results are matched to calls by `tool_use_id`, falling back to the tool
+/// name. Calls without a result end with the run and are marked
+/// `"unfinished"`.
+#[must_use]
+pub fn spans(receipt: &Receipt) -> Vec<Span> {
+    let meta = &receipt.meta;
+    let mut out = vec![Span::new(
+        SpanKind::Run,
+        &receipt.backend.id,
+        meta.started_at,
+        meta.finished_at,
+        RUN_LANE,
+    )];
+    out[0].args = json!({
+        "run_id": meta.run_id,
+        "outcome": receipt.outcome,
+        "events": receipt.trace.len(),
+    });
+
+    let mut agent: Vec<Span> = Vec::new();
+    let mut tools: Vec<Span> = Vec::new();
+    let mut open: Vec<OpenCall<'_>> = Vec::new();
+    let mut prev = meta.started_at;
+
+    for event in &receipt.trace {
+        let ts = event.ts.max(prev);
+        match &event.kind {
+            AgentEventKind::ToolResult {
+                tool_name,
+                tool_use_id,
+                is_error,
+                ..
+            } => {
+                let found = open
+                    .iter()
+                    .position(|c| tool_use_id.is_some() && c.id == tool_use_id.as_deref())
+                    .or_else(|| open.iter().position(|c| c.name == tool_name));
+                if let Some(call) = found.map(|i| open.remove(i)) {
+                    let mut span = Span::new(SpanKind::Tool, call.name, call.start, ts, call.lane);
+                    span.args = json!({
+                        "tool_use_id": call.id,
+                        "input": call.input,
+                        "is_error": is_error,
+                    });
+                    tools.push(span);
+                }
+            }
+            kind => {
+                if open.is_empty() && ts > prev {
+                    let (span_kind, name) = if is_thinking(event) {
+                        (SpanKind::Thinking, "thinking")
+                    } else {
+                        (SpanKind::Waiting, "waiting on backend")
+                    };
+                    extend_or_push(&mut agent, Span::new(span_kind, name, prev, ts, AGENT_LANE));
+                }
+                if let AgentEventKind::ToolCall {
+                    tool_name,
+                    tool_use_id,
+                    input,
+                    ..
+                } = kind
+                {
+                    let lane = (FIRST_TOOL_LANE..)
+                        .find(|l| open.iter().all(|c| c.lane != *l))
+                        .unwrap_or(FIRST_TOOL_LANE);
+                    open.push(OpenCall {
+                        id: tool_use_id.as_deref(),
+                        name: tool_name,
+                        start: ts,
+                        lane,
+                        input,
+                    });
+                }
+            }
+        }
+        prev = ts;
+    }
+
+    let end = meta.finished_at.max(prev);
+    for call in open {
+        let mut span = Span::new(SpanKind::Tool, call.name, call.start, end, call.lane);
+        span.args = json!({
+            "tool_use_id": call.id,
+            "input": call.input,
+            "unfinished": true,
+        });
+        tools.push(span);
+    }
+
+    out.extend(agent);
+    out.extend(tools);
+    out
+}
+
+/// Merge `span` into the last one if it continues it, else append it.
+fn extend_or_push(spans: &mut Vec<Span>, span: Span) {
+    if let Some(last) = spans.last_mut()
+        && last.kind == span.kind
+        && last.end == span.start
+    {
+        last.end = span.end;
+    } else {
+        spans.push(span);
+    }
+}
+
+fn is_thinking(event: &AgentEvent) -> bool {
+    event
+        .ext
+        .as_ref()
+        .and_then(|ext| ext.get("thinking"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Render receipts as a Trace Event Format object.
+///
+/// Timestamps are microseconds since the earliest start among the receipts.
+/// Spans become complete (`"X"`) events; trace events that are not part of
+/// a span (tool calls and results, thinking) become instant (`"i"`) events
+/// on the agent thread.
+#[must_use]
+pub fn to_chrome_trace(receipts: &[Receipt]) -> Value {
+    let origin = receipts
+        .iter()
+        .flat_map(|r| std::iter::once(r.meta.started_at).chain(r.trace.iter().map(|e| e.ts)))
+        .min()
+        .unwrap_or_default();
+    let micros = |ts: DateTime<Utc>| (ts - origin).num_microseconds().unwrap_or(i64::MAX);
+
+    let mut events = Vec::new();
+    for (pid, receipt) in (1u32..).zip(receipts) {
+        let spans = spans(receipt);
+        events.push(metadata(
+            pid,
+            RUN_LANE,
+            "process_name",
+            &format!("{} {}", receipt.backend.id, receipt.meta.run_id),
+        ));
+        let mut lanes: Vec<u32> = spans.iter().map(|s| s.lane).collect();
+        lanes.push(AGENT_LANE);
+        lanes.sort_unstable();
+        lanes.dedup();
+        for lane in lanes {
+            let name = match lane {
+                RUN_LANE => "run".to_string(),
+                AGENT_LANE => "agent".to_string(),
+                n => format!("tool {}", n - FIRST_TOOL_LANE + 1),
+            };
+            events.push(metadata(pid, lane, "thread_name", &name));
+        }
+
+        for span in &spans {
+            events.push(json!({
+                "name": span.name,
+                "cat": span.kind.category(),
+                "ph": "X",
+                "ts": micros(span.start),
+                "dur": (span.end - span.start).num_microseconds().unwrap_or(0),
+                "pid": pid,
+                "tid": span.lane,
+                "args": span.args,
+            }));
+        }
+        for event in &receipt.trace {
+            if matches!(
+                event.kind,
+                AgentEventKind::ToolCall { .. } | AgentEventKind::ToolResult { .. }
+            ) || is_thinking(event)
+            {
+                continue;
+            }
+            let kind = serde_json::to_value(&event.kind).unwrap_or(Value::Null);
+            let name = kind
+                .get("type")
+                .and_then(Value::as_str)
+                .unwrap_or("event")
+                .to_string();
+            events.push(json!({
+                "name": name,
+                "cat": "event",
+                "ph": "i",
+                "s": "t",
+                "ts": micros(event.ts),
+                "pid": pid,
+                "tid": AGENT_LANE,
+                "args": kind,
+            }));
+        }
+    }
+    json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+}
+
+fn metadata(pid: u32, tid: u32, name: &str, value: &str) -> Value {
+    json!({
+        "name": name,
+        "ph": "M",
+        "pid": pid,
+        "tid": tid,
+        "args": { "name": value },
+    })
+}
+
+/// Serialize [`to_chrome_trace`] as JSON, ready to load in a trace viewer.
+///
+/// # Errors
+///
+/// Returns [`ContractError::Json`] if serialization fails.
+pub fn to_chrome_trace_json(receipts: &[Receipt]) -> Result<String, ContractError> {
+    Ok(serde_json::to_string(&to_chrome_trace(receipts))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReceiptBuilder;
+    use chrono::TimeZone;
+
+    fn at(ms: i64) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(1_700_000_000_000 + ms).unwrap()
+    }
+
+    fn event(ms: i64, kind: AgentEventKind) -> AgentEvent {
+        AgentEvent {
+            ts: at(ms),
+            kind,
+            ext: None,
+        }
+    }
+
+    fn call(ms: i64, id: &str) -> AgentEvent {
+        event(
+            ms,
+            AgentEventKind::ToolCall {
+                tool_name: "read".into(),
+                tool_use_id: Some(id.into()),
+                parent_tool_use_id: None,
+                input: json!({"path": id}),
+            },
+        )
+    }
+
+    fn result(ms: i64, id: &str) -> AgentEvent {
+        event(
+            ms,
+            AgentEventKind::ToolResult {
+                tool_name: "read".into(),
+                tool_use_id: Some(id.into()),
+                output: json!("ok"),
+                is_error: false,
+            },
+        )
+    }
+
+    fn receipt(trace: Vec<AgentEvent>) -> Receipt {
+        let mut thought = event(300, AgentEventKind::AssistantDelta { text: "hmm".into() });
+        thought.ext = Some([("thinking".to_string(), json!(true))].into());
+        let mut builder = ReceiptBuilder::new("mock")
+            .started_at(at(0))
+            .finished_at(at(1000))
+            .add_trace_event(thought);
+        for e in trace {
+            builder = builder.add_trace_event(e);
+        }
+        builder.build()
+    }
+
+    #[test]
+    fn time_is_split_between_thinking_waiting_and_tools() {
+        let r = receipt(vec![
+            event(400, AgentEventKind::AssistantDelta { text: "a".into() }),
+            event(500, AgentEventKind::AssistantDelta { text: "b".into() }),
+            call(500, "1"),
+            call(550, "2"),
+            result(700, "1"),
+            result(800, "2"),
+            event(
+                900,
+                AgentEventKind::AssistantMessage {
+                    text: "done".into(),
+                },
+            ),
+        ]);
+        let spans = spans(&r);
+        let summary: Vec<_> = spans
+            .iter()
+            .map(|s| {
+                (
+                    s.kind,
+                    s.lane,
+                    (s.start - at(0)).num_milliseconds(),
+                    (s.end - at(0)).num_milliseconds(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (SpanKind::Run, 0, 0, 1000),
+                (SpanKind::Thinking, 1, 0, 300),
+                (SpanKind::Waiting, 1, 300, 500),
+                (SpanKind::Waiting, 1, 800, 900),
+                (SpanKind::Tool, 2, 500, 700),
+                (SpanKind::Tool, 3, 550, 800),
+            ]
+        );
+        assert_eq!(spans[4].args["input"], json!({"path": "1"}));
+    }
+
+    #[test]
+    fn unfinished_calls_end_with_the_run() {
+        let spans = spans(&receipt(vec![call(400, "1")]));
+        let tool = spans.iter().find(|s| s.kind == SpanKind::Tool).unwrap();
+        assert_eq!(tool.end, at(1000));
+        assert_eq!(tool.args["unfinished"], true);
+    }
+
+    #[test]
+    fn chrome_trace_uses_microseconds_from_the_earliest_start() {
+        let trace = to_chrome_trace(&[receipt(vec![call(400, "1"), result(450, "1")])]);
+        let events = trace["traceEvents"].as_array().unwrap();
+        let tool = events
+            .iter()
+            .find(|e| e["ph"] == "X" && e["cat"] == "tool")
+            .unwrap();
+        assert_eq!(tool["ts"], 400_000);
+        assert_eq!(tool["dur"], 50_000);
+        assert_eq!(tool["tid"], 2);
+        assert!(
+            events
+                .iter()
+                .any(|e| e["ph"] == "M" && e["args"]["name"] == "tool 1")
+        );
+        // The thinking delta is covered by its span, not repeated as an instant.
+        assert!(events.iter().all(|e| e["ph"] != "i"));
+
+        let json = to_chrome_trace_json(&[]).unwrap();
+        let empty: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(empty, json!({"traceEvents": [], "displayTimeUnit": "ms"}));
+    }
+}
@@ -0,0 +1,298 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Verification of receipts written under earlier contract versions.
+//!
+//! A receipt's hash preimage is the receipt itself, rendered with
+//! `receipt_sha256` set to `null`. That preimage includes
+//! `meta.contract_version`, so the version a receipt claims is covered by
+//! its hash: relabelling an archived receipt with another version
+//! invalidates it. Each version fixes how the preimage is rendered, as one or
+//! more [`HashScheme`]s, and a [`VerifierSet`] keeps those rules for the
+//! last [`SUPPORTED_VERSIONS`] versions.
+//!
+//! [`verify_any`] works on the stored bytes rather than on a [`Receipt`]: a
+//! receipt written under an older schema is checked exactly as written, even
+//! if it no longer deserializes into the current types or would serialize
+//! differently after a round trip through them.
+//!
+//! [`HashScheme`]: crate::versioned::HashScheme
+//! [`VerifierSet`]: crate::versioned::VerifierSet
+//! [`SUPPORTED_VERSIONS`]: crate::versioned::SUPPORTED_VERSIONS
+//! [`verify_any`]: crate::versioned::verify_any
+//! [`Receipt`]: abp_core::Receipt
+
+use std::fmt;
+
+use abp_core::{CONTRACT_VERSION, sha256_hex};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// How many contract versions, counting the current one, a default
+/// [`VerifierSet`] keeps verifiers for.
+pub const SUPPORTED_VERSIONS: usize = 3;
+
+/// A way of rendering a receipt's hash preimage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashScheme {
+    /// RFC 8785-style canonical JSON from [`abp_core::canonical`], as
+    /// produced by [`compute_hash`](crate::compute_hash).
+    Canonical,
+    /// Compact `serde_json` output with sorted keys. `abp/v0.1` receipts
+    /// were hashed this way before canonical JSON was adopted; the two
+    /// differ only in how numbers are written.
+    SerdeJson,
+}
+
+impl HashScheme {
+    /// The preimage of `receipt`, a receipt's JSON, under this scheme.
+    #[must_use]
+    pub fn preimage(self, receipt: &Value) -> String {
+        let mut v = receipt.clone();
+        if let Value::Object(map) = &mut v {
+            map.insert("receipt_sha256".to_string(), Value::Null);
+        }
+        match self {
+            Self::Canonical => abp_core::canonical::value_to_canonical_string(&v),
+            Self::SerdeJson => v.to_string(),
+        }
+    }
+
+    /// The hex SHA-256 of [`preimage`](Self::preimage).
+    #[must_use]
+    pub fn hash(self, receipt: &Value) -> String {
+        sha256_hex(self.preimage(receipt).as_bytes())
+    }
+}
+
+impl fmt::Display for HashScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Canonical => "canonical",
+            Self::SerdeJson => "serde_json",
+        })
+    }
+}
+
+/// The hash schemes accepted for one contract version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionRules {
+    /// Contract version, e.g. `"abp/v0.1"`.
+    pub contract_version: String,
+    /// Accepted schemes; the first is the one receipts of this version are
+    /// written with.
+    pub schemes: Vec<HashScheme>,
+}
+
+impl VersionRules {
+    /// Rules for `contract_version` accepting `schemes`.
+    #[must_use]
+    pub fn new(contract_version: impl Into<String>, schemes: Vec<HashScheme>) -> Self {
+        Self {
+            contract_version: contract_version.into(),
+            schemes,
+        }
+    }
+}
+
+/// A receipt that passed [`verify_any`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionedReceipt {
+    /// Contract version the receipt was written under.
+    pub contract_version: String,
+    /// Scheme whose hash matched.
+    pub scheme: HashScheme,
+    /// The verified hash.
+    pub hash: String,
+}
+
+/// Why [`verify_any`] rejected a receipt.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum VersionedVerifyError {
+    /// The bytes are not a JSON object.
+    #[error("receipt is not a JSON object: {0}")]
+    InvalidJson(String),
+    /// The receipt does not say which contract version it was written under.
+    #[error("receipt has no meta.contract_version")]
+    MissingVersion,
+    /// No verifier is kept for the receipt's contract version.
+    #[error("unsupported contract version {version} (supported: {})", supported.join(", "))]
+    UnsupportedVersion {
+        /// The receipt's contract version.
+        version: String,
+        /// Versions with verifiers, newest first.
+        supported: Vec<String>,
+    },
+    /// The receipt has no `receipt_sha256` to check.
+    #[error("{version} receipt has no receipt_sha256")]
+    Unhashed {
+        /// The receipt's contract version.
+        version: String,
+    },
+    /// No accepted scheme reproduces the stored hash.
+    #[error("{version} receipt hash mismatch: stored {stored}, computed {computed}")]
+    HashMismatch {
+        /// The receipt's contract version.
+        version: String,
+        /// Hash stored in the receipt.
+        stored: String,
+        /// Hash under the version's primary scheme.
+        computed: String,
+    },
+}
+
+/// Verifiers for a window of recent contract versions.
+///
+/// Holds at most `window` versions. Registering a newer one drops the
+/// oldest, so a library release verifies receipts from the versions it
+/// was built to overlap with and rejects older ones explicitly.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifierSet {
+    rules: Vec<VersionRules>,
+    window: usize,
+}
+
+impl Default for VerifierSet {
+    /// Verifiers for every built-in version, keeping [`SUPPORTED_VERSIONS`].
+    fn default() -> Self {
+        let mut set = Self::with_window(SUPPORTED_VERSIONS);
+        set.register(VersionRules::new(
+            CONTRACT_VERSION,
+            vec![HashScheme::Canonical, HashScheme::SerdeJson],
+        ));
+        set
+    }
+}
+
+impl VerifierSet {
+    /// An empty set keeping at most `window` versions (at least one).
+    #[must_use]
+    pub fn with_window(window: usize) -> Self {
+        Self {
+            rules: Vec::new(),
+            window: window.max(1),
+        }
+    }
+
+    /// Add or replace the rules for a version.
+    ///
+    /// Versions are ordered by their `vMAJOR.MINOR` number; past the window,
+    /// the oldest are dropped. Returns `false` if `rules` itself fell outside
+    /// the window.
+    pub fn register(&mut self, rules: VersionRules) -> bool {
+        let version = rules.contract_version.clone();
+        self.rules.retain(|r| r.contract_version != version);
+        self.rules.push(rules);
+        self.rules
+            .sort_by_key(|r| std::cmp::Reverse(version_key(&r.contract_version)));
+        self.rules.truncate(self.window);
+        self.rules.iter().any(|r| r.contract_version == version)
+    }
+
+    /// Versions with verifiers, newest first.
+    #[must_use]
+    pub fn versions(&self) -> Vec<&str> {
+        self.rules
+            .iter()
+            .map(|r| r.contract_version.as_str())
+            .collect()
+    }
+
+    /// The rules for `version`, if kept.
+    #[must_use]
+    pub fn rules(&self, version: &str) -> Option<&VersionRules> {
+        self.rules.iter().find(|r| r.contract_version == version)
+    }
+
+    /// Verify a serialized receipt of any supported version.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`VersionedVerifyError`] if the bytes are not a receipt, its
+    /// version is not supported, it has no hash, or the hash does not match.
+    pub fn verify_any(&self, bytes: &[u8]) -> Result<VersionedReceipt, VersionedVerifyError> {
+        let value: Value = serde_json::from_slice(bytes)
+            .map_err(|e| VersionedVerifyError::InvalidJson(e.to_string()))?;
+        self.verify_value(&value)
+    }
+
+    /// Verify a receipt already parsed into JSON.
+    ///
+    /// # Errors
+    ///
+    /// As for [`verify_any`](Self::verify_any).
+    pub fn verify_value(&self, receipt: &Value) -> Result<VersionedReceipt, VersionedVerifyError> {
+        if !receipt.is_object() {
+            return Err(VersionedVerifyError::InvalidJson(format!(
+                "expected an object, found {receipt}"
+            )));
+        }
+        let version = receipt
+            .pointer("/meta/contract_version")
+            .and_then(Value::as_str)
+            .ok_or(VersionedVerifyError::MissingVersion)?;
+        let rules =
+            self.rules(version)
+                .ok_or_else(|| VersionedVerifyError::UnsupportedVersion {
+                    version: version.to_string(),
+                    supported: self.versions().into_iter().map(String::from).collect(),
+                })?;
+        let stored = receipt
+            .get("receipt_sha256")
+            .and_then(Value::as_str)
+            .ok_or_else(|| VersionedVerifyError::Unhashed {
+                version: version.to_string(),
+            })?;
+
+        for &scheme in &rules.schemes {
+            if scheme.hash(receipt) == stored {
+                return Ok(VersionedReceipt {
+                    contract_version: version.to_string(),
+                    scheme,
+                    hash: stored.to_string(),
+                });
+            }
+        }
+        Err(VersionedVerifyError::HashMismatch {
+            version: version.to_string(),
+            stored: stored.to_string(),
+            computed: rules
+                .schemes
+                .first()
+                .map(|s| s.hash(receipt))
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Verify a serialized receipt of any supported contract version with the
+/// default [`VerifierSet`].
+///
+/// # Errors
+///
+/// As for [`VerifierSet::verify_any`].
+///
+/// # Examples
+///
+/// ```
+/// use abp_receipt::{Outcome, ReceiptBuilder, verify_any};
+/// use abp_receipt::versioned::HashScheme;
+///
+/// let r = ReceiptBuilder::new("mock").outcome(Outcome::Complete).with_hash().unwrap();
+/// let bytes = serde_json::to_vec(&r).unwrap();
+/// let verified = verify_any(&bytes).unwrap();
+/// assert_eq!(verified.contract_version, "abp/v0.1");
+/// assert_eq!(verified.scheme, HashScheme::Canonical);
+/// ```
+pub fn verify_any(bytes: &[u8]) -> Result<VersionedReceipt, VersionedVerifyError> {
+    VerifierSet::default().verify_any(bytes)
+}
+
+/// Sort key for `abp/vMAJOR.MINOR`; unparseable versions sort oldest.
+fn version_key(version: &str) -> (u32, u32) {
+    version
+        .strip_prefix("abp/v")
+        .and_then(|rest| rest.split_once('.'))
+        .and_then(|(major, minor)| Some((major.parse().ok()?, minor.parse().ok()?)))
+        .unwrap_or((0, 0))
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Tests for the memory-mapped, streaming receipt archive reader.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use abp_core::{AgentEvent, AgentEventKind};
+use abp_receipt::reader::{RawReceipt, ReaderError, ReceiptArchiveReader};
+use abp_receipt::{Outcome, Receipt, ReceiptBuilder, compute_hash};
+use chrono::{TimeZone, Utc};
+use serde_json::json;
+
+fn event(kind: AgentEventKind) -> AgentEvent {
+    AgentEvent {
+        ts: Utc.with_ymd_and_hms(2025, 3, 1, 9, 0, 0).unwrap(),
+        kind,
+        ext: None,
+    }
+}
+
+fn receipt(n: usize) -> Receipt {
+    let mut b = ReceiptBuilder::new("mock")
+        .outcome(Outcome::Complete)
+        .usage_tokens(n as u64, 2 * n as u64)
+        .model("gpt-4o")
+        .usage_raw(json!({"cost": 0.25, "nested": {"z": [1, 2.5, "x"], "a": null}}));
+    for i in 0..n {
+        b = b.add_trace_event(event(AgentEventKind::AssistantDelta {
+            text: format!("chunk {i} — \"quoted\" {{brace}} [bracket]\n"),
+        }));
+    }
+    let mut ext = BTreeMap::new();
+    ext.insert("raw_message".to_string(), json!({"id": "msg_1", "n": 1e3}));
+    b = b.add_trace_event(AgentEvent {
+        ext: Some(ext),
+        ..event(AgentEventKind::ToolCall {
+            tool_name: "read".into(),
+            tool_use_id: Some("t1".into()),
+            parent_tool_use_id: None,
+            input: json!({"path": "src/lib.rs"}),
+        })
+    });
+    b.with_hash().unwrap()
+}
+
+fn archive(receipts: &[Receipt]) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    for r in receipts {
+        writeln!(file, "{}", serde_json::to_string(r).unwrap()).unwrap();
+    }
+    file.flush().unwrap();
+    file
+}
+
+#[test]
+fn streaming_hash_matches_compute_hash() {
+    for n in [0, 1, 5, 50] {
+        let r = receipt(n);
+        let json = serde_json::to_vec(&r).unwrap();
+        let raw = RawReceipt::new(&json);
+        assert_eq!(raw.compute_hash().unwrap(), compute_hash(&r).unwrap());
+        assert!(raw.verify().unwrap());
+
+        // Whitespace in the stored form does not change the digest.
+        let pretty = serde_json::to_vec_pretty(&r).unwrap();
+        assert_eq!(
+            RawReceipt::new(&pretty).compute_hash().unwrap(),
+            compute_hash(&r).unwrap()
+        );
+    }
+}
+
+#[test]
+fn trace_is_iterated_lazily_and_in_order() {
+    let r = receipt(10);
+    let json = serde_json::to_vec(&r).unwrap();
+    let raw = RawReceipt::new(&json);
+    let events: Vec<AgentEvent> = raw.trace().unwrap().map(Result::unwrap).collect();
+    assert_eq!(
+        serde_json::to_value(&events).unwrap(),
+        serde_json::to_value(&r.trace).unwrap()
+    );
+
+    let header = raw.header().unwrap();
+    assert_eq!(header.meta.run_id, r.meta.run_id);
+    assert_eq!(header.receipt_sha256, r.receipt_sha256);
+    assert_eq!(raw.receipt().unwrap().trace.len(), r.trace.len());
+}
+
+#[test]
+fn archive_verifies_every_line() {
+    let receipts: Vec<_> = (0..20).map(receipt).collect();
+    let file = archive(&receipts);
+    let reader = ReceiptArchiveReader::open(file.path()).unwrap();
+
+    assert_eq!(reader.iter().count(), 20);
+    let report = reader.verify_all().unwrap();
+    assert_eq!(report.receipts, 20);
+    assert!(report.is_valid());
+}
+
+#[test]
+fn tampered_receipt_is_reported_by_line() {
+    let mut receipts: Vec<_> = (1..4).map(receipt).collect();
+    receipts[1].outcome = Outcome::Failed;
+    let file = archive(&receipts);
+    let report = ReceiptArchiveReader::open(file.path())
+        .unwrap()
+        .verify_all()
+        .unwrap();
+    assert_eq!(report.receipts, 3);
+    assert_eq!(report.invalid_lines, [2]);
+}
+
+#[test]
+fn empty_archive_and_blank_lines() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let reader = ReceiptArchiveReader::open(file.path()).unwrap();
+    assert_eq!(reader.iter().count(), 0);
+    assert_eq!(reader.verify_all().unwrap().receipts, 0);
+
+    let mut file = archive(&[receipt(1)]);
+    writeln!(file, "\n   ").unwrap();
+    writeln!(file, "{}", serde_json::to_string(&receipt(2)).unwrap()).unwrap();
+    file.flush().unwrap();
+    let reader = ReceiptArchiveReader::open(file.path()).unwrap();
+    let lines: Vec<_> = reader.iter().map(|r| r.line()).collect();
+    assert_eq!(lines, [1, 4]);
+    assert!(reader.verify_all().unwrap().is_valid());
+}
+
+#[test]
+fn malformed_line_is_an_error_with_its_line_number() {
+    let mut file = archive(&[receipt(1)]);
+    writeln!(file, "{{\"meta\": ").unwrap();
+    file.flush().unwrap();
+    let err = ReceiptArchiveReader::open(file.path())
+        .unwrap()
+        .verify_all()
+        .unwrap_err();
+    assert!(
+        matches!(err, ReaderError::Malformed { line: 2, .. }),
+        "{err}"
+    );
+}
+
+#[test]
+fn missing_file_is_an_io_error() {
+    let err = ReceiptArchiveReader::open("/definitely/not/here.jsonl").unwrap_err();
+    assert!(matches!(err, ReaderError::Io(_)));
+}
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Tests for ed25519 signing and verification of receipt chains.
+
+use abp_receipt::{
+    Outcome, Receipt, ReceiptBuilder, ReceiptChain, ReceiptSigningKey, ReceiptVerifyingKey,
+    SignedChain, SigningError,
+};
+use chrono::{TimeZone, Utc};
+
+fn hashed_receipt(minute: u32) -> Receipt {
+    let t = Utc.with_ymd_and_hms(2025, 1, 1, 12, minute, 0).unwrap();
+    ReceiptBuilder::new("mock")
+        .outcome(Outcome::Complete)
+        .started_at(t)
+        .finished_at(t)
+        .with_hash()
+        .unwrap()
+}
+
+fn chain_of(n: u32) -> ReceiptChain {
+    let mut chain = ReceiptChain::new();
+    for i in 0..n {
+        chain.push(hashed_receipt(i)).unwrap();
+    }
+    chain
+}
+
+fn key() -> ReceiptSigningKey {
+    ReceiptSigningKey::from_seed([7u8; 32]).unwrap()
+}
+
+#[test]
+fn signed_chain_verifies_with_signer_key() {
+    let chain = chain_of(3);
+    let key = key();
+    let signed = chain.sign(&key).unwrap();
+
+    assert_eq!(signed.entries.len(), 3);
+    assert_eq!(signed.public_key, key.verifying_key().to_hex());
+    assert!(signed.entries[0].parent_hash.is_none());
+    assert_eq!(
+        signed.entries[2].parent_hash.as_deref(),
+        Some(signed.entries[1].receipt_hash.as_str())
+    );
+    signed.verify(&chain, &key.verifying_key()).unwrap();
+}
+
+#[test]
+fn signed_chain_survives_json_roundtrip_and_chain_export() {
+    let chain = chain_of(2);
+    let key = key();
+    let signed_json = serde_json::to_string(&chain.sign(&key).unwrap()).unwrap();
+    let exported = chain.export_chain().unwrap();
+
+    let chain = ReceiptChain::import_chain(&exported).unwrap();
+    let signed: SignedChain = serde_json::from_str(&signed_json).unwrap();
+    let trusted = ReceiptVerifyingKey::from_hex(&key.verifying_key().to_hex()).unwrap();
+    signed.verify(&chain, &trusted).unwrap();
+}
+
+#[test]
+fn other_key_is_rejected() {
+    let chain = chain_of(2);
+    let signed = chain.sign(&key()).unwrap();
+    let other = ReceiptSigningKey::generate().unwrap().verifying_key();
+    assert!(matches!(
+        signed.verify(&chain, &other),
+        Err(SigningError::KeyMismatch { .. })
+    ));
+
+    // Claiming the other key does not help: the signatures don't match it.
+    let mut forged = signed;
+    forged.public_key = other.to_hex();
+    assert!(matches!(
+        forged.verify(&chain, &other),
+        Err(SigningError::BadSignature { index: 0 })
+    ));
+}
+
+#[test]
+fn tampered_signature_is_rejected() {
+    let chain = chain_of(3);
+    let key = key();
+    let mut signed = chain.sign(&key).unwrap();
+    let sig = &mut signed.entries[1].signature;
+    let flipped = if sig.starts_with('0') { "1" } else { "0" };
+    sig.replace_range(0..1, flipped);
+    assert!(matches!(
+        signed.verify_entries(&key.verifying_key()),
+        Err(SigningError::BadSignature { index: 1 })
+    ));
+}
+
+#[test]
+fn substituted_receipt_is_rejected() {
+    let chain = chain_of(2);
+    let key = key();
+    let signed = chain.sign(&key).unwrap();
+
+    let mut swapped = ReceiptChain::new();
+    swapped.push(chain.get(0).unwrap().clone()).unwrap();
+    swapped.push(hashed_receipt(30)).unwrap();
+    assert!(matches!(
+        signed.verify(&swapped, &key.verifying_key()),
+        Err(SigningError::EntryMismatch { index: 1 })
+    ));
+}
+
+#[test]
+fn dropped_entry_is_rejected() {
+    let chain = chain_of(3);
+    let key = key();
+    let mut signed = chain.sign(&key).unwrap();
+    signed.entries.remove(1);
+    assert!(matches!(
+        signed.verify(&chain, &key.verifying_key()),
+        Err(SigningError::LengthMismatch {
+            signed: 2,
+            chain: 3
+        })
+    ));
+    assert!(matches!(
+        signed.verify_entries(&key.verifying_key()),
+        Err(SigningError::EntryMismatch { index: 1 })
+    ));
+}
+
+#[test]
+fn rehashed_edit_is_rejected() {
+    let chain = chain_of(2);
+    let key = key();
+    let signed = chain.sign(&key).unwrap();
+
+    // An attacker edits a receipt and recomputes its hash: the chain itself
+    // still verifies, but the signature no longer covers it.
+    let mut edited = chain.get(1).unwrap().clone();
+    edited.outcome = Outcome::Failed;
+    edited.receipt_sha256 = Some(abp_receipt::compute_hash(&edited).unwrap());
+    let mut tampered = ReceiptChain::new();
+    tampered.push(chain.get(0).unwrap().clone()).unwrap();
+    tampered.push(edited).unwrap();
+    tampered.verify().unwrap();
+
+    assert!(matches!(
+        signed.verify(&tampered, &key.verifying_key()),
+        Err(SigningError::EntryMismatch { index: 1 })
+    ));
+}
+
+#[test]
+fn unhashed_receipts_cannot_be_signed() {
+    let mut chain = ReceiptChain::new();
+    chain
+        .push(
+            ReceiptBuilder::new("mock")
+                .outcome(Outcome::Complete)
+                .build(),
+        )
+        .unwrap();
+    assert!(matches!(
+        chain.sign(&key()),
+        Err(SigningError::UnhashedReceipt { index: 0 })
+    ));
+}
+
+#[test]
+fn keys_roundtrip_through_hex() {
+    let key = ReceiptSigningKey::generate().unwrap();
+    let restored = ReceiptSigningKey::from_hex(&key.to_hex()).unwrap();
+    assert_eq!(restored.verifying_key(), key.verifying_key());
+    assert!(!format!("{key:?}").contains(&key.to_hex()));
+    assert!(matches!(
+        ReceiptVerifyingKey::from_hex("abcd"),
+        Err(SigningError::InvalidKey(_))
+    ));
+}
@@ -529,6 +529,7 @@ fn builder_verification_report() {
         git_diff: Some("diff content".into()),
         git_status: Some("M file.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let r = ReceiptBuilder::new("b").verification(report).build();
     assert!(r.verification.harness_ok);
@@ -1702,6 +1703,7 @@ fn unicode_in_verification_report() {
             git_diff: Some("--- a/файл.rs\n+++ b/файл.rs".into()),
             git_status: Some("M 文件.rs".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .started_at(fixed_time())
         .finished_at(fixed_time())
@@ -2102,6 +2104,7 @@ fn hash_changes_with_verification_report() {
             git_diff: Some("changed".into()),
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         })
         .build();
     assert_ne!(compute_hash(&r1).unwrap(), compute_hash(&r2).unwrap());
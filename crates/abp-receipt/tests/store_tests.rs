@@ -369,6 +369,7 @@ fn receipt_with_all_fields_populated() {
             git_diff: Some("diff --git a/f b/f".into()),
             git_status: Some("M f".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .add_event(AgentEvent {
             ts: started,
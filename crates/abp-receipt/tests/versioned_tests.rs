@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Tests for verifying receipts across contract versions.
+
+use abp_receipt::versioned::{HashScheme, SUPPORTED_VERSIONS, VersionRules};
+use abp_receipt::{
+    CONTRACT_VERSION, Outcome, Receipt, ReceiptBuilder, UsageNormalized, VerifierSet,
+    VersionedVerifyError, verify_any, verify_hash,
+};
+use serde_json::{Value, json};
+
+fn receipt() -> Receipt {
+    ReceiptBuilder::new("mock")
+        .outcome(Outcome::Complete)
+        .usage(UsageNormalized {
+            input_tokens: Some(10),
+            estimated_cost_usd: Some(1.0),
+            ..Default::default()
+        })
+        .build()
+}
+
+/// `value` with `receipt_sha256` set under `scheme`.
+fn hashed(mut value: Value, scheme: HashScheme) -> Value {
+    value["receipt_sha256"] = json!(scheme.hash(&value));
+    value
+}
+
+fn bytes(value: &Value) -> Vec<u8> {
+    serde_json::to_vec(value).unwrap()
+}
+
+#[test]
+fn current_receipts_verify_with_the_canonical_scheme() {
+    let r = receipt().with_hash().unwrap();
+    let verified = verify_any(&serde_json::to_vec(&r).unwrap()).unwrap();
+    assert_eq!(verified.contract_version, CONTRACT_VERSION);
+    assert_eq!(verified.scheme, HashScheme::Canonical);
+    assert_eq!(Some(verified.hash), r.receipt_sha256);
+}
+
+#[test]
+fn receipts_hashed_before_canonical_json_still_verify() {
+    let legacy = hashed(
+        serde_json::to_value(receipt()).unwrap(),
+        HashScheme::SerdeJson,
+    );
+    // The float is written `1.0` by serde_json and `1` canonically.
+    let as_receipt: Receipt = serde_json::from_value(legacy.clone()).unwrap();
+    assert!(!verify_hash(&as_receipt));
+
+    let verified = verify_any(&bytes(&legacy)).unwrap();
+    assert_eq!(verified.scheme, HashScheme::SerdeJson);
+}
+
+#[test]
+fn receipts_are_checked_as_written_not_as_reparsed() {
+    let mut old = serde_json::to_value(receipt()).unwrap();
+    // A field the current schema defaults, and one it no longer knows.
+    old.as_object_mut().unwrap().remove("artifacts");
+    old["retired_field"] = json!({"kept": true});
+    let old = hashed(old, HashScheme::Canonical);
+
+    assert_eq!(
+        verify_any(&bytes(&old)).unwrap().scheme,
+        HashScheme::Canonical
+    );
+}
+
+#[test]
+fn contract_version_is_covered_by_the_hash() {
+    let mut set = VerifierSet::default();
+    set.register(VersionRules::new("abp/v0.2", vec![HashScheme::Canonical]));
+
+    let mut relabelled = hashed(
+        serde_json::to_value(receipt()).unwrap(),
+        HashScheme::Canonical,
+    );
+    relabelled["meta"]["contract_version"] = json!("abp/v0.2");
+    let err = set.verify_any(&bytes(&relabelled)).unwrap_err();
+    assert!(matches!(
+        err,
+        VersionedVerifyError::HashMismatch { ref version, .. } if version == "abp/v0.2"
+    ));
+
+    let mut tampered = hashed(
+        serde_json::to_value(receipt()).unwrap(),
+        HashScheme::Canonical,
+    );
+    tampered["outcome"] = json!("failed");
+    assert!(matches!(
+        verify_any(&bytes(&tampered)),
+        Err(VersionedVerifyError::HashMismatch { .. })
+    ));
+}
+
+#[test]
+fn only_the_newest_versions_are_kept() {
+    assert_eq!(VerifierSet::default().versions(), [CONTRACT_VERSION]);
+    assert!(VerifierSet::default().versions().len() <= SUPPORTED_VERSIONS);
+
+    let mut set = VerifierSet::with_window(2);
+    for v in ["abp/v0.2", "abp/v0.1", "abp/v0.10"] {
+        set.register(VersionRules::new(v, vec![HashScheme::Canonical]));
+    }
+    assert_eq!(set.versions(), ["abp/v0.10", "abp/v0.2"]);
+    assert!(!set.register(VersionRules::new("abp/v0.0", vec![HashScheme::Canonical])));
+
+    let r = hashed(
+        serde_json::to_value(receipt()).unwrap(),
+        HashScheme::Canonical,
+    );
+    let err = set.verify_any(&bytes(&r)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unsupported contract version abp/v0.1 (supported: abp/v0.10, abp/v0.2)"
+    );
+}
+
+#[test]
+fn malformed_inputs_are_rejected() {
+    assert!(matches!(
+        verify_any(b"not json"),
+        Err(VersionedVerifyError::InvalidJson(_))
+    ));
+    assert!(matches!(
+        verify_any(b"[1, 2]"),
+        Err(VersionedVerifyError::InvalidJson(_))
+    ));
+    assert_eq!(
+        verify_any(br#"{"meta": {}}"#),
+        Err(VersionedVerifyError::MissingVersion)
+    );
+    assert_eq!(
+        verify_any(&serde_json::to_vec(&receipt()).unwrap()),
+        Err(VersionedVerifyError::Unhashed {
+            version: CONTRACT_VERSION.into()
+        })
+    );
+}
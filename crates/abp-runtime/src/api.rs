@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! The runtime surface application code depends on.
+//!
+//! [`RuntimeApi`](crate::api::RuntimeApi) is implemented by the real
+//! [`Runtime`] and by [`FakeRuntime`](crate::fake::FakeRuntime), so code that
+//! submits work orders can take `&dyn RuntimeApi` and be unit-tested with
+//! scripted receipts instead of workspaces, policy compilation, and backend
+//! tasks.
+
+use abp_core::{Receipt, WorkOrder};
+use async_trait::async_trait;
+
+use crate::{Runtime, RuntimeError};
+
+/// Submit work orders and get receipts back.
+#[async_trait]
+pub trait RuntimeApi: Send + Sync {
+    /// Sorted names of the backends work can be submitted to.
+    fn backend_names(&self) -> Vec<String>;
+
+    /// Run `work_order` on `backend` to completion and return its receipt.
+    ///
+    /// The receipt's trace holds the events the run emitted.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RuntimeError`] if the backend is unknown or the run fails.
+    async fn run(&self, backend: &str, work_order: WorkOrder) -> Result<Receipt, RuntimeError>;
+}
+
+#[async_trait]
+impl RuntimeApi for Runtime {
+    fn backend_names(&self) -> Vec<String> {
+        Runtime::backend_names(self)
+    }
+
+    async fn run(&self, backend: &str, work_order: WorkOrder) -> Result<Receipt, RuntimeError> {
+        let handle = self.run_streaming(backend, work_order).await?;
+        // Drain the event stream so the backend task can complete.
+        let _events: Vec<_> = tokio_stream::StreamExt::collect(handle.events).await;
+        handle
+            .receipt
+            .await
+            .map_err(|e| RuntimeError::internal("run task failed", e))?
+    }
+}
@@ -0,0 +1,356 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Assembly of artifacts a backend streams in chunks.
+//!
+//! Artifacts too large for one event (generated images, datasets) arrive as a
+//! series of [`AgentEventKind::ArtifactChunk`] events. The runtime feeds every
+//! backend event to an
+//! [`ArtifactAssembler`](crate::artifact_stream::ArtifactAssembler), which
+//! decodes and hashes each artifact as its chunks arrive and, when
+//! [`ArtifactStreams::store_in`](crate::artifact_stream::ArtifactStreams::store_in)
+//! names a directory, writes it to `<dir>/<run_id>/<name>`. Chunks are kept out
+//! of the caller's stream and the receipt trace. A completed artifact is listed
+//! in `receipt.artifacts` (kind `"stream"`) and described in
+//! `usage_raw["streamed_artifacts"]` by a
+//! [`StreamedArtifact`](crate::artifact_stream::StreamedArtifact), both covered
+//! by the receipt hash.
+//!
+//! Chunk `0` (re)starts an artifact and every later chunk must follow the
+//! previous one. A chunk out of order, undecodable data, an unsafe name, an
+//! artifact over the size limit, or a final hash that differs from the
+//! declared `sha256` discards the artifact with a warning, as does a stream
+//! that ends before the artifact's last chunk.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+use abp_core::{AgentEvent, AgentEventKind, ArtifactRef, Receipt};
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// Key under `receipt.usage_raw` holding the [`StreamedArtifact`]s.
+pub const STREAMED_ARTIFACTS_KEY: &str = "streamed_artifacts";
+
+/// `ArtifactRef::kind` of streamed artifacts.
+pub const STREAM_ARTIFACT_KIND: &str = "stream";
+
+/// One assembled artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamedArtifact {
+    /// Name the backend gave the artifact.
+    pub name: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Hex-encoded SHA-256 of the content.
+    pub sha256: String,
+    /// Number of chunks it arrived in.
+    pub chunks: u64,
+    /// Media type declared by the backend.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    /// Where the content was written, if the runtime stores artifacts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stored_at: Option<String>,
+}
+
+impl StreamedArtifact {
+    /// The artifacts recorded on `receipt`, if any.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Vec<Self> {
+        receipt
+            .usage_raw
+            .get(STREAMED_ARTIFACTS_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Whether the stored file still has the recorded size and hash.
+    ///
+    /// Always `false` for artifacts that were not stored.
+    #[must_use]
+    pub fn verify(&self) -> bool {
+        self.stored_at
+            .as_ref()
+            .and_then(|path| std::fs::read(path).ok())
+            .is_some_and(|bytes| {
+                bytes.len() as u64 == self.size && abp_core::sha256_hex(&bytes) == self.sha256
+            })
+    }
+
+    /// List `artifacts` in `receipt.artifacts` and store them under
+    /// `usage_raw["streamed_artifacts"]`.
+    pub fn attach(artifacts: &[Self], receipt: &mut Receipt) {
+        if artifacts.is_empty() {
+            return;
+        }
+        for artifact in artifacts {
+            let listed = receipt
+                .artifacts
+                .iter()
+                .any(|a| a.kind == STREAM_ARTIFACT_KIND && a.path == artifact.name);
+            if !listed {
+                receipt.artifacts.push(ArtifactRef {
+                    kind: STREAM_ARTIFACT_KIND.to_string(),
+                    path: artifact.name.clone(),
+                });
+            }
+        }
+        if let (Some(obj), Ok(val)) = (
+            receipt.usage_raw.as_object_mut(),
+            serde_json::to_value(artifacts),
+        ) {
+            obj.insert(STREAMED_ARTIFACTS_KEY.to_string(), val);
+        }
+    }
+}
+
+/// Where streamed artifacts go and how large they may grow.
+///
+/// By default artifacts are hashed and recorded but not kept.
+#[derive(Debug, Clone)]
+pub struct ArtifactStreams {
+    dir: Option<PathBuf>,
+    max_bytes: u64,
+}
+
+impl Default for ArtifactStreams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArtifactStreams {
+    /// Default cap on the size of one artifact (1 GiB).
+    pub const DEFAULT_MAX_BYTES: u64 = 1 << 30;
+
+    /// Record artifacts without storing them.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            dir: None,
+            max_bytes: Self::DEFAULT_MAX_BYTES,
+        }
+    }
+
+    /// Write artifacts under `dir/<run_id>/` (builder pattern).
+    #[must_use]
+    pub fn store_in(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.dir = Some(dir.into());
+        self
+    }
+
+    /// Discard artifacts larger than `max_bytes` (builder pattern).
+    #[must_use]
+    pub fn max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// The directory artifacts are stored under, if any.
+    #[must_use]
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// A fresh assembler for the run `run_id`.
+    #[must_use]
+    pub fn assembler(&self, run_id: Uuid) -> ArtifactAssembler {
+        ArtifactAssembler {
+            dir: self.dir.as_ref().map(|d| d.join(run_id.to_string())),
+            max_bytes: self.max_bytes,
+            open: BTreeMap::new(),
+            done: BTreeMap::new(),
+        }
+    }
+}
+
+/// What [`ArtifactAssembler::absorb`] did with an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Absorbed {
+    /// The event is not an artifact chunk; handle it as usual.
+    NotAChunk,
+    /// The chunk was added to an artifact that is still open.
+    Pending,
+    /// The chunk completed an artifact.
+    Completed(StreamedArtifact),
+    /// The chunk was rejected and its artifact discarded, for this reason.
+    Rejected(String),
+}
+
+/// An artifact whose last chunk has not arrived yet.
+struct OpenArtifact {
+    hasher: Sha256,
+    size: u64,
+    next_seq: u64,
+    media_type: Option<String>,
+    file: Option<(PathBuf, File)>,
+}
+
+/// Assembles the artifacts one run streams.
+pub struct ArtifactAssembler {
+    dir: Option<PathBuf>,
+    max_bytes: u64,
+    open: BTreeMap<String, OpenArtifact>,
+    done: BTreeMap<String, StreamedArtifact>,
+}
+
+impl std::fmt::Debug for ArtifactAssembler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArtifactAssembler")
+            .field("dir", &self.dir)
+            .field("max_bytes", &self.max_bytes)
+            .field("open", &self.open.keys().collect::<Vec<_>>())
+            .field("done", &self.done.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl ArtifactAssembler {
+    /// Add `event` to its artifact if it is an artifact chunk.
+    pub fn absorb(&mut self, event: &AgentEvent) -> Absorbed {
+        let AgentEventKind::ArtifactChunk {
+            name,
+            seq,
+            data,
+            last,
+            media_type,
+            sha256,
+        } = &event.kind
+        else {
+            return Absorbed::NotAChunk;
+        };
+        match self.add_chunk(name, *seq, data, media_type.as_deref()) {
+            Ok(()) if *last => match self.complete(name, sha256.as_deref()) {
+                Ok(artifact) => Absorbed::Completed(artifact),
+                Err(reason) => Absorbed::Rejected(format!("artifact '{name}' discarded: {reason}")),
+            },
+            Ok(()) => Absorbed::Pending,
+            Err(reason) => {
+                self.discard(name);
+                Absorbed::Rejected(format!("artifact '{name}' discarded: {reason}"))
+            }
+        }
+    }
+
+    /// The completed artifacts sorted by name, and the names of artifacts
+    /// whose last chunk never arrived (their partial files are removed).
+    #[must_use]
+    pub fn finish(mut self) -> (Vec<StreamedArtifact>, Vec<String>) {
+        let incomplete: Vec<String> = self.open.keys().cloned().collect();
+        for name in &incomplete {
+            self.discard(name);
+        }
+        (self.done.into_values().collect(), incomplete)
+    }
+
+    fn add_chunk(
+        &mut self,
+        name: &str,
+        seq: u64,
+        data: &str,
+        media_type: Option<&str>,
+    ) -> Result<(), String> {
+        if !is_safe_name(name) {
+            return Err("name must be a relative path without '..'".into());
+        }
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(data)
+            .map_err(|e| format!("chunk {seq} is not valid base64: {e}"))?;
+        if seq == 0 {
+            self.discard(name);
+            let file = match &self.dir {
+                Some(dir) => {
+                    let path = dir.join(name);
+                    if let Some(parent) = path.parent() {
+                        std::fs::create_dir_all(parent)
+                            .map_err(|e| format!("cannot create {}: {e}", parent.display()))?;
+                    }
+                    let file = File::create(&path)
+                        .map_err(|e| format!("cannot create {}: {e}", path.display()))?;
+                    Some((path, file))
+                }
+                None => None,
+            };
+            self.open.insert(
+                name.to_string(),
+                OpenArtifact {
+                    hasher: Sha256::new(),
+                    size: 0,
+                    next_seq: 0,
+                    media_type: None,
+                    file,
+                },
+            );
+        }
+        let Some(open) = self.open.get_mut(name) else {
+            return Err(format!("chunk {seq} arrived before chunk 0"));
+        };
+        if seq != open.next_seq {
+            return Err(format!("chunk {seq} arrived, expected {}", open.next_seq));
+        }
+        open.size += bytes.len() as u64;
+        if open.size > self.max_bytes {
+            return Err(format!("exceeds the {} byte limit", self.max_bytes));
+        }
+        if let Some((path, file)) = &mut open.file {
+            file.write_all(&bytes)
+                .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+        }
+        open.hasher.update(&bytes);
+        open.next_seq += 1;
+        if let Some(media_type) = media_type {
+            open.media_type = Some(media_type.to_string());
+        }
+        Ok(())
+    }
+
+    fn complete(&mut self, name: &str, expected: Option<&str>) -> Result<StreamedArtifact, String> {
+        let open = self
+            .open
+            .remove(name)
+            .expect("a chunk was just added to this artifact");
+        let sha256 = format!("{:x}", open.hasher.finalize());
+        let stored_at = open.file.map(|(path, _)| path);
+        if let Some(expected) = expected
+            && !expected.eq_ignore_ascii_case(&sha256)
+        {
+            if let Some(path) = &stored_at {
+                let _ = std::fs::remove_file(path);
+            }
+            return Err(format!(
+                "SHA-256 {sha256} does not match the declared {expected}"
+            ));
+        }
+        let artifact = StreamedArtifact {
+            name: name.to_string(),
+            size: open.size,
+            sha256,
+            chunks: open.next_seq,
+            media_type: open.media_type,
+            stored_at: stored_at.map(|p| p.to_string_lossy().into_owned()),
+        };
+        self.done.insert(name.to_string(), artifact.clone());
+        Ok(artifact)
+    }
+
+    /// Drop the open artifact `name` and its partial file, if any.
+    fn discard(&mut self, name: &str) {
+        if let Some(OpenArtifact {
+            file: Some((path, file)),
+            ..
+        }) = self.open.remove(name)
+        {
+            drop(file);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Whether `name` stays inside the directory it is joined to.
+fn is_safe_name(name: &str) -> bool {
+    let path = Path::new(name);
+    !name.is_empty() && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
@@ -0,0 +1,190 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Collection of files a run leaves in its workspace.
+//!
+//! With [`Runtime::with_artifact_collector`](crate::Runtime::with_artifact_collector)
+//! the runtime scans the prepared workspace after the backend finishes. Every
+//! file matching the collector's globs is listed in `receipt.artifacts` (kind
+//! `"file"`) and described in `usage_raw["artifacts"]` by an
+//! [`ArtifactRecord`](crate::artifacts::ArtifactRecord): path, size, SHA-256,
+//! and — for small UTF-8 files, if enabled — the content itself. Both are
+//! covered by the receipt hash, so a consumer can later check a workspace
+//! against the receipt with
+//! [`ArtifactRecord::verify`](crate::artifacts::ArtifactRecord::verify).
+//!
+//! `.git` is never scanned.
+
+use std::path::Path;
+
+use abp_core::{ArtifactRef, Receipt};
+use abp_glob::IncludeExcludeGlobs;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+/// Key under `receipt.usage_raw` holding the [`ArtifactRecord`]s.
+pub const ARTIFACTS_KEY: &str = "artifacts";
+
+/// `ArtifactRef::kind` of collected workspace files.
+pub const FILE_ARTIFACT_KIND: &str = "file";
+
+/// One collected file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactRecord {
+    /// Path relative to the workspace root, with `/` separators.
+    pub path: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Hex-encoded SHA-256 of the content.
+    pub sha256: String,
+    /// The content, for UTF-8 files within the inline limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+impl ArtifactRecord {
+    /// The records stored on `receipt`, if any.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Vec<Self> {
+        receipt
+            .usage_raw
+            .get(ARTIFACTS_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Whether the file under `root` still has the recorded size and hash.
+    #[must_use]
+    pub fn verify(&self, root: &Path) -> bool {
+        std::fs::read(root.join(&self.path)).is_ok_and(|bytes| {
+            bytes.len() as u64 == self.size && abp_core::sha256_hex(&bytes) == self.sha256
+        })
+    }
+}
+
+/// Which workspace files to collect after a run, and how much to keep.
+#[derive(Debug, Clone)]
+pub struct ArtifactCollector {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    globs: IncludeExcludeGlobs,
+    inline_max_bytes: u64,
+    max_files: usize,
+}
+
+impl ArtifactCollector {
+    /// Default cap on the number of collected files.
+    pub const DEFAULT_MAX_FILES: usize = 1000;
+
+    /// Collect files matching `include` (every file when empty), hashing
+    /// but not inlining them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pattern is invalid.
+    pub fn new(include: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: include.to_vec(),
+            exclude: Vec::new(),
+            globs: IncludeExcludeGlobs::new(include, &[])?,
+            inline_max_bytes: 0,
+            max_files: Self::DEFAULT_MAX_FILES,
+        })
+    }
+
+    /// Skip files matching `patterns` (builder pattern).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pattern is invalid.
+    pub fn exclude(mut self, patterns: &[String]) -> Result<Self> {
+        self.exclude.extend_from_slice(patterns);
+        self.globs = IncludeExcludeGlobs::new(&self.include, &self.exclude)?;
+        Ok(self)
+    }
+
+    /// Inline UTF-8 files of at most `max_bytes`; zero disables inlining
+    /// (builder pattern).
+    #[must_use]
+    pub fn inline_up_to(mut self, max_bytes: u64) -> Self {
+        self.inline_max_bytes = max_bytes;
+        self
+    }
+
+    /// Stop after `max_files` files (builder pattern).
+    #[must_use]
+    pub fn max_files(mut self, max_files: usize) -> Self {
+        self.max_files = max_files;
+        self
+    }
+
+    /// Scan `root` and describe every matching file, sorted by path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the tree cannot be walked or a file cannot be read.
+    pub fn collect(&self, root: &Path) -> Result<Vec<ArtifactRecord>> {
+        let mut records = Vec::new();
+        let walker = WalkDir::new(root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| e.file_name() != ".git");
+        for entry in walker {
+            let entry = entry.context("walk workspace")?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(root)
+                .expect("walked paths are under the root");
+            if !self.globs.decide_path(rel).is_allowed() {
+                continue;
+            }
+            if records.len() == self.max_files {
+                tracing::warn!(
+                    target: "abp.runtime",
+                    max_files = self.max_files,
+                    "artifact limit reached; remaining files not collected"
+                );
+                break;
+            }
+            let bytes = std::fs::read(entry.path())
+                .with_context(|| format!("read artifact {}", rel.display()))?;
+            let size = bytes.len() as u64;
+            let sha256 = abp_core::sha256_hex(&bytes);
+            let content = (size <= self.inline_max_bytes)
+                .then(|| String::from_utf8(bytes).ok())
+                .flatten();
+            records.push(ArtifactRecord {
+                path: rel.to_string_lossy().replace('\\', "/"),
+                size,
+                sha256,
+                content,
+            });
+        }
+        Ok(records)
+    }
+
+    /// List `records` in `receipt.artifacts` and store them under
+    /// `usage_raw["artifacts"]`.
+    pub fn attach(records: &[ArtifactRecord], receipt: &mut Receipt) {
+        for record in records {
+            let listed = receipt
+                .artifacts
+                .iter()
+                .any(|a| a.kind == FILE_ARTIFACT_KIND && a.path == record.path);
+            if !listed {
+                receipt.artifacts.push(ArtifactRef {
+                    kind: FILE_ARTIFACT_KIND.to_string(),
+                    path: record.path.clone(),
+                });
+            }
+        }
+        if let (Some(obj), Ok(val)) = (
+            receipt.usage_raw.as_object_mut(),
+            serde_json::to_value(records),
+        ) {
+            obj.insert(ARTIFACTS_KEY.to_string(), val);
+        }
+    }
+}
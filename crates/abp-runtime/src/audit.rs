@@ -0,0 +1,372 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Append-only, hash-chained audit log of operational actions.
+//!
+//! Receipts describe what an agent did during a run. The audit log describes
+//! what people and services did to the runtime: who submitted which work
+//! order, configuration changes, approval decisions, cancellations, use of
+//! API keys, and access control decisions. Each [`AuditEntry`](crate::audit::AuditEntry) carries the
+//! SHA-256 of the entry before it, so removing, reordering, or editing any
+//! line breaks [`verify_chain`](crate::audit::verify_chain).
+//!
+//! An [`AuditLog`](crate::audit::AuditLog) keeps entries in memory or appends
+//! them as JSONL to a file; reopening the file resumes the chain where it
+//! left off. Attach one with
+//! [`Runtime::with_audit_log`](crate::Runtime::with_audit_log) to record
+//! every work order the runtime accepts.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use abp_core::WorkOrder;
+use abp_core::clock::{SharedClock, system_clock};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::rbac::Permission;
+
+/// Something that happened to the runtime, as recorded in the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AuditAction {
+    /// A work order was accepted for execution.
+    WorkOrderSubmitted {
+        /// The submitted work order.
+        work_order_id: Uuid,
+        /// Run id assigned to it.
+        run_id: Uuid,
+        /// Backend it was dispatched to.
+        backend: String,
+    },
+    /// A run was cancelled before it finished.
+    RunCancelled {
+        /// The cancelled run.
+        run_id: Uuid,
+        /// Why, if given.
+        reason: Option<String>,
+    },
+    /// A tool call that requires approval was approved or denied.
+    ApprovalDecided {
+        /// Run the tool call belongs to.
+        run_id: Uuid,
+        /// Tool that asked for approval.
+        tool: String,
+        /// Whether the call was allowed.
+        approved: bool,
+    },
+    /// A configuration value was changed.
+    ConfigChanged {
+        /// Dotted path of the setting, e.g. `backends.openai.model`.
+        key: String,
+        /// Previous value, if any.
+        old: Option<serde_json::Value>,
+        /// New value, if any.
+        new: Option<serde_json::Value>,
+    },
+    /// An API key or channel key was used.
+    KeyUsed {
+        /// Identifier of the key (never the secret itself).
+        key_id: String,
+        /// What the key was used for.
+        purpose: String,
+    },
+    /// A caller's permission for an operation was checked.
+    AccessChecked {
+        /// The operation requested.
+        permission: Permission,
+        /// What it targeted, e.g. `run:<uuid>`.
+        resource: String,
+        /// Whether it was allowed.
+        allowed: bool,
+    },
+}
+
+/// One line of the audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Position in the log, starting at 0.
+    pub seq: u64,
+    /// When the action was recorded.
+    pub timestamp: DateTime<Utc>,
+    /// Who performed the action, if known.
+    pub actor: Option<String>,
+    /// What happened.
+    #[serde(flatten)]
+    pub action: AuditAction,
+    /// Hash of the previous entry; `None` for the first entry.
+    pub prev_sha256: Option<String>,
+    /// Hash of this entry, computed with this field set to `null`.
+    pub entry_sha256: Option<String>,
+}
+
+impl AuditEntry {
+    /// Canonical SHA-256 of the entry, ignoring `entry_sha256`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be serialized.
+    pub fn compute_hash(&self) -> Result<String> {
+        let mut unhashed = self.clone();
+        unhashed.entry_sha256 = None;
+        let json = abp_core::canonical_json(&unhashed).context("serialize audit entry")?;
+        Ok(abp_core::sha256_hex(json.as_bytes()))
+    }
+}
+
+/// Why an audit chain failed verification.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AuditChainError {
+    /// An entry's sequence number is not one more than its predecessor's.
+    #[error("audit entry {index}: expected seq {expected}, found {found}")]
+    SeqGap {
+        /// Index of the entry in the slice.
+        index: usize,
+        /// Sequence number expected at that index.
+        expected: u64,
+        /// Sequence number found.
+        found: u64,
+    },
+    /// An entry's `prev_sha256` does not match the previous entry's hash.
+    #[error("audit entry {seq}: previous hash does not match")]
+    BrokenLink {
+        /// Sequence number of the entry.
+        seq: u64,
+    },
+    /// An entry's contents do not match its `entry_sha256`.
+    #[error("audit entry {seq}: hash does not match contents")]
+    Tampered {
+        /// Sequence number of the entry.
+        seq: u64,
+    },
+}
+
+/// Check that `entries` form an unbroken chain.
+///
+/// The first entry may have any sequence number and previous hash, so a
+/// tail of a longer log can be verified on its own.
+///
+/// # Errors
+///
+/// Returns the first [`AuditChainError`] found.
+pub fn verify_chain(entries: &[AuditEntry]) -> std::result::Result<(), AuditChainError> {
+    let mut prev: Option<&AuditEntry> = None;
+    for (index, entry) in entries.iter().enumerate() {
+        if entry.compute_hash().ok().as_deref() != entry.entry_sha256.as_deref() {
+            return Err(AuditChainError::Tampered { seq: entry.seq });
+        }
+        if let Some(prev) = prev {
+            let expected = prev.seq + 1;
+            if entry.seq != expected {
+                return Err(AuditChainError::SeqGap {
+                    index,
+                    expected,
+                    found: entry.seq,
+                });
+            }
+            if entry.prev_sha256 != prev.entry_sha256 {
+                return Err(AuditChainError::BrokenLink { seq: entry.seq });
+            }
+        }
+        prev = Some(entry);
+    }
+    Ok(())
+}
+
+/// Read every entry from a JSONL audit log file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or a line is not an entry.
+pub fn read_log(path: &Path) -> Result<Vec<AuditEntry>> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    text.lines()
+        .filter(|l| !l.trim().is_empty())
+        .enumerate()
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("parse {} line {}", path.display(), i + 1))
+        })
+        .collect()
+}
+
+/// Who submitted a work order: its submitter's API key, else its tenant.
+#[must_use]
+pub fn actor_for_work_order(work_order: &WorkOrder) -> Option<String> {
+    let submitter = work_order.config.submitter.as_ref()?;
+    submitter
+        .api_key_id
+        .clone()
+        .or_else(|| submitter.tenant.clone())
+}
+
+#[derive(Debug, Default)]
+struct ChainHead {
+    next_seq: u64,
+    last_hash: Option<String>,
+    entries: Vec<AuditEntry>,
+}
+
+/// Append-only audit log, in memory or backed by a JSONL file.
+///
+/// # Examples
+///
+/// ```
+/// use abp_runtime::audit::{AuditAction, AuditLog, verify_chain};
+///
+/// let log = AuditLog::in_memory();
+/// log.record(
+///     Some("ops@example.com"),
+///     AuditAction::ConfigChanged {
+///         key: "backends.default".into(),
+///         old: Some("mock".into()),
+///         new: Some("openai".into()),
+///     },
+/// )
+/// .unwrap();
+/// let entries = log.entries();
+/// assert_eq!(entries.len(), 1);
+/// assert!(verify_chain(&entries).is_ok());
+/// ```
+#[derive(Debug)]
+pub struct AuditLog {
+    path: Option<PathBuf>,
+    head: Mutex<ChainHead>,
+    clock: SharedClock,
+}
+
+impl AuditLog {
+    /// A log that keeps entries in memory only.
+    #[must_use]
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            head: Mutex::new(ChainHead::default()),
+            clock: system_clock(),
+        }
+    }
+
+    /// Open (or create) a JSONL log at `path`, resuming its chain.
+    ///
+    /// Existing entries are verified before new ones are appended; only the
+    /// chain head is kept in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, a line cannot be parsed,
+    /// or the existing chain does not verify.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let mut head = ChainHead::default();
+        if path.exists() {
+            let entries = read_log(&path)?;
+            verify_chain(&entries).with_context(|| format!("verify {}", path.display()))?;
+            if let Some(last) = entries.last() {
+                head.next_seq = last.seq + 1;
+                head.last_hash = last.entry_sha256.clone();
+            }
+        }
+        Ok(Self {
+            path: Some(path),
+            head: Mutex::new(head),
+            clock: system_clock(),
+        })
+    }
+
+    /// Timestamp entries with `clock` (builder pattern).
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Return the backing file, if any.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Append an entry for `action` by `actor` and return it.
+    ///
+    /// File-backed logs write and flush the line before returning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be hashed or written; the chain
+    /// is left unchanged in that case.
+    pub fn record(&self, actor: Option<&str>, action: AuditAction) -> Result<AuditEntry> {
+        let mut head = self
+            .head
+            .lock()
+            .map_err(|_| anyhow::anyhow!("audit log lock poisoned"))?;
+        let mut entry = AuditEntry {
+            seq: head.next_seq,
+            timestamp: self.clock.now(),
+            actor: actor.map(str::to_string),
+            action,
+            prev_sha256: head.last_hash.clone(),
+            entry_sha256: None,
+        };
+        entry.entry_sha256 = Some(entry.compute_hash()?);
+
+        match &self.path {
+            Some(path) => {
+                if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(parent)
+                        .with_context(|| format!("create {}", parent.display()))?;
+                }
+                let mut line = serde_json::to_string(&entry).context("serialize audit entry")?;
+                line.push('\n');
+                let mut file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .with_context(|| format!("open {}", path.display()))?;
+                file.write_all(line.as_bytes())
+                    .and_then(|()| file.sync_data())
+                    .with_context(|| format!("append to {}", path.display()))?;
+            }
+            None => head.entries.push(entry.clone()),
+        }
+
+        head.next_seq += 1;
+        head.last_hash = entry.entry_sha256.clone();
+        Ok(entry)
+    }
+
+    /// Every entry in the log, oldest first.
+    ///
+    /// File-backed logs are re-read from disk; unreadable files yield an
+    /// empty list.
+    #[must_use]
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        match &self.path {
+            Some(path) if path.exists() => read_log(path).unwrap_or_default(),
+            Some(_) => Vec::new(),
+            None => self
+                .head
+                .lock()
+                .map(|h| h.entries.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Number of entries recorded so far.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.head.lock().map(|h| h.next_seq).unwrap_or_default()
+    }
+
+    /// Whether nothing has been recorded yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hash of the most recent entry, for anchoring the log elsewhere.
+    #[must_use]
+    pub fn head_hash(&self) -> Option<String> {
+        self.head.lock().ok().and_then(|h| h.last_hash.clone())
+    }
+}
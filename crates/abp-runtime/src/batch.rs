@@ -0,0 +1,324 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Concurrent execution of a batch of work orders.
+//!
+//! [`Runtime::run_batch`] runs every work order of a batch on the backend its
+//! [`BatchTarget`](crate::batch::BatchTarget) picks, with at most
+//! [`concurrency`](crate::batch::BatchOptions::concurrency) runs in flight.
+//! Each run's [`RunHandle`] is yielded on
+//! [`BatchHandle::runs`](crate::batch::BatchHandle::runs) as soon as it starts,
+//! tagged with the work order's index in the batch; a run that could not start
+//! (unknown backend, failed pre-flight check) is yielded as its error instead.
+//! Once every run has finished,
+//! [`BatchHandle::receipt`](crate::batch::BatchHandle::receipt) resolves to a
+//! [`BatchReceipt`](crate::batch::BatchReceipt) with the outcome, usage, and
+//! error of each run and their totals.
+//!
+//! A slot is freed when a run's receipt is ready, and a run only gets there
+//! once its events are drained. Consumers that hold on to handles without
+//! reading their events therefore stall the batch after `concurrency` runs:
+//! drain each handle as it arrives, or spawn a task per handle.
+
+use std::sync::Arc;
+
+use abp_core::{Outcome, Receipt, UsageNormalized, WorkOrder};
+use abp_error::ErrorCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Semaphore, mpsc};
+use tokio::task::JoinHandle;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::tool_loop::add_usage;
+use crate::{RunHandle, Runtime, RuntimeError, elapsed_ms};
+
+/// Which backend each work order of a batch runs on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchTarget {
+    /// Every work order runs on this backend.
+    Backend(String),
+    /// Work order `i` runs on backend `i % len`; an empty list fails every
+    /// run with [`RuntimeError::UnknownBackend`].
+    RoundRobin(Vec<String>),
+    /// Each work order runs on the backend the projection matrix selects for
+    /// it (see [`Runtime::select_backend`]).
+    Projected,
+}
+
+/// How a batch is run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchOptions {
+    /// Backend selection for the batch's work orders.
+    pub target: BatchTarget,
+    /// Maximum number of runs in flight at once (at least 1).
+    pub concurrency: usize,
+}
+
+impl BatchOptions {
+    /// Default number of runs in flight.
+    pub const DEFAULT_CONCURRENCY: usize = 8;
+
+    /// Run the batch against `target` with the default concurrency.
+    #[must_use]
+    pub fn new(target: BatchTarget) -> Self {
+        Self {
+            target,
+            concurrency: Self::DEFAULT_CONCURRENCY,
+        }
+    }
+
+    /// Run every work order on the backend `name`.
+    #[must_use]
+    pub fn backend(name: impl Into<String>) -> Self {
+        Self::new(BatchTarget::Backend(name.into()))
+    }
+
+    /// Keep at most `limit` runs in flight (builder pattern).
+    ///
+    /// A limit of `0` is treated as `1`.
+    #[must_use]
+    pub fn concurrency(mut self, limit: usize) -> Self {
+        self.concurrency = limit;
+        self
+    }
+}
+
+/// A started batch.
+pub struct BatchHandle {
+    /// Each run as it starts, tagged with its index in the batch.
+    ///
+    /// Handles arrive in index order. A handle's receipt is the run's
+    /// receipt; awaiting it is optional.
+    pub runs: ReceiverStream<(usize, Result<RunHandle, RuntimeError>)>,
+    /// Resolves to the batch's summary once every run has finished.
+    pub receipt: JoinHandle<BatchReceipt>,
+}
+
+/// How one run of a batch went.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchRunSummary {
+    /// Position of the work order in the batch.
+    pub index: usize,
+    /// Id of the work order.
+    pub work_order_id: Uuid,
+    /// Backend the run was sent to, if one was selected.
+    pub backend: Option<String>,
+    /// Id of the run, if it started.
+    pub run_id: Option<Uuid>,
+    /// Outcome of the receipt; `None` when the run produced no receipt.
+    pub outcome: Option<Outcome>,
+    /// Error that kept the run from starting or producing a receipt.
+    pub error: Option<String>,
+    /// Taxonomy code of [`error`](Self::error).
+    pub error_code: Option<ErrorCode>,
+    /// Normalized usage from the receipt.
+    pub usage: UsageNormalized,
+    /// Time from the run's start to its receipt, in milliseconds.
+    pub duration_ms: u64,
+}
+
+impl BatchRunSummary {
+    /// Whether the run produced a receipt with [`Outcome::Complete`].
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.outcome == Some(Outcome::Complete)
+    }
+
+    /// Whether the run failed to start, produced no receipt, or ended with
+    /// [`Outcome::Failed`].
+    #[must_use]
+    pub fn is_failure(&self) -> bool {
+        matches!(self.outcome, None | Some(Outcome::Failed))
+    }
+}
+
+/// Aggregate result of a batch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchReceipt {
+    /// Number of work orders in the batch.
+    pub total: usize,
+    /// Runs whose receipt outcome is [`Outcome::Complete`].
+    pub complete: usize,
+    /// Runs whose receipt outcome is [`Outcome::Partial`].
+    pub partial: usize,
+    /// Runs that failed (see [`BatchRunSummary::is_failure`]).
+    pub failed: usize,
+    /// Usage summed over every run.
+    pub usage: UsageNormalized,
+    /// When the batch started.
+    pub started_at: DateTime<Utc>,
+    /// When its last run finished.
+    pub finished_at: DateTime<Utc>,
+    /// Wall-clock time of the whole batch, in milliseconds.
+    pub duration_ms: u64,
+    /// One entry per work order, in batch order.
+    pub runs: Vec<BatchRunSummary>,
+}
+
+impl BatchReceipt {
+    /// The runs that failed, in batch order.
+    pub fn failures(&self) -> impl Iterator<Item = &BatchRunSummary> {
+        self.runs.iter().filter(|r| r.is_failure())
+    }
+}
+
+impl Runtime {
+    /// Run `work_orders` concurrently, at most `opts.concurrency` at a time.
+    ///
+    /// See [`batch`](crate::batch) for how runs are scheduled and reported.
+    /// Must be called from within a Tokio runtime.
+    pub fn run_batch(
+        self: &Arc<Self>,
+        work_orders: Vec<WorkOrder>,
+        opts: BatchOptions,
+    ) -> BatchHandle {
+        let runtime = Arc::clone(self);
+        let concurrency = opts.concurrency.max(1);
+        let (runs_tx, runs_rx) = mpsc::channel(concurrency);
+        let receipt = tokio::spawn(async move {
+            drive_batch(runtime, work_orders, opts.target, concurrency, runs_tx).await
+        });
+        BatchHandle {
+            runs: ReceiverStream::new(runs_rx),
+            receipt,
+        }
+    }
+}
+
+async fn drive_batch(
+    runtime: Arc<Runtime>,
+    work_orders: Vec<WorkOrder>,
+    target: BatchTarget,
+    concurrency: usize,
+    runs_tx: mpsc::Sender<(usize, Result<RunHandle, RuntimeError>)>,
+) -> BatchReceipt {
+    let clock = Arc::clone(&runtime.clock);
+    let started_at = clock.now();
+    let batch_start = clock.instant();
+    let total = work_orders.len();
+    let limit = Arc::new(Semaphore::new(concurrency));
+    let (done_tx, mut done_rx) = mpsc::unbounded_channel();
+
+    for (index, work_order) in work_orders.into_iter().enumerate() {
+        let permit = Arc::clone(&limit)
+            .acquire_owned()
+            .await
+            .expect("batch semaphore is never closed");
+        let run_start = clock.instant();
+        let work_order_id = work_order.id;
+        let backend = match &target {
+            BatchTarget::Backend(name) => Ok(name.clone()),
+            BatchTarget::RoundRobin(names) if names.is_empty() => {
+                Err(RuntimeError::UnknownBackend {
+                    name: String::new(),
+                })
+            }
+            BatchTarget::RoundRobin(names) => Ok(names[index % names.len()].clone()),
+            BatchTarget::Projected => runtime
+                .select_backend(&work_order)
+                .map(|p| p.selected_backend),
+        };
+        let (backend, started) = match backend {
+            Ok(name) => {
+                let started = runtime.run_streaming(&name, work_order).await;
+                (Some(name), started)
+            }
+            Err(e) => (None, Err(e)),
+        };
+        debug!(target: "abp.runtime", index, backend = ?backend, started = started.is_ok(), "batch run dispatched");
+
+        let item = match started {
+            Ok(RunHandle {
+                run_id,
+                events,
+                receipt,
+            }) => {
+                let clock = Arc::clone(&clock);
+                let done_tx = done_tx.clone();
+                let receipt = tokio::spawn(async move {
+                    let result = receipt
+                        .await
+                        .unwrap_or_else(|e| Err(RuntimeError::internal("run task failed", e)));
+                    drop(permit);
+                    let mut summary = summarize(index, work_order_id, backend, result.as_ref());
+                    summary.run_id = Some(run_id);
+                    summary.duration_ms = elapsed_ms(clock.as_ref(), run_start);
+                    let _ = done_tx.send(summary);
+                    result
+                });
+                Ok(RunHandle {
+                    run_id,
+                    events,
+                    receipt,
+                })
+            }
+            Err(e) => {
+                drop(permit);
+                let _ = done_tx.send(summarize(index, work_order_id, backend, Err(&e)));
+                Err(e)
+            }
+        };
+        // A caller that stopped listening still gets the batch receipt.
+        let _ = runs_tx.send((index, item)).await;
+    }
+    drop(runs_tx);
+    drop(done_tx);
+
+    let mut runs = Vec::with_capacity(total);
+    while let Some(summary) = done_rx.recv().await {
+        runs.push(summary);
+    }
+    runs.sort_by_key(|r| r.index);
+
+    let mut usage = UsageNormalized::default();
+    for run in &runs {
+        add_usage(&mut usage, &run.usage);
+    }
+    BatchReceipt {
+        total,
+        complete: runs.iter().filter(|r| r.is_complete()).count(),
+        partial: runs
+            .iter()
+            .filter(|r| r.outcome == Some(Outcome::Partial))
+            .count(),
+        failed: runs.iter().filter(|r| r.is_failure()).count(),
+        usage,
+        started_at,
+        finished_at: clock.now(),
+        duration_ms: elapsed_ms(clock.as_ref(), batch_start),
+        runs,
+    }
+}
+
+/// The summary of one run from its receipt or error.
+fn summarize(
+    index: usize,
+    work_order_id: Uuid,
+    backend: Option<String>,
+    result: Result<&Receipt, &RuntimeError>,
+) -> BatchRunSummary {
+    let mut summary = BatchRunSummary {
+        index,
+        work_order_id,
+        backend,
+        run_id: None,
+        outcome: None,
+        error: None,
+        error_code: None,
+        usage: UsageNormalized::default(),
+        duration_ms: 0,
+    };
+    match result {
+        Ok(receipt) => {
+            summary.outcome = Some(receipt.outcome.clone());
+            summary.usage = receipt.usage.clone();
+        }
+        Err(e) => {
+            summary.error = Some(e.to_string());
+            summary.error_code = Some(e.error_code());
+        }
+    }
+    summary
+}
@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Adaptive batching of assistant text deltas for slow consumers.
+//!
+//! Backends stream [`AssistantDelta`](abp_core::AgentEventKind::AssistantDelta)
+//! events a few characters at a time. A consumer that forwards each one over
+//! a high-latency link (e.g. a websocket to a browser) falls behind and the
+//! run's event channel fills up. With
+//! [`Runtime::with_delta_batching`](crate::Runtime::with_delta_batching) the
+//! runtime watches how full the caller's channel is after each send: while
+//! the consumer keeps up, deltas are forwarded one by one; as a backlog
+//! builds, consecutive deltas are merged into larger chunks, doubling the
+//! chunk size up to
+//! [`DeltaBatching::max_chars`](crate::batching::DeltaBatching::max_chars)
+//! and halving it again once the backlog clears. A partially filled chunk is
+//! never held longer than
+//! [`DeltaBatching::max_delay`](crate::batching::DeltaBatching::max_delay).
+//!
+//! Only the caller's event stream is batched; the receipt trace and journal
+//! keep every delta as the backend sent it.
+
+use std::time::Duration;
+
+use abp_core::{AgentEvent, AgentEventKind};
+use tokio::time::Instant;
+
+/// Limits for [`DeltaBatcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeltaBatching {
+    /// Largest merged chunk, in characters.
+    pub max_chars: usize,
+    /// Longest a partial chunk is held before it is sent anyway.
+    pub max_delay: Duration,
+}
+
+impl Default for DeltaBatching {
+    fn default() -> Self {
+        Self {
+            max_chars: 4096,
+            max_delay: Duration::from_millis(50),
+        }
+    }
+}
+
+/// Merges consecutive assistant deltas into chunks sized to the consumer's
+/// observed drain rate.
+#[derive(Debug, Clone)]
+pub struct DeltaBatcher {
+    config: DeltaBatching,
+    target: usize,
+    pending: Option<(AgentEvent, Instant)>,
+    pending_chars: usize,
+}
+
+impl DeltaBatcher {
+    /// A batcher that starts out forwarding every delta unmerged.
+    #[must_use]
+    pub fn new(config: DeltaBatching) -> Self {
+        Self {
+            config,
+            target: 1,
+            pending: None,
+            pending_chars: 0,
+        }
+    }
+
+    /// Current chunk size, in characters; `1` means no merging.
+    #[must_use]
+    pub fn target_chars(&self) -> usize {
+        self.target
+    }
+
+    /// Adjust the chunk size to the consumer's backlog: `queued` events are
+    /// waiting in a channel that holds `capacity`.
+    ///
+    /// A quarter-full channel doubles the chunk size; an empty one halves it.
+    pub fn observe_backlog(&mut self, queued: usize, capacity: usize) {
+        if capacity > 0 && queued * 4 >= capacity {
+            self.target = (self.target * 2).min(self.config.max_chars.max(1));
+        } else if queued == 0 {
+            self.target = (self.target / 2).max(1);
+        }
+    }
+
+    /// Accept the next event and return those ready to forward, in order.
+    ///
+    /// Any other event first flushes the pending chunk so ordering is kept.
+    /// Deltas carrying `ext` metadata are never merged.
+    pub fn push(&mut self, event: AgentEvent) -> Vec<AgentEvent> {
+        let mergeable =
+            event.ext.is_none() && matches!(event.kind, AgentEventKind::AssistantDelta { .. });
+        if !mergeable || self.target <= 1 {
+            let mut ready: Vec<_> = self.flush().into_iter().collect();
+            ready.push(event);
+            return ready;
+        }
+
+        if let AgentEventKind::AssistantDelta { text } = &event.kind {
+            self.pending_chars += text.chars().count();
+        }
+        match &mut self.pending {
+            Some((
+                AgentEvent {
+                    kind: AgentEventKind::AssistantDelta { text },
+                    ..
+                },
+                _,
+            )) => {
+                if let AgentEventKind::AssistantDelta { text: more } = event.kind {
+                    text.push_str(&more);
+                }
+            }
+            _ => self.pending = Some((event, Instant::now())),
+        }
+
+        if self.pending_chars >= self.target {
+            self.flush().into_iter().collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// When the pending chunk must be sent, if there is one.
+    #[must_use]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.pending
+            .as_ref()
+            .map(|(_, since)| *since + self.config.max_delay)
+    }
+
+    /// Take the pending chunk, if any.
+    pub fn flush(&mut self) -> Option<AgentEvent> {
+        self.pending_chars = 0;
+        self.pending.take().map(|(event, _)| event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn delta(text: &str) -> AgentEvent {
+        AgentEvent {
+            ts: Utc::now(),
+            kind: AgentEventKind::AssistantDelta { text: text.into() },
+            ext: None,
+        }
+    }
+
+    fn text_of(ev: &AgentEvent) -> &str {
+        match &ev.kind {
+            AgentEventKind::AssistantDelta { text } => text,
+            other => panic!("expected delta, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn fast_consumer_gets_every_delta() {
+        let mut b = DeltaBatcher::new(DeltaBatching::default());
+        b.observe_backlog(0, 256);
+        assert_eq!(b.push(delta("a")).len(), 1);
+        assert_eq!(b.push(delta("b")).len(), 1);
+        assert!(b.deadline().is_none());
+    }
+
+    #[test]
+    fn backlog_grows_and_shrinks_chunk_size() {
+        let mut b = DeltaBatcher::new(DeltaBatching {
+            max_chars: 8,
+            ..DeltaBatching::default()
+        });
+        for _ in 0..5 {
+            b.observe_backlog(64, 256);
+        }
+        assert_eq!(b.target_chars(), 8);
+        b.observe_backlog(10, 256);
+        assert_eq!(b.target_chars(), 8);
+        b.observe_backlog(0, 256);
+        b.observe_backlog(0, 256);
+        assert_eq!(b.target_chars(), 2);
+    }
+
+    #[test]
+    fn slow_consumer_gets_merged_chunks() {
+        let mut b = DeltaBatcher::new(DeltaBatching::default());
+        b.observe_backlog(128, 256);
+        b.observe_backlog(128, 256);
+        assert_eq!(b.target_chars(), 4);
+        assert!(b.push(delta("ab")).is_empty());
+        assert!(b.deadline().is_some());
+        let ready = b.push(delta("cd"));
+        assert_eq!(ready.len(), 1);
+        assert_eq!(text_of(&ready[0]), "abcd");
+        assert!(b.deadline().is_none());
+    }
+
+    #[test]
+    fn other_events_flush_pending_chunk_first() {
+        let mut b = DeltaBatcher::new(DeltaBatching::default());
+        b.observe_backlog(256, 256);
+        b.observe_backlog(256, 256);
+        assert!(b.push(delta("x")).is_empty());
+        let msg = AgentEvent {
+            ts: Utc::now(),
+            kind: AgentEventKind::AssistantMessage {
+                text: "done".into(),
+            },
+            ext: None,
+        };
+        let ready = b.push(msg);
+        assert_eq!(ready.len(), 2);
+        assert_eq!(text_of(&ready[0]), "x");
+        assert!(matches!(
+            ready[1].kind,
+            AgentEventKind::AssistantMessage { .. }
+        ));
+    }
+
+    #[test]
+    fn deltas_with_ext_are_not_merged() {
+        let mut b = DeltaBatcher::new(DeltaBatching::default());
+        b.observe_backlog(256, 256);
+        b.observe_backlog(256, 256);
+        let mut tagged = delta("y");
+        tagged.ext = Some(Default::default());
+        assert_eq!(b.push(tagged).len(), 1);
+    }
+}
@@ -0,0 +1,398 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Per-backend circuit breakers.
+//!
+//! A flapping sidecar should cost a few failed runs, not every run.
+//! [`CircuitBreakers`](crate::breaker::CircuitBreakers) attached with
+//! [`Runtime::with_circuit_breakers`](crate::Runtime::with_circuit_breakers)
+//! track each backend's consecutive failures — runs that crashed or timed
+//! out, as judged by [`should_fall_back`](crate::execution::should_fall_back):
+//!
+//! * **Closed** — runs go through. After
+//!   [`BreakerConfig::failure_threshold`](crate::breaker::BreakerConfig::failure_threshold)
+//!   consecutive failures the breaker opens.
+//! * **Open** — runs are rejected with
+//!   [`ErrorCode::CircuitBreakerOpen`](abp_error::ErrorCode::CircuitBreakerOpen)
+//!   and a `retry_after_ms` context entry, and
+//!   [`Runtime::select_backend`](crate::Runtime::select_backend) leaves the
+//!   backend out of projection. After
+//!   [`BreakerConfig::cooldown`](crate::breaker::BreakerConfig::cooldown) it
+//!   turns half-open.
+//! * **Half-open** — up to
+//!   [`BreakerConfig::half_open_probes`](crate::breaker::BreakerConfig::half_open_probes)
+//!   runs go through as probes. If that many succeed the breaker closes; any
+//!   failure opens it again for another cooldown.
+//!
+//! State changes are logged and recorded in the runtime's
+//! [`RunMetrics`](crate::telemetry::RunMetrics), whose snapshot lists each
+//! breaker's state and the number of times breakers tripped.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use abp_core::Receipt;
+use abp_core::clock::{Clock, SharedClock};
+use abp_error::{AbpError, ErrorCode};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+use crate::RuntimeError;
+use crate::telemetry::RunMetrics;
+
+/// State of one backend's breaker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    /// Runs go through.
+    #[default]
+    Closed,
+    /// Runs are rejected until the cooldown ends.
+    Open,
+    /// A limited number of probe runs go through.
+    HalfOpen,
+}
+
+/// When a breaker opens and how it recovers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakerConfig {
+    /// Consecutive failures that open the breaker.
+    pub failure_threshold: u32,
+    /// How long the breaker stays open before probing.
+    #[serde(with = "duration_millis")]
+    pub cooldown: Duration,
+    /// Probe runs let through while half-open, and the successes needed to
+    /// close.
+    pub half_open_probes: u32,
+}
+
+impl Default for BreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+            half_open_probes: 1,
+        }
+    }
+}
+
+impl BreakerConfig {
+    /// Open after `n` consecutive failures (builder pattern; at least 1).
+    #[must_use]
+    pub fn failure_threshold(mut self, n: u32) -> Self {
+        self.failure_threshold = n.max(1);
+        self
+    }
+
+    /// Stay open for `d` before probing (builder pattern).
+    #[must_use]
+    pub fn cooldown(mut self, d: Duration) -> Self {
+        self.cooldown = d;
+        self
+    }
+
+    /// Let `n` probes through while half-open (builder pattern; at least 1).
+    #[must_use]
+    pub fn half_open_probes(mut self, n: u32) -> Self {
+        self.half_open_probes = n.max(1);
+        self
+    }
+}
+
+/// A backend's breaker at one point in time.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BreakerStatus {
+    /// Registered backend name.
+    pub backend: String,
+    /// Current state.
+    pub state: BreakerState,
+    /// Failures since the last success.
+    pub consecutive_failures: u32,
+    /// When the breaker last opened.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opened_at: Option<DateTime<Utc>>,
+    /// Times the breaker has opened.
+    pub trips: u64,
+}
+
+/// How a run was let through, to report its result against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// The breaker was closed.
+    Normal,
+    /// The run is a half-open probe.
+    Probe,
+}
+
+#[derive(Debug, Default)]
+struct Breaker {
+    state: BreakerState,
+    consecutive_failures: u32,
+    opened: Option<(Instant, DateTime<Utc>)>,
+    probes_in_flight: u32,
+    probe_successes: u32,
+    trips: u64,
+}
+
+impl Breaker {
+    /// Time left before an open breaker may probe.
+    fn cooldown_left(&self, config: &BreakerConfig, now: Instant) -> Duration {
+        self.opened.map_or(Duration::ZERO, |(at, _)| {
+            config
+                .cooldown
+                .saturating_sub(now.saturating_duration_since(at))
+        })
+    }
+
+    fn open(&mut self, clock: &dyn Clock) {
+        self.state = BreakerState::Open;
+        self.opened = Some((clock.instant(), clock.now()));
+        self.probes_in_flight = 0;
+        self.probe_successes = 0;
+        self.trips += 1;
+    }
+}
+
+/// Circuit breakers for every backend.
+///
+/// Backends get [`BreakerConfig::default`] unless configured otherwise.
+#[derive(Debug, Default)]
+pub struct CircuitBreakers {
+    default: BreakerConfig,
+    overrides: BTreeMap<String, BreakerConfig>,
+    breakers: Mutex<BTreeMap<String, Breaker>>,
+}
+
+impl CircuitBreakers {
+    /// Breakers with `config` for every backend.
+    #[must_use]
+    pub fn new(config: BreakerConfig) -> Self {
+        Self {
+            default: config,
+            ..Self::default()
+        }
+    }
+
+    /// Use `config` for `backend` instead of the default (builder pattern).
+    #[must_use]
+    pub fn backend(mut self, backend: impl Into<String>, config: BreakerConfig) -> Self {
+        self.overrides.insert(backend.into(), config);
+        self
+    }
+
+    /// The settings for `backend`.
+    #[must_use]
+    pub fn config(&self, backend: &str) -> &BreakerConfig {
+        self.overrides.get(backend).unwrap_or(&self.default)
+    }
+
+    /// Let a run on `backend` through, or say why not.
+    ///
+    /// An open breaker whose cooldown has ended turns half-open here.
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::CircuitBreakerOpen`] while the breaker is open, or
+    /// half-open with every probe slot taken.
+    pub fn admit(&self, backend: &str, clock: &dyn Clock) -> Result<Admission, AbpError> {
+        let config = self.config(backend);
+        let mut breakers = self.lock();
+        let breaker = breakers.entry(backend.to_string()).or_default();
+        if breaker.state == BreakerState::Open {
+            let left = breaker.cooldown_left(config, clock.instant());
+            if !left.is_zero() {
+                return Err(breaker_open(backend, left));
+            }
+            info!(target: "abp.runtime", backend, "circuit breaker half-open; probing backend");
+            breaker.state = BreakerState::HalfOpen;
+        }
+        match breaker.state {
+            BreakerState::Closed => Ok(Admission::Normal),
+            _ if breaker.probes_in_flight < config.half_open_probes => {
+                breaker.probes_in_flight += 1;
+                Ok(Admission::Probe)
+            }
+            _ => Err(breaker_open(backend, Duration::ZERO)),
+        }
+    }
+
+    /// Report how a run let through by [`admit`](Self::admit) ended.
+    ///
+    /// Returns the breaker's new state when it changed.
+    pub fn record(
+        &self,
+        backend: &str,
+        admission: Admission,
+        success: bool,
+        clock: &dyn Clock,
+    ) -> Option<BreakerState> {
+        let config = self.config(backend);
+        let mut breakers = self.lock();
+        let breaker = breakers.entry(backend.to_string()).or_default();
+        let before = breaker.state;
+        if admission == Admission::Probe {
+            breaker.probes_in_flight = breaker.probes_in_flight.saturating_sub(1);
+        }
+        if success {
+            breaker.consecutive_failures = 0;
+            if breaker.state == BreakerState::HalfOpen && admission == Admission::Probe {
+                breaker.probe_successes += 1;
+                if breaker.probe_successes >= config.half_open_probes {
+                    breaker.state = BreakerState::Closed;
+                    breaker.opened = None;
+                }
+            }
+        } else {
+            breaker.consecutive_failures += 1;
+            let trip = match breaker.state {
+                BreakerState::Closed => breaker.consecutive_failures >= config.failure_threshold,
+                BreakerState::HalfOpen => admission == Admission::Probe,
+                BreakerState::Open => false,
+            };
+            if trip {
+                breaker.open(clock);
+            }
+        }
+
+        let after = breaker.state;
+        match after {
+            _ if after == before => return None,
+            BreakerState::Open => warn!(
+                target: "abp.runtime",
+                backend,
+                failures = breaker.consecutive_failures,
+                cooldown_ms = config.cooldown.as_millis() as u64,
+                "circuit breaker opened"
+            ),
+            _ => info!(target: "abp.runtime", backend, "circuit breaker closed"),
+        }
+        Some(after)
+    }
+
+    /// Give back a run let through by [`admit`](Self::admit) that never
+    /// reached the backend, or failed for reasons of its own.
+    ///
+    /// Neither a success nor a failure; a probe slot is freed for another
+    /// run.
+    pub fn release(&self, backend: &str, admission: Admission) {
+        if admission == Admission::Probe
+            && let Some(breaker) = self.lock().get_mut(backend)
+        {
+            breaker.probes_in_flight = breaker.probes_in_flight.saturating_sub(1);
+        }
+    }
+
+    /// Whether a run on `backend` would be let through now.
+    #[must_use]
+    pub fn is_available(&self, backend: &str, clock: &dyn Clock) -> bool {
+        let config = self.config(backend);
+        let breakers = self.lock();
+        let Some(breaker) = breakers.get(backend) else {
+            return true;
+        };
+        match breaker.state {
+            BreakerState::Closed => true,
+            BreakerState::Open => breaker.cooldown_left(config, clock.instant()).is_zero(),
+            BreakerState::HalfOpen => breaker.probes_in_flight < config.half_open_probes,
+        }
+    }
+
+    /// The state of `backend`'s breaker; backends never seen are closed.
+    #[must_use]
+    pub fn state(&self, backend: &str) -> BreakerState {
+        self.lock()
+            .get(backend)
+            .map_or(BreakerState::Closed, |b| b.state)
+    }
+
+    /// Every breaker that has seen a run, sorted by backend.
+    #[must_use]
+    pub fn statuses(&self) -> Vec<BreakerStatus> {
+        self.lock()
+            .iter()
+            .map(|(backend, b)| BreakerStatus {
+                backend: backend.clone(),
+                state: b.state,
+                consecutive_failures: b.consecutive_failures,
+                opened_at: b.opened.map(|(_, at)| at),
+                trips: b.trips,
+            })
+            .collect()
+    }
+
+    /// Close `backend`'s breaker and forget its failures.
+    pub fn reset(&self, backend: &str) {
+        if let Some(breaker) = self.lock().get_mut(backend) {
+            let trips = breaker.trips;
+            *breaker = Breaker {
+                trips,
+                ..Breaker::default()
+            };
+        }
+    }
+
+    /// Report the result of `receipt` to `backend`'s breaker once it
+    /// resolves, returning a handle that resolves to the same result.
+    ///
+    /// Errors that are not the backend's fault, such as policy or workspace
+    /// failures, internal runtime errors, or a run task that died, are
+    /// [released](Self::release) rather than counted.
+    pub(crate) fn watch(
+        self: Arc<Self>,
+        backend: String,
+        admission: Admission,
+        clock: SharedClock,
+        metrics: Arc<RunMetrics>,
+        receipt: JoinHandle<Result<Receipt, RuntimeError>>,
+    ) -> JoinHandle<Result<Receipt, RuntimeError>> {
+        tokio::spawn(async move {
+            let joined = receipt.await;
+            let success = match &joined {
+                Ok(Ok(_)) => Some(true),
+                Ok(Err(e)) if crate::execution::should_fall_back(e) => Some(false),
+                Ok(Err(_)) | Err(_) => None,
+            };
+            match success {
+                Some(success) => {
+                    if let Some(state) = self.record(&backend, admission, success, &*clock) {
+                        metrics.record_breaker_state(&backend, state);
+                    }
+                }
+                None => self.release(&backend, admission),
+            }
+            match joined {
+                Ok(result) => result,
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                Err(e) => Err(RuntimeError::internal("run task failed", e)),
+            }
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Breaker>> {
+        self.breakers.lock().expect("circuit breaker lock poisoned")
+    }
+}
+
+fn breaker_open(backend: &str, retry_after: Duration) -> AbpError {
+    let retry_after_ms = u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX);
+    AbpError::new(
+        ErrorCode::CircuitBreakerOpen,
+        format!("circuit breaker for backend '{backend}' is open"),
+    )
+    .with_context("backend", backend)
+    .with_context("retry_after_ms", retry_after_ms)
+}
+
+mod duration_millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(v: &Duration, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_u64(v.as_millis() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error> {
+        Ok(Duration::from_millis(u64::deserialize(d)?))
+    }
+}
@@ -4,7 +4,26 @@
 //! Tracks token usage, cost, turn count, and wall-clock duration against
 //! configurable limits and reports when any dimension is exceeded or
 //! approaching its cap.
-
+//!
+//! [`BudgetGuard`](crate::budget::BudgetGuard) applies a work order's token
+//! and cost ceilings while the run is in flight. Backends attach cumulative
+//! [`UsageNormalized`](abp_core::UsageNormalized) snapshots to events under
+//! `ext["abp.usage"]` (see [`USAGE_EXT_KEY`](crate::budget::USAGE_EXT_KEY));
+//! once a snapshot crosses a ceiling the guard cancels the run with
+//! [`BudgetExhausted`](crate::cancel::CancellationReason::BudgetExhausted),
+//! and the runtime stops the backend and returns a
+//! [`Partial`](abp_core::Outcome::Partial) receipt whose
+//! `usage_raw["budget_exceeded"]` (see
+//! [`BUDGET_EXCEEDED_KEY`](crate::budget::BUDGET_EXCEEDED_KEY)) says why.
+//!
+//! [`preflight`](crate::budget::preflight) checks the projected input tokens
+//! before dispatch, so a request that cannot fit its token ceiling or the
+//! model's context window is rejected without calling the backend.
+
+use crate::cancel::{CancellableRun, CancellationReason};
+use abp_core::clock::{SharedClock, system_clock};
+use abp_core::{AgentEvent, UsageNormalized, WorkOrder};
+use abp_error::{AbpError, ErrorCode};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering::Relaxed};
@@ -13,6 +32,15 @@ use std::time::{Duration, Instant};
 // Warning fires at this fraction of any limit.
 const WARNING_THRESHOLD: f64 = 0.8;
 
+/// Event extension key carrying a cumulative [`UsageNormalized`] snapshot.
+pub const USAGE_EXT_KEY: &str = "abp.usage";
+
+/// Work order vendor key holding a token ceiling (input + output tokens).
+pub const MAX_TOKENS_VENDOR_KEY: &str = "abp.max_tokens";
+
+/// Key under `receipt.usage_raw` describing why a run was stopped for budget.
+pub const BUDGET_EXCEEDED_KEY: &str = "budget_exceeded";
+
 /// Per-dimension caps for a single run. `None` means unlimited.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BudgetLimit {
@@ -31,6 +59,73 @@ pub struct BudgetLimit {
     pub max_duration: Option<Duration>,
 }
 
+impl BudgetLimit {
+    /// Token and cost ceilings a work order asks for.
+    ///
+    /// The cost cap is `config.max_budget_usd`; the token cap is the
+    /// [`MAX_TOKENS_VENDOR_KEY`] vendor flag. `max_turns` is left to the
+    /// backend, which counts its own turns. Returns `None` when the work
+    /// order sets neither ceiling.
+    #[must_use]
+    pub fn from_work_order(work_order: &WorkOrder) -> Option<Self> {
+        let max_tokens = work_order
+            .config
+            .vendor
+            .get(MAX_TOKENS_VENDOR_KEY)
+            .and_then(serde_json::Value::as_u64);
+        let max_cost_usd = work_order.config.max_budget_usd;
+        (max_tokens.is_some() || max_cost_usd.is_some()).then(|| Self {
+            max_tokens,
+            max_cost_usd,
+            ..Self::default()
+        })
+    }
+}
+
+/// Reject a work order whose input alone would exceed its limits.
+///
+/// Uses the count a shim recorded with [`abp_tokenize::project`], or counts
+/// the work order itself. Returns the projected input tokens, or `0` when
+/// there is neither a [`MAX_TOKENS_VENDOR_KEY`] ceiling nor a
+/// `context_limit` to check against.
+///
+/// # Errors
+///
+/// [`ErrorCode::QuotaExceeded`] when the input exceeds the token ceiling and
+/// [`ErrorCode::BackendContextLength`] when it exceeds the model's context
+/// window.
+pub fn preflight(work_order: &WorkOrder, context_limit: Option<u64>) -> Result<u64, AbpError> {
+    let max_tokens = BudgetLimit::from_work_order(work_order).and_then(|l| l.max_tokens);
+    if max_tokens.is_none() && context_limit.is_none() {
+        return Ok(0);
+    }
+    let projected = abp_tokenize::projected_input_tokens(work_order)
+        .unwrap_or_else(|| abp_tokenize::count_work_order(work_order));
+    if let Some(limit) = max_tokens.filter(|&limit| projected > limit) {
+        return Err(AbpError::new(
+            ErrorCode::QuotaExceeded,
+            format!("projected input of {projected} tokens exceeds the {limit} token budget"),
+        )
+        .with_context("projected_input_tokens", projected)
+        .with_context("max_tokens", limit));
+    }
+    if let Some(limit) = context_limit.filter(|&limit| projected > limit) {
+        let mut err = AbpError::new(
+            ErrorCode::BackendContextLength,
+            format!(
+                "projected input of {projected} tokens exceeds the {limit} token context window"
+            ),
+        )
+        .with_context("projected_input_tokens", projected)
+        .with_context("context_limit", limit);
+        if let Some(model) = &work_order.config.model {
+            err = err.with_context("model", model);
+        }
+        return Err(err);
+    }
+    Ok(projected)
+}
+
 /// Serde helper: serialize/deserialize `Option<Duration>` as milliseconds.
 mod optional_duration_ms {
     use serde::{Deserialize, Deserializer, Serializer};
@@ -77,6 +172,7 @@ pub struct BudgetTracker {
     cost_micro: AtomicU64,
     turns_used: AtomicU32,
     start: std::sync::Mutex<Option<Instant>>,
+    clock: SharedClock,
 }
 
 impl fmt::Debug for BudgetTracker {
@@ -100,12 +196,20 @@ impl BudgetTracker {
             cost_micro: AtomicU64::new(0),
             turns_used: AtomicU32::new(0),
             start: std::sync::Mutex::new(None),
+            clock: system_clock(),
         }
     }
 
+    /// Measure wall-clock duration with `clock` instead of the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Mark the beginning of execution (wall-clock timer).
     pub fn start_timer(&self) {
-        *self.start.lock().expect("start mutex poisoned") = Some(Instant::now());
+        *self.start.lock().expect("start mutex poisoned") = Some(self.clock.instant());
     }
 
     /// Record `count` tokens consumed.
@@ -220,7 +324,7 @@ impl BudgetTracker {
         self.start
             .lock()
             .expect("start mutex poisoned")
-            .map(|s| s.elapsed())
+            .map(|s| self.clock.instant().saturating_duration_since(s))
     }
 }
 
@@ -311,6 +415,88 @@ pub struct BudgetRemaining {
     pub duration: Option<Duration>,
 }
 
+/// Stops a run once the usage it reports crosses its budget.
+///
+/// Feed every backend event to [`observe`](Self::observe). Events without a
+/// usage snapshot are ignored; each snapshot replaces the previous one, since
+/// snapshots are cumulative for the run.
+#[derive(Debug)]
+pub struct BudgetGuard {
+    tracker: BudgetTracker,
+    run: CancellableRun,
+    usage: UsageNormalized,
+    violation: Option<BudgetViolation>,
+}
+
+impl BudgetGuard {
+    /// Guard `run` against `limit`.
+    #[must_use]
+    pub fn new(limit: BudgetLimit, run: CancellableRun) -> Self {
+        Self {
+            tracker: BudgetTracker::new(limit),
+            run,
+            usage: UsageNormalized::default(),
+            violation: None,
+        }
+    }
+
+    /// Guard `run` with the ceilings from `work_order`, if it sets any.
+    #[must_use]
+    pub fn from_work_order(work_order: &WorkOrder, run: CancellableRun) -> Option<Self> {
+        BudgetLimit::from_work_order(work_order).map(|limit| Self::new(limit, run))
+    }
+
+    /// Account for the usage snapshot on `event`, if any.
+    ///
+    /// Returns the violation the first time a ceiling is crossed, after
+    /// cancelling the run with [`CancellationReason::BudgetExhausted`].
+    pub fn observe(&mut self, event: &AgentEvent) -> Option<&BudgetViolation> {
+        if self.violation.is_some() {
+            return None;
+        }
+        let snapshot: UsageNormalized = event
+            .ext
+            .as_ref()?
+            .get(USAGE_EXT_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())?;
+
+        let tokens =
+            |u: &UsageNormalized| u.input_tokens.unwrap_or(0) + u.output_tokens.unwrap_or(0);
+        self.tracker
+            .record_tokens(tokens(&snapshot).saturating_sub(tokens(&self.usage)));
+        let cost = snapshot.estimated_cost_usd.unwrap_or(0.0)
+            - self.usage.estimated_cost_usd.unwrap_or(0.0);
+        if cost > 0.0 {
+            self.tracker.record_cost(cost);
+        }
+        self.usage = snapshot;
+
+        if let BudgetStatus::Exceeded(violation) = self.tracker.check() {
+            self.run.cancel(CancellationReason::BudgetExhausted);
+            self.violation = Some(violation);
+        }
+        self.violation.as_ref()
+    }
+
+    /// The ceiling that was crossed, if any.
+    #[must_use]
+    pub fn violation(&self) -> Option<&BudgetViolation> {
+        self.violation.as_ref()
+    }
+
+    /// Latest usage snapshot seen.
+    #[must_use]
+    pub fn usage(&self) -> &UsageNormalized {
+        &self.usage
+    }
+
+    /// The run this guard cancels.
+    #[must_use]
+    pub fn run(&self) -> &CancellableRun {
+        &self.run
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -386,4 +572,128 @@ mod tests {
             BudgetStatus::Exceeded(BudgetViolation::DurationExceeded { .. })
         ));
     }
+    #[test]
+    fn duration_follows_injected_clock() {
+        let clock = std::sync::Arc::new(abp_core::clock::ManualClock::default());
+        let t = BudgetTracker::new(BudgetLimit {
+            max_duration: Some(Duration::from_secs(10)),
+            ..Default::default()
+        })
+        .with_clock(clock.clone());
+        t.start_timer();
+        assert_eq!(t.check(), BudgetStatus::WithinLimits);
+
+        clock.advance(Duration::from_secs(9));
+        assert!(matches!(t.check(), BudgetStatus::Warning { .. }));
+
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(
+            t.check(),
+            BudgetStatus::Exceeded(BudgetViolation::DurationExceeded {
+                elapsed: Duration::from_secs(11),
+                limit: Duration::from_secs(10),
+            })
+        );
+    }
+
+    fn usage_event(input: u64, output: u64, cost: Option<f64>) -> AgentEvent {
+        let usage = UsageNormalized {
+            input_tokens: Some(input),
+            output_tokens: Some(output),
+            estimated_cost_usd: cost,
+            ..UsageNormalized::default()
+        };
+        AgentEvent {
+            ts: chrono::Utc::now(),
+            kind: abp_core::AgentEventKind::Progress {
+                percent: None,
+                message: "usage".into(),
+            },
+            ext: Some(
+                [(USAGE_EXT_KEY.to_string(), serde_json::json!(usage))]
+                    .into_iter()
+                    .collect(),
+            ),
+        }
+    }
+
+    #[test]
+    fn limit_from_work_order() {
+        let wo = abp_core::WorkOrderBuilder::new("t").build();
+        assert!(BudgetLimit::from_work_order(&wo).is_none());
+
+        let mut wo = abp_core::WorkOrderBuilder::new("t")
+            .max_budget_usd(0.5)
+            .build();
+        wo.config
+            .vendor
+            .insert(MAX_TOKENS_VENDOR_KEY.into(), serde_json::json!(1000));
+        let limit = BudgetLimit::from_work_order(&wo).unwrap();
+        assert_eq!(limit.max_tokens, Some(1000));
+        assert_eq!(limit.max_cost_usd, Some(0.5));
+        assert_eq!(limit.max_turns, None);
+    }
+
+    #[test]
+    fn guard_uses_cumulative_snapshots() {
+        let run = CancellableRun::new(crate::cancel::CancellationToken::new());
+        let mut guard = BudgetGuard::new(
+            BudgetLimit {
+                max_tokens: Some(100),
+                ..Default::default()
+            },
+            run.clone(),
+        );
+        assert!(guard.observe(&usage_event(40, 10, None)).is_none());
+        // Cumulative: 60 + 30 = 90, not 50 + 90.
+        assert!(guard.observe(&usage_event(60, 30, None)).is_none());
+        assert!(!run.is_cancelled());
+
+        let v = guard.observe(&usage_event(70, 40, None)).cloned();
+        assert_eq!(
+            v,
+            Some(BudgetViolation::TokensExceeded {
+                used: 110,
+                limit: 100
+            })
+        );
+        assert!(run.is_cancelled());
+        assert_eq!(run.reason(), Some(CancellationReason::BudgetExhausted));
+        assert_eq!(guard.usage().output_tokens, Some(40));
+        // Reported once only.
+        assert!(guard.observe(&usage_event(80, 40, None)).is_none());
+        assert!(guard.violation().is_some());
+    }
+
+    #[test]
+    fn guard_trips_on_cost() {
+        let run = CancellableRun::new(crate::cancel::CancellationToken::new());
+        let mut guard = BudgetGuard::new(
+            BudgetLimit {
+                max_cost_usd: Some(0.10),
+                ..Default::default()
+            },
+            run,
+        );
+        assert!(guard.observe(&usage_event(1, 1, Some(0.05))).is_none());
+        assert!(matches!(
+            guard.observe(&usage_event(2, 2, Some(0.12))),
+            Some(BudgetViolation::CostExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn guard_ignores_events_without_usage() {
+        let run = CancellableRun::new(crate::cancel::CancellationToken::new());
+        let mut guard = BudgetGuard::new(
+            BudgetLimit {
+                max_tokens: Some(0),
+                ..Default::default()
+            },
+            run,
+        );
+        let mut ev = usage_event(5, 5, None);
+        ev.ext = None;
+        assert!(guard.observe(&ev).is_none());
+    }
 }
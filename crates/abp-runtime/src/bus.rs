@@ -3,8 +3,16 @@
 //!
 //! Unlike the `EventMultiplexer` from the multiplex module, the event bus tracks
 //! publishing statistics and supports filtered subscriptions.
+//!
+//! The runtime also announces backend registry changes on its bus as
+//! [`Progress`](abp_core::AgentEventKind::Progress) events carrying a
+//! [`RegistryChange`](crate::bus::RegistryChange) under `ext["abp.registry"]`,
+//! so projection and health tracking can follow backends that come and go.
 
-use abp_core::AgentEvent;
+use abp_core::{AgentEvent, AgentEventKind};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::broadcast;
@@ -12,6 +20,63 @@ use tokio::sync::broadcast;
 /// Default channel capacity for the event bus.
 const DEFAULT_CAPACITY: usize = 256;
 
+/// `ext` key carrying a [`RegistryChange`] on a bus event.
+pub const REGISTRY_EXT_KEY: &str = "abp.registry";
+
+/// A backend joined or left the runtime's registry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum RegistryChange {
+    /// A backend was registered, or replaced an earlier one of the same name.
+    Registered {
+        /// Registered name.
+        backend: String,
+    },
+    /// A backend was removed; runs already started on it still finish.
+    Deregistered {
+        /// Removed name.
+        backend: String,
+    },
+}
+
+impl RegistryChange {
+    /// Name of the backend that changed.
+    #[must_use]
+    pub fn backend(&self) -> &str {
+        match self {
+            Self::Registered { backend } | Self::Deregistered { backend } => backend,
+        }
+    }
+
+    /// The bus event announcing this change.
+    #[must_use]
+    pub fn to_event(&self, ts: DateTime<Utc>) -> AgentEvent {
+        let message = match self {
+            Self::Registered { backend } => format!("backend '{backend}' registered"),
+            Self::Deregistered { backend } => format!("backend '{backend}' deregistered"),
+        };
+        let mut ext = BTreeMap::new();
+        if let Ok(value) = serde_json::to_value(self) {
+            ext.insert(REGISTRY_EXT_KEY.to_string(), value);
+        }
+        AgentEvent {
+            ts,
+            kind: AgentEventKind::Progress {
+                percent: None,
+                message,
+            },
+            ext: Some(ext),
+        }
+    }
+
+    /// The change an event announces, if it is a registry event.
+    #[must_use]
+    pub fn from_event(event: &AgentEvent) -> Option<Self> {
+        let value = event.ext.as_ref()?.get(REGISTRY_EXT_KEY)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
 /// Shared statistics counters for an [`EventBus`].
 #[derive(Debug, Default)]
 struct StatsInner {
@@ -52,6 +117,15 @@ impl EventBus {
         }
     }
 
+    /// Subscribe to [`RegistryChange`] events only.
+    #[must_use]
+    pub fn subscribe_registry(&self) -> FilteredSubscription {
+        FilteredSubscription::new(
+            self.subscribe(),
+            Box::new(|ev| RegistryChange::from_event(ev).is_some()),
+        )
+    }
+
     /// Publish an event to all current subscribers.
     ///
     /// If no subscribers are listening the event is silently dropped and
@@ -0,0 +1,386 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Reuse of prior results for unchanged inputs.
+//!
+//! With [`Runtime::with_run_cache`](crate::Runtime::with_run_cache) every run
+//! is keyed by its backend, a fingerprint of the work order, and a Merkle
+//! hash of the workspace files it would see (see
+//! [`RunCache::key`](crate::cache::RunCache::key)). When a completed receipt
+//! is already stored under that key, the runtime skips the backend and plays
+//! the stored receipt back instead: its trace, usage, artifacts, and
+//! verification are returned under the new run id, and
+//! `usage_raw["cache"]` holds a [`CacheRecord`](crate::cache::CacheRecord)
+//! naming the run that produced them. On a miss the backend runs as usual
+//! and a completed receipt is stored for next time.
+//!
+//! A work order controls the cache through
+//! `config.vendor["abp"]["cache"]` (or the flat `"abp.cache"` key), see
+//! [`CacheMode`](crate::cache::CacheMode). Operators bust entries with
+//! [`RunCache::invalidate`](crate::cache::RunCache::invalidate),
+//! [`RunCache::clear`](crate::cache::RunCache::clear), a
+//! [`salt`](crate::cache::RunCache::salt) that changes every key, or a
+//! [`max_age`](crate::cache::RunCache::max_age).
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use abp_core::{Outcome, Receipt, WorkOrder, WorkspaceSpec};
+use abp_glob::IncludeExcludeGlobs;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+/// Key under `receipt.usage_raw` holding the [`CacheRecord`].
+pub const CACHE_KEY: &str = "cache";
+
+/// How a work order uses the run cache.
+///
+/// # Examples
+///
+/// ```
+/// use abp_runtime::cache::CacheMode;
+///
+/// assert_eq!(CacheMode::default(), CacheMode::Use);
+/// assert_eq!(CacheMode::parse("Refresh"), Some(CacheMode::Refresh));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheMode {
+    /// Return a stored result if there is one, otherwise run and store.
+    #[default]
+    Use,
+    /// Always run, and replace the stored result.
+    Refresh,
+    /// Neither read nor write the cache.
+    Bypass,
+}
+
+impl CacheMode {
+    /// Parse a mode name (case-insensitive).
+    #[must_use]
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "use" => Some(Self::Use),
+            "refresh" => Some(Self::Refresh),
+            "bypass" => Some(Self::Bypass),
+            _ => None,
+        }
+    }
+
+    /// Read the mode from a work order's vendor config.
+    ///
+    /// Checks `config.vendor["abp"]["cache"]` first, then the flat
+    /// `config.vendor["abp.cache"]` key. Missing or unrecognised values fall
+    /// back to the default ([`CacheMode::Use`]).
+    #[must_use]
+    pub fn from_work_order(work_order: &WorkOrder) -> Self {
+        let vendor = &work_order.config.vendor;
+        vendor
+            .get("abp")
+            .and_then(|abp| abp.get(CACHE_KEY))
+            .or_else(|| vendor.get("abp.cache"))
+            .and_then(Value::as_str)
+            .and_then(Self::parse)
+            .unwrap_or_default()
+    }
+}
+
+/// What the cache did for a run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheStatus {
+    /// A stored result was returned; the backend did not run.
+    Hit,
+    /// Nothing usable was stored; the backend ran.
+    Miss,
+    /// The work order asked for a fresh run; the backend ran.
+    Refreshed,
+}
+
+/// How a run used the cache, recorded under `usage_raw["cache"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheRecord {
+    /// What the cache did.
+    pub status: CacheStatus,
+    /// The input key the run was looked up under.
+    pub key: String,
+    /// Run id of the stored receipt, on a hit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_run_id: Option<Uuid>,
+    /// Hash of the stored receipt, on a hit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_receipt_sha256: Option<String>,
+}
+
+impl CacheRecord {
+    /// Read the record back from a receipt; `None` if the run did not use
+    /// the cache.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        receipt
+            .usage_raw
+            .get(CACHE_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// Store the record under `usage_raw["cache"]`.
+    pub fn attach(&self, receipt: &mut Receipt) {
+        if !receipt.usage_raw.is_object() {
+            receipt.usage_raw = serde_json::json!({ "original": receipt.usage_raw });
+        }
+        if let (Some(obj), Ok(val)) = (
+            receipt.usage_raw.as_object_mut(),
+            serde_json::to_value(self),
+        ) {
+            obj.insert(CACHE_KEY.to_string(), val);
+        }
+    }
+}
+
+/// Completed receipts stored on disk by input key.
+///
+/// Each entry is a JSON file named after its key. Only receipts with
+/// [`Outcome::Complete`] and a valid hash are stored or returned.
+#[derive(Debug, Clone)]
+pub struct RunCache {
+    dir: PathBuf,
+    salt: String,
+    max_age: Option<Duration>,
+}
+
+impl RunCache {
+    /// Cache receipts in `dir`, created on first store.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            salt: String::new(),
+            max_age: None,
+        }
+    }
+
+    /// Mix `salt` into every key, so changing it busts all existing entries
+    /// (builder pattern).
+    #[must_use]
+    pub fn salt(mut self, salt: impl Into<String>) -> Self {
+        self.salt = salt.into();
+        self
+    }
+
+    /// Ignore entries whose run finished more than `max_age` ago (builder
+    /// pattern).
+    #[must_use]
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Directory holding the entries.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The input key of running `work_order` on `backend`.
+    ///
+    /// Hashes the salt, the backend name, the work order's canonical JSON
+    /// without its `id` and cache mode, and the Merkle root over the content
+    /// of every workspace file its include/exclude globs select (`.git`
+    /// excluded).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the workspace cannot be read or a glob is invalid.
+    pub fn key(&self, backend: &str, work_order: &WorkOrder) -> Result<String> {
+        let mut order = serde_json::to_value(work_order).context("serialize work order")?;
+        if let Some(obj) = order.as_object_mut() {
+            obj.remove("id");
+        }
+        if let Some(vendor) = order
+            .pointer_mut("/config/vendor")
+            .and_then(Value::as_object_mut)
+        {
+            vendor.remove("abp.cache");
+            if let Some(abp) = vendor.get_mut("abp").and_then(Value::as_object_mut) {
+                abp.remove(CACHE_KEY);
+                if abp.is_empty() {
+                    vendor.remove("abp");
+                }
+            }
+        }
+        let fingerprint = abp_core::canonical_json(&order).context("canonicalize work order")?;
+        let workspace = workspace_root(&work_order.workspace)?;
+        let input = format!(
+            "{}\n{backend}\n{}\n{workspace}",
+            self.salt,
+            abp_core::sha256_hex(fingerprint.as_bytes())
+        );
+        Ok(abp_core::sha256_hex(input.as_bytes()))
+    }
+
+    /// The receipt stored under `key`, if it is still valid at `now`.
+    #[must_use]
+    pub fn lookup(&self, key: &str, now: DateTime<Utc>) -> Option<Receipt> {
+        let bytes = std::fs::read(self.entry(key)).ok()?;
+        let receipt: Receipt = serde_json::from_slice(&bytes).ok()?;
+        if receipt.outcome != Outcome::Complete || !abp_receipt::verify_hash(&receipt) {
+            return None;
+        }
+        if let Some(max_age) = self.max_age
+            && now
+                .signed_duration_since(receipt.meta.finished_at)
+                .to_std()
+                .is_ok_and(|age| age > max_age)
+        {
+            return None;
+        }
+        Some(receipt)
+    }
+
+    /// Store `receipt` under `key`. Receipts that did not complete, or
+    /// whose hash does not verify, are skipped.
+    ///
+    /// Returns whether the receipt was stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be written.
+    pub fn store(&self, key: &str, receipt: &Receipt) -> Result<bool> {
+        if receipt.outcome != Outcome::Complete || !abp_receipt::verify_hash(receipt) {
+            return Ok(false);
+        }
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("create cache dir {}", self.dir.display()))?;
+        let json = serde_json::to_vec(receipt).context("serialize receipt")?;
+        let path = self.entry(key);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).with_context(|| format!("write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path).with_context(|| format!("write {}", path.display()))?;
+        Ok(true)
+    }
+
+    /// Remove the entry under `key`. Returns whether there was one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry exists but cannot be removed.
+    pub fn invalidate(&self, key: &str) -> Result<bool> {
+        match std::fs::remove_file(self.entry(key)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e).with_context(|| format!("remove cache entry {key}")),
+        }
+    }
+
+    /// Remove every entry. Returns how many were removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read or an entry cannot
+    /// be removed.
+    pub fn clear(&self) -> Result<usize> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e).with_context(|| format!("read {}", self.dir.display())),
+        };
+        let mut removed = 0;
+        for entry in entries {
+            let path = entry.context("read cache dir")?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("remove {}", path.display()))?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn entry(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+/// Merkle root over the files a run of `spec` would see.
+fn workspace_root(spec: &WorkspaceSpec) -> Result<String> {
+    let root = Path::new(&spec.root);
+    let globs = IncludeExcludeGlobs::new(&spec.include, &spec.exclude)?;
+    let mut files = BTreeMap::new();
+    let walker = WalkDir::new(root)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git");
+    for entry in walker {
+        let entry = entry.context("walk workspace")?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry
+            .path()
+            .strip_prefix(root)
+            .expect("walked paths are under the root");
+        if !globs.decide_path(rel).is_allowed() {
+            continue;
+        }
+        let bytes =
+            std::fs::read(entry.path()).with_context(|| format!("read {}", rel.display()))?;
+        files.insert(
+            rel.to_string_lossy().replace('\\', "/"),
+            abp_core::sha256_hex(&bytes),
+        );
+    }
+    Ok(abp_core::merkle::merkle_root(&files))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::WorkOrderBuilder;
+
+    fn order(root: &Path, task: &str) -> WorkOrder {
+        WorkOrderBuilder::new(task)
+            .root(root.to_string_lossy())
+            .build()
+    }
+
+    #[test]
+    fn key_ignores_ids_and_cache_mode_but_not_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "one").unwrap();
+        let cache = RunCache::new(dir.path().join(".cache"));
+
+        let base = cache.key("mock", &order(dir.path(), "t")).unwrap();
+        let mut refreshed = order(dir.path(), "t");
+        refreshed
+            .config
+            .vendor
+            .insert("abp".into(), serde_json::json!({"cache": "refresh"}));
+        assert_eq!(cache.key("mock", &refreshed).unwrap(), base);
+
+        assert_ne!(cache.key("other", &order(dir.path(), "t")).unwrap(), base);
+        assert_ne!(cache.key("mock", &order(dir.path(), "u")).unwrap(), base);
+        assert_ne!(
+            cache
+                .clone()
+                .salt("v2")
+                .key("mock", &order(dir.path(), "t"))
+                .unwrap(),
+            base
+        );
+
+        std::fs::write(dir.path().join("a.txt"), "two").unwrap();
+        assert_ne!(cache.key("mock", &order(dir.path(), "t")).unwrap(), base);
+    }
+
+    #[test]
+    fn mode_is_read_from_vendor_config() {
+        let mut wo = WorkOrderBuilder::new("t").build();
+        assert_eq!(CacheMode::from_work_order(&wo), CacheMode::Use);
+        wo.config
+            .vendor
+            .insert("abp.cache".into(), serde_json::json!("BYPASS"));
+        assert_eq!(CacheMode::from_work_order(&wo), CacheMode::Bypass);
+    }
+}
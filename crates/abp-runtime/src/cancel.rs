@@ -48,15 +48,16 @@ impl CancellationToken {
     ///
     /// If the token is already cancelled the future resolves immediately.
     pub async fn cancelled(&self) {
-        // Fast-path: already cancelled.
-        if self.is_cancelled() {
-            return;
-        }
         loop {
-            self.inner.notify.notified().await;
+            // Register for the wake-up before checking the flag, so a
+            // `cancel` in between is not missed.
+            let notified = self.inner.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
             if self.is_cancelled() {
                 return;
             }
+            notified.await;
         }
     }
 }
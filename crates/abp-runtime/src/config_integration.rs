@@ -8,8 +8,11 @@
 //! configuration types that can be populated from a parsed `BackplaneConfig`
 //! or constructed programmatically.
 
+use crate::rate_limit::BackendRateLimit;
+use crate::retry::BackendRetryConfig;
 use abp_core::PolicyProfile;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -134,6 +137,18 @@ pub struct RuntimeConfig {
     pub run_timeout: Option<Duration>,
     /// Maximum number of concurrent runs (0 = unlimited).
     pub max_concurrent_runs: usize,
+    /// Retry and per-attempt timeout settings, keyed by backend name.
+    /// Backends without an entry are tried once with no timeout.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub backend_retry: BTreeMap<String, BackendRetryConfig>,
+    /// Requests and tokens per minute allowed, keyed by backend name.
+    /// Backends without an entry are not rate limited.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub backend_rate_limits: BTreeMap<String, BackendRateLimit>,
+    /// Experimental feature flags and their defaults; see
+    /// [`FeatureFlags`](crate::flags::FeatureFlags).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub features: BTreeMap<String, bool>,
 }
 
 impl RuntimeConfig {
@@ -188,6 +203,18 @@ impl RuntimeConfig {
     pub fn is_concurrent_limited(&self) -> bool {
         self.max_concurrent_runs > 0
     }
+
+    /// Retry settings for the named backend, if any.
+    #[must_use]
+    pub fn retry_for(&self, backend: &str) -> Option<&BackendRetryConfig> {
+        self.backend_retry.get(backend)
+    }
+
+    /// Rate limit for the named backend, if any.
+    #[must_use]
+    pub fn rate_limit_for(&self, backend: &str) -> Option<&BackendRateLimit> {
+        self.backend_rate_limits.get(backend)
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -240,6 +267,31 @@ impl RuntimeConfigBuilder {
         self
     }
 
+    /// Set retry and timeout settings for one backend.
+    #[must_use]
+    pub fn backend_retry(mut self, backend: impl Into<String>, retry: BackendRetryConfig) -> Self {
+        self.0.backend_retry.insert(backend.into(), retry);
+        self
+    }
+
+    /// Set the requests and tokens per minute allowed on one backend.
+    #[must_use]
+    pub fn backend_rate_limit(
+        mut self,
+        backend: impl Into<String>,
+        limit: BackendRateLimit,
+    ) -> Self {
+        self.0.backend_rate_limits.insert(backend.into(), limit);
+        self
+    }
+
+    /// Declare a feature flag with its default state.
+    #[must_use]
+    pub fn feature(mut self, name: impl Into<String>, enabled: bool) -> Self {
+        self.0.features.insert(name.into(), enabled);
+        self
+    }
+
     /// Consume the builder and produce a [`RuntimeConfig`].
     #[must_use]
     pub fn build(self) -> RuntimeConfig {
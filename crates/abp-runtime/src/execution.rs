@@ -76,6 +76,77 @@ pub enum PipelineEvent {
     },
 }
 
+/// Key under `receipt.usage_raw` listing the backends
+/// [`Runtime::run_with_fallback`](crate::Runtime::run_with_fallback) tried.
+pub const FALLBACK_ATTEMPTS_KEY: &str = "fallback_attempts";
+
+/// One backend attempt made by
+/// [`Runtime::run_with_fallback`](crate::Runtime::run_with_fallback).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FallbackAttempt {
+    /// Backend that was tried.
+    pub backend: String,
+    /// Error code of the failure, or `None` for the attempt that produced
+    /// the receipt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<abp_error::ErrorCode>,
+    /// Error message of the failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl FallbackAttempt {
+    /// An attempt on `backend` that failed with `err`.
+    #[must_use]
+    pub fn failed(backend: impl Into<String>, err: &RuntimeError) -> Self {
+        let error = match err {
+            RuntimeError::BackendFailed(e) => format!("{e:#}"),
+            other => other.to_string(),
+        };
+        Self {
+            backend: backend.into(),
+            error_code: Some(failure_code(err)),
+            error: Some(error),
+        }
+    }
+
+    /// The attempt on `backend` that produced the receipt.
+    #[must_use]
+    pub fn succeeded(backend: impl Into<String>) -> Self {
+        Self {
+            backend: backend.into(),
+            error_code: None,
+            error: None,
+        }
+    }
+}
+
+/// Whether `err` means the backend itself failed or timed out, so the work
+/// order may be retried elsewhere.
+///
+/// A backend failure that carries a classified [`AbpError`](abp_error::AbpError)
+/// falls back only for [`BackendTimeout`](abp_error::ErrorCode::BackendTimeout)
+/// or [`BackendCrashed`](abp_error::ErrorCode::BackendCrashed). Errors about
+/// the work order (policy, capabilities, quotas) are not retried elsewhere.
+#[must_use]
+pub fn should_fall_back(err: &RuntimeError) -> bool {
+    matches!(
+        failure_code(err),
+        abp_error::ErrorCode::BackendCrashed | abp_error::ErrorCode::BackendTimeout
+    )
+}
+
+/// Error code of `err`, looking through a backend failure for the
+/// classified error the backend returned.
+pub(crate) fn failure_code(err: &RuntimeError) -> abp_error::ErrorCode {
+    match err {
+        RuntimeError::BackendFailed(e) => e
+            .downcast_ref::<abp_error::AbpError>()
+            .map_or(abp_error::ErrorCode::BackendCrashed, |e| e.code),
+        other => other.error_code(),
+    }
+}
+
 /// Result of an [`ExecutionPipeline::execute`] call.
 pub type PipelineResult = Result<PipelineOutput, RuntimeError>;
 
@@ -220,7 +291,10 @@ impl ExecutionPipeline {
                                 "retrying after transient error"
                             );
                             pipeline_events.push(event);
-                            tokio::time::sleep(delay).await;
+                            let wait = runtime.clock().sleep_for(delay);
+                            if !wait.is_zero() {
+                                tokio::time::sleep(wait).await;
+                            }
                             last_error = Some(err);
                         } else {
                             // Not retryable or retries exhausted — move to next backend.
@@ -256,7 +330,7 @@ impl ExecutionPipeline {
         handle
             .receipt
             .await
-            .map_err(|e| RuntimeError::BackendFailed(anyhow::Error::new(e)))?
+            .map_err(|e| RuntimeError::internal("run task failed", e))?
     }
 }
 
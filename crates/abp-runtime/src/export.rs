@@ -0,0 +1,524 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Export of run events to message brokers such as Kafka or NATS.
+//!
+//! An [`EventExporter`](crate::export::EventExporter) attached with
+//! [`Runtime::with_event_export`](crate::Runtime::with_event_export) publishes
+//! every run as a sequence of [`ExportMessage`](crate::export::ExportMessage)s:
+//! a `run_started` notification, one `event` per [`AgentEvent`] the caller
+//! receives, and a `run_finished` notification carrying the outcome or error.
+//! Lifecycle notifications go to
+//! [`ExportConfig::lifecycle_topic`](crate::export::ExportConfig::lifecycle_topic),
+//! events to
+//! [`ExportConfig::events_topic`](crate::export::ExportConfig::events_topic);
+//! both names are valid Kafka topics and NATS subjects.
+//!
+//! The broker client is an [`EventProducer`](crate::export::EventProducer);
+//! implement it over `rdkafka`, `async-nats`, or any other client. Each
+//! message is keyed by its run id, so a partitioned topic keeps a run's
+//! messages in order.
+//!
+//! Delivery is at least once. Messages wait in a bounded in-memory buffer and a
+//! background task sends them in order, retrying a failed send with exponential
+//! backoff until it succeeds; a run never waits on the broker. When the buffer
+//! is full the oldest message is dropped and counted in
+//! [`ExportStats::dropped`](crate::export::ExportStats::dropped). Consumers
+//! deduplicate redeliveries on
+//! [`ExportMessage::id`](crate::export::ExportMessage::id). Call
+//! [`EventExporter::flush`](crate::export::EventExporter::flush) before
+//! shutdown to deliver what is still buffered.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::time::Duration;
+
+use abp_core::clock::SharedClock;
+use abp_core::{AgentEvent, Outcome, Receipt};
+use abp_error::ErrorCode;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Notify, mpsc};
+use tokio::task::JoinHandle;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::RuntimeError;
+
+/// Default topic for run lifecycle notifications.
+pub const DEFAULT_LIFECYCLE_TOPIC: &str = "abp.runs";
+
+/// Default topic for agent events.
+pub const DEFAULT_EVENTS_TOPIC: &str = "abp.events";
+
+/// A client that publishes to a message broker.
+#[async_trait]
+pub trait EventProducer: Send + Sync {
+    /// Publish `payload` (a JSON [`ExportMessage`]) to `topic`.
+    ///
+    /// `key` is the run id. Return only once the broker has accepted the
+    /// message; an error makes the exporter retry it.
+    async fn send(&self, topic: &str, key: &str, payload: &[u8]) -> anyhow::Result<()>;
+}
+
+/// Where and how an [`EventExporter`] publishes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportConfig {
+    /// Topic for `run_started` and `run_finished` notifications.
+    pub lifecycle_topic: String,
+    /// Topic for agent events.
+    pub events_topic: String,
+    /// Most messages held while the broker is slow or down.
+    pub buffer: usize,
+    /// Delay before the first retry of a failed send.
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between retries.
+    pub max_backoff: Duration,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            lifecycle_topic: DEFAULT_LIFECYCLE_TOPIC.into(),
+            events_topic: DEFAULT_EVENTS_TOPIC.into(),
+            buffer: 10_000,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ExportConfig {
+    /// Publish lifecycle notifications to `topic` (builder pattern).
+    #[must_use]
+    pub fn lifecycle_topic(mut self, topic: impl Into<String>) -> Self {
+        self.lifecycle_topic = topic.into();
+        self
+    }
+
+    /// Publish agent events to `topic` (builder pattern).
+    #[must_use]
+    pub fn events_topic(mut self, topic: impl Into<String>) -> Self {
+        self.events_topic = topic.into();
+        self
+    }
+
+    /// Hold at most `n` undelivered messages (builder pattern; at least 1).
+    #[must_use]
+    pub fn buffer(mut self, n: usize) -> Self {
+        self.buffer = n.max(1);
+        self
+    }
+
+    /// Retry failed sends after `initial`, doubling up to `max` (builder
+    /// pattern).
+    #[must_use]
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+}
+
+/// One exported message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportMessage {
+    /// Unique message id; the same on every redelivery.
+    pub id: Uuid,
+    /// Run the message belongs to.
+    pub run_id: Uuid,
+    /// Position within the run, starting at 0 for `run_started`.
+    pub seq: u64,
+    /// When the message was produced.
+    pub ts: DateTime<Utc>,
+    /// What happened.
+    #[serde(flatten)]
+    pub body: ExportBody,
+}
+
+/// What an [`ExportMessage`] reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExportBody {
+    /// The run was accepted and is about to start.
+    RunStarted {
+        /// Id of the work order being run.
+        work_order_id: Uuid,
+        /// Backend running it.
+        backend: String,
+    },
+    /// The backend emitted an event.
+    Event {
+        /// The event, as the caller received it.
+        event: AgentEvent,
+    },
+    /// The run ended.
+    RunFinished {
+        /// Id of the work order that was run.
+        work_order_id: Uuid,
+        /// Backend that ran it.
+        backend: String,
+        /// Receipt outcome, when the run produced a receipt.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        outcome: Option<Outcome>,
+        /// Receipt hash, when the run produced a hashed receipt.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        receipt_sha256: Option<String>,
+        /// Why the run produced no receipt.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+        /// Error code for `error`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error_code: Option<ErrorCode>,
+    },
+}
+
+impl ExportBody {
+    /// Whether this is a `run_started` or `run_finished` notification.
+    #[must_use]
+    pub fn is_lifecycle(&self) -> bool {
+        !matches!(self, Self::Event { .. })
+    }
+}
+
+/// Delivery counters of an [`EventExporter`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportStats {
+    /// Messages the broker accepted.
+    pub published: u64,
+    /// Failed sends that were retried.
+    pub retried: u64,
+    /// Messages dropped because the buffer was full.
+    pub dropped: u64,
+    /// Messages waiting to be sent.
+    pub pending: u64,
+}
+
+struct Inner {
+    producer: Arc<dyn EventProducer>,
+    config: ExportConfig,
+    buffer: Mutex<VecDeque<ExportMessage>>,
+    /// Wakes the delivery task when a message is buffered.
+    wake: Arc<Notify>,
+    /// Wakes [`EventExporter::flush`] callers when the buffer empties.
+    idle: Notify,
+    worker: OnceLock<()>,
+    published: AtomicU64,
+    retried: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // Let the delivery task notice the exporter is gone.
+        self.wake.notify_one();
+    }
+}
+
+impl Inner {
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<ExportMessage>> {
+        self.buffer.lock().expect("export buffer lock poisoned")
+    }
+}
+
+/// Buffers run messages and delivers them through an [`EventProducer`].
+///
+/// Cloning shares the buffer and delivery task.
+#[derive(Clone)]
+pub struct EventExporter {
+    inner: Arc<Inner>,
+}
+
+impl std::fmt::Debug for EventExporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventExporter")
+            .field("config", &self.inner.config)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl EventExporter {
+    /// Export through `producer` as `config` describes.
+    #[must_use]
+    pub fn new(producer: impl EventProducer + 'static, config: ExportConfig) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                producer: Arc::new(producer),
+                config,
+                buffer: Mutex::new(VecDeque::new()),
+                wake: Arc::new(Notify::new()),
+                idle: Notify::new(),
+                worker: OnceLock::new(),
+                published: AtomicU64::new(0),
+                retried: AtomicU64::new(0),
+                dropped: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// The exporter's settings.
+    #[must_use]
+    pub fn config(&self) -> &ExportConfig {
+        &self.inner.config
+    }
+
+    /// Queue `message` for delivery.
+    ///
+    /// Never waits. The first call starts the delivery task, so it must be
+    /// made inside a Tokio runtime.
+    pub fn publish(&self, message: ExportMessage) {
+        self.inner.worker.get_or_init(|| {
+            tokio::spawn(deliver(
+                Arc::downgrade(&self.inner),
+                Arc::clone(&self.inner.wake),
+            ));
+        });
+        {
+            let mut buffer = self.inner.lock();
+            buffer.push_back(message);
+            while buffer.len() > self.inner.config.buffer {
+                buffer.pop_front();
+                self.inner.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.inner.wake.notify_one();
+    }
+
+    /// Delivery counters so far.
+    #[must_use]
+    pub fn stats(&self) -> ExportStats {
+        ExportStats {
+            published: self.inner.published.load(Ordering::Relaxed),
+            retried: self.inner.retried.load(Ordering::Relaxed),
+            dropped: self.inner.dropped.load(Ordering::Relaxed),
+            pending: self.inner.lock().len() as u64,
+        }
+    }
+
+    /// Wait until every buffered message has been delivered.
+    ///
+    /// Does not return while the broker keeps refusing messages; bound it
+    /// with a timeout at shutdown.
+    pub async fn flush(&self) {
+        loop {
+            let idle = self.inner.idle.notified();
+            tokio::pin!(idle);
+            idle.as_mut().enable();
+            if self.inner.lock().is_empty() {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    /// Mirror a run to the exporter.
+    ///
+    /// Publishes `run_started` now, then forwards `events` to the returned
+    /// receiver while exporting each one, and publishes `run_finished` once
+    /// `receipt` resolves and every event has been exported. Events keep
+    /// being exported after the caller drops its receiver.
+    pub(crate) fn tap(
+        &self,
+        run_id: Uuid,
+        work_order_id: Uuid,
+        backend: &str,
+        clock: SharedClock,
+        mut events: mpsc::Receiver<AgentEvent>,
+        receipt: JoinHandle<Result<Receipt, RuntimeError>>,
+    ) -> (
+        mpsc::Receiver<AgentEvent>,
+        JoinHandle<Result<Receipt, RuntimeError>>,
+    ) {
+        let message = |seq: u64, body: ExportBody| ExportMessage {
+            id: Uuid::new_v4(),
+            run_id,
+            seq,
+            ts: clock.now(),
+            body,
+        };
+        self.publish(message(
+            0,
+            ExportBody::RunStarted {
+                work_order_id,
+                backend: backend.to_string(),
+            },
+        ));
+
+        let (to_caller, from_tap) = mpsc::channel(events.max_capacity());
+        let exporter = self.clone();
+        let forward_clock = Arc::clone(&clock);
+        let forward = tokio::spawn(async move {
+            let mut seq = 0;
+            while let Some(event) = events.recv().await {
+                seq += 1;
+                exporter.publish(ExportMessage {
+                    id: Uuid::new_v4(),
+                    run_id,
+                    seq,
+                    ts: forward_clock.now(),
+                    body: ExportBody::Event {
+                        event: event.clone(),
+                    },
+                });
+                // A caller that stopped listening does not stop the export.
+                let _ = to_caller.send(event).await;
+            }
+            seq
+        });
+
+        let exporter = self.clone();
+        let backend = backend.to_string();
+        let receipt = tokio::spawn(async move {
+            let result = match receipt.await {
+                Ok(result) => result,
+                Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+                Err(e) => Err(RuntimeError::internal("run task failed", e)),
+            };
+            let seq = forward.await.unwrap_or_default() + 1;
+            let (outcome, receipt_sha256, error, error_code) = match &result {
+                Ok(receipt) => (
+                    Some(receipt.outcome.clone()),
+                    receipt.receipt_sha256.clone(),
+                    None,
+                    None,
+                ),
+                Err(e) => (None, None, Some(e.to_string()), Some(e.error_code())),
+            };
+            exporter.publish(ExportMessage {
+                id: Uuid::new_v4(),
+                run_id,
+                seq,
+                ts: clock.now(),
+                body: ExportBody::RunFinished {
+                    work_order_id,
+                    backend,
+                    outcome,
+                    receipt_sha256,
+                    error,
+                    error_code,
+                },
+            });
+            result
+        });
+        (from_tap, receipt)
+    }
+}
+
+/// Send buffered messages in order until the exporter is dropped.
+async fn deliver(exporter: Weak<Inner>, wake: Arc<Notify>) {
+    let mut backoff = None;
+    loop {
+        let woken = wake.notified();
+        tokio::pin!(woken);
+        woken.as_mut().enable();
+        let Some(inner) = exporter.upgrade() else {
+            return;
+        };
+        let Some(message) = inner.lock().front().cloned() else {
+            inner.idle.notify_waiters();
+            drop(inner);
+            woken.await;
+            continue;
+        };
+
+        let topic = if message.body.is_lifecycle() {
+            &inner.config.lifecycle_topic
+        } else {
+            &inner.config.events_topic
+        };
+        let sent = match serde_json::to_vec(&message) {
+            Ok(payload) => {
+                inner
+                    .producer
+                    .send(topic, &message.run_id.to_string(), &payload)
+                    .await
+            }
+            Err(e) => {
+                // Unserializable messages can never be delivered.
+                warn!(target: "abp.runtime", error=%e, "dropping unserializable export message");
+                inner.dropped.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+        };
+        match sent {
+            Ok(()) => {
+                backoff = None;
+                let mut buffer = inner.lock();
+                // The message may already have been pushed out by overflow.
+                if buffer.front().is_some_and(|m| m.id == message.id) {
+                    buffer.pop_front();
+                    inner.published.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            Err(e) => {
+                inner.retried.fetch_add(1, Ordering::Relaxed);
+                let delay = backoff.map_or(inner.config.initial_backoff, |d: Duration| {
+                    (d * 2).min(inner.config.max_backoff)
+                });
+                backoff = Some(delay);
+                warn!(
+                    target: "abp.runtime",
+                    topic,
+                    error=%e,
+                    retry_in_ms = delay.as_millis() as u64,
+                    "event export failed"
+                );
+                drop(inner);
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+/// A message held by [`MemoryProducer`]: topic, key, payload.
+type Sent = (String, String, Vec<u8>);
+
+/// Producer that keeps published messages in memory, for tests and local
+/// inspection.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryProducer {
+    sent: Arc<Mutex<Vec<Sent>>>,
+}
+
+impl MemoryProducer {
+    /// An empty producer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Messages published to `topic`, in order.
+    #[must_use]
+    pub fn messages(&self, topic: &str) -> Vec<ExportMessage> {
+        self.sent
+            .lock()
+            .expect("memory producer lock poisoned")
+            .iter()
+            .filter(|(t, _, _)| t == topic)
+            .filter_map(|(_, _, payload)| serde_json::from_slice(payload).ok())
+            .collect()
+    }
+
+    /// Every `(topic, key)` published, in order.
+    #[must_use]
+    pub fn keys(&self) -> Vec<(String, String)> {
+        self.sent
+            .lock()
+            .expect("memory producer lock poisoned")
+            .iter()
+            .map(|(topic, key, _)| (topic.clone(), key.clone()))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EventProducer for MemoryProducer {
+    async fn send(&self, topic: &str, key: &str, payload: &[u8]) -> anyhow::Result<()> {
+        self.sent
+            .lock()
+            .expect("memory producer lock poisoned")
+            .push((topic.to_string(), key.to_string(), payload.to_vec()));
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! In-memory test double for the runtime.
+//!
+//! [`FakeRuntime`](crate::fake::FakeRuntime) implements
+//! [`RuntimeApi`](crate::api::RuntimeApi) by answering each run with the next
+//! scripted receipt or error for the backend. It spawns no tasks and touches
+//! no files, so application code can be tested without workspaces, git, or a
+//! real backend. Every submitted work order is kept for assertions.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+use abp_core::{Outcome, Receipt, ReceiptBuilder, WorkOrder};
+use async_trait::async_trait;
+
+use crate::RuntimeError;
+use crate::api::RuntimeApi;
+
+#[derive(Debug)]
+enum Scripted {
+    Receipt(Box<Receipt>),
+    Error(String),
+}
+
+/// Scripted stand-in for [`Runtime`](crate::Runtime).
+///
+/// Runs on a backend without a scripted answer left get a complete, empty
+/// receipt. Receipts are stamped with the work order id and hashed.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::{Outcome, ReceiptBuilder, WorkOrderBuilder};
+/// use abp_runtime::api::RuntimeApi;
+/// use abp_runtime::fake::FakeRuntime;
+///
+/// # async fn demo() {
+/// let rt = FakeRuntime::new()
+///     .with_receipt("mock", ReceiptBuilder::new("mock").outcome(Outcome::Partial).build());
+///
+/// let receipt = rt.run("mock", WorkOrderBuilder::new("task").build()).await.unwrap();
+/// assert_eq!(receipt.outcome, Outcome::Partial);
+/// assert_eq!(rt.submitted().len(), 1);
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct FakeRuntime {
+    scripts: Mutex<BTreeMap<String, VecDeque<Scripted>>>,
+    submitted: Mutex<Vec<(String, WorkOrder)>>,
+}
+
+impl FakeRuntime {
+    /// A fake with no backends.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `backend` without scripting any answer.
+    #[must_use]
+    pub fn with_backend(mut self, backend: impl Into<String>) -> Self {
+        self.script(backend.into());
+        self
+    }
+
+    /// Queue `receipt` as the answer to the next unanswered run on `backend`,
+    /// registering the backend if needed.
+    #[must_use]
+    pub fn with_receipt(mut self, backend: impl Into<String>, receipt: Receipt) -> Self {
+        self.script(backend.into())
+            .push_back(Scripted::Receipt(Box::new(receipt)));
+        self
+    }
+
+    /// Queue a backend failure with `message` as the answer to the next
+    /// unanswered run on `backend`, registering the backend if needed.
+    #[must_use]
+    pub fn with_error(mut self, backend: impl Into<String>, message: impl Into<String>) -> Self {
+        self.script(backend.into())
+            .push_back(Scripted::Error(message.into()));
+        self
+    }
+
+    /// Every `(backend, work order)` run so far, oldest first.
+    #[must_use]
+    pub fn submitted(&self) -> Vec<(String, WorkOrder)> {
+        self.submitted
+            .lock()
+            .expect("fake runtime mutex poisoned")
+            .clone()
+    }
+
+    fn script(&mut self, backend: String) -> &mut VecDeque<Scripted> {
+        self.scripts
+            .get_mut()
+            .expect("fake runtime mutex poisoned")
+            .entry(backend)
+            .or_default()
+    }
+}
+
+#[async_trait]
+impl RuntimeApi for FakeRuntime {
+    fn backend_names(&self) -> Vec<String> {
+        self.scripts
+            .lock()
+            .expect("fake runtime mutex poisoned")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    async fn run(&self, backend: &str, work_order: WorkOrder) -> Result<Receipt, RuntimeError> {
+        let next = {
+            let mut scripts = self.scripts.lock().expect("fake runtime mutex poisoned");
+            let script = scripts
+                .get_mut(backend)
+                .ok_or_else(|| RuntimeError::UnknownBackend {
+                    name: backend.to_string(),
+                })?;
+            script.pop_front()
+        };
+        let work_order_id = work_order.id;
+        self.submitted
+            .lock()
+            .expect("fake runtime mutex poisoned")
+            .push((backend.to_string(), work_order));
+
+        let mut receipt = match next {
+            Some(Scripted::Receipt(receipt)) => *receipt,
+            Some(Scripted::Error(message)) => {
+                return Err(RuntimeError::BackendFailed(anyhow::anyhow!(message)));
+            }
+            None => ReceiptBuilder::new(backend)
+                .outcome(Outcome::Complete)
+                .build(),
+        };
+        receipt.meta.work_order_id = work_order_id;
+        receipt
+            .with_hash()
+            .map_err(|e| RuntimeError::BackendFailed(e.into()))
+    }
+}
@@ -0,0 +1,277 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Work-order fidelity policy: how the runtime reacts to lossy mappings.
+//!
+//! A work order selects a [`FidelityPolicy`](crate::fidelity::FidelityPolicy)
+//! through `config.fidelity_policy` (see
+//! [`FidelityPolicy::from_work_order`](crate::fidelity::FidelityPolicy::from_work_order)
+//! for the vendor-config fallback). Before the backend
+//! is started the runtime collects every emulated capability and lossy
+//! dialect mapping into a
+//! [`FidelityReport`](crate::fidelity::FidelityReport); under the strict
+//! policy a non-empty report rejects the run up front.
+
+use crate::negotiate::NegotiationResult;
+use abp_error::{AbpError, ErrorCode};
+use abp_projection::translate::{TranslationMode, TranslationResult};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+pub use abp_core::FidelityPolicy;
+
+/// Category of a fidelity loss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FidelityIssueKind {
+    /// A required capability is provided through emulation.
+    EmulatedCapability,
+    /// A dialect mapping step drops or approximates information.
+    LossyMapping,
+}
+
+/// A single reason the run would not be a faithful execution of the request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FidelityIssue {
+    /// Category of the loss.
+    pub kind: FidelityIssueKind,
+    /// Capability or feature affected.
+    pub subject: String,
+    /// Human-readable explanation.
+    pub detail: String,
+}
+
+/// Every fidelity loss detected for a run, together with the active policy.
+///
+/// Recorded in the receipt's `usage_raw` under the `"fidelity"` key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FidelityReport {
+    /// Policy the run was evaluated under.
+    pub policy: FidelityPolicy,
+    /// Detected losses, in discovery order.
+    pub issues: Vec<FidelityIssue>,
+}
+
+impl FidelityReport {
+    /// Assess a run from its negotiation result and translation metadata.
+    #[must_use]
+    pub fn assess(
+        policy: FidelityPolicy,
+        negotiation: Option<&NegotiationResult>,
+        translation: Option<&TranslationResult>,
+    ) -> Self {
+        let mut issues = Vec::new();
+
+        for emu in negotiation.into_iter().flat_map(|n| &n.emulated) {
+            issues.push(FidelityIssue {
+                kind: FidelityIssueKind::EmulatedCapability,
+                subject: format!("{:?}", emu.capability),
+                detail: format!("emulated ({}): {}", emu.source, emu.description),
+            });
+        }
+
+        if let Some(tr) = translation {
+            if tr.mode == TranslationMode::Emulated {
+                issues.push(FidelityIssue {
+                    kind: FidelityIssueKind::LossyMapping,
+                    subject: format!("{}->{}", tr.from.label(), tr.to.label()),
+                    detail: "no direct mapper; translation is emulated".into(),
+                });
+            }
+            for gap in &tr.gaps {
+                issues.push(FidelityIssue {
+                    kind: FidelityIssueKind::LossyMapping,
+                    subject: format!("{:?}", gap.feature),
+                    detail: gap.description.clone(),
+                });
+            }
+        }
+
+        Self { policy, issues }
+    }
+
+    /// Returns `true` when no fidelity loss was detected.
+    #[must_use]
+    pub fn is_lossless(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns `true` when the policy requires the run to be rejected.
+    #[must_use]
+    pub fn should_reject(&self) -> bool {
+        self.policy == FidelityPolicy::Strict && !self.is_lossless()
+    }
+
+    /// Convert into a classified error carrying the full report as context.
+    #[must_use]
+    pub fn to_error(&self, backend: &str) -> AbpError {
+        AbpError::new(
+            ErrorCode::MappingLossyConversion,
+            format!("backend '{backend}': strict fidelity policy violated: {self}"),
+        )
+        .with_context("backend", backend)
+        .with_context("fidelity_report", self)
+    }
+}
+
+impl fmt::Display for FidelityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.issues.is_empty() {
+            return f.write_str("lossless");
+        }
+        let parts: Vec<String> = self
+            .issues
+            .iter()
+            .map(|i| format!("{} ({})", i.subject, i.detail))
+            .collect();
+        write!(f, "{} issue(s): {}", self.issues.len(), parts.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::negotiate::EmulatedCapability;
+    use abp_core::{Capability, WorkOrder, WorkOrderBuilder};
+    use abp_dialect::Dialect;
+    use abp_dialect::features::DialectFeature;
+    use abp_projection::translate::CapabilityGap;
+
+    fn work_order_with(vendor: &[(&str, serde_json::Value)]) -> WorkOrder {
+        let mut wo = WorkOrderBuilder::new("t").build();
+        for (k, v) in vendor {
+            wo.config.vendor.insert((*k).to_string(), v.clone());
+        }
+        wo
+    }
+
+    fn emulated_negotiation() -> NegotiationResult {
+        NegotiationResult {
+            native: vec![Capability::Streaming],
+            emulated: vec![EmulatedCapability {
+                capability: Capability::StructuredOutputJsonSchema,
+                source: "runtime".into(),
+                description: "system prompt injection".into(),
+            }],
+            missing: vec![],
+        }
+    }
+
+    fn gapped_translation() -> TranslationResult {
+        TranslationResult {
+            conversation: abp_core::ir::IrConversation::new(),
+            from: Dialect::Claude,
+            to: Dialect::OpenAi,
+            mode: TranslationMode::Mapped,
+            gaps: vec![CapabilityGap {
+                feature: DialectFeature::ExtendedThinking,
+                source: Dialect::Claude,
+                target: Dialect::OpenAi,
+                description: "thinking blocks dropped".into(),
+            }],
+            log: vec![],
+        }
+    }
+
+    #[test]
+    fn policy_defaults_to_warn() {
+        let wo = work_order_with(&[]);
+        assert_eq!(
+            FidelityPolicy::from_work_order(&wo).unwrap(),
+            FidelityPolicy::Warn
+        );
+    }
+
+    #[test]
+    fn policy_read_from_nested_vendor_config() {
+        let wo = work_order_with(&[("abp", serde_json::json!({"fidelity_policy": "strict"}))]);
+        assert_eq!(
+            FidelityPolicy::from_work_order(&wo).unwrap(),
+            FidelityPolicy::Strict
+        );
+    }
+
+    #[test]
+    fn policy_read_from_flat_vendor_key() {
+        let wo = work_order_with(&[("abp.fidelity_policy", serde_json::json!("permissive"))]);
+        assert_eq!(
+            FidelityPolicy::from_work_order(&wo).unwrap(),
+            FidelityPolicy::Permissive
+        );
+    }
+
+    #[test]
+    fn typed_policy_wins_over_vendor_config() {
+        let mut wo = WorkOrderBuilder::new("t")
+            .fidelity_policy(FidelityPolicy::Strict)
+            .build();
+        wo.config.vendor.insert(
+            "abp.fidelity_policy".into(),
+            serde_json::json!("permissive"),
+        );
+        assert_eq!(wo.config.fidelity_policy, Some(FidelityPolicy::Strict));
+        assert_eq!(
+            FidelityPolicy::from_work_order(&wo).unwrap(),
+            FidelityPolicy::Strict
+        );
+    }
+
+    #[test]
+    fn unknown_policy_is_rejected() {
+        let wo = work_order_with(&[("abp", serde_json::json!({"fidelity_policy": "strcit"}))]);
+        let err = FidelityPolicy::from_work_order(&wo).unwrap_err();
+        assert_eq!(err.code, ErrorCode::ConfigInvalid);
+        assert!(err.message.contains("\"strcit\""), "{}", err.message);
+        let wo = work_order_with(&[("abp.fidelity_policy", serde_json::json!(true))]);
+        assert!(FidelityPolicy::from_work_order(&wo).is_err());
+    }
+
+    #[test]
+    fn lossless_report_never_rejects() {
+        let report = FidelityReport::assess(FidelityPolicy::Strict, None, None);
+        assert!(report.is_lossless());
+        assert!(!report.should_reject());
+        assert_eq!(report.to_string(), "lossless");
+    }
+
+    #[test]
+    fn emulation_and_gaps_are_collected() {
+        let neg = emulated_negotiation();
+        let tr = gapped_translation();
+        let report = FidelityReport::assess(FidelityPolicy::Warn, Some(&neg), Some(&tr));
+        assert_eq!(report.issues.len(), 2);
+        assert_eq!(report.issues[0].kind, FidelityIssueKind::EmulatedCapability);
+        assert_eq!(report.issues[1].kind, FidelityIssueKind::LossyMapping);
+        assert!(!report.should_reject());
+    }
+
+    #[test]
+    fn strict_rejects_lossy_report() {
+        let tr = gapped_translation();
+        let report = FidelityReport::assess(FidelityPolicy::Strict, None, Some(&tr));
+        assert!(report.should_reject());
+        let err = report.to_error("mock");
+        assert_eq!(err.code, ErrorCode::MappingLossyConversion);
+        assert!(err.message.contains("thinking blocks dropped"));
+        assert!(err.context.contains_key("fidelity_report"));
+    }
+
+    #[test]
+    fn emulated_translation_mode_is_lossy() {
+        let mut tr = gapped_translation();
+        tr.gaps.clear();
+        tr.mode = TranslationMode::Emulated;
+        let report = FidelityReport::assess(FidelityPolicy::Strict, None, Some(&tr));
+        assert_eq!(report.issues.len(), 1);
+        assert!(report.should_reject());
+    }
+
+    #[test]
+    fn report_serde_roundtrip() {
+        let neg = emulated_negotiation();
+        let report = FidelityReport::assess(FidelityPolicy::Strict, Some(&neg), None);
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["policy"], "strict");
+        assert_eq!(json["issues"][0]["kind"], "emulated_capability");
+        let back: FidelityReport = serde_json::from_value(json).unwrap();
+        assert_eq!(back, report);
+    }
+}
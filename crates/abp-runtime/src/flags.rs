@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Feature flags for experimental runtime behaviour.
+//!
+//! A [`FeatureFlags`](crate::flags::FeatureFlags) registry declares each
+//! experimental flag with its default, either programmatically or from the
+//! `features` table of a
+//! [`RuntimeConfig`](crate::config_integration::RuntimeConfig). A work order
+//! may override declared flags through the `abp.features` vendor key (set with
+//! [`WorkOrderBuilder::feature`](abp_core::WorkOrderBuilder::feature));
+//! overrides of undeclared flags are ignored, so callers cannot switch on
+//! behaviour the operator never registered.
+//!
+//! The resolved flags of every run are recorded under
+//! `usage_raw["feature_flags"]`, so two receipts that behave differently can
+//! be told apart after the fact.
+
+use std::collections::BTreeMap;
+
+use abp_core::{Receipt, WorkOrder};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config_integration::RuntimeConfig;
+
+/// Vendor key carrying a work order's flag overrides (`{"name": bool}`).
+pub const FEATURES_VENDOR_KEY: &str = "abp.features";
+
+/// Key under `receipt.usage_raw` holding the resolved flags.
+pub const FEATURE_FLAGS_KEY: &str = "feature_flags";
+
+/// Compact the receipt trace with the default
+/// [`CompactionPolicy`](abp_core::compact::CompactionPolicy) before hashing.
+pub const TRACE_COMPACTION: &str = "trace_compaction";
+
+/// Registry of declared feature flags and their defaults.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::WorkOrderBuilder;
+/// use abp_runtime::flags::{FeatureFlags, TRACE_COMPACTION};
+///
+/// let flags = FeatureFlags::new().declare(TRACE_COMPACTION, false);
+/// let wo = WorkOrderBuilder::new("task")
+///     .feature(TRACE_COMPACTION, true)
+///     .build();
+/// assert!(flags.resolve(&wo)[TRACE_COMPACTION]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureFlags {
+    defaults: BTreeMap<String, bool>,
+}
+
+impl FeatureFlags {
+    /// An empty registry; every flag is off.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare `name` with its default state, replacing any earlier default.
+    #[must_use]
+    pub fn declare(mut self, name: impl Into<String>, enabled: bool) -> Self {
+        self.defaults.insert(name.into(), enabled);
+        self
+    }
+
+    /// The flags declared in `config.features`.
+    #[must_use]
+    pub fn from_config(config: &RuntimeConfig) -> Self {
+        Self {
+            defaults: config.features.clone(),
+        }
+    }
+
+    /// Whether no flag is declared.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.defaults.is_empty()
+    }
+
+    /// Default state of `name`; undeclared flags are off.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.defaults.get(name).copied().unwrap_or(false)
+    }
+
+    /// State of every declared flag for `work_order`, with its overrides
+    /// applied.
+    #[must_use]
+    pub fn resolve(&self, work_order: &WorkOrder) -> ResolvedFlags {
+        let mut resolved = self.defaults.clone();
+        for (name, enabled) in overrides(work_order) {
+            if let Some(state) = resolved.get_mut(&name) {
+                *state = enabled;
+            }
+        }
+        ResolvedFlags(resolved)
+    }
+}
+
+/// Flag overrides a work order requests, declared or not.
+#[must_use]
+pub fn overrides(work_order: &WorkOrder) -> BTreeMap<String, bool> {
+    let vendor = &work_order.config.vendor;
+    let value = vendor.get(FEATURES_VENDOR_KEY).or_else(|| {
+        vendor
+            .get("abp")
+            .and_then(|abp| abp.get(FEATURES_VENDOR_KEY.trim_start_matches("abp.")))
+    });
+    value
+        .and_then(Value::as_object)
+        .map(|map| {
+            map.iter()
+                .filter_map(|(name, v)| Some((name.clone(), v.as_bool()?)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Flag states in effect for one run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ResolvedFlags(BTreeMap<String, bool>);
+
+impl ResolvedFlags {
+    /// Whether `name` is on for this run.
+    #[must_use]
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.0.get(name).copied().unwrap_or(false)
+    }
+
+    /// Names of the flags that are on, in order.
+    pub fn active(&self) -> impl Iterator<Item = &str> {
+        self.0
+            .iter()
+            .filter(|(_, on)| **on)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Read the resolved flags of a receipt; empty if none were declared.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Self {
+        receipt
+            .usage_raw
+            .get(FEATURE_FLAGS_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Store these flags under `usage_raw["feature_flags"]`; nothing is
+    /// recorded when no flag is declared.
+    pub fn record(&self, receipt: &mut Receipt) {
+        if self.0.is_empty() {
+            return;
+        }
+        if let Some(obj) = receipt.usage_raw.as_object_mut()
+            && let Ok(value) = serde_json::to_value(self)
+        {
+            obj.insert(FEATURE_FLAGS_KEY.to_string(), value);
+        }
+    }
+}
+
+impl std::ops::Index<&str> for ResolvedFlags {
+    type Output = bool;
+
+    fn index(&self, name: &str) -> &bool {
+        self.0.get(name).unwrap_or(&false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::WorkOrderBuilder;
+
+    #[test]
+    fn defaults_apply_without_overrides() {
+        let flags = FeatureFlags::new().declare("a", true).declare("b", false);
+        let resolved = flags.resolve(&WorkOrderBuilder::new("t").build());
+        assert!(resolved.is_enabled("a"));
+        assert!(!resolved.is_enabled("b"));
+        assert_eq!(resolved.active().collect::<Vec<_>>(), ["a"]);
+    }
+
+    #[test]
+    fn work_order_overrides_declared_flags() {
+        let flags = FeatureFlags::new().declare("a", true).declare("b", false);
+        let wo = WorkOrderBuilder::new("t")
+            .feature("a", false)
+            .feature("b", true)
+            .build();
+        let resolved = flags.resolve(&wo);
+        assert!(!resolved.is_enabled("a"));
+        assert!(resolved.is_enabled("b"));
+    }
+
+    #[test]
+    fn undeclared_overrides_are_ignored() {
+        let wo = WorkOrderBuilder::new("t").feature("rogue", true).build();
+        let resolved = FeatureFlags::new().resolve(&wo);
+        assert!(!resolved.is_enabled("rogue"));
+        assert_eq!(overrides(&wo).get("rogue"), Some(&true));
+    }
+
+    #[test]
+    fn flat_vendor_key_is_read() {
+        let mut wo = WorkOrderBuilder::new("t").build();
+        wo.config
+            .vendor
+            .insert(FEATURES_VENDOR_KEY.into(), serde_json::json!({"a": true}));
+        assert_eq!(overrides(&wo).get("a"), Some(&true));
+    }
+}
@@ -0,0 +1,372 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! GitHub reporting of run outcomes.
+//!
+//! [`GitHubReporter`](crate::github::GitHubReporter) posts a finished
+//! receipt to GitHub against the commit the run was about, as a commit
+//! status or as a check run
+//! ([`GitHubReportKind`](crate::github::GitHubReportKind)). Check runs carry
+//! the receipt rendered as Markdown in their summary and the workspace diff
+//! (`verification.git_diff`) in their details, so a run triggered for a pull
+//! request shows up in its checks tab next to CI. Commit statuses only have
+//! room for a one-line description.
+//!
+//! Check runs can only be created with a GitHub App installation token
+//! (such as the `GITHUB_TOKEN` of a GitHub Actions job); commit statuses
+//! work with any token allowed to write statuses.
+
+use abp_core::{AgentEventKind, Outcome, Receipt};
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+
+/// Default GitHub REST API base URL.
+pub const DEFAULT_GITHUB_API: &str = "https://api.github.com";
+
+/// Default status context / check run name.
+pub const DEFAULT_CHECK_NAME: &str = "agent-backplane";
+
+/// REST API version the payloads are written against.
+const API_VERSION: &str = "2022-11-28";
+
+/// GitHub's limit on a commit status description.
+const MAX_DESCRIPTION_CHARS: usize = 140;
+
+/// GitHub's limit on each of a check run's `summary` and `text`.
+const MAX_OUTPUT_CHARS: usize = 65_535;
+
+/// How a run is reported to GitHub.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GitHubReportKind {
+    /// A commit status (`POST /repos/{owner}/{repo}/statuses/{sha}`).
+    #[default]
+    CommitStatus,
+    /// A completed check run (`POST /repos/{owner}/{repo}/check-runs`).
+    CheckRun,
+}
+
+/// Commit status state for a run outcome.
+#[must_use]
+pub fn status_state(outcome: &Outcome) -> &'static str {
+    match outcome {
+        Outcome::Complete => "success",
+        Outcome::Partial | Outcome::Failed => "failure",
+    }
+}
+
+/// Check run conclusion for a run outcome.
+#[must_use]
+pub fn check_conclusion(outcome: &Outcome) -> &'static str {
+    match outcome {
+        Outcome::Complete => "success",
+        Outcome::Partial => "neutral",
+        Outcome::Failed => "failure",
+    }
+}
+
+/// One-line description of a run, short enough for a commit status.
+#[must_use]
+pub fn status_description(receipt: &Receipt) -> String {
+    let mut description = format!(
+        "{} on {} in {}",
+        outcome_label(&receipt.outcome),
+        receipt.backend.id,
+        duration(receipt.meta.duration_ms),
+    );
+    if let (Some(input), Some(output)) = (receipt.usage.input_tokens, receipt.usage.output_tokens) {
+        description.push_str(&format!(", {input} tokens in / {output} out"));
+    }
+    truncate(&description, MAX_DESCRIPTION_CHARS, "…")
+}
+
+/// The receipt rendered as Markdown, for a check run summary.
+#[must_use]
+pub fn check_summary(receipt: &Receipt) -> String {
+    let meta = &receipt.meta;
+    let usage = &receipt.usage;
+    let mut rows = vec![
+        ("Outcome", outcome_label(&receipt.outcome).to_string()),
+        ("Backend", receipt.backend.id.clone()),
+        ("Run", format!("`{}`", meta.run_id)),
+        ("Work order", format!("`{}`", meta.work_order_id)),
+        ("Duration", duration(meta.duration_ms)),
+    ];
+    if let Some(model) = receipt.usage_raw.get("model").and_then(Value::as_str) {
+        rows.push(("Model", format!("`{model}`")));
+    }
+    if usage.input_tokens.is_some() || usage.output_tokens.is_some() {
+        let count = |n: Option<u64>| n.map_or_else(|| "-".to_string(), |n| n.to_string());
+        rows.push((
+            "Tokens",
+            format!(
+                "{} in / {} out",
+                count(usage.input_tokens),
+                count(usage.output_tokens)
+            ),
+        ));
+    }
+    if let Some(cost) = usage.estimated_cost_usd {
+        rows.push(("Estimated cost", format!("${cost:.4}")));
+    }
+
+    let count =
+        |f: fn(&AgentEventKind) -> bool| receipt.trace.iter().filter(|e| f(&e.kind)).count();
+    rows.push((
+        "Tool calls",
+        count(|k| matches!(k, AgentEventKind::ToolCall { .. })).to_string(),
+    ));
+    rows.push((
+        "Files changed",
+        count(|k| matches!(k, AgentEventKind::FileChanged { .. })).to_string(),
+    ));
+    if let Some(hash) = &receipt.receipt_sha256 {
+        rows.push(("Receipt", format!("`{hash}`")));
+    }
+
+    let mut out = String::from("| | |\n|---|---|\n");
+    for (label, value) in rows {
+        out.push_str(&format!("| {label} | {} |\n", value.replace('|', "\\|")));
+    }
+    let errors: Vec<&str> = receipt
+        .trace
+        .iter()
+        .filter_map(|e| match &e.kind {
+            AgentEventKind::Error { message, .. } => Some(message.as_str()),
+            _ => None,
+        })
+        .collect();
+    if !errors.is_empty() {
+        out.push_str("\n**Errors**\n\n");
+        for message in errors {
+            out.push_str(&format!("- {}\n", message.lines().next().unwrap_or("")));
+        }
+    }
+    truncate(&out, MAX_OUTPUT_CHARS, "\n…")
+}
+
+/// The workspace diff as a Markdown code block, for a check run's details.
+///
+/// `None` if the receipt has no diff.
+#[must_use]
+pub fn check_text(receipt: &Receipt) -> Option<String> {
+    let diff = receipt
+        .verification
+        .git_diff
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())?;
+    let fence = "```";
+    let note = "\n… diff truncated";
+    let room = MAX_OUTPUT_CHARS - (fence.len() * 2 + "diff\n\n".len() + note.len());
+    Some(format!(
+        "{fence}diff\n{}\n{fence}",
+        truncate(diff.trim_end(), room, note)
+    ))
+}
+
+/// Request body for a commit status.
+#[must_use]
+pub fn status_payload(receipt: &Receipt, context: &str, target_url: Option<&str>) -> Value {
+    let mut body = json!({
+        "state": status_state(&receipt.outcome),
+        "description": status_description(receipt),
+        "context": context,
+    });
+    if let Some(url) = target_url {
+        body["target_url"] = json!(url);
+    }
+    body
+}
+
+/// Request body for a completed check run on `sha`.
+#[must_use]
+pub fn check_run_payload(
+    receipt: &Receipt,
+    name: &str,
+    sha: &str,
+    details_url: Option<&str>,
+) -> Value {
+    let mut output = json!({
+        "title": status_description(receipt),
+        "summary": check_summary(receipt),
+    });
+    if let Some(text) = check_text(receipt) {
+        output["text"] = json!(text);
+    }
+    let mut body = json!({
+        "name": name,
+        "head_sha": sha,
+        "status": "completed",
+        "external_id": receipt.meta.run_id.to_string(),
+        "started_at": receipt.meta.started_at.to_rfc3339(),
+        "completed_at": receipt.meta.finished_at.to_rfc3339(),
+        "conclusion": check_conclusion(&receipt.outcome),
+        "output": output,
+    });
+    if let Some(url) = details_url {
+        body["details_url"] = json!(url);
+    }
+    body
+}
+
+/// Posts run outcomes to a GitHub repository.
+///
+/// ```no_run
+/// # async fn demo(receipt: abp_core::Receipt) -> anyhow::Result<()> {
+/// use abp_runtime::github::{GitHubReportKind, GitHubReporter};
+///
+/// let token = std::env::var("GITHUB_TOKEN")?;
+/// GitHubReporter::new("octo-org/widgets", token)
+///     .with_kind(GitHubReportKind::CheckRun)
+///     .report(&receipt, "6dcb09b5b57875f334f61aebed695e2e4193db5e")
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct GitHubReporter {
+    api_base: String,
+    repository: String,
+    token: String,
+    name: String,
+    kind: GitHubReportKind,
+    details_url: Option<String>,
+    client: reqwest::Client,
+}
+
+impl std::fmt::Debug for GitHubReporter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitHubReporter")
+            .field("api_base", &self.api_base)
+            .field("repository", &self.repository)
+            .field("name", &self.name)
+            .field("kind", &self.kind)
+            .field("details_url", &self.details_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GitHubReporter {
+    /// Report to `repository` (`owner/name`), authenticating with `token`.
+    pub fn new(repository: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            api_base: DEFAULT_GITHUB_API.into(),
+            repository: repository.into(),
+            token: token.into(),
+            name: DEFAULT_CHECK_NAME.into(),
+            kind: GitHubReportKind::default(),
+            details_url: None,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Use another API base URL, e.g. a GitHub Enterprise Server's
+    /// `https://ghe.example.com/api/v3` (builder pattern).
+    #[must_use]
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Report as a commit status or a check run (builder pattern).
+    #[must_use]
+    pub fn with_kind(mut self, kind: GitHubReportKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Status context / check run name (builder pattern).
+    #[must_use]
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Link the report to `url`, e.g. the run's page in a dashboard
+    /// (builder pattern).
+    #[must_use]
+    pub fn with_details_url(mut self, url: impl Into<String>) -> Self {
+        self.details_url = Some(url.into());
+        self
+    }
+
+    /// How runs are reported.
+    #[must_use]
+    pub fn kind(&self) -> GitHubReportKind {
+        self.kind
+    }
+
+    /// URL the report for commit `sha` is posted to.
+    #[must_use]
+    pub fn report_url(&self, sha: &str) -> String {
+        match self.kind {
+            GitHubReportKind::CommitStatus => {
+                format!("{}/repos/{}/statuses/{sha}", self.api_base, self.repository)
+            }
+            GitHubReportKind::CheckRun => {
+                format!("{}/repos/{}/check-runs", self.api_base, self.repository)
+            }
+        }
+    }
+
+    /// Request body reporting `receipt` on commit `sha`.
+    #[must_use]
+    pub fn payload(&self, receipt: &Receipt, sha: &str) -> Value {
+        let details = self.details_url.as_deref();
+        match self.kind {
+            GitHubReportKind::CommitStatus => status_payload(receipt, &self.name, details),
+            GitHubReportKind::CheckRun => check_run_payload(receipt, &self.name, sha, details),
+        }
+    }
+
+    /// Post `receipt` as the outcome for commit `sha`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or GitHub does not answer with
+    /// a success status.
+    pub async fn report(&self, receipt: &Receipt, sha: &str) -> Result<()> {
+        let url = self.report_url(sha);
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", API_VERSION)
+            .header(reqwest::header::USER_AGENT, DEFAULT_CHECK_NAME)
+            .json(&self.payload(receipt, sha))
+            .send()
+            .await
+            .with_context(|| format!("post run outcome to {url}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("GitHub at {url} returned {status}: {body}");
+        }
+        Ok(())
+    }
+}
+
+fn outcome_label(outcome: &Outcome) -> &'static str {
+    match outcome {
+        Outcome::Complete => "complete",
+        Outcome::Partial => "partial",
+        Outcome::Failed => "failed",
+    }
+}
+
+fn duration(ms: u64) -> String {
+    if ms < 1_000 {
+        format!("{ms} ms")
+    } else {
+        format!("{:.1} s", ms as f64 / 1_000.0)
+    }
+}
+
+/// `text` cut to at most `max` characters, ending in `marker` if cut.
+fn truncate(text: &str, max: usize, marker: &str) -> String {
+    if text.chars().count() <= max {
+        return text.to_string();
+    }
+    let keep = max.saturating_sub(marker.chars().count());
+    let mut out: String = text.chars().take(keep).collect();
+    out.push_str(marker);
+    out
+}
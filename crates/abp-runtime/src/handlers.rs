@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Typed callbacks over a run's event stream.
+//!
+//! [`RunHandlers`](crate::handlers::RunHandlers) lets application code react
+//! to a [`RunHandle`](crate::RunHandle) the way the vendor SDKs' streaming
+//! helpers do — register `on_text`, `on_tool_call`, `on_thinking`, … and
+//! call [`RunHandlers::run`](crate::handlers::RunHandlers::run) — instead of
+//! writing a `match` loop over `AgentEventKind`.
+//!
+//! ```no_run
+//! # async fn demo(rt: abp_runtime::Runtime, wo: abp_core::WorkOrder) -> Result<(), abp_runtime::RuntimeError> {
+//! use abp_runtime::handlers::RunHandlers;
+//!
+//! let handle = rt.run_streaming("mock", wo).await?;
+//! let receipt = RunHandlers::new()
+//!     .on_text(|text| print!("{text}"))
+//!     .on_tool_call(|name, _id, input| println!("\n[{name}] {input}"))
+//!     .run(handle)
+//!     .await?;
+//! println!("\noutcome: {:?}", receipt.outcome);
+//! # Ok(())
+//! # }
+//! ```
+
+use abp_core::{AgentEvent, AgentEventKind, Receipt};
+use tokio_stream::StreamExt;
+
+use crate::{RunHandle, RuntimeError};
+
+type StrFn<'a> = Box<dyn FnMut(&str) + Send + 'a>;
+type ToolCallFn<'a> = Box<dyn FnMut(&str, Option<&str>, &serde_json::Value) + Send + 'a>;
+type ToolResultFn<'a> = Box<dyn FnMut(&str, &serde_json::Value, bool) + Send + 'a>;
+type EventFn<'a> = Box<dyn FnMut(&AgentEvent) + Send + 'a>;
+type CompleteFn<'a> = Box<dyn FnOnce(&Receipt) + Send + 'a>;
+
+/// Callbacks invoked while draining a [`RunHandle`].
+///
+/// Assistant events whose `ext["thinking"]` is `true` go to
+/// [`on_thinking`](Self::on_thinking); all other `AssistantDelta` and
+/// `AssistantMessage` text goes to [`on_text`](Self::on_text).
+/// [`on_event`](Self::on_event) sees every event, including those also
+/// dispatched to a typed callback.
+#[derive(Default)]
+pub struct RunHandlers<'a> {
+    text: Option<StrFn<'a>>,
+    thinking: Option<StrFn<'a>>,
+    tool_call: Option<ToolCallFn<'a>>,
+    tool_result: Option<ToolResultFn<'a>>,
+    error: Option<StrFn<'a>>,
+    event: Option<EventFn<'a>>,
+    complete: Option<CompleteFn<'a>>,
+}
+
+impl std::fmt::Debug for RunHandlers<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunHandlers")
+            .field("text", &self.text.is_some())
+            .field("thinking", &self.thinking.is_some())
+            .field("tool_call", &self.tool_call.is_some())
+            .field("tool_result", &self.tool_result.is_some())
+            .field("error", &self.error.is_some())
+            .field("event", &self.event.is_some())
+            .field("complete", &self.complete.is_some())
+            .finish()
+    }
+}
+
+impl<'a> RunHandlers<'a> {
+    /// Create a handler set with no callbacks registered.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called with each assistant text fragment or complete message.
+    #[must_use]
+    pub fn on_text(mut self, f: impl FnMut(&str) + Send + 'a) -> Self {
+        self.text = Some(Box::new(f));
+        self
+    }
+
+    /// Called with each assistant thinking fragment or block.
+    #[must_use]
+    pub fn on_thinking(mut self, f: impl FnMut(&str) + Send + 'a) -> Self {
+        self.thinking = Some(Box::new(f));
+        self
+    }
+
+    /// Called with `(tool_name, tool_use_id, input)` for each tool call.
+    #[must_use]
+    pub fn on_tool_call(
+        mut self,
+        f: impl FnMut(&str, Option<&str>, &serde_json::Value) + Send + 'a,
+    ) -> Self {
+        self.tool_call = Some(Box::new(f));
+        self
+    }
+
+    /// Called with `(tool_name, output, is_error)` for each tool result.
+    #[must_use]
+    pub fn on_tool_result(
+        mut self,
+        f: impl FnMut(&str, &serde_json::Value, bool) + Send + 'a,
+    ) -> Self {
+        self.tool_result = Some(Box::new(f));
+        self
+    }
+
+    /// Called with the message of each `Error` event.
+    #[must_use]
+    pub fn on_error(mut self, f: impl FnMut(&str) + Send + 'a) -> Self {
+        self.error = Some(Box::new(f));
+        self
+    }
+
+    /// Called with every event, before any typed callback.
+    #[must_use]
+    pub fn on_event(mut self, f: impl FnMut(&AgentEvent) + Send + 'a) -> Self {
+        self.event = Some(Box::new(f));
+        self
+    }
+
+    /// Called once with the final receipt when the run succeeds.
+    #[must_use]
+    pub fn on_complete(mut self, f: impl FnOnce(&Receipt) + Send + 'a) -> Self {
+        self.complete = Some(Box::new(f));
+        self
+    }
+
+    /// Dispatch a single event to the registered callbacks.
+    pub fn dispatch(&mut self, event: &AgentEvent) {
+        if let Some(f) = &mut self.event {
+            f(event);
+        }
+        match &event.kind {
+            AgentEventKind::AssistantDelta { text } | AgentEventKind::AssistantMessage { text } => {
+                let slot = if is_thinking(event) {
+                    &mut self.thinking
+                } else {
+                    &mut self.text
+                };
+                if let Some(f) = slot {
+                    f(text);
+                }
+            }
+            AgentEventKind::ToolCall {
+                tool_name,
+                tool_use_id,
+                input,
+                ..
+            } => {
+                if let Some(f) = &mut self.tool_call {
+                    f(tool_name, tool_use_id.as_deref(), input);
+                }
+            }
+            AgentEventKind::ToolResult {
+                tool_name,
+                output,
+                is_error,
+                ..
+            } => {
+                if let Some(f) = &mut self.tool_result {
+                    f(tool_name, output, *is_error);
+                }
+            }
+            AgentEventKind::Error { message, .. } => {
+                if let Some(f) = &mut self.error {
+                    f(message);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Drain `handle`, dispatching every event, then await its receipt.
+    ///
+    /// # Errors
+    ///
+    /// Returns the run's [`RuntimeError`], or an
+    /// [`Internal`](abp_error::ErrorCode::Internal) error if the receipt task
+    /// failed to complete.
+    pub async fn run(mut self, handle: RunHandle) -> Result<Receipt, RuntimeError> {
+        let mut events = handle.events;
+        while let Some(event) = events.next().await {
+            self.dispatch(&event);
+        }
+        let receipt = handle
+            .receipt
+            .await
+            .map_err(|e| RuntimeError::internal("run task failed", e))??;
+        if let Some(f) = self.complete.take() {
+            f(&receipt);
+        }
+        Ok(receipt)
+    }
+}
+
+fn is_thinking(event: &AgentEvent) -> bool {
+    event
+        .ext
+        .as_ref()
+        .and_then(|ext| ext.get("thinking"))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+}
@@ -0,0 +1,452 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Write-ahead journal for crash-consistent receipt finalization.
+//!
+//! Each in-flight run gets a `{run_id}.wal` JSONL file under the journal
+//! directory: a `begin` record when the backend starts, an `event` record for
+//! every event the runtime forwards, and a `receipt` record once the receipt
+//! is hashed. The entry is removed once the receipt is durable — saved to the
+//! attached [`ReceiptStore`](crate::store::ReceiptStore) or acknowledged via
+//! [`ReceiptJournal::complete`](crate::journal::ReceiptJournal::complete).
+//!
+//! After a crash,
+//! [`ReceiptJournal::recover`](crate::journal::ReceiptJournal::recover) turns
+//! every leftover entry into a receipt: the journalled one if finalization got
+//! that far, otherwise a failed receipt marked `interrupted` that carries the
+//! partial trace.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use abp_core::clock::{SharedClock, system_clock};
+use abp_core::{AgentEvent, Receipt};
+use abp_receipt::ReceiptBuilder;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::store::ReceiptStore;
+
+/// One line of a journal entry.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum JournalRecord {
+    Begin {
+        run_id: Uuid,
+        work_order_id: Uuid,
+        backend: String,
+        started_at: DateTime<Utc>,
+    },
+    Event {
+        event: AgentEvent,
+    },
+    Receipt {
+        receipt: Box<Receipt>,
+    },
+}
+
+/// A run reconstructed from the journal by [`ReceiptJournal::recover`].
+#[derive(Debug, Clone)]
+pub enum RecoveredRun {
+    /// The receipt was finalized but may not have been persisted.
+    Finalized(Receipt),
+    /// The host stopped before the receipt was finalized; the receipt is
+    /// marked failed and carries the events journalled so far.
+    Interrupted(Receipt),
+}
+
+impl RecoveredRun {
+    /// The recovered receipt.
+    #[must_use]
+    pub fn receipt(&self) -> &Receipt {
+        match self {
+            Self::Finalized(r) | Self::Interrupted(r) => r,
+        }
+    }
+
+    /// Consume `self` and return the recovered receipt.
+    #[must_use]
+    pub fn into_receipt(self) -> Receipt {
+        match self {
+            Self::Finalized(r) | Self::Interrupted(r) => r,
+        }
+    }
+}
+
+/// File-based write-ahead journal of in-progress receipts.
+///
+/// # Examples
+///
+/// ```no_run
+/// use abp_runtime::Runtime;
+/// use abp_runtime::journal::ReceiptJournal;
+/// use abp_runtime::store::ReceiptStore;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let journal = ReceiptJournal::new(".agent-backplane/journal")
+///     .with_store(ReceiptStore::new(".agent-backplane/receipts"));
+/// for run in journal.recover()? {
+///     println!("recovered {}", run.receipt().meta.run_id);
+/// }
+/// let rt = Runtime::with_default_backends().with_journal(journal);
+/// # let _ = rt;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ReceiptJournal {
+    dir: PathBuf,
+    store: Option<ReceiptStore>,
+    clock: SharedClock,
+}
+
+impl ReceiptJournal {
+    /// Create a journal rooted at `dir`. The directory is created on first
+    /// write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            store: None,
+            clock: system_clock(),
+        }
+    }
+
+    /// Persist finalized receipts to `store` and drop their journal entries
+    /// automatically (builder pattern).
+    #[must_use]
+    pub fn with_store(mut self, store: ReceiptStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Timestamp `begin` and recovery records with `clock` (builder pattern).
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Return the journal directory.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Return the attached receipt store, if any.
+    #[must_use]
+    pub fn store(&self) -> Option<&ReceiptStore> {
+        self.store.as_ref()
+    }
+
+    /// Start a journal entry for a run, replacing any existing one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be created.
+    pub fn begin(&self, run_id: Uuid, work_order_id: Uuid, backend: &str) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("create journal dir {}", self.dir.display()))?;
+        let path = self.entry_path(run_id);
+        std::fs::write(&path, b"").with_context(|| format!("create {}", path.display()))?;
+        self.append(
+            run_id,
+            &JournalRecord::Begin {
+                run_id,
+                work_order_id,
+                backend: backend.to_string(),
+                started_at: self.clock.now(),
+            },
+            true,
+        )
+    }
+
+    /// Append an observed event to a run's entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry cannot be written.
+    pub fn record_event(&self, run_id: Uuid, event: &AgentEvent) -> Result<()> {
+        self.append(
+            run_id,
+            &JournalRecord::Event {
+                event: event.clone(),
+            },
+            false,
+        )
+    }
+
+    /// Journal the finalized receipt. With a store attached, the receipt is
+    /// then saved and the entry removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receipt cannot be journalled or saved. The
+    /// entry is kept in that case so [`recover`](Self::recover) can retry.
+    pub fn finalize(&self, receipt: &Receipt) -> Result<()> {
+        let run_id = receipt.meta.run_id;
+        self.append(
+            run_id,
+            &JournalRecord::Receipt {
+                receipt: Box::new(receipt.clone()),
+            },
+            true,
+        )?;
+        if let Some(store) = &self.store {
+            store.save(receipt)?;
+            self.complete(run_id)?;
+        }
+        Ok(())
+    }
+
+    /// Remove a run's entry once its receipt is durable elsewhere, or when
+    /// the run ended without a receipt (the error went to the caller).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry exists but cannot be removed.
+    pub fn complete(&self, run_id: Uuid) -> Result<()> {
+        let path = self.entry_path(run_id);
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(anyhow::Error::new(e).context(format!("remove {}", path.display()))),
+        }
+    }
+
+    /// Run ids with a journal entry, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal directory cannot be read.
+    pub fn pending(&self) -> Result<Vec<Uuid>> {
+        let dir = match std::fs::read_dir(&self.dir) {
+            Ok(d) => d,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(anyhow::Error::new(e)
+                    .context(format!("read journal dir {}", self.dir.display())));
+            }
+        };
+        let mut ids = Vec::new();
+        for entry in dir {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("wal")
+                && let Some(stem) = path.file_stem().and_then(|s| s.to_str())
+                && let Ok(id) = Uuid::parse_str(stem)
+            {
+                ids.push(id);
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// Reconstruct a receipt for every leftover entry.
+    ///
+    /// With a store attached, each recovered receipt is saved and its entry
+    /// removed; otherwise entries stay until [`complete`](Self::complete) is
+    /// called. Entries without a `begin` record carry nothing to recover and
+    /// are removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an entry cannot be read or a receipt cannot be
+    /// saved.
+    pub fn recover(&self) -> Result<Vec<RecoveredRun>> {
+        let mut recovered = Vec::new();
+        for run_id in self.pending()? {
+            let Some(run) = self.recover_entry(run_id)? else {
+                self.complete(run_id)?;
+                continue;
+            };
+            if let Some(store) = &self.store {
+                store.save(run.receipt())?;
+                self.complete(run_id)?;
+            }
+            recovered.push(run);
+        }
+        Ok(recovered)
+    }
+
+    fn recover_entry(&self, run_id: Uuid) -> Result<Option<RecoveredRun>> {
+        let path = self.entry_path(run_id);
+        let text =
+            std::fs::read_to_string(&path).with_context(|| format!("read {}", path.display()))?;
+
+        let mut begin = None;
+        let mut trace = Vec::new();
+        for line in text.lines() {
+            // A torn final line is expected after a crash mid-write.
+            let Ok(record) = serde_json::from_str::<JournalRecord>(line) else {
+                break;
+            };
+            match record {
+                JournalRecord::Begin {
+                    work_order_id,
+                    backend,
+                    started_at,
+                    ..
+                } => begin = Some((work_order_id, backend, started_at)),
+                JournalRecord::Event { event } => trace.push(event),
+                JournalRecord::Receipt { receipt } => {
+                    return Ok(Some(RecoveredRun::Finalized(*receipt)));
+                }
+            }
+        }
+
+        let Some((work_order_id, backend, started_at)) = begin else {
+            return Ok(None);
+        };
+        let finished_at = trace.last().map_or(started_at, |e| e.ts);
+        let events = trace.len();
+        let receipt = ReceiptBuilder::new(backend)
+            .run_id(run_id)
+            .work_order_id(work_order_id)
+            .started_at(started_at)
+            .finished_at(finished_at)
+            .events(trace)
+            .usage_raw(serde_json::json!({
+                "interrupted": {
+                    "recovered_at": self.clock.now(),
+                    "events_recovered": events,
+                }
+            }))
+            .error("run interrupted: host stopped before the receipt was persisted")
+            .with_hash()?;
+        Ok(Some(RecoveredRun::Interrupted(receipt)))
+    }
+
+    fn append(&self, run_id: Uuid, record: &JournalRecord, sync: bool) -> Result<()> {
+        let path = self.entry_path(run_id);
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("open {}", path.display()))?;
+        file.write_all(line.as_bytes())
+            .with_context(|| format!("append to {}", path.display()))?;
+        if sync {
+            file.sync_data()
+                .with_context(|| format!("sync {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    fn entry_path(&self, run_id: Uuid) -> PathBuf {
+        self.dir.join(format!("{run_id}.wal"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::{AgentEventKind, Outcome};
+
+    fn event(text: &str) -> AgentEvent {
+        AgentEvent {
+            ts: Utc::now(),
+            kind: AgentEventKind::AssistantMessage { text: text.into() },
+            ext: None,
+        }
+    }
+
+    #[test]
+    fn interrupted_run_recovers_partial_trace() {
+        let tmp = tempfile::tempdir().unwrap();
+        let journal = ReceiptJournal::new(tmp.path());
+        let run_id = Uuid::new_v4();
+        let wo_id = Uuid::new_v4();
+        journal.begin(run_id, wo_id, "mock").unwrap();
+        journal.record_event(run_id, &event("one")).unwrap();
+        journal.record_event(run_id, &event("two")).unwrap();
+
+        let recovered = journal.recover().unwrap();
+        assert_eq!(recovered.len(), 1);
+        let RecoveredRun::Interrupted(receipt) = &recovered[0] else {
+            panic!("expected interrupted run");
+        };
+        assert_eq!(receipt.meta.run_id, run_id);
+        assert_eq!(receipt.meta.work_order_id, wo_id);
+        assert_eq!(receipt.outcome, Outcome::Failed);
+        // Two journalled events plus the interruption error.
+        assert_eq!(receipt.trace.len(), 3);
+        assert_eq!(receipt.usage_raw["interrupted"]["events_recovered"], 2);
+        assert!(receipt.receipt_sha256.is_some());
+        // No store attached: the entry stays until acknowledged.
+        assert_eq!(journal.pending().unwrap(), vec![run_id]);
+        journal.complete(run_id).unwrap();
+        assert!(journal.pending().unwrap().is_empty());
+    }
+
+    #[test]
+    fn finalized_receipt_is_recovered_verbatim() {
+        let tmp = tempfile::tempdir().unwrap();
+        let journal = ReceiptJournal::new(tmp.path());
+        let receipt = ReceiptBuilder::new("mock")
+            .outcome(Outcome::Complete)
+            .with_hash()
+            .unwrap();
+        let run_id = receipt.meta.run_id;
+        journal.begin(run_id, Uuid::nil(), "mock").unwrap();
+        journal.finalize(&receipt).unwrap();
+
+        let recovered = journal.recover().unwrap();
+        let RecoveredRun::Finalized(r) = &recovered[0] else {
+            panic!("expected finalized run");
+        };
+        assert_eq!(r.receipt_sha256, receipt.receipt_sha256);
+    }
+
+    #[test]
+    fn finalize_with_store_persists_and_clears_entry() {
+        let tmp = tempfile::tempdir().unwrap();
+        let journal = ReceiptJournal::new(tmp.path().join("wal"))
+            .with_store(ReceiptStore::new(tmp.path().join("receipts")));
+        let receipt = ReceiptBuilder::new("mock").with_hash().unwrap();
+        let run_id = receipt.meta.run_id;
+        journal.begin(run_id, Uuid::nil(), "mock").unwrap();
+        journal.finalize(&receipt).unwrap();
+
+        assert!(journal.pending().unwrap().is_empty());
+        assert!(journal.store().unwrap().verify(run_id).unwrap());
+    }
+
+    #[test]
+    fn torn_trailing_line_is_ignored() {
+        let tmp = tempfile::tempdir().unwrap();
+        let journal = ReceiptJournal::new(tmp.path());
+        let run_id = Uuid::new_v4();
+        journal.begin(run_id, Uuid::nil(), "mock").unwrap();
+        journal.record_event(run_id, &event("kept")).unwrap();
+        let path = tmp.path().join(format!("{run_id}.wal"));
+        let mut f = std::fs::OpenOptions::new().append(true).open(path).unwrap();
+        f.write_all(br#"{"kind":"event","event":{"ts""#).unwrap();
+
+        let recovered = journal.recover().unwrap();
+        assert_eq!(recovered[0].receipt().trace.len(), 2);
+    }
+
+    #[test]
+    fn recover_with_store_saves_interrupted_receipts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let journal = ReceiptJournal::new(tmp.path().join("wal"))
+            .with_store(ReceiptStore::new(tmp.path().join("receipts")));
+        let run_id = Uuid::new_v4();
+        journal.begin(run_id, Uuid::nil(), "mock").unwrap();
+
+        let recovered = journal.recover().unwrap();
+        assert!(matches!(recovered[0], RecoveredRun::Interrupted(_)));
+        assert!(journal.pending().unwrap().is_empty());
+        let stored = journal.store().unwrap().load(run_id).unwrap();
+        assert_eq!(stored.outcome, Outcome::Failed);
+    }
+
+    #[test]
+    fn missing_journal_dir_has_nothing_pending() {
+        let tmp = tempfile::tempdir().unwrap();
+        let journal = ReceiptJournal::new(tmp.path().join("absent"));
+        assert!(journal.pending().unwrap().is_empty());
+        assert!(journal.recover().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,453 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Post-run quality scoring by a judge backend.
+//!
+//! With [`Runtime::with_judge`](crate::Runtime::with_judge) the runtime sends
+//! the task and final assistant output of a completed run to a designated
+//! judge backend, together with a [`Rubric`](crate::judge::Rubric). The judge
+//! must answer with a JSON object such as
+//!
+//! ```json
+//! {"scores": {"correctness": 4, "completeness": 5, "clarity": 3},
+//!  "rationale": "Fixes the bug but leaves a stub in place."}
+//! ```
+//!
+//! and the runtime records a [`JudgeRecord`](crate::judge::JudgeRecord) under
+//! `usage_raw["judge"]` before the receipt is hashed. A judge that fails,
+//! times out, or answers malformed JSON is recorded as
+//! [`JudgeRecord::Failed`](crate::judge::JudgeRecord::Failed); the run itself
+//! is never failed by its judge.
+//!
+//! [`JudgeConfig::sample_rate`](crate::judge::JudgeConfig::sample_rate) picks
+//! which runs are scored, deterministically by run id, so high-volume traffic
+//! can be monitored at a fraction of the cost. Failed runs are not scored.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use abp_core::{AgentEvent, Outcome, Receipt, WorkOrderBuilder, WorkspaceMode};
+use abp_integrations::Backend;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::pipeline::final_assistant_text;
+
+/// Key under `receipt.usage_raw` holding the [`JudgeRecord`].
+pub const JUDGE_KEY: &str = "judge";
+
+/// One quality dimension the judge scores.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Criterion {
+    /// Key the judge reports the score under, e.g. `correctness`.
+    pub name: String,
+    /// What the judge should assess.
+    pub description: String,
+    /// Relative weight in the overall score.
+    pub weight: f64,
+}
+
+/// Criteria and score scale given to the judge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rubric {
+    /// Criteria scored independently.
+    pub criteria: Vec<Criterion>,
+    /// Highest score per criterion; scores range from 0 to this.
+    pub max_score: u32,
+}
+
+impl Default for Rubric {
+    /// Correctness, completeness, and clarity, each scored 0–5.
+    fn default() -> Self {
+        Self::new(5)
+            .criterion(
+                "correctness",
+                "Is the output factually and technically correct for the task?",
+            )
+            .criterion(
+                "completeness",
+                "Does the output address every part of the task?",
+            )
+            .criterion("clarity", "Is the output clear and well organized?")
+    }
+}
+
+impl Rubric {
+    /// An empty rubric scoring each criterion from 0 to `max_score`.
+    #[must_use]
+    pub fn new(max_score: u32) -> Self {
+        Self {
+            criteria: Vec::new(),
+            max_score: max_score.max(1),
+        }
+    }
+
+    /// Add a criterion with weight 1.
+    #[must_use]
+    pub fn criterion(self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        self.weighted_criterion(name, description, 1.0)
+    }
+
+    /// Add a criterion with an explicit weight.
+    #[must_use]
+    pub fn weighted_criterion(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        weight: f64,
+    ) -> Self {
+        self.criteria.push(Criterion {
+            name: name.into(),
+            description: description.into(),
+            weight: weight.max(0.0),
+        });
+        self
+    }
+
+    /// The prompt asking the judge to score `output` as an answer to `task`.
+    #[must_use]
+    pub fn prompt(&self, task: &str, output: &str) -> String {
+        let criteria: String = self
+            .criteria
+            .iter()
+            .map(|c| format!("- {}: {}\n", c.name, c.description))
+            .collect();
+        let example: Vec<String> = self
+            .criteria
+            .iter()
+            .map(|c| format!("\"{}\": <0-{}>", c.name, self.max_score))
+            .collect();
+        format!(
+            "You are grading an AI agent's response. Score it from 0 to {max} on each \
+             criterion below.\n\nCriteria:\n{criteria}\n<task>\n{task}\n</task>\n\n\
+             <response>\n{output}\n</response>\n\nAnswer with only a JSON object: \
+             {{\"scores\": {{{example}}}, \"rationale\": \"<one or two sentences>\"}}",
+            max = self.max_score,
+            example = example.join(", "),
+        )
+    }
+
+    /// Parse the judge's answer into a verdict.
+    ///
+    /// The first JSON object in `answer` is used, so code fences and
+    /// surrounding prose are tolerated.
+    ///
+    /// # Errors
+    ///
+    /// Fails when no JSON object is found or a criterion is missing or out
+    /// of range.
+    pub fn parse(&self, answer: &str) -> Result<Verdict, String> {
+        let start = answer
+            .find('{')
+            .ok_or("judge answer contains no JSON object")?;
+        let end = answer
+            .rfind('}')
+            .ok_or("judge answer contains no JSON object")?;
+        let value: serde_json::Value = serde_json::from_str(&answer[start..=end])
+            .map_err(|e| format!("judge answer is not valid JSON: {e}"))?;
+
+        let reported = value.get("scores").and_then(|s| s.as_object());
+        let mut scores = BTreeMap::new();
+        for c in &self.criteria {
+            let score = reported
+                .and_then(|s| s.get(&c.name))
+                .and_then(serde_json::Value::as_f64)
+                .ok_or_else(|| format!("judge omitted a score for `{}`", c.name))?;
+            if !(0.0..=f64::from(self.max_score)).contains(&score) {
+                return Err(format!(
+                    "score {score} for `{}` is outside 0..={}",
+                    c.name, self.max_score
+                ));
+            }
+            scores.insert(c.name.clone(), score);
+        }
+
+        Ok(Verdict {
+            overall: self.overall(&scores),
+            scores,
+            max_score: self.max_score,
+            rationale: value
+                .get("rationale")
+                .and_then(|r| r.as_str())
+                .map(str::to_string),
+        })
+    }
+
+    /// Weighted mean of `scores`, normalized to 0.0–1.0.
+    fn overall(&self, scores: &BTreeMap<String, f64>) -> f64 {
+        let (sum, weights) = self.criteria.iter().fold((0.0, 0.0), |(sum, weights), c| {
+            let score = scores.get(&c.name).copied().unwrap_or_default();
+            (sum + c.weight * score, weights + c.weight)
+        });
+        if weights == 0.0 {
+            return 0.0;
+        }
+        sum / (weights * f64::from(self.max_score))
+    }
+}
+
+/// Which backend judges runs, against what rubric, and how often.
+#[derive(Debug, Clone)]
+pub struct JudgeConfig {
+    /// Name of the registered judge backend.
+    pub backend: String,
+    /// Criteria and scale.
+    pub rubric: Rubric,
+    /// Fraction of runs scored, 0.0–1.0.
+    pub sample_rate: f64,
+    /// Bound on the judge's run.
+    pub timeout: Duration,
+}
+
+impl JudgeConfig {
+    /// Default bound on a judge run.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+    /// Score every run with the registered backend `backend` and the default
+    /// [`Rubric`].
+    #[must_use]
+    pub fn new(backend: impl Into<String>) -> Self {
+        Self {
+            backend: backend.into(),
+            rubric: Rubric::default(),
+            sample_rate: 1.0,
+            timeout: Self::DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Use `rubric` instead of the default.
+    #[must_use]
+    pub fn rubric(mut self, rubric: Rubric) -> Self {
+        self.rubric = rubric;
+        self
+    }
+
+    /// Score only this fraction of runs (clamped to 0.0–1.0).
+    #[must_use]
+    pub fn sample_rate(mut self, rate: f64) -> Self {
+        self.sample_rate = rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Bound each judge run by `timeout`.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Whether the run `run_id` falls in the sample.
+    ///
+    /// The decision depends only on the run id, so it is stable across
+    /// replays.
+    #[must_use]
+    pub fn samples(&self, run_id: Uuid) -> bool {
+        if self.sample_rate >= 1.0 {
+            return true;
+        }
+        // Mix all 128 bits (splitmix64 finalizer) so sequential or
+        // version-stamped ids still spread evenly.
+        let id = run_id.as_u128();
+        let mut x = (id >> 64) as u64 ^ id as u64;
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        x ^= x >> 31;
+        (x as f64 / u64::MAX as f64) < self.sample_rate
+    }
+}
+
+/// Scores the judge gave a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Verdict {
+    /// Score per criterion.
+    pub scores: BTreeMap<String, f64>,
+    /// Weighted mean, normalized to 0.0–1.0.
+    pub overall: f64,
+    /// Scale the scores were given on.
+    pub max_score: u32,
+    /// The judge's explanation, if it gave one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+}
+
+/// What the judge stage recorded for a run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum JudgeRecord {
+    /// The judge scored the run.
+    Scored {
+        /// Judge backend name.
+        backend: String,
+        /// Hash of the judge run's own receipt, for auditing the score.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        judge_receipt_sha256: Option<String>,
+        /// The scores.
+        #[serde(flatten)]
+        verdict: Verdict,
+    },
+    /// The judge could not score the run.
+    Failed {
+        /// Judge backend name.
+        backend: String,
+        /// Why scoring failed.
+        error: String,
+    },
+}
+
+impl JudgeRecord {
+    /// The record stored on `receipt`, if the run was judged.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        receipt
+            .usage_raw
+            .get(JUDGE_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// The verdict, when the judge scored the run.
+    #[must_use]
+    pub fn verdict(&self) -> Option<&Verdict> {
+        match self {
+            Self::Scored { verdict, .. } => Some(verdict),
+            Self::Failed { .. } => None,
+        }
+    }
+
+    /// Store the record under `usage_raw["judge"]`.
+    pub fn attach(&self, receipt: &mut Receipt) {
+        if let Ok(val) = serde_json::to_value(self)
+            && let Some(obj) = receipt.usage_raw.as_object_mut()
+        {
+            obj.insert(JUDGE_KEY.to_string(), val);
+        }
+    }
+}
+
+/// Score `receipt` for `task` with the judge backend, or `None` when the run
+/// is outside the sample or failed.
+///
+/// `backend` is `None` when the configured judge is not registered.
+pub(crate) async fn judge_run(
+    config: &JudgeConfig,
+    backend: Option<Arc<dyn Backend>>,
+    run_id: Uuid,
+    task: &str,
+    receipt: &Receipt,
+) -> Option<JudgeRecord> {
+    if receipt.outcome == Outcome::Failed || !config.samples(run_id) {
+        return None;
+    }
+    let failed = |error: String| JudgeRecord::Failed {
+        backend: config.backend.clone(),
+        error,
+    };
+    let Some(backend) = backend else {
+        return Some(failed(format!(
+            "judge backend `{}` is not registered",
+            config.backend
+        )));
+    };
+
+    let output = final_assistant_text(&receipt.trace).unwrap_or_default();
+    let work_order = WorkOrderBuilder::new(config.rubric.prompt(task, &output))
+        .root(".")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+
+    let (tx, mut rx) = mpsc::channel::<AgentEvent>(64);
+    let collect = async {
+        let mut events = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            events.push(ev);
+        }
+        events
+    };
+    let run = tokio::time::timeout(config.timeout, backend.run(Uuid::new_v4(), work_order, tx));
+    let (result, events) = tokio::join!(run, collect);
+
+    let judge_receipt = match result {
+        Ok(Ok(r)) => r,
+        Ok(Err(e)) => return Some(failed(format!("judge run failed: {e:#}"))),
+        Err(_) => {
+            return Some(failed(format!(
+                "judge timed out after {:?}",
+                config.timeout
+            )));
+        }
+    };
+    let answer = final_assistant_text(&events)
+        .or_else(|| final_assistant_text(&judge_receipt.trace))
+        .unwrap_or_default();
+
+    Some(match config.rubric.parse(&answer) {
+        Ok(verdict) => JudgeRecord::Scored {
+            backend: config.backend.clone(),
+            judge_receipt_sha256: judge_receipt.receipt_sha256,
+            verdict,
+        },
+        Err(e) => failed(e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_tolerates_fences_and_weights_the_overall() {
+        let rubric = Rubric::new(4)
+            .weighted_criterion("correctness", "right?", 3.0)
+            .criterion("style", "nice?");
+        let verdict = rubric
+            .parse("```json\n{\"scores\": {\"correctness\": 4, \"style\": 0}, \"rationale\": \"ok\"}\n```")
+            .unwrap();
+        assert_eq!(verdict.scores["correctness"], 4.0);
+        assert!((verdict.overall - 0.75).abs() < 1e-9);
+        assert_eq!(verdict.rationale.as_deref(), Some("ok"));
+    }
+
+    #[test]
+    fn parse_rejects_missing_and_out_of_range_scores() {
+        let rubric = Rubric::default();
+        assert!(
+            rubric
+                .parse(r#"{"scores": {"correctness": 5, "clarity": 5}}"#)
+                .unwrap_err()
+                .contains("completeness")
+        );
+        assert!(
+            rubric
+                .parse(r#"{"scores": {"correctness": 9, "completeness": 5, "clarity": 5}}"#)
+                .unwrap_err()
+                .contains("outside")
+        );
+        assert!(rubric.parse("no idea").is_err());
+    }
+
+    #[test]
+    fn sampling_is_stable_per_run_id() {
+        let ids: Vec<Uuid> = (0..200).map(|_| Uuid::new_v4()).collect();
+        let none = JudgeConfig::new("j").sample_rate(0.0);
+        assert!(ids.iter().all(|id| !none.samples(*id)));
+
+        let half = JudgeConfig::new("j").sample_rate(0.5);
+        let picked = ids.iter().filter(|id| half.samples(**id)).count();
+        assert!((50..150).contains(&picked), "{picked}");
+        assert!(ids.iter().all(|id| half.samples(*id) == half.samples(*id)));
+    }
+
+    #[test]
+    fn records_roundtrip_through_the_receipt() {
+        let mut receipt = abp_receipt::ReceiptBuilder::new("mock").build();
+        let record = JudgeRecord::Scored {
+            backend: "judge".into(),
+            judge_receipt_sha256: None,
+            verdict: Rubric::default()
+                .parse(r#"{"scores": {"correctness": 5, "completeness": 4, "clarity": 3}}"#)
+                .unwrap(),
+        };
+        record.attach(&mut receipt);
+        assert_eq!(receipt.usage_raw[JUDGE_KEY]["status"], "scored");
+        assert_eq!(JudgeRecord::from_receipt(&receipt), Some(record));
+    }
+}
@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Graceful degradation ladders for capability requirements.
+//!
+//! A work order can declare a [`CapabilityLadder`](abp_core::CapabilityLadder) (via
+//! [`WorkOrderBuilder::capability_ladder`](abp_core::WorkOrderBuilder::capability_ladder))
+//! instead of a single fixed requirement, e.g. native JSON-schema output, then
+//! native JSON mode, then prompt-emulated schema output. Before the pre-flight
+//! capability check the runtime picks the highest rung the backend supports,
+//! adds it to the work order's requirements, and records every pick under
+//! `usage_raw["capability_fallbacks"]` as
+//! [`RungSelection`](crate::ladder::RungSelection)s.
+
+use abp_core::{
+    Capability, CapabilityLadder, CapabilityManifest, MinSupport, Receipt, SupportLevel, WorkOrder,
+};
+use serde::{Deserialize, Serialize};
+
+/// Key under `config.vendor["abp"]` holding the work order's ladders.
+pub const LADDERS_VENDOR_KEY: &str = "capability_ladders";
+
+/// Key under `receipt.usage_raw` holding the [`RungSelection`]s.
+pub const FALLBACKS_KEY: &str = "capability_fallbacks";
+
+/// The capability ladders a work order declares.
+///
+/// Entries that do not parse as a [`CapabilityLadder`] are ignored.
+#[must_use]
+pub fn capability_ladders(work_order: &WorkOrder) -> Vec<CapabilityLadder> {
+    work_order
+        .config
+        .vendor
+        .get("abp")
+        .and_then(|abp| abp.get(LADDERS_VENDOR_KEY))
+        .and_then(serde_json::Value::as_array)
+        .map(|ladders| {
+            ladders
+                .iter()
+                .filter_map(|l| serde_json::from_value(l.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The rung chosen for one ladder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RungSelection {
+    /// Index of the ladder in the work order.
+    pub ladder: usize,
+    /// Index of the chosen rung; `0` means no degradation.
+    pub rung: usize,
+    /// Capability of the chosen rung.
+    pub capability: Capability,
+    /// Support level the rung required.
+    pub min_support: MinSupport,
+    /// Support level the backend declares for the capability.
+    pub support: SupportLevel,
+}
+
+impl RungSelection {
+    /// Whether the backend could not satisfy the top rung.
+    #[must_use]
+    pub fn is_degraded(&self) -> bool {
+        self.rung > 0
+    }
+
+    /// Read the rung selections of a receipt; empty if the work order
+    /// declared no ladders.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Vec<Self> {
+        receipt
+            .usage_raw
+            .get(FALLBACKS_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Pick the highest rung of `ladder` that `caps` satisfies.
+///
+/// A capability missing from the manifest counts as unsupported, so only a
+/// rung with [`MinSupport::Any`] matches it.
+#[must_use]
+pub fn select_rung(
+    index: usize,
+    ladder: &CapabilityLadder,
+    caps: &CapabilityManifest,
+) -> Option<RungSelection> {
+    ladder.rungs.iter().enumerate().find_map(|(rung, req)| {
+        let support = caps
+            .get(&req.capability)
+            .cloned()
+            .unwrap_or(SupportLevel::Unsupported);
+        support.satisfies(&req.min_support).then(|| RungSelection {
+            ladder: index,
+            rung,
+            capability: req.capability.clone(),
+            min_support: req.min_support.clone(),
+            support,
+        })
+    })
+}
+
+/// Resolve every ladder of `work_order` against `caps`, appending each chosen
+/// rung to the work order's requirements.
+///
+/// # Errors
+///
+/// Returns a description of the first ladder with no rung the backend
+/// satisfies.
+pub fn resolve(
+    work_order: &mut WorkOrder,
+    caps: &CapabilityManifest,
+) -> Result<Vec<RungSelection>, String> {
+    let mut selections = Vec::new();
+    for (index, ladder) in capability_ladders(work_order).iter().enumerate() {
+        let Some(selection) = select_rung(index, ladder, caps) else {
+            let rungs: Vec<String> = ladder
+                .rungs
+                .iter()
+                .map(|r| format!("{:?}", r.capability))
+                .collect();
+            return Err(format!(
+                "no rung of capability ladder {index} is supported ({})",
+                rungs.join(" -> ")
+            ));
+        };
+        work_order
+            .requirements
+            .required
+            .push(ladder.rungs[selection.rung].clone());
+        selections.push(selection);
+    }
+    Ok(selections)
+}
+
+/// Store `selections` under `usage_raw["capability_fallbacks"]`.
+pub fn record(receipt: &mut Receipt, selections: &[RungSelection]) {
+    if selections.is_empty() {
+        return;
+    }
+    if let Some(obj) = receipt.usage_raw.as_object_mut()
+        && let Ok(value) = serde_json::to_value(selections)
+    {
+        obj.insert(FALLBACKS_KEY.to_string(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::WorkOrderBuilder;
+
+    fn structured_output() -> CapabilityLadder {
+        CapabilityLadder::new()
+            .rung(Capability::StructuredOutputJsonSchema, MinSupport::Native)
+            .rung(Capability::JsonMode, MinSupport::Native)
+            .rung(Capability::StructuredOutputJsonSchema, MinSupport::Emulated)
+    }
+
+    fn manifest(entries: &[(Capability, SupportLevel)]) -> CapabilityManifest {
+        entries.iter().cloned().collect()
+    }
+
+    #[test]
+    fn native_backend_gets_top_rung() {
+        let caps = manifest(&[(Capability::StructuredOutputJsonSchema, SupportLevel::Native)]);
+        let sel = select_rung(0, &structured_output(), &caps).unwrap();
+        assert_eq!(sel.rung, 0);
+        assert!(!sel.is_degraded());
+    }
+
+    #[test]
+    fn json_mode_backend_degrades_one_rung() {
+        let caps = manifest(&[
+            (
+                Capability::StructuredOutputJsonSchema,
+                SupportLevel::Emulated,
+            ),
+            (Capability::JsonMode, SupportLevel::Native),
+        ]);
+        let sel = select_rung(0, &structured_output(), &caps).unwrap();
+        assert_eq!(sel.rung, 1);
+        assert_eq!(sel.capability, Capability::JsonMode);
+    }
+
+    #[test]
+    fn resolve_adds_chosen_rung_to_requirements() {
+        let mut wo = WorkOrderBuilder::new("t")
+            .capability_ladder(structured_output())
+            .build();
+        let caps = manifest(&[(
+            Capability::StructuredOutputJsonSchema,
+            SupportLevel::Emulated,
+        )]);
+        let selections = resolve(&mut wo, &caps).unwrap();
+        assert_eq!(selections[0].rung, 2);
+        assert_eq!(wo.requirements.required.len(), 1);
+        assert!(matches!(
+            wo.requirements.required[0].min_support,
+            MinSupport::Emulated
+        ));
+    }
+
+    #[test]
+    fn unsatisfiable_ladder_is_an_error() {
+        let mut wo = WorkOrderBuilder::new("t")
+            .capability_ladder(structured_output())
+            .build();
+        let err = resolve(&mut wo, &CapabilityManifest::new()).unwrap_err();
+        assert!(err.contains("ladder 0"));
+        assert!(wo.requirements.required.is_empty());
+    }
+}
@@ -43,50 +43,131 @@
 #![deny(unsafe_code)]
 #![warn(missing_docs)]
 
+/// The runtime surface application code depends on.
+pub mod api;
+/// Assembly of artifacts streamed by backends in chunks.
+pub mod artifact_stream;
+/// Collection and hashing of files a run leaves in its workspace.
+pub mod artifacts;
+/// Append-only, hash-chained audit log of operational actions.
+pub mod audit;
+/// Concurrent execution of a batch of work orders.
+pub mod batch;
+/// Adaptive batching of assistant deltas for slow event consumers.
+pub mod batching;
+/// Per-backend circuit breakers that take failing backends out of rotation.
+pub mod breaker;
 /// Budget enforcement for runtime runs.
 pub mod budget;
 /// Broadcast-based event bus for decoupled event distribution.
 pub mod bus;
+/// Reuse of prior receipts for unchanged work orders and workspaces.
+pub mod cache;
 /// Cancellation primitives for runtime runs.
 pub mod cancel;
 /// Runtime configuration integration (backend selection, telemetry, workspace).
 pub mod config_integration;
 /// Retry-and-fallback execution pipeline (parallel path to [`Runtime::run_streaming`]).
 pub mod execution;
+/// Export of run events and lifecycle notifications to Kafka, NATS, and
+/// other brokers.
+pub mod export;
+/// In-memory test double for the runtime.
+pub mod fake;
+/// Work-order fidelity policy (strict / warn / permissive) for lossy mappings.
+pub mod fidelity;
+/// Feature flags for experimental runtime behaviour.
+pub mod flags;
+/// Run outcomes posted to GitHub as commit statuses or check runs.
+pub mod github;
+/// Typed callbacks (`on_text`, `on_tool_call`, …) over a run's event stream.
+pub mod handlers;
 /// Lifecycle hooks for runtime extensibility.
 pub mod hooks;
+/// Write-ahead journal for crash-consistent receipt finalization.
+pub mod journal;
+/// Post-run quality scoring by a judge backend.
+pub mod judge;
+/// Graceful degradation ladders for capability requirements.
+pub mod ladder;
 /// Middleware pattern for pre/post run hooks.
 pub mod middleware;
+/// Models backends advertise at runtime.
+pub mod models;
 /// Event multiplexing and routing for broadcasting agent events.
 pub mod multiplex;
 /// Combined capability negotiation result for the runtime pipeline.
 pub mod negotiate;
 /// Observability primitives: tracing spans and runtime observer.
 pub mod observe;
+/// OpenTelemetry (OTLP) export of receipt traces.
+pub mod otel;
+/// Periodic partial receipts for runs still in progress.
+pub mod partial;
+/// Raw vendor payload passthrough for same-dialect routing.
+pub mod passthrough;
 /// Processing pipeline for work order pre-processing.
 pub mod pipeline;
+/// Per-model token prices and receipt cost estimation.
+pub mod pricing;
+/// Progress events and idle heartbeats for long-running runs.
+pub mod progress;
+/// Work order queue with priorities, not-before times, and per-backend caps.
+pub mod queue;
+/// Calendar-window token and spend quotas per tenant or API key.
+pub mod quota;
+/// Per-backend requests-per-minute and tokens-per-minute limits.
+pub mod rate_limit;
+/// Role-based access control for runtime operations.
+pub mod rbac;
 /// Backend registry for named backend lookup.
 pub mod registry;
+/// Hot reload of backends declared in a config file.
+pub mod reload;
+/// Deterministic replay of seeded runs and recorded receipts.
+pub mod replay;
 /// Retry policies and timeout configuration for resilient backend execution.
 pub mod retry;
+/// Conversation sessions with fork lineage recorded in receipts.
+pub mod session;
 /// Additional built-in pipeline stages, builder, and execution helpers.
 pub mod stages;
 /// Receipt persistence and retrieval.
 pub mod store;
 /// Stream pipeline integration for event filtering, transformation, and recording.
 pub mod stream;
+/// Run summary events at the end of the event stream.
+pub mod summary;
 /// Telemetry and metrics collection.
 pub mod telemetry;
-
-use abp_core::{AgentEvent, CapabilityRequirements, Outcome, Receipt, WorkOrder};
+/// Tool-use loop emulation for single-shot backends.
+pub mod tool_loop;
+/// Host tools registered as Rust handlers with JSON Schema inputs.
+pub mod tool_registry;
+/// Runtime-executed tools with parallel, barrier-synchronised dispatch.
+pub mod tools;
+
+use abp_capability::models::ModelCatalog;
+use abp_core::clock::{Clock, SharedClock, system_clock};
+use abp_core::ids::{SharedIdGenerator, default_id_generator};
+use abp_core::{
+    AgentEvent, AgentEventKind, Capability, CapabilityManifest, CapabilityRequirements,
+    ExecutionMode, Outcome, Receipt, UsageNormalized, WorkOrder,
+};
 use abp_dialect::Dialect;
-use abp_emulation::{EmulationConfig, EmulationEngine, EmulationReport};
+use abp_emulation::response_language::ResponseLanguageEmulator;
+use abp_emulation::structured_output::StructuredOutputEmulator;
+use abp_emulation::{EmulationConfig, EmulationEngine, EmulationEntry, EmulationReport};
 use abp_integrations::{Backend, ensure_capability_requirements};
 use abp_policy::PolicyEngine;
-use abp_projection::translate::{TranslationEngine, TranslationMode, TranslationResult};
+use abp_projection::translate::{
+    TranslationEngine, TranslationMode, TranslationResult, audit_tools,
+};
 use abp_receipt::{ReceiptBuilder, ReceiptChain};
 use abp_workspace::WorkspaceManager;
+use abp_workspace::quota::WorkspaceQuota;
 use anyhow::Context;
+use journal::ReceiptJournal;
 use middleware::{MiddlewareChain, MiddlewareContext};
 use std::sync::Arc;
 use telemetry::RunMetrics;
@@ -197,6 +278,22 @@ impl RuntimeError {
             | Self::NoProjectionMatch { .. } => false,
         }
     }
+
+    /// A failure in the runtime's own code, such as hashing a receipt or a
+    /// run task that panicked.
+    ///
+    /// It is classified as [`Internal`](abp_error::ErrorCode::Internal) so
+    /// that it is neither retried, nor fallen back from, nor counted
+    /// against the backend's circuit breaker.
+    pub(crate) fn internal(
+        context: &str,
+        err: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Self::Classified(
+            abp_error::AbpError::new(abp_error::ErrorCode::Internal, format!("{context}: {err}"))
+                .with_source(err),
+        )
+    }
 }
 
 /// Central orchestrator that holds registered backends and executes work orders.
@@ -207,14 +304,42 @@ impl RuntimeError {
 /// // rt.register_backend("sidecar:node", my_sidecar);
 /// ```
 pub struct Runtime {
-    backends: BackendRegistry,
+    backends: std::sync::RwLock<BackendRegistry>,
     metrics: Arc<RunMetrics>,
     emulation: Option<EmulationConfig>,
     receipt_chain: Arc<Mutex<ReceiptChain>>,
-    projection: Option<ProjectionMatrix>,
+    projection: std::sync::RwLock<Option<ProjectionMatrix>>,
     stream_pipeline: Option<abp_stream::StreamPipeline>,
     translation_engine: Arc<TranslationEngine>,
     middleware: Arc<MiddlewareChain>,
+    model_catalog: Option<Arc<ModelCatalog>>,
+    journal: Option<Arc<ReceiptJournal>>,
+    clock: SharedClock,
+    ids: SharedIdGenerator,
+    workspace_quota: Option<WorkspaceQuota>,
+    quotas: Option<Arc<quota::QuotaEnforcer>>,
+    idle_progress: Option<std::time::Duration>,
+    run_summary: bool,
+    partial_receipts: Option<Arc<partial::PartialReceiptFeed>>,
+    event_export: Option<export::EventExporter>,
+    delta_batching: Option<batching::DeltaBatching>,
+    backend_retry: std::collections::BTreeMap<String, retry::BackendRetryConfig>,
+    rate_limits: Arc<rate_limit::RateLimiter>,
+    breakers: Option<Arc<breaker::CircuitBreakers>>,
+    audit: Option<Arc<audit::AuditLog>>,
+    receipt_store: Option<Arc<dyn abp_receipt_store::ReceiptStore>>,
+    rbac: Option<Arc<rbac::RbacConfig>>,
+    features: flags::FeatureFlags,
+    models: Arc<models::ModelDirectory>,
+    bus: Arc<bus::EventBus>,
+    tools: Option<Arc<tools::ToolDispatcher>>,
+    tool_registry: Option<Arc<tool_registry::ToolRegistry>>,
+    builtin_tools: bool,
+    artifacts: Option<artifacts::ArtifactCollector>,
+    artifact_streams: artifact_stream::ArtifactStreams,
+    judge: Option<judge::JudgeConfig>,
+    cache: Option<Arc<cache::RunCache>>,
+    pricing: Arc<pricing::PricingTable>,
 }
 
 /// Handle to a running work order: provides a run id, event stream, and receipt future.
@@ -238,14 +363,42 @@ impl Runtime {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            backends: BackendRegistry::default(),
+            backends: std::sync::RwLock::new(BackendRegistry::default()),
             metrics: Arc::new(RunMetrics::new()),
             emulation: None,
             receipt_chain: Arc::new(Mutex::new(ReceiptChain::new())),
-            projection: None,
+            projection: std::sync::RwLock::new(None),
             stream_pipeline: None,
             translation_engine: Arc::new(TranslationEngine::with_defaults()),
             middleware: Arc::new(MiddlewareChain::new()),
+            model_catalog: None,
+            journal: None,
+            clock: system_clock(),
+            ids: default_id_generator(),
+            workspace_quota: None,
+            quotas: None,
+            idle_progress: None,
+            run_summary: false,
+            partial_receipts: None,
+            event_export: None,
+            delta_batching: None,
+            backend_retry: std::collections::BTreeMap::new(),
+            rate_limits: Arc::default(),
+            breakers: None,
+            audit: None,
+            receipt_store: None,
+            rbac: None,
+            features: flags::FeatureFlags::new(),
+            models: Arc::new(models::ModelDirectory::new()),
+            bus: Arc::new(bus::EventBus::new()),
+            tools: None,
+            tool_registry: None,
+            builtin_tools: false,
+            artifacts: None,
+            artifact_streams: artifact_stream::ArtifactStreams::new(),
+            judge: None,
+            cache: None,
+            pricing: Arc::new(pricing::PricingTable::with_defaults()),
         }
     }
 
@@ -259,30 +412,97 @@ impl Runtime {
 
     /// Register a backend under the given name, replacing any previous registration.
     pub fn register_backend<B: Backend + 'static>(&mut self, name: &str, backend: B) {
-        self.backends.register(name, backend);
+        self.attach_backend(name, backend);
+    }
+
+    /// Register a backend on a runtime that may already be serving runs,
+    /// replacing any previous registration, and announce it on the
+    /// [`event_bus`](Self::event_bus).
+    pub fn attach_backend<B: Backend + 'static>(&self, name: &str, backend: B) {
+        self.backends
+            .write()
+            .expect("backend registry lock poisoned")
+            .register(name, backend);
+        debug!(target: "abp.runtime", backend=%name, "backend registered");
+        self.bus.publish(
+            bus::RegistryChange::Registered {
+                backend: name.to_string(),
+            }
+            .to_event(self.clock.now()),
+        );
+    }
+
+    /// Deregister a backend on a running runtime and announce it on the
+    /// [`event_bus`](Self::event_bus).
+    ///
+    /// New runs can no longer select it; runs already started keep their
+    /// handle to the backend and finish normally. Its advertised model
+    /// listing is dropped. Returns `false` if no such backend was registered.
+    pub fn detach_backend(&self, name: &str) -> bool {
+        let removed = self
+            .backends
+            .write()
+            .expect("backend registry lock poisoned")
+            .remove(name)
+            .is_some();
+        if removed {
+            self.models.remove(name);
+            debug!(target: "abp.runtime", backend=%name, "backend deregistered");
+            self.bus.publish(
+                bus::RegistryChange::Deregistered {
+                    backend: name.to_string(),
+                }
+                .to_event(self.clock.now()),
+            );
+        }
+        removed
     }
 
     /// Return a sorted list of all registered backend names.
     #[must_use]
     pub fn backend_names(&self) -> Vec<String> {
-        self.backends.list().into_iter().map(String::from).collect()
+        self.registry()
+            .list()
+            .into_iter()
+            .map(String::from)
+            .collect()
     }
 
     /// Look up a backend by name.
     #[must_use]
     pub fn backend(&self, name: &str) -> Option<Arc<dyn Backend>> {
-        self.backends.get_arc(name)
+        self.registry().get_arc(name)
     }
 
-    /// Return a reference to the underlying [`BackendRegistry`].
-    #[must_use]
-    pub fn registry(&self) -> &BackendRegistry {
-        &self.backends
+    /// Read access to the underlying [`BackendRegistry`].
+    ///
+    /// Hold the guard briefly: [`attach_backend`](Self::attach_backend) and
+    /// [`detach_backend`](Self::detach_backend) wait for it.
+    pub fn registry(&self) -> std::sync::RwLockReadGuard<'_, BackendRegistry> {
+        self.backends
+            .read()
+            .expect("backend registry lock poisoned")
     }
 
     /// Return a mutable reference to the underlying [`BackendRegistry`].
     pub fn registry_mut(&mut self) -> &mut BackendRegistry {
-        &mut self.backends
+        self.backends
+            .get_mut()
+            .expect("backend registry lock poisoned")
+    }
+
+    /// Share an [`EventBus`](bus::EventBus) for registry-change announcements
+    /// (builder pattern).
+    #[must_use]
+    pub fn with_event_bus(mut self, bus: Arc<bus::EventBus>) -> Self {
+        self.bus = bus;
+        self
+    }
+
+    /// The bus on which backend registry changes are announced.
+    #[must_use]
+    pub fn event_bus(&self) -> &Arc<bus::EventBus> {
+        &self.bus
     }
 
     /// Return a reference to the shared [`RunMetrics`] collector.
@@ -296,6 +516,10 @@ impl Runtime {
     /// When emulation is enabled and a backend is missing required capabilities,
     /// the runtime will check if the missing capabilities can be emulated. If so,
     /// the run proceeds and the emulation report is recorded in the receipt.
+    ///
+    /// Emulated `StructuredOutputJsonSchema` with a `response_format` in the
+    /// vendor config appends the schema to the task and parses the final
+    /// reply; the recovered value and fidelity notes are added to the report.
     #[must_use]
     pub fn with_emulation(mut self, config: EmulationConfig) -> Self {
         self.emulation = Some(config);
@@ -315,19 +539,54 @@ impl Runtime {
     /// execute a work order against the best-fit backend automatically.
     #[must_use]
     pub fn with_projection(mut self, matrix: ProjectionMatrix) -> Self {
-        self.projection = Some(matrix);
+        *self.projection_slot() = Some(matrix);
         self
     }
 
-    /// Return the current projection matrix, if any.
-    #[must_use]
-    pub fn projection(&self) -> Option<&ProjectionMatrix> {
-        self.projection.as_ref()
+    /// Read access to the current projection matrix, if any.
+    ///
+    /// Hold the guard briefly: [`reload_backends`](Self::reload_backends)
+    /// waits for it.
+    pub fn projection(&self) -> std::sync::RwLockReadGuard<'_, Option<ProjectionMatrix>> {
+        self.projection
+            .read()
+            .expect("projection matrix lock poisoned")
     }
 
     /// Return a mutable reference to the projection matrix, if any.
     pub fn projection_mut(&mut self) -> Option<&mut ProjectionMatrix> {
-        self.projection.as_mut()
+        self.projection_slot().as_mut()
+    }
+
+    fn projection_slot(&mut self) -> &mut Option<ProjectionMatrix> {
+        self.projection
+            .get_mut()
+            .expect("projection matrix lock poisoned")
+    }
+
+    /// Register the backends declared in the TOML file at `path`.
+    ///
+    /// Each backend is registered as with [`register_backend`](Self::register_backend),
+    /// and each one that declares a `dialect` is also added to the projection
+    /// matrix (starting from [`ProjectionMatrix::with_defaults`] if none is set)
+    /// with its effective capabilities and priority. See [`registry`] for the
+    /// file format. Returns the registered names in sorted order.
+    ///
+    /// To pick up later edits to the file, use a
+    /// [`BackendWatcher`](reload::BackendWatcher) instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigError`](abp_config::ConfigError) without registering
+    /// anything if the file cannot be loaded or declares an invalid backend.
+    pub fn register_backends_from_config(
+        &mut self,
+        path: &std::path::Path,
+    ) -> Result<Vec<String>, abp_config::ConfigError> {
+        let decls = registry::BackendDecls::load(path)?;
+        Ok(self
+            .reload_backends(&registry::BackendDecls::default(), &decls)
+            .added)
     }
 
     /// Attach a [`StreamPipeline`](abp_stream::StreamPipeline) that every event
@@ -378,338 +637,1926 @@ impl Runtime {
         &self.middleware
     }
 
-    /// Use the projection matrix to select the best backend for a work order.
+    /// Attach a [`ModelCatalog`] used to refine backend manifests per model
+    /// (builder pattern).
     ///
-    /// Returns a [`ProjectionResult`] containing the selected backend name,
-    /// its composite score, any required emulations, and a fallback chain.
+    /// When the work order names a model (`config.model`) that the catalog
+    /// knows, negotiation runs against the model-specific manifest instead of
+    /// the backend-wide one.
+    #[must_use]
+    pub fn with_model_catalog(mut self, catalog: ModelCatalog) -> Self {
+        self.model_catalog = Some(Arc::new(catalog));
+        self
+    }
+
+    /// Return the attached model catalog, if any.
+    #[must_use]
+    pub fn model_catalog(&self) -> Option<&ModelCatalog> {
+        self.model_catalog.as_deref()
+    }
+
+    /// Attach a [`ReceiptJournal`] so receipts survive a host crash between
+    /// backend completion and persistence (builder pattern).
     ///
-    /// # Errors
+    /// Each run is journalled from backend start until its receipt is
+    /// finalized. Call [`ReceiptJournal::recover`] at startup to finalize or
+    /// mark as interrupted any runs a previous process left behind.
+    #[must_use]
+    pub fn with_journal(mut self, journal: ReceiptJournal) -> Self {
+        self.journal = Some(Arc::new(journal));
+        self
+    }
+
+    /// Return the attached receipt journal, if any.
+    #[must_use]
+    pub fn journal(&self) -> Option<&ReceiptJournal> {
+        self.journal.as_deref()
+    }
+
+    /// Read time from `clock` instead of the system clock (builder pattern).
     ///
-    /// Returns [`RuntimeError::NoProjectionMatch`] if the projection matrix
-    /// is not configured or no backend satisfies the work order.
-    /// Returns [`RuntimeError::UnknownBackend`] if the selected backend is
-    /// not registered in the runtime's [`BackendRegistry`].
-    pub fn select_backend(&self, work_order: &WorkOrder) -> Result<ProjectionResult, RuntimeError> {
-        let matrix = self
-            .projection
-            .as_ref()
-            .ok_or_else(|| RuntimeError::NoProjectionMatch {
-                reason: "no projection matrix configured".into(),
-            })?;
+    /// The runtime uses it for run durations, timestamps on receipts it
+    /// builds itself, and retry back-off in the
+    /// [`execution`] pipeline.
+    #[must_use]
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
 
-        let result = matrix
-            .project(work_order)
-            .map_err(|e| RuntimeError::NoProjectionMatch {
-                reason: e.to_string(),
-            })?;
+    /// Return the runtime's time source.
+    #[must_use]
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
 
-        // Verify the selected backend is actually registered in the runtime.
-        if !self.backends.contains(&result.selected_backend) {
-            return Err(RuntimeError::UnknownBackend {
-                name: result.selected_backend.clone(),
-            });
-        }
+    /// Draw run ids from `ids` instead of random v4 UUIDs (builder pattern).
+    ///
+    /// Use a [`UlidGenerator`](abp_core::ids::UlidGenerator) for run ids that
+    /// sort by start time, or a
+    /// [`SeededIdGenerator`](abp_core::ids::SeededIdGenerator) so repeated
+    /// runs of a golden test or replay get identical ids.
+    #[must_use]
+    pub fn with_id_generator(mut self, ids: SharedIdGenerator) -> Self {
+        self.ids = ids;
+        self
+    }
 
-        Ok(result)
+    /// Return the runtime's identifier generator.
+    #[must_use]
+    pub fn id_generator(&self) -> &SharedIdGenerator {
+        &self.ids
     }
 
-    /// Execute a work order using the projection matrix to select the best backend.
+    /// Limit the disk usage of each run's staged workspace (builder pattern).
     ///
-    /// This is a convenience wrapper around [`select_backend`](Self::select_backend)
-    /// followed by [`run_streaming`](Self::run_streaming). The projection result
-    /// metadata is recorded in the receipt's `usage_raw`.
+    /// The workspace is measured every [`QUOTA_CHECK_INTERVAL`] while the
+    /// backend runs and once more when it finishes. A run that exceeds the
+    /// quota is aborted with
+    /// [`ErrorCode::ExecutionWorkspaceError`](abp_error::ErrorCode::ExecutionWorkspaceError).
+    /// Pass-through workspaces are not limited.
+    #[must_use]
+    pub fn with_workspace_quota(mut self, quota: WorkspaceQuota) -> Self {
+        self.workspace_quota = Some(quota);
+        self
+    }
+
+    /// Return the per-run workspace quota, if any.
+    #[must_use]
+    pub fn workspace_quota(&self) -> Option<&WorkspaceQuota> {
+        self.workspace_quota.as_ref()
+    }
+
+    /// Enforce daily or monthly usage quotas per tenant or API key (builder
+    /// pattern).
     ///
-    /// # Errors
+    /// Before dispatch, a work order is rejected with
+    /// [`ErrorCode::QuotaExceeded`](abp_error::ErrorCode::QuotaExceeded) if
+    /// the tenant or API key its [`Submitter`](abp_core::submitter::Submitter)
+    /// names has used up a quota in the current window, or would with the
+    /// run's projected input. Otherwise that input is reserved until the
+    /// receipt's usage replaces it. Call
+    /// [`QuotaEnforcer::restore_from`](quota::QuotaEnforcer::restore_from)
+    /// first to carry usage over from a previous process.
+    #[must_use]
+    pub fn with_quotas(mut self, quotas: quota::QuotaEnforcer) -> Self {
+        self.quotas = Some(Arc::new(quotas));
+        self
+    }
+
+    /// Return the attached quota enforcer, if any.
+    #[must_use]
+    pub fn quotas(&self) -> Option<&quota::QuotaEnforcer> {
+        self.quotas.as_deref()
+    }
+
+    /// Record every accepted work order in an [`AuditLog`](audit::AuditLog)
+    /// (builder pattern).
     ///
-    /// Returns [`RuntimeError::NoProjectionMatch`] if no suitable backend is found,
-    /// or any error that [`run_streaming`](Self::run_streaming) can return.
-    pub async fn run_projected(&self, work_order: WorkOrder) -> Result<RunHandle, RuntimeError> {
-        let projection_result = self.select_backend(&work_order)?;
+    /// Once a work order passes pre-dispatch checks, the runtime appends a
+    /// `work_order_submitted` entry attributed to its submitter's API key (or
+    /// tenant), plus a `key_used` entry when an API key is named.
+    /// Callers record cancellations, approvals, and config changes through
+    /// [`audit_log`](Self::audit_log).
+    #[must_use]
+    pub fn with_audit_log(mut self, log: audit::AuditLog) -> Self {
+        self.audit = Some(Arc::new(log));
+        self
+    }
 
-        info!(
-            target: "abp.runtime",
-            selected = %projection_result.selected_backend,
-            score = %projection_result.fidelity_score.total,
-            fallbacks = projection_result.fallback_chain.len(),
-            "projection selected backend"
-        );
+    /// Return the attached audit log, if any.
+    #[must_use]
+    pub fn audit_log(&self) -> Option<&audit::AuditLog> {
+        self.audit.as_deref()
+    }
 
-        self.run_streaming(&projection_result.selected_backend, work_order)
-            .await
+    /// Persist every finished receipt to `store` (builder pattern).
+    ///
+    /// After a run's receipt is hashed and journaled, the runtime writes it
+    /// to the store before resolving [`RunHandle::receipt`]. A failed write
+    /// is logged and does not fail the run. Any
+    /// [`abp_receipt_store::ReceiptStore`] works, e.g.
+    /// [`SqliteReceiptStore`](abp_receipt_store::SqliteReceiptStore) or
+    /// [`JsonlDirReceiptStore`](abp_receipt_store::JsonlDirReceiptStore).
+    #[must_use]
+    pub fn with_receipt_store(mut self, store: Arc<dyn abp_receipt_store::ReceiptStore>) -> Self {
+        self.receipt_store = Some(store);
+        self
     }
 
-    /// Return a reference to the shared receipt chain.
+    /// Return the persistent receipt store, if any.
+    #[must_use]
+    pub fn receipt_store(&self) -> Option<&Arc<dyn abp_receipt_store::ReceiptStore>> {
+        self.receipt_store.as_ref()
+    }
+
+    /// Restrict runtime operations by caller role (builder pattern).
     ///
-    /// The chain accumulates receipts from successive [`run_streaming`](Self::run_streaming)
-    /// calls, enabling multi-step receipt verification and diffing.
+    /// The runtime itself does not identify callers; front ends call
+    /// [`authorize`](Self::authorize) with the caller's identity before each
+    /// operation. Without this, every caller is allowed everything.
     #[must_use]
-    pub fn receipt_chain(&self) -> Arc<Mutex<ReceiptChain>> {
-        Arc::clone(&self.receipt_chain)
+    pub fn with_rbac(mut self, rbac: rbac::RbacConfig) -> Self {
+        self.rbac = Some(Arc::new(rbac));
+        self
     }
 
-    /// Check whether a backend's capabilities satisfy the given requirements.
+    /// Return the access control configuration, if any.
+    #[must_use]
+    pub fn rbac(&self) -> Option<&rbac::RbacConfig> {
+        self.rbac.as_deref()
+    }
+
+    /// Declare experimental feature flags (builder pattern).
     ///
-    /// For sidecar backends whose capabilities come from handshake, this will
-    /// check against the (empty) default manifest — use the in-backend check
-    /// for authoritative validation.
+    /// Each run resolves the flags against its work order's overrides and
+    /// records the result in the receipt.
+    #[must_use]
+    pub fn with_feature_flags(mut self, features: flags::FeatureFlags) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Return the declared feature flags.
+    #[must_use]
+    pub fn feature_flags(&self) -> &flags::FeatureFlags {
+        &self.features
+    }
+
+    /// Models the registered backends advertised at the last refresh.
+    #[must_use]
+    pub fn models(&self) -> &models::ModelDirectory {
+        &self.models
+    }
+
+    /// Sorted names of the backends currently advertising `model`.
+    #[must_use]
+    pub fn backends_serving(&self, model: &str) -> Vec<String> {
+        self.models.backends_serving(model)
+    }
+
+    /// Ask every registered backend which models it serves and store the
+    /// answers in [`models()`](Self::models).
     ///
-    /// # Errors
+    /// A backend whose listing fails keeps its previous listing.
+    pub async fn refresh_models(&self) {
+        for name in self.backend_names() {
+            let Some(backend) = self.backend(&name) else {
+                continue;
+            };
+            match backend.list_models().await {
+                Ok(listed) => {
+                    debug!(target: "abp.runtime", backend=%name, models=listed.len(), "refreshed model listing");
+                    self.models.update(&name, listed, self.clock.now());
+                }
+                Err(e) => {
+                    warn!(target: "abp.runtime", backend=%name, error=%e, "failed to list backend models");
+                }
+            }
+        }
+    }
+
+    /// Refresh the model listings every `interval` in a background task.
     ///
-    /// Returns [`RuntimeError::UnknownBackend`] if the backend is not registered,
-    /// or [`RuntimeError::CapabilityCheckFailed`] if requirements are not met.
-    pub fn check_capabilities(
-        &self,
-        backend_name: &str,
-        requirements: &CapabilityRequirements,
-    ) -> Result<(), RuntimeError> {
-        let backend = self
-            .backend(backend_name)
-            .ok_or_else(|| RuntimeError::UnknownBackend {
-                name: backend_name.to_string(),
-            })?;
-        ensure_capability_requirements(requirements, &backend.capabilities()).map_err(|e| {
-            RuntimeError::CapabilityCheckFailed(format!("backend '{backend_name}': {e}"))
-        })?;
-        Ok(())
+    /// The first refresh happens immediately. The task stops once the
+    /// runtime is dropped, or when the returned handle is aborted.
+    pub fn spawn_model_refresh(
+        self: &Arc<Self>,
+        interval: std::time::Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        let runtime = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(interval);
+            loop {
+                tick.tick().await;
+                let Some(runtime) = runtime.upgrade() else {
+                    break;
+                };
+                runtime.refresh_models().await;
+            }
+        })
     }
 
-    /// Execute a work order against the named backend, returning a [`RunHandle`].
+    /// Check whether `identity` may perform `permission` on `resource`.
     ///
-    /// The handle provides a streaming event channel and a receipt future.
-    /// The runtime prepares the workspace, compiles the policy, and attaches
-    /// verification metadata and receipt hash after the backend finishes.
+    /// When RBAC is configured and an audit log is attached, the decision is
+    /// recorded as an `access_checked` entry attributed to `identity`.
     ///
     /// # Errors
     ///
-    /// Returns [`RuntimeError::UnknownBackend`] if the named backend is not
-    /// registered, or [`RuntimeError::CapabilityCheckFailed`] if pre-flight
-    /// capability checks fail.
-    pub async fn run_streaming(
+    /// Returns [`AccessDenied`](rbac::AccessDenied) if the caller's roles do
+    /// not grant `permission`.
+    pub fn authorize(
         &self,
-        backend_name: &str,
-        work_order: WorkOrder,
-    ) -> Result<RunHandle, RuntimeError> {
-        let backend = self.backend(backend_name).ok_or_else(|| {
-            warn!(target: "abp.runtime", name = %backend_name, "unknown backend");
-            RuntimeError::UnknownBackend {
-                name: backend_name.to_string(),
-            }
-        })?;
+        identity: Option<&str>,
+        permission: rbac::Permission,
+        resource: &str,
+    ) -> Result<(), rbac::AccessDenied> {
+        let decision = rbac::check(self.rbac.as_deref(), identity, permission, resource);
+        if self.rbac.is_some()
+            && let Some(log) = &self.audit
+            && let Err(e) = log.record(
+                identity,
+                audit::AuditAction::AccessChecked {
+                    permission,
+                    resource: resource.to_string(),
+                    allowed: decision.is_ok(),
+                },
+            )
+        {
+            warn!(target: "abp.runtime", error=%e, "failed to append audit log entry");
+        }
+        decision
+    }
 
-        // Pre-flight capability check: skip for sidecar backends whose
-        // capabilities are only known after handshake (empty default manifest).
-        let caps = backend.capabilities();
-        let emulation_report: Option<EmulationReport> = if !caps.is_empty() {
-            match ensure_capability_requirements(&work_order.requirements, &caps) {
-                Ok(()) => None,
-                Err(e) => {
-                    // Capabilities unsatisfied — try emulation if configured.
-                    if let Some(emu_config) = &self.emulation {
-                        let missing: Vec<_> = work_order
-                            .requirements
-                            .required
-                            .iter()
-                            .filter(|req| !caps.contains_key(&req.capability))
-                            .map(|req| req.capability.clone())
-                            .collect();
+    /// Emit a heartbeat progress event whenever a backend has been silent for
+    /// `interval` (builder pattern).
+    ///
+    /// Heartbeats go to the caller's event stream only, not to the receipt
+    /// trace; see [`progress`] for how to recognise them. A zero interval
+    /// disables heartbeats.
+    #[must_use]
+    pub fn with_idle_progress(mut self, interval: std::time::Duration) -> Self {
+        self.idle_progress = (!interval.is_zero()).then_some(interval);
+        self
+    }
 
-                        let engine = EmulationEngine::new(emu_config.clone());
-                        let report = engine.check_missing(&missing);
+    /// Return the idle heartbeat interval, if enabled.
+    #[must_use]
+    pub fn idle_progress(&self) -> Option<std::time::Duration> {
+        self.idle_progress
+    }
 
-                        if report.has_unemulatable() {
-                            return Err(RuntimeError::CapabilityCheckFailed(format!(
-                                "backend '{backend_name}': {e} (emulation unavailable for some capabilities)"
-                            )));
-                        }
+    /// End every run's event stream with a
+    /// [`RunSummary`](abp_core::AgentEventKind::RunSummary) event, also
+    /// published on the [`event_bus`](Self::event_bus) (builder pattern).
+    ///
+    /// The stream then stays open until the receipt is final; see
+    /// [`summary`].
+    #[must_use]
+    pub fn with_run_summary(mut self, enabled: bool) -> Self {
+        self.run_summary = enabled;
+        self
+    }
 
-                        info!(
-                            target: "abp.runtime",
-                            backend=%backend_name,
-                            emulated=?report.applied.iter().map(|e| &e.capability).collect::<Vec<_>>(),
-                            "emulating missing capabilities"
-                        );
-                        Some(report)
-                    } else {
-                        return Err(RuntimeError::CapabilityCheckFailed(format!(
-                            "backend '{backend_name}': {e}"
-                        )));
-                    }
-                }
-            }
-        } else {
-            None
-        };
+    /// Return whether runs end with a summary event.
+    #[must_use]
+    pub fn run_summary(&self) -> bool {
+        self.run_summary
+    }
 
-        let backend_name = backend_name.to_string();
-        let run_id = Uuid::new_v4();
-        let metrics = Arc::clone(&self.metrics);
+    /// Publish every run's events and lifecycle notifications through
+    /// `exporter` (builder pattern).
+    ///
+    /// Runs never wait on the broker; see [`export`].
+    #[must_use]
+    pub fn with_event_export(mut self, exporter: export::EventExporter) -> Self {
+        self.event_export = Some(exporter);
+        self
+    }
 
-        // Resolve source and target dialects for translation.
-        let source_dialect = extract_dialect(&work_order);
-        let target_dialect = resolve_backend_dialect(self.projection.as_ref(), &backend_name);
+    /// Return the attached event exporter, if any.
+    #[must_use]
+    pub fn event_export(&self) -> Option<&export::EventExporter> {
+        self.event_export.as_ref()
+    }
+
+    /// Publish a [`PartialReceipt`](partial::PartialReceipt) of every run
+    /// each `interval` while it is in progress (builder pattern).
+    ///
+    /// Subscribe with [`subscribe_partial_receipts`](Self::subscribe_partial_receipts);
+    /// see [`partial`]. A zero interval disables partial receipts.
+    #[must_use]
+    pub fn with_partial_receipts(mut self, interval: std::time::Duration) -> Self {
+        self.partial_receipts =
+            (!interval.is_zero()).then(|| Arc::new(partial::PartialReceiptFeed::new(interval)));
+        self
+    }
+
+    /// Return the partial receipt interval, if enabled.
+    #[must_use]
+    pub fn partial_receipts(&self) -> Option<std::time::Duration> {
+        self.partial_receipts.as_ref().map(|feed| feed.interval())
+    }
+
+    /// Receive partial receipts of every run started from now on, or `None`
+    /// if they are not enabled.
+    #[must_use]
+    pub fn subscribe_partial_receipts(
+        &self,
+    ) -> Option<tokio::sync::broadcast::Receiver<Arc<partial::PartialReceipt>>> {
+        self.partial_receipts.as_ref().map(|feed| feed.subscribe())
+    }
+
+    /// Merge assistant deltas into larger chunks when the caller drains the
+    /// event stream slower than the backend produces it (builder pattern).
+    ///
+    /// Fast consumers still see every delta as it arrives; see [`batching`]
+    /// for how the chunk size adapts. The receipt trace is not affected.
+    #[must_use]
+    pub fn with_delta_batching(mut self, config: batching::DeltaBatching) -> Self {
+        self.delta_batching = Some(config);
+        self
+    }
+
+    /// Return the delta batching limits, if enabled.
+    #[must_use]
+    pub fn delta_batching(&self) -> Option<batching::DeltaBatching> {
+        self.delta_batching
+    }
+
+    /// Execute calls to the dispatcher's tools in the runtime (builder
+    /// pattern).
+    ///
+    /// Each turn of tool calls runs concurrently behind a barrier and the
+    /// results are emitted as `ToolResult` events; see [`tools`].
+    #[must_use]
+    pub fn with_tool_dispatcher(mut self, dispatcher: tools::ToolDispatcher) -> Self {
+        self.tools = Some(Arc::new(dispatcher));
+        self
+    }
+
+    /// Return the runtime tool dispatcher, if one is configured.
+    #[must_use]
+    pub fn tool_dispatcher(&self) -> Option<&tools::ToolDispatcher> {
+        self.tools.as_deref()
+    }
+
+    /// Offer host tools to mapped-mode runs and feed their results back to
+    /// the backend (builder pattern).
+    ///
+    /// See [`tool_registry`].
+    #[must_use]
+    pub fn with_tool_registry(mut self, registry: tool_registry::ToolRegistry) -> Self {
+        self.tool_registry = Some(Arc::new(registry));
+        self
+    }
+
+    /// Return the host tool registry, if one is configured.
+    #[must_use]
+    pub fn tool_registry(&self) -> Option<&tool_registry::ToolRegistry> {
+        self.tool_registry.as_deref()
+    }
+
+    /// Offer the built-in Read, Write, Edit and Bash tools to mapped-mode
+    /// runs and run them in the staged workspace under the work order's
+    /// policy (builder pattern).
+    ///
+    /// Host tools of the same name take precedence. See
+    /// [`ToolLoop::builtin_tools`](tool_loop::ToolLoop::builtin_tools).
+    #[must_use]
+    pub fn with_builtin_tools(mut self) -> Self {
+        self.builtin_tools = true;
+        self
+    }
+
+    /// Whether the built-in tools are offered.
+    #[must_use]
+    pub fn builtin_tools(&self) -> bool {
+        self.builtin_tools
+    }
+
+    /// Scan the workspace after each run and attach the matching files to
+    /// the receipt (builder pattern).
+    ///
+    /// Files are listed in `receipt.artifacts` and described, with size and
+    /// SHA-256, under `usage_raw["artifacts"]`; see [`artifacts`].
+    #[must_use]
+    pub fn with_artifact_collector(mut self, collector: artifacts::ArtifactCollector) -> Self {
+        self.artifacts = Some(collector);
+        self
+    }
+
+    /// Return the artifact collector, if one is configured.
+    #[must_use]
+    pub fn artifact_collector(&self) -> Option<&artifacts::ArtifactCollector> {
+        self.artifacts.as_ref()
+    }
+
+    /// Set where artifacts streamed by backends are stored and how large
+    /// they may grow (builder pattern).
+    ///
+    /// Streamed artifacts are always assembled, hashed, and recorded under
+    /// `usage_raw["streamed_artifacts"]`; by default their content is not
+    /// kept. See [`artifact_stream`].
+    #[must_use]
+    pub fn with_artifact_streams(mut self, streams: artifact_stream::ArtifactStreams) -> Self {
+        self.artifact_streams = streams;
+        self
+    }
+
+    /// Return the streamed-artifact settings.
+    #[must_use]
+    pub fn artifact_streams(&self) -> &artifact_stream::ArtifactStreams {
+        &self.artifact_streams
+    }
+
+    /// Score completed runs with a judge backend (builder pattern).
+    ///
+    /// The judge's scores are recorded under `usage_raw["judge"]` before the
+    /// receipt is hashed; see [`judge`].
+    #[must_use]
+    pub fn with_judge(mut self, config: judge::JudgeConfig) -> Self {
+        self.judge = Some(config);
+        self
+    }
+
+    /// Return the judge configuration, if one is set.
+    #[must_use]
+    pub fn judge(&self) -> Option<&judge::JudgeConfig> {
+        self.judge.as_ref()
+    }
+
+    /// Serve runs with unchanged inputs from `cache` instead of their
+    /// backend (builder pattern).
+    ///
+    /// How each run used the cache is recorded under `usage_raw["cache"]`;
+    /// see [`cache`].
+    #[must_use]
+    pub fn with_run_cache(mut self, cache: cache::RunCache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Return the run cache, if one is configured.
+    #[must_use]
+    pub fn run_cache(&self) -> Option<&cache::RunCache> {
+        self.cache.as_deref()
+    }
+
+    /// Price runs with `table` instead of the built-in list prices
+    /// (builder pattern).
+    ///
+    /// See [`pricing`] for how receipts are priced.
+    #[must_use]
+    pub fn with_pricing(mut self, table: pricing::PricingTable) -> Self {
+        self.pricing = Arc::new(table);
+        self
+    }
+
+    /// Return the pricing table used to estimate run costs.
+    #[must_use]
+    pub fn pricing(&self) -> &pricing::PricingTable {
+        &self.pricing
+    }
+
+    /// Overlay the `[pricing]` section of the TOML file at `path` on the
+    /// current pricing table. Returns the model ids the file priced, in
+    /// sorted order.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigError`](abp_config::ConfigError) without changing
+    /// any price if the file cannot be loaded or holds an invalid price.
+    pub fn register_pricing_from_config(
+        &mut self,
+        path: &std::path::Path,
+    ) -> Result<Vec<String>, abp_config::ConfigError> {
+        let overrides = pricing::PricingTable::load(path)?;
+        let models = overrides.models().into_iter().map(String::from).collect();
+        Arc::make_mut(&mut self.pricing).merge(overrides);
+        Ok(models)
+    }
+
+    /// Retry crashed or timed-out attempts on `backend` and bound each
+    /// attempt by the configured timeout (builder pattern).
+    ///
+    /// Retried failures are listed in the receipt under
+    /// `usage_raw["retry_history"]`; see [`retry`].
+    #[must_use]
+    pub fn with_backend_retry(
+        mut self,
+        backend: impl Into<String>,
+        config: retry::BackendRetryConfig,
+    ) -> Self {
+        self.backend_retry.insert(backend.into(), config);
+        self
+    }
+
+    /// Apply every per-backend retry setting from a
+    /// [`RuntimeConfig`](config_integration::RuntimeConfig) (builder pattern).
+    #[must_use]
+    pub fn with_retry_config(mut self, config: &config_integration::RuntimeConfig) -> Self {
+        self.backend_retry.extend(
+            config
+                .backend_retry
+                .iter()
+                .map(|(name, retry)| (name.clone(), retry.clone())),
+        );
+        self
+    }
+
+    /// Return the retry settings for `backend`, if any.
+    #[must_use]
+    pub fn backend_retry(&self, backend: &str) -> Option<&retry::BackendRetryConfig> {
+        self.backend_retry.get(backend)
+    }
+
+    /// Hold runs on `backend` to the given requests and tokens per minute
+    /// (builder pattern).
+    ///
+    /// A run over the limit waits or is rejected with
+    /// [`ErrorCode::BackendRateLimited`](abp_error::ErrorCode::BackendRateLimited);
+    /// see [`rate_limit`].
+    #[must_use]
+    pub fn with_backend_rate_limit(
+        self,
+        backend: impl Into<String>,
+        limit: rate_limit::BackendRateLimit,
+    ) -> Self {
+        self.rate_limits.set(backend, limit);
+        self
+    }
+
+    /// Apply every per-backend rate limit from a
+    /// [`RuntimeConfig`](config_integration::RuntimeConfig) (builder pattern).
+    #[must_use]
+    pub fn with_rate_limit_config(self, config: &config_integration::RuntimeConfig) -> Self {
+        for (name, limit) in &config.backend_rate_limits {
+            self.rate_limits.set(name.clone(), limit.clone());
+        }
+        self
+    }
+
+    /// Return the per-backend rate limiter.
+    #[must_use]
+    pub fn rate_limits(&self) -> &rate_limit::RateLimiter {
+        &self.rate_limits
+    }
+
+    /// Stop sending runs to backends that keep crashing or timing out
+    /// (builder pattern).
+    ///
+    /// A backend whose breaker is open is rejected with
+    /// [`ErrorCode::CircuitBreakerOpen`](abp_error::ErrorCode::CircuitBreakerOpen)
+    /// and skipped by [`select_backend`](Self::select_backend); see
+    /// [`breaker`].
+    #[must_use]
+    pub fn with_circuit_breakers(mut self, breakers: breaker::CircuitBreakers) -> Self {
+        self.breakers = Some(Arc::new(breakers));
+        self
+    }
+
+    /// Return the attached circuit breakers, if any.
+    #[must_use]
+    pub fn circuit_breakers(&self) -> Option<&breaker::CircuitBreakers> {
+        self.breakers.as_deref()
+    }
+
+    /// Capability manifest a backend offers for a specific work order.
+    ///
+    /// Starts from the backend-wide manifest and, when a model catalog is
+    /// attached and the work order names a model, applies the model profile.
+    #[must_use]
+    pub fn effective_capabilities(
+        &self,
+        backend: &dyn Backend,
+        work_order: &WorkOrder,
+    ) -> CapabilityManifest {
+        let caps = backend.capabilities();
+        match (&self.model_catalog, work_order.config.model.as_deref()) {
+            (Some(catalog), Some(model)) if !caps.is_empty() => catalog.manifest_for(&caps, model),
+            _ => caps,
+        }
+    }
+
+    /// Use the projection matrix to select the best backend for a work order.
+    ///
+    /// Returns a [`ProjectionResult`] containing the selected backend name,
+    /// its composite score, any required emulations, and a fallback chain.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuntimeError::NoProjectionMatch`] if the projection matrix
+    /// is not configured or no backend satisfies the work order. Backends
+    /// whose circuit breaker is open are not considered.
+    /// Returns [`RuntimeError::UnknownBackend`] if the selected backend is
+    /// not registered in the runtime's [`BackendRegistry`].
+    pub fn select_backend(&self, work_order: &WorkOrder) -> Result<ProjectionResult, RuntimeError> {
+        let result = self
+            .projection()
+            .as_ref()
+            .ok_or_else(|| RuntimeError::NoProjectionMatch {
+                reason: "no projection matrix configured".into(),
+            })?
+            .project_where(work_order, |id| {
+                self.breakers
+                    .as_ref()
+                    .is_none_or(|b| b.is_available(id, &*self.clock))
+            })
+            .map_err(|e| RuntimeError::NoProjectionMatch {
+                reason: e.to_string(),
+            })?;
+
+        // Verify the selected backend is actually registered in the runtime.
+        if !self.registry().contains(&result.selected_backend) {
+            return Err(RuntimeError::UnknownBackend {
+                name: result.selected_backend.clone(),
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// Execute a work order using the projection matrix to select the best backend.
+    ///
+    /// This is a convenience wrapper around [`select_backend`](Self::select_backend)
+    /// followed by [`run_streaming`](Self::run_streaming). The projection result
+    /// metadata is recorded in the receipt's `usage_raw`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuntimeError::NoProjectionMatch`] if no suitable backend is found,
+    /// or any error that [`run_streaming`](Self::run_streaming) can return.
+    pub async fn run_projected(&self, work_order: WorkOrder) -> Result<RunHandle, RuntimeError> {
+        let projection_result = self.select_backend(&work_order)?;
+
+        info!(
+            target: "abp.runtime",
+            selected = %projection_result.selected_backend,
+            score = %projection_result.fidelity_score.total,
+            fallbacks = projection_result.fallback_chain.len(),
+            "projection selected backend"
+        );
+
+        self.run_streaming(&projection_result.selected_backend, work_order)
+            .await
+    }
+
+    /// Execute a work order on the projection's best backend, falling back
+    /// along its fallback chain when a backend fails or times out.
+    ///
+    /// Each attempt runs through [`run_streaming`](Self::run_streaming) with
+    /// its events drained. Only failures classified by
+    /// [`should_fall_back`](execution::should_fall_back) move on to the next
+    /// backend; fallback entries that are not registered are skipped. When
+    /// more than one backend was tried, the receipt lists every attempt as
+    /// [`FallbackAttempt`](execution::FallbackAttempt)s under
+    /// `usage_raw["fallback_attempts"]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuntimeError::NoProjectionMatch`] if no suitable backend is
+    /// found, the first error that does not warrant a fallback, or the last
+    /// backend's error once the chain is exhausted.
+    pub async fn run_with_fallback(&self, work_order: WorkOrder) -> Result<Receipt, RuntimeError> {
+        let projection_result = self.select_backend(&work_order)?;
+        let candidates: Vec<String> = std::iter::once(projection_result.selected_backend)
+            .chain(
+                projection_result
+                    .fallback_chain
+                    .into_iter()
+                    .map(|entry| entry.backend_id)
+                    .filter(|name| self.registry().contains(name)),
+            )
+            .collect();
+
+        let mut attempts = Vec::new();
+        let mut last_error = None;
+        for backend_name in &candidates {
+            if let Some(err) = &last_error {
+                info!(
+                    target: "abp.runtime",
+                    backend = %backend_name,
+                    error = %err,
+                    "falling back to next projected backend"
+                );
+            }
+            let result = match self
+                .start_run(backend_name, work_order.clone(), attempts.clone())
+                .await
+            {
+                Ok(handle) => {
+                    // Drain the event stream so the backend task can complete.
+                    let _events: Vec<_> = tokio_stream::StreamExt::collect(handle.events).await;
+                    handle
+                        .receipt
+                        .await
+                        .map_err(|e| RuntimeError::internal("run task failed", e))
+                        .and_then(|r| r)
+                }
+                Err(err) => Err(err),
+            };
+            match result {
+                Err(err) if execution::should_fall_back(&err) => {
+                    warn!(
+                        target: "abp.runtime",
+                        backend = %backend_name,
+                        error = %err,
+                        "projected backend failed"
+                    );
+                    attempts.push(execution::FallbackAttempt::failed(backend_name, &err));
+                    last_error = Some(err);
+                }
+                other => return other,
+            }
+        }
+        Err(last_error.expect("projection always selects a backend"))
+    }
+
+    /// Return a reference to the shared receipt chain.
+    ///
+    /// The chain accumulates receipts from successive [`run_streaming`](Self::run_streaming)
+    /// calls, enabling multi-step receipt verification and diffing.
+    #[must_use]
+    pub fn receipt_chain(&self) -> Arc<Mutex<ReceiptChain>> {
+        Arc::clone(&self.receipt_chain)
+    }
+
+    /// Check whether a backend's capabilities satisfy the given requirements.
+    ///
+    /// For sidecar backends whose capabilities come from handshake, this will
+    /// check against the (empty) default manifest — use the in-backend check
+    /// for authoritative validation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuntimeError::UnknownBackend`] if the backend is not registered,
+    /// or [`RuntimeError::CapabilityCheckFailed`] if requirements are not met.
+    pub fn check_capabilities(
+        &self,
+        backend_name: &str,
+        requirements: &CapabilityRequirements,
+    ) -> Result<(), RuntimeError> {
+        let backend = self
+            .backend(backend_name)
+            .ok_or_else(|| RuntimeError::UnknownBackend {
+                name: backend_name.to_string(),
+            })?;
+        ensure_capability_requirements(requirements, &backend.capabilities()).map_err(|e| {
+            RuntimeError::CapabilityCheckFailed(format!("backend '{backend_name}': {e}"))
+        })?;
+        Ok(())
+    }
+
+    /// Execute a work order against the named backend, returning a [`RunHandle`].
+    ///
+    /// The handle provides a streaming event channel and a receipt future.
+    /// The runtime prepares the workspace, compiles the policy, and attaches
+    /// verification metadata and receipt hash after the backend finishes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuntimeError::UnknownBackend`] if the named backend is not
+    /// registered, or [`RuntimeError::CapabilityCheckFailed`] if pre-flight
+    /// capability checks fail.
+    pub async fn run_streaming(
+        &self,
+        backend_name: &str,
+        work_order: WorkOrder,
+    ) -> Result<RunHandle, RuntimeError> {
+        self.start_run(backend_name, work_order, Vec::new()).await
+    }
+
+    /// Replay a recorded receipt without calling its backend again.
+    ///
+    /// The work order is rebuilt from the receipt (see
+    /// [`replay::recorded_work_order`]) and run through the full pipeline
+    /// against a stand-in backend that re-emits the recorded trace, so stream
+    /// pipelines, middleware, and receipt bookkeeping behave as in the
+    /// original run. The workspace is passed through rather than staged, and
+    /// the new receipt carries a [`replay::ReplayMarker`] naming the recorded
+    /// run.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RuntimeError::Classified`] with
+    /// [`ContractInvalidReceipt`](abp_error::ErrorCode::ContractInvalidReceipt)
+    /// if the receipt holds no work order, or any error the run itself hits.
+    pub async fn replay(&self, receipt: &Receipt) -> Result<RunHandle, RuntimeError> {
+        let mut work_order = replay::recorded_work_order(receipt).ok_or_else(|| {
+            RuntimeError::Classified(abp_error::AbpError::new(
+                abp_error::ErrorCode::ContractInvalidReceipt,
+                format!(
+                    "receipt of run {} holds no work order to replay",
+                    receipt.meta.run_id
+                ),
+            ))
+        })?;
+        work_order.workspace.mode = abp_core::WorkspaceMode::PassThrough;
+        let backend: Arc<dyn Backend> = Arc::new(replay::RecordedBackend::new(receipt.clone()));
+        self.launch(backend, &receipt.backend.id, work_order, Vec::new())
+            .await
+    }
+
+    /// Start a run, recording `prior_attempts` from
+    /// [`run_with_fallback`](Self::run_with_fallback) in the receipt.
+    async fn start_run(
+        &self,
+        backend_name: &str,
+        work_order: WorkOrder,
+        prior_attempts: Vec<execution::FallbackAttempt>,
+    ) -> Result<RunHandle, RuntimeError> {
+        let backend = self.backend(backend_name).ok_or_else(|| {
+            warn!(target: "abp.runtime", name = %backend_name, "unknown backend");
+            RuntimeError::UnknownBackend {
+                name: backend_name.to_string(),
+            }
+        })?;
+        self.launch(backend, backend_name, work_order, prior_attempts)
+            .await
+    }
+
+    /// Run `work_order` on an already resolved `backend`.
+    async fn launch(
+        &self,
+        backend: Arc<dyn Backend>,
+        backend_name: &str,
+        work_order: WorkOrder,
+        prior_attempts: Vec<execution::FallbackAttempt>,
+    ) -> Result<RunHandle, RuntimeError> {
+        let mut plan = self
+            .preflight(backend, backend_name, work_order, prior_attempts)
+            .await?;
+        let run_id = plan.run_id;
+        let admission = plan.admission.take();
+
+        // Two-stage channel: backend -> runtime -> caller. The backend side
+        // is opened per attempt inside the run task.
+        let (to_caller_tx, to_caller_rx) = mpsc::channel::<AgentEvent>(256);
+        let export = self
+            .event_export
+            .clone()
+            .map(|exporter| (exporter, plan.work_order.id, plan.backend_name.clone()));
+
+        let services = self.run_services(&plan);
+        let receipt = tokio::spawn(services.execute(plan, to_caller_tx));
+
+        // Mirror the run to the event export sink.
+        let (to_caller_rx, receipt) = match export {
+            Some((exporter, work_order_id, backend_name)) => exporter.tap(
+                run_id,
+                work_order_id,
+                &backend_name,
+                Arc::clone(&self.clock),
+                to_caller_rx,
+                receipt,
+            ),
+            None => (to_caller_rx, receipt),
+        };
+        // Report how the run ended to its backend's breaker.
+        let receipt = match admission {
+            Some((breakers, admission, backend_name)) => breakers.watch(
+                backend_name,
+                admission,
+                Arc::clone(&self.clock),
+                Arc::clone(&self.metrics),
+                receipt,
+            ),
+            None => receipt,
+        };
+
+        Ok(RunHandle {
+            run_id,
+            events: ReceiverStream::new(to_caller_rx),
+            receipt,
+        })
+    }
+
+    /// Settle everything about a run that can be decided before it starts,
+    /// refusing the work order if any check fails.
+    ///
+    /// An admitted run holds its quota and rate limit charges; dropping the
+    /// returned plan refunds them.
+    async fn preflight(
+        &self,
+        backend: Arc<dyn Backend>,
+        backend_name: &str,
+        work_order: WorkOrder,
+        prior_attempts: Vec<execution::FallbackAttempt>,
+    ) -> Result<RunPlan, RuntimeError> {
+        // Keep the work order as submitted so the run can be replayed.
+        let submitted_work_order = serde_json::to_value(&work_order).ok();
+
+        // Unchanged inputs are served from the run cache: the stored receipt
+        // is played back in place of the backend.
+        let cache_mode = cache::CacheMode::from_work_order(&work_order);
+        let cache_key = self
+            .cache
+            .as_ref()
+            .filter(|_| cache_mode != cache::CacheMode::Bypass)
+            .and_then(|c| match c.key(backend_name, &work_order) {
+                Ok(key) => Some(key),
+                Err(e) => {
+                    warn!(target: "abp.runtime", error=%e, "failed to compute run cache key");
+                    None
+                }
+            });
+        let cached = cache_key
+            .as_ref()
+            .filter(|_| cache_mode == cache::CacheMode::Use)
+            .zip(self.cache.as_ref())
+            .and_then(|(key, c)| c.lookup(key, self.clock.now()));
+        let cache_hit = cached.is_some();
+        let cache_record = cache_key.map(|key| cache::CacheRecord {
+            status: match (&cached, cache_mode) {
+                (Some(_), _) => cache::CacheStatus::Hit,
+                (None, cache::CacheMode::Refresh) => cache::CacheStatus::Refreshed,
+                (None, _) => cache::CacheStatus::Miss,
+            },
+            key,
+            source_run_id: cached.as_ref().map(|r| r.meta.run_id),
+            source_receipt_sha256: cached.as_ref().and_then(|r| r.receipt_sha256.clone()),
+        });
+        let mut work_order = work_order;
+        let backend = match (cached, &cache_record) {
+            (Some(recorded), Some(record)) => {
+                info!(
+                    target: "abp.runtime",
+                    backend=%backend_name,
+                    source_run_id=%recorded.meta.run_id,
+                    "run served from cache"
+                );
+                work_order.workspace.mode = abp_core::WorkspaceMode::PassThrough;
+                Arc::new(replay::RecordedBackend::cached(recorded, record)) as Arc<dyn Backend>
+            }
+            _ => backend,
+        };
+
+        // Check the backend's own vendor namespace and hide everyone else's.
+        if let Some(namespace) = backend.vendor_namespace() {
+            namespace.validate(&work_order).map_err(|e| {
+                RuntimeError::Classified(
+                    abp_error::AbpError::new(abp_error::ErrorCode::ValidationFailed, e.to_string())
+                        .with_context("backend", backend_name)
+                        .with_context("namespace", &e.namespace),
+                )
+            })?;
+            namespace.scope(&mut work_order);
+        }
+
+        // Pre-flight capability check: skip for sidecar backends whose
+        // capabilities are only known after handshake (empty default manifest).
+        // Refuse a model the backend does not advertise.
+        let model = work_order.config.model.clone();
+        self.models
+            .check(backend_name, model.as_deref())
+            .map_err(RuntimeError::Classified)?;
+        let caps = self.effective_capabilities(backend.as_ref(), &work_order);
+        let caps = match model.as_deref() {
+            Some(model) if !caps.is_empty() => self.models.manifest_for(backend_name, &caps, model),
+            _ => caps,
+        };
+
+        // Fold named policy presets into the work order's own policy.
+        let presets =
+            abp_policy::presets::apply(&mut work_order).map_err(RuntimeError::PolicyFailed)?;
+        if !presets.is_empty() {
+            debug!(target: "abp.runtime", presets = ?presets, "applied policy presets");
+        }
+
+        // Settle each capability ladder on the best rung the backend supports.
+        let rung_selections = if caps.is_empty() {
+            Vec::new()
+        } else {
+            ladder::resolve(&mut work_order, &caps).map_err(|e| {
+                RuntimeError::CapabilityCheckFailed(format!("backend '{backend_name}': {e}"))
+            })?
+        };
+        let resolved_flags = self.features.resolve(&work_order);
+        if resolved_flags.active().next().is_some() {
+            debug!(
+                target: "abp.runtime",
+                flags=?resolved_flags.active().collect::<Vec<_>>(),
+                "experimental features active"
+            );
+        }
+        for sel in rung_selections.iter().filter(|s| s.is_degraded()) {
+            info!(
+                target: "abp.runtime",
+                backend=%backend_name,
+                ladder=sel.ladder,
+                rung=sel.rung,
+                capability=?sel.capability,
+                "capability ladder degraded"
+            );
+        }
+        let mut emulation_report: Option<EmulationReport> = if !caps.is_empty() {
+            match ensure_capability_requirements(&work_order.requirements, &caps) {
+                Ok(()) => None,
+                Err(e) => {
+                    // Capabilities unsatisfied — try emulation if configured.
+                    if let Some(emu_config) = &self.emulation {
+                        let missing: Vec<_> = work_order
+                            .requirements
+                            .required
+                            .iter()
+                            .filter(|req| !caps.contains_key(&req.capability))
+                            .map(|req| req.capability.clone())
+                            .collect();
+
+                        let engine = EmulationEngine::new(emu_config.clone());
+                        let report = engine.check_missing(&missing);
+
+                        if report.has_unemulatable() {
+                            return Err(RuntimeError::CapabilityCheckFailed(format!(
+                                "backend '{backend_name}': {e} (emulation unavailable for some capabilities)"
+                            )));
+                        }
+
+                        info!(
+                            target: "abp.runtime",
+                            backend=%backend_name,
+                            emulated=?report.applied.iter().map(|e| &e.capability).collect::<Vec<_>>(),
+                            "emulating missing capabilities"
+                        );
+                        Some(report)
+                    } else {
+                        return Err(RuntimeError::CapabilityCheckFailed(format!(
+                            "backend '{backend_name}': {e}"
+                        )));
+                    }
+                }
+            }
+        } else {
+            None
+        };
+
+        let backend_name = backend_name.to_string();
+        let run_id = self.ids.next_id();
+
+        // Resolve source and target dialects for translation.
+        let source_dialect = extract_dialect(&work_order);
+        let target_dialect = resolve_backend_dialect(self.projection().as_ref(), &backend_name);
         let translation_engine = Arc::clone(&self.translation_engine);
 
-        // Run middleware before_run hooks (short-circuits on error).
-        let mw_chain = Arc::clone(&self.middleware);
-        let mw_ctx = MiddlewareContext::new(&backend_name);
-        if !mw_chain.is_empty() {
-            mw_chain
-                .run_before(&work_order, &mw_ctx)
-                .await
-                .map_err(RuntimeError::PolicyFailed)?;
-        }
+        // ── Dialect translation layer ────────────────────────────────
+        // Detect whether cross-dialect translation is needed and classify
+        // the translation mode.  When source ≠ target, the translation
+        // engine is used to validate the pair and collect capability gaps.
+        let translation_meta: Option<TranslationResult> = match (source_dialect, target_dialect) {
+            (Some(src), Some(tgt)) if src != tgt => {
+                // Translate the attached conversation (or, without one, the
+                // task as a single user turn) so gaps and the audit log
+                // describe what the backend will actually receive.
+                let conversation = abp_integrations::extract_conversation(&work_order)
+                    .filter(|conv| !conv.is_empty())
+                    .unwrap_or_else(|| {
+                        abp_core::ir::IrConversation::from_messages(vec![
+                            abp_core::ir::IrMessage::text(
+                                abp_core::ir::IrRole::User,
+                                &work_order.task,
+                            ),
+                        ])
+                    });
+                match translation_engine.translate(src, tgt, &conversation) {
+                    Ok(mut result) => {
+                        result.log.extend(audit_tools(
+                            src,
+                            tgt,
+                            &abp_integrations::extract_tools(&work_order),
+                            abp_integrations::extract_tool_choice(&work_order).as_ref(),
+                        ));
+                        info!(
+                            target: "abp.runtime",
+                            from = %src,
+                            to = %tgt,
+                            mode = %result.mode,
+                            gaps = result.gaps.len(),
+                            "dialect translation active"
+                        );
+                        Some(result)
+                    }
+                    Err(e) => {
+                        warn!(
+                            target: "abp.runtime",
+                            from = %src,
+                            to = %tgt,
+                            error = %e,
+                            "dialect translation not available, proceeding without"
+                        );
+                        None
+                    }
+                }
+            }
+            (Some(d), Some(t)) if d == t => {
+                debug!(
+                    target: "abp.runtime",
+                    dialect = %d,
+                    "passthrough — source and target dialects match"
+                );
+                Some(TranslationResult {
+                    conversation: abp_core::ir::IrConversation::new(),
+                    from: d,
+                    to: t,
+                    mode: TranslationMode::Passthrough,
+                    gaps: Vec::new(),
+                    log: Vec::new(),
+                })
+            }
+            _ => {
+                debug!(target: "abp.runtime", "no dialect translation — dialect(s) not specified");
+                None
+            }
+        };
+
+        // Same-dialect routing forwards the caller's raw request untouched.
+        let passthrough_record =
+            passthrough::route(&mut work_order, source_dialect, target_dialect);
+
+        // Mapped runs get the host and built-in tools, with the backend
+        // driven in a loop that sends their results back to it.
+        let registry = self.tool_registry.as_deref().filter(|r| !r.is_empty());
+        let backend = if !cache_hit
+            && (registry.is_some() || self.builtin_tools)
+            && abp_integrations::extract_execution_mode(&work_order) == ExecutionMode::Mapped
+        {
+            if self.builtin_tools {
+                tool_registry::advertise(&mut work_order, abp_tools::ToolSuite::definitions());
+            }
+            let dispatcher = registry.map_or_else(tools::ToolDispatcher::new, |registry| {
+                registry.advertise(&mut work_order);
+                registry.dispatcher()
+            });
+            let looped = tool_loop::ToolLoop::shared(backend, dispatcher);
+            Arc::new(if self.builtin_tools {
+                looped.builtin_tools()
+            } else {
+                looped
+            }) as Arc<dyn Backend>
+        } else {
+            backend
+        };
+
+        // A response language the backend cannot honour natively is asked
+        // for in the system prompt. The emulation is recorded before the
+        // fidelity check so a strict work order rejects it.
+        let response_language = abp_integrations::extract_response_language(&work_order)
+            .filter(|_| {
+                !matches!(
+                    caps.get(&Capability::ResponseLanguage),
+                    Some(abp_core::SupportLevel::Native)
+                )
+            })
+            .map(ResponseLanguageEmulator::new);
+        if let Some(emulator) = &response_language {
+            debug!(
+                target: "abp.runtime",
+                backend=%backend_name,
+                language=%emulator.tag(),
+                "emulating response language"
+            );
+            emulation_report
+                .get_or_insert_with(EmulationReport::default)
+                .applied
+                .push(EmulationEntry {
+                    capability: Capability::ResponseLanguage,
+                    strategy: emulator.strategy(),
+                });
+        }
+
+        // ── Fidelity policy ──────────────────────────────────────────
+        // Collect every emulated capability and lossy mapping step; a
+        // strict work order is rejected before the backend starts.
+        let cap_negotiation =
+            (!caps.is_empty()).then(|| abp_capability::negotiate(&caps, &work_order.requirements));
+        let fidelity_policy = fidelity::FidelityPolicy::from_work_order(&work_order)
+            .map_err(RuntimeError::Classified)?;
+        let fidelity_report = {
+            // Runtime emulation counts even when the backend declares no
+            // capabilities to negotiate against.
+            let combined = match (&cap_negotiation, &emulation_report) {
+                (Some(neg), report) => Some(negotiate::NegotiationResult::from_negotiation(
+                    neg,
+                    report.as_ref(),
+                )),
+                (None, Some(report)) => Some(negotiate::NegotiationResult::from_negotiation(
+                    &abp_capability::NegotiationResult {
+                        native: vec![],
+                        emulated: vec![],
+                        unsupported: vec![],
+                    },
+                    Some(report),
+                )),
+                (None, None) => None,
+            };
+            fidelity::FidelityReport::assess(
+                fidelity_policy,
+                combined.as_ref(),
+                translation_meta.as_ref(),
+            )
+        };
+        if fidelity_report.should_reject() {
+            warn!(
+                target: "abp.runtime",
+                backend=%backend_name,
+                report=%fidelity_report,
+                "rejecting run under strict fidelity policy"
+            );
+            return Err(RuntimeError::Classified(
+                fidelity_report.to_error(&backend_name),
+            ));
+        }
+        if fidelity_policy == fidelity::FidelityPolicy::Warn && !fidelity_report.is_lossless() {
+            warn!(
+                target: "abp.runtime",
+                backend=%backend_name,
+                report=%fidelity_report,
+                "run proceeds with reduced fidelity"
+            );
+        }
+
+        // A seed asks for a reproducible run; say so when the backend cannot
+        // promise one.
+        let seed = abp_integrations::extract_seed(&work_order);
+        let session = session::SessionRecord::from_work_order(&work_order);
+        let submitter = abp_core::submitter::Submitter::from_work_order(&work_order);
+        let billed = work_order.config.submitter.as_ref();
+        let tenant = billed.and_then(|s| s.tenant.clone());
+        let api_key_id = billed.and_then(|s| s.api_key_id.clone());
+        if let Some(seed) = seed
+            && !caps.is_empty()
+            && !matches!(
+                caps.get(&abp_core::Capability::SeedDeterminism),
+                Some(abp_core::SupportLevel::Native)
+            )
+        {
+            warn!(
+                target: "abp.runtime",
+                backend=%backend_name,
+                seed,
+                "backend does not declare seed determinism; replay may diverge"
+            );
+        }
+
+        // Refuse work for a tenant or API key that is out of quota, and hold
+        // the run's projected input against its quotas until the receipt
+        // says what it used. The hold is released if the run never bills.
+        let quota_charge = match &self.quotas {
+            Some(quotas) => {
+                let reservation = quotas
+                    .reserve(&work_order, self.clock.now())
+                    .map_err(RuntimeError::Classified)?;
+                Some(quota::QuotaCharge::new(Arc::clone(quotas), reservation))
+            }
+            None => None,
+        };
+
+        // Refuse input that cannot fit the token budget or context window.
+        let context_limit = self
+            .model_catalog
+            .as_deref()
+            .zip(work_order.config.model.as_deref())
+            .and_then(|(catalog, model)| catalog.context_limit(model));
+        budget::preflight(&work_order, context_limit).map_err(RuntimeError::Classified)?;
+
+        // Refuse a backend whose circuit breaker is open. A cached run never
+        // reaches the backend, so it neither needs nor counts as a probe.
+        let admission = match &self.breakers {
+            Some(breakers) if !cache_hit => {
+                let admission = breakers
+                    .admit(&backend_name, &*self.clock)
+                    .map_err(RuntimeError::Classified)?;
+                if admission == breaker::Admission::Probe {
+                    self.metrics
+                        .record_breaker_state(&backend_name, breaker::BreakerState::HalfOpen);
+                }
+                Some((Arc::clone(breakers), admission, backend_name.clone()))
+            }
+            _ => None,
+        };
+        let release_breaker = |admission: &Option<(Arc<breaker::CircuitBreakers>, _, String)>| {
+            if let Some((breakers, admission, backend_name)) = admission {
+                breakers.release(backend_name, *admission);
+            }
+        };
+
+        // Hold back, or refuse, a run that would push its backend over its
+        // rate limit. A cached run never reaches the backend. The charge is
+        // refunded if the run is refused before dispatch.
+        let rate_charge = if cache_hit {
+            None
+        } else {
+            self.rate_limits
+                .acquire(&backend_name, &work_order, &*self.clock)
+                .await
+                .inspect_err(|_| release_breaker(&admission))
+                .map_err(RuntimeError::Classified)?
+                .map(|tokens| {
+                    rate_limit::RateCharge::new(
+                        Arc::clone(&self.rate_limits),
+                        &backend_name,
+                        tokens,
+                    )
+                })
+        };
+
+        // Run middleware before_run hooks (short-circuits on error).
+        let mw_chain = Arc::clone(&self.middleware);
+        let mw_ctx = MiddlewareContext::new(&backend_name);
+        if !mw_chain.is_empty() {
+            mw_chain
+                .run_before(&work_order, &mw_ctx)
+                .await
+                .inspect_err(|_| release_breaker(&admission))
+                .map_err(RuntimeError::PolicyFailed)?;
+        }
+
+        // The work order is accepted; record who submitted it.
+        if let Some(log) = &self.audit {
+            let actor = audit::actor_for_work_order(&work_order);
+            let mut actions = vec![audit::AuditAction::WorkOrderSubmitted {
+                work_order_id: work_order.id,
+                run_id,
+                backend: backend_name.clone(),
+            }];
+            if let Some(key_id) = &api_key_id {
+                actions.push(audit::AuditAction::KeyUsed {
+                    key_id: key_id.clone(),
+                    purpose: "submit work order".into(),
+                });
+            }
+            for action in actions {
+                if let Err(e) = log.record(actor.as_deref(), action) {
+                    warn!(target: "abp.runtime", error=%e, "failed to append audit log entry");
+                }
+            }
+        }
+
+        Ok(RunPlan {
+            run_id,
+            backend,
+            backend_name,
+            work_order,
+            submitted_work_order,
+            prior_attempts,
+            model,
+            cache_hit,
+            cache_record,
+            rung_selections,
+            resolved_flags,
+            emulation_report,
+            translation_meta,
+            passthrough_record,
+            response_language,
+            cap_negotiation,
+            fidelity_report,
+            seed,
+            session,
+            submitter,
+            tenant,
+            api_key_id,
+            quota_charge,
+            rate_charge,
+            admission,
+            mw_ctx,
+        })
+    }
+
+    /// The runtime handles a spawned run of `plan` works with. A cached run
+    /// already carries its artifacts and judge scores, and its tool calls
+    /// were run when it was recorded.
+    fn run_services(&self, plan: &RunPlan) -> RunServices {
+        let fresh = !plan.cache_hit;
+        RunServices {
+            metrics: Arc::clone(&self.metrics),
+            receipt_chain: Arc::clone(&self.receipt_chain),
+            pipeline: self.stream_pipeline.clone(),
+            journal: self.journal.clone(),
+            receipt_store: self.receipt_store.clone(),
+            clock: Arc::clone(&self.clock),
+            workspace_quota: self.workspace_quota.clone(),
+            idle_progress: self.idle_progress,
+            summary_bus: self.run_summary.then(|| Arc::clone(&self.bus)),
+            partial_receipts: self.partial_receipts.clone(),
+            delta_batching: self.delta_batching,
+            tool_dispatcher: self.tools.clone().filter(|_| fresh),
+            artifact_collector: self.artifacts.clone().filter(|_| fresh),
+            artifact_streams: self.artifact_streams.clone(),
+            judge: self
+                .judge
+                .clone()
+                .filter(|_| fresh)
+                .map(|config| (self.backend(&config.backend), config)),
+            run_cache: self.cache.clone().filter(|_| fresh),
+            pricing: Arc::clone(&self.pricing),
+            middleware: Arc::clone(&self.middleware),
+            backend_retry: self.backend_retry.get(&plan.backend_name).cloned(),
+        }
+    }
+}
+
+/// Everything [`Runtime::preflight`] settled for an admitted run.
+struct RunPlan {
+    run_id: Uuid,
+    backend: Arc<dyn Backend>,
+    backend_name: String,
+    work_order: WorkOrder,
+    /// The work order as submitted, kept so the run can be replayed.
+    submitted_work_order: Option<serde_json::Value>,
+    prior_attempts: Vec<execution::FallbackAttempt>,
+    model: Option<String>,
+    cache_hit: bool,
+    cache_record: Option<cache::CacheRecord>,
+    rung_selections: Vec<ladder::RungSelection>,
+    resolved_flags: flags::ResolvedFlags,
+    emulation_report: Option<EmulationReport>,
+    translation_meta: Option<TranslationResult>,
+    passthrough_record: Option<passthrough::PassthroughRecord>,
+    response_language: Option<ResponseLanguageEmulator>,
+    cap_negotiation: Option<abp_capability::NegotiationResult>,
+    fidelity_report: fidelity::FidelityReport,
+    seed: Option<u64>,
+    session: Option<session::SessionRecord>,
+    submitter: Option<abp_core::submitter::Submitter>,
+    tenant: Option<String>,
+    api_key_id: Option<String>,
+    quota_charge: Option<quota::QuotaCharge>,
+    rate_charge: Option<rate_limit::RateCharge>,
+    /// Taken by [`Runtime::launch`] to report the outcome to the breaker.
+    admission: Option<(Arc<breaker::CircuitBreakers>, breaker::Admission, String)>,
+    mw_ctx: MiddlewareContext,
+}
+
+/// Runtime handles cloned into a spawned run.
+struct RunServices {
+    metrics: Arc<RunMetrics>,
+    receipt_chain: Arc<Mutex<ReceiptChain>>,
+    pipeline: Option<stream::StreamPipeline>,
+    journal: Option<Arc<ReceiptJournal>>,
+    receipt_store: Option<Arc<dyn abp_receipt_store::ReceiptStore>>,
+    clock: SharedClock,
+    workspace_quota: Option<WorkspaceQuota>,
+    idle_progress: Option<std::time::Duration>,
+    summary_bus: Option<Arc<bus::EventBus>>,
+    partial_receipts: Option<Arc<partial::PartialReceiptFeed>>,
+    delta_batching: Option<batching::DeltaBatching>,
+    tool_dispatcher: Option<Arc<tools::ToolDispatcher>>,
+    artifact_collector: Option<artifacts::ArtifactCollector>,
+    artifact_streams: artifact_stream::ArtifactStreams,
+    judge: Option<(Option<Arc<dyn Backend>>, judge::JudgeConfig)>,
+    run_cache: Option<Arc<cache::RunCache>>,
+    pricing: Arc<pricing::PricingTable>,
+    middleware: Arc<MiddlewareChain>,
+    backend_retry: Option<retry::BackendRetryConfig>,
+}
+
+/// A run's prepared workspace and the work order the backend receives.
+struct StagedRun {
+    run_start: std::time::Instant,
+    started_at: chrono::DateTime<chrono::Utc>,
+    /// Kept alive for the duration of the run.
+    prepared: abp_workspace::PreparedWorkspace,
+    input_digest: Option<abp_core::merkle::WorkspaceDigest>,
+    wo: WorkOrder,
+    structured_output: Option<StructuredOutputEmulator>,
+    policy: PolicyEngine,
+    negotiation_result: Option<abp_capability::NegotiationResult>,
+}
+
+/// What the dispatch loop saw of a run.
+struct Dispatched {
+    trace: Vec<AgentEvent>,
+    receipt: Option<Receipt>,
+    error: Option<RuntimeError>,
+    retry_history: Vec<retry::RetryAttempt>,
+    streamed_artifacts: Vec<artifact_stream::StreamedArtifact>,
+    budget_violation: Option<(String, UsageNormalized)>,
+}
 
-        // Two-stage channel: backend -> runtime -> caller
-        let (from_backend_tx, mut from_backend_rx) = mpsc::channel::<AgentEvent>(256);
-        let (to_caller_tx, to_caller_rx) = mpsc::channel::<AgentEvent>(256);
+impl RunServices {
+    /// Run `plan` to completion: stage its workspace, drive the backend and
+    /// finalise the receipt.
+    async fn execute(
+        self,
+        mut plan: RunPlan,
+        to_caller_tx: mpsc::Sender<AgentEvent>,
+    ) -> Result<Receipt, RuntimeError> {
+        let staged = self.stage(&mut plan)?;
+        let run_id = plan.run_id;
+
+        debug!(target: "abp.runtime", backend=%plan.backend_name, run_id=%run_id, "starting run");
+
+        if let Some(j) = &self.journal
+            && let Err(e) = j.begin(run_id, plan.work_order.id, &plan.backend_name)
+        {
+            warn!(target: "abp.runtime", error=%e, "failed to open receipt journal entry");
+        }
 
-        let receipt_chain = Arc::clone(&self.receipt_chain);
-        let pipeline = self.stream_pipeline.clone();
-
-        let receipt = tokio::spawn(async move {
-            let run_start = std::time::Instant::now();
-
-            // Keep the prepared workspace alive for the duration of the run.
-            let prepared = WorkspaceManager::prepare(&work_order.workspace)
-                .context("prepare workspace")
-                .map_err(RuntimeError::WorkspaceFailed)?;
-
-            // Clone and rewrite the work order to point at prepared workspace.
-            let mut wo = work_order.clone();
-            wo.workspace.root = prepared.path().to_string_lossy().to_string();
-
-            // Strip emulated capability requirements so the backend's own check
-            // does not reject capabilities the runtime is emulating.
-            if let Some(ref report) = emulation_report {
-                let emulated_caps: std::collections::BTreeSet<_> =
-                    report.applied.iter().map(|e| &e.capability).collect();
-                wo.requirements
-                    .required
-                    .retain(|r| !emulated_caps.contains(&r.capability));
+        let run = self.dispatch(&mut plan, &staged, &to_caller_tx).await;
+
+        // Close the caller event stream before returning any error so
+        // the caller's drain loop terminates cleanly. A summary is sent
+        // first, so the stream then stays open until the receipt is final.
+        let summary_tx = self.summary_bus.is_some().then_some(to_caller_tx);
+
+        if let Some(err) = run.error {
+            if let (Some(tx), Some(bus)) = (summary_tx, &self.summary_bus) {
+                let denied = err.error_code() == abp_error::ErrorCode::PolicyDenied;
+                let kind = summary::summarize(
+                    Outcome::Failed,
+                    elapsed_ms(self.clock.as_ref(), staged.run_start),
+                    UsageNormalized::default(),
+                    &run.trace,
+                    u64::from(denied),
+                );
+                let ev = summary::summary_event(kind, self.clock.now());
+                send_summary(&tx, bus, self.pipeline.as_ref(), run_id, ev).await;
+            }
+            // The caller gets the error; there is no receipt to recover.
+            if let Some(j) = &self.journal
+                && let Err(e) = j.complete(run_id)
+            {
+                warn!(target: "abp.runtime", error=%e, "failed to clear receipt journal entry");
             }
+            return Err(err);
+        }
 
-            // Compile policy globs (even if adapters do the heavy lifting).
-            let _policy = PolicyEngine::new(&wo.policy)
-                .context("compile policy")
-                .map_err(RuntimeError::PolicyFailed)?;
+        self.finalize_receipt(plan, staged, run, summary_tx).await
+    }
 
-            // Capability negotiation via abp-capability crate.
-            let negotiation_result = {
-                let manifest = backend.capabilities();
-                if !manifest.is_empty() {
-                    let result = abp_capability::negotiate(&manifest, &work_order.requirements);
-                    if !result.is_compatible() {
-                        // Check if unsupported capabilities are covered by runtime emulation.
-                        let truly_unsupported: Vec<_> = match emulation_report {
-                            Some(ref emu) => {
-                                let emulated_caps: std::collections::BTreeSet<_> =
-                                    emu.applied.iter().map(|e| &e.capability).collect();
-                                result
-                                    .unsupported_caps()
-                                    .into_iter()
-                                    .filter(|c| !emulated_caps.contains(c))
-                                    .collect()
-                            }
-                            None => result.unsupported_caps(),
-                        };
-                        if !truly_unsupported.is_empty() {
-                            let names: Vec<String> =
-                                truly_unsupported.iter().map(|c| format!("{c:?}")).collect();
-                            return Err(RuntimeError::CapabilityCheckFailed(format!(
-                                "backend '{backend_name}': unsupported capabilities: {}",
-                                names.join(", ")
-                            )));
-                        }
-                    }
-                    if !result.emulated.is_empty() {
-                        warn!(
-                            target: "abp.runtime",
-                            backend=%backend_name,
-                            emulated=?result.emulated_caps(),
-                            "capabilities require emulation"
-                        );
-                    }
-                    Some(result)
-                } else {
+    /// Prepare the workspace and rewrite the work order for the backend.
+    fn stage(&self, plan: &mut RunPlan) -> Result<StagedRun, RuntimeError> {
+        let run_start = self.clock.instant();
+        let started_at = self.clock.now();
+        let RunPlan {
+            backend_name,
+            work_order,
+            emulation_report,
+            response_language,
+            cap_negotiation,
+            ..
+        } = plan;
+
+        // Keep the prepared workspace alive for the duration of the run.
+        let prepared = WorkspaceManager::prepare(&work_order.workspace)
+            .context("prepare workspace")
+            .map_err(RuntimeError::WorkspaceFailed)?;
+
+        // Record the staged input state before the backend can touch it.
+        let input_digest = if prepared.is_staged() {
+            match abp_workspace::workspace_digest(prepared.path()) {
+                Ok(digest) => Some(digest),
+                Err(e) => {
+                    warn!(
+                        target: "abp.runtime",
+                        error = %e,
+                        "failed to compute input workspace digest"
+                    );
                     None
                 }
-            };
+            }
+        } else {
+            None
+        };
 
-            debug!(target: "abp.runtime", backend=%backend_name, run_id=%run_id, "starting run");
+        // Clone and rewrite the work order to point at prepared workspace.
+        let mut wo = work_order.clone();
+        wo.workspace.root = prepared.path().to_string_lossy().to_string();
+
+        // Strip emulated capability requirements so the backend's own check
+        // does not reject capabilities the runtime is emulating.
+        if let Some(report) = emulation_report {
+            let emulated_caps: std::collections::BTreeSet<_> =
+                report.applied.iter().map(|e| &e.capability).collect();
+            wo.requirements
+                .required
+                .retain(|r| !emulated_caps.contains(&r.capability));
+        }
 
-            // ── Dialect translation layer ────────────────────────────────
-            // Detect whether cross-dialect translation is needed and classify
-            // the translation mode.  When source ≠ target, the translation
-            // engine is used to validate the pair and collect capability gaps.
-            let translation_meta: Option<TranslationResult> = match (source_dialect, target_dialect)
-            {
-                (Some(src), Some(tgt)) if src != tgt => {
-                    // Build a probe conversation from the work order task to
-                    // detect capability gaps and validate the mapping pair.
-                    let probe = abp_core::ir::IrConversation::from_messages(vec![
-                        abp_core::ir::IrMessage::text(abp_core::ir::IrRole::User, &wo.task),
-                    ]);
-                    match translation_engine.translate(src, tgt, &probe) {
-                        Ok(result) => {
-                            info!(
-                                target: "abp.runtime",
-                                from = %src,
-                                to = %tgt,
-                                mode = %result.mode,
-                                gaps = result.gaps.len(),
-                                "dialect translation active"
-                            );
-                            Some(result)
-                        }
-                        Err(e) => {
-                            warn!(
-                                target: "abp.runtime",
-                                from = %src,
-                                to = %tgt,
-                                error = %e,
-                                "dialect translation not available, proceeding without"
-                            );
-                            None
+        // Emulated structured output: ask for the schema in the task and
+        // recover the JSON from the reply once the run is done.
+        let structured_output = emulation_report
+            .as_ref()
+            .filter(|report| {
+                report
+                    .applied
+                    .iter()
+                    .any(|e| e.capability == Capability::StructuredOutputJsonSchema)
+            })
+            .and_then(|_| wo.config.vendor.get(pipeline::RESPONSE_FORMAT_KEY))
+            .and_then(StructuredOutputEmulator::from_response_format);
+        if let Some(emulator) = &structured_output {
+            wo.task = emulator.instruct_task(&wo.task);
+        }
+        if let Some(emulator) = &response_language {
+            match abp_integrations::extract_conversation(&wo) {
+                Some(mut conv) if !conv.is_empty() => {
+                    emulator.inject(&mut conv);
+                    tool_loop::attach_conversation(&mut wo, &conv);
+                }
+                _ => wo.task = emulator.instruct_task(&wo.task),
+            }
+        }
+
+        // Compile policy globs. Adapters enforce most rules; the runtime
+        // also stops runs whose tool calls reach a denied network host.
+        let policy = PolicyEngine::new(&wo.policy)
+            .context("compile policy")
+            .map_err(RuntimeError::PolicyFailed)?;
+
+        // Capability negotiation via abp-capability crate.
+        let negotiation_result = {
+            if let Some(result) = cap_negotiation.take() {
+                if !result.is_compatible() {
+                    // Check if unsupported capabilities are covered by runtime emulation.
+                    let truly_unsupported: Vec<_> = match emulation_report {
+                        Some(emu) => {
+                            let emulated_caps: std::collections::BTreeSet<_> =
+                                emu.applied.iter().map(|e| &e.capability).collect();
+                            result
+                                .unsupported_caps()
+                                .into_iter()
+                                .filter(|c| !emulated_caps.contains(c))
+                                .collect()
                         }
+                        None => result.unsupported_caps(),
+                    };
+                    if !truly_unsupported.is_empty() {
+                        let names: Vec<String> =
+                            truly_unsupported.iter().map(|c| format!("{c:?}")).collect();
+                        return Err(RuntimeError::CapabilityCheckFailed(format!(
+                            "backend '{backend_name}': unsupported capabilities: {}",
+                            names.join(", ")
+                        )));
                     }
                 }
-                (Some(d), Some(t)) if d == t => {
-                    debug!(
+                if !result.emulated.is_empty() {
+                    warn!(
                         target: "abp.runtime",
-                        dialect = %d,
-                        "passthrough — source and target dialects match"
+                        backend=%backend_name,
+                        emulated=?result.emulated_caps(),
+                        "capabilities require emulation"
                     );
-                    Some(TranslationResult {
-                        conversation: abp_core::ir::IrConversation::new(),
-                        from: d,
-                        to: t,
-                        mode: TranslationMode::Passthrough,
-                        gaps: Vec::new(),
-                    })
-                }
-                _ => {
-                    debug!(target: "abp.runtime", "no dialect translation — dialect(s) not specified");
-                    None
                 }
-            };
+                Some(result)
+            } else {
+                None
+            }
+        };
+
+        Ok(StagedRun {
+            run_start,
+            started_at,
+            prepared,
+            input_digest,
+            wo,
+            structured_output,
+            policy,
+            negotiation_result,
+        })
+    }
+
+    /// Drive the backend, retrying failed attempts, and stream its events
+    /// to the caller.
+    async fn dispatch(
+        &self,
+        plan: &mut RunPlan,
+        staged: &StagedRun,
+        to_caller_tx: &mpsc::Sender<AgentEvent>,
+    ) -> Dispatched {
+        let RunServices {
+            metrics,
+            clock,
+            pipeline,
+            journal,
+            tool_dispatcher,
+            backend_retry,
+            ..
+        } = self;
+        let RunPlan {
+            run_id,
+            backend,
+            backend_name,
+            work_order,
+            rate_charge,
+            ..
+        } = plan;
+        let run_id = *run_id;
+        let StagedRun {
+            started_at,
+            prepared,
+            wo,
+            policy,
+            ..
+        } = staged;
+        let idle_progress = self.idle_progress;
+        let mut assembler = self.artifact_streams.assembler(run_id);
+
+        let mut trace: Vec<AgentEvent> = Vec::new();
+        let mut receipt_opt: Option<Receipt> = None;
+        let mut backend_error: Option<RuntimeError> = None;
+
+        // Only staged copies are metered; pass-through runs use the
+        // caller's own directory.
+        let workspace_quota = self
+            .workspace_quota
+            .clone()
+            .filter(|_| prepared.is_staged());
+        let mut quota_tick = tokio::time::interval(QUOTA_CHECK_INTERVAL);
+        quota_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        // Heartbeat timer, reset by every backend event.
+        let mut last_backend_event = tokio::time::Instant::now();
+        let idle_timer = tokio::time::sleep(idle_progress.unwrap_or_default());
+        tokio::pin!(idle_timer);
+
+        // Partial receipt snapshots for subscribers, if enabled.
+        let mut snapshots = self
+            .partial_receipts
+            .as_ref()
+            .map(|feed| feed.run(run_id, work_order.id, backend_name, *started_at));
+        let snapshot_interval = snapshots
+            .as_ref()
+            .map_or(QUOTA_CHECK_INTERVAL, partial::RunSnapshots::interval);
+        let mut snapshot_tick = tokio::time::interval_at(
+            tokio::time::Instant::now() + snapshot_interval,
+            snapshot_interval,
+        );
+        snapshot_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        // Stop the backend once its reported usage crosses the work
+        // order's token or cost ceiling: the guard cancels `run_cancel`,
+        // which every attempt watches.
+        let run_cancel = cancel::CancellationToken::new();
+        let mut budget_guard = budget::BudgetGuard::from_work_order(
+            work_order,
+            cancel::CancellableRun::new(run_cancel.clone()),
+        );
+
+        // Deltas held back for a slow consumer, and when they must go out.
+        let mut batcher = self.delta_batching.map(batching::DeltaBatcher::new);
+        let batch_timer = tokio::time::sleep(std::time::Duration::ZERO);
+        tokio::pin!(batch_timer);
+
+        // Retry settings for this backend; without them the backend gets
+        // a single attempt with no deadline.
+        let retry_policy = backend_retry
+            .as_ref()
+            .map_or_else(retry::RetryPolicy::no_retry, |r| r.policy.clone());
+        let attempt_timeout = backend_retry.as_ref().and_then(|r| r.timeout);
+        let mut retry_history: Vec<retry::RetryAttempt> = Vec::new();
+
+        // Calls of the current turn waiting for the runtime to run them.
+        let mut pending_tools: Vec<AgentEvent> = Vec::new();
+
+        // From here on the run reaches the backend and keeps its rate
+        // limit charge.
+        if let Some(charge) = rate_charge {
+            charge.dispatch();
+        }
+
+        loop {
+            // With retries configured, events are tagged with the attempt
+            // that produced them.
+            let attempt_tag = backend_retry.as_ref().map(|_| retry_history.len() as u32);
+            let (from_backend_tx, mut from_backend_rx) = mpsc::channel::<AgentEvent>(256);
 
             // Run backend in a task so we can multiplex events.
             let backend2 = backend.clone();
-            let mut backend_handle =
-                tokio::spawn(async move { backend2.run(run_id, wo, from_backend_tx).await });
-
-            let mut trace: Vec<AgentEvent> = Vec::new();
-            let mut receipt_opt: Option<Receipt> = None;
-            let mut backend_error: Option<RuntimeError> = None;
+            let attempt_wo = wo.clone();
+            let cancelled = run_cancel.clone();
+            let mut backend_handle = tokio::spawn(async move {
+                tokio::select! {
+                    res = retry::with_attempt_timeout(
+                        attempt_timeout,
+                        backend2.run(run_id, attempt_wo, from_backend_tx),
+                    ) => res,
+                    () = cancelled.cancelled() => Err(anyhow::anyhow!("run cancelled")),
+                }
+            });
 
             loop {
+                if let Some(deadline) = batcher.as_ref().and_then(batching::DeltaBatcher::deadline)
+                {
+                    batch_timer.as_mut().reset(deadline);
+                }
                 tokio::select! {
+                    () = &mut batch_timer, if batcher.as_ref().is_some_and(|b| b.deadline().is_some()) => {
+                        if let Some(ev) = batcher.as_mut().and_then(batching::DeltaBatcher::flush) {
+                            let _ = to_caller_tx.send(ev).await;
+                        }
+                    }
+                    () = &mut idle_timer, if idle_progress.is_some() => {
+                        let heartbeat = progress::idle_progress_event(
+                            last_backend_event.elapsed(),
+                            clock.now(),
+                        );
+                        if let Some(ev) = stream::apply_pipeline(pipeline.as_ref(), heartbeat) {
+                            send_to_caller(to_caller_tx, batcher.as_mut(), ev).await;
+                        }
+                        if let Some(interval) = idle_progress {
+                            idle_timer.as_mut().reset(tokio::time::Instant::now() + interval);
+                        }
+                    }
+                    _ = snapshot_tick.tick(), if snapshots.is_some() => {
+                        if let Some(snapshots) = snapshots.as_mut() {
+                            snapshots.publish(&trace, Outcome::Partial, false, clock.now());
+                        }
+                    }
+                    _ = quota_tick.tick(), if workspace_quota.is_some() => {
+                        if let Some(quota) = &workspace_quota
+                            && let Some(err) = check_workspace_quota(quota, prepared.path(), metrics)
+                        {
+                            backend_handle.abort();
+                            backend_error = Some(err);
+                            break;
+                        }
+                    }
                     ev = from_backend_rx.recv() => {
                         match ev {
                             Some(ev) => {
+                                last_backend_event = tokio::time::Instant::now();
+                                if let Some(interval) = idle_progress {
+                                    idle_timer.as_mut().reset(last_backend_event + interval);
+                                }
+                                let Some(mut ev) = absorb_artifact_chunk(&mut assembler, ev, clock.now()) else {
+                                    continue;
+                                };
+                                if let Some(attempt) = attempt_tag {
+                                    retry::tag_attempt(&mut ev, attempt);
+                                }
+                                if !tools::continues_turn(&ev)
+                                    && let Some(dispatcher) = &tool_dispatcher
+                                    && !pending_tools.is_empty()
+                                {
+                                    let calls = std::mem::take(&mut pending_tools);
+                                    run_tool_turn(
+                                        dispatcher,
+                                        &calls,
+                                        clock.as_ref(),
+                                        pipeline.as_ref(),
+                                        journal.as_deref(),
+                                        run_id,
+                                        &mut trace,
+                                        to_caller_tx,
+                                        batcher.as_mut(),
+                                    )
+                                    .await;
+                                }
+                                let egress = check_network_egress(policy, &ev);
+                                let exceeded = budget_guard
+                                    .as_mut()
+                                    .and_then(|g| g.observe(&ev))
+                                    .map(ToString::to_string);
+                                let dispatched = tool_dispatcher
+                                    .as_deref()
+                                    .is_some_and(|d| tools::is_dispatched(d, &ev));
+                                if dispatched && egress.is_none() {
+                                    pending_tools.push(ev.clone());
+                                }
                                 if let Some(ev) = stream::apply_pipeline(pipeline.as_ref(), ev) {
+                                    journal_event(journal.as_deref(), run_id, &ev);
                                     trace.push(ev.clone());
-                                    let _ = to_caller_tx.send(ev).await;
+                                    send_to_caller(to_caller_tx, batcher.as_mut(), ev).await;
+                                }
+                                if let Some(err) = egress {
+                                    warn!(target: "abp.runtime", backend=%backend_name, error=%err, "stopping run on denied network egress");
+                                    backend_handle.abort();
+                                    backend_error = Some(err);
+                                    break;
+                                }
+                                if let Some(reason) = exceeded {
+                                    warn!(target: "abp.runtime", backend=%backend_name, %reason, "stopping run over budget");
+                                    let ev = AgentEvent {
+                                        ts: clock.now(),
+                                        kind: AgentEventKind::Warning { message: reason },
+                                        ext: None,
+                                    };
+                                    journal_event(journal.as_deref(), run_id, &ev);
+                                    trace.push(ev.clone());
+                                    send_to_caller(to_caller_tx, batcher.as_mut(), ev).await;
+                                    break;
                                 }
                             }
                             None => break,
@@ -737,15 +2584,68 @@ impl Runtime {
             // Drain any remaining events so the caller sees everything the
             // backend sent, even when the backend ultimately fails.
             while let Some(ev) = from_backend_rx.recv().await {
+                let Some(mut ev) = absorb_artifact_chunk(&mut assembler, ev, clock.now()) else {
+                    continue;
+                };
+                if let Some(attempt) = attempt_tag {
+                    retry::tag_attempt(&mut ev, attempt);
+                }
+                if backend_error.is_none() {
+                    backend_error = check_network_egress(policy, &ev);
+                }
+                if backend_error.is_none()
+                    && let Some(dispatcher) = &tool_dispatcher
+                {
+                    if !tools::continues_turn(&ev) && !pending_tools.is_empty() {
+                        let calls = std::mem::take(&mut pending_tools);
+                        run_tool_turn(
+                            dispatcher,
+                            &calls,
+                            clock.as_ref(),
+                            pipeline.as_ref(),
+                            journal.as_deref(),
+                            run_id,
+                            &mut trace,
+                            to_caller_tx,
+                            batcher.as_mut(),
+                        )
+                        .await;
+                    }
+                    if tools::is_dispatched(dispatcher, &ev) {
+                        pending_tools.push(ev.clone());
+                    }
+                }
                 if let Some(ev) = stream::apply_pipeline(pipeline.as_ref(), ev) {
+                    journal_event(journal.as_deref(), run_id, &ev);
                     trace.push(ev.clone());
-                    let _ = to_caller_tx.send(ev).await;
+                    send_to_caller(to_caller_tx, batcher.as_mut(), ev).await;
                 }
             }
-
+            // The backend's stream ended mid-turn: run what is left,
+            // unless the run is already failing.
+            let calls = std::mem::take(&mut pending_tools);
+            if backend_error.is_none()
+                && let Some(dispatcher) = &tool_dispatcher
+            {
+                run_tool_turn(
+                    dispatcher,
+                    &calls,
+                    clock.as_ref(),
+                    pipeline.as_ref(),
+                    journal.as_deref(),
+                    run_id,
+                    &mut trace,
+                    to_caller_tx,
+                    batcher.as_mut(),
+                )
+                .await;
+            }
             // If the channel closed before the select polled the backend handle,
             // await it now so we don't lose the real receipt or error.
-            if receipt_opt.is_none() && backend_error.is_none() {
+            let over_budget = budget_guard
+                .as_ref()
+                .is_some_and(|g| g.violation().is_some());
+            if receipt_opt.is_none() && backend_error.is_none() && !over_budget {
                 match backend_handle.await {
                     Ok(Ok(r)) => receipt_opt = Some(r),
                     Ok(Err(e)) => {
@@ -762,143 +2662,519 @@ impl Runtime {
                 }
             }
 
-            // Close the caller event stream before returning any error so
-            // the caller's drain loop terminates cleanly.
-            drop(to_caller_tx);
-
-            if let Some(err) = backend_error {
-                return Err(err);
+            // A crashed or timed-out attempt is retried while the policy
+            // allows; anything else ends the run.
+            let attempt = retry_history.len() as u32;
+            match &backend_error {
+                Some(err)
+                    if !over_budget
+                        && retry_policy.should_retry(attempt)
+                        && execution::should_fall_back(err) =>
+                {
+                    let delay = retry_policy.compute_delay(attempt);
+                    let record = retry::RetryAttempt::failed(attempt, err, delay);
+                    warn!(
+                        target: "abp.runtime",
+                        backend=%backend_name,
+                        attempt,
+                        delay_ms = record.delay_ms,
+                        error=%record.error,
+                        "retrying backend attempt"
+                    );
+                    let mut ev = AgentEvent {
+                        ts: clock.now(),
+                        kind: AgentEventKind::Warning {
+                            message: format!(
+                                "backend '{backend_name}' attempt {} failed ({}); retrying in {} ms",
+                                attempt + 1,
+                                record.error_code.as_str(),
+                                record.delay_ms
+                            ),
+                        },
+                        ext: None,
+                    };
+                    retry::tag_attempt(&mut ev, attempt);
+                    journal_event(journal.as_deref(), run_id, &ev);
+                    trace.push(ev.clone());
+                    send_to_caller(to_caller_tx, batcher.as_mut(), ev).await;
+                    retry_history.push(record);
+                    backend_error = None;
+                    let wait = clock.sleep_for(delay);
+                    if !wait.is_zero() {
+                        tokio::time::sleep(wait).await;
+                    }
+                }
+                _ => break,
             }
+        }
+
+        let (streamed_artifacts, incomplete_artifacts) = assembler.finish();
+        for name in incomplete_artifacts {
+            let ev = AgentEvent {
+                ts: clock.now(),
+                kind: AgentEventKind::Warning {
+                    message: format!(
+                        "artifact '{name}' discarded: the stream ended before its last chunk"
+                    ),
+                },
+                ext: None,
+            };
+            journal_event(journal.as_deref(), run_id, &ev);
+            trace.push(ev.clone());
+            send_to_caller(to_caller_tx, batcher.as_mut(), ev).await;
+        }
+
+        if let Some(ev) = batcher.as_mut().and_then(batching::DeltaBatcher::flush) {
+            let _ = to_caller_tx.send(ev).await;
+        }
+
+        let budget_violation = budget_guard
+            .as_ref()
+            .and_then(|g| g.violation().map(|v| (v.to_string(), g.usage().clone())));
+
+        // Final measurement: catches a backend that filled the workspace
+        // between checks, and feeds the staging usage metric.
+        if backend_error.is_none() && prepared.is_staged() {
+            let quota = workspace_quota
+                .clone()
+                .unwrap_or_else(|| WorkspaceQuota::new(u64::MAX));
+            backend_error = check_workspace_quota(&quota, prepared.path(), metrics);
+        }
+
+        // Last snapshot, with the outcome the receipt will carry.
+        if let Some(snapshots) = snapshots.as_mut() {
+            let (outcome, final_trace) = match (&backend_error, &receipt_opt) {
+                (None, Some(r)) if !r.trace.is_empty() => (r.outcome.clone(), &r.trace),
+                (None, Some(r)) => (r.outcome.clone(), &trace),
+                (None, None) if budget_violation.is_some() => (Outcome::Partial, &trace),
+                _ => (Outcome::Failed, &trace),
+            };
+            snapshots.publish(final_trace, outcome, true, clock.now());
+        }
+
+        Dispatched {
+            trace,
+            receipt: receipt_opt,
+            error: backend_error,
+            retry_history,
+            streamed_artifacts,
+            budget_violation,
+        }
+    }
 
-            let mut receipt = receipt_opt.unwrap_or_else(|| {
-                // Backend crashed before returning a receipt — build via ReceiptBuilder.
-                let identity = backend.identity();
-                ReceiptBuilder::new(&identity.id)
+    /// Complete the backend's receipt with everything the runtime recorded,
+    /// hash it, and hand it to the journal, stores, chain and metrics.
+    async fn finalize_receipt(
+        self,
+        plan: RunPlan,
+        staged: StagedRun,
+        run: Dispatched,
+        summary_tx: Option<mpsc::Sender<AgentEvent>>,
+    ) -> Result<Receipt, RuntimeError> {
+        let RunServices {
+            metrics,
+            receipt_chain,
+            pipeline,
+            journal,
+            receipt_store,
+            clock,
+            summary_bus,
+            artifact_collector,
+            judge,
+            run_cache,
+            pricing,
+            middleware: mw_chain,
+            ..
+        } = self;
+        let RunPlan {
+            run_id,
+            backend,
+            backend_name,
+            work_order,
+            submitted_work_order,
+            prior_attempts,
+            model,
+            cache_hit,
+            cache_record,
+            rung_selections,
+            resolved_flags,
+            emulation_report,
+            translation_meta,
+            passthrough_record,
+            fidelity_report,
+            seed,
+            session,
+            submitter,
+            tenant,
+            api_key_id,
+            quota_charge,
+            rate_charge,
+            mw_ctx,
+            ..
+        } = plan;
+        let StagedRun {
+            run_start,
+            started_at,
+            prepared,
+            input_digest,
+            structured_output,
+            negotiation_result,
+            ..
+        } = staged;
+        let Dispatched {
+            trace,
+            receipt: receipt_opt,
+            retry_history,
+            streamed_artifacts,
+            budget_violation,
+            ..
+        } = run;
+
+        let mut receipt = receipt_opt.unwrap_or_else(|| {
+            let identity = backend.identity();
+            if let Some((_, usage)) = &budget_violation {
+                // Stopped over budget: keep what the backend produced.
+                return ReceiptBuilder::new(&identity.id)
                     .backend_version(identity.backend_version.unwrap_or_default())
                     .adapter_version(identity.adapter_version.unwrap_or_default())
                     .capabilities(backend.capabilities())
                     .run_id(run_id)
                     .work_order_id(work_order.id)
-                    .outcome(Outcome::Failed)
-                    .usage_raw(serde_json::json!({"error": "no receipt"}))
-                    .build()
+                    .started_at(started_at)
+                    .finished_at(clock.now())
+                    .outcome(Outcome::Partial)
+                    .usage(usage.clone())
+                    .build();
+            }
+            // Backend crashed before returning a receipt — build via ReceiptBuilder.
+            ReceiptBuilder::new(&identity.id)
+                .backend_version(identity.backend_version.unwrap_or_default())
+                .adapter_version(identity.adapter_version.unwrap_or_default())
+                .capabilities(backend.capabilities())
+                .run_id(run_id)
+                .work_order_id(work_order.id)
+                .started_at(started_at)
+                .finished_at(clock.now())
+                .outcome(Outcome::Failed)
+                .usage_raw(serde_json::json!({"error": "no receipt"}))
+                .build()
+        });
+
+        // A run stopped over budget says so next to any raw usage the
+        // backend reported.
+        if let Some((reason, _)) = budget_violation {
+            let marker = serde_json::json!({
+                "cancellation": cancel::CancellationReason::BudgetExhausted,
+                "reason": reason,
             });
+            usage_raw_object(&mut receipt).insert(budget::BUDGET_EXCEEDED_KEY.to_string(), marker);
+        }
 
-            // If backend didn't include a trace, attach what we observed.
-            if receipt.trace.is_empty() {
-                receipt.trace = trace;
-            }
+        // If backend didn't include a trace, attach what we observed.
+        // Artifact chunks are registered below, not kept in the trace.
+        if receipt.trace.is_empty() {
+            receipt.trace = trace;
+        } else {
+            receipt
+                .trace
+                .retain(|e| !matches!(e.kind, AgentEventKind::ArtifactChunk { .. }));
+        }
+
+        // Recover cache counts the backend only reported in raw usage,
+        // so they are priced and reported like the other counters.
+        receipt.usage.fill_cache_from_raw(&receipt.usage_raw);
+
+        // Price the run if the backend did not report a cost.
+        pricing.apply(&mut receipt, work_order.config.model.as_deref());
+
+        // Fill verification if missing.
+        if receipt.verification.git_diff.is_none() {
+            receipt.verification.git_diff = WorkspaceManager::git_diff(prepared.path());
+        }
+        if receipt.verification.git_status.is_none() {
+            receipt.verification.git_status = WorkspaceManager::git_status(prepared.path());
+        }
+        if receipt.verification.input_digest.is_none() {
+            receipt.verification.input_digest = input_digest;
+        }
 
-            // Fill verification if missing.
-            if receipt.verification.git_diff.is_none() {
-                receipt.verification.git_diff = WorkspaceManager::git_diff(prepared.path());
+        // Describe the files the run left behind.
+        if let Some(collector) = &artifact_collector {
+            match collector.collect(prepared.path()) {
+                Ok(records) => artifacts::ArtifactCollector::attach(&records, &mut receipt),
+                Err(e) => {
+                    warn!(target: "abp.runtime", error=%e, "failed to collect artifacts");
+                }
             }
-            if receipt.verification.git_status.is_none() {
-                receipt.verification.git_status = WorkspaceManager::git_status(prepared.path());
+        }
+
+        // Register the artifacts the backend streamed.
+        artifact_stream::StreamedArtifact::attach(&streamed_artifacts, &mut receipt);
+
+        // Score the output with the judge backend, if one is configured.
+        if let Some((judge_backend, config)) = judge
+            && let Some(record) =
+                judge::judge_run(&config, judge_backend, run_id, &work_order.task, &receipt).await
+        {
+            if let judge::JudgeRecord::Failed { error, .. } = &record {
+                warn!(target: "abp.runtime", error=%error, "judge could not score run");
             }
+            record.attach(&mut receipt);
+        }
 
-            // Record emulation report in receipt metadata if emulation was applied.
-            if let Some(ref emu_report) = emulation_report
-                && let (false, Ok(report_value)) =
-                    (emu_report.is_empty(), serde_json::to_value(emu_report))
+        // Record how the run used the cache.
+        if let Some(record) = &cache_record {
+            record.attach(&mut receipt);
+        }
+
+        // Record emulation report in receipt metadata if emulation was applied.
+        if let Some(ref emu_report) = emulation_report
+            && let (false, Ok(mut report_value)) =
+                (emu_report.is_empty(), serde_json::to_value(emu_report))
+        {
+            if let Some(emulator) = &structured_output
+                && let Some(obj) = report_value.as_object_mut()
             {
-                if let Some(obj) = receipt.usage_raw.as_object_mut() {
-                    obj.insert("emulation".to_string(), report_value);
-                } else {
-                    receipt.usage_raw = serde_json::json!({
-                        "original": receipt.usage_raw,
-                        "emulation": report_value,
-                    });
-                }
+                let text = pipeline::final_assistant_text(&receipt.trace).unwrap_or_default();
+                let result = emulator.process(&text);
+                obj.insert(
+                    "fidelity_notes".to_string(),
+                    serde_json::json!(result.fidelity_notes()),
+                );
+                obj.insert(
+                    "structured_output".to_string(),
+                    serde_json::json!({
+                        "schema": emulator.name(),
+                        "valid": result.is_valid(),
+                        "value": result.value,
+                        "repairs": result.repairs,
+                        "violations": result.violations,
+                    }),
+                );
             }
+            usage_raw_object(&mut receipt).insert("emulation".to_string(), report_value);
+        }
+
+        // Record capability negotiation result in receipt metadata.
+        if let Some(ref neg_result) = negotiation_result
+            && let Ok(neg_value) = serde_json::to_value(neg_result)
+        {
+            usage_raw_object(&mut receipt).insert("capability_negotiation".to_string(), neg_value);
+        }
+
+        // Record dialect translation metadata in receipt.
+        if let Some(ref tr) = translation_meta {
+            let translation_value = serde_json::json!({
+                "source_dialect": tr.from.label(),
+                "target_dialect": tr.to.label(),
+                "translation_mode": tr.mode.to_string(),
+                "capability_gaps": tr.gaps.iter().map(|g| serde_json::json!({
+                    "feature": format!("{:?}", g.feature),
+                    "source": g.source.label(),
+                    "target": g.target.label(),
+                    "description": g.description,
+                })).collect::<Vec<_>>(),
+            });
+            usage_raw_object(&mut receipt)
+                .insert("dialect_translation".to_string(), translation_value);
 
-            // Record capability negotiation result in receipt metadata.
-            if let Some(ref neg_result) = negotiation_result
-                && let Ok(neg_value) = serde_json::to_value(neg_result)
-                && let Some(obj) = receipt.usage_raw.as_object_mut()
+            // Audit trail of concrete transformations, so users can see
+            // exactly how the backend's prompt differs from the request.
+            if tr.mode != TranslationMode::Passthrough
+                && let Ok(log_value) = serde_json::to_value(&tr.log)
             {
-                obj.insert("capability_negotiation".to_string(), neg_value);
+                usage_raw_object(&mut receipt).insert("translation_log".to_string(), log_value);
             }
+        }
 
-            // Record dialect translation metadata in receipt.
-            if let Some(ref tr) = translation_meta {
-                let translation_value = serde_json::json!({
-                    "source_dialect": tr.from.label(),
-                    "target_dialect": tr.to.label(),
-                    "translation_mode": tr.mode.to_string(),
-                    "capability_gaps": tr.gaps.iter().map(|g| serde_json::json!({
-                        "feature": format!("{:?}", g.feature),
-                        "source": g.source.label(),
-                        "target": g.target.label(),
-                        "description": g.description,
-                    })).collect::<Vec<_>>(),
-                });
-                if let Some(obj) = receipt.usage_raw.as_object_mut() {
-                    obj.insert("dialect_translation".to_string(), translation_value);
-                } else {
-                    receipt.usage_raw = serde_json::json!({
-                        "original": receipt.usage_raw,
-                        "dialect_translation": translation_value,
-                    });
-                }
+        // Record the fidelity assessment so degradation is never silent.
+        if !fidelity_report.is_lossless()
+            && let Ok(val) = serde_json::to_value(&fidelity_report)
+        {
+            usage_raw_object(&mut receipt).insert("fidelity".to_string(), val);
+        }
+
+        // Record the nondeterministic inputs of a seeded run for replay.
+        if let Some(seed) = seed
+            && let Ok(val) =
+                serde_json::to_value(replay::DeterminismRecord::capture(seed, &receipt))
+        {
+            usage_raw_object(&mut receipt).insert(replay::DETERMINISM_KEY.to_string(), val);
+        }
+
+        // Record which session (and fork lineage) the run belongs to.
+        if let Some(record) = &session
+            && let Ok(val) = serde_json::to_value(record)
+        {
+            usage_raw_object(&mut receipt).insert(session::SESSION_KEY.to_string(), val);
+        }
+
+        // Record who and what submitted the run.
+        if let Some(submitter) = &submitter
+            && let Ok(val) = serde_json::to_value(submitter)
+        {
+            usage_raw_object(&mut receipt)
+                .insert(abp_core::submitter::SUBMITTER_KEY.to_string(), val);
+        }
+
+        if let Some(val) = submitted_work_order {
+            usage_raw_object(&mut receipt).insert(replay::WORK_ORDER_KEY.to_string(), val);
+        }
+
+        // Record who is billed for the run and, unless the backend
+        // reported it, the model that was requested.
+        {
+            let obj = usage_raw_object(&mut receipt);
+            if let Some(tenant) = &tenant {
+                obj.insert("tenant".to_string(), serde_json::json!(tenant));
+            }
+            if let Some(api_key_id) = &api_key_id {
+                obj.insert(
+                    quota::API_KEY_USAGE_KEY.to_string(),
+                    serde_json::json!(api_key_id),
+                );
             }
+            if let Some(model) = &model {
+                obj.entry("model")
+                    .or_insert_with(|| serde_json::json!(model));
+            }
+        }
 
-            // Build and record combined negotiation result.
-            {
-                let combined = match &negotiation_result {
-                    Some(neg) => negotiate::NegotiationResult::from_negotiation(
-                        neg,
-                        emulation_report.as_ref(),
-                    ),
-                    None => negotiate::NegotiationResult::all_native(vec![]),
-                };
-                if let Ok(val) = serde_json::to_value(&combined) {
-                    if let Some(obj) = receipt.usage_raw.as_object_mut() {
-                        obj.insert("negotiation_result".to_string(), val);
-                    }
+        // Build and record combined negotiation result.
+        {
+            let combined = match &negotiation_result {
+                Some(neg) => {
+                    negotiate::NegotiationResult::from_negotiation(neg, emulation_report.as_ref())
                 }
+                None => negotiate::NegotiationResult::all_native(vec![]),
+            };
+            if let Ok(val) = serde_json::to_value(&combined) {
+                usage_raw_object(&mut receipt).insert("negotiation_result".to_string(), val);
             }
+        }
 
-            // Ensure receipt hash is present and consistent via abp-receipt.
-            receipt.receipt_sha256 = Some(
-                abp_receipt::compute_hash(&receipt)
-                    .context("hash receipt")
-                    .map_err(RuntimeError::BackendFailed)?,
+        // Record the backends a fallback run tried before this one.
+        if !prior_attempts.is_empty() {
+            let mut attempts = prior_attempts;
+            attempts.push(execution::FallbackAttempt::succeeded(&backend_name));
+            usage_raw_object(&mut receipt).insert(
+                execution::FALLBACK_ATTEMPTS_KEY.to_string(),
+                serde_json::json!(attempts),
             );
+        }
 
-            // Append to the runtime's receipt chain for multi-step tracking.
-            {
-                let mut chain = receipt_chain.lock().await;
-                // Best-effort: log but don't fail the run if chain push fails.
-                if let Err(e) = chain.push(receipt.clone()) {
-                    warn!(target: "abp.runtime", error=%e, "failed to append receipt to chain");
-                }
+        // Record the failed attempts that were retried on this backend.
+        if !retry_history.is_empty() {
+            usage_raw_object(&mut receipt).insert(
+                retry::RETRY_HISTORY_KEY.to_string(),
+                serde_json::json!(retry_history),
+            );
+        }
+
+        // Record which rung each capability ladder settled on.
+        ladder::record(&mut receipt, &rung_selections);
+
+        // Record the forwarded request digest and raw response count.
+        if let Some(record) = passthrough_record {
+            record.attach(&mut receipt);
+        }
+
+        // Normalize provider request ids (OpenAI `system_fingerprint`,
+        // Anthropic request ids, ...) into a `provenance` block so the
+        // output can be traced back to the exact provider call.
+        abp_receipt::provenance::record(&mut receipt);
+
+        // Explain behaviour differences by the flags this run used.
+        resolved_flags.record(&mut receipt);
+
+        // Ensure receipt hash is present and consistent via abp-receipt.
+        receipt.receipt_sha256 = Some(
+            abp_receipt::compute_hash(&receipt)
+                .map_err(|e| RuntimeError::internal("hash receipt", e))?,
+        );
+
+        // Compact only after hashing the full trace, so the compaction
+        // record keeps the original hash; `compact` re-hashes the result.
+        if resolved_flags.is_enabled(flags::TRACE_COMPACTION) {
+            receipt = receipt
+                .compact(&abp_core::compact::CompactionPolicy::default())
+                .map_err(|e| RuntimeError::internal("compact receipt", e))?;
+        }
+
+        // Journal the final receipt before handing it out, so a crash
+        // from here on still leaves a recoverable record.
+        if let Some(j) = &journal
+            && let Err(e) = j.finalize(&receipt)
+        {
+            warn!(target: "abp.runtime", error=%e, "failed to journal final receipt");
+        }
+
+        // A cached run cost nothing, so it is not billed again.
+        if let Some(charge) = quota_charge
+            && !cache_hit
+        {
+            charge.settle(&receipt, started_at);
+        }
+        if let Some(charge) = rate_charge {
+            charge.settle(&receipt.usage);
+        }
+
+        // Keep a completed run for the next identical one.
+        if let (Some(run_cache), Some(record)) = (&run_cache, &cache_record)
+            && let Err(e) = run_cache.store(&record.key, &receipt)
+        {
+            warn!(target: "abp.runtime", error=%e, "failed to store receipt in run cache");
+        }
+
+        if let Some(store) = &receipt_store
+            && let Err(e) = store.store(&receipt).await
+        {
+            warn!(target: "abp.runtime", error=%e, "failed to persist receipt");
+        }
+
+        // Append to the runtime's receipt chain for multi-step tracking.
+        {
+            let mut chain = receipt_chain.lock().await;
+            // Best-effort: log but don't fail the run if chain push fails.
+            if let Err(e) = chain.push(receipt.clone()) {
+                warn!(target: "abp.runtime", error=%e, "failed to append receipt to chain");
             }
+        }
 
-            // Record telemetry.
-            let duration_ms = run_start.elapsed().as_millis() as u64;
-            let success = matches!(receipt.outcome, Outcome::Complete | Outcome::Partial);
-            let event_count = receipt.trace.len() as u64;
-            metrics.record_run(duration_ms, success, event_count);
-
-            // Run middleware after_run hooks (errors are collected, not fatal).
-            if !mw_chain.is_empty() {
-                let after_errors = mw_chain
-                    .run_after(&work_order, &mw_ctx, Some(&receipt))
-                    .await;
-                for e in &after_errors {
-                    warn!(target: "abp.runtime.middleware", error=%e, "after_run hook error");
-                }
+        // Record telemetry.
+        let duration_ms = elapsed_ms(clock.as_ref(), run_start);
+        let success = matches!(receipt.outcome, Outcome::Complete | Outcome::Partial);
+        let event_count = receipt.trace.len() as u64;
+        metrics.record_run(duration_ms, success, event_count);
+        // A cached run cost nothing, so it is not counted again.
+        if !cache_hit {
+            let model = pricing::receipt_model(&receipt)
+                .or(work_order.config.model.as_deref())
+                .unwrap_or(telemetry::UNKNOWN_MODEL);
+            metrics.record_cost(&backend_name, model, &receipt.usage);
+        }
+        if let Some(delivery) = receipt.usage_raw.get("event_delivery") {
+            let count = |k: &str| delivery.get(k).and_then(|v| v.as_u64()).unwrap_or(0);
+            metrics.record_event_delivery(count("duplicates"), count("gaps"), count("missing"));
+        }
+
+        // Run middleware after_run hooks (errors are collected, not fatal).
+        if !mw_chain.is_empty() {
+            let after_errors = mw_chain
+                .run_after(&work_order, &mw_ctx, Some(&receipt))
+                .await;
+            for e in &after_errors {
+                warn!(target: "abp.runtime.middleware", error=%e, "after_run hook error");
             }
+        }
 
-            Ok(receipt)
-        });
+        if let (Some(tx), Some(bus)) = (summary_tx, &summary_bus) {
+            let kind = summary::receipt_summary(&receipt, duration_ms);
+            let ev = summary::summary_event(kind, clock.now());
+            send_summary(&tx, bus, pipeline.as_ref(), run_id, ev).await;
+        }
 
-        Ok(RunHandle {
-            run_id,
-            events: ReceiverStream::new(to_caller_rx),
-            receipt,
-        })
+        Ok(receipt)
     }
 }
 
@@ -952,6 +3228,204 @@ pub fn infer_dialect_from_backend(backend_name: &str) -> Option<Dialect> {
     }
 }
 
+/// How often a running backend's staged workspace is measured against the
+/// runtime's workspace quota.
+pub const QUOTA_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// `receipt.usage_raw` as an object to record run metadata in.
+///
+/// A backend that reported raw usage as something other than an object
+/// keeps it under `"original"`, so nothing the runtime adds is dropped.
+fn usage_raw_object(receipt: &mut Receipt) -> &mut serde_json::Map<String, serde_json::Value> {
+    let raw = &mut receipt.usage_raw;
+    if !raw.is_object() {
+        *raw = serde_json::json!({ "original": raw.take() });
+    }
+    let serde_json::Value::Object(obj) = raw else {
+        unreachable!("usage_raw was just wrapped in an object");
+    };
+    obj
+}
+
+/// Measure the workspace at `root`, record its size, and return an error if
+/// it exceeds `quota`.
+fn check_workspace_quota(
+    quota: &WorkspaceQuota,
+    root: &std::path::Path,
+    metrics: &RunMetrics,
+) -> Option<RuntimeError> {
+    let status = match quota.check(root) {
+        Ok(status) => status,
+        Err(e) => {
+            warn!(target: "abp.runtime", error=%e, "failed to measure workspace");
+            return None;
+        }
+    };
+    metrics.record_workspace_usage(status.used_bytes);
+    if !status.exceeded {
+        return None;
+    }
+    metrics.record_quota_exceeded();
+    Some(RuntimeError::Classified(
+        abp_error::AbpError::new(
+            abp_error::ErrorCode::ExecutionWorkspaceError,
+            format!(
+                "workspace disk quota exceeded: {} of {} bytes used",
+                status.used_bytes, status.limit_bytes
+            ),
+        )
+        .with_context("used_bytes", status.used_bytes)
+        .with_context("limit_bytes", status.limit_bytes),
+    ))
+}
+
+/// `PolicyDenied` error for a tool call that reaches a host the work order's
+/// network rules deny.
+///
+/// Like the sidecar adapters, a call's target is its `url`, `uri`, or
+/// `endpoint` input, or a `host` with an optional `port`.
+fn check_network_egress(policy: &PolicyEngine, ev: &AgentEvent) -> Option<RuntimeError> {
+    let AgentEventKind::ToolCall {
+        tool_name, input, ..
+    } = &ev.kind
+    else {
+        return None;
+    };
+    if policy.network().is_empty() {
+        return None;
+    }
+    let url = ["url", "uri", "endpoint"]
+        .iter()
+        .find_map(|k| input.get(*k).and_then(serde_json::Value::as_str));
+    let (decision, host, port) = if let Some(url) = url {
+        let (host, port) = abp_policy::network::url_target(url).unwrap_or((url.to_string(), None));
+        (policy.can_fetch_url(url), host, port)
+    } else {
+        let host = input.get("host").and_then(serde_json::Value::as_str)?;
+        let port = input
+            .get("port")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|p| u16::try_from(p).ok());
+        (policy.can_connect(host, port), host.to_string(), port)
+    };
+    if decision.allowed {
+        return None;
+    }
+    let reason = decision
+        .reason
+        .unwrap_or_else(|| "network access denied".into());
+    let mut err = abp_error::AbpError::new(abp_error::ErrorCode::PolicyDenied, reason)
+        .with_context("host", host)
+        .with_context("tool", tool_name);
+    if let Some(port) = port {
+        err = err.with_context("port", port);
+    }
+    Some(RuntimeError::Classified(err))
+}
+
+/// Feed a backend event to the run's artifact assembler.
+///
+/// Returns the event to handle as usual: `ev` itself when it is not an
+/// artifact chunk, a warning when the chunk was rejected, and nothing when
+/// the chunk was absorbed.
+fn absorb_artifact_chunk(
+    assembler: &mut artifact_stream::ArtifactAssembler,
+    ev: AgentEvent,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<AgentEvent> {
+    match assembler.absorb(&ev) {
+        artifact_stream::Absorbed::NotAChunk => Some(ev),
+        artifact_stream::Absorbed::Pending => None,
+        artifact_stream::Absorbed::Completed(artifact) => {
+            debug!(
+                target: "abp.runtime",
+                name = %artifact.name,
+                size = artifact.size,
+                "assembled streamed artifact"
+            );
+            None
+        }
+        artifact_stream::Absorbed::Rejected(message) => {
+            warn!(target: "abp.runtime", %message, "rejected streamed artifact");
+            Some(AgentEvent {
+                ts: now,
+                kind: AgentEventKind::Warning { message },
+                ext: None,
+            })
+        }
+    }
+}
+
+/// Forward an event to the caller, merging assistant deltas when a batcher is
+/// attached and the caller is falling behind.
+async fn send_to_caller(
+    tx: &mpsc::Sender<AgentEvent>,
+    batcher: Option<&mut batching::DeltaBatcher>,
+    ev: AgentEvent,
+) {
+    let Some(batcher) = batcher else {
+        let _ = tx.send(ev).await;
+        return;
+    };
+    batcher.observe_backlog(tx.max_capacity() - tx.capacity(), tx.max_capacity());
+    for ev in batcher.push(ev) {
+        let _ = tx.send(ev).await;
+    }
+}
+
+/// Send a run summary to the caller and publish it on the runtime's bus.
+async fn send_summary(
+    tx: &mpsc::Sender<AgentEvent>,
+    bus: &bus::EventBus,
+    pipeline: Option<&abp_stream::StreamPipeline>,
+    run_id: Uuid,
+    ev: AgentEvent,
+) {
+    let Some(ev) = stream::apply_pipeline(pipeline, ev) else {
+        return;
+    };
+    bus.publish(summary::bus_event(&ev, run_id));
+    let _ = tx.send(ev).await;
+}
+
+/// Milliseconds elapsed on `clock` since `start`.
+pub(crate) fn elapsed_ms(clock: &dyn Clock, start: std::time::Instant) -> u64 {
+    clock.instant().saturating_duration_since(start).as_millis() as u64
+}
+
+/// Best-effort append of a forwarded event to the run's journal entry.
+fn journal_event(journal: Option<&ReceiptJournal>, run_id: Uuid, event: &AgentEvent) {
+    if let Some(j) = journal
+        && let Err(e) = j.record_event(run_id, event)
+    {
+        debug!(target: "abp.runtime", error=%e, "failed to journal event");
+    }
+}
+
+/// Run one turn of tool calls with `dispatcher` and forward each result as
+/// if the backend had sent it: through the stream pipeline, into the journal
+/// and the trace, and on to the caller.
+#[allow(clippy::too_many_arguments)]
+async fn run_tool_turn(
+    dispatcher: &tools::ToolDispatcher,
+    calls: &[AgentEvent],
+    clock: &dyn Clock,
+    pipeline: Option<&stream::StreamPipeline>,
+    journal: Option<&ReceiptJournal>,
+    run_id: Uuid,
+    trace: &mut Vec<AgentEvent>,
+    tx: &mpsc::Sender<AgentEvent>,
+    mut batcher: Option<&mut batching::DeltaBatcher>,
+) {
+    for res in dispatcher.dispatch(calls, || clock.now()).await {
+        if let Some(res) = stream::apply_pipeline(pipeline, res) {
+            journal_event(journal, run_id, &res);
+            trace.push(res.clone());
+            send_to_caller(tx, batcher.as_deref_mut(), res).await;
+        }
+    }
+}
+
 /// Resolve the target dialect for a backend: first check the projection matrix
 /// for a registered dialect, then fall back to name inference.
 fn resolve_backend_dialect(
@@ -1042,6 +3516,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn internal_errors_are_not_blamed_on_the_backend() {
+        let err = RuntimeError::internal("hash receipt", std::io::Error::other("boom"));
+        assert_eq!(err.error_code(), abp_error::ErrorCode::Internal);
+        assert!(err.to_string().contains("hash receipt: boom"));
+        assert!(!err.is_retryable());
+        assert!(!execution::should_fall_back(&err));
+    }
+
     #[test]
     fn abp_error_converts_to_runtime_error() {
         let abp_err = abp_error::AbpError::new(abp_error::ErrorCode::BackendTimeout, "timed out");
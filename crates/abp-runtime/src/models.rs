@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Models backends advertise at runtime.
+//!
+//! [`Runtime::refresh_models`](crate::Runtime::refresh_models) asks every
+//! registered backend for its [`list_models`](abp_integrations::Backend::list_models)
+//! and stores the answers in a [`ModelDirectory`](crate::models::ModelDirectory).
+//! [`Runtime::spawn_model_refresh`](crate::Runtime::spawn_model_refresh) keeps
+//! the directory current in the background.
+//!
+//! Once a backend has advertised a non-empty list, work orders naming a model
+//! it does not list are rejected with
+//! [`BackendModelNotFound`](abp_error::ErrorCode::BackendModelNotFound), and
+//! the listed model's capabilities refine the backend manifest during
+//! negotiation. Backends that advertise nothing are not restricted.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use abp_core::CapabilityManifest;
+use abp_error::{AbpError, ErrorCode};
+use abp_integrations::ModelInfo;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The models one backend advertised at its last refresh.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelListing {
+    /// Advertised models, in the order the backend reported them.
+    pub models: Vec<ModelInfo>,
+    /// When the listing was fetched.
+    pub refreshed_at: DateTime<Utc>,
+}
+
+impl ModelListing {
+    /// The advertised entry for `model`, if listed.
+    #[must_use]
+    pub fn find(&self, model: &str) -> Option<&ModelInfo> {
+        self.models.iter().find(|m| m.matches(model))
+    }
+
+    /// Whether a work order naming `model` may run on this backend: the
+    /// model is listed, or the backend advertises no models at all.
+    #[must_use]
+    pub fn serves(&self, model: &str) -> bool {
+        self.models.is_empty() || self.find(model).is_some()
+    }
+}
+
+/// Latest [`ModelListing`] of each backend, by registered name.
+///
+/// # Examples
+///
+/// ```
+/// use abp_integrations::ModelInfo;
+/// use abp_runtime::models::ModelDirectory;
+///
+/// let directory = ModelDirectory::new();
+/// directory.update("ollama", vec![ModelInfo::new("llama3.1:8b")], chrono::Utc::now());
+///
+/// assert_eq!(directory.backends_serving("llama3.1:8b"), ["ollama"]);
+/// assert!(directory.check("ollama", Some("mistral")).is_err());
+/// // Backends that never advertised are unrestricted.
+/// assert!(directory.check("mock", Some("mistral")).is_ok());
+/// ```
+#[derive(Debug, Default)]
+pub struct ModelDirectory {
+    listings: Mutex<BTreeMap<String, ModelListing>>,
+}
+
+impl ModelDirectory {
+    /// An empty directory.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the listing of `backend`.
+    pub fn update(&self, backend: &str, models: Vec<ModelInfo>, refreshed_at: DateTime<Utc>) {
+        self.listings
+            .lock()
+            .expect("model directory mutex poisoned")
+            .insert(
+                backend.to_string(),
+                ModelListing {
+                    models,
+                    refreshed_at,
+                },
+            );
+    }
+
+    /// Forget the listing of `backend`. Returns `true` if it had one.
+    pub fn remove(&self, backend: &str) -> bool {
+        self.listings
+            .lock()
+            .expect("model directory mutex poisoned")
+            .remove(backend)
+            .is_some()
+    }
+
+    /// The last listing of `backend`, if it has been refreshed.
+    #[must_use]
+    pub fn listing(&self, backend: &str) -> Option<ModelListing> {
+        self.listings
+            .lock()
+            .expect("model directory mutex poisoned")
+            .get(backend)
+            .cloned()
+    }
+
+    /// Sorted names of the backends that advertise `model`.
+    #[must_use]
+    pub fn backends_serving(&self, model: &str) -> Vec<String> {
+        self.listings
+            .lock()
+            .expect("model directory mutex poisoned")
+            .iter()
+            .filter(|(_, listing)| listing.find(model).is_some())
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Check that `backend` serves `model`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorCode::BackendModelNotFound`] when the backend
+    /// advertised a non-empty list that does not include `model`.
+    pub fn check(&self, backend: &str, model: Option<&str>) -> Result<(), AbpError> {
+        let (Some(model), Some(listing)) = (model, self.listing(backend)) else {
+            return Ok(());
+        };
+        if listing.serves(model) {
+            return Ok(());
+        }
+        let available: Vec<&str> = listing.models.iter().map(|m| m.id.as_str()).collect();
+        Err(AbpError::new(
+            ErrorCode::BackendModelNotFound,
+            format!("backend '{backend}' does not serve model '{model}'"),
+        )
+        .with_context("backend", backend)
+        .with_context("model", model)
+        .with_context("available", available))
+    }
+
+    /// `base` refined by the capabilities `backend` advertises for `model`.
+    #[must_use]
+    pub fn manifest_for(
+        &self,
+        backend: &str,
+        base: &CapabilityManifest,
+        model: &str,
+    ) -> CapabilityManifest {
+        match self.listing(backend).as_ref().and_then(|l| l.find(model)) {
+            Some(info) => info.apply_to(base),
+            None => base.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::{Capability, SupportLevel};
+
+    #[test]
+    fn empty_listing_serves_everything() {
+        let directory = ModelDirectory::new();
+        directory.update("b", Vec::new(), Utc::now());
+        assert!(directory.check("b", Some("any")).is_ok());
+        assert!(directory.backends_serving("any").is_empty());
+    }
+
+    #[test]
+    fn unlisted_model_is_rejected_with_available_models() {
+        let directory = ModelDirectory::new();
+        directory.update("b", vec![ModelInfo::new("m1")], Utc::now());
+        let err = directory.check("b", Some("m2")).unwrap_err();
+        assert_eq!(err.code, ErrorCode::BackendModelNotFound);
+        assert_eq!(err.context["available"], serde_json::json!(["m1"]));
+        assert!(directory.check("b", None).is_ok());
+    }
+
+    #[test]
+    fn advertised_capabilities_refine_manifest() {
+        let directory = ModelDirectory::new();
+        directory.update(
+            "b",
+            vec![ModelInfo::new("m").capability(Capability::Vision, SupportLevel::Native)],
+            Utc::now(),
+        );
+        let mut base = CapabilityManifest::new();
+        base.insert(Capability::Streaming, SupportLevel::Native);
+        let manifest = directory.manifest_for("b", &base, "m");
+        assert_eq!(manifest.len(), 2);
+        assert_eq!(directory.manifest_for("b", &base, "other"), base);
+    }
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 //! Observability primitives: distributed-style tracing spans and a runtime observer
 //! that aggregates metrics and trace data.
+//!
+//! To ship finished runs to an OpenTelemetry backend, see [`otel`](crate::otel).
 
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -0,0 +1,504 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! OpenTelemetry export of receipts.
+//!
+//! [`receipt_spans`](crate::otel::receipt_spans) turns a finished
+//! [`Receipt`] into a span tree: one `abp.run` root span per run, a child
+//! span for every tool call (ending at its matching result), and a child span
+//! for every assistant message. Other trace events (warnings, errors, file
+//! changes, commands) become span events on the root. The run id is the
+//! trace id, so every span of a run lands in the same trace.
+//!
+//! [`OtlpExporter`](crate::otel::OtlpExporter) ships those spans to any
+//! collector that accepts OTLP over HTTP with JSON encoding (`/v1/traces`).
+
+use std::collections::BTreeMap;
+
+use abp_core::{AgentEvent, AgentEventKind, Outcome, Receipt};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+/// Default OTLP/HTTP endpoint of a local collector.
+pub const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4318";
+
+/// Instrumentation scope name reported on exported spans.
+pub const SCOPE_NAME: &str = "abp-runtime";
+
+/// Kind of an exported span, as defined by OTLP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(into = "u8")]
+pub enum OtelSpanKind {
+    /// Work inside the runtime.
+    Internal,
+    /// An outgoing call, such as a tool invocation.
+    Client,
+}
+
+impl From<OtelSpanKind> for u8 {
+    fn from(kind: OtelSpanKind) -> Self {
+        match kind {
+            OtelSpanKind::Internal => 1,
+            OtelSpanKind::Client => 3,
+        }
+    }
+}
+
+/// Status code of an exported span, as defined by OTLP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtelStatusCode {
+    /// No status recorded.
+    Unset,
+    /// The operation succeeded.
+    Ok,
+    /// The operation failed.
+    Error,
+}
+
+impl OtelStatusCode {
+    fn code(self) -> u8 {
+        match self {
+            Self::Unset => 0,
+            Self::Ok => 1,
+            Self::Error => 2,
+        }
+    }
+}
+
+/// A timestamped event attached to a span.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtelSpanEvent {
+    /// Event name, e.g. `warning`.
+    pub name: String,
+    /// When it happened.
+    pub time: DateTime<Utc>,
+    /// Event attributes.
+    pub attributes: BTreeMap<String, Value>,
+}
+
+/// One span derived from a receipt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtelSpan {
+    /// 32 hex characters; the run id.
+    pub trace_id: String,
+    /// 16 hex characters, stable for a given run and position.
+    pub span_id: String,
+    /// Parent span id; `None` for the run's root span.
+    pub parent_span_id: Option<String>,
+    /// Span name.
+    pub name: String,
+    /// Span kind.
+    pub kind: OtelSpanKind,
+    /// Start time.
+    pub start: DateTime<Utc>,
+    /// End time.
+    pub end: DateTime<Utc>,
+    /// Span attributes. Values are strings, integers, floats, or booleans.
+    pub attributes: BTreeMap<String, Value>,
+    /// Span events.
+    pub events: Vec<OtelSpanEvent>,
+    /// Status code.
+    pub status: OtelStatusCode,
+    /// Status message for error spans.
+    pub status_message: Option<String>,
+}
+
+impl OtelSpan {
+    /// Encode the span as an OTLP/JSON `Span` object.
+    #[must_use]
+    pub fn to_otlp_json(&self) -> Value {
+        let mut span = json!({
+            "traceId": self.trace_id,
+            "spanId": self.span_id,
+            "name": self.name,
+            "kind": self.kind,
+            "startTimeUnixNano": unix_nanos(self.start),
+            "endTimeUnixNano": unix_nanos(self.end),
+            "attributes": otlp_attributes(&self.attributes),
+            "events": self.events.iter().map(|e| json!({
+                "name": e.name,
+                "timeUnixNano": unix_nanos(e.time),
+                "attributes": otlp_attributes(&e.attributes),
+            })).collect::<Vec<_>>(),
+            "status": { "code": self.status.code() },
+        });
+        if let Some(parent) = &self.parent_span_id {
+            span["parentSpanId"] = json!(parent);
+        }
+        if let Some(message) = &self.status_message {
+            span["status"]["message"] = json!(message);
+        }
+        span
+    }
+}
+
+/// Convert a receipt's trace into spans, root span first.
+#[must_use]
+pub fn receipt_spans(receipt: &Receipt) -> Vec<OtelSpan> {
+    let run_id = receipt.meta.run_id;
+    let trace_id = run_id.simple().to_string();
+    let root_id = span_id(&trace_id, "run");
+    let run_end = receipt.meta.finished_at.max(receipt.meta.started_at);
+
+    let mut root = OtelSpan {
+        trace_id: trace_id.clone(),
+        span_id: root_id.clone(),
+        parent_span_id: None,
+        name: "abp.run".into(),
+        kind: OtelSpanKind::Internal,
+        start: receipt.meta.started_at,
+        end: run_end,
+        attributes: run_attributes(receipt),
+        events: Vec::new(),
+        status: match receipt.outcome {
+            Outcome::Failed => OtelStatusCode::Error,
+            Outcome::Complete | Outcome::Partial => OtelStatusCode::Ok,
+        },
+        status_message: None,
+    };
+
+    let mut children: Vec<OtelSpan> = Vec::new();
+    // Indices into `children` of tool calls still waiting for a result.
+    let mut open_tools: Vec<(usize, Option<String>)> = Vec::new();
+    let mut last_ts = receipt.meta.started_at;
+
+    for (i, event) in receipt.trace.iter().enumerate() {
+        match &event.kind {
+            AgentEventKind::ToolCall {
+                tool_name,
+                tool_use_id,
+                parent_tool_use_id,
+                ..
+            } => {
+                let parent = parent_tool_use_id
+                    .as_deref()
+                    .and_then(|p| find_tool(&children, &open_tools, Some(p), None))
+                    .map_or_else(|| root_id.clone(), |idx| children[idx].span_id.clone());
+                let mut attributes = BTreeMap::new();
+                attributes.insert("abp.tool.name".into(), json!(tool_name));
+                if let Some(id) = tool_use_id {
+                    attributes.insert("abp.tool.use_id".into(), json!(id));
+                }
+                open_tools.push((children.len(), tool_use_id.clone()));
+                children.push(OtelSpan {
+                    trace_id: trace_id.clone(),
+                    span_id: span_id(&trace_id, &i.to_string()),
+                    parent_span_id: Some(parent),
+                    name: format!("tool_call {tool_name}"),
+                    kind: OtelSpanKind::Client,
+                    start: event.ts,
+                    end: run_end,
+                    attributes,
+                    events: Vec::new(),
+                    status: OtelStatusCode::Unset,
+                    status_message: None,
+                });
+            }
+            AgentEventKind::ToolResult {
+                tool_name,
+                tool_use_id,
+                is_error,
+                ..
+            } => {
+                match find_tool(
+                    &children,
+                    &open_tools,
+                    tool_use_id.as_deref(),
+                    Some(tool_name),
+                ) {
+                    Some(idx) => {
+                        open_tools.retain(|(open, _)| *open != idx);
+                        let span = &mut children[idx];
+                        span.end = event.ts.max(span.start);
+                        span.status = if *is_error {
+                            OtelStatusCode::Error
+                        } else {
+                            OtelStatusCode::Ok
+                        };
+                    }
+                    None => root.events.push(span_event("tool_result", event)),
+                }
+            }
+            AgentEventKind::AssistantMessage { text } => {
+                let mut attributes = BTreeMap::new();
+                attributes.insert("abp.message.chars".into(), json!(text.chars().count()));
+                children.push(OtelSpan {
+                    trace_id: trace_id.clone(),
+                    span_id: span_id(&trace_id, &i.to_string()),
+                    parent_span_id: Some(root_id.clone()),
+                    name: "assistant_message".into(),
+                    kind: OtelSpanKind::Internal,
+                    start: last_ts.min(event.ts),
+                    end: event.ts,
+                    attributes,
+                    events: Vec::new(),
+                    status: OtelStatusCode::Ok,
+                    status_message: None,
+                });
+            }
+            // Deltas are summarised by the assistant message they build.
+            AgentEventKind::AssistantDelta { .. } => {}
+            AgentEventKind::Error { message, .. } => {
+                if root.status_message.is_none() && root.status == OtelStatusCode::Error {
+                    root.status_message = Some(message.clone());
+                }
+                root.events.push(span_event("error", event));
+            }
+            AgentEventKind::RunStarted { .. } => root.events.push(span_event("run_started", event)),
+            AgentEventKind::RunCompleted { .. } => {
+                root.events.push(span_event("run_completed", event));
+            }
+            AgentEventKind::FileChanged { .. } => {
+                root.events.push(span_event("file_changed", event));
+            }
+            AgentEventKind::CommandExecuted { .. } => {
+                root.events.push(span_event("command_executed", event));
+            }
+            AgentEventKind::Progress { .. } => root.events.push(span_event("progress", event)),
+            AgentEventKind::Warning { .. } => root.events.push(span_event("warning", event)),
+            AgentEventKind::RunSummary { .. } => {
+                root.events.push(span_event("run_summary", event));
+            }
+            AgentEventKind::ArtifactChunk { .. } => {
+                root.events.push(span_event("artifact_chunk", event));
+            }
+        }
+        last_ts = event.ts;
+    }
+
+    let mut spans = Vec::with_capacity(children.len() + 1);
+    spans.push(root);
+    spans.extend(children);
+    spans
+}
+
+/// Build an OTLP/JSON `ExportTraceServiceRequest` for `receipts`.
+#[must_use]
+pub fn export_request(service_name: &str, receipts: &[Receipt]) -> Value {
+    let spans: Vec<Value> = receipts
+        .iter()
+        .flat_map(receipt_spans)
+        .map(|s| s.to_otlp_json())
+        .collect();
+    let mut resource = BTreeMap::new();
+    resource.insert("service.name".to_string(), json!(service_name));
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": otlp_attributes(&resource) },
+            "scopeSpans": [{
+                "scope": { "name": SCOPE_NAME, "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+/// Ships receipts as traces to an OTLP/HTTP collector.
+///
+/// # Examples
+///
+/// ```no_run
+/// use abp_runtime::otel::OtlpExporter;
+///
+/// # async fn demo(receipt: abp_core::Receipt) -> anyhow::Result<()> {
+/// let exporter = OtlpExporter::new("http://otel-collector:4318")
+///     .with_service_name("agent-backplane")
+///     .with_header("x-honeycomb-team", "secret");
+/// exporter.export(&[receipt]).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OtlpExporter {
+    endpoint: String,
+    service_name: String,
+    headers: BTreeMap<String, String>,
+    client: reqwest::Client,
+}
+
+impl Default for OtlpExporter {
+    fn default() -> Self {
+        Self::new(DEFAULT_OTLP_ENDPOINT)
+    }
+}
+
+impl OtlpExporter {
+    /// Export to the collector at `endpoint` (without the `/v1/traces`
+    /// path).
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            service_name: "agent-backplane".into(),
+            headers: BTreeMap::new(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Report spans under `service_name` (builder pattern).
+    #[must_use]
+    pub fn with_service_name(mut self, service_name: impl Into<String>) -> Self {
+        self.service_name = service_name.into();
+        self
+    }
+
+    /// Send `name: value` with every export, e.g. an API key (builder
+    /// pattern).
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// URL spans are posted to.
+    #[must_use]
+    pub fn traces_url(&self) -> String {
+        format!("{}/v1/traces", self.endpoint)
+    }
+
+    /// Post the spans of `receipts` in one request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the collector does not
+    /// answer with a success status.
+    pub async fn export(&self, receipts: &[Receipt]) -> Result<()> {
+        if receipts.is_empty() {
+            return Ok(());
+        }
+        let url = self.traces_url();
+        let mut request = self
+            .client
+            .post(&url)
+            .json(&export_request(&self.service_name, receipts));
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("post traces to {url}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("collector at {url} returned {status}: {body}");
+        }
+        Ok(())
+    }
+}
+
+fn run_attributes(receipt: &Receipt) -> BTreeMap<String, Value> {
+    let mut attrs = BTreeMap::new();
+    attrs.insert("abp.run_id".into(), json!(receipt.meta.run_id.to_string()));
+    attrs.insert(
+        "abp.work_order_id".into(),
+        json!(receipt.meta.work_order_id.to_string()),
+    );
+    attrs.insert("abp.backend.id".into(), json!(receipt.backend.id));
+    attrs.insert(
+        "abp.outcome".into(),
+        serde_json::to_value(&receipt.outcome).unwrap_or(Value::Null),
+    );
+    attrs.insert("abp.duration_ms".into(), json!(receipt.meta.duration_ms));
+    let usage = &receipt.usage;
+    let optional = [
+        (
+            "abp.usage.input_tokens",
+            usage.input_tokens.map(Value::from),
+        ),
+        (
+            "abp.usage.output_tokens",
+            usage.output_tokens.map(Value::from),
+        ),
+        (
+            "abp.usage.cost_usd",
+            usage.estimated_cost_usd.map(Value::from),
+        ),
+        ("abp.model", receipt.usage_raw.get("model").cloned()),
+        ("abp.tenant", receipt.usage_raw.get("tenant").cloned()),
+    ];
+    for (key, value) in optional {
+        if let Some(value) = value.filter(|v| !v.is_null()) {
+            attrs.insert(key.into(), value);
+        }
+    }
+    attrs
+}
+
+fn span_event(name: &str, event: &AgentEvent) -> OtelSpanEvent {
+    let mut attributes = BTreeMap::new();
+    let text = match &event.kind {
+        AgentEventKind::Warning { message }
+        | AgentEventKind::Error { message, .. }
+        | AgentEventKind::RunStarted { message }
+        | AgentEventKind::RunCompleted { message }
+        | AgentEventKind::Progress { message, .. } => Some(("message", message)),
+        AgentEventKind::FileChanged { path, .. } => Some(("path", path)),
+        AgentEventKind::CommandExecuted { command, .. } => Some(("command", command)),
+        AgentEventKind::ToolResult { tool_name, .. } => Some(("tool_name", tool_name)),
+        _ => None,
+    };
+    if let Some((key, value)) = text {
+        attributes.insert(key.into(), json!(value));
+    }
+    if let AgentEventKind::CommandExecuted {
+        exit_code: Some(code),
+        ..
+    } = &event.kind
+    {
+        attributes.insert("exit_code".into(), json!(code));
+    }
+    OtelSpanEvent {
+        name: name.into(),
+        time: event.ts,
+        attributes,
+    }
+}
+
+/// Open tool call matching `use_id`, else the latest open call to `name`.
+fn find_tool(
+    spans: &[OtelSpan],
+    open: &[(usize, Option<String>)],
+    use_id: Option<&str>,
+    name: Option<&str>,
+) -> Option<usize> {
+    if let Some(id) = use_id
+        && let Some((idx, _)) = open.iter().rev().find(|(_, u)| u.as_deref() == Some(id))
+    {
+        return Some(*idx);
+    }
+    let name = name?;
+    open.iter()
+        .rev()
+        .find(|(idx, u)| {
+            (use_id.is_none() || u.is_none())
+                && spans[*idx].attributes.get("abp.tool.name") == Some(&json!(name))
+        })
+        .map(|(idx, _)| *idx)
+}
+
+/// Stable 64-bit span id for a position within a trace.
+fn span_id(trace_id: &str, position: &str) -> String {
+    abp_core::sha256_hex(format!("{trace_id}:{position}").as_bytes())[..16].to_string()
+}
+
+fn unix_nanos(ts: DateTime<Utc>) -> String {
+    ts.timestamp_nanos_opt().unwrap_or_default().to_string()
+}
+
+fn otlp_attributes(attrs: &BTreeMap<String, Value>) -> Vec<Value> {
+    attrs
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::Bool(b) => json!({ "boolValue": b }),
+                Value::Number(n) if n.is_i64() || n.is_u64() => {
+                    json!({ "intValue": n.to_string() })
+                }
+                Value::Number(n) => json!({ "doubleValue": n.as_f64() }),
+                Value::String(s) => json!({ "stringValue": s }),
+                other => json!({ "stringValue": other.to_string() }),
+            };
+            json!({ "key": key, "value": value })
+        })
+        .collect()
+}
@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Partial receipts for runs still in progress.
+//!
+//! A receipt is only built once a run ends, which leaves dashboards watching
+//! hour-long runs with nothing structured to show. With
+//! [`Runtime::with_partial_receipts`](crate::Runtime::with_partial_receipts)
+//! the runtime publishes a [`PartialReceipt`](crate::partial::PartialReceipt)
+//! for every run at a fixed interval: the trace so far, the latest usage
+//! snapshot a backend reported under `ext["abp.usage"]`, and a provisional
+//! outcome. When the backend finishes, one last snapshot with
+//! [`done`](crate::partial::PartialReceipt::done) set carries the outcome the
+//! receipt will have.
+//!
+//! Snapshots from all runs go to every subscriber of
+//! [`Runtime::subscribe_partial_receipts`](crate::Runtime::subscribe_partial_receipts);
+//! filter on [`run_id`](crate::partial::PartialReceipt::run_id). A subscriber
+//! that falls behind misses snapshots, never runs: each one supersedes the
+//! last.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use abp_core::{AgentEvent, Outcome, UsageNormalized};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::budget::USAGE_EXT_KEY;
+
+/// Snapshots buffered per subscriber before the oldest are dropped.
+const FEED_CAPACITY: usize = 64;
+
+/// The state of a run's receipt while the run is still going.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialReceipt {
+    /// Run the snapshot belongs to.
+    pub run_id: Uuid,
+    /// Work order being executed.
+    pub work_order_id: Uuid,
+    /// Registered name of the backend running it.
+    pub backend: String,
+    /// Position of this snapshot among the run's snapshots, from 0.
+    pub seq: u64,
+    /// When the run started.
+    pub started_at: DateTime<Utc>,
+    /// When the snapshot was taken.
+    pub as_of: DateTime<Utc>,
+    /// Events recorded so far, as they will appear in the receipt trace.
+    pub trace: Vec<AgentEvent>,
+    /// Latest cumulative usage the backend reported.
+    pub usage: UsageNormalized,
+    /// [`Outcome::Partial`] while running; the expected final outcome once
+    /// [`done`](Self::done).
+    pub outcome: Outcome,
+    /// Whether the backend has finished and this is the last snapshot.
+    pub done: bool,
+}
+
+/// The latest usage snapshot in `trace`, or empty usage if there is none.
+#[must_use]
+pub fn latest_usage(trace: &[AgentEvent]) -> UsageNormalized {
+    trace
+        .iter()
+        .rev()
+        .filter_map(|ev| ev.ext.as_ref()?.get(USAGE_EXT_KEY))
+        .find_map(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Publishes partial receipts at a fixed interval.
+#[derive(Debug)]
+pub struct PartialReceiptFeed {
+    interval: Duration,
+    tx: broadcast::Sender<Arc<PartialReceipt>>,
+}
+
+impl PartialReceiptFeed {
+    /// A feed publishing a snapshot of each run every `interval`.
+    #[must_use]
+    pub fn new(interval: Duration) -> Self {
+        let (tx, _) = broadcast::channel(FEED_CAPACITY);
+        Self { interval, tx }
+    }
+
+    /// How often a running run is snapshotted.
+    #[must_use]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Receive snapshots of every run from now on.
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<PartialReceipt>> {
+        self.tx.subscribe()
+    }
+
+    /// Start tracking one run.
+    pub(crate) fn run(
+        self: &Arc<Self>,
+        run_id: Uuid,
+        work_order_id: Uuid,
+        backend: &str,
+        started_at: DateTime<Utc>,
+    ) -> RunSnapshots {
+        RunSnapshots {
+            feed: Arc::clone(self),
+            run_id,
+            work_order_id,
+            backend: backend.to_string(),
+            started_at,
+            seq: 0,
+        }
+    }
+}
+
+/// Snapshot publisher for a single run.
+pub(crate) struct RunSnapshots {
+    feed: Arc<PartialReceiptFeed>,
+    run_id: Uuid,
+    work_order_id: Uuid,
+    backend: String,
+    started_at: DateTime<Utc>,
+    seq: u64,
+}
+
+impl RunSnapshots {
+    pub(crate) fn interval(&self) -> Duration {
+        self.feed.interval
+    }
+
+    /// Publish the run's state as of `as_of`. Skipped when nobody listens.
+    pub(crate) fn publish(
+        &mut self,
+        trace: &[AgentEvent],
+        outcome: Outcome,
+        done: bool,
+        as_of: DateTime<Utc>,
+    ) {
+        if self.feed.tx.receiver_count() == 0 {
+            return;
+        }
+        let snapshot = PartialReceipt {
+            run_id: self.run_id,
+            work_order_id: self.work_order_id,
+            backend: self.backend.clone(),
+            seq: self.seq,
+            started_at: self.started_at,
+            as_of,
+            trace: trace.to_vec(),
+            usage: latest_usage(trace),
+            outcome,
+            done,
+        };
+        self.seq += 1;
+        let _ = self.feed.tx.send(Arc::new(snapshot));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::AgentEventKind;
+    use std::collections::BTreeMap;
+
+    fn event(usage: Option<u64>) -> AgentEvent {
+        AgentEvent {
+            ts: Utc::now(),
+            kind: AgentEventKind::AssistantDelta { text: "x".into() },
+            ext: usage.map(|tokens| {
+                BTreeMap::from([(
+                    USAGE_EXT_KEY.to_string(),
+                    serde_json::json!({ "output_tokens": tokens }),
+                )])
+            }),
+        }
+    }
+
+    #[test]
+    fn latest_usage_takes_the_last_snapshot() {
+        assert_eq!(latest_usage(&[]).output_tokens, None);
+        let trace = [event(Some(5)), event(Some(12)), event(None)];
+        assert_eq!(latest_usage(&trace).output_tokens, Some(12));
+    }
+
+    #[tokio::test]
+    async fn snapshots_are_numbered_per_run() {
+        let feed = Arc::new(PartialReceiptFeed::new(Duration::from_secs(1)));
+        let mut run = feed.run(Uuid::nil(), Uuid::nil(), "mock", Utc::now());
+        run.publish(&[], Outcome::Partial, false, Utc::now());
+
+        let mut rx = feed.subscribe();
+        run.publish(&[event(Some(3))], Outcome::Partial, false, Utc::now());
+        run.publish(&[], Outcome::Complete, true, Utc::now());
+
+        let first = rx.recv().await.unwrap();
+        assert_eq!((first.seq, first.usage.output_tokens), (0, Some(3)));
+        let last = rx.recv().await.unwrap();
+        assert_eq!((last.seq, last.done), (1, true));
+        assert_eq!(last.outcome, Outcome::Complete);
+    }
+}
@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Passthrough of raw vendor payloads for same-dialect routing.
+//!
+//! A frontend that speaks a provider's wire format (e.g. the OpenAI shim)
+//! can attach the caller's original request to the work order with
+//! [`attach_raw_request`](crate::passthrough::attach_raw_request). When the
+//! work order's dialect matches the backend's, the runtime forwards that
+//! request untouched and marks the work order `abp.mode = "passthrough"`, so
+//! the backend sends the exact payload to the provider instead of rebuilding
+//! it from the task text. Backends keep each raw provider response in
+//! `ext["raw_message"]` of the mapped events, and the receipt's
+//! `usage_raw["passthrough"]` block
+//! ([`PassthroughRecord`](crate::passthrough::PassthroughRecord)) records the
+//! request digest, so a receipt proves which bytes were sent.
+//!
+//! When the dialects differ, the raw request cannot be forwarded as-is; the
+//! runtime removes it and the backend runs in mapped mode.
+
+use abp_core::{AgentEvent, ExecutionMode, Receipt, WorkOrder};
+use abp_dialect::Dialect;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Vendor key carrying the caller's raw provider request.
+///
+/// A string value holds the verbatim request body; any other JSON value is
+/// the parsed request.
+pub const REQUEST_VENDOR_KEY: &str = "abp.request";
+
+/// Vendor key selecting the backend's [`ExecutionMode`].
+pub const MODE_VENDOR_KEY: &str = "abp.mode";
+
+/// Event extension key holding a raw provider response or stream event.
+pub const RAW_MESSAGE_EXT_KEY: &str = "raw_message";
+
+/// Key under `receipt.usage_raw` holding the [`PassthroughRecord`].
+pub const PASSTHROUGH_KEY: &str = "passthrough";
+
+/// Attach the caller's original request body to a work order.
+pub fn attach_raw_request(work_order: &mut WorkOrder, body: impl Into<String>) {
+    work_order
+        .config
+        .vendor
+        .insert(REQUEST_VENDOR_KEY.to_string(), Value::String(body.into()));
+}
+
+/// The raw provider request a work order carries, if any.
+#[must_use]
+pub fn raw_request(work_order: &WorkOrder) -> Option<&Value> {
+    let vendor = &work_order.config.vendor;
+    vendor.get(REQUEST_VENDOR_KEY).or_else(|| {
+        vendor
+            .get("abp")
+            .and_then(|abp| abp.get(REQUEST_VENDOR_KEY.trim_start_matches("abp.")))
+    })
+}
+
+/// Bytes of a raw request as forwarded to the backend: the verbatim body for
+/// a string, the JSON encoding otherwise.
+#[must_use]
+pub fn request_bytes(request: &Value) -> Vec<u8> {
+    match request {
+        Value::String(body) => body.clone().into_bytes(),
+        other => serde_json::to_vec(other).unwrap_or_default(),
+    }
+}
+
+/// Route a work order's raw request for a run from `source` to `target`.
+///
+/// Returns the passthrough record to attach to the receipt when the raw
+/// request is forwarded (matching dialects), or `None` otherwise. On a known
+/// dialect mismatch the raw request is removed; when either dialect is
+/// unknown the work order is left as it is.
+pub fn route(
+    work_order: &mut WorkOrder,
+    source: Option<Dialect>,
+    target: Option<Dialect>,
+) -> Option<PassthroughRecord> {
+    let request = raw_request(work_order)?;
+    match (source, target) {
+        (Some(src), Some(tgt)) if src == tgt => {
+            let record = PassthroughRecord {
+                dialect: src.label().to_string(),
+                request_sha256: abp_core::sha256_hex(&request_bytes(request)),
+                raw_events: 0,
+            };
+            work_order.config.vendor.insert(
+                MODE_VENDOR_KEY.to_string(),
+                serde_json::json!(ExecutionMode::Passthrough),
+            );
+            Some(record)
+        }
+        (Some(_), Some(_)) => {
+            work_order.config.vendor.remove(REQUEST_VENDOR_KEY);
+            if let Some(abp) = work_order
+                .config
+                .vendor
+                .get_mut("abp")
+                .and_then(Value::as_object_mut)
+            {
+                abp.remove(REQUEST_VENDOR_KEY.trim_start_matches("abp."));
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Whether `event` carries a raw provider payload.
+#[must_use]
+pub fn has_raw_message(event: &AgentEvent) -> bool {
+    event
+        .ext
+        .as_ref()
+        .is_some_and(|ext| ext.contains_key(RAW_MESSAGE_EXT_KEY))
+}
+
+/// What a passthrough run forwarded and preserved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PassthroughRecord {
+    /// Dialect of both the request and the backend.
+    pub dialect: String,
+    /// SHA-256 of the request bytes forwarded to the backend.
+    pub request_sha256: String,
+    /// Trace events that carry a raw provider payload.
+    pub raw_events: usize,
+}
+
+impl PassthroughRecord {
+    /// Read the passthrough record of a receipt, if the run was passthrough.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        let value = receipt.usage_raw.get(PASSTHROUGH_KEY)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// Count the receipt's raw payloads, mark it passthrough, and store this
+    /// record under `usage_raw["passthrough"]`.
+    pub fn attach(mut self, receipt: &mut Receipt) {
+        self.raw_events = receipt.trace.iter().filter(|e| has_raw_message(e)).count();
+        receipt.mode = ExecutionMode::Passthrough;
+        if let Some(obj) = receipt.usage_raw.as_object_mut()
+            && let Ok(value) = serde_json::to_value(&self)
+        {
+            obj.insert(PASSTHROUGH_KEY.to_string(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::WorkOrderBuilder;
+
+    fn work_order_with(body: &str) -> WorkOrder {
+        let mut wo = WorkOrderBuilder::new("hi").build();
+        attach_raw_request(&mut wo, body);
+        wo
+    }
+
+    #[test]
+    fn matching_dialects_forward_request_untouched() {
+        let body =
+            r#"{"model":"gpt-4o","messages":[{"role":"user","content":"hi"}],"x_unknown":1}"#;
+        let mut wo = work_order_with(body);
+        let record = route(&mut wo, Some(Dialect::OpenAi), Some(Dialect::OpenAi)).unwrap();
+        assert_eq!(raw_request(&wo), Some(&Value::String(body.into())));
+        assert_eq!(
+            abp_backend_core::extract_execution_mode(&wo),
+            ExecutionMode::Passthrough
+        );
+        assert_eq!(record.request_sha256, abp_core::sha256_hex(body.as_bytes()));
+    }
+
+    #[test]
+    fn mismatched_dialects_drop_request() {
+        let mut wo = work_order_with("{}");
+        assert!(route(&mut wo, Some(Dialect::OpenAi), Some(Dialect::Claude)).is_none());
+        assert!(raw_request(&wo).is_none());
+        assert_eq!(
+            abp_backend_core::extract_execution_mode(&wo),
+            ExecutionMode::Mapped
+        );
+    }
+
+    #[test]
+    fn unknown_dialect_leaves_work_order_alone() {
+        let mut wo = work_order_with("{}");
+        assert!(route(&mut wo, None, Some(Dialect::OpenAi)).is_none());
+        assert!(raw_request(&wo).is_some());
+    }
+
+    #[test]
+    fn nested_request_is_found() {
+        let mut wo = WorkOrderBuilder::new("hi").build();
+        wo.config
+            .vendor
+            .insert("abp".into(), serde_json::json!({"request": {"model": "m"}}));
+        assert_eq!(raw_request(&wo), Some(&serde_json::json!({"model": "m"})));
+    }
+
+    #[test]
+    fn no_request_is_not_routed() {
+        let mut wo = WorkOrderBuilder::new("hi").build();
+        assert!(route(&mut wo, Some(Dialect::OpenAi), Some(Dialect::OpenAi)).is_none());
+        assert!(!wo.config.vendor.contains_key(MODE_VENDOR_KEY));
+    }
+}
@@ -5,14 +5,22 @@
 //! inspect and optionally mutate a [`WorkOrder`] before it reaches a backend.
 //! Stages run in insertion order; any failure short-circuits the remaining
 //! stages.
+//!
+//! [`RuntimePipeline`](crate::pipeline::RuntimePipeline) also post-processes
+//! a run: when the work order declares a `response_format` (see
+//! [`OutputSchema`](crate::pipeline::OutputSchema)), the final assistant
+//! message is checked against it, the backend is re-prompted to repair
+//! invalid output, and the result is recorded in the receipt.
 
-use abp_core::{AgentEvent, Receipt, WorkOrder};
+use abp_core::{AgentEvent, AgentEventKind, Receipt, WorkOrder};
 use abp_integrations::{Backend, ensure_capability_requirements};
 use abp_policy::PolicyEngine;
 use abp_receipt::ReceiptBuilder;
 use abp_workspace::WorkspaceManager;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::{Mutex, mpsc};
@@ -234,24 +242,41 @@ pub struct StageOutcome {
 /// 4. **prepare_workspace** — stage the workspace directory
 /// 5. **run_backend** — execute the backend and collect events
 /// 6. **collect_events** — drain remaining events from the channel
-/// 7. **produce_receipt** — assemble and hash the final receipt
+/// 7. **validate_output** — only when the order declares a
+///    `response_format`: check the final assistant message, re-prompting up
+///    to [`max_repairs`](Self::with_max_repairs) times while it is invalid
+/// 8. **produce_receipt** — assemble and hash the final receipt
 ///
 /// Each stage is exposed as a separate method for testability and composability.
 pub struct RuntimePipeline {
     backend: Arc<dyn Backend>,
     backend_name: String,
+    max_repairs: u32,
 }
 
 impl RuntimePipeline {
+    /// Default number of repair attempts for invalid structured output.
+    pub const DEFAULT_MAX_REPAIRS: u32 = 1;
+
     /// Create a pipeline for the given backend.
     #[must_use]
     pub fn new(backend_name: impl Into<String>, backend: Arc<dyn Backend>) -> Self {
         Self {
             backend,
             backend_name: backend_name.into(),
+            max_repairs: Self::DEFAULT_MAX_REPAIRS,
         }
     }
 
+    /// Re-prompt the backend at most `max_repairs` times when its output
+    /// does not match the declared `response_format`; zero only validates
+    /// (builder pattern).
+    #[must_use]
+    pub fn with_max_repairs(mut self, max_repairs: u32) -> Self {
+        self.max_repairs = max_repairs;
+        self
+    }
+
     /// Return the backend name.
     #[must_use]
     pub fn backend_name(&self) -> &str {
@@ -368,6 +393,56 @@ impl RuntimePipeline {
         Ok(receipt)
     }
 
+    // -- Stage 7 (optional) --
+
+    /// Validate the run's final assistant message against `schema`,
+    /// re-running the backend with a repair prompt while it is invalid.
+    ///
+    /// Events of repair runs are appended to `trace`.
+    pub async fn validate_output(
+        &self,
+        run_id: uuid::Uuid,
+        order: &WorkOrder,
+        schema: &OutputSchema,
+        trace: &mut Vec<AgentEvent>,
+    ) -> SchemaValidation {
+        let mut attempts = 1;
+        loop {
+            let output = final_assistant_text(trace).unwrap_or_default();
+            let errors = match schema.check(&output) {
+                Ok(_) => Vec::new(),
+                Err(errors) => errors,
+            };
+            if errors.is_empty() || attempts > self.max_repairs {
+                return SchemaValidation {
+                    schema: schema.name().to_string(),
+                    valid: errors.is_empty(),
+                    attempts,
+                    errors,
+                };
+            }
+
+            debug!(
+                target: "abp.runtime.pipeline",
+                %run_id, attempt = attempts, "structured output invalid; requesting repair"
+            );
+            let mut repair = order.clone();
+            repair.task = schema.repair_prompt(&order.task, &output, &errors);
+            let (tx, mut rx) = mpsc::channel::<AgentEvent>(256);
+            let result = self.run_backend(run_id, repair, tx).await;
+            trace.extend(self.collect_events(&mut rx).await);
+            attempts += 1;
+            if let Err(e) = result {
+                return SchemaValidation {
+                    schema: schema.name().to_string(),
+                    valid: false,
+                    attempts,
+                    errors: vec![format!("repair run failed: {e}")],
+                };
+            }
+        }
+    }
+
     // -- Full orchestration --
 
     /// Execute the full pipeline, returning stage outcomes and the final receipt.
@@ -429,6 +504,19 @@ impl RuntimePipeline {
         // Rewrite workspace root to the prepared path.
         order.workspace.root = prepared.path().to_string_lossy().to_string();
 
+        let schema = match OutputSchema::from_work_order(&order) {
+            Ok(schema) => schema,
+            Err(e) => {
+                outcomes.push(StageOutcome {
+                    name: "validate_output".into(),
+                    success: false,
+                    duration_ms: 0,
+                    error: Some(e.to_string()),
+                });
+                return (outcomes, Err(e));
+            }
+        };
+
         // Stage 5: run backend
         let (tx, mut rx) = mpsc::channel::<AgentEvent>(256);
         let start = Instant::now();
@@ -442,7 +530,7 @@ impl RuntimePipeline {
 
         // Stage 6: collect events
         let start = Instant::now();
-        let trace = self.collect_events(&mut rx).await;
+        let mut trace = self.collect_events(&mut rx).await;
         outcomes.push(StageOutcome {
             name: "collect_events".into(),
             success: true,
@@ -450,15 +538,36 @@ impl RuntimePipeline {
             error: None,
         });
 
-        // Stage 7: produce receipt
+        // Stage 7: validate structured output
+        let mut validation = None;
+        if let (Some(schema), Ok(_)) = (&schema, &backend_result) {
+            let start = Instant::now();
+            let result = self
+                .validate_output(run_id, &order, schema, &mut trace)
+                .await;
+            outcomes.push(StageOutcome {
+                name: "validate_output".into(),
+                success: result.valid,
+                duration_ms: start.elapsed().as_millis() as u64,
+                error: (!result.valid).then(|| result.errors.join("; ")),
+            });
+            validation = Some(result);
+        }
+
+        // Stage 8: produce receipt
         let start = Instant::now();
-        let outcome_val = if backend_result.is_ok() {
-            abp_core::Outcome::Complete
-        } else {
-            abp_core::Outcome::Failed
+        let outcome_val = match (&backend_result, &validation) {
+            (Err(_), _) => abp_core::Outcome::Failed,
+            (Ok(_), Some(v)) if !v.valid => abp_core::Outcome::Partial,
+            (Ok(_), _) => abp_core::Outcome::Complete,
         };
 
-        let receipt_result = self.produce_receipt(run_id, &order, outcome_val, trace);
+        let receipt_result = self
+            .produce_receipt(run_id, &order, outcome_val, trace)
+            .and_then(|receipt| match &validation {
+                Some(v) => v.record(receipt),
+                None => Ok(receipt),
+            });
         outcomes.push(StageOutcome {
             name: "produce_receipt".into(),
             success: receipt_result.is_ok(),
@@ -470,6 +579,197 @@ impl RuntimePipeline {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Structured output
+// ---------------------------------------------------------------------------
+
+/// Vendor config key holding an OpenAI-style `response_format`.
+pub const RESPONSE_FORMAT_KEY: &str = "response_format";
+
+/// Receipt `usage_raw` key holding the [`SchemaValidation`].
+pub const SCHEMA_VALIDATION_KEY: &str = "schema_validation";
+
+/// The output contract a work order declares with an OpenAI-style
+/// `response_format` in `config.vendor["response_format"]`.
+///
+/// `{"type": "json_object"}` requires any JSON object;
+/// `{"type": "json_schema", "json_schema": {"name": .., "schema": ..}}`
+/// additionally requires the object to match the schema. `"text"` declares
+/// nothing.
+pub struct OutputSchema {
+    name: String,
+    validator: Option<jsonschema::Validator>,
+}
+
+impl std::fmt::Debug for OutputSchema {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OutputSchema")
+            .field("name", &self.name)
+            .field("has_schema", &self.validator.is_some())
+            .finish()
+    }
+}
+
+impl OutputSchema {
+    /// The contract declared by `order`, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `response_format` is malformed or its schema
+    /// does not compile.
+    pub fn from_work_order(order: &WorkOrder) -> Result<Option<Self>> {
+        let Some(format) = order.config.vendor.get(RESPONSE_FORMAT_KEY) else {
+            return Ok(None);
+        };
+        match format.get("type").and_then(Value::as_str) {
+            Some("text") => Ok(None),
+            Some("json_object") => Ok(Some(Self {
+                name: "json_object".into(),
+                validator: None,
+            })),
+            Some("json_schema") => {
+                let spec = format
+                    .get("json_schema")
+                    .context("response_format json_schema is missing its spec")?;
+                let schema = spec
+                    .get("schema")
+                    .context("response_format json_schema is missing `schema`")?;
+                let validator = jsonschema::validator_for(schema)
+                    .map_err(|e| anyhow::anyhow!("invalid response_format schema: {e}"))?;
+                Ok(Some(Self {
+                    name: spec
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .unwrap_or("json_schema")
+                        .to_string(),
+                    validator: Some(validator),
+                }))
+            }
+            other => anyhow::bail!("unsupported response_format type {other:?}"),
+        }
+    }
+
+    /// Name of the schema (`"json_object"` when only JSON is required).
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Parse `output` and check it against the contract.
+    ///
+    /// A surrounding Markdown code fence is ignored. Returns the parsed
+    /// value, or one message per violation.
+    pub fn check(&self, output: &str) -> std::result::Result<Value, Vec<String>> {
+        let value: Value = serde_json::from_str(strip_code_fence(output))
+            .map_err(|e| vec![format!("output is not valid JSON: {e}")])?;
+        if !value.is_object() {
+            return Err(vec!["output must be a JSON object".into()]);
+        }
+        if let Some(validator) = &self.validator {
+            let errors: Vec<String> = validator
+                .iter_errors(&value)
+                .map(|e| {
+                    let path = e.instance_path.to_string();
+                    if path.is_empty() {
+                        e.to_string()
+                    } else {
+                        format!("{path}: {e}")
+                    }
+                })
+                .collect();
+            if !errors.is_empty() {
+                return Err(errors);
+            }
+        }
+        Ok(value)
+    }
+
+    /// Task for a repair run: the original task, the rejected output, and
+    /// what was wrong with it.
+    #[must_use]
+    pub fn repair_prompt(&self, task: &str, output: &str, errors: &[String]) -> String {
+        let problems = errors
+            .iter()
+            .map(|e| format!("- {e}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!(
+            "{task}\n\nYour previous answer did not match the required `{}` format:\n\n\
+             {output}\n\nProblems:\n{problems}\n\n\
+             Reply again with only a JSON object that fixes these problems.",
+            self.name
+        )
+    }
+}
+
+/// Outcome of checking a run's output against its [`OutputSchema`],
+/// stored in `receipt.usage_raw["schema_validation"]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaValidation {
+    /// Name of the schema checked against.
+    pub schema: String,
+    /// Whether the final output matched.
+    pub valid: bool,
+    /// Backend runs made, including repairs.
+    pub attempts: u32,
+    /// Violations in the final output; empty when valid.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl SchemaValidation {
+    /// The validation recorded on `receipt`, if any.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        receipt
+            .usage_raw
+            .get(SCHEMA_VALIDATION_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    fn record(&self, mut receipt: Receipt) -> Result<Receipt> {
+        if !receipt.usage_raw.is_object() {
+            receipt.usage_raw = Value::Object(Default::default());
+        }
+        receipt.usage_raw[SCHEMA_VALIDATION_KEY] = serde_json::to_value(self)?;
+        receipt.receipt_sha256 = Some(abp_receipt::compute_hash(&receipt).context("hash receipt")?);
+        Ok(receipt)
+    }
+}
+
+/// The final assistant output in `trace`: the last complete message, or
+/// the concatenated deltas when the backend only streamed.
+#[must_use]
+pub fn final_assistant_text(trace: &[AgentEvent]) -> Option<String> {
+    let message = trace.iter().rev().find_map(|ev| match &ev.kind {
+        AgentEventKind::AssistantMessage { text } => Some(text.clone()),
+        _ => None,
+    });
+    message.or_else(|| {
+        let deltas: String = trace
+            .iter()
+            .filter_map(|ev| match &ev.kind {
+                AgentEventKind::AssistantDelta { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        (!deltas.is_empty()).then_some(deltas)
+    })
+}
+
+fn strip_code_fence(output: &str) -> &str {
+    let trimmed = output.trim();
+    let Some(body) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    let body = body.strip_suffix("```").unwrap_or(body);
+    // Drop the info string (e.g. `json`) on the opening line.
+    match body.split_once('\n') {
+        Some((_, rest)) => rest.trim(),
+        None => body.trim(),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -0,0 +1,324 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Per-model token prices and the receipt cost stage.
+//!
+//! A [`PricingTable`](crate::pricing::PricingTable) maps model ids to
+//! [`ModelPrice`](crate::pricing::ModelPrice)s in USD per million tokens.
+//! After every run the runtime looks up the model the receipt names (or the
+//! work order asked for) and, if the backend did not report a cost itself,
+//! fills `usage.estimated_cost_usd` before the receipt is hashed. Per-backend
+//! and per-model totals are kept in [`RunMetrics`](crate::telemetry::RunMetrics).
+//!
+//! The built-in table covers the models in the default capability catalog.
+//! Prices change, so they can be overridden, and other models added, from a
+//! `[pricing]` section of `backplane.toml`:
+//!
+//! ```toml
+//! [pricing."gpt-4o"]
+//! input_per_mtok = 2.50
+//! output_per_mtok = 10.00
+//! cache_read_per_mtok = 1.25
+//!
+//! [pricing."my-finetune"]
+//! input_per_mtok = 3.00
+//! output_per_mtok = 12.00
+//! ```
+//!
+//! Lookups fall back to the longest registered prefix, so a dated snapshot
+//! such as `gpt-4o-2024-08-06` is priced as `gpt-4o`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use abp_config::ConfigError;
+use abp_core::{Receipt, UsageNormalized};
+use serde::{Deserialize, Serialize};
+
+/// Key under `receipt.usage_raw` recording how the cost was estimated.
+pub const PRICING_USAGE_KEY: &str = "pricing";
+
+/// Token prices for one model, in USD per million tokens.
+///
+/// [`UsageNormalized`] counts cache tokens separately from input tokens.
+/// Cache reads and writes without a price of their own are charged at the
+/// input price.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ModelPrice {
+    /// Price of a million input tokens.
+    pub input_per_mtok: f64,
+    /// Price of a million output tokens.
+    pub output_per_mtok: f64,
+    /// Price of a million tokens read from the prompt cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_per_mtok: Option<f64>,
+    /// Price of a million tokens written to the prompt cache.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_per_mtok: Option<f64>,
+}
+
+impl ModelPrice {
+    /// Prices for input and output tokens; cache tokens at the input price.
+    #[must_use]
+    pub const fn new(input_per_mtok: f64, output_per_mtok: f64) -> Self {
+        Self {
+            input_per_mtok,
+            output_per_mtok,
+            cache_read_per_mtok: None,
+            cache_write_per_mtok: None,
+        }
+    }
+
+    /// Set the cache read and write prices.
+    #[must_use]
+    pub const fn with_cache(mut self, read_per_mtok: f64, write_per_mtok: f64) -> Self {
+        self.cache_read_per_mtok = Some(read_per_mtok);
+        self.cache_write_per_mtok = Some(write_per_mtok);
+        self
+    }
+
+    /// Cost of `usage` in USD, or `None` if it counts no tokens at all.
+    #[must_use]
+    pub fn cost(&self, usage: &UsageNormalized) -> Option<f64> {
+        let counts = [
+            (usage.input_tokens, self.input_per_mtok),
+            (usage.output_tokens, self.output_per_mtok),
+            (
+                usage.cache_read_tokens,
+                self.cache_read_per_mtok.unwrap_or(self.input_per_mtok),
+            ),
+            (
+                usage.cache_write_tokens,
+                self.cache_write_per_mtok.unwrap_or(self.input_per_mtok),
+            ),
+        ];
+        if counts.iter().all(|(n, _)| n.is_none()) {
+            return None;
+        }
+        Some(
+            counts
+                .iter()
+                .map(|(n, price)| n.unwrap_or(0) as f64 * price / 1_000_000.0)
+                .sum(),
+        )
+    }
+
+    fn problems(&self, model: &str) -> Vec<String> {
+        [
+            ("input_per_mtok", Some(self.input_per_mtok)),
+            ("output_per_mtok", Some(self.output_per_mtok)),
+            ("cache_read_per_mtok", self.cache_read_per_mtok),
+            ("cache_write_per_mtok", self.cache_write_per_mtok),
+        ]
+        .into_iter()
+        .filter_map(|(field, v)| v.map(|v| (field, v)))
+        .filter(|(_, v)| !(v.is_finite() && *v >= 0.0))
+        .map(|(field, v)| {
+            format!("pricing '{model}': {field} must be a non-negative number, got {v}")
+        })
+        .collect()
+    }
+}
+
+/// Token prices keyed by model id.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PricingTable {
+    #[serde(default)]
+    pricing: BTreeMap<String, ModelPrice>,
+}
+
+impl PricingTable {
+    /// An empty table, which prices nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// List prices of the models in the default capability catalog.
+    #[must_use]
+    pub fn with_defaults() -> Self {
+        let mut table = Self::new();
+        for (model, price) in [
+            (
+                "gpt-4o",
+                ModelPrice::new(2.50, 10.00).with_cache(1.25, 2.50),
+            ),
+            (
+                "gpt-4o-mini",
+                ModelPrice::new(0.15, 0.60).with_cache(0.075, 0.15),
+            ),
+            ("gpt-4-turbo", ModelPrice::new(10.00, 30.00)),
+            ("gpt-3.5-turbo", ModelPrice::new(0.50, 1.50)),
+            ("o1", ModelPrice::new(15.00, 60.00).with_cache(7.50, 15.00)),
+            (
+                "o3-mini",
+                ModelPrice::new(1.10, 4.40).with_cache(0.55, 1.10),
+            ),
+            (
+                "claude-3-5-sonnet",
+                ModelPrice::new(3.00, 15.00).with_cache(0.30, 3.75),
+            ),
+            (
+                "claude-3-7-sonnet",
+                ModelPrice::new(3.00, 15.00).with_cache(0.30, 3.75),
+            ),
+            (
+                "claude-3-5-haiku",
+                ModelPrice::new(0.80, 4.00).with_cache(0.08, 1.00),
+            ),
+            (
+                "claude-3-opus",
+                ModelPrice::new(15.00, 75.00).with_cache(1.50, 18.75),
+            ),
+            ("gemini-1.5-pro", ModelPrice::new(1.25, 5.00)),
+            ("gemini-1.5-flash", ModelPrice::new(0.075, 0.30)),
+            ("gemini-2.0-flash", ModelPrice::new(0.10, 0.40)),
+        ] {
+            table.insert(model, price);
+        }
+        table
+    }
+
+    /// Set the price of `model`, returning the one it replaces.
+    pub fn insert(&mut self, model: impl Into<String>, price: ModelPrice) -> Option<ModelPrice> {
+        self.pricing.insert(model.into(), price)
+    }
+
+    /// Remove `model`'s price. Returns `true` if it was present.
+    pub fn remove(&mut self, model: &str) -> bool {
+        self.pricing.remove(model).is_some()
+    }
+
+    /// Registered model ids, sorted.
+    #[must_use]
+    pub fn models(&self) -> Vec<&str> {
+        self.pricing.keys().map(String::as_str).collect()
+    }
+
+    /// The entry pricing `model`: an exact match, else the longest
+    /// registered prefix. Returns the entry's id with its price.
+    #[must_use]
+    pub fn lookup(&self, model: &str) -> Option<(&str, &ModelPrice)> {
+        if let Some((id, price)) = self.pricing.get_key_value(model) {
+            return Some((id, price));
+        }
+        self.pricing
+            .iter()
+            .filter(|(id, _)| model.starts_with(id.as_str()))
+            .max_by_key(|(id, _)| id.len())
+            .map(|(id, price)| (id.as_str(), price))
+    }
+
+    /// Cost of `usage` on `model` in USD, if the model is priced and the
+    /// usage counts any tokens.
+    #[must_use]
+    pub fn cost(&self, model: &str, usage: &UsageNormalized) -> Option<f64> {
+        self.lookup(model)?.1.cost(usage)
+    }
+
+    /// Overlay `other`'s prices on this table.
+    pub fn merge(&mut self, other: Self) {
+        self.pricing.extend(other.pricing);
+    }
+
+    /// Fill `receipt.usage.estimated_cost_usd` from this table.
+    ///
+    /// The model is the one the backend recorded in `usage_raw["model"]`,
+    /// else `requested_model`. A cost the backend reported itself is left
+    /// alone. When a cost is filled in, the model and the table entry used
+    /// are recorded under `usage_raw["pricing"]`. Returns the receipt's
+    /// cost, whether filled here or by the backend.
+    pub fn apply(&self, receipt: &mut Receipt, requested_model: Option<&str>) -> Option<f64> {
+        if receipt.usage.estimated_cost_usd.is_some() {
+            return receipt.usage.estimated_cost_usd;
+        }
+        let model = receipt_model(receipt).or(requested_model)?.to_string();
+        let (entry, price) = self.lookup(&model)?;
+        let cost = price.cost(&receipt.usage)?;
+        let record = serde_json::json!({ "model": model, "entry": entry });
+        receipt.usage.estimated_cost_usd = Some(cost);
+        if let Some(obj) = receipt.usage_raw.as_object_mut() {
+            obj.insert(PRICING_USAGE_KEY.to_string(), record);
+        }
+        Some(cost)
+    }
+
+    /// Parse and validate a `[pricing]` section from a TOML string. Other
+    /// sections are ignored.
+    ///
+    /// # Errors
+    ///
+    /// [`ConfigError::ParseError`] for malformed TOML or unknown price
+    /// fields, [`ConfigError::ValidationError`] for negative or non-finite
+    /// prices.
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        let table: Self = toml::from_str(content).map_err(|e| ConfigError::ParseError {
+            reason: e.to_string(),
+        })?;
+        let reasons: Vec<String> = table
+            .pricing
+            .iter()
+            .flat_map(|(model, price)| price.problems(model))
+            .collect();
+        if reasons.is_empty() {
+            Ok(table)
+        } else {
+            Err(ConfigError::ValidationError { reasons })
+        }
+    }
+
+    /// Read the `[pricing]` section of the TOML file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// [`ConfigError::FileNotFound`] if the file cannot be read, otherwise
+    /// as for [`parse`](Self::parse).
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let content = std::fs::read_to_string(path).map_err(|_| ConfigError::FileNotFound {
+            path: path.display().to_string(),
+        })?;
+        Self::parse(&content)
+    }
+}
+
+/// The model a receipt says served the run, from `usage_raw["model"]`.
+#[must_use]
+pub fn receipt_model(receipt: &Receipt) -> Option<&str> {
+    receipt
+        .usage_raw
+        .get("model")
+        .and_then(serde_json::Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(input: u64, output: u64) -> UsageNormalized {
+        UsageNormalized {
+            input_tokens: Some(input),
+            output_tokens: Some(output),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cache_tokens_default_to_input_price() {
+        let price = ModelPrice::new(2.0, 8.0);
+        let mut u = usage(1_000_000, 500_000);
+        u.cache_read_tokens = Some(1_000_000);
+        assert_eq!(price.cost(&u), Some(2.0 + 4.0 + 2.0));
+        assert_eq!(price.with_cache(0.5, 2.5).cost(&u), Some(2.0 + 4.0 + 0.5));
+        assert_eq!(price.cost(&UsageNormalized::default()), None);
+    }
+
+    #[test]
+    fn lookup_prefers_longest_prefix() {
+        let table = PricingTable::with_defaults();
+        assert_eq!(
+            table.lookup("gpt-4o-mini-2024-07-18").unwrap().0,
+            "gpt-4o-mini"
+        );
+        assert_eq!(table.lookup("gpt-4o-2024-08-06").unwrap().0, "gpt-4o");
+        assert!(table.lookup("llama-3").is_none());
+    }
+}
@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Progress events and idle heartbeats.
+//!
+//! Backends report long-running work with
+//! [`AgentEventKind::Progress`](abp_core::AgentEventKind::Progress) events:
+//! a human-readable `message` and, when the backend can estimate it, a
+//! `percent` in `0.0..=100.0`.
+//!
+//! When the runtime is configured with
+//! [`Runtime::with_idle_progress`](crate::Runtime::with_idle_progress), it
+//! also synthesizes a progress event whenever the backend has been silent for
+//! the configured interval. These heartbeats carry
+//! `ext["abp.idle_ms"]` (see [`IDLE_MS_KEY`](crate::progress::IDLE_MS_KEY))
+//! so a UI can tell a backend that is still working from one that has hung.
+//! Heartbeats are delivered to the caller's event stream only; they are not
+//! part of the receipt trace.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use abp_core::{AgentEvent, AgentEventKind};
+use chrono::{DateTime, Utc};
+
+/// Extension key holding how long the backend has been silent, in
+/// milliseconds, on a synthesized heartbeat.
+pub const IDLE_MS_KEY: &str = "abp.idle_ms";
+
+/// Build the heartbeat emitted after the backend has been silent for `idle`.
+#[must_use]
+pub fn idle_progress_event(idle: Duration, ts: DateTime<Utc>) -> AgentEvent {
+    let idle_ms = u64::try_from(idle.as_millis()).unwrap_or(u64::MAX);
+    let mut ext = BTreeMap::new();
+    ext.insert(IDLE_MS_KEY.to_string(), serde_json::json!(idle_ms));
+    AgentEvent {
+        ts,
+        kind: AgentEventKind::Progress {
+            percent: None,
+            message: format!("waiting for backend ({}s without events)", idle.as_secs()),
+        },
+        ext: Some(ext),
+    }
+}
+
+/// How long the backend had been silent, if `event` is a runtime heartbeat.
+#[must_use]
+pub fn idle_duration(event: &AgentEvent) -> Option<Duration> {
+    if !matches!(event.kind, AgentEventKind::Progress { .. }) {
+        return None;
+    }
+    event
+        .ext
+        .as_ref()?
+        .get(IDLE_MS_KEY)?
+        .as_u64()
+        .map(Duration::from_millis)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heartbeat_round_trips_idle_duration() {
+        let ev = idle_progress_event(Duration::from_millis(2500), Utc::now());
+        assert_eq!(idle_duration(&ev), Some(Duration::from_millis(2500)));
+        assert!(matches!(
+            ev.kind,
+            AgentEventKind::Progress { percent: None, .. }
+        ));
+    }
+
+    #[test]
+    fn backend_progress_is_not_a_heartbeat() {
+        let ev = AgentEvent {
+            ts: Utc::now(),
+            kind: AgentEventKind::Progress {
+                percent: Some(40.0),
+                message: "indexing".into(),
+            },
+            ext: None,
+        };
+        assert_eq!(idle_duration(&ev), None);
+    }
+}
@@ -0,0 +1,536 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Priority work queue for running the backplane as a long-lived service.
+//!
+//! [`WorkQueue`](crate::queue::WorkQueue) holds work orders submitted with
+//! [`QueuedWork`](crate::queue::QueuedWork): a backend, a priority, and an
+//! optional not-before time. Once
+//! [`WorkQueue::start`](crate::queue::WorkQueue::start) attaches it to a
+//! [`Runtime`], a dispatcher task starts jobs as capacity allows:
+//!
+//! * Among jobs whose not-before time has passed, the highest priority runs
+//!   first; equal priorities run in submission order.
+//! * At most
+//!   [`QueueConfig::max_running`](crate::queue::QueueConfig::max_running) jobs
+//!   run at once, and no backend runs more than its
+//!   [`QueueConfig::backend_limit`](crate::queue::QueueConfig::backend_limit).
+//!   A job whose backend is full waits without holding up jobs for other
+//!   backends.
+//!
+//! Each job runs through [`Runtime::run_streaming`] with its events drained;
+//! subscribe to the runtime's [`event_bus`](Runtime::event_bus) to watch them
+//! live. [`WorkQueue::snapshot`](crate::queue::WorkQueue::snapshot) lists
+//! pending, running, and recently finished jobs,
+//! [`WorkQueue::receipt`](crate::queue::WorkQueue::receipt) returns a finished
+//! job's receipt, and [`WorkQueue::wait`](crate::queue::WorkQueue::wait)
+//! resolves when a job finishes. Only the last
+//! [`QueueConfig::history`](crate::queue::QueueConfig::history) finished jobs
+//! are kept.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use abp_core::clock::{SharedClock, system_clock};
+use abp_core::{Outcome, Receipt, WorkOrder};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use tokio_stream::StreamExt;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::Runtime;
+
+/// Longest the dispatcher sleeps before re-checking not-before times.
+const MAX_IDLE: Duration = Duration::from_secs(1);
+
+/// Limits of a [`WorkQueue`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueConfig {
+    /// Maximum number of jobs running at once, across all backends.
+    pub max_running: usize,
+    /// Maximum number of jobs running at once per backend. Backends not
+    /// listed are only bounded by [`max_running`](Self::max_running).
+    #[serde(default)]
+    pub backend_limits: BTreeMap<String, usize>,
+    /// Number of finished jobs kept for inspection.
+    pub history: usize,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            max_running: 8,
+            backend_limits: BTreeMap::new(),
+            history: 1000,
+        }
+    }
+}
+
+impl QueueConfig {
+    /// Run at most `n` jobs at once (builder pattern; at least 1).
+    #[must_use]
+    pub fn max_running(mut self, n: usize) -> Self {
+        self.max_running = n.max(1);
+        self
+    }
+
+    /// Run at most `n` jobs at once on `backend` (builder pattern; at
+    /// least 1).
+    #[must_use]
+    pub fn backend_limit(mut self, backend: impl Into<String>, n: usize) -> Self {
+        self.backend_limits.insert(backend.into(), n.max(1));
+        self
+    }
+
+    /// Keep the last `n` finished jobs (builder pattern).
+    #[must_use]
+    pub fn history(mut self, n: usize) -> Self {
+        self.history = n;
+        self
+    }
+}
+
+/// A work order to queue.
+#[derive(Debug, Clone)]
+pub struct QueuedWork {
+    backend: String,
+    work_order: WorkOrder,
+    priority: i32,
+    not_before: Option<DateTime<Utc>>,
+}
+
+impl QueuedWork {
+    /// Run `work_order` on `backend` at the default priority of `0`.
+    #[must_use]
+    pub fn new(backend: impl Into<String>, work_order: WorkOrder) -> Self {
+        Self {
+            backend: backend.into(),
+            work_order,
+            priority: 0,
+            not_before: None,
+        }
+    }
+
+    /// Set the priority; higher runs first (builder pattern).
+    #[must_use]
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Do not start before `at` (builder pattern).
+    #[must_use]
+    pub fn not_before(mut self, at: DateTime<Utc>) -> Self {
+        self.not_before = Some(at);
+        self
+    }
+}
+
+/// Where a job is in its lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting to be started.
+    Pending,
+    /// Running on its backend.
+    Running,
+    /// Finished with a receipt.
+    Completed,
+    /// Finished without a receipt.
+    Failed,
+    /// Removed from the queue before it started.
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Whether the job will not change state again.
+    #[must_use]
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// What the queue knows about one job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobInfo {
+    /// Queue-assigned job id.
+    pub id: Uuid,
+    /// Id of the queued work order.
+    pub work_order_id: Uuid,
+    /// Backend the job runs on.
+    pub backend: String,
+    /// Scheduling priority; higher runs first.
+    pub priority: i32,
+    /// Earliest start time, if any.
+    pub not_before: Option<DateTime<Utc>>,
+    /// Current state.
+    pub status: JobStatus,
+    /// When the job was queued.
+    pub enqueued_at: DateTime<Utc>,
+    /// When it started running.
+    pub started_at: Option<DateTime<Utc>>,
+    /// When it finished or was cancelled.
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Id of the run, once started.
+    pub run_id: Option<Uuid>,
+    /// Outcome of the receipt, for completed jobs.
+    pub outcome: Option<Outcome>,
+    /// Why a failed job produced no receipt.
+    pub error: Option<String>,
+}
+
+/// The queue's jobs at one point in time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QueueSnapshot {
+    /// Waiting jobs, in the order they would start if capacity allowed.
+    pub pending: Vec<JobInfo>,
+    /// Running jobs, oldest first.
+    pub running: Vec<JobInfo>,
+    /// Recently finished jobs, oldest first.
+    pub finished: Vec<JobInfo>,
+}
+
+struct PendingJob {
+    seq: u64,
+    info: JobInfo,
+    work_order: WorkOrder,
+}
+
+struct FinishedJob {
+    info: JobInfo,
+    receipt: Option<Receipt>,
+}
+
+#[derive(Default)]
+struct State {
+    next_seq: u64,
+    pending: Vec<PendingJob>,
+    running: BTreeMap<Uuid, JobInfo>,
+    finished: VecDeque<FinishedJob>,
+}
+
+struct Inner {
+    config: QueueConfig,
+    clock: SharedClock,
+    state: Mutex<State>,
+    /// Wakes the dispatcher when a job is queued or a slot frees up.
+    wake: Arc<Notify>,
+    /// Wakes [`WorkQueue::wait`] callers when a job finishes.
+    finished: Notify,
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        // Let the dispatcher notice the queue is gone.
+        self.wake.notify_one();
+    }
+}
+
+/// A priority queue of work orders with per-backend concurrency caps.
+///
+/// Cloning shares the queue.
+#[derive(Clone)]
+pub struct WorkQueue {
+    inner: Arc<Inner>,
+}
+
+impl std::fmt::Debug for WorkQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.inner.state.lock().expect("queue state lock poisoned");
+        f.debug_struct("WorkQueue")
+            .field("config", &self.inner.config)
+            .field("pending", &state.pending.len())
+            .field("running", &state.running.len())
+            .field("finished", &state.finished.len())
+            .finish()
+    }
+}
+
+impl Default for WorkQueue {
+    fn default() -> Self {
+        Self::new(QueueConfig::default())
+    }
+}
+
+impl WorkQueue {
+    /// An empty queue with the given limits.
+    #[must_use]
+    pub fn new(config: QueueConfig) -> Self {
+        Self::with_clock(config, system_clock())
+    }
+
+    /// An empty queue that reads time from `clock`.
+    #[must_use]
+    pub fn with_clock(config: QueueConfig, clock: SharedClock) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                config,
+                clock,
+                state: Mutex::new(State::default()),
+                wake: Arc::new(Notify::new()),
+                finished: Notify::new(),
+            }),
+        }
+    }
+
+    /// The queue's limits.
+    #[must_use]
+    pub fn config(&self) -> &QueueConfig {
+        &self.inner.config
+    }
+
+    /// Queue `work` and return its job id.
+    pub fn enqueue(&self, work: QueuedWork) -> Uuid {
+        let id = Uuid::new_v4();
+        let info = JobInfo {
+            id,
+            work_order_id: work.work_order.id,
+            backend: work.backend,
+            priority: work.priority,
+            not_before: work.not_before,
+            status: JobStatus::Pending,
+            enqueued_at: self.inner.clock.now(),
+            started_at: None,
+            finished_at: None,
+            run_id: None,
+            outcome: None,
+            error: None,
+        };
+        {
+            let mut state = self.inner.lock();
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            state.pending.push(PendingJob {
+                seq,
+                info,
+                work_order: work.work_order,
+            });
+        }
+        self.inner.wake.notify_one();
+        id
+    }
+
+    /// Remove a pending job. Returns `false` if it is not pending.
+    pub fn cancel(&self, id: Uuid) -> bool {
+        let mut state = self.inner.lock();
+        let Some(pos) = state.pending.iter().position(|j| j.info.id == id) else {
+            return false;
+        };
+        let mut info = state.pending.remove(pos).info;
+        info.status = JobStatus::Cancelled;
+        info.finished_at = Some(self.inner.clock.now());
+        self.inner.record_finished(&mut state, info, None);
+        drop(state);
+        self.inner.finished.notify_waiters();
+        true
+    }
+
+    /// The current state of job `id`, if the queue still knows it.
+    #[must_use]
+    pub fn status(&self, id: Uuid) -> Option<JobInfo> {
+        let state = self.inner.lock();
+        state
+            .pending
+            .iter()
+            .map(|j| &j.info)
+            .chain(state.running.values())
+            .chain(state.finished.iter().map(|j| &j.info))
+            .find(|info| info.id == id)
+            .cloned()
+    }
+
+    /// The receipt of finished job `id`, if it produced one.
+    #[must_use]
+    pub fn receipt(&self, id: Uuid) -> Option<Receipt> {
+        let state = self.inner.lock();
+        state
+            .finished
+            .iter()
+            .find(|j| j.info.id == id)
+            .and_then(|j| j.receipt.clone())
+    }
+
+    /// Pending, running, and recently finished jobs.
+    #[must_use]
+    pub fn snapshot(&self) -> QueueSnapshot {
+        let state = self.inner.lock();
+        let mut pending: Vec<&PendingJob> = state.pending.iter().collect();
+        pending.sort_by_key(|j| dispatch_order(j));
+        let mut running: Vec<JobInfo> = state.running.values().cloned().collect();
+        running.sort_by_key(|info| info.started_at);
+        QueueSnapshot {
+            pending: pending.into_iter().map(|j| j.info.clone()).collect(),
+            running,
+            finished: state.finished.iter().map(|j| j.info.clone()).collect(),
+        }
+    }
+
+    /// Wait until job `id` finishes and return its final state.
+    ///
+    /// Returns `None` if the queue does not know the job, including finished
+    /// jobs that have dropped out of the history.
+    pub async fn wait(&self, id: Uuid) -> Option<JobInfo> {
+        loop {
+            let notified = self.inner.finished.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            let info = self.status(id)?;
+            if info.status.is_finished() {
+                return Some(info);
+            }
+            notified.await;
+        }
+    }
+
+    /// Start dispatching queued jobs to `runtime`.
+    ///
+    /// The dispatcher stops when every handle to the queue is dropped, or
+    /// when the returned task is aborted; jobs already running finish
+    /// either way. Start at most one dispatcher per queue.
+    pub fn start(&self, runtime: Arc<Runtime>) -> tokio::task::JoinHandle<()> {
+        let queue = Arc::downgrade(&self.inner);
+        let wake = Arc::clone(&self.inner.wake);
+        tokio::spawn(async move {
+            while let Some(idle) = dispatch(&queue, &runtime) {
+                match idle {
+                    Some(wait) => {
+                        tokio::select! {
+                            () = wake.notified() => {}
+                            () = tokio::time::sleep(wait) => {}
+                        }
+                    }
+                    None => wake.notified().await,
+                }
+            }
+        })
+    }
+}
+
+impl Inner {
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("queue state lock poisoned")
+    }
+
+    fn record_finished(&self, state: &mut State, info: JobInfo, receipt: Option<Receipt>) {
+        state.finished.push_back(FinishedJob { info, receipt });
+        while state.finished.len() > self.config.history {
+            state.finished.pop_front();
+        }
+    }
+
+    /// Number of running jobs on `backend`.
+    fn running_on(state: &State, backend: &str) -> usize {
+        state
+            .running
+            .values()
+            .filter(|info| info.backend == backend)
+            .count()
+    }
+
+    /// Move the next startable job from pending to running.
+    fn take_next(&self, state: &mut State) -> Option<(JobInfo, WorkOrder)> {
+        if state.running.len() >= self.config.max_running {
+            return None;
+        }
+        let now = self.clock.now();
+        let pos = state
+            .pending
+            .iter()
+            .enumerate()
+            .filter(|(_, j)| j.info.not_before.is_none_or(|at| at <= now))
+            .filter(|(_, j)| {
+                self.config
+                    .backend_limits
+                    .get(&j.info.backend)
+                    .is_none_or(|&limit| Self::running_on(state, &j.info.backend) < limit)
+            })
+            .min_by_key(|(_, j)| dispatch_order(j))
+            .map(|(pos, _)| pos)?;
+        let PendingJob {
+            mut info,
+            work_order,
+            ..
+        } = state.pending.remove(pos);
+        info.status = JobStatus::Running;
+        info.started_at = Some(now);
+        state.running.insert(info.id, info.clone());
+        Some((info, work_order))
+    }
+
+    /// How long until the earliest not-before time among pending jobs.
+    fn next_due(&self, state: &State) -> Option<Duration> {
+        let now = self.clock.now();
+        state
+            .pending
+            .iter()
+            .filter_map(|j| j.info.not_before)
+            .filter(|&at| at > now)
+            .min()
+            .map(|at| (at - now).to_std().unwrap_or_default())
+    }
+}
+
+/// Sort key putting the job that should start first at the front.
+fn dispatch_order(job: &PendingJob) -> (std::cmp::Reverse<i32>, u64) {
+    (std::cmp::Reverse(job.info.priority), job.seq)
+}
+
+/// Start every job that can start now.
+///
+/// Returns `None` once the queue is gone; otherwise how long the dispatcher
+/// may sleep before a delayed job becomes due (`None` for "until woken").
+fn dispatch(queue: &Weak<Inner>, runtime: &Arc<Runtime>) -> Option<Option<Duration>> {
+    let inner = queue.upgrade()?;
+    let mut state = inner.lock();
+    while let Some((info, work_order)) = inner.take_next(&mut state) {
+        debug!(target: "abp.runtime", job = %info.id, backend = %info.backend, priority = info.priority, "starting queued job");
+        tokio::spawn(run_job(
+            Arc::clone(&inner),
+            Arc::clone(runtime),
+            info,
+            work_order,
+        ));
+    }
+    let wait = inner
+        .next_due(&state)
+        .map(|wait| inner.clock.sleep_for(wait).min(MAX_IDLE));
+    Some(wait)
+}
+
+async fn run_job(inner: Arc<Inner>, runtime: Arc<Runtime>, info: JobInfo, work_order: WorkOrder) {
+    let result = match runtime.run_streaming(&info.backend, work_order).await {
+        Ok(mut handle) => {
+            if let Some(running) = inner.lock().running.get_mut(&info.id) {
+                running.run_id = Some(handle.run_id);
+            }
+            while handle.events.next().await.is_some() {}
+            match handle.receipt.await {
+                Ok(result) => result.map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            }
+        }
+        Err(e) => Err(e.to_string()),
+    };
+
+    let mut state = inner.lock();
+    let mut info = state.running.remove(&info.id).unwrap_or(info);
+    info.finished_at = Some(inner.clock.now());
+    let receipt = match result {
+        Ok(receipt) => {
+            info.status = JobStatus::Completed;
+            info.outcome = Some(receipt.outcome.clone());
+            Some(receipt)
+        }
+        Err(error) => {
+            warn!(target: "abp.runtime", job = %info.id, backend = %info.backend, %error, "queued job failed");
+            info.status = JobStatus::Failed;
+            info.error = Some(error);
+            None
+        }
+    };
+    inner.record_finished(&mut state, info, receipt);
+    drop(state);
+    inner.finished.notify_waiters();
+    inner.wake.notify_one();
+}
@@ -0,0 +1,459 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Calendar-window usage quotas per tenant or API key.
+//!
+//! A [`Quota`](crate::quota::Quota) caps the tokens or USD a tenant or API
+//! key may spend per UTC day or calendar month. The tenant and key come from
+//! the work order's [`Submitter`](abp_core::submitter::Submitter). The
+//! [`QuotaEnforcer`](crate::quota::QuotaEnforcer) reserves each run's
+//! projected input tokens at admission and settles the reservation against
+//! the finished receipt, so concurrent runs cannot all slip under a nearly
+//! exhausted cap. Work orders whose scope would go over a cap are rejected
+//! with [`ErrorCode::QuotaExceeded`](abp_error::ErrorCode::QuotaExceeded),
+//! and the counters can be rebuilt from a
+//! [`ReceiptStore`](crate::store::ReceiptStore) so a restart does not reset
+//! them.
+
+use crate::store::ReceiptStore;
+use abp_core::{Receipt, WorkOrder};
+use abp_error::{AbpError, ErrorCode};
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Key under `receipt.usage_raw` naming the API key billed for a run.
+pub const API_KEY_USAGE_KEY: &str = "api_key_id";
+
+/// Calendar window a [`Quota`] resets on, in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaWindow {
+    /// Midnight to midnight.
+    Daily,
+    /// First of the month to first of the next month.
+    Monthly,
+}
+
+impl QuotaWindow {
+    /// Start of the window containing `at`.
+    #[must_use]
+    pub fn start(self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let date = at.date_naive();
+        let first = match self {
+            Self::Daily => date,
+            Self::Monthly => NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap_or(date),
+        };
+        first.and_time(chrono::NaiveTime::MIN).and_utc()
+    }
+
+    /// End (exclusive) of the window containing `at`, when the quota resets.
+    #[must_use]
+    pub fn end(self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let start = self.start(at);
+        match self {
+            Self::Daily => start + chrono::Duration::days(1),
+            Self::Monthly => start
+                .checked_add_months(Months::new(1))
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Daily => "daily",
+            Self::Monthly => "monthly",
+        }
+    }
+}
+
+impl fmt::Display for QuotaWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Caps for one window. `None` means unlimited.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Quota {
+    /// Window the caps reset on.
+    pub window: QuotaWindow,
+    /// Maximum input plus output tokens per window.
+    pub max_tokens: Option<u64>,
+    /// Maximum estimated spend in USD per window.
+    pub max_cost_usd: Option<f64>,
+}
+
+impl Quota {
+    /// A token cap per `window`.
+    #[must_use]
+    pub fn tokens(window: QuotaWindow, max_tokens: u64) -> Self {
+        Self {
+            window,
+            max_tokens: Some(max_tokens),
+            max_cost_usd: None,
+        }
+    }
+
+    /// A spend cap per `window`.
+    #[must_use]
+    pub fn cost_usd(window: QuotaWindow, max_cost_usd: f64) -> Self {
+        Self {
+            window,
+            max_tokens: None,
+            max_cost_usd: Some(max_cost_usd),
+        }
+    }
+}
+
+/// Who a [`Quota`] applies to.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "snake_case")]
+pub enum QuotaScope {
+    /// A tenant, from the submitter's `tenant`.
+    Tenant(String),
+    /// An API key, from the submitter's `api_key_id`.
+    ApiKey(String),
+}
+
+impl QuotaScope {
+    /// Scopes a work order is billed to, from its `config.submitter`.
+    #[must_use]
+    pub fn for_work_order(work_order: &WorkOrder) -> Vec<Self> {
+        let submitter = work_order.config.submitter.as_ref();
+        Self::collect(
+            submitter.and_then(|s| s.tenant.clone()),
+            submitter.and_then(|s| s.api_key_id.clone()),
+        )
+    }
+
+    /// Scopes a receipt was billed to, from its `usage_raw`.
+    #[must_use]
+    pub fn for_receipt(receipt: &Receipt) -> Vec<Self> {
+        let raw = |key: &str| {
+            receipt
+                .usage_raw
+                .get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string)
+        };
+        Self::collect(raw("tenant"), raw(API_KEY_USAGE_KEY))
+    }
+
+    fn collect(tenant: Option<String>, api_key: Option<String>) -> Vec<Self> {
+        tenant
+            .map(Self::Tenant)
+            .into_iter()
+            .chain(api_key.map(Self::ApiKey))
+            .collect()
+    }
+}
+
+impl fmt::Display for QuotaScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tenant(id) => write!(f, "tenant '{id}'"),
+            Self::ApiKey(id) => write!(f, "API key '{id}'"),
+        }
+    }
+}
+
+/// Usage counted against a scope within one window.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    /// Input plus output tokens.
+    pub tokens: u64,
+    /// Estimated spend in USD.
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone)]
+struct WindowUsage {
+    start: DateTime<Utc>,
+    usage: QuotaUsage,
+}
+
+/// Projected input tokens a run holds against its scopes' quotas until it
+/// is [`settle`](QuotaEnforcer::settle)d or
+/// [`release`](QuotaEnforcer::release)d.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[must_use]
+pub struct QuotaReservation {
+    tokens: u64,
+    holds: Vec<(QuotaScope, QuotaWindow, DateTime<Utc>)>,
+}
+
+impl QuotaReservation {
+    /// Tokens held against each quota window.
+    #[must_use]
+    pub fn tokens(&self) -> u64 {
+        self.tokens
+    }
+}
+
+/// Enforces [`Quota`]s across runs.
+///
+/// # Examples
+///
+/// ```
+/// use abp_runtime::quota::{Quota, QuotaEnforcer, QuotaScope, QuotaWindow};
+///
+/// let quotas = QuotaEnforcer::new().with_quota(
+///     QuotaScope::Tenant("acme".into()),
+///     Quota::tokens(QuotaWindow::Monthly, 1_000_000),
+/// );
+/// assert_eq!(quotas.quotas(&QuotaScope::Tenant("acme".into())).len(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct QuotaEnforcer {
+    quotas: BTreeMap<QuotaScope, Vec<Quota>>,
+    usage: Mutex<BTreeMap<(QuotaScope, QuotaWindow), WindowUsage>>,
+}
+
+impl QuotaEnforcer {
+    /// Create an enforcer with no quotas.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a quota for `scope` (builder pattern).
+    #[must_use]
+    pub fn with_quota(mut self, scope: QuotaScope, quota: Quota) -> Self {
+        self.quotas.entry(scope).or_default().push(quota);
+        self
+    }
+
+    /// Quotas configured for `scope`.
+    #[must_use]
+    pub fn quotas(&self, scope: &QuotaScope) -> &[Quota] {
+        self.quotas.get(scope).map_or(&[], Vec::as_slice)
+    }
+
+    /// Usage counted against `scope` in the `window` containing `now`,
+    /// including tokens reserved by runs still in flight.
+    #[must_use]
+    pub fn usage(&self, scope: &QuotaScope, window: QuotaWindow, now: DateTime<Utc>) -> QuotaUsage {
+        let usage = self.usage.lock().expect("quota mutex poisoned");
+        usage
+            .get(&(scope.clone(), window))
+            .filter(|w| w.start == window.start(now))
+            .map(|w| w.usage.clone())
+            .unwrap_or_default()
+    }
+
+    /// Admit `work_order` and reserve its projected input tokens against
+    /// every quota of the scopes it is billed to, in the windows containing
+    /// `now`.
+    ///
+    /// The run is rejected if a scope has already used up a quota, or if its
+    /// projected input would take a token quota past its cap. Reservations
+    /// count as usage until they are settled, so concurrent runs see each
+    /// other's share.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`ErrorCode::QuotaExceeded`] error naming the scope,
+    /// window, limit, and when the window resets. Nothing is reserved.
+    pub fn reserve(
+        &self,
+        work_order: &WorkOrder,
+        now: DateTime<Utc>,
+    ) -> Result<QuotaReservation, AbpError> {
+        let scopes: Vec<_> = QuotaScope::for_work_order(work_order)
+            .into_iter()
+            .filter(|scope| !self.quotas(scope).is_empty())
+            .collect();
+        if scopes.is_empty() {
+            return Ok(QuotaReservation::default());
+        }
+        let projected = abp_tokenize::projected_input_tokens(work_order)
+            .unwrap_or_else(|| abp_tokenize::count_work_order(work_order));
+
+        let mut usage = self.usage.lock().expect("quota mutex poisoned");
+        for scope in &scopes {
+            for quota in self.quotas(scope) {
+                let used = usage
+                    .get(&(scope.clone(), quota.window))
+                    .filter(|w| w.start == quota.window.start(now))
+                    .map(|w| w.usage.clone())
+                    .unwrap_or_default();
+                let exceeded = if quota
+                    .max_tokens
+                    .is_some_and(|max| used.tokens >= max || used.tokens + projected > max)
+                {
+                    Some((
+                        "tokens",
+                        used.tokens as f64,
+                        quota.max_tokens.unwrap_or(0) as f64,
+                    ))
+                } else if quota.max_cost_usd.is_some_and(|max| used.cost_usd >= max) {
+                    Some(("cost_usd", used.cost_usd, quota.max_cost_usd.unwrap_or(0.0)))
+                } else {
+                    None
+                };
+                if let Some((dimension, used, limit)) = exceeded {
+                    return Err(AbpError::new(
+                        ErrorCode::QuotaExceeded,
+                        format!(
+                            "{scope} exhausted its {} {dimension} quota ({used} of {limit})",
+                            quota.window
+                        ),
+                    )
+                    .with_context("scope", scope)
+                    .with_context("window", quota.window)
+                    .with_context("dimension", dimension)
+                    .with_context("used", used)
+                    .with_context("limit", limit)
+                    .with_context("projected_input_tokens", projected)
+                    .with_context("resets_at", quota.window.end(now).to_rfc3339()));
+                }
+            }
+        }
+
+        let mut reservation = QuotaReservation {
+            tokens: projected,
+            holds: Vec::new(),
+        };
+        for scope in scopes {
+            // Quotas on the same window share one counter.
+            let windows: BTreeSet<_> = self.quotas(&scope).iter().map(|q| q.window).collect();
+            for window in windows {
+                let start = window.start(now);
+                let entry = window_entry(&mut usage, &scope, window, start);
+                if entry.start != start {
+                    continue;
+                }
+                entry.usage.tokens += projected;
+                reservation.holds.push((scope.clone(), window, start));
+            }
+        }
+        Ok(reservation)
+    }
+
+    /// Give back the tokens `reservation` holds, for a run that finished
+    /// without a receipt to bill.
+    pub fn release(&self, reservation: QuotaReservation) {
+        let mut usage = self.usage.lock().expect("quota mutex poisoned");
+        for (scope, window, start) in reservation.holds {
+            if let Some(entry) = usage.get_mut(&(scope, window))
+                && entry.start == start
+            {
+                entry.usage.tokens = entry.usage.tokens.saturating_sub(reservation.tokens);
+            }
+        }
+    }
+
+    /// Replace `reservation` with the usage `receipt` reports, counted in
+    /// the windows containing `at`.
+    pub fn settle(&self, reservation: QuotaReservation, receipt: &Receipt, at: DateTime<Utc>) {
+        self.release(reservation);
+        self.record(receipt, at);
+    }
+
+    /// Count `receipt`'s usage against the scopes in its `usage_raw`, in the
+    /// windows containing `at`.
+    ///
+    /// Usage from a window older than the one already tracked is dropped;
+    /// usage from a newer window starts a fresh count.
+    pub fn record(&self, receipt: &Receipt, at: DateTime<Utc>) {
+        let tokens =
+            receipt.usage.input_tokens.unwrap_or(0) + receipt.usage.output_tokens.unwrap_or(0);
+        let cost_usd = receipt.usage.estimated_cost_usd.unwrap_or(0.0);
+        let mut usage = self.usage.lock().expect("quota mutex poisoned");
+        for scope in QuotaScope::for_receipt(receipt) {
+            let Some(quotas) = self.quotas.get(&scope) else {
+                continue;
+            };
+            // Quotas on the same window share one counter.
+            let windows: BTreeSet<_> = quotas.iter().map(|q| q.window).collect();
+            for window in windows {
+                let start = window.start(at);
+                let entry = window_entry(&mut usage, &scope, window, start);
+                if entry.start != start {
+                    continue;
+                }
+                entry.usage.tokens += tokens;
+                entry.usage.cost_usd += cost_usd;
+            }
+        }
+    }
+
+    /// Rebuild usage from every receipt in `store`, keyed by each receipt's
+    /// start time. Returns the number of receipts counted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store cannot be listed or a receipt cannot be
+    /// loaded.
+    pub fn restore_from(&self, store: &ReceiptStore) -> anyhow::Result<usize> {
+        let mut receipts = store
+            .list()?
+            .into_iter()
+            .map(|id| store.load(id))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        receipts.sort_by_key(|r| r.meta.started_at);
+        for receipt in &receipts {
+            self.record(receipt, receipt.meta.started_at);
+        }
+        Ok(receipts.len())
+    }
+}
+
+/// The counter for `scope` in `window`, moved on to the window starting at
+/// `start` if that is newer. An older window leaves the counter as it is.
+fn window_entry<'a>(
+    usage: &'a mut BTreeMap<(QuotaScope, QuotaWindow), WindowUsage>,
+    scope: &QuotaScope,
+    window: QuotaWindow,
+    start: DateTime<Utc>,
+) -> &'a mut WindowUsage {
+    let entry = usage
+        .entry((scope.clone(), window))
+        .or_insert_with(|| WindowUsage {
+            start,
+            usage: QuotaUsage::default(),
+        });
+    if start > entry.start {
+        *entry = WindowUsage {
+            start,
+            usage: QuotaUsage::default(),
+        };
+    }
+    entry
+}
+
+/// A run's reservation against its scopes' quotas.
+///
+/// Dropping a charge that was never [`settle`](Self::settle)d releases it,
+/// so a run that is refused, fails, or is served from the cache is billed
+/// nothing.
+#[derive(Debug)]
+pub(crate) struct QuotaCharge {
+    enforcer: Arc<QuotaEnforcer>,
+    reservation: Option<QuotaReservation>,
+}
+
+impl QuotaCharge {
+    pub(crate) fn new(enforcer: Arc<QuotaEnforcer>, reservation: QuotaReservation) -> Self {
+        Self {
+            enforcer,
+            reservation: Some(reservation),
+        }
+    }
+
+    /// Bill the usage `receipt` reports in place of the reservation.
+    pub(crate) fn settle(mut self, receipt: &Receipt, at: DateTime<Utc>) {
+        if let Some(reservation) = self.reservation.take() {
+            self.enforcer.settle(reservation, receipt, at);
+        }
+    }
+}
+
+impl Drop for QuotaCharge {
+    fn drop(&mut self) {
+        if let Some(reservation) = self.reservation.take() {
+            self.enforcer.release(reservation);
+        }
+    }
+}
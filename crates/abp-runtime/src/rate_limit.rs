@@ -0,0 +1,412 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Per-backend request and token rate limits.
+//!
+//! Upstream providers throttle by requests and tokens per minute. A
+//! [`BackendRateLimit`](crate::rate_limit::BackendRateLimit) mirrors those
+//! limits for one backend, and
+//! [`Runtime::run_streaming`](crate::Runtime::run_streaming) checks it
+//! before dispatch, so the runtime slows down before the provider starts
+//! answering `429`.
+//!
+//! Each limit is a token bucket holding one minute's allowance and refilling
+//! continuously. A run takes one request and its projected input tokens (see
+//! [`abp_tokenize::projected_input_tokens`]); when the receipt arrives, the
+//! token bucket is corrected to the tokens the run actually used, input plus
+//! output. A run that does not fit either waits for the buckets to refill
+//! ([`RateLimitMode::Wait`](crate::rate_limit::RateLimitMode::Wait), bounded by
+//! [`BackendRateLimit::max_wait`](crate::rate_limit::BackendRateLimit::max_wait))
+//! or is rejected at once
+//! ([`RateLimitMode::Reject`](crate::rate_limit::RateLimitMode::Reject)), in
+//! both cases with
+//! [`ErrorCode::BackendRateLimited`](abp_error::ErrorCode::BackendRateLimited)
+//! and a `retry_after_ms` context entry. A limit of zero admits nothing, so
+//! its runs are always rejected, without a `retry_after_ms`.
+//!
+//! A run's charge is refunded if the run is refused after admission, before
+//! it reaches the backend (by middleware, policy compilation, or capability
+//! negotiation, for example).
+
+use abp_core::clock::Clock;
+use abp_core::{UsageNormalized, WorkOrder};
+use abp_error::{AbpError, ErrorCode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// What to do with a run that would exceed its backend's rate limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitMode {
+    /// Hold the run until the limit allows it.
+    #[default]
+    Wait,
+    /// Fail the run immediately.
+    Reject,
+}
+
+/// Requests and tokens per minute allowed on one backend.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BackendRateLimit {
+    /// Runs started per minute. `None` means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<u32>,
+    /// Input plus output tokens per minute. `None` means unlimited.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens_per_minute: Option<u64>,
+    /// Whether an over-limit run waits or fails.
+    #[serde(default)]
+    pub mode: RateLimitMode,
+    /// Longest a run waits in [`RateLimitMode::Wait`] before it fails
+    /// instead. `None` means no bound.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "option_duration_millis"
+    )]
+    pub max_wait: Option<Duration>,
+}
+
+impl BackendRateLimit {
+    /// No limits; over-limit runs wait.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow `n` runs per minute (builder pattern).
+    ///
+    /// Zero admits no runs at all.
+    #[must_use]
+    pub fn requests_per_minute(mut self, n: u32) -> Self {
+        self.requests_per_minute = Some(n);
+        self
+    }
+
+    /// Allow `n` tokens per minute (builder pattern).
+    ///
+    /// Zero admits no runs at all.
+    #[must_use]
+    pub fn tokens_per_minute(mut self, n: u64) -> Self {
+        self.tokens_per_minute = Some(n);
+        self
+    }
+
+    /// Fail over-limit runs instead of holding them (builder pattern).
+    #[must_use]
+    pub fn reject(mut self) -> Self {
+        self.mode = RateLimitMode::Reject;
+        self
+    }
+
+    /// Fail a waiting run once it would wait longer than `d` (builder
+    /// pattern).
+    #[must_use]
+    pub fn max_wait(mut self, d: Duration) -> Self {
+        self.max_wait = Some(d);
+        self
+    }
+}
+
+/// A bucket holding one minute's allowance.
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    /// Goes negative when a run used more tokens than it was charged.
+    available: f64,
+}
+
+impl Bucket {
+    fn per_minute(n: f64) -> Self {
+        Self {
+            capacity: n,
+            available: n,
+        }
+    }
+
+    fn refill(&mut self, elapsed: Duration) {
+        let added = self.capacity * elapsed.as_secs_f64() / 60.0;
+        self.available = (self.available + added).min(self.capacity);
+    }
+
+    /// Time until `amount` fits; a request larger than the whole bucket
+    /// only needs a full one. An empty bucket never fits anything.
+    fn wait_for(&self, amount: f64) -> Duration {
+        if self.capacity <= 0.0 {
+            return Duration::MAX;
+        }
+        let deficit = amount.min(self.capacity) - self.available;
+        if deficit <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(deficit * 60.0 / self.capacity)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BackendBuckets {
+    limit: BackendRateLimit,
+    requests: Option<Bucket>,
+    tokens: Option<Bucket>,
+    refilled_at: Option<Instant>,
+}
+
+impl BackendBuckets {
+    fn new(limit: BackendRateLimit) -> Self {
+        Self {
+            requests: limit
+                .requests_per_minute
+                .map(|n| Bucket::per_minute(n.into())),
+            tokens: limit
+                .tokens_per_minute
+                .map(|n| Bucket::per_minute(n as f64)),
+            limit,
+            refilled_at: None,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        if let Some(last) = self.refilled_at {
+            let elapsed = now.saturating_duration_since(last);
+            for bucket in [&mut self.requests, &mut self.tokens].into_iter().flatten() {
+                bucket.refill(elapsed);
+            }
+        }
+        self.refilled_at = Some(now);
+    }
+
+    /// Take one request and `tokens`, or say how long until they fit.
+    fn try_take(&mut self, tokens: u64, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+        let wait = [(&self.requests, 1.0), (&self.tokens, tokens as f64)]
+            .into_iter()
+            .filter_map(|(bucket, amount)| bucket.as_ref().map(|b| b.wait_for(amount)))
+            .max()
+            .unwrap_or_default();
+        if !wait.is_zero() {
+            return Err(wait);
+        }
+        if let Some(bucket) = &mut self.requests {
+            bucket.available -= 1.0;
+        }
+        if let Some(bucket) = &mut self.tokens {
+            bucket.available -= tokens as f64;
+        }
+        Ok(())
+    }
+
+    /// Give back one request and `tokens` taken by [`try_take`](Self::try_take).
+    fn put_back(&mut self, tokens: u64) {
+        for (bucket, amount) in [(&mut self.requests, 1.0), (&mut self.tokens, tokens as f64)] {
+            if let Some(b) = bucket {
+                b.available = (b.available + amount).min(b.capacity);
+            }
+        }
+    }
+}
+
+/// Rate limit state for every limited backend.
+///
+/// Backends without a [`BackendRateLimit`] are never held back.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    backends: Mutex<BTreeMap<String, BackendBuckets>>,
+}
+
+impl RateLimiter {
+    /// A limiter with no limits.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit `backend`, replacing any earlier limit and its state.
+    pub fn set(&self, backend: impl Into<String>, limit: BackendRateLimit) {
+        self.lock()
+            .insert(backend.into(), BackendBuckets::new(limit));
+    }
+
+    /// The limit on `backend`, if any.
+    #[must_use]
+    pub fn limit(&self, backend: &str) -> Option<BackendRateLimit> {
+        self.lock().get(backend).map(|b| b.limit.clone())
+    }
+
+    /// Take one request and `tokens` from `backend`'s allowance without
+    /// waiting.
+    ///
+    /// On failure nothing is taken and the error says how long until the
+    /// run would fit; [`Duration::MAX`] means it never will, because the
+    /// limit is zero.
+    pub fn try_acquire(
+        &self,
+        backend: &str,
+        tokens: u64,
+        clock: &dyn Clock,
+    ) -> Result<(), Duration> {
+        match self.lock().get_mut(backend) {
+            Some(buckets) => buckets.try_take(tokens, clock.instant()),
+            None => Ok(()),
+        }
+    }
+
+    /// Admit `work_order` to `backend`, waiting if its limit says to.
+    ///
+    /// Returns the tokens charged, for [`settle`](Self::settle), or `None`
+    /// when the backend is not limited.
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::BackendRateLimited`] when the run does not fit and the
+    /// limit rejects it, or would have to wait longer than
+    /// [`BackendRateLimit::max_wait`]. A zero limit fails at once in either
+    /// mode.
+    pub async fn acquire(
+        &self,
+        backend: &str,
+        work_order: &WorkOrder,
+        clock: &dyn Clock,
+    ) -> Result<Option<u64>, AbpError> {
+        let Some(limit) = self.limit(backend) else {
+            return Ok(None);
+        };
+        let tokens = abp_tokenize::projected_input_tokens(work_order).unwrap_or(0);
+        let mut waited = Duration::ZERO;
+        loop {
+            let wait = match self.try_acquire(backend, tokens, clock) {
+                Ok(()) => return Ok(Some(tokens)),
+                Err(Duration::MAX) => {
+                    warn!(target: "abp.runtime", backend, "rejecting run on zero rate limit");
+                    return Err(zero_limit(backend));
+                }
+                Err(wait) => wait,
+            };
+            let over_max = limit
+                .max_wait
+                .is_some_and(|max| waited.saturating_add(wait) > max);
+            if limit.mode == RateLimitMode::Reject || over_max {
+                warn!(
+                    target: "abp.runtime",
+                    backend,
+                    retry_after_ms = wait.as_millis() as u64,
+                    "rejecting run over backend rate limit"
+                );
+                return Err(rate_limited(backend, wait));
+            }
+            debug!(
+                target: "abp.runtime",
+                backend,
+                wait_ms = wait.as_millis() as u64,
+                "waiting for backend rate limit"
+            );
+            tokio::time::sleep(clock.sleep_for(wait)).await;
+            waited = waited.saturating_add(wait);
+        }
+    }
+
+    /// Correct `backend`'s token allowance once a run charged `charged`
+    /// tokens has reported `usage`.
+    ///
+    /// Runs that report no token counts keep their charge.
+    pub fn settle(&self, backend: &str, charged: u64, usage: &UsageNormalized) {
+        if usage.input_tokens.is_none() && usage.output_tokens.is_none() {
+            return;
+        }
+        let used = usage.input_tokens.unwrap_or(0) + usage.output_tokens.unwrap_or(0);
+        if let Some(bucket) = self.lock().get_mut(backend).and_then(|b| b.tokens.as_mut()) {
+            bucket.available =
+                (bucket.available + charged as f64 - used as f64).min(bucket.capacity);
+        }
+    }
+
+    /// Give back the request and `charged` tokens taken by a run that never
+    /// reached `backend`.
+    pub fn refund(&self, backend: &str, charged: u64) {
+        if let Some(buckets) = self.lock().get_mut(backend) {
+            buckets.put_back(charged);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, BackendBuckets>> {
+        self.backends.lock().expect("rate limiter lock poisoned")
+    }
+}
+
+fn rate_limited(backend: &str, retry_after: Duration) -> AbpError {
+    let retry_after_ms = u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX);
+    AbpError::new(
+        ErrorCode::BackendRateLimited,
+        format!("backend '{backend}' is over its rate limit"),
+    )
+    .with_context("backend", backend)
+    .with_context("retry_after_ms", retry_after_ms)
+}
+
+fn zero_limit(backend: &str) -> AbpError {
+    AbpError::new(
+        ErrorCode::BackendRateLimited,
+        format!("backend '{backend}' has a rate limit of zero and accepts no runs"),
+    )
+    .with_context("backend", backend)
+}
+
+/// A run's admission charge against its backend's rate limit.
+///
+/// Dropping a charge that was never [`dispatch`](Self::dispatch)ed refunds
+/// it, so a run refused before it reaches the backend costs nothing.
+#[derive(Debug)]
+pub(crate) struct RateCharge {
+    limiter: Arc<RateLimiter>,
+    backend: String,
+    tokens: u64,
+    dispatched: bool,
+}
+
+impl RateCharge {
+    pub(crate) fn new(limiter: Arc<RateLimiter>, backend: &str, tokens: u64) -> Self {
+        Self {
+            limiter,
+            backend: backend.to_string(),
+            tokens,
+            dispatched: false,
+        }
+    }
+
+    /// The run has been handed to the backend; keep the charge.
+    pub(crate) fn dispatch(&mut self) {
+        self.dispatched = true;
+    }
+
+    /// Correct the charge to the usage the run reported.
+    pub(crate) fn settle(mut self, usage: &UsageNormalized) {
+        self.dispatched = true;
+        self.limiter.settle(&self.backend, self.tokens, usage);
+    }
+}
+
+impl Drop for RateCharge {
+    fn drop(&mut self) {
+        if !self.dispatched {
+            self.limiter.refund(&self.backend, self.tokens);
+        }
+    }
+}
+
+mod option_duration_millis {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(v: &Option<Duration>, s: S) -> Result<S::Ok, S::Error> {
+        match v {
+            Some(d) => s.serialize_u64(d.as_millis() as u64),
+            None => s.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Duration>, D::Error> {
+        let ms: Option<u64> = Option::deserialize(d)?;
+        Ok(ms.map(Duration::from_millis))
+    }
+}
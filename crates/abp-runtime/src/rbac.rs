@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Role-based access control for runtime operations.
+//!
+//! Roles and identity bindings come from the `[rbac]` section of the
+//! backplane config ([`RbacConfig`](crate::rbac::RbacConfig)). The runtime
+//! does not know who is calling it; the surfaces in front of it — the daemon
+//! HTTP API and the CLI — resolve a caller identity and ask
+//! [`Runtime::authorize`](crate::Runtime::authorize) before submitting,
+//! cancelling or deleting runs, or reading runs and receipts. Each
+//! decision is appended to the runtime's audit log, if one is attached.
+
+use serde::{Deserialize, Serialize};
+
+pub use abp_config::rbac::{Permission, RbacConfig};
+
+/// A caller was refused an operation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[error("{} is not permitted to {permission} on {resource}", .identity.as_deref().unwrap_or("anonymous caller"))]
+pub struct AccessDenied {
+    /// Who asked; `None` for an anonymous caller.
+    pub identity: Option<String>,
+    /// The operation that was refused.
+    pub permission: Permission,
+    /// What the operation targeted, e.g. `run:<uuid>` or `receipts`.
+    pub resource: String,
+}
+
+/// Check `permission` for `identity` against `rbac`.
+///
+/// With no RBAC configuration every caller is allowed everything.
+///
+/// # Errors
+///
+/// Returns [`AccessDenied`] if none of the caller's roles grant `permission`.
+pub fn check(
+    rbac: Option<&RbacConfig>,
+    identity: Option<&str>,
+    permission: Permission,
+    resource: &str,
+) -> Result<(), AccessDenied> {
+    match rbac {
+        Some(rbac) if !rbac.allows(identity, permission) => Err(AccessDenied {
+            identity: identity.map(str::to_string),
+            permission,
+            resource: resource.to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
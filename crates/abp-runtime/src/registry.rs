@@ -1,14 +1,47 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 //! Typed wrapper around the backend map used by the runtime.
+//!
+//! Backends can also be declared in a TOML file and loaded with
+//! [`BackendRegistry::from_config`] or
+//! [`Runtime::register_backends_from_config`](crate::Runtime::register_backends_from_config):
+//!
+//! ```toml
+//! [backends.local]
+//! type = "sidecar"
+//! command = "node"
+//! args = ["hosts/node/host.js"]
+//! env = { NODE_ENV = "production" }
+//! dialect = "claude"
+//! priority = 80
+//! capabilities = { streaming = "native", tool_bash = "unsupported" }
+//!
+//! [backends.remote]
+//! type = "grpc"
+//! endpoint = "https://agents.internal:50051"
+//! ```
+//!
+//! Each entry accepts the same keys as a `[backends.<name>]` table in
+//! `backplane.toml`, plus `env` (sidecars only), `dialect`, `priority`, and
+//! `capabilities`. Backends reachable over HTTP are declared as `grpc`
+//! (an `http://` or `https://` endpoint) or `websocket`.
 
+use abp_config::{BackendEntry, BackplaneConfig, ConfigError};
 use abp_core::{AgentEvent, BackendIdentity, CapabilityManifest, Receipt, WorkOrder};
-use abp_integrations::Backend;
+use abp_dialect::Dialect;
+use abp_host::SidecarSpec;
+use abp_integrations::{Backend, GrpcBackend, IpcBackend, MockBackend, SidecarBackend, WsBackend};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
+/// Projection priority for declared backends that do not set `priority`.
+pub const DEFAULT_PRIORITY: u32 = 50;
+
 /// A typed registry of named [`Backend`] implementations.
 #[derive(Default)]
 pub struct BackendRegistry {
@@ -47,6 +80,32 @@ impl BackendRegistry {
         self.backends.contains_key(name)
     }
 
+    /// Build a registry from the backends declared in the TOML file at `path`.
+    ///
+    /// See the [module docs](self) for the file format. Use
+    /// [`Runtime::register_backends_from_config`](crate::Runtime::register_backends_from_config)
+    /// to also add projection matrix entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigError`] if the file cannot be read, does not parse,
+    /// or declares an invalid backend.
+    pub fn from_config(path: &Path) -> Result<Self, ConfigError> {
+        Ok(Self::from_decls(&BackendDecls::load(path)?))
+    }
+
+    /// Build a registry from already-loaded backend declarations.
+    #[must_use]
+    pub fn from_decls(decls: &BackendDecls) -> Self {
+        let mut registry = Self::default();
+        for (name, decl) in &decls.backends {
+            registry
+                .backends
+                .insert(name.clone(), Arc::new(decl.build()));
+        }
+        registry
+    }
+
     /// Remove a backend by name, returning it if it existed.
     pub fn remove(&mut self, name: &str) -> Option<Box<dyn Backend>> {
         self.backends
@@ -74,4 +133,177 @@ impl Backend for SharedBackend {
     ) -> anyhow::Result<Receipt> {
         self.0.run(run_id, work_order, events_tx).await
     }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<abp_integrations::ModelInfo>> {
+        self.0.list_models().await
+    }
+
+    fn vendor_namespace(&self) -> Option<abp_integrations::VendorNamespace> {
+        self.0.vendor_namespace()
+    }
+}
+
+/// A single backend declared in a registry config file.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BackendDecl {
+    /// Transport and its settings, tagged by `type`.
+    #[serde(flatten)]
+    pub entry: BackendEntry,
+    /// Extra environment variables for a sidecar process.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Projection priority; [`DEFAULT_PRIORITY`] when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u32>,
+    /// Dialect the backend speaks. Only backends with a dialect get a
+    /// projection matrix entry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dialect: Option<Dialect>,
+    /// Capability levels that replace what the backend advertises.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub capabilities: CapabilityManifest,
+}
+
+impl BackendDecl {
+    /// Construct the backend, wrapped so capability overrides apply.
+    pub(crate) fn build(&self) -> DeclaredBackend {
+        let timeout = |secs: &Option<u64>| secs.map(Duration::from_secs);
+        let inner: Arc<dyn Backend> = match &self.entry {
+            BackendEntry::Mock {} => Arc::new(MockBackend),
+            BackendEntry::Sidecar { command, args, .. } => {
+                let mut spec = SidecarSpec::new(command);
+                spec.args = args.clone();
+                spec.env = self.env.clone();
+                Arc::new(SidecarBackend::new(spec))
+            }
+            BackendEntry::Grpc {
+                endpoint,
+                timeout_secs,
+            } => {
+                let mut backend = GrpcBackend::new(endpoint);
+                if let Some(t) = timeout(timeout_secs) {
+                    backend = backend.with_connect_timeout(t);
+                }
+                Arc::new(backend)
+            }
+            BackendEntry::WebSocket { url, timeout_secs } => {
+                let mut backend = WsBackend::new(url);
+                if let Some(t) = timeout(timeout_secs) {
+                    backend = backend.with_connect_timeout(t);
+                }
+                Arc::new(backend)
+            }
+            BackendEntry::Ipc { path, timeout_secs } => {
+                let mut backend = IpcBackend::new(path);
+                if let Some(t) = timeout(timeout_secs) {
+                    backend = backend.with_connect_timeout(t);
+                }
+                Arc::new(backend)
+            }
+        };
+        DeclaredBackend {
+            inner,
+            overrides: self.capabilities.clone(),
+        }
+    }
+}
+
+/// The `[backends.<name>]` tables of a registry config file.
+///
+/// Other top-level keys are ignored, so the declarations can live in
+/// `backplane.toml` alongside the rest of the configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BackendDecls {
+    /// Declared backends, keyed by registration name.
+    #[serde(default)]
+    pub backends: BTreeMap<String, BackendDecl>,
+}
+
+impl BackendDecls {
+    /// Read and validate the declarations in the TOML file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// [`ConfigError::FileNotFound`] if the file cannot be read, otherwise
+    /// as for [`parse`](Self::parse).
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let content = std::fs::read_to_string(path).map_err(|_| ConfigError::FileNotFound {
+            path: path.display().to_string(),
+        })?;
+        Self::parse(&content)
+    }
+
+    /// Parse and validate declarations from a TOML string.
+    ///
+    /// Transport settings are checked with [`abp_config::validate_config`].
+    ///
+    /// # Errors
+    ///
+    /// [`ConfigError::ParseError`] for malformed TOML or unknown values, and
+    /// [`ConfigError::ValidationError`] for invalid settings or `env` on a
+    /// backend that is not a sidecar.
+    pub fn parse(content: &str) -> Result<Self, ConfigError> {
+        let decls: Self = toml::from_str(content).map_err(|e| ConfigError::ParseError {
+            reason: e.to_string(),
+        })?;
+
+        let mut reasons: Vec<String> = decls
+            .backends
+            .iter()
+            .filter(|(_, d)| !d.env.is_empty() && !matches!(d.entry, BackendEntry::Sidecar { .. }))
+            .map(|(name, _)| format!("backend '{name}': env is only supported for sidecars"))
+            .collect();
+        let config = BackplaneConfig {
+            backends: decls
+                .backends
+                .iter()
+                .map(|(name, d)| (name.clone(), d.entry.clone()))
+                .collect(),
+            ..BackplaneConfig::default()
+        };
+        if let Err(ConfigError::ValidationError { reasons: more }) =
+            abp_config::validate_config(&config)
+        {
+            reasons.extend(more);
+        }
+        if reasons.is_empty() {
+            Ok(decls)
+        } else {
+            Err(ConfigError::ValidationError { reasons })
+        }
+    }
+}
+
+/// A declared backend with its capability overrides applied.
+pub(crate) struct DeclaredBackend {
+    inner: Arc<dyn Backend>,
+    overrides: CapabilityManifest,
+}
+
+#[async_trait]
+impl Backend for DeclaredBackend {
+    fn identity(&self) -> BackendIdentity {
+        self.inner.identity()
+    }
+    fn capabilities(&self) -> CapabilityManifest {
+        let mut caps = self.inner.capabilities();
+        caps.extend(self.overrides.clone());
+        caps
+    }
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        self.inner.run(run_id, work_order, events_tx).await
+    }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<abp_integrations::ModelInfo>> {
+        self.inner.list_models().await
+    }
+
+    fn vendor_namespace(&self) -> Option<abp_integrations::VendorNamespace> {
+        self.inner.vendor_namespace()
+    }
 }
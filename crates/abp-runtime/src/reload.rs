@@ -0,0 +1,279 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Hot reload of backends declared in a config file.
+//!
+//! [`BackendWatcher`](crate::reload::BackendWatcher) polls a file in the
+//! format described in [`registry`](crate::registry) and applies each valid
+//! change to a running, shared [`Runtime`] with
+//! [`Runtime::reload_backends`]:
+//!
+//! * Newly declared backends are registered.
+//! * Backends no longer declared are deregistered. Runs already started on
+//!   them drain normally; new runs can no longer select them.
+//! * Backends whose transport, `env`, or `capabilities` changed are
+//!   replaced; runs in flight keep the old instance.
+//! * Projection matrix entries follow `dialect`, `priority`, and
+//!   `capabilities`.
+//!
+//! Backends registered in code are never touched, and a file that fails to
+//! parse or validate is skipped with a warning, leaving the last good set in
+//! place.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use abp_config::ConfigError;
+use abp_integrations::Backend;
+use abp_projection::ProjectionMatrix;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::Runtime;
+use crate::registry::{BackendDecl, BackendDecls, DEFAULT_PRIORITY};
+
+/// Default interval between polls.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// After a change is seen, wait this long for writers to finish.
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(100);
+
+/// What a reload changed, by backend name in sorted order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackendReload {
+    /// Newly declared backends that were registered.
+    pub added: Vec<String>,
+    /// Backends no longer declared that were deregistered.
+    pub removed: Vec<String>,
+    /// Backends rebuilt because their transport, `env`, or capabilities
+    /// changed.
+    pub replaced: Vec<String>,
+    /// Backends whose only changes were `dialect` or `priority`.
+    pub reprojected: Vec<String>,
+}
+
+impl BackendReload {
+    /// Whether the reload changed nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.replaced.is_empty()
+            && self.reprojected.is_empty()
+    }
+}
+
+impl Runtime {
+    /// Move the declared backends from `old` to `new` on a running runtime.
+    ///
+    /// See [`reload`](crate::reload) for what each kind of change does.
+    /// Registry changes are announced on the
+    /// [`event_bus`](Self::event_bus) as with
+    /// [`attach_backend`](Self::attach_backend) and
+    /// [`detach_backend`](Self::detach_backend). A projection matrix is
+    /// created from [`ProjectionMatrix::with_defaults`] the first time a
+    /// declared backend needs one.
+    pub fn reload_backends(&self, old: &BackendDecls, new: &BackendDecls) -> BackendReload {
+        let mut report = BackendReload::default();
+
+        for name in old.backends.keys() {
+            if !new.backends.contains_key(name) {
+                self.detach_backend(name);
+                self.unproject(name);
+                report.removed.push(name.clone());
+            }
+        }
+
+        for (name, decl) in &new.backends {
+            let previous = old.backends.get(name);
+            if previous == Some(decl) {
+                continue;
+            }
+            let backend = decl.build();
+            self.project(name, decl, backend.capabilities());
+            match previous {
+                None => {
+                    self.attach_backend(name, backend);
+                    report.added.push(name.clone());
+                }
+                Some(prev)
+                    if prev.entry != decl.entry
+                        || prev.env != decl.env
+                        || prev.capabilities != decl.capabilities =>
+                {
+                    self.attach_backend(name, backend);
+                    report.replaced.push(name.clone());
+                }
+                Some(_) => report.reprojected.push(name.clone()),
+            }
+        }
+
+        report
+    }
+
+    fn project(&self, name: &str, decl: &BackendDecl, capabilities: abp_core::CapabilityManifest) {
+        let mut slot = self
+            .projection
+            .write()
+            .expect("projection matrix lock poisoned");
+        match decl.dialect {
+            Some(dialect) => slot
+                .get_or_insert_with(ProjectionMatrix::with_defaults)
+                .register_backend(
+                    name,
+                    capabilities,
+                    dialect,
+                    decl.priority.unwrap_or(DEFAULT_PRIORITY),
+                ),
+            None => {
+                if let Some(matrix) = slot.as_mut() {
+                    matrix.remove_backend(name);
+                }
+            }
+        }
+    }
+
+    fn unproject(&self, name: &str) {
+        if let Some(matrix) = self
+            .projection
+            .write()
+            .expect("projection matrix lock poisoned")
+            .as_mut()
+        {
+            matrix.remove_backend(name);
+        }
+    }
+}
+
+/// Polls a backend declaration file and reloads a runtime when it changes.
+///
+/// # Lifecycle
+///
+/// 1. Create with [`BackendWatcher::new`].
+/// 2. Call [`start`](BackendWatcher::start), which registers the declared
+///    backends and spawns a background thread.
+/// 3. Call [`stop`](BackendWatcher::stop) to shut down.
+///
+/// The watcher is stopped when dropped.
+pub struct BackendWatcher {
+    path: PathBuf,
+    poll_interval: Duration,
+    running: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl BackendWatcher {
+    /// Create a watcher for `path` with the default 5-second poll interval.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            running: Arc::new(AtomicBool::new(false)),
+            handle: None,
+        }
+    }
+
+    /// Override the poll interval.
+    #[must_use]
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Register the backends declared now, then keep `runtime` in step with
+    /// the file from a background thread.
+    ///
+    /// Does nothing and returns an empty report if already running.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConfigError`] without starting if the file cannot be
+    /// loaded or declares an invalid backend.
+    pub fn start(&mut self, runtime: Arc<Runtime>) -> Result<BackendReload, ConfigError> {
+        if self.running.load(Ordering::SeqCst) {
+            return Ok(BackendReload::default());
+        }
+
+        let mut last_mtime = file_mtime(&self.path);
+        let mut current = BackendDecls::load(&self.path)?;
+        let initial = runtime.reload_backends(&BackendDecls::default(), &current);
+
+        self.running.store(true, Ordering::SeqCst);
+        let running = Arc::clone(&self.running);
+        let path = self.path.clone();
+        let interval = self.poll_interval;
+
+        self.handle = Some(thread::spawn(move || {
+            while running.load(Ordering::SeqCst) {
+                thread::sleep(interval);
+                if !running.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let mtime = file_mtime(&path);
+                if mtime == last_mtime || mtime.is_none() {
+                    continue;
+                }
+                thread::sleep(DEBOUNCE_DELAY);
+                if file_mtime(&path) != mtime {
+                    // Still being written; look again next cycle.
+                    continue;
+                }
+                last_mtime = mtime;
+
+                match BackendDecls::load(&path) {
+                    Ok(next) => {
+                        let report = runtime.reload_backends(&current, &next);
+                        if !report.is_empty() {
+                            info!(
+                                target: "abp.runtime",
+                                added = ?report.added,
+                                removed = ?report.removed,
+                                replaced = ?report.replaced,
+                                reprojected = ?report.reprojected,
+                                "reloaded backends from {}",
+                                path.display()
+                            );
+                        }
+                        current = next;
+                    }
+                    Err(e) => {
+                        warn!(
+                            target: "abp.runtime",
+                            error = %e,
+                            "ignoring invalid backend config {}",
+                            path.display()
+                        );
+                    }
+                }
+            }
+        }));
+
+        Ok(initial)
+    }
+
+    /// Signal the watcher thread to stop and wait for it to finish.
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(h) = self.handle.take() {
+            let _ = h.join();
+        }
+    }
+
+    /// Whether the watcher is currently running.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for BackendWatcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn file_mtime(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).ok().and_then(|m| m.modified().ok())
+}
@@ -0,0 +1,433 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Deterministic replay of seeded runs.
+//!
+//! A work order carrying a seed (`config.vendor["abp"]["seed"]`, see
+//! [`extract_seed`](abp_integrations::extract_seed)) is a request for a
+//! reproducible run. The runtime records a
+//! [`DeterminismRecord`](crate::replay::DeterminismRecord) under
+//! `usage_raw["determinism"]`: the seed, whether the backend declares
+//! [`Capability::SeedDeterminism`](abp_core::Capability::SeedDeterminism),
+//! and the volatile inputs of the run — ids and timestamps — that replay
+//! deliberately ignores.
+//!
+//! [`ReplayHarness`](crate::replay::ReplayHarness) re-executes a work order
+//! and compares its trace against a recorded receipt, event by event, with
+//! timestamps stripped. The first mismatch is reported as a
+//! [`TraceDivergence`](crate::replay::TraceDivergence).
+//!
+//! Every runtime receipt also keeps the work order as submitted under
+//! `usage_raw["work_order"]`. [`Runtime::replay`](crate::Runtime::replay)
+//! rebuilds it and re-emits the recorded trace through the runtime as if the
+//! backend were running, without calling the backend. The resulting receipt
+//! is marked with a [`ReplayMarker`](crate::replay::ReplayMarker) under
+//! `usage_raw["replay"]`.
+
+use std::fmt;
+
+use abp_core::{
+    AgentEvent, BackendIdentity, Capability, CapabilityManifest, Receipt, SupportLevel, WorkOrder,
+};
+use abp_integrations::Backend;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+use crate::{Runtime, RuntimeError};
+
+/// Key under `receipt.usage_raw` holding the [`DeterminismRecord`].
+pub const DETERMINISM_KEY: &str = "determinism";
+
+/// Key under `receipt.usage_raw` holding the work order as submitted.
+pub const WORK_ORDER_KEY: &str = "work_order";
+
+/// Key under `receipt.usage_raw` holding the [`ReplayMarker`] of a replay.
+pub const REPLAY_KEY: &str = "replay";
+
+/// Nondeterministic inputs of a seeded run, recorded in the receipt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeterminismRecord {
+    /// Seed requested by the work order.
+    pub seed: u64,
+    /// The backend's declared support for seeded sampling; `None` if it does
+    /// not declare [`Capability::SeedDeterminism`] at all.
+    pub backend_support: Option<SupportLevel>,
+    /// Run identifier assigned by the runtime.
+    pub run_id: Uuid,
+    /// Identifier of the work order that was executed.
+    pub work_order_id: Uuid,
+    /// Wall-clock start of the run.
+    pub started_at: DateTime<Utc>,
+    /// Wall-clock end of the run.
+    pub finished_at: DateTime<Utc>,
+    /// Number of events in the trace.
+    pub trace_len: usize,
+    /// [`trace_fingerprint`] of the trace.
+    pub trace_fingerprint: String,
+}
+
+impl DeterminismRecord {
+    /// Capture the record for `receipt`, produced from a work order seeded
+    /// with `seed`.
+    #[must_use]
+    pub fn capture(seed: u64, receipt: &Receipt) -> Self {
+        Self {
+            seed,
+            backend_support: receipt
+                .capabilities
+                .get(&Capability::SeedDeterminism)
+                .cloned(),
+            run_id: receipt.meta.run_id,
+            work_order_id: receipt.meta.work_order_id,
+            started_at: receipt.meta.started_at,
+            finished_at: receipt.meta.finished_at,
+            trace_len: receipt.trace.len(),
+            trace_fingerprint: trace_fingerprint(&receipt.trace),
+        }
+    }
+
+    /// Read the record back from a receipt, if the run was seeded.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        receipt
+            .usage_raw
+            .get(DETERMINISM_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+
+    /// Whether the backend natively honours the seed.
+    #[must_use]
+    pub fn seed_honored(&self) -> bool {
+        matches!(self.backend_support, Some(SupportLevel::Native))
+    }
+}
+
+/// An event as compared during replay: its JSON form without `ts`.
+#[must_use]
+pub fn normalize_event(event: &AgentEvent) -> Value {
+    let mut value = serde_json::to_value(event).unwrap_or(Value::Null);
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove("ts");
+    }
+    value
+}
+
+/// SHA-256 over the canonical JSON of the timestamp-free trace.
+#[must_use]
+pub fn trace_fingerprint(trace: &[AgentEvent]) -> String {
+    let normalized: Vec<Value> = trace.iter().map(normalize_event).collect();
+    let json = abp_core::canonical_json(&normalized).unwrap_or_default();
+    abp_core::sha256_hex(json.as_bytes())
+}
+
+/// First point at which a replayed trace differs from the recorded one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceDivergence {
+    /// Index of the first differing event.
+    pub index: usize,
+    /// Recorded event at `index`, or `None` if the recorded trace ended.
+    pub expected: Option<Value>,
+    /// Replayed event at `index`, or `None` if the replayed trace ended.
+    pub actual: Option<Value>,
+}
+
+impl fmt::Display for TraceDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |v: &Option<Value>| {
+            v.as_ref()
+                .map_or_else(|| "<end of trace>".to_string(), Value::to_string)
+        };
+        write!(
+            f,
+            "trace diverges at event {}: expected {}, got {}",
+            self.index,
+            show(&self.expected),
+            show(&self.actual)
+        )
+    }
+}
+
+impl std::error::Error for TraceDivergence {}
+
+/// Compare two traces, ignoring timestamps.
+#[must_use]
+pub fn compare_traces(expected: &[AgentEvent], actual: &[AgentEvent]) -> Option<TraceDivergence> {
+    let len = expected.len().max(actual.len());
+    (0..len).find_map(|index| {
+        let e = expected.get(index).map(normalize_event);
+        let a = actual.get(index).map(normalize_event);
+        (e != a).then_some(TraceDivergence {
+            index,
+            expected: e,
+            actual: a,
+        })
+    })
+}
+
+/// Outcome of replaying a work order against a recorded receipt.
+#[derive(Debug, Clone)]
+pub struct ReplayReport {
+    /// Fingerprint of the recorded trace.
+    pub recorded_fingerprint: String,
+    /// Fingerprint of the replayed trace.
+    pub replayed_fingerprint: String,
+    /// First difference between the traces, if any.
+    pub divergence: Option<TraceDivergence>,
+    /// Receipt produced by the replay.
+    pub receipt: Receipt,
+}
+
+impl ReplayReport {
+    /// Whether the replayed trace matches the recorded one.
+    #[must_use]
+    pub fn is_identical(&self) -> bool {
+        self.divergence.is_none()
+    }
+
+    /// The replayed receipt, or the divergence if the traces differ.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`TraceDivergence`] when the replay did not reproduce the
+    /// recorded trace.
+    pub fn verify(self) -> Result<Receipt, TraceDivergence> {
+        match self.divergence {
+            None => Ok(self.receipt),
+            Some(d) => Err(d),
+        }
+    }
+}
+
+/// Re-executes work orders against a backend and checks their traces.
+///
+/// Replay is only meaningful for seeded work orders on backends that honour
+/// the seed; pass the same work order (including its seed) that produced the
+/// recorded receipt.
+pub struct ReplayHarness<'a> {
+    runtime: &'a Runtime,
+    backend: String,
+}
+
+impl<'a> ReplayHarness<'a> {
+    /// Create a harness that runs work orders on `backend`.
+    #[must_use]
+    pub fn new(runtime: &'a Runtime, backend: impl Into<String>) -> Self {
+        Self {
+            runtime,
+            backend: backend.into(),
+        }
+    }
+
+    /// Run `work_order` to completion and return its receipt.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`RuntimeError`] of a failed run.
+    pub async fn record(&self, work_order: WorkOrder) -> Result<Receipt, RuntimeError> {
+        let mut handle = self
+            .runtime
+            .run_streaming(&self.backend, work_order)
+            .await?;
+        while handle.events.next().await.is_some() {}
+        handle
+            .receipt
+            .await
+            .map_err(|e| RuntimeError::internal("run task failed", e))?
+    }
+
+    /// Run `work_order` again and compare its trace with `recorded`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`RuntimeError`] of a failed run. A trace mismatch is not
+    /// an error; it is reported in [`ReplayReport::divergence`].
+    pub async fn replay(
+        &self,
+        work_order: WorkOrder,
+        recorded: &Receipt,
+    ) -> Result<ReplayReport, RuntimeError> {
+        let receipt = self.record(work_order).await?;
+        Ok(ReplayReport {
+            recorded_fingerprint: trace_fingerprint(&recorded.trace),
+            replayed_fingerprint: trace_fingerprint(&receipt.trace),
+            divergence: compare_traces(&recorded.trace, &receipt.trace),
+            receipt,
+        })
+    }
+
+    /// Run `work_order` twice and compare the two traces.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`RuntimeError`] of either failed run.
+    pub async fn check(&self, work_order: WorkOrder) -> Result<ReplayReport, RuntimeError> {
+        let recorded = self.record(work_order.clone()).await?;
+        self.replay(work_order, &recorded).await
+    }
+}
+
+/// The work order a runtime receipt was produced from, if it was recorded.
+#[must_use]
+pub fn recorded_work_order(receipt: &Receipt) -> Option<WorkOrder> {
+    receipt
+        .usage_raw
+        .get(WORK_ORDER_KEY)
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+}
+
+/// Marks a receipt produced by [`Runtime::replay`] rather than a backend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayMarker {
+    /// Run id of the recorded receipt.
+    pub source_run_id: Uuid,
+    /// Hash of the recorded receipt, if it had one.
+    pub source_receipt_sha256: Option<String>,
+}
+
+impl ReplayMarker {
+    /// Read the marker from a receipt; `None` if it is not a replay.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        receipt
+            .usage_raw
+            .get(REPLAY_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+}
+
+/// Stand-in backend that plays back a recorded receipt.
+///
+/// It reports the recorded identity and capabilities, sends each recorded
+/// trace event, and returns the recorded outcome, usage, artifacts, and
+/// verification with the trace left for the runtime to fill in from the
+/// events it observed.
+#[derive(Debug, Clone)]
+pub struct RecordedBackend {
+    recorded: Receipt,
+    marker: (&'static str, Value),
+}
+
+impl RecordedBackend {
+    /// Play back `recorded`.
+    #[must_use]
+    pub fn new(recorded: Receipt) -> Self {
+        let marker = ReplayMarker {
+            source_run_id: recorded.meta.run_id,
+            source_receipt_sha256: recorded.receipt_sha256.clone(),
+        };
+        let marker = serde_json::to_value(marker).unwrap_or(Value::Null);
+        Self {
+            recorded,
+            marker: (REPLAY_KEY, marker),
+        }
+    }
+
+    /// Play back a cached receipt, marked with `record` instead of a
+    /// [`ReplayMarker`].
+    pub(crate) fn cached(recorded: Receipt, record: &crate::cache::CacheRecord) -> Self {
+        let marker = serde_json::to_value(record).unwrap_or(Value::Null);
+        Self {
+            recorded,
+            marker: (crate::cache::CACHE_KEY, marker),
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for RecordedBackend {
+    fn identity(&self) -> BackendIdentity {
+        self.recorded.backend.clone()
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        self.recorded.capabilities.clone()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: tokio::sync::mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        let started_at = Utc::now();
+        for event in &self.recorded.trace {
+            if events_tx.send(event.clone()).await.is_err() {
+                break;
+            }
+        }
+
+        let mut receipt = self.recorded.clone();
+        receipt.meta.run_id = run_id;
+        receipt.meta.work_order_id = work_order.id;
+        receipt.meta.started_at = started_at;
+        receipt.meta.finished_at = Utc::now();
+        receipt.meta.duration_ms = 0;
+        receipt.trace.clear();
+        receipt.receipt_sha256 = None;
+        if !receipt.usage_raw.is_object() {
+            receipt.usage_raw = serde_json::json!({ "original": receipt.usage_raw });
+        }
+        if let Some(obj) = receipt.usage_raw.as_object_mut() {
+            let (key, marker) = &self.marker;
+            obj.insert((*key).to_string(), marker.clone());
+        }
+        Ok(receipt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::AgentEventKind;
+    use chrono::TimeZone;
+
+    fn event(secs: i64, text: &str) -> AgentEvent {
+        AgentEvent {
+            ts: Utc.timestamp_opt(secs, 0).unwrap(),
+            kind: AgentEventKind::AssistantMessage { text: text.into() },
+            ext: None,
+        }
+    }
+
+    #[test]
+    fn fingerprint_ignores_timestamps() {
+        let a = vec![event(1, "hi"), event(2, "there")];
+        let b = vec![event(10, "hi"), event(20, "there")];
+        assert_eq!(trace_fingerprint(&a), trace_fingerprint(&b));
+        assert!(compare_traces(&a, &b).is_none());
+    }
+
+    #[test]
+    fn divergence_reports_first_differing_event() {
+        let a = vec![event(1, "hi"), event(2, "there")];
+        let b = vec![event(1, "hi"), event(2, "world")];
+        let d = compare_traces(&a, &b).unwrap();
+        assert_eq!(d.index, 1);
+        assert!(d.to_string().contains("\"there\""));
+        assert_ne!(trace_fingerprint(&a), trace_fingerprint(&b));
+    }
+
+    #[test]
+    fn shorter_trace_diverges_at_its_end() {
+        let a = vec![event(1, "hi"), event(2, "there")];
+        let d = compare_traces(&a, &a[..1]).unwrap();
+        assert_eq!(d.index, 1);
+        assert!(d.actual.is_none());
+        assert!(d.to_string().ends_with("got <end of trace>"));
+    }
+
+    #[test]
+    fn record_round_trips_through_receipt() {
+        let mut receipt = abp_receipt::ReceiptBuilder::new("mock").build();
+        receipt.trace = vec![event(1, "hi")];
+        let record = DeterminismRecord::capture(42, &receipt);
+        receipt.usage_raw =
+            serde_json::json!({ DETERMINISM_KEY: serde_json::to_value(&record).unwrap() });
+        let back = DeterminismRecord::from_receipt(&receipt).unwrap();
+        assert_eq!(back, record);
+        assert_eq!(back.trace_len, 1);
+        assert!(!back.seed_honored());
+    }
+}
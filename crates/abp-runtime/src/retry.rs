@@ -1,7 +1,25 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 //! Retry policies and timeout configuration for resilient backend execution.
-
+//!
+//! [`BackendRetryConfig`](crate::retry::BackendRetryConfig) is the
+//! per-backend form that
+//! [`Runtime::run_streaming`](crate::Runtime::run_streaming) applies: each
+//! attempt is bounded by the configured timeout, and an attempt that crashes
+//! or times out is retried after the policy's backoff. Every retried failure
+//! is recorded as a [`RetryAttempt`](crate::retry::RetryAttempt) under
+//! `usage_raw["retry_history"]`.
+//!
+//! Events from every attempt stay in the trace. On a backend with retries
+//! configured, each event the backend emits, and the warning announcing a
+//! retry, carries its zero-based attempt number under `ext["abp.attempt"]`
+//! (see [`ATTEMPT_EXT_KEY`](crate::retry::ATTEMPT_EXT_KEY)), so a consumer can
+//! set aside the output of attempts that failed.
+
+use crate::RuntimeError;
+use abp_core::AgentEvent;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::time::Duration;
 
@@ -143,6 +161,122 @@ pub struct TimeoutConfig {
     pub event_timeout: Option<Duration>,
 }
 
+/// Retry and timeout settings for one backend.
+///
+/// Registered per backend with
+/// [`Runtime::with_backend_retry`](crate::Runtime::with_backend_retry) or
+/// through [`RuntimeConfig::backend_retry`](crate::config_integration::RuntimeConfig::backend_retry).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BackendRetryConfig {
+    /// Retry policy for attempts that crash or time out.
+    #[serde(default)]
+    pub policy: RetryPolicy,
+    /// Deadline for a single attempt. `None` means no limit.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "option_duration_millis"
+    )]
+    pub timeout: Option<Duration>,
+}
+
+impl BackendRetryConfig {
+    /// Settings with the given policy and no attempt timeout.
+    #[must_use]
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self {
+            policy,
+            timeout: None,
+        }
+    }
+
+    /// Bound each attempt by `timeout`.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+/// Key under `receipt.usage_raw` listing the attempts
+/// [`Runtime::run_streaming`](crate::Runtime::run_streaming) retried.
+pub const RETRY_HISTORY_KEY: &str = "retry_history";
+
+/// Key under an event's `ext` holding the zero-based backend attempt that
+/// produced it.
+pub const ATTEMPT_EXT_KEY: &str = "abp.attempt";
+
+/// Mark `event` as produced during backend attempt `attempt`.
+pub(crate) fn tag_attempt(event: &mut AgentEvent, attempt: u32) {
+    event
+        .ext
+        .get_or_insert_with(BTreeMap::new)
+        .insert(ATTEMPT_EXT_KEY.to_string(), serde_json::json!(attempt));
+}
+
+/// The backend attempt that produced `event`, if the runtime tagged it.
+#[must_use]
+pub fn event_attempt(event: &AgentEvent) -> Option<u32> {
+    event
+        .ext
+        .as_ref()?
+        .get(ATTEMPT_EXT_KEY)?
+        .as_u64()
+        .and_then(|n| u32::try_from(n).ok())
+}
+
+/// A failed backend attempt that was retried.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryAttempt {
+    /// Zero-based number of the attempt that failed.
+    pub attempt: u32,
+    /// Error code of the failure.
+    pub error_code: abp_error::ErrorCode,
+    /// Error message of the failure.
+    pub error: String,
+    /// Backoff waited before the next attempt, in milliseconds.
+    pub delay_ms: u64,
+}
+
+impl RetryAttempt {
+    /// Attempt `attempt` failed with `err` and is retried after `delay`.
+    #[must_use]
+    pub fn failed(attempt: u32, err: &RuntimeError, delay: Duration) -> Self {
+        let error = match err {
+            RuntimeError::BackendFailed(e) => format!("{e:#}"),
+            other => other.to_string(),
+        };
+        Self {
+            attempt,
+            error_code: crate::execution::failure_code(err),
+            error,
+            delay_ms: delay.as_millis() as u64,
+        }
+    }
+}
+
+/// Run one backend attempt, failing it with
+/// [`BackendTimeout`](abp_error::ErrorCode::BackendTimeout) if it outlives
+/// `timeout`.
+pub(crate) async fn with_attempt_timeout<F>(
+    timeout: Option<Duration>,
+    attempt: F,
+) -> anyhow::Result<abp_core::Receipt>
+where
+    F: Future<Output = anyhow::Result<abp_core::Receipt>>,
+{
+    let Some(limit) = timeout else {
+        return attempt.await;
+    };
+    match tokio::time::timeout(limit, attempt).await {
+        Ok(result) => result,
+        Err(_) => Err(anyhow::Error::new(abp_error::AbpError::new(
+            abp_error::ErrorCode::BackendTimeout,
+            format!("attempt timed out after {} ms", limit.as_millis()),
+        ))),
+    }
+}
+
 // --- helpers ----------------------------------------------------------------
 
 /// Produce a deterministic jitter factor in [0.75, 1.25] for the given attempt.
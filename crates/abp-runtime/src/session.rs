@@ -0,0 +1,412 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Conversation sessions with branching.
+//!
+//! A [`Session`](crate::session::Session) holds an `IrConversation` and the
+//! run ids of the receipts produced in it.
+//! [`Session::fork`](crate::session::Session::fork) starts a new session
+//! that keeps the history before a given message index, so callers can
+//! explore an alternative approach without copying state by hand.
+//!
+//! Work orders tagged with [`Session::tag`](crate::session::Session::tag)
+//! carry a [`SessionRecord`](crate::session::SessionRecord) under
+//! `config.vendor["abp.session"]`. The runtime copies it to
+//! `usage_raw["session"]` before hashing, so every receipt records the
+//! session it belongs to and the chain of forks that led there. Tagging
+//! also attaches the session's history as the work order's conversation
+//! (`config.vendor["abp"]["conversation"]`), so backends see the earlier
+//! turns and not just the task.
+//!
+//! A [`SessionManager`](crate::session::SessionManager) keeps sessions by id
+//! so a conversation outlives a single request: each shim attaches only the
+//! new turn, [`SessionManager::prepare`](crate::session::SessionManager::prepare)
+//! prepends the stored history, and
+//! [`SessionManager::record_receipt`](crate::session::SessionManager::record_receipt)
+//! appends the reply. The history is kept as IR, so a conversation started
+//! through one shim can be continued through another.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use abp_core::ir::{IrConversation, IrMessage, IrRole};
+use abp_core::{AgentEventKind, Receipt, WorkOrder};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Work-order vendor key carrying the [`SessionRecord`].
+pub const SESSION_VENDOR_KEY: &str = "abp.session";
+
+/// Key under `receipt.usage_raw` holding the [`SessionRecord`].
+pub const SESSION_KEY: &str = "session";
+
+/// Errors from session operations.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SessionError {
+    /// The fork index is past the end of the conversation.
+    #[error("cannot fork at message {index}: session has {len} messages")]
+    ForkOutOfRange {
+        /// Requested fork index.
+        index: usize,
+        /// Number of messages in the session.
+        len: usize,
+    },
+    /// No session with this id is stored.
+    #[error("unknown session {id}")]
+    UnknownSession {
+        /// Requested session id.
+        id: Uuid,
+    },
+}
+
+/// Where a session branched off its parent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForkPoint {
+    /// Session the fork was taken from.
+    pub parent_id: Uuid,
+    /// Number of parent messages the fork kept.
+    pub message_index: usize,
+}
+
+/// Session identity and fork lineage, as recorded in work orders and receipts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionRecord {
+    /// Session the run belongs to.
+    pub session_id: Uuid,
+    /// Fork points from the root session down to this one (empty for a root).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lineage: Vec<ForkPoint>,
+}
+
+impl SessionRecord {
+    /// Read the record a work order was tagged with, if any.
+    #[must_use]
+    pub fn from_work_order(work_order: &WorkOrder) -> Option<Self> {
+        let value = work_order.config.vendor.get(SESSION_VENDOR_KEY)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+
+    /// Read the record the runtime stored in a receipt, if any.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        let value = receipt.usage_raw.get(SESSION_KEY)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+/// A conversation that can be extended, run, and forked.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::ir::{IrMessage, IrRole};
+/// use abp_runtime::session::Session;
+///
+/// let mut session = Session::new();
+/// session.push(IrMessage::text(IrRole::User, "Refactor the parser"));
+/// session.push(IrMessage::text(IrRole::Assistant, "Plan A: split the lexer"));
+///
+/// // Try a different answer to the same question.
+/// let mut alt = session.fork(1).unwrap();
+/// alt.push(IrMessage::text(IrRole::Assistant, "Plan B: use a parser combinator"));
+///
+/// assert_eq!(alt.conversation().len(), 2);
+/// assert_eq!(alt.parent_id(), Some(session.id()));
+/// assert_eq!(session.conversation().len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    id: Uuid,
+    conversation: IrConversation,
+    lineage: Vec<ForkPoint>,
+    runs: Vec<Uuid>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Session {
+    /// Start an empty root session.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::from_conversation(IrConversation::new())
+    }
+
+    /// Start a root session from existing history.
+    #[must_use]
+    pub fn from_conversation(conversation: IrConversation) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            conversation,
+            lineage: Vec::new(),
+            runs: Vec::new(),
+        }
+    }
+
+    /// Unique identifier of this session.
+    #[must_use]
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// The conversation so far.
+    #[must_use]
+    pub fn conversation(&self) -> &IrConversation {
+        &self.conversation
+    }
+
+    /// Fork points from the root session down to this one.
+    #[must_use]
+    pub fn lineage(&self) -> &[ForkPoint] {
+        &self.lineage
+    }
+
+    /// The session this one was forked from, if any.
+    #[must_use]
+    pub fn parent_id(&self) -> Option<Uuid> {
+        self.lineage.last().map(|p| p.parent_id)
+    }
+
+    /// Run ids of the receipts recorded in this session, oldest first.
+    #[must_use]
+    pub fn runs(&self) -> &[Uuid] {
+        &self.runs
+    }
+
+    /// Append a message to the conversation.
+    pub fn push(&mut self, message: IrMessage) {
+        self.conversation.messages.push(message);
+    }
+
+    /// Branch a new session that keeps the first `at_message_index` messages.
+    ///
+    /// The new session gets a fresh id and no runs; its lineage is this
+    /// session's lineage plus the new fork point. `at_message_index` may
+    /// equal the conversation length to branch after the last message.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SessionError::ForkOutOfRange`] if `at_message_index` is
+    /// greater than the number of messages.
+    pub fn fork(&self, at_message_index: usize) -> Result<Session, SessionError> {
+        let len = self.conversation.len();
+        if at_message_index > len {
+            return Err(SessionError::ForkOutOfRange {
+                index: at_message_index,
+                len,
+            });
+        }
+        let mut lineage = self.lineage.clone();
+        lineage.push(ForkPoint {
+            parent_id: self.id,
+            message_index: at_message_index,
+        });
+        Ok(Session {
+            id: Uuid::new_v4(),
+            conversation: IrConversation::from_messages(
+                self.conversation.messages[..at_message_index].to_vec(),
+            ),
+            lineage,
+            runs: Vec::new(),
+        })
+    }
+
+    /// The record stamped into work orders and receipts for this session.
+    #[must_use]
+    pub fn record(&self) -> SessionRecord {
+        SessionRecord {
+            session_id: self.id,
+            lineage: self.lineage.clone(),
+        }
+    }
+
+    /// Tag a work order so its receipt records this session's lineage.
+    ///
+    /// Also attaches the conversation so far, followed by the work order's
+    /// task as the next user turn unless the history already ends with it.
+    pub fn tag(&self, work_order: &mut WorkOrder) {
+        let mut conversation = self.conversation.clone();
+        let ends_with_task = conversation
+            .last_message()
+            .is_some_and(|m| m.role == IrRole::User && m.text_content() == work_order.task);
+        if !work_order.task.is_empty() && !ends_with_task {
+            conversation
+                .messages
+                .push(IrMessage::text(IrRole::User, work_order.task.clone()));
+        }
+        self.attach(work_order, conversation);
+    }
+
+    /// Stamp the session record and attach `conversation` to a work order.
+    fn attach(&self, work_order: &mut WorkOrder, conversation: IrConversation) {
+        if let Ok(value) = serde_json::to_value(self.record()) {
+            work_order
+                .config
+                .vendor
+                .insert(SESSION_VENDOR_KEY.to_string(), value);
+        }
+        if conversation.is_empty() {
+            return;
+        }
+        let abp = work_order
+            .config
+            .vendor
+            .entry("abp".to_string())
+            .or_insert_with(|| serde_json::json!({}));
+        if let (Some(abp), Ok(value)) = (abp.as_object_mut(), serde_json::to_value(conversation)) {
+            abp.insert("conversation".to_string(), value);
+        }
+    }
+
+    /// Record a finished run: remember its id and append its assistant
+    /// messages to the conversation.
+    pub fn record_receipt(&mut self, receipt: &Receipt) {
+        self.runs.push(receipt.meta.run_id);
+        for event in &receipt.trace {
+            if let AgentEventKind::AssistantMessage { text } = &event.kind {
+                self.push(IrMessage::text(IrRole::Assistant, text.clone()));
+            }
+        }
+    }
+}
+
+/// Thread-safe store of sessions keyed by id.
+///
+/// # Examples
+///
+/// ```
+/// use abp_core::WorkOrderBuilder;
+/// use abp_runtime::session::SessionManager;
+///
+/// let sessions = SessionManager::new();
+/// let id = sessions.create();
+///
+/// let mut wo = WorkOrderBuilder::new("hello").build();
+/// sessions.prepare(id, &mut wo).unwrap();
+/// assert_eq!(sessions.get(id).unwrap().conversation().len(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct SessionManager {
+    sessions: Mutex<BTreeMap<Uuid, Session>>,
+}
+
+impl SessionManager {
+    /// An empty manager.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start and store an empty session, returning its id.
+    pub fn create(&self) -> Uuid {
+        self.insert(Session::new())
+    }
+
+    /// Store `session` (e.g. a fork), replacing any session with its id.
+    pub fn insert(&self, session: Session) -> Uuid {
+        let id = session.id();
+        self.lock().insert(id, session);
+        id
+    }
+
+    /// A snapshot of the session with `id`.
+    #[must_use]
+    pub fn get(&self, id: Uuid) -> Option<Session> {
+        self.lock().get(&id).cloned()
+    }
+
+    /// Forget the session with `id`, returning it.
+    pub fn remove(&self, id: Uuid) -> Option<Session> {
+        self.lock().remove(&id)
+    }
+
+    /// Ids of all stored sessions.
+    #[must_use]
+    pub fn ids(&self) -> Vec<Uuid> {
+        self.lock().keys().copied().collect()
+    }
+
+    /// Number of stored sessions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no session is stored.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Append a work order's turn to session `id` and attach the full history.
+    ///
+    /// The new turn is the work order's conversation
+    /// (`config.vendor["abp"]["conversation"]`), or its task as a user
+    /// message when it has none. A caller that re-sends earlier history is
+    /// handled too: leading messages the session already holds, in the same
+    /// order, are skipped. The work order is then tagged with the session and
+    /// carries the stored conversation.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SessionError::UnknownSession`] if no session has this id.
+    pub fn prepare(&self, id: Uuid, work_order: &mut WorkOrder) -> Result<(), SessionError> {
+        let mut sessions = self.lock();
+        let session = sessions
+            .get_mut(&id)
+            .ok_or(SessionError::UnknownSession { id })?;
+
+        let incoming = abp_integrations::extract_conversation(work_order)
+            .map(|c| c.messages)
+            .unwrap_or_default();
+        // Skip the leading messages the session already holds, in order; the
+        // last message is always the caller's new turn.
+        let history = &session.conversation.messages;
+        let mut cursor = 0;
+        let mut known = 0;
+        for message in &incoming {
+            match history[cursor..].iter().position(|h| h == message) {
+                Some(offset) => {
+                    cursor += offset + 1;
+                    known += 1;
+                }
+                None => break,
+            }
+        }
+        let new_turns = &incoming[known.min(incoming.len().saturating_sub(1))..];
+
+        if new_turns.is_empty() {
+            if !work_order.task.is_empty() {
+                session.push(IrMessage::text(IrRole::User, work_order.task.clone()));
+            }
+        } else {
+            session.conversation.messages.extend_from_slice(new_turns);
+        }
+        session.attach(work_order, session.conversation.clone());
+        Ok(())
+    }
+
+    /// Append a finished run's reply to the session its receipt records.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SessionError::UnknownSession`] if the receipt names a
+    /// session this manager does not hold. Receipts without a session record
+    /// are ignored.
+    pub fn record_receipt(&self, receipt: &Receipt) -> Result<(), SessionError> {
+        let Some(record) = SessionRecord::from_receipt(receipt) else {
+            return Ok(());
+        };
+        let id = record.session_id;
+        self.lock()
+            .get_mut(&id)
+            .ok_or(SessionError::UnknownSession { id })?
+            .record_receipt(receipt);
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<Uuid, Session>> {
+        self.sessions.lock().expect("session mutex poisoned")
+    }
+}
@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Machine-readable run summaries at the end of the event stream.
+//!
+//! When the runtime is configured with
+//! [`Runtime::with_run_summary`](crate::Runtime::with_run_summary), it ends
+//! every run's event stream with an
+//! [`AgentEventKind::RunSummary`](abp_core::AgentEventKind::RunSummary): the
+//! outcome, duration, usage, tool call counts, changed files, and policy
+//! denials, sent just before the channel closes. Streaming consumers get the
+//! roll-up without awaiting or parsing the receipt.
+//!
+//! The same event is published on the runtime's
+//! [`EventBus`](crate::bus::EventBus), tagged with the run id under
+//! `ext["abp.run_id"]` (see [`RUN_ID_EXT_KEY`](crate::summary::RUN_ID_EXT_KEY)).
+//! Like heartbeats, summaries are not part of the receipt trace: they are
+//! derived from the final receipt. A run that fails without a receipt is
+//! summarized from the events observed before it failed.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use abp_core::{AgentEvent, AgentEventKind, Outcome, Receipt, UsageNormalized};
+use abp_error::ErrorCode;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Extension key holding the run id on a summary published to the bus.
+pub const RUN_ID_EXT_KEY: &str = "abp.run_id";
+
+/// Summarize a run from its outcome, usage, and trace.
+///
+/// `extra_denials` counts policy denials that ended the run without an
+/// event of their own, such as the runtime's network egress check.
+#[must_use]
+pub fn summarize(
+    outcome: Outcome,
+    duration_ms: u64,
+    usage: UsageNormalized,
+    trace: &[AgentEvent],
+    extra_denials: u64,
+) -> AgentEventKind {
+    let mut tool_calls = BTreeMap::new();
+    let mut files = BTreeSet::new();
+    let mut policy_denials = extra_denials;
+    for event in trace {
+        match &event.kind {
+            AgentEventKind::ToolCall { tool_name, .. } => {
+                *tool_calls.entry(tool_name.clone()).or_insert(0) += 1;
+            }
+            AgentEventKind::FileChanged { path, .. } => {
+                files.insert(path.clone());
+            }
+            AgentEventKind::Error {
+                error_code: Some(ErrorCode::PolicyDenied),
+                ..
+            } => policy_denials += 1,
+            _ => {}
+        }
+    }
+    AgentEventKind::RunSummary {
+        outcome,
+        duration_ms,
+        usage,
+        tool_calls,
+        files_changed: files.into_iter().collect(),
+        policy_denials,
+    }
+}
+
+/// Summarize a finished run from its receipt.
+#[must_use]
+pub fn receipt_summary(receipt: &Receipt, duration_ms: u64) -> AgentEventKind {
+    summarize(
+        receipt.outcome.clone(),
+        duration_ms,
+        receipt.usage.clone(),
+        &receipt.trace,
+        0,
+    )
+}
+
+/// The bus copy of a summary event, tagged with its run id.
+#[must_use]
+pub fn bus_event(event: &AgentEvent, run_id: Uuid) -> AgentEvent {
+    let mut event = event.clone();
+    event
+        .ext
+        .get_or_insert_with(BTreeMap::new)
+        .insert(RUN_ID_EXT_KEY.to_string(), serde_json::json!(run_id));
+    event
+}
+
+/// The run a bus summary belongs to, if `event` is one.
+#[must_use]
+pub fn run_id(event: &AgentEvent) -> Option<Uuid> {
+    if !matches!(event.kind, AgentEventKind::RunSummary { .. }) {
+        return None;
+    }
+    event
+        .ext
+        .as_ref()?
+        .get(RUN_ID_EXT_KEY)?
+        .as_str()?
+        .parse()
+        .ok()
+}
+
+/// Wrap a summary as an event emitted at `ts`.
+#[must_use]
+pub fn summary_event(kind: AgentEventKind, ts: DateTime<Utc>) -> AgentEvent {
+    AgentEvent {
+        ts,
+        kind,
+        ext: None,
+    }
+}
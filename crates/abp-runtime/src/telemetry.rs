@@ -1,18 +1,33 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 //! Telemetry and metrics collection for runtime runs.
 
+use crate::breaker::BreakerState;
+use abp_core::UsageNormalized;
 use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
 use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
 
+/// Model name recorded for runs that did not say which model served them.
+pub const UNKNOWN_MODEL: &str = "unknown";
+
 /// Atomic run-level metrics that can be shared across threads.
 pub struct RunMetrics {
     total_runs: AtomicU64,
     successful_runs: AtomicU64,
     failed_runs: AtomicU64,
     total_events: AtomicU64,
+    duplicate_events: AtomicU64,
+    event_gaps: AtomicU64,
+    missing_events: AtomicU64,
+    peak_workspace_bytes: AtomicU64,
+    quota_exceeded_runs: AtomicU64,
     /// Cumulative duration used to compute the running average.
     cumulative_duration_ms: AtomicU64,
     average_run_duration_ms: AtomicU64,
+    costs: Mutex<BTreeMap<(String, String), CostTotals>>,
+    breaker_trips: AtomicU64,
+    breakers: Mutex<BTreeMap<String, BreakerState>>,
 }
 
 impl RunMetrics {
@@ -24,8 +39,16 @@ impl RunMetrics {
             successful_runs: AtomicU64::new(0),
             failed_runs: AtomicU64::new(0),
             total_events: AtomicU64::new(0),
+            duplicate_events: AtomicU64::new(0),
+            event_gaps: AtomicU64::new(0),
+            missing_events: AtomicU64::new(0),
+            peak_workspace_bytes: AtomicU64::new(0),
+            quota_exceeded_runs: AtomicU64::new(0),
             cumulative_duration_ms: AtomicU64::new(0),
             average_run_duration_ms: AtomicU64::new(0),
+            costs: Mutex::new(BTreeMap::new()),
+            breaker_trips: AtomicU64::new(0),
+            breakers: Mutex::new(BTreeMap::new()),
         }
     }
 
@@ -43,15 +66,93 @@ impl RunMetrics {
             .store(cumulative / total, Relaxed);
     }
 
+    /// Record sidecar event-delivery anomalies observed during a run:
+    /// re-delivered events that were dropped, sequence gaps, and events
+    /// that never arrived.
+    pub fn record_event_delivery(&self, duplicates: u64, gaps: u64, missing: u64) {
+        self.duplicate_events.fetch_add(duplicates, Relaxed);
+        self.event_gaps.fetch_add(gaps, Relaxed);
+        self.missing_events.fetch_add(missing, Relaxed);
+    }
+
+    /// Record the observed size of a run's staged workspace.
+    pub fn record_workspace_usage(&self, bytes: u64) {
+        self.peak_workspace_bytes.fetch_max(bytes, Relaxed);
+    }
+
+    /// Record a run aborted for exceeding its workspace disk quota.
+    pub fn record_quota_exceeded(&self) {
+        self.quota_exceeded_runs.fetch_add(1, Relaxed);
+    }
+
+    /// Record that `backend`'s circuit breaker changed to `state`.
+    pub fn record_breaker_state(&self, backend: &str, state: BreakerState) {
+        if state == BreakerState::Open {
+            self.breaker_trips.fetch_add(1, Relaxed);
+        }
+        self.breakers
+            .lock()
+            .expect("breaker states lock poisoned")
+            .insert(backend.to_string(), state);
+    }
+
+    /// Record the token usage and cost of a run on `backend` served by
+    /// `model`.
+    pub fn record_cost(&self, backend: &str, model: &str, usage: &UsageNormalized) {
+        let mut costs = self.costs.lock().expect("cost totals lock poisoned");
+        let totals = costs
+            .entry((backend.to_string(), model.to_string()))
+            .or_default();
+        totals.runs += 1;
+        totals.input_tokens += usage.input_tokens.unwrap_or(0);
+        totals.output_tokens += usage.output_tokens.unwrap_or(0);
+        totals.cache_read_tokens += usage.cache_read_tokens.unwrap_or(0);
+        totals.cache_write_tokens += usage.cache_write_tokens.unwrap_or(0);
+        match usage.estimated_cost_usd {
+            Some(cost) => totals.cost_usd += cost,
+            None => totals.unpriced_runs += 1,
+        }
+    }
+
+    /// Usage and cost totals per backend and model, sorted by backend then
+    /// model.
+    #[must_use]
+    pub fn cost_breakdown(&self) -> Vec<CostEntry> {
+        self.costs
+            .lock()
+            .expect("cost totals lock poisoned")
+            .iter()
+            .map(|((backend, model), totals)| CostEntry {
+                backend: backend.clone(),
+                model: model.clone(),
+                totals: totals.clone(),
+            })
+            .collect()
+    }
+
     /// Take a point-in-time snapshot of the current metric values.
     #[must_use]
     pub fn snapshot(&self) -> MetricsSnapshot {
+        let costs = self.cost_breakdown();
         MetricsSnapshot {
             total_runs: self.total_runs.load(Relaxed),
             successful_runs: self.successful_runs.load(Relaxed),
             failed_runs: self.failed_runs.load(Relaxed),
             total_events: self.total_events.load(Relaxed),
+            duplicate_events: self.duplicate_events.load(Relaxed),
+            event_gaps: self.event_gaps.load(Relaxed),
+            missing_events: self.missing_events.load(Relaxed),
+            peak_workspace_bytes: self.peak_workspace_bytes.load(Relaxed),
+            quota_exceeded_runs: self.quota_exceeded_runs.load(Relaxed),
             average_run_duration_ms: self.average_run_duration_ms.load(Relaxed),
+            total_cost_usd: costs.iter().map(|c| c.totals.cost_usd).sum(),
+            costs,
+            breaker_trips: self.breaker_trips.load(Relaxed),
+            breakers: self
+                .breakers
+                .lock()
+                .expect("breaker states lock poisoned")
+                .clone(),
         }
     }
 }
@@ -73,6 +174,56 @@ pub struct MetricsSnapshot {
     pub failed_runs: u64,
     /// Cumulative number of [`AgentEvent`](abp_core::AgentEvent)s across all runs.
     pub total_events: u64,
+    /// Duplicate sidecar events dropped by de-duplication.
+    pub duplicate_events: u64,
+    /// Times a sidecar event arrived ahead of its expected sequence number.
+    pub event_gaps: u64,
+    /// Sidecar events that never arrived.
+    pub missing_events: u64,
+    /// Largest staged workspace observed, in bytes.
+    pub peak_workspace_bytes: u64,
+    /// Runs aborted for exceeding their workspace disk quota.
+    pub quota_exceeded_runs: u64,
     /// Running average of run duration in milliseconds.
     pub average_run_duration_ms: u64,
+    /// Estimated USD spent across all runs.
+    pub total_cost_usd: f64,
+    /// Usage and cost per backend and model.
+    pub costs: Vec<CostEntry>,
+    /// Times a backend's circuit breaker opened.
+    pub breaker_trips: u64,
+    /// Last known circuit breaker state per backend.
+    pub breakers: BTreeMap<String, BreakerState>,
+}
+
+/// Accumulated usage and cost for one backend and model.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CostTotals {
+    /// Runs recorded.
+    pub runs: u64,
+    /// Input tokens across those runs.
+    pub input_tokens: u64,
+    /// Output tokens across those runs.
+    pub output_tokens: u64,
+    /// Tokens read from the prompt cache.
+    pub cache_read_tokens: u64,
+    /// Tokens written to the prompt cache.
+    pub cache_write_tokens: u64,
+    /// Estimated USD across the priced runs.
+    pub cost_usd: f64,
+    /// Runs with no cost estimate, e.g. on a model missing from the
+    /// [`PricingTable`](crate::pricing::PricingTable).
+    pub unpriced_runs: u64,
+}
+
+/// [`CostTotals`] for one backend and model.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CostEntry {
+    /// Registered backend name.
+    pub backend: String,
+    /// Model that served the runs, or [`UNKNOWN_MODEL`].
+    pub model: String,
+    /// The totals.
+    #[serde(flatten)]
+    pub totals: CostTotals,
 }
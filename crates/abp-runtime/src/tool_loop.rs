@@ -0,0 +1,472 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tool-use loop emulation for single-shot backends.
+//!
+//! Some backends complete a conversation once and stop: they can ask for a
+//! tool, but nothing runs it or sends the result back. A
+//! [`ToolLoop`](crate::tool_loop::ToolLoop) wraps such a backend and drives
+//! the loop itself. Each turn it runs the backend on the conversation so far
+//! (`config.vendor["abp"]["conversation"]`) and looks for tool calls — both
+//! `ToolCall` events and `<tool_call>` blocks in the assistant text, which
+//! the registered tools are described in the system prompt to produce
+//! (unless the backend declares native tool use). The calls run on a
+//! [`ToolDispatcher`](crate::tools::ToolDispatcher); the assistant turn and
+//! a `tool` message with the results are appended to the conversation, and
+//! the backend runs again.
+//!
+//! With [`builtin_tools`](crate::tool_loop::ToolLoop::builtin_tools) the
+//! loop also runs the standard Read, Write, Edit and Bash tools from
+//! `abp-tools` in the work order's workspace, under its policy. Their side
+//! effects are recorded as `FileChanged` and `CommandExecuted` events
+//! ahead of each result.
+//!
+//! The loop ends at the first turn without tool calls, or when the turn
+//! budget — `config.max_turns`, else the loop's own limit — is spent, in
+//! which case the receipt is `Partial`. Callers see ordinary `ToolCall` and
+//! `ToolResult` events, and the receipt records a
+//! [`ToolLoopRecord`](crate::tool_loop::ToolLoopRecord) under
+//! `usage_raw["tool_loop"]`.
+//!
+//! ```
+//! use abp_runtime::Runtime;
+//! use abp_runtime::tool_loop::ToolLoop;
+//! use abp_runtime::tools::ToolDispatcher;
+//!
+//! let tools = ToolDispatcher::new()
+//!     .register_fn("clock", |_input| async { Ok(serde_json::json!("12:00")) });
+//! let mut rt = Runtime::new();
+//! rt.register_backend("looping", ToolLoop::new(abp_integrations::MockBackend, tools));
+//! ```
+
+use std::sync::Arc;
+
+use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole, IrToolDefinition};
+use abp_core::{
+    AgentEvent, AgentEventKind, BackendIdentity, Capability, CapabilityManifest, Outcome, Receipt,
+    SupportLevel, UsageNormalized, WorkOrder,
+};
+use abp_emulation::strategies::ToolUseEmulation;
+use abp_integrations::{Backend, VendorNamespace, extract_conversation, extract_tools};
+use abp_tools::ToolSuite;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::tools::ToolDispatcher;
+
+/// Key under `receipt.usage_raw` holding the [`ToolLoopRecord`], and under
+/// `AgentEvent::ext` marking calls the loop has already run.
+pub const TOOL_LOOP_KEY: &str = "tool_loop";
+
+/// Why a tool loop stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolLoopStop {
+    /// The backend answered without calling a tool.
+    EndTurn,
+    /// The turn budget ran out while the backend was still calling tools.
+    BudgetExhausted,
+}
+
+/// Summary of a tool loop, stored in the receipt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolLoopRecord {
+    /// Backend runs made.
+    pub turns: u32,
+    /// Tool calls executed across all turns.
+    pub tool_calls: usize,
+    /// Why the loop stopped.
+    pub stop: ToolLoopStop,
+}
+
+impl ToolLoopRecord {
+    /// The record stored on `receipt`, if the run went through a tool loop.
+    #[must_use]
+    pub fn from_receipt(receipt: &Receipt) -> Option<Self> {
+        receipt
+            .usage_raw
+            .get(TOOL_LOOP_KEY)
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+    }
+}
+
+/// A backend wrapper that runs tool calls and feeds the results back until
+/// the wrapped backend stops calling tools.
+#[derive(Clone)]
+pub struct ToolLoop {
+    inner: Arc<dyn Backend>,
+    tools: ToolDispatcher,
+    builtin_tools: bool,
+    max_turns: u32,
+}
+
+impl std::fmt::Debug for ToolLoop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolLoop")
+            .field("inner", &self.inner.identity().id)
+            .field("tools", &self.tools)
+            .field("builtin_tools", &self.builtin_tools)
+            .field("max_turns", &self.max_turns)
+            .finish()
+    }
+}
+
+impl ToolLoop {
+    /// Turn budget for work orders that do not set `config.max_turns`.
+    pub const DEFAULT_MAX_TURNS: u32 = 8;
+
+    /// Drive `inner` in a loop, running its calls with `tools`.
+    #[must_use]
+    pub fn new(inner: impl Backend + 'static, tools: ToolDispatcher) -> Self {
+        Self::shared(Arc::new(inner), tools)
+    }
+
+    /// Drive an already shared backend.
+    pub(crate) fn shared(inner: Arc<dyn Backend>, tools: ToolDispatcher) -> Self {
+        Self {
+            inner,
+            tools,
+            builtin_tools: false,
+            max_turns: Self::DEFAULT_MAX_TURNS,
+        }
+    }
+
+    /// Also run the built-in Read, Write, Edit and Bash tools in the work
+    /// order's workspace, under its policy (builder pattern).
+    ///
+    /// Tools of the same name on the dispatcher take precedence.
+    #[must_use]
+    pub fn builtin_tools(mut self) -> Self {
+        self.builtin_tools = true;
+        self
+    }
+
+    /// Turn budget for work orders that do not set their own (builder
+    /// pattern).
+    #[must_use]
+    pub fn max_turns(mut self, max_turns: u32) -> Self {
+        self.max_turns = max_turns.max(1);
+        self
+    }
+
+    /// The tools the loop runs.
+    #[must_use]
+    pub fn tools(&self) -> &ToolDispatcher {
+        &self.tools
+    }
+
+    /// Definitions of the registered tools, for the system prompt.
+    ///
+    /// Tools the work order declares keep their description and schema;
+    /// the rest accept any object.
+    fn definitions(&self, work_order: &WorkOrder) -> Vec<IrToolDefinition> {
+        let declared = extract_tools(work_order);
+        let mut definitions: Vec<IrToolDefinition> = self
+            .tools
+            .tool_names()
+            .map(|name| {
+                declared
+                    .iter()
+                    .find(|t| t.name == name)
+                    .cloned()
+                    .unwrap_or_else(|| IrToolDefinition {
+                        name: name.to_string(),
+                        description: String::new(),
+                        parameters: json!({"type": "object"}),
+                    })
+            })
+            .collect();
+        if self.builtin_tools {
+            definitions.extend(
+                ToolSuite::definitions()
+                    .into_iter()
+                    .filter(|t| !self.tools.handles(&t.name)),
+            );
+        }
+        definitions
+    }
+
+    /// Run the wrapped backend once and collect everything it emitted.
+    async fn run_turn(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+    ) -> anyhow::Result<(Vec<AgentEvent>, Receipt)> {
+        let (tx, mut rx) = mpsc::channel(64);
+        let inner = Arc::clone(&self.inner);
+        let handle = tokio::spawn(async move { inner.run(run_id, work_order, tx).await });
+        let mut events = Vec::new();
+        while let Some(ev) = rx.recv().await {
+            events.push(ev);
+        }
+        let receipt = handle.await??;
+        Ok((events, receipt))
+    }
+}
+
+#[async_trait]
+impl Backend for ToolLoop {
+    fn identity(&self) -> BackendIdentity {
+        self.inner.identity()
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        let mut caps = self.inner.capabilities();
+        caps.entry(Capability::ToolUse)
+            .or_insert(SupportLevel::Emulated);
+        if self.builtin_tools {
+            for (capability, level) in ToolSuite::capabilities() {
+                caps.entry(capability).or_insert(level);
+            }
+        }
+        caps
+    }
+
+    fn vendor_namespace(&self) -> Option<VendorNamespace> {
+        self.inner.vendor_namespace()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        let budget = work_order.config.max_turns.unwrap_or(self.max_turns).max(1);
+        let mut conversation = extract_conversation(&work_order).unwrap_or_else(|| {
+            IrConversation::from_messages(vec![IrMessage::text(IrRole::User, &work_order.task)])
+        });
+        // Backends with native tool use read the definitions from the work
+        // order; the rest learn about the tools from the system prompt.
+        let native = matches!(
+            self.inner.capabilities().get(&Capability::ToolUse),
+            Some(SupportLevel::Native)
+        );
+        if !native {
+            ToolUseEmulation::inject_tools(&mut conversation, &self.definitions(&work_order));
+        }
+        // Every call gets a result: the loop's tools, then the built-in
+        // ones, and an error for anything else.
+        let mut tools = self.tools.clone().refuse_unknown();
+        if self.builtin_tools {
+            tools = tools.register_suite(ToolSuite::new(
+                &work_order.workspace.root,
+                &work_order.policy,
+            )?);
+        }
+
+        let mut trace = Vec::new();
+        let mut usage = UsageNormalized::default();
+        let mut tool_calls = 0;
+        let mut turn = 0;
+        // Only the last turn's `RunCompleted` ends the caller's run.
+        let mut completed = None;
+        let (mut receipt, stop) = loop {
+            turn += 1;
+            let mut order = work_order.clone();
+            attach_conversation(&mut order, &conversation);
+            let (events, receipt) = self.run_turn(run_id, order).await?;
+            add_usage(&mut usage, &receipt.usage);
+
+            let mut text = String::new();
+            let mut calls = Vec::new();
+            let mut forward = Vec::new();
+            for ev in events {
+                match &ev.kind {
+                    AgentEventKind::RunStarted { .. } if turn > 1 => {}
+                    AgentEventKind::RunCompleted { .. } => completed = Some(ev),
+                    AgentEventKind::ToolCall { .. } => calls.push(ev),
+                    AgentEventKind::AssistantMessage { text: reply } => {
+                        let parsed = ToolUseEmulation::parse_tool_calls(reply);
+                        if parsed.is_empty() {
+                            text.push_str(reply);
+                            forward.push(ev);
+                            continue;
+                        }
+                        let outside = ToolUseEmulation::extract_text_outside_tool_calls(reply);
+                        text.push_str(&outside);
+                        if !outside.is_empty() {
+                            forward.push(AgentEvent {
+                                kind: AgentEventKind::AssistantMessage { text: outside },
+                                ..ev.clone()
+                            });
+                        }
+                        for call in parsed {
+                            match call {
+                                Ok(call) => calls.push(AgentEvent {
+                                    ts: ev.ts,
+                                    kind: AgentEventKind::ToolCall {
+                                        tool_use_id: Some(format!(
+                                            "toolu_loop_{turn}_{}",
+                                            calls.len()
+                                        )),
+                                        tool_name: call.name,
+                                        parent_tool_use_id: None,
+                                        input: call.arguments,
+                                    },
+                                    ext: None,
+                                }),
+                                Err(message) => forward.push(AgentEvent {
+                                    ts: ev.ts,
+                                    kind: AgentEventKind::Warning { message },
+                                    ext: None,
+                                }),
+                            }
+                        }
+                    }
+                    _ => forward.push(ev),
+                }
+            }
+            for ev in forward {
+                emit(&events_tx, &mut trace, ev).await;
+            }
+
+            if calls.is_empty() {
+                break (receipt, ToolLoopStop::EndTurn);
+            }
+            for call in &mut calls {
+                call.ext
+                    .get_or_insert_with(Default::default)
+                    .insert(TOOL_LOOP_KEY.to_string(), json!({ "turn": turn }));
+                emit(&events_tx, &mut trace, call.clone()).await;
+            }
+            let results = tools.dispatch(&calls, chrono::Utc::now).await;
+            for result in &results {
+                emit(&events_tx, &mut trace, result.clone()).await;
+            }
+            tool_calls += calls.len();
+            record_turn(&mut conversation, &text, &calls, &results);
+
+            if turn >= budget {
+                break (receipt, ToolLoopStop::BudgetExhausted);
+            }
+        };
+
+        if let Some(ev) = completed {
+            emit(&events_tx, &mut trace, ev).await;
+        }
+        receipt.trace = trace;
+        receipt.usage = usage;
+        if stop == ToolLoopStop::BudgetExhausted && receipt.outcome == Outcome::Complete {
+            receipt.outcome = Outcome::Partial;
+        }
+        let record = ToolLoopRecord {
+            turns: turn,
+            tool_calls,
+            stop,
+        };
+        if let (Some(obj), Ok(value)) = (
+            receipt.usage_raw.as_object_mut(),
+            serde_json::to_value(&record),
+        ) {
+            obj.insert(TOOL_LOOP_KEY.to_string(), value);
+        } else if let Ok(value) = serde_json::to_value(&record) {
+            receipt.usage_raw = json!({
+                "original": receipt.usage_raw,
+                TOOL_LOOP_KEY: value,
+            });
+        }
+        Ok(receipt)
+    }
+}
+
+/// Whether `event` is a call a [`ToolLoop`] has already run.
+pub(crate) fn is_loop_call(event: &AgentEvent) -> bool {
+    event
+        .ext
+        .as_ref()
+        .is_some_and(|ext| ext.contains_key(TOOL_LOOP_KEY))
+}
+
+/// Send `ev` to the caller and keep it for the receipt trace.
+async fn emit(tx: &mpsc::Sender<AgentEvent>, trace: &mut Vec<AgentEvent>, ev: AgentEvent) {
+    trace.push(ev.clone());
+    let _ = tx.send(ev).await;
+}
+
+/// Append the assistant turn and its tool results to `conversation`.
+fn record_turn(
+    conversation: &mut IrConversation,
+    text: &str,
+    calls: &[AgentEvent],
+    results: &[AgentEvent],
+) {
+    let mut assistant = Vec::new();
+    if !text.is_empty() {
+        assistant.push(IrContentBlock::Text {
+            text: text.to_string(),
+        });
+    }
+    assistant.extend(calls.iter().filter_map(|ev| match &ev.kind {
+        AgentEventKind::ToolCall {
+            tool_name,
+            tool_use_id,
+            input,
+            ..
+        } => Some(IrContentBlock::ToolUse {
+            id: tool_use_id.clone().unwrap_or_default(),
+            name: tool_name.clone(),
+            input: input.clone(),
+        }),
+        _ => None,
+    }));
+    let tool_results = results
+        .iter()
+        .filter_map(|ev| match &ev.kind {
+            AgentEventKind::ToolResult {
+                tool_use_id,
+                output,
+                is_error,
+                ..
+            } => Some(IrContentBlock::ToolResult {
+                tool_use_id: tool_use_id.clone().unwrap_or_default(),
+                content: vec![IrContentBlock::Text {
+                    text: match output {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    },
+                }],
+                is_error: *is_error,
+            }),
+            _ => None,
+        })
+        .collect();
+    conversation
+        .messages
+        .push(IrMessage::new(IrRole::Assistant, assistant));
+    conversation
+        .messages
+        .push(IrMessage::new(IrRole::Tool, tool_results));
+}
+
+/// Store `conversation` as the work order's `vendor["abp"]["conversation"]`.
+pub(crate) fn attach_conversation(work_order: &mut WorkOrder, conversation: &IrConversation) {
+    let abp = work_order
+        .config
+        .vendor
+        .entry("abp".to_string())
+        .or_insert_with(|| json!({}));
+    if let (Some(abp), Ok(value)) = (abp.as_object_mut(), serde_json::to_value(conversation)) {
+        abp.insert("conversation".to_string(), value);
+    }
+}
+
+/// Add one turn's token counts and cost to the running total.
+pub(crate) fn add_usage(total: &mut UsageNormalized, turn: &UsageNormalized) {
+    fn sum(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(a + b),
+            (a, b) => a.or(b),
+        }
+    }
+    total.input_tokens = sum(total.input_tokens, turn.input_tokens);
+    total.output_tokens = sum(total.output_tokens, turn.output_tokens);
+    total.cache_read_tokens = sum(total.cache_read_tokens, turn.cache_read_tokens);
+    total.cache_write_tokens = sum(total.cache_write_tokens, turn.cache_write_tokens);
+    total.request_units = sum(total.request_units, turn.request_units);
+    total.estimated_cost_usd = match (total.estimated_cost_usd, turn.estimated_cost_usd) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, b) => a.or(b),
+    };
+}
@@ -0,0 +1,333 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Host tools: Rust handlers the runtime runs for mapped-mode backends.
+//!
+//! A [`ToolRegistry`](crate::tool_registry::ToolRegistry) holds tools by
+//! name, each with a description, a JSON Schema for its input and an async
+//! handler. Installed with
+//! [`Runtime::with_tool_registry`](crate::Runtime::with_tool_registry), the
+//! runtime offers the tools on every mapped-mode work order
+//! (`config.vendor["abp"]["tools"]`), runs the calls the backend makes to
+//! them, and sends the results back by running the backend again on the
+//! conversation extended with them — see
+//! [`ToolLoop`](crate::tool_loop::ToolLoop).
+//!
+//! Input that does not match a tool's schema never reaches the handler: the
+//! call gets an `is_error` result listing the violations, so the model can
+//! correct itself on the next turn.
+//!
+//! Passthrough runs are left alone. Their raw request goes to the backend
+//! untouched, so host tools are neither offered nor run.
+//!
+//! ```
+//! use abp_runtime::Runtime;
+//! use abp_runtime::tool_registry::ToolRegistry;
+//! use serde_json::json;
+//!
+//! let tools = ToolRegistry::new().register_fn(
+//!     "add",
+//!     "Add two integers.",
+//!     json!({
+//!         "type": "object",
+//!         "properties": {"a": {"type": "integer"}, "b": {"type": "integer"}},
+//!         "required": ["a", "b"]
+//!     }),
+//!     |input| async move {
+//!         let (a, b) = (input["a"].as_i64(), input["b"].as_i64());
+//!         Ok(json!(a.unwrap_or(0) + b.unwrap_or(0)))
+//!     },
+//! );
+//! let rt = Runtime::with_default_backends().with_tool_registry(tools);
+//! # let _ = rt;
+//! ```
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+
+use abp_core::WorkOrder;
+use abp_core::ir::IrToolDefinition;
+use abp_integrations::extract_tools;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+
+use crate::tools::{FnHandler, ToolDispatcher, ToolHandler};
+
+/// A registered tool: what the model is told about it and what runs it.
+#[derive(Clone)]
+struct RegisteredTool {
+    definition: IrToolDefinition,
+    handler: Arc<dyn ToolHandler>,
+}
+
+/// Host tools by name, each with a JSON Schema for its input.
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: BTreeMap<String, RegisteredTool>,
+}
+
+impl std::fmt::Debug for ToolRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.tools.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl ToolRegistry {
+    /// An empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `handler` for the tool `definition` describes, replacing any
+    /// earlier tool of that name (builder pattern).
+    #[must_use]
+    pub fn register(
+        mut self,
+        definition: IrToolDefinition,
+        handler: impl ToolHandler + 'static,
+    ) -> Self {
+        self.tools.insert(
+            definition.name.clone(),
+            RegisteredTool {
+                definition,
+                handler: Arc::new(handler),
+            },
+        );
+        self
+    }
+
+    /// Register an async closure as tool `name`, taking input that matches
+    /// the JSON Schema `parameters` (builder pattern).
+    #[must_use]
+    pub fn register_fn<F, Fut>(
+        self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        parameters: Value,
+        f: F,
+    ) -> Self
+    where
+        F: Fn(Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Value, String>> + Send + 'static,
+    {
+        let definition = IrToolDefinition {
+            name: name.into(),
+            description: description.into(),
+            parameters,
+        };
+        self.register(definition, FnHandler(f))
+    }
+
+    /// Number of registered tools.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    /// Whether no tools are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// Whether tool `name` is registered.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.tools.contains_key(name)
+    }
+
+    /// The definition of tool `name`, if it is registered.
+    #[must_use]
+    pub fn definition(&self, name: &str) -> Option<&IrToolDefinition> {
+        self.tools.get(name).map(|t| &t.definition)
+    }
+
+    /// Definitions of every registered tool, in name order.
+    #[must_use]
+    pub fn definitions(&self) -> Vec<IrToolDefinition> {
+        self.tools.values().map(|t| t.definition.clone()).collect()
+    }
+
+    /// Check `input` against the schema of tool `name`.
+    ///
+    /// # Errors
+    ///
+    /// Describes why the input was refused: the tool is unknown, its schema
+    /// does not compile, or the input violates it.
+    pub fn validate(&self, name: &str, input: &Value) -> Result<(), String> {
+        let tool = self
+            .tools
+            .get(name)
+            .ok_or_else(|| format!("tool '{name}' is not registered"))?;
+        check_input(&tool.definition, input)
+    }
+
+    /// A dispatcher running the registered tools, refusing input that does
+    /// not match a tool's schema.
+    #[must_use]
+    pub fn dispatcher(&self) -> ToolDispatcher {
+        self.tools
+            .values()
+            .fold(ToolDispatcher::new(), |dispatcher, tool| {
+                dispatcher.register(
+                    tool.definition.name.clone(),
+                    Validated {
+                        definition: tool.definition.clone(),
+                        handler: Arc::clone(&tool.handler),
+                    },
+                )
+            })
+    }
+
+    /// Offer the registered tools on `work_order`.
+    ///
+    /// The definitions are merged into `config.vendor["abp"]["tools"]`; a
+    /// tool the work order already declares under the same name is
+    /// replaced, since the registry is what will run it.
+    pub fn advertise(&self, work_order: &mut WorkOrder) {
+        advertise(work_order, self.definitions());
+    }
+}
+
+/// Merge `definitions` into `config.vendor["abp"]["tools"]`, replacing
+/// declared tools of the same name.
+pub(crate) fn advertise(work_order: &mut WorkOrder, definitions: Vec<IrToolDefinition>) {
+    if definitions.is_empty() {
+        return;
+    }
+    let mut tools: Vec<IrToolDefinition> = extract_tools(work_order)
+        .into_iter()
+        .filter(|t| !definitions.iter().any(|d| d.name == t.name))
+        .collect();
+    tools.extend(definitions);
+    let Ok(value) = serde_json::to_value(&tools) else {
+        return;
+    };
+    let vendor = &mut work_order.config.vendor;
+    vendor.remove("abp.tools");
+    let abp = vendor.entry("abp".to_string()).or_insert_with(|| json!({}));
+    if let Some(abp) = abp.as_object_mut() {
+        abp.insert("tools".to_string(), value);
+    }
+}
+
+/// A handler that only sees input matching its tool's schema.
+struct Validated {
+    definition: IrToolDefinition,
+    handler: Arc<dyn ToolHandler>,
+}
+
+#[async_trait]
+impl ToolHandler for Validated {
+    async fn call(&self, input: Value) -> Result<Value, String> {
+        check_input(&self.definition, &input)?;
+        self.handler.call(input).await
+    }
+}
+
+fn check_input(definition: &IrToolDefinition, input: &Value) -> Result<(), String> {
+    let validator = jsonschema::validator_for(&definition.parameters)
+        .map_err(|e| format!("schema of tool '{}' does not compile: {e}", definition.name))?;
+    let violations: Vec<String> = validator
+        .iter_errors(input)
+        .map(|e| {
+            let path = e.instance_path.to_string();
+            if path.is_empty() {
+                e.to_string()
+            } else {
+                format!("{path}: {e}")
+            }
+        })
+        .collect();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid input for tool '{}': {}",
+            definition.name,
+            violations.join("; ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::{AgentEvent, AgentEventKind, WorkOrderBuilder};
+
+    fn registry() -> ToolRegistry {
+        ToolRegistry::new().register_fn(
+            "echo",
+            "Echo the text back.",
+            json!({
+                "type": "object",
+                "properties": {"text": {"type": "string"}},
+                "required": ["text"]
+            }),
+            |input| async move { Ok(input["text"].clone()) },
+        )
+    }
+
+    fn call(input: Value) -> AgentEvent {
+        AgentEvent {
+            ts: chrono::Utc::now(),
+            kind: AgentEventKind::ToolCall {
+                tool_name: "echo".into(),
+                tool_use_id: Some("t1".into()),
+                parent_tool_use_id: None,
+                input,
+            },
+            ext: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatcher_refuses_input_that_violates_the_schema() {
+        let dispatcher = registry().dispatcher();
+        let results = dispatcher
+            .dispatch(
+                &[call(json!({"text": "hi"})), call(json!({"text": 1}))],
+                chrono::Utc::now,
+            )
+            .await;
+        let outputs: Vec<_> = results
+            .iter()
+            .map(|ev| match &ev.kind {
+                AgentEventKind::ToolResult {
+                    output, is_error, ..
+                } => (output.clone(), *is_error),
+                other => panic!("unexpected event {other:?}"),
+            })
+            .collect();
+        assert_eq!(outputs[0], (json!("hi"), false));
+        assert!(outputs[1].1);
+        assert!(outputs[1].0.to_string().contains("/text"));
+    }
+
+    #[test]
+    fn advertise_merges_into_declared_tools() {
+        let mut wo = WorkOrderBuilder::new("hi").build();
+        wo.config.vendor.insert(
+            "abp.tools".into(),
+            json!([
+                {"name": "search", "description": "", "parameters": {}},
+                {"name": "echo", "description": "stale", "parameters": {}}
+            ]),
+        );
+        registry().advertise(&mut wo);
+        let tools = extract_tools(&wo);
+        let names: Vec<_> = tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["search", "echo"]);
+        assert_eq!(tools[1].description, "Echo the text back.");
+        assert!(!wo.config.vendor.contains_key("abp.tools"));
+    }
+
+    #[test]
+    fn validate_names_unknown_tools() {
+        let err = registry().validate("nope", &json!({})).unwrap_err();
+        assert!(err.contains("not registered"));
+        assert!(registry().validate("echo", &json!({"text": "x"})).is_ok());
+    }
+}
@@ -0,0 +1,362 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Runtime-executed tools with parallel, barrier-synchronised dispatch.
+//!
+//! A backend without native tool execution reports the tools it wants run as
+//! [`ToolCall`](abp_core::AgentEventKind::ToolCall) events. With
+//! [`Runtime::with_tool_dispatcher`](crate::Runtime::with_tool_dispatcher)
+//! the runtime runs calls to registered tools itself and emits a
+//! [`ToolResult`](abp_core::AgentEventKind::ToolResult) for each one.
+//!
+//! Consecutive tool calls form one turn. The turn ends at the backend's next
+//! non-tool-call event (or the end of its stream); the runtime then runs
+//! every registered call of the turn concurrently and waits for all of them
+//! — the barrier — before forwarding anything else, so the results sit
+//! between the turn's calls and whatever the backend sent next. Results are
+//! emitted in call order by default, which is what every supported provider
+//! expects when results are fed back; see
+//! [`ResultOrder`](crate::tools::ResultOrder).
+//!
+//! Each call runs under the dispatcher's timeout for its tool. A call that
+//! times out or fails produces an `is_error` result rather than failing the
+//! run, so one slow tool cannot hold the rest of the turn hostage.
+//!
+//! The dispatcher is the one place tools run: host tools from a
+//! [`ToolRegistry`](crate::tool_registry::ToolRegistry) and the built-in
+//! [`ToolSuite`] (see [`register_suite`](ToolDispatcher::register_suite))
+//! are registered on it, and a [`ToolLoop`](crate::tool_loop::ToolLoop)
+//! runs each turn through it too.
+//!
+//! ```
+//! # async fn demo() {
+//! use std::time::Duration;
+//! use abp_runtime::Runtime;
+//! use abp_runtime::tools::ToolDispatcher;
+//!
+//! let tools = ToolDispatcher::new()
+//!     .with_timeout(Duration::from_secs(30))
+//!     .register_fn("clock", |_input| async { Ok(serde_json::json!("12:00")) })
+//!     .tool_timeout("clock", Duration::from_secs(1));
+//! let rt = Runtime::with_default_backends().with_tool_dispatcher(tools);
+//! # let _ = rt;
+//! # }
+//! ```
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use abp_core::{AgentEvent, AgentEventKind};
+use abp_tools::ToolSuite;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+use tokio::task::JoinSet;
+
+/// A tool the runtime executes on a backend's behalf.
+#[async_trait]
+pub trait ToolHandler: Send + Sync {
+    /// Run the tool with the call's `input`.
+    ///
+    /// # Errors
+    ///
+    /// The error message becomes the output of an `is_error` tool result.
+    async fn call(&self, input: Value) -> Result<Value, String>;
+
+    /// Run the tool like [`call`](Self::call), also returning events that
+    /// describe its side effects, such as `FileChanged`. They are emitted
+    /// ahead of the call's result.
+    ///
+    /// The default reports no side effects.
+    async fn call_traced(&self, input: Value) -> (Result<Value, String>, Vec<AgentEventKind>) {
+        (self.call(input).await, Vec::new())
+    }
+}
+
+/// Adapts an async closure to [`ToolHandler`].
+pub(crate) struct FnHandler<F>(pub(crate) F);
+
+#[async_trait]
+impl<F, Fut> ToolHandler for FnHandler<F>
+where
+    F: Fn(Value) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<Value, String>> + Send,
+{
+    async fn call(&self, input: Value) -> Result<Value, String> {
+        (self.0)(input).await
+    }
+}
+
+/// A built-in tool run by a shared [`ToolSuite`].
+struct SuiteTool {
+    suite: Arc<ToolSuite>,
+    name: String,
+}
+
+#[async_trait]
+impl ToolHandler for SuiteTool {
+    async fn call(&self, input: Value) -> Result<Value, String> {
+        self.call_traced(input).await.0
+    }
+
+    async fn call_traced(&self, input: Value) -> (Result<Value, String>, Vec<AgentEventKind>) {
+        match self.suite.execute(&self.name, &input).await {
+            Ok(out) => (Ok(out.output), out.events),
+            Err(e) => (Err(e.to_string()), Vec::new()),
+        }
+    }
+}
+
+/// Order in which a turn's tool results are emitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResultOrder {
+    /// The order the backend made the calls in. Providers that correlate
+    /// results positionally (Gemini `functionResponse` parts, OpenAI
+    /// parallel tool messages) need this.
+    #[default]
+    CallOrder,
+    /// The order the calls finished in, for consumers that only display
+    /// results.
+    Completion,
+}
+
+/// Registered tools plus the timeouts and ordering used to run them.
+#[derive(Clone, Default)]
+pub struct ToolDispatcher {
+    handlers: BTreeMap<String, Arc<dyn ToolHandler>>,
+    timeouts: BTreeMap<String, Duration>,
+    default_timeout: Option<Duration>,
+    order: ResultOrder,
+    refuse_unknown: bool,
+}
+
+impl std::fmt::Debug for ToolDispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ToolDispatcher")
+            .field("tools", &self.handlers.keys().collect::<Vec<_>>())
+            .field("timeouts", &self.timeouts)
+            .field("default_timeout", &self.default_timeout)
+            .field("order", &self.order)
+            .field("refuse_unknown", &self.refuse_unknown)
+            .finish()
+    }
+}
+
+impl ToolDispatcher {
+    /// A dispatcher with no tools, no timeout, and call-order results.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `handler` under tool name `name`, replacing any earlier one
+    /// (builder pattern).
+    #[must_use]
+    pub fn register(
+        mut self,
+        name: impl Into<String>,
+        handler: impl ToolHandler + 'static,
+    ) -> Self {
+        self.handlers.insert(name.into(), Arc::new(handler));
+        self
+    }
+
+    /// Register an async closure as tool `name` (builder pattern).
+    #[must_use]
+    pub fn register_fn<F, Fut>(self, name: impl Into<String>, f: F) -> Self
+    where
+        F: Fn(Value) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Value, String>> + Send + 'static,
+    {
+        self.register(name, FnHandler(f))
+    }
+
+    /// Register the Read, Write, Edit and Bash tools of `suite`, leaving
+    /// tools already registered under those names in place (builder
+    /// pattern).
+    #[must_use]
+    pub fn register_suite(mut self, suite: ToolSuite) -> Self {
+        let suite = Arc::new(suite);
+        for definition in ToolSuite::definitions() {
+            if !self.handles(&definition.name) {
+                let handler = SuiteTool {
+                    suite: Arc::clone(&suite),
+                    name: definition.name.clone(),
+                };
+                self.handlers.insert(definition.name, Arc::new(handler));
+            }
+        }
+        self
+    }
+
+    /// Answer calls to unregistered tools with an `is_error` result instead
+    /// of skipping them (builder pattern).
+    ///
+    /// A [`ToolLoop`](crate::tool_loop::ToolLoop) owes the backend a result
+    /// for every call; the runtime's in-stream dispatch leaves other calls
+    /// to the backend.
+    #[must_use]
+    pub(crate) fn refuse_unknown(mut self) -> Self {
+        self.refuse_unknown = true;
+        self
+    }
+
+    /// Timeout for tools without their own (builder pattern).
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for tool `name`, overriding the default (builder pattern).
+    #[must_use]
+    pub fn tool_timeout(mut self, name: impl Into<String>, timeout: Duration) -> Self {
+        self.timeouts.insert(name.into(), timeout);
+        self
+    }
+
+    /// Order in which results are emitted (builder pattern).
+    #[must_use]
+    pub fn result_order(mut self, order: ResultOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Whether tool `name` is registered.
+    #[must_use]
+    pub fn handles(&self, name: &str) -> bool {
+        self.handlers.contains_key(name)
+    }
+
+    /// Names of the registered tools.
+    pub fn tool_names(&self) -> impl Iterator<Item = &str> {
+        self.handlers.keys().map(String::as_str)
+    }
+
+    /// The timeout applied to calls of tool `name`, if any.
+    #[must_use]
+    pub fn timeout_for(&self, name: &str) -> Option<Duration> {
+        self.timeouts.get(name).copied().or(self.default_timeout)
+    }
+
+    /// Run one turn of tool calls concurrently and return their results once
+    /// every call has finished.
+    ///
+    /// Events that are not tool calls are skipped, as are calls to
+    /// unregistered tools unless the dispatcher refuses them. Each result
+    /// follows the side-effect events its tool reported; all are stamped
+    /// with `now()` as they are emitted.
+    pub async fn dispatch(
+        &self,
+        calls: &[AgentEvent],
+        now: impl Fn() -> DateTime<Utc>,
+    ) -> Vec<AgentEvent> {
+        let mut set = JoinSet::new();
+        let mut spawned = Vec::new();
+        let mut finished = Vec::new();
+        for (index, ev) in calls.iter().enumerate() {
+            let AgentEventKind::ToolCall {
+                tool_name,
+                tool_use_id,
+                input,
+                ..
+            } = &ev.kind
+            else {
+                continue;
+            };
+            let Some(handler) = self.handlers.get(tool_name).cloned() else {
+                if self.refuse_unknown {
+                    let output = json!({ "error": format!("tool '{tool_name}' is not available") });
+                    finished.push((
+                        index,
+                        vec![AgentEventKind::ToolResult {
+                            tool_name: tool_name.clone(),
+                            tool_use_id: tool_use_id.clone(),
+                            output,
+                            is_error: true,
+                        }],
+                    ));
+                }
+                continue;
+            };
+            let timeout = self.timeout_for(tool_name);
+            spawned.push((index, tool_name.clone(), tool_use_id.clone()));
+            let tool_name = tool_name.clone();
+            let tool_use_id = tool_use_id.clone();
+            let input = input.clone();
+            set.spawn(async move {
+                let (outcome, mut events) = match timeout {
+                    Some(limit) => tokio::time::timeout(limit, handler.call_traced(input))
+                        .await
+                        .unwrap_or_else(|_| {
+                            let message = format!(
+                                "tool '{tool_name}' timed out after {} ms",
+                                limit.as_millis()
+                            );
+                            (Err(message), Vec::new())
+                        }),
+                    None => handler.call_traced(input).await,
+                };
+                let (output, is_error) = match outcome {
+                    Ok(output) => (output, false),
+                    Err(message) => (json!({ "error": message }), true),
+                };
+                events.push(AgentEventKind::ToolResult {
+                    tool_name,
+                    tool_use_id,
+                    output,
+                    is_error,
+                });
+                (index, events)
+            });
+        }
+
+        while let Some(joined) = set.join_next().await {
+            match joined {
+                Ok(result) => finished.push(result),
+                Err(e) => tracing::warn!(target: "abp.runtime", error=%e, "tool task panicked"),
+            }
+        }
+        // A panicked handler still owes the backend a result.
+        for (index, tool_name, tool_use_id) in spawned {
+            if !finished.iter().any(|(i, _)| *i == index) {
+                let output = json!({ "error": format!("tool '{tool_name}' panicked") });
+                finished.push((
+                    index,
+                    vec![AgentEventKind::ToolResult {
+                        tool_name,
+                        tool_use_id,
+                        output,
+                        is_error: true,
+                    }],
+                ));
+            }
+        }
+        if self.order == ResultOrder::CallOrder {
+            finished.sort_by_key(|(index, _)| *index);
+        }
+        finished
+            .into_iter()
+            .flat_map(|(_, kinds)| kinds)
+            .map(|kind| AgentEvent {
+                ts: now(),
+                kind,
+                ext: None,
+            })
+            .collect()
+    }
+}
+
+/// Whether `event` continues the current turn of tool calls.
+pub(crate) fn continues_turn(event: &AgentEvent) -> bool {
+    matches!(event.kind, AgentEventKind::ToolCall { .. })
+}
+
+/// Whether `event` is a call the runtime should run with `dispatcher`.
+///
+/// Calls a [`ToolLoop`](crate::tool_loop::ToolLoop) has already run are
+/// left alone.
+pub(crate) fn is_dispatched(dispatcher: &ToolDispatcher, event: &AgentEvent) -> bool {
+    matches!(&event.kind, AgentEventKind::ToolCall { tool_name, .. } if dispatcher.handles(tool_name))
+        && !crate::tool_loop::is_loop_call(event)
+}
@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for assembling artifacts that backends stream in chunks.
+
+pub mod common;
+
+use abp_core::{AgentEvent, AgentEventKind, Receipt};
+use abp_runtime::Runtime;
+use abp_runtime::artifact_stream::{
+    Absorbed, ArtifactStreams, STREAM_ARTIFACT_KIND, StreamedArtifact,
+};
+use base64::Engine as _;
+use common::{ScriptedBackend, work_order};
+use uuid::Uuid;
+
+fn chunk(name: &str, seq: u64, bytes: &[u8], last: bool, sha256: Option<String>) -> AgentEvent {
+    AgentEvent {
+        ts: chrono::Utc::now(),
+        kind: AgentEventKind::ArtifactChunk {
+            name: name.into(),
+            seq,
+            data: base64::engine::general_purpose::STANDARD.encode(bytes),
+            last,
+            media_type: (seq == 0).then(|| "image/png".into()),
+            sha256,
+        },
+        ext: None,
+    }
+}
+
+/// Runs a backend that streams `events`, then completes.
+async fn run(mut rt: Runtime, events: Vec<AgentEvent>) -> (Vec<AgentEvent>, Receipt) {
+    rt.register_backend("streamer", ScriptedBackend::new("streamer").events(events));
+    let (events, receipt) =
+        common::run_with_events(&rt, "streamer", work_order("draw a cat")).await;
+    (events, receipt.unwrap())
+}
+
+fn warnings(events: &[AgentEvent]) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|e| match &e.kind {
+            AgentEventKind::Warning { message } => Some(message.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn chunks_are_assembled_stored_and_registered() {
+    let dir = tempfile::tempdir().unwrap();
+    let image = b"\x89PNG fake image bytes";
+    let rt = Runtime::new().with_artifact_streams(ArtifactStreams::new().store_in(dir.path()));
+    let (events, receipt) = run(
+        rt,
+        vec![
+            chunk("images/cat.png", 0, &image[..10], false, None),
+            chunk(
+                "images/cat.png",
+                1,
+                &image[10..],
+                true,
+                Some(abp_core::sha256_hex(image)),
+            ),
+        ],
+    )
+    .await;
+
+    let is_chunk = |e: &AgentEvent| matches!(e.kind, AgentEventKind::ArtifactChunk { .. });
+    assert!(!events.iter().any(is_chunk));
+    assert!(!receipt.trace.iter().any(is_chunk));
+    assert!(warnings(&events).is_empty());
+
+    assert!(
+        receipt
+            .artifacts
+            .iter()
+            .any(|a| a.kind == STREAM_ARTIFACT_KIND && a.path == "images/cat.png")
+    );
+    let artifacts = StreamedArtifact::from_receipt(&receipt);
+    assert_eq!(artifacts.len(), 1);
+    let cat = &artifacts[0];
+    assert_eq!(cat.size, image.len() as u64);
+    assert_eq!(cat.sha256, abp_core::sha256_hex(image));
+    assert_eq!(cat.chunks, 2);
+    assert_eq!(cat.media_type.as_deref(), Some("image/png"));
+    let stored = dir
+        .path()
+        .join(receipt.meta.run_id.to_string())
+        .join("images/cat.png");
+    assert_eq!(cat.stored_at.as_deref(), Some(&*stored.to_string_lossy()));
+    assert_eq!(std::fs::read(&stored).unwrap(), image);
+    assert!(cat.verify());
+}
+
+#[tokio::test]
+async fn broken_streams_are_discarded_with_warnings() {
+    let rt = Runtime::new();
+    let (events, receipt) = run(
+        rt,
+        vec![
+            chunk("gap.bin", 0, b"ab", false, None),
+            chunk("gap.bin", 2, b"cd", true, None),
+            chunk("../escape.bin", 0, b"x", true, None),
+            chunk("bad-hash.bin", 0, b"x", true, Some("00".into())),
+            chunk("open.bin", 0, b"x", false, None),
+            chunk("ok.bin", 0, b"fine", true, None),
+        ],
+    )
+    .await;
+
+    let warnings = warnings(&events);
+    assert_eq!(warnings.len(), 4, "{warnings:?}");
+    assert!(warnings[0].contains("'gap.bin'") && warnings[0].contains("expected 1"));
+    assert!(warnings[1].contains("'../escape.bin'"));
+    assert!(warnings[2].contains("'bad-hash.bin'") && warnings[2].contains("does not match"));
+    assert!(warnings[3].contains("'open.bin'") && warnings[3].contains("last chunk"));
+
+    let artifacts = StreamedArtifact::from_receipt(&receipt);
+    let names: Vec<_> = artifacts.iter().map(|a| a.name.as_str()).collect();
+    assert_eq!(names, ["ok.bin"]);
+    assert_eq!(artifacts[0].stored_at, None);
+    assert!(!artifacts[0].verify());
+}
+
+#[test]
+fn assembler_enforces_the_size_limit_and_restarts_on_chunk_zero() {
+    let mut assembler = ArtifactStreams::new().max_bytes(4).assembler(Uuid::nil());
+    assert!(matches!(
+        assembler.absorb(&chunk("a", 0, b"abc", false, None)),
+        Absorbed::Pending
+    ));
+    let Absorbed::Rejected(reason) = assembler.absorb(&chunk("a", 1, b"de", true, None)) else {
+        panic!("expected the artifact to be rejected");
+    };
+    assert!(reason.contains("4 byte limit"), "{reason}");
+
+    assembler.absorb(&chunk("b", 0, b"xx", false, None));
+    let Absorbed::Completed(b) = assembler.absorb(&chunk("b", 0, b"yy", true, None)) else {
+        panic!("expected a restarted artifact to complete");
+    };
+    assert_eq!(b.sha256, abp_core::sha256_hex(b"yy"));
+    assert_eq!(
+        assembler.absorb(&AgentEvent {
+            ts: chrono::Utc::now(),
+            kind: AgentEventKind::Warning {
+                message: "hi".into()
+            },
+            ext: None,
+        }),
+        Absorbed::NotAChunk
+    );
+    let (done, incomplete) = assembler.finish();
+    assert_eq!(done, [b]);
+    assert!(incomplete.is_empty());
+}
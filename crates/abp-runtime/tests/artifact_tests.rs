@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for collecting workspace artifacts into receipts.
+
+use std::path::Path;
+
+use abp_core::{
+    AgentEvent, BackendIdentity, CapabilityManifest, Receipt, WorkOrder, WorkOrderBuilder,
+    WorkspaceMode,
+};
+use abp_integrations::{Backend, MockBackend};
+use abp_runtime::Runtime;
+use abp_runtime::artifacts::{ARTIFACTS_KEY, ArtifactCollector, ArtifactRecord};
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Writes a report and a binary blob into its workspace, then finishes like
+/// the mock backend.
+struct Writer;
+
+#[async_trait]
+impl Backend for Writer {
+    fn identity(&self) -> BackendIdentity {
+        MockBackend.identity()
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        MockBackend.capabilities()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        let root = Path::new(&work_order.workspace.root);
+        std::fs::create_dir_all(root.join("out"))?;
+        std::fs::write(root.join("out/report.txt"), "all green")?;
+        std::fs::write(root.join("out/blob.bin"), [0xff, 0xfe, 0x00])?;
+        std::fs::write(root.join("out/big.txt"), "x".repeat(100))?;
+        MockBackend.run(run_id, work_order, events_tx).await
+    }
+}
+
+async fn run(collector: ArtifactCollector) -> (Receipt, tempfile::TempDir) {
+    let source = tempfile::tempdir().unwrap();
+    std::fs::write(source.path().join("main.rs"), "fn main() {}").unwrap();
+    let mut rt = Runtime::new().with_artifact_collector(collector);
+    rt.register_backend("writer", Writer);
+    let wo = WorkOrderBuilder::new("write a report")
+        .root(source.path().to_string_lossy())
+        .workspace_mode(WorkspaceMode::Staged)
+        .build();
+    let handle = rt.run_streaming("writer", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    (handle.receipt.await.unwrap().unwrap(), source)
+}
+
+#[tokio::test]
+async fn matching_files_are_hashed_listed_and_small_text_inlined() {
+    let collector = ArtifactCollector::new(&["out/**".into()])
+        .unwrap()
+        .exclude(&["**/*.tmp".into()])
+        .unwrap()
+        .inline_up_to(16);
+    let (receipt, source) = run(collector).await;
+
+    let records = ArtifactRecord::from_receipt(&receipt);
+    let paths: Vec<_> = records.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(paths, ["out/big.txt", "out/blob.bin", "out/report.txt"]);
+
+    let report = &records[2];
+    assert_eq!(report.size, 9);
+    assert_eq!(report.sha256, abp_core::sha256_hex(b"all green"));
+    assert_eq!(report.content.as_deref(), Some("all green"));
+    assert_eq!(records[0].content, None, "over the inline limit");
+    assert_eq!(records[1].content, None, "not UTF-8");
+
+    let listed: Vec<_> = receipt
+        .artifacts
+        .iter()
+        .map(|a| (a.kind.as_str(), a.path.as_str()))
+        .collect();
+    assert!(listed.contains(&("file", "out/report.txt")));
+    assert!(!listed.iter().any(|(_, p)| *p == "main.rs"));
+
+    // The records are part of the hashed receipt.
+    let stored = receipt.receipt_sha256.clone().unwrap();
+    assert_eq!(abp_core::receipt_hash(&receipt).unwrap(), stored);
+
+    // Staged runs leave the source untouched, so nothing there verifies.
+    assert!(!report.verify(source.path()));
+}
+
+#[tokio::test]
+async fn records_verify_against_a_matching_tree() {
+    let (receipt, _source) = run(ArtifactCollector::new(&[]).unwrap().max_files(2)).await;
+    let records = ArtifactRecord::from_receipt(&receipt);
+    assert_eq!(records.len(), 2, "capped by max_files");
+
+    let copy = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(copy.path().join("out")).unwrap();
+    std::fs::write(copy.path().join("main.rs"), "fn main() {}").unwrap();
+    std::fs::write(copy.path().join("out/big.txt"), "x".repeat(100)).unwrap();
+    std::fs::write(copy.path().join("out/blob.bin"), [0xff, 0xfe, 0x01]).unwrap();
+    let verified: Vec<_> = records.iter().map(|r| r.verify(copy.path())).collect();
+    assert_eq!(
+        records.iter().map(|r| r.path.as_str()).collect::<Vec<_>>(),
+        ["main.rs", "out/big.txt"]
+    );
+    assert_eq!(verified, [true, true]);
+
+    let blob = ArtifactCollector::new(&["out/blob.bin".into()])
+        .unwrap()
+        .collect(copy.path())
+        .unwrap();
+    assert_ne!(blob[0].sha256, abp_core::sha256_hex(&[0xff, 0xfe, 0x00]));
+    assert!(receipt.usage_raw.get(ARTIFACTS_KEY).is_some());
+}
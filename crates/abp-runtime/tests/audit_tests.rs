@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the hash-chained audit log.
+
+use std::sync::Arc;
+
+use abp_core::clock::{Clock, ManualClock};
+use abp_core::submitter::Submitter;
+use abp_core::{WorkOrder, WorkOrderBuilder};
+use abp_runtime::Runtime;
+use abp_runtime::audit::{
+    AuditAction, AuditChainError, AuditLog, actor_for_work_order, read_log, verify_chain,
+};
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+
+fn config_change(new: &str) -> AuditAction {
+    AuditAction::ConfigChanged {
+        key: "backends.default".into(),
+        old: None,
+        new: Some(serde_json::json!(new)),
+    }
+}
+
+fn work_order(submitter: Submitter) -> WorkOrder {
+    WorkOrderBuilder::new("audited task")
+        .root(".")
+        .workspace_mode(abp_core::WorkspaceMode::PassThrough)
+        .submitter(submitter)
+        .build()
+}
+
+#[test]
+fn entries_link_to_their_predecessor() {
+    let log = AuditLog::in_memory();
+    let first = log.record(Some("alice"), config_change("mock")).unwrap();
+    let second = log.record(Some("bob"), config_change("openai")).unwrap();
+
+    assert_eq!((first.seq, second.seq), (0, 1));
+    assert_eq!(first.prev_sha256, None);
+    assert_eq!(second.prev_sha256, first.entry_sha256);
+    assert_eq!(log.head_hash(), second.entry_sha256);
+    assert_eq!(log.len(), 2);
+    verify_chain(&log.entries()).unwrap();
+}
+
+#[test]
+fn edited_entry_is_detected() {
+    let log = AuditLog::in_memory();
+    log.record(Some("alice"), config_change("mock")).unwrap();
+    log.record(Some("alice"), config_change("openai")).unwrap();
+
+    let mut entries = log.entries();
+    entries[0].actor = Some("mallory".into());
+    assert_eq!(
+        verify_chain(&entries),
+        Err(AuditChainError::Tampered { seq: 0 })
+    );
+}
+
+#[test]
+fn removed_entry_is_detected() {
+    let log = AuditLog::in_memory();
+    for new in ["a", "b", "c"] {
+        log.record(None, config_change(new)).unwrap();
+    }
+    let mut entries = log.entries();
+    entries.remove(1);
+    assert!(matches!(
+        verify_chain(&entries),
+        Err(AuditChainError::SeqGap {
+            expected: 1,
+            found: 2,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn rehashed_entry_still_breaks_the_link() {
+    let log = AuditLog::in_memory();
+    log.record(None, config_change("a")).unwrap();
+    log.record(None, config_change("b")).unwrap();
+
+    let mut entries = log.entries();
+    entries[0].actor = Some("mallory".into());
+    entries[0].entry_sha256 = Some(entries[0].compute_hash().unwrap());
+    assert_eq!(
+        verify_chain(&entries),
+        Err(AuditChainError::BrokenLink { seq: 1 })
+    );
+}
+
+#[test]
+fn file_log_resumes_chain_after_reopen() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("audit").join("audit.jsonl");
+
+    let log = AuditLog::open(&path).unwrap();
+    log.record(Some("alice"), config_change("mock")).unwrap();
+    let head = log.head_hash();
+    drop(log);
+
+    let log = AuditLog::open(&path).unwrap();
+    assert_eq!(log.len(), 1);
+    let entry = log
+        .record(
+            Some("alice"),
+            AuditAction::RunCancelled {
+                run_id: Uuid::nil(),
+                reason: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(entry.seq, 1);
+    assert_eq!(entry.prev_sha256, head);
+
+    let entries = read_log(&path).unwrap();
+    assert_eq!(entries.len(), 2);
+    verify_chain(&entries).unwrap();
+}
+
+#[test]
+fn tampered_file_is_refused_on_open() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let log = AuditLog::open(&path).unwrap();
+    log.record(Some("alice"), config_change("mock")).unwrap();
+    drop(log);
+
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, text.replace("alice", "mallory")).unwrap();
+    assert!(AuditLog::open(&path).is_err());
+}
+
+#[test]
+fn entries_serialize_action_inline() {
+    let log = AuditLog::in_memory();
+    let entry = log
+        .record(
+            Some("reviewer"),
+            AuditAction::ApprovalDecided {
+                run_id: Uuid::nil(),
+                tool: "bash".into(),
+                approved: false,
+            },
+        )
+        .unwrap();
+    let json = serde_json::to_value(&entry).unwrap();
+    assert_eq!(json["action"], "approval_decided");
+    assert_eq!(json["tool"], "bash");
+    assert_eq!(json["approved"], false);
+}
+
+#[test]
+fn actor_prefers_api_key_over_tenant() {
+    assert_eq!(
+        actor_for_work_order(&work_order(
+            Submitter::new().tenant("acme").api_key_id("key-1")
+        )),
+        Some("key-1".into())
+    );
+    assert_eq!(
+        actor_for_work_order(&work_order(Submitter::new().tenant("acme"))),
+        Some("acme".into())
+    );
+    assert_eq!(actor_for_work_order(&work_order(Submitter::new())), None);
+}
+
+#[tokio::test]
+async fn runtime_records_submissions_and_key_use() {
+    let clock = Arc::new(ManualClock::new(
+        Utc.with_ymd_and_hms(2026, 3, 14, 9, 0, 0).unwrap(),
+    ));
+    let rt = Runtime::with_default_backends()
+        .with_audit_log(AuditLog::in_memory().with_clock(clock.clone()));
+
+    let wo = work_order(Submitter::new().tenant("acme").api_key_id("key-1"));
+    let wo_id = wo.id;
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let run_id = handle.run_id;
+    drop(handle.events);
+    handle.receipt.await.unwrap().unwrap();
+
+    let entries = rt.audit_log().unwrap().entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(
+        entries[0].action,
+        AuditAction::WorkOrderSubmitted {
+            work_order_id: wo_id,
+            run_id,
+            backend: "mock".into(),
+        }
+    );
+    assert_eq!(entries[0].actor.as_deref(), Some("key-1"));
+    assert_eq!(entries[0].timestamp, clock.now());
+    assert!(matches!(
+        &entries[1].action,
+        AuditAction::KeyUsed { key_id, .. } if key_id == "key-1"
+    ));
+    verify_chain(&entries).unwrap();
+}
+
+#[tokio::test]
+async fn rejected_work_order_is_not_recorded() {
+    let rt = Runtime::with_default_backends().with_audit_log(AuditLog::in_memory());
+    assert!(
+        rt.run_streaming("missing", work_order(Submitter::new()))
+            .await
+            .is_err()
+    );
+    assert!(rt.audit_log().unwrap().is_empty());
+}
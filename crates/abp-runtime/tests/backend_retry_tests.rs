@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for per-backend retry and attempt timeouts in `run_streaming`.
+
+pub mod common;
+
+use std::time::Duration;
+
+use abp_core::clock::Clock;
+use abp_core::{AgentEvent, AgentEventKind, Receipt};
+use abp_error::ErrorCode;
+use abp_runtime::config_integration::RuntimeConfig;
+use abp_runtime::retry::{
+    BackendRetryConfig, RETRY_HISTORY_KEY, RetryAttempt, RetryPolicy, event_attempt,
+};
+use abp_runtime::{Runtime, RuntimeError};
+use common::{Behavior, ScriptedBackend, clock, work_order};
+
+fn fast_retry(max_retries: u32) -> BackendRetryConfig {
+    BackendRetryConfig::new(
+        RetryPolicy::builder()
+            .max_retries(max_retries)
+            .initial_backoff(Duration::from_millis(1))
+            .max_backoff(Duration::from_millis(5))
+            .build(),
+    )
+}
+
+/// A runtime whose `flaky` backend ends its first `failures` runs with
+/// `failure`. Every attempt starts with a `RunStarted` event.
+fn runtime(failure: Behavior, failures: u32) -> (Runtime, ScriptedBackend) {
+    let flaky = ScriptedBackend::new("flaky")
+        .event(AgentEventKind::RunStarted {
+            message: "attempt".into(),
+        })
+        .fail_first(failures, failure);
+    let mut rt = Runtime::new();
+    rt.register_backend("flaky", flaky.clone());
+    (rt, flaky)
+}
+
+async fn run(rt: &Runtime) -> (Vec<AgentEvent>, Result<Receipt, RuntimeError>) {
+    common::run_with_events(rt, "flaky", work_order("retry me")).await
+}
+
+fn history(receipt: &Receipt) -> Vec<RetryAttempt> {
+    serde_json::from_value(receipt.usage_raw[RETRY_HISTORY_KEY].clone()).unwrap()
+}
+
+#[tokio::test]
+async fn crashed_attempt_is_retried_and_recorded() {
+    let (rt, flaky) = runtime(Behavior::Crash, 1);
+    let rt = rt.with_backend_retry("flaky", fast_retry(3));
+    let (events, result) = run(&rt).await;
+    let receipt = result.unwrap();
+
+    assert_eq!(flaky.calls(), 2);
+    let history = history(&receipt);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].attempt, 0);
+    assert_eq!(history[0].error_code, ErrorCode::BackendCrashed);
+    assert!(history[0].error.contains("flaky crashed"));
+    assert!(events.iter().any(|e| matches!(
+        &e.kind,
+        AgentEventKind::Warning { message } if message.contains("retrying")
+    )));
+}
+
+#[tokio::test]
+async fn backoff_follows_the_runtime_clock_and_events_carry_their_attempt() {
+    let clock = clock();
+    let (rt, flaky) = runtime(Behavior::Crash, 1);
+    let rt = rt.with_clock(clock.clone()).with_backend_retry(
+        "flaky",
+        BackendRetryConfig::new(
+            RetryPolicy::builder()
+                .max_retries(1)
+                .initial_backoff(Duration::from_secs(60))
+                .max_backoff(Duration::from_secs(60))
+                .build(),
+        ),
+    );
+    let before = clock.now();
+    let (events, result) = tokio::time::timeout(Duration::from_secs(5), run(&rt))
+        .await
+        .expect("backoff must not sleep in real time");
+    let receipt = result.unwrap();
+
+    assert_eq!(flaky.calls(), 2);
+    let delay = history(&receipt)[0].delay_ms;
+    assert_eq!(
+        clock.now() - before,
+        chrono::Duration::milliseconds(delay as i64)
+    );
+
+    let attempts: Vec<_> = events
+        .iter()
+        .filter(|e| matches!(e.kind, AgentEventKind::RunStarted { .. }))
+        .map(event_attempt)
+        .collect();
+    assert_eq!(attempts, [Some(0), Some(1)]);
+    let warning = events
+        .iter()
+        .find(|e| matches!(e.kind, AgentEventKind::Warning { .. }))
+        .unwrap();
+    assert_eq!(event_attempt(warning), Some(0));
+}
+
+#[tokio::test]
+async fn events_are_untagged_without_retry_settings() {
+    let (rt, _) = runtime(Behavior::Crash, 0);
+    let (events, result) = run(&rt).await;
+    result.unwrap();
+    assert!(events.iter().all(|e| event_attempt(e).is_none()));
+}
+
+#[tokio::test]
+async fn attempt_timeout_is_classified_and_retried() {
+    let (rt, flaky) = runtime(Behavior::Hang, 1);
+    let rt = rt.with_backend_retry(
+        "flaky",
+        fast_retry(1).with_timeout(Duration::from_millis(50)),
+    );
+    let (_, result) = run(&rt).await;
+    let receipt = result.unwrap();
+
+    assert_eq!(flaky.calls(), 2);
+    let history = history(&receipt);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].error_code, ErrorCode::BackendTimeout);
+}
+
+#[tokio::test]
+async fn exhausted_retries_return_the_last_error() {
+    let (rt, flaky) = runtime(Behavior::Crash, u32::MAX);
+    let rt = rt.with_backend_retry("flaky", fast_retry(2));
+    let (_, result) = run(&rt).await;
+
+    assert!(matches!(result, Err(RuntimeError::BackendFailed(_))));
+    assert_eq!(flaky.calls(), 3);
+}
+
+#[tokio::test]
+async fn non_transient_errors_are_not_retried() {
+    let (rt, flaky) = runtime(Behavior::Fail(ErrorCode::BackendRateLimited), 1);
+    let rt = rt.with_backend_retry("flaky", fast_retry(3));
+    let (_, result) = run(&rt).await;
+
+    assert!(result.is_err());
+    assert_eq!(flaky.calls(), 1);
+}
+
+#[tokio::test]
+async fn backends_without_settings_get_one_attempt() {
+    let (rt, flaky) = runtime(Behavior::Crash, 1);
+    let (_, result) = run(&rt).await;
+
+    assert!(result.is_err());
+    assert_eq!(flaky.calls(), 1);
+}
+
+#[tokio::test]
+async fn runtime_config_settings_are_applied() {
+    let config = RuntimeConfig::builder()
+        .backend_retry("flaky", fast_retry(1))
+        .build();
+    let (rt, flaky) = runtime(Behavior::Crash, 1);
+    let rt = rt.with_retry_config(&config);
+    assert!(rt.backend_retry("flaky").is_some());
+
+    let (_, result) = run(&rt).await;
+    let receipt = result.unwrap();
+    assert_eq!(flaky.calls(), 2);
+    assert!(receipt.usage_raw.get(RETRY_HISTORY_KEY).is_some());
+}
+
+#[test]
+fn backend_retry_config_serde_roundtrip() {
+    let config = fast_retry(2).with_timeout(Duration::from_secs(30));
+    let json = serde_json::to_value(&config).unwrap();
+    assert_eq!(json["timeout"], 30_000);
+    let back: BackendRetryConfig = serde_json::from_value(json).unwrap();
+    assert_eq!(back, config);
+}
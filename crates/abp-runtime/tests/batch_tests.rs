@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for running batches of work orders concurrently.
+
+pub mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use abp_core::{
+    AgentEvent, BackendIdentity, CapabilityManifest, Outcome, Receipt, UsageNormalized, WorkOrder,
+};
+use abp_error::ErrorCode;
+use abp_integrations::Backend;
+use abp_runtime::Runtime;
+use abp_runtime::batch::{BatchOptions, BatchTarget};
+use async_trait::async_trait;
+use common::work_order;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Records how many runs overlap; fails work orders whose task says "fail".
+#[derive(Clone, Default)]
+struct Counting {
+    in_flight: Arc<AtomicUsize>,
+    peak: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Backend for Counting {
+    fn identity(&self) -> BackendIdentity {
+        BackendIdentity {
+            id: "counting".into(),
+            backend_version: None,
+            adapter_version: None,
+        }
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        CapabilityManifest::new()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        _events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.peak.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        if work_order.task.contains("fail") {
+            anyhow::bail!("asked to fail");
+        }
+        Ok(abp_receipt::ReceiptBuilder::new("counting")
+            .run_id(run_id)
+            .work_order_id(work_order.id)
+            .outcome(Outcome::Complete)
+            .usage(UsageNormalized {
+                input_tokens: Some(10),
+                output_tokens: Some(5),
+                ..UsageNormalized::default()
+            })
+            .build())
+    }
+}
+
+#[tokio::test]
+async fn batch_respects_the_concurrency_limit_and_sums_usage() {
+    let backend = Counting::default();
+    let mut rt = Runtime::new();
+    rt.register_backend("counting", backend.clone());
+    let rt = Arc::new(rt);
+
+    let orders: Vec<_> = (0..10).map(|i| work_order(&format!("task {i}"))).collect();
+    let ids: Vec<_> = orders.iter().map(|wo| wo.id).collect();
+    let mut batch = rt.run_batch(orders, BatchOptions::backend("counting").concurrency(3));
+
+    let mut indices = Vec::new();
+    while let Some((index, handle)) = batch.runs.next().await {
+        let handle = handle.unwrap();
+        indices.push(index);
+        tokio::spawn(async move {
+            let _: Vec<_> = handle.events.collect().await;
+            handle.receipt.await.unwrap().unwrap();
+        });
+    }
+    let receipt = batch.receipt.await.unwrap();
+
+    assert_eq!(indices, (0..10).collect::<Vec<_>>());
+    assert!(backend.peak.load(Ordering::SeqCst) <= 3);
+    assert_eq!(receipt.total, 10);
+    assert_eq!(receipt.complete, 10);
+    assert_eq!(receipt.failed, 0);
+    assert_eq!(receipt.usage.input_tokens, Some(100));
+    assert_eq!(receipt.usage.output_tokens, Some(50));
+    let order_ids: Vec<_> = receipt.runs.iter().map(|r| r.work_order_id).collect();
+    assert_eq!(order_ids, ids);
+    assert!(receipt.runs.iter().all(|r| r.run_id.is_some()));
+}
+
+#[tokio::test]
+async fn failures_are_reported_per_run() {
+    let mut rt = Runtime::new();
+    rt.register_backend("counting", Counting::default());
+    let rt = Arc::new(rt);
+
+    let orders = vec![
+        work_order("ok"),
+        work_order("please fail"),
+        work_order("ok"),
+    ];
+    let target = BatchTarget::RoundRobin(vec!["counting".into(), "counting".into(), "nope".into()]);
+    let mut batch = rt.run_batch(orders, BatchOptions::new(target).concurrency(0));
+
+    let mut start_errors = Vec::new();
+    while let Some((index, handle)) = batch.runs.next().await {
+        match handle {
+            Ok(handle) => {
+                let _: Vec<_> = handle.events.collect().await;
+                let _ = handle.receipt.await.unwrap();
+            }
+            Err(_) => start_errors.push(index),
+        }
+    }
+    let receipt = batch.receipt.await.unwrap();
+
+    assert_eq!(start_errors, [2]);
+    assert_eq!(receipt.complete, 1);
+    assert_eq!(receipt.failed, 2);
+    let failures: Vec<_> = receipt.failures().map(|r| r.index).collect();
+    assert_eq!(failures, [1, 2]);
+    assert_eq!(receipt.runs[1].backend.as_deref(), Some("counting"));
+    assert!(receipt.runs[1].error.is_some());
+    assert_eq!(receipt.runs[2].backend.as_deref(), Some("nope"));
+    assert_eq!(receipt.runs[2].run_id, None);
+    assert_eq!(receipt.runs[2].error_code, Some(ErrorCode::BackendNotFound));
+}
+
+#[tokio::test]
+async fn batch_receipt_resolves_when_runs_are_not_consumed() {
+    let mut rt = Runtime::new();
+    rt.register_backend("counting", Counting::default());
+    let rt = Arc::new(rt);
+
+    let batch = rt.run_batch(
+        vec![work_order("a"), work_order("b"), work_order("c")],
+        BatchOptions::backend("counting").concurrency(2),
+    );
+    drop(batch.runs);
+    let receipt = batch.receipt.await.unwrap();
+    assert_eq!(receipt.total, 3);
+    assert_eq!(receipt.complete, 3);
+}
@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for adaptive assistant delta batching in the caller event stream.
+
+pub mod common;
+
+use std::time::Duration;
+
+use abp_core::{AgentEvent, AgentEventKind, WorkOrder};
+use abp_runtime::Runtime;
+use abp_runtime::batching::DeltaBatching;
+use common::ScriptedBackend;
+use tokio_stream::StreamExt;
+
+const DELTAS: usize = 400;
+
+/// A runtime whose backend streams [`DELTAS`] one-character deltas,
+/// optionally pausing after each.
+fn runtime(pause: Option<Duration>) -> Runtime {
+    let delta = common::event(AgentEventKind::AssistantDelta { text: "x".into() });
+    let mut chatty = ScriptedBackend::new("chatty").events(vec![delta; DELTAS]);
+    if let Some(pause) = pause {
+        chatty = chatty.pause(pause);
+    }
+    let mut rt = Runtime::new().with_delta_batching(DeltaBatching::default());
+    rt.register_backend("chatty", chatty);
+    rt
+}
+
+fn work_order() -> WorkOrder {
+    common::work_order("stream a lot")
+}
+
+fn delta_texts(events: &[AgentEvent]) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|e| match &e.kind {
+            AgentEventKind::AssistantDelta { text } => Some(text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn slow_consumer_receives_merged_deltas() {
+    let rt = runtime(None);
+    let mut handle = rt.run_streaming("chatty", work_order()).await.unwrap();
+
+    // Let the backend run ahead of us before draining anything.
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let mut events = Vec::new();
+    while let Some(ev) = handle.events.next().await {
+        events.push(ev);
+    }
+    let receipt = handle.receipt.await.unwrap().unwrap();
+
+    let texts = delta_texts(&events);
+    assert!(
+        texts.len() < DELTAS,
+        "expected merging, got {}",
+        texts.len()
+    );
+    assert_eq!(texts.concat(), "x".repeat(DELTAS));
+    // The receipt keeps every delta as the backend sent it.
+    assert_eq!(delta_texts(&receipt.trace).len(), DELTAS);
+}
+
+#[tokio::test]
+async fn fast_consumer_receives_every_delta() {
+    let rt = runtime(Some(Duration::from_millis(1)));
+    let mut handle = rt.run_streaming("chatty", work_order()).await.unwrap();
+    let mut events = Vec::new();
+    while let Some(ev) = handle.events.next().await {
+        events.push(ev);
+    }
+    handle.receipt.await.unwrap().unwrap();
+
+    let texts = delta_texts(&events);
+    assert_eq!(texts.len(), DELTAS);
+    assert_eq!(texts.concat(), "x".repeat(DELTAS));
+}
+
+#[test]
+fn batching_is_off_by_default() {
+    assert!(Runtime::new().delta_batching().is_none());
+    let rt = Runtime::new().with_delta_batching(DeltaBatching::default());
+    assert_eq!(rt.delta_batching(), Some(DeltaBatching::default()));
+}
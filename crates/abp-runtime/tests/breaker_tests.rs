@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for per-backend circuit breakers.
+
+pub mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use abp_core::CapabilityManifest;
+use abp_core::clock::ManualClock;
+use abp_dialect::Dialect;
+use abp_error::ErrorCode;
+use abp_projection::ProjectionMatrix;
+use abp_runtime::breaker::{Admission, BreakerConfig, BreakerState, CircuitBreakers};
+use abp_runtime::{Runtime, RuntimeError};
+use common::{Behavior, ScriptedBackend, clock, work_order};
+
+fn config() -> BreakerConfig {
+    BreakerConfig::default()
+        .failure_threshold(2)
+        .cooldown(Duration::from_secs(10))
+}
+
+/// A runtime with a crashing `flaky` backend, returned for the test to
+/// heal, and a `steady` one.
+fn runtime(clock: Arc<ManualClock>) -> (Runtime, ScriptedBackend) {
+    let flaky = ScriptedBackend::new("flaky").behavior(Behavior::Crash);
+    let mut rt = Runtime::new()
+        .with_clock(clock)
+        .with_circuit_breakers(CircuitBreakers::new(config()));
+    rt.register_backend("flaky", flaky.clone());
+    rt.register_backend("steady", ScriptedBackend::new("steady"));
+    (rt, flaky)
+}
+
+async fn run(rt: &Runtime, backend: &str) -> Result<abp_core::Receipt, RuntimeError> {
+    common::run(rt, backend, work_order("do work")).await
+}
+
+async fn trip(rt: &Runtime) {
+    for _ in 0..2 {
+        let err = run(rt, "flaky").await.unwrap_err();
+        assert_eq!(err.error_code(), ErrorCode::BackendCrashed);
+    }
+}
+
+fn retry_after_ms(err: &RuntimeError) -> u64 {
+    assert_eq!(err.error_code(), ErrorCode::CircuitBreakerOpen);
+    match err {
+        RuntimeError::Classified(e) => e.context["retry_after_ms"].as_u64().unwrap(),
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[tokio::test]
+async fn consecutive_failures_open_the_breaker() {
+    let clock = clock();
+    let (rt, _) = runtime(clock.clone());
+
+    trip(&rt).await;
+    let err = run(&rt, "flaky").await.unwrap_err();
+    assert_eq!(retry_after_ms(&err), 10_000);
+    clock.advance(Duration::from_secs(4));
+    let err = run(&rt, "flaky").await.unwrap_err();
+    assert_eq!(retry_after_ms(&err), 6_000);
+
+    // Other backends are unaffected.
+    run(&rt, "steady").await.unwrap();
+
+    let breakers = rt.circuit_breakers().unwrap();
+    assert_eq!(breakers.state("flaky"), BreakerState::Open);
+    assert_eq!(breakers.state("steady"), BreakerState::Closed);
+    let status = &breakers.statuses()[0];
+    assert_eq!(status.backend, "flaky");
+    assert_eq!(status.consecutive_failures, 2);
+    assert_eq!(status.trips, 1);
+    assert!(status.opened_at.is_some());
+
+    let snapshot = rt.metrics().snapshot();
+    assert_eq!(snapshot.breaker_trips, 1);
+    assert_eq!(snapshot.breakers["flaky"], BreakerState::Open);
+}
+
+#[tokio::test]
+async fn a_successful_probe_closes_the_breaker() {
+    let clock = clock();
+    let (rt, flaky) = runtime(clock.clone());
+
+    trip(&rt).await;
+    clock.advance(Duration::from_secs(10));
+    flaky.set_behavior(Behavior::Succeed);
+    run(&rt, "flaky").await.unwrap();
+
+    assert_eq!(
+        rt.circuit_breakers().unwrap().state("flaky"),
+        BreakerState::Closed
+    );
+    let snapshot = rt.metrics().snapshot();
+    assert_eq!(snapshot.breakers["flaky"], BreakerState::Closed);
+    assert_eq!(snapshot.breaker_trips, 1);
+}
+
+#[tokio::test]
+async fn a_failed_probe_reopens_the_breaker() {
+    let clock = clock();
+    let (rt, _) = runtime(clock.clone());
+
+    trip(&rt).await;
+    clock.advance(Duration::from_secs(10));
+    let err = run(&rt, "flaky").await.unwrap_err();
+    assert_eq!(err.error_code(), ErrorCode::BackendCrashed);
+
+    let err = run(&rt, "flaky").await.unwrap_err();
+    assert_eq!(retry_after_ms(&err), 10_000);
+    assert_eq!(rt.circuit_breakers().unwrap().statuses()[0].trips, 2);
+    assert_eq!(rt.metrics().snapshot().breaker_trips, 2);
+}
+
+#[tokio::test]
+async fn projection_skips_backends_with_open_breakers() {
+    let clock = clock();
+    let mut matrix = ProjectionMatrix::new();
+    matrix.register_backend("flaky", CapabilityManifest::default(), Dialect::OpenAi, 90);
+    matrix.register_backend("steady", CapabilityManifest::default(), Dialect::OpenAi, 10);
+    let (rt, _) = runtime(clock.clone());
+    let rt = rt.with_projection(matrix);
+
+    assert_eq!(
+        rt.select_backend(&work_order("do work"))
+            .unwrap()
+            .selected_backend,
+        "flaky"
+    );
+    trip(&rt).await;
+
+    let result = rt.select_backend(&work_order("do work")).unwrap();
+    assert_eq!(result.selected_backend, "steady");
+    assert!(
+        result
+            .fallback_chain
+            .iter()
+            .all(|f| f.backend_id != "flaky")
+    );
+
+    // Once the cooldown ends the backend is eligible again, for a probe.
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(
+        rt.select_backend(&work_order("do work"))
+            .unwrap()
+            .selected_backend,
+        "flaky"
+    );
+}
+
+#[test]
+fn half_open_breakers_limit_concurrent_probes() {
+    let clock = clock();
+    let breakers = CircuitBreakers::new(config())
+        .backend("slow", config().failure_threshold(1).half_open_probes(2));
+    assert_eq!(breakers.config("slow").failure_threshold, 1);
+
+    let admission = breakers.admit("slow", &*clock).unwrap();
+    assert_eq!(admission, Admission::Normal);
+    assert_eq!(
+        breakers.record("slow", admission, false, &*clock),
+        Some(BreakerState::Open)
+    );
+    assert!(!breakers.is_available("slow", &*clock));
+
+    clock.advance(Duration::from_secs(10));
+    assert!(breakers.is_available("slow", &*clock));
+    let first = breakers.admit("slow", &*clock).unwrap();
+    let second = breakers.admit("slow", &*clock).unwrap();
+    assert_eq!((first, second), (Admission::Probe, Admission::Probe));
+    assert_eq!(breakers.state("slow"), BreakerState::HalfOpen);
+    let err = breakers.admit("slow", &*clock).unwrap_err();
+    assert_eq!(err.code, ErrorCode::CircuitBreakerOpen);
+
+    // A probe that never reached the backend frees its slot.
+    breakers.release("slow", second);
+    let second = breakers.admit("slow", &*clock).unwrap();
+
+    // Closing takes as many successes as there are probes.
+    assert_eq!(breakers.record("slow", first, true, &*clock), None);
+    assert_eq!(
+        breakers.record("slow", second, true, &*clock),
+        Some(BreakerState::Closed)
+    );
+
+    breakers.record("slow", Admission::Normal, false, &*clock);
+    breakers.reset("slow");
+    assert_eq!(breakers.state("slow"), BreakerState::Closed);
+    assert_eq!(breakers.statuses()[0].trips, 2);
+}
+
+#[test]
+fn breaker_config_round_trips_through_serde() {
+    let config: BreakerConfig = serde_json::from_value(serde_json::json!({
+        "failure_threshold": 3,
+        "cooldown": 5000,
+        "half_open_probes": 2,
+    }))
+    .unwrap();
+    assert_eq!(
+        config,
+        BreakerConfig::default()
+            .failure_threshold(3)
+            .cooldown(Duration::from_secs(5))
+            .half_open_probes(2)
+    );
+    assert_eq!(
+        serde_json::to_value(&config).unwrap()["cooldown"],
+        serde_json::json!(5000)
+    );
+}
@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for stopping runs mid-flight when reported usage exceeds the budget.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use abp_capability::models::{ModelCatalog, ModelProfile};
+use abp_core::{
+    AgentEvent, AgentEventKind, BackendIdentity, CapabilityManifest, Outcome, Receipt,
+    UsageNormalized, WorkOrder, WorkOrderBuilder, WorkspaceMode,
+};
+use abp_error::ErrorCode;
+use abp_integrations::Backend;
+use abp_runtime::budget::{BUDGET_EXCEEDED_KEY, MAX_TOKENS_VENDOR_KEY, USAGE_EXT_KEY};
+use abp_runtime::{Runtime, RuntimeError};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Reports 100 more output tokens ($0.01) per step, for ten steps.
+#[derive(Debug, Clone, Default)]
+struct Spender {
+    steps: Arc<AtomicU32>,
+}
+
+#[async_trait]
+impl Backend for Spender {
+    fn identity(&self) -> BackendIdentity {
+        BackendIdentity {
+            id: "spender".into(),
+            backend_version: None,
+            adapter_version: None,
+        }
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        CapabilityManifest::new()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> Result<Receipt> {
+        for step in 1..=10u64 {
+            self.steps.fetch_add(1, Ordering::SeqCst);
+            let usage = UsageNormalized {
+                input_tokens: Some(0),
+                output_tokens: Some(step * 100),
+                estimated_cost_usd: Some(step as f64 * 0.01),
+                ..UsageNormalized::default()
+            };
+            let _ = events_tx
+                .send(AgentEvent {
+                    ts: Utc::now(),
+                    kind: AgentEventKind::AssistantDelta {
+                        text: format!("step {step}"),
+                    },
+                    ext: Some([(USAGE_EXT_KEY.to_string(), json!(usage))].into()),
+                })
+                .await;
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        Ok(abp_receipt::ReceiptBuilder::new("spender")
+            .run_id(run_id)
+            .work_order_id(work_order.id)
+            .outcome(Outcome::Complete)
+            .build())
+    }
+}
+
+fn runtime() -> (Runtime, Arc<AtomicU32>) {
+    let backend = Spender::default();
+    let steps = backend.steps.clone();
+    let mut rt = Runtime::new();
+    rt.register_backend("spender", backend);
+    (rt, steps)
+}
+
+fn work_order() -> WorkOrderBuilder {
+    WorkOrderBuilder::new("spend").workspace_mode(WorkspaceMode::PassThrough)
+}
+
+async fn run(rt: &Runtime, wo: WorkOrder) -> (Vec<AgentEvent>, Receipt) {
+    let mut handle = rt.run_streaming("spender", wo).await.unwrap();
+    let mut events = Vec::new();
+    while let Some(ev) = handle.events.next().await {
+        events.push(ev);
+    }
+    (events, handle.receipt.await.unwrap().unwrap())
+}
+
+async fn rejection(rt: &Runtime, wo: WorkOrder) -> RuntimeError {
+    match rt.run_streaming("spender", wo).await {
+        Err(err) => err,
+        Ok(handle) => {
+            drop(handle.events);
+            handle.receipt.await.unwrap().unwrap_err()
+        }
+    }
+}
+
+#[tokio::test]
+async fn token_ceiling_stops_run_with_partial_receipt() {
+    let (rt, steps) = runtime();
+    let mut wo = work_order().build();
+    wo.config
+        .vendor
+        .insert(MAX_TOKENS_VENDOR_KEY.into(), json!(250));
+    let (events, receipt) = run(&rt, wo).await;
+
+    assert_eq!(receipt.outcome, Outcome::Partial);
+    assert_eq!(receipt.usage.output_tokens, Some(300));
+    let exceeded = &receipt.usage_raw[BUDGET_EXCEEDED_KEY];
+    assert_eq!(exceeded["cancellation"], "budget_exhausted");
+    assert!(
+        exceeded["reason"]
+            .as_str()
+            .unwrap()
+            .contains("token budget exceeded")
+    );
+    assert!(steps.load(Ordering::SeqCst) < 10);
+    assert!(matches!(
+        events.last().map(|e| &e.kind),
+        Some(AgentEventKind::Warning { .. })
+    ));
+    assert!(
+        receipt
+            .trace
+            .iter()
+            .any(|e| matches!(e.kind, AgentEventKind::Warning { .. }))
+    );
+    assert!(abp_receipt::verify_hash(&receipt));
+}
+
+#[tokio::test]
+async fn over_budget_run_cancels_the_backend() {
+    let (rt, steps) = runtime();
+    let (_, receipt) = run(&rt, work_order().max_budget_usd(0.015).build()).await;
+    assert_eq!(receipt.outcome, Outcome::Partial);
+
+    // The backend's attempt was cancelled, so it takes no further steps.
+    let stopped_at = steps.load(Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(steps.load(Ordering::SeqCst), stopped_at);
+}
+
+#[tokio::test]
+async fn cost_ceiling_stops_run() {
+    let (rt, _) = runtime();
+    let (_, receipt) = run(&rt, work_order().max_budget_usd(0.045).build()).await;
+    assert_eq!(receipt.outcome, Outcome::Partial);
+    assert_eq!(receipt.usage.estimated_cost_usd, Some(0.05));
+    assert!(
+        receipt.usage_raw[BUDGET_EXCEEDED_KEY]["reason"]
+            .as_str()
+            .unwrap()
+            .contains("cost budget exceeded")
+    );
+}
+
+#[tokio::test]
+async fn run_within_budget_completes() {
+    let (rt, steps) = runtime();
+    let (_, receipt) = run(&rt, work_order().max_budget_usd(1.0).build()).await;
+    assert_eq!(receipt.outcome, Outcome::Complete);
+    assert!(receipt.usage_raw.get(BUDGET_EXCEEDED_KEY).is_none());
+    assert_eq!(steps.load(Ordering::SeqCst), 10);
+}
+
+#[tokio::test]
+async fn input_over_token_ceiling_is_rejected_before_dispatch() {
+    let (rt, steps) = runtime();
+    let mut wo = WorkOrderBuilder::new("word ".repeat(400))
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    wo.config
+        .vendor
+        .insert(MAX_TOKENS_VENDOR_KEY.into(), json!(250));
+    let err = rejection(&rt, wo).await;
+    assert_eq!(err.error_code(), ErrorCode::QuotaExceeded);
+    assert_eq!(steps.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn projected_count_is_checked_against_the_context_window() {
+    let (rt, steps) = runtime();
+    let mut catalog = ModelCatalog::new();
+    catalog.register(ModelProfile::new("tiny").max_context_tokens(1_000));
+    let rt = rt.with_model_catalog(catalog);
+
+    let mut wo = work_order().model("tiny").build();
+    wo.config
+        .vendor
+        .insert("abp".into(), json!({"projected_input_tokens": 5_000}));
+    let err = rejection(&rt, wo).await;
+    assert_eq!(err.error_code(), ErrorCode::BackendContextLength);
+    assert_eq!(steps.load(Ordering::SeqCst), 0);
+
+    let (_, receipt) = run(&rt, work_order().model("tiny").build()).await;
+    assert_eq!(receipt.outcome, Outcome::Complete);
+}
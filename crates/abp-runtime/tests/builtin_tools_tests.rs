@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the built-in tools run by the runtime's tool loop.
+
+pub mod common;
+
+use abp_core::ir::IrRole;
+use abp_core::{
+    AgentEvent, AgentEventKind, Capability, PolicyProfile, Receipt, SupportLevel, WorkOrderBuilder,
+    WorkspaceMode,
+};
+use abp_integrations::{extract_conversation, extract_tools};
+use abp_runtime::Runtime;
+use common::{ScriptedBackend, event};
+use serde_json::json;
+use tokio_stream::StreamExt;
+
+/// Writes `hello.txt` on the first turn and stops once it sees a result.
+fn writer() -> ScriptedBackend {
+    ScriptedBackend::new("writer")
+        .capabilities(
+            [
+                (Capability::Streaming, SupportLevel::Native),
+                (Capability::ToolUse, SupportLevel::Native),
+            ]
+            .into(),
+        )
+        .respond_with(|work_order| {
+            assert!(extract_tools(work_order).iter().any(|t| t.name == "Write"));
+            let answered = extract_conversation(work_order)
+                .is_some_and(|c| c.messages.iter().any(|m| m.role == IrRole::Tool));
+            let kind = if answered {
+                AgentEventKind::AssistantMessage {
+                    text: "done".into(),
+                }
+            } else {
+                AgentEventKind::ToolCall {
+                    tool_name: "Write".into(),
+                    tool_use_id: Some("call_1".into()),
+                    parent_tool_use_id: None,
+                    input: json!({"path": "hello.txt", "content": "hi\n"}),
+                }
+            };
+            vec![event(kind)]
+        })
+}
+
+async fn run(policy: PolicyProfile) -> (tempfile::TempDir, Vec<AgentEvent>, Receipt) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("README.md"), "# demo\n").unwrap();
+    let wo = WorkOrderBuilder::new("Say hello in a file")
+        .root(dir.path().to_string_lossy().to_string())
+        .workspace_mode(WorkspaceMode::Staged)
+        .policy(policy)
+        .build();
+    let mut rt = Runtime::new().with_builtin_tools();
+    rt.register_backend("writer", writer());
+    let handle = rt.run_streaming("writer", wo).await.unwrap();
+    let events: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    (dir, events, receipt)
+}
+
+#[tokio::test]
+async fn writes_land_in_the_staged_workspace_and_the_trace() {
+    let (dir, events, receipt) = run(PolicyProfile::default()).await;
+
+    assert!(events.iter().any(|e| matches!(
+        &e.kind,
+        AgentEventKind::FileChanged { path, .. } if path == "hello.txt"
+    )));
+    assert!(receipt.trace.iter().any(|e| matches!(
+        &e.kind,
+        AgentEventKind::FileChanged { path, .. } if path == "hello.txt"
+    )));
+    assert!(
+        receipt
+            .verification
+            .git_status
+            .as_deref()
+            .unwrap_or_default()
+            .contains("hello.txt")
+    );
+    // The caller's directory is untouched.
+    assert!(!dir.path().join("hello.txt").exists());
+}
+
+#[tokio::test]
+async fn policy_denials_come_back_as_error_results() {
+    let policy = PolicyProfile {
+        deny_write: vec!["hello.txt".into()],
+        ..PolicyProfile::default()
+    };
+    let (_dir, events, _) = run(policy).await;
+
+    assert!(
+        !events
+            .iter()
+            .any(|e| matches!(e.kind, AgentEventKind::FileChanged { .. }))
+    );
+    assert!(
+        events
+            .iter()
+            .any(|e| matches!(e.kind, AgentEventKind::ToolResult { is_error: true, .. }))
+    );
+}
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Serving unchanged work orders from the run cache.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use abp_core::{
+    AgentEvent, BackendIdentity, CapabilityManifest, Receipt, WorkOrder, WorkOrderBuilder,
+    WorkspaceMode,
+};
+use abp_integrations::{Backend, MockBackend};
+use abp_runtime::Runtime;
+use abp_runtime::artifacts::{ArtifactCollector, ArtifactRecord};
+use abp_runtime::cache::{CacheRecord, CacheStatus, RunCache};
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Writes a report into its workspace, counting how often it ran.
+#[derive(Clone, Default)]
+struct Transform {
+    runs: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Backend for Transform {
+    fn identity(&self) -> BackendIdentity {
+        MockBackend.identity()
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        MockBackend.capabilities()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        self.runs.fetch_add(1, Ordering::SeqCst);
+        let root = Path::new(&work_order.workspace.root);
+        std::fs::write(root.join("report.txt"), "all green")?;
+        MockBackend.run(run_id, work_order, events_tx).await
+    }
+}
+
+struct Fixture {
+    rt: Runtime,
+    backend: Transform,
+    source: tempfile::TempDir,
+    _cache_dir: tempfile::TempDir,
+}
+
+impl Fixture {
+    fn new() -> Self {
+        let source = tempfile::tempdir().unwrap();
+        std::fs::write(source.path().join("input.txt"), "v1").unwrap();
+        let cache_dir = tempfile::tempdir().unwrap();
+        let backend = Transform::default();
+        let mut rt = Runtime::new()
+            .with_run_cache(RunCache::new(cache_dir.path()))
+            .with_artifact_collector(
+                ArtifactCollector::new(&["report.txt".into()])
+                    .unwrap()
+                    .inline_up_to(1024),
+            );
+        rt.register_backend("transform", backend.clone());
+        Self {
+            rt,
+            backend,
+            source,
+            _cache_dir: cache_dir,
+        }
+    }
+
+    fn order(&self, cache: Option<&str>) -> WorkOrder {
+        let mut wo = WorkOrderBuilder::new("summarize input.txt")
+            .root(self.source.path().to_string_lossy())
+            .workspace_mode(WorkspaceMode::Staged)
+            .build();
+        if let Some(mode) = cache {
+            wo.config
+                .vendor
+                .insert("abp".into(), serde_json::json!({ "cache": mode }));
+        }
+        wo
+    }
+
+    async fn run(&self, cache: Option<&str>) -> Receipt {
+        let handle = self
+            .rt
+            .run_streaming("transform", self.order(cache))
+            .await
+            .unwrap();
+        let _: Vec<_> = handle.events.collect().await;
+        handle.receipt.await.unwrap().unwrap()
+    }
+
+    fn runs(&self) -> usize {
+        self.backend.runs.load(Ordering::SeqCst)
+    }
+}
+
+#[tokio::test]
+async fn unchanged_inputs_are_served_from_the_cache() {
+    let fx = Fixture::new();
+    let first = fx.run(None).await;
+    let second = fx.run(None).await;
+
+    assert_eq!(fx.runs(), 1);
+    let miss = CacheRecord::from_receipt(&first).unwrap();
+    assert_eq!(miss.status, CacheStatus::Miss);
+
+    let hit = CacheRecord::from_receipt(&second).unwrap();
+    assert_eq!(hit.status, CacheStatus::Hit);
+    assert_eq!(hit.key, miss.key);
+    assert_eq!(hit.source_run_id, Some(first.meta.run_id));
+    assert_eq!(hit.source_receipt_sha256, first.receipt_sha256);
+
+    assert_ne!(second.meta.run_id, first.meta.run_id);
+    assert_eq!(second.trace.len(), first.trace.len());
+    let artifacts = ArtifactRecord::from_receipt(&second);
+    assert_eq!(artifacts, ArtifactRecord::from_receipt(&first));
+    assert_eq!(artifacts[0].content.as_deref(), Some("all green"));
+    assert!(abp_receipt::verify_hash(&second));
+}
+
+#[tokio::test]
+async fn changed_workspaces_miss_the_cache() {
+    let fx = Fixture::new();
+    let first = fx.run(None).await;
+    std::fs::write(fx.source.path().join("input.txt"), "v2").unwrap();
+    let second = fx.run(None).await;
+
+    assert_eq!(fx.runs(), 2);
+    let (a, b) = (
+        CacheRecord::from_receipt(&first).unwrap(),
+        CacheRecord::from_receipt(&second).unwrap(),
+    );
+    assert_eq!(b.status, CacheStatus::Miss);
+    assert_ne!(a.key, b.key);
+}
+
+#[tokio::test]
+async fn work_orders_and_operators_can_bust_the_cache() {
+    let fx = Fixture::new();
+    let first = fx.run(None).await;
+
+    let bypassed = fx.run(Some("bypass")).await;
+    assert_eq!(fx.runs(), 2);
+    assert!(CacheRecord::from_receipt(&bypassed).is_none());
+
+    let refreshed = fx.run(Some("refresh")).await;
+    assert_eq!(fx.runs(), 3);
+    assert_eq!(
+        CacheRecord::from_receipt(&refreshed).unwrap().status,
+        CacheStatus::Refreshed
+    );
+    let hit = fx.run(None).await;
+    assert_eq!(fx.runs(), 3);
+    assert_eq!(
+        CacheRecord::from_receipt(&hit).unwrap().source_run_id,
+        Some(refreshed.meta.run_id)
+    );
+
+    let cache = fx.rt.run_cache().unwrap();
+    let key = CacheRecord::from_receipt(&first).unwrap().key;
+    assert!(cache.invalidate(&key).unwrap());
+    fx.run(None).await;
+    assert_eq!(fx.runs(), 4);
+
+    assert_eq!(cache.clear().unwrap(), 1);
+    fx.run(None).await;
+    assert_eq!(fx.runs(), 5);
+}
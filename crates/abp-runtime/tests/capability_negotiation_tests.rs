@@ -979,3 +979,64 @@ async fn preflight_prevents_backend_execution() {
     let result = rt.run_streaming("mock", wo).await;
     assert!(result.is_err(), "should fail at preflight");
 }
+
+// ---------- 35. Per-model manifests refine backend capabilities ----------
+
+fn streaming_required() -> CapabilityRequirements {
+    CapabilityRequirements {
+        required: vec![CapabilityRequirement {
+            capability: Capability::Streaming,
+            min_support: MinSupport::Native,
+        }],
+    }
+}
+
+fn batch_only_catalog() -> abp_capability::models::ModelCatalog {
+    let mut catalog = abp_capability::models::ModelCatalog::new();
+    catalog.register(abp_capability::models::ModelProfile::new("batch-only").streaming(false));
+    catalog
+}
+
+#[tokio::test]
+async fn model_catalog_rejects_capability_missing_on_model() {
+    let rt = Runtime::with_default_backends().with_model_catalog(batch_only_catalog());
+    let mut wo = mock_work_order();
+    wo.requirements = streaming_required();
+    wo.config.model = Some("batch-only".into());
+
+    let result = rt.run_streaming("mock", wo).await;
+    assert!(result.is_err(), "model without streaming must be rejected");
+}
+
+#[tokio::test]
+async fn model_catalog_ignored_for_unknown_model() {
+    let rt = Runtime::with_default_backends().with_model_catalog(batch_only_catalog());
+    let mut wo = mock_work_order();
+    wo.requirements = streaming_required();
+    wo.config.model = Some("something-else".into());
+
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    assert!(matches!(receipt.outcome, abp_core::Outcome::Complete));
+}
+
+#[test]
+fn effective_capabilities_applies_model_profile() {
+    let rt = Runtime::with_default_backends().with_model_catalog(batch_only_catalog());
+    let backend = rt.backend("mock").unwrap();
+    let mut wo = mock_work_order();
+
+    let backend_wide = rt.effective_capabilities(backend.as_ref(), &wo);
+    assert_eq!(
+        backend_wide.get(&Capability::Streaming),
+        Some(&abp_core::SupportLevel::Native)
+    );
+
+    wo.config.model = Some("batch-only".into());
+    let per_model = rt.effective_capabilities(backend.as_ref(), &wo);
+    assert_eq!(
+        per_model.get(&Capability::Streaming),
+        Some(&abp_core::SupportLevel::Unsupported)
+    );
+}
@@ -0,0 +1,340 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Fixtures shared by the runtime integration tests.
+//!
+//! [`ScriptedBackend`] is a backend whose identity, capabilities, events,
+//! receipt usage, and run ending are set by the test, which reads back how
+//! often it was called and with which work orders.
+//!
+//! Test files declare this module `pub mod common;`: each uses only part of
+//! it, and public items are not reported as dead code.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use abp_core::clock::ManualClock;
+use abp_core::{
+    AgentEvent, AgentEventKind, BackendIdentity, CapabilityManifest, Receipt, UsageNormalized,
+    WorkOrder, WorkOrderBuilder, WorkspaceMode,
+};
+use abp_error::{AbpError, ErrorCode};
+use abp_integrations::{Backend, VendorNamespace};
+use abp_runtime::{Runtime, RuntimeError};
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// A work order for `task` that runs in place, without staging a workspace.
+pub fn work_order(task: &str) -> WorkOrder {
+    WorkOrderBuilder::new(task)
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build()
+}
+
+/// An event of `kind` stamped now.
+pub fn event(kind: AgentEventKind) -> AgentEvent {
+    AgentEvent {
+        ts: Utc::now(),
+        kind,
+        ext: None,
+    }
+}
+
+/// A manual clock set to 2026-03-14 09:00 UTC.
+pub fn clock() -> Arc<ManualClock> {
+    Arc::new(ManualClock::new(
+        Utc.with_ymd_and_hms(2026, 3, 14, 9, 0, 0).unwrap(),
+    ))
+}
+
+/// Run `wo` on `backend`, discarding the streamed events.
+pub async fn run(rt: &Runtime, backend: &str, wo: WorkOrder) -> Result<Receipt, RuntimeError> {
+    run_with_events(rt, backend, wo).await.1
+}
+
+/// Run `wo` on `backend`, returning the streamed events with the result.
+pub async fn run_with_events(
+    rt: &Runtime,
+    backend: &str,
+    wo: WorkOrder,
+) -> (Vec<AgentEvent>, Result<Receipt, RuntimeError>) {
+    let handle = match rt.run_streaming(backend, wo).await {
+        Ok(handle) => handle,
+        Err(e) => return (Vec::new(), Err(e)),
+    };
+    let events: Vec<_> = handle.events.collect().await;
+    (events, handle.receipt.await.unwrap())
+}
+
+/// How a [`ScriptedBackend`] run ends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Behavior {
+    /// Return a receipt.
+    Succeed,
+    /// Fail with an unclassified error, as a crashed sidecar does.
+    Crash,
+    /// Fail with a classified error.
+    Fail(ErrorCode),
+    /// Never finish.
+    Hang,
+}
+
+/// Produces a run's events from its work order.
+type Responder = Arc<dyn Fn(&WorkOrder) -> Vec<AgentEvent> + Send + Sync>;
+
+#[derive(Debug)]
+struct Script {
+    behavior: Behavior,
+    /// Calls left before later ones succeed; `None` applies `behavior` to
+    /// every call.
+    remaining: Option<u32>,
+}
+
+/// Backend whose events, receipt, and failures are set by the test.
+///
+/// Clones share the call count, the recorded work orders, and the
+/// behaviour, so a test can keep a clone of a registered backend.
+#[derive(Clone)]
+pub struct ScriptedBackend {
+    id: String,
+    capabilities: CapabilityManifest,
+    vendor_namespace: Option<VendorNamespace>,
+    events: Vec<AgentEvent>,
+    respond: Option<Responder>,
+    pause: Option<Duration>,
+    usage: UsageNormalized,
+    usage_raw: Option<serde_json::Value>,
+    trace: bool,
+    fail_marker: Option<String>,
+    script: Arc<Mutex<Script>>,
+    calls: Arc<AtomicU32>,
+    work_orders: Arc<Mutex<Vec<WorkOrder>>>,
+}
+
+impl ScriptedBackend {
+    /// A backend named `id` that succeeds without events or capabilities.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            capabilities: CapabilityManifest::default(),
+            vendor_namespace: None,
+            events: Vec::new(),
+            respond: None,
+            pause: None,
+            usage: UsageNormalized::default(),
+            usage_raw: None,
+            trace: false,
+            fail_marker: None,
+            script: Arc::new(Mutex::new(Script {
+                behavior: Behavior::Succeed,
+                remaining: None,
+            })),
+            calls: Arc::new(AtomicU32::new(0)),
+            work_orders: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Advertise `capabilities`.
+    #[must_use]
+    pub fn capabilities(mut self, capabilities: CapabilityManifest) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Claim the `config.vendor` namespace `namespace`.
+    #[must_use]
+    pub fn vendor_namespace(mut self, namespace: VendorNamespace) -> Self {
+        self.vendor_namespace = Some(namespace);
+        self
+    }
+
+    /// Stream an event of `kind` on every run.
+    #[must_use]
+    pub fn event(self, kind: AgentEventKind) -> Self {
+        self.events([event(kind)])
+    }
+
+    /// Stream `events` on every run.
+    #[must_use]
+    pub fn events(mut self, events: impl IntoIterator<Item = AgentEvent>) -> Self {
+        self.events.extend(events);
+        self
+    }
+
+    /// Stream the events `respond` builds from each run's work order,
+    /// after the fixed ones.
+    #[must_use]
+    pub fn respond_with(
+        mut self,
+        respond: impl Fn(&WorkOrder) -> Vec<AgentEvent> + Send + Sync + 'static,
+    ) -> Self {
+        self.respond = Some(Arc::new(respond));
+        self
+    }
+
+    /// Sleep for `pause` after each streamed event.
+    #[must_use]
+    pub fn pause(mut self, pause: Duration) -> Self {
+        self.pause = Some(pause);
+        self
+    }
+
+    /// Report `usage` in the receipt.
+    #[must_use]
+    pub fn usage(mut self, usage: UsageNormalized) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Report `raw` as the receipt's raw usage.
+    #[must_use]
+    pub fn usage_raw(mut self, raw: serde_json::Value) -> Self {
+        self.usage_raw = Some(raw);
+        self
+    }
+
+    /// Report `input` and `output` tokens in the receipt.
+    #[must_use]
+    pub fn tokens(self, input: u64, output: u64) -> Self {
+        self.usage(UsageNormalized {
+            input_tokens: Some(input),
+            output_tokens: Some(output),
+            ..UsageNormalized::default()
+        })
+    }
+
+    /// Put the streamed events in the receipt's trace as well.
+    #[must_use]
+    pub fn with_trace(mut self) -> Self {
+        self.trace = true;
+        self
+    }
+
+    /// End every run with `behavior`.
+    #[must_use]
+    pub fn behavior(self, behavior: Behavior) -> Self {
+        self.set_behavior(behavior);
+        self
+    }
+
+    /// End the first `times` runs with `behavior` and succeed after that.
+    #[must_use]
+    pub fn fail_first(self, times: u32, behavior: Behavior) -> Self {
+        *self.script.lock().unwrap() = Script {
+            behavior,
+            remaining: Some(times),
+        };
+        self
+    }
+
+    /// Crash every run whose task contains `marker`.
+    #[must_use]
+    pub fn crash_on_task(mut self, marker: &str) -> Self {
+        self.fail_marker = Some(marker.to_string());
+        self
+    }
+
+    /// End later runs with `behavior`.
+    pub fn set_behavior(&self, behavior: Behavior) {
+        *self.script.lock().unwrap() = Script {
+            behavior,
+            remaining: None,
+        };
+    }
+
+    /// How many runs have started.
+    pub fn calls(&self) -> u32 {
+        self.calls.load(Ordering::SeqCst)
+    }
+
+    /// The work orders handed to the backend, in call order.
+    pub fn work_orders(&self) -> Vec<WorkOrder> {
+        self.work_orders.lock().unwrap().clone()
+    }
+
+    /// The next call's ending, counting it against `fail_first`.
+    fn next_behavior(&self) -> Behavior {
+        let mut script = self.script.lock().unwrap();
+        match &mut script.remaining {
+            None => script.behavior,
+            Some(0) => Behavior::Succeed,
+            Some(n) => {
+                *n -= 1;
+                script.behavior
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for ScriptedBackend {
+    fn identity(&self) -> BackendIdentity {
+        BackendIdentity {
+            id: self.id.clone(),
+            backend_version: None,
+            adapter_version: None,
+        }
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        self.capabilities.clone()
+    }
+
+    fn vendor_namespace(&self) -> Option<VendorNamespace> {
+        self.vendor_namespace
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.work_orders.lock().unwrap().push(work_order.clone());
+
+        let mut events = self.events.clone();
+        if let Some(respond) = &self.respond {
+            events.extend(respond(&work_order));
+        }
+        for ev in &events {
+            let _ = events_tx.send(ev.clone()).await;
+            if let Some(pause) = self.pause {
+                tokio::time::sleep(pause).await;
+            }
+        }
+
+        let crash_marked = self
+            .fail_marker
+            .as_deref()
+            .is_some_and(|marker| work_order.task.contains(marker));
+        let behavior = if crash_marked {
+            Behavior::Crash
+        } else {
+            self.next_behavior()
+        };
+        match behavior {
+            Behavior::Succeed => {}
+            Behavior::Crash => anyhow::bail!("{} crashed", self.id),
+            Behavior::Fail(code) => {
+                return Err(AbpError::new(code, format!("{} failed", self.id)).into());
+            }
+            Behavior::Hang => std::future::pending().await,
+        }
+
+        let mut receipt = abp_receipt::ReceiptBuilder::new(&self.id)
+            .run_id(run_id)
+            .work_order_id(work_order.id)
+            .capabilities(self.capabilities.clone())
+            .usage(self.usage.clone());
+        if self.trace {
+            receipt = receipt.events(events);
+        }
+        if let Some(raw) = &self.usage_raw {
+            receipt = receipt.usage_raw(raw.clone());
+        }
+        Ok(receipt.build())
+    }
+}
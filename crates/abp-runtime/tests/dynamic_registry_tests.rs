@@ -0,0 +1,114 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for registering and removing backends on a running runtime.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use abp_core::{
+    AgentEvent, BackendIdentity, CapabilityManifest, Outcome, Receipt, WorkOrder, WorkOrderBuilder,
+    WorkspaceMode,
+};
+use abp_integrations::{Backend, MockBackend, ModelInfo};
+use abp_runtime::bus::RegistryChange;
+use abp_runtime::{Runtime, RuntimeError};
+use async_trait::async_trait;
+use tokio::sync::{Notify, mpsc};
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Mock backend that waits for a go-ahead before answering.
+#[derive(Clone, Default)]
+struct Gated {
+    go: Arc<Notify>,
+}
+
+#[async_trait]
+impl Backend for Gated {
+    fn identity(&self) -> BackendIdentity {
+        MockBackend.identity()
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        MockBackend.capabilities()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        self.go.notified().await;
+        MockBackend.run(run_id, work_order, events_tx).await
+    }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<ModelInfo>> {
+        Ok(vec![ModelInfo::new("gated-1")])
+    }
+}
+
+fn work_order() -> WorkOrder {
+    WorkOrderBuilder::new("dynamic registry")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build()
+}
+
+#[tokio::test]
+async fn backend_attached_to_a_shared_runtime_serves_runs() {
+    let rt = Arc::new(Runtime::new());
+    let mut changes = rt.event_bus().subscribe_registry();
+
+    let attacher = Arc::clone(&rt);
+    tokio::spawn(async move { attacher.attach_backend("late", MockBackend) })
+        .await
+        .unwrap();
+
+    assert_eq!(rt.backend_names(), ["late"]);
+    let handle = rt.run_streaming("late", work_order()).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    assert_eq!(receipt.outcome, Outcome::Complete);
+
+    let event = changes.recv().await.unwrap();
+    assert_eq!(
+        RegistryChange::from_event(&event),
+        Some(RegistryChange::Registered {
+            backend: "late".into()
+        })
+    );
+}
+
+#[tokio::test]
+async fn detached_backend_finishes_in_flight_runs_but_takes_no_new_ones() {
+    let gated = Gated::default();
+    let rt = Runtime::new();
+    rt.attach_backend("gated", gated.clone());
+    rt.refresh_models().await;
+    let mut changes = rt.event_bus().subscribe_registry();
+
+    let in_flight = rt.run_streaming("gated", work_order()).await.unwrap();
+    assert!(rt.detach_backend("gated"));
+    assert!(!rt.detach_backend("gated"));
+
+    let err = rt.run_streaming("gated", work_order()).await.err().unwrap();
+    assert!(matches!(err, RuntimeError::UnknownBackend { .. }));
+    assert!(rt.models().listing("gated").is_none());
+
+    gated.go.notify_one();
+    let _: Vec<_> = in_flight.events.collect().await;
+    let receipt = tokio::time::timeout(Duration::from_secs(5), in_flight.receipt)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(receipt.outcome, Outcome::Complete);
+
+    let change = RegistryChange::from_event(&changes.recv().await.unwrap()).unwrap();
+    assert_eq!(
+        change,
+        RegistryChange::Deregistered {
+            backend: "gated".into()
+        }
+    );
+    assert_eq!(change.backend(), "gated");
+}
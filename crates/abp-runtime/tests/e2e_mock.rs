@@ -137,6 +137,39 @@ async fn staged_workspace_has_git_metadata() {
     );
 }
 
+#[tokio::test]
+async fn staged_workspace_records_input_digest() {
+    let tmp = tempfile::tempdir().expect("create temp dir");
+    std::fs::write(tmp.path().join("hello.txt"), "world").expect("write file");
+
+    let rt = Runtime::with_default_backends();
+    let mut wo = mock_work_order();
+    wo.workspace = WorkspaceSpec {
+        root: tmp.path().to_string_lossy().into_owned(),
+        mode: WorkspaceMode::Staged,
+        include: vec![],
+        exclude: vec![],
+    };
+
+    let (_events, receipt) = run_to_completion(&rt, wo).await;
+    let digest = receipt
+        .verification
+        .input_digest
+        .expect("staged workspace should record an input digest");
+    assert!(digest.verify());
+    assert_eq!(
+        digest.files.get("hello.txt"),
+        Some(&abp_core::sha256_hex(b"world"))
+    );
+}
+
+#[tokio::test]
+async fn pass_through_workspace_has_no_input_digest() {
+    let rt = Runtime::with_default_backends();
+    let (_events, receipt) = run_to_completion(&rt, mock_work_order()).await;
+    assert!(receipt.verification.input_digest.is_none());
+}
+
 // ---------- 4. Multiple sequential runs ----------
 
 #[tokio::test]
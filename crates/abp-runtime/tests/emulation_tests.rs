@@ -424,3 +424,114 @@ async fn satisfied_requirement_not_counted_as_emulated() {
         "natively satisfied capability must not trigger emulation"
     );
 }
+
+// ── 17. Emulated structured output is requested and repaired ──
+
+fn structured_work_order() -> WorkOrder {
+    let mut wo = mock_work_order();
+    wo.requirements = CapabilityRequirements {
+        required: vec![CapabilityRequirement {
+            capability: Capability::StructuredOutputJsonSchema,
+            min_support: MinSupport::Native,
+        }],
+    };
+    wo.config.vendor.insert(
+        "response_format".into(),
+        serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "colour",
+                "schema": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}},
+                    "required": ["name"]
+                }
+            }
+        }),
+    );
+    wo
+}
+
+/// Replies with fixed text after checking the schema reached the task.
+struct Replying(&'static str);
+
+#[async_trait::async_trait]
+impl abp_integrations::Backend for Replying {
+    fn identity(&self) -> abp_core::BackendIdentity {
+        abp_core::BackendIdentity {
+            id: "replying".into(),
+            backend_version: None,
+            adapter_version: None,
+        }
+    }
+
+    fn capabilities(&self) -> abp_core::CapabilityManifest {
+        let mut m = abp_core::CapabilityManifest::default();
+        m.insert(Capability::Streaming, abp_core::SupportLevel::Native);
+        m
+    }
+
+    async fn run(
+        &self,
+        _run_id: uuid::Uuid,
+        work_order: WorkOrder,
+        events_tx: tokio::sync::mpsc::Sender<abp_core::AgentEvent>,
+    ) -> anyhow::Result<abp_core::Receipt> {
+        anyhow::ensure!(
+            work_order.task.contains("JSON Schema `colour`"),
+            "schema missing from task"
+        );
+        let _ = events_tx
+            .send(abp_core::AgentEvent {
+                ts: chrono::Utc::now(),
+                kind: abp_core::AgentEventKind::AssistantMessage {
+                    text: self.0.into(),
+                },
+                ext: None,
+            })
+            .await;
+        Ok(abp_receipt::ReceiptBuilder::new("replying").build())
+    }
+}
+
+async fn run_structured(reply: &'static str) -> abp_core::Receipt {
+    let mut rt = Runtime::new().with_emulation(EmulationConfig::new());
+    rt.register_backend("replying", Replying(reply));
+    let handle = rt
+        .run_streaming("replying", structured_work_order())
+        .await
+        .expect("run_streaming");
+    let _: Vec<_> = handle.events.collect().await;
+    handle.receipt.await.expect("join").expect("receipt")
+}
+
+#[tokio::test]
+async fn structured_output_emulation_repairs_reply() {
+    let receipt = run_structured("```json\n{\"name\": \"red\",}\n```").await;
+
+    let emulation = &receipt.usage_raw["emulation"];
+    let output = &emulation["structured_output"];
+    assert_eq!(output["schema"], "colour");
+    assert_eq!(output["valid"], true);
+    assert_eq!(output["value"], serde_json::json!({"name": "red"}));
+    let notes: Vec<String> = serde_json::from_value(emulation["fidelity_notes"].clone()).unwrap();
+    assert!(notes.iter().any(|n| n.contains("Markdown code fence")));
+    assert!(notes.iter().any(|n| n.contains("trailing commas")));
+    assert!(abp_receipt::verify_hash(&receipt));
+}
+
+#[tokio::test]
+async fn structured_output_emulation_notes_invalid_reply() {
+    let receipt = run_structured("Red, probably.").await;
+
+    let emulation = &receipt.usage_raw["emulation"];
+    assert_eq!(emulation["structured_output"]["valid"], false);
+    let notes = emulation["fidelity_notes"].as_array().unwrap();
+    assert!(
+        notes[0]
+            .as_str()
+            .unwrap()
+            .contains("not enforced by the backend")
+    );
+    assert!(notes[1].as_str().unwrap().contains("no JSON value found"));
+}
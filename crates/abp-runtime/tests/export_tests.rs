@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for exporting run events to message brokers.
+
+pub mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use abp_core::{AgentEventKind, Outcome};
+use abp_error::ErrorCode;
+use abp_runtime::Runtime;
+use abp_runtime::export::{
+    EventExporter, EventProducer, ExportBody, ExportConfig, ExportMessage, MemoryProducer,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use common::{ScriptedBackend, work_order};
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Refuses the first `failures` sends, then stores like [`MemoryProducer`].
+struct Flaky {
+    failures: AtomicUsize,
+    inner: MemoryProducer,
+}
+
+#[async_trait]
+impl EventProducer for Flaky {
+    async fn send(&self, topic: &str, key: &str, payload: &[u8]) -> anyhow::Result<()> {
+        if self
+            .failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            anyhow::bail!("broker unavailable");
+        }
+        self.inner.send(topic, key, payload).await
+    }
+}
+
+fn runtime(exporter: EventExporter) -> Runtime {
+    let mut rt = Runtime::new().with_event_export(exporter);
+    rt.register_backend(
+        "chatty",
+        ScriptedBackend::new("chatty")
+            .event(AgentEventKind::AssistantMessage {
+                text: "hello".into(),
+            })
+            .event(AgentEventKind::AssistantMessage {
+                text: "world".into(),
+            })
+            .crash_on_task("fail"),
+    );
+    rt
+}
+
+fn fast_retries() -> ExportConfig {
+    ExportConfig::default().backoff(Duration::from_millis(1), Duration::from_millis(5))
+}
+
+async fn flush(exporter: &EventExporter) {
+    tokio::time::timeout(Duration::from_secs(5), exporter.flush())
+        .await
+        .expect("export did not drain");
+}
+
+fn seqs(messages: &[ExportMessage]) -> Vec<u64> {
+    messages.iter().map(|m| m.seq).collect()
+}
+
+#[tokio::test]
+async fn runs_are_exported_as_lifecycle_and_event_messages() {
+    let producer = MemoryProducer::new();
+    let exporter = EventExporter::new(producer.clone(), ExportConfig::default());
+    let rt = runtime(exporter.clone());
+
+    let wo = work_order("talk");
+    let wo_id = wo.id;
+    let handle = rt.run_streaming("chatty", wo).await.unwrap();
+    let run_id = handle.run_id;
+    let events: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    flush(&exporter).await;
+
+    let lifecycle = producer.messages("abp.runs");
+    let exported = producer.messages("abp.events");
+    assert_eq!(exported.len(), events.len());
+    assert!(exported.len() >= 2);
+    assert_eq!(
+        seqs(&exported),
+        (1..=events.len() as u64).collect::<Vec<_>>()
+    );
+    assert_eq!(seqs(&lifecycle), [0, events.len() as u64 + 1]);
+    assert!(matches!(
+        &lifecycle[0].body,
+        ExportBody::RunStarted { work_order_id, backend } if *work_order_id == wo_id && backend == "chatty"
+    ));
+    match &lifecycle[1].body {
+        ExportBody::RunFinished {
+            outcome,
+            receipt_sha256,
+            error,
+            ..
+        } => {
+            assert_eq!(outcome.as_ref(), Some(&Outcome::Complete));
+            assert_eq!(receipt_sha256, &receipt.receipt_sha256);
+            assert!(error.is_none());
+        }
+        other => panic!("unexpected body: {other:?}"),
+    }
+    let key = run_id.to_string();
+    assert!(producer.keys().iter().all(|(_, k)| *k == key));
+    assert!(
+        lifecycle
+            .iter()
+            .chain(&exported)
+            .all(|m| m.run_id == run_id)
+    );
+}
+
+#[tokio::test]
+async fn failed_runs_and_unread_events_are_still_exported() {
+    let producer = MemoryProducer::new();
+    let exporter = EventExporter::new(producer.clone(), ExportConfig::default());
+    let rt = runtime(exporter.clone());
+
+    let handle = rt
+        .run_streaming("chatty", work_order("fail"))
+        .await
+        .unwrap();
+    drop(handle.events);
+    assert!(handle.receipt.await.unwrap().is_err());
+    flush(&exporter).await;
+
+    assert!(producer.messages("abp.events").len() >= 2);
+    let lifecycle = producer.messages("abp.runs");
+    match &lifecycle.last().unwrap().body {
+        ExportBody::RunFinished {
+            outcome,
+            error,
+            error_code,
+            ..
+        } => {
+            assert!(outcome.is_none());
+            assert!(error.is_some());
+            assert_eq!(*error_code, Some(ErrorCode::BackendCrashed));
+        }
+        other => panic!("unexpected body: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn failed_sends_are_retried_in_order() {
+    let inner = MemoryProducer::new();
+    let exporter = EventExporter::new(
+        Flaky {
+            failures: AtomicUsize::new(3),
+            inner: inner.clone(),
+        },
+        fast_retries().events_topic("agent.events"),
+    );
+    let rt = runtime(exporter.clone());
+
+    let handle = rt
+        .run_streaming("chatty", work_order("talk"))
+        .await
+        .unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    handle.receipt.await.unwrap().unwrap();
+    flush(&exporter).await;
+
+    let stats = exporter.stats();
+    assert_eq!(stats.retried, 3);
+    assert_eq!(stats.dropped, 0);
+    assert_eq!(stats.pending, 0);
+    let mut all = inner.messages("abp.runs");
+    all.extend(inner.messages("agent.events"));
+    all.sort_by_key(|m| m.seq);
+    assert_eq!(stats.published, all.len() as u64);
+    assert_eq!(seqs(&all), (0..all.len() as u64).collect::<Vec<_>>());
+}
+
+#[tokio::test]
+async fn a_full_buffer_drops_the_oldest_messages() {
+    let inner = MemoryProducer::new();
+    let exporter = EventExporter::new(
+        Flaky {
+            failures: AtomicUsize::new(usize::MAX),
+            inner,
+        },
+        fast_retries().buffer(2),
+    );
+    let run_id = Uuid::new_v4();
+    for seq in 0..5 {
+        exporter.publish(ExportMessage {
+            id: Uuid::new_v4(),
+            run_id,
+            seq,
+            ts: Utc::now(),
+            body: ExportBody::RunStarted {
+                work_order_id: Uuid::nil(),
+                backend: "chatty".into(),
+            },
+        });
+    }
+
+    let stats = exporter.stats();
+    assert_eq!(stats.dropped, 3);
+    assert_eq!(stats.pending, 2);
+    assert_eq!(stats.published, 0);
+}
@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the `RuntimeApi` trait and its in-memory fake.
+
+use abp_core::{
+    AgentEvent, AgentEventKind, Outcome, ReceiptBuilder, WorkOrderBuilder, WorkspaceMode,
+};
+use abp_runtime::api::RuntimeApi;
+use abp_runtime::fake::FakeRuntime;
+use abp_runtime::{Runtime, RuntimeError};
+
+/// Application code under test: only depends on the trait.
+async fn ask(rt: &dyn RuntimeApi, question: &str) -> Result<Vec<String>, RuntimeError> {
+    let wo = WorkOrderBuilder::new(question)
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    let backend = rt.backend_names().into_iter().next().expect("a backend");
+    let receipt = rt.run(&backend, wo).await?;
+    Ok(receipt
+        .trace
+        .iter()
+        .filter_map(|e| match &e.kind {
+            AgentEventKind::AssistantMessage { text } => Some(text.clone()),
+            _ => None,
+        })
+        .collect())
+}
+
+fn answer(text: &str) -> abp_core::Receipt {
+    ReceiptBuilder::new("scripted")
+        .outcome(Outcome::Complete)
+        .add_trace_event(AgentEvent {
+            ts: chrono::Utc::now(),
+            kind: AgentEventKind::AssistantMessage { text: text.into() },
+            ext: None,
+        })
+        .build()
+}
+
+#[tokio::test]
+async fn fake_answers_with_scripted_receipts_in_order() {
+    let rt = FakeRuntime::new()
+        .with_receipt("scripted", answer("first"))
+        .with_receipt("scripted", answer("second"));
+
+    assert_eq!(ask(&rt, "q1").await.unwrap(), ["first"]);
+    assert_eq!(ask(&rt, "q2").await.unwrap(), ["second"]);
+    // Script exhausted: an empty, complete receipt.
+    assert!(ask(&rt, "q3").await.unwrap().is_empty());
+
+    let submitted = rt.submitted();
+    assert_eq!(submitted.len(), 3);
+    assert_eq!(submitted[1].1.task, "q2");
+}
+
+#[tokio::test]
+async fn fake_receipts_are_stamped_and_hashed() {
+    let rt = FakeRuntime::new().with_backend("b");
+    let wo = WorkOrderBuilder::new("t").build();
+    let id = wo.id;
+    let receipt = rt.run("b", wo).await.unwrap();
+    assert_eq!(receipt.meta.work_order_id, id);
+    assert_eq!(
+        receipt.receipt_sha256.as_deref(),
+        Some(abp_core::receipt_hash(&receipt).unwrap().as_str())
+    );
+}
+
+#[tokio::test]
+async fn fake_scripted_error_and_unknown_backend() {
+    let rt = FakeRuntime::new().with_error("b", "boom");
+    let err = rt
+        .run("b", WorkOrderBuilder::new("t").build())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RuntimeError::BackendFailed(_)));
+    assert!(err.is_retryable());
+
+    let err = rt
+        .run("missing", WorkOrderBuilder::new("t").build())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RuntimeError::UnknownBackend { .. }));
+    assert_eq!(rt.submitted().len(), 1);
+}
+
+#[tokio::test]
+async fn real_runtime_satisfies_the_same_trait() {
+    let rt = Runtime::with_default_backends();
+    let texts = ask(&rt, "hello").await.unwrap();
+    assert!(!texts.is_empty());
+}
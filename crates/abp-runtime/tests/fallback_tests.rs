@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for projection-aware fallback execution.
+
+pub mod common;
+
+use abp_core::{Capability, CapabilityManifest, Receipt, SupportLevel, WorkOrder};
+use abp_dialect::Dialect;
+use abp_error::{AbpError, ErrorCode};
+use abp_runtime::execution::{FALLBACK_ATTEMPTS_KEY, FallbackAttempt};
+use abp_runtime::{ProjectionMatrix, Runtime, RuntimeError};
+use common::{Behavior, ScriptedBackend};
+
+fn manifest() -> CapabilityManifest {
+    let mut m = CapabilityManifest::new();
+    m.insert(Capability::Streaming, SupportLevel::Native);
+    m
+}
+
+/// Runtime with backends projected in the given order (highest priority first).
+fn runtime(backends: &[(&'static str, Behavior)]) -> (Runtime, Vec<ScriptedBackend>) {
+    let mut matrix = ProjectionMatrix::new();
+    for (i, (id, _)) in backends.iter().enumerate() {
+        matrix.register_backend(*id, manifest(), Dialect::OpenAi, 90 - 10 * i as u32);
+    }
+    let mut rt = Runtime::new().with_projection(matrix);
+    let mut registered = Vec::new();
+    for (id, behavior) in backends {
+        let backend = ScriptedBackend::new(*id)
+            .capabilities(manifest())
+            .behavior(*behavior);
+        rt.register_backend(id, backend.clone());
+        registered.push(backend);
+    }
+    (rt, registered)
+}
+
+fn work_order() -> WorkOrder {
+    common::work_order("fall back")
+}
+
+/// Code of the classified error a backend failed with.
+fn backend_code(err: &RuntimeError) -> ErrorCode {
+    match err {
+        RuntimeError::BackendFailed(e) => e.downcast_ref::<AbpError>().unwrap().code,
+        other => panic!("expected a backend failure, got {other:?}"),
+    }
+}
+
+fn attempts(receipt: &Receipt) -> Vec<FallbackAttempt> {
+    serde_json::from_value(receipt.usage_raw[FALLBACK_ATTEMPTS_KEY].clone()).unwrap()
+}
+
+#[tokio::test]
+async fn first_backend_success_records_no_attempts() {
+    let (rt, backends) = runtime(&[("alpha", Behavior::Succeed), ("beta", Behavior::Succeed)]);
+    let receipt = rt.run_with_fallback(work_order()).await.unwrap();
+    assert_eq!(receipt.backend.id, "alpha");
+    assert!(receipt.usage_raw.get(FALLBACK_ATTEMPTS_KEY).is_none());
+    assert_eq!(backends[1].calls(), 0);
+}
+
+#[tokio::test]
+async fn crash_falls_back_and_records_attempts() {
+    let (rt, _) = runtime(&[("alpha", Behavior::Crash), ("beta", Behavior::Succeed)]);
+    let receipt = rt.run_with_fallback(work_order()).await.unwrap();
+    assert_eq!(receipt.backend.id, "beta");
+    let attempts = attempts(&receipt);
+    assert_eq!(attempts.len(), 2);
+    assert_eq!(attempts[0].backend, "alpha");
+    assert_eq!(attempts[0].error_code, Some(ErrorCode::BackendCrashed));
+    assert_eq!(attempts[1], FallbackAttempt::succeeded("beta"));
+    assert!(abp_receipt::verify_hash(&receipt));
+}
+
+#[tokio::test]
+async fn timeout_falls_back_through_whole_chain() {
+    let (rt, _) = runtime(&[
+        ("alpha", Behavior::Fail(ErrorCode::BackendTimeout)),
+        ("beta", Behavior::Crash),
+        ("gamma", Behavior::Succeed),
+    ]);
+    let receipt = rt.run_with_fallback(work_order()).await.unwrap();
+    assert_eq!(receipt.backend.id, "gamma");
+    let backends: Vec<_> = attempts(&receipt)
+        .into_iter()
+        .map(|a| (a.backend, a.error_code))
+        .collect();
+    assert_eq!(
+        backends,
+        vec![
+            ("alpha".to_string(), Some(ErrorCode::BackendTimeout)),
+            ("beta".to_string(), Some(ErrorCode::BackendCrashed)),
+            ("gamma".to_string(), None),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn other_errors_do_not_fall_back() {
+    let (rt, backends) = runtime(&[
+        ("alpha", Behavior::Fail(ErrorCode::BackendRateLimited)),
+        ("beta", Behavior::Succeed),
+    ]);
+    let err = rt.run_with_fallback(work_order()).await.unwrap_err();
+    assert_eq!(backend_code(&err), ErrorCode::BackendRateLimited);
+    assert_eq!(backends[1].calls(), 0);
+}
+
+#[tokio::test]
+async fn exhausted_chain_returns_last_error() {
+    let (rt, backends) = runtime(&[
+        ("alpha", Behavior::Crash),
+        ("beta", Behavior::Fail(ErrorCode::BackendTimeout)),
+    ]);
+    let err = rt.run_with_fallback(work_order()).await.unwrap_err();
+    assert_eq!(backend_code(&err), ErrorCode::BackendTimeout);
+    assert!(backends.iter().all(|b| b.calls() == 1));
+}
+
+#[tokio::test]
+async fn unregistered_fallback_entries_are_skipped() {
+    let (mut rt, _) = runtime(&[("alpha", Behavior::Crash), ("gamma", Behavior::Succeed)]);
+    rt.projection_mut()
+        .unwrap()
+        .register_backend("ghost", manifest(), Dialect::OpenAi, 85);
+    let receipt = rt.run_with_fallback(work_order()).await.unwrap();
+    assert_eq!(receipt.backend.id, "gamma");
+    assert_eq!(attempts(&receipt).len(), 2);
+}
+
+#[tokio::test]
+async fn requires_projection_matrix() {
+    let rt = Runtime::with_default_backends();
+    let err = rt.run_with_fallback(work_order()).await.unwrap_err();
+    assert!(matches!(err, RuntimeError::NoProjectionMatch { .. }));
+}
@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the work-order fidelity policy (strict / warn / permissive).
+
+use abp_core::{
+    Capability, CapabilityRequirement, CapabilityRequirements, MinSupport, Outcome, WorkOrder,
+    WorkOrderBuilder, WorkspaceMode,
+};
+use abp_error::ErrorCode;
+use abp_runtime::{Runtime, RuntimeError};
+use tokio_stream::StreamExt;
+
+fn work_order(policy: Option<&str>, caps: &[Capability]) -> WorkOrder {
+    let mut wo = WorkOrderBuilder::new("fidelity test")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .requirements(CapabilityRequirements {
+            required: caps
+                .iter()
+                .map(|c| CapabilityRequirement {
+                    capability: c.clone(),
+                    min_support: MinSupport::Emulated,
+                })
+                .collect(),
+        })
+        .build();
+    if let Some(p) = policy {
+        wo.config
+            .vendor
+            .insert("abp".into(), serde_json::json!({ "fidelity_policy": p }));
+    }
+    wo
+}
+
+#[tokio::test]
+async fn strict_policy_rejects_emulated_capability_up_front() {
+    let rt = Runtime::with_default_backends();
+    // The mock backend only emulates ToolEdit.
+    let wo = work_order(Some("strict"), &[Capability::ToolEdit]);
+
+    let err = match rt.run_streaming("mock", wo).await {
+        Err(e) => e,
+        Ok(_) => panic!("strict policy must reject emulated capabilities"),
+    };
+    assert_eq!(err.error_code(), ErrorCode::MappingLossyConversion);
+    assert!(!err.is_retryable());
+    let RuntimeError::Classified(abp) = err else {
+        panic!("expected classified error");
+    };
+    let report = &abp.context["fidelity_report"];
+    assert_eq!(report["policy"], "strict");
+    assert_eq!(report["issues"][0]["subject"], "ToolEdit");
+}
+
+#[tokio::test]
+async fn unrecognised_policy_rejects_the_run() {
+    let rt = Runtime::with_default_backends();
+    let wo = work_order(Some("strcit"), &[Capability::ToolEdit]);
+
+    let err = match rt.run_streaming("mock", wo).await {
+        Err(e) => e,
+        Ok(_) => panic!("a misspelt policy must not fall back to warn"),
+    };
+    assert_eq!(err.error_code(), ErrorCode::ConfigInvalid);
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn strict_policy_allows_native_only_runs() {
+    let rt = Runtime::with_default_backends();
+    let wo = work_order(Some("strict"), &[Capability::Streaming]);
+
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    assert_eq!(receipt.outcome, Outcome::Complete);
+    assert!(receipt.usage_raw.get("fidelity").is_none());
+}
+
+#[tokio::test]
+async fn warn_policy_runs_and_records_report() {
+    let rt = Runtime::with_default_backends();
+    let wo = work_order(None, &[Capability::ToolEdit]);
+
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    assert_eq!(receipt.outcome, Outcome::Complete);
+    let fidelity = receipt.usage_raw.get("fidelity").unwrap();
+    assert_eq!(fidelity["policy"], "warn");
+    assert_eq!(fidelity["issues"][0]["kind"], "emulated_capability");
+}
+
+#[tokio::test]
+async fn permissive_policy_runs_and_records_report() {
+    let rt = Runtime::with_default_backends();
+    let wo = work_order(Some("permissive"), &[Capability::ToolEdit]);
+
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    assert_eq!(receipt.usage_raw["fidelity"]["policy"], "permissive");
+}
@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for runtime feature flags and their receipt record.
+
+use abp_core::compact::CompactionRecord;
+use abp_core::{Receipt, WorkOrder, WorkOrderBuilder, WorkspaceMode};
+use abp_runtime::Runtime;
+use abp_runtime::config_integration::RuntimeConfig;
+use abp_runtime::flags::{FEATURE_FLAGS_KEY, FeatureFlags, ResolvedFlags, TRACE_COMPACTION};
+use tokio_stream::StreamExt;
+
+fn builder() -> WorkOrderBuilder {
+    WorkOrderBuilder::new("flags").workspace_mode(WorkspaceMode::PassThrough)
+}
+
+async fn run(rt: &Runtime, wo: WorkOrder) -> Receipt {
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    handle.receipt.await.unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn enabled_compaction_flag_compacts_and_is_recorded() {
+    let config = RuntimeConfig::builder()
+        .feature(TRACE_COMPACTION, true)
+        .build();
+    let rt =
+        Runtime::with_default_backends().with_feature_flags(FeatureFlags::from_config(&config));
+    let receipt = run(&rt, builder().build()).await;
+
+    let flags = ResolvedFlags::from_receipt(&receipt);
+    assert!(flags.is_enabled(TRACE_COMPACTION));
+    let record = CompactionRecord::from_receipt(&receipt).unwrap();
+    assert!(record.original_sha256.is_some());
+    assert_ne!(record.original_sha256, receipt.receipt_sha256);
+    assert_eq!(
+        receipt.receipt_sha256.as_deref(),
+        Some(abp_core::receipt_hash(&receipt).unwrap().as_str())
+    );
+}
+
+#[tokio::test]
+async fn work_order_can_switch_a_declared_flag_off() {
+    let rt = Runtime::with_default_backends()
+        .with_feature_flags(FeatureFlags::new().declare(TRACE_COMPACTION, true));
+    let receipt = run(&rt, builder().feature(TRACE_COMPACTION, false).build()).await;
+
+    assert!(!ResolvedFlags::from_receipt(&receipt).is_enabled(TRACE_COMPACTION));
+    assert_eq!(
+        receipt.usage_raw[FEATURE_FLAGS_KEY][TRACE_COMPACTION],
+        false
+    );
+    assert!(CompactionRecord::from_receipt(&receipt).is_none());
+}
+
+#[tokio::test]
+async fn undeclared_flag_cannot_be_enabled_by_work_order() {
+    let rt = Runtime::with_default_backends();
+    let receipt = run(&rt, builder().feature(TRACE_COMPACTION, true).build()).await;
+
+    assert!(receipt.usage_raw.get(FEATURE_FLAGS_KEY).is_none());
+    assert!(CompactionRecord::from_receipt(&receipt).is_none());
+}
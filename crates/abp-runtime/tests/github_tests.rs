@@ -0,0 +1,155 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for reporting run outcomes to GitHub.
+
+use abp_core::{AgentEvent, AgentEventKind, Outcome, Receipt, VerificationReport};
+use abp_receipt::ReceiptBuilder;
+use abp_runtime::github::{
+    GitHubReportKind, GitHubReporter, check_run_payload, check_text, status_payload,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+const SHA: &str = "6dcb09b5b57875f334f61aebed695e2e4193db5e";
+
+fn receipt(outcome: Outcome, diff: Option<&str>) -> Receipt {
+    ReceiptBuilder::new("mock")
+        .outcome(outcome)
+        .usage_tokens(1200, 300)
+        .verification(VerificationReport {
+            git_diff: diff.map(str::to_string),
+            ..VerificationReport::default()
+        })
+        .add_trace_event(AgentEvent {
+            ts: chrono::Utc::now(),
+            kind: AgentEventKind::ToolCall {
+                tool_name: "edit".into(),
+                tool_use_id: Some("t1".into()),
+                parent_tool_use_id: None,
+                input: serde_json::json!({}),
+            },
+            ext: None,
+        })
+        .with_hash()
+        .unwrap()
+}
+
+#[test]
+fn commit_status_maps_outcome_and_fits_the_description_limit() {
+    let body = status_payload(&receipt(Outcome::Complete, None), "abp", Some("https://x"));
+    assert_eq!(body["state"], "success");
+    assert_eq!(body["context"], "abp");
+    assert_eq!(body["target_url"], "https://x");
+    assert!(
+        body["description"]
+            .as_str()
+            .unwrap()
+            .contains("1200 tokens in / 300 out")
+    );
+
+    let mut long = receipt(Outcome::Failed, None);
+    long.backend.id = "b".repeat(300);
+    let body = status_payload(&long, "abp", None);
+    assert_eq!(body["state"], "failure");
+    assert_eq!(body["description"].as_str().unwrap().chars().count(), 140);
+    assert!(body.get("target_url").is_none());
+}
+
+#[test]
+fn check_run_carries_summary_and_diff() {
+    let diff = "diff --git a/x b/x\n+hello\n";
+    let r = receipt(Outcome::Partial, Some(diff));
+    let body = check_run_payload(&r, "abp", SHA, None);
+    assert_eq!(body["head_sha"], SHA);
+    assert_eq!(body["status"], "completed");
+    assert_eq!(body["conclusion"], "neutral");
+    assert_eq!(body["external_id"], r.meta.run_id.to_string());
+
+    let summary = body["output"]["summary"].as_str().unwrap();
+    assert!(summary.contains("| Outcome | partial |"));
+    assert!(summary.contains("| Tool calls | 1 |"));
+    assert!(summary.contains(r.receipt_sha256.as_deref().unwrap()));
+    let text = body["output"]["text"].as_str().unwrap();
+    assert!(text.starts_with("```diff\ndiff --git"));
+    assert!(text.ends_with("+hello\n```"));
+
+    let without = check_run_payload(&receipt(Outcome::Complete, None), "abp", SHA, None);
+    assert!(without["output"].get("text").is_none());
+}
+
+#[test]
+fn oversized_diffs_are_truncated() {
+    let diff = "+x\n".repeat(40_000);
+    let text = check_text(&receipt(Outcome::Complete, Some(&diff))).unwrap();
+    assert!(text.chars().count() <= 65_535);
+    assert!(text.contains("diff truncated"));
+}
+
+/// Accept one HTTP request, answer with `status`, and return the raw request.
+async fn one_shot_server(status: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+            let text = String::from_utf8_lossy(&buf);
+            if let Some(header_end) = text.find("\r\n\r\n") {
+                let len = text[..header_end]
+                    .lines()
+                    .find_map(|l| {
+                        l.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if buf.len() >= header_end + 4 + len || n == 0 {
+                    break;
+                }
+            }
+        }
+        let reply = format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+        socket.write_all(reply.as_bytes()).await.unwrap();
+        String::from_utf8(buf).unwrap()
+    });
+    (format!("http://{addr}"), handle)
+}
+
+#[tokio::test]
+async fn reporter_posts_commit_statuses() {
+    let (api, server) = one_shot_server("201 Created").await;
+    let reporter = GitHubReporter::new("octo/widgets", "secret").with_api_base(format!("{api}/"));
+    assert_eq!(
+        reporter.report_url(SHA),
+        format!("{api}/repos/octo/widgets/statuses/{SHA}")
+    );
+
+    reporter
+        .report(&receipt(Outcome::Complete, None), SHA)
+        .await
+        .unwrap();
+    let request = server.await.unwrap();
+    assert!(request.starts_with(&format!("POST /repos/octo/widgets/statuses/{SHA} HTTP/1.1")));
+    assert!(request.contains("authorization: Bearer secret"));
+    assert!(request.contains("x-github-api-version: 2022-11-28"));
+    assert!(request.contains("\"state\":\"success\""));
+}
+
+#[tokio::test]
+async fn reporter_posts_check_runs_and_surfaces_errors() {
+    let (api, server) = one_shot_server("403 Forbidden").await;
+    let reporter = GitHubReporter::new("octo/widgets", "secret")
+        .with_api_base(api)
+        .with_kind(GitHubReportKind::CheckRun)
+        .with_name("agents");
+
+    let err = reporter
+        .report(&receipt(Outcome::Complete, Some("+x\n")), SHA)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("403"));
+    let request = server.await.unwrap();
+    assert!(request.starts_with("POST /repos/octo/widgets/check-runs HTTP/1.1"));
+    assert!(request.contains("\"name\":\"agents\""));
+}
@@ -0,0 +1,123 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for typed run-handle callbacks.
+
+pub mod common;
+
+use std::collections::BTreeMap;
+
+use abp_core::{AgentEvent, AgentEventKind, Outcome, WorkOrder};
+use abp_runtime::Runtime;
+use abp_runtime::handlers::RunHandlers;
+use common::{ScriptedBackend, event};
+use serde_json::json;
+
+fn thinking(text: &str) -> AgentEvent {
+    let mut ext = BTreeMap::new();
+    ext.insert("thinking".to_string(), json!(true));
+    AgentEvent {
+        ext: Some(ext),
+        ..event(AgentEventKind::AssistantMessage { text: text.into() })
+    }
+}
+
+/// A runtime whose backend replays `events`, keeping them in the receipt.
+fn runtime(events: Vec<AgentEvent>) -> Runtime {
+    let mut rt = Runtime::new();
+    rt.register_backend(
+        "scripted",
+        ScriptedBackend::new("scripted").events(events).with_trace(),
+    );
+    rt
+}
+
+fn work_order() -> WorkOrder {
+    common::work_order("read a file")
+}
+
+#[tokio::test]
+async fn callbacks_receive_typed_events() {
+    let rt = runtime(vec![
+        event(AgentEventKind::RunStarted {
+            message: "go".into(),
+        }),
+        thinking("plan"),
+        event(AgentEventKind::AssistantDelta {
+            text: "Let me ".into(),
+        }),
+        event(AgentEventKind::AssistantDelta {
+            text: "look.".into(),
+        }),
+        event(AgentEventKind::ToolCall {
+            tool_name: "read_file".into(),
+            tool_use_id: Some("tu_1".into()),
+            parent_tool_use_id: None,
+            input: json!({"path": "a.rs"}),
+        }),
+        event(AgentEventKind::ToolResult {
+            tool_name: "read_file".into(),
+            tool_use_id: Some("tu_1".into()),
+            output: json!("fn main() {}"),
+            is_error: false,
+        }),
+        event(AgentEventKind::Error {
+            message: "boom".into(),
+            error_code: None,
+        }),
+        event(AgentEventKind::RunCompleted {
+            message: "done".into(),
+        }),
+    ]);
+    let handle = rt.run_streaming("scripted", work_order()).await.unwrap();
+
+    let mut text = String::new();
+    let mut thoughts = Vec::new();
+    let mut calls = Vec::new();
+    let mut results = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen = 0;
+    let mut completed = None;
+    let receipt = RunHandlers::new()
+        .on_text(|t| text.push_str(t))
+        .on_thinking(|t| thoughts.push(t.to_string()))
+        .on_tool_call(|name, id, input| {
+            calls.push((name.to_string(), id.map(String::from), input.clone()));
+        })
+        .on_tool_result(|name, output, is_error| {
+            results.push((name.to_string(), output.clone(), is_error));
+        })
+        .on_error(|m| errors.push(m.to_string()))
+        .on_event(|_| seen += 1)
+        .on_complete(|r| completed = Some(r.meta.run_id))
+        .run(handle)
+        .await
+        .unwrap();
+
+    assert_eq!(text, "Let me look.");
+    assert_eq!(thoughts, ["plan"]);
+    assert_eq!(
+        calls,
+        [(
+            "read_file".to_string(),
+            Some("tu_1".to_string()),
+            json!({"path": "a.rs"})
+        )]
+    );
+    assert_eq!(
+        results,
+        [("read_file".to_string(), json!("fn main() {}"), false)]
+    );
+    assert_eq!(errors, ["boom"]);
+    assert_eq!(seen, 8);
+    assert_eq!(completed, Some(receipt.meta.run_id));
+}
+
+#[tokio::test]
+async fn unset_callbacks_still_return_receipt() {
+    let rt = runtime(vec![event(AgentEventKind::AssistantMessage {
+        text: "hi".into(),
+    })]);
+    let handle = rt.run_streaming("scripted", work_order()).await.unwrap();
+    let receipt = RunHandlers::new().run(handle).await.unwrap();
+    assert_eq!(receipt.outcome, Outcome::Complete);
+    assert_eq!(receipt.trace.len(), 1);
+}
@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Judge-backend scoring of completed runs.
+
+pub mod common;
+
+use abp_core::{AgentEventKind, Receipt, WorkOrder, WorkOrderBuilder, WorkspaceMode};
+use abp_integrations::MockBackend;
+use abp_runtime::Runtime;
+use abp_runtime::judge::{JudgeConfig, JudgeRecord, Rubric};
+use common::ScriptedBackend;
+use tokio_stream::StreamExt;
+
+/// A judge backend answering every work order with `answer`.
+fn judge(answer: &str) -> ScriptedBackend {
+    ScriptedBackend::new("judge").event(AgentEventKind::AssistantMessage {
+        text: answer.into(),
+    })
+}
+
+fn order() -> WorkOrder {
+    WorkOrderBuilder::new("Explain the borrow checker")
+        .root(".")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build()
+}
+
+async fn run(rt: Runtime) -> Receipt {
+    let handle = rt.run_streaming("mock", order()).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    handle.receipt.await.unwrap().unwrap()
+}
+
+fn runtime(judge: &ScriptedBackend, config: JudgeConfig) -> Runtime {
+    let mut rt = Runtime::new().with_judge(config);
+    rt.register_backend("mock", MockBackend);
+    rt.register_backend("judge", judge.clone());
+    rt
+}
+
+#[tokio::test]
+async fn completed_runs_are_scored_before_hashing() {
+    let judge = judge(
+        r#"{"scores": {"correctness": 5, "completeness": 4, "clarity": 3}, "rationale": "Solid."}"#,
+    );
+    let receipt = run(runtime(&judge, JudgeConfig::new("judge"))).await;
+
+    let prompt = judge.work_orders().pop().unwrap().task;
+    assert!(prompt.contains("Explain the borrow checker"), "{prompt}");
+    assert!(prompt.contains("completeness"), "{prompt}");
+
+    let record = JudgeRecord::from_receipt(&receipt).unwrap();
+    let verdict = record.verdict().unwrap();
+    assert_eq!(verdict.scores["correctness"], 5.0);
+    assert!((verdict.overall - 0.8).abs() < 1e-9);
+    assert_eq!(verdict.rationale.as_deref(), Some("Solid."));
+    assert!(abp_receipt::verify_hash(&receipt));
+}
+
+#[tokio::test]
+async fn unusable_answers_are_recorded_as_failures() {
+    let judge = judge("I'd give it a B+.");
+    let rubric = Rubric::new(10).criterion("helpfulness", "Is it helpful?");
+    let receipt = run(runtime(&judge, JudgeConfig::new("judge").rubric(rubric))).await;
+
+    match JudgeRecord::from_receipt(&receipt).unwrap() {
+        JudgeRecord::Failed { backend, error } => {
+            assert_eq!(backend, "judge");
+            assert!(error.contains("no JSON"), "{error}");
+        }
+        other => panic!("expected a failure, got {other:?}"),
+    }
+
+    let mut rt = Runtime::new().with_judge(JudgeConfig::new("absent"));
+    rt.register_backend("mock", MockBackend);
+    let receipt = run(rt).await;
+    assert!(matches!(
+        JudgeRecord::from_receipt(&receipt),
+        Some(JudgeRecord::Failed { .. })
+    ));
+}
+
+#[tokio::test]
+async fn runs_outside_the_sample_are_not_judged() {
+    let judge = judge("{}");
+    let receipt = run(runtime(&judge, JudgeConfig::new("judge").sample_rate(0.0))).await;
+
+    assert_eq!(judge.calls(), 0);
+    assert!(receipt.usage_raw.get("judge").is_none());
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for capability degradation ladders in the runtime pre-flight.
+
+use abp_core::{
+    Capability, CapabilityLadder, MinSupport, SupportLevel, WorkOrder, WorkOrderBuilder,
+    WorkspaceMode,
+};
+use abp_runtime::ladder::{FALLBACKS_KEY, RungSelection};
+use abp_runtime::{Runtime, RuntimeError};
+use tokio_stream::StreamExt;
+
+fn structured_output() -> CapabilityLadder {
+    CapabilityLadder::new()
+        .rung(Capability::StructuredOutputJsonSchema, MinSupport::Native)
+        .rung(Capability::JsonMode, MinSupport::Native)
+        .rung(Capability::StructuredOutputJsonSchema, MinSupport::Emulated)
+}
+
+fn work_order(ladder: CapabilityLadder) -> WorkOrder {
+    WorkOrderBuilder::new("ladder")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .capability_ladder(ladder)
+        .build()
+}
+
+#[tokio::test]
+async fn mock_backend_settles_on_emulated_rung() {
+    let rt = Runtime::with_default_backends();
+    let handle = rt
+        .run_streaming("mock", work_order(structured_output()))
+        .await
+        .unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+
+    let selections = RungSelection::from_receipt(&receipt);
+    assert_eq!(selections.len(), 1);
+    assert_eq!(selections[0].rung, 2);
+    assert!(selections[0].is_degraded());
+    assert_eq!(
+        selections[0].capability,
+        Capability::StructuredOutputJsonSchema
+    );
+    assert_eq!(selections[0].support, SupportLevel::Emulated);
+}
+
+#[tokio::test]
+async fn unsatisfiable_ladder_fails_preflight() {
+    let rt = Runtime::with_default_backends();
+    let ladder = CapabilityLadder::new().rung(Capability::JsonMode, MinSupport::Native);
+    let err = rt
+        .run_streaming("mock", work_order(ladder))
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(err, RuntimeError::CapabilityCheckFailed(_)));
+}
+
+#[tokio::test]
+async fn no_ladders_leaves_receipt_untouched() {
+    let rt = Runtime::with_default_backends();
+    let wo = WorkOrderBuilder::new("plain")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    assert!(receipt.usage_raw.get(FALLBACKS_KEY).is_none());
+}
@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for dynamically advertised backend models.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use abp_core::{
+    AgentEvent, BackendIdentity, Capability, CapabilityManifest, CapabilityRequirement,
+    CapabilityRequirements, MinSupport, Receipt, SupportLevel, WorkOrder, WorkOrderBuilder,
+    WorkspaceMode,
+};
+use abp_error::ErrorCode;
+use abp_integrations::{Backend, MockBackend, ModelInfo};
+use abp_runtime::{Runtime, RuntimeError};
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Mock backend whose model list can change, like a local Ollama.
+#[derive(Clone, Default)]
+struct PulledModels {
+    models: Arc<Mutex<Option<Vec<ModelInfo>>>>,
+}
+
+impl PulledModels {
+    fn set(&self, models: Option<Vec<ModelInfo>>) {
+        *self.models.lock().unwrap() = models;
+    }
+}
+
+#[async_trait]
+impl Backend for PulledModels {
+    fn identity(&self) -> BackendIdentity {
+        MockBackend.identity()
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        MockBackend.capabilities()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        mut work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        // The runtime already negotiated against the model's manifest; the
+        // mock would re-check against its static one.
+        work_order.requirements = CapabilityRequirements::default();
+        MockBackend.run(run_id, work_order, events_tx).await
+    }
+
+    async fn list_models(&self) -> anyhow::Result<Vec<ModelInfo>> {
+        self.models
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("daemon unreachable"))
+    }
+}
+
+fn runtime(backend: &PulledModels) -> Runtime {
+    let mut rt = Runtime::with_default_backends();
+    rt.register_backend("ollama", backend.clone());
+    rt
+}
+
+fn work_order(model: &str) -> WorkOrder {
+    WorkOrderBuilder::new("models")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .model(model)
+        .build()
+}
+
+async fn run(rt: &Runtime, backend: &str, wo: WorkOrder) -> Result<Receipt, RuntimeError> {
+    let handle = rt.run_streaming(backend, wo).await?;
+    let _: Vec<_> = handle.events.collect().await;
+    handle.receipt.await.unwrap()
+}
+
+#[tokio::test]
+async fn unlisted_model_is_rejected_after_refresh() {
+    let backend = PulledModels::default();
+    backend.set(Some(vec![ModelInfo::new("llama3.1:8b")]));
+    let rt = runtime(&backend);
+
+    // Nothing advertised yet: no restriction.
+    run(&rt, "ollama", work_order("mistral")).await.unwrap();
+
+    rt.refresh_models().await;
+    let err = run(&rt, "ollama", work_order("mistral")).await.unwrap_err();
+    assert_eq!(err.error_code(), ErrorCode::BackendModelNotFound);
+    run(&rt, "ollama", work_order("llama3.1:8b")).await.unwrap();
+
+    // The mock backend advertises nothing and stays unrestricted.
+    run(&rt, "mock", work_order("mistral")).await.unwrap();
+    assert_eq!(rt.backends_serving("llama3.1:8b"), ["ollama"]);
+}
+
+#[tokio::test]
+async fn advertised_model_capabilities_satisfy_requirements() {
+    let backend = PulledModels::default();
+    backend.set(Some(vec![
+        ModelInfo::new("llava").capability(Capability::Vision, SupportLevel::Native),
+        ModelInfo::new("llama3.1:8b"),
+    ]));
+    let rt = runtime(&backend);
+    rt.refresh_models().await;
+
+    let needs_vision = |model: &str| {
+        let mut wo = work_order(model);
+        wo.requirements = CapabilityRequirements {
+            required: vec![CapabilityRequirement {
+                capability: Capability::Vision,
+                min_support: MinSupport::Native,
+            }],
+        };
+        wo
+    };
+    run(&rt, "ollama", needs_vision("llava")).await.unwrap();
+    let err = run(&rt, "ollama", needs_vision("llama3.1:8b"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RuntimeError::CapabilityCheckFailed(_)));
+}
+
+#[tokio::test]
+async fn failed_listing_keeps_the_previous_one() {
+    let backend = PulledModels::default();
+    backend.set(Some(vec![ModelInfo::new("llama3.1:8b")]));
+    let rt = runtime(&backend);
+    rt.refresh_models().await;
+
+    backend.set(None);
+    rt.refresh_models().await;
+    let listing = rt.models().listing("ollama").unwrap();
+    assert_eq!(listing.models, [ModelInfo::new("llama3.1:8b")]);
+}
+
+#[tokio::test]
+async fn background_refresh_picks_up_newly_pulled_models() {
+    let backend = PulledModels::default();
+    backend.set(Some(vec![ModelInfo::new("llama3.1:8b")]));
+    let rt = Arc::new(runtime(&backend));
+    let task = rt.spawn_model_refresh(Duration::from_millis(10));
+
+    backend.set(Some(vec![
+        ModelInfo::new("llama3.1:8b"),
+        ModelInfo::new("qwen2.5-coder"),
+    ]));
+    let mut served = Vec::new();
+    for _ in 0..200 {
+        served = rt.backends_serving("qwen2.5-coder");
+        if !served.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert_eq!(served, ["ollama"]);
+
+    drop(rt);
+    tokio::time::timeout(Duration::from_secs(5), task)
+        .await
+        .expect("refresh task stops with the runtime")
+        .unwrap();
+}
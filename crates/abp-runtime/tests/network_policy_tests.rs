@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for network egress rules enforced on backend tool calls.
+
+use abp_core::{
+    AgentEvent, AgentEventKind, BackendIdentity, CapabilityManifest, Outcome, PolicyProfile,
+    Receipt, WorkOrder, WorkOrderBuilder, WorkspaceMode,
+};
+use abp_error::ErrorCode;
+use abp_integrations::{Backend, MockBackend};
+use abp_runtime::{Runtime, RuntimeError};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Calls one tool with `input`, then finishes like the mock backend.
+struct Fetcher {
+    input: Value,
+}
+
+#[async_trait]
+impl Backend for Fetcher {
+    fn identity(&self) -> BackendIdentity {
+        MockBackend.identity()
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        MockBackend.capabilities()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        let call = AgentEvent {
+            ts: chrono::Utc::now(),
+            kind: AgentEventKind::ToolCall {
+                tool_name: "WebFetch".into(),
+                tool_use_id: Some("tu-1".into()),
+                parent_tool_use_id: None,
+                input: self.input.clone(),
+            },
+            ext: None,
+        };
+        let _ = events_tx.send(call).await;
+        MockBackend.run(run_id, work_order, events_tx).await
+    }
+}
+
+async fn run(input: Value, policy: PolicyProfile) -> Result<Receipt, RuntimeError> {
+    let mut rt = Runtime::new();
+    rt.register_backend("fetcher", Fetcher { input });
+    let mut wo = WorkOrderBuilder::new("fetch")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    wo.policy = policy;
+    let handle = rt.run_streaming("fetcher", wo).await?;
+    let _: Vec<_> = handle.events.collect().await;
+    handle.receipt.await.unwrap()
+}
+
+fn policy(allow: &[&str], deny: &[&str]) -> PolicyProfile {
+    PolicyProfile {
+        allow_network: allow.iter().map(|s| (*s).to_string()).collect(),
+        deny_network: deny.iter().map(|s| (*s).to_string()).collect(),
+        ..PolicyProfile::default()
+    }
+}
+
+#[tokio::test]
+async fn denied_host_fails_the_run_with_policy_denied() {
+    let err = run(
+        json!({"url": "https://metadata.internal/latest"}),
+        policy(&[], &["*.internal"]),
+    )
+    .await
+    .unwrap_err();
+
+    let RuntimeError::Classified(err) = err else {
+        panic!("expected a classified error, got {err:?}");
+    };
+    assert_eq!(err.code, ErrorCode::PolicyDenied);
+    assert_eq!(err.context["host"], "metadata.internal");
+    assert_eq!(err.context["port"], 443);
+    assert_eq!(err.context["tool"], "WebFetch");
+    assert!(err.message.contains("*.internal"), "{}", err.message);
+}
+
+#[tokio::test]
+async fn port_outside_the_allow_list_is_denied() {
+    let err = run(
+        json!({"host": "db.example.com", "port": 5432}),
+        policy(&["*.example.com:443"], &[]),
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(err.error_code(), ErrorCode::PolicyDenied);
+}
+
+#[tokio::test]
+async fn allowed_hosts_and_rule_free_policies_run_to_completion() {
+    let receipt = run(
+        json!({"url": "https://api.example.com/v1"}),
+        policy(&["*.example.com:443"], &["admin.example.com"]),
+    )
+    .await
+    .unwrap();
+    assert_eq!(receipt.outcome, Outcome::Complete);
+
+    run(json!({"url": "http://anything"}), PolicyProfile::default())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn the_no_network_preset_denies_every_host() {
+    let mut rt = Runtime::new();
+    rt.register_backend(
+        "fetcher",
+        Fetcher {
+            input: json!({"url": "https://api.example.com/v1"}),
+        },
+    );
+    let mut wo = WorkOrderBuilder::new("fetch")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    wo.config
+        .vendor
+        .insert("abp".into(), json!({"policy_presets": "no_network"}));
+    let handle = rt.run_streaming("fetcher", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let err = handle.receipt.await.unwrap().unwrap_err();
+    assert_eq!(err.error_code(), ErrorCode::PolicyDenied);
+
+    let mut wo = WorkOrderBuilder::new("fetch").build();
+    wo.config
+        .vendor
+        .insert("abp".into(), json!({"policy_presets": ["no_net"]}));
+    let Err(err) = rt.run_streaming("fetcher", wo).await else {
+        panic!("an unknown preset should be refused");
+    };
+    assert_eq!(err.error_code(), ErrorCode::PolicyInvalid);
+}
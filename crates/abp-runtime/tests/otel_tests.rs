@@ -0,0 +1,251 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for OpenTelemetry export of receipts.
+
+use abp_core::{AgentEvent, AgentEventKind, Outcome, Receipt};
+use abp_receipt::ReceiptBuilder;
+use abp_runtime::otel::{
+    OtelSpanKind, OtelStatusCode, OtlpExporter, export_request, receipt_spans,
+};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use uuid::Uuid;
+
+fn t(secs: i64) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 14, 9, 0, 0).unwrap() + Duration::seconds(secs)
+}
+
+fn ev(secs: i64, kind: AgentEventKind) -> AgentEvent {
+    AgentEvent {
+        ts: t(secs),
+        kind,
+        ext: None,
+    }
+}
+
+fn tool_call(secs: i64, name: &str, id: &str, parent: Option<&str>) -> AgentEvent {
+    ev(
+        secs,
+        AgentEventKind::ToolCall {
+            tool_name: name.into(),
+            tool_use_id: Some(id.into()),
+            parent_tool_use_id: parent.map(Into::into),
+            input: serde_json::json!({}),
+        },
+    )
+}
+
+fn tool_result(secs: i64, name: &str, id: &str, is_error: bool) -> AgentEvent {
+    ev(
+        secs,
+        AgentEventKind::ToolResult {
+            tool_name: name.into(),
+            tool_use_id: Some(id.into()),
+            output: serde_json::json!("ok"),
+            is_error,
+        },
+    )
+}
+
+fn receipt(outcome: Outcome, trace: Vec<AgentEvent>) -> Receipt {
+    let mut builder = ReceiptBuilder::new("mock")
+        .run_id(Uuid::from_u128(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef))
+        .started_at(t(0))
+        .finished_at(t(10))
+        .outcome(outcome)
+        .usage_tokens(100, 20);
+    for event in trace {
+        builder = builder.add_trace_event(event);
+    }
+    builder.build()
+}
+
+#[test]
+fn run_becomes_root_span_with_tool_and_message_children() {
+    let r = receipt(
+        Outcome::Complete,
+        vec![
+            tool_call(1, "read_file", "t1", None),
+            tool_result(3, "read_file", "t1", false),
+            ev(4, AgentEventKind::AssistantDelta { text: "Do".into() }),
+            ev(
+                5,
+                AgentEventKind::AssistantMessage {
+                    text: "Done".into(),
+                },
+            ),
+        ],
+    );
+    let spans = receipt_spans(&r);
+    assert_eq!(spans.len(), 3);
+
+    let root = &spans[0];
+    assert_eq!(root.name, "abp.run");
+    assert_eq!(root.trace_id, "0123456789abcdef0123456789abcdef");
+    assert_eq!(root.parent_span_id, None);
+    assert_eq!((root.start, root.end), (t(0), t(10)));
+    assert_eq!(root.status, OtelStatusCode::Ok);
+    assert_eq!(root.attributes["abp.backend.id"], "mock");
+    assert_eq!(root.attributes["abp.usage.input_tokens"], 100);
+
+    let tool = &spans[1];
+    assert_eq!(tool.name, "tool_call read_file");
+    assert_eq!(tool.kind, OtelSpanKind::Client);
+    assert_eq!(tool.parent_span_id.as_deref(), Some(root.span_id.as_str()));
+    assert_eq!((tool.start, tool.end), (t(1), t(3)));
+    assert_eq!(tool.status, OtelStatusCode::Ok);
+
+    let message = &spans[2];
+    assert_eq!(message.name, "assistant_message");
+    assert_eq!((message.start, message.end), (t(4), t(5)));
+    assert!(spans.iter().all(|s| s.trace_id == root.trace_id));
+}
+
+#[test]
+fn nested_and_failed_tool_calls() {
+    let r = receipt(
+        Outcome::Failed,
+        vec![
+            tool_call(1, "task", "outer", None),
+            tool_call(2, "bash", "inner", Some("outer")),
+            tool_result(3, "bash", "inner", true),
+            ev(
+                4,
+                AgentEventKind::Error {
+                    message: "boom".into(),
+                    error_code: None,
+                },
+            ),
+        ],
+    );
+    let spans = receipt_spans(&r);
+    let (root, outer, inner) = (&spans[0], &spans[1], &spans[2]);
+
+    assert_eq!(
+        inner.parent_span_id.as_deref(),
+        Some(outer.span_id.as_str())
+    );
+    assert_eq!(inner.status, OtelStatusCode::Error);
+    // A tool call without a result runs until the end of the run.
+    assert_eq!(outer.end, t(10));
+    assert_eq!(outer.status, OtelStatusCode::Unset);
+
+    assert_eq!(root.status, OtelStatusCode::Error);
+    assert_eq!(root.status_message.as_deref(), Some("boom"));
+    assert_eq!(root.events.len(), 1);
+    assert_eq!(root.events[0].name, "error");
+}
+
+#[test]
+fn span_ids_are_stable_and_distinct() {
+    let trace = vec![
+        tool_call(1, "a", "1", None),
+        tool_result(2, "a", "1", false),
+        tool_call(3, "b", "2", None),
+    ];
+    let first = receipt_spans(&receipt(Outcome::Complete, trace.clone()));
+    let second = receipt_spans(&receipt(Outcome::Complete, trace));
+    let ids: Vec<_> = first.iter().map(|s| s.span_id.clone()).collect();
+    assert_eq!(
+        ids,
+        second.iter().map(|s| s.span_id.clone()).collect::<Vec<_>>()
+    );
+    assert!(ids.iter().all(|id| id.len() == 16));
+    let unique: std::collections::BTreeSet<_> = ids.iter().collect();
+    assert_eq!(unique.len(), ids.len());
+}
+
+#[test]
+fn export_request_is_otlp_json() {
+    let r = receipt(Outcome::Complete, vec![tool_call(1, "grep", "g", None)]);
+    let body = export_request("abp-test", &[r]);
+
+    let resource = &body["resourceSpans"][0];
+    assert_eq!(
+        resource["resource"]["attributes"][0],
+        serde_json::json!({"key": "service.name", "value": {"stringValue": "abp-test"}})
+    );
+    let spans = resource["scopeSpans"][0]["spans"].as_array().unwrap();
+    assert_eq!(spans.len(), 2);
+    assert!(spans[0].get("parentSpanId").is_none());
+    assert_eq!(spans[1]["parentSpanId"], spans[0]["spanId"]);
+    assert_eq!(spans[1]["kind"], 3);
+    let start = t(1).timestamp_nanos_opt().unwrap().to_string();
+    assert_eq!(spans[1]["startTimeUnixNano"], start);
+    let tokens = spans[0]["attributes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|a| a["key"] == "abp.usage.input_tokens")
+        .unwrap();
+    assert_eq!(tokens["value"]["intValue"], "100");
+}
+
+/// Accept one HTTP request, answer with `status`, and return the raw request.
+async fn one_shot_collector(status: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut chunk).await.unwrap();
+            buf.extend_from_slice(&chunk[..n]);
+            let text = String::from_utf8_lossy(&buf);
+            if let Some(header_end) = text.find("\r\n\r\n") {
+                let len = text[..header_end]
+                    .lines()
+                    .find_map(|l| {
+                        l.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if buf.len() >= header_end + 4 + len || n == 0 {
+                    break;
+                }
+            }
+        }
+        let reply = format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+        socket.write_all(reply.as_bytes()).await.unwrap();
+        String::from_utf8(buf).unwrap()
+    });
+    (format!("http://{addr}"), handle)
+}
+
+#[tokio::test]
+async fn exporter_posts_to_v1_traces() {
+    let (endpoint, server) = one_shot_collector("200 OK").await;
+    let exporter = OtlpExporter::new(format!("{endpoint}/"))
+        .with_service_name("abp-test")
+        .with_header("x-api-key", "secret");
+    assert_eq!(exporter.traces_url(), format!("{endpoint}/v1/traces"));
+
+    exporter
+        .export(&[receipt(Outcome::Complete, vec![])])
+        .await
+        .unwrap();
+    let request = server.await.unwrap();
+    assert!(request.starts_with("POST /v1/traces HTTP/1.1"));
+    assert!(request.contains("x-api-key: secret"));
+    assert!(request.contains("\"abp.run\""));
+}
+
+#[tokio::test]
+async fn exporter_reports_collector_errors() {
+    let (endpoint, server) = one_shot_collector("503 Service Unavailable").await;
+    let err = OtlpExporter::new(endpoint)
+        .export(&[receipt(Outcome::Complete, vec![])])
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("503"), "{err}");
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn exporting_nothing_sends_nothing() {
+    OtlpExporter::new("http://127.0.0.1:9")
+        .export(&[])
+        .await
+        .unwrap();
+}
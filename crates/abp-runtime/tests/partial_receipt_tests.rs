@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for partial receipts published while a run is in progress.
+
+pub mod common;
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use abp_core::{AgentEvent, AgentEventKind, Outcome};
+use abp_runtime::Runtime;
+use abp_runtime::budget::USAGE_EXT_KEY;
+use common::{ScriptedBackend, work_order};
+use tokio_stream::StreamExt;
+
+fn step(text: &str, output_tokens: u64) -> AgentEvent {
+    AgentEvent {
+        ext: Some(BTreeMap::from([(
+            USAGE_EXT_KEY.to_string(),
+            serde_json::json!({ "output_tokens": output_tokens }),
+        )])),
+        ..common::event(AgentEventKind::AssistantDelta { text: text.into() })
+    }
+}
+
+#[tokio::test]
+async fn snapshots_track_the_run_and_end_with_its_outcome() {
+    let mut rt = Runtime::new().with_partial_receipts(Duration::from_millis(20));
+    // Reports growing usage in two steps, pausing after each.
+    rt.register_backend(
+        "stepped",
+        ScriptedBackend::new("stepped")
+            .events([step("one", 10), step("two", 25)])
+            .pause(Duration::from_millis(150)),
+    );
+    let mut rx = rt.subscribe_partial_receipts().unwrap();
+
+    let handle = rt
+        .run_streaming("stepped", work_order("take a while"))
+        .await
+        .unwrap();
+    let run_id = handle.run_id;
+    let _: Vec<AgentEvent> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+
+    let mut snapshots = Vec::new();
+    while let Ok(s) = rx.try_recv() {
+        snapshots.push(s);
+    }
+    assert!(snapshots.len() >= 3, "got {} snapshots", snapshots.len());
+    assert!(snapshots.iter().all(|s| s.run_id == run_id));
+    assert!(snapshots.iter().enumerate().all(|(i, s)| s.seq == i as u64));
+
+    let (last, running) = snapshots.split_last().unwrap();
+    assert!(
+        running
+            .iter()
+            .all(|s| !s.done && s.outcome == Outcome::Partial)
+    );
+    assert!(
+        running
+            .iter()
+            .any(|s| s.trace.len() == 1 && s.usage.output_tokens == Some(10))
+    );
+    assert!(
+        running
+            .iter()
+            .any(|s| s.trace.len() == 2 && s.usage.output_tokens == Some(25))
+    );
+
+    assert!(last.done);
+    assert_eq!(last.outcome, Outcome::Complete);
+    assert_eq!(last.trace.len(), receipt.trace.len());
+    assert_eq!(last.usage.output_tokens, Some(25));
+}
+
+#[tokio::test]
+async fn disabled_by_default_and_by_zero_interval() {
+    assert!(Runtime::new().subscribe_partial_receipts().is_none());
+    let rt = Runtime::new().with_partial_receipts(Duration::ZERO);
+    assert_eq!(rt.partial_receipts(), None);
+    assert!(rt.subscribe_partial_receipts().is_none());
+}
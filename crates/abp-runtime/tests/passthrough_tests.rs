@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for raw vendor payload passthrough on same-dialect routes.
+
+pub mod common;
+
+use std::collections::BTreeMap;
+
+use abp_core::{
+    AgentEventKind, ExecutionMode, Receipt, WorkOrder, WorkOrderBuilder, WorkspaceMode,
+};
+use abp_runtime::Runtime;
+use abp_runtime::passthrough::{
+    PassthroughRecord, RAW_MESSAGE_EXT_KEY, attach_raw_request, raw_request,
+};
+use common::{ScriptedBackend, event};
+use tokio_stream::StreamExt;
+
+const REQUEST: &str = r#"{"model":"gpt-4o",  "messages":[{"role":"user","content":"hi"}],"logit_bias":{"50256":-100}}"#;
+
+fn work_order(dialect: &str) -> WorkOrder {
+    let mut wo = WorkOrderBuilder::new("hi")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    wo.config
+        .vendor
+        .insert("abp.dialect".into(), serde_json::json!(dialect));
+    attach_raw_request(&mut wo, REQUEST);
+    wo
+}
+
+/// Run `wo` on a backend answering with one raw response; returns the
+/// receipt and the work order the backend received.
+async fn run(wo: WorkOrder) -> (Receipt, WorkOrder) {
+    let raw = serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "choices": [{"message": {"role": "assistant", "content": "hello"}}],
+    });
+    let mut ev = event(AgentEventKind::AssistantMessage {
+        text: "hello".into(),
+    });
+    ev.ext = Some(BTreeMap::from([(RAW_MESSAGE_EXT_KEY.to_string(), raw)]));
+    let backend = ScriptedBackend::new("openai-recorder").events([ev]);
+    let mut rt = Runtime::new();
+    rt.register_backend("openai-recorder", backend.clone());
+    let handle = rt.run_streaming("openai-recorder", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    (receipt, backend.work_orders().pop().unwrap())
+}
+
+#[tokio::test]
+async fn same_dialect_forwards_request_bytes_untouched() {
+    let (receipt, seen) = run(work_order("openai")).await;
+
+    assert_eq!(
+        raw_request(&seen),
+        Some(&serde_json::Value::String(REQUEST.into()))
+    );
+    assert_eq!(
+        abp_backend_core::extract_execution_mode(&seen),
+        ExecutionMode::Passthrough
+    );
+
+    assert_eq!(receipt.mode, ExecutionMode::Passthrough);
+    let record = PassthroughRecord::from_receipt(&receipt).unwrap();
+    assert_eq!(
+        record.request_sha256,
+        abp_core::sha256_hex(REQUEST.as_bytes())
+    );
+    assert_eq!(record.raw_events, 1);
+    assert_eq!(
+        receipt.trace[0].ext.as_ref().unwrap()[RAW_MESSAGE_EXT_KEY]["id"],
+        "chatcmpl-1"
+    );
+}
+
+#[tokio::test]
+async fn cross_dialect_runs_mapped_without_raw_request() {
+    let (receipt, seen) = run(work_order("claude")).await;
+
+    assert!(raw_request(&seen).is_none());
+    assert_eq!(
+        abp_backend_core::extract_execution_mode(&seen),
+        ExecutionMode::Mapped
+    );
+    assert!(PassthroughRecord::from_receipt(&receipt).is_none());
+}
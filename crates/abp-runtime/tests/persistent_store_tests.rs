@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for persisting receipts through `Runtime::with_receipt_store`.
+
+use std::sync::Arc;
+
+use abp_core::{WorkOrder, WorkOrderBuilder};
+use abp_receipt_store::{
+    InMemoryReceiptStore, JsonlDirReceiptStore, Receipt, ReceiptFilter, ReceiptStore,
+    SqliteReceiptStore, StoreError,
+};
+use abp_runtime::Runtime;
+use async_trait::async_trait;
+
+fn work_order() -> WorkOrder {
+    WorkOrderBuilder::new("persisted task")
+        .root(".")
+        .workspace_mode(abp_core::WorkspaceMode::PassThrough)
+        .build()
+}
+
+async fn run(rt: &Runtime, wo: WorkOrder) -> Receipt {
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    handle.receipt.await.unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn receipts_are_persisted_after_each_run() {
+    let store = Arc::new(InMemoryReceiptStore::new());
+    let rt = Runtime::with_default_backends().with_receipt_store(store.clone());
+    assert!(rt.receipt_store().is_some());
+
+    let wo = work_order();
+    let woid = wo.id.to_string();
+    let first = run(&rt, wo.clone()).await;
+    let second = run(&rt, wo).await;
+
+    assert_eq!(store.count().await.unwrap(), 2);
+    let stored = store
+        .get(&first.meta.run_id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.receipt_sha256, first.receipt_sha256);
+    let for_wo = ReceiptStore::get_by_work_order_id(store.as_ref(), &woid)
+        .await
+        .unwrap();
+    let mut ids: Vec<_> = for_wo.iter().map(|r| r.meta.run_id).collect();
+    ids.sort();
+    let mut expected = vec![first.meta.run_id, second.meta.run_id];
+    expected.sort();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+async fn sqlite_store_survives_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("receipts.db");
+    let rt = Runtime::with_default_backends()
+        .with_receipt_store(Arc::new(SqliteReceiptStore::open(&path).unwrap()));
+    let receipt = run(&rt, work_order()).await;
+    drop(rt);
+
+    let reopened = SqliteReceiptStore::open(&path).unwrap();
+    let listed = reopened.list(ReceiptFilter::default()).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].meta.run_id, receipt.meta.run_id);
+}
+
+#[tokio::test]
+async fn jsonl_dir_store_receives_receipts() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = Arc::new(JsonlDirReceiptStore::new(dir.path()));
+    let rt = Runtime::with_default_backends().with_receipt_store(store.clone());
+    let receipt = run(&rt, work_order()).await;
+
+    assert!(store.file_for(&receipt).exists());
+    assert_eq!(store.count().await.unwrap(), 1);
+}
+
+struct FailingStore;
+
+#[async_trait]
+impl ReceiptStore for FailingStore {
+    async fn store(&self, _: &Receipt) -> abp_receipt_store::Result<()> {
+        Err(StoreError::Other("disk full".into()))
+    }
+    async fn get(&self, _: &str) -> abp_receipt_store::Result<Option<Receipt>> {
+        Ok(None)
+    }
+    async fn list(&self, _: ReceiptFilter) -> abp_receipt_store::Result<Vec<Receipt>> {
+        Ok(Vec::new())
+    }
+    async fn delete(&self, _: &str) -> abp_receipt_store::Result<bool> {
+        Ok(false)
+    }
+    async fn count(&self) -> abp_receipt_store::Result<usize> {
+        Ok(0)
+    }
+}
+
+#[tokio::test]
+async fn store_failure_does_not_fail_the_run() {
+    let rt = Runtime::with_default_backends().with_receipt_store(Arc::new(FailingStore));
+    let receipt = run(&rt, work_order()).await;
+    assert_eq!(receipt.outcome, abp_core::Outcome::Complete);
+}
@@ -94,6 +94,7 @@ fn mock_receipt(run_id: Uuid, work_order_id: Uuid, backend_id: &str) -> Receipt
             git_diff: None,
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -715,3 +716,43 @@ fn pipeline_config_accessor() {
     assert!(pipeline.config().retry_policy.is_some());
     assert!(pipeline.config().fallback_chain.is_none());
 }
+
+// 19. Retry back-off waits on the runtime clock.
+#[tokio::test]
+async fn retry_backoff_advances_injected_clock() {
+    let clock = Arc::new(abp_core::clock::ManualClock::default());
+    let backend = FailThenSucceedBackend::new("primary", 2);
+    let mut rt = Runtime::new().with_clock(clock.clone());
+    rt.register_backend("primary", backend.clone());
+
+    let config = ExecutionConfig {
+        retry_policy: Some(
+            RetryPolicy::builder()
+                .max_retries(3)
+                .initial_backoff(Duration::from_secs(30))
+                .max_backoff(Duration::from_secs(120))
+                .build(),
+        ),
+        fallback_chain: None,
+    };
+    let started = std::time::Instant::now();
+    let output = ExecutionPipeline::new(config)
+        .execute(&rt, "primary", mock_work_order())
+        .await
+        .unwrap();
+
+    let waited_ms: u64 = output
+        .events
+        .iter()
+        .filter_map(|e| match e {
+            PipelineEvent::Retry { delay_ms, .. } => Some(*delay_ms),
+            _ => None,
+        })
+        .sum();
+    assert_eq!(backend.calls(), 3);
+    // Event delays are truncated to whole milliseconds.
+    let elapsed_ms = clock.elapsed().as_millis() as u64;
+    assert!((waited_ms..=waited_ms + 2).contains(&elapsed_ms));
+    assert!(waited_ms >= 60_000);
+    assert!(started.elapsed() < Duration::from_secs(30));
+}
@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for pricing receipts and accounting cost per backend and model.
+
+use abp_core::{
+    AgentEvent, BackendIdentity, CapabilityManifest, Receipt, UsageNormalized, WorkOrder,
+    WorkOrderBuilder, WorkspaceMode,
+};
+use abp_integrations::{Backend, MockBackend};
+use abp_runtime::Runtime;
+use abp_runtime::pricing::{ModelPrice, PRICING_USAGE_KEY, PricingTable};
+use abp_runtime::telemetry::UNKNOWN_MODEL;
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Finishes like the mock backend, but reports token usage, optionally the
+/// model that served the run, and optionally its own cost.
+struct Metered {
+    model: Option<&'static str>,
+    cost: Option<f64>,
+}
+
+#[async_trait]
+impl Backend for Metered {
+    fn identity(&self) -> BackendIdentity {
+        MockBackend.identity()
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        MockBackend.capabilities()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        let mut receipt = MockBackend.run(run_id, work_order, events_tx).await?;
+        receipt.usage = UsageNormalized {
+            input_tokens: Some(200_000),
+            output_tokens: Some(10_000),
+            cache_read_tokens: Some(100_000),
+            estimated_cost_usd: self.cost,
+            ..Default::default()
+        };
+        if let Some(model) = self.model {
+            receipt.usage_raw["model"] = model.into();
+        }
+        Ok(receipt)
+    }
+}
+
+async fn run(rt: &Runtime, backend: &str, model: Option<&str>) -> Receipt {
+    let dir = tempfile::tempdir().unwrap();
+    let mut wo = WorkOrderBuilder::new("count tokens")
+        .root(dir.path().to_string_lossy())
+        .workspace_mode(WorkspaceMode::PassThrough);
+    if let Some(model) = model {
+        wo = wo.model(model);
+    }
+    let handle = rt.run_streaming(backend, wo.build()).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    handle.receipt.await.unwrap().unwrap()
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[tokio::test]
+async fn receipts_are_priced_and_costs_grouped_by_backend_and_model() {
+    let mut rt = Runtime::new();
+    rt.register_backend(
+        "served",
+        Metered {
+            model: Some("claude-3-5-sonnet-20241022"),
+            cost: None,
+        },
+    );
+    rt.register_backend(
+        "requested",
+        Metered {
+            model: None,
+            cost: None,
+        },
+    );
+    rt.register_backend(
+        "self-priced",
+        Metered {
+            model: Some("gpt-4o"),
+            cost: Some(0.5),
+        },
+    );
+
+    // The model the backend reports wins over the one requested.
+    let served = run(&rt, "served", Some("gpt-4o")).await;
+    let expected = 0.2 * 3.00 + 0.01 * 15.00 + 0.1 * 0.30;
+    assert!(close(served.usage.estimated_cost_usd.unwrap(), expected));
+    assert_eq!(
+        served.usage_raw[PRICING_USAGE_KEY]["entry"],
+        "claude-3-5-sonnet"
+    );
+    assert_eq!(
+        served.receipt_sha256.as_deref(),
+        Some(abp_receipt::compute_hash(&served).unwrap().as_str()),
+        "the cost is hashed with the receipt"
+    );
+
+    let requested = run(&rt, "requested", Some("gpt-4o-mini")).await;
+    assert_eq!(
+        requested.usage_raw[PRICING_USAGE_KEY]["model"],
+        "gpt-4o-mini"
+    );
+    run(&rt, "requested", Some("gpt-4o-mini")).await;
+
+    let unpriced = run(&rt, "requested", None).await;
+    assert_eq!(unpriced.usage.estimated_cost_usd, None);
+
+    let self_priced = run(&rt, "self-priced", None).await;
+    assert_eq!(self_priced.usage.estimated_cost_usd, Some(0.5));
+    assert!(self_priced.usage_raw.get(PRICING_USAGE_KEY).is_none());
+
+    let snapshot = rt.metrics().snapshot();
+    let groups: Vec<_> = snapshot
+        .costs
+        .iter()
+        .map(|c| (c.backend.as_str(), c.model.as_str(), c.totals.runs))
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            ("requested", "gpt-4o-mini", 2),
+            ("requested", UNKNOWN_MODEL, 1),
+            ("self-priced", "gpt-4o", 1),
+            ("served", "claude-3-5-sonnet-20241022", 1),
+        ]
+    );
+    let mini = &snapshot.costs[0].totals;
+    assert_eq!(mini.input_tokens, 400_000);
+    assert_eq!(mini.cache_read_tokens, 200_000);
+    assert_eq!(snapshot.costs[1].totals.unpriced_runs, 1);
+    let total: f64 = snapshot.costs.iter().map(|c| c.totals.cost_usd).sum();
+    assert!(close(snapshot.total_cost_usd, total));
+    assert!(close(
+        total,
+        expected + 0.5 + 2.0 * (0.2 * 0.15 + 0.01 * 0.60 + 0.1 * 0.075)
+    ));
+}
+
+#[tokio::test]
+async fn config_overrides_and_extends_the_table() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("backplane.toml");
+    std::fs::write(
+        &path,
+        r#"
+default_backend = "mock"
+
+[pricing."gpt-4o"]
+input_per_mtok = 1.0
+output_per_mtok = 2.0
+
+[pricing."house-model"]
+input_per_mtok = 10.0
+output_per_mtok = 10.0
+cache_read_per_mtok = 0.0
+"#,
+    )
+    .unwrap();
+
+    let mut rt = Runtime::new();
+    assert_eq!(
+        rt.register_pricing_from_config(&path).unwrap(),
+        ["gpt-4o", "house-model"]
+    );
+    assert_eq!(
+        rt.pricing().lookup("gpt-4o").unwrap().1,
+        &ModelPrice::new(1.0, 2.0)
+    );
+    assert!(rt.pricing().lookup("claude-3-opus").is_some());
+
+    rt.register_backend(
+        "house",
+        Metered {
+            model: Some("house-model"),
+            cost: None,
+        },
+    );
+    let receipt = run(&rt, "house", None).await;
+    assert!(close(receipt.usage.estimated_cost_usd.unwrap(), 2.0 + 0.1));
+}
+
+#[test]
+fn invalid_prices_are_rejected() {
+    let err = PricingTable::parse("[pricing.m]\ninput_per_mtok = -1.0\noutput_per_mtok = 1.0\n")
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("input_per_mtok must be a non-negative")
+    );
+
+    assert!(
+        PricingTable::parse(
+            "[pricing.m]\ninput_per_mtok = 1.0\noutput_per_mtok = 1.0\nper_request = 2.0\n"
+        )
+        .is_err()
+    );
+    assert!(PricingTable::parse("[pricing.m]\ninput_per_mtok = 1.0\n").is_err());
+    assert_eq!(PricingTable::parse("").unwrap(), PricingTable::new());
+}
@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for progress events and idle heartbeats.
+
+pub mod common;
+
+use std::time::Duration;
+
+use abp_core::{AgentEvent, AgentEventKind, Receipt};
+use abp_runtime::Runtime;
+use abp_runtime::progress::idle_duration;
+use common::{ScriptedBackend, work_order};
+
+/// A runtime whose backend reports progress, then goes quiet for `silence`.
+fn runtime(silence: Duration) -> Runtime {
+    let slow = ScriptedBackend::new("slow")
+        .event(AgentEventKind::Progress {
+            percent: Some(10.0),
+            message: "planning".into(),
+        })
+        .pause(silence)
+        .with_trace();
+    let mut rt = Runtime::new();
+    rt.register_backend("slow", slow);
+    rt
+}
+
+async fn run(rt: &Runtime) -> (Vec<AgentEvent>, Receipt) {
+    let (events, receipt) = common::run_with_events(rt, "slow", work_order("think hard")).await;
+    (events, receipt.unwrap())
+}
+
+#[tokio::test]
+async fn silent_backend_gets_idle_heartbeats() {
+    let rt = runtime(Duration::from_millis(600)).with_idle_progress(Duration::from_millis(100));
+    let (events, receipt) = run(&rt).await;
+
+    let idle: Vec<Duration> = events.iter().filter_map(idle_duration).collect();
+    assert!(idle.len() >= 2, "expected heartbeats, got {events:?}");
+    assert!(idle.windows(2).all(|w| w[0] <= w[1]), "{idle:?}");
+    assert!(idle[0] >= Duration::from_millis(100));
+
+    // Heartbeats are for the caller only; the receipt keeps the real trace.
+    assert!(receipt.trace.iter().all(|e| idle_duration(e).is_none()));
+    assert!(receipt.trace.iter().any(|e| matches!(
+        e.kind,
+        AgentEventKind::Progress {
+            percent: Some(p),
+            ..
+        } if p == 10.0
+    )));
+}
+
+#[tokio::test]
+async fn heartbeats_are_off_by_default() {
+    let rt = runtime(Duration::from_millis(200));
+    assert_eq!(rt.idle_progress(), None);
+    let (events, _) = run(&rt).await;
+    assert!(events.iter().all(|e| idle_duration(e).is_none()));
+}
+
+#[tokio::test]
+async fn zero_interval_disables_heartbeats() {
+    let rt = runtime(Duration::ZERO).with_idle_progress(Duration::ZERO);
+    assert_eq!(rt.idle_progress(), None);
+}
@@ -41,6 +41,9 @@ fn work_order_with_dialect(dialect: &str) -> WorkOrder {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -70,6 +73,9 @@ fn work_order_with_flat_dialect(dialect: &str) -> WorkOrder {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -426,6 +432,7 @@ async fn e2e_same_dialect_passthrough_records_metadata() {
     assert_eq!(translation["translation_mode"], "passthrough");
     assert_eq!(translation["source_dialect"], "OpenAI");
     assert_eq!(translation["target_dialect"], "OpenAI");
+    assert!(!usage.contains_key("translation_log"));
 }
 
 #[tokio::test]
@@ -450,6 +457,92 @@ async fn e2e_cross_dialect_mapped_records_metadata() {
     assert_eq!(translation["translation_mode"], "mapped");
     assert_eq!(translation["source_dialect"], "OpenAI");
     assert_eq!(translation["target_dialect"], "Claude");
+    // A plain-text task maps without any recorded transformation.
+    let log = usage
+        .get("translation_log")
+        .expect("mapped runs should record a translation log");
+    assert_eq!(log, &serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn e2e_translation_log_records_losses_in_attached_request() {
+    use abp_core::{Capability, SupportLevel};
+
+    let mut manifest = abp_core::CapabilityManifest::default();
+    manifest.insert(Capability::Streaming, SupportLevel::Native);
+
+    let mut matrix = abp_projection::ProjectionMatrix::new();
+    matrix.register_backend("mock", manifest, Dialect::OpenAi, 50);
+
+    let rt = Runtime::with_default_backends().with_projection(matrix);
+    let conversation = IrConversation::from_messages(vec![
+        IrMessage::text(IrRole::User, "What is 2 + 2?"),
+        IrMessage::new(
+            IrRole::Assistant,
+            vec![
+                IrContentBlock::Thinking {
+                    text: "Add the numbers.".into(),
+                },
+                IrContentBlock::Text { text: "4".into() },
+            ],
+        ),
+        IrMessage::text(IrRole::User, "And 3 + 3?"),
+    ]);
+    let mut wo = work_order_with_dialect("claude");
+    wo.config.vendor.insert(
+        "abp".into(),
+        serde_json::json!({
+            "dialect": "claude",
+            "conversation": conversation,
+        }),
+    );
+    let receipt = run_and_get_receipt(&rt, "mock", wo).await.unwrap();
+    assert_eq!(receipt.outcome, Outcome::Complete);
+
+    let log = receipt.usage_raw["translation_log"]
+        .as_array()
+        .expect("mapped runs should record a translation log");
+    assert!(
+        log.iter().any(|e| e["kind"] == "content_dropped"
+            && e["message_index"] == 1
+            && e["field"] == "messages[1].content"
+            && e["detail"] == "1 thinking block(s) dropped"),
+        "log: {log:?}"
+    );
+}
+
+#[tokio::test]
+async fn e2e_translation_log_records_tool_choice_loss() {
+    use abp_core::{Capability, SupportLevel};
+
+    let mut manifest = abp_core::CapabilityManifest::default();
+    manifest.insert(Capability::Streaming, SupportLevel::Native);
+    manifest.insert(Capability::ToolUse, SupportLevel::Native);
+
+    let mut matrix = abp_projection::ProjectionMatrix::new();
+    matrix.register_backend("mock", manifest, Dialect::Claude, 50);
+
+    let rt = Runtime::with_default_backends().with_projection(matrix);
+    let mut wo = work_order_with_dialect("openai");
+    wo.config.vendor.insert(
+        "abp".into(),
+        serde_json::json!({
+            "dialect": "openai",
+            "tools": [{
+                "name": "search",
+                "description": "Search the web",
+                "parameters": {"type": "object"},
+            }],
+            "tool_choice": {"type": "none"},
+        }),
+    );
+    let receipt = run_and_get_receipt(&rt, "mock", wo).await.unwrap();
+    assert_eq!(receipt.outcome, Outcome::Complete);
+
+    let log = &receipt.usage_raw["translation_log"];
+    assert_eq!(log.as_array().unwrap().len(), 1, "log: {log:?}");
+    assert_eq!(log[0]["kind"], "field_dropped");
+    assert_eq!(log[0]["field"], "tool_choice");
 }
 
 #[tokio::test]
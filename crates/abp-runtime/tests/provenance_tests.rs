@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for recording provider request provenance in run receipts.
+
+pub mod common;
+
+use std::collections::BTreeMap;
+
+use abp_core::{AgentEventKind, WorkOrder, WorkOrderBuilder, WorkspaceMode};
+use abp_receipt::provenance::{PROVENANCE_KEY, Provenance, ProviderRequest};
+use abp_runtime::Runtime;
+use common::{ScriptedBackend, event};
+use serde_json::json;
+
+/// Streams one passthrough event carrying a raw OpenAI completion.
+fn openai_passthrough() -> ScriptedBackend {
+    let mut ext = BTreeMap::new();
+    ext.insert(
+        "raw_message".to_string(),
+        json!({
+            "id": "chatcmpl-abc123",
+            "object": "chat.completion",
+            "model": "gpt-4o-2024-08-06",
+            "system_fingerprint": "fp_44709d6fcb",
+            "choices": [],
+        }),
+    );
+    let mut ev = event(AgentEventKind::AssistantMessage {
+        text: "hello".into(),
+    });
+    ev.ext = Some(ext);
+    ScriptedBackend::new("openai-passthrough").events([ev])
+}
+
+fn work_order() -> WorkOrder {
+    WorkOrderBuilder::new("trace me")
+        .root(".")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build()
+}
+
+#[tokio::test]
+async fn provider_ids_from_passthrough_events_land_in_receipt() {
+    let mut rt = Runtime::new();
+    rt.register_backend("openai-passthrough", openai_passthrough());
+    let handle = rt
+        .run_streaming("openai-passthrough", work_order())
+        .await
+        .unwrap();
+    let receipt = handle.receipt.await.unwrap().unwrap();
+
+    let provenance = Provenance::from_receipt(&receipt).expect("provenance block");
+    assert_eq!(
+        provenance.requests,
+        [ProviderRequest::new("openai")
+            .response_id("chatcmpl-abc123")
+            .system_fingerprint("fp_44709d6fcb")
+            .model("gpt-4o-2024-08-06")]
+    );
+    // The block is written before hashing, so the hash covers it.
+    assert_eq!(
+        receipt.receipt_sha256.as_deref(),
+        Some(abp_receipt::compute_hash(&receipt).unwrap().as_str())
+    );
+}
+
+#[tokio::test]
+async fn runs_without_provider_ids_get_no_provenance_block() {
+    let rt = Runtime::with_default_backends();
+    let handle = rt.run_streaming("mock", work_order()).await.unwrap();
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    assert!(receipt.usage_raw.get(PROVENANCE_KEY).is_none());
+}
@@ -0,0 +1,189 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the priority work queue.
+
+pub mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use abp_core::clock::{Clock, ManualClock};
+use abp_core::{AgentEvent, BackendIdentity, CapabilityManifest, Outcome, Receipt, WorkOrder};
+use abp_integrations::Backend;
+use abp_runtime::Runtime;
+use abp_runtime::queue::{JobStatus, QueueConfig, QueuedWork, WorkQueue};
+use async_trait::async_trait;
+use common::work_order;
+use tokio::sync::{Semaphore, mpsc};
+use uuid::Uuid;
+
+/// Records the order tasks start in; each run waits for a permit.
+#[derive(Clone)]
+struct Gated {
+    started: Arc<Mutex<Vec<String>>>,
+    gate: Arc<Semaphore>,
+}
+
+impl Gated {
+    fn new(open: bool) -> Self {
+        Self {
+            started: Arc::default(),
+            gate: Arc::new(Semaphore::new(if open {
+                Semaphore::MAX_PERMITS
+            } else {
+                0
+            })),
+        }
+    }
+
+    fn started(&self) -> Vec<String> {
+        self.started.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl Backend for Gated {
+    fn identity(&self) -> BackendIdentity {
+        BackendIdentity {
+            id: "gated".into(),
+            backend_version: None,
+            adapter_version: None,
+        }
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        CapabilityManifest::new()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        _events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        self.started.lock().unwrap().push(work_order.task.clone());
+        self.gate.acquire().await?.forget();
+        if work_order.task.contains("fail") {
+            anyhow::bail!("asked to fail");
+        }
+        Ok(abp_receipt::ReceiptBuilder::new("gated")
+            .run_id(run_id)
+            .work_order_id(work_order.id)
+            .outcome(Outcome::Complete)
+            .build())
+    }
+}
+
+fn runtime(backends: &[(&str, &Gated)]) -> Arc<Runtime> {
+    let mut rt = Runtime::new();
+    for (name, backend) in backends {
+        rt.register_backend(name, (*backend).clone());
+    }
+    Arc::new(rt)
+}
+
+/// Wait until `n` runs have started on `backend`.
+async fn started(backend: &Gated, n: usize) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while backend.started().len() < n {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("runs did not start");
+}
+
+#[tokio::test]
+async fn higher_priorities_start_first() {
+    let backend = Gated::new(false);
+    let queue = WorkQueue::new(QueueConfig::default().max_running(1));
+    let low = queue.enqueue(QueuedWork::new("gated", work_order("low")));
+    let first = queue.enqueue(QueuedWork::new("gated", work_order("high 1")).priority(5));
+    let second = queue.enqueue(QueuedWork::new("gated", work_order("high 2")).priority(5));
+
+    let pending: Vec<_> = queue.snapshot().pending.iter().map(|j| j.id).collect();
+    assert_eq!(pending, [first, second, low]);
+
+    let _dispatcher = queue.start(runtime(&[("gated", &backend)]));
+    backend.gate.add_permits(3);
+    let info = queue.wait(low).await.unwrap();
+
+    assert_eq!(info.status, JobStatus::Completed);
+    assert_eq!(info.outcome, Some(Outcome::Complete));
+    assert!(info.run_id.is_some());
+    assert_eq!(backend.started(), ["high 1", "high 2", "low"]);
+    assert_eq!(
+        queue.receipt(low).unwrap().meta.work_order_id,
+        queue.status(low).unwrap().work_order_id
+    );
+}
+
+#[tokio::test]
+async fn backend_limits_do_not_block_other_backends() {
+    let slow = Gated::new(false);
+    let fast = Gated::new(true);
+    let queue = WorkQueue::new(QueueConfig::default().backend_limit("slow", 1));
+    let a = queue.enqueue(QueuedWork::new("slow", work_order("a")).priority(1));
+    let b = queue.enqueue(QueuedWork::new("slow", work_order("b")).priority(1));
+    let c = queue.enqueue(QueuedWork::new("fast", work_order("c")));
+    let _dispatcher = queue.start(runtime(&[("slow", &slow), ("fast", &fast)]));
+
+    assert_eq!(queue.wait(c).await.unwrap().status, JobStatus::Completed);
+    started(&slow, 1).await;
+    let snapshot = queue.snapshot();
+    assert_eq!(snapshot.running.len(), 1);
+    assert_eq!(snapshot.running[0].id, a);
+    assert_eq!(snapshot.pending.len(), 1);
+    assert_eq!(snapshot.pending[0].id, b);
+
+    slow.gate.add_permits(2);
+    assert_eq!(queue.wait(b).await.unwrap().status, JobStatus::Completed);
+    assert_eq!(slow.started(), ["a", "b"]);
+}
+
+#[tokio::test]
+async fn not_before_delays_a_job() {
+    let backend = Gated::new(true);
+    let clock = Arc::new(ManualClock::new(chrono::Utc::now()));
+    let queue = WorkQueue::with_clock(QueueConfig::default(), clock.clone());
+    let later = queue.enqueue(
+        QueuedWork::new("gated", work_order("later"))
+            .priority(10)
+            .not_before(clock.now() + chrono::Duration::minutes(5)),
+    );
+    let now = queue.enqueue(QueuedWork::new("gated", work_order("now")));
+    let _dispatcher = queue.start(runtime(&[("gated", &backend)]));
+
+    assert_eq!(queue.wait(now).await.unwrap().status, JobStatus::Completed);
+    let info = queue.wait(later).await.unwrap();
+    assert_eq!(info.status, JobStatus::Completed);
+    assert!(info.started_at.unwrap() >= info.not_before.unwrap());
+    assert_eq!(backend.started(), ["now", "later"]);
+}
+
+#[tokio::test]
+async fn failures_cancellation_and_history() {
+    let backend = Gated::new(true);
+    let queue = WorkQueue::new(QueueConfig::default().history(2));
+    let cancelled = queue.enqueue(QueuedWork::new("gated", work_order("never")));
+    assert!(queue.cancel(cancelled));
+    assert!(!queue.cancel(cancelled));
+    assert_eq!(
+        queue.status(cancelled).unwrap().status,
+        JobStatus::Cancelled
+    );
+
+    let failed = queue.enqueue(QueuedWork::new("gated", work_order("please fail")));
+    let unknown = queue.enqueue(QueuedWork::new("nope", work_order("x")));
+    let _dispatcher = queue.start(runtime(&[("gated", &backend)]));
+
+    let info = queue.wait(failed).await.unwrap();
+    assert_eq!(info.status, JobStatus::Failed);
+    assert!(info.error.is_some());
+    assert!(queue.receipt(failed).is_none());
+    assert_eq!(queue.wait(unknown).await.unwrap().status, JobStatus::Failed);
+
+    // Only the last two finished jobs are kept.
+    assert!(queue.status(cancelled).is_none());
+    assert!(queue.wait(cancelled).await.is_none());
+    assert_eq!(queue.snapshot().finished.len(), 2);
+}
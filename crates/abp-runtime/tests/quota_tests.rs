@@ -0,0 +1,318 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for per-tenant and per-API-key calendar-window quotas.
+
+pub mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use abp_core::clock::{Clock, ManualClock};
+use abp_core::submitter::Submitter;
+use abp_core::{Receipt, UsageNormalized, WorkOrder, WorkOrderBuilder};
+use abp_error::ErrorCode;
+use abp_runtime::quota::{Quota, QuotaEnforcer, QuotaScope, QuotaUsage, QuotaWindow};
+use abp_runtime::store::ReceiptStore;
+use abp_runtime::{Runtime, RuntimeError};
+use chrono::{DateTime, TimeZone, Utc};
+use common::{Behavior, ScriptedBackend};
+
+fn at(y: i32, m: u32, d: u32, h: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(y, m, d, h, 0, 0).unwrap()
+}
+
+fn acme() -> QuotaScope {
+    QuotaScope::Tenant("acme".into())
+}
+
+fn runtime(quotas: QuotaEnforcer, clock: Arc<ManualClock>) -> Runtime {
+    let mut rt = Runtime::new().with_quotas(quotas).with_clock(clock);
+    rt.register_backend(
+        "metered",
+        ScriptedBackend::new("metered").usage(UsageNormalized {
+            input_tokens: Some(600),
+            output_tokens: Some(0),
+            estimated_cost_usd: Some(0.25),
+            ..UsageNormalized::default()
+        }),
+    );
+    rt
+}
+
+fn work_order(submitter: Submitter) -> WorkOrder {
+    WorkOrderBuilder::new("spend tokens")
+        .root(".")
+        .workspace_mode(abp_core::WorkspaceMode::PassThrough)
+        .submitter(submitter)
+        .build()
+}
+
+fn acme_order() -> WorkOrder {
+    work_order(Submitter::new().tenant("acme"))
+}
+
+async fn run(rt: &Runtime, wo: WorkOrder) -> Result<Receipt, RuntimeError> {
+    common::run(rt, "metered", wo).await
+}
+
+fn assert_quota_error(err: RuntimeError) {
+    assert_eq!(err.error_code(), ErrorCode::QuotaExceeded);
+    assert!(!err.is_retryable());
+}
+
+#[test]
+fn daily_window_spans_one_utc_day() {
+    let now = at(2026, 3, 14, 15);
+    assert_eq!(QuotaWindow::Daily.start(now), at(2026, 3, 14, 0));
+    assert_eq!(QuotaWindow::Daily.end(now), at(2026, 3, 15, 0));
+}
+
+#[test]
+fn monthly_window_spans_one_calendar_month() {
+    let now = at(2026, 12, 31, 23);
+    assert_eq!(QuotaWindow::Monthly.start(now), at(2026, 12, 1, 0));
+    assert_eq!(QuotaWindow::Monthly.end(now), at(2027, 1, 1, 0));
+}
+
+#[tokio::test]
+async fn rejects_before_dispatch_once_token_quota_used() {
+    let clock = Arc::new(ManualClock::new(at(2026, 3, 14, 9)));
+    let rt = runtime(
+        QuotaEnforcer::new().with_quota(acme(), Quota::tokens(QuotaWindow::Daily, 1_000)),
+        clock.clone(),
+    );
+
+    run(&rt, acme_order()).await.unwrap();
+    run(&rt, acme_order()).await.unwrap();
+    assert_eq!(
+        rt.quotas()
+            .unwrap()
+            .usage(&acme(), QuotaWindow::Daily, clock.now()),
+        QuotaUsage {
+            tokens: 1_200,
+            cost_usd: 0.5
+        }
+    );
+
+    let err = rt
+        .run_streaming("metered", acme_order())
+        .await
+        .err()
+        .unwrap();
+    assert_quota_error(err);
+}
+
+#[tokio::test]
+async fn cost_quota_rejects_with_context() {
+    let clock = Arc::new(ManualClock::new(at(2026, 3, 14, 9)));
+    let rt = runtime(
+        QuotaEnforcer::new().with_quota(acme(), Quota::cost_usd(QuotaWindow::Monthly, 0.25)),
+        clock,
+    );
+
+    run(&rt, acme_order()).await.unwrap();
+    let Err(RuntimeError::Classified(err)) = rt.run_streaming("metered", acme_order()).await else {
+        panic!("expected a classified quota error");
+    };
+    assert_eq!(err.code, ErrorCode::QuotaExceeded);
+    assert!(err.message.contains("monthly cost_usd quota"), "{err}");
+    assert_eq!(err.context["window"], serde_json::json!("monthly"));
+    assert_eq!(
+        err.context["resets_at"],
+        serde_json::json!(at(2026, 4, 1, 0).to_rfc3339())
+    );
+}
+
+#[tokio::test]
+async fn usage_resets_when_window_rolls_over() {
+    let clock = Arc::new(ManualClock::new(at(2026, 3, 14, 23)));
+    let rt = runtime(
+        QuotaEnforcer::new().with_quota(acme(), Quota::tokens(QuotaWindow::Daily, 500)),
+        clock.clone(),
+    );
+
+    run(&rt, acme_order()).await.unwrap();
+    assert_quota_error(
+        rt.run_streaming("metered", acme_order())
+            .await
+            .err()
+            .unwrap(),
+    );
+
+    clock.advance(Duration::from_secs(3600));
+    run(&rt, acme_order()).await.unwrap();
+}
+
+#[tokio::test]
+async fn other_tenants_and_untagged_orders_are_not_limited() {
+    let clock = Arc::new(ManualClock::new(at(2026, 3, 14, 9)));
+    let rt = runtime(
+        QuotaEnforcer::new().with_quota(acme(), Quota::tokens(QuotaWindow::Daily, 100)),
+        clock,
+    );
+
+    run(&rt, acme_order()).await.unwrap();
+    run(&rt, work_order(Submitter::new().tenant("globex")))
+        .await
+        .unwrap();
+    run(&rt, work_order(Submitter::new())).await.unwrap();
+    assert_quota_error(
+        rt.run_streaming("metered", acme_order())
+            .await
+            .err()
+            .unwrap(),
+    );
+}
+
+#[tokio::test]
+async fn api_key_quota_applies_and_is_stamped_on_receipt() {
+    let clock = Arc::new(ManualClock::new(at(2026, 3, 14, 9)));
+    let key = QuotaScope::ApiKey("key-1".into());
+    let rt = runtime(
+        QuotaEnforcer::new().with_quota(key, Quota::tokens(QuotaWindow::Monthly, 600)),
+        clock,
+    );
+    let order = || work_order(Submitter::new().tenant("acme").api_key_id("key-1"));
+
+    let receipt = run(&rt, order()).await.unwrap();
+    assert_eq!(receipt.usage_raw["api_key_id"], serde_json::json!("key-1"));
+    assert_quota_error(rt.run_streaming("metered", order()).await.err().unwrap());
+}
+
+#[tokio::test]
+async fn usage_survives_restart_via_store() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ReceiptStore::new(dir.path());
+    let quotas =
+        || QuotaEnforcer::new().with_quota(acme(), Quota::tokens(QuotaWindow::Monthly, 1_000));
+
+    let clock = Arc::new(ManualClock::new(at(2026, 3, 14, 9)));
+    let rt = runtime(quotas(), clock.clone());
+    for _ in 0..2 {
+        let mut receipt = run(&rt, acme_order()).await.unwrap();
+        receipt.meta.started_at = clock.now();
+        store.save(&receipt.with_hash().unwrap()).unwrap();
+    }
+
+    let restored = quotas();
+    assert_eq!(restored.restore_from(&store).unwrap(), 2);
+    let rt = runtime(restored, clock);
+    assert_quota_error(
+        rt.run_streaming("metered", acme_order())
+            .await
+            .err()
+            .unwrap(),
+    );
+}
+
+#[tokio::test]
+async fn restore_ignores_receipts_from_past_windows() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = ReceiptStore::new(dir.path());
+    let mut old = abp_receipt::ReceiptBuilder::new("metered")
+        .started_at(at(2026, 2, 27, 9))
+        .usage_tokens(5_000, 0)
+        .usage_raw(serde_json::json!({"tenant": "acme"}))
+        .build();
+    old = old.with_hash().unwrap();
+    store.save(&old).unwrap();
+
+    let quotas =
+        QuotaEnforcer::new().with_quota(acme(), Quota::tokens(QuotaWindow::Monthly, 1_000));
+    quotas.restore_from(&store).unwrap();
+    assert_eq!(
+        quotas.usage(&acme(), QuotaWindow::Monthly, at(2026, 3, 1, 0)),
+        QuotaUsage::default()
+    );
+    assert!(quotas.reserve(&acme_order(), at(2026, 2, 28, 0)).is_err());
+    assert!(quotas.reserve(&acme_order(), at(2026, 3, 1, 0)).is_ok());
+}
+
+/// A tenant order whose projected input is `tokens` tokens.
+fn acme_order_of(tokens: u64) -> WorkOrder {
+    let mut wo = acme_order();
+    wo.config.vendor.insert(
+        "abp".into(),
+        serde_json::json!({ abp_tokenize::PROJECTED_INPUT_TOKENS_KEY: tokens }),
+    );
+    wo
+}
+
+#[test]
+fn in_flight_reservations_count_against_the_cap() {
+    let now = at(2026, 3, 14, 9);
+    let quotas = QuotaEnforcer::new().with_quota(acme(), Quota::tokens(QuotaWindow::Daily, 1_000));
+
+    let first = quotas.reserve(&acme_order_of(600), now).unwrap();
+    assert_eq!(first.tokens(), 600);
+    assert_eq!(quotas.usage(&acme(), QuotaWindow::Daily, now).tokens, 600);
+    let err = quotas.reserve(&acme_order_of(600), now).unwrap_err();
+    assert_eq!(err.code, ErrorCode::QuotaExceeded);
+    assert_eq!(
+        err.context["projected_input_tokens"],
+        serde_json::json!(600)
+    );
+
+    quotas.release(first);
+    assert_eq!(quotas.usage(&acme(), QuotaWindow::Daily, now).tokens, 0);
+    assert!(quotas.reserve(&acme_order_of(600), now).is_ok());
+}
+
+#[test]
+fn settling_replaces_the_reservation_with_receipt_usage() {
+    let now = at(2026, 3, 14, 9);
+    let quotas = QuotaEnforcer::new().with_quota(acme(), Quota::tokens(QuotaWindow::Daily, 1_000));
+    let reservation = quotas.reserve(&acme_order_of(600), now).unwrap();
+    let receipt = abp_receipt::ReceiptBuilder::new("metered")
+        .usage_tokens(150, 50)
+        .usage_raw(serde_json::json!({"tenant": "acme"}))
+        .build();
+
+    quotas.settle(reservation, &receipt, now);
+    assert_eq!(quotas.usage(&acme(), QuotaWindow::Daily, now).tokens, 200);
+}
+
+#[tokio::test]
+async fn concurrent_runs_cannot_overshoot_a_nearly_used_quota() {
+    let clock = Arc::new(ManualClock::new(at(2026, 3, 14, 9)));
+    let mut rt = runtime(
+        QuotaEnforcer::new().with_quota(acme(), Quota::tokens(QuotaWindow::Daily, 1_000)),
+        clock,
+    );
+    rt.register_backend(
+        "slow",
+        ScriptedBackend::new("slow").behavior(Behavior::Hang),
+    );
+
+    // The first run is still in flight when the second arrives.
+    let _first = rt.run_streaming("slow", acme_order_of(600)).await.unwrap();
+    assert_quota_error(
+        rt.run_streaming("metered", acme_order_of(600))
+            .await
+            .err()
+            .unwrap(),
+    );
+    run(&rt, acme_order_of(400)).await.unwrap();
+}
+
+#[tokio::test]
+async fn refused_runs_release_their_reservation() {
+    let clock = Arc::new(ManualClock::new(at(2026, 3, 14, 9)));
+    let rt = runtime(
+        QuotaEnforcer::new().with_quota(acme(), Quota::tokens(QuotaWindow::Daily, 1_000)),
+        clock.clone(),
+    );
+
+    assert!(rt.run_streaming("missing", acme_order()).await.is_err());
+    let mut over_budget = acme_order_of(600);
+    over_budget.config.vendor.insert(
+        abp_runtime::budget::MAX_TOKENS_VENDOR_KEY.into(),
+        serde_json::json!(10),
+    );
+    assert!(rt.run_streaming("metered", over_budget).await.is_err());
+    assert_eq!(
+        rt.quotas()
+            .unwrap()
+            .usage(&acme(), QuotaWindow::Daily, clock.now()),
+        QuotaUsage::default()
+    );
+}
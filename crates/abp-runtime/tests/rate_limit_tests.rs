@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for per-backend request and token rate limits.
+
+pub mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use abp_core::clock::{Clock, ManualClock};
+use abp_core::{Receipt, WorkOrder};
+use abp_error::ErrorCode;
+use abp_runtime::config_integration::RuntimeConfig;
+use abp_runtime::rate_limit::{BackendRateLimit, RateLimitMode, RateLimiter};
+use abp_runtime::{Runtime, RuntimeError};
+use chrono::{TimeZone, Utc};
+use common::{ScriptedBackend, clock};
+
+fn runtime(limit: BackendRateLimit, tokens: u64, clock: Arc<ManualClock>) -> Runtime {
+    let mut rt = Runtime::new()
+        .with_clock(clock)
+        .with_backend_rate_limit("metered", limit);
+    rt.register_backend("metered", ScriptedBackend::new("metered").tokens(tokens, 0));
+    rt
+}
+
+fn work_order() -> WorkOrder {
+    common::work_order("spend tokens")
+}
+
+async fn run(rt: &Runtime) -> Result<Receipt, RuntimeError> {
+    common::run(rt, "metered", work_order()).await
+}
+
+fn retry_after_ms(err: &RuntimeError) -> u64 {
+    assert_eq!(err.error_code(), ErrorCode::BackendRateLimited);
+    assert!(err.is_retryable());
+    match err {
+        RuntimeError::Classified(e) => e.context["retry_after_ms"].as_u64().unwrap(),
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[tokio::test]
+async fn rejects_runs_over_requests_per_minute() {
+    let clock = clock();
+    let rt = runtime(
+        BackendRateLimit::new().requests_per_minute(2).reject(),
+        10,
+        clock.clone(),
+    );
+
+    run(&rt).await.unwrap();
+    run(&rt).await.unwrap();
+    let err = run(&rt).await.unwrap_err();
+    assert_eq!(retry_after_ms(&err), 30_000);
+
+    // Half a minute refills one request.
+    clock.advance(Duration::from_secs(30));
+    run(&rt).await.unwrap();
+}
+
+#[tokio::test]
+async fn waits_for_the_limit_by_default() {
+    let clock = clock();
+    let rt = runtime(
+        BackendRateLimit::new().requests_per_minute(1),
+        10,
+        clock.clone(),
+    );
+
+    run(&rt).await.unwrap();
+    let before = clock.now();
+    run(&rt).await.unwrap();
+    assert_eq!(clock.now() - before, chrono::Duration::seconds(60));
+}
+
+#[tokio::test]
+async fn waits_no_longer_than_max_wait() {
+    let clock = clock();
+    let rt = runtime(
+        BackendRateLimit::new()
+            .requests_per_minute(1)
+            .max_wait(Duration::from_secs(10)),
+        10,
+        clock.clone(),
+    );
+
+    run(&rt).await.unwrap();
+    let err = run(&rt).await.unwrap_err();
+    assert_eq!(retry_after_ms(&err), 60_000);
+}
+
+#[tokio::test]
+async fn token_usage_from_receipts_counts_against_the_limit() {
+    let clock = clock();
+    let rt = runtime(
+        BackendRateLimit::new().tokens_per_minute(1_000).reject(),
+        600,
+        clock.clone(),
+    );
+
+    run(&rt).await.unwrap();
+    run(&rt).await.unwrap();
+    // 1 200 tokens used against 1 000 per minute: 200 tokens of debt.
+    let err = run(&rt).await.unwrap_err();
+    assert_eq!(retry_after_ms(&err), 12_000);
+    assert!(rt.rate_limits().limit("other").is_none());
+}
+
+#[tokio::test]
+async fn zero_limits_reject_every_run_without_waiting() {
+    for limit in [
+        BackendRateLimit::new().requests_per_minute(0),
+        BackendRateLimit::new().tokens_per_minute(0),
+    ] {
+        let clock = clock();
+        let rt = runtime(limit, 10, clock.clone());
+        for _ in 0..2 {
+            let err = run(&rt).await.unwrap_err();
+            assert_eq!(err.error_code(), ErrorCode::BackendRateLimited);
+            let RuntimeError::Classified(e) = &err else {
+                panic!("unexpected error: {err}");
+            };
+            assert!(e.message.contains("rate limit of zero"));
+            assert!(!e.context.contains_key("retry_after_ms"));
+        }
+        assert_eq!(
+            clock.now(),
+            Utc.with_ymd_and_hms(2026, 3, 14, 9, 0, 0).unwrap()
+        );
+    }
+}
+
+#[tokio::test]
+async fn runs_refused_before_dispatch_are_refunded() {
+    let clock = clock();
+    let rt = runtime(
+        BackendRateLimit::new().requests_per_minute(1).reject(),
+        10,
+        clock.clone(),
+    );
+
+    // Admitted, then refused when its policy fails to compile.
+    let mut bad = work_order();
+    bad.policy.deny_read = vec!["[invalid".into()];
+    let handle = rt.run_streaming("metered", bad).await.unwrap();
+    drop(handle.events);
+    assert!(matches!(
+        handle.receipt.await.unwrap(),
+        Err(RuntimeError::PolicyFailed(_))
+    ));
+
+    // The refused run gave its request back.
+    run(&rt).await.unwrap();
+    let err = run(&rt).await.unwrap_err();
+    assert_eq!(retry_after_ms(&err), 60_000);
+}
+
+#[test]
+fn oversized_requests_fit_a_full_bucket() {
+    let clock = clock();
+    let limiter = RateLimiter::new();
+    limiter.set("b", BackendRateLimit::new().tokens_per_minute(100));
+
+    assert_eq!(limiter.try_acquire("b", 500, &*clock), Ok(()));
+    assert_eq!(
+        limiter.try_acquire("b", 500, &*clock),
+        Err(Duration::from_secs(300))
+    );
+    assert_eq!(limiter.try_acquire("unlimited", 500, &*clock), Ok(()));
+}
+
+#[test]
+fn limits_come_from_runtime_config() {
+    let limit: BackendRateLimit = serde_json::from_value(serde_json::json!({
+        "requests_per_minute": 60,
+        "tokens_per_minute": 90000,
+        "mode": "reject",
+    }))
+    .unwrap();
+    assert_eq!(limit.mode, RateLimitMode::Reject);
+    assert_eq!(limit.max_wait, None);
+
+    let config = RuntimeConfig::builder()
+        .backend_rate_limit("openai", limit.clone())
+        .build();
+    assert_eq!(config.rate_limit_for("openai"), Some(&limit));
+
+    let rt = Runtime::new().with_rate_limit_config(&config);
+    assert_eq!(rt.rate_limits().limit("openai"), Some(limit));
+}
@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for role-based access checks on the runtime.
+
+use abp_runtime::Runtime;
+use abp_runtime::audit::{AuditAction, AuditLog};
+use abp_runtime::rbac::{AccessDenied, Permission, RbacConfig, check};
+
+fn rbac() -> RbacConfig {
+    RbacConfig {
+        roles: [
+            (
+                "operator".into(),
+                [Permission::Submit, Permission::Cancel].into(),
+            ),
+            ("admin".into(), Permission::ALL.into()),
+        ]
+        .into(),
+        bindings: [
+            ("alice".into(), vec!["operator".into()]),
+            ("root".into(), vec!["admin".into()]),
+        ]
+        .into(),
+        anonymous_roles: vec![],
+    }
+}
+
+#[test]
+fn without_rbac_everything_is_allowed() {
+    let rt = Runtime::new().with_audit_log(AuditLog::in_memory());
+    assert!(rt.rbac().is_none());
+    rt.authorize(None, Permission::Cancel, "run:1").unwrap();
+    // Nothing to audit when access control is off.
+    assert!(rt.audit_log().unwrap().is_empty());
+}
+
+#[test]
+fn roles_grant_permissions() {
+    let rt = Runtime::new().with_rbac(rbac());
+    rt.authorize(Some("alice"), Permission::Submit, "backend:mock")
+        .unwrap();
+    rt.authorize(Some("root"), Permission::ReadReceipts, "receipts")
+        .unwrap();
+
+    let err = rt
+        .authorize(Some("alice"), Permission::ReadReceipts, "receipts")
+        .unwrap_err();
+    assert_eq!(
+        err,
+        AccessDenied {
+            identity: Some("alice".into()),
+            permission: Permission::ReadReceipts,
+            resource: "receipts".into(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "alice is not permitted to read_receipts on receipts"
+    );
+}
+
+#[test]
+fn anonymous_callers_get_anonymous_roles() {
+    let mut config = rbac();
+    assert!(check(Some(&config), None, Permission::Submit, "x").is_err());
+    config.anonymous_roles = vec!["operator".into()];
+    assert!(check(Some(&config), None, Permission::Submit, "x").is_ok());
+    let err = check(Some(&config), None, Permission::ReadReceipts, "x").unwrap_err();
+    assert!(err.to_string().starts_with("anonymous caller"));
+}
+
+#[test]
+fn decisions_are_recorded_in_the_audit_log() {
+    let rt = Runtime::new()
+        .with_rbac(rbac())
+        .with_audit_log(AuditLog::in_memory());
+    rt.authorize(Some("alice"), Permission::Cancel, "run:7")
+        .unwrap();
+    rt.authorize(Some("mallory"), Permission::Submit, "backend:mock")
+        .unwrap_err();
+
+    let entries = rt.audit_log().unwrap().entries();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].actor.as_deref(), Some("alice"));
+    assert_eq!(
+        entries[1].action,
+        AuditAction::AccessChecked {
+            permission: Permission::Submit,
+            resource: "backend:mock".into(),
+            allowed: false,
+        }
+    );
+    let json = serde_json::to_value(&entries[1]).unwrap();
+    assert_eq!(json["action"], "access_checked");
+    assert_eq!(json["permission"], "submit");
+}
@@ -284,3 +284,48 @@ async fn runtime_receipt_uses_builder_hash() {
     let recomputed = abp_receipt::compute_hash(&receipt).unwrap();
     assert_eq!(hash, &recomputed);
 }
+
+// ── 6. Write-ahead journal ─────────────────────────────────────────
+
+#[tokio::test]
+async fn runtime_journal_persists_receipt_and_clears_entry() {
+    use abp_runtime::journal::ReceiptJournal;
+    use abp_runtime::store::ReceiptStore;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let journal = ReceiptJournal::new(tmp.path().join("wal"))
+        .with_store(ReceiptStore::new(tmp.path().join("receipts")));
+    let rt = Runtime::with_default_backends().with_journal(journal);
+
+    let handle = rt
+        .run_streaming("mock", simple_work_order("journalled"))
+        .await
+        .unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+
+    let journal = rt.journal().unwrap();
+    assert!(journal.pending().unwrap().is_empty());
+    let stored = journal.store().unwrap().load(receipt.meta.run_id).unwrap();
+    assert_eq!(stored.receipt_sha256, receipt.receipt_sha256);
+}
+
+// ── 7. Billing attribution ─────────────────────────────────────────
+
+#[tokio::test]
+async fn receipt_records_tenant_and_model() {
+    let rt = Runtime::with_default_backends();
+    let wo = WorkOrderBuilder::new("billed")
+        .root(".")
+        .model("gpt-4o")
+        .submitter(abp_core::submitter::Submitter::new().tenant("acme"))
+        .build();
+
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+
+    assert_eq!(receipt.usage_raw["tenant"], "acme");
+    assert_eq!(receipt.usage_raw["model"], "gpt-4o");
+    assert!(verify_hash(&receipt));
+}
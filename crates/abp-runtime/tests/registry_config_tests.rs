@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for loading backend registrations from a TOML file.
+
+use std::path::PathBuf;
+
+use abp_config::ConfigError;
+use abp_core::{Capability, SupportLevel};
+use abp_dialect::Dialect;
+use abp_runtime::registry::{BackendDecls, DEFAULT_PRIORITY};
+use abp_runtime::{BackendRegistry, Runtime};
+
+const DECLS: &str = r#"
+log_level = "debug"
+
+[backends.fast]
+type = "mock"
+dialect = "open_ai"
+priority = 90
+capabilities = { streaming = "unsupported", tool_bash = "native" }
+
+[backends.local]
+type = "sidecar"
+command = "node"
+args = ["hosts/node/host.js"]
+env = { NODE_ENV = "production" }
+dialect = "claude"
+
+[backends.spare]
+type = "mock"
+"#;
+
+fn write(dir: &tempfile::TempDir, content: &str) -> PathBuf {
+    let path = dir.path().join("backplane.toml");
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn registry_builds_declared_backends_with_overrides() {
+    let dir = tempfile::tempdir().unwrap();
+    let registry = BackendRegistry::from_config(&write(&dir, DECLS)).unwrap();
+    assert_eq!(registry.list(), ["fast", "local", "spare"]);
+
+    let fast = registry.get("fast").unwrap();
+    assert_eq!(fast.identity().id, "mock");
+    let caps = fast.capabilities();
+    assert_eq!(caps[&Capability::Streaming], SupportLevel::Unsupported);
+    assert_eq!(caps[&Capability::ToolBash], SupportLevel::Native);
+    assert_eq!(caps[&Capability::ToolRead], SupportLevel::Emulated);
+
+    let decls = BackendDecls::parse(DECLS).unwrap();
+    assert_eq!(decls.backends["local"].env["NODE_ENV"], "production");
+}
+
+#[tokio::test]
+async fn runtime_registers_backends_and_projection_entries() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut rt = Runtime::new();
+    let names = rt
+        .register_backends_from_config(&write(&dir, DECLS))
+        .unwrap();
+    assert_eq!(names, ["fast", "local", "spare"]);
+    assert_eq!(rt.backend_names(), ["fast", "local", "spare"]);
+
+    {
+        let pm = rt.projection();
+        let pm = pm.as_ref().expect("projection matrix created");
+        let fast = pm.backend_entry("fast").unwrap();
+        assert_eq!(fast.dialect, Dialect::OpenAi);
+        assert_eq!(fast.priority, 90);
+        assert_eq!(
+            fast.capabilities[&Capability::Streaming],
+            SupportLevel::Unsupported
+        );
+        let local = pm.backend_entry("local").unwrap();
+        assert_eq!(local.dialect, Dialect::Claude);
+        assert_eq!(local.priority, DEFAULT_PRIORITY);
+        assert!(pm.backend_entry("spare").is_none());
+    }
+
+    let handle = rt
+        .run_streaming("spare", abp_core::WorkOrderBuilder::new("hi").build())
+        .await
+        .unwrap();
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    assert_eq!(receipt.outcome, abp_core::Outcome::Complete);
+}
+
+#[test]
+fn env_on_non_sidecar_is_rejected() {
+    let err = BackendDecls::parse(
+        r#"
+[backends.remote]
+type = "websocket"
+url = "ws://agents.internal:7070/abp"
+env = { TOKEN = "x" }
+"#,
+    )
+    .unwrap_err();
+    let ConfigError::ValidationError { reasons } = err else {
+        panic!("expected validation error, got {err:?}");
+    };
+    assert_eq!(
+        reasons,
+        ["backend 'remote': env is only supported for sidecars"]
+    );
+}
+
+#[test]
+fn invalid_transport_settings_register_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(
+        &dir,
+        r#"
+[backends.ok]
+type = "mock"
+dialect = "gemini"
+
+[backends.bad]
+type = "grpc"
+endpoint = "localhost:50051"
+"#,
+    );
+    let mut rt = Runtime::new();
+    let err = rt.register_backends_from_config(&path).unwrap_err();
+    assert!(
+        matches!(err, ConfigError::ValidationError { .. }),
+        "{err:?}"
+    );
+    assert!(rt.backend_names().is_empty());
+    assert!(rt.projection().is_none());
+
+    let missing = BackendRegistry::from_config(&dir.path().join("nope.toml"));
+    assert!(matches!(missing, Err(ConfigError::FileNotFound { .. })));
+}
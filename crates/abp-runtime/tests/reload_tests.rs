@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for hot reload of declared backends.
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use abp_core::{Capability, SupportLevel};
+use abp_dialect::Dialect;
+use abp_integrations::MockBackend;
+use abp_runtime::Runtime;
+use abp_runtime::bus::RegistryChange;
+use abp_runtime::registry::BackendDecls;
+use abp_runtime::reload::{BackendReload, BackendWatcher};
+
+const BEFORE: &str = r#"
+[backends.steady]
+type = "mock"
+dialect = "claude"
+priority = 10
+
+[backends.retired]
+type = "mock"
+dialect = "gemini"
+
+[backends.worker]
+type = "sidecar"
+command = "node"
+args = ["host.js"]
+"#;
+
+const AFTER: &str = r#"
+[backends.steady]
+type = "mock"
+dialect = "claude"
+priority = 90
+
+[backends.worker]
+type = "sidecar"
+command = "node"
+args = ["host.js"]
+env = { LOG = "debug" }
+dialect = "codex"
+capabilities = { streaming = "native" }
+
+[backends.fresh]
+type = "mock"
+"#;
+
+fn names(list: &[&str]) -> Vec<String> {
+    list.iter().map(|s| (*s).to_string()).collect()
+}
+
+#[test]
+fn reload_applies_the_diff() {
+    let mut rt = Runtime::new();
+    rt.register_backend("by-hand", MockBackend);
+    let before = BackendDecls::parse(BEFORE).unwrap();
+    let after = BackendDecls::parse(AFTER).unwrap();
+
+    let initial = rt.reload_backends(&BackendDecls::default(), &before);
+    assert_eq!(initial.added, names(&["retired", "steady", "worker"]));
+    assert!(
+        rt.projection()
+            .as_ref()
+            .unwrap()
+            .backend_entry("retired")
+            .is_some()
+    );
+
+    let mut bus = rt.event_bus().subscribe();
+    let report = rt.reload_backends(&before, &after);
+    assert_eq!(
+        report,
+        BackendReload {
+            added: names(&["fresh"]),
+            removed: names(&["retired"]),
+            replaced: names(&["worker"]),
+            reprojected: names(&["steady"]),
+        }
+    );
+    assert_eq!(rt.backend_names(), ["by-hand", "fresh", "steady", "worker"]);
+
+    let pm = rt.projection();
+    let pm = pm.as_ref().unwrap();
+    assert!(pm.backend_entry("retired").is_none());
+    assert!(pm.backend_entry("fresh").is_none());
+    assert_eq!(pm.backend_entry("steady").unwrap().priority, 90);
+    let worker = pm.backend_entry("worker").unwrap();
+    assert_eq!(worker.dialect, Dialect::Codex);
+    assert_eq!(
+        worker.capabilities[&Capability::Streaming],
+        SupportLevel::Native
+    );
+
+    let mut changes = Vec::new();
+    while let Some(ev) = bus.try_recv() {
+        changes.extend(RegistryChange::from_event(&ev));
+    }
+    assert_eq!(
+        changes,
+        [
+            RegistryChange::Deregistered {
+                backend: "retired".into()
+            },
+            RegistryChange::Registered {
+                backend: "fresh".into()
+            },
+            RegistryChange::Registered {
+                backend: "worker".into()
+            },
+        ]
+    );
+
+    assert!(rt.reload_backends(&after, &after).is_empty());
+}
+
+fn write(path: &Path, content: &str) {
+    std::fs::write(path, content).unwrap();
+}
+
+fn wait_for(rt: &Runtime, pred: impl Fn(&[String]) -> bool) -> bool {
+    for _ in 0..50 {
+        if pred(&rt.backend_names()) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    false
+}
+
+#[test]
+fn watcher_follows_the_file_and_skips_invalid_edits() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("backplane.toml");
+    write(&path, BEFORE);
+
+    let rt = Arc::new(Runtime::new());
+    let mut watcher = BackendWatcher::new(&path).poll_interval(Duration::from_millis(20));
+    let initial = watcher.start(Arc::clone(&rt)).unwrap();
+    assert_eq!(initial.added, names(&["retired", "steady", "worker"]));
+    assert!(watcher.is_running());
+
+    std::thread::sleep(Duration::from_millis(50));
+    write(&path, AFTER);
+    assert!(wait_for(&rt, |n| n == ["fresh", "steady", "worker"]));
+
+    std::thread::sleep(Duration::from_millis(50));
+    write(
+        &path,
+        "[backends.broken]\ntype = \"grpc\"\nendpoint = \"nope\"\n",
+    );
+    std::thread::sleep(Duration::from_millis(300));
+    assert_eq!(rt.backend_names(), ["fresh", "steady", "worker"]);
+
+    watcher.stop();
+    assert!(!watcher.is_running());
+}
+
+#[test]
+fn watcher_does_not_start_on_invalid_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("backplane.toml");
+    write(
+        &path,
+        "[backends.x]\ntype = \"mock\"\nenv = { A = \"1\" }\n",
+    );
+
+    let rt = Arc::new(Runtime::new());
+    let mut watcher = BackendWatcher::new(&path);
+    assert!(watcher.start(Arc::clone(&rt)).is_err());
+    assert!(!watcher.is_running());
+    assert!(rt.backend_names().is_empty());
+}
@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for seeded runs and the deterministic replay harness.
+
+pub mod common;
+
+use abp_core::ids::{SeededIdGenerator, UlidGenerator};
+use abp_core::{
+    AgentEventKind, Capability, CapabilityManifest, SupportLevel, WorkOrder, WorkOrderBuilder,
+    WorkspaceMode,
+};
+use abp_integrations::extract_seed;
+use abp_runtime::Runtime;
+use abp_runtime::replay::{
+    DETERMINISM_KEY, DeterminismRecord, ReplayHarness, ReplayMarker, compare_traces,
+    recorded_work_order,
+};
+use common::{ScriptedBackend, event};
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Backend whose output depends only on the seed (or is random without one).
+fn seeded_backend() -> ScriptedBackend {
+    let mut caps = CapabilityManifest::default();
+    caps.insert(Capability::Streaming, SupportLevel::Native);
+    caps.insert(Capability::SeedDeterminism, SupportLevel::Native);
+    ScriptedBackend::new("seeded")
+        .capabilities(caps)
+        .with_trace()
+        .respond_with(|work_order| {
+            let sample = match extract_seed(work_order) {
+                Some(seed) => seed.wrapping_mul(6364136223846793005).to_string(),
+                None => Uuid::new_v4().to_string(),
+            };
+            vec![event(AgentEventKind::AssistantMessage { text: sample })]
+        })
+}
+
+fn runtime() -> Runtime {
+    let mut rt = Runtime::with_default_backends();
+    rt.register_backend("seeded", seeded_backend());
+    rt
+}
+
+fn work_order(seed: Option<u64>) -> WorkOrder {
+    let builder = WorkOrderBuilder::new("replay test").workspace_mode(WorkspaceMode::PassThrough);
+    match seed {
+        Some(s) => builder.seed(s).build(),
+        None => builder.build(),
+    }
+}
+
+#[tokio::test]
+async fn seeded_run_records_determinism_block() {
+    let rt = runtime();
+    let receipt = ReplayHarness::new(&rt, "seeded")
+        .record(work_order(Some(42)))
+        .await
+        .unwrap();
+
+    let record = DeterminismRecord::from_receipt(&receipt).unwrap();
+    assert_eq!(record.seed, 42);
+    assert!(record.seed_honored());
+    assert_eq!(record.run_id, receipt.meta.run_id);
+    assert_eq!(record.trace_len, receipt.trace.len());
+}
+
+#[tokio::test]
+async fn unseeded_run_has_no_determinism_block() {
+    let rt = runtime();
+    let receipt = ReplayHarness::new(&rt, "mock")
+        .record(work_order(None))
+        .await
+        .unwrap();
+    assert!(receipt.usage_raw.get(DETERMINISM_KEY).is_none());
+}
+
+#[tokio::test]
+async fn seeded_replay_reproduces_trace() {
+    let rt = runtime();
+    let report = ReplayHarness::new(&rt, "seeded")
+        .check(work_order(Some(7)))
+        .await
+        .unwrap();
+    assert!(report.is_identical());
+    assert_eq!(report.recorded_fingerprint, report.replayed_fingerprint);
+    report.verify().unwrap();
+}
+
+#[tokio::test]
+async fn unseeded_replay_reports_divergence() {
+    let rt = runtime();
+    let report = ReplayHarness::new(&rt, "seeded")
+        .check(work_order(None))
+        .await
+        .unwrap();
+    let divergence = report.verify().unwrap_err();
+    assert_eq!(divergence.index, 0);
+}
+
+#[tokio::test]
+async fn mock_backend_is_replayable_but_does_not_honor_seed() {
+    let rt = runtime();
+    let report = ReplayHarness::new(&rt, "mock")
+        .check(work_order(Some(1)))
+        .await
+        .unwrap();
+    assert!(report.is_identical());
+    let record = DeterminismRecord::from_receipt(&report.receipt).unwrap();
+    assert!(!record.seed_honored());
+    assert_eq!(report.receipt.usage_raw["seed"], 1);
+}
+
+#[tokio::test]
+async fn seeded_id_generator_gives_replays_identical_ids() {
+    let wo_id = Uuid::from_u128(7);
+    let mut receipts = Vec::new();
+    for _ in 0..2 {
+        let rt = runtime().with_id_generator(Arc::new(SeededIdGenerator::new(3)));
+        let wo = WorkOrderBuilder::new("replay test")
+            .id(wo_id)
+            .workspace_mode(WorkspaceMode::PassThrough)
+            .seed(3)
+            .build();
+        receipts.push(ReplayHarness::new(&rt, "seeded").record(wo).await.unwrap());
+    }
+    assert_eq!(receipts[0].meta.run_id, receipts[1].meta.run_id);
+    assert_eq!(receipts[0].meta.work_order_id, wo_id);
+    let (a, b) = (
+        DeterminismRecord::from_receipt(&receipts[0]).unwrap(),
+        DeterminismRecord::from_receipt(&receipts[1]).unwrap(),
+    );
+    assert_eq!(a.run_id, b.run_id);
+    assert_eq!(a.trace_fingerprint, b.trace_fingerprint);
+}
+
+#[tokio::test]
+async fn ulid_run_ids_sort_by_start() {
+    let rt = runtime().with_id_generator(Arc::new(UlidGenerator::new()));
+    let harness = ReplayHarness::new(&rt, "mock");
+    let first = harness.record(work_order(None)).await.unwrap();
+    let second = harness.record(work_order(None)).await.unwrap();
+    assert!(first.meta.run_id < second.meta.run_id);
+    assert!(first.meta.run_id.to_string() < second.meta.run_id.to_string());
+}
+
+#[tokio::test]
+async fn runtime_receipts_record_their_work_order() {
+    let rt = runtime();
+    let wo = work_order(Some(5));
+    let receipt = ReplayHarness::new(&rt, "mock")
+        .record(wo.clone())
+        .await
+        .unwrap();
+    let recorded = recorded_work_order(&receipt).unwrap();
+    assert_eq!(recorded.id, wo.id);
+    assert_eq!(recorded.task, wo.task);
+    assert_eq!(extract_seed(&recorded), Some(5));
+}
+
+#[tokio::test]
+async fn replaying_a_receipt_reemits_its_trace_without_the_backend() {
+    let recorder = abp_stream::EventRecorder::new();
+    let rt = runtime().with_stream_pipeline(
+        abp_stream::StreamPipelineBuilder::new()
+            .with_recorder(recorder.clone())
+            .build(),
+    );
+    // An unseeded run of this backend is random, so only playback can
+    // reproduce it.
+    let original = ReplayHarness::new(&rt, "seeded")
+        .record(work_order(None))
+        .await
+        .unwrap();
+    recorder.clear();
+
+    let mut handle = rt.replay(&original).await.unwrap();
+    let mut streamed = Vec::new();
+    while let Some(ev) = handle.events.next().await {
+        streamed.push(ev);
+    }
+    let replayed = handle.receipt.await.unwrap().unwrap();
+
+    assert!(compare_traces(&original.trace, &streamed).is_none());
+    assert!(compare_traces(&original.trace, &replayed.trace).is_none());
+    assert_eq!(recorder.len(), original.trace.len());
+    assert_ne!(replayed.meta.run_id, original.meta.run_id);
+    assert_eq!(replayed.meta.work_order_id, original.meta.work_order_id);
+    assert_eq!(replayed.outcome, original.outcome);
+    assert_eq!(
+        ReplayMarker::from_receipt(&replayed),
+        Some(ReplayMarker {
+            source_run_id: original.meta.run_id,
+            source_receipt_sha256: original.receipt_sha256.clone(),
+        })
+    );
+    assert!(ReplayMarker::from_receipt(&original).is_none());
+    assert_eq!(
+        replayed.receipt_sha256.as_deref(),
+        Some(abp_core::receipt_hash(&replayed).unwrap().as_str())
+    );
+}
+
+#[tokio::test]
+async fn replay_needs_a_recorded_work_order() {
+    let rt = runtime();
+    let receipt = abp_receipt::ReceiptBuilder::new("seeded").build();
+    let err = rt.replay(&receipt).await.err().unwrap();
+    assert_eq!(
+        err.error_code(),
+        abp_error::ErrorCode::ContractInvalidReceipt
+    );
+}
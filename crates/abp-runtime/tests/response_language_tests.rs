@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Response-language lowering in the runtime.
+
+pub mod common;
+
+use abp_core::ir::{IrConversation, IrMessage, IrRole};
+use abp_core::{
+    Capability, CapabilityManifest, FidelityPolicy, Receipt, SupportLevel, WorkOrder,
+    WorkOrderBuilder, WorkspaceMode,
+};
+use abp_error::ErrorCode;
+use abp_integrations::{extract_conversation, extract_response_language};
+use abp_runtime::Runtime;
+use common::ScriptedBackend;
+use tokio_stream::StreamExt;
+
+/// A streaming backend, with native response-language support if `native`.
+fn recording(native: bool) -> ScriptedBackend {
+    let mut caps = CapabilityManifest::new();
+    caps.insert(Capability::Streaming, SupportLevel::Native);
+    if native {
+        caps.insert(Capability::ResponseLanguage, SupportLevel::Native);
+    }
+    ScriptedBackend::new("recording").capabilities(caps)
+}
+
+fn order(task: &str) -> WorkOrderBuilder {
+    WorkOrderBuilder::new(task)
+        .root(".")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .response_language("fr_ca")
+}
+
+async fn run(backend: &ScriptedBackend, wo: WorkOrder) -> (WorkOrder, Receipt) {
+    let mut rt = Runtime::new();
+    rt.register_backend("recording", backend.clone());
+    let handle = rt.run_streaming("recording", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    let seen = backend.work_orders().pop().unwrap();
+    (seen, receipt)
+}
+
+#[tokio::test]
+async fn task_only_orders_get_the_instruction_in_the_task() {
+    let backend = recording(false);
+    let (seen, receipt) = run(&backend, order("Summarize the changelog").build()).await;
+
+    assert!(seen.task.starts_with("Summarize the changelog\n\n"));
+    assert!(seen.task.contains("French (fr-CA)"), "{}", seen.task);
+
+    let applied = &receipt.usage_raw["emulation"]["applied"];
+    assert_eq!(applied[0]["capability"], "response_language");
+    assert_eq!(applied[0]["strategy"]["type"], "system_prompt_injection");
+    assert!(abp_receipt::verify_hash(&receipt));
+}
+
+#[tokio::test]
+async fn conversations_get_the_instruction_in_the_system_prompt() {
+    let conv = IrConversation::new()
+        .push(IrMessage::text(IrRole::System, "Be brief."))
+        .push(IrMessage::text(IrRole::User, "What changed?"));
+    let backend = recording(false);
+    let (seen, _) = run(&backend, order("What changed?").conversation(conv).build()).await;
+
+    assert_eq!(seen.task, "What changed?");
+    let conv = extract_conversation(&seen).unwrap();
+    assert_eq!(conv.messages.len(), 2);
+    let system = conv.messages[0].text_content();
+    assert!(system.starts_with("Be brief."));
+    assert!(system.contains("French (fr-CA)"), "{system}");
+}
+
+#[tokio::test]
+async fn native_backends_receive_the_normalized_tag_untouched() {
+    let backend = recording(true);
+    let (seen, receipt) = run(&backend, order("Summarize the changelog").build()).await;
+
+    assert_eq!(seen.task, "Summarize the changelog");
+    assert_eq!(extract_response_language(&seen).as_deref(), Some("fr-CA"));
+    assert!(receipt.usage_raw.get("emulation").is_none());
+}
+
+#[tokio::test]
+async fn strict_orders_reject_an_emulated_response_language() {
+    let backend = recording(false);
+    let mut rt = Runtime::new();
+    rt.register_backend("recording", backend.clone());
+    let wo = order("Summarize the changelog")
+        .fidelity_policy(FidelityPolicy::Strict)
+        .build();
+
+    let err = match rt.run_streaming("recording", wo).await {
+        Err(e) => e,
+        Ok(_) => panic!("strict policy must reject an emulated response language"),
+    };
+    assert_eq!(err.error_code(), ErrorCode::MappingLossyConversion);
+    assert!(err.to_string().contains("ResponseLanguage"), "{err}");
+    assert_eq!(backend.calls(), 0);
+}
+
+#[tokio::test]
+async fn strict_orders_run_on_native_backends() {
+    let backend = recording(true);
+    let wo = order("Summarize the changelog")
+        .fidelity_policy(FidelityPolicy::Strict)
+        .build();
+    let (seen, _) = run(&backend, wo).await;
+    assert_eq!(extract_response_language(&seen).as_deref(), Some("fr-CA"));
+}
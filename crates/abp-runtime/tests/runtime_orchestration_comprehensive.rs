@@ -940,6 +940,17 @@ fn run_metrics_record_failure() {
     assert_eq!(snap.total_events, 2);
 }
 
+#[test]
+fn run_metrics_record_event_delivery() {
+    let m = RunMetrics::new();
+    m.record_event_delivery(3, 1, 2);
+    m.record_event_delivery(1, 0, 0);
+    let snap = m.snapshot();
+    assert_eq!(snap.duplicate_events, 4);
+    assert_eq!(snap.event_gaps, 1);
+    assert_eq!(snap.missing_events, 2);
+}
+
 #[test]
 fn run_metrics_average_duration() {
     let m = RunMetrics::new();
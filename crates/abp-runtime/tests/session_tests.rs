@@ -0,0 +1,205 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for conversation sessions and fork lineage.
+
+use abp_core::ir::{IrMessage, IrRole};
+use abp_core::{WorkOrderBuilder, WorkspaceMode};
+use abp_runtime::Runtime;
+use abp_runtime::session::{ForkPoint, Session, SessionError, SessionManager, SessionRecord};
+use tokio_stream::StreamExt;
+
+fn three_turns() -> Session {
+    let mut session = Session::new();
+    session.push(IrMessage::text(IrRole::User, "fix the bug"));
+    session.push(IrMessage::text(IrRole::Assistant, "patch the parser"));
+    session.push(IrMessage::text(IrRole::User, "tests still fail"));
+    session
+}
+
+#[test]
+fn fork_keeps_prefix_and_leaves_parent_untouched() {
+    let parent = three_turns();
+    let mut child = parent.fork(1).unwrap();
+    child.push(IrMessage::text(IrRole::Assistant, "rewrite the lexer"));
+
+    assert_ne!(child.id(), parent.id());
+    assert_eq!(child.parent_id(), Some(parent.id()));
+    assert_eq!(child.conversation().len(), 2);
+    assert_eq!(
+        child.conversation().messages[0],
+        parent.conversation().messages[0]
+    );
+    assert_eq!(
+        child.conversation().messages[1].text_content(),
+        "rewrite the lexer"
+    );
+    assert_eq!(parent.conversation().len(), 3);
+    assert!(parent.lineage().is_empty());
+}
+
+#[test]
+fn fork_at_end_and_out_of_range() {
+    let parent = three_turns();
+    assert_eq!(parent.fork(3).unwrap().conversation().len(), 3);
+    assert_eq!(parent.fork(0).unwrap().conversation().len(), 0);
+    assert_eq!(
+        parent.fork(4).unwrap_err(),
+        SessionError::ForkOutOfRange { index: 4, len: 3 }
+    );
+}
+
+#[test]
+fn nested_forks_accumulate_lineage() {
+    let root = three_turns();
+    let child = root.fork(2).unwrap();
+    let grandchild = child.fork(1).unwrap();
+
+    assert_eq!(
+        grandchild.lineage(),
+        [
+            ForkPoint {
+                parent_id: root.id(),
+                message_index: 2,
+            },
+            ForkPoint {
+                parent_id: child.id(),
+                message_index: 1,
+            },
+        ]
+    );
+    assert_eq!(grandchild.parent_id(), Some(child.id()));
+}
+
+#[tokio::test]
+async fn receipts_record_session_lineage() {
+    let rt = Runtime::with_default_backends();
+    let root = three_turns();
+    let mut fork = root.fork(1).unwrap();
+
+    let mut wo = WorkOrderBuilder::new("try another approach")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    fork.tag(&mut wo);
+    assert_eq!(SessionRecord::from_work_order(&wo), Some(fork.record()));
+
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+
+    let record = SessionRecord::from_receipt(&receipt).unwrap();
+    assert_eq!(record.session_id, fork.id());
+    assert_eq!(
+        record.lineage,
+        [ForkPoint {
+            parent_id: root.id(),
+            message_index: 1,
+        }]
+    );
+    assert!(abp_receipt::verify_hash(&receipt));
+
+    fork.record_receipt(&receipt);
+    assert_eq!(fork.runs(), [receipt.meta.run_id]);
+    assert_eq!(
+        fork.conversation()
+            .last_message()
+            .map(|m| (m.role, m.text_content())),
+        Some((IrRole::Assistant, receipt_last_text(&receipt)))
+    );
+}
+
+#[test]
+fn tag_attaches_history_and_task() {
+    let session = three_turns();
+    let mut wo = WorkOrderBuilder::new("try the other branch").build();
+    session.tag(&mut wo);
+
+    let conv = abp_integrations::extract_conversation(&wo).unwrap();
+    assert_eq!(conv.len(), 4);
+    assert_eq!(conv.messages[..3], session.conversation().messages[..]);
+    assert_eq!(
+        conv.last_message().map(|m| (m.role, m.text_content())),
+        Some((IrRole::User, "try the other branch".to_string()))
+    );
+
+    // A task that repeats the last user turn is not duplicated.
+    let mut wo = WorkOrderBuilder::new("tests still fail").build();
+    session.tag(&mut wo);
+    assert_eq!(
+        abp_integrations::extract_conversation(&wo).unwrap().len(),
+        3
+    );
+}
+
+#[tokio::test]
+async fn untagged_runs_have_no_session() {
+    let rt = Runtime::with_default_backends();
+    let wo = WorkOrderBuilder::new("no session")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    assert!(SessionRecord::from_receipt(&receipt).is_none());
+}
+
+fn receipt_last_text(receipt: &abp_core::Receipt) -> String {
+    receipt
+        .trace
+        .iter()
+        .rev()
+        .find_map(|e| match &e.kind {
+            abp_core::AgentEventKind::AssistantMessage { text } => Some(text.clone()),
+            _ => None,
+        })
+        .unwrap()
+}
+
+#[test]
+fn manager_prepare_appends_task_and_attaches_history() {
+    let sessions = SessionManager::new();
+    let id = sessions.insert(three_turns());
+
+    let mut wo = WorkOrderBuilder::new("try again").build();
+    sessions.prepare(id, &mut wo).unwrap();
+
+    let session = sessions.get(id).unwrap();
+    assert_eq!(session.conversation().len(), 4);
+    assert_eq!(SessionRecord::from_work_order(&wo), Some(session.record()));
+    let sent = abp_backend_core::extract_conversation(&wo).unwrap();
+    assert_eq!(&sent, session.conversation());
+}
+
+#[test]
+fn manager_rejects_unknown_sessions() {
+    let sessions = SessionManager::new();
+    let id = uuid::Uuid::new_v4();
+    let mut wo = WorkOrderBuilder::new("hi").build();
+    assert_eq!(
+        sessions.prepare(id, &mut wo),
+        Err(SessionError::UnknownSession { id })
+    );
+    assert!(sessions.is_empty());
+}
+
+#[tokio::test]
+async fn manager_records_replies_from_receipts() {
+    let sessions = SessionManager::new();
+    let id = sessions.create();
+    let mut wo = WorkOrderBuilder::new("hello")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    sessions.prepare(id, &mut wo).unwrap();
+
+    let rt = Runtime::with_default_backends();
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    sessions.record_receipt(&receipt).unwrap();
+
+    let session = sessions.get(id).unwrap();
+    assert_eq!(session.runs(), [receipt.meta.run_id]);
+    assert!(session.conversation().len() > 1);
+    assert_eq!(
+        session.conversation().last_message().unwrap().role,
+        IrRole::Assistant
+    );
+}
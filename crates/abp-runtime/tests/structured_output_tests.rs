@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Structured output enforcement in `RuntimePipeline`.
+
+pub mod common;
+
+use std::sync::{Arc, Mutex};
+
+use abp_core::{AgentEventKind, Outcome, WorkOrder, WorkOrderBuilder, WorkspaceMode};
+use abp_runtime::pipeline::{RuntimePipeline, SchemaValidation};
+use common::{ScriptedBackend, event};
+use serde_json::json;
+
+fn order() -> WorkOrder {
+    let mut wo = WorkOrderBuilder::new("Name a colour")
+        .root(".")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    wo.config.vendor.insert(
+        "response_format".into(),
+        json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "colour",
+                "schema": {
+                    "type": "object",
+                    "properties": {"name": {"type": "string"}},
+                    "required": ["name"]
+                }
+            }
+        }),
+    );
+    wo
+}
+
+/// Replies with the next of `replies` on each run.
+fn backend(replies: &[&'static str]) -> ScriptedBackend {
+    let replies = Mutex::new(replies.to_vec());
+    ScriptedBackend::new("scripted").respond_with(move |_| {
+        let reply = replies.lock().unwrap().remove(0);
+        vec![event(AgentEventKind::AssistantMessage {
+            text: reply.into(),
+        })]
+    })
+}
+
+#[tokio::test]
+async fn invalid_output_is_repaired_and_recorded() {
+    let backend = backend(&[r#"{"colour": "red"}"#, "```json\n{\"name\": \"red\"}\n```"]);
+    let pipeline = RuntimePipeline::new("scripted", Arc::new(backend.clone()));
+    let (stages, receipt) = pipeline.execute(order()).await;
+    let receipt = receipt.unwrap();
+
+    let validation = SchemaValidation::from_receipt(&receipt).unwrap();
+    assert_eq!(validation.schema, "colour");
+    assert!(validation.valid);
+    assert_eq!(validation.attempts, 2);
+    assert_eq!(receipt.outcome, Outcome::Complete);
+    assert_eq!(
+        abp_receipt::compute_hash(&receipt).unwrap(),
+        receipt.receipt_sha256.clone().unwrap()
+    );
+
+    let tasks: Vec<_> = backend
+        .work_orders()
+        .into_iter()
+        .map(|wo| wo.task)
+        .collect();
+    assert!(tasks[1].starts_with("Name a colour"));
+    assert!(tasks[1].contains(r#"{"colour": "red"}"#));
+    assert!(tasks[1].contains("\"name\" is a required property"));
+    assert!(
+        stages
+            .iter()
+            .any(|s| s.name == "validate_output" && s.success)
+    );
+}
+
+#[tokio::test]
+async fn output_still_invalid_after_repairs_is_partial() {
+    let backend = backend(&["not json"]);
+    let pipeline = RuntimePipeline::new("scripted", Arc::new(backend.clone())).with_max_repairs(0);
+    let (stages, receipt) = pipeline.execute(order()).await;
+    let receipt = receipt.unwrap();
+
+    let validation = SchemaValidation::from_receipt(&receipt).unwrap();
+    assert!(!validation.valid);
+    assert_eq!(validation.attempts, 1);
+    assert!(validation.errors[0].contains("not valid JSON"));
+    assert_eq!(receipt.outcome, Outcome::Partial);
+    assert_eq!(backend.calls(), 1);
+    let stage = stages.iter().find(|s| s.name == "validate_output").unwrap();
+    assert!(!stage.success);
+}
+
+#[tokio::test]
+async fn orders_without_response_format_skip_validation() {
+    let mut wo = order();
+    wo.config.vendor.remove("response_format");
+    let pipeline = RuntimePipeline::new("scripted", Arc::new(backend(&["plain text"])));
+    let (stages, receipt) = pipeline.execute(wo).await;
+    let receipt = receipt.unwrap();
+    assert!(SchemaValidation::from_receipt(&receipt).is_none());
+    assert!(stages.iter().all(|s| s.name != "validate_output"));
+}
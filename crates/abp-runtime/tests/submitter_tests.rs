@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for carrying submitter provenance into run receipts.
+
+pub mod common;
+
+use abp_core::submitter::{SUBMITTER_KEY, Submitter};
+use abp_core::{Receipt, WorkOrder, WorkOrderBuilder, WorkspaceMode};
+use abp_runtime::Runtime;
+use tokio_stream::StreamExt;
+
+async fn run(wo: WorkOrder) -> Receipt {
+    let rt = Runtime::with_default_backends();
+    let handle = rt.run_streaming("mock", wo).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    handle.receipt.await.unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn submitter_is_recorded_in_hashed_receipt() {
+    let submitter = Submitter::new()
+        .user("alice")
+        .client("review-bot", Some("1.4.0"))
+        .git_commit("9fceb02");
+    let wo = WorkOrderBuilder::new("review")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .submitter(submitter.clone())
+        .build();
+    let receipt = run(wo).await;
+
+    assert_eq!(Submitter::from_receipt(&receipt), Some(submitter));
+    assert_eq!(
+        receipt.receipt_sha256.as_deref(),
+        Some(abp_core::receipt_hash(&receipt).unwrap().as_str())
+    );
+}
+
+#[tokio::test]
+async fn anonymous_work_order_records_no_submitter() {
+    let wo = WorkOrderBuilder::new("review")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    let receipt = run(wo).await;
+    assert!(receipt.usage_raw.get(SUBMITTER_KEY).is_none());
+}
+
+#[tokio::test]
+async fn non_object_raw_usage_is_kept_alongside_the_submitter() {
+    let mut rt = Runtime::new();
+    rt.register_backend(
+        "opaque",
+        common::ScriptedBackend::new("opaque").usage_raw(serde_json::json!("opaque usage")),
+    );
+    let wo = WorkOrderBuilder::new("review")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .submitter(Submitter::new().user("alice").tenant("acme"))
+        .build();
+    let receipt = common::run(&rt, "opaque", wo).await.unwrap();
+
+    assert_eq!(receipt.usage_raw["original"], "opaque usage");
+    assert_eq!(receipt.usage_raw["tenant"], "acme");
+    assert_eq!(
+        Submitter::from_receipt(&receipt).unwrap().user.as_deref(),
+        Some("alice")
+    );
+}
@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the run summary event at the end of the stream.
+
+pub mod common;
+
+use std::collections::BTreeMap;
+
+use abp_core::{AgentEvent, AgentEventKind, Outcome, UsageNormalized, WorkOrder};
+use abp_error::ErrorCode;
+use abp_runtime::{Runtime, summary};
+use common::{Behavior, ScriptedBackend, event};
+use tokio_stream::StreamExt;
+
+fn tool_call(name: &str) -> AgentEvent {
+    event(AgentEventKind::ToolCall {
+        tool_name: name.into(),
+        tool_use_id: None,
+        parent_tool_use_id: None,
+        input: serde_json::json!({}),
+    })
+}
+
+fn file_changed(path: &str) -> AgentEvent {
+    event(AgentEventKind::FileChanged {
+        path: path.into(),
+        summary: "edited".into(),
+    })
+}
+
+/// Backend that calls tools, edits files, and hits a policy denial, then
+/// either returns a receipt or crashes.
+fn scripted(behavior: Behavior) -> ScriptedBackend {
+    ScriptedBackend::new("scripted")
+        .events([
+            tool_call("read"),
+            tool_call("edit"),
+            tool_call("read"),
+            file_changed("src/b.rs"),
+            file_changed("src/a.rs"),
+            file_changed("src/b.rs"),
+            event(AgentEventKind::Error {
+                message: "write to .env denied".into(),
+                error_code: Some(ErrorCode::PolicyDenied),
+            }),
+        ])
+        .usage(UsageNormalized {
+            input_tokens: Some(120),
+            output_tokens: Some(30),
+            ..Default::default()
+        })
+        .with_trace()
+        .behavior(behavior)
+}
+
+fn runtime(behavior: Behavior) -> Runtime {
+    let mut rt = Runtime::new().with_run_summary(true);
+    rt.register_backend("scripted", scripted(behavior));
+    rt
+}
+
+fn work_order() -> WorkOrder {
+    common::work_order("tidy up")
+}
+
+#[tokio::test]
+async fn stream_ends_with_a_summary_of_the_receipt() {
+    let rt = runtime(Behavior::Succeed);
+    let mut bus = rt.event_bus().subscribe();
+    let handle = rt.run_streaming("scripted", work_order()).await.unwrap();
+    let run_id = handle.run_id;
+    let events: Vec<AgentEvent> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+
+    let AgentEventKind::RunSummary {
+        outcome,
+        usage,
+        tool_calls,
+        files_changed,
+        policy_denials,
+        ..
+    } = &events.last().unwrap().kind
+    else {
+        panic!("last event is not a summary: {:?}", events.last());
+    };
+    assert_eq!(*outcome, Outcome::Complete);
+    assert_eq!(usage.input_tokens, Some(120));
+    assert_eq!(usage.output_tokens, Some(30));
+    assert_eq!(
+        tool_calls,
+        &BTreeMap::from([("edit".to_string(), 1), ("read".to_string(), 2)])
+    );
+    assert_eq!(files_changed, &["src/a.rs", "src/b.rs"]);
+    assert_eq!(*policy_denials, 1);
+
+    // The summary is derived from the receipt, not part of it.
+    assert!(
+        !receipt
+            .trace
+            .iter()
+            .any(|e| matches!(e.kind, AgentEventKind::RunSummary { .. }))
+    );
+
+    let published = bus.try_recv().expect("summary published on the bus");
+    assert_eq!(summary::run_id(&published), Some(run_id));
+}
+
+#[tokio::test]
+async fn failed_runs_are_summarized_from_observed_events() {
+    let rt = runtime(Behavior::Crash);
+    let handle = rt.run_streaming("scripted", work_order()).await.unwrap();
+    let events: Vec<AgentEvent> = handle.events.collect().await;
+    assert!(handle.receipt.await.unwrap().is_err());
+
+    match &events.last().unwrap().kind {
+        AgentEventKind::RunSummary {
+            outcome,
+            usage,
+            tool_calls,
+            files_changed,
+            ..
+        } => {
+            assert_eq!(*outcome, Outcome::Failed);
+            assert_eq!(usage.input_tokens, None);
+            assert_eq!(tool_calls.values().sum::<u64>(), 3);
+            assert_eq!(files_changed.len(), 2);
+        }
+        other => panic!("last event is not a summary: {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn summaries_are_off_by_default() {
+    let mut rt = Runtime::new();
+    rt.register_backend("scripted", scripted(Behavior::Succeed));
+    assert!(!rt.run_summary());
+    let handle = rt.run_streaming("scripted", work_order()).await.unwrap();
+    let events: Vec<AgentEvent> = handle.events.collect().await;
+    assert_eq!(events.len(), 7);
+    assert!(
+        !events
+            .iter()
+            .any(|e| matches!(e.kind, AgentEventKind::RunSummary { .. }))
+    );
+}
@@ -0,0 +1,221 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for runtime-executed tool calls and their turn barrier.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use abp_core::{
+    AgentEvent, AgentEventKind, BackendIdentity, CapabilityManifest, Receipt, WorkOrder,
+    WorkOrderBuilder, WorkspaceMode,
+};
+use abp_integrations::{Backend, MockBackend};
+use abp_runtime::Runtime;
+use abp_runtime::tools::{ResultOrder, ToolDispatcher};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// Emits one turn of tool calls, then finishes like the mock backend.
+struct Caller {
+    calls: Vec<(&'static str, Value)>,
+}
+
+#[async_trait]
+impl Backend for Caller {
+    fn identity(&self) -> BackendIdentity {
+        MockBackend.identity()
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        MockBackend.capabilities()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        for (i, (name, input)) in self.calls.iter().enumerate() {
+            let call = AgentEvent {
+                ts: chrono::Utc::now(),
+                kind: AgentEventKind::ToolCall {
+                    tool_name: (*name).into(),
+                    tool_use_id: Some(format!("tu-{i}")),
+                    parent_tool_use_id: None,
+                    input: input.clone(),
+                },
+                ext: None,
+            };
+            let _ = events_tx.send(call).await;
+        }
+        MockBackend.run(run_id, work_order, events_tx).await
+    }
+}
+
+/// Sleeps for `input.ms` and echoes the input back.
+fn sleeper() -> ToolDispatcher {
+    ToolDispatcher::new().register_fn("sleep", |input: Value| async move {
+        let ms = input["ms"].as_u64().unwrap_or(0);
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+        Ok(input)
+    })
+}
+
+async fn run(calls: Vec<(&'static str, Value)>, tools: ToolDispatcher) -> Vec<AgentEvent> {
+    let mut rt = Runtime::new().with_tool_dispatcher(tools);
+    rt.register_backend("caller", Caller { calls });
+    let wo = WorkOrderBuilder::new("use tools")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    let handle = rt.run_streaming("caller", wo).await.unwrap();
+    let events: Vec<_> = handle.events.collect().await;
+    handle.receipt.await.unwrap().unwrap();
+    events
+}
+
+fn results(events: &[AgentEvent]) -> Vec<(String, Value, bool)> {
+    events
+        .iter()
+        .filter_map(|e| match &e.kind {
+            AgentEventKind::ToolResult {
+                tool_use_id,
+                output,
+                is_error,
+                ..
+            } => Some((tool_use_id.clone().unwrap(), output.clone(), *is_error)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn a_turn_runs_concurrently_and_results_keep_call_order() {
+    let calls = vec![
+        ("sleep", json!({"ms": 300})),
+        ("sleep", json!({"ms": 10})),
+        ("sleep", json!({"ms": 300})),
+    ];
+    let started = Instant::now();
+    let events = run(calls, sleeper()).await;
+    assert!(
+        started.elapsed() < Duration::from_millis(800),
+        "calls ran sequentially: {:?}",
+        started.elapsed()
+    );
+
+    let ids: Vec<_> = results(&events).into_iter().map(|r| r.0).collect();
+    assert_eq!(ids, ["tu-0", "tu-1", "tu-2"]);
+
+    // Barrier: every result comes after the last call and before the
+    // backend's next event.
+    let kinds: Vec<&str> = events
+        .iter()
+        .map(|e| match e.kind {
+            AgentEventKind::ToolCall { .. } => "call",
+            AgentEventKind::ToolResult { .. } => "result",
+            _ => "other",
+        })
+        .collect();
+    let first_other = kinds.iter().position(|k| *k == "other").unwrap();
+    assert_eq!(
+        &kinds[..first_other],
+        ["call", "call", "call", "result", "result", "result"]
+    );
+}
+
+#[tokio::test]
+async fn completion_order_emits_the_fast_call_first() {
+    let calls = vec![("sleep", json!({"ms": 200})), ("sleep", json!({"ms": 1}))];
+    let events = run(calls, sleeper().result_order(ResultOrder::Completion)).await;
+    let ids: Vec<_> = results(&events).into_iter().map(|r| r.0).collect();
+    assert_eq!(ids, ["tu-1", "tu-0"]);
+}
+
+#[tokio::test]
+async fn per_tool_timeouts_and_failures_become_error_results() {
+    let tools = sleeper()
+        .with_timeout(Duration::from_secs(5))
+        .tool_timeout("sleep", Duration::from_millis(50))
+        .register_fn("fail", |_| async { Err("disk full".to_string()) });
+    let calls = vec![
+        ("sleep", json!({"ms": 2000})),
+        ("fail", json!({})),
+        ("unregistered", json!({})),
+    ];
+    let started = Instant::now();
+    let events = run(calls, tools).await;
+    assert!(started.elapsed() < Duration::from_millis(1500));
+
+    let results = results(&events);
+    assert_eq!(results.len(), 2, "unregistered tools are left alone");
+    assert_eq!(results[0].0, "tu-0");
+    assert!(results[0].2);
+    assert_eq!(results[0].1["error"], "tool 'sleep' timed out after 50 ms");
+    assert_eq!(
+        results[1],
+        ("tu-1".into(), json!({"error": "disk full"}), true)
+    );
+}
+
+#[tokio::test]
+async fn dispatch_reports_each_registered_call_once() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let seen = count.clone();
+    let tools = ToolDispatcher::new().register_fn("count", move |_| {
+        let seen = seen.clone();
+        async move {
+            seen.fetch_add(1, Ordering::SeqCst);
+            Ok(json!(null))
+        }
+    });
+    assert!(tools.handles("count"));
+    assert_eq!(tools.tool_names().collect::<Vec<_>>(), ["count"]);
+
+    let events = run(vec![("count", json!({})), ("count", json!({}))], tools).await;
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    assert_eq!(results(&events).len(), 2);
+}
+
+#[tokio::test]
+async fn suite_tools_report_side_effects_ahead_of_their_result() {
+    let dir = tempfile::tempdir().unwrap();
+    let suite = abp_tools::ToolSuite::new(dir.path(), &abp_core::PolicyProfile::default()).unwrap();
+    let tools = ToolDispatcher::new().register_suite(suite);
+    let call = |name: &str, input: Value| AgentEvent {
+        ts: chrono::Utc::now(),
+        kind: AgentEventKind::ToolCall {
+            tool_name: name.into(),
+            tool_use_id: Some(format!("tu-{name}")),
+            parent_tool_use_id: None,
+            input,
+        },
+        ext: None,
+    };
+    let events = tools
+        .dispatch(
+            &[
+                call("Write", json!({"path": "a.txt", "content": "hi"})),
+                call("unknown", json!({})),
+            ],
+            chrono::Utc::now,
+        )
+        .await;
+
+    assert_eq!(events.len(), 2);
+    assert!(matches!(
+        &events[0].kind,
+        AgentEventKind::FileChanged { path, .. } if path == "a.txt"
+    ));
+    assert!(matches!(
+        &events[1].kind,
+        AgentEventKind::ToolResult { tool_name, is_error: false, .. } if tool_name == "Write"
+    ));
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+        "hi"
+    );
+}
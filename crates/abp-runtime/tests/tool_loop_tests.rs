@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the tool-use loop driver around single-shot backends.
+
+pub mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use abp_core::ir::{IrContentBlock, IrConversation, IrRole};
+use abp_core::{
+    AgentEvent, AgentEventKind, Outcome, Receipt, WorkOrder, WorkOrderBuilder, WorkspaceMode,
+};
+use abp_integrations::extract_conversation;
+use abp_runtime::Runtime;
+use abp_runtime::tool_loop::{ToolLoop, ToolLoopRecord, ToolLoopStop};
+use abp_runtime::tools::ToolDispatcher;
+use common::{ScriptedBackend, event};
+use serde_json::{Value, json};
+use tokio_stream::StreamExt;
+
+/// Answers once per call: asks for a tool until the conversation holds a
+/// tool result (or always, with `insist`), then reports the result.
+fn single_shot(insist: bool) -> ScriptedBackend {
+    ScriptedBackend::new("single-shot")
+        .tokens(10, 4)
+        .respond_with(move |work_order| {
+            let conversation = extract_conversation(work_order).unwrap();
+            let result = conversation
+                .messages
+                .last()
+                .filter(|m| m.role == IrRole::Tool)
+                .and_then(|m| match &m.content[0] {
+                    IrContentBlock::ToolResult { content, .. } => match &content[0] {
+                        IrContentBlock::Text { text } => Some(text.clone()),
+                        _ => None,
+                    },
+                    _ => None,
+                });
+            let text = match result {
+                Some(sum) if !insist => format!("The sum is {sum}."),
+                _ => "Let me add.\n<tool_call>\n{\"name\": \"add\", \"arguments\": {\"a\": 2, \"b\": 3}}\n</tool_call>"
+                    .into(),
+            };
+            vec![event(AgentEventKind::AssistantMessage { text })]
+        })
+}
+
+/// The conversations `backend` was sent, in call order.
+fn conversations(backend: &ScriptedBackend) -> Vec<IrConversation> {
+    backend
+        .work_orders()
+        .iter()
+        .map(|wo| extract_conversation(wo).unwrap())
+        .collect()
+}
+
+fn adder(count: Arc<AtomicUsize>) -> ToolDispatcher {
+    ToolDispatcher::new().register_fn("add", move |input: Value| {
+        let count = Arc::clone(&count);
+        async move {
+            count.fetch_add(1, Ordering::SeqCst);
+            Ok(json!(
+                input["a"].as_i64().unwrap() + input["b"].as_i64().unwrap()
+            ))
+        }
+    })
+}
+
+async fn run(rt: Runtime, wo: WorkOrder) -> (Vec<AgentEvent>, Receipt) {
+    let handle = rt.run_streaming("looping", wo).await.unwrap();
+    let events: Vec<_> = handle.events.collect().await;
+    (events, handle.receipt.await.unwrap().unwrap())
+}
+
+fn order() -> WorkOrder {
+    WorkOrderBuilder::new("What is 2 + 3?")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build()
+}
+
+#[tokio::test]
+async fn tool_calls_are_run_and_fed_back_until_end_turn() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let backend = single_shot(false);
+    let mut rt = Runtime::new().with_tool_dispatcher(adder(Arc::clone(&count)));
+    rt.register_backend(
+        "looping",
+        ToolLoop::new(backend.clone(), adder(Arc::clone(&count))),
+    );
+    let (events, receipt) = run(rt, order()).await;
+
+    let kinds: Vec<_> = events
+        .iter()
+        .filter_map(|e| match &e.kind {
+            AgentEventKind::AssistantMessage { text } => Some(format!("say {text}")),
+            AgentEventKind::ToolCall { tool_name, .. } => Some(format!("call {tool_name}")),
+            AgentEventKind::ToolResult { output, .. } => Some(format!("result {output}")),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            "say Let me add.",
+            "call add",
+            "result 5",
+            "say The sum is 5."
+        ]
+    );
+    // The runtime's own dispatcher leaves the loop's calls alone.
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    let record = ToolLoopRecord::from_receipt(&receipt).unwrap();
+    assert_eq!(record.turns, 2);
+    assert_eq!(record.tool_calls, 1);
+    assert_eq!(record.stop, ToolLoopStop::EndTurn);
+    assert_eq!(receipt.outcome, Outcome::Complete);
+    assert_eq!(receipt.usage.input_tokens, Some(20));
+    assert!(abp_receipt::verify_hash(&receipt));
+
+    let conversations = conversations(&backend);
+    let first = &conversations[0];
+    assert_eq!(first.messages[0].role, IrRole::System);
+    assert!(first.messages[0].text_content().contains("## add"));
+    let second = &conversations[1];
+    let roles: Vec<_> = second.messages.iter().map(|m| m.role).collect();
+    assert_eq!(
+        roles,
+        [
+            IrRole::System,
+            IrRole::User,
+            IrRole::Assistant,
+            IrRole::Tool
+        ]
+    );
+    assert_eq!(second.messages[2].tool_use_blocks().len(), 1);
+}
+
+#[tokio::test]
+async fn exhausted_turn_budget_is_partial() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let backend = single_shot(true);
+    let mut rt = Runtime::new();
+    rt.register_backend("looping", ToolLoop::new(backend, adder(Arc::clone(&count))));
+    let mut wo = order();
+    wo.config.max_turns = Some(3);
+    let (_, receipt) = run(rt, wo).await;
+
+    let record = ToolLoopRecord::from_receipt(&receipt).unwrap();
+    assert_eq!(record.turns, 3);
+    assert_eq!(record.stop, ToolLoopStop::BudgetExhausted);
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+    assert_eq!(receipt.outcome, Outcome::Partial);
+}
+
+#[tokio::test]
+async fn calls_to_unknown_tools_get_error_results() {
+    let backend = single_shot(false);
+    let mut rt = Runtime::new();
+    rt.register_backend(
+        "looping",
+        ToolLoop::new(backend.clone(), ToolDispatcher::new()).max_turns(2),
+    );
+    let (events, _) = run(rt, order()).await;
+
+    let result = events.iter().find_map(|e| match &e.kind {
+        AgentEventKind::ToolResult {
+            output, is_error, ..
+        } => Some((output.clone(), *is_error)),
+        _ => None,
+    });
+    let (output, is_error) = result.unwrap();
+    assert!(is_error);
+    assert!(output["error"].as_str().unwrap().contains("not available"));
+    // Without registered tools nothing is described to the model.
+    let conversations = conversations(&backend);
+    assert_eq!(conversations[0].messages[0].role, IrRole::User);
+}
@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for host tools registered with the runtime.
+
+use std::sync::{Arc, Mutex};
+
+use abp_core::ir::{IrContentBlock, IrRole};
+use abp_core::{
+    AgentEvent, AgentEventKind, BackendIdentity, Capability, CapabilityManifest, Receipt,
+    SupportLevel, WorkOrder, WorkOrderBuilder, WorkspaceMode,
+};
+use abp_integrations::{Backend, extract_conversation, extract_tools};
+use abp_receipt::ReceiptBuilder;
+use abp_runtime::Runtime;
+use abp_runtime::tool_loop::ToolLoopRecord;
+use abp_runtime::tool_registry::ToolRegistry;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use uuid::Uuid;
+
+/// A native tool-use backend: calls `weather` with `input` until it sees a
+/// tool result, then answers with that result.
+#[derive(Clone)]
+struct NativeTools {
+    input: Value,
+    orders: Arc<Mutex<Vec<WorkOrder>>>,
+}
+
+impl NativeTools {
+    fn new(input: Value) -> Self {
+        Self {
+            input,
+            orders: Arc::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for NativeTools {
+    fn identity(&self) -> BackendIdentity {
+        BackendIdentity {
+            id: "native-tools".into(),
+            backend_version: None,
+            adapter_version: None,
+        }
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        [
+            (Capability::Streaming, SupportLevel::Native),
+            (Capability::ToolUse, SupportLevel::Native),
+        ]
+        .into()
+    }
+
+    async fn run(
+        &self,
+        _run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> anyhow::Result<Receipt> {
+        let result = extract_conversation(&work_order).and_then(|c| {
+            c.messages
+                .iter()
+                .rev()
+                .find(|m| m.role == IrRole::Tool)
+                .and_then(|m| match &m.content[0] {
+                    IrContentBlock::ToolResult { content, .. } => match &content[0] {
+                        IrContentBlock::Text { text } => Some(text.clone()),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+        });
+        self.orders.lock().unwrap().push(work_order);
+        let kind = match result {
+            Some(text) => AgentEventKind::AssistantMessage {
+                text: format!("Forecast: {text}"),
+            },
+            None => AgentEventKind::ToolCall {
+                tool_name: "weather".into(),
+                tool_use_id: Some("call_1".into()),
+                parent_tool_use_id: None,
+                input: self.input.clone(),
+            },
+        };
+        let _ = events_tx
+            .send(AgentEvent {
+                ts: chrono::Utc::now(),
+                kind,
+                ext: None,
+            })
+            .await;
+        Ok(ReceiptBuilder::new("native-tools").build())
+    }
+}
+
+fn registry() -> ToolRegistry {
+    ToolRegistry::new().register_fn(
+        "weather",
+        "Current weather for a city.",
+        json!({
+            "type": "object",
+            "properties": {"city": {"type": "string"}},
+            "required": ["city"]
+        }),
+        |input| async move {
+            Ok(json!(format!(
+                "sunny in {}",
+                input["city"].as_str().unwrap()
+            )))
+        },
+    )
+}
+
+fn order() -> WorkOrder {
+    WorkOrderBuilder::new("Weather in Paris?")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build()
+}
+
+async fn run(backend: NativeTools, wo: WorkOrder) -> (Vec<AgentEvent>, Receipt) {
+    let mut rt = Runtime::new().with_tool_registry(registry());
+    rt.register_backend("native", backend);
+    let handle = rt.run_streaming("native", wo).await.unwrap();
+    let events: Vec<_> = handle.events.collect().await;
+    (events, handle.receipt.await.unwrap().unwrap())
+}
+
+fn texts(events: &[AgentEvent]) -> Vec<String> {
+    events
+        .iter()
+        .filter_map(|e| match &e.kind {
+            AgentEventKind::AssistantMessage { text } => Some(text.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn host_tools_are_offered_run_and_fed_back() {
+    let backend = NativeTools::new(json!({"city": "Paris"}));
+    let (events, receipt) = run(backend.clone(), order()).await;
+
+    assert_eq!(texts(&events), ["Forecast: sunny in Paris"]);
+    assert_eq!(
+        ToolLoopRecord::from_receipt(&receipt).unwrap().tool_calls,
+        1
+    );
+
+    let orders = backend.orders.lock().unwrap();
+    assert_eq!(orders.len(), 2);
+    let tools = extract_tools(&orders[0]);
+    assert_eq!(tools[0].name, "weather");
+    assert_eq!(tools[0].parameters["required"], json!(["city"]));
+    // Native tool use reads the definitions, so the prompt is left alone.
+    let first = extract_conversation(&orders[0]).unwrap();
+    assert_eq!(first.messages[0].role, IrRole::User);
+}
+
+#[tokio::test]
+async fn input_violating_the_schema_gets_an_error_result() {
+    let backend = NativeTools::new(json!({"town": "Paris"}));
+    let (events, _) = run(backend, order()).await;
+
+    let (output, is_error) = events
+        .iter()
+        .find_map(|e| match &e.kind {
+            AgentEventKind::ToolResult {
+                output, is_error, ..
+            } => Some((output.to_string(), *is_error)),
+            _ => None,
+        })
+        .unwrap();
+    assert!(is_error);
+    assert!(
+        output.contains("\\\"city\\\" is a required property"),
+        "{output}"
+    );
+}
+
+#[tokio::test]
+async fn passthrough_runs_are_left_alone() {
+    let backend = NativeTools::new(json!({"city": "Paris"}));
+    let mut wo = order();
+    wo.config
+        .vendor
+        .insert("abp".into(), json!({"mode": "passthrough"}));
+    let (events, receipt) = run(backend.clone(), wo).await;
+
+    assert!(ToolLoopRecord::from_receipt(&receipt).is_none());
+    assert!(
+        !events
+            .iter()
+            .any(|e| matches!(e.kind, AgentEventKind::ToolResult { .. }))
+    );
+    let orders = backend.orders.lock().unwrap();
+    assert_eq!(orders.len(), 1);
+    assert!(extract_tools(&orders[0]).is_empty());
+}
@@ -0,0 +1,85 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for validating and scoping backend vendor namespaces before dispatch.
+
+pub mod common;
+
+use abp_core::{WorkOrder, WorkOrderBuilder, WorkspaceMode};
+use abp_error::ErrorCode;
+use abp_integrations::{VendorConfig, VendorNamespace};
+use abp_runtime::{Runtime, RuntimeError};
+use common::ScriptedBackend;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[allow(dead_code)]
+struct OpenAiKnobs {
+    temperature: Option<f64>,
+}
+
+impl VendorConfig for OpenAiKnobs {
+    const NAMESPACE: &'static str = "openai";
+}
+
+fn work_order(vendor: Value) -> WorkOrder {
+    let mut wo = WorkOrderBuilder::new("task")
+        .workspace_mode(WorkspaceMode::PassThrough)
+        .build();
+    wo.config.vendor = serde_json::from_value(vendor).unwrap();
+    wo
+}
+
+/// A runtime with a backend that claims the `openai` namespace.
+fn runtime() -> (Runtime, ScriptedBackend) {
+    let backend =
+        ScriptedBackend::new("recorder").vendor_namespace(VendorNamespace::of::<OpenAiKnobs>());
+    let mut rt = Runtime::new();
+    rt.register_backend("recorder", backend.clone());
+    (rt, backend)
+}
+
+#[tokio::test]
+async fn backend_only_sees_its_own_namespace() {
+    let (rt, seen) = runtime();
+    let wo = work_order(json!({
+        "abp": {"mode": "mapped"},
+        "openai": {"temperature": 0.3},
+        "claude": {"max_tokens": 10},
+        "gemini.topP": 0.9,
+    }));
+    let handle = rt.run_streaming("recorder", wo).await.unwrap();
+    handle.receipt.await.unwrap().unwrap();
+
+    let keys: Vec<_> = seen.work_orders()[0]
+        .config
+        .vendor
+        .keys()
+        .cloned()
+        .collect();
+    assert_eq!(keys, ["abp", "openai"]);
+}
+
+#[tokio::test]
+async fn invalid_namespace_is_rejected_before_dispatch() {
+    let (rt, seen) = runtime();
+    let wo = work_order(json!({"openai": {"temperature": 0.3, "top_k": 5}}));
+    let Err(RuntimeError::Classified(err)) = rt.run_streaming("recorder", wo).await else {
+        panic!("expected a validation error");
+    };
+    assert_eq!(err.code, ErrorCode::ValidationFailed);
+    assert!(
+        err.message.contains("invalid vendor.openai config"),
+        "{err}"
+    );
+    assert_eq!(err.context["namespace"], json!("openai"));
+    assert_eq!(seen.calls(), 0);
+}
+
+#[tokio::test]
+async fn other_backends_knobs_are_not_validated() {
+    let (rt, _) = runtime();
+    let wo = work_order(json!({"claude": {"anything": true}}));
+    let handle = rt.run_streaming("recorder", wo).await.unwrap();
+    handle.receipt.await.unwrap().unwrap();
+}
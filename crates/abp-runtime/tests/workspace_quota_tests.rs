@@ -0,0 +1,130 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for per-run workspace disk quotas.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use abp_core::{
+    AgentEvent, BackendIdentity, CapabilityManifest, Receipt, WorkOrder, WorkOrderBuilder,
+    WorkspaceMode,
+};
+use abp_error::ErrorCode;
+use abp_integrations::Backend;
+use abp_runtime::{Runtime, RuntimeError};
+use abp_workspace::quota::WorkspaceQuota;
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Backend that writes `bytes` into the workspace, then idles for `linger`.
+#[derive(Debug, Clone)]
+struct WritingBackend {
+    bytes: usize,
+    linger: Duration,
+}
+
+#[async_trait]
+impl Backend for WritingBackend {
+    fn identity(&self) -> BackendIdentity {
+        BackendIdentity {
+            id: "writer".into(),
+            backend_version: None,
+            adapter_version: None,
+        }
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        CapabilityManifest::default()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        _events_tx: mpsc::Sender<AgentEvent>,
+    ) -> Result<Receipt> {
+        let out = Path::new(&work_order.workspace.root).join("output.bin");
+        std::fs::write(out, vec![0u8; self.bytes])?;
+        tokio::time::sleep(self.linger).await;
+        Ok(abp_receipt::ReceiptBuilder::new("writer")
+            .run_id(run_id)
+            .work_order_id(work_order.id)
+            .build())
+    }
+}
+
+fn runtime(bytes: usize, linger: Duration, quota: u64) -> Runtime {
+    let mut rt = Runtime::new().with_workspace_quota(WorkspaceQuota::new(quota));
+    rt.register_backend("writer", WritingBackend { bytes, linger });
+    rt
+}
+
+fn work_order(source: &Path, mode: WorkspaceMode) -> WorkOrder {
+    WorkOrderBuilder::new("fill the disk")
+        .root(source.to_string_lossy())
+        .workspace_mode(mode)
+        .build()
+}
+
+async fn run(rt: &Runtime, wo: WorkOrder) -> Result<Receipt, RuntimeError> {
+    let handle = rt.run_streaming("writer", wo).await?;
+    drop(handle.events);
+    handle.receipt.await.unwrap()
+}
+
+fn assert_quota_error(err: RuntimeError) {
+    assert_eq!(err.error_code(), ErrorCode::ExecutionWorkspaceError);
+    assert!(!err.is_retryable());
+    assert!(err.to_string().contains("quota exceeded"), "{err}");
+}
+
+#[tokio::test]
+async fn run_within_quota_succeeds_and_records_usage() {
+    let src = tempfile::tempdir().unwrap();
+    std::fs::write(src.path().join("input.txt"), "hello").unwrap();
+    let rt = runtime(100, Duration::ZERO, 10_000);
+
+    run(&rt, work_order(src.path(), WorkspaceMode::Staged))
+        .await
+        .unwrap();
+    let snap = rt.metrics().snapshot();
+    assert_eq!(snap.peak_workspace_bytes, 105);
+    assert_eq!(snap.quota_exceeded_runs, 0);
+}
+
+#[tokio::test]
+async fn quota_is_checked_when_backend_finishes() {
+    let src = tempfile::tempdir().unwrap();
+    let rt = runtime(4096, Duration::ZERO, 1024);
+
+    let err = run(&rt, work_order(src.path(), WorkspaceMode::Staged))
+        .await
+        .unwrap_err();
+    assert_quota_error(err);
+    assert_eq!(rt.metrics().snapshot().quota_exceeded_runs, 1);
+}
+
+#[tokio::test]
+async fn quota_violation_aborts_running_backend() {
+    let src = tempfile::tempdir().unwrap();
+    let rt = runtime(4096, Duration::from_secs(60), 1024);
+
+    let start = Instant::now();
+    let err = run(&rt, work_order(src.path(), WorkspaceMode::Staged))
+        .await
+        .unwrap_err();
+    assert_quota_error(err);
+    assert!(start.elapsed() < Duration::from_secs(30));
+}
+
+#[tokio::test]
+async fn pass_through_workspace_is_not_metered() {
+    let src = tempfile::tempdir().unwrap();
+    let rt = runtime(4096, Duration::ZERO, 1024);
+
+    run(&rt, work_order(src.path(), WorkspaceMode::PassThrough))
+        .await
+        .unwrap();
+    assert_eq!(rt.metrics().snapshot().peak_workspace_bytes, 0);
+}
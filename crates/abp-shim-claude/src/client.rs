@@ -329,13 +329,7 @@ impl AnthropicClient {
         &self,
         request: &MessagesRequest,
     ) -> std::result::Result<MessagesResponse, crate::error::ClaudeShimError> {
-        use crate::error::ClaudeShimError;
-
-        if request.messages.is_empty() {
-            return Err(ClaudeShimError::InvalidRequest(
-                "messages must not be empty".into(),
-            ));
-        }
+        crate::validate::validate_request(request)?;
 
         if let Some(ref handler) = self.handler {
             return handler(request);
@@ -370,15 +364,10 @@ impl AnthropicClient {
         &self,
         request: &MessagesRequest,
     ) -> std::result::Result<crate::streaming::MessageStream, crate::error::ClaudeShimError> {
-        use crate::error::ClaudeShimError;
         use crate::streaming::MessageStream;
         use crate::types::{ClaudeUsage, ContentBlock, MessageDeltaBody, StreamDelta};
 
-        if request.messages.is_empty() {
-            return Err(ClaudeShimError::InvalidRequest(
-                "messages must not be empty".into(),
-            ));
-        }
+        crate::validate::validate_request(request)?;
 
         if let Some(ref handler) = self.stream_handler {
             let events = handler(request)?;
@@ -10,13 +10,14 @@
 
 use std::collections::BTreeMap;
 
+use abp_core::ir::{IrConversation, IrToolChoice, IrToolDefinition};
 use abp_core::{AgentEvent, AgentEventKind, Receipt, RuntimeConfig, WorkOrder, WorkOrderBuilder};
 use abp_sdk_types::Dialect;
 use serde_json::json;
 
 use crate::types::{
-    ClaudeContent, ClaudeTool, ClaudeUsage, ContentBlock, ErrorResponse, MessageDeltaBody,
-    MessagesRequest, MessagesResponse, StreamDelta, StreamEvent,
+    ClaudeContent, ClaudeTool, ClaudeToolChoice, ClaudeUsage, ContentBlock, ErrorResponse,
+    MessageDeltaBody, MessagesRequest, MessagesResponse, StreamDelta, StreamEvent,
 };
 
 // ---------------------------------------------------------------------------
@@ -26,12 +27,18 @@ use crate::types::{
 /// Convert a Claude Messages API request into an ABP [`WorkOrder`].
 ///
 /// Maps the Claude-specific fields into the canonical ABP contract:
-/// - Messages are inspected to extract the last user text as the task description.
+/// - Messages are inspected to extract the last user text as the task description;
+///   the full history is attached as an IR conversation.
 /// - The system prompt is stored in `vendor["system"]`.
 /// - Model, temperature, top_p, max_tokens, and stream are preserved in
 ///   `config.vendor`.
-/// - Tools are stored as a JSON array in `vendor["tools"]`.
+/// - Tools are stored as a JSON array in `vendor["tools"]`; tools and
+///   tool choice are also set on the work order in IR form.
 /// - The dialect is recorded as `vendor["dialect"] = "claude"`.
+/// - The projected input token count is recorded as
+///   `vendor["abp"]["projected_input_tokens"]` (see [`abp_tokenize::project`]).
+/// - Capabilities the content needs (tools, images) are added to
+///   `requirements` (see [`abp_capability::infer`]).
 #[must_use]
 pub fn to_work_order(req: &MessagesRequest) -> WorkOrder {
     let task = extract_task(req);
@@ -95,10 +102,64 @@ pub fn to_work_order(req: &MessagesRequest) -> WorkOrder {
         ..Default::default()
     };
 
-    WorkOrderBuilder::new(task)
+    let mut builder = WorkOrderBuilder::new(task)
         .model(&req.model)
         .config(config)
-        .build()
+        .conversation(request_to_ir(req));
+    if let Some(ref tools) = req.tools {
+        builder = builder.tools(tools.iter().map(tool_to_ir).collect());
+    }
+    if let Some(ref tool_choice) = req.tool_choice {
+        builder = builder.tool_choice(tool_choice_to_ir(tool_choice));
+    }
+    let mut work_order = builder.build();
+    abp_tokenize::project(&mut work_order);
+    abp_capability::infer::infer_requirements(&mut work_order);
+    work_order
+}
+
+/// Convert the system prompt and messages of a request into an [`IrConversation`].
+///
+/// Block content is handed to the Claude SDK lowering as its JSON encoding,
+/// which the lowering expands back into IR content blocks. The shim's
+/// request types do not carry `cache_control`, so the conversation has no
+/// cache breakpoints.
+#[must_use]
+pub fn request_to_ir(req: &MessagesRequest) -> IrConversation {
+    let messages: Vec<abp_claude_sdk::dialect::ClaudeMessage> = req
+        .messages
+        .iter()
+        .map(|m| abp_claude_sdk::dialect::ClaudeMessage {
+            role: m.role.clone(),
+            content: match &m.content {
+                ClaudeContent::Text(text) => text.clone(),
+                ClaudeContent::Blocks(blocks) => serde_json::to_string(blocks).unwrap_or_default(),
+            },
+        })
+        .collect();
+    abp_claude_sdk::lowering::to_ir(&messages, req.system.as_deref())
+}
+
+/// Convert a [`ClaudeTool`] into an IR tool definition.
+#[must_use]
+pub fn tool_to_ir(tool: &ClaudeTool) -> IrToolDefinition {
+    IrToolDefinition {
+        name: tool.name.clone(),
+        description: tool.description.clone().unwrap_or_default(),
+        parameters: tool.input_schema.clone(),
+    }
+}
+
+/// Convert a [`ClaudeToolChoice`] into the IR tool-choice policy.
+///
+/// Claude's `any` (must use some tool) maps to [`IrToolChoice::Required`].
+#[must_use]
+pub fn tool_choice_to_ir(choice: &ClaudeToolChoice) -> IrToolChoice {
+    match choice {
+        ClaudeToolChoice::Auto {} => IrToolChoice::Auto,
+        ClaudeToolChoice::Any {} => IrToolChoice::Required,
+        ClaudeToolChoice::Tool { name } => IrToolChoice::Tool { name: name.clone() },
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -436,7 +497,8 @@ pub fn build_response(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{ClaudeMessage, ClaudeToolChoice, ImageSource};
+    use crate::types::{ClaudeMessage, ImageSource};
+    use abp_core::ir::IrRole;
     use abp_core::{AgentEvent, AgentEventKind, Outcome, ReceiptBuilder};
     use chrono::Utc;
     use serde_json::json;
@@ -658,6 +720,11 @@ mod tests {
         let tools = wo.config.vendor.get("tools").unwrap();
         assert!(tools.is_array());
         assert_eq!(tools.as_array().unwrap().len(), 1);
+        assert_eq!(wo.config.vendor["abp"]["tools"][0]["name"], "read_file");
+        assert_eq!(
+            wo.config.vendor["abp"]["tools"][0]["description"],
+            "Read a file"
+        );
     }
 
     #[test]
@@ -666,6 +733,26 @@ mod tests {
         req.tool_choice = Some(ClaudeToolChoice::Auto {});
         let wo = to_work_order(&req);
         assert!(wo.config.vendor.contains_key("tool_choice"));
+        assert_eq!(
+            wo.config.vendor["abp"]["tool_choice"],
+            json!({"type": "auto"})
+        );
+    }
+
+    #[test]
+    fn tool_choice_any_maps_to_required() {
+        assert_eq!(
+            tool_choice_to_ir(&ClaudeToolChoice::Any {}),
+            IrToolChoice::Required
+        );
+        assert_eq!(
+            tool_choice_to_ir(&ClaudeToolChoice::Tool {
+                name: "bash".into()
+            }),
+            IrToolChoice::Tool {
+                name: "bash".into()
+            }
+        );
     }
 
     // ── 5. Content block types ──────────────────────────────────────────
@@ -1235,6 +1322,22 @@ mod tests {
         assert_eq!(msgs.as_array().unwrap().len(), 3);
         // Task comes from last user message
         assert_eq!(wo.task, "second");
+
+        let conv: IrConversation =
+            serde_json::from_value(wo.config.vendor["abp"]["conversation"].clone()).unwrap();
+        let turns: Vec<_> = conv
+            .messages
+            .iter()
+            .map(|m| (m.role, m.text_content()))
+            .collect();
+        assert_eq!(
+            turns,
+            [
+                (IrRole::User, "first".to_string()),
+                (IrRole::Assistant, "reply".to_string()),
+                (IrRole::User, "second".to_string()),
+            ]
+        );
     }
 
     // ── 16. Edge: assistant message as last ─────────────────────────────
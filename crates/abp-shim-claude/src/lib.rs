@@ -11,20 +11,28 @@ pub mod convert;
 pub mod error;
 /// Message request builder and API handle.
 pub mod messages;
+/// Anthropic-compatible HTTP frontend (`POST /v1/messages`).
+pub mod server;
 /// SSE streaming adapter.
 pub mod streaming;
 pub mod types;
+/// Client-side request validation.
+pub mod validate;
 
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use abp_claude_sdk::dialect::{
     self, ClaudeConfig, ClaudeContentBlock, ClaudeImageSource, ClaudeMessage, ClaudeResponse,
     ClaudeStreamDelta, ClaudeStreamEvent, ClaudeUsage, ThinkingConfig,
 };
-use abp_core::{AgentEvent, AgentEventKind, WorkOrderBuilder};
+use abp_core::{AgentEvent, AgentEventKind, Receipt, WorkOrderBuilder};
+use abp_runtime::{RunHandle, Runtime};
+use convert::{tool_choice_to_ir, tool_to_ir};
 use serde::{Deserialize, Serialize};
 use tokio_stream::Stream;
+use types::{ClaudeTool, ClaudeToolChoice};
 
 // ---------------------------------------------------------------------------
 // Error type
@@ -79,6 +87,9 @@ pub enum Role {
 }
 
 /// A content block within a message — mirrors Anthropic content block types.
+///
+/// `cache_control` annotations are not modelled and are ignored when a
+/// request is parsed; see [`types`] for where prompt-caching breakpoints go.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentBlock {
@@ -173,6 +184,12 @@ pub struct MessageRequest {
     /// Whether to stream the response.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stream: Option<bool>,
+    /// Tools the model may call, each with an `input_schema` JSON Schema.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<ClaudeTool>>,
+    /// How the model should choose among `tools`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<ClaudeToolChoice>,
 }
 
 /// Token usage in a response.
@@ -436,6 +453,16 @@ pub fn request_to_claude(req: &MessageRequest) -> abp_claude_sdk::dialect::Claud
         system: req.system.clone(),
         messages,
         thinking: req.thinking.clone(),
+        tools: req
+            .tools
+            .iter()
+            .flatten()
+            .map(|tool| dialect::tool_def_from_ir(&tool_to_ir(tool)))
+            .collect(),
+        tool_choice: req
+            .tool_choice
+            .as_ref()
+            .and_then(|choice| dialect::tool_choice_from_ir(&tool_choice_to_ir(choice))),
     }
 }
 
@@ -529,6 +556,91 @@ fn stream_delta_from_claude(delta: &ClaudeStreamDelta) -> StreamDelta {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Conversion: Shim types ↔ wire types
+// ---------------------------------------------------------------------------
+
+/// Convert a shim `ContentBlock` to its [`types::ContentBlock`] wire form.
+#[must_use]
+pub fn content_block_to_wire(block: &ContentBlock) -> types::ContentBlock {
+    match block {
+        ContentBlock::Text { text } => types::ContentBlock::Text { text: text.clone() },
+        ContentBlock::ToolUse { id, name, input } => types::ContentBlock::ToolUse {
+            id: id.clone(),
+            name: name.clone(),
+            input: input.clone(),
+        },
+        ContentBlock::ToolResult {
+            tool_use_id,
+            content,
+            is_error,
+        } => types::ContentBlock::ToolResult {
+            tool_use_id: tool_use_id.clone(),
+            content: content.clone().unwrap_or_default(),
+            is_error: *is_error,
+        },
+        ContentBlock::Thinking {
+            thinking,
+            signature,
+        } => types::ContentBlock::Thinking {
+            thinking: thinking.clone(),
+            signature: signature.clone(),
+        },
+        ContentBlock::Image { source } => types::ContentBlock::Image {
+            source: match source {
+                ImageSource::Base64 { media_type, data } => types::ImageSource::Base64 {
+                    media_type: media_type.clone(),
+                    data: data.clone(),
+                },
+                ImageSource::Url { url } => types::ImageSource::Url { url: url.clone() },
+            },
+        },
+    }
+}
+
+/// Convert a `MessageRequest` to the [`types::MessagesRequest`] body sent on
+/// the wire, so it can be checked with [`validate::validate_request`].
+#[must_use]
+pub fn request_to_wire(req: &MessageRequest) -> types::MessagesRequest {
+    types::MessagesRequest {
+        model: req.model.clone(),
+        messages: req
+            .messages
+            .iter()
+            .map(|m| types::ClaudeMessage {
+                role: match m.role {
+                    Role::User => "user".into(),
+                    Role::Assistant => "assistant".into(),
+                },
+                content: types::ClaudeContent::Blocks(
+                    m.content.iter().map(content_block_to_wire).collect(),
+                ),
+            })
+            .collect(),
+        max_tokens: req.max_tokens,
+        system: req.system.clone(),
+        temperature: req.temperature,
+        top_p: None,
+        top_k: None,
+        stream: req.stream,
+        stop_sequences: req.stop_sequences.clone(),
+        tools: req.tools.clone(),
+        tool_choice: req.tool_choice.clone(),
+        thinking: req.thinking.as_ref().map(|t| types::ThinkingConfig {
+            thinking_type: t.thinking_type.clone(),
+            budget_tokens: t.budget_tokens,
+        }),
+    }
+}
+
+/// Check `req` against the Messages API's request constraints.
+fn validate(req: &MessageRequest) -> Result<(), ShimError> {
+    validate::validate_request(&request_to_wire(req)).map_err(|e| match e {
+        error::ClaudeShimError::InvalidRequest(msg) => ShimError::InvalidRequest(msg),
+        other => ShimError::Internal(other.to_string()),
+    })
+}
+
 // ---------------------------------------------------------------------------
 // ABP pipeline: request → WorkOrder → Receipt → response
 // ---------------------------------------------------------------------------
@@ -569,8 +681,17 @@ pub fn request_to_work_order(req: &MessageRequest) -> abp_core::WorkOrder {
         };
         builder = builder.config(config);
     }
+    if let Some(tools) = &req.tools {
+        builder = builder.tools(tools.iter().map(tool_to_ir).collect());
+    }
+    if let Some(choice) = &req.tool_choice {
+        builder = builder.tool_choice(tool_choice_to_ir(choice));
+    }
 
-    builder.build()
+    let mut work_order = builder.build();
+    abp_tokenize::project(&mut work_order);
+    abp_capability::infer::infer_requirements(&mut work_order);
+    work_order
 }
 
 /// Synthesize a `MessageResponse` from ABP agent events (mock pipeline).
@@ -663,6 +784,398 @@ pub fn response_from_events(
     }
 }
 
+/// Convert a runtime [`Receipt`] into a `MessageResponse`.
+///
+/// Content and stop reason come from the receipt trace (see
+/// [`response_from_events`]), usage from `usage_raw`, and the message id
+/// from the run id.
+#[must_use]
+pub fn response_from_receipt(receipt: &Receipt, model: &str) -> MessageResponse {
+    MessageResponse {
+        id: format!("msg_{}", receipt.meta.run_id.as_simple()),
+        usage: receipt_usage(receipt),
+        ..response_from_events(&receipt.trace, model, None)
+    }
+}
+
+/// Token usage reported in a receipt's `usage_raw`.
+fn receipt_usage(receipt: &Receipt) -> Usage {
+    let usage = convert::usage_from_raw(&receipt.usage_raw);
+    Usage {
+        input_tokens: usage.input_tokens,
+        output_tokens: usage.output_tokens,
+        cache_creation_input_tokens: usage.cache_creation_input_tokens,
+        cache_read_input_tokens: usage.cache_read_input_tokens,
+    }
+}
+
+/// Expand a complete `MessageResponse` into the canonical streaming sequence.
+///
+/// Emits `message_start` (with empty content), one start/delta/stop triple
+/// per content block, `message_delta` carrying the stop reason and usage,
+/// and `message_stop`.
+#[must_use]
+pub fn stream_events_from_response(response: &MessageResponse) -> Vec<StreamEvent> {
+    let mut events = vec![StreamEvent::MessageStart {
+        message: MessageResponse {
+            content: vec![],
+            stop_reason: None,
+            stop_sequence: None,
+            usage: Usage {
+                output_tokens: 0,
+                ..response.usage.clone()
+            },
+            ..response.clone()
+        },
+    }];
+
+    for (index, block) in (0u32..).zip(&response.content) {
+        let (start, deltas) = match block {
+            ContentBlock::Text { text } => (
+                ContentBlock::Text {
+                    text: String::new(),
+                },
+                vec![StreamDelta::TextDelta { text: text.clone() }],
+            ),
+            ContentBlock::Thinking {
+                thinking,
+                signature,
+            } => {
+                let mut deltas = vec![StreamDelta::ThinkingDelta {
+                    thinking: thinking.clone(),
+                }];
+                if let Some(signature) = signature {
+                    deltas.push(StreamDelta::SignatureDelta {
+                        signature: signature.clone(),
+                    });
+                }
+                (
+                    ContentBlock::Thinking {
+                        thinking: String::new(),
+                        signature: None,
+                    },
+                    deltas,
+                )
+            }
+            ContentBlock::ToolUse { id, name, input } => {
+                events.extend(tool_use_stream_events(index, id, name, input));
+                continue;
+            }
+            other => (other.clone(), vec![]),
+        };
+        events.push(StreamEvent::ContentBlockStart {
+            index,
+            content_block: start,
+        });
+        events.extend(
+            deltas
+                .into_iter()
+                .map(|delta| StreamEvent::ContentBlockDelta { index, delta }),
+        );
+        events.push(StreamEvent::ContentBlockStop { index });
+    }
+
+    events.push(StreamEvent::MessageDelta {
+        delta: MessageDeltaPayload {
+            stop_reason: response.stop_reason.clone(),
+            stop_sequence: response.stop_sequence.clone(),
+        },
+        usage: Some(response.usage.clone()),
+    });
+    events.push(StreamEvent::MessageStop {});
+    events
+}
+
+/// Characters of tool input JSON carried by each `input_json_delta`.
+const TOOL_INPUT_CHUNK_CHARS: usize = 16;
+
+/// Stream a `tool_use` block the way the Messages API does.
+///
+/// The `content_block_start` carries an empty `input`; the serialized input
+/// follows as an empty `input_json_delta` and then fragments of at most 16
+/// characters, which only form valid JSON once concatenated, and the block
+/// ends with `content_block_stop`.
+#[must_use]
+pub fn tool_use_stream_events(
+    index: u32,
+    id: &str,
+    name: &str,
+    input: &serde_json::Value,
+) -> Vec<StreamEvent> {
+    let json: Vec<char> = input.to_string().chars().collect();
+    let mut events = vec![
+        StreamEvent::ContentBlockStart {
+            index,
+            content_block: ContentBlock::ToolUse {
+                id: id.to_string(),
+                name: name.to_string(),
+                input: serde_json::json!({}),
+            },
+        },
+        StreamEvent::ContentBlockDelta {
+            index,
+            delta: StreamDelta::InputJsonDelta {
+                partial_json: String::new(),
+            },
+        },
+    ];
+    events.extend(json.chunks(TOOL_INPUT_CHUNK_CHARS).map(|chunk| {
+        StreamEvent::ContentBlockDelta {
+            index,
+            delta: StreamDelta::InputJsonDelta {
+                partial_json: chunk.iter().collect(),
+            },
+        }
+    }));
+    events.push(StreamEvent::ContentBlockStop { index });
+    events
+}
+
+/// Stream a list of ABP agent events as a complete Messages API stream.
+///
+/// The streaming counterpart of [`response_from_events`]: events go through
+/// a [`LiveStreamMapper`], so tool calls arrive as fragmented
+/// `input_json_delta`s, and the closing `message_delta` carries `usage`.
+#[must_use]
+pub fn stream_events_from_events(
+    events: &[AgentEvent],
+    model: &str,
+    usage: Option<&ClaudeUsage>,
+) -> Vec<StreamEvent> {
+    let mut mapper = LiveStreamMapper::new(model);
+    let mut out: Vec<StreamEvent> = events.iter().flat_map(|e| mapper.map(e)).collect();
+    out.extend(mapper.finish(Usage {
+        input_tokens: usage.map_or(0, |u| u.input_tokens),
+        output_tokens: usage.map_or(0, |u| u.output_tokens),
+        cache_creation_input_tokens: usage.and_then(|u| u.cache_creation_input_tokens),
+        cache_read_input_tokens: usage.and_then(|u| u.cache_read_input_tokens),
+    }));
+    out
+}
+
+// ---------------------------------------------------------------------------
+// Live streaming: AgentEvent → StreamEvent
+// ---------------------------------------------------------------------------
+
+/// Kind of the content block a [`LiveStreamMapper`] currently has open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LiveBlock {
+    Text,
+    Thinking,
+}
+
+/// Incrementally translates ABP [`AgentEvent`]s into Anthropic stream events.
+///
+/// `AssistantDelta` events extend the open text (or thinking) block, so
+/// tokens reach the client as the backend produces them. An
+/// `AssistantMessage` that follows deltas closes their block rather than
+/// repeating the text; without preceding deltas it becomes a complete
+/// block. Tool calls become `tool_use` blocks whose input is streamed in
+/// fragments (see [`tool_use_stream_events`]) and `Error` events become
+/// `error` stream events. The first call to [`map`](Self::map) emits
+/// `message_start`; [`finish`](Self::finish) emits the closing
+/// `message_delta` and `message_stop`.
+#[derive(Debug, Clone)]
+pub struct LiveStreamMapper {
+    model: String,
+    started: bool,
+    next_index: u32,
+    open: Option<(LiveBlock, bool)>,
+    stop_reason: Option<String>,
+}
+
+impl LiveStreamMapper {
+    /// Create a mapper for a response from `model`.
+    #[must_use]
+    pub fn new(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            started: false,
+            next_index: 0,
+            open: None,
+            stop_reason: None,
+        }
+    }
+
+    /// Translate one agent event into zero or more stream events.
+    pub fn map(&mut self, event: &AgentEvent) -> Vec<StreamEvent> {
+        let mut out = Vec::new();
+        self.start(&mut out);
+        let kind = if event
+            .ext
+            .as_ref()
+            .and_then(|e| e.get("thinking"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+        {
+            LiveBlock::Thinking
+        } else {
+            LiveBlock::Text
+        };
+
+        match &event.kind {
+            AgentEventKind::AssistantDelta { text } => {
+                if self.open.map(|(k, _)| k) != Some(kind) {
+                    self.close(&mut out);
+                    self.open(kind, &mut out);
+                }
+                self.open = Some((kind, true));
+                out.push(self.delta(kind, text));
+            }
+            AgentEventKind::AssistantMessage { text } => {
+                if self.open == Some((kind, true)) {
+                    self.close(&mut out);
+                } else {
+                    self.close(&mut out);
+                    self.open(kind, &mut out);
+                    out.push(self.delta(kind, text));
+                    if let Some(signature) = event
+                        .ext
+                        .as_ref()
+                        .and_then(|e| e.get("signature"))
+                        .and_then(|v| v.as_str())
+                    {
+                        out.push(StreamEvent::ContentBlockDelta {
+                            index: self.next_index - 1,
+                            delta: StreamDelta::SignatureDelta {
+                                signature: signature.to_string(),
+                            },
+                        });
+                    }
+                    self.close(&mut out);
+                }
+            }
+            AgentEventKind::ToolCall {
+                tool_name,
+                tool_use_id,
+                input,
+                ..
+            } => {
+                self.close(&mut out);
+                let index = self.next_index;
+                self.next_index += 1;
+                out.extend(tool_use_stream_events(
+                    index,
+                    tool_use_id.as_deref().unwrap_or_default(),
+                    tool_name,
+                    input,
+                ));
+                self.stop_reason = Some("tool_use".to_string());
+            }
+            AgentEventKind::Error { message, .. } => {
+                self.close(&mut out);
+                out.push(StreamEvent::Error {
+                    error: ApiError {
+                        error_type: "api_error".to_string(),
+                        message: message.clone(),
+                    },
+                });
+            }
+            _ => {}
+        }
+        out
+    }
+
+    /// Close the stream after a successful run.
+    pub fn finish(&mut self, usage: Usage) -> Vec<StreamEvent> {
+        let mut out = Vec::new();
+        self.start(&mut out);
+        self.close(&mut out);
+        out.push(StreamEvent::MessageDelta {
+            delta: MessageDeltaPayload {
+                stop_reason: Some(
+                    self.stop_reason
+                        .take()
+                        .unwrap_or_else(|| "end_turn".to_string()),
+                ),
+                stop_sequence: None,
+            },
+            usage: Some(usage),
+        });
+        out.push(StreamEvent::MessageStop {});
+        out
+    }
+
+    /// Close the stream after a failed run.
+    pub fn fail(&mut self, message: &str) -> Vec<StreamEvent> {
+        let mut out = Vec::new();
+        self.start(&mut out);
+        self.close(&mut out);
+        out.push(StreamEvent::Error {
+            error: ApiError {
+                error_type: "api_error".to_string(),
+                message: message.to_string(),
+            },
+        });
+        out
+    }
+
+    fn start(&mut self, out: &mut Vec<StreamEvent>) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        out.push(StreamEvent::MessageStart {
+            message: MessageResponse {
+                id: format!("msg_{}", uuid::Uuid::new_v4().as_simple()),
+                response_type: "message".to_string(),
+                role: "assistant".to_string(),
+                content: vec![],
+                model: self.model.clone(),
+                stop_reason: None,
+                stop_sequence: None,
+                usage: Usage {
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cache_creation_input_tokens: None,
+                    cache_read_input_tokens: None,
+                },
+            },
+        });
+    }
+
+    fn open(&mut self, kind: LiveBlock, out: &mut Vec<StreamEvent>) {
+        let content_block = match kind {
+            LiveBlock::Text => ContentBlock::Text {
+                text: String::new(),
+            },
+            LiveBlock::Thinking => ContentBlock::Thinking {
+                thinking: String::new(),
+                signature: None,
+            },
+        };
+        out.push(StreamEvent::ContentBlockStart {
+            index: self.next_index,
+            content_block,
+        });
+        self.next_index += 1;
+        self.open = Some((kind, false));
+    }
+
+    fn close(&mut self, out: &mut Vec<StreamEvent>) {
+        if self.open.take().is_some() {
+            out.push(StreamEvent::ContentBlockStop {
+                index: self.next_index - 1,
+            });
+        }
+    }
+
+    fn delta(&self, kind: LiveBlock, text: &str) -> StreamEvent {
+        let delta = match kind {
+            LiveBlock::Text => StreamDelta::TextDelta {
+                text: text.to_string(),
+            },
+            LiveBlock::Thinking => StreamDelta::ThinkingDelta {
+                thinking: text.to_string(),
+            },
+        };
+        StreamEvent::ContentBlockDelta {
+            index: self.next_index - 1,
+            delta,
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Client
 // ---------------------------------------------------------------------------
@@ -679,14 +1192,16 @@ pub type StreamHandler =
 
 /// Drop-in-compatible Anthropic client backed by ABP.
 ///
-/// By default, uses a mock pipeline that converts through the Claude SDK
-/// dialect types. A custom `RequestHandler` can be installed for real backend
-/// integration.
+/// Requests are dispatched in this order: an installed `RequestHandler` /
+/// `StreamHandler`, then the runtime backend selected with
+/// [`AnthropicClient::with_runtime`], and finally a mock pipeline that
+/// converts through the Claude SDK dialect types.
 pub struct AnthropicClient {
     model: String,
     max_tokens: u32,
     handler: Option<RequestHandler>,
     stream_handler: Option<StreamHandler>,
+    runtime: Option<(Arc<Runtime>, String)>,
 }
 
 impl std::fmt::Debug for AnthropicClient {
@@ -694,6 +1209,7 @@ impl std::fmt::Debug for AnthropicClient {
         f.debug_struct("AnthropicClient")
             .field("model", &self.model)
             .field("max_tokens", &self.max_tokens)
+            .field("backend", &self.backend_name())
             .finish()
     }
 }
@@ -705,6 +1221,7 @@ impl Default for AnthropicClient {
             max_tokens: 4096,
             handler: None,
             stream_handler: None,
+            runtime: None,
         }
     }
 }
@@ -725,6 +1242,37 @@ impl AnthropicClient {
         }
     }
 
+    /// Create a client that executes requests on `backend` through `runtime`.
+    ///
+    /// Each request becomes a [`WorkOrder`](abp_core::WorkOrder) that is run
+    /// with [`Runtime::run_streaming`]; the resulting [`Receipt`] is converted
+    /// back into a [`MessageResponse`] or [`StreamEvent`] sequence.
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use abp_runtime::Runtime;
+    /// use abp_shim_claude::AnthropicClient;
+    ///
+    /// let client = AnthropicClient::with_runtime(
+    ///     Arc::new(Runtime::with_default_backends()),
+    ///     "mock",
+    /// );
+    /// assert_eq!(client.backend_name(), Some("mock"));
+    /// ```
+    #[must_use]
+    pub fn with_runtime(runtime: Arc<Runtime>, backend: impl Into<String>) -> Self {
+        Self {
+            runtime: Some((runtime, backend.into())),
+            ..Self::default()
+        }
+    }
+
+    /// Name of the runtime backend requests are routed to, if any.
+    #[must_use]
+    pub fn backend_name(&self) -> Option<&str> {
+        self.runtime.as_ref().map(|(_, backend)| backend.as_str())
+    }
+
     /// Set a custom request handler for non-streaming requests.
     pub fn set_handler(&mut self, handler: RequestHandler) {
         self.handler = Some(handler);
@@ -735,25 +1283,70 @@ impl AnthropicClient {
         self.stream_handler = Some(handler);
     }
 
+    /// Run `request` on the configured runtime backend and wait for its receipt.
+    async fn run_on_runtime(
+        runtime: &Runtime,
+        backend: &str,
+        request: &MessageRequest,
+    ) -> Result<Receipt, ShimError> {
+        use tokio_stream::StreamExt;
+
+        let handle = Self::start_run(runtime, backend, request).await?;
+
+        // Drain the live event channel so the backend never blocks on a full
+        // buffer; the receipt carries the full trace.
+        let mut events = handle.events;
+        while events.next().await.is_some() {}
+
+        handle
+            .receipt
+            .await
+            .map_err(|e| ShimError::Internal(e.to_string()))?
+            .map_err(|e| ShimError::ApiError {
+                error_type: "api_error".into(),
+                message: e.to_string(),
+            })
+    }
+
+    /// Start `request` on the configured runtime backend.
+    async fn start_run(
+        runtime: &Runtime,
+        backend: &str,
+        request: &MessageRequest,
+    ) -> Result<RunHandle, ShimError> {
+        runtime
+            .run_streaming(backend, request_to_work_order(request))
+            .await
+            .map_err(|e| ShimError::ApiError {
+                error_type: "api_error".into(),
+                message: e.to_string(),
+            })
+    }
+
     /// Non-streaming message creation — mirrors `client.messages.create(...)`.
     ///
-    /// Converts the request through ABP's Claude dialect, runs a mock
-    /// pipeline, and returns the response.
+    /// Converts the request through ABP's Claude dialect and runs it on the
+    /// configured handler or runtime backend, falling back to a mock
+    /// pipeline when neither is set.
+    ///
+    /// The request is checked with [`validate::validate_request`] before
+    /// it is dispatched, whichever pipeline runs it.
     ///
     /// # Errors
     ///
     /// Returns `ShimError` if the request is invalid or the pipeline fails.
     pub async fn create(&self, request: MessageRequest) -> Result<MessageResponse, ShimError> {
-        if request.messages.is_empty() {
-            return Err(ShimError::InvalidRequest(
-                "messages must not be empty".into(),
-            ));
-        }
+        validate(&request)?;
 
         if let Some(ref handler) = self.handler {
             return handler(&request);
         }
 
+        if let Some((runtime, backend)) = &self.runtime {
+            let receipt = Self::run_on_runtime(runtime, backend, &request).await?;
+            return Ok(response_from_receipt(&receipt, &request.model));
+        }
+
         // Default mock pipeline:
         // 1. Convert to Claude SDK request
         let claude_req = request_to_claude(&request);
@@ -802,23 +1395,25 @@ impl AnthropicClient {
     /// Streaming message creation — mirrors `client.messages.stream(...)`.
     ///
     /// Returns a `Pin<Box<dyn Stream<Item = StreamEvent>>>` that yields
-    /// streaming events in the canonical Anthropic order.
+    /// streaming events in the canonical Anthropic order. With a runtime
+    /// backend, events are forwarded as the backend emits them.
     ///
     /// # Errors
     ///
     /// Returns `ShimError` if the request is invalid.
     pub async fn create_stream(&self, request: MessageRequest) -> Result<EventStream, ShimError> {
-        if request.messages.is_empty() {
-            return Err(ShimError::InvalidRequest(
-                "messages must not be empty".into(),
-            ));
-        }
+        validate(&request)?;
 
         if let Some(ref handler) = self.stream_handler {
             let events = handler(&request)?;
             return Ok(EventStream::from_vec(events));
         }
 
+        if let Some((runtime, backend)) = &self.runtime {
+            let handle = Self::start_run(runtime, backend, &request).await?;
+            return Ok(EventStream::from_run(handle, &request.model));
+        }
+
         // Default mock streaming pipeline
         let claude_req = request_to_claude(&request);
         let response_text = format!(
@@ -889,15 +1484,62 @@ impl AnthropicClient {
 /// A stream of `StreamEvent` items.
 #[derive(Debug)]
 pub struct EventStream {
-    events: Vec<StreamEvent>,
-    index: usize,
+    source: EventSource,
+}
+
+#[derive(Debug)]
+enum EventSource {
+    Buffered {
+        events: Vec<StreamEvent>,
+        index: usize,
+    },
+    Live(tokio_stream::wrappers::ReceiverStream<StreamEvent>),
 }
 
 impl EventStream {
     /// Create from a pre-built event list.
     #[must_use]
     pub fn from_vec(events: Vec<StreamEvent>) -> Self {
-        Self { events, index: 0 }
+        Self {
+            source: EventSource::Buffered { events, index: 0 },
+        }
+    }
+
+    /// Stream a live runtime run, translating each [`AgentEvent`] as the
+    /// backend emits it (see [`LiveStreamMapper`]).
+    ///
+    /// The closing `message_delta` carries the usage from the run's receipt;
+    /// if the run fails, the stream ends with an `error` event instead.
+    /// Must be called from within a Tokio runtime.
+    #[must_use]
+    pub fn from_run(handle: RunHandle, model: &str) -> Self {
+        use tokio_stream::StreamExt;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(64);
+        let mut mapper = LiveStreamMapper::new(model);
+        tokio::spawn(async move {
+            let mut events = handle.events;
+            while let Some(event) = events.next().await {
+                for out in mapper.map(&event) {
+                    if tx.send(out).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            let tail = match handle.receipt.await {
+                Ok(Ok(receipt)) => mapper.finish(receipt_usage(&receipt)),
+                Ok(Err(e)) => mapper.fail(&e.to_string()),
+                Err(e) => mapper.fail(&e.to_string()),
+            };
+            for out in tail {
+                if tx.send(out).await.is_err() {
+                    return;
+                }
+            }
+        });
+        Self {
+            source: EventSource::Live(tokio_stream::wrappers::ReceiverStream::new(rx)),
+        }
     }
 
     /// Collect all remaining events.
@@ -914,19 +1556,29 @@ impl EventStream {
 impl Stream for EventStream {
     type Item = StreamEvent;
 
-    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        if self.index < self.events.len() {
-            let event = self.events[self.index].clone();
-            self.index += 1;
-            Poll::Ready(Some(event))
-        } else {
-            Poll::Ready(None)
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match &mut self.source {
+            EventSource::Buffered { events, index } => {
+                if *index < events.len() {
+                    let event = events[*index].clone();
+                    *index += 1;
+                    Poll::Ready(Some(event))
+                } else {
+                    Poll::Ready(None)
+                }
+            }
+            EventSource::Live(rx) => Pin::new(rx).poll_next(cx),
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let remaining = self.events.len() - self.index;
-        (remaining, Some(remaining))
+        match &self.source {
+            EventSource::Buffered { events, index } => {
+                let remaining = events.len() - index;
+                (remaining, Some(remaining))
+            }
+            EventSource::Live(rx) => rx.size_hint(),
+        }
     }
 }
 
@@ -957,6 +1609,8 @@ mod tests {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         }
     }
 
@@ -1160,6 +1814,8 @@ mod tests {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let claude_req = request_to_claude(&req);
         assert_eq!(
@@ -1187,6 +1843,8 @@ mod tests {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let wo = request_to_work_order(&req);
         assert!(wo.task.contains("Help me"));
@@ -1223,6 +1881,8 @@ mod tests {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let claude_req = request_to_claude(&req);
         assert_eq!(claude_req.messages.len(), 3);
@@ -1260,6 +1920,8 @@ mod tests {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let resp = client.create(req).await.unwrap();
         assert_eq!(resp.role, "assistant");
@@ -1283,6 +1945,8 @@ mod tests {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let json = serde_json::to_value(&req).unwrap();
         assert_eq!(json["temperature"], 0.7);
@@ -1305,6 +1969,8 @@ mod tests {
             stop_sequences: Some(vec!["STOP".to_string(), "END".to_string()]),
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let json = serde_json::to_value(&req).unwrap();
         let stops = json["stop_sequences"].as_array().unwrap();
@@ -1328,12 +1994,78 @@ mod tests {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let wo = request_to_work_order(&req);
         let max_tok = wo.config.vendor.get("max_tokens");
         assert_eq!(max_tok, Some(&serde_json::Value::from(2048)));
     }
 
+    #[test]
+    fn tools_deserialize_and_lower_to_claude_request() {
+        let req: MessageRequest = serde_json::from_value(json!({
+            "model": "claude-sonnet-4-20250514",
+            "max_tokens": 1024,
+            "messages": [{"role": "user", "content": [{"type": "text", "text": "Weather?"}]}],
+            "tools": [{
+                "name": "get_weather",
+                "description": "Current weather for a city",
+                "input_schema": {
+                    "type": "object",
+                    "properties": {"city": {"type": "string"}},
+                    "required": ["city"],
+                },
+            }],
+            "tool_choice": {"type": "tool", "name": "get_weather"},
+        }))
+        .unwrap();
+
+        let claude = request_to_claude(&req);
+        assert_eq!(claude.tools.len(), 1);
+        assert_eq!(claude.tools[0].name, "get_weather");
+        assert_eq!(claude.tools[0].input_schema["required"][0], "city");
+        assert_eq!(
+            claude.tool_choice,
+            Some(dialect::ClaudeToolChoice::Tool {
+                name: "get_weather".into()
+            })
+        );
+        let json = serde_json::to_value(&claude).unwrap();
+        assert_eq!(json["tool_choice"]["type"], "tool");
+    }
+
+    #[test]
+    fn tools_become_ir_tool_definitions_on_work_order() {
+        let mut req = simple_request("Weather?");
+        req.temperature = Some(0.2);
+        req.tools = Some(vec![ClaudeTool {
+            name: "get_weather".into(),
+            description: None,
+            input_schema: json!({"type": "object"}),
+        }]);
+        req.tool_choice = Some(ClaudeToolChoice::Any {});
+
+        let wo = request_to_work_order(&req);
+        let abp = &wo.config.vendor["abp"];
+        let tools: Vec<abp_core::ir::IrToolDefinition> =
+            serde_json::from_value(abp["tools"].clone()).unwrap();
+        assert_eq!(tools[0].name, "get_weather");
+        assert_eq!(tools[0].parameters, json!({"type": "object"}));
+        let choice: abp_core::ir::IrToolChoice =
+            serde_json::from_value(abp["tool_choice"].clone()).unwrap();
+        assert_eq!(choice, abp_core::ir::IrToolChoice::Required);
+        assert_eq!(wo.config.vendor["temperature"], json!(0.2));
+    }
+
+    #[test]
+    fn requests_without_tools_omit_them() {
+        let claude = request_to_claude(&simple_request("hi"));
+        let json = serde_json::to_value(&claude).unwrap();
+        assert!(json.get("tools").is_none());
+        assert!(json.get("tool_choice").is_none());
+    }
+
     // ── 8. Streaming event sequence ─────────────────────────────────────
 
     #[tokio::test]
@@ -1459,6 +2191,8 @@ mod tests {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let resp = client.create(req).await.unwrap();
         assert_eq!(resp.model, "claude-opus-4-20250514");
@@ -1485,6 +2219,8 @@ mod tests {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let err = client.create(req).await.unwrap_err();
         assert!(matches!(err, ShimError::InvalidRequest(_)));
@@ -1502,6 +2238,8 @@ mod tests {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let err = client.create_stream(req).await.unwrap_err();
         assert!(matches!(err, ShimError::InvalidRequest(_)));
@@ -1567,6 +2305,8 @@ mod tests {
             stop_sequences: Some(vec!["END".into()]),
             thinking: Some(ThinkingConfig::new(2048)),
             stream: Some(true),
+            tools: None,
+            tool_choice: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         let back: MessageRequest = serde_json::from_str(&json).unwrap();
@@ -1692,4 +2432,332 @@ mod tests {
         assert!(resp.content.is_empty());
         assert!(resp.stop_reason.is_none());
     }
+
+    // ── 12. Runtime backend wiring ──────────────────────────────────────
+
+    fn mock_runtime_client() -> AnthropicClient {
+        AnthropicClient::with_runtime(Arc::new(Runtime::with_default_backends()), "mock")
+    }
+
+    #[tokio::test]
+    async fn runtime_create_returns_backend_output() {
+        let client = mock_runtime_client();
+        let resp = client.create(simple_request("Hello")).await.unwrap();
+
+        assert!(resp.id.starts_with("msg_"));
+        assert_eq!(resp.model, "claude-sonnet-4-20250514");
+        assert_eq!(resp.stop_reason.as_deref(), Some("end_turn"));
+        let ContentBlock::Text { text } = &resp.content[0] else {
+            panic!("expected text block, got {:?}", resp.content[0]);
+        };
+        assert!(text.contains("mock backend"));
+        assert!(!text.contains("Mock response to"));
+    }
+
+    #[tokio::test]
+    async fn runtime_stream_yields_live_events() {
+        let client = mock_runtime_client();
+        let events = client
+            .create_stream(simple_request("Hello"))
+            .await
+            .unwrap()
+            .collect_all()
+            .await;
+
+        assert!(matches!(events[0], StreamEvent::MessageStart { .. }));
+        assert!(matches!(events.last(), Some(StreamEvent::MessageStop {})));
+        let text: String = events
+            .iter()
+            .filter_map(|e| match e {
+                StreamEvent::ContentBlockDelta {
+                    delta: StreamDelta::TextDelta { text },
+                    ..
+                } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(text.contains("mock backend"));
+    }
+
+    fn agent_event(kind: AgentEventKind) -> AgentEvent {
+        AgentEvent {
+            ts: Utc::now(),
+            kind,
+            ext: None,
+        }
+    }
+
+    #[test]
+    fn live_mapper_streams_deltas_incrementally() {
+        let mut mapper = LiveStreamMapper::new("claude-sonnet-4-20250514");
+
+        let first = mapper.map(&agent_event(AgentEventKind::AssistantDelta {
+            text: "Hel".into(),
+        }));
+        assert!(matches!(first[0], StreamEvent::MessageStart { .. }));
+        assert!(matches!(
+            first[1],
+            StreamEvent::ContentBlockStart { index: 0, .. }
+        ));
+        assert!(matches!(
+            &first[2],
+            StreamEvent::ContentBlockDelta {
+                index: 0,
+                delta: StreamDelta::TextDelta { text },
+            } if text == "Hel"
+        ));
+
+        let second = mapper.map(&agent_event(AgentEventKind::AssistantDelta {
+            text: "lo".into(),
+        }));
+        assert_eq!(second.len(), 1);
+
+        // The final message repeats the deltas, so it only closes the block.
+        let closed = mapper.map(&agent_event(AgentEventKind::AssistantMessage {
+            text: "Hello".into(),
+        }));
+        assert!(matches!(
+            closed[..],
+            [StreamEvent::ContentBlockStop { index: 0 }]
+        ));
+
+        let tool = mapper.map(&agent_event(AgentEventKind::ToolCall {
+            tool_name: "read_file".into(),
+            tool_use_id: Some("tu_1".into()),
+            parent_tool_use_id: None,
+            input: serde_json::json!({"path": "a.rs"}),
+        }));
+        // start, empty delta, one fragment, stop
+        assert_eq!(tool.len(), 4);
+        assert!(matches!(
+            &tool[0],
+            StreamEvent::ContentBlockStart {
+                index: 1,
+                content_block: ContentBlock::ToolUse { name, .. },
+            } if name == "read_file"
+        ));
+
+        let tail = mapper.finish(Usage {
+            input_tokens: 3,
+            output_tokens: 5,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
+        });
+        assert!(matches!(
+            &tail[0],
+            StreamEvent::MessageDelta { delta, .. }
+                if delta.stop_reason.as_deref() == Some("tool_use")
+        ));
+        assert!(matches!(tail[1], StreamEvent::MessageStop {}));
+    }
+
+    #[test]
+    fn live_mapper_message_without_deltas_is_full_block() {
+        let mut mapper = LiveStreamMapper::new("m");
+        let events = mapper.map(&agent_event(AgentEventKind::AssistantMessage {
+            text: "Done.".into(),
+        }));
+        assert_eq!(events.len(), 4);
+        assert!(matches!(
+            events[3],
+            StreamEvent::ContentBlockStop { index: 0 }
+        ));
+        let failed = mapper.fail("boom");
+        assert!(matches!(
+            &failed[..],
+            [StreamEvent::Error { error }] if error.message == "boom"
+        ));
+    }
+
+    #[tokio::test]
+    async fn runtime_unknown_backend_is_api_error() {
+        let client =
+            AnthropicClient::with_runtime(Arc::new(Runtime::with_default_backends()), "nope");
+        let err = client.create(simple_request("Hello")).await.unwrap_err();
+        assert!(matches!(err, ShimError::ApiError { .. }));
+        assert!(err.to_string().contains("unknown backend"));
+    }
+
+    #[tokio::test]
+    async fn runtime_create_rejects_zero_max_tokens() {
+        let client = mock_runtime_client();
+        let mut req = simple_request("Hello");
+        req.max_tokens = 0;
+        let err = client.create(req).await.unwrap_err();
+        assert!(matches!(
+            &err,
+            ShimError::InvalidRequest(msg) if msg.starts_with("max_tokens:")
+        ));
+    }
+
+    #[tokio::test]
+    async fn runtime_stream_rejects_non_alternating_roles() {
+        let client = mock_runtime_client();
+        let mut req = simple_request("Hello");
+        req.messages.push(Message {
+            role: Role::User,
+            content: vec![ContentBlock::Text {
+                text: "Again".into(),
+            }],
+        });
+        let err = client.create_stream(req).await.unwrap_err();
+        assert!(matches!(
+            &err,
+            ShimError::InvalidRequest(msg) if msg.contains("roles must alternate")
+        ));
+
+        let mut req = simple_request("Hello");
+        req.messages[0].role = Role::Assistant;
+        let err = client.create(req).await.unwrap_err();
+        assert!(matches!(
+            &err,
+            ShimError::InvalidRequest(msg) if msg.starts_with("messages.0.role:")
+        ));
+    }
+
+    #[tokio::test]
+    async fn handler_takes_precedence_over_runtime() {
+        let mut client = mock_runtime_client();
+        client.set_handler(Box::new(|req| {
+            Ok(response_from_events(&[], &req.model, None))
+        }));
+        let resp = client.create(simple_request("Hello")).await.unwrap();
+        assert!(resp.content.is_empty());
+    }
+
+    #[test]
+    fn stream_events_from_response_tool_use() {
+        let response = MessageResponse {
+            id: "msg_1".into(),
+            response_type: "message".into(),
+            role: "assistant".into(),
+            content: vec![
+                ContentBlock::Text {
+                    text: "Reading.".into(),
+                },
+                ContentBlock::ToolUse {
+                    id: "toolu_1".into(),
+                    name: "read_file".into(),
+                    input: json!({"path": "a.rs"}),
+                },
+            ],
+            model: "claude-sonnet-4-20250514".into(),
+            stop_reason: Some("tool_use".into()),
+            stop_sequence: None,
+            usage: Usage {
+                input_tokens: 12,
+                output_tokens: 30,
+                cache_creation_input_tokens: None,
+                cache_read_input_tokens: None,
+            },
+        };
+        let events = stream_events_from_response(&response);
+
+        // start + text (start, delta, stop) + tool (start, 2 × delta, stop)
+        // + message_delta + stop
+        assert_eq!(events.len(), 10);
+        let StreamEvent::MessageStart { message } = &events[0] else {
+            panic!("expected message_start");
+        };
+        assert!(message.content.is_empty());
+        assert_eq!(message.usage.input_tokens, 12);
+        assert_eq!(message.usage.output_tokens, 0);
+        assert_eq!(
+            events[6],
+            StreamEvent::ContentBlockDelta {
+                index: 1,
+                delta: StreamDelta::InputJsonDelta {
+                    partial_json: r#"{"path":"a.rs"}"#.into(),
+                },
+            }
+        );
+        let StreamEvent::MessageDelta { delta, usage } = &events[8] else {
+            panic!("expected message_delta");
+        };
+        assert_eq!(delta.stop_reason.as_deref(), Some("tool_use"));
+        assert_eq!(usage.as_ref().unwrap().output_tokens, 30);
+    }
+
+    #[test]
+    fn tool_input_is_streamed_in_fragments() {
+        let input = json!({"path": "src/lib.rs", "pattern": "fn main", "context": 3});
+        let events = tool_use_stream_events(2, "toolu_1", "grep", &input);
+        let StreamEvent::ContentBlockStart {
+            index: 2,
+            content_block: ContentBlock::ToolUse { input: start, .. },
+        } = &events[0]
+        else {
+            panic!("expected content_block_start");
+        };
+        assert_eq!(start, &json!({}));
+        assert!(matches!(
+            events.last(),
+            Some(StreamEvent::ContentBlockStop { index: 2 })
+        ));
+
+        let fragments: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match e {
+                StreamEvent::ContentBlockDelta {
+                    delta: StreamDelta::InputJsonDelta { partial_json },
+                    ..
+                } => Some(partial_json.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(fragments[0], "");
+        assert!(fragments.len() > 3);
+        assert!(serde_json::from_str::<serde_json::Value>(fragments[1]).is_err());
+        let joined: String = fragments.concat();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&joined).unwrap(),
+            input
+        );
+    }
+
+    #[test]
+    fn stream_events_from_events_matches_response() {
+        let events = vec![
+            agent_event(AgentEventKind::AssistantMessage {
+                text: "Listing.".into(),
+            }),
+            agent_event(AgentEventKind::ToolCall {
+                tool_name: "list_dir".into(),
+                tool_use_id: Some("toolu_9".into()),
+                parent_tool_use_id: None,
+                input: json!({"path": "crates/abp-shim-claude/src"}),
+            }),
+        ];
+        let usage = ClaudeUsage {
+            input_tokens: 7,
+            output_tokens: 11,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
+        };
+        let stream = stream_events_from_events(&events, "m", Some(&usage));
+        let response = response_from_events(&events, "m", Some(&usage));
+
+        let mut collected = String::new();
+        for event in &stream {
+            if let StreamEvent::ContentBlockDelta {
+                index: 1,
+                delta: StreamDelta::InputJsonDelta { partial_json },
+            } = event
+            {
+                collected.push_str(partial_json);
+            }
+        }
+        let ContentBlock::ToolUse { input, .. } = &response.content[1] else {
+            panic!("expected tool_use block");
+        };
+        assert_eq!(
+            &serde_json::from_str::<serde_json::Value>(&collected).unwrap(),
+            input
+        );
+        assert!(matches!(
+            &stream[stream.len() - 2],
+            StreamEvent::MessageDelta { delta, usage: Some(u) }
+                if delta.stop_reason == response.stop_reason && u.output_tokens == 11
+        ));
+    }
 }
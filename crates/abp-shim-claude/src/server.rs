@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Anthropic-compatible HTTP frontend for the shim.
+//!
+//! [`ShimServer`](crate::server::ShimServer) exposes `POST /v1/messages`
+//! over HTTP, backed by an
+//! [`AnthropicClient`](crate::client::AnthropicClient), so tooling that
+//! speaks the Anthropic wire protocol can talk to ABP by changing only its
+//! base URL.
+//!
+//! Requests with `"stream": true` are answered with Server-Sent Events whose
+//! `event:` name matches the payload's `type` (`message_start`,
+//! `content_block_delta`, …), as the Messages API does. Failures are
+//! returned as [`AnthropicErrorResponse`](crate::error::AnthropicErrorResponse)
+//! bodies with the matching HTTP status.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::extract::rejection::JsonRejection;
+use axum::http::StatusCode;
+use axum::response::sse::{Event as SseEvent, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use tokio_stream::StreamExt;
+
+use crate::client::AnthropicClient;
+use crate::error::{AnthropicErrorResponse, ClaudeShimError, ErrorKind};
+use crate::types::{MessagesRequest, StreamEvent};
+
+/// Path of the messages endpoint.
+pub const MESSAGES_PATH: &str = "/v1/messages";
+
+/// HTTP server exposing an [`AnthropicClient`] on the Anthropic wire protocol.
+#[derive(Debug, Clone)]
+pub struct ShimServer {
+    client: Arc<AnthropicClient>,
+}
+
+impl ShimServer {
+    /// Create a server that answers requests with `client`.
+    #[must_use]
+    pub fn new(client: AnthropicClient) -> Self {
+        Self {
+            client: Arc::new(client),
+        }
+    }
+
+    /// Build the Axum [`Router`] for this server.
+    pub fn router(&self) -> Router {
+        router(self.client.clone())
+    }
+
+    /// Bind to `addr` (e.g. `"127.0.0.1:8080"`) and serve until shutdown.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address cannot be bound or the server fails.
+    pub async fn start(self, addr: &str) -> std::io::Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        axum::serve(listener, self.router()).await
+    }
+
+    /// The client requests are routed through.
+    #[must_use]
+    pub fn client(&self) -> &AnthropicClient {
+        &self.client
+    }
+}
+
+/// Build a [`Router`] serving `POST /v1/messages` with `client`.
+pub fn router(client: Arc<AnthropicClient>) -> Router {
+    Router::new()
+        .route(MESSAGES_PATH, post(handle_messages))
+        .with_state(client)
+}
+
+/// `POST /v1/messages` handler.
+async fn handle_messages(
+    State(client): State<Arc<AnthropicClient>>,
+    body: Result<Json<MessagesRequest>, JsonRejection>,
+) -> Response {
+    let request = match body {
+        Ok(Json(request)) => request,
+        Err(rejection) => {
+            return error_response(ErrorKind::InvalidRequestError, rejection.body_text());
+        }
+    };
+
+    let messages = client.messages();
+    if request.stream == Some(true) {
+        match messages.stream(&request).await {
+            Ok(stream) => {
+                Sse::new(stream.map(|event| Ok::<_, Infallible>(sse_event(&event)))).into_response()
+            }
+            Err(err) => shim_error_response(&err),
+        }
+    } else {
+        match messages.create(&request).await {
+            Ok(response) => Json(response).into_response(),
+            Err(err) => shim_error_response(&err),
+        }
+    }
+}
+
+/// Frame a stream event as SSE, naming the event after its `type` tag.
+fn sse_event(event: &StreamEvent) -> SseEvent {
+    match serde_json::to_value(event) {
+        Ok(value) => {
+            let name = value["type"].as_str().unwrap_or("message").to_string();
+            SseEvent::default().event(name).data(value.to_string())
+        }
+        Err(e) => {
+            let body = AnthropicErrorResponse::api_error(e.to_string());
+            SseEvent::default()
+                .event("error")
+                .data(serde_json::to_string(&body).unwrap_or_default())
+        }
+    }
+}
+
+fn shim_error_response(err: &ClaudeShimError) -> Response {
+    match err {
+        ClaudeShimError::InvalidRequest(msg) => {
+            error_response(ErrorKind::InvalidRequestError, msg.clone())
+        }
+        ClaudeShimError::Serde(msg) => error_response(ErrorKind::InvalidRequestError, msg.clone()),
+        ClaudeShimError::Api {
+            kind,
+            message,
+            status,
+            ..
+        } => {
+            let status = StatusCode::from_u16(*status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            let body = AnthropicErrorResponse::new(kind.clone(), message.clone());
+            (status, Json(body)).into_response()
+        }
+        ClaudeShimError::Http(msg)
+        | ClaudeShimError::Stream(msg)
+        | ClaudeShimError::Internal(msg) => error_response(ErrorKind::ApiError, msg.clone()),
+    }
+}
+
+fn error_response(kind: ErrorKind, message: String) -> Response {
+    let status =
+        StatusCode::from_u16(kind.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, Json(AnthropicErrorResponse::new(kind, message))).into_response()
+}
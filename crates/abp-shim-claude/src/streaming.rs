@@ -1,17 +1,21 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 //! SSE-compatible streaming adapter for Claude message events.
 //!
-//! Provides `MessageStream` for consuming streaming responses and
-//! `SseParser` for parsing raw SSE text into typed `StreamEvent`s.
+//! Provides `MessageStream` for consuming streaming responses,
+//! `SseParser` for parsing raw SSE text into typed `StreamEvent`s,
+//! `Accumulator` for rebuilding the final message from those events, and
+//! `StreamHandlers` for reacting to them with typed callbacks.
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use tokio_stream::Stream;
 
 use crate::error::ClaudeShimError;
-use crate::types::{MessagesResponse, StreamEvent};
+use crate::types::{
+    ClaudeUsage, ContentBlock, ErrorResponse, MessagesResponse, StreamDelta, StreamEvent,
+};
 
 // ---------------------------------------------------------------------------
 // MessageStream — typed stream of StreamEvents
@@ -55,7 +59,6 @@ impl MessageStream {
 
     /// Collect all text deltas from the stream into a single string.
     pub async fn collect_text(self) -> String {
-        use crate::types::StreamDelta;
         let events = self.collect_all().await;
         let mut text = String::new();
         for event in events {
@@ -70,6 +73,19 @@ impl MessageStream {
         text
     }
 
+    /// Consume the stream and assemble the final message with an
+    /// [`Accumulator`].
+    ///
+    /// Returns `None` if the stream has no `MessageStart` event.
+    pub async fn collect_message(mut self) -> Option<MessagesResponse> {
+        use tokio_stream::StreamExt;
+        let mut acc = Accumulator::new();
+        while let Some(event) = StreamExt::next(&mut self).await {
+            acc.feed(&event);
+        }
+        acc.finish()
+    }
+
     /// Extract the final [`MessagesResponse`] from the stream's `message_start` event.
     ///
     /// Returns `None` if the stream has no `MessageStart` event.
@@ -239,6 +255,344 @@ pub fn parse_sse_text(text: &str) -> Vec<Result<StreamEvent, String>> {
         .collect()
 }
 
+// ---------------------------------------------------------------------------
+// Accumulator — builds the final message from stream events
+// ---------------------------------------------------------------------------
+
+/// Incrementally rebuilds the final [`MessagesResponse`] from a stream.
+///
+/// Feed every [`StreamEvent`] as it arrives (e.g. while rendering deltas) and
+/// call [`Accumulator::finish`] at the end. Text and thinking deltas are
+/// appended to their blocks, `input_json_delta` fragments are parsed into
+/// the tool-use `input` when the block stops, and usage from `message_delta`
+/// is merged into the usage reported by `message_start`.
+///
+/// # Examples
+///
+/// ```
+/// use abp_shim_claude::streaming::Accumulator;
+/// use abp_shim_claude::types::{ContentBlock, StreamDelta, StreamEvent};
+///
+/// let mut acc = Accumulator::new();
+/// acc.feed(&StreamEvent::ContentBlockStart {
+///     index: 0,
+///     content_block: ContentBlock::Text { text: String::new() },
+/// });
+/// acc.feed(&StreamEvent::ContentBlockDelta {
+///     index: 0,
+///     delta: StreamDelta::TextDelta { text: "Hello".into() },
+/// });
+/// assert_eq!(acc.text(), "Hello");
+/// // No `message_start` was seen, so there is no message to finish.
+/// assert!(acc.finish().is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Accumulator {
+    message: Option<MessagesResponse>,
+    blocks: BTreeMap<u32, ContentBlock>,
+    tool_json: BTreeMap<u32, String>,
+    error: Option<ErrorResponse>,
+    complete: bool,
+}
+
+impl Accumulator {
+    /// Create an empty accumulator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one stream event.
+    pub fn feed(&mut self, event: &StreamEvent) {
+        match event {
+            StreamEvent::MessageStart { message } => self.message = Some(message.clone()),
+            StreamEvent::ContentBlockStart {
+                index,
+                content_block,
+            } => {
+                self.blocks.insert(*index, content_block.clone());
+            }
+            StreamEvent::ContentBlockDelta { index, delta } => self.apply_delta(*index, delta),
+            StreamEvent::ContentBlockStop { index } => self.close_block(*index),
+            StreamEvent::MessageDelta { delta, usage } => {
+                if let Some(message) = &mut self.message {
+                    if delta.stop_reason.is_some() {
+                        message.stop_reason.clone_from(&delta.stop_reason);
+                    }
+                    if let Some(usage) = usage {
+                        merge_usage(&mut message.usage, usage);
+                    }
+                }
+            }
+            StreamEvent::MessageStop {} => self.complete = true,
+            StreamEvent::Ping {} => {}
+            StreamEvent::Error { error } => self.error = Some(error.clone()),
+        }
+    }
+
+    fn apply_delta(&mut self, index: u32, delta: &StreamDelta) {
+        if let StreamDelta::InputJsonDelta { partial_json } = delta {
+            self.tool_json
+                .entry(index)
+                .or_default()
+                .push_str(partial_json);
+            return;
+        }
+        let block = self.blocks.entry(index).or_insert_with(|| match delta {
+            StreamDelta::ThinkingDelta { .. } | StreamDelta::SignatureDelta { .. } => {
+                ContentBlock::Thinking {
+                    thinking: String::new(),
+                    signature: None,
+                }
+            }
+            _ => ContentBlock::Text {
+                text: String::new(),
+            },
+        });
+        match (block, delta) {
+            (ContentBlock::Text { text }, StreamDelta::TextDelta { text: t }) => text.push_str(t),
+            (
+                ContentBlock::Thinking { thinking, .. },
+                StreamDelta::ThinkingDelta { thinking: t },
+            ) => {
+                thinking.push_str(t);
+            }
+            (
+                ContentBlock::Thinking { signature, .. },
+                StreamDelta::SignatureDelta { signature: s },
+            ) => {
+                signature.get_or_insert_with(String::new).push_str(s);
+            }
+            _ => {}
+        }
+    }
+
+    /// Parse buffered tool-input JSON into the tool-use block at `index`.
+    fn close_block(&mut self, index: u32) {
+        let Some(json) = self.tool_json.remove(&index) else {
+            return;
+        };
+        if let Some(ContentBlock::ToolUse { input, .. }) = self.blocks.get_mut(&index)
+            && !json.trim().is_empty()
+        {
+            *input = serde_json::from_str(&json).unwrap_or(serde_json::Value::String(json));
+        }
+    }
+
+    /// Concatenated text of all text blocks so far.
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.blocks
+            .values()
+            .filter_map(|b| match b {
+                ContentBlock::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether `message_stop` has been received.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// The error event received mid-stream, if any.
+    #[must_use]
+    pub fn error(&self) -> Option<&ErrorResponse> {
+        self.error.as_ref()
+    }
+
+    /// Build the final message, or `None` if no `message_start` was seen.
+    #[must_use]
+    pub fn finish(mut self) -> Option<MessagesResponse> {
+        let open: Vec<u32> = self.tool_json.keys().copied().collect();
+        for index in open {
+            self.close_block(index);
+        }
+        let mut message = self.message?;
+        message.content = self.blocks.into_values().collect();
+        Some(message)
+    }
+}
+
+fn merge_usage(usage: &mut ClaudeUsage, delta: &ClaudeUsage) {
+    if delta.input_tokens > 0 {
+        usage.input_tokens = delta.input_tokens;
+    }
+    usage.output_tokens = delta.output_tokens;
+    if delta.cache_creation_input_tokens.is_some() {
+        usage.cache_creation_input_tokens = delta.cache_creation_input_tokens;
+    }
+    if delta.cache_read_input_tokens.is_some() {
+        usage.cache_read_input_tokens = delta.cache_read_input_tokens;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// StreamHandlers — typed callbacks over a stream
+// ---------------------------------------------------------------------------
+
+type StrFn<'a> = Box<dyn FnMut(&str) + Send + 'a>;
+type ToolCallFn<'a> = Box<dyn FnMut(&str, &str, &serde_json::Value) + Send + 'a>;
+type ErrorFn<'a> = Box<dyn FnMut(&ErrorResponse) + Send + 'a>;
+type EventFn<'a> = Box<dyn FnMut(&StreamEvent) + Send + 'a>;
+type CompleteFn<'a> = Box<dyn FnOnce(&MessagesResponse) + Send + 'a>;
+
+/// Typed callbacks over a stream of [`StreamEvent`]s.
+///
+/// Mirrors the Anthropic SDKs' `stream.on("text", ...)` helpers: register the
+/// callbacks you need and [`run`](StreamHandlers::run) the stream. Text and
+/// thinking callbacks fire per delta; `on_tool_call` fires once per tool-use
+/// block, after its input JSON has been fully assembled.
+///
+/// # Examples
+///
+/// ```
+/// # async fn demo() {
+/// use abp_shim_claude::streaming::{MessageStream, StreamHandlers};
+///
+/// # let stream = MessageStream::empty();
+/// let mut text = String::new();
+/// let message = StreamHandlers::new()
+///     .on_text(|t| text.push_str(t))
+///     .on_tool_call(|id, name, input| println!("{id}: {name}({input})"))
+///     .run(stream)
+///     .await;
+/// # assert!(message.is_none());
+/// # }
+/// ```
+#[derive(Default)]
+pub struct StreamHandlers<'a> {
+    text: Option<StrFn<'a>>,
+    thinking: Option<StrFn<'a>>,
+    tool_call: Option<ToolCallFn<'a>>,
+    error: Option<ErrorFn<'a>>,
+    event: Option<EventFn<'a>>,
+    complete: Option<CompleteFn<'a>>,
+}
+
+impl std::fmt::Debug for StreamHandlers<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamHandlers")
+            .field("text", &self.text.is_some())
+            .field("thinking", &self.thinking.is_some())
+            .field("tool_call", &self.tool_call.is_some())
+            .field("error", &self.error.is_some())
+            .field("event", &self.event.is_some())
+            .field("complete", &self.complete.is_some())
+            .finish()
+    }
+}
+
+impl<'a> StreamHandlers<'a> {
+    /// Create a handler set with no callbacks registered.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called with each `text_delta` fragment.
+    #[must_use]
+    pub fn on_text(mut self, f: impl FnMut(&str) + Send + 'a) -> Self {
+        self.text = Some(Box::new(f));
+        self
+    }
+
+    /// Called with each `thinking_delta` fragment.
+    #[must_use]
+    pub fn on_thinking(mut self, f: impl FnMut(&str) + Send + 'a) -> Self {
+        self.thinking = Some(Box::new(f));
+        self
+    }
+
+    /// Called with `(id, name, input)` when a tool-use block completes.
+    #[must_use]
+    pub fn on_tool_call(
+        mut self,
+        f: impl FnMut(&str, &str, &serde_json::Value) + Send + 'a,
+    ) -> Self {
+        self.tool_call = Some(Box::new(f));
+        self
+    }
+
+    /// Called with each mid-stream `error` event.
+    #[must_use]
+    pub fn on_error(mut self, f: impl FnMut(&ErrorResponse) + Send + 'a) -> Self {
+        self.error = Some(Box::new(f));
+        self
+    }
+
+    /// Called with every event, before any typed callback.
+    #[must_use]
+    pub fn on_event(mut self, f: impl FnMut(&StreamEvent) + Send + 'a) -> Self {
+        self.event = Some(Box::new(f));
+        self
+    }
+
+    /// Called once with the assembled message when the stream ends.
+    #[must_use]
+    pub fn on_complete(mut self, f: impl FnOnce(&MessagesResponse) + Send + 'a) -> Self {
+        self.complete = Some(Box::new(f));
+        self
+    }
+
+    /// Drive `stream` to completion, dispatching every event.
+    ///
+    /// Returns the assembled message, or `None` if the stream had no
+    /// `message_start` (in which case `on_complete` is not called).
+    pub async fn run<S>(mut self, mut stream: S) -> Option<MessagesResponse>
+    where
+        S: Stream<Item = StreamEvent> + Unpin,
+    {
+        use tokio_stream::StreamExt;
+        let mut acc = Accumulator::new();
+        while let Some(event) = StreamExt::next(&mut stream).await {
+            acc.feed(&event);
+            self.dispatch(&acc, &event);
+        }
+        let message = acc.finish()?;
+        if let Some(f) = self.complete.take() {
+            f(&message);
+        }
+        Some(message)
+    }
+
+    fn dispatch(&mut self, acc: &Accumulator, event: &StreamEvent) {
+        if let Some(f) = &mut self.event {
+            f(event);
+        }
+        match event {
+            StreamEvent::ContentBlockDelta { delta, .. } => match delta {
+                StreamDelta::TextDelta { text } => {
+                    if let Some(f) = &mut self.text {
+                        f(text);
+                    }
+                }
+                StreamDelta::ThinkingDelta { thinking } => {
+                    if let Some(f) = &mut self.thinking {
+                        f(thinking);
+                    }
+                }
+                _ => {}
+            },
+            StreamEvent::ContentBlockStop { index } => {
+                if let (Some(f), Some(ContentBlock::ToolUse { id, name, input })) =
+                    (&mut self.tool_call, acc.blocks.get(index))
+                {
+                    f(id, name, input);
+                }
+            }
+            StreamEvent::Error { error } => {
+                if let Some(f) = &mut self.error {
+                    f(error);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -504,4 +858,250 @@ mod tests {
         assert_eq!(results.len(), 6);
         assert!(results.iter().all(|r| r.is_ok()));
     }
+
+    #[test]
+    fn accumulator_assembles_text_tool_use_and_usage() {
+        let usage = |input, output| ClaudeUsage {
+            input_tokens: input,
+            output_tokens: output,
+            cache_creation_input_tokens: None,
+            cache_read_input_tokens: None,
+        };
+        let events = vec![
+            StreamEvent::MessageStart {
+                message: MessagesResponse {
+                    id: "msg_1".into(),
+                    type_field: "message".into(),
+                    role: "assistant".into(),
+                    content: vec![],
+                    model: "claude-sonnet-4-20250514".into(),
+                    stop_reason: None,
+                    usage: usage(12, 1),
+                },
+            },
+            StreamEvent::ContentBlockStart {
+                index: 0,
+                content_block: ContentBlock::Text {
+                    text: String::new(),
+                },
+            },
+            StreamEvent::ContentBlockDelta {
+                index: 0,
+                delta: StreamDelta::TextDelta {
+                    text: "Let me ".into(),
+                },
+            },
+            StreamEvent::ContentBlockDelta {
+                index: 0,
+                delta: StreamDelta::TextDelta {
+                    text: "check.".into(),
+                },
+            },
+            StreamEvent::ContentBlockStop { index: 0 },
+            StreamEvent::ContentBlockStart {
+                index: 1,
+                content_block: ContentBlock::ToolUse {
+                    id: "toolu_1".into(),
+                    name: "read_file".into(),
+                    input: serde_json::json!({}),
+                },
+            },
+            StreamEvent::ContentBlockDelta {
+                index: 1,
+                delta: StreamDelta::InputJsonDelta {
+                    partial_json: "{\"path\": ".into(),
+                },
+            },
+            StreamEvent::ContentBlockDelta {
+                index: 1,
+                delta: StreamDelta::InputJsonDelta {
+                    partial_json: "\"a.rs\"}".into(),
+                },
+            },
+            StreamEvent::ContentBlockStop { index: 1 },
+            StreamEvent::MessageDelta {
+                delta: MessageDeltaBody {
+                    stop_reason: Some("tool_use".into()),
+                    stop_sequence: None,
+                },
+                usage: Some(usage(0, 40)),
+            },
+            StreamEvent::MessageStop {},
+        ];
+
+        let mut acc = Accumulator::new();
+        for event in &events {
+            acc.feed(event);
+        }
+        assert!(acc.is_complete());
+        assert_eq!(acc.text(), "Let me check.");
+
+        let message = acc.finish().unwrap();
+        assert_eq!(message.stop_reason.as_deref(), Some("tool_use"));
+        assert_eq!(message.usage.input_tokens, 12);
+        assert_eq!(message.usage.output_tokens, 40);
+        assert_eq!(message.content.len(), 2);
+        assert_eq!(
+            message.content[1],
+            ContentBlock::ToolUse {
+                id: "toolu_1".into(),
+                name: "read_file".into(),
+                input: serde_json::json!({"path": "a.rs"}),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn collect_message_matches_thinking_stream() {
+        let events = vec![
+            StreamEvent::MessageStart {
+                message: MessagesResponse {
+                    id: "msg_2".into(),
+                    type_field: "message".into(),
+                    role: "assistant".into(),
+                    content: vec![],
+                    model: "claude-sonnet-4-20250514".into(),
+                    stop_reason: None,
+                    usage: ClaudeUsage {
+                        input_tokens: 5,
+                        output_tokens: 0,
+                        cache_creation_input_tokens: None,
+                        cache_read_input_tokens: None,
+                    },
+                },
+            },
+            StreamEvent::ContentBlockStart {
+                index: 0,
+                content_block: ContentBlock::Thinking {
+                    thinking: String::new(),
+                    signature: None,
+                },
+            },
+            StreamEvent::ContentBlockDelta {
+                index: 0,
+                delta: StreamDelta::ThinkingDelta {
+                    thinking: "hmm".into(),
+                },
+            },
+            StreamEvent::ContentBlockDelta {
+                index: 0,
+                delta: StreamDelta::SignatureDelta {
+                    signature: "sig".into(),
+                },
+            },
+            StreamEvent::ContentBlockStop { index: 0 },
+            StreamEvent::MessageStop {},
+        ];
+        let message = MessageStream::from_vec(events)
+            .collect_message()
+            .await
+            .unwrap();
+        assert_eq!(
+            message.content,
+            vec![ContentBlock::Thinking {
+                thinking: "hmm".into(),
+                signature: Some("sig".into()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn handlers_dispatch_typed_callbacks() {
+        let events = vec![
+            StreamEvent::MessageStart {
+                message: MessagesResponse {
+                    id: "msg_3".into(),
+                    type_field: "message".into(),
+                    role: "assistant".into(),
+                    content: vec![],
+                    model: "claude-sonnet-4-20250514".into(),
+                    stop_reason: None,
+                    usage: ClaudeUsage {
+                        input_tokens: 3,
+                        output_tokens: 0,
+                        cache_creation_input_tokens: None,
+                        cache_read_input_tokens: None,
+                    },
+                },
+            },
+            StreamEvent::ContentBlockStart {
+                index: 0,
+                content_block: ContentBlock::Thinking {
+                    thinking: String::new(),
+                    signature: None,
+                },
+            },
+            StreamEvent::ContentBlockDelta {
+                index: 0,
+                delta: StreamDelta::ThinkingDelta {
+                    thinking: "plan".into(),
+                },
+            },
+            StreamEvent::ContentBlockStop { index: 0 },
+            StreamEvent::ContentBlockStart {
+                index: 1,
+                content_block: ContentBlock::Text {
+                    text: String::new(),
+                },
+            },
+            StreamEvent::ContentBlockDelta {
+                index: 1,
+                delta: StreamDelta::TextDelta { text: "Hi".into() },
+            },
+            StreamEvent::ContentBlockStop { index: 1 },
+            StreamEvent::ContentBlockStart {
+                index: 2,
+                content_block: ContentBlock::ToolUse {
+                    id: "toolu_1".into(),
+                    name: "ls".into(),
+                    input: serde_json::json!({}),
+                },
+            },
+            StreamEvent::ContentBlockDelta {
+                index: 2,
+                delta: StreamDelta::InputJsonDelta {
+                    partial_json: "{\"dir\": \".\"}".into(),
+                },
+            },
+            StreamEvent::ContentBlockStop { index: 2 },
+            StreamEvent::Error {
+                error: ErrorResponse {
+                    error_type: "overloaded_error".into(),
+                    message: "Overloaded".into(),
+                },
+            },
+            StreamEvent::MessageStop {},
+        ];
+
+        let mut text = String::new();
+        let mut thinking = String::new();
+        let mut calls = Vec::new();
+        let mut errors = Vec::new();
+        let mut completed = None;
+        let message = StreamHandlers::new()
+            .on_text(|t| text.push_str(t))
+            .on_thinking(|t| thinking.push_str(t))
+            .on_tool_call(|id, name, input| {
+                calls.push((id.to_string(), name.to_string(), input.clone()));
+            })
+            .on_error(|e| errors.push(e.error_type.clone()))
+            .on_complete(|m| completed = Some(m.id.clone()))
+            .run(MessageStream::from_vec(events))
+            .await
+            .unwrap();
+
+        assert_eq!(text, "Hi");
+        assert_eq!(thinking, "plan");
+        assert_eq!(
+            calls,
+            vec![(
+                "toolu_1".to_string(),
+                "ls".to_string(),
+                serde_json::json!({"dir": "."})
+            )]
+        );
+        assert_eq!(errors, vec!["overloaded_error".to_string()]);
+        assert_eq!(completed.as_deref(), Some("msg_3"));
+        assert_eq!(message.content.len(), 3);
+    }
 }
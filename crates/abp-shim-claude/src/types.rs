@@ -4,6 +4,13 @@
 //! These types mirror the **real** Anthropic Messages API JSON format,
 //! suitable for serializing requests to `POST /v1/messages` and
 //! deserializing responses (both synchronous and streamed SSE).
+//!
+//! Prompt caching is out of scope: `cache_control` on content blocks and
+//! system blocks is not modelled and is ignored when a request is parsed.
+//! Set breakpoints on the work order's IR conversation instead
+//! ([`IrMessage::with_cache_control`](abp_core::ir::IrMessage::with_cache_control)
+//! and
+//! [`IrMessage::with_block_cache_control`](abp_core::ir::IrMessage::with_block_cache_control)).
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
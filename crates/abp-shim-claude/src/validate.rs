@@ -0,0 +1,219 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Client-side request validation mirroring the Messages API.
+//!
+//! [`validate_request`](crate::validate::validate_request) enforces the constraints the real API checks before
+//! it starts generating, so a malformed request fails fast with an
+//! [`ClaudeShimError::InvalidRequest`](crate::error::ClaudeShimError::InvalidRequest) carrying the same `field: reason`
+//! shape as the API's `invalid_request_error` messages.
+
+use std::collections::BTreeSet;
+
+use crate::error::ClaudeShimError;
+use crate::types::{ClaudeContent, ClaudeMessage, ContentBlock, MessagesRequest};
+
+/// Check `request` against the Messages API's request constraints.
+///
+/// - `messages` is non-empty and every role is `"user"` or `"assistant"`;
+/// - roles alternate, starting with `"user"`;
+/// - `max_tokens` is at least 1;
+/// - each `tool_result` block sits in a user message and answers a
+///   `tool_use` block from the immediately preceding assistant message;
+/// - `thinking.budget_tokens` does not exceed `max_tokens`.
+///
+/// # Errors
+///
+/// Returns [`ClaudeShimError::InvalidRequest`] describing the first
+/// violation found.
+pub fn validate_request(request: &MessagesRequest) -> Result<(), ClaudeShimError> {
+    if request.messages.is_empty() {
+        return Err(invalid("messages must not be empty"));
+    }
+    if request.max_tokens == 0 {
+        return Err(invalid("max_tokens: must be greater than or equal to 1"));
+    }
+    if let Some(thinking) = &request.thinking
+        && thinking.budget_tokens > request.max_tokens
+    {
+        return Err(invalid(format!(
+            "thinking.budget_tokens: {} must not exceed max_tokens ({})",
+            thinking.budget_tokens, request.max_tokens
+        )));
+    }
+
+    let mut previous: Option<&ClaudeMessage> = None;
+    for (i, message) in request.messages.iter().enumerate() {
+        match message.role.as_str() {
+            "user" | "assistant" => {}
+            other => {
+                return Err(invalid(format!(
+                    "messages.{i}.role: expected \"user\" or \"assistant\", found \"{other}\""
+                )));
+            }
+        }
+        match previous {
+            None if message.role != "user" => {
+                return Err(invalid(
+                    "messages.0.role: first message must use the \"user\" role",
+                ));
+            }
+            Some(prev) if prev.role == message.role => {
+                return Err(invalid(format!(
+                    "messages.{i}: roles must alternate between \"user\" and \"assistant\", \
+                     but found multiple \"{}\" roles in a row",
+                    message.role
+                )));
+            }
+            _ => {}
+        }
+        check_tool_results(i, message, previous)?;
+        previous = Some(message);
+    }
+    Ok(())
+}
+
+/// Every `tool_result` in `message` must answer a `tool_use` in `previous`.
+fn check_tool_results(
+    index: usize,
+    message: &ClaudeMessage,
+    previous: Option<&ClaudeMessage>,
+) -> Result<(), ClaudeShimError> {
+    let tool_use_ids: BTreeSet<&str> = previous
+        .map(|p| blocks(&p.content))
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|b| match b {
+            ContentBlock::ToolUse { id, .. } => Some(id.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    for (j, block) in blocks(&message.content).iter().enumerate() {
+        let ContentBlock::ToolResult { tool_use_id, .. } = block else {
+            continue;
+        };
+        if message.role != "user" {
+            return Err(invalid(format!(
+                "messages.{index}.content.{j}: `tool_result` blocks may only appear in \"user\" messages"
+            )));
+        }
+        if !tool_use_ids.contains(tool_use_id.as_str()) {
+            return Err(invalid(format!(
+                "messages.{index}.content.{j}: unexpected `tool_use_id` found in `tool_result` \
+                 blocks: {tool_use_id}. Each `tool_result` block must have a corresponding \
+                 `tool_use` block in the previous message."
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn blocks(content: &ClaudeContent) -> &[ContentBlock] {
+    match content {
+        ClaudeContent::Blocks(blocks) => blocks,
+        ClaudeContent::Text(_) => &[],
+    }
+}
+
+fn invalid(message: impl Into<String>) -> ClaudeShimError {
+    ClaudeShimError::InvalidRequest(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messages::CreateMessageRequest;
+    use serde_json::json;
+
+    fn tool_use(id: &str) -> ContentBlock {
+        ContentBlock::ToolUse {
+            id: id.into(),
+            name: "read_file".into(),
+            input: json!({"path": "a.rs"}),
+        }
+    }
+
+    fn tool_result(id: &str) -> ContentBlock {
+        ContentBlock::ToolResult {
+            tool_use_id: id.into(),
+            content: "fn main() {}".into(),
+            is_error: None,
+        }
+    }
+
+    fn message(role: &str, blocks: Vec<ContentBlock>) -> ClaudeMessage {
+        ClaudeMessage {
+            role: role.into(),
+            content: ClaudeContent::Blocks(blocks),
+        }
+    }
+
+    fn error_of(request: &MessagesRequest) -> String {
+        match validate_request(request) {
+            Err(ClaudeShimError::InvalidRequest(msg)) => msg,
+            other => panic!("expected InvalidRequest, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn accepts_tool_round_trip() {
+        let mut req = CreateMessageRequest::new("claude-sonnet-4-20250514", 1024)
+            .user("read a.rs")
+            .build();
+        req.messages
+            .push(message("assistant", vec![tool_use("tu_1")]));
+        req.messages
+            .push(message("user", vec![tool_result("tu_1")]));
+        validate_request(&req).unwrap();
+    }
+
+    #[test]
+    fn rejects_zero_max_tokens() {
+        let req = CreateMessageRequest::new("claude-sonnet-4-20250514", 0)
+            .user("hi")
+            .build();
+        assert!(error_of(&req).starts_with("max_tokens"));
+    }
+
+    #[test]
+    fn rejects_non_alternating_roles() {
+        let req = CreateMessageRequest::new("claude-sonnet-4-20250514", 1024)
+            .user("one")
+            .user("two")
+            .build();
+        assert!(error_of(&req).contains("roles must alternate"));
+
+        let req = CreateMessageRequest::new("claude-sonnet-4-20250514", 1024)
+            .assistant("prefill")
+            .build();
+        assert!(error_of(&req).starts_with("messages.0.role"));
+    }
+
+    #[test]
+    fn rejects_unmatched_tool_result() {
+        let mut req = CreateMessageRequest::new("claude-sonnet-4-20250514", 1024)
+            .user("read a.rs")
+            .build();
+        req.messages
+            .push(message("assistant", vec![tool_use("tu_1")]));
+        req.messages
+            .push(message("user", vec![tool_result("tu_2")]));
+        let msg = error_of(&req);
+        assert!(msg.starts_with("messages.2.content.0"), "{msg}");
+        assert!(msg.contains("tu_2"));
+    }
+
+    #[test]
+    fn rejects_thinking_budget_over_max_tokens() {
+        let req = CreateMessageRequest::new("claude-sonnet-4-20250514", 1024)
+            .user("think")
+            .thinking(2048)
+            .build();
+        assert!(error_of(&req).starts_with("thinking.budget_tokens"));
+
+        let req = CreateMessageRequest::new("claude-sonnet-4-20250514", 2048)
+            .user("think")
+            .thinking(2048)
+            .build();
+        validate_request(&req).unwrap();
+    }
+}
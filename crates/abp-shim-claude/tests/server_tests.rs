@@ -0,0 +1,118 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the Anthropic-compatible HTTP frontend.
+
+use abp_shim_claude::client::AnthropicClient;
+use abp_shim_claude::error::{AnthropicErrorResponse, ClaudeShimError};
+use abp_shim_claude::server::{MESSAGES_PATH, ShimServer};
+use abp_shim_claude::types::MessagesResponse;
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use axum::response::Response;
+use http_body_util::BodyExt;
+use serde_json::json;
+use tower::ServiceExt;
+
+async fn post(server: &ShimServer, body: impl Into<Body>) -> Response {
+    let request = Request::post(MESSAGES_PATH)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body.into())
+        .unwrap();
+    server.router().oneshot(request).await.unwrap()
+}
+
+async fn body_text(response: Response) -> String {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+fn messages_body(stream: bool) -> String {
+    json!({
+        "model": "claude-sonnet-4-20250514",
+        "max_tokens": 1024,
+        "messages": [{"role": "user", "content": "Hi"}],
+        "stream": stream,
+    })
+    .to_string()
+}
+
+async fn error_of(response: Response) -> AnthropicErrorResponse {
+    serde_json::from_str(&body_text(response).await).unwrap()
+}
+
+#[tokio::test]
+async fn messages_returns_anthropic_response() {
+    let server = ShimServer::new(AnthropicClient::new("sk-test"));
+    let response = post(&server, messages_body(false)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: MessagesResponse = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(body.type_field, "message");
+    assert_eq!(body.role, "assistant");
+    assert_eq!(body.model, "claude-sonnet-4-20250514");
+}
+
+#[tokio::test]
+async fn streaming_request_names_sse_events_by_type() {
+    let server = ShimServer::new(AnthropicClient::new("sk-test"));
+    let response = post(&server, messages_body(true)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/event-stream"
+    );
+
+    let text = body_text(response).await;
+    let names: Vec<&str> = text
+        .lines()
+        .filter_map(|l| l.strip_prefix("event: "))
+        .collect();
+    assert_eq!(names.first(), Some(&"message_start"));
+    assert_eq!(names.last(), Some(&"message_stop"));
+    assert!(names.contains(&"content_block_delta"));
+    for data in text.lines().filter_map(|l| l.strip_prefix("data: ")) {
+        let value: serde_json::Value = serde_json::from_str(data).unwrap();
+        assert!(value["type"].is_string());
+    }
+}
+
+#[tokio::test]
+async fn invalid_request_returns_400_error_body() {
+    let server = ShimServer::new(AnthropicClient::new("sk-test"));
+    let body = json!({
+        "model": "claude-sonnet-4-20250514",
+        "max_tokens": 0,
+        "messages": [{"role": "user", "content": "Hi"}],
+    });
+    let response = post(&server, body.to_string()).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let err = error_of(response).await;
+    assert_eq!(err.response_type, "error");
+    assert_eq!(err.error.error_type, "invalid_request_error");
+    assert!(err.error.message.starts_with("max_tokens"));
+}
+
+#[tokio::test]
+async fn malformed_body_returns_invalid_request_error() {
+    let server = ShimServer::new(AnthropicClient::new("sk-test"));
+    let response = post(&server, "{not json").await;
+    assert!(response.status().is_client_error());
+    assert_eq!(
+        error_of(response).await.error.error_type,
+        "invalid_request_error"
+    );
+}
+
+#[tokio::test]
+async fn api_errors_keep_their_status() {
+    let mut client = AnthropicClient::new("sk-test");
+    client.set_handler(Box::new(|_| {
+        Err(ClaudeShimError::from_status_and_body(529, "overloaded"))
+    }));
+    let response = post(&ShimServer::new(client), messages_body(false)).await;
+    assert_eq!(response.status().as_u16(), 529);
+    assert_eq!(
+        error_of(response).await.error.error_type,
+        "overloaded_error"
+    );
+}
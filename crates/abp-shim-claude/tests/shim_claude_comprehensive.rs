@@ -65,6 +65,8 @@ fn simple_request(text: &str) -> MessageRequest {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     }
 }
 
@@ -487,6 +489,8 @@ fn request_serde_roundtrip_full() {
         stop_sequences: Some(vec!["END".into()]),
         thinking: Some(ThinkingConfig::new(1024)),
         stream: Some(true),
+        tools: None,
+        tool_choice: None,
     };
     let json = serde_json::to_string(&req).unwrap();
     let back: MessageRequest = serde_json::from_str(&json).unwrap();
@@ -30,9 +30,14 @@ pub fn request_to_ir(request: &CodexRequest) -> IrConversation {
 /// Convert a [`CodexRequest`] into an ABP [`WorkOrder`].
 ///
 /// Maps the Codex request fields onto an ABP work order:
-/// - `input` → task text (extracted from last user message)
+/// - `input` → task text (extracted from last user message), plus the full
+///   history as an IR conversation
 /// - `model` → `work_order.config.model`
 /// - `temperature`, `max_output_tokens` → `work_order.config.vendor`
+/// - Projected input tokens → `config.vendor["abp"]["projected_input_tokens"]`
+///   (see [`abp_tokenize::project`])
+/// - Capabilities the content needs (tools, images, `response_format`) →
+///   `requirements` (see [`abp_capability::infer`])
 pub fn request_to_work_order(request: &CodexRequest) -> WorkOrder {
     let conv = request_to_ir(request);
     let task = conv
@@ -60,9 +65,12 @@ pub fn request_to_work_order(request: &CodexRequest) -> WorkOrder {
         vendor,
         ..Default::default()
     };
-    builder = builder.config(config);
+    builder = builder.config(config).conversation(conv);
 
-    builder.build()
+    let mut work_order = builder.build();
+    abp_tokenize::project(&mut work_order);
+    abp_capability::infer::infer_requirements(&mut work_order);
+    work_order
 }
 
 // ── Conversion: Receipt → CodexResponse ─────────────────────────────────
@@ -32,11 +32,16 @@ use crate::types::{
 /// Convert a [`CodexExtendedRequest`] into an ABP [`WorkOrder`].
 ///
 /// Maps all Codex-specific fields:
-/// - `input` → task text (from last user message)
+/// - `input` → task text (from last user message), plus the full history
+///   as an IR conversation
 /// - `instructions` → system-level context snippet
 /// - `context` → context packet file entries and snippets
 /// - `model`, `temperature`, `max_output_tokens` → runtime config
 /// - `sandbox` → vendor config sandbox settings
+/// - Projected input tokens → `config.vendor["abp"]["projected_input_tokens"]`
+///   (see [`abp_tokenize::project`])
+/// - Capabilities the content needs (tools, images, `response_format`) →
+///   `requirements` (see [`abp_capability::infer`])
 pub fn codex_to_work_order(request: &CodexExtendedRequest) -> WorkOrder {
     let base_request = request.to_base_request();
     let conv = request_to_ir(&base_request);
@@ -76,7 +81,7 @@ pub fn codex_to_work_order(request: &CodexExtendedRequest) -> WorkOrder {
         vendor,
         ..Default::default()
     };
-    builder = builder.config(config);
+    builder = builder.config(config).conversation(conv);
 
     // Build context packet from instructions + context items
     let mut snippets = Vec::new();
@@ -98,7 +103,10 @@ pub fn codex_to_work_order(request: &CodexExtendedRequest) -> WorkOrder {
     let context = abp_core::ContextPacket { files, snippets };
     builder = builder.context(context);
 
-    builder.build()
+    let mut work_order = builder.build();
+    abp_tokenize::project(&mut work_order);
+    abp_capability::infer::infer_requirements(&mut work_order);
+    work_order
 }
 
 // ── Receipt → Extended Codex response ───────────────────────────────────
@@ -45,9 +45,12 @@ pub fn request_to_work_order(request: &abp_copilot_sdk::dialect::CopilotRequest)
         model: Some(request.model.clone()),
         ..Default::default()
     };
-    builder = builder.config(config);
+    builder = builder.config(config).conversation(conv);
 
-    builder.build()
+    let mut work_order = builder.build();
+    abp_tokenize::project(&mut work_order);
+    abp_capability::infer::infer_requirements(&mut work_order);
+    work_order
 }
 
 // ── Conversion: Receipt → CopilotResponse ───────────────────────────────
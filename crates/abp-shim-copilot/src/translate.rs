@@ -35,6 +35,10 @@ use crate::types::{
 /// - `references` → `work_order.context.files` / `vendor["copilot_references"]`
 /// - `skills` → `work_order.config.vendor["copilot_skills"]`
 /// - `temperature`, `max_tokens` → `work_order.config.vendor`
+/// - Projected input tokens → `config.vendor["abp"]["projected_input_tokens"]`
+///   (see [`abp_tokenize::project`])
+/// - Capabilities the content needs (tools, images, `response_format`) →
+///   `requirements` (see [`abp_capability::infer`])
 pub fn copilot_to_work_order(req: &CopilotChatRequest) -> WorkOrder {
     let task = extract_task(req);
     let mut builder = WorkOrderBuilder::new(task).model(req.model.clone());
@@ -125,7 +129,10 @@ pub fn copilot_to_work_order(req: &CopilotChatRequest) -> WorkOrder {
     };
     builder = builder.config(config).context(context);
 
-    builder.build()
+    let mut work_order = builder.build();
+    abp_tokenize::project(&mut work_order);
+    abp_capability::infer::infer_requirements(&mut work_order);
+    work_order
 }
 
 /// Extract the task string from the last user message.
@@ -5,15 +5,17 @@
 //! and the internal dialect types, as well as the ABP intermediate
 //! representation (IR) used for the pipeline.
 
-use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole, IrToolDefinition, IrUsage};
+use abp_core::ir::{
+    IrContentBlock, IrConversation, IrMessage, IrRole, IrToolChoice, IrToolDefinition, IrUsage,
+};
 use abp_core::{
     AgentEvent, AgentEventKind, Outcome, Receipt, ReceiptBuilder, UsageNormalized, WorkOrderBuilder,
 };
 use abp_gemini_sdk::dialect::{
-    self, GeminiContent, GeminiFunctionCallingConfig, GeminiFunctionDeclaration,
-    GeminiGenerationConfig, GeminiInlineData, GeminiPart, GeminiRequest, GeminiResponse,
-    GeminiSafetyRating, GeminiSafetySetting, GeminiStreamChunk, GeminiTool, GeminiToolConfig,
-    HarmProbability as DialectHarmProbability,
+    self, FunctionCallingMode, GeminiContent, GeminiFunctionCallingConfig,
+    GeminiFunctionDeclaration, GeminiGenerationConfig, GeminiInlineData, GeminiPart, GeminiRequest,
+    GeminiResponse, GeminiSafetyRating, GeminiSafetySetting, GeminiStreamChunk, GeminiTool,
+    GeminiToolConfig, HarmProbability as DialectHarmProbability,
 };
 use abp_gemini_sdk::lowering;
 use chrono::Utc;
@@ -28,6 +30,10 @@ use crate::types::{
 // ── Shim ↔ Dialect conversions ──────────────────────────────────────────
 
 /// Convert a shim [`Part`] to a dialect [`GeminiPart`].
+///
+/// The dialect has no fragment type, so a lone
+/// [`Part::FunctionCallDelta`] becomes a function call with whatever
+/// arguments it carries; assemble streamed calls first.
 #[must_use]
 pub fn part_to_dialect(part: &Part) -> GeminiPart {
     match part {
@@ -40,6 +46,12 @@ pub fn part_to_dialect(part: &Part) -> GeminiPart {
             name: name.clone(),
             args: args.clone(),
         },
+        Part::FunctionCallDelta {
+            name, partial_args, ..
+        } => GeminiPart::FunctionCall {
+            name: name.clone().unwrap_or_default(),
+            args: crate::streaming::assembled_args(partial_args),
+        },
         Part::FunctionResponse { name, response } => GeminiPart::FunctionResponse {
             name: name.clone(),
             response: response.clone(),
@@ -278,6 +290,23 @@ pub fn tools_to_ir(tools: &[ToolDeclaration]) -> Vec<IrToolDefinition> {
         .collect()
 }
 
+/// Convert a shim [`ToolConfig`] to the IR tool-choice policy.
+///
+/// `ANY` restricted to exactly one function forces that function; any other
+/// `ANY` maps to [`IrToolChoice::Required`].
+#[must_use]
+pub fn tool_config_to_ir(tc: &ToolConfig) -> IrToolChoice {
+    let cfg = &tc.function_calling_config;
+    match cfg.mode {
+        FunctionCallingMode::Auto => IrToolChoice::Auto,
+        FunctionCallingMode::None => IrToolChoice::None,
+        FunctionCallingMode::Any => match cfg.allowed_function_names.as_deref() {
+            Some([name]) => IrToolChoice::Tool { name: name.clone() },
+            _ => IrToolChoice::Required,
+        },
+    }
+}
+
 /// Convert a slice of [`IrToolDefinition`]s back to shim [`ToolDeclaration`]s.
 ///
 /// Each IR tool becomes a single-function [`ToolDeclaration`] to match the
@@ -366,6 +395,10 @@ pub fn from_dialect_stream_chunk(chunk: &GeminiStreamChunk) -> StreamEvent {
 pub struct IrRequest {
     /// The conversation in ABP intermediate representation.
     pub conversation: IrConversation,
+    /// Tools declared on the request.
+    pub tools: Vec<IrToolDefinition>,
+    /// Tool-choice policy from the request's `toolConfig`, if set.
+    pub tool_choice: Option<IrToolChoice>,
 }
 
 /// Convert a [`GenerateContentRequest`] to the ABP intermediate representation.
@@ -387,7 +420,18 @@ pub fn request_to_ir(
     let gen_config = req.generation_config.clone();
     let safety = req.safety_settings.clone().unwrap_or_default();
 
-    Ok((IrRequest { conversation }, gen_config, safety))
+    let tools = req.tools.as_deref().map(tools_to_ir).unwrap_or_default();
+    let tool_choice = req.tool_config.as_ref().map(tool_config_to_ir);
+
+    Ok((
+        IrRequest {
+            conversation,
+            tools,
+            tool_choice,
+        },
+        gen_config,
+        safety,
+    ))
 }
 
 /// Convert an IR request into an ABP [`WorkOrder`][abp_core::WorkOrder].
@@ -411,15 +455,25 @@ pub fn ir_to_work_order(
     } else {
         task
     })
-    .model(dialect::to_canonical_model(model));
+    .model(dialect::to_canonical_model(model))
+    .conversation(ir.conversation.clone());
 
     if let Some(cfg) = gen_config
         && let Some(max_tokens) = cfg.max_output_tokens
     {
         builder = builder.max_turns(max_tokens);
     }
+    if !ir.tools.is_empty() {
+        builder = builder.tools(ir.tools.clone());
+    }
+    if let Some(choice) = &ir.tool_choice {
+        builder = builder.tool_choice(choice.clone());
+    }
 
-    builder.build()
+    let mut work_order = builder.build();
+    abp_tokenize::project(&mut work_order);
+    abp_capability::infer::infer_requirements(&mut work_order);
+    work_order
 }
 
 /// Execute a work order and produce a mock receipt.
@@ -597,6 +651,40 @@ pub fn make_usage_metadata(usage: &UsageNormalized) -> Option<UsageMetadata> {
     }
 }
 
+/// Convert a single [`AgentEvent`] into a Gemini stream chunk.
+///
+/// Assistant text and deltas become text chunks, tool calls become
+/// `functionCall` chunks, errors become a text chunk finished with `OTHER`,
+/// and `RunCompleted` becomes an empty chunk finished with `STOP`. Other
+/// events have no Gemini equivalent and yield `None`.
+#[must_use]
+pub fn agent_event_to_stream_event(event: &AgentEvent) -> Option<StreamEvent> {
+    let (parts, finish_reason) = match &event.kind {
+        AgentEventKind::AssistantMessage { text } | AgentEventKind::AssistantDelta { text } => {
+            (vec![Part::text(text.clone())], None)
+        }
+        AgentEventKind::ToolCall {
+            tool_name, input, ..
+        } => (
+            vec![Part::function_call(tool_name.clone(), input.clone())],
+            None,
+        ),
+        AgentEventKind::Error { message, .. } => {
+            (vec![Part::text(format!("Error: {message}"))], Some("OTHER"))
+        }
+        AgentEventKind::RunCompleted { .. } => (vec![], Some("STOP")),
+        _ => return None,
+    };
+    Some(StreamEvent {
+        candidates: vec![Candidate {
+            content: Content::model(parts),
+            finish_reason: finish_reason.map(Into::into),
+            safety_ratings: None,
+        }],
+        usage_metadata: None,
+    })
+}
+
 /// Convert a [`Receipt`] into a sequence of [`StreamEvent`]s.
 #[must_use]
 pub fn receipt_to_stream_events(receipt: &Receipt) -> Vec<StreamEvent> {
@@ -609,53 +697,11 @@ pub fn receipt_to_stream_events(receipt: &Receipt) -> Vec<StreamEvent> {
     };
 
     for agent_event in &receipt.trace {
-        match &agent_event.kind {
-            AgentEventKind::AssistantMessage { text } | AgentEventKind::AssistantDelta { text } => {
-                events.push(StreamEvent {
-                    candidates: vec![Candidate {
-                        content: Content::model(vec![Part::text(text.clone())]),
-                        finish_reason: None,
-                        safety_ratings: None,
-                    }],
-                    usage_metadata: None,
-                });
+        if let Some(mut event) = agent_event_to_stream_event(agent_event) {
+            if matches!(agent_event.kind, AgentEventKind::RunCompleted { .. }) {
+                event.candidates[0].finish_reason = Some(finish_reason.into());
             }
-            AgentEventKind::ToolCall {
-                tool_name, input, ..
-            } => {
-                events.push(StreamEvent {
-                    candidates: vec![Candidate {
-                        content: Content::model(vec![Part::function_call(
-                            tool_name.clone(),
-                            input.clone(),
-                        )]),
-                        finish_reason: None,
-                        safety_ratings: None,
-                    }],
-                    usage_metadata: None,
-                });
-            }
-            AgentEventKind::Error { message, .. } => {
-                events.push(StreamEvent {
-                    candidates: vec![Candidate {
-                        content: Content::model(vec![Part::text(format!("Error: {message}"))]),
-                        finish_reason: Some("OTHER".into()),
-                        safety_ratings: None,
-                    }],
-                    usage_metadata: None,
-                });
-            }
-            AgentEventKind::RunCompleted { .. } => {
-                events.push(StreamEvent {
-                    candidates: vec![Candidate {
-                        content: Content::model(vec![]),
-                        finish_reason: Some(finish_reason.into()),
-                        safety_ratings: None,
-                    }],
-                    usage_metadata: None,
-                });
-            }
-            _ => {}
+            events.push(event);
         }
     }
 
@@ -26,7 +26,8 @@ pub use types::*;
 pub use client::{GeminiClient, GeminiClientBuilder};
 pub use generate::{GenerateContentRequestBuilder, response_full_text, text_request};
 pub use streaming::{
-    GeminiStreamParser, StreamAdapter, accumulate_text, final_usage, parse_stream_body,
+    Accumulator, FunctionCallAssembler, GeminiStreamParser, StreamAdapter, StreamHandlers,
+    accumulate_text, final_usage, from_agent_events, parse_stream_body,
 };
 
 // ── Re-exports from dialect for user convenience ────────────────────────
@@ -36,19 +37,43 @@ pub use abp_gemini_sdk::dialect::{
     HarmBlockThreshold, HarmCategory,
 };
 
+use std::sync::Arc;
+
+use abp_core::{Receipt, WorkOrder};
+use abp_runtime::Runtime;
 use tokio_stream::Stream;
 
 // ── Pipeline Client ──────────────────────────────────────────────────────
 
+/// A callback that processes a [`WorkOrder`] and returns a [`Receipt`].
+///
+/// Shared rather than boxed so that [`PipelineClient`] stays `Clone`.
+pub type ProcessFn = Arc<dyn Fn(&WorkOrder) -> Receipt + Send + Sync>;
+
 /// ABP-pipeline client that routes requests through the internal pipeline.
 ///
 /// Routes: request → IR → WorkOrder → (execute) → Receipt → IR → response.
 ///
+/// Work orders are executed by an installed [`ProcessFn`], then by the
+/// runtime backend selected with [`PipelineClient::with_runtime`], and
+/// finally by a mock executor that echoes the task.
+///
 /// For a drop-in SDK replacement that takes an API key, use
 /// [`client::GeminiClient`] instead.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct PipelineClient {
     model: String,
+    processor: Option<ProcessFn>,
+    runtime: Option<(Arc<Runtime>, String)>,
+}
+
+impl std::fmt::Debug for PipelineClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PipelineClient")
+            .field("model", &self.model)
+            .field("backend", &self.backend_name())
+            .finish()
+    }
 }
 
 impl PipelineClient {
@@ -57,15 +82,84 @@ impl PipelineClient {
     pub fn new(model: impl Into<String>) -> Self {
         Self {
             model: model.into(),
+            processor: None,
+            runtime: None,
+        }
+    }
+
+    /// Create a client that executes requests on `backend` through `runtime`.
+    ///
+    /// Each request becomes a [`WorkOrder`] that is run with
+    /// [`Runtime::run_streaming`]; the resulting [`Receipt`] is converted back
+    /// into a [`GenerateContentResponse`] or [`StreamEvent`] sequence.
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use abp_runtime::Runtime;
+    /// use abp_shim_gemini::PipelineClient;
+    ///
+    /// let client = PipelineClient::with_runtime(
+    ///     Arc::new(Runtime::with_default_backends()),
+    ///     "mock",
+    /// );
+    /// assert_eq!(client.backend_name(), Some("mock"));
+    /// ```
+    #[must_use]
+    pub fn with_runtime(runtime: Arc<Runtime>, backend: impl Into<String>) -> Self {
+        Self {
+            runtime: Some((runtime, backend.into())),
+            ..Self::new(abp_gemini_sdk::dialect::DEFAULT_MODEL)
         }
     }
 
+    /// Set a custom processor function for handling work orders.
+    ///
+    /// A processor takes precedence over a runtime backend; it is used for
+    /// testing and custom routing.
+    #[must_use]
+    pub fn with_processor(mut self, processor: ProcessFn) -> Self {
+        self.processor = Some(processor);
+        self
+    }
+
     /// Return the model this client targets.
     #[must_use]
     pub fn model(&self) -> &str {
         &self.model
     }
 
+    /// Name of the runtime backend requests are routed to, if any.
+    #[must_use]
+    pub fn backend_name(&self) -> Option<&str> {
+        self.runtime.as_ref().map(|(_, backend)| backend.as_str())
+    }
+
+    /// Execute `work_order` on the processor, runtime backend, or mock.
+    async fn execute(&self, work_order: WorkOrder) -> Result<Receipt, GeminiError> {
+        if let Some(processor) = &self.processor {
+            return Ok(processor(&work_order));
+        }
+        let Some((runtime, backend)) = &self.runtime else {
+            return Ok(execute_work_order(&work_order));
+        };
+
+        let handle = runtime
+            .run_streaming(backend, work_order)
+            .await
+            .map_err(|e| GeminiError::BackendError(e.to_string()))?;
+
+        // Drain the live event channel so the backend never blocks on a full
+        // buffer; the receipt carries the full trace.
+        let mut events = handle.events;
+        while tokio_stream::StreamExt::next(&mut events).await.is_some() {}
+
+        handle
+            .receipt
+            .await
+            .map_err(|e| GeminiError::BackendError(e.to_string()))?
+            .map_err(|e| GeminiError::BackendError(e.to_string()))
+    }
+
     /// Non-streaming content generation.
     ///
     /// Converts the request through the ABP pipeline and returns the response.
@@ -79,7 +173,7 @@ impl PipelineClient {
     ) -> Result<GenerateContentResponse, GeminiError> {
         let (ir_request, gen_config, safety_settings) = request_to_ir(&request)?;
         let work_order = ir_to_work_order(&ir_request, &request.model, &gen_config);
-        let receipt = execute_work_order(&work_order);
+        let receipt = self.execute(work_order).await?;
         let ir_response = receipt_to_ir(&receipt);
         ir_to_response(&ir_response, &receipt, &gen_config, &safety_settings)
     }
@@ -91,14 +185,14 @@ impl PipelineClient {
     ///
     /// # Errors
     ///
-    /// Returns [`GeminiError`] if initial conversion fails.
+    /// Returns [`GeminiError`] if initial conversion or execution fails.
     pub async fn generate_stream(
         &self,
         request: GenerateContentRequest,
     ) -> Result<impl Stream<Item = StreamEvent>, GeminiError> {
         let (ir_request, gen_config, _safety) = request_to_ir(&request)?;
         let work_order = ir_to_work_order(&ir_request, &request.model, &gen_config);
-        let receipt = execute_work_order(&work_order);
+        let receipt = self.execute(work_order).await?;
 
         let events = receipt_to_stream_events(&receipt);
         Ok(tokio_stream::iter(events))
@@ -110,7 +204,7 @@ impl PipelineClient {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole, IrUsage};
+    use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole, IrToolChoice, IrUsage};
     use abp_core::{AgentEventKind, Outcome, ReceiptBuilder};
     use abp_gemini_sdk::dialect::{
         self, GeminiCandidate, GeminiContent, GeminiPart, GeminiResponse, GeminiStreamChunk,
@@ -502,6 +596,51 @@ mod tests {
         assert_eq!(wo.config.model.as_deref(), Some("google/gemini-2.5-pro"));
     }
 
+    #[test]
+    fn work_order_carries_tools_and_tool_choice() {
+        let req = GenerateContentRequest::new("gemini-2.5-pro")
+            .add_content(Content::user(vec![Part::text("weather?")]))
+            .tools(vec![ToolDeclaration {
+                function_declarations: vec![FunctionDeclaration {
+                    name: "get_weather".into(),
+                    description: "Get weather".into(),
+                    parameters: json!({"type": "object"}),
+                }],
+            }])
+            .tool_config(ToolConfig {
+                function_calling_config: FunctionCallingConfig {
+                    mode: FunctionCallingMode::Any,
+                    allowed_function_names: Some(vec!["get_weather".into()]),
+                },
+            });
+        let (ir, gen_config, _) = request_to_ir(&req).unwrap();
+        assert_eq!(
+            ir.tool_choice,
+            Some(IrToolChoice::Tool {
+                name: "get_weather".into()
+            })
+        );
+
+        let wo = ir_to_work_order(&ir, &req.model, &gen_config);
+        let abp = &wo.config.vendor["abp"];
+        assert_eq!(abp["tools"][0]["name"], "get_weather");
+        assert_eq!(
+            abp["tool_choice"],
+            json!({"type": "tool", "name": "get_weather"})
+        );
+    }
+
+    #[test]
+    fn tool_config_any_without_single_name_is_required() {
+        let tc = ToolConfig {
+            function_calling_config: FunctionCallingConfig {
+                mode: FunctionCallingMode::Any,
+                allowed_function_names: None,
+            },
+        };
+        assert_eq!(tool_config_to_ir(&tc), IrToolChoice::Required);
+    }
+
     // ── 10. Error responses ─────────────────────────────────────────────
 
     #[test]
@@ -1596,4 +1735,56 @@ mod tests {
         let json = serde_json::to_string(&schema).unwrap();
         assert!(json.contains("Candidate"));
     }
+
+    // ── Runtime and processor execution ─────────────────────────────────
+
+    #[tokio::test]
+    async fn runtime_backend_serves_requests() {
+        let client = PipelineClient::with_runtime(
+            Arc::new(abp_runtime::Runtime::with_default_backends()),
+            "mock",
+        );
+        let request = GenerateContentRequest::new("gemini-2.5-flash")
+            .add_content(Content::user(vec![Part::text("Hello")]));
+        let response = client.generate(request).await.unwrap();
+        assert!(response.text().unwrap().contains("mock backend"));
+    }
+
+    #[tokio::test]
+    async fn unknown_runtime_backend_is_an_error() {
+        let client = PipelineClient::with_runtime(
+            Arc::new(abp_runtime::Runtime::with_default_backends()),
+            "nope",
+        );
+        let request = GenerateContentRequest::new("gemini-2.5-flash")
+            .add_content(Content::user(vec![Part::text("Hello")]));
+        let err = client.generate(request).await.unwrap_err();
+        assert!(matches!(err, GeminiError::BackendError(_)));
+    }
+
+    #[tokio::test]
+    async fn processor_takes_precedence() {
+        let processor: ProcessFn = Arc::new(|wo| {
+            ReceiptBuilder::new("custom")
+                .outcome(Outcome::Complete)
+                .work_order_id(wo.id)
+                .add_trace_event(abp_core::AgentEvent {
+                    ts: chrono::Utc::now(),
+                    kind: AgentEventKind::AssistantMessage {
+                        text: "from processor".into(),
+                    },
+                    ext: None,
+                })
+                .build()
+        });
+        let client = PipelineClient::with_runtime(
+            Arc::new(abp_runtime::Runtime::with_default_backends()),
+            "mock",
+        )
+        .with_processor(processor);
+        let request = GenerateContentRequest::new("gemini-2.5-flash")
+            .add_content(Content::user(vec![Part::text("Hello")]));
+        let response = client.generate(request).await.unwrap();
+        assert_eq!(response.text(), Some("from processor"));
+    }
 }
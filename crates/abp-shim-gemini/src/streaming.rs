@@ -3,15 +3,27 @@
 //!
 //! The Gemini streaming API returns a JSON array where each element is a
 //! `GenerateContentResponse` chunk. This module provides parsers and
-//! adapters that process that stream incrementally.
+//! adapters that process that stream incrementally, plus an
+//! [`Accumulator`](crate::streaming::Accumulator) that folds the chunks back
+//! into a single `GenerateContentResponse`,
+//! [`StreamHandlers`](crate::streaming::StreamHandlers) for typed callbacks,
+//! and [`from_agent_events`](crate::streaming::from_agent_events) for
+//! forwarding live runtime events.
+//!
+//! Function calls may arrive whole or as
+//! [`FunctionCallDelta`](crate::types::Part::FunctionCallDelta) fragments;
+//! [`FunctionCallAssembler`](crate::streaming::FunctionCallAssembler) joins
+//! the fragments by index.
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+use abp_core::AgentEvent;
 use futures_core::Stream;
 
-use crate::types::{StreamEvent, UsageMetadata};
+use crate::convert::agent_event_to_stream_event;
+use crate::types::{Candidate, GenerateContentResponse, Part, StreamEvent, UsageMetadata};
 
 // ── Stream parser ───────────────────────────────────────────────────────
 
@@ -212,6 +224,22 @@ impl Stream for StreamAdapter {
     }
 }
 
+// ── Live runtime events ─────────────────────────────────────────────────
+
+/// Translate a live stream of ABP [`AgentEvent`]s into Gemini chunks as
+/// they arrive.
+///
+/// Each event is mapped with [`agent_event_to_stream_event`], so
+/// `AssistantDelta` tokens reach the caller as soon as the backend emits
+/// them. Pass a runtime `RunHandle::events` stream to forward a run without
+/// waiting for its receipt.
+pub fn from_agent_events<S>(events: S) -> impl Stream<Item = StreamEvent>
+where
+    S: Stream<Item = AgentEvent>,
+{
+    tokio_stream::StreamExt::filter_map(events, |event| agent_event_to_stream_event(&event))
+}
+
 // ── Convenience: parse full response ────────────────────────────────────
 
 /// Parse a complete Gemini streaming response body into events.
@@ -243,6 +271,363 @@ pub fn final_usage(events: &[StreamEvent]) -> Option<&UsageMetadata> {
     events.iter().rev().find_map(|e| e.usage_metadata.as_ref())
 }
 
+// ── Function call assembly ──────────────────────────────────────────────
+
+/// Joins [`Part::FunctionCallDelta`] fragments into whole
+/// [`Part::FunctionCall`]s, keyed by the fragment `index`.
+///
+/// ```
+/// use abp_shim_gemini::{FunctionCallAssembler, Part};
+/// use serde_json::json;
+///
+/// let mut calls = FunctionCallAssembler::new();
+/// let first = Part::function_call_delta(0, Some("search".into()), r#"{"q":"ru"#, true);
+/// assert_eq!(calls.push(&first), None);
+/// let last = Part::function_call_delta(0, None, r#"st"}"#, false);
+/// assert_eq!(
+///     calls.push(&last),
+///     Some(Part::function_call("search", json!({"q": "rust"})))
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FunctionCallAssembler {
+    pending: BTreeMap<u32, (String, String)>,
+}
+
+impl FunctionCallAssembler {
+    /// Create an assembler with no calls in progress.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one fragment, returning the finished call when `part` completes
+    /// it. Parts other than fragments are ignored.
+    pub fn push(&mut self, part: &Part) -> Option<Part> {
+        let Part::FunctionCallDelta {
+            index,
+            name,
+            partial_args,
+            will_continue,
+        } = part
+        else {
+            return None;
+        };
+        let (call_name, args) = self.pending.entry(*index).or_default();
+        if let Some(name) = name {
+            call_name.clone_from(name);
+        }
+        args.push_str(partial_args);
+        if *will_continue {
+            return None;
+        }
+        let (name, args) = self.pending.remove(index)?;
+        Some(Part::function_call(name, assembled_args(&args)))
+    }
+
+    /// Whether any call is still waiting for fragments.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Finish every call still in progress, in index order.
+    ///
+    /// Used when a stream ends without closing its calls.
+    pub fn flush(&mut self) -> Vec<Part> {
+        std::mem::take(&mut self.pending)
+            .into_values()
+            .map(|(name, args)| Part::function_call(name, assembled_args(&args)))
+            .collect()
+    }
+}
+
+/// Arguments of an assembled call: the parsed JSON, `{}` when nothing was
+/// streamed, or the raw text if it is not valid JSON.
+pub(crate) fn assembled_args(raw: &str) -> serde_json::Value {
+    if raw.trim().is_empty() {
+        return serde_json::json!({});
+    }
+    serde_json::from_str(raw).unwrap_or_else(|_| serde_json::Value::String(raw.to_string()))
+}
+
+// ── Response accumulator ────────────────────────────────────────────────
+
+/// Incrementally rebuilds a [`GenerateContentResponse`] from
+/// [`StreamEvent`] chunks.
+///
+/// Candidates are matched by their position in each chunk. Consecutive text
+/// parts are merged into one, function calls and other parts are appended
+/// as they arrive, streamed function call fragments are appended as whole
+/// calls once complete, and the latest finish reason, safety ratings, and
+/// usage metadata win.
+///
+/// ```
+/// use abp_shim_gemini::{Accumulator, parse_stream_body};
+///
+/// let body = r#"[
+///   {"candidates":[{"content":{"role":"model","parts":[{"text":"Hel"}]}}]},
+///   {"candidates":[{"content":{"role":"model","parts":[{"text":"lo"}]},"finishReason":"STOP"}]}
+/// ]"#;
+/// let mut acc = Accumulator::new();
+/// for event in parse_stream_body(body) {
+///     acc.feed(&event);
+/// }
+/// assert_eq!(acc.finish().text(), Some("Hello"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Accumulator {
+    candidates: Vec<Candidate>,
+    calls: Vec<FunctionCallAssembler>,
+    usage: Option<UsageMetadata>,
+}
+
+impl Accumulator {
+    /// Create an empty accumulator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold one streaming chunk into the response being built.
+    pub fn feed(&mut self, event: &StreamEvent) {
+        if let Some(usage) = &event.usage_metadata {
+            self.usage = Some(usage.clone());
+        }
+        for (i, chunk) in event.candidates.iter().enumerate() {
+            if i == self.candidates.len() {
+                let mut fresh = chunk.clone();
+                fresh.content.parts.clear();
+                self.candidates.push(fresh);
+                self.calls.push(FunctionCallAssembler::new());
+            }
+            let candidate = &mut self.candidates[i];
+            for part in &chunk.content.parts {
+                if matches!(part, Part::FunctionCallDelta { .. }) {
+                    if let Some(call) = self.calls[i].push(part) {
+                        candidate.content.parts.push(call);
+                    }
+                    continue;
+                }
+                match (candidate.content.parts.last_mut(), part) {
+                    (Some(Part::Text(text)), Part::Text(delta)) => text.push_str(delta),
+                    _ => candidate.content.parts.push(part.clone()),
+                }
+            }
+            if chunk.finish_reason.is_some() {
+                candidate.finish_reason.clone_from(&chunk.finish_reason);
+            }
+            if chunk.safety_ratings.is_some() {
+                candidate.safety_ratings.clone_from(&chunk.safety_ratings);
+            }
+        }
+    }
+
+    /// Text accumulated so far for the first candidate.
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.candidates
+            .first()
+            .map(|c| {
+                c.content
+                    .parts
+                    .iter()
+                    .filter_map(|p| match p {
+                        Part::Text(t) => Some(t.as_str()),
+                        _ => None,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Whether every candidate seen so far has reported a finish reason.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        !self.candidates.is_empty() && self.candidates.iter().all(|c| c.finish_reason.is_some())
+    }
+
+    /// Usage metadata reported by the stream, if any.
+    #[must_use]
+    pub fn usage(&self) -> Option<&UsageMetadata> {
+        self.usage.as_ref()
+    }
+
+    /// Assemble the final response.
+    ///
+    /// Function calls whose last fragment never arrived are closed with the
+    /// arguments streamed so far.
+    #[must_use]
+    pub fn finish(mut self) -> GenerateContentResponse {
+        for (candidate, calls) in self.candidates.iter_mut().zip(&mut self.calls) {
+            candidate.content.parts.extend(calls.flush());
+        }
+        GenerateContentResponse {
+            candidates: self.candidates,
+            usage_metadata: self.usage,
+            prompt_feedback: None,
+        }
+    }
+}
+
+// ── Stream handlers ─────────────────────────────────────────────────────
+
+type StrFn<'a> = Box<dyn FnMut(&str) + Send + 'a>;
+type ToolCallFn<'a> = Box<dyn FnMut(&str, &serde_json::Value) + Send + 'a>;
+type ToolCallDeltaFn<'a> = Box<dyn FnMut(u32, Option<&str>, &str) + Send + 'a>;
+type EventFn<'a> = Box<dyn FnMut(&StreamEvent) + Send + 'a>;
+type CompleteFn<'a> = Box<dyn FnOnce(&GenerateContentResponse) + Send + 'a>;
+
+/// Typed callbacks over a stream of [`StreamEvent`] chunks.
+///
+/// Register the callbacks you need and [`run`](StreamHandlers::run) the
+/// stream. `on_text` and `on_tool_call` see the parts of the first candidate
+/// in each chunk, matching [`StreamEvent::text`]. `on_tool_call` fires as
+/// soon as a call is whole: at once for a `functionCall` part, or at the
+/// last fragment of a streamed call, whose pieces `on_tool_call_delta` sees
+/// as they arrive.
+///
+/// ```
+/// # async fn demo() {
+/// use abp_shim_gemini::{StreamAdapter, StreamHandlers, parse_stream_body};
+///
+/// let body = r#"[{"candidates":[{"content":{"role":"model","parts":[{"text":"Hi"}]}}]}]"#;
+/// let mut text = String::new();
+/// let response = StreamHandlers::new()
+///     .on_text(|t| text.push_str(t))
+///     .on_tool_call(|name, args| println!("{name}({args})"))
+///     .run(StreamAdapter::from_events(parse_stream_body(body)))
+///     .await;
+/// assert_eq!(text, "Hi");
+/// assert_eq!(response.text(), Some("Hi"));
+/// # }
+/// ```
+#[derive(Default)]
+pub struct StreamHandlers<'a> {
+    text: Option<StrFn<'a>>,
+    tool_call: Option<ToolCallFn<'a>>,
+    tool_call_delta: Option<ToolCallDeltaFn<'a>>,
+    event: Option<EventFn<'a>>,
+    complete: Option<CompleteFn<'a>>,
+}
+
+impl std::fmt::Debug for StreamHandlers<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamHandlers")
+            .field("text", &self.text.is_some())
+            .field("tool_call", &self.tool_call.is_some())
+            .field("tool_call_delta", &self.tool_call_delta.is_some())
+            .field("event", &self.event.is_some())
+            .field("complete", &self.complete.is_some())
+            .finish()
+    }
+}
+
+impl<'a> StreamHandlers<'a> {
+    /// Create a handler set with no callbacks registered.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called with each text part.
+    #[must_use]
+    pub fn on_text(mut self, f: impl FnMut(&str) + Send + 'a) -> Self {
+        self.text = Some(Box::new(f));
+        self
+    }
+
+    /// Called with `(name, args)` for each function call part.
+    #[must_use]
+    pub fn on_tool_call(mut self, f: impl FnMut(&str, &serde_json::Value) + Send + 'a) -> Self {
+        self.tool_call = Some(Box::new(f));
+        self
+    }
+
+    /// Called with `(index, name, partial_args)` for each function call
+    /// fragment; `name` is set on the first fragment of a call.
+    #[must_use]
+    pub fn on_tool_call_delta(
+        mut self,
+        f: impl FnMut(u32, Option<&str>, &str) + Send + 'a,
+    ) -> Self {
+        self.tool_call_delta = Some(Box::new(f));
+        self
+    }
+
+    /// Called with every chunk, before any typed callback.
+    #[must_use]
+    pub fn on_event(mut self, f: impl FnMut(&StreamEvent) + Send + 'a) -> Self {
+        self.event = Some(Box::new(f));
+        self
+    }
+
+    /// Called once with the assembled response when the stream ends.
+    #[must_use]
+    pub fn on_complete(mut self, f: impl FnOnce(&GenerateContentResponse) + Send + 'a) -> Self {
+        self.complete = Some(Box::new(f));
+        self
+    }
+
+    /// Drive `stream` to completion, dispatching every chunk, and return the
+    /// assembled response.
+    pub async fn run<S>(mut self, mut stream: S) -> GenerateContentResponse
+    where
+        S: Stream<Item = StreamEvent> + Unpin,
+    {
+        use tokio_stream::StreamExt;
+        let mut acc = Accumulator::new();
+        let mut calls = FunctionCallAssembler::new();
+        while let Some(event) = stream.next().await {
+            acc.feed(&event);
+            if let Some(f) = &mut self.event {
+                f(&event);
+            }
+            let parts = event.candidates.first().map(|c| c.content.parts.as_slice());
+            for part in parts.unwrap_or_default() {
+                match part {
+                    Part::Text(text) => {
+                        if let Some(f) = &mut self.text {
+                            f(text);
+                        }
+                    }
+                    Part::FunctionCall { name, args } => {
+                        if let Some(f) = &mut self.tool_call {
+                            f(name, args);
+                        }
+                    }
+                    Part::FunctionCallDelta {
+                        index,
+                        name,
+                        partial_args,
+                        ..
+                    } => {
+                        if let Some(f) = &mut self.tool_call_delta {
+                            f(*index, name.as_deref(), partial_args);
+                        }
+                        if let Some(Part::FunctionCall { name, args }) = calls.push(part)
+                            && let Some(f) = &mut self.tool_call
+                        {
+                            f(&name, &args);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        for call in calls.flush() {
+            if let (Part::FunctionCall { name, args }, Some(f)) = (call, &mut self.tool_call) {
+                f(&name, &args);
+            }
+        }
+        let response = acc.finish();
+        if let Some(f) = self.complete.take() {
+            f(&response);
+        }
+        response
+    }
+}
+
 // ── Tests ───────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -495,4 +880,191 @@ mod tests {
         assert!(!parser.is_done());
         assert_eq!(parser.pending_count(), 0);
     }
+
+    #[test]
+    fn accumulator_merges_text_and_keeps_function_calls() {
+        let call = json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [{"functionCall": {"name": "search", "args": {"q": "rust"}}}]
+                },
+                "finishReason": "STOP"
+            }]
+        })
+        .to_string();
+        let body = format!(
+            "[{},{},{},{}]",
+            make_text_event("Let me "),
+            make_text_event("search."),
+            call,
+            make_usage_event(8, 12)
+        );
+
+        let mut acc = Accumulator::new();
+        for event in parse_stream_body(&body) {
+            acc.feed(&event);
+        }
+        assert!(acc.is_complete());
+        assert_eq!(acc.text(), "Let me search.");
+        assert_eq!(acc.usage().map(|u| u.total_token_count), Some(20));
+
+        let response = acc.finish();
+        let candidate = &response.candidates[0];
+        assert_eq!(candidate.finish_reason.as_deref(), Some("STOP"));
+        assert_eq!(
+            candidate.content.parts,
+            vec![
+                Part::text("Let me search."),
+                Part::function_call("search", json!({"q": "rust"})),
+            ]
+        );
+        assert_eq!(response.usage_metadata.unwrap().prompt_token_count, 8);
+    }
+
+    #[tokio::test]
+    async fn handlers_dispatch_text_and_function_calls() {
+        let call = json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [{"functionCall": {"name": "search", "args": {"q": "rust"}}}]
+                },
+                "finishReason": "STOP"
+            }]
+        })
+        .to_string();
+        let body = format!(
+            "[{},{},{}]",
+            make_text_event("Let me "),
+            make_text_event("search."),
+            call
+        );
+
+        let mut text = String::new();
+        let mut calls = Vec::new();
+        let mut chunks = 0;
+        let mut finish = None;
+        let response = StreamHandlers::new()
+            .on_text(|t| text.push_str(t))
+            .on_tool_call(|name, args| calls.push((name.to_string(), args.clone())))
+            .on_event(|_| chunks += 1)
+            .on_complete(|r| finish = r.candidates[0].finish_reason.clone())
+            .run(StreamAdapter::from_events(parse_stream_body(&body)))
+            .await;
+
+        assert_eq!(text, "Let me search.");
+        assert_eq!(calls, vec![("search".to_string(), json!({"q": "rust"}))]);
+        assert_eq!(chunks, 3);
+        assert_eq!(finish.as_deref(), Some("STOP"));
+        assert_eq!(response.candidates[0].content.parts.len(), 2);
+    }
+
+    fn make_call_delta(index: u32, name: Option<&str>, args: &str, more: bool) -> String {
+        let mut delta = json!({"index": index, "partialArgs": args, "willContinue": more});
+        if let Some(name) = name {
+            delta["name"] = json!(name);
+        }
+        json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [{"functionCallDelta": delta}]}
+            }]
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn accumulator_assembles_interleaved_call_deltas_by_index() {
+        let body = format!(
+            "[{},{},{},{},{}]",
+            make_text_event("Checking."),
+            make_call_delta(0, Some("read"), r#"{"path":"#, true),
+            make_call_delta(1, Some("list"), "", false),
+            make_call_delta(0, None, r#""a.rs"}"#, false),
+            make_call_delta(2, Some("grep"), r#"{"pat"#, true),
+        );
+        let mut acc = Accumulator::new();
+        for event in parse_stream_body(&body) {
+            acc.feed(&event);
+        }
+        let response = acc.finish();
+        assert_eq!(
+            response.candidates[0].content.parts,
+            vec![
+                Part::text("Checking."),
+                Part::function_call("list", json!({})),
+                Part::function_call("read", json!({"path": "a.rs"})),
+                // Never closed: flushed with the raw text seen so far.
+                Part::function_call("grep", json!(r#"{"pat"#)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn handlers_report_call_deltas_and_the_assembled_call() {
+        let body = format!(
+            "[{},{}]",
+            make_call_delta(0, Some("search"), r#"{"q":"ru"#, true),
+            make_call_delta(0, None, r#"st"}"#, false),
+        );
+        let mut fragments = Vec::new();
+        let mut calls = Vec::new();
+        StreamHandlers::new()
+            .on_tool_call_delta(|i, name, args| {
+                fragments.push((i, name.map(str::to_string), args.to_string()));
+            })
+            .on_tool_call(|name, args| calls.push((name.to_string(), args.clone())))
+            .run(StreamAdapter::from_events(parse_stream_body(&body)))
+            .await;
+        assert_eq!(
+            fragments,
+            vec![
+                (0, Some("search".to_string()), r#"{"q":"ru"#.to_string()),
+                (0, None, r#"st"}"#.to_string()),
+            ]
+        );
+        assert_eq!(calls, vec![("search".to_string(), json!({"q": "rust"}))]);
+    }
+
+    #[tokio::test]
+    async fn live_agent_events_become_chunks() {
+        use abp_core::AgentEventKind;
+        use tokio_stream::StreamExt;
+
+        let event = |kind| AgentEvent {
+            ts: chrono::Utc::now(),
+            kind,
+            ext: None,
+        };
+        let (tx, rx) = tokio::sync::mpsc::channel(8);
+        let mut chunks = Box::pin(from_agent_events(
+            tokio_stream::wrappers::ReceiverStream::new(rx),
+        ));
+
+        tx.send(event(AgentEventKind::RunStarted {
+            message: "go".into(),
+        }))
+        .await
+        .unwrap();
+        tx.send(event(AgentEventKind::AssistantDelta { text: "Hel".into() }))
+            .await
+            .unwrap();
+        // The first delta is available before the run finishes.
+        assert_eq!(chunks.next().await.unwrap().text(), Some("Hel"));
+
+        tx.send(event(AgentEventKind::AssistantDelta { text: "lo".into() }))
+            .await
+            .unwrap();
+        tx.send(event(AgentEventKind::RunCompleted {
+            message: "done".into(),
+        }))
+        .await
+        .unwrap();
+        drop(tx);
+
+        let rest: Vec<_> = chunks.collect().await;
+        assert_eq!(rest.len(), 2);
+        assert_eq!(rest[0].text(), Some("lo"));
+        assert_eq!(rest[1].candidates[0].finish_reason.as_deref(), Some("STOP"));
+    }
 }
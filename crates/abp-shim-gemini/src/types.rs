@@ -17,6 +17,7 @@ use serde::{Deserialize, Serialize};
 /// - `{"text": "…"}`
 /// - `{"inlineData": {"mimeType": "…", "data": "…"}}`
 /// - `{"functionCall": {"name": "…", "args": {…}}}`
+/// - `{"functionCallDelta": {"index": 0, "name": "…", "partialArgs": "…", "willContinue": true}}`
 /// - `{"functionResponse": {"name": "…", "response": {…}}}`
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -37,6 +38,28 @@ pub enum Part {
         /// Arguments as a JSON value.
         args: serde_json::Value,
     },
+    /// A fragment of a function call whose arguments are streamed in
+    /// pieces.
+    ///
+    /// Fragments with the same `index` belong to one call: the name comes
+    /// with the first, `partial_args` pieces concatenate into the JSON
+    /// arguments, and the call is complete at the first fragment without
+    /// `will_continue`. [`Accumulator`](crate::streaming::Accumulator)
+    /// assembles them into [`Part::FunctionCall`]s.
+    #[serde(rename_all = "camelCase")]
+    FunctionCallDelta {
+        /// Position of the call among the candidate's streamed calls.
+        index: u32,
+        /// Name of the function; set on the first fragment.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        /// Next piece of the JSON-encoded arguments.
+        #[serde(default)]
+        partial_args: String,
+        /// Whether more fragments of this call follow.
+        #[serde(default)]
+        will_continue: bool,
+    },
     /// A function response returned to the model.
     FunctionResponse {
         /// Name of the function that was called.
@@ -71,6 +94,22 @@ impl Part {
         }
     }
 
+    /// Create a function call fragment.
+    #[must_use]
+    pub fn function_call_delta(
+        index: u32,
+        name: Option<String>,
+        partial_args: impl Into<String>,
+        will_continue: bool,
+    ) -> Self {
+        Self::FunctionCallDelta {
+            index,
+            name,
+            partial_args: partial_args.into(),
+            will_continue,
+        }
+    }
+
     /// Create a function response part.
     #[must_use]
     pub fn function_response(name: impl Into<String>, response: serde_json::Value) -> Self {
@@ -36,9 +36,14 @@ pub fn request_to_ir(request: &KimiRequest) -> IrConversation {
 /// Convert a [`KimiRequest`] into an ABP [`WorkOrder`].
 ///
 /// Maps:
-/// - `messages` → `work_order.task` (extracted from last user message)
+/// - `messages` → `work_order.task` (extracted from last user message), plus
+///   the full history as an IR conversation
 /// - `model` → `work_order.config.model`
 /// - `temperature`, `max_tokens` → `work_order.config.vendor`
+/// - Projected input tokens → `config.vendor["abp"]["projected_input_tokens"]`
+///   (see [`abp_tokenize::project`])
+/// - Capabilities the content needs (tools, images, `response_format`) →
+///   `requirements` (see [`abp_capability::infer`])
 pub fn request_to_work_order(request: &KimiRequest) -> WorkOrder {
     let conv = request_to_ir(request);
     let task = conv
@@ -63,9 +68,12 @@ pub fn request_to_work_order(request: &KimiRequest) -> WorkOrder {
         vendor,
         ..Default::default()
     };
-    builder = builder.config(config);
+    builder = builder.config(config).conversation(conv);
 
-    builder.build()
+    let mut work_order = builder.build();
+    abp_tokenize::project(&mut work_order);
+    abp_capability::infer::infer_requirements(&mut work_order);
+    work_order
 }
 
 // ── Conversion: Receipt → KimiResponse ──────────────────────────────────
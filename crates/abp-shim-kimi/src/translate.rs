@@ -49,6 +49,10 @@ pub fn translate_from_receipt(receipt: &Receipt, model: &str) -> KimiChatRespons
 /// - `model` → `work_order.config.model`
 /// - `temperature`, `max_tokens`, `top_p` → `work_order.config.vendor`
 /// - `use_search`, `ref_file_ids`, `plugin_ids` → `work_order.config.vendor` (under `kimi.*` keys)
+/// - Projected input tokens → `config.vendor["abp"]["projected_input_tokens"]`
+///   (see [`abp_tokenize::project`])
+/// - Capabilities the content needs (tools, images, `response_format`) →
+///   `requirements` (see [`abp_capability::infer`])
 pub fn kimi_to_work_order(request: &KimiChatRequest) -> WorkOrder {
     let task = request
         .messages
@@ -124,10 +128,13 @@ pub fn kimi_to_work_order(request: &KimiChatRequest) -> WorkOrder {
         ..Default::default()
     };
 
-    WorkOrderBuilder::new(task)
+    let mut work_order = WorkOrderBuilder::new(task)
         .model(request.model.clone())
         .config(config)
-        .build()
+        .build();
+    abp_tokenize::project(&mut work_order);
+    abp_capability::infer::infer_requirements(&mut work_order);
+    work_order
 }
 
 // ── Receipt → KimiChatResponse ──────────────────────────────────────────
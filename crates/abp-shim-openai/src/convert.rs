@@ -10,17 +10,21 @@
 
 use std::collections::BTreeMap;
 
+use abp_capability::infer::RequestContent;
+use abp_core::ir::{IrConversation, IrToolChoice, IrToolDefinition};
 use abp_core::{
     AgentEvent, AgentEventKind, Receipt, RuntimeConfig, UsageNormalized, WorkOrder,
     WorkOrderBuilder,
 };
+use abp_openai_sdk::dialect::{OpenAIFunctionCall, OpenAIMessage, OpenAIToolCall};
+use abp_openai_sdk::lowering;
 use abp_sdk_types::Dialect;
 use chrono::Utc;
 
 use crate::types::{
     ChatCompletionRequest, ChatCompletionResponse, ChatMessage, Choice, ChoiceMessage,
     FunctionCall, MessageContent, StreamChoice, StreamChunk, StreamDelta, StreamFunctionCall,
-    StreamToolCall, Tool, ToolCall, Usage,
+    StreamToolCall, Tool, ToolCall, ToolChoice, ToolChoiceMode, Usage,
 };
 
 // ── Primary conversions ─────────────────────────────────────────────────
@@ -28,11 +32,18 @@ use crate::types::{
 /// Convert an OpenAI [`ChatCompletionRequest`] into an ABP [`WorkOrder`].
 ///
 /// Maps:
-/// - `messages` → `work_order.task` (extracted from last user message)
+/// - `messages` → `work_order.task` (extracted from last user message), plus
+///   the full history as an IR conversation (see [`WorkOrderBuilder::conversation`])
 /// - `model` → `work_order.config.model`
 /// - `temperature`, `top_p`, `max_tokens`, `stream` → `work_order.config.vendor`
-/// - `tools` → `work_order.config.vendor["tools"]`
+/// - `tools` → `work_order.config.vendor["tools"]`, plus the canonical
+///   [`IrToolDefinition`]s and [`IrToolChoice`] read by backends
+///   (see [`WorkOrderBuilder::tools`])
 /// - Sets `dialect = Dialect::OpenAi` in vendor config
+/// - Projected input tokens → `config.vendor["abp"]["projected_input_tokens"]`
+///   (see [`abp_tokenize::project`])
+/// - Capabilities the content needs (tools, images, `response_format`) →
+///   `requirements` (see [`abp_capability::infer`])
 pub fn to_work_order(req: &ChatCompletionRequest) -> WorkOrder {
     let task = extract_task(req);
     let mut builder = WorkOrderBuilder::new(task).model(req.model.clone());
@@ -73,9 +84,107 @@ pub fn to_work_order(req: &ChatCompletionRequest) -> WorkOrder {
         vendor,
         ..Default::default()
     };
-    builder = builder.config(config);
+    builder = builder
+        .config(config)
+        .conversation(messages_to_ir(&req.messages));
 
-    builder.build()
+    if let Some(tools) = &req.tools {
+        builder = builder.tools(tools.iter().map(tool_to_ir).collect());
+    }
+    if let Some(tc) = &req.tool_choice {
+        builder = builder.tool_choice(tool_choice_to_ir(tc));
+    }
+
+    let mut work_order = builder.build();
+    abp_tokenize::project(&mut work_order);
+    let mut content = RequestContent::from_work_order(&work_order);
+    // Image URLs are not carried into the IR conversation.
+    content.images |= has_image_parts(&req.messages);
+    content.apply(&mut work_order);
+    work_order
+}
+
+/// Whether any user message has an image part.
+fn has_image_parts(messages: &[ChatMessage]) -> bool {
+    messages.iter().any(|m| {
+        matches!(
+            m,
+            ChatMessage::User {
+                content: MessageContent::Parts(parts)
+            } if parts
+                .iter()
+                .any(|p| matches!(p, crate::types::ContentPart::ImageUrl { .. }))
+        )
+    })
+}
+
+/// Convert OpenAI [`ChatMessage`]s into an [`IrConversation`].
+#[must_use]
+pub fn messages_to_ir(messages: &[ChatMessage]) -> IrConversation {
+    let dialect: Vec<OpenAIMessage> = messages
+        .iter()
+        .map(|m| {
+            let (content, tool_calls, tool_call_id) = match m {
+                ChatMessage::System { content } => (Some(content.clone()), None, None),
+                ChatMessage::User { content } => {
+                    (Some(message_content_to_string(content)), None, None)
+                }
+                ChatMessage::Assistant {
+                    content,
+                    tool_calls,
+                } => (
+                    content.clone(),
+                    tool_calls.as_ref().map(|tcs| {
+                        tcs.iter()
+                            .map(|tc| OpenAIToolCall {
+                                id: tc.id.clone(),
+                                call_type: tc.call_type.clone(),
+                                function: OpenAIFunctionCall {
+                                    name: tc.function.name.clone(),
+                                    arguments: tc.function.arguments.clone(),
+                                },
+                            })
+                            .collect()
+                    }),
+                    None,
+                ),
+                ChatMessage::Tool {
+                    content,
+                    tool_call_id,
+                } => (Some(content.clone()), None, Some(tool_call_id.clone())),
+            };
+            OpenAIMessage {
+                role: role_to_str(m).to_string(),
+                content,
+                tool_calls,
+                tool_call_id,
+            }
+        })
+        .collect();
+    lowering::to_ir(&dialect)
+}
+
+/// Convert a [`Tool`] to a canonical [`IrToolDefinition`].
+#[must_use]
+pub fn tool_to_ir(tool: &Tool) -> IrToolDefinition {
+    IrToolDefinition {
+        name: tool.function.name.clone(),
+        description: tool.function.description.clone(),
+        parameters: tool.function.parameters.clone(),
+    }
+}
+
+/// Convert a [`ToolChoice`] to the canonical [`IrToolChoice`].
+#[must_use]
+pub fn tool_choice_to_ir(choice: &ToolChoice) -> IrToolChoice {
+    match choice {
+        ToolChoice::Mode(ToolChoiceMode::None) => IrToolChoice::None,
+        ToolChoice::Mode(ToolChoiceMode::Auto) => IrToolChoice::Auto,
+        ToolChoice::Mode(ToolChoiceMode::Required) => IrToolChoice::Required,
+        ToolChoice::Function { function, .. } => IrToolChoice::Tool {
+            name: function.name.clone(),
+        },
+    }
 }
 
 /// Convert an ABP [`Receipt`] back into an OpenAI [`ChatCompletionResponse`].
@@ -337,6 +446,7 @@ pub fn count_roles(req: &ChatCompletionRequest) -> BTreeMap<&'static str, usize>
 mod tests {
     use super::*;
     use crate::types::*;
+    use abp_core::ir::IrRole;
     use abp_core::{
         AgentEvent, AgentEventKind, Outcome, ReceiptBuilder, UsageNormalized, WorkOrderBuilder,
     };
@@ -508,6 +618,56 @@ mod tests {
         assert!(wo.config.vendor.contains_key("tools"));
         let tools_val = &wo.config.vendor["tools"];
         assert!(tools_val.is_array());
+        assert_eq!(wo.config.vendor["abp"]["tools"][0]["name"], "get_weather");
+    }
+
+    #[test]
+    fn to_work_order_attaches_conversation() {
+        let wo = to_work_order(&request_with_system());
+        let conv: IrConversation =
+            serde_json::from_value(wo.config.vendor["abp"]["conversation"].clone()).unwrap();
+        let turns: Vec<_> = conv
+            .messages
+            .iter()
+            .map(|m| (m.role, m.text_content()))
+            .collect();
+        assert_eq!(
+            turns,
+            [
+                (IrRole::System, "You are helpful.".to_string()),
+                (IrRole::User, "Hi".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn to_work_order_infers_requirements_from_content() {
+        let mut req = request_with_tools();
+        req.messages.push(ChatMessage::User {
+            content: MessageContent::Parts(vec![ContentPart::ImageUrl {
+                image_url: ImageUrl {
+                    url: "https://example.com/img.png".into(),
+                    detail: None,
+                },
+            }]),
+        });
+        let wo = to_work_order(&req);
+        let caps: Vec<_> = wo
+            .requirements
+            .required
+            .iter()
+            .map(|r| r.capability.clone())
+            .collect();
+        assert_eq!(
+            caps,
+            [abp_core::Capability::Vision, abp_core::Capability::ToolUse]
+        );
+        assert!(
+            to_work_order(&minimal_request())
+                .requirements
+                .required
+                .is_empty()
+        );
     }
 
     // 9
@@ -606,6 +766,10 @@ mod tests {
         };
         let wo = to_work_order(&req);
         assert!(wo.config.vendor.contains_key("tool_choice"));
+        assert_eq!(
+            wo.config.vendor["abp"]["tool_choice"],
+            json!({"type": "auto"})
+        );
     }
 
     // ═══════════════════════════════════════════════════════════════════
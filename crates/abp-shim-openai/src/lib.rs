@@ -15,14 +15,19 @@ pub mod client;
 pub mod convert;
 /// OpenAI-compatible error types (ApiError, RateLimitError, AuthenticationError).
 pub mod error;
+/// OpenAI-compatible HTTP frontend (`POST /v1/chat/completions`).
+pub mod server;
 /// SSE-compatible streaming adapter.
 pub mod streaming;
 /// Strongly-typed OpenAI Chat Completions API types using a role-tagged message enum.
 pub mod types;
+/// Client-side request validation.
+pub mod validate;
 
 use std::pin::Pin;
 
-use abp_core::ir::{IrConversation, IrRole, IrToolDefinition, IrUsage};
+use abp_capability::models::ModelCatalog;
+use abp_core::ir::{IrConversation, IrRole, IrToolChoice, IrToolDefinition, IrUsage};
 use abp_core::{AgentEvent, AgentEventKind, Receipt, UsageNormalized, WorkOrder, WorkOrderBuilder};
 use abp_openai_sdk::lowering;
 use chrono::Utc;
@@ -36,6 +41,11 @@ pub use abp_openai_sdk::dialect::{
     ToolChoiceFunctionRef, ToolChoiceMode,
 };
 pub use abp_openai_sdk::response_format::ResponseFormat;
+pub use abp_openai_sdk::responses::{
+    ReasoningConfig, ReasoningEffort, ReasoningSummary, ResponseError, ResponseStatus,
+    ResponsesContentPart, ResponsesInput, ResponsesInputItem, ResponsesMessageContent,
+    ResponsesOutputItem, ResponsesRequest, ResponsesResponse, ResponsesTool, ResponsesUsage,
+};
 
 // ── Error types ─────────────────────────────────────────────────────────
 
@@ -45,6 +55,10 @@ pub enum ShimError {
     /// The request was invalid.
     #[error("invalid request: {0}")]
     InvalidRequest(String),
+    /// The request was rejected by validation, with the error the OpenAI API
+    /// would return.
+    #[error("invalid request: {}", .0.message)]
+    Validation(ErrorDetail),
     /// An internal processing error.
     #[error("internal error: {0}")]
     Internal(String),
@@ -502,6 +516,18 @@ pub fn tools_to_ir(tools: &[Tool]) -> Vec<IrToolDefinition> {
         .collect()
 }
 
+/// Convert a shim [`ToolChoice`] to the IR tool-choice policy.
+pub fn tool_choice_to_ir(choice: &ToolChoice) -> IrToolChoice {
+    match choice {
+        ToolChoice::Mode(ToolChoiceMode::None) => IrToolChoice::None,
+        ToolChoice::Mode(ToolChoiceMode::Auto) => IrToolChoice::Auto,
+        ToolChoice::Mode(ToolChoiceMode::Required) => IrToolChoice::Required,
+        ToolChoice::Function { function, .. } => IrToolChoice::Tool {
+            name: function.name.clone(),
+        },
+    }
+}
+
 /// Convert a [`ChatCompletionRequest`] into an [`IrConversation`].
 pub fn request_to_ir(request: &ChatCompletionRequest) -> IrConversation {
     let openai_msgs = to_openai_messages(&request.messages);
@@ -528,14 +554,30 @@ pub fn request_to_work_order(request: &ChatCompletionRequest) -> WorkOrder {
             serde_json::to_value(stop).unwrap_or_default(),
         );
     }
+    if let Some(format) = &request.response_format {
+        vendor.insert(
+            "response_format".to_string(),
+            serde_json::to_value(format).unwrap_or_default(),
+        );
+    }
     let config = abp_core::RuntimeConfig {
         model: Some(request.model.clone()),
         vendor,
         ..Default::default()
     };
-    builder = builder.config(config);
+    builder = builder.config(config).conversation(conv);
 
-    builder.build()
+    if let Some(tools) = &request.tools {
+        builder = builder.tools(tools_to_ir(tools));
+    }
+    if let Some(choice) = &request.tool_choice {
+        builder = builder.tool_choice(tool_choice_to_ir(choice));
+    }
+
+    let mut work_order = builder.build();
+    abp_tokenize::project(&mut work_order);
+    abp_capability::infer::infer_requirements(&mut work_order);
+    work_order
 }
 
 /// Extract a task description from the conversation (last user message or first user message).
@@ -775,6 +817,185 @@ pub fn ir_usage_to_usage(ir: &IrUsage) -> Usage {
     }
 }
 
+// ── Conversion: Responses API ───────────────────────────────────────────
+
+/// Convert a [`ResponsesRequest`] into an ABP [`WorkOrder`].
+pub fn responses_request_to_work_order(request: &ResponsesRequest) -> WorkOrder {
+    let conv = lowering::responses_to_ir(request);
+    let task = extract_task_from_conversation(&conv);
+
+    let mut builder = WorkOrderBuilder::new(task).model(request.model.clone());
+
+    let mut vendor = std::collections::BTreeMap::new();
+    if let Some(temp) = request.temperature {
+        vendor.insert("temperature".to_string(), serde_json::Value::from(temp));
+    }
+    if let Some(max) = request.max_output_tokens {
+        vendor.insert(
+            "max_output_tokens".to_string(),
+            serde_json::Value::from(max),
+        );
+    }
+    if let Some(reasoning) = &request.reasoning {
+        vendor.insert(
+            "reasoning".to_string(),
+            serde_json::to_value(reasoning).unwrap_or_default(),
+        );
+    }
+    if let Some(previous) = &request.previous_response_id {
+        vendor.insert(
+            "previous_response_id".to_string(),
+            serde_json::Value::from(previous.clone()),
+        );
+    }
+    let config = abp_core::RuntimeConfig {
+        model: Some(request.model.clone()),
+        vendor,
+        ..Default::default()
+    };
+    builder = builder.config(config).conversation(conv);
+
+    if let Some(tools) = &request.tools {
+        builder = builder.tools(responses_tools_to_ir(tools));
+    }
+    if let Some(choice) = request
+        .tool_choice
+        .as_ref()
+        .and_then(responses_tool_choice_to_ir)
+    {
+        builder = builder.tool_choice(choice);
+    }
+
+    let mut work_order = builder.build();
+    abp_tokenize::project(&mut work_order);
+    abp_capability::infer::infer_requirements(&mut work_order);
+    work_order
+}
+
+/// Convert Responses API [`ResponsesTool`]s to IR tool definitions.
+pub fn responses_tools_to_ir(tools: &[ResponsesTool]) -> Vec<IrToolDefinition> {
+    tools
+        .iter()
+        .map(|tool| {
+            let ResponsesTool::Function {
+                name,
+                description,
+                parameters,
+                ..
+            } = tool;
+            IrToolDefinition {
+                name: name.clone(),
+                description: description.clone().unwrap_or_default(),
+                parameters: parameters.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Convert a Responses API `tool_choice` to the IR tool-choice policy.
+///
+/// Accepts `"none"`, `"auto"`, `"required"`, and
+/// `{"type": "function", "name": ...}`; anything else yields `None`.
+pub fn responses_tool_choice_to_ir(choice: &serde_json::Value) -> Option<IrToolChoice> {
+    match choice {
+        serde_json::Value::String(mode) => match mode.as_str() {
+            "none" => Some(IrToolChoice::None),
+            "auto" => Some(IrToolChoice::Auto),
+            "required" => Some(IrToolChoice::Required),
+            _ => None,
+        },
+        serde_json::Value::Object(obj) if obj.get("type")? == "function" => {
+            Some(IrToolChoice::Tool {
+                name: obj.get("name")?.as_str()?.to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Build a [`ResponsesResponse`] from a [`Receipt`] and the original model
+/// name.
+///
+/// Assistant text becomes one `message` output item followed by a
+/// `function_call` item per tool call. An error event or a failed outcome
+/// marks the response `failed`; a partial outcome marks it `incomplete`.
+pub fn receipt_to_responses_response(receipt: &Receipt, model: &str) -> ResponsesResponse {
+    let mut text: Option<String> = None;
+    let mut calls = Vec::new();
+    let mut error: Option<ResponseError> = None;
+
+    for event in &receipt.trace {
+        match &event.kind {
+            AgentEventKind::AssistantMessage { text: full } => {
+                text = Some(full.clone());
+            }
+            AgentEventKind::AssistantDelta { text: delta } => {
+                text.get_or_insert_with(String::new).push_str(delta);
+            }
+            AgentEventKind::ToolCall {
+                tool_name,
+                tool_use_id,
+                input,
+                ..
+            } => {
+                calls.push(ResponsesOutputItem::FunctionCall {
+                    id: format!("fc_{}", uuid::Uuid::new_v4().simple()),
+                    call_id: tool_use_id
+                        .clone()
+                        .unwrap_or_else(|| format!("call_{}", uuid::Uuid::new_v4())),
+                    name: tool_name.clone(),
+                    arguments: serde_json::to_string(input).unwrap_or_default(),
+                });
+            }
+            AgentEventKind::Error { message, .. } => {
+                error = Some(ResponseError {
+                    code: "server_error".into(),
+                    message: message.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let mut output = Vec::with_capacity(calls.len() + 1);
+    if let Some(text) = text {
+        output.push(ResponsesOutputItem::Message {
+            id: format!("msg_{}", receipt.meta.run_id.simple()),
+            role: "assistant".into(),
+            content: vec![ResponsesContentPart::OutputText {
+                text,
+                annotations: Vec::new(),
+            }],
+        });
+    }
+    output.extend(calls);
+
+    let status = match receipt.outcome {
+        _ if error.is_some() => ResponseStatus::Failed,
+        abp_core::Outcome::Complete => ResponseStatus::Completed,
+        abp_core::Outcome::Partial => ResponseStatus::Incomplete,
+        abp_core::Outcome::Failed => ResponseStatus::Failed,
+    };
+    let input_tokens = receipt.usage.input_tokens.unwrap_or(0);
+    let output_tokens = receipt.usage.output_tokens.unwrap_or(0);
+
+    ResponsesResponse {
+        id: format!("resp_{}", receipt.meta.run_id.simple()),
+        object: "response".into(),
+        created_at: receipt.meta.started_at.timestamp() as u64,
+        model: model.to_string(),
+        status,
+        output,
+        usage: Some(ResponsesUsage {
+            input_tokens,
+            output_tokens,
+            total_tokens: input_tokens + output_tokens,
+            output_tokens_details: None,
+        }),
+        error,
+    }
+}
+
 // ── Model selection and fallback ────────────────────────────────────────
 
 /// Known OpenAI model families for fallback resolution.
@@ -909,6 +1130,7 @@ pub type ProcessFn = Box<dyn Fn(&WorkOrder) -> Receipt + Send + Sync>;
 pub struct OpenAiClient {
     model: String,
     processor: Option<ProcessFn>,
+    catalog: ModelCatalog,
 }
 
 impl std::fmt::Debug for OpenAiClient {
@@ -926,9 +1148,24 @@ impl OpenAiClient {
         Self {
             model: model.into(),
             processor: None,
+            catalog: ModelCatalog::with_defaults(),
         }
     }
 
+    /// Set the model catalog used to validate model-specific request
+    /// constraints (defaults to [`ModelCatalog::with_defaults`]).
+    #[must_use]
+    pub fn with_model_catalog(mut self, catalog: ModelCatalog) -> Self {
+        self.catalog = catalog;
+        self
+    }
+
+    /// The model catalog requests are validated against.
+    #[must_use]
+    pub fn model_catalog(&self) -> &ModelCatalog {
+        &self.catalog
+    }
+
     /// Set a custom processor function for handling work orders.
     ///
     /// This is used for testing and custom routing.
@@ -943,6 +1180,11 @@ impl OpenAiClient {
         ChatApi { client: self }
     }
 
+    /// Access the Responses API.
+    pub fn responses(&self) -> ResponsesApi<'_> {
+        ResponsesApi { client: self }
+    }
+
     /// Get the configured model name.
     #[must_use]
     pub fn model(&self) -> &str {
@@ -972,9 +1214,12 @@ pub struct CompletionsApi<'a> {
 impl<'a> CompletionsApi<'a> {
     /// Create a chat completion (non-streaming).
     ///
-    /// Converts the request to IR, then to a WorkOrder, processes it,
-    /// and converts the receipt back into a ChatCompletionResponse.
+    /// Validates the request (see [`validate::validate_request`]), converts
+    /// it to IR, then to a WorkOrder, processes it, and converts the receipt
+    /// back into a ChatCompletionResponse.
     pub async fn create(&self, request: ChatCompletionRequest) -> Result<ChatCompletionResponse> {
+        validate::validate_request(&request, &self.client.catalog)
+            .map_err(ShimError::Validation)?;
         let work_order = request_to_work_order(&request);
 
         let receipt = if let Some(processor) = &self.client.processor {
@@ -995,6 +1240,8 @@ impl<'a> CompletionsApi<'a> {
         &self,
         request: ChatCompletionRequest,
     ) -> Result<Pin<Box<dyn Stream<Item = StreamEvent> + Send>>> {
+        validate::validate_request(&request, &self.client.catalog)
+            .map_err(ShimError::Validation)?;
         let work_order = request_to_work_order(&request);
         let model = request.model.clone();
 
@@ -1011,6 +1258,34 @@ impl<'a> CompletionsApi<'a> {
     }
 }
 
+/// Responses API namespace (mirrors `client.responses`).
+pub struct ResponsesApi<'a> {
+    client: &'a OpenAiClient,
+}
+
+impl ResponsesApi<'_> {
+    /// Create a response (non-streaming).
+    ///
+    /// Validates the request (see [`validate::validate_responses_request`]),
+    /// lowers its input items to IR, then to a WorkOrder, processes it, and
+    /// converts the receipt back into a ResponsesResponse.
+    pub async fn create(&self, request: ResponsesRequest) -> Result<ResponsesResponse> {
+        validate::validate_responses_request(&request, &self.client.catalog)
+            .map_err(ShimError::Validation)?;
+        let work_order = responses_request_to_work_order(&request);
+
+        let receipt = if let Some(processor) = &self.client.processor {
+            processor(&work_order)
+        } else {
+            return Err(ShimError::Internal(
+                "no processor configured; use with_processor() to set a backend".into(),
+            ));
+        };
+
+        Ok(receipt_to_responses_response(&receipt, &request.model))
+    }
+}
+
 // ── Test helpers ────────────────────────────────────────────────────────
 
 /// Create a mock receipt for testing purposes.
@@ -1262,6 +1537,33 @@ mod tests {
         assert_eq!(stop, &json!(["END", "STOP"]));
     }
 
+    #[test]
+    fn tools_and_tool_choice_carried_on_work_order() {
+        let req = ChatCompletionRequest::builder()
+            .model("gpt-4o")
+            .messages(vec![Message::user("weather?")])
+            .tools(vec![Tool::function(
+                "get_weather",
+                "Get weather",
+                json!({"type": "object"}),
+            )])
+            .tool_choice(ToolChoice::Function {
+                tool_type: "function".into(),
+                function: ToolChoiceFunctionRef {
+                    name: "get_weather".into(),
+                },
+            })
+            .build();
+
+        let wo = request_to_work_order(&req);
+        let abp = &wo.config.vendor["abp"];
+        assert_eq!(abp["tools"][0]["name"], "get_weather");
+        assert_eq!(
+            abp["tool_choice"],
+            json!({"type": "tool", "name": "get_weather"})
+        );
+    }
+
     // ── 9. Model name preservation ──────────────────────────────────────
 
     #[tokio::test]
@@ -1605,6 +1907,22 @@ mod tests {
         assert!(json.get("response_format").is_some());
     }
 
+    #[test]
+    fn response_format_reaches_work_order_vendor_config() {
+        let req = ChatCompletionRequest::builder()
+            .messages(vec![Message::user("test")])
+            .response_format(ResponseFormat::json_schema(
+                "answer",
+                json!({"type": "object"}),
+            ))
+            .build();
+
+        let wo = request_to_work_order(&req);
+        let format = &wo.config.vendor["response_format"];
+        assert_eq!(format["type"], "json_schema");
+        assert_eq!(format["json_schema"]["name"], "answer");
+    }
+
     // ── 27. Chat completion response ID format ──────────────────────────
 
     #[tokio::test]
@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! OpenAI-compatible HTTP frontend for the shim.
+//!
+//! [`ShimServer`](crate::server::ShimServer) exposes
+//! `POST /v1/chat/completions` and `POST /v1/responses` over HTTP, backed by
+//! an [`OpenAiClient`](crate::OpenAiClient), so existing OpenAI SDK clients
+//! can talk to ABP by changing only their base URL.
+//!
+//! Chat completion requests with `"stream": true` are answered with
+//! Server-Sent Events: one `data:` line per
+//! [`StreamEvent`](crate::StreamEvent) chunk, terminated by `data: [DONE]`,
+//! exactly as the OpenAI API does. Streaming responses are not supported yet
+//! and are rejected. Failures are returned as OpenAI-shaped
+//! [`ErrorResponse`](crate::ErrorResponse) bodies.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::extract::rejection::JsonRejection;
+use axum::http::StatusCode;
+use axum::response::sse::{Event as SseEvent, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use tokio_stream::StreamExt;
+
+use crate::{
+    ChatCompletionRequest, ErrorDetail, ErrorResponse, OpenAiClient, ResponsesRequest, ShimError,
+};
+
+/// Path of the chat completions endpoint.
+pub const CHAT_COMPLETIONS_PATH: &str = "/v1/chat/completions";
+
+/// Path of the Responses API endpoint.
+pub const RESPONSES_PATH: &str = "/v1/responses";
+
+/// HTTP server exposing an [`OpenAiClient`] on the OpenAI wire protocol.
+#[derive(Debug, Clone)]
+pub struct ShimServer {
+    client: Arc<OpenAiClient>,
+}
+
+impl ShimServer {
+    /// Create a server that answers requests with `client`.
+    #[must_use]
+    pub fn new(client: OpenAiClient) -> Self {
+        Self {
+            client: Arc::new(client),
+        }
+    }
+
+    /// Build the Axum [`Router`] for this server.
+    pub fn router(&self) -> Router {
+        router(self.client.clone())
+    }
+
+    /// Bind to `addr` (e.g. `"127.0.0.1:8080"`) and serve until shutdown.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address cannot be bound or the server fails.
+    pub async fn start(self, addr: &str) -> std::io::Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        axum::serve(listener, self.router()).await
+    }
+
+    /// The client requests are routed through.
+    #[must_use]
+    pub fn client(&self) -> &OpenAiClient {
+        &self.client
+    }
+}
+
+/// Build a [`Router`] serving `POST /v1/chat/completions` and
+/// `POST /v1/responses` with `client`.
+pub fn router(client: Arc<OpenAiClient>) -> Router {
+    Router::new()
+        .route(CHAT_COMPLETIONS_PATH, post(handle_chat_completions))
+        .route(RESPONSES_PATH, post(handle_responses))
+        .with_state(client)
+}
+
+/// `POST /v1/chat/completions` handler.
+async fn handle_chat_completions(
+    State(client): State<Arc<OpenAiClient>>,
+    body: Result<Json<ChatCompletionRequest>, JsonRejection>,
+) -> Response {
+    let request = match body {
+        Ok(Json(request)) => request,
+        Err(rejection) => {
+            return error_response(
+                rejection.status(),
+                "invalid_request_error",
+                rejection.body_text(),
+            );
+        }
+    };
+
+    let completions = client.chat().completions();
+    if request.stream == Some(true) {
+        match completions.create_stream(request).await {
+            Ok(stream) => {
+                let chunks = stream.map(|chunk| {
+                    let data = serde_json::to_string(&chunk)
+                        .unwrap_or_else(|e| error_body("api_error", e.to_string()));
+                    Ok::<_, Infallible>(SseEvent::default().data(data))
+                });
+                let done = tokio_stream::once(Ok(SseEvent::default().data("[DONE]")));
+                Sse::new(chunks.chain(done)).into_response()
+            }
+            Err(err) => shim_error_response(&err),
+        }
+    } else {
+        match completions.create(request).await {
+            Ok(response) => Json(response).into_response(),
+            Err(err) => shim_error_response(&err),
+        }
+    }
+}
+
+/// `POST /v1/responses` handler.
+async fn handle_responses(
+    State(client): State<Arc<OpenAiClient>>,
+    body: Result<Json<ResponsesRequest>, JsonRejection>,
+) -> Response {
+    let request = match body {
+        Ok(Json(request)) => request,
+        Err(rejection) => {
+            return error_response(
+                rejection.status(),
+                "invalid_request_error",
+                rejection.body_text(),
+            );
+        }
+    };
+    if request.stream == Some(true) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "invalid_request_error",
+            "streaming is not supported on /v1/responses".into(),
+        );
+    }
+
+    match client.responses().create(request).await {
+        Ok(response) => Json(response).into_response(),
+        Err(err) => shim_error_response(&err),
+    }
+}
+
+fn shim_error_response(err: &ShimError) -> Response {
+    match err {
+        ShimError::InvalidRequest(msg) => error_response(
+            StatusCode::BAD_REQUEST,
+            "invalid_request_error",
+            msg.clone(),
+        ),
+        ShimError::Validation(detail) => (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: detail.clone(),
+            }),
+        )
+            .into_response(),
+        ShimError::Serde(e) => error_response(
+            StatusCode::BAD_REQUEST,
+            "invalid_request_error",
+            e.to_string(),
+        ),
+        ShimError::Internal(msg) => {
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, "api_error", msg.clone())
+        }
+    }
+}
+
+fn error_response(status: StatusCode, error_type: &str, message: String) -> Response {
+    (status, Json(error_payload(error_type, message))).into_response()
+}
+
+fn error_body(error_type: &str, message: String) -> String {
+    serde_json::to_string(&error_payload(error_type, message)).unwrap_or_default()
+}
+
+fn error_payload(error_type: &str, message: String) -> ErrorResponse {
+    ErrorResponse {
+        error: ErrorDetail {
+            message,
+            error_type: error_type.into(),
+            param: None,
+            code: None,
+        },
+    }
+}
@@ -2,11 +2,20 @@
 //! SSE-compatible streaming adapter for OpenAI chat completions.
 //!
 //! Provides utilities for parsing Server-Sent Events (SSE) streams that
-//! conform to the OpenAI streaming format, and for formatting stream
-//! chunks back into SSE text.
+//! conform to the OpenAI streaming format, for formatting stream
+//! chunks back into SSE text, for rebuilding the final
+//! [`ChatCompletionResponse`](crate::ChatCompletionResponse) from a stream
+//! with [`Accumulator`](crate::streaming::Accumulator), and for reacting to
+//! a stream with typed callbacks via
+//! [`StreamHandlers`](crate::streaming::StreamHandlers).
+
+use std::collections::BTreeMap;
 
 use crate::chat::ChatCompletionChunk;
 use crate::types::StreamChunk;
+use crate::{
+    ChatCompletionResponse, Choice, FunctionCall, Message, Role, StreamEvent, ToolCall, Usage,
+};
 
 // Re-export the SseLineStream from client.rs for direct usage.
 pub use crate::client::SseLineStream;
@@ -84,6 +93,305 @@ pub fn finish_reason(chunks: &[ChatCompletionChunk]) -> Option<String> {
         .and_then(|ch| ch.finish_reason.clone())
 }
 
+// ── Response accumulator ────────────────────────────────────────────────
+
+/// Incrementally rebuilds a [`ChatCompletionResponse`] from [`StreamEvent`]
+/// chunks.
+///
+/// Content deltas are concatenated per choice, tool-call fragments are
+/// merged by their stream `index` (the id and name arrive on the first
+/// fragment, the arguments are spread across the rest), and the most
+/// recent `usage` block wins.
+///
+/// ```
+/// use abp_shim_openai::streaming::Accumulator;
+/// use abp_shim_openai::{Delta, StreamChoice, StreamEvent};
+///
+/// let chunk = |content: Option<&str>, finish: Option<&str>| StreamEvent {
+///     id: "chatcmpl-1".into(),
+///     object: "chat.completion.chunk".into(),
+///     created: 0,
+///     model: "gpt-4o".into(),
+///     choices: vec![StreamChoice {
+///         index: 0,
+///         delta: Delta { content: content.map(Into::into), ..Default::default() },
+///         finish_reason: finish.map(Into::into),
+///     }],
+///     usage: None,
+/// };
+///
+/// let mut acc = Accumulator::new();
+/// acc.feed(&chunk(Some("Hel"), None));
+/// acc.feed(&chunk(Some("lo"), Some("stop")));
+/// let response = acc.finish().unwrap();
+/// assert_eq!(response.choices[0].message.content.as_deref(), Some("Hello"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Accumulator {
+    header: Option<(String, u64, String)>,
+    choices: BTreeMap<u32, ChoiceState>,
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ChoiceState {
+    role: Option<String>,
+    content: Option<String>,
+    tool_calls: BTreeMap<u32, ToolCall>,
+    finish_reason: Option<String>,
+}
+
+impl Accumulator {
+    /// Create an empty accumulator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold one streaming chunk into the response being built.
+    pub fn feed(&mut self, event: &StreamEvent) {
+        if self.header.is_none() {
+            self.header = Some((event.id.clone(), event.created, event.model.clone()));
+        }
+        if let Some(usage) = &event.usage {
+            self.usage = Some(usage.clone());
+        }
+        for choice in &event.choices {
+            let state = self.choices.entry(choice.index).or_default();
+            let delta = &choice.delta;
+            if let Some(role) = &delta.role {
+                state.role = Some(role.clone());
+            }
+            if let Some(content) = &delta.content {
+                state
+                    .content
+                    .get_or_insert_with(String::new)
+                    .push_str(content);
+            }
+            for fragment in delta.tool_calls.iter().flatten() {
+                let call = state
+                    .tool_calls
+                    .entry(fragment.index)
+                    .or_insert_with(|| ToolCall {
+                        id: String::new(),
+                        call_type: "function".into(),
+                        function: FunctionCall {
+                            name: String::new(),
+                            arguments: String::new(),
+                        },
+                    });
+                if let Some(id) = &fragment.id {
+                    call.id.clone_from(id);
+                }
+                if let Some(call_type) = &fragment.call_type {
+                    call.call_type.clone_from(call_type);
+                }
+                if let Some(function) = &fragment.function {
+                    if let Some(name) = &function.name {
+                        call.function.name.push_str(name);
+                    }
+                    if let Some(arguments) = &function.arguments {
+                        call.function.arguments.push_str(arguments);
+                    }
+                }
+            }
+            if choice.finish_reason.is_some() {
+                state.finish_reason.clone_from(&choice.finish_reason);
+            }
+        }
+    }
+
+    /// Text accumulated so far for the first choice.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        self.choices
+            .values()
+            .next()
+            .and_then(|c| c.content.as_deref())
+            .unwrap_or("")
+    }
+
+    /// Whether every choice seen so far has reported a finish reason.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        !self.choices.is_empty() && self.choices.values().all(|c| c.finish_reason.is_some())
+    }
+
+    /// Usage reported by the stream, if any.
+    #[must_use]
+    pub fn usage(&self) -> Option<&Usage> {
+        self.usage.as_ref()
+    }
+
+    /// Assemble the final response.
+    ///
+    /// Returns `None` if no chunk was fed.
+    #[must_use]
+    pub fn finish(self) -> Option<ChatCompletionResponse> {
+        let (id, created, model) = self.header?;
+        let choices = self
+            .choices
+            .into_iter()
+            .map(|(index, state)| {
+                let tool_calls: Vec<ToolCall> = state.tool_calls.into_values().collect();
+                Choice {
+                    index,
+                    message: Message {
+                        role: match state.role.as_deref() {
+                            Some("system") => Role::System,
+                            Some("user") => Role::User,
+                            Some("tool") => Role::Tool,
+                            _ => Role::Assistant,
+                        },
+                        content: state.content,
+                        tool_calls: (!tool_calls.is_empty()).then_some(tool_calls),
+                        tool_call_id: None,
+                    },
+                    finish_reason: state.finish_reason,
+                }
+            })
+            .collect();
+        Some(ChatCompletionResponse {
+            id,
+            object: "chat.completion".into(),
+            created,
+            model,
+            choices,
+            usage: self.usage,
+        })
+    }
+}
+
+// ── Stream handlers ─────────────────────────────────────────────────────
+
+type StrFn<'a> = Box<dyn FnMut(&str) + Send + 'a>;
+type ToolCallFn<'a> = Box<dyn FnMut(&ToolCall) + Send + 'a>;
+type EventFn<'a> = Box<dyn FnMut(&StreamEvent) + Send + 'a>;
+type CompleteFn<'a> = Box<dyn FnOnce(&ChatCompletionResponse) + Send + 'a>;
+
+/// Typed callbacks over a stream of [`StreamEvent`] chunks.
+///
+/// Register the callbacks you need and [`run`](StreamHandlers::run) the
+/// stream, instead of matching on deltas by hand. `on_text` fires per
+/// content delta; `on_tool_call` fires once per assembled tool call when its
+/// choice reports a finish reason (or when the stream ends).
+///
+/// ```
+/// # async fn demo() {
+/// use abp_shim_openai::streaming::StreamHandlers;
+///
+/// # let stream = tokio_stream::iter(Vec::<abp_shim_openai::StreamEvent>::new());
+/// let mut text = String::new();
+/// let response = StreamHandlers::new()
+///     .on_text(|t| text.push_str(t))
+///     .on_tool_call(|call| println!("{}({})", call.function.name, call.function.arguments))
+///     .run(stream)
+///     .await;
+/// # assert!(response.is_none());
+/// # }
+/// ```
+#[derive(Default)]
+pub struct StreamHandlers<'a> {
+    text: Option<StrFn<'a>>,
+    tool_call: Option<ToolCallFn<'a>>,
+    event: Option<EventFn<'a>>,
+    complete: Option<CompleteFn<'a>>,
+}
+
+impl std::fmt::Debug for StreamHandlers<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamHandlers")
+            .field("text", &self.text.is_some())
+            .field("tool_call", &self.tool_call.is_some())
+            .field("event", &self.event.is_some())
+            .field("complete", &self.complete.is_some())
+            .finish()
+    }
+}
+
+impl<'a> StreamHandlers<'a> {
+    /// Create a handler set with no callbacks registered.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Called with each content delta.
+    #[must_use]
+    pub fn on_text(mut self, f: impl FnMut(&str) + Send + 'a) -> Self {
+        self.text = Some(Box::new(f));
+        self
+    }
+
+    /// Called with each fully assembled tool call.
+    #[must_use]
+    pub fn on_tool_call(mut self, f: impl FnMut(&ToolCall) + Send + 'a) -> Self {
+        self.tool_call = Some(Box::new(f));
+        self
+    }
+
+    /// Called with every chunk, before any typed callback.
+    #[must_use]
+    pub fn on_event(mut self, f: impl FnMut(&StreamEvent) + Send + 'a) -> Self {
+        self.event = Some(Box::new(f));
+        self
+    }
+
+    /// Called once with the assembled response when the stream ends.
+    #[must_use]
+    pub fn on_complete(mut self, f: impl FnOnce(&ChatCompletionResponse) + Send + 'a) -> Self {
+        self.complete = Some(Box::new(f));
+        self
+    }
+
+    /// Drive `stream` to completion, dispatching every chunk.
+    ///
+    /// Returns the assembled response, or `None` if the stream was empty (in
+    /// which case `on_complete` is not called).
+    pub async fn run<S>(mut self, mut stream: S) -> Option<ChatCompletionResponse>
+    where
+        S: tokio_stream::Stream<Item = StreamEvent> + Unpin,
+    {
+        use tokio_stream::StreamExt;
+        let mut acc = Accumulator::new();
+        let mut flushed = std::collections::BTreeSet::new();
+        while let Some(event) = stream.next().await {
+            acc.feed(&event);
+            if let Some(f) = &mut self.event {
+                f(&event);
+            }
+            for choice in &event.choices {
+                if let (Some(f), Some(content)) = (&mut self.text, &choice.delta.content) {
+                    f(content);
+                }
+                if choice.finish_reason.is_some() && flushed.insert(choice.index) {
+                    self.flush_tool_calls(&acc, choice.index);
+                }
+            }
+        }
+        let pending: Vec<u32> = acc
+            .choices
+            .keys()
+            .copied()
+            .filter(|i| !flushed.contains(i))
+            .collect();
+        for index in pending {
+            self.flush_tool_calls(&acc, index);
+        }
+        let response = acc.finish()?;
+        if let Some(f) = self.complete.take() {
+            f(&response);
+        }
+        Some(response)
+    }
+
+    fn flush_tool_calls(&mut self, acc: &Accumulator, index: u32) {
+        if let (Some(f), Some(state)) = (&mut self.tool_call, acc.choices.get(&index)) {
+            state.tool_calls.values().for_each(f);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -216,4 +524,163 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert!(results[0].is_ok());
     }
+
+    fn event(delta: crate::Delta, finish: Option<&str>, usage: Option<Usage>) -> StreamEvent {
+        StreamEvent {
+            id: "chatcmpl-1".into(),
+            object: "chat.completion.chunk".into(),
+            created: 1700000000,
+            model: "gpt-4o".into(),
+            choices: vec![crate::StreamChoice {
+                index: 0,
+                delta,
+                finish_reason: finish.map(|s| s.to_string()),
+            }],
+            usage,
+        }
+    }
+
+    fn tool_fragment(
+        index: u32,
+        id: Option<&str>,
+        name: Option<&str>,
+        arguments: &str,
+    ) -> crate::Delta {
+        crate::Delta {
+            tool_calls: Some(vec![crate::StreamToolCall {
+                index,
+                id: id.map(|s| s.to_string()),
+                call_type: id.map(|_| "function".to_string()),
+                function: Some(crate::StreamFunctionCall {
+                    name: name.map(|s| s.to_string()),
+                    arguments: Some(arguments.into()),
+                }),
+            }]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn accumulator_merges_tool_call_fragments_by_index() {
+        let events = vec![
+            event(
+                crate::Delta {
+                    role: Some("assistant".into()),
+                    ..Default::default()
+                },
+                None,
+                None,
+            ),
+            event(
+                tool_fragment(0, Some("call_a"), Some("read"), ""),
+                None,
+                None,
+            ),
+            event(
+                tool_fragment(1, Some("call_b"), Some("ls"), "{}"),
+                None,
+                None,
+            ),
+            event(tool_fragment(0, None, None, "{\"path\":"), None, None),
+            event(tool_fragment(0, None, None, "\"a.rs\"}"), None, None),
+            event(crate::Delta::default(), Some("tool_calls"), None),
+            StreamEvent {
+                choices: vec![],
+                usage: Some(Usage {
+                    prompt_tokens: 10,
+                    completion_tokens: 7,
+                    total_tokens: 17,
+                }),
+                ..event(crate::Delta::default(), None, None)
+            },
+        ];
+
+        let mut acc = Accumulator::new();
+        for e in &events {
+            acc.feed(e);
+        }
+        assert!(acc.is_complete());
+        assert_eq!(acc.usage().map(|u| u.total_tokens), Some(17));
+
+        let response = acc.finish().unwrap();
+        assert_eq!(response.object, "chat.completion");
+        let choice = &response.choices[0];
+        assert_eq!(choice.finish_reason.as_deref(), Some("tool_calls"));
+        assert_eq!(choice.message.role, Role::Assistant);
+        assert!(choice.message.content.is_none());
+        let calls = choice.message.tool_calls.as_ref().unwrap();
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].id, "call_a");
+        assert_eq!(calls[0].function.name, "read");
+        assert_eq!(calls[0].function.arguments, "{\"path\":\"a.rs\"}");
+        assert_eq!(calls[1].function.arguments, "{}");
+    }
+
+    #[test]
+    fn accumulator_without_chunks_yields_nothing() {
+        let acc = Accumulator::new();
+        assert!(!acc.is_complete());
+        assert_eq!(acc.text(), "");
+        assert!(acc.finish().is_none());
+    }
+
+    #[tokio::test]
+    async fn handlers_fire_text_tool_calls_and_complete() {
+        let events = vec![
+            event(
+                crate::Delta {
+                    content: Some("Checking".into()),
+                    ..Default::default()
+                },
+                None,
+                None,
+            ),
+            event(
+                tool_fragment(0, Some("call_a"), Some("read"), "{\"path\":"),
+                None,
+                None,
+            ),
+            event(tool_fragment(0, None, None, "\"a.rs\"}"), None, None),
+            event(crate::Delta::default(), Some("tool_calls"), None),
+        ];
+
+        let mut text = String::new();
+        let mut calls = Vec::new();
+        let mut chunks = 0;
+        let mut completed = None;
+        let response = StreamHandlers::new()
+            .on_text(|t| text.push_str(t))
+            .on_tool_call(|call| calls.push(call.clone()))
+            .on_event(|_| chunks += 1)
+            .on_complete(|r| completed = Some(r.id.clone()))
+            .run(tokio_stream::iter(events))
+            .await
+            .unwrap();
+
+        assert_eq!(text, "Checking");
+        assert_eq!(chunks, 4);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id, "call_a");
+        assert_eq!(calls[0].function.arguments, "{\"path\":\"a.rs\"}");
+        assert_eq!(completed.as_deref(), Some("chatcmpl-1"));
+        assert_eq!(
+            response.choices[0].finish_reason.as_deref(),
+            Some("tool_calls")
+        );
+    }
+
+    #[tokio::test]
+    async fn handlers_flush_tool_calls_without_finish_reason() {
+        let events = vec![event(
+            tool_fragment(0, Some("call_a"), Some("ls"), "{}"),
+            None,
+            None,
+        )];
+        let mut names = Vec::new();
+        StreamHandlers::new()
+            .on_tool_call(|call| names.push(call.function.name.clone()))
+            .run(tokio_stream::iter(events))
+            .await;
+        assert_eq!(names, vec!["ls".to_string()]);
+    }
 }
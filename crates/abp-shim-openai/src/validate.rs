@@ -0,0 +1,332 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Client-side request validation mirroring the Chat Completions and
+//! Responses APIs.
+//!
+//! [`validate_request`](crate::validate::validate_request) and
+//! [`validate_responses_request`](crate::validate::validate_responses_request)
+//! reject requests the real API would refuse, returning the same
+//! `invalid_request_error` payload (`message`, `param`, `code`) so callers
+//! can handle shim and vendor errors identically. Model-specific rules come from a
+//! [`ModelCatalog`](abp_capability::models::ModelCatalog); models missing
+//! from the catalog only get the model-independent checks.
+//!
+//! The API accepts `response_format` together with `tools`, so the two are
+//! only checked independently against the model's profile.
+
+use abp_capability::models::{ModelCatalog, ModelProfile};
+
+use crate::{
+    ChatCompletionRequest, ErrorDetail, ResponseFormat, ResponsesRequest, ResponsesTool, Tool,
+};
+
+const MAX_TEMPERATURE: f64 = 2.0;
+const MAX_FUNCTION_NAME_LEN: usize = 64;
+
+/// Check `request` against the Chat Completions request constraints for its
+/// model, as described by `catalog`.
+///
+/// # Errors
+///
+/// Returns the [`ErrorDetail`] the API would report for the first violation
+/// found.
+pub fn validate_request(
+    request: &ChatCompletionRequest,
+    catalog: &ModelCatalog,
+) -> Result<(), ErrorDetail> {
+    let profile = catalog.lookup(&request.model);
+
+    if let Some(t) = request.temperature {
+        check_temperature(t, profile)?;
+    }
+    if let (Some(max_tokens), Some(limit)) = (
+        request.max_tokens,
+        profile.and_then(|p| p.max_output_tokens),
+    ) && u64::from(max_tokens) > limit
+    {
+        return Err(invalid(
+            format!(
+                "max_tokens is too large: {max_tokens}. This model supports at most {limit} \
+                 completion tokens, whereas you provided {max_tokens}."
+            ),
+            "max_tokens",
+            Some("invalid_value"),
+        ));
+    }
+    if let Some(tools) = request.tools.as_deref().filter(|t| !t.is_empty()) {
+        if profile.is_some_and(|p| p.tool_calling == Some(false)) {
+            return Err(unsupported_parameter("tools"));
+        }
+        for (i, tool) in tools.iter().enumerate() {
+            check_tool(i, tool)?;
+        }
+    }
+    if let Some(format) = &request.response_format {
+        check_response_format(format, profile)?;
+    }
+    Ok(())
+}
+
+/// Check a Responses API `request` against the constraints for its model,
+/// as described by `catalog`.
+///
+/// # Errors
+///
+/// Returns the [`ErrorDetail`] the API would report for the first violation
+/// found.
+pub fn validate_responses_request(
+    request: &ResponsesRequest,
+    catalog: &ModelCatalog,
+) -> Result<(), ErrorDetail> {
+    let profile = catalog.lookup(&request.model);
+
+    if let Some(t) = request.temperature {
+        check_temperature(t, profile)?;
+    }
+    if let (Some(max_tokens), Some(limit)) = (
+        request.max_output_tokens,
+        profile.and_then(|p| p.max_output_tokens),
+    ) && u64::from(max_tokens) > limit
+    {
+        return Err(invalid(
+            format!(
+                "max_output_tokens is too large: {max_tokens}. This model supports at most \
+                 {limit} output tokens, whereas you provided {max_tokens}."
+            ),
+            "max_output_tokens",
+            Some("invalid_value"),
+        ));
+    }
+    if request
+        .reasoning
+        .as_ref()
+        .is_some_and(|r| r.effort.is_some())
+        && profile.is_some_and(|p| p.extended_thinking == Some(false))
+    {
+        return Err(unsupported_parameter("reasoning.effort"));
+    }
+    if let Some(tools) = request.tools.as_deref().filter(|t| !t.is_empty()) {
+        if profile.is_some_and(|p| p.tool_calling == Some(false)) {
+            return Err(unsupported_parameter("tools"));
+        }
+        for (i, tool) in tools.iter().enumerate() {
+            let ResponsesTool::Function {
+                name, parameters, ..
+            } = tool;
+            check_function(name, parameters, &format!("tools[{i}]."))?;
+        }
+    }
+    Ok(())
+}
+
+fn check_temperature(t: f64, profile: Option<&ModelProfile>) -> Result<(), ErrorDetail> {
+    if t < 0.0 {
+        return Err(invalid(
+            format!(
+                "Invalid 'temperature': decimal below minimum value. Expected a value >= 0, \
+                 but got {t} instead."
+            ),
+            "temperature",
+            Some("decimal_below_min_value"),
+        ));
+    }
+    if t > MAX_TEMPERATURE {
+        return Err(invalid(
+            format!(
+                "Invalid 'temperature': decimal above maximum value. Expected a value <= 2, \
+                 but got {t} instead."
+            ),
+            "temperature",
+            Some("decimal_above_max_value"),
+        ));
+    }
+    // Reasoning (o-series) models only accept the default temperature.
+    if profile.is_some_and(|p| p.extended_thinking == Some(true)) && t != 1.0 {
+        return Err(invalid(
+            format!(
+                "Unsupported value: 'temperature' does not support {t} with this model. \
+                 Only the default (1) value is supported."
+            ),
+            "temperature",
+            Some("unsupported_value"),
+        ));
+    }
+    Ok(())
+}
+
+fn check_tool(index: usize, tool: &Tool) -> Result<(), ErrorDetail> {
+    if tool.tool_type != "function" {
+        return Err(invalid(
+            format!(
+                "Invalid value: '{}'. Supported values are: 'function'.",
+                tool.tool_type
+            ),
+            format!("tools[{index}].type"),
+            Some("invalid_value"),
+        ));
+    }
+    check_function(
+        &tool.function.name,
+        &tool.function.parameters,
+        &format!("tools[{index}].function."),
+    )
+}
+
+/// Check a function tool's name and parameter schema; `prefix` locates the
+/// function's fields in the request (e.g. `tools[0].function.`).
+fn check_function(name: &str, params: &serde_json::Value, prefix: &str) -> Result<(), ErrorDetail> {
+    let name_ok = !name.is_empty()
+        && name.len() <= MAX_FUNCTION_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !name_ok {
+        return Err(invalid(
+            format!(
+                "Invalid '{prefix}name': string does not match pattern. \
+                 Expected a string that matches the pattern '^[a-zA-Z0-9_-]{{1,64}}$'."
+            ),
+            format!("{prefix}name"),
+            Some("invalid_value"),
+        ));
+    }
+    let schema_type = params.get("type").and_then(|t| t.as_str());
+    if !(params.is_null() || params.is_object()) || schema_type.is_some_and(|t| t != "object") {
+        let got = schema_type.map_or_else(|| params.to_string(), |t| format!("type: \"{t}\""));
+        return Err(invalid(
+            format!(
+                "Invalid schema for function '{name}': schema must be a JSON Schema of \
+                 'type: \"object\"', got '{got}'."
+            ),
+            format!("{prefix}parameters"),
+            Some("invalid_function_parameters"),
+        ));
+    }
+    Ok(())
+}
+
+fn check_response_format(
+    format: &ResponseFormat,
+    profile: Option<&ModelProfile>,
+) -> Result<(), ErrorDetail> {
+    let (kind, supported) = match format {
+        ResponseFormat::Text => return Ok(()),
+        ResponseFormat::JsonObject => (
+            "json_object",
+            profile.is_none_or(|p| p.json_mode != Some(false)),
+        ),
+        ResponseFormat::JsonSchema { .. } => (
+            "json_schema",
+            profile.is_none_or(|p| p.structured_output != Some(false)),
+        ),
+    };
+    if !supported {
+        return Err(invalid(
+            format!(
+                "Invalid parameter: 'response_format' of type '{kind}' is not supported with \
+                 this model."
+            ),
+            "response_format",
+            None,
+        ));
+    }
+    Ok(())
+}
+
+fn unsupported_parameter(param: &str) -> ErrorDetail {
+    invalid(
+        format!("Unsupported parameter: '{param}' is not supported with this model."),
+        param,
+        Some("unsupported_parameter"),
+    )
+}
+
+fn invalid(
+    message: impl Into<String>,
+    param: impl Into<String>,
+    code: Option<&str>,
+) -> ErrorDetail {
+    ErrorDetail {
+        message: message.into(),
+        error_type: "invalid_request_error".into(),
+        param: Some(param.into()),
+        code: code.map(str::to_string),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Message;
+    use serde_json::json;
+
+    fn request(model: &str) -> ChatCompletionRequest {
+        ChatCompletionRequest::builder()
+            .model(model)
+            .messages(vec![Message::user("Hi")])
+            .build()
+    }
+
+    fn code_of(req: &ChatCompletionRequest) -> Option<String> {
+        validate_request(req, &ModelCatalog::with_defaults())
+            .unwrap_err()
+            .code
+    }
+
+    #[test]
+    fn temperature_range_is_enforced() {
+        let mut req = request("gpt-4o");
+        req.temperature = Some(2.5);
+        assert_eq!(code_of(&req).as_deref(), Some("decimal_above_max_value"));
+        req.temperature = Some(-0.1);
+        assert_eq!(code_of(&req).as_deref(), Some("decimal_below_min_value"));
+        req.temperature = Some(1.3);
+        validate_request(&req, &ModelCatalog::with_defaults()).unwrap();
+    }
+
+    #[test]
+    fn reasoning_models_only_accept_default_temperature() {
+        let mut req = request("o3-mini-2025-01-31");
+        req.temperature = Some(0.2);
+        assert_eq!(code_of(&req).as_deref(), Some("unsupported_value"));
+        req.temperature = Some(1.0);
+        validate_request(&req, &ModelCatalog::with_defaults()).unwrap();
+    }
+
+    #[test]
+    fn tool_schemas_are_checked() {
+        let mut req = request("gpt-4o");
+        req.tools = Some(vec![Tool::function("read file", "", json!({}))]);
+        let err = validate_request(&req, &ModelCatalog::with_defaults()).unwrap_err();
+        assert_eq!(err.param.as_deref(), Some("tools[0].function.name"));
+
+        req.tools = Some(vec![Tool::function(
+            "read_file",
+            "",
+            json!({"type": "string"}),
+        )]);
+        assert_eq!(
+            code_of(&req).as_deref(),
+            Some("invalid_function_parameters")
+        );
+    }
+
+    #[test]
+    fn response_format_follows_model_profile() {
+        let mut req = request("gpt-4-turbo");
+        req.response_format = Some(ResponseFormat::json_schema("answer", json!({})));
+        let err = validate_request(&req, &ModelCatalog::with_defaults()).unwrap_err();
+        assert_eq!(err.param.as_deref(), Some("response_format"));
+
+        let mut req = request("gpt-4o");
+        req.response_format = Some(ResponseFormat::json_schema("answer", json!({})));
+        validate_request(&req, &ModelCatalog::with_defaults()).unwrap();
+    }
+
+    #[test]
+    fn unknown_models_skip_model_specific_checks() {
+        let mut req = request("my-finetune");
+        req.temperature = Some(0.0);
+        req.max_tokens = Some(1_000_000);
+        validate_request(&req, &ModelCatalog::with_defaults()).unwrap();
+    }
+}
@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the `client.responses()` namespace.
+
+use abp_core::ir::{IrConversation, IrRole, IrToolChoice};
+use abp_core::{AgentEvent, AgentEventKind, Outcome, UsageNormalized};
+use abp_shim_openai::{
+    OpenAiClient, ResponseStatus, ResponsesContentPart, ResponsesOutputItem, ResponsesRequest,
+    ShimError, mock_receipt, mock_receipt_with_usage, responses_request_to_work_order,
+};
+use chrono::Utc;
+use serde_json::json;
+
+fn event(kind: AgentEventKind) -> AgentEvent {
+    AgentEvent {
+        ts: Utc::now(),
+        kind,
+        ext: None,
+    }
+}
+
+fn request(body: serde_json::Value) -> ResponsesRequest {
+    serde_json::from_value(body).unwrap()
+}
+
+#[tokio::test]
+async fn create_returns_message_and_function_call_items() {
+    let events = vec![
+        event(AgentEventKind::AssistantMessage {
+            text: "Checking.".into(),
+        }),
+        event(AgentEventKind::ToolCall {
+            tool_name: "get_weather".into(),
+            tool_use_id: Some("call_1".into()),
+            parent_tool_use_id: None,
+            input: json!({"city": "Paris"}),
+        }),
+    ];
+    let usage = UsageNormalized {
+        input_tokens: Some(12),
+        output_tokens: Some(4),
+        ..Default::default()
+    };
+    let client = OpenAiClient::new("gpt-4o").with_processor(Box::new(move |_| {
+        mock_receipt_with_usage(events.clone(), usage.clone())
+    }));
+
+    let resp = client
+        .responses()
+        .create(ResponsesRequest::new("gpt-4o", "Weather in Paris?"))
+        .await
+        .unwrap();
+
+    assert_eq!(resp.object, "response");
+    assert!(resp.id.starts_with("resp_"));
+    assert_eq!(resp.status, ResponseStatus::Completed);
+    assert_eq!(resp.output_text(), "Checking.");
+    assert!(matches!(
+        &resp.output[0],
+        ResponsesOutputItem::Message { content, .. }
+            if matches!(&content[0], ResponsesContentPart::OutputText { text, .. } if text == "Checking.")
+    ));
+    assert!(matches!(
+        &resp.output[1],
+        ResponsesOutputItem::FunctionCall { call_id, name, arguments, .. }
+            if call_id == "call_1" && name == "get_weather" && arguments == r#"{"city":"Paris"}"#
+    ));
+    let usage = resp.usage.unwrap();
+    assert_eq!(usage.total_tokens, 16);
+}
+
+#[tokio::test]
+async fn error_event_marks_response_failed() {
+    let client = OpenAiClient::new("gpt-4o").with_processor(Box::new(|_| {
+        let mut receipt = mock_receipt(vec![event(AgentEventKind::Error {
+            message: "backend down".into(),
+            error_code: None,
+        })]);
+        receipt.outcome = Outcome::Failed;
+        receipt
+    }));
+    let resp = client
+        .responses()
+        .create(ResponsesRequest::new("gpt-4o", "hi"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status, ResponseStatus::Failed);
+    assert_eq!(resp.error.unwrap().message, "backend down");
+}
+
+#[test]
+fn work_order_carries_items_tools_and_settings() {
+    let req = request(json!({
+        "model": "o3",
+        "instructions": "Be terse.",
+        "input": [
+            {"role": "user", "content": "Weather in Paris?"},
+            {"type": "function_call", "call_id": "call_1", "name": "get_weather",
+             "arguments": "{}"},
+            {"type": "function_call_output", "call_id": "call_1", "output": "18C"},
+        ],
+        "tools": [{"type": "function", "name": "get_weather",
+                   "description": "Look up weather", "parameters": {"type": "object"}}],
+        "tool_choice": {"type": "function", "name": "get_weather"},
+        "reasoning": {"effort": "low"},
+        "max_output_tokens": 256,
+        "previous_response_id": "resp_0",
+    }));
+    let wo = responses_request_to_work_order(&req);
+
+    assert_eq!(wo.task, "Weather in Paris?");
+    let vendor = &wo.config.vendor;
+    let conv: IrConversation =
+        serde_json::from_value(vendor["abp"]["conversation"].clone()).unwrap();
+    let roles: Vec<_> = conv.messages.iter().map(|m| m.role).collect();
+    assert_eq!(
+        roles,
+        [
+            IrRole::System,
+            IrRole::User,
+            IrRole::Assistant,
+            IrRole::Tool
+        ]
+    );
+    assert_eq!(vendor["abp"]["tools"][0]["name"], "get_weather");
+    let choice: IrToolChoice =
+        serde_json::from_value(vendor["abp"]["tool_choice"].clone()).unwrap();
+    assert_eq!(
+        choice,
+        IrToolChoice::Tool {
+            name: "get_weather".into()
+        }
+    );
+    assert_eq!(vendor["max_output_tokens"], 256);
+    assert_eq!(vendor["reasoning"]["effort"], "low");
+    assert_eq!(vendor["previous_response_id"], "resp_0");
+}
+
+#[tokio::test]
+async fn reasoning_on_non_reasoning_model_is_rejected() {
+    let client = OpenAiClient::new("gpt-4o").with_processor(Box::new(|_| mock_receipt(vec![])));
+    let req = request(json!({
+        "model": "gpt-4o",
+        "input": "hi",
+        "reasoning": {"effort": "high"},
+    }));
+    let err = client.responses().create(req).await.unwrap_err();
+    let ShimError::Validation(detail) = err else {
+        panic!("expected validation error, got {err:?}");
+    };
+    assert_eq!(detail.param.as_deref(), Some("reasoning.effort"));
+    assert_eq!(detail.code.as_deref(), Some("unsupported_parameter"));
+}
+
+#[tokio::test]
+async fn invalid_tool_name_is_rejected() {
+    let client = OpenAiClient::new("gpt-4o").with_processor(Box::new(|_| mock_receipt(vec![])));
+    let req = request(json!({
+        "model": "gpt-4o",
+        "input": "hi",
+        "tools": [{"type": "function", "name": "bad name", "parameters": {}}],
+    }));
+    let ShimError::Validation(detail) = client.responses().create(req).await.unwrap_err() else {
+        panic!("expected validation error");
+    };
+    assert_eq!(detail.param.as_deref(), Some("tools[0].name"));
+}
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the OpenAI-compatible HTTP frontend.
+
+use abp_core::{AgentEvent, AgentEventKind};
+use abp_shim_openai::server::{CHAT_COMPLETIONS_PATH, RESPONSES_PATH, ShimServer};
+use abp_shim_openai::{
+    ChatCompletionResponse, ErrorResponse, OpenAiClient, ResponsesResponse, mock_receipt,
+};
+use axum::body::Body;
+use axum::http::{Request, StatusCode, header};
+use axum::response::Response;
+use chrono::Utc;
+use http_body_util::BodyExt;
+use serde_json::json;
+use tower::ServiceExt;
+
+fn server() -> ShimServer {
+    let events = vec![AgentEvent {
+        ts: Utc::now(),
+        kind: AgentEventKind::AssistantMessage {
+            text: "Hello from ABP".into(),
+        },
+        ext: None,
+    }];
+    let client = OpenAiClient::new("gpt-4o")
+        .with_processor(Box::new(move |_wo| mock_receipt(events.clone())));
+    ShimServer::new(client)
+}
+
+async fn post(server: &ShimServer, body: impl Into<Body>) -> Response {
+    post_to(server, CHAT_COMPLETIONS_PATH, body).await
+}
+
+async fn post_to(server: &ShimServer, path: &str, body: impl Into<Body>) -> Response {
+    let request = Request::post(path)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body.into())
+        .unwrap();
+    server.router().oneshot(request).await.unwrap()
+}
+
+async fn body_text(response: Response) -> String {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+fn chat_body(stream: bool) -> String {
+    json!({
+        "model": "gpt-4o",
+        "messages": [{"role": "user", "content": "Hi"}],
+        "stream": stream,
+    })
+    .to_string()
+}
+
+#[tokio::test]
+async fn chat_completion_returns_openai_response() {
+    let response = post(&server(), chat_body(false)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: ChatCompletionResponse = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(body.object, "chat.completion");
+    assert_eq!(body.model, "gpt-4o");
+    assert_eq!(
+        body.choices[0].message.content.as_deref(),
+        Some("Hello from ABP")
+    );
+}
+
+#[tokio::test]
+async fn streaming_request_returns_sse_chunks_and_done() {
+    let response = post(&server(), chat_body(true)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        "text/event-stream"
+    );
+
+    let text = body_text(response).await;
+    let data: Vec<&str> = text
+        .lines()
+        .filter_map(|l| l.strip_prefix("data: "))
+        .collect();
+    assert_eq!(data.last(), Some(&"[DONE]"));
+    let chunks: Vec<serde_json::Value> = data[..data.len() - 1]
+        .iter()
+        .map(|d| serde_json::from_str(d).unwrap())
+        .collect();
+    assert!(!chunks.is_empty());
+    assert!(
+        chunks
+            .iter()
+            .all(|c| c["object"] == "chat.completion.chunk")
+    );
+    assert!(text.contains("Hello from ABP"));
+}
+
+#[tokio::test]
+async fn malformed_body_returns_invalid_request_error() {
+    let response = post(&server(), "{not json").await;
+    assert!(response.status().is_client_error());
+
+    let body: ErrorResponse = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(body.error.error_type, "invalid_request_error");
+}
+
+#[tokio::test]
+async fn validation_failure_returns_openai_error_code() {
+    let body = json!({
+        "model": "o3-mini",
+        "messages": [{"role": "user", "content": "Hi"}],
+        "temperature": 0.2,
+    });
+    let response = post(&server(), body.to_string()).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body: ErrorResponse = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(body.error.error_type, "invalid_request_error");
+    assert_eq!(body.error.param.as_deref(), Some("temperature"));
+    assert_eq!(body.error.code.as_deref(), Some("unsupported_value"));
+}
+
+#[tokio::test]
+async fn client_without_processor_returns_api_error() {
+    let server = ShimServer::new(OpenAiClient::new("gpt-4o"));
+    let response = post(&server, chat_body(false)).await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    let body: ErrorResponse = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(body.error.error_type, "api_error");
+}
+
+#[tokio::test]
+async fn responses_endpoint_returns_output_items() {
+    let body = json!({"model": "gpt-4o", "input": "Hi"});
+    let response = post_to(&server(), RESPONSES_PATH, body.to_string()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: ResponsesResponse = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(body.object, "response");
+    assert_eq!(body.output_text(), "Hello from ABP");
+}
+
+#[tokio::test]
+async fn streaming_responses_request_is_rejected() {
+    let body = json!({"model": "gpt-4o", "input": "Hi", "stream": true});
+    let response = post_to(&server(), RESPONSES_PATH, body.to_string()).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body: ErrorResponse = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(body.error.error_type, "invalid_request_error");
+}
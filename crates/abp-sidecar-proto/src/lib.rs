@@ -21,6 +21,7 @@ pub mod state_machine;
 pub mod version_negotiation;
 
 use abp_core::{AgentEvent, BackendIdentity, CapabilityManifest, Receipt, WorkOrder};
+use abp_protocol::secure::{ChannelKey, ChannelRole, Sealer, SecureHandshake};
 use abp_protocol::{Envelope, JsonlCodec, ProtocolError};
 use async_trait::async_trait;
 use thiserror::Error;
@@ -132,6 +133,7 @@ impl EventSender {
         let envelope = Envelope::Event {
             ref_id: self.ref_id.clone(),
             event,
+            seq: None,
         };
         self.tx
             .send(envelope)
@@ -187,6 +189,7 @@ pub async fn send_event(
     let envelope = Envelope::Event {
         ref_id: ref_id.to_string(),
         event,
+        seq: None,
     };
     write_envelope(writer, &envelope).await
 }
@@ -222,7 +225,19 @@ async fn write_envelope(
     writer: &mut (impl tokio::io::AsyncWrite + Unpin),
     envelope: &Envelope,
 ) -> Result<(), SidecarProtoError> {
-    let line = JsonlCodec::encode(envelope)?;
+    write_sealed(writer, envelope, &mut None).await
+}
+
+/// Write `envelope`, encrypting it when a secure channel is up.
+async fn write_sealed(
+    writer: &mut (impl tokio::io::AsyncWrite + Unpin),
+    envelope: &Envelope,
+    sealer: &mut Option<Sealer>,
+) -> Result<(), SidecarProtoError> {
+    let mut line = JsonlCodec::encode(envelope)?;
+    if let Some(sealer) = sealer {
+        line = sealer.seal(&line).map_err(ProtocolError::from)?;
+    }
     writer.write_all(line.as_bytes()).await?;
     writer.flush().await?;
     Ok(())
@@ -289,14 +304,52 @@ impl<H: SidecarHandler> SidecarServer<H> {
     /// 2. Reads a `run` envelope from stdin.
     /// 3. Dispatches to the handler.
     /// 4. Sends buffered envelopes and, on error, a `fatal` envelope.
+    ///
+    /// When the host set
+    /// [`CHANNEL_KEY_ENV`](abp_protocol::secure::CHANNEL_KEY_ENV), the loop
+    /// runs over a secure channel (see
+    /// [`run_secure_with_io`](Self::run_secure_with_io)).
     pub async fn run(self) -> Result<(), SidecarProtoError> {
         let stdin = tokio::io::stdin();
         let mut stdout = tokio::io::stdout();
-        self.run_with_io(stdin, &mut stdout).await
+        match ChannelKey::from_env().map_err(ProtocolError::from)? {
+            Some(key) => self.run_secure_with_io(stdin, &mut stdout, &key).await,
+            None => self.run_with_io(stdin, &mut stdout).await,
+        }
     }
 
     /// Run the protocol loop with injectable I/O (for testing).
     pub async fn run_with_io<R, W>(self, reader: R, writer: &mut W) -> Result<(), SidecarProtoError>
+    where
+        R: tokio::io::AsyncRead + Send + Unpin,
+        W: tokio::io::AsyncWrite + Send + Unpin,
+    {
+        self.serve(reader, writer, None).await
+    }
+
+    /// Run the protocol loop over a channel encrypted with `key`.
+    ///
+    /// The server sends its [`SecureHandshake`] line, reads the host's, and
+    /// seals or opens every line after that.
+    pub async fn run_secure_with_io<R, W>(
+        self,
+        reader: R,
+        writer: &mut W,
+        key: &ChannelKey,
+    ) -> Result<(), SidecarProtoError>
+    where
+        R: tokio::io::AsyncRead + Send + Unpin,
+        W: tokio::io::AsyncWrite + Send + Unpin,
+    {
+        self.serve(reader, writer, Some(key)).await
+    }
+
+    async fn serve<R, W>(
+        self,
+        reader: R,
+        writer: &mut W,
+        key: Option<&ChannelKey>,
+    ) -> Result<(), SidecarProtoError>
     where
         R: tokio::io::AsyncRead + Send + Unpin,
         W: tokio::io::AsyncWrite + Send + Unpin,
@@ -307,12 +360,33 @@ impl<H: SidecarHandler> SidecarServer<H> {
             capabilities,
         } = self;
 
+        let mut reader = BufReader::new(reader);
+        let mut line = String::new();
+
+        // Step 0: on a secure channel, exchange handshake lines first
+        let (mut sealer, mut opener) = match key {
+            Some(key) => {
+                let handshake =
+                    SecureHandshake::new(key, ChannelRole::Sidecar).map_err(ProtocolError::from)?;
+                writer.write_all(handshake.line().as_bytes()).await?;
+                writer.flush().await?;
+                if reader.read_line(&mut line).await? == 0 {
+                    return Err(SidecarProtoError::StdinClosed);
+                }
+                let (sealer, opener) = handshake
+                    .complete(&line)
+                    .map_err(ProtocolError::from)?
+                    .split();
+                (Some(sealer), Some(opener))
+            }
+            None => (None, None),
+        };
+
         // Step 1: send hello
-        send_hello(writer, identity, capabilities).await?;
+        let hello = Envelope::hello(identity, capabilities);
+        write_sealed(writer, &hello, &mut sealer).await?;
 
         // Step 2: read envelopes from stdin until we get a Run
-        let mut reader = BufReader::new(reader);
-        let mut line = String::new();
 
         let (run_id, work_order) = loop {
             line.clear();
@@ -326,7 +400,12 @@ impl<H: SidecarHandler> SidecarServer<H> {
                 continue;
             }
 
-            let envelope = JsonlCodec::decode(trimmed)?;
+            let envelope = match &mut opener {
+                Some(opener) => {
+                    JsonlCodec::decode(&opener.open(trimmed).map_err(ProtocolError::from)?)?
+                }
+                None => JsonlCodec::decode(trimmed)?,
+            };
             match envelope {
                 Envelope::Run { id, work_order } => break (id, work_order),
                 other => {
@@ -346,7 +425,7 @@ impl<H: SidecarHandler> SidecarServer<H> {
 
         // Step 4: drain buffered envelopes to writer
         while let Ok(envelope) = rx.try_recv() {
-            write_envelope(writer, &envelope).await?;
+            write_sealed(writer, &envelope, &mut sealer).await?;
         }
 
         // Step 5: on error, send a fatal envelope
@@ -356,7 +435,7 @@ impl<H: SidecarHandler> SidecarServer<H> {
                 error: e.to_string(),
                 error_code: None,
             };
-            write_envelope(writer, &fatal).await?;
+            write_sealed(writer, &fatal, &mut sealer).await?;
         }
 
         Ok(())
@@ -542,7 +621,7 @@ mod tests {
         let line = drain_duplex(r).await;
         let env = JsonlCodec::decode(line.trim()).unwrap();
         match env {
-            Envelope::Event { ref_id, event } => {
+            Envelope::Event { ref_id, event, .. } => {
                 assert_eq!(ref_id, "run-1");
                 assert!(matches!(event.kind, AgentEventKind::RunStarted { .. }));
             }
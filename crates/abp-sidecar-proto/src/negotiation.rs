@@ -7,11 +7,12 @@
 
 use std::fmt;
 
+use abp_core::compat;
 use abp_core::{
     BackendIdentity, CONTRACT_VERSION, Capability, CapabilityManifest, CapabilityRequirements,
     SupportLevel,
 };
-use abp_protocol::{Envelope, ProtocolError, is_compatible_version, parse_version};
+use abp_protocol::{Envelope, ProtocolError, parse_version};
 use serde::{Deserialize, Serialize};
 
 // ---------------------------------------------------------------------------
@@ -252,12 +253,9 @@ impl HandshakeValidator {
                 capabilities,
                 ..
             } => {
-                if !is_compatible_version(contract_version, &self.our_version.to_version_string()) {
-                    return Err(ProtocolError::Violation(format!(
-                        "incompatible contract version: got \"{contract_version}\", \
-                         expected compatible with \"{}\"",
-                        self.our_version
-                    )));
+                let report = compat::check(&self.our_version.to_version_string(), contract_version);
+                if !report.is_compatible() {
+                    return Err(ProtocolError::Violation(report.to_string()));
                 }
 
                 for cap in &self.required_capabilities {
@@ -498,6 +496,7 @@ mod tests {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let validator = HandshakeValidator::new();
         assert!(validator.validate_hello(&event).is_err());
@@ -510,6 +509,7 @@ mod tests {
             backend: test_identity(),
             capabilities: CapabilityManifest::new(),
             mode: abp_core::ExecutionMode::default(),
+            features: None,
         };
         let validator = HandshakeValidator::new();
         let err = validator.validate_hello(&hello).unwrap_err();
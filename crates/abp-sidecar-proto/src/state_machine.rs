@@ -219,6 +219,7 @@ mod tests {
                 kind: AgentEventKind::AssistantMessage { text: "msg".into() },
                 ext: None,
             },
+            seq: None,
         }
     }
 
@@ -326,6 +326,7 @@ async fn hello_roundtrip_preserves_fields() {
             contract_version,
             capabilities,
             mode,
+            ..
         } => {
             assert_eq!(backend.id, "comprehensive-test-sidecar");
             assert_eq!(backend.backend_version.as_deref(), Some("1.0.0"));
@@ -519,7 +520,7 @@ async fn event_roundtrip_run_started() {
     let text = drain(r).await;
     let env = JsonlCodec::decode(text.trim()).unwrap();
     match env {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "r1");
             assert!(matches!(event.kind, AgentEventKind::RunStarted { .. }));
         }
@@ -1356,6 +1357,7 @@ async fn server_rejects_event_as_first_non_blank() {
     let line = JsonlCodec::encode(&Envelope::Event {
         ref_id: "r1".into(),
         event: started(),
+        seq: None,
     })
     .unwrap();
     let (mut w, _r) = tokio::io::duplex(4096);
@@ -1623,6 +1625,7 @@ async fn validator_accepts_valid_sequence() {
         Envelope::Event {
             ref_id: run_id.into(),
             event: started(),
+            seq: None,
         },
         Envelope::Final {
             ref_id: run_id.into(),
@@ -1930,38 +1933,47 @@ async fn roundtrip_all_envelope_types_via_codec() {
         Envelope::Event {
             ref_id: "r-rt-all".into(),
             event: started(),
+            seq: None,
         },
         Envelope::Event {
             ref_id: "r-rt-all".into(),
             event: delta("tok"),
+            seq: None,
         },
         Envelope::Event {
             ref_id: "r-rt-all".into(),
             event: tool_call("read_file"),
+            seq: None,
         },
         Envelope::Event {
             ref_id: "r-rt-all".into(),
             event: tool_result("read_file"),
+            seq: None,
         },
         Envelope::Event {
             ref_id: "r-rt-all".into(),
             event: file_changed("/src/lib.rs"),
+            seq: None,
         },
         Envelope::Event {
             ref_id: "r-rt-all".into(),
             event: cmd_event("ls"),
+            seq: None,
         },
         Envelope::Event {
             ref_id: "r-rt-all".into(),
             event: warning("warn"),
+            seq: None,
         },
         Envelope::Event {
             ref_id: "r-rt-all".into(),
             event: error_event("err"),
+            seq: None,
         },
         Envelope::Event {
             ref_id: "r-rt-all".into(),
             event: completed(),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r-rt-all".into(),
@@ -2001,6 +2013,7 @@ async fn encode_to_writer_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event: delta("test"),
+        seq: None,
     };
     let mut buf = Vec::new();
     JsonlCodec::encode_to_writer(&mut buf, &env).unwrap();
@@ -2120,6 +2133,7 @@ async fn all_tags_use_t_not_type() {
             JsonlCodec::encode(&Envelope::Event {
                 ref_id: "r".into(),
                 event: started(),
+                seq: None,
             })
             .unwrap(),
         ),
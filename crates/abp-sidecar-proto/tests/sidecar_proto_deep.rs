@@ -202,6 +202,7 @@ async fn hello_message_construction_and_fields() {
             contract_version,
             capabilities,
             mode,
+            ..
         } => {
             assert_eq!(backend.id, "deep-test-sidecar");
             assert_eq!(backend.backend_version.as_deref(), Some("0.1.0"));
@@ -240,7 +241,7 @@ async fn event_message_with_agent_event() {
     let text = drain_duplex(r).await;
     let env = JsonlCodec::decode(text.trim()).unwrap();
     match env {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-ev");
             assert!(matches!(event.kind, AgentEventKind::RunStarted { .. }));
         }
@@ -325,11 +326,12 @@ async fn event_serde_roundtrip() {
     let original = Envelope::Event {
         ref_id: "run-rt".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&original).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-rt");
             match event.kind {
                 AgentEventKind::ToolCall {
@@ -363,6 +365,7 @@ async fn message_discriminator_tags_correct() {
     let event_json = JsonlCodec::encode(&Envelope::Event {
         ref_id: "r1".into(),
         event: run_started_event(),
+        seq: None,
     })
     .unwrap();
     assert!(event_json.contains(r#""t":"event""#));
@@ -692,7 +695,7 @@ async fn message_type_conversion_event_sender_to_envelope() {
 
     // Verify the envelope wraps the event correctly with ref_id
     match envelope {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-conv");
             match event.kind {
                 AgentEventKind::ToolResult {
@@ -822,10 +825,12 @@ async fn sequence_validator_accepts_valid_sidecar_output() {
         Envelope::Event {
             ref_id: run_id.into(),
             event: run_started_event(),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
             event: run_completed_event(),
+            seq: None,
         },
         Envelope::Final {
             ref_id: run_id.into(),
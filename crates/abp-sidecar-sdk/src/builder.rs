@@ -23,11 +23,13 @@
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use abp_core::{
     BackendIdentity, Capability, CapabilityManifest, ExecutionMode, Receipt, SupportLevel,
     WorkOrder,
 };
+use abp_protocol::features::ProtocolFeatures;
 
 use crate::emitter::EventEmitter;
 use crate::runtime::SidecarRuntime;
@@ -84,9 +86,24 @@ pub struct SidecarBuilder {
     adapter_version: Option<String>,
     capabilities: CapabilityManifest,
     mode: ExecutionMode,
+    features: Option<ProtocolFeatures>,
+    shutdown_grace: Duration,
     handler: Option<RunHandler>,
 }
 
+/// How long a run may keep going after shutdown starts, by default.
+pub const DEFAULT_SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+
+/// Features advertised in `hello` unless overridden: the baseline event
+/// kinds plus heartbeats, which the runtime answers on the handler's behalf.
+#[must_use]
+pub fn default_features() -> ProtocolFeatures {
+    ProtocolFeatures {
+        heartbeat: true,
+        ..ProtocolFeatures::baseline()
+    }
+}
+
 impl std::fmt::Debug for SidecarBuilder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SidecarBuilder")
@@ -95,6 +112,8 @@ impl std::fmt::Debug for SidecarBuilder {
             .field("adapter_version", &self.adapter_version)
             .field("capabilities", &self.capabilities)
             .field("mode", &self.mode)
+            .field("features", &self.features)
+            .field("shutdown_grace", &self.shutdown_grace)
             .field("handler", &self.handler.as_ref().map(|_| "..."))
             .finish()
     }
@@ -110,6 +129,8 @@ impl SidecarBuilder {
             adapter_version: None,
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: Some(default_features()),
+            shutdown_grace: DEFAULT_SHUTDOWN_GRACE,
             handler: None,
         }
     }
@@ -149,6 +170,24 @@ impl SidecarBuilder {
         self
     }
 
+    /// Replace the protocol features advertised in `hello`, or pass `None`
+    /// to advertise none, as sidecars predating feature negotiation do.
+    ///
+    /// Defaults to [`default_features`].
+    #[must_use]
+    pub fn protocol_features(mut self, features: Option<ProtocolFeatures>) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// How long an in-flight run may keep going once shutdown starts before
+    /// it is aborted. Defaults to [`DEFAULT_SHUTDOWN_GRACE`].
+    #[must_use]
+    pub fn shutdown_grace(mut self, grace: Duration) -> Self {
+        self.shutdown_grace = grace;
+        self
+    }
+
     /// Register the run handler that will process incoming work orders.
     ///
     /// The handler receives the [`WorkOrder`] and an [`EventEmitter`] for
@@ -177,12 +216,10 @@ impl SidecarBuilder {
             adapter_version: self.adapter_version.clone(),
         };
 
-        Ok(SidecarRuntime::new(
-            identity,
-            self.capabilities,
-            self.mode,
-            handler,
-        ))
+        Ok(
+            SidecarRuntime::new(identity, self.capabilities, self.mode, handler)
+                .with_lifecycle(self.features, self.shutdown_grace),
+        )
     }
 
     /// The configured sidecar name.
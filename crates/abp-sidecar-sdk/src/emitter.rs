@@ -3,12 +3,19 @@
 //!
 //! [`EventEmitter`] provides ergonomic helper methods for emitting the
 //! most common event types without manually constructing [`AgentEvent`]
-//! structs.
+//! structs. For anything else, [`EventEmitter::event`] starts an
+//! [`EventBuilder`] that can set the timestamp and extension fields.
+//!
+//! The emitter also tells a handler when the sidecar is shutting down: see
+//! [`EventEmitter::cancelled`].
+
+use std::collections::BTreeMap;
 
-use abp_core::{AgentEvent, AgentEventKind, Outcome, Receipt, ReceiptBuilder};
+use abp_core::{AgentEvent, AgentEventKind, Outcome, Receipt, ReceiptBuilder, UsageNormalized};
 use abp_error::ErrorCode;
-use chrono::Utc;
-use tokio::sync::mpsc;
+use abp_runtime::budget::USAGE_EXT_KEY;
+use chrono::{DateTime, Utc};
+use tokio::sync::{mpsc, watch};
 
 /// Sends [`AgentEvent`]s to the runtime for JSONL serialization.
 ///
@@ -33,6 +40,7 @@ use tokio::sync::mpsc;
 pub struct EventEmitter {
     ref_id: String,
     tx: mpsc::Sender<AgentEvent>,
+    cancel: Option<watch::Receiver<bool>>,
 }
 
 impl EventEmitter {
@@ -46,6 +54,7 @@ impl EventEmitter {
             Self {
                 ref_id: ref_id.into(),
                 tx,
+                cancel: None,
             },
             rx,
         )
@@ -57,15 +66,86 @@ impl EventEmitter {
         Self {
             ref_id: ref_id.into(),
             tx,
+            cancel: None,
         }
     }
 
+    /// Attach the signal that [`cancelled`](Self::cancelled) waits on.
+    pub(crate) fn with_cancel(mut self, cancel: watch::Receiver<bool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
     /// The run reference ID this emitter is associated with.
     #[must_use]
     pub fn ref_id(&self) -> &str {
         &self.ref_id
     }
 
+    /// Whether the sidecar has asked this run to stop.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|c| *c.borrow())
+    }
+
+    /// Wait until the sidecar asks this run to stop.
+    ///
+    /// Resolves when the sidecar starts shutting down; the handler then has
+    /// the builder's [`shutdown_grace`](crate::builder::SidecarBuilder::shutdown_grace)
+    /// to return a receipt before it is aborted. Never resolves for an
+    /// emitter created outside a [`SidecarRuntime`](crate::runtime::SidecarRuntime).
+    pub async fn cancelled(&self) {
+        if let Some(cancel) = &self.cancel {
+            let mut cancel = cancel.clone();
+            if cancel.wait_for(|stop| *stop).await.is_ok() {
+                return;
+            }
+        }
+        std::future::pending::<()>().await;
+    }
+
+    /// Start building an event of the given kind.
+    ///
+    /// ```
+    /// use abp_core::{AgentEventKind, UsageNormalized};
+    /// use abp_sidecar_sdk::emitter::EventEmitter;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// let (emitter, mut rx) = EventEmitter::new("run-1", 16);
+    /// let usage = UsageNormalized { output_tokens: Some(42), ..Default::default() };
+    /// emitter
+    ///     .event(AgentEventKind::AssistantMessage { text: "done".into() })
+    ///     .usage(&usage)
+    ///     .send()
+    ///     .await
+    ///     .unwrap();
+    /// assert!(rx.recv().await.unwrap().ext.unwrap().contains_key("abp.usage"));
+    /// # }
+    /// ```
+    pub fn event(&self, kind: AgentEventKind) -> EventBuilder<'_> {
+        EventBuilder {
+            emitter: self,
+            event: AgentEvent {
+                ts: Utc::now(),
+                kind,
+                ext: None,
+            },
+        }
+    }
+
+    /// Emit a fully formed event as is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receiving end has been dropped.
+    pub async fn emit_event(&self, event: AgentEvent) -> Result<(), EmitError> {
+        self.tx
+            .send(event)
+            .await
+            .map_err(|_| EmitError::ChannelClosed)
+    }
+
     /// Emit a streaming text delta (token).
     ///
     /// # Errors
@@ -235,15 +315,54 @@ impl EventEmitter {
     // -- internal ---------------------------------------------------------
 
     async fn emit(&self, kind: AgentEventKind) -> Result<(), EmitError> {
-        let event = AgentEvent {
-            ts: Utc::now(),
-            kind,
-            ext: None,
-        };
-        self.tx
-            .send(event)
-            .await
-            .map_err(|_| EmitError::ChannelClosed)
+        self.event(kind).send().await
+    }
+}
+
+/// An event being built by [`EventEmitter::event`].
+#[derive(Debug)]
+#[must_use = "an event is only emitted by `send`"]
+pub struct EventBuilder<'a> {
+    emitter: &'a EventEmitter,
+    event: AgentEvent,
+}
+
+impl EventBuilder<'_> {
+    /// Override the timestamp, which defaults to when the builder was made.
+    pub fn at(mut self, ts: DateTime<Utc>) -> Self {
+        self.event.ts = ts;
+        self
+    }
+
+    /// Set an extension field, e.g. a vendor's raw payload.
+    pub fn ext(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.event
+            .ext
+            .get_or_insert_with(BTreeMap::new)
+            .insert(key.into(), value);
+        self
+    }
+
+    /// Report cumulative usage so far under `ext["abp.usage"]`, where the
+    /// runtime's budget tracking and partial receipts look for it.
+    pub fn usage(self, usage: &UsageNormalized) -> Self {
+        let value = serde_json::to_value(usage).unwrap_or_default();
+        self.ext(USAGE_EXT_KEY, value)
+    }
+
+    /// The event without sending it.
+    #[must_use]
+    pub fn build(self) -> AgentEvent {
+        self.event
+    }
+
+    /// Send the event.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receiving end has been dropped.
+    pub async fn send(self) -> Result<(), EmitError> {
+        self.emitter.emit_event(self.event).await
     }
 }
 
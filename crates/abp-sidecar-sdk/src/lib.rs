@@ -9,10 +9,12 @@ pub mod runtime;
 
 // Re-export the high-level builder API for convenience.
 pub use builder::{SidecarBuilder, SidecarError};
-pub use emitter::{EmitError, EventEmitter};
+pub use emitter::{EmitError, EventBuilder, EventEmitter};
 pub use runtime::SidecarRuntime;
 
 use abp_host::SidecarSpec;
+pub use abp_integrations::{VendorConfig, VendorNamespace, vendor};
+
 use abp_integrations::SidecarBackend;
 use abp_runtime::Runtime;
 use anyhow::Result;
@@ -28,21 +30,48 @@ pub fn register_sidecar_backend(
     default_command: &str,
     provider_label: &str,
 ) -> Result<bool> {
+    let backend = resolve_sidecar_backend(
+        host_root,
+        host_script_relative,
+        command_override,
+        default_command,
+        provider_label,
+    )?;
+    match backend {
+        Some(backend) => {
+            runtime.register_backend(backend_name, backend);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Build a sidecar backend for a host script relative to `host_root`.
+///
+/// Returns `None` when neither the command nor the script is available.
+/// Vendor SDKs use this to configure the backend (e.g.
+/// [`SidecarBackend::with_vendor_namespace`]) before registering it.
+pub fn resolve_sidecar_backend(
+    host_root: &Path,
+    host_script_relative: &str,
+    command_override: Option<&str>,
+    default_command: &str,
+    provider_label: &str,
+) -> Result<Option<SidecarBackend>> {
     let command = resolve_command(command_override, default_command, provider_label)?;
     let command = match command {
         Some(c) => c,
-        None => return Ok(false),
+        None => return Ok(None),
     };
 
     let host_script = sidecar_script(host_root, host_script_relative);
     if !host_script.is_file() {
-        return Ok(false);
+        return Ok(None);
     }
 
     let mut spec = SidecarSpec::new(command);
     spec.args = vec![host_script.to_string_lossy().into_owned()];
-    runtime.register_backend(backend_name, SidecarBackend::new(spec));
-    Ok(true)
+    Ok(Some(SidecarBackend::new(spec)))
 }
 
 /// Resolve the host script path for a given runtime root.
@@ -3,20 +3,34 @@
 //!
 //! [`SidecarRuntime`] is produced by [`SidecarBuilder::build`] and handles:
 //!
-//! - Sending the `hello` handshake automatically
+//! - Sending the `hello` handshake automatically, with the configured
+//!   protocol features
 //! - Reading `run` envelopes from stdin
+//! - Answering heartbeat `ping`s with `pong`s, also while a run is going
 //! - Delegating to the registered run handler
-//! - Streaming events back as `event` envelopes
+//! - Streaming events back as numbered `event` envelopes
 //! - Sending the terminal `final` or `fatal` envelope
-//! - Graceful shutdown
+//! - Graceful shutdown: on a shutdown signal the in-flight run is asked to
+//!   stop (see [`EventEmitter::cancelled`]) and aborted with a `fatal` if it
+//!   outlives the grace period
 //!
 //! [`SidecarBuilder::build`]: crate::builder::SidecarBuilder::build
 
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
 use abp_core::{BackendIdentity, CapabilityManifest, ExecutionMode, WorkOrder};
+use abp_error::ErrorCode;
+use abp_protocol::features::ProtocolFeatures;
 use abp_protocol::{Envelope, JsonlCodec};
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader, Lines};
+use tokio::sync::watch;
 
-use crate::builder::{RunHandler, SidecarError};
+use crate::builder::{
+    DEFAULT_SHUTDOWN_GRACE, RunHandler, RunResult, SidecarError, default_features,
+};
 use crate::emitter::EventEmitter;
 
 /// Manages the sidecar protocol lifecycle.
@@ -26,6 +40,8 @@ pub struct SidecarRuntime {
     identity: BackendIdentity,
     capabilities: CapabilityManifest,
     mode: ExecutionMode,
+    features: Option<ProtocolFeatures>,
+    shutdown_grace: Duration,
     handler: RunHandler,
 }
 
@@ -35,6 +51,8 @@ impl std::fmt::Debug for SidecarRuntime {
             .field("identity", &self.identity)
             .field("capabilities", &self.capabilities)
             .field("mode", &self.mode)
+            .field("features", &self.features)
+            .field("shutdown_grace", &self.shutdown_grace)
             .field("handler", &"<RunHandler>")
             .finish()
     }
@@ -52,10 +70,22 @@ impl SidecarRuntime {
             identity,
             capabilities,
             mode,
+            features: Some(default_features()),
+            shutdown_grace: DEFAULT_SHUTDOWN_GRACE,
             handler,
         }
     }
 
+    pub(crate) fn with_lifecycle(
+        mut self,
+        features: Option<ProtocolFeatures>,
+        shutdown_grace: Duration,
+    ) -> Self {
+        self.features = features;
+        self.shutdown_grace = shutdown_grace;
+        self
+    }
+
     /// The backend identity of this sidecar.
     #[must_use]
     pub fn identity(&self) -> &BackendIdentity {
@@ -74,50 +104,64 @@ impl SidecarRuntime {
         self.mode
     }
 
+    /// The protocol features advertised in `hello`, if any.
+    #[must_use]
+    pub fn protocol_features(&self) -> Option<&ProtocolFeatures> {
+        self.features.as_ref()
+    }
+
     /// Run the sidecar protocol loop using the provided reader and writer.
     ///
     /// This is the testable core: it reads envelopes from `reader`, writes
     /// responses to `writer`, and delegates work orders to the run handler.
+    /// Returns once `reader` reaches EOF and any in-flight run has finished.
     ///
     /// # Errors
     ///
     /// Returns [`SidecarError`] on I/O or protocol failures.
-    pub async fn run_with_io<R, W>(&self, reader: R, mut writer: W) -> Result<(), SidecarError>
+    pub async fn run_with_io<R, W>(&self, reader: R, writer: W) -> Result<(), SidecarError>
     where
-        R: tokio::io::AsyncBufRead + Unpin,
-        W: tokio::io::AsyncWrite + Unpin,
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
     {
-        // 1. Send hello
-        self.send_hello(&mut writer).await?;
-
-        // 2. Read envelopes from reader
-        let mut reader = reader;
-        let mut line = String::new();
-
-        loop {
-            line.clear();
-            let n = reader.read_line(&mut line).await?;
-            if n == 0 {
-                break; // EOF
-            }
+        self.run_until(reader, writer, std::future::pending()).await
+    }
 
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
+    /// Like [`run_with_io`](Self::run_with_io), but also returns when
+    /// `shutdown` completes.
+    ///
+    /// Work orders not yet started are dropped. An in-flight run is asked to
+    /// stop through [`EventEmitter::cancelled`] and may still send its
+    /// receipt within the shutdown grace period; after that it is aborted
+    /// and a `fatal` envelope is sent in its place.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SidecarError`] on I/O or protocol failures.
+    pub async fn run_until<R, W, S>(
+        &self,
+        reader: R,
+        writer: W,
+        shutdown: S,
+    ) -> Result<(), SidecarError>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+        S: Future<Output = ()>,
+    {
+        let mut session = Session {
+            lines: reader.lines(),
+            writer,
+            shutdown: std::pin::pin!(shutdown),
+            pending: VecDeque::new(),
+            eof: false,
+            stopping: false,
+        };
 
-            let envelope = JsonlCodec::decode(trimmed)
-                .map_err(|e| SidecarError::Protocol(format!("failed to decode envelope: {e}")))?;
+        self.send_hello(&mut session.writer).await?;
 
-            match envelope {
-                Envelope::Run { id, work_order } => {
-                    self.handle_run(&id, work_order, &mut writer).await?;
-                }
-                _ => {
-                    // Ignore unexpected envelopes (hello, event, etc.)
-                    continue;
-                }
-            }
+        while let Some((run_id, work_order)) = session.next_run().await? {
+            self.handle_run(&run_id, work_order, &mut session).await?;
         }
 
         Ok(())
@@ -125,7 +169,9 @@ impl SidecarRuntime {
 
     /// Run the sidecar using real stdin/stdout.
     ///
-    /// This is the main entry point for a sidecar binary.
+    /// This is the main entry point for a sidecar binary. Ctrl-C, and
+    /// `SIGTERM` on Unix, start a graceful shutdown as described in
+    /// [`run_until`](Self::run_until).
     ///
     /// # Errors
     ///
@@ -134,85 +180,202 @@ impl SidecarRuntime {
         let stdin = tokio::io::stdin();
         let stdout = tokio::io::stdout();
         let reader = BufReader::new(stdin);
-        self.run_with_io(reader, stdout).await
+        self.run_until(reader, stdout, shutdown_signal()).await
     }
 
     // -- internal ---------------------------------------------------------
 
     async fn send_hello<W>(&self, writer: &mut W) -> Result<(), SidecarError>
     where
-        W: tokio::io::AsyncWrite + Unpin,
+        W: AsyncWrite + Unpin,
     {
-        let envelope =
+        let mut envelope =
             Envelope::hello_with_mode(self.identity.clone(), self.capabilities.clone(), self.mode);
-        let line = JsonlCodec::encode(&envelope)
-            .map_err(|e| SidecarError::Protocol(format!("failed to encode hello: {e}")))?;
-        writer.write_all(line.as_bytes()).await?;
-        writer.flush().await?;
-        Ok(())
+        if let Some(features) = &self.features {
+            envelope = envelope.with_features(features.clone());
+        }
+        write_envelope(writer, &envelope, "hello").await
     }
 
-    async fn handle_run<W>(
+    async fn handle_run<R, W, S>(
         &self,
         run_id: &str,
         work_order: WorkOrder,
-        writer: &mut W,
+        session: &mut Session<'_, R, W, S>,
     ) -> Result<(), SidecarError>
     where
-        W: tokio::io::AsyncWrite + Unpin,
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+        S: Future<Output = ()>,
     {
+        let (cancel_tx, cancel_rx) = watch::channel(false);
         let (emitter, mut rx) = EventEmitter::new(run_id, 64);
+        let emitter = emitter.with_cancel(cancel_rx);
 
         let handler = self.handler.clone();
 
         // Spawn the handler in a background task.
-        let handle = tokio::spawn(async move { (handler)(work_order, emitter).await });
+        let mut handle = tokio::spawn(async move { (handler)(work_order, emitter).await });
+        let mut deadline = None;
+        let mut seq = 0;
+
+        // Stream events from the handler to the writer, answering pings and
+        // queueing further runs in the meantime.
+        let result: RunResult = loop {
+            tokio::select! {
+                biased;
+                event = rx.recv() => match event {
+                    Some(event) => {
+                        let envelope = Envelope::Event {
+                            ref_id: run_id.to_string(),
+                            event,
+                            seq: Some(seq),
+                        };
+                        seq += 1;
+                        write_envelope(&mut session.writer, &envelope, "event").await?;
+                    }
+                    // Every emitter is gone, so the handler has finished.
+                    None => {
+                        break (&mut handle).await.unwrap_or_else(|e| {
+                            Err(SidecarError::Handler(format!("handler task panicked: {e}")))
+                        });
+                    }
+                },
+                line = session.lines.next_line(), if !session.eof => match line? {
+                    Some(line) => session.handle_line(&line).await?,
+                    None => session.eof = true,
+                },
+                () = session.shutdown.as_mut(), if !session.stopping => {
+                    session.stopping = true;
+                    let _ = cancel_tx.send(true);
+                    deadline = Some(Box::pin(tokio::time::sleep(self.shutdown_grace)));
+                }
+                () = async { deadline.as_mut().expect("guarded").await }, if deadline.is_some() => {
+                    handle.abort();
+                    let envelope = Envelope::Fatal {
+                        ref_id: Some(run_id.to_string()),
+                        error: "sidecar shut down before the run finished".into(),
+                        error_code: Some(ErrorCode::BackendUnavailable),
+                    };
+                    return write_envelope(&mut session.writer, &envelope, "fatal").await;
+                }
+            }
+        };
 
-        // Stream events from the handler to the writer.
-        while let Some(event) = rx.recv().await {
-            let envelope = Envelope::Event {
+        let envelope = match result {
+            Ok(receipt) => Envelope::Final {
                 ref_id: run_id.to_string(),
-                event,
-            };
-            let line = JsonlCodec::encode(&envelope)
-                .map_err(|e| SidecarError::Protocol(format!("failed to encode event: {e}")))?;
-            writer.write_all(line.as_bytes()).await?;
-            writer.flush().await?;
-        }
+                receipt,
+            },
+            Err(e) => Envelope::Fatal {
+                ref_id: Some(run_id.to_string()),
+                error: e.to_string(),
+                error_code: None,
+            },
+        };
+        write_envelope(&mut session.writer, &envelope, "terminal envelope").await
+    }
+}
 
-        // Wait for the handler to finish.
-        let result = handle
-            .await
-            .map_err(|e| SidecarError::Handler(format!("handler task panicked: {e}")))?;
-
-        match result {
-            Ok(receipt) => {
-                let envelope = Envelope::Final {
-                    ref_id: run_id.to_string(),
-                    receipt,
-                };
-                let line = JsonlCodec::encode(&envelope)
-                    .map_err(|e| SidecarError::Protocol(format!("failed to encode final: {e}")))?;
-                writer.write_all(line.as_bytes()).await?;
-                writer.flush().await?;
+/// I/O state shared by the protocol loop and the run in progress.
+struct Session<'s, R, W, S> {
+    lines: Lines<R>,
+    writer: W,
+    shutdown: Pin<&'s mut S>,
+    /// Run envelopes received while another run was in progress.
+    pending: VecDeque<(String, WorkOrder)>,
+    eof: bool,
+    stopping: bool,
+}
+
+impl<R, W, S> Session<'_, R, W, S>
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+    S: Future<Output = ()>,
+{
+    /// The next work order to run, or `None` once input ends or shutdown
+    /// starts.
+    async fn next_run(&mut self) -> Result<Option<(String, WorkOrder)>, SidecarError> {
+        loop {
+            if self.stopping {
+                return Ok(None);
             }
-            Err(e) => {
-                let envelope = Envelope::Fatal {
-                    ref_id: Some(run_id.to_string()),
-                    error: e.to_string(),
-                    error_code: None,
-                };
-                let line = JsonlCodec::encode(&envelope)
-                    .map_err(|e| SidecarError::Protocol(format!("failed to encode fatal: {e}")))?;
-                writer.write_all(line.as_bytes()).await?;
-                writer.flush().await?;
+            if let Some(run) = self.pending.pop_front() {
+                return Ok(Some(run));
+            }
+            if self.eof {
+                return Ok(None);
+            }
+            tokio::select! {
+                line = self.lines.next_line() => match line? {
+                    Some(line) => self.handle_line(&line).await?,
+                    None => self.eof = true,
+                },
+                () = self.shutdown.as_mut() => self.stopping = true,
             }
         }
+    }
 
+    async fn handle_line(&mut self, line: &str) -> Result<(), SidecarError> {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            return Ok(());
+        }
+
+        match JsonlCodec::decode(trimmed) {
+            Ok(Envelope::Run { id, work_order }) => self.pending.push_back((id, work_order)),
+            // Ignore unexpected envelopes (hello, event, etc.)
+            Ok(_) => {}
+            Err(e) => {
+                // Heartbeats are not envelopes.
+                let value: serde_json::Value = serde_json::from_str(trimmed).unwrap_or_default();
+                if value["t"] != "ping" {
+                    return Err(SidecarError::Protocol(format!(
+                        "failed to decode envelope: {e}"
+                    )));
+                }
+                let mut pong = serde_json::json!({"t": "pong", "seq": value["seq"]}).to_string();
+                pong.push('\n');
+                self.writer.write_all(pong.as_bytes()).await?;
+                self.writer.flush().await?;
+            }
+        }
         Ok(())
     }
 }
 
+async fn write_envelope<W>(
+    writer: &mut W,
+    envelope: &Envelope,
+    what: &str,
+) -> Result<(), SidecarError>
+where
+    W: AsyncWrite + Unpin,
+{
+    let line = JsonlCodec::encode(envelope)
+        .map_err(|e| SidecarError::Protocol(format!("failed to encode {what}: {e}")))?;
+    writer.write_all(line.as_bytes()).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Resolves on Ctrl-C, or `SIGTERM` on Unix.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        if let Ok(mut term) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = term.recv() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,152 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the sidecar runtime's handshake features, heartbeats, event
+//! numbering, and graceful shutdown.
+
+use std::time::Duration;
+
+use abp_core::{AgentEventKind, WorkOrderBuilder};
+use abp_error::ErrorCode;
+use abp_protocol::{Envelope, JsonlCodec};
+use abp_sidecar_sdk::builder::SidecarBuilder;
+use tokio::io::BufReader;
+
+fn run_line(id: &str) -> String {
+    let wo = WorkOrderBuilder::new("task").build();
+    JsonlCodec::encode(&Envelope::Run {
+        id: id.into(),
+        work_order: wo,
+    })
+    .unwrap()
+}
+
+fn output_lines(output: &[u8]) -> Vec<serde_json::Value> {
+    std::str::from_utf8(output)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn answers_pings_and_numbers_events() {
+    let rt = SidecarBuilder::new("beat")
+        .on_run(|_wo, em| async move {
+            em.emit_text_delta("a").await.unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            em.event(AgentEventKind::AssistantMessage { text: "b".into() })
+                .ext("vendor", serde_json::json!({"raw": true}))
+                .send()
+                .await
+                .unwrap();
+            Ok(em.finish("beat"))
+        })
+        .build()
+        .unwrap();
+
+    let input = format!(
+        "{{\"t\":\"ping\",\"seq\":1}}\n{}{{\"t\":\"ping\",\"seq\":2}}\n",
+        run_line("r1")
+    );
+    let mut output = Vec::new();
+    rt.run_with_io(BufReader::new(input.as_bytes()), &mut output)
+        .await
+        .unwrap();
+
+    let lines = output_lines(&output);
+    assert_eq!(lines[0]["t"], "hello");
+    assert_eq!(lines[0]["features"]["heartbeat"], true);
+    assert_eq!(lines[1], serde_json::json!({"t": "pong", "seq": 1}));
+    assert!(lines.contains(&serde_json::json!({"t": "pong", "seq": 2})));
+
+    let events: Vec<_> = lines.iter().filter(|l| l["t"] == "event").collect();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["seq"], 0);
+    assert_eq!(events[1]["seq"], 1);
+    assert_eq!(events[1]["event"]["ext"]["vendor"]["raw"], true);
+    assert_eq!(lines.last().unwrap()["t"], "final");
+}
+
+#[tokio::test]
+async fn features_can_be_withheld() {
+    let rt = SidecarBuilder::new("old")
+        .protocol_features(None)
+        .on_run(|_wo, em| async move { Ok(em.finish("old")) })
+        .build()
+        .unwrap();
+    let mut output = Vec::new();
+    rt.run_with_io(BufReader::new(b"" as &[u8]), &mut output)
+        .await
+        .unwrap();
+    assert!(output_lines(&output)[0].get("features").is_none());
+}
+
+#[tokio::test]
+async fn shutdown_lets_a_cooperative_run_finish() {
+    let rt = SidecarBuilder::new("polite")
+        .on_run(|_wo, em| async move {
+            em.emit_run_started("waiting").await.unwrap();
+            em.cancelled().await;
+            assert!(em.is_cancelled());
+            Ok(em.finish_failed("polite"))
+        })
+        .build()
+        .unwrap();
+
+    // Keep the input open so only the shutdown signal ends the loop.
+    let (mut host, sidecar) = tokio::io::duplex(4096);
+    tokio::io::AsyncWriteExt::write_all(&mut host, run_line("r1").as_bytes())
+        .await
+        .unwrap();
+    let mut output = Vec::new();
+    rt.run_until(
+        BufReader::new(sidecar),
+        &mut output,
+        tokio::time::sleep(Duration::from_millis(50)),
+    )
+    .await
+    .unwrap();
+
+    let lines = output_lines(&output);
+    let last = lines.last().unwrap();
+    assert_eq!(last["t"], "final");
+    assert_eq!(last["receipt"]["outcome"], "failed");
+}
+
+#[tokio::test]
+async fn shutdown_aborts_a_run_that_outlives_the_grace_period() {
+    let rt = SidecarBuilder::new("stubborn")
+        .shutdown_grace(Duration::from_millis(20))
+        .on_run(|_wo, em| async move {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(em.finish("stubborn"))
+        })
+        .build()
+        .unwrap();
+
+    let (mut host, sidecar) = tokio::io::duplex(4096);
+    let input = format!("{}{}", run_line("r1"), run_line("r2"));
+    tokio::io::AsyncWriteExt::write_all(&mut host, input.as_bytes())
+        .await
+        .unwrap();
+    let mut output = Vec::new();
+    rt.run_until(
+        BufReader::new(sidecar),
+        &mut output,
+        tokio::time::sleep(Duration::from_millis(30)),
+    )
+    .await
+    .unwrap();
+
+    let lines = output_lines(&output);
+    assert_eq!(lines.len(), 2, "the queued run must not start");
+    let fatal: Envelope = serde_json::from_value(lines[1].clone()).unwrap();
+    match fatal {
+        Envelope::Fatal {
+            ref_id, error_code, ..
+        } => {
+            assert_eq!(ref_id.as_deref(), Some("r1"));
+            assert_eq!(error_code, Some(ErrorCode::BackendUnavailable));
+        }
+        other => panic!("expected Fatal, got {other:?}"),
+    }
+}
@@ -65,6 +65,7 @@ pub struct EventStreamStats {
 /// let env = Envelope::Event {
 ///     ref_id: "run-1".into(),
 ///     event: event.clone(),
+///     seq: None,
 /// };
 /// let result = proc.process_envelope(&env);
 /// assert!(result.is_ok());
@@ -115,7 +116,7 @@ impl EventStreamProcessor {
         envelope: &Envelope,
     ) -> Result<Option<AgentEvent>, EventStreamError> {
         match envelope {
-            Envelope::Event { ref_id, event } => {
+            Envelope::Event { ref_id, event, .. } => {
                 if self.terminal_received {
                     self.stats.events_after_terminal += 1;
                     warn!(ref_id = %ref_id, "event received after terminal envelope");
@@ -200,6 +201,7 @@ mod tests {
                 kind,
                 ext: None,
             },
+            seq: None,
         }
     }
 
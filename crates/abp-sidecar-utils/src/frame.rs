@@ -84,6 +84,7 @@ pub fn encode_event(ref_id: &str, event: &AgentEvent) -> String {
     let envelope = Envelope::Event {
         ref_id: ref_id.into(),
         event: event.clone(),
+        seq: None,
     };
     JsonlCodec::encode(&envelope).expect("event envelope serialization should not fail")
 }
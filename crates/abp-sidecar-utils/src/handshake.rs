@@ -3,8 +3,10 @@
 
 use std::time::Duration;
 
+use abp_core::compat;
 use abp_core::{BackendIdentity, CONTRACT_VERSION, CapabilityManifest, ExecutionMode};
-use abp_protocol::{Envelope, JsonlCodec, ProtocolError, is_compatible_version};
+use abp_protocol::features::{ProtocolFeatures, negotiate_features};
+use abp_protocol::{Envelope, JsonlCodec, ProtocolError};
 use thiserror::Error;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt};
 
@@ -22,6 +24,9 @@ pub struct HelloInfo {
     pub capabilities: CapabilityManifest,
     /// Execution mode the peer will use.
     pub mode: ExecutionMode,
+    /// Protocol features agreed with the peer (the intersection of ours and
+    /// the peer's advertised block, or the baseline if it sent none).
+    pub features: ProtocolFeatures,
 }
 
 /// Errors specific to the handshake phase.
@@ -31,9 +36,10 @@ pub enum HandshakeError {
     #[error("handshake timed out after {0:?}")]
     Timeout(Duration),
     /// The peer sent an incompatible contract version.
-    #[error(
-        "incompatible contract version: got \"{got}\", expected compatible with \"{expected}\""
-    )]
+    ///
+    /// The message comes from [`compat::check`] and says which side to
+    /// upgrade.
+    #[error("{}", compat::check(.expected, .got))]
     IncompatibleVersion {
         /// Version the peer advertised.
         got: String,
@@ -75,7 +81,9 @@ pub struct HandshakeManager;
 impl HandshakeManager {
     /// Wait for a hello envelope from the peer, with a timeout.
     ///
-    /// Validates that the contract version is compatible with ours.
+    /// Validates that the contract version is compatible with ours. A peer
+    /// with a different major version is still accepted when it advertises
+    /// a feature-negotiation block; both sides then use the agreed subset.
     ///
     /// # Errors
     ///
@@ -108,8 +116,9 @@ impl HandshakeManager {
                 backend,
                 capabilities,
                 mode,
+                features,
             } => {
-                if !is_compatible_version(&contract_version, CONTRACT_VERSION) {
+                if !compat::check_sidecar(&contract_version).is_compatible() {
                     return Err(HandshakeError::IncompatibleVersion {
                         got: contract_version,
                         expected: CONTRACT_VERSION.to_string(),
@@ -120,6 +129,7 @@ impl HandshakeManager {
                     backend,
                     capabilities,
                     mode,
+                    features: negotiate_features(&ProtocolFeatures::current(), features.as_ref()),
                 })
             }
             other => Err(HandshakeError::UnexpectedMessage(format!("{other:?}"))),
@@ -139,7 +149,8 @@ impl HandshakeManager {
     where
         W: tokio::io::AsyncWrite + Unpin,
     {
-        let envelope = Envelope::hello(backend, capabilities);
+        let envelope =
+            Envelope::hello(backend, capabilities).with_features(ProtocolFeatures::current());
         let line = JsonlCodec::encode(&envelope).map_err(HandshakeError::Protocol)?;
         writer
             .write_all(line.as_bytes())
@@ -166,6 +177,7 @@ mod tests {
             },
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         JsonlCodec::encode(&env).unwrap()
     }
@@ -189,6 +201,91 @@ mod tests {
             .await
             .unwrap_err();
         assert!(matches!(err, HandshakeError::IncompatibleVersion { .. }));
+        assert!(
+            err.to_string()
+                .contains("upgrade the host to an abp/v99.x release")
+        );
+    }
+
+    #[tokio::test]
+    async fn await_hello_without_features_uses_baseline() {
+        let hello = make_hello_line(CONTRACT_VERSION);
+        let reader = BufReader::new(hello.as_bytes());
+        let info = HandshakeManager::await_hello(reader, DEFAULT_HANDSHAKE_TIMEOUT)
+            .await
+            .unwrap();
+        assert_eq!(info.features, ProtocolFeatures::baseline());
+    }
+
+    fn make_hello_line_with_features(version: &str, features: ProtocolFeatures) -> String {
+        let env = Envelope::hello(
+            BackendIdentity {
+                id: "future-sidecar".into(),
+                backend_version: None,
+                adapter_version: None,
+            },
+            CapabilityManifest::new(),
+        )
+        .with_features(features);
+        let mut line = serde_json::to_value(&env).unwrap();
+        line["contract_version"] = version.into();
+        format!("{line}\n")
+    }
+
+    #[tokio::test]
+    async fn await_hello_minor_version_with_features_negotiates() {
+        let mut features = ProtocolFeatures::current();
+        features.heartbeat = false;
+        features.event_kinds.insert("reasoning_delta".into());
+        let line = make_hello_line_with_features("abp/v0.2", features);
+
+        let info = HandshakeManager::await_hello(
+            BufReader::new(line.as_bytes()),
+            DEFAULT_HANDSHAKE_TIMEOUT,
+        )
+        .await
+        .unwrap();
+        assert_eq!(info.contract_version, "abp/v0.2");
+        assert!(!info.features.heartbeat);
+        assert!(info.features.tool_channel);
+        assert!(!info.features.supports_event_kind("reasoning_delta"));
+    }
+
+    #[tokio::test]
+    async fn await_hello_major_mismatch_with_features_is_rejected() {
+        for version in ["abp/v1.0", "not-a-version"] {
+            let line = make_hello_line_with_features(version, ProtocolFeatures::current());
+            let err = HandshakeManager::await_hello(
+                BufReader::new(line.as_bytes()),
+                DEFAULT_HANDSHAKE_TIMEOUT,
+            )
+            .await
+            .unwrap_err();
+            assert!(
+                matches!(err, HandshakeError::IncompatibleVersion { ref got, .. } if got == version)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn send_hello_advertises_features() {
+        let mut buf = Vec::new();
+        HandshakeManager::send_hello(
+            &mut buf,
+            BackendIdentity {
+                id: "me".into(),
+                backend_version: None,
+                adapter_version: None,
+            },
+            CapabilityManifest::new(),
+        )
+        .await
+        .unwrap();
+        let info =
+            HandshakeManager::await_hello(BufReader::new(&buf[..]), DEFAULT_HANDSHAKE_TIMEOUT)
+                .await
+                .unwrap();
+        assert_eq!(info.features, ProtocolFeatures::current());
     }
 
     #[tokio::test]
@@ -49,6 +49,7 @@ pub fn mock_event(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantMessage { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -7,14 +7,14 @@
 //! - A well-formed sequence starts with `Hello`, has zero or more `Event`s,
 //!   and ends with exactly one `Final` or `Fatal`.
 
-use abp_core::CONTRACT_VERSION;
-use abp_protocol::{Envelope, ProtocolError, is_compatible_version};
+use abp_core::compat;
+use abp_protocol::{Envelope, ProtocolError};
 
 /// Validate that an envelope is a well-formed `Hello`.
 ///
 /// Checks:
 /// - The envelope is the `Hello` variant.
-/// - The `contract_version` is compatible with [`CONTRACT_VERSION`].
+/// - The `contract_version` is compatible with [`CONTRACT_VERSION`](abp_core::CONTRACT_VERSION).
 ///
 /// # Errors
 ///
@@ -24,11 +24,9 @@ pub fn validate_hello(envelope: &Envelope) -> Result<(), ProtocolError> {
         Envelope::Hello {
             contract_version, ..
         } => {
-            if !is_compatible_version(contract_version, CONTRACT_VERSION) {
-                return Err(ProtocolError::Violation(format!(
-                    "incompatible contract version: got \"{contract_version}\", \
-                     expected compatible with \"{CONTRACT_VERSION}\""
-                )));
+            let report = compat::check_sidecar(contract_version);
+            if !report.is_compatible() {
+                return Err(ProtocolError::Violation(report.to_string()));
             }
             Ok(())
         }
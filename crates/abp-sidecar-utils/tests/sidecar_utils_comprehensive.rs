@@ -49,6 +49,7 @@ fn make_event_envelope(ref_id: &str, kind: AgentEventKind) -> Envelope {
     Envelope::Event {
         ref_id: ref_id.into(),
         event: make_agent_event(kind),
+        seq: None,
     }
 }
 
@@ -62,6 +63,7 @@ fn make_hello_line(version: &str) -> String {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     JsonlCodec::encode(&env).unwrap()
 }
@@ -359,7 +361,7 @@ fn testing_mock_hello() {
 fn testing_mock_event() {
     let event = mock_event("run-1", "hello world");
     match &event {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             assert!(matches!(
                 &event.kind,
@@ -442,6 +444,7 @@ fn validate_hello_incompatible_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = validate_hello(&hello).unwrap_err();
     assert!(matches!(err, ProtocolError::Violation(_)));
@@ -51,6 +51,7 @@ fn make_hello_line(version: &str) -> String {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     JsonlCodec::encode(&env).unwrap()
 }
@@ -440,16 +441,19 @@ fn esp_counts_by_type_tracks_different_kinds() {
     let msg_env = Envelope::Event {
         ref_id: "run-1".into(),
         event: make_agent_event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+        seq: None,
     };
     let delta_env = Envelope::Event {
         ref_id: "run-1".into(),
         event: make_agent_event(AgentEventKind::AssistantDelta { text: "tok".into() }),
+        seq: None,
     };
     let started_env = Envelope::Event {
         ref_id: "run-1".into(),
         event: make_agent_event(AgentEventKind::RunStarted {
             message: "go".into(),
         }),
+        seq: None,
     };
 
     proc.process_envelope(&msg_env).unwrap();
@@ -612,7 +616,10 @@ fn frame_encode_event_roundtrip() {
     assert!(line.ends_with('\n'));
 
     let env = decode_envelope(&line).unwrap();
-    if let Envelope::Event { ref_id, event: e } = env {
+    if let Envelope::Event {
+        ref_id, event: e, ..
+    } = env
+    {
         assert_eq!(ref_id, "run-99");
         assert!(matches!(
             e.kind,
@@ -1106,7 +1113,7 @@ fn mock_hello_backend_id_preserved() {
 #[test]
 fn mock_event_ref_id_and_text() {
     let env = mock_event("run-abc", "some text");
-    if let Envelope::Event { ref_id, event } = &env {
+    if let Envelope::Event { ref_id, event, .. } = &env {
         assert_eq!(ref_id, "run-abc");
         if let AgentEventKind::AssistantMessage { text } = &event.kind {
             assert_eq!(text, "some text");
@@ -1244,6 +1251,7 @@ fn validate_hello_rejects_incompatible_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = validate_hello(&hello).unwrap_err();
     assert!(err.to_string().contains("incompatible"));
@@ -1260,6 +1268,7 @@ fn validate_hello_rejects_empty_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = validate_hello(&hello).unwrap_err();
     assert!(err.to_string().contains("incompatible"));
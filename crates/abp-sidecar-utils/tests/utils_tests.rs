@@ -75,7 +75,9 @@ fn roundtrip_event() {
     let line = encode_event("run-42", &event);
     let env = decode_envelope(&line).unwrap();
     match &env {
-        Envelope::Event { ref_id, event: e } => {
+        Envelope::Event {
+            ref_id, event: e, ..
+        } => {
             assert_eq!(ref_id, "run-42");
             assert!(
                 matches!(&e.kind, AgentEventKind::AssistantMessage { text } if text == "hello")
@@ -197,6 +199,7 @@ fn validate_hello_rejects_incompatible_version() {
         },
         capabilities: abp_core::CapabilityManifest::new(),
         mode: abp_core::ExecutionMode::default(),
+        features: None,
     };
     let err = validate_hello(&hello).unwrap_err();
     let msg = err.to_string();
@@ -356,7 +359,7 @@ fn mock_hello_roundtrip_jsonl() {
 fn mock_event_produces_valid_envelope() {
     let env = mock_event("run-1", "hello world");
     match &env {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             assert!(
                 matches!(&event.kind, AgentEventKind::AssistantMessage { text } if text == "hello world")
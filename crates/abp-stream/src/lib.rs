@@ -21,6 +21,7 @@ pub mod merged;
 pub mod metrics;
 pub mod mux;
 pub mod replay;
+pub mod skew;
 pub mod tee;
 pub mod timeout;
 pub mod transform;
@@ -38,6 +39,7 @@ pub use merged::MergedStream;
 pub use metrics::{MetricsSummary, PerStreamMetrics, StreamMetrics};
 pub use mux::StreamMultiplexer;
 pub use replay::{ReplayBuffer, ReplaySubscription, StreamRecorder, TimedEvent};
+pub use skew::{ClockSkew, SkewCorrector};
 pub use tee::{StreamTee, TeeError};
 pub use timeout::{StreamTimeout, TimeoutItem, TimeoutStream};
 pub use transform::{BatchStream, FilterStream, MapStream, TakeUntilStream, ThrottleStream};
@@ -516,42 +518,71 @@ impl futures_core::Stream for EventStream {
 // ---------------------------------------------------------------------------
 
 /// Combines multiple event streams into one, maintaining ordering by timestamp.
+///
+/// Sources stamped by other hosts can be given a [`ClockSkew`] with
+/// [`with_skew`](Self::with_skew). Every event's `ts` is rewritten onto the
+/// local clock by a [`SkewCorrector`], so a source's events keep the order
+/// it sent them in even when its own timestamps go backwards.
 pub struct EventMultiplexer {
     receivers: Vec<mpsc::Receiver<AgentEvent>>,
+    skews: Vec<ClockSkew>,
 }
 
 impl EventMultiplexer {
     /// Create a new multiplexer from multiple receivers.
     pub fn new(receivers: Vec<mpsc::Receiver<AgentEvent>>) -> Self {
-        Self { receivers }
+        let skews = vec![ClockSkew::NONE; receivers.len()];
+        Self { receivers, skews }
+    }
+
+    /// Set the clock skew of the source at `index` (builder pattern).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not a source of this multiplexer.
+    #[must_use]
+    pub fn with_skew(mut self, index: usize, skew: ClockSkew) -> Self {
+        self.skews[index] = skew;
+        self
+    }
+
+    /// The clock skew assumed for each source, in source order.
+    pub fn skews(&self) -> &[ClockSkew] {
+        &self.skews
     }
 
-    /// Drain all streams and return events sorted by timestamp.
+    /// Drain all streams and return events sorted by corrected timestamp.
     ///
-    /// Each stream is drained concurrently; the final output is sorted.
+    /// Events with equal timestamps are ordered by source index, and a
+    /// source's events always stay in the order it sent them.
     pub async fn collect_sorted(self) -> Vec<AgentEvent> {
         let mut all = Vec::new();
-        for mut rx in self.receivers {
+        for (mut rx, skew) in self.receivers.into_iter().zip(self.skews) {
+            let mut corrector = SkewCorrector::new(skew);
             while let Some(ev) = rx.recv().await {
-                all.push(ev);
+                all.push(corrector.correct(ev));
             }
         }
+        // Stable, and each source is already non-decreasing after correction.
         all.sort_by_key(|ev| ev.ts);
         all
     }
 
     /// Merge streams into a single output channel using true interleaved
     /// fan-in. Events are forwarded as they arrive from any source stream,
-    /// without waiting for all streams to close first.
+    /// without waiting for all streams to close first, with their timestamps
+    /// corrected as in [`collect_sorted`](Self::collect_sorted).
     ///
     /// Returns the receiving end of the merged stream.
     pub fn merge(self, buffer: usize) -> mpsc::Receiver<AgentEvent> {
         let (tx, rx) = mpsc::channel(buffer);
 
-        for mut r in self.receivers {
+        for (mut r, skew) in self.receivers.into_iter().zip(self.skews) {
             let tx = tx.clone();
             tokio::spawn(async move {
+                let mut corrector = SkewCorrector::new(skew);
                 while let Some(ev) = r.recv().await {
+                    let ev = corrector.correct(ev);
                     if tx.send(ev).await.is_err() {
                         break;
                     }
@@ -704,6 +735,9 @@ pub fn event_kind_name(kind: &AgentEventKind) -> String {
         AgentEventKind::ToolResult { .. } => "tool_result".to_string(),
         AgentEventKind::FileChanged { .. } => "file_changed".to_string(),
         AgentEventKind::CommandExecuted { .. } => "command_executed".to_string(),
+        AgentEventKind::Progress { .. } => "progress".to_string(),
+        AgentEventKind::RunSummary { .. } => "run_summary".to_string(),
+        AgentEventKind::ArtifactChunk { .. } => "artifact_chunk".to_string(),
         AgentEventKind::Warning { .. } => "warning".to_string(),
         AgentEventKind::Error { .. } => "error".to_string(),
     }
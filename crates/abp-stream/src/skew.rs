@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Clock skew correction for events stamped by other hosts.
+//!
+//! Each backend stamps events with its own clock, so sorting a merged stream
+//! by raw `ts` interleaves sources wrongly whenever their clocks disagree.
+//! A [`ClockSkew`] holds a source's offset from the local clock, usually
+//! estimated from a handshake, and a [`SkewCorrector`] applies it while
+//! keeping each source's events in the order they were sent.
+
+use abp_core::AgentEvent;
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// How far a source's clock runs ahead of the local clock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClockSkew {
+    offset: TimeDelta,
+}
+
+impl ClockSkew {
+    /// A source whose clock agrees with ours.
+    pub const NONE: Self = Self {
+        offset: TimeDelta::zero(),
+    };
+
+    /// A source whose clock runs `offset` ahead of ours (negative if behind).
+    #[must_use]
+    pub fn from_offset(offset: TimeDelta) -> Self {
+        Self { offset }
+    }
+
+    /// Estimate the offset from one round trip.
+    ///
+    /// `sent` and `received` are local times at which a request went out and
+    /// its reply came back; `remote` is the time the source wrote into the
+    /// reply (e.g. its `hello`). The source is assumed to have stamped the
+    /// reply halfway through the round trip, so the estimate is off by at most
+    /// half the round-trip time.
+    #[must_use]
+    pub fn from_handshake(
+        sent: DateTime<Utc>,
+        remote: DateTime<Utc>,
+        received: DateTime<Utc>,
+    ) -> Self {
+        let midpoint = sent + (received - sent) / 2;
+        Self {
+            offset: remote - midpoint,
+        }
+    }
+
+    /// The estimated offset.
+    #[must_use]
+    pub fn offset(&self) -> TimeDelta {
+        self.offset
+    }
+
+    /// Translate a timestamp from the source's clock to ours.
+    #[must_use]
+    pub fn correct(&self, ts: DateTime<Utc>) -> DateTime<Utc> {
+        ts - self.offset
+    }
+}
+
+/// Corrects the timestamps of one source's events, in the order it sent them.
+///
+/// Besides removing the [`ClockSkew`], a corrector never lets time run
+/// backwards within the source: an event stamped earlier than its
+/// predecessor, e.g. after the source's clock was stepped, takes the
+/// predecessor's time. Merging corrected sources by `ts` with a stable sort
+/// therefore keeps every source's sequence intact.
+#[derive(Debug, Clone, Default)]
+pub struct SkewCorrector {
+    skew: ClockSkew,
+    last: Option<DateTime<Utc>>,
+}
+
+impl SkewCorrector {
+    /// A corrector for a source with the given skew.
+    #[must_use]
+    pub fn new(skew: ClockSkew) -> Self {
+        Self { skew, last: None }
+    }
+
+    /// Rewrite `event.ts` onto the local clock.
+    #[must_use]
+    pub fn correct(&mut self, mut event: AgentEvent) -> AgentEvent {
+        let ts = self.skew.correct(event.ts);
+        let ts = self.last.map_or(ts, |last| ts.max(last));
+        self.last = Some(ts);
+        event.ts = ts;
+        event
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use abp_core::AgentEventKind;
+
+    fn at(ms: i64) -> DateTime<Utc> {
+        DateTime::UNIX_EPOCH + TimeDelta::milliseconds(ms)
+    }
+
+    #[test]
+    fn handshake_estimate_uses_round_trip_midpoint() {
+        let skew = ClockSkew::from_handshake(at(1_000), at(5_050), at(1_100));
+        assert_eq!(skew.offset(), TimeDelta::milliseconds(4_000));
+        assert_eq!(skew.correct(at(5_050)), at(1_050));
+    }
+
+    #[test]
+    fn corrector_never_runs_backwards() {
+        let mut c = SkewCorrector::new(ClockSkew::from_offset(TimeDelta::milliseconds(-200)));
+        let ev = |ms| AgentEvent {
+            ts: at(ms),
+            kind: AgentEventKind::AssistantDelta { text: "x".into() },
+            ext: None,
+        };
+        assert_eq!(c.correct(ev(100)).ts, at(300));
+        assert_eq!(c.correct(ev(50)).ts, at(300));
+        assert_eq!(c.correct(ev(150)).ts, at(350));
+    }
+}
@@ -758,6 +758,48 @@ async fn multiplexer_preserves_timestamp_order() {
     assert_eq!(events[3].ts, ts4);
 }
 
+#[tokio::test]
+async fn multiplexer_corrects_skewed_sources() {
+    let base = Utc::now();
+    let ms = chrono::Duration::milliseconds;
+    let text = |ev: &AgentEvent| match &ev.kind {
+        AgentEventKind::AssistantDelta { text } => text.clone(),
+        _ => unreachable!(),
+    };
+
+    // Source 0 runs on our clock. Source 1's clock is 1s fast, and it
+    // stepped back 500ms between its two events.
+    let (tx0, rx0) = mpsc::channel(16);
+    let (tx1, rx1) = mpsc::channel(16);
+    for (text, ts) in [("local-a", base + ms(10)), ("local-b", base + ms(30))] {
+        tx0.send(make_event_with_ts(
+            AgentEventKind::AssistantDelta { text: text.into() },
+            ts,
+        ))
+        .await
+        .unwrap();
+    }
+    for (text, ts) in [("remote-a", base + ms(1_020)), ("remote-b", base + ms(520))] {
+        tx1.send(make_event_with_ts(
+            AgentEventKind::AssistantDelta { text: text.into() },
+            ts,
+        ))
+        .await
+        .unwrap();
+    }
+    drop(tx0);
+    drop(tx1);
+
+    let skew = ClockSkew::from_handshake(base, base + ms(1_000), base);
+    assert_eq!(skew.offset(), ms(1_000));
+    let mux = EventMultiplexer::new(vec![rx0, rx1]).with_skew(1, skew);
+    let events = mux.collect_sorted().await;
+    let order: Vec<_> = events.iter().map(text).collect();
+    assert_eq!(order, ["local-a", "remote-a", "remote-b", "local-b"]);
+    assert_eq!(events[1].ts, base + ms(20));
+    assert_eq!(events[2].ts, base + ms(20));
+}
+
 #[tokio::test]
 async fn multiplexer_three_streams() {
     let base = Utc::now();
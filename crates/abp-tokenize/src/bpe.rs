@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Approximate byte-pair encoding counts for OpenAI models.
+//!
+//! Splits text the way the `cl100k_base` / `o200k_base` pre-tokenizer does —
+//! contractions, words with their leading space, digit groups of up to
+//! three, punctuation runs, and whitespace — then estimates how many merged
+//! tokens each piece becomes. Common English words are a single token; long
+//! or non-ASCII words split further. No vocabulary is bundled, so counts are
+//! estimates, typically within a few percent for English prose and code.
+
+/// Letters an ASCII word may have and still be a single token.
+const WORD_TOKEN_LETTERS: usize = 8;
+
+/// Letters each further token of a long ASCII word covers.
+const SUFFIX_TOKEN_LETTERS: usize = 6;
+
+/// Characters one token covers in a punctuation run.
+const PUNCT_TOKEN_CHARS: usize = 3;
+
+/// Contraction suffixes the pre-tokenizer keeps as their own piece.
+const CONTRACTIONS: [&str; 7] = ["'ll", "'re", "'ve", "'s", "'t", "'m", "'d"];
+
+/// Estimated token count of `text`.
+#[must_use]
+pub fn count(text: &str) -> u64 {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if let Some(len) = contraction_at(&chars[i..]) {
+            tokens += 1;
+            i += len;
+        } else if c.is_alphabetic()
+            || (!c.is_numeric() && !is_newline(c) && is_letter(&chars, i + 1))
+        {
+            // A word, with at most one leading space or punctuation mark.
+            let start = if c.is_alphabetic() { i } else { i + 1 };
+            let end = run_end(&chars, start, char::is_alphabetic);
+            tokens += word_tokens(&chars[start..end]);
+            i = end;
+        } else if c.is_numeric() {
+            let end = run_end(&chars, i, char::is_numeric);
+            tokens += (end - i).div_ceil(3) as u64;
+            i = end;
+        } else if c.is_whitespace() && !(c == ' ' && is_punct(&chars, i + 1)) {
+            let mut end = run_end(&chars, i, char::is_whitespace);
+            // A trailing space joins the punctuation after it.
+            if end - i > 1 && chars[end - 1] == ' ' && is_punct(&chars, end) {
+                end -= 1;
+            }
+            tokens += 1;
+            i = end;
+        } else {
+            // A punctuation run, with at most one leading space.
+            let start = if c == ' ' { i + 1 } else { i };
+            let end = run_end(&chars, start, |c| {
+                !c.is_whitespace() && !c.is_alphanumeric()
+            });
+            tokens += (end - start).div_ceil(PUNCT_TOKEN_CHARS) as u64;
+            // Newlines directly after punctuation belong to it.
+            i = run_end(&chars, end, is_newline);
+        }
+    }
+    tokens
+}
+
+/// Tokens a run of letters becomes.
+fn word_tokens(word: &[char]) -> u64 {
+    if word.iter().all(char::is_ascii) {
+        let extra = word.len().saturating_sub(WORD_TOKEN_LETTERS);
+        return 1 + extra.div_ceil(SUFFIX_TOKEN_LETTERS) as u64;
+    }
+    let cjk = word.iter().filter(|c| is_cjk(**c)).count();
+    (cjk + (word.len() - cjk).div_ceil(2)).max(1) as u64
+}
+
+/// Length of the contraction starting at `chars[0]`, if any.
+fn contraction_at(chars: &[char]) -> Option<usize> {
+    CONTRACTIONS
+        .iter()
+        .find(|s| {
+            s.len() <= chars.len()
+                && s.chars()
+                    .zip(chars)
+                    .all(|(a, b)| a == b.to_ascii_lowercase())
+        })
+        .map(|s| s.len())
+}
+
+fn is_letter(chars: &[char], i: usize) -> bool {
+    chars.get(i).is_some_and(|c| c.is_alphabetic())
+}
+
+fn is_punct(chars: &[char], i: usize) -> bool {
+    chars
+        .get(i)
+        .is_some_and(|c| !c.is_whitespace() && !c.is_alphanumeric())
+}
+
+fn run_end(chars: &[char], start: usize, pred: impl Fn(char) -> bool) -> usize {
+    chars[start..]
+        .iter()
+        .position(|&c| !pred(c))
+        .map_or(chars.len(), |n| start + n)
+}
+
+fn is_newline(c: char) -> bool {
+    c == '\n' || c == '\r'
+}
+
+/// Whether `c` is a Chinese, Japanese, or Korean character.
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{30ff}'
+            | '\u{3400}'..='\u{4dbf}'
+            | '\u{4e00}'..='\u{9fff}'
+            | '\u{ac00}'..='\u{d7af}'
+            | '\u{f900}'..='\u{faff}'
+    )
+}
@@ -0,0 +1,211 @@
+#![doc = include_str!("../README.md")]
+#![deny(unsafe_code)]
+#![warn(missing_docs)]
+//! Approximate token counting for pre-flight budgeting.
+
+pub mod bpe;
+
+use abp_core::WorkOrder;
+use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole, IrToolDefinition};
+use abp_dialect::Dialect;
+use serde::{Deserialize, Serialize};
+
+/// Key under `config.vendor["abp"]` holding the projected input token count.
+pub const PROJECTED_INPUT_TOKENS_KEY: &str = "projected_input_tokens";
+
+/// Tokens of framing each message costs on top of its content.
+pub const MESSAGE_OVERHEAD_TOKENS: u64 = 3;
+
+/// Tokens the provider adds to prime the assistant's reply.
+pub const REPLY_PRIMING_TOKENS: u64 = 3;
+
+/// Tokens of framing each tool definition costs on top of its content.
+pub const TOOL_OVERHEAD_TOKENS: u64 = 8;
+
+/// Tokens charged per image, whatever its size.
+///
+/// Matches a 512×512 high-detail image on OpenAI models; larger images cost
+/// more, so treat image-heavy counts as a lower bound.
+pub const IMAGE_TOKENS: u64 = 765;
+
+/// How text is counted for a model family.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizerKind {
+    /// OpenAI models: a `cl100k_base`-style BPE approximation.
+    OpenAiBpe,
+    /// Anthropic Claude models: about 3.5 characters per token.
+    Claude,
+    /// Google Gemini models: about 4 characters per token.
+    Gemini,
+    /// Any other model: about 4 characters per token.
+    Generic,
+}
+
+impl TokenizerKind {
+    /// The tokenizer for a model name, by its family prefix.
+    #[must_use]
+    pub fn for_model(model: &str) -> Self {
+        let model = model.to_ascii_lowercase();
+        let model = model.rsplit('/').next().unwrap_or_default();
+        if ["gpt", "o1", "o3", "o4", "chatgpt", "codex"]
+            .iter()
+            .any(|p| model.starts_with(p))
+        {
+            Self::OpenAiBpe
+        } else if model.starts_with("claude") {
+            Self::Claude
+        } else if model.starts_with("gemini") {
+            Self::Gemini
+        } else {
+            Self::Generic
+        }
+    }
+
+    /// The tokenizer for models usually served through `dialect`.
+    #[must_use]
+    pub fn for_dialect(dialect: Dialect) -> Self {
+        match dialect {
+            Dialect::OpenAi | Dialect::Codex | Dialect::Copilot => Self::OpenAiBpe,
+            Dialect::Claude => Self::Claude,
+            Dialect::Gemini => Self::Gemini,
+            Dialect::Kimi => Self::Generic,
+        }
+    }
+
+    /// Estimated token count of `text`.
+    #[must_use]
+    pub fn count(self, text: &str) -> u64 {
+        match self {
+            Self::OpenAiBpe => bpe::count(text),
+            Self::Claude => heuristic(text, 3.5),
+            Self::Gemini | Self::Generic => heuristic(text, 4.0),
+        }
+    }
+
+    /// Estimated tokens for one message, including its framing.
+    #[must_use]
+    pub fn count_message(self, message: &IrMessage) -> u64 {
+        MESSAGE_OVERHEAD_TOKENS + self.count_blocks(&message.content)
+    }
+
+    /// Estimated input tokens for a whole conversation.
+    #[must_use]
+    pub fn count_conversation(self, conversation: &IrConversation) -> u64 {
+        REPLY_PRIMING_TOKENS
+            + conversation
+                .messages
+                .iter()
+                .map(|m| self.count_message(m))
+                .sum::<u64>()
+    }
+
+    /// Estimated tokens the tool definitions add to the prompt.
+    #[must_use]
+    pub fn count_tools(self, tools: &[IrToolDefinition]) -> u64 {
+        tools
+            .iter()
+            .map(|t| {
+                TOOL_OVERHEAD_TOKENS
+                    + self.count(&t.name)
+                    + self.count(&t.description)
+                    + self.count(&t.parameters.to_string())
+            })
+            .sum()
+    }
+
+    /// Estimated input tokens for a work order.
+    ///
+    /// Counts the conversation under `config.vendor["abp"]["conversation"]`
+    /// (or the task alone when there is none), the context snippets, and the
+    /// tool definitions under `config.vendor["abp"]["tools"]`.
+    #[must_use]
+    pub fn count_work_order(self, work_order: &WorkOrder) -> u64 {
+        let abp = work_order.config.vendor.get("abp");
+        let conversation = abp
+            .and_then(|v| v.get("conversation"))
+            .and_then(|v| serde_json::from_value::<IrConversation>(v.clone()).ok())
+            .unwrap_or_else(|| {
+                IrConversation::new().push(IrMessage::text(IrRole::User, work_order.task.clone()))
+            });
+        let tools = abp
+            .and_then(|v| v.get("tools"))
+            .and_then(|v| serde_json::from_value::<Vec<IrToolDefinition>>(v.clone()).ok())
+            .unwrap_or_default();
+        let snippets: u64 = work_order
+            .context
+            .snippets
+            .iter()
+            .map(|s| self.count(&s.name) + self.count(&s.content))
+            .sum();
+        self.count_conversation(&conversation) + snippets + self.count_tools(&tools)
+    }
+
+    fn count_blocks(self, blocks: &[IrContentBlock]) -> u64 {
+        blocks
+            .iter()
+            .map(|block| match block {
+                IrContentBlock::Text { text } | IrContentBlock::Thinking { text } => {
+                    self.count(text)
+                }
+                IrContentBlock::Image { .. } => IMAGE_TOKENS,
+                IrContentBlock::ToolUse { name, input, .. } => {
+                    self.count(name) + self.count(&input.to_string())
+                }
+                IrContentBlock::ToolResult { content, .. } => self.count_blocks(content),
+            })
+            .sum()
+    }
+}
+
+/// Estimated tokens for `text` at `chars_per_token` characters per token.
+///
+/// CJK characters are counted one token each.
+fn heuristic(text: &str, chars_per_token: f64) -> u64 {
+    let cjk = text.chars().filter(|&c| bpe::is_cjk(c)).count();
+    let other = text.chars().count() - cjk;
+    cjk as u64 + (other as f64 / chars_per_token).ceil() as u64
+}
+
+/// Estimated input tokens for a work order, counted for its model.
+///
+/// Work orders without a model are counted with
+/// [`TokenizerKind::Generic`].
+#[must_use]
+pub fn count_work_order(work_order: &WorkOrder) -> u64 {
+    let kind = work_order
+        .config
+        .model
+        .as_deref()
+        .map_or(TokenizerKind::Generic, TokenizerKind::for_model);
+    kind.count_work_order(work_order)
+}
+
+/// Count a work order's input tokens and record them on it.
+///
+/// Stores the count under
+/// `config.vendor["abp"]["projected_input_tokens"]` and returns it.
+pub fn project(work_order: &mut WorkOrder) -> u64 {
+    let tokens = count_work_order(work_order);
+    let abp = work_order
+        .config
+        .vendor
+        .entry("abp".to_string())
+        .or_insert_with(|| serde_json::json!({}));
+    if !abp.is_object() {
+        *abp = serde_json::json!({});
+    }
+    abp[PROJECTED_INPUT_TOKENS_KEY] = serde_json::json!(tokens);
+    tokens
+}
+
+/// The input token count recorded by [`project`], if any.
+#[must_use]
+pub fn projected_input_tokens(work_order: &WorkOrder) -> Option<u64> {
+    work_order
+        .config
+        .vendor
+        .get("abp")?
+        .get(PROJECTED_INPUT_TOKENS_KEY)?
+        .as_u64()
+}
@@ -0,0 +1,125 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+
+//! Tests for approximate token counting.
+
+use abp_core::WorkOrderBuilder;
+use abp_core::ir::{IrContentBlock, IrConversation, IrMessage, IrRole, IrToolDefinition};
+use abp_dialect::Dialect;
+use abp_tokenize::{
+    IMAGE_TOKENS, MESSAGE_OVERHEAD_TOKENS, REPLY_PRIMING_TOKENS, TokenizerKind, count_work_order,
+    project, projected_input_tokens,
+};
+use serde_json::json;
+
+#[test]
+fn bpe_counts_common_english_like_cl100k() {
+    let bpe = TokenizerKind::OpenAiBpe;
+    assert_eq!(bpe.count(""), 0);
+    assert_eq!(bpe.count("Hello, world!"), 4);
+    assert_eq!(
+        bpe.count("The quick brown fox jumps over the lazy dog."),
+        10
+    );
+    assert_eq!(bpe.count("don't"), 2);
+    assert_eq!(bpe.count("1234567"), 3);
+    let code = bpe.count("fn main() {\n    println!(\"hi\");\n}");
+    assert!((9..=13).contains(&code), "{code}");
+}
+
+#[test]
+fn long_and_non_latin_words_cost_more() {
+    let bpe = TokenizerKind::OpenAiBpe;
+    assert_eq!(bpe.count("internationalization"), 3);
+    assert_eq!(bpe.count("日本語"), 3);
+    assert!(TokenizerKind::Claude.count("你好世界") >= 4);
+}
+
+#[test]
+fn heuristics_scale_with_length() {
+    let text = "a".repeat(700);
+    assert_eq!(TokenizerKind::Claude.count(&text), 200);
+    assert_eq!(TokenizerKind::Gemini.count(&text), 175);
+    assert_eq!(TokenizerKind::Generic.count(&text), 175);
+}
+
+#[test]
+fn kind_follows_model_and_dialect() {
+    assert_eq!(TokenizerKind::for_model("gpt-4o"), TokenizerKind::OpenAiBpe);
+    assert_eq!(
+        TokenizerKind::for_model("openai/o3-mini"),
+        TokenizerKind::OpenAiBpe
+    );
+    assert_eq!(
+        TokenizerKind::for_model("claude-sonnet-4-20250514"),
+        TokenizerKind::Claude
+    );
+    assert_eq!(
+        TokenizerKind::for_model("gemini-2.5-pro"),
+        TokenizerKind::Gemini
+    );
+    assert_eq!(
+        TokenizerKind::for_model("moonshot-v1-8k"),
+        TokenizerKind::Generic
+    );
+    assert_eq!(
+        TokenizerKind::for_dialect(Dialect::Copilot),
+        TokenizerKind::OpenAiBpe
+    );
+    assert_eq!(
+        TokenizerKind::for_dialect(Dialect::Kimi),
+        TokenizerKind::Generic
+    );
+}
+
+#[test]
+fn conversations_include_framing_images_and_tools() {
+    let bpe = TokenizerKind::OpenAiBpe;
+    let conv = IrConversation::new()
+        .push(IrMessage::text(IrRole::System, "Be brief."))
+        .push(IrMessage::new(
+            IrRole::User,
+            vec![
+                IrContentBlock::Text {
+                    text: "What is this?".into(),
+                },
+                IrContentBlock::Image {
+                    media_type: "image/png".into(),
+                    data: "AAAA".into(),
+                },
+            ],
+        ));
+    assert_eq!(
+        bpe.count_conversation(&conv),
+        REPLY_PRIMING_TOKENS + 2 * MESSAGE_OVERHEAD_TOKENS + 3 + 4 + IMAGE_TOKENS
+    );
+
+    let tools = [IrToolDefinition {
+        name: "read_file".into(),
+        description: "Read a file".into(),
+        parameters: json!({"type": "object"}),
+    }];
+    assert!(bpe.count_tools(&tools) > 0);
+}
+
+#[test]
+fn work_orders_count_their_conversation_and_record_it() {
+    let wo = WorkOrderBuilder::new("Say hello").model("gpt-4o").build();
+    let task_only = count_work_order(&wo);
+    assert_eq!(
+        task_only,
+        REPLY_PRIMING_TOKENS + MESSAGE_OVERHEAD_TOKENS + 2
+    );
+
+    let conv = IrConversation::new()
+        .push(IrMessage::text(IrRole::System, "x ".repeat(100)))
+        .push(IrMessage::text(IrRole::User, "Say hello"));
+    let mut wo = WorkOrderBuilder::new("Say hello")
+        .model("gpt-4o")
+        .conversation(conv)
+        .build();
+    assert_eq!(projected_input_tokens(&wo), None);
+    let tokens = project(&mut wo);
+    assert!(tokens > task_only + 100);
+    assert_eq!(projected_input_tokens(&wo), Some(tokens));
+    assert!(wo.config.vendor["abp"]["conversation"].is_object());
+}
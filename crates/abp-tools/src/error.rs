@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Errors raised by built-in tool calls.
+
+/// A built-in tool call failed.
+///
+/// Every variant is reported back to the model as an `is_error` tool
+/// result, so the messages are written for it to act on.
+#[derive(Debug, thiserror::Error)]
+pub enum ToolError {
+    /// The suite has no tool of this name.
+    #[error("unknown tool '{0}'")]
+    UnknownTool(String),
+
+    /// The call's input is missing a field or has one of the wrong type.
+    #[error("invalid input for tool '{tool}': {message}")]
+    InvalidInput {
+        /// Tool that was called.
+        tool: String,
+        /// What is wrong with the input.
+        message: String,
+    },
+
+    /// The policy forbids the call.
+    #[error("denied by policy: {0}")]
+    Denied(String),
+
+    /// A file could not be read or written.
+    #[error("{path}: {source}")]
+    Io {
+        /// Path the tool was given.
+        path: String,
+        /// Underlying I/O error.
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// An edit could not be applied as asked.
+    #[error("{0}")]
+    Edit(String),
+
+    /// The shell could not be started.
+    #[error("failed to run command")]
+    Spawn(#[source] std::io::Error),
+
+    /// The command did not finish in time and was killed.
+    #[error("command timed out after {ms} ms")]
+    Timeout {
+        /// Time limit that was exceeded.
+        ms: u64,
+    },
+}
@@ -0,0 +1,12 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+#![doc = include_str!("../README.md")]
+#![deny(unsafe_code)]
+#![warn(missing_docs)]
+
+//! Built-in read/write/edit/bash tools run under policy for Agent Backplane.
+
+mod error;
+mod suite;
+
+pub use error::ToolError;
+pub use suite::{BASH, EDIT, READ, ToolOutput, ToolSuite, WRITE};
@@ -0,0 +1,543 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! The built-in tool suite.
+
+use std::io;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use abp_core::ir::IrToolDefinition;
+use abp_core::{
+    AgentEvent, AgentEventKind, Capability, CapabilityManifest, PolicyProfile, SupportLevel,
+};
+use abp_glob::IncludeExcludeGlobs;
+use abp_policy::{Decision, PolicyEngine};
+use abp_workspace::vfs::Vfs;
+use serde_json::{Value, json};
+
+use crate::ToolError;
+
+/// Name of the file-reading tool.
+pub const READ: &str = "Read";
+/// Name of the file-writing tool.
+pub const WRITE: &str = "Write";
+/// Name of the string-replacement tool.
+pub const EDIT: &str = "Edit";
+/// Name of the shell tool.
+pub const BASH: &str = "Bash";
+
+/// Characters of command output kept in a `CommandExecuted` event.
+const PREVIEW_CHARS: usize = 200;
+
+/// What a successful tool call produced.
+#[derive(Debug, Clone)]
+pub struct ToolOutput {
+    /// The tool result returned to the model.
+    pub output: Value,
+    /// Events describing the call's side effects, for the trace.
+    pub events: Vec<AgentEventKind>,
+}
+
+impl ToolOutput {
+    fn new(output: Value) -> Self {
+        Self {
+            output,
+            events: Vec::new(),
+        }
+    }
+
+    fn with_event(mut self, event: AgentEventKind) -> Self {
+        self.events.push(event);
+        self
+    }
+}
+
+/// Read, Write, Edit and Bash tools confined to one workspace and gated by
+/// a policy.
+#[derive(Debug, Clone)]
+pub struct ToolSuite {
+    vfs: Vfs,
+    policy: PolicyEngine,
+    command_patterns: Vec<String>,
+    commands: IncludeExcludeGlobs,
+    bash_timeout: Duration,
+    max_output: usize,
+}
+
+impl ToolSuite {
+    /// Time a command may run when the call does not set `timeout_ms`.
+    pub const DEFAULT_BASH_TIMEOUT: Duration = Duration::from_secs(120);
+
+    /// Bytes of stdout (and of stderr) returned from a command.
+    pub const DEFAULT_MAX_OUTPUT: usize = 30_000;
+
+    /// Tools working in `root` under `policy`.
+    ///
+    /// `disallowed_tools` entries of the form `Bash(<glob>)` are taken as
+    /// command patterns the shell tool refuses.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `root` does not exist or a policy pattern is
+    /// invalid.
+    pub fn new(root: impl AsRef<Path>, policy: &PolicyProfile) -> anyhow::Result<Self> {
+        let command_patterns: Vec<String> = policy
+            .disallowed_tools
+            .iter()
+            .filter_map(|t| command_pattern(t))
+            .collect();
+        Ok(Self {
+            vfs: Vfs::from_policy(root, policy)?,
+            policy: PolicyEngine::new(policy)?,
+            commands: IncludeExcludeGlobs::new(&[], &command_patterns)?,
+            command_patterns,
+            bash_timeout: Self::DEFAULT_BASH_TIMEOUT,
+            max_output: Self::DEFAULT_MAX_OUTPUT,
+        })
+    }
+
+    /// Also refuse commands matching `patterns` (builder pattern).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pattern is invalid.
+    pub fn deny_commands(mut self, patterns: &[String]) -> anyhow::Result<Self> {
+        self.command_patterns.extend_from_slice(patterns);
+        self.commands = IncludeExcludeGlobs::new(&[], &self.command_patterns)?;
+        Ok(self)
+    }
+
+    /// Time limit for commands whose call does not set one (builder
+    /// pattern).
+    #[must_use]
+    pub fn bash_timeout(mut self, timeout: Duration) -> Self {
+        self.bash_timeout = timeout;
+        self
+    }
+
+    /// Bytes of stdout and stderr kept from a command (builder pattern).
+    #[must_use]
+    pub fn max_output(mut self, bytes: usize) -> Self {
+        self.max_output = bytes;
+        self
+    }
+
+    /// The workspace the tools work in.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        self.vfs.root()
+    }
+
+    /// Whether `name` is one of the built-in tools.
+    #[must_use]
+    pub fn handles(name: &str) -> bool {
+        [READ, WRITE, EDIT, BASH].contains(&name)
+    }
+
+    /// Definitions of the built-in tools, to offer on a work order.
+    #[must_use]
+    pub fn definitions() -> Vec<IrToolDefinition> {
+        vec![
+            IrToolDefinition {
+                name: READ.into(),
+                description: "Read a UTF-8 file in the workspace. `offset` (1-based) and \
+                              `limit` select a range of lines."
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {"type": "string"},
+                        "offset": {"type": "integer", "minimum": 1},
+                        "limit": {"type": "integer", "minimum": 1}
+                    },
+                    "required": ["path"]
+                }),
+            },
+            IrToolDefinition {
+                name: WRITE.into(),
+                description: "Create or overwrite a file in the workspace.".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {"type": "string"},
+                        "content": {"type": "string"}
+                    },
+                    "required": ["path", "content"]
+                }),
+            },
+            IrToolDefinition {
+                name: EDIT.into(),
+                description: "Replace `old_string` with `new_string` in a file. \
+                              `old_string` must occur exactly once unless \
+                              `replace_all` is set."
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "path": {"type": "string"},
+                        "old_string": {"type": "string"},
+                        "new_string": {"type": "string"},
+                        "replace_all": {"type": "boolean"}
+                    },
+                    "required": ["path", "old_string", "new_string"]
+                }),
+            },
+            IrToolDefinition {
+                name: BASH.into(),
+                description: "Run a shell command in the workspace root and return its \
+                              exit code, stdout and stderr."
+                    .into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {
+                        "command": {"type": "string"},
+                        "timeout_ms": {"type": "integer", "minimum": 1}
+                    },
+                    "required": ["command"]
+                }),
+            },
+        ]
+    }
+
+    /// The capabilities the suite emulates for a backend.
+    #[must_use]
+    pub fn capabilities() -> CapabilityManifest {
+        [
+            Capability::ToolRead,
+            Capability::ToolWrite,
+            Capability::ToolEdit,
+            Capability::ToolBash,
+        ]
+        .into_iter()
+        .map(|c| (c, SupportLevel::Emulated))
+        .collect()
+    }
+
+    /// Whether the policy lets the shell tool run `command`.
+    ///
+    /// The command is checked whole and as each of the simple commands it
+    /// chains with `;`, `&&`, `||`, `|` or newlines. This is a denylist, not
+    /// a sandbox: a determined command can still get past it.
+    #[must_use]
+    pub fn can_run_command(&self, command: &str) -> Decision {
+        let command = command.trim();
+        let denied = std::iter::once(command)
+            .chain(simple_commands(command))
+            .find(|c| !self.commands.decide_str(c).is_allowed());
+        match denied {
+            Some(c) => Decision::deny(format!("command '{c}' is denied")),
+            None => Decision::allow(),
+        }
+    }
+
+    /// Run tool `name` with `input`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ToolError`] if the tool is unknown or denied, the input
+    /// is invalid, or the tool itself fails.
+    pub async fn execute(&self, name: &str, input: &Value) -> Result<ToolOutput, ToolError> {
+        if !Self::handles(name) {
+            return Err(ToolError::UnknownTool(name.to_string()));
+        }
+        let decision = self.policy.can_use_tool(name);
+        if !decision.allowed {
+            return Err(ToolError::Denied(
+                decision
+                    .reason
+                    .unwrap_or_else(|| format!("tool '{name}' is not allowed")),
+            ));
+        }
+        match name {
+            READ => self.read(input),
+            WRITE => self.write(input),
+            EDIT => self.edit(input),
+            _ => self.bash(input).await,
+        }
+    }
+
+    /// Run a `ToolCall` event addressed to the suite.
+    ///
+    /// Returns the events describing the call's side effects followed by
+    /// its `ToolResult`, or `None` if the event is not a call to a built-in
+    /// tool. Failures become `is_error` results.
+    pub async fn handle(&self, call: &AgentEvent) -> Option<Vec<AgentEvent>> {
+        let AgentEventKind::ToolCall {
+            tool_name,
+            tool_use_id,
+            input,
+            ..
+        } = &call.kind
+        else {
+            return None;
+        };
+        if !Self::handles(tool_name) {
+            return None;
+        }
+        let (output, is_error, side_effects) = match self.execute(tool_name, input).await {
+            Ok(out) => (out.output, false, out.events),
+            Err(e) => (json!({ "error": e.to_string() }), true, Vec::new()),
+        };
+        let result = AgentEventKind::ToolResult {
+            tool_name: tool_name.clone(),
+            tool_use_id: tool_use_id.clone(),
+            output,
+            is_error,
+        };
+        Some(
+            side_effects
+                .into_iter()
+                .chain([result])
+                .map(|kind| AgentEvent {
+                    ts: chrono::Utc::now(),
+                    kind,
+                    ext: None,
+                })
+                .collect(),
+        )
+    }
+
+    fn read(&self, input: &Value) -> Result<ToolOutput, ToolError> {
+        let path = string_field(READ, input, "path")?;
+        let offset = integer_field(READ, input, "offset")?.unwrap_or(1).max(1);
+        let limit = integer_field(READ, input, "limit")?;
+        let text = self
+            .vfs
+            .read_to_string(path)
+            .map_err(|source| io_error(path, source))?;
+        if offset == 1 && limit.is_none() {
+            return Ok(ToolOutput::new(Value::String(text)));
+        }
+        let lines = text
+            .split_inclusive('\n')
+            .skip(offset - 1)
+            .take(limit.unwrap_or(usize::MAX));
+        Ok(ToolOutput::new(Value::String(lines.collect())))
+    }
+
+    fn write(&self, input: &Value) -> Result<ToolOutput, ToolError> {
+        let path = string_field(WRITE, input, "path")?;
+        let content = string_field(WRITE, input, "content")?;
+        let existed = self.vfs.exists(path);
+        self.vfs
+            .write(path, content)
+            .map_err(|source| io_error(path, source))?;
+        let verb = if existed { "overwrote" } else { "created" };
+        Ok(
+            ToolOutput::new(json!(format!("wrote {} bytes to {path}", content.len()))).with_event(
+                AgentEventKind::FileChanged {
+                    path: self.display_path(path)?,
+                    summary: format!("{verb} ({} bytes)", content.len()),
+                },
+            ),
+        )
+    }
+
+    fn edit(&self, input: &Value) -> Result<ToolOutput, ToolError> {
+        let path = string_field(EDIT, input, "path")?;
+        let old = string_field(EDIT, input, "old_string")?;
+        let new = string_field(EDIT, input, "new_string")?;
+        let replace_all = input
+            .get("replace_all")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if old.is_empty() {
+            return Err(ToolError::Edit("old_string must not be empty".into()));
+        }
+        if old == new {
+            return Err(ToolError::Edit(
+                "old_string and new_string are identical".into(),
+            ));
+        }
+        let text = self
+            .vfs
+            .read_to_string(path)
+            .map_err(|source| io_error(path, source))?;
+        let count = text.matches(old).count();
+        if count == 0 {
+            return Err(ToolError::Edit(format!("old_string not found in {path}")));
+        }
+        if count > 1 && !replace_all {
+            return Err(ToolError::Edit(format!(
+                "old_string occurs {count} times in {path}; include more context or set \
+                 replace_all"
+            )));
+        }
+        let edited = if replace_all {
+            text.replace(old, new)
+        } else {
+            text.replacen(old, new, 1)
+        };
+        self.vfs
+            .write(path, &edited)
+            .map_err(|source| io_error(path, source))?;
+        let summary = if count == 1 {
+            "replaced 1 occurrence".to_string()
+        } else {
+            format!("replaced {count} occurrences")
+        };
+        Ok(
+            ToolOutput::new(json!(format!("{summary} in {path}"))).with_event(
+                AgentEventKind::FileChanged {
+                    path: self.display_path(path)?,
+                    summary,
+                },
+            ),
+        )
+    }
+
+    async fn bash(&self, input: &Value) -> Result<ToolOutput, ToolError> {
+        let command = string_field(BASH, input, "command")?;
+        let decision = self.can_run_command(command);
+        if !decision.allowed {
+            return Err(ToolError::Denied(decision.reason.unwrap_or_default()));
+        }
+        let timeout = integer_field(BASH, input, "timeout_ms")?
+            .map_or(self.bash_timeout, |ms| Duration::from_millis(ms as u64));
+
+        let mut shell = shell(command);
+        shell
+            .current_dir(self.root())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        let child = shell.spawn().map_err(ToolError::Spawn)?;
+        let output = tokio::time::timeout(timeout, child.wait_with_output())
+            .await
+            .map_err(|_| ToolError::Timeout {
+                ms: u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX),
+            })?
+            .map_err(ToolError::Spawn)?;
+
+        let stdout = truncate(&String::from_utf8_lossy(&output.stdout), self.max_output);
+        let stderr = truncate(&String::from_utf8_lossy(&output.stderr), self.max_output);
+        let exit_code = output.status.code();
+        let preview: String = if stdout.is_empty() { &stderr } else { &stdout }
+            .chars()
+            .take(PREVIEW_CHARS)
+            .collect();
+        Ok(ToolOutput::new(json!({
+            "exit_code": exit_code,
+            "stdout": stdout,
+            "stderr": stderr,
+        }))
+        .with_event(AgentEventKind::CommandExecuted {
+            command: command.to_string(),
+            exit_code,
+            output_preview: (!preview.is_empty()).then_some(preview),
+        }))
+    }
+
+    /// `path` relative to the workspace root, with `/` separators.
+    fn display_path(&self, path: &str) -> Result<String, ToolError> {
+        let rel = self
+            .vfs
+            .relative(path)
+            .map_err(|source| io_error(path, source))?;
+        Ok(rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"))
+    }
+}
+
+/// The command glob in a `Bash(<glob>)` tool rule.
+fn command_pattern(rule: &str) -> Option<String> {
+    rule.strip_prefix("Bash(")
+        .and_then(|r| r.strip_suffix(')'))
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+}
+
+/// The simple commands `command` chains together.
+fn simple_commands(command: &str) -> impl Iterator<Item = &str> {
+    command
+        .split(['\n', ';', '&', '|'])
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+}
+
+#[cfg(not(windows))]
+fn shell(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+/// `text` cut to at most `max` bytes on a character boundary, with a note
+/// of what was dropped.
+fn truncate(text: &str, max: usize) -> String {
+    if text.len() <= max {
+        return text.to_string();
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}\n[truncated {} bytes]", &text[..end], text.len() - end)
+}
+
+fn io_error(path: &str, source: io::Error) -> ToolError {
+    ToolError::Io {
+        path: path.to_string(),
+        source,
+    }
+}
+
+fn string_field<'a>(tool: &str, input: &'a Value, field: &str) -> Result<&'a str, ToolError> {
+    input
+        .get(field)
+        .and_then(Value::as_str)
+        .ok_or_else(|| ToolError::InvalidInput {
+            tool: tool.to_string(),
+            message: format!("'{field}' must be a string"),
+        })
+}
+
+fn integer_field(tool: &str, input: &Value, field: &str) -> Result<Option<usize>, ToolError> {
+    match input.get(field) {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v
+            .as_u64()
+            .and_then(|n| usize::try_from(n).ok())
+            .map(Some)
+            .ok_or_else(|| ToolError::InvalidInput {
+                tool: tool.to_string(),
+                message: format!("'{field}' must be a non-negative integer"),
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn command_patterns_come_from_bash_rules() {
+        assert_eq!(command_pattern("Bash(rm *)").as_deref(), Some("rm *"));
+        assert_eq!(command_pattern("Bash"), None);
+        assert_eq!(command_pattern("Bash()"), None);
+        assert_eq!(command_pattern("Read(*.rs)"), None);
+    }
+
+    #[test]
+    fn chained_commands_are_split() {
+        let parts: Vec<_> = simple_commands("ls && rm -rf x; echo hi | wc").collect();
+        assert_eq!(parts, ["ls", "rm -rf x", "echo hi", "wc"]);
+    }
+
+    #[test]
+    fn truncation_respects_char_boundaries() {
+        assert_eq!(truncate("héllo", 10), "héllo");
+        assert_eq!(truncate("héllo", 2), "h\n[truncated 5 bytes]");
+    }
+}
@@ -0,0 +1,190 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for the built-in tools and the policy they run under.
+
+use abp_core::{AgentEvent, AgentEventKind, PolicyProfile};
+use abp_tools::{ToolError, ToolSuite};
+use serde_json::{Value, json};
+
+fn suite(policy: &PolicyProfile) -> (tempfile::TempDir, ToolSuite) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "one\ntwo\nthree\n").unwrap();
+    std::fs::write(dir.path().join(".env"), "SECRET=1\n").unwrap();
+    let tools = ToolSuite::new(dir.path(), policy).unwrap();
+    (dir, tools)
+}
+
+fn call(tool: &str, input: Value) -> AgentEvent {
+    AgentEvent {
+        ts: chrono::Utc::now(),
+        kind: AgentEventKind::ToolCall {
+            tool_name: tool.into(),
+            tool_use_id: Some("t1".into()),
+            parent_tool_use_id: None,
+            input,
+        },
+        ext: None,
+    }
+}
+
+#[tokio::test]
+async fn read_selects_lines() {
+    let (_dir, tools) = suite(&PolicyProfile::default());
+    let out = tools
+        .execute(
+            "Read",
+            &json!({"path": "notes.txt", "offset": 2, "limit": 1}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(out.output, json!("two\n"));
+    assert!(out.events.is_empty());
+}
+
+#[tokio::test]
+async fn writes_and_edits_report_file_changes() {
+    let (dir, tools) = suite(&PolicyProfile::default());
+    let events = tools
+        .handle(&call(
+            "Write",
+            json!({"path": "src/lib.rs", "content": "fn a() {}\nfn a2() {}\n"}),
+        ))
+        .await
+        .unwrap();
+    assert!(matches!(
+        &events[0].kind,
+        AgentEventKind::FileChanged { path, summary }
+            if path == "src/lib.rs" && summary == "created (21 bytes)"
+    ));
+    assert!(matches!(
+        &events[1].kind,
+        AgentEventKind::ToolResult { is_error: false, tool_use_id: Some(id), .. } if id == "t1"
+    ));
+
+    let ambiguous = tools
+        .execute(
+            "Edit",
+            &json!({"path": "src/lib.rs", "old_string": "fn a", "new_string": "fn b"}),
+        )
+        .await
+        .unwrap_err();
+    assert!(ambiguous.to_string().contains("occurs 2 times"));
+
+    let out = tools
+        .execute(
+            "Edit",
+            &json!({
+                "path": "src/lib.rs",
+                "old_string": "fn a",
+                "new_string": "fn b",
+                "replace_all": true
+            }),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(
+        &out.events[0],
+        AgentEventKind::FileChanged { summary, .. } if summary == "replaced 2 occurrences"
+    ));
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("src/lib.rs")).unwrap(),
+        "fn b() {}\nfn b2() {}\n"
+    );
+}
+
+#[tokio::test]
+async fn paths_are_confined_and_policed() {
+    let policy = PolicyProfile {
+        deny_read: vec![".env".into()],
+        deny_write: vec!["notes.txt".into()],
+        ..PolicyProfile::default()
+    };
+    let (_dir, tools) = suite(&policy);
+    for (tool, input) in [
+        ("Read", json!({"path": ".env"})),
+        ("Read", json!({"path": "../outside"})),
+        ("Write", json!({"path": "notes.txt", "content": ""})),
+        (
+            "Edit",
+            json!({"path": "notes.txt", "old_string": "one", "new_string": "1"}),
+        ),
+    ] {
+        let err = tools.execute(tool, &input).await.unwrap_err();
+        assert!(matches!(err, ToolError::Io { .. }), "{tool} {input}: {err}");
+    }
+}
+
+#[tokio::test]
+async fn denied_tools_become_error_results() {
+    let policy = PolicyProfile {
+        disallowed_tools: vec!["Write".into()],
+        ..PolicyProfile::default()
+    };
+    let (dir, tools) = suite(&policy);
+    let events = tools
+        .handle(&call("Write", json!({"path": "x", "content": "x"})))
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    let AgentEventKind::ToolResult {
+        output, is_error, ..
+    } = &events[0].kind
+    else {
+        panic!("expected a tool result");
+    };
+    assert!(is_error);
+    assert!(
+        output["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("denied by policy")
+    );
+    assert!(!dir.path().join("x").exists());
+
+    assert!(tools.handle(&call("Search", json!({}))).await.is_none());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn bash_runs_in_the_workspace_and_honours_the_denylist() {
+    let policy = PolicyProfile {
+        disallowed_tools: vec!["Bash(rm *)".into()],
+        ..PolicyProfile::default()
+    };
+    let (_dir, tools) = suite(&policy);
+
+    let events = tools
+        .handle(&call("Bash", json!({"command": "cat notes.txt; exit 3"})))
+        .await
+        .unwrap();
+    assert!(matches!(
+        &events[0].kind,
+        AgentEventKind::CommandExecuted { exit_code: Some(3), output_preview: Some(p), .. }
+            if p == "one\ntwo\nthree\n"
+    ));
+    let AgentEventKind::ToolResult { output, .. } = &events[1].kind else {
+        panic!("expected a tool result");
+    };
+    assert_eq!(output["exit_code"], 3);
+
+    assert!(!tools.can_run_command("ls && rm -rf .").allowed);
+    let err = tools
+        .execute("Bash", &json!({"command": "rm notes.txt"}))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::Denied(_)));
+    assert!(tools.root().join("notes.txt").exists());
+
+    let tools = tools.deny_commands(&["curl *".into()]).unwrap();
+    assert!(!tools.can_run_command("curl https://example.com").allowed);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn slow_commands_time_out() {
+    let (_dir, tools) = suite(&PolicyProfile::default());
+    let err = tools
+        .execute("Bash", &json!({"command": "sleep 5", "timeout_ms": 50}))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ToolError::Timeout { ms: 50 }));
+}
@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Client side of the IPC transport.
+
+use std::path::Path;
+use std::time::Duration;
+
+use abp_core::{AgentEvent, Receipt, WorkOrder};
+use abp_host::SidecarHello;
+use abp_protocol::{Envelope, JsonlCodec, ProtocolError};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::{debug, warn};
+
+use crate::IpcError;
+
+type Reader = BufReader<Box<dyn AsyncRead + Send + Unpin>>;
+type Writer = Box<dyn AsyncWrite + Send + Unpin>;
+
+/// A connection to a sidecar daemon that has completed its `hello`
+/// handshake.
+///
+/// The daemon keeps running between runs; each connection carries a single
+/// run, like a spawned sidecar process.
+pub struct IpcClient {
+    /// Parsed hello handshake data.
+    pub hello: SidecarHello,
+    features_advertised: bool,
+    reader: Reader,
+    writer: Writer,
+}
+
+impl std::fmt::Debug for IpcClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IpcClient")
+            .field("hello", &self.hello)
+            .field("features_advertised", &self.features_advertised)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Handle to an in-progress run on a sidecar daemon.
+#[derive(Debug)]
+pub struct IpcRun {
+    /// Stream of events for the run.
+    pub events: ReceiverStream<AgentEvent>,
+
+    /// Final receipt for the run.
+    pub receipt: oneshot::Receiver<Result<Receipt, IpcError>>,
+}
+
+impl IpcClient {
+    /// Connect to the daemon listening at `path` and wait for its `hello`.
+    ///
+    /// `path` is a Unix domain socket path on Unix and a named pipe name
+    /// (e.g. `\\.\pipe\abp-sidecar`) on Windows. `timeout` bounds the
+    /// connection and the wait for `hello` separately.
+    ///
+    /// # Errors
+    ///
+    /// [`IpcError::Io`] when nothing is listening at `path`,
+    /// [`IpcError::Timeout`] when the daemon does not answer in time, and
+    /// [`IpcError::Protocol`] when the first line is not a hello or its contract
+    /// version is incompatible.
+    pub async fn connect(path: &Path, timeout: Duration) -> Result<Self, IpcError> {
+        let (reader, writer) = tokio::time::timeout(timeout, open(path))
+            .await
+            .map_err(|_| IpcError::Timeout { duration: timeout })??;
+        let mut reader = BufReader::new(reader);
+
+        let first = tokio::time::timeout(timeout, next_envelope(&mut reader))
+            .await
+            .map_err(|_| IpcError::Timeout { duration: timeout })??;
+
+        let (contract_version, backend, capabilities, advertised) = match first {
+            Envelope::Hello {
+                contract_version,
+                backend,
+                capabilities,
+                features,
+                ..
+            } => (contract_version, backend, capabilities, features),
+            other => {
+                return Err(IpcError::Protocol(ProtocolError::UnexpectedMessage {
+                    expected: "hello".into(),
+                    got: format!("{other:?}"),
+                }));
+            }
+        };
+
+        // Feature negotiation only bridges minor versions; a differing major
+        // or malformed version is rejected either way.
+        if let Some(err) = abp_core::compat::check_sidecar(&contract_version).to_error() {
+            return Err(IpcError::Protocol(err.into()));
+        }
+
+        debug!(
+            target: "abp.transport.ipc",
+            "sidecar hello: backend={} path={}",
+            backend.id,
+            path.display(),
+        );
+
+        Ok(Self {
+            hello: SidecarHello {
+                contract_version,
+                backend,
+                capabilities,
+            },
+            features_advertised: advertised.is_some(),
+            reader,
+            writer,
+        })
+    }
+
+    /// Whether the sidecar sent a feature-negotiation block in its hello.
+    #[must_use]
+    pub fn features_advertised(&self) -> bool {
+        self.features_advertised
+    }
+
+    /// Compare the sidecar's contract version with ours.
+    #[must_use]
+    pub fn compat(&self) -> abp_core::compat::CompatReport {
+        abp_core::compat::check_sidecar(&self.hello.contract_version)
+    }
+
+    /// Send a `run` and stream its events until `final` or `fatal`.
+    ///
+    /// Events for other run ids are dropped with a warning. A connection
+    /// that drops before `final` resolves the receipt with
+    /// [`IpcError::Closed`] or [`IpcError::Io`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the run cannot be sent.
+    pub async fn run(mut self, run_id: String, work_order: WorkOrder) -> Result<IpcRun, IpcError> {
+        let line = JsonlCodec::encode(&Envelope::Run {
+            id: run_id.clone(),
+            work_order,
+        })?;
+        self.writer.write_all(line.as_bytes()).await?;
+        self.writer.flush().await?;
+
+        let (events_tx, events_rx) = mpsc::channel(256);
+        let (receipt_tx, receipt_rx) = oneshot::channel();
+        let mut reader = self.reader;
+        let mut writer = self.writer;
+
+        tokio::spawn(async move {
+            let result = loop {
+                match next_envelope(&mut reader).await {
+                    Ok(Envelope::Event { ref_id, event, .. }) if ref_id == run_id => {
+                        let _ = events_tx.send(event).await;
+                    }
+                    Ok(Envelope::Final { ref_id, receipt }) if ref_id == run_id => {
+                        break Ok(receipt);
+                    }
+                    Ok(Envelope::Fatal {
+                        error, error_code, ..
+                    }) => break Err(IpcError::Fatal { error, error_code }),
+                    Ok(other) => {
+                        warn!(target: "abp.transport.ipc", "dropping unexpected envelope: {other:?}");
+                    }
+                    Err(e) => break Err(e),
+                }
+            };
+            let _ = writer.shutdown().await;
+            drop(events_tx);
+            let _ = receipt_tx.send(result);
+        });
+
+        Ok(IpcRun {
+            events: ReceiverStream::new(events_rx),
+            receipt: receipt_rx,
+        })
+    }
+}
+
+#[cfg(unix)]
+async fn open(path: &Path) -> std::io::Result<(Box<dyn AsyncRead + Send + Unpin>, Writer)> {
+    let stream = tokio::net::UnixStream::connect(path).await?;
+    let (reader, writer) = stream.into_split();
+    Ok((Box::new(reader), Box::new(writer)))
+}
+
+#[cfg(windows)]
+async fn open(path: &Path) -> std::io::Result<(Box<dyn AsyncRead + Send + Unpin>, Writer)> {
+    let pipe = tokio::net::windows::named_pipe::ClientOptions::new().open(path)?;
+    let (reader, writer) = tokio::io::split(pipe);
+    Ok((Box::new(reader), Box::new(writer)))
+}
+
+/// Read the next envelope, skipping blank lines.
+async fn next_envelope(reader: &mut Reader) -> Result<Envelope, IpcError> {
+    let mut buf = String::new();
+    loop {
+        buf.clear();
+        if reader.read_line(&mut buf).await? == 0 {
+            return Err(IpcError::Closed);
+        }
+        let line = buf.trim();
+        if !line.is_empty() {
+            return Ok(JsonlCodec::decode(line)?);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Errors from the local IPC transport.
+
+use std::time::Duration;
+
+use abp_error::{AbpError, ErrorCode};
+use abp_protocol::ProtocolError;
+use thiserror::Error;
+
+/// Errors from connecting to or talking with a sidecar daemon.
+#[derive(Debug, Error)]
+pub enum IpcError {
+    /// The socket or pipe could not be opened, read, or written.
+    #[error("ipc transport error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The sidecar closed the connection before completing the run.
+    #[error("sidecar closed the connection")]
+    Closed,
+
+    /// A line was not a valid envelope, or arrived out of order.
+    #[error("protocol error: {0}")]
+    Protocol(#[from] ProtocolError),
+
+    /// The sidecar reported a fatal error via a `fatal` envelope.
+    #[error("sidecar fatal error: {error}")]
+    Fatal {
+        /// Human-readable error description.
+        error: String,
+        /// Error code reported by the sidecar, if any.
+        error_code: Option<ErrorCode>,
+    },
+
+    /// The sidecar did not answer in time.
+    #[error("sidecar timed out after {duration:?}")]
+    Timeout {
+        /// How long we waited before timing out.
+        duration: Duration,
+    },
+}
+
+impl IpcError {
+    /// Whether reconnecting may help: the connection failed or dropped, as
+    /// opposed to the sidecar misbehaving or rejecting the run.
+    #[must_use]
+    pub fn is_connection_error(&self) -> bool {
+        matches!(self, Self::Io(_) | Self::Closed | Self::Timeout { .. })
+    }
+
+    /// The classified error code: connection drops are
+    /// [`BackendCrashed`](ErrorCode::BackendCrashed).
+    #[must_use]
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Io(_) | Self::Closed => ErrorCode::BackendCrashed,
+            Self::Timeout { .. } => ErrorCode::BackendTimeout,
+            Self::Protocol(e) => e.error_code().unwrap_or(ErrorCode::ProtocolInvalidEnvelope),
+            Self::Fatal { error_code, .. } => error_code.unwrap_or(ErrorCode::BackendCrashed),
+        }
+    }
+}
+
+impl From<IpcError> for AbpError {
+    fn from(err: IpcError) -> Self {
+        AbpError::new(err.error_code(), err.to_string()).with_source(err)
+    }
+}
@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! `IpcBackend` against an in-process Unix domain socket daemon.
+#![cfg(unix)]
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use abp_backend_core::Backend;
+use abp_core::{
+    AgentEvent, AgentEventKind, BackendIdentity, Capability, CapabilityManifest, Outcome,
+    SupportLevel, WorkOrderBuilder,
+};
+use abp_error::{AbpError, ErrorCode};
+use abp_host::retry::RetryConfig;
+use abp_protocol::{Envelope, JsonlCodec};
+use abp_receipt::ReceiptBuilder;
+use abp_transport_ipc::IpcBackend;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixListener;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Ok,
+    /// Drops the connection after one event.
+    DropMidRun,
+}
+
+fn delta(ref_id: &str, text: &str) -> Envelope {
+    Envelope::Event {
+        ref_id: ref_id.into(),
+        event: AgentEvent {
+            ts: chrono::Utc::now(),
+            kind: AgentEventKind::AssistantDelta { text: text.into() },
+            ext: None,
+        },
+        seq: None,
+    }
+}
+
+/// Runs a sidecar daemon on `path` that serves any number of connections.
+fn serve(path: &Path, mode: Mode) {
+    let listener = UnixListener::bind(path).unwrap();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                let send = |env: Envelope| JsonlCodec::encode(&env).unwrap();
+
+                let mut caps = CapabilityManifest::new();
+                caps.insert(Capability::Streaming, SupportLevel::Native);
+                let hello = Envelope::hello(
+                    BackendIdentity {
+                        id: "mock-ipc".into(),
+                        backend_version: None,
+                        adapter_version: None,
+                    },
+                    caps,
+                );
+                writer.write_all(send(hello).as_bytes()).await.unwrap();
+
+                let Ok(Some(line)) = lines.next_line().await else {
+                    return;
+                };
+                let Ok(Envelope::Run { id, work_order }) = JsonlCodec::decode(&line) else {
+                    return;
+                };
+
+                if matches!(mode, Mode::DropMidRun) {
+                    let event = send(delta(&id, "partial"));
+                    writer.write_all(event.as_bytes()).await.unwrap();
+                    return;
+                }
+                for text in ["hello ", "over a socket"] {
+                    let event = send(delta(&id, text));
+                    writer.write_all(event.as_bytes()).await.unwrap();
+                }
+                let receipt = ReceiptBuilder::new("mock-ipc")
+                    .work_order_id(work_order.id)
+                    .outcome(Outcome::Complete)
+                    .with_hash()
+                    .unwrap();
+                let done = send(Envelope::Final {
+                    ref_id: id,
+                    receipt,
+                });
+                writer.write_all(done.as_bytes()).await.unwrap();
+            });
+        }
+    });
+}
+
+fn socket_path(dir: &tempfile::TempDir) -> PathBuf {
+    dir.path().join("sidecar.sock")
+}
+
+fn quick_retries() -> RetryConfig {
+    RetryConfig {
+        max_retries: 5,
+        base_delay: Duration::from_millis(20),
+        max_delay: Duration::from_millis(100),
+        overall_timeout: Duration::from_secs(5),
+        jitter_factor: 0.0,
+    }
+}
+
+#[tokio::test]
+async fn a_long_lived_daemon_serves_consecutive_runs() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = socket_path(&dir);
+    serve(&path, Mode::Ok);
+    let backend = IpcBackend::new(&path);
+
+    for _ in 0..2 {
+        let wo = WorkOrderBuilder::new("say hello").build();
+        let (tx, mut rx) = mpsc::channel(16);
+        let receipt = backend.run(Uuid::new_v4(), wo.clone(), tx).await.unwrap();
+
+        let mut text = String::new();
+        while let Ok(ev) = rx.try_recv() {
+            if let AgentEventKind::AssistantDelta { text: t } = ev.kind {
+                text.push_str(&t);
+            }
+        }
+        assert_eq!(text, "hello over a socket");
+        assert_eq!(receipt.meta.work_order_id, wo.id);
+        assert!(abp_receipt::verify_hash(&receipt));
+    }
+}
+
+#[tokio::test]
+async fn dropped_connections_surface_as_backend_crashed() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = socket_path(&dir);
+    serve(&path, Mode::DropMidRun);
+    let (tx, mut rx) = mpsc::channel(16);
+
+    let err = IpcBackend::new(&path)
+        .run(Uuid::new_v4(), WorkOrderBuilder::new("x").build(), tx)
+        .await
+        .unwrap_err();
+
+    let abp = err.downcast_ref::<AbpError>().expect("classified error");
+    assert_eq!(abp.code, ErrorCode::BackendCrashed);
+    assert_eq!(abp.context["events_received"], 1);
+    assert!(rx.try_recv().is_ok());
+}
+
+#[tokio::test]
+async fn connects_once_the_daemon_starts_listening() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = socket_path(&dir);
+    let backend = IpcBackend::new(&path).with_reconnect(quick_retries());
+
+    let late = path.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        serve(&late, Mode::Ok);
+    });
+
+    let (tx, _rx) = mpsc::channel(16);
+    let receipt = backend
+        .run(Uuid::new_v4(), WorkOrderBuilder::new("x").build(), tx)
+        .await
+        .unwrap();
+    assert_eq!(receipt.outcome, Outcome::Complete);
+}
+
+#[tokio::test]
+async fn missing_daemons_fail_after_retries() {
+    let dir = tempfile::tempdir().unwrap();
+    let backend = IpcBackend::new(socket_path(&dir)).with_reconnect(RetryConfig {
+        max_retries: 1,
+        ..quick_retries()
+    });
+
+    let (tx, _rx) = mpsc::channel(16);
+    let err = backend
+        .run(Uuid::new_v4(), WorkOrderBuilder::new("x").build(), tx)
+        .await
+        .unwrap_err();
+
+    let abp = err.downcast_ref::<AbpError>().expect("classified error");
+    assert_eq!(abp.code, ErrorCode::BackendCrashed);
+    assert!(abp.context.contains_key("path"));
+}
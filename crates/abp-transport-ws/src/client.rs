@@ -0,0 +1,188 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Client side of the WebSocket transport.
+
+use std::time::Duration;
+
+use abp_core::{AgentEvent, Receipt, WorkOrder};
+use abp_host::SidecarHello;
+use abp_protocol::{Envelope, JsonlCodec, ProtocolError};
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, connect_async};
+use tracing::{debug, warn};
+
+use crate::WsError;
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// A connection to a remote backend that has completed its `hello`
+/// handshake.
+///
+/// Each connection carries a single run, like a sidecar process.
+#[derive(Debug)]
+pub struct WsClient {
+    /// Parsed hello handshake data.
+    pub hello: SidecarHello,
+    features_advertised: bool,
+    socket: Socket,
+}
+
+/// Handle to an in-progress run on a remote backend.
+#[derive(Debug)]
+pub struct WsRun {
+    /// Stream of events for the run.
+    pub events: ReceiverStream<AgentEvent>,
+
+    /// Final receipt for the run.
+    pub receipt: oneshot::Receiver<Result<Receipt, WsError>>,
+}
+
+impl WsClient {
+    /// Connect to `url` (e.g. `ws://agents.internal:7070/abp`) and wait for
+    /// the remote's `hello`.
+    ///
+    /// `timeout` bounds the connection and the wait for `hello` separately.
+    ///
+    /// # Errors
+    ///
+    /// [`WsError::Transport`] when the remote is unreachable,
+    /// [`WsError::Timeout`] when it does not answer in time, and
+    /// [`WsError::Protocol`] when the first message is not a hello or its contract
+    /// version is incompatible.
+    pub async fn connect(url: &str, timeout: Duration) -> Result<Self, WsError> {
+        let (mut socket, _) = tokio::time::timeout(timeout, connect_async(url))
+            .await
+            .map_err(|_| WsError::Timeout { duration: timeout })??;
+
+        let first = tokio::time::timeout(timeout, next_envelope(&mut socket))
+            .await
+            .map_err(|_| WsError::Timeout { duration: timeout })??;
+
+        let (contract_version, backend, capabilities, advertised) = match first {
+            Envelope::Hello {
+                contract_version,
+                backend,
+                capabilities,
+                features,
+                ..
+            } => (contract_version, backend, capabilities, features),
+            other => {
+                return Err(WsError::Protocol(ProtocolError::UnexpectedMessage {
+                    expected: "hello".into(),
+                    got: format!("{other:?}"),
+                }));
+            }
+        };
+
+        // Feature negotiation only bridges minor versions; a differing major
+        // or malformed version is rejected either way.
+        if let Some(err) = abp_core::compat::check_sidecar(&contract_version).to_error() {
+            return Err(WsError::Protocol(err.into()));
+        }
+
+        debug!(target: "abp.transport.ws", "remote hello: backend={} url={url}", backend.id);
+
+        Ok(Self {
+            hello: SidecarHello {
+                contract_version,
+                backend,
+                capabilities,
+            },
+            features_advertised: advertised.is_some(),
+            socket,
+        })
+    }
+
+    /// Whether the remote sent a feature-negotiation block in its hello.
+    #[must_use]
+    pub fn features_advertised(&self) -> bool {
+        self.features_advertised
+    }
+
+    /// Compare the remote's contract version with ours.
+    #[must_use]
+    pub fn compat(&self) -> abp_core::compat::CompatReport {
+        abp_core::compat::check_sidecar(&self.hello.contract_version)
+    }
+
+    /// Send a `run` and stream its events until `final` or `fatal`.
+    ///
+    /// Events for other run ids are dropped with a warning. A connection
+    /// that drops before `final` resolves the receipt with
+    /// [`WsError::Closed`] or [`WsError::Transport`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the run cannot be sent.
+    pub async fn run(mut self, run_id: String, work_order: WorkOrder) -> Result<WsRun, WsError> {
+        let line = JsonlCodec::encode(&Envelope::Run {
+            id: run_id.clone(),
+            work_order,
+        })?;
+        self.socket
+            .send(Message::text(line.trim_end().to_string()))
+            .await?;
+
+        let (events_tx, events_rx) = mpsc::channel(256);
+        let (receipt_tx, receipt_rx) = oneshot::channel();
+        let mut socket = self.socket;
+
+        tokio::spawn(async move {
+            let result = loop {
+                match next_envelope(&mut socket).await {
+                    Ok(Envelope::Event { ref_id, event, .. }) if ref_id == run_id => {
+                        let _ = events_tx.send(event).await;
+                    }
+                    Ok(Envelope::Final { ref_id, receipt }) if ref_id == run_id => {
+                        break Ok(receipt);
+                    }
+                    Ok(Envelope::Fatal {
+                        error, error_code, ..
+                    }) => break Err(WsError::Fatal { error, error_code }),
+                    Ok(other) => {
+                        warn!(target: "abp.transport.ws", "dropping unexpected envelope: {other:?}");
+                    }
+                    Err(e) => break Err(e),
+                }
+            };
+            if result.is_ok() {
+                let _ = socket.close(None).await;
+            }
+            drop(events_tx);
+            let _ = receipt_tx.send(result);
+        });
+
+        Ok(WsRun {
+            events: ReceiverStream::new(events_rx),
+            receipt: receipt_rx,
+        })
+    }
+}
+
+/// Read the next envelope, skipping control frames.
+async fn next_envelope(socket: &mut Socket) -> Result<Envelope, WsError> {
+    loop {
+        let text = match socket.next().await {
+            Some(Ok(Message::Text(text))) => text.to_string(),
+            Some(Ok(Message::Binary(bytes))) => String::from_utf8(bytes.to_vec())
+                .map_err(|e| ProtocolError::Violation(format!("binary frame is not UTF-8: {e}")))?,
+            Some(Ok(Message::Close(frame))) => {
+                return Err(WsError::Closed {
+                    reason: frame
+                        .map(|f| f.reason.to_string())
+                        .filter(|r| !r.is_empty()),
+                });
+            }
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return Err(e.into()),
+            None => return Err(WsError::Closed { reason: None }),
+        };
+        if text.trim().is_empty() {
+            continue;
+        }
+        return Ok(JsonlCodec::decode(text.trim())?);
+    }
+}
@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Errors from the WebSocket transport.
+
+use std::time::Duration;
+
+use abp_error::{AbpError, ErrorCode};
+use abp_protocol::ProtocolError;
+use thiserror::Error;
+use tokio_tungstenite::tungstenite;
+
+/// Errors from connecting to or talking with a remote backend.
+#[derive(Debug, Error)]
+pub enum WsError {
+    /// The connection could not be established or failed mid-stream.
+    #[error("websocket transport error: {0}")]
+    Transport(Box<tungstenite::Error>),
+
+    /// The remote closed the connection before completing the run.
+    #[error("remote backend closed the connection{}", reason.as_deref().map(|r| format!(": {r}")).unwrap_or_default())]
+    Closed {
+        /// Close reason sent by the remote, if any.
+        reason: Option<String>,
+    },
+
+    /// A frame was not a valid envelope, or arrived out of order.
+    #[error("protocol error: {0}")]
+    Protocol(#[from] ProtocolError),
+
+    /// The remote reported a fatal error via a `fatal` envelope.
+    #[error("remote backend fatal error: {error}")]
+    Fatal {
+        /// Human-readable error description.
+        error: String,
+        /// Error code reported by the remote, if any.
+        error_code: Option<ErrorCode>,
+    },
+
+    /// The remote did not answer in time.
+    #[error("remote backend timed out after {duration:?}")]
+    Timeout {
+        /// How long we waited before timing out.
+        duration: Duration,
+    },
+}
+
+impl From<tungstenite::Error> for WsError {
+    fn from(err: tungstenite::Error) -> Self {
+        Self::Transport(Box::new(err))
+    }
+}
+
+impl WsError {
+    /// Whether reconnecting may help: the connection failed or dropped, as
+    /// opposed to the remote misbehaving or rejecting the run.
+    #[must_use]
+    pub fn is_connection_error(&self) -> bool {
+        matches!(
+            self,
+            Self::Transport(_) | Self::Closed { .. } | Self::Timeout { .. }
+        )
+    }
+
+    /// The classified error code: connection drops are
+    /// [`BackendCrashed`](ErrorCode::BackendCrashed).
+    #[must_use]
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::Transport(_) | Self::Closed { .. } => ErrorCode::BackendCrashed,
+            Self::Timeout { .. } => ErrorCode::BackendTimeout,
+            Self::Protocol(e) => e.error_code().unwrap_or(ErrorCode::ProtocolInvalidEnvelope),
+            Self::Fatal { error_code, .. } => error_code.unwrap_or(ErrorCode::BackendCrashed),
+        }
+    }
+}
+
+impl From<WsError> for AbpError {
+    fn from(err: WsError) -> Self {
+        AbpError::new(err.error_code(), err.to_string()).with_source(err)
+    }
+}
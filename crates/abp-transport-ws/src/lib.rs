@@ -0,0 +1,162 @@
+#![doc = include_str!("../README.md")]
+#![deny(unsafe_code)]
+#![warn(missing_docs)]
+//! WebSocket transport for remote backends.
+
+pub mod client;
+pub mod error;
+
+pub use client::{WsClient, WsRun};
+pub use error::WsError;
+
+use std::time::Duration;
+
+use abp_backend_core::{Backend, ensure_capability_requirements};
+use abp_core::{AgentEvent, BackendIdentity, CapabilityManifest, Receipt, WorkOrder};
+use abp_error::AbpError;
+use abp_host::retry::{RetryConfig, compute_delay};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio_stream::StreamExt;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// Default bound on connecting to a remote backend and receiving its `hello`.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Backend running on another host, reached over a WebSocket.
+///
+/// The remote speaks the JSONL sidecar protocol with one envelope per text
+/// frame: it sends `hello` on accept, receives one `run`, and answers with
+/// `event` frames followed by `final` or `fatal`.
+///
+/// Failing to connect or losing the connection during the handshake is
+/// retried according to [`reconnect`](Self::reconnect). Once the `run` has
+/// been sent the run is never replayed; a dropped connection fails it with
+/// [`ErrorCode::BackendCrashed`](abp_error::ErrorCode::BackendCrashed).
+#[derive(Debug, Clone)]
+pub struct WsBackend {
+    /// Remote URL, e.g. `ws://agents.internal:7070/abp`.
+    pub url: String,
+    /// Bound on connecting and receiving the `hello`, per attempt.
+    pub connect_timeout: Duration,
+    /// Backoff policy for connection attempts.
+    pub reconnect: RetryConfig,
+}
+
+impl WsBackend {
+    /// Creates a WebSocket backend for the remote at `url`.
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            reconnect: RetryConfig::default(),
+        }
+    }
+
+    /// Sets the per-attempt connect-and-hello timeout.
+    #[must_use]
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Sets the backoff policy for connection attempts.
+    #[must_use]
+    pub fn with_reconnect(mut self, reconnect: RetryConfig) -> Self {
+        self.reconnect = reconnect;
+        self
+    }
+
+    /// Connects and completes the handshake, retrying connection errors.
+    ///
+    /// Returns the client and the number of reconnects it took.
+    async fn connect(&self) -> Result<(WsClient, u32), WsError> {
+        let started = Instant::now();
+        let mut attempt = 0;
+        loop {
+            match WsClient::connect(&self.url, self.connect_timeout).await {
+                Ok(client) => return Ok((client, attempt)),
+                Err(e) if e.is_connection_error() && attempt < self.reconnect.max_retries => {
+                    let delay = compute_delay(&self.reconnect, attempt);
+                    if started.elapsed() + delay > self.reconnect.overall_timeout {
+                        return Err(e);
+                    }
+                    warn!(
+                        target: "abp.transport.ws",
+                        "connect to {} failed (attempt {}): {e}; retrying in {delay:?}",
+                        self.url,
+                        attempt + 1,
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for WsBackend {
+    fn identity(&self) -> BackendIdentity {
+        BackendIdentity {
+            id: "websocket".to_string(),
+            backend_version: None,
+            adapter_version: Some("0.1".to_string()),
+        }
+    }
+
+    fn capabilities(&self) -> CapabilityManifest {
+        CapabilityManifest::default()
+    }
+
+    async fn run(
+        &self,
+        run_id: Uuid,
+        work_order: WorkOrder,
+        events_tx: mpsc::Sender<AgentEvent>,
+    ) -> Result<Receipt> {
+        let (client, reconnects) = self.connect().await.map_err(|e| {
+            anyhow::Error::new(
+                AbpError::from(e)
+                    .with_context("url", &self.url)
+                    .with_context("run_id", run_id.to_string()),
+            )
+        })?;
+
+        ensure_capability_requirements(&work_order.requirements, &client.hello.capabilities)
+            .context("capability requirements not satisfied")?;
+
+        debug!(
+            target: "abp.transport.ws",
+            "connected to remote backend={} url={} reconnects={reconnects}",
+            client.hello.backend.id,
+            self.url,
+        );
+
+        let mut run = client
+            .run(run_id.to_string(), work_order)
+            .await
+            .context("start run")?;
+
+        let mut events_received = 0u64;
+        while let Some(ev) = run.events.next().await {
+            events_received += 1;
+            let _ = events_tx.send(ev).await;
+        }
+
+        match run.receipt.await.context("receive receipt")? {
+            Ok(receipt) => Ok(receipt),
+            Err(e) => Err(anyhow::Error::new(
+                AbpError::from(e)
+                    .with_context("url", &self.url)
+                    .with_context("run_id", run_id.to_string())
+                    .with_context("events_received", events_received)
+                    .with_context("reconnects", reconnects),
+            )),
+        }
+    }
+}
@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! `WsBackend` against an in-process WebSocket server.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+use abp_backend_core::Backend;
+use abp_core::{
+    AgentEvent, AgentEventKind, BackendIdentity, Capability, CapabilityManifest, Outcome,
+    SupportLevel, WorkOrderBuilder,
+};
+use abp_error::{AbpError, ErrorCode};
+use abp_host::retry::RetryConfig;
+use abp_protocol::{Envelope, JsonlCodec};
+use abp_receipt::ReceiptBuilder;
+use abp_transport_ws::WsBackend;
+use futures::{SinkExt, StreamExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+use uuid::Uuid;
+
+#[derive(Clone, Copy)]
+enum Mode {
+    Ok,
+    /// Drops the connection after one event.
+    DropMidRun,
+    /// Closes the first connection before `hello`, then behaves like `Ok`.
+    FlakyHandshake,
+    /// Announces an incompatible contract version.
+    FutureVersion,
+}
+
+fn frame(env: &Envelope) -> Message {
+    Message::text(JsonlCodec::encode(env).unwrap().trim_end().to_string())
+}
+
+fn delta(ref_id: &str, text: &str) -> Envelope {
+    Envelope::Event {
+        ref_id: ref_id.into(),
+        event: AgentEvent {
+            ts: chrono::Utc::now(),
+            kind: AgentEventKind::AssistantDelta { text: text.into() },
+            ext: None,
+        },
+        seq: None,
+    }
+}
+
+/// Serves remote-backend sessions; returns the URL and a connection counter.
+async fn serve(mode: Mode) -> (String, Arc<AtomicU32>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicU32::new(0));
+    let counter = Arc::clone(&connections);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let n = counter.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(async move {
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                if matches!(mode, Mode::FlakyHandshake) && n == 0 {
+                    let _ = ws.close(None).await;
+                    return;
+                }
+
+                let mut caps = CapabilityManifest::new();
+                caps.insert(Capability::Streaming, SupportLevel::Native);
+                let mut hello = Envelope::hello(
+                    BackendIdentity {
+                        id: "mock-ws".into(),
+                        backend_version: None,
+                        adapter_version: None,
+                    },
+                    caps,
+                );
+                if matches!(mode, Mode::FutureVersion)
+                    && let Envelope::Hello {
+                        contract_version, ..
+                    } = &mut hello
+                {
+                    *contract_version = "abp/v9.0".into();
+                }
+                ws.send(frame(&hello)).await.unwrap();
+
+                let Some(Ok(Message::Text(text))) = ws.next().await else {
+                    return;
+                };
+                let Ok(Envelope::Run { id, work_order }) = JsonlCodec::decode(&text) else {
+                    return;
+                };
+
+                if matches!(mode, Mode::DropMidRun) {
+                    ws.send(frame(&delta(&id, "partial"))).await.unwrap();
+                    return;
+                }
+                for text in ["hello ", "from afar"] {
+                    ws.send(frame(&delta(&id, text))).await.unwrap();
+                }
+                let receipt = ReceiptBuilder::new("mock-ws")
+                    .work_order_id(work_order.id)
+                    .outcome(Outcome::Complete)
+                    .with_hash()
+                    .unwrap();
+                ws.send(frame(&Envelope::Final {
+                    ref_id: id,
+                    receipt,
+                }))
+                .await
+                .unwrap();
+                let _ = ws.next().await;
+            });
+        }
+    });
+    (url, connections)
+}
+
+fn quick_retries() -> RetryConfig {
+    RetryConfig {
+        max_retries: 3,
+        base_delay: Duration::from_millis(10),
+        max_delay: Duration::from_millis(50),
+        overall_timeout: Duration::from_secs(5),
+        jitter_factor: 0.0,
+    }
+}
+
+#[tokio::test]
+async fn runs_stream_events_and_return_the_remote_receipt() {
+    let (url, _) = serve(Mode::Ok).await;
+    let backend = WsBackend::new(url);
+    let wo = WorkOrderBuilder::new("say hello").build();
+    let (tx, mut rx) = mpsc::channel(16);
+
+    let receipt = backend.run(Uuid::new_v4(), wo.clone(), tx).await.unwrap();
+
+    let mut text = String::new();
+    while let Ok(ev) = rx.try_recv() {
+        if let AgentEventKind::AssistantDelta { text: t } = ev.kind {
+            text.push_str(&t);
+        }
+    }
+    assert_eq!(text, "hello from afar");
+    assert_eq!(receipt.meta.work_order_id, wo.id);
+    assert!(abp_receipt::verify_hash(&receipt));
+}
+
+#[tokio::test]
+async fn dropped_connections_surface_as_backend_crashed() {
+    let (url, connections) = serve(Mode::DropMidRun).await;
+    let backend = WsBackend::new(url).with_reconnect(quick_retries());
+    let (tx, mut rx) = mpsc::channel(16);
+
+    let err = backend
+        .run(Uuid::new_v4(), WorkOrderBuilder::new("x").build(), tx)
+        .await
+        .unwrap_err();
+
+    let abp = err.downcast_ref::<AbpError>().expect("classified error");
+    assert_eq!(abp.code, ErrorCode::BackendCrashed);
+    assert_eq!(abp.context["events_received"], 1);
+    assert!(rx.try_recv().is_ok());
+    assert_eq!(
+        connections.load(Ordering::SeqCst),
+        1,
+        "runs are not replayed"
+    );
+}
+
+#[tokio::test]
+async fn handshake_drops_are_retried() {
+    let (url, connections) = serve(Mode::FlakyHandshake).await;
+    let backend = WsBackend::new(url).with_reconnect(quick_retries());
+    let (tx, _rx) = mpsc::channel(16);
+
+    let receipt = backend
+        .run(Uuid::new_v4(), WorkOrderBuilder::new("x").build(), tx)
+        .await
+        .unwrap();
+
+    assert_eq!(receipt.outcome, Outcome::Complete);
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn incompatible_contract_versions_are_rejected() {
+    let (url, _) = serve(Mode::FutureVersion).await;
+    let backend = WsBackend::new(url);
+    let (tx, _rx) = mpsc::channel(16);
+
+    let err = backend
+        .run(Uuid::new_v4(), WorkOrderBuilder::new("x").build(), tx)
+        .await
+        .unwrap_err();
+
+    let abp = err.downcast_ref::<AbpError>().expect("classified error");
+    assert_eq!(abp.code, ErrorCode::ProtocolVersionMismatch);
+}
+
+#[tokio::test]
+async fn unreachable_remotes_fail_after_retries() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let backend = WsBackend::new(url).with_reconnect(quick_retries());
+    let (tx, _rx) = mpsc::channel(16);
+    let err = backend
+        .run(Uuid::new_v4(), WorkOrderBuilder::new("x").build(), tx)
+        .await
+        .unwrap_err();
+
+    let abp = err.downcast_ref::<AbpError>().expect("classified error");
+    assert_eq!(abp.code, ErrorCode::BackendCrashed);
+}
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: MIT OR Apache-2.0
 //! Protocol envelope validation.
 
-use abp_core::CONTRACT_VERSION;
+use abp_core::compat;
 use abp_protocol::Envelope;
 
 use crate::{ValidationErrorKind, ValidationErrors, Validator};
@@ -183,25 +183,13 @@ pub fn validate_hello_version(envelope: &Envelope) -> Result<(), ValidationError
         contract_version, ..
     } = envelope
     {
-        if contract_version != CONTRACT_VERSION {
-            // Allow same-major compat check
-            let ours = CONTRACT_VERSION
-                .strip_prefix("abp/v")
-                .and_then(|r| r.split_once('.'))
-                .map(|(m, _)| m);
-            let theirs = contract_version
-                .strip_prefix("abp/v")
-                .and_then(|r| r.split_once('.'))
-                .map(|(m, _)| m);
-            if ours != theirs {
-                errs.add(
-                    "contract_version",
-                    ValidationErrorKind::InvalidReference,
-                    format!(
-                        "incompatible contract version '{contract_version}', expected '{CONTRACT_VERSION}'"
-                    ),
-                );
-            }
+        let report = compat::check_sidecar(contract_version);
+        if !report.is_compatible() {
+            errs.add(
+                "contract_version",
+                ValidationErrorKind::InvalidReference,
+                report.to_string(),
+            );
         }
     }
 
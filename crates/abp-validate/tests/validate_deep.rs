@@ -1127,6 +1127,7 @@ fn edge_hello_version_same_major_different_minor_passes() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     // Same major (0), different minor — should pass
     assert!(validate_hello_version(&env).is_ok());
@@ -1143,6 +1144,7 @@ fn edge_hello_version_different_major_fails() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = validate_hello_version(&env).unwrap_err();
     assert!(
@@ -1162,6 +1164,7 @@ fn edge_hello_version_exact_match_passes() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     assert!(validate_hello_version(&env).is_ok());
 }
@@ -604,6 +604,7 @@ fn envelope_hello_empty_contract_version_fails() {
         backend: backend("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert_has_path(&err, "contract_version");
@@ -616,6 +617,7 @@ fn envelope_hello_bad_version_prefix_fails() {
         backend: backend("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert_has_path(&err, "contract_version");
@@ -629,6 +631,7 @@ fn envelope_hello_empty_backend_id_fails() {
         backend: backend(""),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert_has_path(&err, "backend.id");
@@ -671,6 +674,7 @@ fn envelope_event_empty_ref_id_fails() {
     let env = Envelope::Event {
         ref_id: "".into(),
         event: ev(AgentEventKind::AssistantMessage { text: "hi".into() }),
+        seq: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert_has_path(&err, "ref_id");
@@ -681,6 +685,7 @@ fn envelope_event_valid_passes() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event: ev(AgentEventKind::AssistantMessage { text: "hi".into() }),
+        seq: None,
     };
     assert!(EnvelopeValidator.validate(&env).is_ok());
 }
@@ -730,6 +735,7 @@ fn hello_version_incompatible_major_fails() {
         backend: backend("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = validate_hello_version(&env).unwrap_err();
     assert_has_kind(&err, &ValidationErrorKind::InvalidReference);
@@ -1247,6 +1253,7 @@ fn batch_validate_envelopes() {
         Envelope::Event {
             ref_id: "run-1".into(),
             event: ev(AgentEventKind::AssistantMessage { text: "hi".into() }),
+            seq: None,
         },
     ];
     let results: Vec<_> = envelopes
@@ -864,6 +864,7 @@ fn envelope_validator_valid_hello() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     assert!(EnvelopeValidator.validate(&env).is_ok());
 }
@@ -879,6 +880,7 @@ fn envelope_validator_empty_contract_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     assert!(EnvelopeValidator.validate(&env).is_err());
 }
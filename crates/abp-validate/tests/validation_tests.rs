@@ -482,6 +482,7 @@ fn hello_empty_backend_id_fails() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert!(err.iter().any(|e| e.path == "backend.id"));
@@ -498,6 +499,7 @@ fn hello_invalid_contract_version_fails() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert!(err.iter().any(|e| e.path == "contract_version"));
@@ -530,6 +532,7 @@ fn valid_event_envelope_passes() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event: make_event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+        seq: None,
     };
     assert!(EnvelopeValidator.validate(&env).is_ok());
 }
@@ -539,6 +542,7 @@ fn event_envelope_empty_ref_id_fails() {
     let env = Envelope::Event {
         ref_id: "".into(),
         event: make_event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+        seq: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert!(err.iter().any(|e| e.path == "ref_id"));
@@ -678,6 +682,7 @@ fn hello_version_incompatible_fails() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = validate_hello_version(&env).unwrap_err();
     assert!(
@@ -0,0 +1,336 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tracking and cleanup of staged workspace directories.
+//!
+//! Staged workspaces live in temporary directories that are removed when the
+//! [`PreparedWorkspace`](crate::PreparedWorkspace) is dropped — but a crashed
+//! host never drops them. [`WorkspaceJanitor`] makes every staging directory
+//! discoverable: it is named with [`STAGING_PREFIX`] and paired with an owner
+//! record (`<dir>.owner.json`) naming the creating process. At startup,
+//! [`WorkspaceJanitor::sweep`] removes directories whose owner is gone.
+//!
+//! A directory is considered orphaned when:
+//!
+//! - its owner process is known to have exited (checked via `/proc` on Linux);
+//! - or its owner cannot be checked (other platforms, or no owner record) and
+//!   it is older than the janitor's grace period.
+//!
+//! Directories owned by the current process are never orphaned.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+use tracing::{debug, warn};
+use walkdir::WalkDir;
+
+/// Name prefix of every staging directory created by the janitor.
+pub const STAGING_PREFIX: &str = "abp-ws-";
+
+/// Suffix of the owner record written next to each staging directory.
+const OWNER_SUFFIX: &str = ".owner.json";
+
+/// Ownership metadata recorded for a staging directory.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StagingOwner {
+    /// Process id of the host that created the directory.
+    pub pid: u32,
+    /// When the directory was created.
+    pub created_at: DateTime<Utc>,
+    /// Workspace the directory was staged from, if known.
+    pub source_root: Option<PathBuf>,
+}
+
+impl StagingOwner {
+    /// Owner record for the current process.
+    #[must_use]
+    pub fn current(source_root: Option<&Path>) -> Self {
+        Self {
+            pid: std::process::id(),
+            created_at: Utc::now(),
+            source_root: source_root.map(Path::to_path_buf),
+        }
+    }
+}
+
+/// A staging directory found by [`WorkspaceJanitor::scan`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StagingDir {
+    /// Path of the directory.
+    pub path: PathBuf,
+    /// Owner record, if one could be read.
+    pub owner: Option<StagingOwner>,
+    /// Total size of the files inside, in bytes.
+    pub size_bytes: u64,
+    /// Whether the janitor would remove this directory.
+    pub orphaned: bool,
+}
+
+/// Disk usage of all staging directories under a janitor's root.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StagingUsage {
+    /// Number of staging directories.
+    pub dirs: usize,
+    /// Total size of all staging directories, in bytes.
+    pub total_bytes: u64,
+    /// Number of orphaned staging directories.
+    pub orphaned_dirs: usize,
+    /// Total size of orphaned staging directories, in bytes.
+    pub orphaned_bytes: u64,
+}
+
+/// Summary returned by [`WorkspaceJanitor::sweep`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SweepReport {
+    /// Directories that were removed.
+    pub removed: Vec<PathBuf>,
+    /// Bytes freed by the removals.
+    pub bytes_reclaimed: u64,
+    /// Directories that could not be removed, with error descriptions.
+    pub errors: Vec<(PathBuf, String)>,
+}
+
+/// A tracked staging directory, removed together with its owner record when
+/// dropped.
+#[derive(Debug)]
+pub struct StagingGuard {
+    dir: TempDir,
+    _owner: OwnerRecord,
+}
+
+/// Removes an owner record when dropped.
+#[derive(Debug)]
+struct OwnerRecord(PathBuf);
+
+impl Drop for OwnerRecord {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+impl StagingGuard {
+    /// Path of the staging directory.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Remove the directory and its owner record now, reporting errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be removed.
+    pub fn close(self) -> Result<()> {
+        self.dir
+            .close()
+            .context("remove temporary workspace directory")
+    }
+}
+
+/// Creates, tracks, and cleans up staging directories under one root.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use abp_workspace::janitor::WorkspaceJanitor;
+/// // At host startup: reclaim directories left behind by crashed runs.
+/// let report = WorkspaceJanitor::default().sweep().unwrap();
+/// println!("reclaimed {} bytes", report.bytes_reclaimed);
+/// ```
+#[derive(Clone, Debug)]
+pub struct WorkspaceJanitor {
+    root: PathBuf,
+    grace: Duration,
+}
+
+impl Default for WorkspaceJanitor {
+    /// A janitor over the system temp directory with a 24 hour grace period.
+    fn default() -> Self {
+        Self::new(std::env::temp_dir())
+    }
+}
+
+impl WorkspaceJanitor {
+    /// Create a janitor for staging directories under `root`.
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            grace: Duration::hours(24),
+        }
+    }
+
+    /// Set how old a directory whose owner cannot be checked must be before
+    /// it is treated as orphaned.
+    #[must_use]
+    pub fn with_grace(mut self, grace: Duration) -> Self {
+        self.grace = grace;
+        self
+    }
+
+    /// Directory under which staging directories are created.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Create a tracked staging directory for a workspace copied from
+    /// `source_root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory or its owner record cannot be
+    /// created.
+    pub fn create(&self, source_root: Option<&Path>) -> Result<StagingGuard> {
+        fs::create_dir_all(&self.root)
+            .with_context(|| format!("create staging root {}", self.root.display()))?;
+        let dir = tempfile::Builder::new()
+            .prefix(STAGING_PREFIX)
+            .tempdir_in(&self.root)
+            .context("create temp dir")?;
+        let owner_path = owner_path(dir.path());
+        let owner = StagingOwner::current(source_root);
+        let json = serde_json::to_vec(&owner).context("serialize staging owner")?;
+        fs::write(&owner_path, json)
+            .with_context(|| format!("write staging owner {}", owner_path.display()))?;
+        Ok(StagingGuard {
+            dir,
+            _owner: OwnerRecord(owner_path),
+        })
+    }
+
+    /// List every staging directory under the root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the root cannot be read.
+    pub fn scan(&self) -> Result<Vec<StagingDir>> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("read {}", self.root.display()));
+            }
+        };
+
+        let now = Utc::now();
+        let mut dirs = Vec::new();
+        for entry in entries {
+            let entry = entry.with_context(|| format!("read {}", self.root.display()))?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if !name.starts_with(STAGING_PREFIX) || !entry.path().is_dir() {
+                continue;
+            }
+            let path = entry.path();
+            let owner = read_owner(&path);
+            let orphaned = self.is_orphaned(&path, owner.as_ref(), now);
+            dirs.push(StagingDir {
+                size_bytes: dir_size(&path),
+                path,
+                owner,
+                orphaned,
+            });
+        }
+        dirs.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(dirs)
+    }
+
+    /// Disk usage of the staging directories under the root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the root cannot be read.
+    pub fn usage(&self) -> Result<StagingUsage> {
+        let mut usage = StagingUsage::default();
+        for dir in self.scan()? {
+            usage.dirs += 1;
+            usage.total_bytes += dir.size_bytes;
+            if dir.orphaned {
+                usage.orphaned_dirs += 1;
+                usage.orphaned_bytes += dir.size_bytes;
+            }
+        }
+        Ok(usage)
+    }
+
+    /// Remove every orphaned staging directory and its owner record.
+    ///
+    /// Failures to remove individual directories are collected in the report
+    /// rather than aborting the sweep.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the root cannot be read.
+    pub fn sweep(&self) -> Result<SweepReport> {
+        let mut report = SweepReport::default();
+        for dir in self.scan()?.into_iter().filter(|d| d.orphaned) {
+            match fs::remove_dir_all(&dir.path) {
+                Ok(()) => {
+                    let _ = fs::remove_file(owner_path(&dir.path));
+                    debug!(target: "abp.workspace", path = %dir.path.display(), "removed orphaned staging directory");
+                    report.bytes_reclaimed += dir.size_bytes;
+                    report.removed.push(dir.path);
+                }
+                Err(e) => {
+                    warn!(target: "abp.workspace", path = %dir.path.display(), error = %e, "failed to remove orphaned staging directory");
+                    report.errors.push((dir.path, e.to_string()));
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    fn is_orphaned(&self, path: &Path, owner: Option<&StagingOwner>, now: DateTime<Utc>) -> bool {
+        if let Some(owner) = owner {
+            if owner.pid == std::process::id() {
+                return false;
+            }
+            if let Some(alive) = process_alive(owner.pid) {
+                return !alive;
+            }
+        }
+        let created_at = owner.map(|o| o.created_at).or_else(|| {
+            fs::metadata(path)
+                .and_then(|m| m.modified())
+                .ok()
+                .map(DateTime::<Utc>::from)
+        });
+        created_at.is_some_and(|t| now - t > self.grace)
+    }
+}
+
+/// Path of the owner record for the staging directory at `dir`.
+fn owner_path(dir: &Path) -> PathBuf {
+    let mut name = dir.file_name().unwrap_or_default().to_os_string();
+    name.push(OWNER_SUFFIX);
+    dir.with_file_name(name)
+}
+
+fn read_owner(dir: &Path) -> Option<StagingOwner> {
+    let bytes = fs::read(owner_path(dir)).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Whether process `pid` is running, or `None` if that cannot be determined
+/// on this platform.
+fn process_alive(pid: u32) -> Option<bool> {
+    if cfg!(target_os = "linux") {
+        Some(Path::new("/proc").join(pid.to_string()).exists())
+    } else {
+        None
+    }
+}
+
+/// Total size of the regular files under `root`, ignoring unreadable entries.
+fn dir_size(root: &Path) -> u64 {
+    WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
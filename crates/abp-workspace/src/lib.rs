@@ -15,6 +15,7 @@ pub mod archive;
 pub mod changes;
 pub mod diff;
 pub mod git_ops;
+pub mod janitor;
 pub mod lifecycle;
 pub mod merge;
 pub mod ops;
@@ -25,17 +26,20 @@ pub mod semantic_diff;
 pub mod snapshot;
 pub mod template;
 pub mod tracker;
+pub mod vfs;
 
+use abp_core::merkle::WorkspaceDigest;
 use abp_core::{WorkspaceMode, WorkspaceSpec};
 use abp_git::{ensure_git_repo, git_diff as git_diff_impl, git_status as git_status_impl};
 use abp_glob::IncludeExcludeGlobs;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use janitor::{StagingGuard, WorkspaceJanitor};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use tempfile::TempDir;
 use tracing::debug;
 use walkdir::WalkDir;
 
@@ -80,11 +84,12 @@ impl ValidationResult {
 /// A workspace ready for use, potentially backed by a temporary directory.
 ///
 /// For [`WorkspaceMode::Staged`] workspaces the temp directory is cleaned up
-/// when this value is dropped.
+/// when this value is dropped. It is also tracked by the [`janitor`], so a
+/// copy left behind by a crashed host can be reclaimed later.
 #[derive(Debug)]
 pub struct PreparedWorkspace {
     path: PathBuf,
-    _temp: Option<TempDir>,
+    _temp: Option<StagingGuard>,
     created_at: DateTime<Utc>,
 }
 
@@ -191,8 +196,7 @@ impl PreparedWorkspace {
     /// Returns an error if the temporary directory cannot be removed.
     pub fn cleanup(mut self) -> Result<()> {
         if let Some(tmp) = self._temp.take() {
-            tmp.close()
-                .context("remove temporary workspace directory")?;
+            tmp.close()?;
         }
         Ok(())
     }
@@ -222,7 +226,7 @@ impl WorkspaceManager {
                 created_at: Utc::now(),
             }),
             WorkspaceMode::Staged => {
-                let tmp = tempfile::tempdir().context("create temp dir")?;
+                let tmp = WorkspaceJanitor::default().create(Some(&root))?;
                 let dest = tmp.path().to_path_buf();
 
                 let path_rules = IncludeExcludeGlobs::new(&spec.include, &spec.exclude)
@@ -385,7 +389,7 @@ impl WorkspaceStager {
             root.display()
         );
 
-        let tmp = tempfile::tempdir().context("create temp dir")?;
+        let tmp = WorkspaceJanitor::default().create(Some(&root))?;
         let dest = tmp.path().to_path_buf();
 
         let path_rules = IncludeExcludeGlobs::new(&self.include, &self.exclude)
@@ -507,3 +511,36 @@ pub fn workspace_content_hash(root: &Path) -> Result<String> {
 
     Ok(format!("{:x}", hasher.finalize()))
 }
+
+/// Compute a Merkle [`WorkspaceDigest`] over the contents of every file in a
+/// workspace.
+///
+/// Each file's contents are hashed with SHA-256 and keyed by its
+/// forward-slash relative path. The `.git` directory is excluded.
+pub fn workspace_digest(root: &Path) -> Result<WorkspaceDigest> {
+    let mut files = BTreeMap::new();
+
+    let walker = WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != std::ffi::OsStr::new(".git"));
+
+    for entry in walker {
+        let entry = entry.with_context(|| format!("walk {}", root.display()))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(root).unwrap_or(entry.path());
+        let mut file = fs::File::open(entry.path())
+            .with_context(|| format!("open {}", entry.path().display()))?;
+        let mut hasher = Sha256::new();
+        std::io::copy(&mut file, &mut hasher)
+            .with_context(|| format!("hash {}", entry.path().display()))?;
+        files.insert(
+            rel.to_string_lossy().replace('\\', "/"),
+            format!("{:x}", hasher.finalize()),
+        );
+    }
+
+    Ok(WorkspaceDigest::from_files(files))
+}
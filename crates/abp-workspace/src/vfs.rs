@@ -0,0 +1,354 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Policy-enforcing virtual filesystem for tool implementations.
+//!
+//! A tool that touches files through a [`Vfs`] cannot leave the workspace
+//! whatever path it is handed: every path is resolved against the workspace
+//! root, symlinks included, and the policy is applied at this boundary rather
+//! than trusted to each tool.
+//!
+//! - **Confinement** — absolute paths outside the root, `..` segments that
+//!   climb above it, and symlinks that point out of it fail with
+//!   [`PermissionDenied`](std::io::ErrorKind::PermissionDenied).
+//! - **Hidden paths** — paths matching the policy's `deny_read` globs (or
+//!   [`Vfs::hide`]) behave as if they did not exist: reads fail with
+//!   [`NotFound`](std::io::ErrorKind::NotFound) and listings leave them out.
+//! - **Read-only paths** — paths matching `deny_write` (or
+//!   [`Vfs::read_only`]) can be read but every write, removal, or directory
+//!   creation fails with `PermissionDenied`.
+//! - **Write redirection** — with [`Vfs::with_lower`] the root is layered
+//!   over a read-only source tree: reads fall through to the source, while
+//!   writes — including ones addressed to the source by absolute path — land
+//!   in the root, normally the staged workspace.
+//!
+//! ```
+//! use abp_core::PolicyProfile;
+//! use abp_workspace::vfs::Vfs;
+//!
+//! let dir = tempfile::tempdir().unwrap();
+//! std::fs::write(dir.path().join(".env"), "SECRET=1").unwrap();
+//! let policy = PolicyProfile {
+//!     deny_read: vec![".env".into()],
+//!     deny_write: vec!["Cargo.lock".into()],
+//!     ..PolicyProfile::default()
+//! };
+//! let vfs = Vfs::from_policy(dir.path(), &policy).unwrap();
+//!
+//! vfs.write("src/main.rs", b"fn main() {}").unwrap();
+//! assert_eq!(vfs.list("").unwrap(), ["src"]);
+//! assert!(vfs.read(".env").is_err());
+//! assert!(vfs.write("Cargo.lock", b"").is_err());
+//! assert!(vfs.read("../etc/passwd").is_err());
+//! ```
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Component, Path, PathBuf};
+
+use abp_core::PolicyProfile;
+use abp_glob::IncludeExcludeGlobs;
+use anyhow::Result;
+
+/// Workspace-confined, policy-enforcing view of a directory tree.
+#[derive(Debug, Clone)]
+pub struct Vfs {
+    root: PathBuf,
+    lower: Option<PathBuf>,
+    hidden_patterns: Vec<String>,
+    read_only_patterns: Vec<String>,
+    hidden: IncludeExcludeGlobs,
+    read_only: IncludeExcludeGlobs,
+}
+
+impl Vfs {
+    /// A view of `root` with no hidden or read-only paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `root` does not exist.
+    pub fn new(root: impl AsRef<Path>) -> io::Result<Self> {
+        let none = IncludeExcludeGlobs::new(&[], &[]).expect("empty glob sets compile");
+        Ok(Self {
+            root: root.as_ref().canonicalize()?,
+            lower: None,
+            hidden_patterns: Vec::new(),
+            read_only_patterns: Vec::new(),
+            hidden: none.clone(),
+            read_only: none,
+        })
+    }
+
+    /// A view of `root` that hides `deny_read` paths and protects
+    /// `deny_write` paths of `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `root` does not exist or a pattern is invalid.
+    pub fn from_policy(root: impl AsRef<Path>, policy: &PolicyProfile) -> Result<Self> {
+        Self::new(root)?
+            .hide(&policy.deny_read)?
+            .read_only(&policy.deny_write)
+    }
+
+    /// Also hide paths matching `patterns` (builder pattern).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pattern is invalid.
+    pub fn hide(mut self, patterns: &[String]) -> Result<Self> {
+        self.hidden_patterns.extend_from_slice(patterns);
+        self.hidden = IncludeExcludeGlobs::new(&[], &self.hidden_patterns)?;
+        Ok(self)
+    }
+
+    /// Also make paths matching `patterns` read-only (builder pattern).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a pattern is invalid.
+    pub fn read_only(mut self, patterns: &[String]) -> Result<Self> {
+        self.read_only_patterns.extend_from_slice(patterns);
+        self.read_only = IncludeExcludeGlobs::new(&[], &self.read_only_patterns)?;
+        Ok(self)
+    }
+
+    /// Layer the root over `source`: reads fall back to `source` and writes
+    /// addressed to it are redirected into the root (builder pattern).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `source` does not exist.
+    pub fn with_lower(mut self, source: impl AsRef<Path>) -> io::Result<Self> {
+        self.lower = Some(source.as_ref().canonicalize()?);
+        Ok(self)
+    }
+
+    /// The writable root directory.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Whether the workspace-relative `rel` is hidden.
+    #[must_use]
+    pub fn is_hidden(&self, rel: &Path) -> bool {
+        !rel.as_os_str().is_empty() && !self.hidden.decide_path(rel).is_allowed()
+    }
+
+    /// Whether the workspace-relative `rel` is read-only.
+    #[must_use]
+    pub fn is_read_only(&self, rel: &Path) -> bool {
+        !self.read_only.decide_path(rel).is_allowed()
+    }
+
+    /// Resolve `path` to a workspace-relative path without touching the
+    /// filesystem.
+    ///
+    /// Relative paths are taken from the root; absolute paths must lie under
+    /// the root or the lower source.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `PermissionDenied` if the path leaves the workspace.
+    pub fn relative(&self, path: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let path = path.as_ref();
+        let tail = if path.is_absolute() {
+            [Some(&self.root), self.lower.as_ref()]
+                .into_iter()
+                .flatten()
+                .find_map(|base| path.strip_prefix(base).ok())
+                .ok_or_else(|| escape(path))?
+        } else {
+            path
+        };
+        let mut rel = PathBuf::new();
+        for component in tail.components() {
+            match component {
+                Component::Normal(part) => rel.push(part),
+                Component::CurDir => {}
+                Component::ParentDir if rel.pop() => {}
+                _ => return Err(escape(path)),
+            }
+        }
+        Ok(rel)
+    }
+
+    /// Whether `path` exists and is visible.
+    #[must_use]
+    pub fn exists(&self, path: impl AsRef<Path>) -> bool {
+        self.locate(path.as_ref()).is_ok()
+    }
+
+    /// Read the whole file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the path leaves the workspace, is hidden, or cannot be read.
+    pub fn read(&self, path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+        fs::read(self.locate(path.as_ref())?)
+    }
+
+    /// Read the whole file at `path` as UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`read`](Self::read), or if the file is not UTF-8.
+    pub fn read_to_string(&self, path: impl AsRef<Path>) -> io::Result<String> {
+        fs::read_to_string(self.locate(path.as_ref())?)
+    }
+
+    /// Write `contents` to `path` in the root, creating parent directories.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the path leaves the workspace, is hidden or read-only, or
+    /// cannot be written.
+    pub fn write(&self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+        let target = self.writable(path.as_ref())?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, contents)
+    }
+
+    /// Create the directory `path` and its parents in the root.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`write`](Self::write).
+    pub fn create_dir_all(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::create_dir_all(self.writable(path.as_ref())?)
+    }
+
+    /// Remove the file at `path` from the root.
+    ///
+    /// Files that only exist in the lower source cannot be removed.
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`write`](Self::write), or if there is no such file in the
+    /// root.
+    pub fn remove_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let target = self.writable(path.as_ref())?;
+        if !target.exists() && self.locate(path.as_ref()).is_ok() {
+            return Err(io::Error::new(
+                ErrorKind::PermissionDenied,
+                format!("'{}' is in the read-only source", path.as_ref().display()),
+            ));
+        }
+        fs::remove_file(target)
+    }
+
+    /// Visible entry names in the directory `path`, merged across the root
+    /// and the lower source and sorted.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the path leaves the workspace, is hidden, or is not a
+    /// directory.
+    pub fn list(&self, path: impl AsRef<Path>) -> io::Result<Vec<String>> {
+        let rel = self.relative(path.as_ref())?;
+        if self.is_hidden(&rel) {
+            return Err(not_found(path.as_ref()));
+        }
+        let mut names = BTreeSet::new();
+        let mut found = false;
+        for base in self.layers() {
+            let dir = base.join(&rel);
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            self.confine(&dir.canonicalize()?, path.as_ref())?;
+            found = true;
+            for entry in entries {
+                let name = entry?.file_name().to_string_lossy().into_owned();
+                if !self.is_hidden(&rel.join(&name)) {
+                    names.insert(name);
+                }
+            }
+        }
+        if !found {
+            return Err(not_found(path.as_ref()));
+        }
+        Ok(names.into_iter().collect())
+    }
+
+    /// The root, then the lower source if any.
+    fn layers(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.root).chain(self.lower.as_ref())
+    }
+
+    /// Physical path of the visible file behind `path`.
+    fn locate(&self, path: &Path) -> io::Result<PathBuf> {
+        let rel = self.relative(path)?;
+        if self.is_hidden(&rel) {
+            return Err(not_found(path));
+        }
+        for base in self.layers() {
+            let candidate = base.join(&rel);
+            if let Ok(real) = candidate.canonicalize() {
+                self.confine(&real, path)?;
+                return Ok(real);
+            }
+        }
+        Err(not_found(path))
+    }
+
+    /// Physical path in the root that a write to `path` goes to.
+    fn writable(&self, path: &Path) -> io::Result<PathBuf> {
+        let rel = self.relative(path)?;
+        if rel.as_os_str().is_empty() || self.is_hidden(&rel) || self.is_read_only(&rel) {
+            return Err(io::Error::new(
+                ErrorKind::PermissionDenied,
+                format!("'{}' is read-only", path.display()),
+            ));
+        }
+        let target = self.root.join(&rel);
+        let dangling = fs::symlink_metadata(&target).is_ok_and(|m| m.file_type().is_symlink())
+            && target.canonicalize().is_err();
+        if dangling {
+            return Err(escape(path));
+        }
+        // Symlinks on the way — the target itself or a parent directory —
+        // must not carry the write out of the root or onto a protected path.
+        let existing = target
+            .ancestors()
+            .find_map(|p| p.canonicalize().ok())
+            .ok_or_else(|| escape(path))?;
+        let real_rel = self.confine(&existing, path)?;
+        if !existing.starts_with(&self.root) || self.is_read_only(&real_rel) {
+            return Err(io::Error::new(
+                ErrorKind::PermissionDenied,
+                format!("'{}' is read-only", path.display()),
+            ));
+        }
+        Ok(target)
+    }
+
+    /// Check that the resolved `real` path stays inside a layer and is not
+    /// hidden under its resolved name; returns that name.
+    fn confine(&self, real: &Path, requested: &Path) -> io::Result<PathBuf> {
+        let rel = self
+            .layers()
+            .find_map(|base| real.strip_prefix(base).ok())
+            .ok_or_else(|| escape(requested))?;
+        if self.is_hidden(rel) {
+            return Err(not_found(requested));
+        }
+        Ok(rel.to_path_buf())
+    }
+}
+
+fn escape(path: &Path) -> io::Error {
+    io::Error::new(
+        ErrorKind::PermissionDenied,
+        format!("'{}' is outside the workspace", path.display()),
+    )
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        ErrorKind::NotFound,
+        format!("'{}' not found", path.display()),
+    )
+}
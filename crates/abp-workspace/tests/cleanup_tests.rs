@@ -31,6 +31,7 @@
 
 use abp_core::{WorkspaceMode, WorkspaceSpec};
 use abp_workspace::WorkspaceManager;
+use abp_workspace::janitor::{STAGING_PREFIX, StagingOwner, WorkspaceJanitor};
 use std::fs;
 use std::path::PathBuf;
 use tempfile::tempdir;
@@ -226,6 +227,105 @@ fn combined_include_exclude_globs() {
     );
 }
 
+// ── Janitor ─────────────────────────────────────────────────────────
+
+#[test]
+fn staged_workspace_is_tracked_until_dropped() {
+    let src = tempdir().unwrap();
+    fs::write(src.path().join("a.txt"), "data").unwrap();
+
+    let prepared = WorkspaceManager::prepare(&staged_spec(&src.path().to_string_lossy())).unwrap();
+    let ws_path = prepared.path().to_path_buf();
+    let name = ws_path.file_name().unwrap().to_string_lossy().into_owned();
+    assert!(name.starts_with(STAGING_PREFIX));
+    let owner_file = ws_path.with_file_name(format!("{name}.owner.json"));
+
+    let found = WorkspaceJanitor::default().scan().unwrap();
+    let dir = found.iter().find(|d| d.path == ws_path).unwrap();
+    let owner = dir.owner.as_ref().unwrap();
+    assert_eq!(owner.pid, std::process::id());
+    assert_eq!(owner.source_root.as_deref(), Some(src.path()));
+    assert!(!dir.orphaned, "live run's directory must not be swept");
+    assert!(dir.size_bytes >= 4);
+
+    drop(prepared);
+    assert!(!ws_path.exists());
+    assert!(
+        !owner_file.exists(),
+        "owner record must go with the directory"
+    );
+}
+
+fn orphan(root: &std::path::Path, name: &str, contents: &str) -> PathBuf {
+    let dir = root.join(format!("{STAGING_PREFIX}{name}"));
+    fs::create_dir(&dir).unwrap();
+    fs::write(dir.join("big.bin"), contents).unwrap();
+    let owner = StagingOwner {
+        pid: u32::MAX,
+        created_at: chrono::Utc::now() - chrono::Duration::days(2),
+        source_root: None,
+    };
+    fs::write(
+        root.join(format!("{STAGING_PREFIX}{name}.owner.json")),
+        serde_json::to_vec(&owner).unwrap(),
+    )
+    .unwrap();
+    dir
+}
+
+#[test]
+fn sweep_removes_only_orphaned_staging_dirs() {
+    let root = tempdir().unwrap();
+    let janitor = WorkspaceJanitor::new(root.path());
+    let dead = orphan(root.path(), "dead", "0123456789");
+    let live = janitor.create(None).unwrap();
+    let untracked = root.path().join(format!("{STAGING_PREFIX}fresh"));
+    fs::create_dir(&untracked).unwrap();
+    let unrelated = root.path().join("not-ours");
+    fs::create_dir(&unrelated).unwrap();
+
+    let report = janitor.sweep().unwrap();
+    assert_eq!(report.removed, vec![dead.clone()]);
+    assert_eq!(report.bytes_reclaimed, 10);
+    assert!(report.errors.is_empty());
+    assert!(!dead.exists());
+    assert!(
+        !root
+            .path()
+            .join(format!("{STAGING_PREFIX}dead.owner.json"))
+            .exists()
+    );
+    assert!(live.path().exists());
+    assert!(untracked.exists(), "untracked dirs get a grace period");
+    assert!(unrelated.exists());
+}
+
+#[test]
+fn usage_reports_staging_disk_consumption() {
+    let root = tempdir().unwrap();
+    let janitor = WorkspaceJanitor::new(root.path());
+    orphan(root.path(), "a", "12345");
+    let live = janitor.create(None).unwrap();
+    fs::write(live.path().join("f"), "123").unwrap();
+
+    let usage = janitor.usage().unwrap();
+    assert_eq!(usage.dirs, 2);
+    assert_eq!(usage.total_bytes, 8);
+    assert_eq!(usage.orphaned_dirs, 1);
+    assert_eq!(usage.orphaned_bytes, 5);
+
+    janitor.sweep().unwrap();
+    assert_eq!(janitor.usage().unwrap().orphaned_dirs, 0);
+}
+
+#[test]
+fn missing_staging_root_scans_empty() {
+    let root = tempdir().unwrap();
+    let janitor = WorkspaceJanitor::new(root.path().join("missing"));
+    assert!(janitor.scan().unwrap().is_empty());
+    assert!(janitor.sweep().unwrap().removed.is_empty());
+}
+
 // ── Git initialisation ──────────────────────────────────────────────
 
 #[test]
@@ -2,7 +2,7 @@
 //! Tests for enhanced workspace staging: metadata, validation, cleanup,
 //! snapshots, diff extraction, and content hashing.
 
-use abp_workspace::{WorkspaceStager, workspace_content_hash};
+use abp_workspace::{WorkspaceStager, workspace_content_hash, workspace_digest};
 use std::fs;
 use tempfile::tempdir;
 
@@ -244,3 +244,34 @@ fn content_hash_changes_on_file_addition() {
 
     assert_ne!(h_before, h_after, "hash should change after adding a file");
 }
+
+// ── Merkle digest tests ─────────────────────────────────────────────────
+
+#[test]
+fn digest_covers_every_file_except_git() {
+    let src = make_source_tree();
+    let ws = stage_from(src.path());
+
+    let digest = workspace_digest(ws.path()).unwrap();
+    let paths: Vec<&str> = digest.files.keys().map(String::as_str).collect();
+    assert_eq!(paths, vec!["hello.txt", "sub/nested.txt"]);
+    assert_eq!(
+        digest.files["hello.txt"],
+        abp_core::sha256_hex(b"hello world")
+    );
+    assert!(digest.verify());
+}
+
+#[test]
+fn digest_identifies_tampered_file() {
+    let src = make_source_tree();
+    let ws = stage_from(src.path());
+
+    let before = workspace_digest(ws.path()).unwrap();
+    // Same size, different contents: invisible to the size-based content hash.
+    fs::write(ws.path().join("hello.txt"), "HELLO WORLD").unwrap();
+    let after = workspace_digest(ws.path()).unwrap();
+
+    assert_ne!(before.root, after.root);
+    assert_eq!(before.changed_paths(&after), vec!["hello.txt"]);
+}
@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+//! Tests for `abp_workspace::vfs`.
+
+use std::fs;
+use std::io::ErrorKind;
+
+use abp_core::PolicyProfile;
+use abp_workspace::vfs::Vfs;
+use tempfile::TempDir;
+
+fn workspace() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir_all(dir.path().join("src")).unwrap();
+    fs::create_dir_all(dir.path().join("secrets")).unwrap();
+    fs::write(dir.path().join("src/lib.rs"), "pub fn f() {}").unwrap();
+    fs::write(dir.path().join("secrets/key.pem"), "KEY").unwrap();
+    fs::write(dir.path().join("Cargo.lock"), "# lock").unwrap();
+    dir
+}
+
+fn policy() -> PolicyProfile {
+    PolicyProfile {
+        deny_read: vec!["secrets/**".into(), "secrets".into()],
+        deny_write: vec!["Cargo.lock".into()],
+        ..PolicyProfile::default()
+    }
+}
+
+#[test]
+fn paths_cannot_leave_the_root() {
+    let ws = workspace();
+    let outside = tempfile::tempdir().unwrap();
+    fs::write(outside.path().join("x"), "outside").unwrap();
+    let vfs = Vfs::new(ws.path()).unwrap();
+
+    for path in [
+        "../x".to_string(),
+        "src/../../x".to_string(),
+        outside.path().join("x").display().to_string(),
+    ] {
+        let err = vfs.read(&path).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied, "{path}");
+        assert!(vfs.write(&path, "y").is_err(), "{path}");
+    }
+    assert_eq!(
+        fs::read_to_string(outside.path().join("x")).unwrap(),
+        "outside"
+    );
+
+    // Climbing and coming back down is fine.
+    assert_eq!(
+        vfs.read_to_string("src/../src/lib.rs").unwrap(),
+        "pub fn f() {}"
+    );
+    let absolute = ws.path().canonicalize().unwrap().join("src/lib.rs");
+    assert!(vfs.exists(absolute));
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinks_out_of_the_root_are_refused() {
+    let ws = workspace();
+    let outside = tempfile::tempdir().unwrap();
+    fs::write(outside.path().join("passwd"), "root").unwrap();
+    std::os::unix::fs::symlink(outside.path().join("passwd"), ws.path().join("link")).unwrap();
+    std::os::unix::fs::symlink(outside.path(), ws.path().join("dir")).unwrap();
+    std::os::unix::fs::symlink(outside.path().join("new"), ws.path().join("dangling")).unwrap();
+    std::os::unix::fs::symlink(ws.path().join("secrets/key.pem"), ws.path().join("key")).unwrap();
+    std::os::unix::fs::symlink(ws.path().join("Cargo.lock"), ws.path().join("lock")).unwrap();
+    let vfs = Vfs::from_policy(ws.path(), &policy()).unwrap();
+
+    assert_eq!(
+        vfs.read("link").unwrap_err().kind(),
+        ErrorKind::PermissionDenied
+    );
+    assert!(vfs.write("link", "pwned").is_err());
+    assert!(vfs.write("dir/new", "pwned").is_err());
+    assert!(vfs.write("dangling", "pwned").is_err());
+    assert!(!outside.path().join("new").exists());
+    assert_eq!(
+        fs::read_to_string(outside.path().join("passwd")).unwrap(),
+        "root"
+    );
+
+    // Aliases of hidden or protected files inherit their restrictions.
+    assert_eq!(vfs.read("key").unwrap_err().kind(), ErrorKind::NotFound);
+    assert_eq!(vfs.read_to_string("lock").unwrap(), "# lock");
+    assert!(vfs.write("lock", "changed").is_err());
+}
+
+#[test]
+fn hidden_paths_look_absent_and_read_only_paths_reject_changes() {
+    let ws = workspace();
+    let vfs = Vfs::from_policy(ws.path(), &policy()).unwrap();
+
+    assert_eq!(vfs.list("").unwrap(), ["Cargo.lock", "src"]);
+    assert_eq!(
+        vfs.read("secrets/key.pem").unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    assert_eq!(vfs.list("secrets").unwrap_err().kind(), ErrorKind::NotFound);
+    assert!(!vfs.exists("secrets/key.pem"));
+    assert!(vfs.write("secrets/new.pem", "x").is_err());
+
+    assert_eq!(vfs.read_to_string("Cargo.lock").unwrap(), "# lock");
+    let err = vfs.write("Cargo.lock", "changed").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    assert!(vfs.remove_file("Cargo.lock").is_err());
+    assert_eq!(
+        fs::read_to_string(ws.path().join("Cargo.lock")).unwrap(),
+        "# lock"
+    );
+
+    vfs.write("src/new.rs", "// new").unwrap();
+    vfs.create_dir_all("docs/guide").unwrap();
+    assert_eq!(vfs.list("src").unwrap(), ["lib.rs", "new.rs"]);
+    vfs.remove_file("src/new.rs").unwrap();
+    assert!(!ws.path().join("src/new.rs").exists());
+    assert!(ws.path().join("docs/guide").is_dir());
+}
+
+#[test]
+fn writes_over_a_lower_source_are_redirected_into_the_root() {
+    let source = workspace();
+    fs::write(source.path().join("notes.txt"), "keep").unwrap();
+    let staged = tempfile::tempdir().unwrap();
+    let vfs = Vfs::from_policy(staged.path(), &policy())
+        .unwrap()
+        .with_lower(source.path())
+        .unwrap();
+
+    assert_eq!(vfs.read_to_string("src/lib.rs").unwrap(), "pub fn f() {}");
+    assert_eq!(vfs.list("").unwrap(), ["Cargo.lock", "notes.txt", "src"]);
+
+    vfs.write("src/lib.rs", "pub fn g() {}").unwrap();
+    let by_source_path = source.path().canonicalize().unwrap().join("README.md");
+    vfs.write(&by_source_path, "# hi").unwrap();
+
+    assert_eq!(vfs.read_to_string("src/lib.rs").unwrap(), "pub fn g() {}");
+    assert_eq!(
+        fs::read_to_string(staged.path().join("src/lib.rs")).unwrap(),
+        "pub fn g() {}"
+    );
+    assert_eq!(
+        fs::read_to_string(staged.path().join("README.md")).unwrap(),
+        "# hi"
+    );
+    assert_eq!(
+        fs::read_to_string(source.path().join("src/lib.rs")).unwrap(),
+        "pub fn f() {}"
+    );
+    assert!(!source.path().join("README.md").exists());
+    assert_eq!(
+        vfs.list("").unwrap(),
+        ["Cargo.lock", "README.md", "notes.txt", "src"]
+    );
+
+    // The source copy is not the staged copy's to delete.
+    assert_eq!(
+        vfs.remove_file("notes.txt").unwrap_err().kind(),
+        ErrorKind::PermissionDenied
+    );
+}
@@ -85,6 +85,20 @@ pub fn event_warning(message: &str) -> Value {
     })
 }
 
+/// Create a `progress` event value; `percent` is omitted when `None`.
+#[must_use]
+pub fn event_progress(percent: Option<f64>, message: &str) -> Value {
+    let mut ev = json!({
+        "ts": Utc::now().to_rfc3339(),
+        "type": "progress",
+        "message": message,
+    });
+    if let Some(pct) = percent {
+        ev["percent"] = json!(pct);
+    }
+    ev
+}
+
 /// Create a `run_started` event value.
 #[must_use]
 pub fn event_run_started(message: &str) -> Value {
@@ -132,6 +146,52 @@ pub fn event_command_executed(
     })
 }
 
+/// Split an artifact into `artifact_chunk` event values of at most
+/// `chunk_size` bytes each (at least one byte).
+///
+/// The last chunk carries the artifact's SHA-256 so the host can check what
+/// it assembled. An empty artifact becomes a single empty chunk.
+#[must_use]
+pub fn event_artifact_chunks(
+    name: &str,
+    bytes: &[u8],
+    chunk_size: usize,
+    media_type: Option<&str>,
+) -> Vec<Value> {
+    use base64::Engine as _;
+
+    let sha256 = abp_core::sha256_hex(bytes);
+    let mut chunks: Vec<&[u8]> = bytes.chunks(chunk_size.max(1)).collect();
+    if chunks.is_empty() {
+        chunks.push(&[]);
+    }
+    let count = chunks.len();
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(seq, chunk)| {
+            let last = seq + 1 == count;
+            let mut event = json!({
+                "ts": Utc::now().to_rfc3339(),
+                "type": "artifact_chunk",
+                "name": name,
+                "seq": seq,
+                "data": base64::engine::general_purpose::STANDARD.encode(chunk),
+                "last": last,
+            });
+            if seq == 0
+                && let Some(media_type) = media_type
+            {
+                event["media_type"] = json!(media_type);
+            }
+            if last {
+                event["sha256"] = json!(sha256);
+            }
+            event
+        })
+        .collect()
+}
+
 // ── Frame helpers ───────────────────────────────────────────────────
 
 /// Build a [`Frame::Event`] wrapping the given event value.
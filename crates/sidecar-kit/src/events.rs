@@ -171,6 +171,19 @@ pub fn warning_event(message: impl Into<String>) -> AgentEvent {
     }
 }
 
+/// Create a progress event; `percent` is an estimate in `0.0..=100.0`.
+#[must_use]
+pub fn progress_event(percent: Option<f64>, message: impl Into<String>) -> AgentEvent {
+    AgentEvent {
+        ts: Utc::now(),
+        kind: AgentEventKind::Progress {
+            percent,
+            message: message.into(),
+        },
+        ext: None,
+    }
+}
+
 /// Create a file-changed event.
 #[must_use]
 pub fn file_changed_event(path: impl Into<String>, action: impl Into<String>) -> AgentEvent {
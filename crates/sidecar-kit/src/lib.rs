@@ -39,9 +39,10 @@ pub mod typed_middleware;
 pub mod work_order;
 
 pub use builders::{
-    EventBuilder, ReceiptBuilder, event_command_executed, event_error, event_file_changed,
-    event_frame, event_run_completed, event_run_started, event_text_delta, event_text_message,
-    event_tool_call, event_tool_result, event_warning, fatal_frame, final_frame, hello_frame,
+    EventBuilder, ReceiptBuilder, event_artifact_chunks, event_command_executed, event_error,
+    event_file_changed, event_frame, event_progress, event_run_completed, event_run_started,
+    event_text_delta, event_text_message, event_tool_call, event_tool_result, event_warning,
+    fatal_frame, final_frame, hello_frame,
 };
 pub use cancel::CancelToken;
 pub use client::{HelloData, SidecarClient};
@@ -74,8 +75,8 @@ pub use typed_middleware::{
 
 pub use events::{
     EventBuilder as TypedEventBuilder, command_event, delta_event, error_event, file_changed_event,
-    run_completed_event, run_started_event, text_event, tool_call_event, tool_result_event,
-    warning_event,
+    progress_event, run_completed_event, run_started_event, text_event, tool_call_event,
+    tool_result_event, warning_event,
 };
 pub use protocol_helpers::{read_run, send_event, send_fatal, send_final, send_hello};
 pub use receipt_builder::TypedReceiptBuilder;
@@ -259,6 +259,9 @@ mod tests {
                 env: Default::default(),
                 max_budget_usd: None,
                 max_turns: Some(10),
+                response_language: None,
+                fidelity_policy: None,
+                submitter: None,
             },
         };
         let wo_value = serde_json::to_value(&wo).unwrap();
@@ -305,6 +308,9 @@ mod tests {
                 env: Default::default(),
                 max_budget_usd: None,
                 max_turns: None,
+                response_language: None,
+                fidelity_policy: None,
+                submitter: None,
             },
         };
         let wo_value = serde_json::to_value(&wo).unwrap();
@@ -80,6 +80,7 @@ impl TypedReceiptBuilder {
                 git_diff: None,
                 git_status: None,
                 harness_ok: false,
+                input_digest: None,
             },
         }
     }
@@ -302,6 +303,7 @@ impl TypedReceiptBuilder {
                 git_diff: None,
                 git_status: None,
                 harness_ok: false,
+                input_digest: None,
             },
             outcome: Outcome::Complete,
             receipt_sha256: None,
@@ -36,6 +36,9 @@ fn kind_name(kind: &AgentEventKind) -> &'static str {
         AgentEventKind::ToolResult { .. } => "tool_result",
         AgentEventKind::FileChanged { .. } => "file_changed",
         AgentEventKind::CommandExecuted { .. } => "command_executed",
+        AgentEventKind::Progress { .. } => "progress",
+        AgentEventKind::RunSummary { .. } => "run_summary",
+        AgentEventKind::ArtifactChunk { .. } => "artifact_chunk",
         AgentEventKind::Warning { .. } => "warning",
         AgentEventKind::Error { .. } => "error",
     }
@@ -147,6 +150,10 @@ impl EventTransformer for RedactTransformer {
                 exit_code,
                 output_preview: output_preview.map(|s| self.redact_string(&s)),
             },
+            AgentEventKind::Progress { percent, message } => AgentEventKind::Progress {
+                percent,
+                message: self.redact_string(&message),
+            },
             AgentEventKind::Warning { message } => AgentEventKind::Warning {
                 message: self.redact_string(&message),
             },
@@ -158,6 +165,8 @@ impl EventTransformer for RedactTransformer {
                 message: self.redact_string(&message),
                 error_code,
             },
+            summary @ AgentEventKind::RunSummary { .. } => summary,
+            chunk @ AgentEventKind::ArtifactChunk { .. } => chunk,
         };
         Some(event)
     }
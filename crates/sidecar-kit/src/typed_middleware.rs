@@ -108,6 +108,9 @@ fn event_kind_name(kind: &AgentEventKind) -> &'static str {
         AgentEventKind::ToolResult { .. } => "tool_result",
         AgentEventKind::FileChanged { .. } => "file_changed",
         AgentEventKind::CommandExecuted { .. } => "command_executed",
+        AgentEventKind::Progress { .. } => "progress",
+        AgentEventKind::RunSummary { .. } => "run_summary",
+        AgentEventKind::ArtifactChunk { .. } => "artifact_chunk",
         AgentEventKind::Warning { .. } => "warning",
         AgentEventKind::Error { .. } => "error",
     }
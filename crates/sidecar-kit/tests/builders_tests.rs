@@ -31,9 +31,10 @@
 
 use serde_json::{Value, json};
 use sidecar_kit::builders::{
-    ReceiptBuilder, event_command_executed, event_error, event_file_changed, event_frame,
-    event_run_completed, event_run_started, event_text_delta, event_text_message, event_tool_call,
-    event_tool_result, event_warning, fatal_frame, hello_frame,
+    ReceiptBuilder, event_artifact_chunks, event_command_executed, event_error, event_file_changed,
+    event_frame, event_progress, event_run_completed, event_run_started, event_text_delta,
+    event_text_message, event_tool_call, event_tool_result, event_warning, fatal_frame,
+    hello_frame,
 };
 use sidecar_kit::middleware::{ErrorWrapMiddleware, EventMiddleware, TimingMiddleware};
 use sidecar_kit::{Frame, JsonlCodec, MiddlewareChain};
@@ -100,6 +101,21 @@ fn warning_event_has_message() {
     assert_eq!(ev["message"], "be careful");
 }
 
+#[test]
+fn progress_event_round_trips_into_agent_event() {
+    let ev = event_progress(Some(42.5), "indexing");
+    assert_eq!(ev["type"], "progress");
+    assert_eq!(ev["percent"], 42.5);
+    let typed: abp_core::AgentEvent = serde_json::from_value(ev).unwrap();
+    assert!(matches!(
+        typed.kind,
+        abp_core::AgentEventKind::Progress { percent: Some(p), ref message } if p == 42.5 && message == "indexing"
+    ));
+
+    let ev = event_progress(None, "thinking");
+    assert!(ev.get("percent").is_none());
+}
+
 #[test]
 fn run_started_event() {
     let ev = event_run_started("beginning work");
@@ -122,6 +138,31 @@ fn file_changed_event() {
     assert_eq!(ev["summary"], "added function");
 }
 
+#[test]
+fn artifact_chunk_events_parse_and_carry_the_hash_last() {
+    let chunks = event_artifact_chunks("out/data.csv", b"a,b\n1,2\n", 4, Some("text/csv"));
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0]["type"], "artifact_chunk");
+    assert_eq!(chunks[0]["seq"], 0);
+    assert_eq!(chunks[0]["data"], "YSxiCg==");
+    assert_eq!(chunks[0]["media_type"], "text/csv");
+    assert!(chunks[0].get("sha256").is_none());
+    assert_eq!(chunks[1]["last"], true);
+    assert_eq!(chunks[1]["sha256"], abp_core::sha256_hex(b"a,b\n1,2\n"));
+    for chunk in &chunks {
+        let ev: abp_core::AgentEvent = serde_json::from_value(chunk.clone()).unwrap();
+        assert!(matches!(
+            ev.kind,
+            abp_core::AgentEventKind::ArtifactChunk { .. }
+        ));
+    }
+
+    let empty = event_artifact_chunks("empty.bin", b"", 1024, None);
+    assert_eq!(empty.len(), 1);
+    assert_eq!(empty[0]["data"], "");
+    assert_eq!(empty[0]["last"], true);
+}
+
 #[test]
 fn command_executed_event_with_all_fields() {
     let ev = event_command_executed("cargo test", Some(0), Some("ok"));
@@ -86,7 +86,7 @@ fuzz_target!(|input: FuzzReceipt| {
         } else {
             None
         },
-        harness_ok: input.harness_ok,
+        harness_ok: input.harness_ok, input_digest: None,
     });
 
     // Set usage.
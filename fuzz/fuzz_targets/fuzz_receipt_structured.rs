@@ -69,7 +69,7 @@ fuzz_target!(|input: ReceiptInput| {
         } else {
             None
         },
-        harness_ok: input.harness_ok,
+        harness_ok: input.harness_ok, input_digest: None,
     });
 
     // Set usage.
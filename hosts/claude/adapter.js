@@ -445,8 +445,16 @@ function normalizeUsage(raw) {
     "completion_tokens",
     "completionTokens",
   ]);
-  const cacheReadTokens = pickNumber(usage, ["cache_read_tokens", "cacheReadTokens"]);
-  const cacheWriteTokens = pickNumber(usage, ["cache_write_tokens", "cacheWriteTokens"]);
+  const cacheReadTokens = pickNumber(usage, [
+    "cache_read_tokens",
+    "cacheReadTokens",
+    "cache_read_input_tokens",
+  ]);
+  const cacheWriteTokens = pickNumber(usage, [
+    "cache_write_tokens",
+    "cacheWriteTokens",
+    "cache_creation_input_tokens",
+  ]);
 
   return compactObject({
     input_tokens: inputTokens,
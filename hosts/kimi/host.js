@@ -107,6 +107,15 @@ function compileGlob(pattern) {
   return new RegExp(out);
 }
 
+// Network rules may carry a `:port` / `:lo-hi` / `:*` suffix; the runtime
+// enforces ports, so sidecars match on the host part only.
+function networkHostPatterns(list) {
+  return (list || []).map((rule) => {
+    const bracketed = /^\[(.*)\]/.exec(String(rule));
+    return bracketed ? bracketed[1] : String(rule).replace(/:(\d+(-\d+)?|\*)$/, "");
+  });
+}
+
 function compileGlobList(list) {
   if (!Array.isArray(list) || list.length === 0) {
     return [];
@@ -178,8 +187,8 @@ function buildPolicyEngine(policy, workspaceRoot) {
   const denyRead = compileGlobList(policy.deny_read || []);
   const denyWrite = compileGlobList(policy.deny_write || []);
   const requireApprovalFor = compileGlobList(policy.require_approval_for || []);
-  const allowNetwork = compileGlobList(policy.allow_network || []);
-  const denyNetwork = compileGlobList(policy.deny_network || []);
+  const allowNetwork = compileGlobList(networkHostPatterns(policy.allow_network));
+  const denyNetwork = compileGlobList(networkHostPatterns(policy.deny_network));
 
   function canUseTool(toolName) {
     if (matchesAny(disallowedTools, toolName)) {
@@ -563,6 +563,7 @@ fn agent_event_wraps_in_envelope_correctly() {
     let envelope = Envelope::Event {
         ref_id: ref_id.clone(),
         event: e.clone(),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&envelope).unwrap();
     assert!(encoded.contains("\"t\":\"event\""));
@@ -578,6 +579,7 @@ fn event_envelope_has_correct_ref_id() {
     let envelope = Envelope::Event {
         ref_id: ref_id.clone(),
         event: e,
+        seq: None,
     };
     let json_str = JsonlCodec::encode(&envelope).unwrap();
     let parsed: Value = serde_json::from_str(json_str.trim()).unwrap();
@@ -602,6 +604,7 @@ fn multiple_events_create_valid_jsonl_stream() {
         let envelope = Envelope::Event {
             ref_id: ref_id.clone(),
             event: e.clone(),
+            seq: None,
         };
         JsonlCodec::encode_to_writer(&mut buf, &envelope).unwrap();
     }
@@ -661,10 +664,12 @@ fn event_sequence_validation_via_jsonl() {
         event: ev(AgentEventKind::RunStarted {
             message: "go".into(),
         }),
+        seq: None,
     };
     let delta_event = Envelope::Event {
         ref_id: "r1".into(),
         event: delta("token"),
+        seq: None,
     };
     let receipt = ReceiptBuilder::new("mock")
         .outcome(Outcome::Complete)
@@ -1141,6 +1141,7 @@ fn given_receipt_with_verification_when_built_then_verification_preserved() {
             git_diff: Some("diff --git a/foo b/foo".into()),
             git_status: Some("M foo".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .build();
 
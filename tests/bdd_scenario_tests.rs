@@ -1012,6 +1012,8 @@ fn given_claude_request_when_converted_to_work_order_then_task_populated() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
 
     let wo = request_to_work_order(&request);
@@ -1009,6 +1009,7 @@ fn feature_sidecar_skips_hello_protocol_error() {
                     },
                     ext: None,
                 },
+                seq: None,
             };
             JsonlCodec::encode(&event_env).unwrap()
         });
@@ -1399,6 +1399,7 @@ fn given_receipt_builder_with_verification_when_built_then_reflected() {
         git_diff: Some("diff --git a/...".into()),
         git_status: Some("M src/lib.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let receipt = ReceiptBuilder::new("mock")
         .verification(verification)
@@ -1625,6 +1626,7 @@ fn given_event_envelope_when_ref_id_present_then_matches_run_id() {
     let envelope = Envelope::Event {
         ref_id: "run-42".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&envelope).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1468,6 +1468,7 @@ fn extra_given_verification_report_when_built_then_preserved() {
             git_diff: Some("diff output".into()),
             git_status: Some("M src/main.rs".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .build();
     assert!(receipt.verification.harness_ok);
@@ -720,6 +720,7 @@ fn story4_given_event_envelope_when_roundtripped_then_event_kind_preserved() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event,
+        seq: None,
     };
 
     // When: Encoded and decoded
@@ -727,7 +728,7 @@ fn story4_given_event_envelope_when_roundtripped_then_event_kind_preserved() {
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
 
     // Then: The event kind and text are preserved
-    if let Envelope::Event { ref_id, event } = decoded {
+    if let Envelope::Event { ref_id, event, .. } = decoded {
         assert_eq!(ref_id, "run-1");
         if let AgentEventKind::AssistantMessage { text } = &event.kind {
             assert_eq!(text, "Hello from sidecar");
@@ -1877,6 +1878,7 @@ fn story13_given_delta_event_when_encoded_as_envelope_then_preserves_text() {
     let env = Envelope::Event {
         ref_id: "stream-run-1".into(),
         event,
+        seq: None,
     };
 
     // When: Encoded to JSONL and decoded
@@ -1908,6 +1910,7 @@ fn story13_given_multiple_deltas_when_encoded_then_each_is_separate_jsonl_line()
             let env = Envelope::Event {
                 ref_id: "stream-1".into(),
                 event,
+                seq: None,
             };
             JsonlCodec::encode(&env).unwrap()
         })
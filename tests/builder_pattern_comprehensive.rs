@@ -541,6 +541,7 @@ fn receipt_builder_verification() {
         git_diff: Some("diff --git a/f b/f".into()),
         git_status: Some("M f".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let r = ReceiptBuilder::new("b").verification(v).build();
     assert!(r.verification.harness_ok);
@@ -936,7 +937,7 @@ fn event_builder_creates_event_envelope() {
     let evt = make_event("hello world");
     let env = EnvelopeBuilder::event(evt).ref_id("ref-1").build().unwrap();
     match &env {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "ref-1");
         }
         _ => panic!("expected Event"),
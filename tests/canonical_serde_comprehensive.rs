@@ -242,6 +242,7 @@ fn canonical_json_envelope_hello_identical_calls() {
         backend: mk_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let a = canonical_json(&env).unwrap();
     let b = canonical_json(&env).unwrap();
@@ -614,6 +615,7 @@ fn envelope_hello_roundtrip_stable() {
         backend: mk_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Passthrough,
+        features: None,
     };
     let j1 = canonical_json(&env).unwrap();
     let env2: Envelope = serde_json::from_str(&j1).unwrap();
@@ -638,6 +640,7 @@ fn envelope_event_roundtrip_stable() {
     let env = Envelope::Event {
         ref_id: uid1().to_string(),
         event: mk_event(AgentEventKind::AssistantDelta { text: "hi".into() }),
+        seq: None,
     };
     let j1 = canonical_json(&env).unwrap();
     let env2: Envelope = serde_json::from_str(&j1).unwrap();
@@ -924,6 +927,7 @@ fn envelope_discriminator_is_t_field() {
         backend: mk_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let v: Value = serde_json::to_value(&env).unwrap();
     assert_eq!(v["t"], "hello");
@@ -936,6 +940,7 @@ fn envelope_all_variants_t_field() {
         backend: mk_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let run = Envelope::Run {
         id: "r1".into(),
@@ -946,6 +951,7 @@ fn envelope_all_variants_t_field() {
         event: mk_event(AgentEventKind::RunStarted {
             message: "x".into(),
         }),
+        seq: None,
     };
     let fin = Envelope::Final {
         ref_id: "r1".into(),
@@ -1338,6 +1344,7 @@ fn verification_report_with_git_diff() {
         git_diff: Some("diff --git a/f.rs\n+new line".into()),
         git_status: Some("M f.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let j1 = canonical_json(&vr).unwrap();
     let vr2: VerificationReport = serde_json::from_str(&j1).unwrap();
@@ -1710,6 +1717,7 @@ fn full_receipt_roundtrip_and_hash() {
             git_diff: Some("diff content".into()),
             git_status: Some("M src/main.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -91,6 +91,7 @@ fn all_capabilities() -> Vec<Capability> {
         Capability::BatchMode,
         Capability::Embeddings,
         Capability::ImageGeneration,
+        Capability::ResponseLanguage,
     ]
 }
 
@@ -132,7 +133,7 @@ fn all_capability_variants_accounted() {
     let caps = all_capabilities();
     assert_eq!(
         caps.len(),
-        41,
+        42,
         "update all_capabilities() if variants added"
     );
 }
@@ -801,7 +802,7 @@ fn empty_capability_set() {
 #[test]
 fn all_capabilities_set() {
     let set: BTreeSet<Capability> = all_capabilities().into_iter().collect();
-    assert_eq!(set.len(), 41);
+    assert_eq!(set.len(), 42);
 }
 
 #[test]
@@ -1244,7 +1245,7 @@ fn manifest_all_capabilities_inserted() {
     for cap in all_capabilities() {
         m.insert(cap, SupportLevel::Native);
     }
-    assert_eq!(m.len(), 41);
+    assert_eq!(m.len(), 42);
 }
 
 // ===========================================================================
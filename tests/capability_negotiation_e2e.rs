@@ -214,6 +214,7 @@ impl Backend for CustomCapBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: Outcome::Complete,
             receipt_sha256: None,
@@ -1446,7 +1446,7 @@ fn invalid_toml_bare_value() {
 fn invalid_backend_type_unknown_variant() {
     let toml = r#"
         [backends.custom]
-        type = "grpc"
+        type = "carrier_pigeon"
         endpoint = "localhost:50051"
     "#;
     let err = parse_toml(toml).unwrap_err();
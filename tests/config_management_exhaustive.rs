@@ -29,7 +29,9 @@ fn minimal_valid_config() -> BackplaneConfig {
         bind_address: Some("127.0.0.1".into()),
         port: Some(8080),
         policy_profiles: vec![],
+        policy_presets: Vec::new(),
         backends: BTreeMap::from([("mock".into(), BackendEntry::Mock {})]),
+        rbac: None,
     }
 }
 
@@ -221,7 +221,7 @@ command = "node"
 fn toml_parse_unknown_backend_type() {
     let toml = r#"
 [backends.bad]
-type = "grpc"
+type = "carrier_pigeon"
 command = "node"
 "#;
     let err = parse_toml(toml).unwrap_err();
@@ -319,10 +319,12 @@ fn toml_roundtrip_serialize_deserialize() {
         bind_address: Some("0.0.0.0".into()),
         port: Some(9090),
         policy_profiles: vec!["p1.toml".into()],
+        policy_presets: Vec::new(),
         backends: BTreeMap::from([
             ("mock".into(), BackendEntry::Mock {}),
             ("sc".into(), sidecar_entry("node", vec!["h.js"], Some(120))),
         ]),
+        rbac: None,
     };
     let serialized = toml::to_string(&cfg).unwrap();
     let deserialized: BackplaneConfig = toml::from_str(&serialized).unwrap();
@@ -463,10 +465,12 @@ fn json_toml_roundtrip_produces_same_config() {
         bind_address: Some("127.0.0.1".into()),
         port: Some(8080),
         policy_profiles: vec!["p.toml".into()],
+        policy_presets: Vec::new(),
         backends: BTreeMap::from([
             ("mock".into(), BackendEntry::Mock {}),
             ("sc".into(), sidecar_entry("node", vec!["h.js"], Some(120))),
         ]),
+        rbac: None,
     };
     let json_str = serde_json::to_string(&cfg).unwrap();
     let from_json: BackplaneConfig = serde_json::from_str(&json_str).unwrap();
@@ -1235,7 +1239,9 @@ fn merge_override_single_field_others_unchanged() {
         bind_address: Some("127.0.0.1".into()),
         port: Some(8080),
         policy_profiles: vec!["p.toml".into()],
+        policy_presets: Vec::new(),
         backends: BTreeMap::from([("mock".into(), BackendEntry::Mock {})]),
+        rbac: None,
     };
     let overlay = BackplaneConfig {
         log_level: Some("trace".into()),
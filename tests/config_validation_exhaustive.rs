@@ -1030,10 +1030,12 @@ fn toml_roundtrip_full_config() {
         bind_address: Some("127.0.0.1".into()),
         port: Some(3000),
         policy_profiles: vec!["p1.toml".into()],
+        policy_presets: Vec::new(),
         backends: BTreeMap::from([
             ("m".into(), BackendEntry::Mock {}),
             ("sc".into(), sidecar_entry("node", &["a.js"], Some(60))),
         ]),
+        rbac: None,
     };
     let s = toml::to_string(&cfg).unwrap();
     let d: BackplaneConfig = toml::from_str(&s).unwrap();
@@ -1642,7 +1644,9 @@ fn config_with_all_valid_fields_passes() {
         bind_address: Some("127.0.0.1".into()),
         port: Some(3000),
         policy_profiles: vec!["Cargo.toml".into()],
+        policy_presets: Vec::new(),
         backends: BTreeMap::from([("mock".into(), BackendEntry::Mock {})]),
+        rbac: None,
     };
     let warnings = validate_config(&cfg).unwrap();
     // No missing-optional-field warnings since both default_backend and receipts_dir are set
@@ -357,6 +357,7 @@ fn envelope_event_tag_is_t() {
         event: make_event(AgentEventKind::RunStarted {
             message: "hi".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&evt).unwrap();
     assert!(json.contains(r#""t":"event""#));
@@ -437,6 +438,7 @@ fn envelope_event_ref_id_correlation() {
     let evt = Envelope::Event {
         ref_id: run_id.into(),
         event: make_event(AgentEventKind::AssistantDelta { text: "hi".into() }),
+        seq: None,
     };
     if let Envelope::Event { ref_id, .. } = &evt {
         assert_eq!(ref_id, run_id);
@@ -581,6 +583,7 @@ fn envelope_event_all_agent_event_kinds() {
         let evt = Envelope::Event {
             ref_id: "run-x".into(),
             event: make_event(kind),
+            seq: None,
         };
         let json = serde_json::to_string(&evt).unwrap();
         assert!(json.contains(r#""t":"event""#));
@@ -730,6 +733,9 @@ fn work_order_config_vendor_btreemap() {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .build();
     assert!(wo.config.vendor.contains_key("abp"));
@@ -917,6 +923,7 @@ fn serde_roundtrip_envelope_event() {
         event: make_event(AgentEventKind::Warning {
             message: "warn".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&evt).unwrap();
     let evt2: Envelope = serde_json::from_str(&json).unwrap();
@@ -981,6 +988,9 @@ fn serde_roundtrip_runtime_config_btreemap() {
         env: BTreeMap::new(),
         max_budget_usd: Some(5.0),
         max_turns: Some(20),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json1 = serde_json::to_string(&cfg).unwrap();
     let cfg2: RuntimeConfig = serde_json::from_str(&json1).unwrap();
@@ -1124,6 +1134,7 @@ fn jsonl_codec_roundtrip_event_envelope() {
         event: make_event(AgentEventKind::AssistantMessage {
             text: "hello".into(),
         }),
+        seq: None,
     };
     let line = JsonlCodec::encode(&evt).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -1275,6 +1286,7 @@ fn serde_roundtrip_verification_report() {
         git_diff: Some("diff --git ...".into()),
         git_status: Some("M src/main.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let json = serde_json::to_string(&vr).unwrap();
     let vr2: VerificationReport = serde_json::from_str(&json).unwrap();
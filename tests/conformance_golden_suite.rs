@@ -131,6 +131,7 @@ fn fixed_receipt(outcome: Outcome, mode: ExecutionMode) -> Receipt {
             git_diff: Some("+added line".into()),
             git_status: Some("M src/main.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome,
         receipt_sha256: None,
@@ -176,6 +177,9 @@ fn fixed_work_order(mode: WorkspaceMode) -> WorkOrder {
             env: BTreeMap::new(),
             max_budget_usd: Some(1.0),
             max_turns: Some(10),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -242,6 +246,7 @@ fn golden_full_session_transcript() {
             },
             ext: None,
         },
+        seq: None,
     };
     let event2 = Envelope::Event {
         ref_id: "run-001".into(),
@@ -252,6 +257,7 @@ fn golden_full_session_transcript() {
             },
             ext: None,
         },
+        seq: None,
     };
     let final_env = Envelope::Final {
         ref_id: "run-001".into(),
@@ -539,6 +545,7 @@ fn golden_envelope_event() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -134,6 +134,7 @@ fn make_event_envelope(ref_id: &str) -> Envelope {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -495,6 +495,7 @@ mod envelope_structure {
         let env = Envelope::Event {
             ref_id: "run-1".into(),
             event: make_event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+            seq: None,
         };
         let json = serde_json::to_string(&env).unwrap();
         assert!(json.contains("\"t\":\"event\""));
@@ -571,6 +572,7 @@ mod envelope_structure {
             event: make_event(AgentEventKind::RunStarted {
                 message: "go".into(),
             }),
+            seq: None,
         };
         let json = serde_json::to_string(&env).unwrap();
         assert!(json.contains("\"ref_id\":\"ref-123\""));
@@ -1011,6 +1013,7 @@ mod jsonl_codec {
         let env = Envelope::Event {
             ref_id: "r1".into(),
             event: make_event(AgentEventKind::AssistantMessage { text: "yo".into() }),
+            seq: None,
         };
         let line = JsonlCodec::encode(&env).unwrap();
         let back = JsonlCodec::decode(line.trim()).unwrap();
@@ -1369,6 +1372,7 @@ mod sidecar_handshake {
         let event = Envelope::Event {
             ref_id: run_id.clone(),
             event: make_event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+            seq: None,
         };
         let final_env = Envelope::Final {
             ref_id: run_id,
@@ -1461,6 +1465,7 @@ mod sidecar_handshake {
         let event = Envelope::Event {
             ref_id: "wrong-id".into(),
             event: make_event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+            seq: None,
         };
         let final_env = Envelope::Final {
             ref_id: "run-1".into(),
@@ -1520,6 +1525,7 @@ mod sidecar_handshake {
             },
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let validator = EnvelopeValidator::new();
         let result = validator.validate(&env);
@@ -1537,6 +1543,7 @@ mod sidecar_handshake {
             },
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let validator = EnvelopeValidator::new();
         let result = validator.validate(&env);
@@ -1573,6 +1580,7 @@ mod sidecar_handshake {
             event: make_event(AgentEventKind::RunStarted {
                 message: "go".into(),
             }),
+            seq: None,
         };
         let validator = EnvelopeValidator::new();
         let result = validator.validate(&env);
@@ -2034,6 +2042,7 @@ mod receipt_builder_tests {
             git_diff: Some("diff".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         };
         let r = ReceiptBuilder::new("mock").verification(v).build();
         assert!(r.verification.harness_ok);
@@ -84,6 +84,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind, offset_ms: i64) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -367,7 +368,7 @@ fn envelope_round_trip_event() {
     let json = JsonlCodec::encode(&original).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "r1");
             assert!(matches!(event.kind, AgentEventKind::AssistantDelta { .. }));
         }
@@ -464,6 +465,7 @@ fn handshake_hello_backend_id_required() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&hello);
     assert!(!result.valid, "empty backend.id should be invalid");
@@ -485,6 +487,7 @@ fn handshake_hello_contract_version_required() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&hello);
     assert!(!result.valid);
@@ -506,6 +509,7 @@ fn handshake_hello_bad_version_format() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&hello);
     assert!(!result.valid);
@@ -827,6 +831,7 @@ fn lifecycle_event_validator_checks_empty_ref_id() {
             },
             ext: None,
         },
+        seq: None,
     };
     let result = validator.validate(&event);
     assert!(!result.valid, "empty ref_id should be invalid");
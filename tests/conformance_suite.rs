@@ -817,6 +817,7 @@ fn protocol_envelope_t_values() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = serde_json::to_value(&event).unwrap();
     assert_eq!(json["t"], "event");
@@ -883,6 +884,7 @@ fn protocol_event_carries_valid_agent_event() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = serde_json::to_value(&event).unwrap();
     assert!(json["event"].is_object());
@@ -951,6 +953,7 @@ fn protocol_jsonl_one_line_per_envelope() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r1".into(),
@@ -982,6 +985,7 @@ fn protocol_ref_id_correlation() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
@@ -990,6 +994,7 @@ fn protocol_ref_id_correlation() {
                 kind: AgentEventKind::AssistantMessage { text: "msg".into() },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: run_id.into(),
@@ -1030,6 +1035,7 @@ fn protocol_validator_sequence_hello_not_first() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::hello(test_backend(), test_capabilities()),
     ];
@@ -1063,6 +1069,7 @@ fn protocol_all_variants_roundtrip() {
                 kind: AgentEventKind::AssistantMessage { text: "hi".into() },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r1".into(),
@@ -1126,6 +1133,7 @@ fn handshake_hello_must_include_contract_version() {
         backend: test_backend(),
         capabilities: test_capabilities(),
         mode: abp_core::ExecutionMode::default(),
+        features: None,
     };
     let result = v.validate(&hello);
     assert!(
@@ -1155,6 +1163,7 @@ fn handshake_hello_must_include_backend_identity() {
         },
         capabilities: test_capabilities(),
         mode: abp_core::ExecutionMode::default(),
+        features: None,
     };
     let result = v.validate(&hello);
     assert!(!result.valid, "empty backend.id should fail validation");
@@ -1198,6 +1207,7 @@ fn handshake_reject_wrong_contract_version() {
         backend: test_backend(),
         capabilities: test_capabilities(),
         mode: abp_core::ExecutionMode::default(),
+        features: None,
     };
     let result = v.validate(&hello);
     assert!(
@@ -1322,6 +1332,7 @@ fn workorder_ref_id_propagated_to_events_and_final() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: "run-A".into(),
@@ -1455,6 +1466,7 @@ fn event_text_delta_preserves_content() {
             kind: AgentEventKind::AssistantDelta { text: text.into() },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -1485,6 +1497,7 @@ fn event_tool_call_includes_name_and_arguments() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -1520,6 +1533,7 @@ fn event_error_includes_message() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -1547,6 +1561,7 @@ fn event_must_reference_run_ref_id() {
             },
             ext: None,
         },
+        seq: None,
     };
     let result = v.validate(&env);
     assert!(
@@ -1568,6 +1583,7 @@ fn event_empty_payloads_are_valid() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim());
@@ -1582,6 +1598,7 @@ fn event_empty_payloads_are_valid() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&delta).unwrap();
     assert!(
@@ -1604,6 +1621,7 @@ fn event_multiple_events_stream() {
                 },
                 ext: None,
             },
+            seq: None,
         })
         .collect();
 
@@ -1626,6 +1644,7 @@ fn event_ordering_preserved() {
                 kind: AgentEventKind::AssistantDelta { text: m.clone() },
                 ext: None,
             },
+            seq: None,
         })
         .collect();
 
@@ -1815,6 +1834,7 @@ fn receipt_fatal_terminates_run() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Fatal {
             ref_id: Some("r1".into()),
@@ -2191,6 +2211,7 @@ fn sequence_valid_hello_run_event_final() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r1".into(),
@@ -2261,6 +2282,7 @@ fn sequence_missing_terminal_detected() {
                 },
                 ext: None,
             },
+            seq: None,
         },
     ];
     let errors = v.validate_sequence(&envelopes);
@@ -2427,6 +2449,7 @@ fn protocol_no_type_field_at_envelope_level() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r1".into(),
@@ -167,6 +167,7 @@ fn core_to_protocol_jsonl_stream_roundtrip() {
     let ev = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let fin = Envelope::Final {
         ref_id: "r1".into(),
@@ -235,6 +236,7 @@ fn core_to_protocol_event_kinds_preserved_in_envelope() {
         let envelope = Envelope::Event {
             ref_id: "r1".into(),
             event: ev,
+            seq: None,
         };
         let line = JsonlCodec::encode(&envelope).unwrap();
         let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -400,6 +402,7 @@ fn protocol_simulated_sidecar_roundtrip() {
         let env = Envelope::Event {
             ref_id: wo_id.to_string(),
             event: ev.clone(),
+            seq: None,
         };
         JsonlCodec::encode_to_writer(&mut buf, &env).unwrap();
     }
@@ -1055,6 +1058,7 @@ fn receipt_builder_produces_valid_receipt() {
             git_diff: Some("diff".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .add_trace_event(make_event(AgentEventKind::RunStarted {
             message: "go".into(),
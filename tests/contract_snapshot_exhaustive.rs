@@ -225,6 +225,9 @@ fn exhaustive_work_order_full() {
             },
             max_budget_usd: Some(5.0),
             max_turns: Some(50),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     insta::assert_json_snapshot!(wo);
@@ -333,6 +336,9 @@ fn exhaustive_work_order_with_vendor_config() {
             env: BTreeMap::new(),
             max_budget_usd: Some(100.0),
             max_turns: Some(200),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     insta::assert_json_snapshot!(wo);
@@ -366,6 +372,9 @@ fn exhaustive_work_order_with_env() {
             },
             max_budget_usd: None,
             max_turns: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     insta::assert_json_snapshot!(wo);
@@ -525,6 +534,7 @@ fn exhaustive_receipt_with_extensions() {
         git_diff: Some("diff --git a/src/auth.rs b/src/auth.rs\n+pub fn validate() {}".into()),
         git_status: Some("M src/auth.rs\nA src/auth_test.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     r.artifacts = vec![
         ArtifactRef {
@@ -613,6 +623,7 @@ fn exhaustive_receipt_builder_full() {
             git_diff: Some("diff".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .add_trace_event(AgentEvent {
             ts: ts(),
@@ -940,6 +951,7 @@ fn exhaustive_envelope_event_delta() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -958,6 +970,7 @@ fn exhaustive_envelope_event_tool_call() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -974,6 +987,7 @@ fn exhaustive_envelope_event_file_changed() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -1056,6 +1070,8 @@ fn exhaustive_config_full() {
             "profiles/default.json".into(),
         ],
         backends,
+        policy_presets: Vec::new(),
+        rbac: None,
     };
     insta::assert_json_snapshot!(cfg);
 }
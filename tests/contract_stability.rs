@@ -86,6 +86,7 @@ fn contract_version_in_hello_envelope() {
         },
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let json = serde_json::to_value(&hello).unwrap();
     assert_eq!(json["contract_version"], CONTRACT_VERSION);
@@ -432,6 +433,7 @@ fn envelope_hello_uses_t_discriminator() {
         },
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let json = serde_json::to_value(&hello).unwrap();
     assert_eq!(json["t"], "hello", "Envelope discriminator must be 't'");
@@ -462,6 +464,7 @@ fn envelope_event_serializes_correctly() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event: evt,
+        seq: None,
     };
     let json = serde_json::to_value(&env).unwrap();
     assert_eq!(json["t"], "event");
@@ -505,6 +508,7 @@ fn all_envelope_variants_roundtrip() {
             },
             capabilities: BTreeMap::new(),
             mode: ExecutionMode::Mapped,
+            features: None,
         },
         Envelope::Run {
             id: "r".into(),
@@ -519,6 +523,7 @@ fn all_envelope_variants_roundtrip() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r".into(),
@@ -2033,6 +2033,7 @@ fn receipt_with_verification_roundtrips() {
         git_diff: Some("diff --git ...".into()),
         git_status: Some("M src/main.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let json = serde_json::to_string(&r).unwrap();
     let r2: Receipt = serde_json::from_str(&json).unwrap();
@@ -564,6 +564,7 @@ fn envelope_event_uses_t_discriminator() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     assert!(json.contains(r#""t":"event""#));
@@ -628,6 +629,7 @@ fn envelope_event_has_ref_id() {
     let env = Envelope::Event {
         ref_id: "r-42".into(),
         event,
+        seq: None,
     };
     let v: serde_json::Value = serde_json::to_value(&env).unwrap();
     assert_eq!(v["ref_id"], "r-42");
@@ -926,6 +928,9 @@ fn runtime_config_vendor_uses_btreemap() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json = serde_json::to_string(&config).unwrap();
     assert!(json.find("a_vendor").unwrap() < json.find("z_vendor").unwrap());
@@ -105,6 +105,9 @@ fn sample_runtime_config() -> RuntimeConfig {
         env: BTreeMap::new(),
         max_budget_usd: Some(1.0),
         max_turns: Some(10),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     }
 }
 
@@ -124,6 +127,7 @@ fn sample_verification_report() -> VerificationReport {
         git_diff: Some("diff --git a/f b/f".into()),
         git_status: Some("M f".into()),
         harness_ok: true,
+        input_digest: None,
     }
 }
 
@@ -1190,6 +1190,7 @@ fn envelope_event_shape() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event,
+        seq: None,
     };
     let v: Value = serde_json::to_value(&env).unwrap();
     assert_eq!(v["t"], "event");
@@ -1242,6 +1243,7 @@ fn envelope_variant_tags_stable() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r".into(),
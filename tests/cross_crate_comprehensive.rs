@@ -455,6 +455,7 @@ mod protocol_receipt_hash {
         let env = Envelope::Event {
             ref_id: "run-1".into(),
             event,
+            seq: None,
         };
         let line = JsonlCodec::encode(&env).unwrap();
         assert!(line.contains("\"t\":\"event\""));
@@ -721,6 +722,7 @@ mod full_pipeline {
             .map(|e| Envelope::Event {
                 ref_id: wo.id.to_string(),
                 event: e.clone(),
+                seq: None,
             })
             .collect();
 
@@ -843,6 +845,7 @@ mod full_pipeline {
                 },
                 ext: None,
             },
+            seq: None,
         };
 
         // 4. Final
@@ -184,6 +184,7 @@ fn core_agent_event_survives_protocol_roundtrip() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event: evt,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -236,6 +237,7 @@ fn hello_envelope_roundtrip() {
         backend: sample_backend_identity(),
         capabilities: sample_capability_manifest(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -785,6 +787,7 @@ fn event_run_started_through_protocol() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event: evt,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -808,6 +811,7 @@ fn event_tool_call_with_optional_fields() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event: evt,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let rt = JsonlCodec::decode(json.trim()).unwrap();
@@ -838,6 +842,7 @@ fn event_file_changed_through_protocol() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event: evt,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -862,6 +867,7 @@ fn event_error_with_code() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event: evt,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -896,6 +902,7 @@ fn event_with_ext_data() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event: evt,
+        seq: None,
     };
     let json_str = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json_str.trim()).unwrap();
@@ -1142,6 +1149,7 @@ fn serde_verification_report_roundtrip() {
         git_diff: Some("diff --git ...".into()),
         git_status: Some("M src/main.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let json = serde_json::to_string(&report).unwrap();
     let rt: VerificationReport = serde_json::from_str(&json).unwrap();
@@ -1250,6 +1258,7 @@ fn receipt_builder_with_verification() {
             git_diff: Some("diff".into()),
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         })
         .build();
     assert!(receipt.verification.harness_ok);
@@ -1434,6 +1443,7 @@ fn protocol_decode_stream() {
         let env = Envelope::Event {
             ref_id: "r1".into(),
             event: evt.clone(),
+            seq: None,
         };
         let line = JsonlCodec::encode(&env).unwrap();
         buf.extend_from_slice(line.as_bytes());
@@ -1686,6 +1696,7 @@ fn receipt_with_all_fields_through_protocol() {
             git_diff: Some("diff".into()),
             git_status: Some("clean".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .add_trace_event(make_event(AgentEventKind::RunStarted {
             message: "go".into(),
@@ -1742,6 +1753,7 @@ fn multiple_events_in_sequence_through_protocol() {
         let env = Envelope::Event {
             ref_id: "r1".into(),
             event: evt.clone(),
+            seq: None,
         };
         let line = JsonlCodec::encode(&env).unwrap();
         encoded.extend_from_slice(line.as_bytes());
@@ -288,6 +288,7 @@ impl Backend for MultiEventBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: Outcome::Complete,
             receipt_sha256: None,
@@ -345,6 +346,7 @@ mod core_types_flow {
         let env = Envelope::Event {
             ref_id: "run-1".into(),
             event: evt,
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -389,6 +391,7 @@ mod core_types_flow {
             backend: sample_backend_identity(),
             capabilities: sample_capability_manifest(),
             mode: ExecutionMode::Mapped,
+            features: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -398,6 +401,7 @@ mod core_types_flow {
                 capabilities,
                 contract_version,
                 mode,
+                ..
             } => {
                 assert_eq!(backend.id, "test-backend");
                 assert!(capabilities.contains_key(&Capability::Streaming));
@@ -478,6 +482,7 @@ mod core_types_flow {
             let env = Envelope::Event {
                 ref_id: "test".into(),
                 event: evt,
+                seq: None,
             };
             let json = JsonlCodec::encode(&env).unwrap();
             let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -614,6 +619,7 @@ mod contract_version {
             },
             capabilities: BTreeMap::new(),
             mode: ExecutionMode::Mapped,
+            features: None,
         };
         assert!(abp_validate::validate_hello_version(&env).is_err());
     }
@@ -2292,6 +2298,7 @@ mod validate_integration {
             },
             capabilities: BTreeMap::new(),
             mode: ExecutionMode::Mapped,
+            features: None,
         };
         assert!(abp_validate::validate_hello_version(&invalid).is_err());
     }
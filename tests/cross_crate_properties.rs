@@ -207,8 +207,11 @@ fn arb_envelope() -> impl Strategy<Value = Envelope> {
         arb_backend_identity().prop_map(|bi| Envelope::hello(bi, CapabilityManifest::new())),
         (arb_safe_string(), arb_work_order())
             .prop_map(|(id, wo)| Envelope::Run { id, work_order: wo }),
-        (arb_safe_string(), arb_agent_event())
-            .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event }),
+        (arb_safe_string(), arb_agent_event()).prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        }),
         (arb_safe_string(), arb_safe_string()).prop_map(|(ref_id, error)| Envelope::Fatal {
             ref_id: Some(ref_id),
             error,
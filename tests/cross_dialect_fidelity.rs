@@ -315,6 +315,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         }
     }
 
@@ -341,6 +343,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
 
         let claude_req = abp_shim_claude::request_to_claude(&req);
@@ -71,6 +71,8 @@ fn claude_request_simple(system: &str, user: &str) -> abp_shim_claude::MessageRe
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     }
 }
 
@@ -975,6 +977,8 @@ mod sampling_parameters {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let wo = abp_shim_claude::request_to_work_order(&req);
 
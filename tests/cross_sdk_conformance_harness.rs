@@ -369,6 +369,8 @@ mod claude_passthrough {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         }
     }
 
@@ -157,6 +157,8 @@ fn make_claude_request() -> abp_shim_claude::MessageRequest {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     }
 }
 
@@ -73,6 +73,8 @@ fn claude_request_simple(text: &str) -> abp_shim_claude::MessageRequest {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     }
 }
 
@@ -494,6 +496,8 @@ fn multi_turn_claude_messages_convert_to_ir() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
 
     // Should produce a valid work order
@@ -862,3 +866,93 @@ fn gemini_usage_ir_roundtrip_is_lossless() {
 
     assert_eq!(original, back);
 }
+
+// =========================================================================
+// Sessions shared across shims
+// =========================================================================
+
+async fn run_in_session(
+    sessions: &abp_runtime::session::SessionManager,
+    id: uuid::Uuid,
+    mut wo: abp_core::WorkOrder,
+) -> abp_core::WorkOrder {
+    use tokio_stream::StreamExt;
+
+    sessions.prepare(id, &mut wo).unwrap();
+    wo.workspace.mode = abp_core::WorkspaceMode::PassThrough;
+    let rt = abp_runtime::Runtime::with_default_backends();
+    let handle = rt.run_streaming("mock", wo.clone()).await.unwrap();
+    let _: Vec<_> = handle.events.collect().await;
+    let receipt = handle.receipt.await.unwrap().unwrap();
+    sessions.record_receipt(&receipt).unwrap();
+    wo
+}
+
+#[tokio::test]
+async fn claude_continues_session_started_through_openai() {
+    let sessions = abp_runtime::session::SessionManager::new();
+    let id = sessions.create();
+
+    let openai = abp_shim_openai::request_to_work_order(&openai_request_simple("Plan a refactor"));
+    run_in_session(&sessions, id, openai).await;
+    let after_openai = sessions.get(id).unwrap().conversation().len();
+    // System prompt, user turn, and the mock backend's two replies.
+    assert_eq!(after_openai, 4);
+
+    let claude = abp_shim_claude::request_to_work_order(&claude_request_simple("Now do step one"));
+    let sent = run_in_session(&sessions, id, claude).await;
+
+    let history = abp_backend_core::extract_conversation(&sent).unwrap();
+    assert_eq!(history.len(), after_openai + 1);
+    assert_eq!(history.messages[0].role, IrRole::System);
+    assert_eq!(history.messages[1].text_content(), "Plan a refactor");
+    assert_eq!(
+        history.last_message().unwrap().text_content(),
+        "Now do step one"
+    );
+    assert_eq!(sessions.get(id).unwrap().runs().len(), 2);
+}
+
+#[tokio::test]
+async fn resent_history_is_not_duplicated() {
+    let sessions = abp_runtime::session::SessionManager::new();
+    let id = sessions.create();
+    let first = openai_request_simple("hello");
+    run_in_session(
+        &sessions,
+        id,
+        abp_shim_openai::request_to_work_order(&first),
+    )
+    .await;
+
+    // The caller re-sends the whole conversation plus one new turn.
+    let mut messages = first.messages.clone();
+    messages.push(abp_shim_openai::Message::user("and again"));
+    let second = abp_shim_openai::ChatCompletionRequest::builder()
+        .model("gpt-4o")
+        .messages(messages)
+        .build();
+    let sent = run_in_session(
+        &sessions,
+        id,
+        abp_shim_openai::request_to_work_order(&second),
+    )
+    .await;
+
+    let history = abp_backend_core::extract_conversation(&sent).unwrap();
+    let users: Vec<_> = history
+        .messages
+        .iter()
+        .filter(|m| m.role == IrRole::User)
+        .map(|m| m.text_content())
+        .collect();
+    assert_eq!(users, ["hello", "and again"]);
+    assert_eq!(
+        history
+            .messages
+            .iter()
+            .filter(|m| m.role == IrRole::System)
+            .count(),
+        1
+    );
+}
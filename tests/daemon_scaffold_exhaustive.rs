@@ -902,7 +902,9 @@ fn config_serde_roundtrip() {
         bind_address: Some("0.0.0.0".into()),
         port: Some(9999),
         policy_profiles: vec!["default.toml".into()],
+        policy_presets: Vec::new(),
         backends: BTreeMap::new(),
+        rbac: None,
     };
     let json = serde_json::to_string(&cfg).unwrap();
     let back: BackplaneConfig = serde_json::from_str(&json).unwrap();
@@ -109,6 +109,9 @@ fn make_work_order() -> WorkOrder {
             ]),
             max_budget_usd: Some(1.5),
             max_turns: Some(10),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -166,6 +169,7 @@ fn make_receipt() -> Receipt {
             git_diff: Some("diff --git a/file b/file".into()),
             git_status: Some("M file".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -108,6 +108,9 @@ fn make_work_order() -> WorkOrder {
             ]),
             max_budget_usd: Some(1.5),
             max_turns: Some(10),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -165,6 +168,7 @@ fn make_receipt() -> Receipt {
             git_diff: Some("diff --git a/file b/file".into()),
             git_status: Some("M file".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -597,6 +601,9 @@ fn runtime_config_nested_vendor_map_deterministic() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
 
     let a = canonical_json(&cfg).unwrap();
@@ -615,6 +622,9 @@ fn runtime_config_nested_maps_keys_sorted() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
 
     let json = canonical_json(&cfg).unwrap();
@@ -652,6 +662,9 @@ fn deeply_nested_config_determinism() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
 
     let a = canonical_json(&cfg).unwrap();
@@ -675,6 +688,9 @@ fn config_with_all_fields_populated() {
         env,
         max_budget_usd: Some(10.0),
         max_turns: Some(20),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
 
     let json1 = canonical_json(&cfg).unwrap();
@@ -1112,6 +1128,9 @@ proptest! {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         };
         let a = canonical_json(&cfg).unwrap();
         let b = canonical_json(&cfg).unwrap();
@@ -1207,6 +1226,9 @@ proptest! {
                 env: BTreeMap::new(),
                 max_budget_usd: None,
                 max_turns: None,
+                response_language: None,
+                fidelity_policy: None,
+                submitter: None,
             },
         };
 
@@ -1244,6 +1266,9 @@ proptest! {
                 env: BTreeMap::new(),
                 max_budget_usd: None,
                 max_turns: None,
+                response_language: None,
+                fidelity_policy: None,
+                submitter: None,
             },
         };
 
@@ -1313,6 +1338,9 @@ fn large_vendor_map_determinism() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
 
     let a = canonical_json(&cfg).unwrap();
@@ -1333,6 +1361,9 @@ fn large_env_map_determinism() {
         env,
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
 
     let a = canonical_json(&cfg).unwrap();
@@ -1464,6 +1495,9 @@ fn unicode_vendor_keys_deterministic() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
 
     let a = canonical_json(&cfg).unwrap();
@@ -1485,6 +1519,9 @@ fn unicode_vendor_keys_sorted_by_codepoint() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     })
     .unwrap();
 
@@ -1535,6 +1572,9 @@ fn unicode_emoji_sequences_deterministic() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
 
     let a = canonical_json(&cfg).unwrap();
@@ -1554,6 +1594,9 @@ fn unicode_escape_sequences_in_json() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
 
     let a = canonical_json(&cfg).unwrap();
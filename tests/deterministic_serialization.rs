@@ -925,6 +925,7 @@ fn verification_report_optional_none_git_diff() {
         git_diff: None,
         git_status: None,
         harness_ok: false,
+        input_digest: None,
     };
     let json = serde_json::to_string(&v).unwrap();
     assert!(json.contains("\"git_diff\":null"));
@@ -1080,6 +1081,7 @@ fn deeply_nested_receipt_deterministic() {
         git_diff: Some("+fn new_func() {}".into()),
         git_status: Some("M src/lib.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     assert_roundtrip_deterministic(&r);
 }
@@ -1299,6 +1301,7 @@ fn protocol_envelope_event_roundtrip() {
     let env = abp_protocol::Envelope::Event {
         ref_id: "run-1".into(),
         event: make_event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+        seq: None,
     };
     assert_roundtrip_deterministic(&env);
 }
@@ -1394,6 +1397,7 @@ fn verification_report_roundtrip() {
         git_diff: Some("+new line".into()),
         git_status: Some("M file.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     assert_roundtrip_deterministic(&v);
 }
@@ -286,6 +286,7 @@ fn doc_receipt_builder_verification() {
         git_diff: Some("diff output".into()),
         git_status: Some("M src/lib.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let receipt = ReceiptBuilder::new("mock").verification(v).build();
     assert!(receipt.verification.harness_ok);
@@ -797,6 +798,7 @@ fn doc_protocol_flow_hello_run_events_final() {
             },
             ext: None,
         },
+        seq: None,
     };
 
     let receipt = ReceiptBuilder::new("test-sidecar")
@@ -890,6 +892,7 @@ fn doc_validator_hello_empty_backend_id() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let v = EnvelopeValidator::new();
     let result = v.validate(&hello);
@@ -913,6 +916,7 @@ fn doc_validator_hello_invalid_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let v = EnvelopeValidator::new();
     let result = v.validate(&hello);
@@ -950,6 +954,7 @@ fn doc_validator_sequence_valid() {
                 kind: AgentEventKind::AssistantMessage { text: "hi".into() },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: run_id,
@@ -1566,6 +1566,9 @@ async fn config_override_custom_runtime_config() {
         env: BTreeMap::new(),
         max_budget_usd: Some(5.0),
         max_turns: Some(10),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
 
     let wo = WorkOrderBuilder::new("custom config")
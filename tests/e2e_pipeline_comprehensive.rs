@@ -949,7 +949,8 @@ fn backend_registry_list() {
     let mut rt = Runtime::new();
     rt.register_backend("a", MockBackend);
     rt.register_backend("b", MockBackend);
-    let list = rt.registry().list();
+    let registry = rt.registry();
+    let list = registry.list();
     assert_eq!(list.len(), 2);
 }
 
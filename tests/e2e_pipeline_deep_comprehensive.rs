@@ -198,6 +198,7 @@ impl Backend for ConfigurableBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: self.outcome.clone(),
             receipt_sha256: None,
@@ -301,6 +302,7 @@ impl Backend for SlowBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: Outcome::Complete,
             receipt_sha256: None,
@@ -377,6 +379,7 @@ impl Backend for CountingBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: Outcome::Complete,
             receipt_sha256: None,
@@ -196,13 +196,16 @@ async fn handshake_reports_contract_version() {
 }
 
 #[tokio::test]
-async fn handshake_wrong_version_is_preserved() {
+async fn handshake_wrong_version_is_rejected() {
     let py = require_python!();
-    let client = SidecarClient::spawn(mock_spec_with_mode(&py, "wrong_version"))
+    let err = SidecarClient::spawn(mock_spec_with_mode(&py, "wrong_version"))
         .await
-        .expect("spawn should succeed");
+        .expect_err("spawn should reject an incompatible contract version");
 
-    assert_eq!(client.hello.contract_version, "abp/v999.0");
+    assert!(
+        matches!(err, HostError::Protocol(_)),
+        "expected Protocol error, got: {err}"
+    );
 }
 
 #[tokio::test]
@@ -97,6 +97,7 @@ fn make_receipt(
             git_diff: None,
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         },
         outcome,
         receipt_sha256: None,
@@ -210,6 +211,7 @@ impl Backend for StreamingBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: Outcome::Complete,
             receipt_sha256: None,
@@ -297,6 +299,7 @@ impl Backend for ToolCallBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: Outcome::Complete,
             receipt_sha256: None,
@@ -369,6 +372,7 @@ impl Backend for PartialBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: Outcome::Partial,
             receipt_sha256: None,
@@ -134,7 +134,9 @@ fn suggested_http_status(code: &ErrorCode) -> u16 {
         | ErrorCode::CapabilityEmulationFailed => 424,
 
         // 429 Too Many Requests
-        ErrorCode::BackendRateLimited | ErrorCode::RateLimitExceeded => 429,
+        ErrorCode::BackendRateLimited | ErrorCode::RateLimitExceeded | ErrorCode::QuotaExceeded => {
+            429
+        }
 
         // 502 Bad Gateway — upstream backend error
         ErrorCode::BackendCrashed | ErrorCode::BackendUnavailable => 502,
@@ -1512,10 +1512,14 @@ fn error_event_in_envelope_roundtrip() {
     let envelope = Envelope::Event {
         ref_id: "run-1".into(),
         event: event.clone(),
+        seq: None,
     };
     let line = JsonlCodec::encode(&envelope).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
-    if let Envelope::Event { ref_id, event: ev } = decoded {
+    if let Envelope::Event {
+        ref_id, event: ev, ..
+    } = decoded
+    {
         assert_eq!(ref_id, "run-1");
         if let AgentEventKind::Error { error_code, .. } = &ev.kind {
             assert_eq!(*error_code, Some(ErrorCode::IrInvalid));
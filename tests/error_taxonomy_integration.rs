@@ -722,6 +722,7 @@ fn full_error_propagation_scenario() {
     let envelope = Envelope::Event {
         ref_id: "run-789".into(),
         event: error_event.clone(),
+        seq: None,
     };
     let json = JsonlCodec::encode(&envelope).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
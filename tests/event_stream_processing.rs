@@ -779,6 +779,9 @@ fn type_tag(kind: &AgentEventKind) -> &'static str {
         AgentEventKind::ToolResult { .. } => "tool_result",
         AgentEventKind::FileChanged { .. } => "file_changed",
         AgentEventKind::CommandExecuted { .. } => "command_executed",
+        AgentEventKind::Progress { .. } => "progress",
+        AgentEventKind::RunSummary { .. } => "run_summary",
+        AgentEventKind::ArtifactChunk { .. } => "artifact_chunk",
         AgentEventKind::Warning { .. } => "warning",
         AgentEventKind::Error { .. } => "error",
     }
@@ -430,6 +430,7 @@ fn wrap_event_in_envelope() {
     let envelope = Envelope::Event {
         ref_id: "run-1".into(),
         event: ev,
+        seq: None,
     };
     assert!(matches!(envelope, Envelope::Event { .. }));
 }
@@ -442,10 +443,11 @@ fn envelope_event_serde_roundtrip() {
     let envelope = Envelope::Event {
         ref_id: "r-1".into(),
         event: ev,
+        seq: None,
     };
     let json = JsonlCodec::encode(&envelope).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
-    if let Envelope::Event { ref_id, event } = decoded {
+    if let Envelope::Event { ref_id, event, .. } = decoded {
         assert_eq!(ref_id, "r-1");
         assert!(matches!(event.kind, AgentEventKind::Warning { .. }));
     } else {
@@ -461,6 +463,7 @@ fn envelope_discriminator_is_t() {
     let envelope = Envelope::Event {
         ref_id: "r".into(),
         event: ev,
+        seq: None,
     };
     let json = JsonlCodec::encode(&envelope).unwrap();
     assert!(json.contains(r#""t":"event""#));
@@ -498,6 +501,7 @@ fn envelope_ref_id_correlation() {
     let envelope = Envelope::Event {
         ref_id: run_id.into(),
         event: ev,
+        seq: None,
     };
     if let Envelope::Event { ref_id, .. } = &envelope {
         assert_eq!(ref_id, run_id);
@@ -513,6 +517,7 @@ fn multiple_events_share_ref_id() {
             event: make_event(AgentEventKind::AssistantDelta {
                 text: format!("tok-{i}"),
             }),
+            seq: None,
         })
         .collect();
     for env in &envs {
@@ -587,6 +592,7 @@ fn envelope_event_does_not_have_error_code() {
     let envelope = Envelope::Event {
         ref_id: "r".into(),
         event: ev,
+        seq: None,
     };
     assert!(envelope.error_code().is_none());
 }
@@ -1517,6 +1523,7 @@ fn envelope_event_discriminator_vs_kind_discriminator() {
     let envelope = Envelope::Event {
         ref_id: "r".into(),
         event: ev,
+        seq: None,
     };
     let json = JsonlCodec::encode(&envelope).unwrap();
     let v: serde_json::Value = serde_json::from_str(json.trim()).unwrap();
@@ -1614,7 +1621,7 @@ fn event_from_json_string_literal() {
 fn envelope_event_from_json_string() {
     let json = r#"{"t":"event","ref_id":"r-1","event":{"ts":"2024-01-01T00:00:00Z","type":"run_started","message":"go"}}"#;
     let env: Envelope = serde_json::from_str(json).unwrap();
-    if let Envelope::Event { ref_id, event } = env {
+    if let Envelope::Event { ref_id, event, .. } = env {
         assert_eq!(ref_id, "r-1");
         assert!(matches!(event.kind, AgentEventKind::RunStarted { .. }));
     } else {
@@ -315,6 +315,7 @@ fn receipt_unicode_git_diff() {
             )),
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         })
         .build();
     let json = serde_json::to_string(&r).unwrap();
@@ -448,6 +449,9 @@ fn runtime_config_nested_json_vendor_flags() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json = serde_json::to_string(&config).unwrap();
     let rt: RuntimeConfig = serde_json::from_str(&json).unwrap();
@@ -518,6 +522,9 @@ fn vendor_flags_json_string_value_containing_json() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json = serde_json::to_string(&config).unwrap();
     let _: RuntimeConfig = serde_json::from_str(&json).unwrap();
@@ -1379,6 +1386,7 @@ fn envelope_event_roundtrip() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -1887,6 +1895,7 @@ fn verification_report_large_diff() {
         git_diff: Some(big_diff.clone()),
         git_status: Some("M file.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let json = serde_json::to_string(&v).unwrap();
     let rt: VerificationReport = serde_json::from_str(&json).unwrap();
@@ -373,6 +373,7 @@ fn envelope_event_with_all_event_kinds() {
         let env = Envelope::Event {
             ref_id: "r1".into(),
             event,
+            seq: None,
         };
         let encoded = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(&encoded);
@@ -934,6 +935,7 @@ fn envelope_encode_never_contains_raw_newlines() {
         let env = Envelope::Event {
             ref_id: "r1".into(),
             event,
+            seq: None,
         };
         let encoded = JsonlCodec::encode(&env).unwrap();
         // JSONL: each envelope must be exactly one line (trailing \n allowed)
@@ -1781,6 +1783,7 @@ fn fuzz_stream_events_as_envelope_batch() {
                 },
                 ext: None,
             },
+            seq: None,
         })
         .collect();
     let batch = StreamingCodec::encode_batch(&envelopes);
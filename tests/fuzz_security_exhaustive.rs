@@ -567,6 +567,7 @@ fn roundtrip_receipt() {
             git_diff: Some("diff --git ...".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .usage(UsageNormalized {
             input_tokens: Some(100),
@@ -655,6 +656,7 @@ fn roundtrip_envelope_all_variants() {
         Envelope::Event {
             ref_id: "run-1".into(),
             event: make_event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "run-1".into(),
@@ -717,6 +719,7 @@ fn roundtrip_config() {
         bind_address: Some("127.0.0.1".into()),
         port: Some(8080),
         policy_profiles: vec!["policy.toml".into()],
+        policy_presets: Vec::new(),
         backends: {
             let mut m = BTreeMap::new();
             m.insert("mock".into(), BackendEntry::Mock {});
@@ -730,6 +733,7 @@ fn roundtrip_config() {
             );
             m
         },
+        rbac: None,
     };
     let toml_str = toml::to_string(&config).unwrap();
     let rt: BackplaneConfig = toml::from_str(&toml_str).unwrap();
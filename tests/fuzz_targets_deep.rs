@@ -1345,7 +1345,7 @@ proptest! {
         let report = VerificationReport {
             git_diff: diff,
             git_status: status,
-            harness_ok: true,
+            harness_ok: true, input_digest: None,
         };
         let json = serde_json::to_string(&report).unwrap();
         let report2: VerificationReport = serde_json::from_str(&json).unwrap();
@@ -141,6 +141,9 @@ fn full_work_order() -> WorkOrder {
             env: BTreeMap::from([("RUST_LOG".into(), "debug".into())]),
             max_budget_usd: Some(5.0),
             max_turns: Some(25),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -229,6 +232,7 @@ fn full_receipt() -> Receipt {
             git_diff: Some("+added line\n-removed line".into()),
             git_status: Some("M src/auth.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -360,6 +364,9 @@ fn golden_work_order_with_runtime_config() {
             env: BTreeMap::new(),
             max_budget_usd: Some(10.0),
             max_turns: Some(50),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
         ..minimal_work_order()
     };
@@ -437,6 +444,7 @@ fn golden_receipt_with_verification() {
             git_diff: Some("diff --git a/src/main.rs".into()),
             git_status: Some("M src/main.rs\nA src/new.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         ..minimal_receipt()
     };
@@ -725,6 +733,7 @@ fn golden_envelope_event() {
         event: make_event(AgentEventKind::AssistantMessage {
             text: "Working on it".into(),
         }),
+        seq: None,
     };
     assert_json_snapshot!("golden_envelope_event", env);
 }
@@ -739,6 +748,7 @@ fn golden_envelope_event_tool_call() {
             parent_tool_use_id: None,
             input: json!({"path": "src/lib.rs", "content": "fn hello() {}"}),
         }),
+        seq: None,
     };
     assert_json_snapshot!("golden_envelope_event_tool_call", env);
 }
@@ -946,6 +956,9 @@ fn golden_runtime_config_full() {
         ]),
         max_budget_usd: Some(25.0),
         max_turns: Some(100),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     assert_json_snapshot!("golden_runtime_config_full", c);
 }
@@ -1237,6 +1250,7 @@ fn golden_handshake_event_jsonl() {
         event: make_event(AgentEventKind::AssistantDelta {
             text: "Hello".into(),
         }),
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains("\"t\":\"event\""));
@@ -1285,6 +1299,7 @@ fn golden_handshake_full_sequence() {
         event: make_event(AgentEventKind::RunStarted {
             message: "Go".into(),
         }),
+        seq: None,
     };
     let fin = Envelope::Final {
         ref_id: "run-seq".into(),
@@ -1521,6 +1536,7 @@ fn golden_verification_report_full() {
         git_diff: Some("diff content here".into()),
         git_status: Some("M file.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     assert_json_snapshot!("golden_verification_report_full", v);
 }
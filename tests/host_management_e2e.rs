@@ -374,6 +374,7 @@ fn jsonl_encode_event_contains_tag_t() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&event).unwrap();
     assert!(line.contains(r#""t":"event""#));
@@ -578,6 +579,7 @@ fn protocol_handshake_sequence_encode_decode() {
             },
             ext: None,
         },
+        seq: None,
     };
 
     let receipt = make_receipt();
@@ -1752,6 +1754,7 @@ fn jsonl_large_message_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -1777,6 +1780,7 @@ fn jsonl_encode_special_characters() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -1805,6 +1809,7 @@ fn jsonl_encode_unicode() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
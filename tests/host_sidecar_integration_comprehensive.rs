@@ -86,6 +86,7 @@ fn make_event_envelope(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -173,7 +174,7 @@ fn encode_event_assistant_message_roundtrip() {
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-001");
             match event.kind {
                 AgentEventKind::AssistantMessage { text } => assert_eq!(text, "Hello world"),
@@ -749,6 +750,7 @@ fn validate_hello_with_empty_backend_id() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -768,6 +770,7 @@ fn validate_hello_with_invalid_version() {
         backend: make_backend_identity("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -791,6 +794,7 @@ fn validate_hello_warns_missing_optional_fields() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(result.valid);
@@ -818,6 +822,7 @@ fn validate_event_with_empty_ref_id() {
             kind: AgentEventKind::AssistantMessage { text: "x".into() },
             ext: None,
         },
+        seq: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -1215,6 +1220,7 @@ fn text_message_events_preserved_through_codec() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event: event.clone(),
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -1252,6 +1258,7 @@ fn tool_call_events_preserved_through_codec() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -1365,6 +1372,7 @@ fn event_with_extension_data_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -190,6 +190,7 @@ fn make_full_receipt(backend: &str, outcome: Outcome) -> Receipt {
             git_diff: None,
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         },
         outcome,
         receipt_sha256: None,
@@ -290,6 +291,7 @@ mod core_to_protocol_roundtrip {
             let env = Envelope::Event {
                 ref_id: "chain".into(),
                 event: evt,
+                seq: None,
             };
             let json = JsonlCodec::encode(&env).unwrap();
             let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -305,6 +307,7 @@ mod core_to_protocol_roundtrip {
             backend: sample_backend_identity(),
             capabilities: caps.clone(),
             mode: ExecutionMode::Mapped,
+            features: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -401,6 +404,7 @@ mod core_to_protocol_roundtrip {
                 event: make_event(AgentEventKind::AssistantDelta {
                     text: "hello".into(),
                 }),
+                seq: None,
             },
             Envelope::Final {
                 ref_id: "r1".into(),
@@ -886,6 +890,7 @@ mod error_taxonomy_protocol_chain {
         let env = Envelope::Event {
             ref_id: "err-run".into(),
             event: evt,
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1579,12 +1584,14 @@ mod cross_chain_integration {
             event: make_event(AgentEventKind::RunStarted {
                 message: "start".into(),
             }),
+            seq: None,
         };
         let event2 = Envelope::Event {
             ref_id: "session-1".into(),
             event: make_event(AgentEventKind::AssistantDelta {
                 text: "response".into(),
             }),
+            seq: None,
         };
         let final_env = Envelope::Final {
             ref_id: "session-1".into(),
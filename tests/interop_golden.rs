@@ -112,6 +112,7 @@ fn test_receipt() -> Receipt {
             git_diff: None,
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -191,6 +192,7 @@ fn event_envelope_matches_golden_json() {
             },
             ext: None,
         },
+        seq: None,
     };
     let v = envelope_to_value(&event);
 
@@ -289,6 +291,7 @@ fn envelope_discriminator_is_t_not_type() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r1".into(),
@@ -465,6 +468,7 @@ fn full_session_hello_run_events_final_produces_valid_jsonl() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
@@ -475,6 +479,7 @@ fn full_session_hello_run_events_final_produces_valid_jsonl() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
@@ -485,6 +490,7 @@ fn full_session_hello_run_events_final_produces_valid_jsonl() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Final {
             ref_id: run_id.into(),
@@ -566,6 +572,7 @@ fn rust_decodes_js_golden_hello() {
             backend,
             capabilities,
             mode,
+            ..
         } => {
             assert_eq!(contract_version, "abp/v0.1");
             assert_eq!(backend.id, "example_node_sidecar");
@@ -610,7 +617,7 @@ fn rust_decodes_python_golden_event() {
 
     let env = JsonlCodec::decode(py_event).unwrap();
     match env {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-abc");
             match event.kind {
                 AgentEventKind::ToolCall {
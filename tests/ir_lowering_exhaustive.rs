@@ -1502,6 +1502,23 @@ fn deterministic_output_all_dialects() {
 
 #[test]
 fn tool_result_nested_non_text_claude_unknown() {
+    let conv = IrConversation::new().push(IrMessage::new(
+        IrRole::Tool,
+        vec![IrContentBlock::ToolResult {
+            tool_use_id: "c1".into(),
+            content: vec![IrContentBlock::Thinking { text: "hmm".into() }],
+            is_error: false,
+        }],
+    ));
+    let v = lower_to_claude(&conv, &[]);
+    let inner = v["messages"][0]["content"][0]["content"]
+        .as_array()
+        .unwrap();
+    assert_eq!(inner[0]["type"], "unknown");
+}
+
+#[test]
+fn tool_result_nested_image_claude_kept() {
     let conv = IrConversation::new().push(IrMessage::new(
         IrRole::Tool,
         vec![IrContentBlock::ToolResult {
@@ -1517,7 +1534,8 @@ fn tool_result_nested_non_text_claude_unknown() {
     let inner = v["messages"][0]["content"][0]["content"]
         .as_array()
         .unwrap();
-    assert_eq!(inner[0]["type"], "unknown");
+    assert_eq!(inner[0]["type"], "image");
+    assert_eq!(inner[0]["source"]["media_type"], "image/png");
 }
 
 #[test]
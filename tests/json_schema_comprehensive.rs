@@ -997,6 +997,7 @@ fn envelope_event_roundtrips_json() {
             kind: AgentEventKind::AssistantDelta { text: "hi".into() },
             ext: None,
         },
+        seq: None,
     };
     let json = abp_protocol::JsonlCodec::encode(&event).unwrap();
     let decoded = abp_protocol::JsonlCodec::decode(json.trim()).unwrap();
@@ -1208,6 +1209,7 @@ fn receipt_with_verification_data_passes() {
         git_diff: Some("diff --git a/foo b/foo".into()),
         git_status: Some("M foo".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let v = serde_json::to_value(receipt).unwrap();
     assert_valid(&receipt_schema(), &v);
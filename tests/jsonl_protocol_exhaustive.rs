@@ -75,6 +75,7 @@ fn make_event(ref_id: &str) -> Envelope {
         event: make_agent_event(AgentEventKind::AssistantMessage {
             text: "hello".into(),
         }),
+        seq: None,
     }
 }
 
@@ -127,6 +128,7 @@ fn hello_roundtrip_serde() {
             backend,
             capabilities,
             mode,
+            ..
         } => {
             assert_eq!(contract_version, CONTRACT_VERSION);
             assert_eq!(backend.id, "test-sidecar");
@@ -276,11 +278,12 @@ fn event_roundtrip_assistant_message() {
         event: make_agent_event(AgentEventKind::AssistantMessage {
             text: "Hello world".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-42");
             match event.kind {
                 AgentEventKind::AssistantMessage { text } => assert_eq!(text, "Hello world"),
@@ -296,6 +299,7 @@ fn event_roundtrip_assistant_delta() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event: make_agent_event(AgentEventKind::AssistantDelta { text: "tok".into() }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -318,6 +322,7 @@ fn event_roundtrip_tool_call() {
             parent_tool_use_id: None,
             input: serde_json::json!({"path": "/tmp/foo.txt"}),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -345,6 +350,7 @@ fn event_roundtrip_tool_result() {
             output: serde_json::json!({"content": "file contents"}),
             is_error: false,
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -372,6 +378,7 @@ fn event_roundtrip_file_changed() {
             path: "src/main.rs".into(),
             summary: "Added entry point".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -396,6 +403,7 @@ fn event_roundtrip_command_executed() {
             exit_code: Some(0),
             output_preview: Some("ok".into()),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -423,6 +431,7 @@ fn event_roundtrip_run_started() {
         event: make_agent_event(AgentEventKind::RunStarted {
             message: "Starting run".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -442,6 +451,7 @@ fn event_roundtrip_run_completed() {
         event: make_agent_event(AgentEventKind::RunCompleted {
             message: "Done".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -461,6 +471,7 @@ fn event_roundtrip_warning() {
         event: make_agent_event(AgentEventKind::Warning {
             message: "Low memory".into(),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -481,6 +492,7 @@ fn event_roundtrip_error() {
             message: "Crash".into(),
             error_code: None,
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -504,6 +516,7 @@ fn event_with_ext_field() {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     assert!(json.contains("raw_message"));
@@ -1440,6 +1453,7 @@ fn validate_hello_empty_backend_id() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -1456,6 +1470,7 @@ fn validate_hello_invalid_version() {
         backend: make_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -1475,6 +1490,7 @@ fn validate_hello_empty_version() {
         backend: make_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -1495,6 +1511,7 @@ fn validate_hello_warns_missing_optional_fields() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -1512,6 +1529,7 @@ fn validate_event_empty_ref_id() {
         event: make_agent_event(AgentEventKind::Warning {
             message: "hi".into(),
         }),
+        seq: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -1690,6 +1708,7 @@ fn large_payload_assistant_message() {
         event: make_agent_event(AgentEventKind::AssistantMessage {
             text: big_text.clone(),
         }),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1715,6 +1734,7 @@ fn large_payload_tool_result() {
             output: big_output,
             is_error: false,
         }),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1727,6 +1747,7 @@ fn large_payload_stream_parser() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event: make_agent_event(AgentEventKind::AssistantMessage { text: big_text }),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
 
@@ -1751,6 +1772,7 @@ fn large_payload_many_events_in_stream() {
             event: make_agent_event(AgentEventKind::AssistantDelta {
                 text: format!("token_{i}"),
             }),
+            seq: None,
         };
         JsonlCodec::encode_to_writer(&mut buf, &env).unwrap();
     }
@@ -1773,6 +1795,7 @@ fn unicode_assistant_message_emoji() {
         event: make_agent_event(AgentEventKind::AssistantMessage {
             text: "Hello 🌍🚀✨ World!".into(),
         }),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1796,6 +1819,7 @@ fn unicode_cjk_characters() {
         event: make_agent_event(AgentEventKind::AssistantMessage {
             text: "日本語テスト 中文测试 한국어".into(),
         }),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1819,6 +1843,7 @@ fn unicode_rtl_text() {
         event: make_agent_event(AgentEventKind::AssistantMessage {
             text: "مرحبا بالعالم".into(),
         }),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1838,6 +1863,7 @@ fn unicode_mixed_scripts() {
         event: make_agent_event(AgentEventKind::AssistantMessage {
             text: "Ελληνικά Кириллица ñ ü ö".into(),
         }),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1864,6 +1890,7 @@ fn unicode_in_backend_id() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1897,6 +1924,7 @@ fn unicode_null_bytes_rejected_by_json() {
         event: make_agent_event(AgentEventKind::AssistantMessage {
             text: "before\0after".into(),
         }),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1919,6 +1947,7 @@ fn unicode_special_json_chars() {
         event: make_agent_event(AgentEventKind::AssistantMessage {
             text: r#"quotes: "hello" backslash: \ newline: \n tab: \t"#.into(),
         }),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1945,18 +1974,21 @@ fn write_and_read_full_session() {
             event: make_agent_event(AgentEventKind::RunStarted {
                 message: "Starting".into(),
             }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.clone(),
             event: make_agent_event(AgentEventKind::AssistantMessage {
                 text: "Done".into(),
             }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.clone(),
             event: make_agent_event(AgentEventKind::RunCompleted {
                 message: "Finished".into(),
             }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: run_id.clone(),
@@ -2148,6 +2180,7 @@ fn multiple_events_different_kinds_roundtrip() {
         let env = Envelope::Event {
             ref_id: "run-1".into(),
             event: make_agent_event(kind.clone()),
+            seq: None,
         };
         JsonlCodec::encode_to_writer(&mut buf, &env).unwrap();
     }
@@ -2242,6 +2275,7 @@ fn tool_call_with_nested_tool_use_id() {
             parent_tool_use_id: Some("tu-parent".into()),
             input: serde_json::json!({}),
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -2266,6 +2300,7 @@ fn tool_result_is_error_true() {
             output: serde_json::json!({"stderr": "command not found"}),
             is_error: true,
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -2287,6 +2322,7 @@ fn command_executed_no_exit_code() {
             exit_code: None,
             output_preview: None,
         }),
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
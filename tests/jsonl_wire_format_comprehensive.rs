@@ -87,6 +87,7 @@ fn make_event(ref_id: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -530,6 +531,7 @@ fn event_tool_call_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -561,6 +563,7 @@ fn event_tool_result_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -592,6 +595,7 @@ fn event_file_changed_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -620,6 +624,7 @@ fn event_command_executed_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -648,6 +653,7 @@ fn event_warning_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -672,6 +678,7 @@ fn event_error_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -695,6 +702,7 @@ fn event_run_started_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -717,6 +725,7 @@ fn event_run_completed_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -739,6 +748,7 @@ fn event_assistant_delta_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -762,6 +772,7 @@ fn event_with_ext_data_roundtrips() {
             kind: AgentEventKind::AssistantMessage { text: "msg".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1049,6 +1060,7 @@ fn decode_stream_full_handshake_sequence() {
             kind: AgentEventKind::AssistantDelta { text: "tok".into() },
             ext: None,
         },
+        seq: None,
     })
     .unwrap();
     let fin = JsonlCodec::encode(&make_final("run-1")).unwrap();
@@ -1198,6 +1210,7 @@ fn event_timestamp_survives_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1230,6 +1243,7 @@ fn tool_call_with_nested_json_input() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1261,6 +1275,7 @@ fn tool_result_error_flag_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
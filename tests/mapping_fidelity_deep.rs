@@ -281,6 +281,8 @@ mod system_message_handling {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         // The system param is separate from messages
         assert!(req.system.is_some());
@@ -502,6 +504,8 @@ mod model_name_normalization {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let wo = abp_shim_claude::request_to_work_order(&req);
         assert_eq!(wo.config.model.as_deref(), Some("claude-sonnet-4-20250514"));
@@ -553,6 +557,8 @@ mod model_name_normalization {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
 
         let wo_openai = abp_shim_openai::request_to_work_order(&openai_req);
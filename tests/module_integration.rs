@@ -375,6 +375,7 @@ fn envelope_validate_then_route() {
             },
             capabilities: BTreeMap::new(),
             mode: ExecutionMode::Mapped,
+            features: None,
         },
         Envelope::Run {
             id: "run-1".into(),
@@ -385,6 +386,7 @@ fn envelope_validate_then_route() {
             event: make_event(AgentEventKind::RunStarted {
                 message: "go".into(),
             }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "run-1".into(),
@@ -572,6 +574,7 @@ fn batch_compress_decompress_roundtrip() {
             event: make_event(AgentEventKind::AssistantMessage {
                 text: "batch message".into(),
             }),
+            seq: None,
         },
     ];
 
@@ -1083,6 +1086,7 @@ fn protocol_validate_batch_compress_pipeline() {
             },
             capabilities: BTreeMap::new(),
             mode: ExecutionMode::Mapped,
+            features: None,
         },
         Envelope::Run {
             id: "r1".into(),
@@ -1093,6 +1097,7 @@ fn protocol_validate_batch_compress_pipeline() {
             event: make_event(AgentEventKind::RunStarted {
                 message: "go".into(),
             }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r1".into(),
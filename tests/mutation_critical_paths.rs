@@ -498,6 +498,7 @@ mod contract_version {
             },
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::Mapped,
+            features: None,
         };
         let json = serde_json::to_string(&env).unwrap();
         let parsed: Envelope = serde_json::from_str(&json).unwrap();
@@ -1021,6 +1022,7 @@ mod protocol_envelope {
             backend: test_backend(),
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::Mapped,
+            features: None,
         };
         let json: serde_json::Value = serde_json::to_value(&env).unwrap();
         assert_eq!(
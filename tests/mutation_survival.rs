@@ -989,6 +989,7 @@ mod protocol_mutations {
             },
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let result = v.validate(&hello);
         assert!(!result.valid);
@@ -1007,6 +1008,7 @@ mod protocol_mutations {
             },
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let result = v.validate(&hello);
         assert!(!result.valid);
@@ -1024,6 +1026,7 @@ mod protocol_mutations {
             },
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let result = v.validate(&hello);
         assert!(!result.valid);
@@ -317,6 +317,7 @@ mod tests {
                     git_diff: Some("diff --git a/f.rs b/f.rs\n+new line".into()),
                     git_status: Some("M src/f.rs\n".into()),
                     harness_ok: true,
+                    input_digest: None,
                 },
                 ..passthrough_receipt_default(vec![])
             };
@@ -429,6 +430,7 @@ mod tests {
             let envelope = Envelope::Event {
                 ref_id: "run_123".into(),
                 event: event.clone(),
+                seq: None,
             };
             let envelope_json = serde_json::to_value(&envelope).unwrap();
 
@@ -527,6 +529,7 @@ mod tests {
                 },
                 capabilities: CapabilityManifest::new(),
                 mode: ExecutionMode::Passthrough,
+                features: None,
             };
             let hello_json = serde_json::to_value(&hello).unwrap();
             // Hello envelope has a "t" tag that must not propagate.
@@ -560,6 +563,7 @@ mod tests {
             let envelope = Envelope::Event {
                 ref_id: "run_abc".into(),
                 event: event.clone(),
+                seq: None,
             };
             let envelope_json = serde_json::to_value(&envelope).unwrap();
 
@@ -1513,6 +1517,7 @@ mod tests {
                 },
                 capabilities: CapabilityManifest::new(),
                 mode: ExecutionMode::Passthrough,
+                features: None,
             };
             let json_str = serde_json::to_string(&hello).unwrap();
             let back: Envelope = serde_json::from_str(&json_str).unwrap();
@@ -1541,6 +1546,7 @@ mod tests {
             let envelope = Envelope::Event {
                 ref_id: "run_1".into(),
                 event,
+                seq: None,
             };
             let json_str = serde_json::to_string(&envelope).unwrap();
             let back: Envelope = serde_json::from_str(&json_str).unwrap();
@@ -507,6 +507,8 @@ mod tests {
                 stop_sequences: None,
                 thinking: None,
                 stream: None,
+                tools: None,
+                tool_choice: None,
             };
 
             let resp = client.create(req).await.unwrap();
@@ -594,6 +596,8 @@ mod tests {
                 stop_sequences: None,
                 thinking: None,
                 stream: Some(true),
+                tools: None,
+                tool_choice: None,
             };
 
             let events = client.create_stream(req).await.unwrap().collect_all().await;
@@ -149,6 +149,7 @@ fn make_jsonl_line(i: usize) -> String {
     let envelope = Envelope::Event {
         ref_id: format!("ref-{i}"),
         event: make_event(i),
+        seq: None,
     };
     JsonlCodec::encode(&envelope).unwrap()
 }
@@ -356,6 +357,7 @@ fn perf_jsonl_encode_1000() {
         .map(|i| Envelope::Event {
             ref_id: format!("ref-{i}"),
             event: make_event(i),
+            seq: None,
         })
         .collect();
     let start = Instant::now();
@@ -390,6 +392,7 @@ fn perf_jsonl_roundtrip_1000() {
         let env = Envelope::Event {
             ref_id: format!("ref-{i}"),
             event: make_event(i),
+            seq: None,
         };
         let encoded = JsonlCodec::encode(&env).unwrap();
         let _ = JsonlCodec::decode(&encoded).unwrap();
@@ -453,6 +456,7 @@ fn perf_jsonl_mixed_envelope_types() {
             1 => Envelope::Event {
                 ref_id: format!("ref-{i}"),
                 event: make_event(i),
+                seq: None,
             },
             _ => Envelope::Final {
                 ref_id: format!("ref-{i}"),
@@ -1939,6 +1943,7 @@ fn sanity_envelope_roundtrip() {
     let env = Envelope::Event {
         ref_id: "test-ref".into(),
         event: make_event(0),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(&encoded).unwrap();
@@ -299,6 +299,9 @@ fn arb_runtime_config() -> BoxedStrategy<RuntimeConfig> {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .boxed()
 }
@@ -373,6 +376,7 @@ fn arb_verification_report() -> BoxedStrategy<VerificationReport> {
             git_diff,
             git_status,
             harness_ok,
+            input_digest: None,
         })
         .boxed()
 }
@@ -560,7 +564,11 @@ fn arb_envelope_run() -> BoxedStrategy<Envelope> {
 
 fn arb_envelope_event() -> BoxedStrategy<Envelope> {
     (arb_safe_string(), arb_agent_event())
-        .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event })
+        .prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        })
         .boxed()
 }
 
@@ -611,7 +619,9 @@ fn arb_backplane_config() -> BoxedStrategy<BackplaneConfig> {
                 bind_address: None,
                 port,
                 policy_profiles: vec![],
+                policy_presets: Vec::new(),
                 backends: BTreeMap::new(),
+                rbac: None,
             },
         )
         .boxed()
@@ -1363,7 +1373,9 @@ proptest! {
             bind_address: None,
             port: None,
             policy_profiles: vec![],
+            policy_presets: Vec::new(),
             backends: BTreeMap::new(),
+            rbac: None,
         };
         let merged = merge_configs(base.clone(), overlay);
         prop_assert_eq!(&base.default_backend, &merged.default_backend);
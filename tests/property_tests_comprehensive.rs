@@ -216,6 +216,9 @@ fn arb_runtime_config() -> BoxedStrategy<RuntimeConfig> {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .boxed()
 }
@@ -316,6 +319,7 @@ fn arb_verification_report() -> BoxedStrategy<VerificationReport> {
             git_diff,
             git_status,
             harness_ok,
+            input_digest: None,
         })
         .boxed()
 }
@@ -427,12 +431,16 @@ fn arb_envelope() -> BoxedStrategy<Envelope> {
                     backend,
                     capabilities,
                     mode,
+                    features: None,
                 }
             }),
         (arb_short_string(), arb_work_order())
             .prop_map(|(id, work_order)| Envelope::Run { id, work_order }),
-        (arb_short_string(), arb_agent_event())
-            .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event }),
+        (arb_short_string(), arb_agent_event()).prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        }),
         (arb_short_string(), arb_receipt())
             .prop_map(|(ref_id, receipt)| Envelope::Final { ref_id, receipt }),
         (prop::option::of(arb_short_string()), arb_safe_string()).prop_map(|(ref_id, error)| {
@@ -496,7 +504,9 @@ fn arb_backplane_config() -> BoxedStrategy<BackplaneConfig> {
                     bind_address,
                     port,
                     policy_profiles: vec![],
+                    policy_presets: Vec::new(),
                     backends: backends_vec.into_iter().collect(),
+                    rbac: None,
                 }
             },
         )
@@ -974,10 +984,10 @@ proptest! {
     // 36
     #[test]
     fn envelope_event_roundtrip(ref_id in arb_short_string(), evt in arb_agent_event()) {
-        let env = Envelope::Event { ref_id: ref_id.clone(), event: evt.clone() };
+        let env = Envelope::Event { ref_id: ref_id.clone(), event: evt.clone(), seq: None, };
         let encoded = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
-        if let Envelope::Event { ref_id: ri, event } = decoded {
+        if let Envelope::Event { ref_id: ri, event, .. } = decoded {
             prop_assert_eq!(ref_id, ri);
             prop_assert_eq!(evt.ts, event.ts);
         } else {
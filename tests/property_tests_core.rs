@@ -182,6 +182,9 @@ fn arb_runtime_config() -> impl Strategy<Value = RuntimeConfig> {
             env,
             max_budget_usd: budget,
             max_turns: turns,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
 }
 
@@ -285,8 +288,11 @@ fn arb_envelope() -> impl Strategy<Value = Envelope> {
         (nonempty_string(), arb_work_order())
             .prop_map(|(id, wo)| Envelope::Run { id, work_order: wo }),
         // Event
-        (nonempty_string(), arb_agent_event())
-            .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event }),
+        (nonempty_string(), arb_agent_event()).prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        }),
         // Fatal
         (
             prop::option::of(nonempty_string()),
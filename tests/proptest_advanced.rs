@@ -272,6 +272,9 @@ fn arb_runtime_config() -> BoxedStrategy<RuntimeConfig> {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .boxed()
 }
@@ -372,6 +375,7 @@ fn arb_verification_report() -> BoxedStrategy<VerificationReport> {
             git_diff,
             git_status,
             harness_ok,
+            input_digest: None,
         })
         .boxed()
 }
@@ -476,8 +480,11 @@ fn arb_envelope() -> BoxedStrategy<Envelope> {
             .prop_map(|(backend, caps)| { Envelope::hello(backend, caps) }),
         (arb_short_string(), arb_work_order())
             .prop_map(|(id, wo)| Envelope::Run { id, work_order: wo }),
-        (arb_short_string(), arb_agent_event())
-            .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event }),
+        (arb_short_string(), arb_agent_event()).prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        }),
         (arb_short_string(), arb_receipt())
             .prop_map(|(ref_id, receipt)| Envelope::Final { ref_id, receipt }),
         (prop::option::of(arb_short_string()), arb_safe_string()).prop_map(|(ref_id, error)| {
@@ -870,7 +877,7 @@ proptest! {
     // 34
     #[test]
     fn envelope_event_preserves_ref_id(ref_id in arb_short_string(), evt in arb_agent_event()) {
-        let env = Envelope::Event { ref_id: ref_id.clone(), event: evt };
+        let env = Envelope::Event { ref_id: ref_id.clone(), event: evt, seq: None, };
         let line = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(line.trim()).unwrap();
         if let Envelope::Event { ref_id: rt_ref, .. } = decoded {
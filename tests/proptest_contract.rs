@@ -257,6 +257,9 @@ fn arb_runtime_config() -> BoxedStrategy<RuntimeConfig> {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .boxed()
 }
@@ -357,6 +360,7 @@ fn arb_verification_report() -> BoxedStrategy<VerificationReport> {
             git_diff,
             git_status,
             harness_ok,
+            input_digest: None,
         })
         .boxed()
 }
@@ -337,6 +337,9 @@ fn arb_runtime_config() -> BoxedStrategy<RuntimeConfig> {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .boxed()
 }
@@ -437,6 +440,7 @@ fn arb_verification_report() -> BoxedStrategy<VerificationReport> {
             git_diff,
             git_status,
             harness_ok,
+            input_digest: None,
         })
         .boxed()
 }
@@ -562,6 +566,7 @@ fn arb_envelope_hello() -> BoxedStrategy<Envelope> {
             backend,
             capabilities,
             mode,
+            features: None,
         })
         .boxed()
 }
@@ -574,7 +579,11 @@ fn arb_envelope_run() -> BoxedStrategy<Envelope> {
 
 fn arb_envelope_event() -> BoxedStrategy<Envelope> {
     (arb_short_string(), arb_agent_event())
-        .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event })
+        .prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        })
         .boxed()
 }
 
@@ -700,6 +709,9 @@ proptest! {
             vendor: BTreeMap::new(),
             env: BTreeMap::new(),
             max_budget_usd: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         };
         let overrides = RuntimeConfig {
             model: override_model.clone(),
@@ -707,6 +719,9 @@ proptest! {
             vendor: BTreeMap::new(),
             env: BTreeMap::new(),
             max_budget_usd: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         };
         // Merge: override wins when present
         let merged_model = overrides.model.or(base.model);
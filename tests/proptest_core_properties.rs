@@ -327,6 +327,9 @@ fn arb_runtime_config() -> BoxedStrategy<RuntimeConfig> {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .boxed()
 }
@@ -427,6 +430,7 @@ fn arb_verification_report() -> BoxedStrategy<VerificationReport> {
             git_diff,
             git_status,
             harness_ok,
+            input_digest: None,
         })
         .boxed()
 }
@@ -593,6 +597,7 @@ fn arb_envelope_hello() -> BoxedStrategy<Envelope> {
             backend,
             capabilities,
             mode,
+            features: None,
         })
         .boxed()
 }
@@ -605,7 +610,11 @@ fn arb_envelope_run() -> BoxedStrategy<Envelope> {
 
 fn arb_envelope_event() -> BoxedStrategy<Envelope> {
     (arb_short_string(), arb_agent_event())
-        .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event })
+        .prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        })
         .boxed()
 }
 
@@ -1066,8 +1075,8 @@ proptest! {
         let rt: Envelope = serde_json::from_str(&json).unwrap();
         match (&env, &rt) {
             (
-                Envelope::Event { ref_id: r1, event: e1 },
-                Envelope::Event { ref_id: r2, event: e2 },
+                Envelope::Event { ref_id: r1, event: e1, seq: None, },
+                Envelope::Event { ref_id: r2, event: e2, seq: None, },
             ) => {
                 prop_assert_eq!(r1, r2);
                 prop_assert_eq!(e1.ts, e2.ts);
@@ -288,8 +288,11 @@ fn arb_envelope() -> impl Strategy<Value = Envelope> {
             work_order: wo,
         }),
         // Event
-        (arb_safe_string(), arb_agent_event())
-            .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event }),
+        (arb_safe_string(), arb_agent_event()).prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        }),
         // Final
         (arb_safe_string(), arb_receipt())
             .prop_map(|(ref_id, receipt)| Envelope::Final { ref_id, receipt }),
@@ -593,7 +596,9 @@ fn arb_backplane_config() -> impl Strategy<Value = abp_config::BackplaneConfig>
                     bind_address: None,
                     port,
                     policy_profiles: vec![],
+                    policy_presets: Vec::new(),
                     backends: BTreeMap::new(),
+                    rbac: None,
                 }
             },
         )
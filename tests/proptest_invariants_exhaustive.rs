@@ -341,6 +341,9 @@ fn arb_runtime_config() -> BoxedStrategy<RuntimeConfig> {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .boxed()
 }
@@ -441,6 +444,7 @@ fn arb_verification_report() -> BoxedStrategy<VerificationReport> {
             git_diff,
             git_status,
             harness_ok,
+            input_digest: None,
         })
         .boxed()
 }
@@ -564,6 +568,7 @@ fn arb_envelope_hello() -> BoxedStrategy<Envelope> {
             backend,
             capabilities,
             mode,
+            features: None,
         })
         .boxed()
 }
@@ -576,7 +581,11 @@ fn arb_envelope_run() -> BoxedStrategy<Envelope> {
 
 fn arb_envelope_event() -> BoxedStrategy<Envelope> {
     (arb_short_string(), arb_agent_event())
-        .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event })
+        .prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        })
         .boxed()
 }
 
@@ -76,6 +76,7 @@ fn make_event() -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -971,11 +972,12 @@ fn event_with_tool_call_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-abc");
             match event.kind {
                 AgentEventKind::ToolCall { tool_name, .. } => {
@@ -999,6 +1001,7 @@ fn event_with_warning_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1017,6 +1020,7 @@ fn event_with_file_changed_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
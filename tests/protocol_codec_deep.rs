@@ -80,6 +80,7 @@ fn event_env(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantMessage { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -307,7 +308,7 @@ mod serde_roundtrip {
         let env = event_env("r1", "some text");
         let decoded = roundtrip(&env);
         match decoded {
-            Envelope::Event { ref_id, event } => {
+            Envelope::Event { ref_id, event, .. } => {
                 assert_eq!(ref_id, "r1");
                 match event.kind {
                     AgentEventKind::AssistantMessage { ref text } => {
@@ -444,6 +445,7 @@ mod serde_roundtrip {
                     kind,
                     ext: None,
                 },
+                seq: None,
             };
             let decoded = roundtrip(&env);
             assert!(matches!(decoded, Envelope::Event { .. }));
@@ -1091,6 +1093,7 @@ mod large_payloads {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let decoded = roundtrip(&env);
         assert!(matches!(decoded, Envelope::Event { .. }));
@@ -1397,6 +1400,7 @@ mod edge_cases {
                 },
                 ext: Some(ext),
             },
+            seq: None,
         };
         let decoded = roundtrip(&env);
         match decoded {
@@ -1487,6 +1491,7 @@ mod edge_cases {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let decoded = roundtrip(&env);
         match decoded {
@@ -1511,6 +1516,7 @@ mod edge_cases {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let decoded = roundtrip(&env);
         match decoded {
@@ -1540,6 +1546,7 @@ mod edge_cases {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let decoded = roundtrip(&env);
         match decoded {
@@ -1563,6 +1570,7 @@ mod edge_cases {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let decoded = roundtrip(&env);
         match decoded {
@@ -1595,6 +1603,7 @@ mod edge_cases {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let decoded = roundtrip(&env);
         match decoded {
@@ -1623,6 +1632,7 @@ mod edge_cases {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let decoded = roundtrip(&env);
         match decoded {
@@ -86,6 +86,7 @@ fn event_env(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantMessage { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -444,6 +445,7 @@ mod event_envelope {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let rt = roundtrip(&env);
         match rt {
@@ -468,6 +470,7 @@ mod event_envelope {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let rt = roundtrip(&env);
         match rt {
@@ -498,6 +501,7 @@ mod event_envelope {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let json = encode(&env);
         assert!(json.contains("tool_result"));
@@ -523,6 +527,7 @@ mod event_envelope {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let rt = roundtrip(&env);
         match rt {
@@ -550,6 +555,7 @@ mod event_envelope {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let rt = roundtrip(&env);
         match rt {
@@ -574,6 +580,7 @@ mod event_envelope {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let rt = roundtrip(&env);
         match rt {
@@ -596,6 +603,7 @@ mod event_envelope {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let rt = roundtrip(&env);
         match rt {
@@ -620,6 +628,7 @@ mod event_envelope {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let json = encode(&env);
         assert!(json.contains("run_started"));
@@ -636,6 +645,7 @@ mod event_envelope {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let json = encode(&env);
         assert!(json.contains("run_completed"));
@@ -652,6 +662,7 @@ mod event_envelope {
                 kind: AgentEventKind::AssistantMessage { text: "ext".into() },
                 ext: Some(ext),
             },
+            seq: None,
         };
         let json = encode(&env);
         assert!(json.contains("custom_key"));
@@ -1190,6 +1201,7 @@ mod large_payloads {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let json = encode(&env);
         let rt = JsonlCodec::decode(json.trim()).unwrap();
@@ -1299,6 +1311,7 @@ mod unicode_payloads {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let rt = roundtrip(&env);
         match rt {
@@ -1437,6 +1450,7 @@ mod roundtrip_fidelity {
                 backend,
                 capabilities,
                 mode,
+                ..
             } => {
                 assert_eq!(contract_version, CONTRACT_VERSION);
                 assert_eq!(backend.id, "fidelity-test");
@@ -1817,6 +1831,7 @@ mod validation {
             backend: backend("x"),
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::Mapped,
+            features: None,
         };
         let v = EnvelopeValidator::new();
         let result = v.validate(&env);
@@ -107,6 +107,7 @@ fn make_event_envelope(ref_id: &str, msg: &str) -> Envelope {
         event: make_event(AgentEventKind::AssistantMessage {
             text: msg.to_string(),
         }),
+        seq: None,
     }
 }
 
@@ -235,7 +236,7 @@ mod envelope_serde {
         assert!(json.contains(r#""t":"event""#));
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
         match decoded {
-            Envelope::Event { ref_id, event } => {
+            Envelope::Event { ref_id, event, .. } => {
                 assert_eq!(ref_id, "run-1");
                 assert!(matches!(
                     event.kind,
@@ -253,6 +254,7 @@ mod envelope_serde {
             event: make_event(AgentEventKind::AssistantDelta {
                 text: "chunk".into(),
             }),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -275,6 +277,7 @@ mod envelope_serde {
                 parent_tool_use_id: None,
                 input: serde_json::json!({"path": "/tmp/file.txt"}),
             }),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -304,6 +307,7 @@ mod envelope_serde {
                 output: serde_json::json!("file contents here"),
                 is_error: false,
             }),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -330,6 +334,7 @@ mod envelope_serde {
             event: make_event(AgentEventKind::RunStarted {
                 message: "starting".into(),
             }),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -352,6 +357,7 @@ mod envelope_serde {
             event: make_event(AgentEventKind::RunCompleted {
                 message: "done".into(),
             }),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -375,6 +381,7 @@ mod envelope_serde {
                 path: "src/main.rs".into(),
                 summary: "added fn main".into(),
             }),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -399,6 +406,7 @@ mod envelope_serde {
                 exit_code: Some(0),
                 output_preview: Some("Compiling...".into()),
             }),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -426,6 +434,7 @@ mod envelope_serde {
             event: make_event(AgentEventKind::Warning {
                 message: "deprecated API".into(),
             }),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -446,6 +455,7 @@ mod envelope_serde {
                 message: "something broke".into(),
                 error_code: None,
             }),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1014,6 +1024,7 @@ mod envelope_validation {
             },
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let result = validator.validate(&env);
         assert!(!result.valid);
@@ -1034,6 +1045,7 @@ mod envelope_validation {
             },
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let result = validator.validate(&env);
         assert!(!result.valid);
@@ -1057,6 +1069,7 @@ mod envelope_validation {
             },
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let result = validator.validate(&env);
         assert!(!result.valid);
@@ -1074,6 +1087,7 @@ mod envelope_validation {
             },
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let result = validator.validate(&env);
         assert!(result.valid);
@@ -1113,6 +1127,7 @@ mod envelope_validation {
         let env = Envelope::Event {
             ref_id: String::new(),
             event: make_event(AgentEventKind::AssistantMessage { text: "msg".into() }),
+            seq: None,
         };
         let result = validator.validate(&env);
         assert!(!result.valid);
@@ -1889,6 +1904,7 @@ mod edge_cases {
             event: make_event(AgentEventKind::AssistantMessage {
                 text: String::new(),
             }),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1934,6 +1950,7 @@ mod edge_cases {
                 kind: AgentEventKind::AssistantMessage { text: "hi".into() },
                 ext: Some(ext),
             },
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -575,6 +575,7 @@ mod forward_compat {
                     },
                     ext: None,
                 },
+                seq: None,
             },
             Envelope::Final {
                 ref_id: "run-1".into(),
@@ -1061,6 +1062,7 @@ mod validator_sequence {
                     kind: AgentEventKind::AssistantDelta { text: "hi".into() },
                     ext: None,
                 },
+                seq: None,
             },
             Envelope::Final {
                 ref_id: "run-1".into(),
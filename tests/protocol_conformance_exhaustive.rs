@@ -66,6 +66,7 @@ fn event_msg(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantMessage { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -77,6 +78,7 @@ fn event_delta(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantDelta { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -93,6 +95,7 @@ fn event_tool_call(ref_id: &str, name: &str, input: Value) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -109,6 +112,7 @@ fn event_tool_result(ref_id: &str, name: &str, output: Value) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -123,6 +127,7 @@ fn event_file_changed(ref_id: &str, path: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -138,6 +143,7 @@ fn event_command(ref_id: &str, cmd: &str, exit_code: Option<i32>) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -151,6 +157,7 @@ fn event_warning(ref_id: &str, msg: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -165,6 +172,7 @@ fn event_error(ref_id: &str, msg: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -178,6 +186,7 @@ fn event_run_started(ref_id: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -191,6 +200,7 @@ fn event_run_completed(ref_id: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -345,7 +355,7 @@ mod deserialization {
         let env = event_msg("r1", "hello world");
         let json = serde_json::to_string(&env).unwrap();
         let back: Envelope = serde_json::from_str(&json).unwrap();
-        if let Envelope::Event { ref_id, event } = back {
+        if let Envelope::Event { ref_id, event, .. } = back {
             assert_eq!(ref_id, "r1");
             assert!(matches!(
                 event.kind,
@@ -1544,6 +1554,7 @@ mod extension_field {
                 kind: AgentEventKind::AssistantMessage { text: "hi".into() },
                 ext: Some(ext),
             },
+            seq: None,
         };
         let encoded = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1571,6 +1582,7 @@ mod extension_field {
                 kind: AgentEventKind::AssistantDelta { text: "t".into() },
                 ext: Some(ext),
             },
+            seq: None,
         };
         let encoded = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1592,6 +1604,7 @@ mod extension_field {
                 kind: AgentEventKind::AssistantMessage { text: "t".into() },
                 ext: Some(BTreeMap::new()),
             },
+            seq: None,
         };
         let encoded = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
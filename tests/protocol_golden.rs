@@ -112,6 +112,7 @@ fn sample_receipt() -> Receipt {
             git_diff: Some("diff --git a/file.txt".into()),
             git_status: Some("M file.txt".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -175,6 +176,7 @@ fn golden_serialize_event_delta() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!("golden_event_delta", serde_json::to_value(&env).unwrap());
 }
@@ -193,6 +195,7 @@ fn golden_serialize_event_tool_call() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(
         "golden_event_tool_call",
@@ -214,6 +217,7 @@ fn golden_serialize_event_tool_result() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(
         "golden_event_tool_result",
@@ -267,6 +271,7 @@ fn golden_deserialize_hello() {
             backend,
             capabilities,
             mode,
+            ..
         } => {
             assert_eq!(contract_version, "abp/v0.1");
             assert_eq!(backend.id, "test");
@@ -301,7 +306,7 @@ fn golden_deserialize_event() {
     let line = r#"{"t":"event","ref_id":"run-1","event":{"ts":"2025-01-15T12:00:00Z","type":"assistant_delta","text":"hi"}}"#;
     let env = JsonlCodec::decode(line).unwrap();
     match env {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             assert!(
                 matches!(event.kind, AgentEventKind::AssistantDelta { ref text } if text == "hi")
@@ -440,6 +445,7 @@ fn roundtrip_event_all_kinds() {
                 kind,
                 ext: None,
             },
+            seq: None,
         });
     }
 }
@@ -526,6 +532,7 @@ fn edge_event_with_ext() {
                 m
             }),
         },
+        seq: None,
     };
     roundtrip(&env);
 }
@@ -555,6 +562,7 @@ fn edge_tool_call_null_ids() {
             },
             ext: None,
         },
+        seq: None,
     };
     roundtrip(&env);
 }
@@ -576,6 +584,7 @@ fn edge_receipt_all_usage_null() {
         git_diff: None,
         git_status: None,
         harness_ok: false,
+        input_digest: None,
     };
     let env = Envelope::Final {
         ref_id: "r".into(),
@@ -607,6 +616,7 @@ fn jsonl_multi_line_parse() {
             },
             ext: None,
         },
+        seq: None,
     };
     let fatal = Envelope::Fatal {
         ref_id: Some("r1".into()),
@@ -798,7 +808,7 @@ fn field_order_event_shuffled() {
     let line = r#"{"event":{"ts":"2025-01-15T12:00:00Z","type":"warning","message":"watch out"},"t":"event","ref_id":"r1"}"#;
     let env = JsonlCodec::decode(line).unwrap();
     match env {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "r1");
             assert!(
                 matches!(event.kind, AgentEventKind::Warning { ref message } if message == "watch out")
@@ -839,6 +849,7 @@ fn unicode_in_event_text() {
             },
             ext: None,
         },
+        seq: None,
     };
     roundtrip(&env);
 }
@@ -857,6 +868,7 @@ fn unicode_in_tool_output() {
             },
             ext: None,
         },
+        seq: None,
     };
     roundtrip(&env);
 }
@@ -891,6 +903,7 @@ fn large_tool_output() {
             },
             ext: None,
         },
+        seq: None,
     };
     roundtrip(&env);
 }
@@ -907,6 +920,7 @@ fn large_assistant_message() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -953,6 +967,7 @@ fn large_jsonl_stream() {
                 },
                 ext: None,
             },
+            seq: None,
         })
         .collect();
     JsonlCodec::encode_many_to_writer(&mut buf, &envelopes).unwrap();
@@ -121,6 +121,7 @@ fn all_envelope_variants() -> Vec<Envelope> {
         Envelope::Event {
             ref_id: "run-1".into(),
             event: test_event(),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "run-1".into(),
@@ -198,6 +199,7 @@ mod envelope_invariants {
         let env = Envelope::Event {
             ref_id: "r".into(),
             event: test_event(),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(json.trim()).unwrap();
@@ -253,6 +255,7 @@ mod envelope_invariants {
             let env = Envelope::Event {
                 ref_id: id.to_string(),
                 event: test_event(),
+                seq: None,
             };
             let json = JsonlCodec::encode(&env).unwrap();
             let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -376,6 +379,7 @@ mod protocol_ordering {
         Envelope::Event {
             ref_id: "run-1".into(),
             event: test_event(),
+            seq: None,
         }
     }
 
@@ -497,6 +501,7 @@ mod protocol_ordering {
             Envelope::Event {
                 ref_id: "wrong-id".into(),
                 event: test_event(),
+                seq: None,
             },
             final_env(),
         ];
@@ -551,6 +556,7 @@ mod envelope_edge_cases {
         let env = Envelope::Event {
             ref_id: String::new(),
             event: test_event(),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -582,6 +588,7 @@ mod envelope_edge_cases {
         let env = Envelope::Event {
             ref_id: long_id.clone(),
             event: test_event(),
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -647,11 +654,12 @@ mod envelope_edge_cases {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
         match decoded {
-            Envelope::Event { ref_id, event } => {
+            Envelope::Event { ref_id, event, .. } => {
                 assert_eq!(ref_id, "ünïcödé-rün");
                 match event.kind {
                     AgentEventKind::AssistantMessage { text } => {
@@ -745,6 +753,7 @@ mod envelope_edge_cases {
                 kind: AgentEventKind::AssistantMessage { text: "hi".into() },
                 ext: Some(ext.clone()),
             },
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -772,6 +781,7 @@ mod envelope_edge_cases {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         // ext=None should be omitted from JSON (skip_serializing_if)
@@ -841,6 +851,7 @@ mod envelope_edge_cases {
                     kind,
                     ext: None,
                 },
+                seq: None,
             };
             let json = JsonlCodec::encode(&env).unwrap();
             let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -67,6 +67,7 @@ fn make_event(ref_id: &str, msg: &str) -> Envelope {
             kind: AgentEventKind::AssistantMessage { text: msg.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -222,6 +223,7 @@ fn empty_ref_id_event_validation_error() {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: None,
         },
+        seq: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&event);
@@ -381,6 +383,7 @@ fn large_payload_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
 
     let encoded = JsonlCodec::encode(&event).unwrap();
@@ -408,6 +411,7 @@ fn large_payload_triggers_validation_warning() {
             kind: AgentEventKind::AssistantMessage { text: big_text },
             ext: None,
         },
+        seq: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&event);
@@ -648,6 +652,7 @@ fn valid_multibyte_utf8_in_payload() {
             },
             ext: None,
         },
+        seq: None,
     };
 
     let encoded = JsonlCodec::encode(&event).unwrap();
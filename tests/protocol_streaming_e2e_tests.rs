@@ -76,6 +76,7 @@ fn make_event(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantMessage { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -92,6 +93,7 @@ fn make_tool_call_event(ref_id: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -106,6 +108,7 @@ fn make_error_event(ref_id: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -310,7 +313,7 @@ fn envelope_parse_event() {
     let line = JsonlCodec::encode(&event).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             assert!(matches!(
                 event.kind,
@@ -430,6 +433,7 @@ fn envelope_parse_event_with_ext_field() {
             },
             ext: Some(ext),
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -524,6 +528,7 @@ fn stream_events_with_tool_calls() {
                 },
                 ext: None,
             },
+            seq: None,
         },
     ];
 
@@ -664,6 +669,7 @@ fn stream_simulation_file_changed_and_command_events() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Event {
             ref_id: "run-1".into(),
@@ -676,6 +682,7 @@ fn stream_simulation_file_changed_and_command_events() {
                 },
                 ext: None,
             },
+            seq: None,
         },
     ];
 
@@ -697,6 +704,7 @@ fn stream_simulation_run_started_completed() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         make_event("run-1", "working"),
         Envelope::Event {
@@ -708,6 +716,7 @@ fn stream_simulation_run_started_completed() {
                 },
                 ext: None,
             },
+            seq: None,
         },
     ];
 
@@ -757,6 +766,7 @@ fn encode_event_assistant_delta_jsonl() {
             kind: AgentEventKind::AssistantDelta { text: "tok".into() },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains(r#""type":"assistant_delta""#));
@@ -784,6 +794,7 @@ fn encode_event_tool_result_jsonl() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains(r#""type":"tool_result""#));
@@ -800,6 +811,7 @@ fn encode_event_warning_jsonl() {
             },
             ext: None,
         },
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains(r#""type":"warning""#));
@@ -1072,6 +1084,7 @@ fn edge_deeply_nested_json_in_event() {
             },
             ext: None,
         },
+        seq: None,
     };
 
     let line = JsonlCodec::encode(&env).unwrap();
@@ -1102,6 +1115,7 @@ fn edge_special_json_values_in_payload() {
             },
             ext: None,
         },
+        seq: None,
     };
 
     let line = JsonlCodec::encode(&env).unwrap();
@@ -1199,6 +1213,7 @@ fn edge_encode_all_event_kinds_roundtrip() {
                 kind,
                 ext: None,
             },
+            seq: None,
         };
         let line = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -399,6 +399,7 @@ fn hello_with_custom_version_round_trips() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let json = JsonlCodec::encode(&hello).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -903,6 +904,7 @@ fn hello_envelope_with_future_version_deserializes() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let json = JsonlCodec::encode(&hello).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -986,6 +988,7 @@ fn validator_rejects_empty_contract_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = EnvelopeValidator::new().validate(&hello);
     assert!(!result.valid);
@@ -1007,6 +1010,7 @@ fn validator_rejects_unparseable_contract_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = EnvelopeValidator::new().validate(&hello);
     assert!(!result.valid);
@@ -1122,6 +1126,7 @@ fn negotiate_then_build_hello_with_effective_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     match &hello {
         Envelope::Hello {
@@ -1318,6 +1323,7 @@ fn decode_stream_multiple_hello_envelopes_with_versions() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let h2 = Envelope::Hello {
         contract_version: "abp/v0.2".into(),
@@ -1328,6 +1334,7 @@ fn decode_stream_multiple_hello_envelopes_with_versions() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let mut buf = Vec::new();
     JsonlCodec::encode_many_to_writer(&mut buf, &[h1, h2]).unwrap();
@@ -66,6 +66,7 @@ fn make_event(ref_id: &str, text: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -265,7 +266,7 @@ fn event_roundtrip() {
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-42");
             match event.kind {
                 AgentEventKind::AssistantMessage { text } => {
@@ -300,6 +301,7 @@ fn event_assistant_delta() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -325,6 +327,7 @@ fn event_tool_call_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -356,6 +359,7 @@ fn event_tool_result_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -387,6 +391,7 @@ fn event_file_changed_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -415,6 +420,7 @@ fn event_command_executed_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -443,6 +449,7 @@ fn event_warning_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -467,6 +474,7 @@ fn event_error_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -496,6 +504,7 @@ fn event_run_started_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -518,6 +527,7 @@ fn event_run_completed_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1152,6 +1162,7 @@ fn validate_hello_empty_backend_id() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = v.validate(&env);
     assert!(!result.valid);
@@ -1173,6 +1184,7 @@ fn validate_hello_empty_contract_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = v.validate(&env);
     assert!(!result.valid);
@@ -1194,6 +1206,7 @@ fn validate_hello_invalid_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = v.validate(&env);
     assert!(!result.valid);
@@ -1217,6 +1230,7 @@ fn validate_hello_warns_missing_backend_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = v.validate(&env);
     assert!(result.valid);
@@ -2065,6 +2079,7 @@ fn event_with_ext_field_roundtrip() {
             },
             ext: Some(ext),
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -94,6 +94,7 @@ fn event_env(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantMessage { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -105,6 +106,7 @@ fn event_delta_env(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantDelta { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -219,7 +221,7 @@ fn roundtrip_event_assistant_message() {
     let env = event_env("run-1", "The answer is 42.");
     let decoded = roundtrip(&env);
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             assert!(matches!(
                 event.kind,
@@ -259,6 +261,7 @@ fn roundtrip_event_tool_call() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -286,6 +289,7 @@ fn roundtrip_event_tool_result() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -311,6 +315,7 @@ fn roundtrip_event_file_changed() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -337,6 +342,7 @@ fn roundtrip_event_command_executed() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -364,6 +370,7 @@ fn roundtrip_event_warning() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -389,6 +396,7 @@ fn roundtrip_event_error() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -413,6 +421,7 @@ fn roundtrip_event_run_started() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -434,6 +443,7 @@ fn roundtrip_event_run_completed() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -889,6 +899,7 @@ fn base64_in_tool_call_input() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -919,6 +930,7 @@ fn large_base64_payload_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -1315,6 +1327,7 @@ fn validate_hello_invalid_contract_version() {
         backend: backend("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -1334,6 +1347,7 @@ fn validate_hello_empty_contract_version() {
         backend: backend("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -1513,6 +1527,7 @@ fn event_ext_field_roundtrip() {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -634,15 +634,15 @@ fn no_crate_depends_on_daemon() {
 }
 
 #[test]
-fn shim_crates_do_not_depend_on_runtime() {
+fn runtime_does_not_depend_on_shims() {
     for (rel, t) in workspace_members() {
-        if !rel.contains("abp-shim-") {
+        if !rel.ends_with("abp-runtime") {
             continue;
         }
         let deps = internal_deps(&t);
         assert!(
-            !deps.contains(&"abp-runtime".to_string()),
-            "{rel} (shim) should not depend on abp-runtime"
+            !deps.iter().any(|d| d.starts_with("abp-shim-")),
+            "{rel} should not depend on shim crates (shims sit above the runtime)"
         );
     }
 }
@@ -144,6 +144,7 @@ fn populated_receipt() -> Receipt {
             git_diff: Some("diff --git a/f.rs".into()),
             git_status: Some("M f.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
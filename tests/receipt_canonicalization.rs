@@ -512,6 +512,7 @@ fn edge_all_optional_fields_none() {
             git_diff: None,
             git_status: None,
             harness_ok: false,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -571,6 +572,7 @@ fn edge_all_optional_fields_some() {
             git_diff: Some("--- a/file\n+++ b/file\n@@ ...\n+line".into()),
             git_status: Some("M file".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -777,6 +779,7 @@ fn serde_roundtrip_preserves_all_fields() {
             git_diff: Some("diff".into()),
             git_status: Some("M file".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: Some("abc123".into()),
@@ -1082,6 +1085,7 @@ fn builder_chained_methods_all_reflected() {
             git_diff: Some("diff".into()),
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         })
         .build();
 
@@ -136,6 +136,7 @@ fn full_receipt() -> Receipt {
             git_diff: Some("diff --git a/foo b/foo".into()),
             git_status: Some("M foo".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .add_trace_event(AgentEvent {
             ts: t1,
@@ -287,6 +288,7 @@ fn construction_builder_verification() {
         git_diff: Some("diff".into()),
         git_status: Some("M file.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let r = ReceiptBuilder::new("x").verification(v).build();
     assert_eq!(r.verification.git_diff.as_deref(), Some("diff"));
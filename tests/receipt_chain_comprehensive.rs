@@ -162,6 +162,7 @@ fn construct_receipt_builder_all_setters() {
             git_diff: Some("diff".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .add_trace_event(AgentEvent {
             ts: ts1,
@@ -468,6 +469,7 @@ fn serde_roundtrip_preserves_all_fields() {
             git_diff: Some("diff".into()),
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         })
         .add_trace_event(AgentEvent {
             ts,
@@ -1870,6 +1870,7 @@ fn receipt_with_full_verification_report() {
             git_diff: Some("diff --git a/file.rs b/file.rs\n+new line".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .with_hash()
         .unwrap();
@@ -2271,6 +2272,7 @@ fn serde_roundtrip_with_verification_report_preserves_hash() {
             git_diff: Some("diff --git a/x b/x\n+line".into()),
             git_status: Some("M x".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .with_hash()
         .unwrap();
@@ -1346,6 +1346,7 @@ fn t77_receipt_with_verification_report() {
             git_diff: Some("diff --git a/foo b/foo\n+bar".into()),
             git_status: Some("M foo".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .with_hash()
         .unwrap();
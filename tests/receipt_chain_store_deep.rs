@@ -270,6 +270,7 @@ fn hash_all_fields_populated() {
             git_diff: Some("diff --git a/f b/f".into()),
             git_status: Some("M f".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .build();
 
@@ -890,6 +891,7 @@ fn receipt_with_verification_data() {
             git_diff: Some("--- a/file\n+++ b/file\n@@ -1 +1 @@\n-old\n+new".into()),
             git_status: Some("M file".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .with_hash()
         .unwrap();
@@ -108,6 +108,7 @@ fn full_receipt() -> Receipt {
             git_diff: Some("diff --git a/foo b/foo".into()),
             git_status: Some("M foo".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .build()
 }
@@ -1342,6 +1343,7 @@ fn verification_all_none() {
         git_diff: None,
         git_status: None,
         harness_ok: false,
+        input_digest: None,
     };
     let json = serde_json::to_string(&v).unwrap();
     let rt: VerificationReport = serde_json::from_str(&json).unwrap();
@@ -1355,6 +1357,7 @@ fn verification_all_populated() {
         git_diff: Some("diff".into()),
         git_status: Some("status".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let json = serde_json::to_string(&v).unwrap();
     let rt: VerificationReport = serde_json::from_str(&json).unwrap();
@@ -1722,6 +1725,7 @@ fn builder_full_chain() {
             git_diff: Some("d".into()),
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         })
         .add_trace_event(AgentEvent {
             ts: fixed_time(),
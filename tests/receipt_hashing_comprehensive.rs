@@ -168,6 +168,7 @@ fn fully_populated_receipt() -> Receipt {
             git_diff: Some("diff --git a/src/main.rs".into()),
             git_status: Some("M src/main.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -920,6 +920,7 @@ fn metadata_verification_preserved() {
         git_diff: Some("diff text".into()),
         git_status: Some("M file.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let receipt = ReceiptBuilder::new("test")
         .outcome(Outcome::Complete)
@@ -1377,6 +1378,7 @@ fn serde_roundtrip_preserves_all_fields() {
             git_diff: Some("diff".into()),
             git_status: Some("M".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .add_artifact(ArtifactRef {
             kind: "patch".into(),
@@ -1827,6 +1829,7 @@ fn builder_chained_calls() {
             git_diff: None,
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         })
         .add_artifact(ArtifactRef {
             kind: "file".into(),
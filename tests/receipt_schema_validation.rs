@@ -86,6 +86,7 @@ fn full_receipt() -> Receipt {
             git_diff: Some("diff --git a/foo.rs b/foo.rs\n".into()),
             git_status: Some("M foo.rs\n".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .add_trace_event(AgentEvent {
             ts: started,
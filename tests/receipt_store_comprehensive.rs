@@ -849,6 +849,7 @@ mod metadata_audit_trail {
             git_diff: Some("diff --git a/foo b/foo\n+bar".into()),
             git_status: Some("M foo.rs".into()),
             harness_ok: true,
+            input_digest: None,
         };
         let r = ReceiptBuilder::new("mock")
             .verification(vr)
@@ -1516,6 +1517,7 @@ mod edge_cases {
                 git_diff: Some("diff".into()),
                 git_status: Some("M file.rs".into()),
                 harness_ok: true,
+                input_digest: None,
             })
             .add_artifact(ArtifactRef {
                 kind: "patch".into(),
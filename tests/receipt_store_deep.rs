@@ -1365,6 +1365,7 @@ fn receipt_construction_verification_report_all_fields() {
         git_diff: Some("diff --git a/foo.rs b/foo.rs".into()),
         git_status: Some("M foo.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let r = ReceiptBuilder::new("test").verification(vr).build();
     assert!(r.verification.harness_ok);
@@ -1568,6 +1569,7 @@ fn serde_roundtrip_preserves_verification_report() {
             git_diff: Some("diff data".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .build();
     let json = serde_json::to_string(&r).unwrap();
@@ -111,6 +111,7 @@ fn full_receipt() -> Receipt {
         git_diff: Some("diff --git a/f b/f".into()),
         git_status: Some("M f".into()),
         harness_ok: true,
+        input_digest: None,
     };
 
     ReceiptBuilder::new("sidecar:node")
@@ -333,6 +334,7 @@ mod builder_patterns {
             git_diff: Some("diff".into()),
             git_status: Some("M file".into()),
             harness_ok: true,
+            input_digest: None,
         };
         let r = ReceiptBuilder::new("mock").verification(v).build();
         assert!(r.verification.harness_ok);
@@ -1150,6 +1152,7 @@ mod serialization_roundtrip {
             git_diff: Some("diff --git".into()),
             git_status: Some("M src/lib.rs".into()),
             harness_ok: true,
+            input_digest: None,
         };
         let r = ReceiptBuilder::new("mock")
             .run_id(fixed_uuid(1))
@@ -1295,6 +1298,7 @@ mod edge_cases {
             git_diff: Some("改行\n追加".into()),
             git_status: None,
             harness_ok: false,
+            input_digest: None,
         };
         let r = ReceiptBuilder::new("mock")
             .run_id(fixed_uuid(1))
@@ -91,6 +91,7 @@ fn make_full_receipt() -> Receipt {
         git_diff: Some("diff --git a/foo b/foo".into()),
         git_status: Some("M foo".into()),
         harness_ok: true,
+        input_digest: None,
     };
 
     ReceiptBuilder::new("test-backend")
@@ -168,6 +168,7 @@ fn rh_receipt_with_all_optional_fields() {
             git_diff: Some("diff here".into()),
             git_status: Some("M src/lib.rs".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .build();
     let h = receipt_hash(&r).unwrap();
@@ -695,6 +696,7 @@ fn proto_event_ref_id_correlates() {
     let env = Envelope::Event {
         ref_id: "run-42".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     assert!(json.contains(r#""ref_id":"run-42""#));
@@ -1586,6 +1586,7 @@ fn event_envelope_has_ref_id() {
         event: make_event(AgentEventKind::RunStarted {
             message: "go".into(),
         }),
+        seq: None,
     };
     let json = JsonlCodec::encode(&event).unwrap();
     let v: serde_json::Value = serde_json::from_str(json.trim()).unwrap();
@@ -356,7 +356,8 @@ fn registry_list_returns_sorted() {
     let mut rt = Runtime::new();
     rt.register_backend("z", MockBackend);
     rt.register_backend("a", MockBackend);
-    let list = rt.registry().list();
+    let registry = rt.registry();
+    let list = registry.list();
     assert_eq!(list, vec!["a", "z"]);
 }
 
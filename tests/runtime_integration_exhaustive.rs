@@ -196,6 +196,7 @@ impl Backend for MultiEventBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: Outcome::Complete,
             receipt_sha256: None,
@@ -297,6 +298,7 @@ impl Backend for ToolCallBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: Outcome::Complete,
             receipt_sha256: None,
@@ -384,6 +386,7 @@ impl Backend for ErrorEventBackend {
                 git_diff: None,
                 git_status: None,
                 harness_ok: true,
+                input_digest: None,
             },
             outcome: Outcome::Partial,
             receipt_sha256: None,
@@ -622,7 +622,8 @@ fn reg_registry_contains_checks() {
 #[test]
 fn reg_registry_list() {
     let rt = Runtime::with_default_backends();
-    let list = rt.registry().list();
+    let registry = rt.registry();
+    let list = registry.list();
     assert_eq!(list, vec!["mock"]);
 }
 
@@ -1203,6 +1204,9 @@ async fn stream_all_event_kinds_can_be_streamed() {
             AgentEventKind::ToolResult { .. } => "tool_result",
             AgentEventKind::FileChanged { .. } => "file_changed",
             AgentEventKind::CommandExecuted { .. } => "command_executed",
+            AgentEventKind::Progress { .. } => "progress",
+            AgentEventKind::RunSummary { .. } => "run_summary",
+            AgentEventKind::ArtifactChunk { .. } => "artifact_chunk",
             AgentEventKind::Warning { .. } => "warning",
             AgentEventKind::Error { .. } => "error",
         };
@@ -1570,6 +1574,7 @@ fn receipt_builder_verification() {
         git_diff: Some("diff".into()),
         git_status: Some("status".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let r = ReceiptBuilder::new("ver").verification(v).build();
     assert!(r.verification.harness_ok);
@@ -812,7 +812,8 @@ fn runtime_projection_with_mapper_and_capabilities() {
     assert_eq!(result.selected_backend, "mock");
 
     // The mapper should be resolvable for OpenAI identity
-    let pm = rt.projection().unwrap();
+    let pm = rt.projection();
+    let pm = pm.as_ref().unwrap();
     let mapper = pm.resolve_mapper(Dialect::OpenAi, Dialect::OpenAi);
     assert!(mapper.is_some());
 }
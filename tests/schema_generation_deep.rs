@@ -685,6 +685,7 @@ fn capability_enum_has_all_variants() {
         "batch_mode",
         "embeddings",
         "image_generation",
+        "response_language",
     ];
     for e in &expected {
         assert!(consts.contains(e), "Capability missing variant: {e}");
@@ -711,8 +712,11 @@ fn agent_event_kind_variants_in_schema() {
         "tool_result",
         "file_changed",
         "command_executed",
+        "progress",
         "warning",
         "error",
+        "run_summary",
+        "artifact_chunk",
     ];
     for e in &expected {
         assert!(
@@ -327,6 +327,7 @@ fn capability_enum_variants_in_schema() {
         "batch_mode",
         "embeddings",
         "image_generation",
+        "response_language",
     ];
     for variant in &expected {
         assert!(
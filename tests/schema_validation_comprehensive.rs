@@ -1278,6 +1278,7 @@ fn cross_receipt_verification_matches_standalone() {
             git_diff: Some("diff".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .build();
     let r_json = serde_json::to_value(&r).unwrap();
@@ -763,6 +763,7 @@ fn envelope_event_roundtrip() {
     let envelope = abp_protocol::Envelope::Event {
         ref_id: "run-1".into(),
         event: ev,
+        seq: None,
     };
     let json = serde_json::to_string(&envelope).unwrap();
     assert!(json.contains(r#""t":"event""#));
@@ -1370,6 +1371,9 @@ fn serde_work_order_with_config_validates() {
         env: BTreeMap::from([("API_KEY".into(), "xxx".into())]),
         max_budget_usd: Some(10.0),
         max_turns: Some(50),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let v = serde_json::to_value(&wo).unwrap();
     assert_valid(&s, &v);
@@ -1436,6 +1440,7 @@ fn serde_receipt_with_verification_validates() {
         git_diff: Some("diff --git ...".into()),
         git_status: Some("M src/main.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     let v = serde_json::to_value(&r).unwrap();
     assert_valid(&s, &v);
@@ -2336,6 +2336,8 @@ mod edge_cases {
                 content: "hi".into(),
             }],
             thinking: None,
+            tools: vec![],
+            tool_choice: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         let parsed: ClaudeRequest = serde_json::from_str(&json).unwrap();
@@ -362,6 +362,8 @@ fn claude_message_request_construction() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     assert_eq!(req.model, "claude-sonnet-4-20250514");
     assert_eq!(req.max_tokens, 1024);
@@ -383,6 +385,8 @@ fn claude_message_request_serde_roundtrip() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let json = serde_json::to_string(&req).unwrap();
     let back: abp_shim_claude::MessageRequest = serde_json::from_str(&json).unwrap();
@@ -526,6 +530,8 @@ fn claude_system_prompt_handling() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let json = serde_json::to_value(&with_system).unwrap();
     assert_eq!(json["system"], "Be concise");
@@ -542,6 +548,8 @@ fn claude_system_prompt_handling() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let json = serde_json::to_value(&without_system).unwrap();
     assert!(
@@ -992,6 +1000,8 @@ fn cross_sdk_same_prompt_different_request_formats() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let claude_json = serde_json::to_value(&claude_req).unwrap();
     assert_eq!(claude_json["messages"][0]["role"], "user");
@@ -1201,6 +1211,8 @@ fn cross_sdk_system_prompt_patterns() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let cj = serde_json::to_value(&claude_req).unwrap();
     assert_eq!(cj["system"], "Be helpful");
@@ -1415,6 +1427,8 @@ fn cross_sdk_request_serde_roundtrip() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let json = serde_json::to_string(&claude_req).unwrap();
     let _: abp_shim_claude::MessageRequest = serde_json::from_str(&json).unwrap();
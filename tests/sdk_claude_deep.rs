@@ -214,6 +214,8 @@ fn request_serde_roundtrip() {
         system: Some("system".into()),
         messages: vec![claude_msg("user", "hi")],
         thinking: Some(ThinkingConfig::new(5000)),
+        tools: vec![],
+        tool_choice: None,
     };
     let json = serde_json::to_string(&req).unwrap();
     let back: ClaudeRequest = serde_json::from_str(&json).unwrap();
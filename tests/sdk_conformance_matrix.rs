@@ -362,6 +362,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         }
     }
 
@@ -392,6 +394,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         }
     }
 
@@ -1265,6 +1269,8 @@ mod cross_shim {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         });
         assert!(!wo.task.is_empty(), "claude task empty");
 
@@ -1323,6 +1329,8 @@ mod cross_shim {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         });
         assert_eq!(
             claude_wo.config.model,
@@ -75,6 +75,8 @@ fn simple_request(text: &str) -> MessageRequest {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     }
 }
 
@@ -203,6 +205,8 @@ mod messages_api_to_work_order {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let wo = request_to_work_order(&req);
         assert_eq!(wo.task, "Write tests");
@@ -260,6 +264,8 @@ mod messages_api_to_work_order {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let claude_req = request_to_claude(&req);
         assert_eq!(claude_req.messages.len(), 3);
@@ -939,6 +945,8 @@ mod system_prompt_handling {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let claude_req = request_to_claude(&req);
         assert_eq!(claude_req.system.as_deref(), Some("System instructions"));
@@ -1606,6 +1614,8 @@ mod error_type_mapping {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let err = client.create(req).await.unwrap_err();
         assert!(matches!(err, ShimError::InvalidRequest(_)));
@@ -1668,6 +1678,8 @@ mod error_type_mapping {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let err = client.create_stream(req).await.unwrap_err();
         assert!(matches!(err, ShimError::InvalidRequest(_)));
@@ -1801,6 +1813,8 @@ mod metadata_and_tracking {
             stop_sequences: Some(vec!["STOP".into()]),
             thinking: Some(ThinkingConfig::new(2048)),
             stream: Some(false),
+            tools: None,
+            tool_choice: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         let back: MessageRequest = serde_json::from_str(&json).unwrap();
@@ -2168,6 +2182,8 @@ mod content_block_serde {
             system: Some("system prompt".into()),
             messages: vec![claude_msg("user", "hi")],
             thinking: Some(ThinkingConfig::new(5000)),
+            tools: vec![],
+            tool_choice: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         let back: ClaudeRequest = serde_json::from_str(&json).unwrap();
@@ -730,6 +730,8 @@ mod model_name {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let wo = abp_shim_claude::request_to_work_order(&req);
         assert_eq!(wo.config.model.as_deref(), Some("claude-sonnet-4-20250514"));
@@ -2076,6 +2076,7 @@ mod receipts {
                 git_diff: Some("+fn new_function() {}".into()),
                 git_status: Some("M src/lib.rs".into()),
                 harness_ok: true,
+                input_digest: None,
             })
             .build();
         insta::assert_json_snapshot!(receipt, {
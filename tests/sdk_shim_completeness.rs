@@ -292,6 +292,8 @@ fn claude_message_request_construction() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
 
     assert_eq!(req.model, "claude-sonnet-4-20250514");
@@ -346,6 +348,8 @@ fn claude_system_prompt_in_request() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
 
     let wo = request_to_work_order(&req);
@@ -395,6 +399,8 @@ fn claude_request_to_claude_sdk() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
 
     let claude_req = request_to_claude(&req);
@@ -420,6 +426,8 @@ fn claude_request_to_work_order() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
 
     let wo = request_to_work_order(&req);
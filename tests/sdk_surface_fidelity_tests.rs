@@ -318,6 +318,8 @@ mod claude {
             stop_sequences: Some(vec!["STOP".into()]),
             thinking: None,
             stream: Some(false),
+            tools: None,
+            tool_choice: None,
         };
         assert_eq!(req.model, "claude-sonnet-4-20250514");
         assert_eq!(req.max_tokens, 1024);
@@ -455,6 +457,8 @@ mod claude {
                 stop_sequences: None,
                 thinking: None,
                 stream: None,
+                tools: None,
+                tool_choice: None,
             };
             assert_eq!(req.model, *model);
         }
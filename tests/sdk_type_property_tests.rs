@@ -1116,6 +1116,9 @@ fn arb_work_order() -> impl Strategy<Value = WorkOrder> {
                 env: BTreeMap::new(),
                 max_budget_usd: None,
                 max_turns: None,
+                response_language: None,
+                fidelity_policy: None,
+                submitter: None,
             },
         })
 }
@@ -1464,6 +1467,9 @@ proptest! {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         };
         let json = serde_json::to_string(&config).expect("serialize");
         let v: serde_json::Value = serde_json::from_str(&json).expect("parse");
@@ -711,6 +711,7 @@ fn audit_oversized_event_produces_warning() {
     let envelope = Envelope::Event {
         ref_id: "run-1".into(),
         event,
+        seq: None,
     };
     let result = EnvelopeValidator::new().validate(&envelope);
     assert!(
@@ -128,6 +128,7 @@ fn jsonl_injection_via_ref_id_prevented() {
             },
             ext: None,
         },
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&event).unwrap();
     assert_eq!(encoded.matches('\n').count(), 1);
@@ -541,6 +542,7 @@ fn special_chars_in_tool_call_input_round_trip() {
     let envelope = Envelope::Event {
         ref_id: "run-1".into(),
         event: event.clone(),
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&envelope).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -563,6 +565,7 @@ fn emoji_in_assistant_message_round_trips() {
     let envelope = Envelope::Event {
         ref_id: "run-1".into(),
         event,
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&envelope).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1453,6 +1456,7 @@ fn validator_detects_ref_id_mismatch() {
                 },
                 ext: None,
             },
+            seq: None,
         },
     ];
     let errors = validator.validate_sequence(&envelopes);
@@ -663,6 +663,7 @@ fn protocol_sequence_ref_id_mismatch_detected() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Fatal {
             ref_id: Some("run-1".into()),
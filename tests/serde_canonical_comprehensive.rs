@@ -1122,6 +1122,7 @@ fn receipt_hash_changes_with_verification() {
             git_diff: Some("diff content".into()),
             git_status: None,
             harness_ok: true,
+            input_digest: None,
         },
         ..minimal_receipt()
     };
@@ -1381,6 +1382,7 @@ fn envelope_event_uses_t_tag() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event: event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     assert!(json.contains(r#""t":"event""#));
@@ -1631,6 +1633,7 @@ fn jsonl_roundtrip_preserves_all_envelope_variants() {
         Envelope::Event {
             ref_id: "r1".into(),
             event: event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r1".into(),
@@ -1690,6 +1693,7 @@ fn receipt_serde_roundtrip_preserves_all_fields() {
             git_diff: Some("diff --git".into()),
             git_status: Some("M file.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -623,6 +623,7 @@ fn envelope_hello_uses_tag_t() {
         backend: make_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let v: Value = serde_json::to_value(&env).unwrap();
     assert_eq!(v["t"], "hello");
@@ -649,6 +650,7 @@ fn envelope_event_uses_tag_t() {
         event: make_event(AgentEventKind::RunStarted {
             message: "go".into(),
         }),
+        seq: None,
     };
     let v: Value = serde_json::to_value(&env).unwrap();
     assert_eq!(v["t"], "event");
@@ -682,6 +684,7 @@ fn envelope_hello_canonical_deterministic() {
         backend: make_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let json1 = canonical_json(&env).unwrap();
     let json2 = canonical_json(&env).unwrap();
@@ -707,6 +710,7 @@ fn envelope_all_variants_roundtrip() {
             backend: make_backend(),
             capabilities: CapabilityManifest::new(),
             mode: ExecutionMode::default(),
+            features: None,
         },
         Envelope::Run {
             id: "r".into(),
@@ -717,6 +721,7 @@ fn envelope_all_variants_roundtrip() {
             event: make_event(AgentEventKind::RunStarted {
                 message: "go".into(),
             }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r".into(),
@@ -1258,6 +1263,7 @@ fn envelope_uses_tag_t_not_type() {
         backend: make_backend(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let v: Value = serde_json::to_value(&env).unwrap();
     assert!(v.get("t").is_some(), "Envelope should use 't' tag");
@@ -1294,6 +1300,7 @@ fn nested_event_in_envelope_preserves_both_tags() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event,
+        seq: None,
     };
     let v: Value = serde_json::to_value(&env).unwrap();
     // Outer envelope uses "t"
@@ -1902,6 +1909,7 @@ fn receipt_full_lifecycle_hash_stability() {
         git_diff: Some("diff content".into()),
         git_status: Some("M src/main.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     r.trace = vec![
         make_event(AgentEventKind::RunStarted {
@@ -1929,6 +1937,9 @@ fn work_order_full_config_canonical() {
         env: BTreeMap::new(),
         max_budget_usd: Some(100.0),
         max_turns: Some(10),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     config.vendor.insert(
         "anthropic".into(),
@@ -1996,6 +2007,7 @@ fn envelope_hello_with_full_capabilities() {
         backend: make_backend(),
         capabilities: caps,
         mode: ExecutionMode::Passthrough,
+        features: None,
     };
     let json1 = canonical_json(&env).unwrap();
     let json2 = canonical_json(&env).unwrap();
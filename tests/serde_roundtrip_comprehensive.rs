@@ -608,6 +608,7 @@ fn t46_envelope_event_roundtrip() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     assert!(encoded.contains("\"t\":\"event\""));
@@ -660,7 +661,9 @@ fn t49_backplane_config_roundtrip_json() {
         bind_address: None,
         port: Some(8080),
         policy_profiles: vec![],
+        policy_presets: Vec::new(),
         backends: BTreeMap::new(),
+        rbac: None,
     };
     let json = serde_json::to_string(&cfg).unwrap();
     let back: BackplaneConfig = serde_json::from_str(&json).unwrap();
@@ -677,7 +680,9 @@ fn t50_backplane_config_optional_fields_omitted() {
         bind_address: None,
         port: None,
         policy_profiles: vec![],
+        policy_presets: Vec::new(),
         backends: BTreeMap::new(),
+        rbac: None,
     };
     let json = serde_json::to_string(&cfg).unwrap();
     assert!(!json.contains("\"default_backend\""));
@@ -274,6 +274,9 @@ fn test_runtime_config_with_values() {
         env,
         max_budget_usd: Some(1.50),
         max_turns: Some(10),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     roundtrip_value(&cfg);
 }
@@ -545,6 +548,7 @@ fn test_verification_report_with_data() {
         git_diff: Some("diff --git a/f b/f\n".into()),
         git_status: Some("M f\n".into()),
         harness_ok: true,
+        input_digest: None,
     };
     roundtrip_value(&vr);
 }
@@ -1302,6 +1306,7 @@ fn test_envelope_hello_roundtrip() {
         backend: make_backend_identity(),
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     roundtrip_value(&env);
 }
@@ -1313,6 +1318,7 @@ fn test_envelope_hello_tag() {
         backend: make_backend_identity(),
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let json = serde_json::to_string(&env).unwrap();
     let v: Value = serde_json::from_str(&json).unwrap();
@@ -1340,6 +1346,7 @@ fn test_envelope_event_roundtrip() {
             kind: AgentEventKind::AssistantDelta { text: "hi".into() },
             ext: None,
         },
+        seq: None,
     };
     roundtrip_value(&env);
     let json = serde_json::to_string(&env).unwrap();
@@ -2249,12 +2256,24 @@ fn test_metrics_snapshot_serialize() {
         successful_runs: 90,
         failed_runs: 10,
         total_events: 500,
+        duplicate_events: 3,
+        event_gaps: 1,
+        missing_events: 1,
+        peak_workspace_bytes: 4096,
+        quota_exceeded_runs: 0,
         average_run_duration_ms: 1500,
+        total_cost_usd: 0.0,
+        costs: Vec::new(),
+        breaker_trips: 2,
+        breakers: BTreeMap::new(),
     };
     let json = serde_json::to_string(&ms).unwrap();
     assert_json_has_key(&json, "total_runs");
     assert_json_has_key(&json, "successful_runs");
     assert_json_has_key(&json, "average_run_duration_ms");
+    assert_json_has_key(&json, "duplicate_events");
+    assert_json_has_key(&json, "costs");
+    assert_json_has_key(&json, "breaker_trips");
 }
 
 // =========================================================================
@@ -2858,6 +2877,7 @@ fn test_nested_envelope_with_receipt_and_events() {
             git_diff: Some("diff data".into()),
             git_status: Some("M file".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: Some("sha256hash".into()),
@@ -2929,6 +2949,9 @@ fn test_nested_work_order_full() {
             },
             max_budget_usd: Some(2.0),
             max_turns: Some(20),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     roundtrip_value(&wo);
@@ -289,6 +289,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         assert_eq!(req.model, "claude-sonnet-4-20250514");
         assert_eq!(req.max_tokens, 4096);
@@ -308,6 +310,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let val: Value = serde_json::to_value(&req).unwrap();
         assert!(val.get("model").is_some(), "missing 'model'");
@@ -497,6 +501,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let sdk_req = request_to_claude(&req);
         assert_eq!(sdk_req.model, "claude-sonnet-4-20250514");
@@ -342,6 +342,8 @@ mod claude_shim {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         }
     }
 
@@ -360,6 +362,8 @@ mod claude_shim {
             stop_sequences: None,
             thinking: None,
             stream: Some(false),
+            tools: None,
+            tool_choice: None,
         };
 
         assert_eq!(req.model, "claude-sonnet-4-20250514");
@@ -534,6 +538,8 @@ mod claude_shim {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let result = client.create(req).await;
         assert!(result.is_err());
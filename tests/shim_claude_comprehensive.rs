@@ -66,6 +66,8 @@ fn simple_request(text: &str) -> MessageRequest {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     }
 }
 
@@ -98,6 +100,8 @@ fn full_request() -> MessageRequest {
         stop_sequences: Some(vec!["STOP".into()]),
         thinking: Some(ThinkingConfig::new(2048)),
         stream: Some(false),
+        tools: None,
+        tool_choice: None,
     }
 }
 
@@ -284,6 +288,8 @@ fn request_to_claude_structured_content_serialized() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let claude_req = request_to_claude(&req);
     // Multi-block content is JSON-serialized
@@ -1101,6 +1107,8 @@ async fn empty_messages_returns_invalid_request() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let err = client.create(req).await.unwrap_err();
     assert!(matches!(err, ShimError::InvalidRequest(_)));
@@ -1118,6 +1126,8 @@ async fn empty_messages_stream_returns_invalid_request() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let err = client.create_stream(req).await.unwrap_err();
     assert!(matches!(err, ShimError::InvalidRequest(_)));
@@ -1444,6 +1454,8 @@ fn request_to_work_order_extracts_task_from_last_message() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let wo = request_to_work_order(&req);
     assert_eq!(wo.task, "Last user message");
@@ -1467,6 +1479,8 @@ fn request_to_work_order_fallback_task() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let wo = request_to_work_order(&req);
     // No text block in last message → fallback
@@ -1960,6 +1974,8 @@ async fn full_roundtrip_multi_turn() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let resp = client.create(req).await.unwrap();
     assert_eq!(resp.role, "assistant");
@@ -2020,6 +2036,8 @@ fn many_messages_conversion() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let claude_req = request_to_claude(&req);
     assert_eq!(claude_req.messages.len(), 100);
@@ -69,6 +69,8 @@ fn simple_request(text: &str) -> MessageRequest {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     }
 }
 
@@ -146,6 +148,8 @@ fn request_all_fields_present_in_json() {
         stop_sequences: Some(vec!["END".into()]),
         thinking: Some(ThinkingConfig::new(2048)),
         stream: Some(true),
+        tools: None,
+        tool_choice: None,
     };
     let v = serde_json::to_value(&req).unwrap();
     assert_eq!(v["model"], "claude-sonnet-4-20250514");
@@ -191,6 +195,8 @@ fn request_multiple_stop_sequences() {
         stop_sequences: Some(vec!["A".into(), "B".into(), "C".into()]),
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let v = serde_json::to_value(&req).unwrap();
     assert_eq!(v["stop_sequences"].as_array().unwrap().len(), 3);
@@ -684,6 +690,8 @@ fn work_order_task_from_last_text_message() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let wo = request_to_work_order(&req);
     assert_eq!(wo.task, "Second question");
@@ -707,6 +715,8 @@ fn work_order_fallback_when_no_text_in_last_message() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let wo = request_to_work_order(&req);
     assert_eq!(wo.task, "Claude shim request");
@@ -763,6 +773,8 @@ fn work_order_from_empty_messages_still_works() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let wo = request_to_work_order(&req);
     assert_eq!(wo.task, "Claude shim request");
@@ -1364,6 +1376,8 @@ async fn create_empty_messages_returns_invalid_request() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let err = client.create(req).await.unwrap_err();
     assert!(matches!(err, ShimError::InvalidRequest(_)));
@@ -1381,6 +1395,8 @@ async fn create_stream_empty_messages_returns_invalid_request() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let err = client.create_stream(req).await.unwrap_err();
     assert!(matches!(err, ShimError::InvalidRequest(_)));
@@ -1758,6 +1774,8 @@ fn multi_turn_request_to_claude() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let claude_req = request_to_claude(&req);
     assert_eq!(claude_req.messages.len(), 3);
@@ -1807,6 +1825,8 @@ fn multi_turn_with_tool_cycle() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let claude_req = request_to_claude(&req);
     assert_eq!(claude_req.messages.len(), 4);
@@ -1866,6 +1886,8 @@ async fn multi_turn_client_roundtrip() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let resp = client.create(req).await.unwrap();
     assert_eq!(resp.role, "assistant");
@@ -2819,6 +2841,8 @@ fn many_messages_conversion() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let claude_req = request_to_claude(&req);
     assert_eq!(claude_req.messages.len(), 50);
@@ -218,6 +218,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         }
     }
 
@@ -306,6 +308,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let err = client.create(empty_req).await.unwrap_err();
         assert!(matches!(err, ShimError::InvalidRequest(_)));
@@ -358,6 +358,8 @@ fn claude_request_to_work_order_preserves_model() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let wo = abp_shim_claude::request_to_work_order(&req);
     assert_eq!(wo.config.model.as_deref(), Some("claude-sonnet-4-20250514"));
@@ -381,6 +383,8 @@ fn claude_request_to_claude_preserves_system() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let claude_req = abp_shim_claude::request_to_claude(&req);
     assert_eq!(claude_req.system.as_deref(), Some("You are a pirate."));
@@ -789,6 +789,9 @@ fn gemini_text_content_roundtrips_through_ir() {
                             },
                         )
                     }
+                    abp_shim_gemini::Part::FunctionCallDelta { .. } => {
+                        unreachable!("the request has no streamed parts")
+                    }
                 })
                 .collect(),
         })
@@ -226,6 +226,8 @@ mod shim_work_order_creation {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let wo = abp_shim_claude::request_to_work_order(&req);
         assert!(wo.task.contains("Hello Claude"));
@@ -793,6 +793,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let resp = client.create(req).await.unwrap();
         assert!(!resp.content.is_empty());
@@ -811,6 +813,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let result = client.create(req).await;
         assert!(result.is_err());
@@ -833,6 +837,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let mut stream = client.create_stream(req).await.unwrap();
         let mut collected = Vec::new();
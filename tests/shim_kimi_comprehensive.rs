@@ -385,7 +385,8 @@ fn request_to_work_order_no_vendor_flags_when_unset() {
         .messages(vec![Message::user("test")])
         .build();
     let wo = request_to_work_order(&req);
-    assert!(wo.config.vendor.is_empty());
+    // Only the canonical `abp` namespace (carrying the conversation) is set.
+    assert!(wo.config.vendor.keys().all(|k| k == "abp"));
 }
 
 #[test]
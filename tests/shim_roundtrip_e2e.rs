@@ -521,6 +521,8 @@ mod claude_roundtrip {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         }
     }
 
@@ -622,6 +624,8 @@ mod claude_roundtrip {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let dialect = request_to_claude(&req);
         assert_eq!(dialect.model, "claude-sonnet-4-20250514");
@@ -699,6 +703,8 @@ mod claude_roundtrip {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let err = client.create(req).await.unwrap_err();
         assert!(matches!(err, ShimError::InvalidRequest(_)));
@@ -1641,6 +1647,8 @@ mod cross_shim {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let wo2 = abp_shim_claude::request_to_work_order(&claude_req);
         assert_eq!(wo2.task, "task");
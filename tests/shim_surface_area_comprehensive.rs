@@ -482,6 +482,8 @@ mod claude {
             stop_sequences: Some(vec!["END".into()]),
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         assert_eq!(req.model, "claude-sonnet-4-20250514");
         assert_eq!(req.max_tokens, 4096);
@@ -540,6 +542,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let claude_req = request_to_claude(&req);
         assert_eq!(claude_req.model, "claude-sonnet-4-20250514");
@@ -631,6 +635,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         // Claude uses a separate system field, not a system role message
         assert_eq!(req.system.as_deref(), Some("You are a cat"));
@@ -697,6 +703,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let wo = request_to_work_order(&req);
         assert!(wo.task.contains("Explain Rust"));
@@ -721,6 +729,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let resp = client.create(req).await.unwrap();
         assert_eq!(resp.response_type, "message");
@@ -739,6 +749,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: None,
+            tools: None,
+            tool_choice: None,
         };
         let result = client.create(req).await;
         assert!(result.is_err());
@@ -759,6 +771,8 @@ mod claude {
             stop_sequences: None,
             thinking: None,
             stream: Some(true),
+            tools: None,
+            tool_choice: None,
         };
         let stream = client.create_stream(req).await.unwrap();
         let events = stream.collect_all().await;
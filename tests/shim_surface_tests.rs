@@ -313,6 +313,8 @@ fn claude_request_construction() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     assert_eq!(req.model, "claude-sonnet-4-20250514");
     assert_eq!(req.max_tokens, 4096);
@@ -533,6 +535,8 @@ fn claude_request_to_work_order_lowering() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
     let wo = abp_shim_claude::request_to_work_order(&req);
     assert_eq!(wo.config.model.as_deref(), Some("claude-sonnet-4-20250514"));
@@ -69,6 +69,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -880,6 +881,7 @@ fn event_ext_field_roundtrips() {
             },
             ext: Some(ext.clone()),
         },
+        seq: None,
     };
 
     let line = JsonlCodec::encode(&event).unwrap();
@@ -1210,6 +1212,7 @@ fn validator_hello_empty_backend_id_fails() {
         },
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = v.validate(&hello);
     assert!(!result.valid, "empty backend.id should fail validation");
@@ -1223,6 +1226,7 @@ fn validator_hello_invalid_version_fails() {
         backend: test_backend(),
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = v.validate(&hello);
     assert!(!result.valid, "unparseable contract_version should fail");
@@ -1240,6 +1244,7 @@ fn validator_event_empty_ref_id_fails() {
             },
             ext: None,
         },
+        seq: None,
     };
     let result = v.validate(&event);
     assert!(!result.valid, "empty ref_id on event should fail");
@@ -77,6 +77,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -232,7 +233,7 @@ fn round_trip_event() {
     );
     let rt = round_trip(&env);
     match rt {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "rt-run");
             assert!(
                 matches!(event.kind, AgentEventKind::AssistantMessage { text } if text == "hello world")
@@ -806,6 +807,7 @@ fn event_with_ext_passthrough() {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     let rt = round_trip(&env);
     match rt {
@@ -78,6 +78,7 @@ fn make_hello_with_version(version: &str) -> Envelope {
         backend: test_backend(),
         capabilities: test_capabilities(),
         mode: ExecutionMode::Mapped,
+        features: None,
     }
 }
 
@@ -96,6 +97,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -218,6 +220,7 @@ fn hello_with_empty_backend_id_is_invalid() {
         },
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&hello);
@@ -231,6 +234,7 @@ fn hello_with_empty_contract_version_is_invalid() {
         backend: test_backend(),
         capabilities: test_capabilities(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&hello);
@@ -1309,6 +1313,7 @@ fn event_with_empty_ref_id_is_invalid() {
             },
             ext: None,
         },
+        seq: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -1379,6 +1384,7 @@ fn ext_field_preserves_passthrough_data() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -70,6 +70,7 @@ fn make_hello_with_version(backend_id: &str, version: &str) -> Envelope {
         backend: make_backend(backend_id),
         capabilities: CapabilityManifest::default(),
         mode: ExecutionMode::default(),
+        features: None,
     }
 }
 
@@ -90,6 +91,7 @@ fn make_event(ref_id: &str, text: &str) -> Envelope {
             },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -206,6 +208,7 @@ mod hello_envelope {
             backend: make_backend_minimal("test"),
             capabilities: CapabilityManifest::default(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let validator = EnvelopeValidator::new();
         let result = validator.validate(&env);
@@ -223,6 +226,7 @@ mod hello_envelope {
             backend: make_backend_minimal("test"),
             capabilities: CapabilityManifest::default(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let validator = EnvelopeValidator::new();
         let result = validator.validate(&env);
@@ -239,6 +243,7 @@ mod hello_envelope {
             backend: make_backend("test"),
             capabilities: CapabilityManifest::default(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let validator = EnvelopeValidator::new();
         let result = validator.validate(&env);
@@ -255,6 +260,7 @@ mod hello_envelope {
             backend: make_backend("test"),
             capabilities: CapabilityManifest::default(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let validator = EnvelopeValidator::new();
         let result = validator.validate(&env);
@@ -278,6 +284,7 @@ mod hello_envelope {
             },
             capabilities: CapabilityManifest::default(),
             mode: ExecutionMode::default(),
+            features: None,
         };
         let validator = EnvelopeValidator::new();
         let result = validator.validate(&env);
@@ -476,7 +483,7 @@ mod event_envelope {
         let encoded = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
         match decoded {
-            Envelope::Event { ref_id, event } => {
+            Envelope::Event { ref_id, event, .. } => {
                 assert_eq!(ref_id, "run-1");
                 match event.kind {
                     AgentEventKind::AssistantMessage { ref text } => {
@@ -500,6 +507,7 @@ mod event_envelope {
                 },
                 ext: None,
             },
+            seq: None,
         };
         let validator = EnvelopeValidator::new();
         let result = validator.validate(&env);
@@ -574,6 +582,7 @@ mod event_envelope {
         let env = Envelope::Event {
             ref_id: "run-1".to_string(),
             event,
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -603,6 +612,7 @@ mod event_envelope {
         let env = Envelope::Event {
             ref_id: "run-1".to_string(),
             event,
+            seq: None,
         };
         let encoded = JsonlCodec::encode(&env).unwrap();
         let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -622,6 +632,7 @@ mod event_envelope {
         let env = Envelope::Event {
             ref_id: "run-1".to_string(),
             event,
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         assert!(json.contains("file_changed"));
@@ -639,6 +650,7 @@ mod event_envelope {
         let env = Envelope::Event {
             ref_id: "run-1".to_string(),
             event,
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         assert!(json.contains("warning"));
@@ -657,6 +669,7 @@ mod event_envelope {
         let env = Envelope::Event {
             ref_id: "run-1".to_string(),
             event,
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         assert!(json.contains(r#""type":"error""#));
@@ -676,6 +689,7 @@ mod event_envelope {
         let env = Envelope::Event {
             ref_id: "run-1".to_string(),
             event,
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         assert!(json.contains("command_executed"));
@@ -695,6 +709,7 @@ mod event_envelope {
         let env = Envelope::Event {
             ref_id: "run-1".to_string(),
             event,
+            seq: None,
         };
         let json = JsonlCodec::encode(&env).unwrap();
         assert!(json.contains("custom_field"));
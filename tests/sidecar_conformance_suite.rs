@@ -86,6 +86,7 @@ fn make_hello_with_version(version: &str) -> Envelope {
         backend: test_backend(),
         capabilities: test_capabilities(),
         mode: ExecutionMode::default(),
+        features: None,
     }
 }
 
@@ -104,6 +105,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -287,6 +289,7 @@ fn hello_empty_backend_id_fails_validation() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validator().validate(&env);
     assert!(!result.valid);
@@ -305,6 +308,7 @@ fn hello_empty_contract_version_fails_validation() {
         backend: test_backend(),
         capabilities: test_capabilities(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validator().validate(&env);
     assert!(!result.valid);
@@ -551,6 +555,7 @@ fn event_empty_ref_id_fails_validation() {
             },
             ext: None,
         },
+        seq: None,
     };
     let result = validator().validate(&env);
     assert!(!result.valid);
@@ -939,6 +944,7 @@ fn event_empty_ref_id_sequence_error() {
             },
             ext: None,
         },
+        seq: None,
     };
     let result = validator().validate(&env);
     assert!(!result.valid);
@@ -1530,6 +1536,7 @@ fn event_with_ext_field_roundtrip() {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     let line = encode(&env);
     let decoded = decode(&line).unwrap();
@@ -132,11 +132,12 @@ fn codec_event_roundtrip() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event,
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             assert!(matches!(event.kind, AgentEventKind::AssistantDelta { .. }));
         }
@@ -225,6 +226,7 @@ fn handshake_event_has_t_tag() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     assert!(encoded.contains("\"t\":\"event\""));
@@ -278,6 +280,7 @@ fn handshake_event_ref_id_matches_run_id() {
     let env = Envelope::Event {
         ref_id: run_id.into(),
         event,
+        seq: None,
     };
     match JsonlCodec::decode(JsonlCodec::encode(&env).unwrap().trim_end()).unwrap() {
         Envelope::Event { ref_id, .. } => assert_eq!(ref_id, run_id),
@@ -384,6 +387,7 @@ fn error_fatal_envelope_terminates_protocol() {
             event: make_event(AgentEventKind::RunStarted {
                 message: "go".into(),
             }),
+            seq: None,
         },
         Envelope::Fatal {
             ref_id: Some(run_id.into()),
@@ -442,24 +446,28 @@ fn full_protocol_flow_simulation() {
             event: make_event(AgentEventKind::RunStarted {
                 message: "start".into(),
             }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
             event: make_event(AgentEventKind::AssistantDelta {
                 text: "chunk 1".into(),
             }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
             event: make_event(AgentEventKind::AssistantDelta {
                 text: "chunk 2".into(),
             }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
             event: make_event(AgentEventKind::RunCompleted {
                 message: "done".into(),
             }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: run_id.into(),
@@ -497,6 +505,7 @@ fn full_protocol_with_fatal_instead_of_final() {
             event: make_event(AgentEventKind::RunStarted {
                 message: "start".into(),
             }),
+            seq: None,
         },
         Envelope::Fatal {
             ref_id: Some(run_id.into()),
@@ -531,6 +540,7 @@ fn event_stream_multiple_text_deltas() {
             event: make_event(AgentEventKind::AssistantDelta {
                 text: format!("delta-{i}"),
             }),
+            seq: None,
         })
         .collect();
 
@@ -543,7 +553,7 @@ fn event_stream_multiple_text_deltas() {
     assert_eq!(decoded.len(), 10);
     for (i, env) in decoded.iter().enumerate() {
         match env {
-            Envelope::Event { ref_id, event } => {
+            Envelope::Event { ref_id, event, .. } => {
                 assert_eq!(ref_id, run_id);
                 match &event.kind {
                     AgentEventKind::AssistantDelta { text } => {
@@ -577,10 +587,12 @@ fn event_stream_tool_use_events() {
         Envelope::Event {
             ref_id: run_id.into(),
             event: tool_call,
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.into(),
             event: tool_result,
+            seq: None,
         },
     ];
 
@@ -656,6 +668,7 @@ fn event_stream_mixed_event_types() {
         .map(|event| Envelope::Event {
             ref_id: run_id.into(),
             event,
+            seq: None,
         })
         .collect();
 
@@ -712,6 +725,7 @@ fn event_stream_ordering_preserved_large_batch() {
             event: make_event(AgentEventKind::AssistantDelta {
                 text: format!("msg-{i:03}"),
             }),
+            seq: None,
         })
         .collect();
 
@@ -740,6 +754,7 @@ fn event_stream_error_event_type() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -1051,6 +1066,7 @@ fn encode_decode_roundtrip_via_writer() {
             event: make_event(AgentEventKind::RunStarted {
                 message: "start".into(),
             }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: "r1".into(),
@@ -242,7 +242,7 @@ fn parse_event_envelope_from_json_string() {
     let encoded = JsonlCodec::encode(&event_env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-42");
             match event.kind {
                 AgentEventKind::AssistantMessage { ref text } => {
@@ -721,6 +721,7 @@ fn validate_hello_rejects_incompatible_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validate_hello(&hello);
     assert!(result.is_err());
@@ -134,6 +134,7 @@ fn make_event(ref_id: &str) -> Envelope {
     Envelope::Event {
         ref_id: ref_id.into(),
         event: test_event(),
+        seq: None,
     }
 }
 
@@ -375,6 +376,7 @@ fn hello_empty_backend_id_is_validation_error() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&hello);
@@ -478,6 +480,7 @@ fn hello_with_invalid_version_fails_validation() {
         backend: test_identity(),
         capabilities: test_capabilities(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&hello);
@@ -497,6 +500,7 @@ fn hello_with_empty_version_fails_validation() {
         backend: test_identity(),
         capabilities: test_capabilities(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&hello);
@@ -530,6 +534,7 @@ fn event_with_assistant_delta() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     assert!(json.contains("assistant_delta"));
@@ -550,6 +555,7 @@ fn event_with_tool_call() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(encoded.trim()).unwrap();
@@ -579,6 +585,7 @@ fn event_with_tool_result() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     assert!(json.contains("tool_result"));
@@ -658,6 +665,7 @@ fn event_empty_ref_id_fails_validation() {
     let event = Envelope::Event {
         ref_id: "".into(),
         event: test_event(),
+        seq: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&event);
@@ -1784,6 +1792,7 @@ fn hello_missing_backend_version_warns() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&hello);
@@ -1935,6 +1944,9 @@ fn run_with_complex_work_order() {
             env: BTreeMap::new(),
             max_budget_usd: Some(1.0),
             max_turns: Some(10),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
 
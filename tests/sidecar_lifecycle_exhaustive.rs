@@ -134,6 +134,7 @@ fn make_event(ref_id: &str) -> Envelope {
     Envelope::Event {
         ref_id: ref_id.into(),
         event: test_event(),
+        seq: None,
     }
 }
 
@@ -141,6 +142,7 @@ fn make_event_with_kind(ref_id: &str, kind: AgentEventKind) -> Envelope {
     Envelope::Event {
         ref_id: ref_id.into(),
         event: test_event_kind(kind),
+        seq: None,
     }
 }
 
@@ -472,6 +474,7 @@ fn validate_hello_with_empty_backend_id() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validator().validate(&hello);
     assert!(!result.valid);
@@ -488,6 +491,7 @@ fn validate_hello_with_empty_contract_version() {
         backend: test_identity(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validator().validate(&hello);
     assert!(!result.valid);
@@ -503,6 +507,7 @@ fn validate_hello_with_invalid_version_format() {
         backend: test_identity(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = validator().validate(&hello);
     assert!(!result.valid);
@@ -545,6 +550,7 @@ fn validate_event_with_empty_ref_id() {
     let event = Envelope::Event {
         ref_id: "".into(),
         event: test_event(),
+        seq: None,
     };
     let result = validator().validate(&event);
     assert!(!result.valid);
@@ -1896,6 +1902,7 @@ fn event_with_ext_field_roundtrips() {
     let envelope = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let rt = round_trip(&envelope);
     match rt {
@@ -1915,6 +1922,7 @@ fn event_without_ext_field_is_none() {
     let envelope = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let rt = round_trip(&envelope);
     match rt {
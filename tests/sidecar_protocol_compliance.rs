@@ -94,6 +94,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -383,7 +384,7 @@ fn event_run_started() {
     let json = JsonlCodec::encode(&env).unwrap();
     assert!(json.contains("\"t\":\"event\""));
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
-    if let Envelope::Event { ref_id, event } = decoded {
+    if let Envelope::Event { ref_id, event, .. } = decoded {
         assert_eq!(ref_id, "run-1");
         assert!(matches!(event.kind, AgentEventKind::RunStarted { .. }));
     } else {
@@ -725,6 +726,7 @@ fn event_with_extension_data() {
             },
             ext: Some(ext),
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -1142,6 +1144,7 @@ fn state_validator_hello_empty_backend_id() {
         },
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&hello);
@@ -1159,6 +1162,7 @@ fn state_validator_hello_invalid_version() {
         backend: test_backend(),
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&hello);
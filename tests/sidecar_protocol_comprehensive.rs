@@ -117,6 +117,7 @@ fn make_event(ref_id: &str) -> Envelope {
     Envelope::Event {
         ref_id: ref_id.into(),
         event: test_event(),
+        seq: None,
     }
 }
 
@@ -306,7 +307,7 @@ fn decode_event_roundtrip() {
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "r1");
             assert!(matches!(event.kind, AgentEventKind::RunStarted { .. }));
         }
@@ -380,6 +381,7 @@ fn roundtrip_event_kind(kind: AgentEventKind) {
     let env = Envelope::Event {
         ref_id: "rt".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -627,6 +629,7 @@ fn validate_hello_empty_backend_id() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -643,6 +646,7 @@ fn validate_hello_empty_contract_version() {
         backend: test_identity(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -659,6 +663,7 @@ fn validate_hello_invalid_contract_version() {
         backend: test_identity(),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -679,6 +684,7 @@ fn validate_hello_warns_missing_backend_version() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -744,6 +750,7 @@ fn validate_event_empty_ref_id() {
     let env = Envelope::Event {
         ref_id: "".into(),
         event: test_event(),
+        seq: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -993,6 +1000,7 @@ fn builder_hello_all_fields() {
             mode,
             capabilities,
             contract_version,
+            ..
         } => {
             assert_eq!(backend.id, "sc");
             assert_eq!(backend.backend_version.as_deref(), Some("2.0"));
@@ -1430,6 +1438,7 @@ fn event_with_ext_roundtrips() {
     let env = Envelope::Event {
         ref_id: "r1".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     let decoded = JsonlCodec::decode(line.trim()).unwrap();
@@ -117,6 +117,7 @@ fn event_env(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -1459,6 +1460,7 @@ fn validator_rejects_invalid_contract_version() {
         backend: backend(),
         capabilities: caps(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = EnvelopeValidator::new().validate(&env);
     assert!(!result.valid);
@@ -1613,6 +1615,7 @@ fn ext_field_roundtrips_on_event() {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     if let Envelope::Event { event, .. } = roundtrip(&env) {
         assert_eq!(event.ext.as_ref().unwrap()["vendor_trace_id"], "abc-123");
@@ -1647,6 +1650,7 @@ fn ext_with_nested_object() {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     if let Envelope::Event { event, .. } = roundtrip(&env) {
         assert_eq!(
@@ -1669,6 +1673,7 @@ fn ext_empty_map_does_not_fail() {
             },
             ext: Some(BTreeMap::new()),
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     assert!(matches!(decoded, Envelope::Event { .. }));
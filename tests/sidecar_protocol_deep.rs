@@ -90,6 +90,7 @@ fn make_event(ref_id: &str, msg: &str) -> Envelope {
             kind: AgentEventKind::AssistantMessage { text: msg.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -101,6 +102,7 @@ fn make_delta_event(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantDelta { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -177,7 +179,7 @@ fn event_roundtrip() {
     let env = make_event("run-1", "hello world");
     let decoded = roundtrip(&env);
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             match event.kind {
                 AgentEventKind::AssistantMessage { text } => assert_eq!(text, "hello world"),
@@ -492,6 +494,7 @@ fn empty_contract_version_is_error() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -512,6 +515,7 @@ fn invalid_contract_version_format_is_error() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -820,6 +824,7 @@ fn empty_backend_id_in_hello_is_error() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let validator = EnvelopeValidator::new();
     let result = validator.validate(&env);
@@ -863,6 +868,7 @@ fn event_with_tool_call_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -895,6 +901,7 @@ fn event_with_tool_result_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -918,6 +925,7 @@ fn event_with_file_changed_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -942,6 +950,7 @@ fn event_with_command_executed_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -972,6 +981,7 @@ fn event_with_ext_field_roundtrips() {
             },
             ext: Some(ext.clone()),
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -1338,6 +1348,7 @@ fn warning_event_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -1363,6 +1374,7 @@ fn error_event_roundtrips() {
             },
             ext: None,
         },
+        seq: None,
     };
     let decoded = roundtrip(&env);
     match decoded {
@@ -1387,6 +1399,7 @@ fn run_started_and_completed_events_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let end = Envelope::Event {
         ref_id: "r".into(),
@@ -1397,6 +1410,7 @@ fn run_started_and_completed_events_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     let s = roundtrip(&start);
     let e = roundtrip(&end);
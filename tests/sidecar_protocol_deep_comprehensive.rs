@@ -103,6 +103,7 @@ fn event_env(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -221,7 +222,7 @@ fn roundtrip_run_preserves_id() {
 fn roundtrip_event_assistant_message() {
     let env = msg_event_env("run-1", "hello world");
     let rt = roundtrip(&env);
-    if let Envelope::Event { ref_id, event } = rt {
+    if let Envelope::Event { ref_id, event, .. } = rt {
         assert_eq!(ref_id, "run-1");
         assert!(matches!(
             event.kind,
@@ -451,6 +452,7 @@ fn hello_custom_version_roundtrip() {
         backend: backend("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let rt = roundtrip(&env);
     if let Envelope::Hello {
@@ -629,6 +631,9 @@ fn run_work_order_with_vendor_config() {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .build();
     let id = wo.id.to_string();
@@ -657,6 +662,9 @@ fn run_work_order_with_env_vars() {
             env: env_vars,
             max_budget_usd: None,
             max_turns: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .build();
     let id = wo.id.to_string();
@@ -1050,6 +1058,7 @@ fn event_with_extension_data() {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     let rt = roundtrip(&env);
     if let Envelope::Event { event, .. } = rt {
@@ -1210,6 +1219,7 @@ fn final_receipt_with_verification() {
             git_diff: Some("diff --git a/f.rs b/f.rs\n+fn new()".into()),
             git_status: Some("M src/f.rs\n".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .build();
 
@@ -2065,6 +2075,7 @@ fn minimal_hello() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let rt = roundtrip(&env);
     assert!(matches!(rt, Envelope::Hello { .. }));
@@ -2286,6 +2297,9 @@ fn vendor_config_btreemap_sorted() {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .build();
     let id = wo.id.to_string();
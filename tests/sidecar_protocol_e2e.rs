@@ -85,6 +85,7 @@ fn event_msg(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantMessage { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -96,6 +97,7 @@ fn event_delta(ref_id: &str, text: &str) -> Envelope {
             kind: AgentEventKind::AssistantDelta { text: text.into() },
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -178,7 +180,7 @@ fn e2e_event_serde_roundtrip() {
     let env = event_msg("run-42", "hello e2e");
     let decoded = roundtrip(&env);
     match decoded {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-42");
             match event.kind {
                 AgentEventKind::AssistantMessage { text } => assert_eq!(text, "hello e2e"),
@@ -467,6 +469,7 @@ fn e2e_event_run_started() {
             },
             ext: None,
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -487,6 +490,7 @@ fn e2e_event_run_completed() {
             },
             ext: None,
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -534,6 +538,7 @@ fn e2e_event_tool_call() {
             },
             ext: None,
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -567,6 +572,7 @@ fn e2e_event_tool_result() {
             },
             ext: None,
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -598,6 +604,7 @@ fn e2e_event_tool_result_error() {
             },
             ext: None,
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -620,6 +627,7 @@ fn e2e_event_file_changed() {
             },
             ext: None,
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -646,6 +654,7 @@ fn e2e_event_command_executed() {
             },
             ext: None,
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -672,6 +681,7 @@ fn e2e_event_warning() {
             },
             ext: None,
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -693,6 +703,7 @@ fn e2e_event_error() {
             },
             ext: None,
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -720,6 +731,7 @@ fn e2e_event_with_ext_passthrough() {
             kind: AgentEventKind::AssistantMessage { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => {
@@ -1005,6 +1017,7 @@ fn e2e_empty_contract_version_validation() {
         backend: backend("t"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = EnvelopeValidator::new().validate(&env);
     assert!(!result.valid);
@@ -1020,6 +1033,7 @@ fn e2e_invalid_contract_version_format() {
         backend: backend("t"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = EnvelopeValidator::new().validate(&env);
     assert!(!result.valid);
@@ -1042,6 +1056,7 @@ fn e2e_empty_backend_id_validation() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = EnvelopeValidator::new().validate(&env);
     assert!(!result.valid);
@@ -1107,6 +1122,7 @@ fn e2e_missing_backend_version_is_warning() {
         },
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let result = EnvelopeValidator::new().validate(&env);
     assert!(result.valid);
@@ -1447,6 +1463,7 @@ fn e2e_large_tool_input_roundtrip() {
             },
             ext: None,
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
@@ -1776,6 +1793,7 @@ fn e2e_full_protocol_flow_happy_path() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.clone(),
@@ -1789,6 +1807,7 @@ fn e2e_full_protocol_flow_happy_path() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id.clone(),
@@ -1800,6 +1819,7 @@ fn e2e_full_protocol_flow_happy_path() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         event_msg(&run_id, "Done!"),
         final_env(&run_id),
@@ -1832,6 +1852,7 @@ fn e2e_full_protocol_flow_fatal_path() {
                 },
                 ext: None,
             },
+            seq: None,
         },
         fatal_env(Some(&run_id), "Rate limited"),
     ];
@@ -1987,6 +2008,7 @@ fn e2e_nested_tool_call_parent_id() {
             },
             ext: None,
         },
+        seq: None,
     };
     match roundtrip(&env) {
         Envelope::Event { event, .. } => match event.kind {
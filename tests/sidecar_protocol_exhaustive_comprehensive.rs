@@ -62,6 +62,7 @@ fn make_event(ref_id: &str, kind: AgentEventKind) -> Envelope {
             kind,
             ext: None,
         },
+        seq: None,
     }
 }
 
@@ -770,6 +771,7 @@ fn event_with_ext_data() {
             kind: AgentEventKind::AssistantDelta { text: "hi".into() },
             ext: Some(ext),
         },
+        seq: None,
     };
     let json = JsonlCodec::encode(&env).unwrap();
     assert!(json.contains("raw_message"));
@@ -1441,6 +1443,7 @@ fn validate_hello_empty_contract_version() {
         backend: make_backend("x"),
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -1457,6 +1460,7 @@ fn validate_hello_invalid_version() {
         backend: make_backend("x"),
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -1480,6 +1484,7 @@ fn validate_hello_empty_backend_id() {
         },
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(!result.valid);
@@ -1503,6 +1508,7 @@ fn validate_hello_warns_missing_optional_versions() {
         },
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let result = validator.validate(&env);
     assert!(result.valid);
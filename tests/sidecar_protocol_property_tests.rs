@@ -357,11 +357,15 @@ fn arb_envelope() -> impl Strategy<Value = Envelope> {
                 backend,
                 capabilities,
                 mode,
+                features: None,
             }),
         (arb_nonempty_string(), arb_work_order())
             .prop_map(|(id, work_order)| Envelope::Run { id, work_order }),
-        (arb_nonempty_string(), arb_agent_event())
-            .prop_map(|(ref_id, event)| Envelope::Event { ref_id, event }),
+        (arb_nonempty_string(), arb_agent_event()).prop_map(|(ref_id, event)| Envelope::Event {
+            ref_id,
+            event,
+            seq: None,
+        }),
         (arb_nonempty_string(), arb_receipt())
             .prop_map(|(ref_id, receipt)| Envelope::Final { ref_id, receipt }),
         (prop::option::of(arb_nonempty_string()), arb_string()).prop_map(|(ref_id, error)| {
@@ -422,7 +426,7 @@ proptest! {
     /// 1.3 ref_id is preserved across serde for Event envelopes.
     #[test]
     fn envelope_event_ref_id_preserved(ref_id in arb_nonempty_string(), event in arb_agent_event()) {
-        let env = Envelope::Event { ref_id: ref_id.clone(), event };
+        let env = Envelope::Event { ref_id: ref_id.clone(), event, seq: None, };
         let json = serde_json::to_string(&env).unwrap();
         let decoded: Envelope = serde_json::from_str(&json).unwrap();
         if let Envelope::Event { ref_id: got, .. } = decoded {
@@ -469,7 +473,7 @@ proptest! {
             contract_version: CONTRACT_VERSION.to_string(),
             backend,
             capabilities: caps,
-            mode,
+            mode, features: None,
         };
         let json = serde_json::to_string(&env).unwrap();
         let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -494,7 +498,7 @@ proptest! {
     /// 1.8 Event variant roundtrips correctly.
     #[test]
     fn envelope_event_roundtrip(ref_id in arb_nonempty_string(), event in arb_agent_event()) {
-        let env = Envelope::Event { ref_id, event };
+        let env = Envelope::Event { ref_id, event, seq: None, };
         let json = serde_json::to_string(&env).unwrap();
         let decoded: Envelope = serde_json::from_str(&json).unwrap();
         prop_assert_eq!(
@@ -561,10 +565,10 @@ proptest! {
                 contract_version: CONTRACT_VERSION.to_string(),
                 backend,
                 capabilities: caps,
-                mode,
+                mode, features: None,
             },
             Envelope::Run { id: "r1".into(), work_order: wo },
-            Envelope::Event { ref_id: "r1".into(), event },
+            Envelope::Event { ref_id: "r1".into(), event, seq: None, },
             Envelope::Final { ref_id: "r1".into(), receipt },
             Envelope::Fatal { ref_id: Some("r1".into()), error: "boom".into(), error_code: None },
         ];
@@ -600,7 +604,7 @@ proptest! {
             contract_version: CONTRACT_VERSION.to_string(),
             backend,
             capabilities: caps,
-            mode: ExecutionMode::default(),
+            mode: ExecutionMode::default(), features: None,
         };
         let json = serde_json::to_string(&env).unwrap();
         let decoded: Envelope = serde_json::from_str(&json).unwrap();
@@ -1011,9 +1015,9 @@ proptest! {
                 contract_version: CONTRACT_VERSION.to_string(),
                 backend,
                 capabilities: caps,
-                mode: ExecutionMode::default(),
+                mode: ExecutionMode::default(), features: None,
             },
-            Envelope::Event { ref_id: "r1".into(), event },
+            Envelope::Event { ref_id: "r1".into(), event, seq: None, },
             Envelope::Final { ref_id: "r1".into(), receipt },
         ];
         let validator = EnvelopeValidator::new();
@@ -1034,7 +1038,7 @@ proptest! {
                 contract_version: CONTRACT_VERSION.to_string(),
                 backend,
                 capabilities: caps,
-                mode: ExecutionMode::default(),
+                mode: ExecutionMode::default(), features: None,
             },
             Envelope::Run { id: "r1".into(), work_order: wo },
             Envelope::Final { ref_id: "r1".into(), receipt },
@@ -1059,7 +1063,7 @@ proptest! {
                 contract_version: CONTRACT_VERSION.to_string(),
                 backend,
                 capabilities: caps,
-                mode: ExecutionMode::default(),
+                mode: ExecutionMode::default(), features: None,
             },
             Envelope::Run { id: "r1".into(), work_order: wo },
             Envelope::Fatal { ref_id: Some("r1".into()), error: "boom".into(), error_code: None },
@@ -1082,7 +1086,7 @@ proptest! {
                 contract_version: CONTRACT_VERSION.to_string(),
                 backend,
                 capabilities: caps,
-                mode: ExecutionMode::default(),
+                mode: ExecutionMode::default(), features: None,
             },
             Envelope::Run { id: "r1".into(), work_order: wo },
             Envelope::Final { ref_id: "r1".into(), receipt },
@@ -1107,10 +1111,10 @@ proptest! {
                 contract_version: CONTRACT_VERSION.to_string(),
                 backend,
                 capabilities: caps,
-                mode: ExecutionMode::default(),
+                mode: ExecutionMode::default(), features: None,
             },
             Envelope::Run { id: "run-abc".into(), work_order: wo },
-            Envelope::Event { ref_id: "wrong-id".into(), event },
+            Envelope::Event { ref_id: "wrong-id".into(), event, seq: None, },
             Envelope::Final { ref_id: "run-abc".into(), receipt },
         ];
         let validator = EnvelopeValidator::new();
@@ -1133,12 +1137,12 @@ proptest! {
                 contract_version: CONTRACT_VERSION.to_string(),
                 backend,
                 capabilities: caps,
-                mode: ExecutionMode::default(),
+                mode: ExecutionMode::default(), features: None,
             },
             Envelope::Run { id: "r1".into(), work_order: wo },
         ];
         for event in events {
-            seq.push(Envelope::Event { ref_id: "r1".into(), event });
+            seq.push(Envelope::Event { ref_id: "r1".into(), event, seq: None, });
         }
         seq.push(Envelope::Final { ref_id: "r1".into(), receipt });
         let validator = EnvelopeValidator::new();
@@ -1163,7 +1167,7 @@ proptest! {
                 contract_version: CONTRACT_VERSION.to_string(),
                 backend,
                 capabilities: caps,
-                mode: ExecutionMode::default(),
+                mode: ExecutionMode::default(), features: None,
             },
             Envelope::Final { ref_id: "r1".into(), receipt },
         ];
@@ -1194,7 +1198,7 @@ proptest! {
                 contract_version: CONTRACT_VERSION.to_string(),
                 backend,
                 capabilities: caps,
-                mode: ExecutionMode::default(),
+                mode: ExecutionMode::default(), features: None,
             },
             Envelope::Run { id: "r1".into(), work_order: wo },
         ];
@@ -866,6 +866,7 @@ fn hello_envelope_construction() {
             backend,
             capabilities,
             mode,
+            ..
         } => {
             assert_eq!(contract_version, CONTRACT_VERSION);
             assert_eq!(backend.id, "test-sidecar");
@@ -1064,6 +1065,7 @@ fn event_envelope_assistant_delta() {
     let env = Envelope::Event {
         ref_id: "run-001".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains("assistant_delta"));
@@ -1084,6 +1086,7 @@ fn event_envelope_tool_call() {
     let env = Envelope::Event {
         ref_id: "run-001".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains("tool_call"));
@@ -1105,6 +1108,7 @@ fn event_envelope_tool_result() {
     let env = Envelope::Event {
         ref_id: "run-001".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains("tool_result"));
@@ -1123,6 +1127,7 @@ fn event_envelope_file_changed() {
     let env = Envelope::Event {
         ref_id: "run-001".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains("file_changed"));
@@ -1142,6 +1147,7 @@ fn event_envelope_command_executed() {
     let env = Envelope::Event {
         ref_id: "run-001".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains("command_executed"));
@@ -1160,6 +1166,7 @@ fn event_envelope_warning() {
     let env = Envelope::Event {
         ref_id: "run-001".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains("warning"));
@@ -1178,6 +1185,7 @@ fn event_envelope_error() {
     let env = Envelope::Event {
         ref_id: "run-001".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains("\"type\":\"error\""));
@@ -1195,6 +1203,7 @@ fn event_envelope_run_started() {
     let env = Envelope::Event {
         ref_id: "run-001".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains("run_started"));
@@ -1212,6 +1221,7 @@ fn event_envelope_run_completed() {
     let env = Envelope::Event {
         ref_id: "run-001".into(),
         event,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.contains("run_completed"));
@@ -1270,6 +1280,7 @@ fn multiple_events_encode_as_jsonl_stream() {
                 },
                 ext: None,
             },
+            seq: None,
         })
         .collect();
 
@@ -1904,6 +1915,7 @@ fn full_protocol_flow_hello_run_events_final() {
             },
             ext: None,
         },
+        seq: None,
     };
     let final_env = Envelope::Final {
         ref_id: "run-001".into(),
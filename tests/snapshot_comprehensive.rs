@@ -162,6 +162,7 @@ fn snap_receipt_with_artifacts_and_verification() {
         git_diff: Some("diff --git a/main.rs b/main.rs\n+// fixed".into()),
         git_status: Some("M main.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     assert_json_snapshot!(receipt_to_value(&r));
 }
@@ -218,6 +219,9 @@ fn snap_work_order_with_model_and_budget() {
             env: BTreeMap::new(),
             max_budget_usd: Some(5.0),
             max_turns: Some(20),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     assert_json_snapshot!(wo);
@@ -317,6 +321,9 @@ fn snap_work_order_with_vendor_config() {
             env,
             max_budget_usd: None,
             max_turns: Some(5),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     assert_json_snapshot!(wo);
@@ -545,6 +552,7 @@ fn snap_envelope_event() {
     let env = Envelope::Event {
         ref_id: "run-001".into(),
         event,
+        seq: None,
     };
     assert_json_snapshot!(envelope_to_value(&env));
 }
@@ -693,7 +701,9 @@ fn snap_config_with_sidecar_backend() {
         bind_address: Some("127.0.0.1".into()),
         port: Some(8080),
         policy_profiles: vec!["policies/default.toml".into()],
+        policy_presets: Vec::new(),
         backends,
+        rbac: None,
     };
     assert_json_snapshot!(cfg);
 }
@@ -146,6 +146,8 @@ fn shim_claude_request_to_ir_roundtrip() {
         stop_sequences: None,
         thinking: None,
         stream: None,
+        tools: None,
+        tool_choice: None,
     };
 
     let wo = abp_shim_claude::request_to_work_order(&req);
@@ -527,6 +529,7 @@ fn large_receipt_many_trace_events() {
             git_diff: Some("+added line\n-removed line".into()),
             git_status: Some("M src/main.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
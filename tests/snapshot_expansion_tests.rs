@@ -175,6 +175,7 @@ fn exp_receipt_canonical_full() {
             git_diff: Some("+new line\n-old line".into()),
             git_status: Some("M src/lib.rs\nA src/new.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -332,6 +333,9 @@ fn exp_work_order_all_fields() {
             env: BTreeMap::from([("RUST_LOG".into(), "debug".into())]),
             max_budget_usd: Some(10.0),
             max_turns: Some(50),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     assert_json_snapshot!(wo);
@@ -510,6 +514,7 @@ fn exp_envelope_hello() {
         },
         capabilities: caps,
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     // Envelope with CapabilityManifest has non-string map keys.
     let pretty = serde_json::to_string_pretty(&env).unwrap();
@@ -546,6 +551,7 @@ fn exp_envelope_event() {
         event: make_event(AgentEventKind::AssistantDelta {
             text: "Hello".into(),
         }),
+        seq: None,
     };
     assert_json_snapshot!(env);
 }
@@ -1512,6 +1518,7 @@ fn exp_jsonl_codec_encode_hello() {
         },
         capabilities: caps,
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let line = serde_json::to_string(&env).unwrap();
     assert_snapshot!(line);
@@ -1606,6 +1613,7 @@ fn exp_verification_report_full() {
         git_diff: Some("+new\n-old".into()),
         git_status: Some("M file.rs\nA new.rs\nD old.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     assert_json_snapshot!(v);
 }
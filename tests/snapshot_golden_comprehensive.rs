@@ -222,6 +222,9 @@ fn wo_runtime_config_full() {
         },
         max_budget_usd: Some(1.5),
         max_turns: Some(25),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     assert_eq!(
         serde_json::to_value(rc).unwrap(),
@@ -368,6 +371,7 @@ fn receipt_verification_report_full() {
         git_diff: Some("diff --git a/f.rs b/f.rs".into()),
         git_status: Some("M f.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     assert_eq!(
         serde_json::to_value(vr).unwrap(),
@@ -825,6 +829,7 @@ fn envelope_event_structure() {
             },
             ext: None,
         },
+        seq: None,
     };
     let v = serde_json::to_value(env).unwrap();
     assert_eq!(v["t"], "event");
@@ -218,6 +218,9 @@ fn gm_work_order_full() {
             },
             max_budget_usd: Some(5.0),
             max_turns: Some(50),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     insta::assert_json_snapshot!("gm_work_order_full", wo);
@@ -282,6 +285,9 @@ fn gm_work_order_empty_context() {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     insta::assert_json_snapshot!("gm_work_order_empty_context", wo);
@@ -429,6 +435,7 @@ fn gm_receipt_with_verification() {
         git_diff: Some("diff --git a/src/auth.rs b/src/auth.rs\n+pub fn login()".into()),
         git_status: Some("M src/auth.rs\nA src/jwt.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     insta::assert_snapshot!("gm_receipt_with_verification", snap_json(&r));
 }
@@ -727,6 +734,7 @@ fn gm_envelope_event() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!("gm_envelope_event", env);
 }
@@ -969,7 +977,9 @@ fn gm_config_full() {
         bind_address: Some("127.0.0.1".into()),
         port: Some(8080),
         policy_profiles: vec!["policies/default.toml".into()],
+        policy_presets: Vec::new(),
         backends,
+        rbac: None,
     };
     insta::assert_json_snapshot!("gm_config_full", cfg);
 }
@@ -1347,6 +1357,9 @@ fn gm_cross_format_runtime_config_json() {
         env: BTreeMap::from([("LANG".into(), "en_US".into())]),
         max_budget_usd: Some(10.0),
         max_turns: Some(100),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     insta::assert_snapshot!("gm_cross_format_runtime_config_json", snap_json(&cfg));
 }
@@ -1359,6 +1372,9 @@ fn gm_cross_format_runtime_config_toml() {
         env: BTreeMap::from([("LANG".into(), "en_US".into())]),
         max_budget_usd: Some(10.0),
         max_turns: Some(100),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let toml_str = toml::to_string_pretty(&cfg).unwrap();
     insta::assert_snapshot!("gm_cross_format_runtime_config_toml", toml_str);
@@ -91,6 +91,7 @@ fn sample_receipt() -> Receipt {
             git_diff: Some("diff --git a/file.txt".into()),
             git_status: Some("M file.txt".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -1030,6 +1031,7 @@ mod receipt_canonical {
                 git_diff: None,
                 git_status: None,
                 harness_ok: false,
+                input_digest: None,
             },
             outcome: Outcome::Failed,
             receipt_sha256: None,
@@ -1146,6 +1148,7 @@ mod protocol_envelopes {
                 },
                 ext: None,
             },
+            seq: None,
         };
         insta::assert_json_snapshot!("envelope_event", &env);
     }
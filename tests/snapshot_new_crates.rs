@@ -116,6 +116,7 @@ fn sample_receipt() -> Receipt {
             git_diff: Some("diff --git a/file.txt".into()),
             git_status: Some("M file.txt".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -109,6 +109,7 @@ fn sample_receipt() -> Receipt {
             git_diff: Some("diff --git a/file.txt".into()),
             git_status: Some("M file.txt".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -220,6 +221,7 @@ fn envelope_event_text_delta() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -238,6 +240,7 @@ fn envelope_event_tool_call() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -256,6 +259,7 @@ fn envelope_event_tool_result() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -272,6 +276,7 @@ fn envelope_event_error() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -287,6 +292,7 @@ fn envelope_event_run_started() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -302,6 +308,7 @@ fn envelope_event_run_completed() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -317,6 +324,7 @@ fn envelope_event_assistant_message() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -332,6 +340,7 @@ fn envelope_event_warning() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -348,6 +357,7 @@ fn envelope_event_file_changed() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
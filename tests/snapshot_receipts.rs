@@ -192,7 +192,7 @@ fn full_receipt() -> Receipt {
         verification: VerificationReport {
             git_diff: Some("diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1,2 @@\n fn main() {}\n+fn helper() {}".into()),
             git_status: Some("M src/lib.rs".into()),
-            harness_ok: true,
+            harness_ok: true, input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -441,6 +441,7 @@ fn receipt_builder_stage_with_trace_and_artifacts() {
             git_diff: Some("diff --git a/output.txt".into()),
             git_status: Some("A output.txt".into()),
             harness_ok: true,
+            input_digest: None,
         })
         .build();
     let json_str = serde_json::to_string_pretty(&r).unwrap();
@@ -652,6 +653,7 @@ fn receipt_mapped_mode_snapshot() {
             git_diff: None,
             git_status: None,
             harness_ok: false,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -109,6 +109,9 @@ fn make_work_order() -> WorkOrder {
             env: BTreeMap::from([("RUST_LOG".into(), "debug".into())]),
             max_budget_usd: Some(5.0),
             max_turns: Some(20),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -167,6 +170,7 @@ fn make_receipt() -> Receipt {
             git_diff: Some("+added line".into()),
             git_status: Some("M src/lib.rs".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -414,6 +418,7 @@ fn regr_envelope_event() {
         event: make_event(AgentEventKind::AssistantDelta {
             text: "streaming...".into(),
         }),
+        seq: None,
     };
     assert_json_snapshot!(env);
 }
@@ -133,6 +133,7 @@ fn sample_receipt() -> Receipt {
             git_diff: Some("diff --git a/file.txt".into()),
             git_status: Some("M file.txt".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -352,6 +353,8 @@ fn claude_request_with_thinking() {
             content: "Explain ownership in Rust.".into(),
         }],
         thinking: Some(ThinkingConfig::new(10000)),
+        tools: vec![],
+        tool_choice: None,
     };
     insta::assert_json_snapshot!("claude_request_with_thinking", req);
 }
@@ -634,6 +637,7 @@ fn envelope_hello() {
         },
         capabilities: sample_capabilities(),
         mode: ExecutionMode::Mapped,
+        features: None,
     };
     let json = serde_json::to_string_pretty(&env).unwrap();
     insta::assert_snapshot!("envelope_hello", json);
@@ -777,6 +781,7 @@ fn envelope_event_all_kinds() {
         let env = Envelope::Event {
             ref_id: "run-fixed-001".into(),
             event: event.clone(),
+            seq: None,
         };
         insta::assert_json_snapshot!(format!("envelope_event_{name}"), env);
     }
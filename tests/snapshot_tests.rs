@@ -179,6 +179,7 @@ fn full_receipt() -> Receipt {
             git_diff: Some("diff --git a/f.txt b/f.txt".into()),
             git_status: Some("M f.txt".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -238,6 +239,9 @@ fn sample_work_order() -> WorkOrder {
             },
             max_budget_usd: Some(1.0),
             max_turns: Some(25),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     }
 }
@@ -722,6 +726,7 @@ fn snapshot_envelope_event() {
             },
             ext: None,
         },
+        seq: None,
     };
     insta::assert_json_snapshot!(env);
 }
@@ -1081,6 +1086,9 @@ fn snapshot_runtime_config_full() {
         },
         max_budget_usd: Some(5.0),
         max_turns: Some(100),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     insta::assert_json_snapshot!(cfg);
 }
@@ -1306,6 +1314,7 @@ fn snapshot_verification_report_full() {
         git_diff: Some("diff --git a/x.rs b/x.rs\n+added".into()),
         git_status: Some("M x.rs\nA y.rs".into()),
         harness_ok: true,
+        input_digest: None,
     };
     insta::assert_json_snapshot!(v);
 }
@@ -121,6 +121,9 @@ fn kind_name(ev: &AgentEvent) -> &'static str {
         AgentEventKind::ToolResult { .. } => "tool_result",
         AgentEventKind::FileChanged { .. } => "file_changed",
         AgentEventKind::CommandExecuted { .. } => "command_executed",
+        AgentEventKind::Progress { .. } => "progress",
+        AgentEventKind::RunSummary { .. } => "run_summary",
+        AgentEventKind::ArtifactChunk { .. } => "artifact_chunk",
         AgentEventKind::Warning { .. } => "warning",
         AgentEventKind::Error { .. } => "error",
     }
@@ -2396,10 +2399,12 @@ async fn concurrent_stream_ref_id_isolation() {
     let envelope1 = Envelope::Event {
         ref_id: run_id_1.clone(),
         event: make_event(AgentEventKind::AssistantDelta { text: "a".into() }),
+        seq: None,
     };
     let envelope2 = Envelope::Event {
         ref_id: run_id_2.clone(),
         event: make_event(AgentEventKind::AssistantDelta { text: "b".into() }),
+        seq: None,
     };
     let json1 = JsonlCodec::encode(&envelope1).unwrap();
     let json2 = JsonlCodec::encode(&envelope2).unwrap();
@@ -2910,6 +2915,7 @@ async fn metadata_envelope_ref_id_preserved() {
     let envelope = Envelope::Event {
         ref_id: ref_id.clone(),
         event: make_event(AgentEventKind::AssistantDelta { text: "x".into() }),
+        seq: None,
     };
     let json = JsonlCodec::encode(&envelope).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -2972,6 +2978,7 @@ async fn metadata_event_envelope_newline_terminated() {
     let envelope = Envelope::Event {
         ref_id: "r".into(),
         event: make_event(AgentEventKind::AssistantDelta { text: "x".into() }),
+        seq: None,
     };
     let json = JsonlCodec::encode(&envelope).unwrap();
     assert!(json.ends_with('\n'));
@@ -3071,10 +3078,11 @@ async fn dialect_envelope_event_preserves_all_fields() {
     let envelope = Envelope::Event {
         ref_id: "run-abc".into(),
         event: ev,
+        seq: None,
     };
     let json = JsonlCodec::encode(&envelope).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
-    if let Envelope::Event { ref_id, event } = decoded {
+    if let Envelope::Event { ref_id, event, .. } = decoded {
         assert_eq!(ref_id, "run-abc");
         if let AgentEventKind::ToolCall {
             tool_name,
@@ -3110,6 +3118,7 @@ async fn dialect_stream_encode_decode_sequence() {
         let env = Envelope::Event {
             ref_id: run_id.into(),
             event: ev.clone(),
+            seq: None,
         };
         encoded.push_str(&JsonlCodec::encode(&env).unwrap());
     }
@@ -3145,6 +3154,7 @@ async fn dialect_passthrough_ext_preserved() {
     let envelope = Envelope::Event {
         ref_id: "r".into(),
         event: ev,
+        seq: None,
     };
     let json = JsonlCodec::encode(&envelope).unwrap();
     let decoded = JsonlCodec::decode(json.trim()).unwrap();
@@ -3288,6 +3298,7 @@ async fn dialect_full_protocol_handshake_flow() {
         let env = Envelope::Event {
             ref_id: "run-1".into(),
             event: ev.clone(),
+            seq: None,
         };
         wire.push_str(&JsonlCodec::encode(&env).unwrap());
     }
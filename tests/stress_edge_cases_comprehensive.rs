@@ -129,6 +129,7 @@ fn make_receipt() -> Receipt {
             git_diff: Some("diff --git a/f b/f".into()),
             git_status: Some("M f".into()),
             harness_ok: true,
+            input_digest: None,
         },
         outcome: Outcome::Complete,
         receipt_sha256: None,
@@ -325,6 +326,9 @@ fn size_deeply_nested_json_in_config_vendor() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json = serde_json::to_string(&cfg).unwrap();
     let rt: RuntimeConfig = serde_json::from_str(&json).unwrap();
@@ -460,6 +464,9 @@ fn size_empty_strings_everywhere() {
             env: BTreeMap::new(),
             max_budget_usd: None,
             max_turns: None,
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         },
     };
     let json = serde_json::to_string(&wo).unwrap();
@@ -501,6 +508,7 @@ fn size_empty_arrays_everywhere() {
             git_diff: None,
             git_status: None,
             harness_ok: false,
+            input_digest: None,
         },
         outcome: Outcome::Failed,
         receipt_sha256: None,
@@ -523,6 +531,9 @@ fn size_large_env_map() {
         env,
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json = serde_json::to_string(&cfg).unwrap();
     let rt: RuntimeConfig = serde_json::from_str(&json).unwrap();
@@ -612,6 +623,9 @@ fn unicode_rtl_in_config_values() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json = serde_json::to_string(&cfg).unwrap();
     let rt: RuntimeConfig = serde_json::from_str(&json).unwrap();
@@ -1207,6 +1221,9 @@ fn serde_very_long_key_in_vendor() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json = serde_json::to_string(&cfg).unwrap();
     let rt: RuntimeConfig = serde_json::from_str(&json).unwrap();
@@ -1224,6 +1241,9 @@ fn serde_very_long_value_in_vendor() {
         env: BTreeMap::new(),
         max_budget_usd: None,
         max_turns: None,
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json = serde_json::to_string(&cfg).unwrap();
     let rt: RuntimeConfig = serde_json::from_str(&json).unwrap();
@@ -1376,6 +1396,7 @@ fn serde_envelope_all_variants_roundtrip() {
         Envelope::Event {
             ref_id: run_id.clone(),
             event: make_event(AgentEventKind::AssistantDelta { text: "hi".into() }),
+            seq: None,
         },
         Envelope::Final {
             ref_id: run_id.clone(),
@@ -1896,18 +1917,22 @@ fn protocol_interleaved_envelopes_from_different_runs() {
         Envelope::Event {
             ref_id: run_id_1.into(),
             event: make_event(AgentEventKind::AssistantDelta { text: "r1a".into() }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id_2.into(),
             event: make_event(AgentEventKind::AssistantDelta { text: "r2a".into() }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id_1.into(),
             event: make_event(AgentEventKind::AssistantDelta { text: "r1b".into() }),
+            seq: None,
         },
         Envelope::Event {
             ref_id: run_id_2.into(),
             event: make_event(AgentEventKind::AssistantDelta { text: "r2b".into() }),
+            seq: None,
         },
     ];
     let mut buf = Vec::new();
@@ -695,6 +695,7 @@ fn stress_jsonl_event_envelopes_10000() {
         let env = Envelope::Event {
             ref_id: format!("run-{}", i / 100),
             event: make_delta(&format!("token-{i}")),
+            seq: None,
         };
         let line = JsonlCodec::encode(&env).unwrap();
         buffer.extend_from_slice(line.as_bytes());
@@ -728,6 +729,7 @@ fn stress_jsonl_large_payload_roundtrip() {
     let env = Envelope::Event {
         ref_id: "big-run".into(),
         event: ev,
+        seq: None,
     };
     let line = JsonlCodec::encode(&env).unwrap();
     assert!(line.len() > 50_000);
@@ -1053,6 +1055,7 @@ fn stress_envelope_serde_roundtrip_2000() {
             2 => Envelope::Event {
                 ref_id: format!("run-{i}"),
                 event: make_delta("tok"),
+                seq: None,
             },
             _ => Envelope::Fatal {
                 ref_id: Some(format!("run-{i}")),
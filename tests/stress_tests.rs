@@ -1251,6 +1251,7 @@ fn stress_protocol_event_envelope_with_large_payload() {
     let envelope = Envelope::Event {
         ref_id: "run-1".into(),
         event,
+        seq: None,
     };
     let encoded = JsonlCodec::encode(&envelope).unwrap();
     assert!(encoded.len() > 50_000);
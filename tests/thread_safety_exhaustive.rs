@@ -498,6 +498,7 @@ fn make_envelope() -> Envelope {
         },
         capabilities: BTreeMap::new(),
         mode: ExecutionMode::Mapped,
+        features: None,
     }
 }
 
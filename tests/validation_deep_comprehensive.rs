@@ -1033,6 +1033,7 @@ fn envelope_hello_empty_backend_id_fails() {
         backend: backend_id(""),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert!(err.iter().any(|e| e.path == "backend.id"));
@@ -1045,6 +1046,7 @@ fn envelope_hello_empty_contract_version_fails() {
         backend: backend_id("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert!(
@@ -1060,6 +1062,7 @@ fn envelope_hello_invalid_contract_version_format() {
         backend: backend_id("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert!(
@@ -1075,6 +1078,7 @@ fn envelope_hello_whitespace_backend_fails() {
         backend: backend_id("  "),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert!(err.iter().any(|e| e.path == "backend.id"));
@@ -1139,6 +1143,7 @@ fn envelope_event_valid_passes() {
     let env = Envelope::Event {
         ref_id: "run-1".into(),
         event: make_event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+        seq: None,
     };
     assert!(EnvelopeValidator.validate(&env).is_ok());
 }
@@ -1148,6 +1153,7 @@ fn envelope_event_empty_ref_id_fails() {
     let env = Envelope::Event {
         ref_id: "".into(),
         event: make_event(AgentEventKind::AssistantMessage { text: "hi".into() }),
+        seq: None,
     };
     let err = EnvelopeValidator.validate(&env).unwrap_err();
     assert!(err.iter().any(|e| e.path == "ref_id"));
@@ -1422,6 +1428,7 @@ fn hello_version_incompatible_major_fails() {
         backend: backend_id("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     let err = validate_hello_version(&env).unwrap_err();
     assert!(
@@ -1438,6 +1445,7 @@ fn hello_version_same_major_different_minor_passes() {
         backend: backend_id("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     assert!(validate_hello_version(&env).is_ok());
 }
@@ -1460,6 +1468,7 @@ fn hello_version_non_abp_prefix_fails() {
         backend: backend_id("test"),
         capabilities: CapabilityManifest::new(),
         mode: ExecutionMode::default(),
+        features: None,
     };
     // "xyz/v0.1" doesn't strip "abp/v", so theirs is None while ours is Some("0")
     let result = validate_hello_version(&env);
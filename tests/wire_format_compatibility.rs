@@ -68,6 +68,7 @@ fn parse_hello_from_node_sidecar() {
             backend,
             capabilities,
             mode,
+            ..
         } => {
             assert_eq!(contract_version, "abp/v0.1");
             assert_eq!(backend.id, "example_node_sidecar");
@@ -92,6 +93,7 @@ fn parse_hello_from_python_sidecar() {
             backend,
             capabilities,
             mode,
+            ..
         } => {
             assert_eq!(contract_version, "abp/v0.1");
             assert_eq!(backend.id, "python_sidecar");
@@ -108,7 +110,7 @@ fn parse_event_assistant_message() {
     let json = r#"{"t":"event","ref_id":"run-1","event":{"ts":"2025-01-01T00:00:00Z","type":"assistant_message","text":"Hello from sidecar"}}"#;
     let env = must_decode(json);
     match env {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             assert!(matches!(
                 event.kind,
@@ -124,7 +126,7 @@ fn parse_event_run_started() {
     let json = r#"{"t":"event","ref_id":"run-1","event":{"ts":"2025-01-01T00:00:00Z","type":"run_started","message":"node sidecar starting: fix bug"}}"#;
     let env = must_decode(json);
     match env {
-        Envelope::Event { ref_id, event } => {
+        Envelope::Event { ref_id, event, .. } => {
             assert_eq!(ref_id, "run-1");
             assert!(
                 matches!(event.kind, AgentEventKind::RunStarted { ref message } if message.contains("sidecar"))
@@ -282,6 +282,9 @@ fn roundtrip_stability_pretty_vs_compact() {
             model: Some("gpt-4".into()),
             max_turns: Some(10),
             max_budget_usd: Some(1.5),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .build();
     // Pretty → deserialize → compact == compact from original
@@ -564,6 +567,9 @@ fn canonical_json_same_for_clone() {
             model: Some("gpt-4".into()),
             max_turns: Some(10),
             max_budget_usd: Some(1.0),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .build();
     let cloned = wo.clone();
@@ -991,6 +997,9 @@ fn clone_deep_vendor_equality() {
             model: Some("m".into()),
             max_turns: Some(5),
             max_budget_usd: Some(0.5),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .build();
     let c = wo.clone();
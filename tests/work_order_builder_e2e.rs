@@ -100,6 +100,9 @@ fn full_runtime_config() -> RuntimeConfig {
         env,
         max_budget_usd: Some(5.0),
         max_turns: Some(20),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     }
 }
 
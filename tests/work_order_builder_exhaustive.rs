@@ -56,6 +56,9 @@ fn sample_runtime_config() -> RuntimeConfig {
         },
         max_budget_usd: Some(1.50),
         max_turns: Some(10),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     }
 }
 
@@ -96,6 +99,7 @@ fn sample_verification() -> VerificationReport {
         git_diff: Some("+hello".into()),
         git_status: Some("M src/main.rs".into()),
         harness_ok: true,
+        input_digest: None,
     }
 }
 
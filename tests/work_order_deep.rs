@@ -99,6 +99,9 @@ fn maximal_wo() -> WorkOrder {
             env,
             max_budget_usd: Some(5.0),
             max_turns: Some(50),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .build()
 }
@@ -1878,6 +1881,9 @@ fn runtime_config_serde_roundtrip() {
         env,
         max_budget_usd: Some(9.99),
         max_turns: Some(100),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json = serde_json::to_string(&cfg).unwrap();
     let cfg2: RuntimeConfig = serde_json::from_str(&json).unwrap();
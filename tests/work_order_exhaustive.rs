@@ -98,6 +98,9 @@ fn full() -> WorkOrder {
             env,
             max_budget_usd: Some(5.0),
             max_turns: Some(20),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .build()
 }
@@ -1449,6 +1452,9 @@ fn runtime_config_serde_roundtrip() {
         env,
         max_budget_usd: Some(2.0),
         max_turns: Some(8),
+        response_language: None,
+        fidelity_policy: None,
+        submitter: None,
     };
     let json = serde_json::to_string(&cfg).unwrap();
     let back: RuntimeConfig = serde_json::from_str(&json).unwrap();
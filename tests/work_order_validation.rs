@@ -98,6 +98,9 @@ fn maximal_wo() -> WorkOrder {
             env,
             max_budget_usd: Some(5.0),
             max_turns: Some(50),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .build()
 }
@@ -306,6 +309,9 @@ fn builder_full_runtime_config() {
             env,
             max_budget_usd: Some(1.0),
             max_turns: Some(5),
+            response_language: None,
+            fidelity_policy: None,
+            submitter: None,
         })
         .build();
     assert_eq!(wo.config.model.as_deref(), Some("m"));
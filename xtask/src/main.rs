@@ -21,6 +21,12 @@ enum Command {
         #[arg(long, default_value = "contracts/schemas")]
         out_dir: PathBuf,
     },
+    /// Generate the machine-readable error code catalogue.
+    ErrorCodes {
+        /// Output file.
+        #[arg(long, default_value = "contracts/error_codes.json")]
+        out: PathBuf,
+    },
     /// Run full CI checks locally (fmt, clippy, test, doc-test).
     Check,
     /// Print instructions for running code coverage with tarpaulin.
@@ -65,6 +71,7 @@ fn main() -> Result<()> {
     warn_if_hooks_missing();
     match cli.command {
         Command::Schema { out_dir } => schema(out_dir),
+        Command::ErrorCodes { out } => error_codes(out),
         Command::Check => check(),
         Command::Coverage => coverage(),
         Command::Lint => lint(),
@@ -102,6 +109,18 @@ fn write_schema(path: &PathBuf, schema: &schemars::Schema) -> Result<()> {
     Ok(())
 }
 
+// ── error codes ──────────────────────────────────────────────────
+
+fn error_codes(out: PathBuf) -> Result<()> {
+    if let Some(dir) = out.parent() {
+        std::fs::create_dir_all(dir).context("create error code output dir")?;
+    }
+    std::fs::write(&out, abp_error_taxonomy::catalog::catalog_json())
+        .with_context(|| format!("write {}", out.display()))?;
+    eprintln!("wrote error code catalogue to {}", out.display());
+    Ok(())
+}
+
 // ── check ────────────────────────────────────────────────────────────
 
 fn run_cargo(args: &[&str]) -> Result<()> {